@@ -0,0 +1,252 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::metrics::API_KEY_REQUESTS;
+use aptos_api_types::{AptosError, AptosErrorCode};
+use aptos_config::config::{ApiAuthenticationConfig, ApiScope};
+use aptos_logger::{
+    prelude::{sample, SampleRate},
+    warn,
+};
+use poem::{
+    http::{header::AUTHORIZATION, Method, StatusCode},
+    Endpoint, IntoResponse, Middleware, Request, Result,
+};
+use poem_openapi::payload::Json;
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+    time::Duration,
+};
+
+/// The header the API key is passed in. Alternatively, it can be passed as a bearer token in the
+/// Authorization header.
+pub const API_KEY_HEADER: &str = "x-aptos-api-key";
+
+/// The configured API keys, indexed by the SHA-256 of the key.
+struct ApiKeys {
+    protected_scopes: HashSet<ApiScope>,
+    keys: HashMap<String, (String, HashSet<ApiScope>)>,
+}
+
+/// This middleware checks that requests to the endpoints of the protected scopes carry an API key
+/// granting access to that scope. Requests to other endpoints are passed through.
+pub struct ApiKeyAuth {
+    api_keys: Arc<ApiKeys>,
+}
+
+impl ApiKeyAuth {
+    pub fn new(config: &ApiAuthenticationConfig) -> Self {
+        let keys = config
+            .api_keys
+            .iter()
+            .map(|api_key| {
+                (
+                    api_key.key_sha256.to_lowercase(),
+                    (
+                        api_key.name.clone(),
+                        api_key.scopes.iter().copied().collect(),
+                    ),
+                )
+            })
+            .collect();
+        Self {
+            api_keys: Arc::new(ApiKeys {
+                protected_scopes: config.protected_scopes.iter().copied().collect(),
+                keys,
+            }),
+        }
+    }
+}
+
+impl<E: Endpoint> Middleware<E> for ApiKeyAuth {
+    type Output = ApiKeyAuthEndpoint<E>;
+
+    fn transform(&self, ep: E) -> Self::Output {
+        ApiKeyAuthEndpoint {
+            inner: ep,
+            api_keys: self.api_keys.clone(),
+        }
+    }
+}
+
+/// Endpoint for ApiKeyAuth middleware.
+pub struct ApiKeyAuthEndpoint<E> {
+    inner: E,
+    api_keys: Arc<ApiKeys>,
+}
+
+impl<E: Endpoint> Endpoint for ApiKeyAuthEndpoint<E> {
+    type Output = E::Output;
+
+    async fn call(&self, req: Request) -> Result<Self::Output> {
+        let Some(scope) = request_scope(req.method(), req.uri().path()) else {
+            return self.inner.call(req).await;
+        };
+        if !self.api_keys.protected_scopes.contains(&scope) {
+            return self.inner.call(req).await;
+        }
+
+        let Some(api_key) = request_api_key(&req) else {
+            API_KEY_REQUESTS
+                .with_label_values(&[scope.as_str(), "none", "missing"])
+                .inc();
+            return Err(unauthorized(format!(
+                "{} requires an API key, in the {} header",
+                req.uri().path(),
+                API_KEY_HEADER
+            )));
+        };
+        match self.api_keys.keys.get(&sha256::digest(api_key)) {
+            Some((name, scopes)) if scopes.contains(&scope) => {
+                API_KEY_REQUESTS
+                    .with_label_values(&[scope.as_str(), name, "authorized"])
+                    .inc();
+                self.inner.call(req).await
+            },
+            Some((name, _)) => {
+                API_KEY_REQUESTS
+                    .with_label_values(&[scope.as_str(), name, "forbidden"])
+                    .inc();
+                Err(forbidden(format!(
+                    "The API key doesn't grant access to {}",
+                    req.uri().path()
+                )))
+            },
+            None => {
+                API_KEY_REQUESTS
+                    .with_label_values(&[scope.as_str(), "none", "invalid"])
+                    .inc();
+                sample!(
+                    SampleRate::Duration(Duration::from_secs(10)),
+                    warn!("Invalid API key for request to {}", req.uri().path())
+                );
+                Err(unauthorized("Invalid API key".to_string()))
+            },
+        }
+    }
+}
+
+/// Returns the scope of the endpoint, if it's privileged.
+fn request_scope(method: &Method, path: &str) -> Option<ApiScope> {
+    let path = path.strip_prefix("/v1")?.trim_end_matches('/');
+    match (method, path) {
+        (&Method::POST, "/transactions" | "/transactions/batch") => {
+            Some(ApiScope::SubmitTransactions)
+        },
+        (&Method::POST, "/transactions/simulate") => Some(ApiScope::SimulateTransactions),
+        (&Method::GET, path) if path.starts_with("/transactions/wait_by_hash/") => {
+            Some(ApiScope::Streaming)
+        },
+        (_, "/set_failpoint") => Some(ApiScope::Admin),
+        _ => None,
+    }
+}
+
+fn request_api_key(req: &Request) -> Option<&str> {
+    if let Some(api_key) = req.header(API_KEY_HEADER) {
+        return Some(api_key);
+    }
+    req.headers()
+        .get(AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+}
+
+fn unauthorized(message: String) -> poem::Error {
+    error_response(
+        message,
+        AptosErrorCode::Unauthorized,
+        StatusCode::UNAUTHORIZED,
+    )
+}
+
+fn forbidden(message: String) -> poem::Error {
+    error_response(message, AptosErrorCode::Forbidden, StatusCode::FORBIDDEN)
+}
+
+fn error_response(message: String, error_code: AptosErrorCode, status: StatusCode) -> poem::Error {
+    let mut response = Json(AptosError::new_with_error_code(message, error_code)).into_response();
+    response.set_status(status);
+    poem::Error::from_response(response)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aptos_config::config::ApiKeyConfig;
+    use poem::{endpoint::make_sync, EndpointExt};
+
+    async fn call_status(ep: &impl Endpoint, path: &str, api_key: Option<&str>) -> StatusCode {
+        let mut req = Request::builder().method(Method::POST).uri_str(path);
+        if let Some(api_key) = api_key {
+            req = req.header(API_KEY_HEADER, api_key);
+        }
+        match ep.call(req.finish()).await {
+            Ok(response) => response.into_response().status(),
+            Err(err) => err.into_response().status(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_api_key_auth() {
+        let ep = make_sync(|_| "ok").with(ApiKeyAuth::new(&ApiAuthenticationConfig {
+            protected_scopes: vec![ApiScope::SubmitTransactions, ApiScope::Admin],
+            api_keys: vec![ApiKeyConfig {
+                name: "submitter".to_string(),
+                key_sha256: sha256::digest("key"),
+                scopes: vec![ApiScope::SubmitTransactions],
+            }],
+        }));
+
+        assert_eq!(
+            call_status(&ep, "/v1/transactions", Some("key")).await,
+            StatusCode::OK
+        );
+        assert_eq!(
+            call_status(&ep, "/v1/transactions", None).await,
+            StatusCode::UNAUTHORIZED
+        );
+        assert_eq!(
+            call_status(&ep, "/v1/transactions", Some("other")).await,
+            StatusCode::UNAUTHORIZED
+        );
+        // A valid key without the scope of the endpoint
+        assert_eq!(
+            call_status(&ep, "/v1/set_failpoint", Some("key")).await,
+            StatusCode::FORBIDDEN
+        );
+        // Not a protected scope
+        assert_eq!(
+            call_status(&ep, "/v1/transactions/simulate", None).await,
+            StatusCode::OK
+        );
+    }
+
+    #[test]
+    fn test_request_scope() {
+        assert_eq!(
+            request_scope(&Method::POST, "/v1/transactions"),
+            Some(ApiScope::SubmitTransactions)
+        );
+        assert_eq!(
+            request_scope(&Method::POST, "/v1/transactions/batch/"),
+            Some(ApiScope::SubmitTransactions)
+        );
+        assert_eq!(
+            request_scope(&Method::POST, "/v1/transactions/simulate"),
+            Some(ApiScope::SimulateTransactions)
+        );
+        assert_eq!(
+            request_scope(&Method::GET, "/v1/transactions/wait_by_hash/0x1"),
+            Some(ApiScope::Streaming)
+        );
+        assert_eq!(
+            request_scope(&Method::GET, "/v1/set_failpoint"),
+            Some(ApiScope::Admin)
+        );
+        assert_eq!(request_scope(&Method::GET, "/v1/transactions"), None);
+        assert_eq!(request_scope(&Method::GET, "/v1/accounts/0x1"), None);
+        assert_eq!(request_scope(&Method::GET, "/"), None);
+    }
+}
@@ -8,7 +8,7 @@ use crate::{
     response::{BasicResponse, BasicResponseStatus, BasicResultWith404},
     ApiTags,
 };
-use aptos_api_types::{BcsBlock, Block, LedgerInfo};
+use aptos_api_types::{BcsBlock, Block, BlockTimestampSearch, LedgerInfo};
 use poem_openapi::{
     param::{Path, Query},
     OpenApi,
@@ -102,6 +102,55 @@ impl BlocksApi {
         })
         .await
     }
+
+    /// Get blocks by timestamp
+    ///
+    /// This endpoint allows you to get the transactions in a block
+    /// and the corresponding block information given a timestamp.
+    ///
+    /// By default, the latest block started at or before the timestamp is returned, which
+    /// is the block the ledger was in at that time. Set `search` to `closest_after` to get the
+    /// earliest block started at or after the timestamp instead.
+    ///
+    /// Transactions are limited by max default transactions size.  If not all transactions
+    /// are present, the user will need to query for the rest of the transactions via the
+    /// get transactions API.
+    ///
+    /// If the block has been pruned, it will return a 410
+    #[oai(
+        path = "/blocks/by_timestamp/:timestamp",
+        method = "get",
+        operation_id = "get_block_by_timestamp",
+        tag = "ApiTags::Blocks"
+    )]
+    async fn get_block_by_timestamp(
+        &self,
+        accept_type: AcceptType,
+        /// Timestamp to lookup the block for, in microseconds since the Unix epoch
+        timestamp: Path<u64>,
+        /// Which block to return if no block started exactly at the timestamp
+        ///
+        /// If not provided, defaults to `closest_before`
+        search: Query<Option<BlockTimestampSearch>>,
+        /// If set to true, include all transactions in the block
+        ///
+        /// If not provided, no transactions will be retrieved
+        with_transactions: Query<Option<bool>>,
+    ) -> BasicResultWith404<Block> {
+        fail_point_poem("endpoint_get_block_by_timestamp")?;
+        self.context
+            .check_api_output_enabled("Get block by timestamp", &accept_type)?;
+        let api = self.clone();
+        api_spawn_blocking(move || {
+            api.get_by_timestamp(
+                accept_type,
+                timestamp.0,
+                search.0.unwrap_or(BlockTimestampSearch::ClosestBefore),
+                with_transactions.0.unwrap_or_default(),
+            )
+        })
+        .await
+    }
 }
 
 impl BlocksApi {
@@ -135,6 +184,24 @@ impl BlocksApi {
         self.render_bcs_block(&accept_type, latest_ledger_info, bcs_block)
     }
 
+    fn get_by_timestamp(
+        &self,
+        accept_type: AcceptType,
+        timestamp_usecs: u64,
+        search: BlockTimestampSearch,
+        with_transactions: bool,
+    ) -> BasicResultWith404<Block> {
+        let latest_ledger_info = self.context.get_latest_ledger_info()?;
+        let bcs_block = self.context.get_block_by_timestamp(
+            timestamp_usecs,
+            search,
+            &latest_ledger_info,
+            with_transactions,
+        )?;
+
+        self.render_bcs_block(&accept_type, latest_ledger_info, bcs_block)
+    }
+
     /// Renders a [`BcsBlock`] into a [`Block`] if it's a JSON accept type
     fn render_bcs_block(
         &self,
@@ -153,6 +220,9 @@ impl BlocksApi {
                 } else {
                     None
                 };
+                let execution_stats = self
+                    .context
+                    .get_block_execution_stats(&latest_ledger_info, bcs_block.first_version)?;
                 let block = Block {
                     block_height: bcs_block.block_height.into(),
                     block_hash: bcs_block.block_hash.into(),
@@ -160,6 +230,7 @@ impl BlocksApi {
                     first_version: bcs_block.first_version.into(),
                     last_version: bcs_block.last_version.into(),
                     transactions,
+                    execution_stats,
                 };
                 BasicResponse::try_from_json((block, &latest_ledger_info, BasicResponseStatus::Ok))
             },
@@ -5,18 +5,24 @@
 use crate::{
     accept_type::AcceptType,
     metrics,
+    module_cache::ModuleMetadataCache,
+    network::NetworkHealthCollector,
     response::{
-        bcs_api_disabled, block_not_found_by_height, block_not_found_by_version,
-        block_pruned_by_height, json_api_disabled, version_not_found, version_pruned,
-        ForbiddenError, InternalError, NotFoundError, ServiceUnavailableError, StdApiError,
+        bcs_api_disabled, block_not_found_by_height, block_not_found_by_timestamp,
+        block_not_found_by_version, block_pruned_by_height, json_api_disabled, version_not_found,
+        version_pruned, ForbiddenError, InternalError, NotFoundError, ServiceUnavailableError,
+        StdApiError,
     },
 };
 use anyhow::{anyhow, bail, ensure, format_err, Context as AnyhowContext, Result};
 use aptos_api_types::{
-    transaction::ReplayProtector, AptosErrorCode, AsConverter, BcsBlock, GasEstimation, LedgerInfo,
+    transaction::ReplayProtector, AptosErrorCode, AsConverter, BcsBlock, BlockExecutionStats,
+    BlockTimestampSearch, EntryFunctionGasEstimation, EntryFunctionId, GasEstimation, LedgerInfo,
     ResourceGroup, TransactionOnChainData, TransactionSummary,
 };
-use aptos_config::config::{GasEstimationConfig, NodeConfig, RoleType};
+use aptos_config::config::{
+    reloadable_config, GasEstimationConfig, GasEstimationStrategy, NodeConfig, RoleType,
+};
 use aptos_crypto::HashValue;
 use aptos_gas_schedule::{AptosGasParameters, FromOnChainGasSchedule};
 use aptos_logger::{error, info, Schema};
@@ -39,6 +45,7 @@ use aptos_types::{
     on_chain_config::{
         FeatureFlag, Features, GasSchedule, GasScheduleV2, OnChainConfig, OnChainExecutionConfig,
     },
+    proof::AccumulatorConsistencyProof,
     state_store::{
         state_key::{inner::StateKeyInner, prefix::StateKeyPrefix, StateKey},
         state_value::StateValue,
@@ -47,7 +54,8 @@ use aptos_types::{
     transaction::{
         block_epilogue::BlockEndInfo,
         use_case::{UseCaseAwareTransaction, UseCaseKey},
-        IndexedTransactionSummary, SignedTransaction, Transaction, TransactionWithProof, Version,
+        IndexedTransactionSummary, SignedTransaction, Transaction, TransactionExecutableRef,
+        TransactionWithProof, Version,
     },
 };
 use futures::{channel::oneshot, SinkExt};
@@ -60,7 +68,7 @@ use move_core_types::{
 use serde::Serialize;
 use std::{
     cmp::Reverse,
-    collections::{BTreeMap, HashMap},
+    collections::{BTreeMap, HashMap, VecDeque},
     ops::{Bound::Included, Deref},
     sync::{
         atomic::{AtomicU64, AtomicUsize, Ordering},
@@ -79,10 +87,13 @@ pub struct Context {
     gas_schedule_cache: Arc<RwLock<GasScheduleCache>>,
     gas_estimation_cache: Arc<RwLock<GasEstimationCache>>,
     gas_limit_cache: Arc<RwLock<GasLimitCache>>,
+    entry_function_gas_stats: Arc<RwLock<EntryFunctionGasStats>>,
     view_function_stats: Arc<FunctionStats>,
     simulate_txn_stats: Arc<FunctionStats>,
     pub indexer_reader: Option<Arc<dyn IndexerReader>>,
     pub wait_for_hash_active_connections: Arc<AtomicUsize>,
+    module_metadata_cache: Arc<ModuleMetadataCache>,
+    network_health_collector: Arc<NetworkHealthCollector>,
 }
 
 impl std::fmt::Debug for Context {
@@ -112,6 +123,12 @@ impl Context {
                 )),
             )
         };
+        let network_health_collector = Arc::new(NetworkHealthCollector::new(
+            node_config.api.network_health_window_samples,
+        ));
+        let module_metadata_cache = Arc::new(ModuleMetadataCache::new(
+            node_config.api.module_metadata_cache_size,
+        ));
         Self {
             chain_id,
             db,
@@ -125,29 +142,49 @@ impl Context {
                 last_updated_epoch: None,
                 last_updated_time: None,
                 estimation: None,
-                min_inclusion_prices: BTreeMap::new(),
+                block_gas_stats: BTreeMap::new(),
             })),
             gas_limit_cache: Arc::new(RwLock::new(GasLimitCache {
                 last_updated_epoch: None,
                 execution_onchain_config: OnChainExecutionConfig::default_if_missing(),
             })),
+            entry_function_gas_stats: Arc::new(RwLock::new(EntryFunctionGasStats {
+                next_version: 0,
+                samples: HashMap::new(),
+            })),
             view_function_stats,
             simulate_txn_stats,
             indexer_reader,
             wait_for_hash_active_connections: Arc::new(AtomicUsize::new(0)),
+            module_metadata_cache,
+            network_health_collector,
         }
     }
 
+    pub fn module_metadata_cache(&self) -> &ModuleMetadataCache {
+        &self.module_metadata_cache
+    }
+
+    pub fn network_health_collector(&self) -> &NetworkHealthCollector {
+        &self.network_health_collector
+    }
+
     pub fn max_transactions_page_size(&self) -> u16 {
-        self.node_config.api.max_transactions_page_size
+        reloadable_config::get()
+            .map(|config| config.api_max_transactions_page_size)
+            .unwrap_or(self.node_config.api.max_transactions_page_size)
     }
 
     pub fn max_events_page_size(&self) -> u16 {
-        self.node_config.api.max_events_page_size
+        reloadable_config::get()
+            .map(|config| config.api_max_events_page_size)
+            .unwrap_or(self.node_config.api.max_events_page_size)
     }
 
     pub fn max_account_resources_page_size(&self) -> u16 {
-        self.node_config.api.max_account_resources_page_size
+        reloadable_config::get()
+            .map(|config| config.api_max_account_resources_page_size)
+            .unwrap_or(self.node_config.api.max_account_resources_page_size)
     }
 
     pub fn max_account_modules_page_size(&self) -> u16 {
@@ -212,7 +249,21 @@ impl Context {
     }
 
     pub fn max_submit_transaction_batch_size(&self) -> usize {
-        self.node_config.api.max_submit_transaction_batch_size
+        reloadable_config::get()
+            .map(|config| config.api_max_submit_transaction_batch_size)
+            .unwrap_or(self.node_config.api.max_submit_transaction_batch_size)
+    }
+
+    /// Returns true iff the transaction is denied by the node's (hot-reloadable) denylist
+    pub fn is_transaction_denylisted(&self, txn: &SignedTransaction) -> bool {
+        match reloadable_config::get() {
+            Some(config) => config.transaction_denylist.denies_transaction(txn),
+            None => self
+                .node_config
+                .transaction_filters
+                .transaction_denylist
+                .denies_transaction(txn),
+        }
     }
 
     pub async fn submit_transaction(&self, txn: SignedTransaction) -> Result<SubmissionStatus> {
@@ -372,6 +423,48 @@ impl Context {
         Ok(self.db.get_latest_ledger_info()?)
     }
 
+    /// Returns the proof that the accumulator at `target_version` extends the one at
+    /// `client_known_version`, along with the root hash of the former.
+    pub fn get_accumulator_consistency_proof<E: StdApiError>(
+        &self,
+        client_known_version: Option<Version>,
+        target_version: Option<Version>,
+    ) -> Result<(LedgerInfo, Version, AccumulatorConsistencyProof, HashValue), E> {
+        let (latest_ledger_info, target_version) =
+            self.get_latest_ledger_info_and_verify_lookup_version(target_version)?;
+        if let Some(client_known_version) = client_known_version {
+            if client_known_version > target_version {
+                return Err(E::bad_request_with_code(
+                    format!(
+                        "Client known version ({}) is higher than the target version ({})",
+                        client_known_version, target_version
+                    ),
+                    AptosErrorCode::InvalidInput,
+                    &latest_ledger_info,
+                ));
+            } else if client_known_version < latest_ledger_info.oldest_ledger_version.0 {
+                return Err(version_pruned(client_known_version, &latest_ledger_info));
+            }
+        }
+
+        let proof = self
+            .db
+            .get_accumulator_consistency_proof(client_known_version, target_version)
+            .context("Failed to retrieve the accumulator consistency proof")
+            .map_err(|err| {
+                E::internal_with_code(err, AptosErrorCode::InternalError, &latest_ledger_info)
+            })?;
+        let target_root_hash = self
+            .db
+            .get_accumulator_root_hash(target_version)
+            .context("Failed to retrieve the accumulator root hash")
+            .map_err(|err| {
+                E::internal_with_code(err, AptosErrorCode::InternalError, &latest_ledger_info)
+            })?;
+
+        Ok((latest_ledger_info, target_version, proof, target_root_hash))
+    }
+
     pub fn get_state_value(&self, state_key: &StateKey, version: u64) -> Result<Option<Vec<u8>>> {
         Ok(self
             .db
@@ -468,6 +561,13 @@ impl Context {
         Ok(kvs)
     }
 
+    /// Returns up to `limit` resources of the account, starting at `prev_state_key`, and the key
+    /// to start the next page at.
+    ///
+    /// The members of resource groups are paginated individually, so that a page never exceeds
+    /// `limit` resources, however large the groups are. The key of a page is always the resource
+    /// key of its first resource: if that resource is a resource group member (i.e., it isn't
+    /// stored at that key), the page starts at that member of its group.
     pub fn get_resources_by_pagination(
         &self,
         address: AccountAddress,
@@ -475,12 +575,33 @@ impl Context {
         version: u64,
         limit: u64,
     ) -> Result<(Vec<(StructTag, Vec<u8>)>, Option<StateKey>)> {
+        let state_view = self.state_view_at_version(version)?;
+        let converter = state_view.as_converter(self.db.clone(), self.indexer_reader.clone());
+
+        // If the page starts at a resource group member, start at its group instead, and skip
+        // the members before it
+        let start_member = prev_state_key.and_then(|key| match key.inner() {
+            StateKeyInner::AccessPath(AccessPath { address: _, path }) => {
+                match Path::try_from(path.as_slice()) {
+                    Ok(Path::Resource(struct_tag)) => converter
+                        .find_resource_group(&struct_tag)
+                        .map(|group_tag| (group_tag, struct_tag)),
+                    _ => None,
+                }
+            },
+            _ => None,
+        });
+        let start_key = match &start_member {
+            Some((group_tag, _)) => Some(StateKey::resource_group(&address, group_tag)),
+            None => prev_state_key.cloned(),
+        };
+
         let account_iter = if !db_sharding_enabled(&self.node_config) {
             Box::new(
                 self.db
                     .get_prefixed_state_value_iterator(
                         &StateKeyPrefix::from(address),
-                        prev_state_key,
+                        start_key.as_ref(),
                         version,
                     )?
                     .map(|item| item.map_err(|err| anyhow!(err.to_string()))),
@@ -491,71 +612,70 @@ impl Context {
                 .ok_or_else(|| format_err!("Indexer reader doesn't exist"))?
                 .get_prefixed_state_value_iterator(
                     &StateKeyPrefix::from(address),
-                    prev_state_key,
+                    start_key.as_ref(),
                     version,
                 )?
         };
-        // TODO: Consider rewriting this to consider resource groups:
-        // * If a resource group is found, expand
-        // * Return Option<Result<(PathType, StructTag, Vec<u8>)>>
-        // * Count resources and only include a resource group if it can completely fit
-        // * Get next_key as the first struct_tag not included
+
+        // Expand the resource groups into their members, along with the key to start a page at
+        // each resource
         let mut resource_iter = account_iter
-            .filter_map(|res| match res {
-                Ok((k, v)) => match k.inner() {
+            .flat_map(|res| -> Vec<Result<(StructTag, Vec<u8>, StateKey)>> {
+                let (k, v) = match res {
+                    Ok(kv) => kv,
+                    Err(e) => return vec![Err(e)],
+                };
+                match k.inner() {
                     StateKeyInner::AccessPath(AccessPath { address: _, path }) => {
                         match Path::try_from(path.as_slice()) {
                             Ok(Path::Resource(struct_tag)) => {
-                                Some(Ok((struct_tag, v.bytes().to_vec())))
-                            }
-                            // TODO: Consider expanding to Path::Resource
-                            Ok(Path::ResourceGroup(struct_tag)) => {
-                                Some(Ok((struct_tag, v.bytes().to_vec())))
-                            }
-                            Ok(Path::Code(_)) => None,
-                            Err(e) => Some(Err(anyhow::Error::from(e))),
+                                vec![Ok((struct_tag, v.bytes().to_vec(), k.clone()))]
+                            },
+                            Ok(Path::ResourceGroup(group_tag)) => {
+                                if !converter.is_resource_group(&group_tag) {
+                                    return vec![Ok((group_tag, v.bytes().to_vec(), k.clone()))];
+                                }
+                                // An error here means a storage invariant has been violated
+                                let group = match bcs::from_bytes::<ResourceGroup>(v.bytes()) {
+                                    Ok(group) => group,
+                                    Err(e) => return vec![Err(e.into())],
+                                };
+                                let first_member = start_member
+                                    .as_ref()
+                                    .filter(|(start_group_tag, _)| *start_group_tag == group_tag)
+                                    .map(|(_, member_tag)| member_tag);
+                                group
+                                    .into_iter()
+                                    .filter(|(tag, _)| {
+                                        first_member.is_none_or(|first| tag >= first)
+                                    })
+                                    .map(|(tag, value)| {
+                                        let key = StateKey::resource(&address, &tag)?;
+                                        Ok((tag, value, key))
+                                    })
+                                    .collect()
+                            },
+                            Ok(Path::Code(_)) => vec![],
+                            Err(e) => vec![Err(anyhow::Error::from(e))],
                         }
-                    }
+                    },
                     _ => {
                         error!("storage prefix scan return inconsistent key ({:?}) with expected key prefix ({:?}).", k, StateKeyPrefix::from(address));
-                        Some(Err(format_err!( "storage prefix scan return inconsistent key ({:?})", k )))
-                    }
-                },
-                Err(e) => Some(Err(e)),
+                        vec![Err(format_err!( "storage prefix scan return inconsistent key ({:?})", k ))]
+                    },
+                }
             })
             .take(limit as usize + 1);
         let kvs = resource_iter
             .by_ref()
             .take(limit as usize)
+            .map(|res| res.map(|(tag, value, _)| (tag, value)))
             .collect::<Result<Vec<(StructTag, Vec<u8>)>>>()?;
 
-        // We should be able to do an unwrap here, otherwise the above db read would fail.
-        let state_view = self.state_view_at_version(version)?;
-        let converter = state_view.as_converter(self.db.clone(), self.indexer_reader.clone());
-
-        // Extract resources from resource groups and flatten into all resources
-        let kvs = kvs
-            .into_iter()
-            .map(|(tag, value)| {
-                if converter.is_resource_group(&tag) {
-                    // An error here means a storage invariant has been violated
-                    bcs::from_bytes::<ResourceGroup>(&value)
-                        .map(|map| map.into_iter().collect::<Vec<_>>())
-                        .map_err(|e| e.into())
-                } else {
-                    Ok(vec![(tag, value)])
-                }
-            })
-            .collect::<Result<Vec<Vec<(StructTag, Vec<u8>)>>>>()?
-            .into_iter()
-            .flatten()
-            .collect();
-
-        let next_key = if let Some((struct_tag, _v)) = resource_iter.next().transpose()? {
-            Some(StateKey::resource(&address, &struct_tag)?)
-        } else {
-            None
-        };
+        let next_key = resource_iter
+            .next()
+            .transpose()?
+            .map(|(_, _, next_key)| next_key);
         Ok((kvs, next_key))
     }
 
@@ -626,6 +746,18 @@ impl Context {
             .map_err(|err| E::internal_with_code(err, AptosErrorCode::InternalError, ledger_info))
     }
 
+    pub fn get_block_execution_stats<E: InternalError>(
+        &self,
+        ledger_info: &LedgerInfo,
+        version: u64,
+    ) -> Result<Option<BlockExecutionStats>, E> {
+        self.db
+            .get_block_stats(version)
+            .context("Failed to retrieve block execution stats")
+            .map(|stats| stats.map(Into::into))
+            .map_err(|err| E::internal_with_code(err, AptosErrorCode::InternalError, ledger_info))
+    }
+
     pub fn get_block_by_height<E: StdApiError>(
         &self,
         height: u64,
@@ -678,6 +810,33 @@ impl Context {
         )
     }
 
+    pub fn get_block_by_timestamp<E: StdApiError>(
+        &self,
+        timestamp_usecs: u64,
+        search: BlockTimestampSearch,
+        latest_ledger_info: &LedgerInfo,
+        with_transactions: bool,
+    ) -> Result<BcsBlock, E> {
+        let (first_version, last_version, new_block_event) = self
+            .db
+            .get_block_by_timestamp(timestamp_usecs, search.into())
+            .map_err(|_| block_not_found_by_timestamp(timestamp_usecs, latest_ledger_info))?;
+        if first_version < latest_ledger_info.oldest_ledger_version.0 {
+            return Err(block_pruned_by_height(
+                new_block_event.height(),
+                latest_ledger_info,
+            ));
+        }
+
+        self.get_block(
+            latest_ledger_info,
+            with_transactions,
+            first_version,
+            last_version,
+            new_block_event,
+        )
+    }
+
     fn get_block<E: StdApiError>(
         &self,
         latest_ledger_info: &LedgerInfo,
@@ -1133,6 +1292,8 @@ impl Context {
             deprioritized_gas_estimate: Some(min_gas_unit_price),
             gas_estimate: min_gas_unit_price,
             prioritized_gas_estimate: Some(self.next_bucket(min_gas_unit_price)),
+            gas_estimate_lower_bound: None,
+            gas_estimate_upper_bound: None,
         }
     }
 
@@ -1223,14 +1384,15 @@ impl Context {
         Ok((gas_prices, block_end_infos, majority_use_case_fraction))
     }
 
-    fn block_min_inclusion_price(
+    fn block_gas_stats(
         &self,
         ledger_info: &LedgerInfo,
         first: Version,
         last: Version,
+        min_gas_unit_price: u64,
         gas_estimation_config: &GasEstimationConfig,
         execution_config: &OnChainExecutionConfig,
-    ) -> Option<u64> {
+    ) -> BlockGasStats {
         let user_use_case_spread_factor = if gas_estimation_config.incorporate_reordering_effects {
             execution_config
                 .transaction_shuffler_type()
@@ -1246,6 +1408,19 @@ impl Context {
             user_use_case_spread_factor.is_some(),
         ) {
             Ok((prices_and_used, block_end_infos, majority_use_case_fraction)) => {
+                let block_gas_limit = execution_config.block_gas_limit_type().block_gas_limit();
+                let gas_used = match block_end_infos.first() {
+                    Some(block_end_info) => block_end_info.block_effective_gas_units(),
+                    None => prices_and_used.iter().map(|(_, used)| *used).sum::<u64>(),
+                };
+                let txns_fullness_percent = (prices_and_used.len() * 100)
+                    .checked_div(gas_estimation_config.full_block_txns)
+                    .unwrap_or(0) as u64;
+                let gas_fullness_percent = block_gas_limit
+                    .and_then(|limit| gas_used.saturating_mul(100).checked_div(limit))
+                    .unwrap_or(0);
+                let fullness_percent = txns_fullness_percent.max(gas_fullness_percent).min(100);
+
                 let is_full_block =
                     if majority_use_case_fraction.is_some_and(|fraction| fraction > 0.5) {
                         // If majority use case is above half of transactions, UseCaseAware block reordering
@@ -1256,30 +1431,34 @@ impl Context {
                     } else if !block_end_infos.is_empty() {
                         assert_eq!(1, block_end_infos.len());
                         block_end_infos.first().unwrap().limit_reached()
-                    } else if let Some(block_gas_limit) =
-                        execution_config.block_gas_limit_type().block_gas_limit()
-                    {
-                        let gas_used = prices_and_used.iter().map(|(_, used)| *used).sum::<u64>();
+                    } else if let Some(block_gas_limit) = block_gas_limit {
                         gas_used >= block_gas_limit
                     } else {
                         false
                     };
 
-                if is_full_block {
-                    Some(
-                        self.next_bucket(
-                            prices_and_used
-                                .iter()
-                                .map(|(price, _)| *price)
-                                .min()
-                                .unwrap(),
-                        ),
+                let min_inclusion_price = if is_full_block {
+                    self.next_bucket(
+                        prices_and_used
+                            .iter()
+                            .map(|(price, _)| *price)
+                            .min()
+                            .unwrap(),
                     )
                 } else {
-                    None
+                    min_gas_unit_price
+                };
+                BlockGasStats {
+                    min_inclusion_price,
+                    fullness_percent,
+                    limit_reached: is_full_block,
                 }
             },
-            Err(_) => None,
+            Err(_) => BlockGasStats {
+                min_inclusion_price: min_gas_unit_price,
+                fullness_percent: 0,
+                limit_reached: false,
+            },
         }
     }
 
@@ -1298,6 +1477,8 @@ impl Context {
                 deprioritized_gas_estimate: Some(static_override.low),
                 gas_estimate: static_override.market,
                 prioritized_gas_estimate: Some(static_override.aggressive),
+                gas_estimate_lower_bound: None,
+                gas_estimate_upper_bound: None,
             });
         }
 
@@ -1319,7 +1500,7 @@ impl Context {
         // Clear the cache if the epoch has changed
         if let Some(cached_epoch) = cache.last_updated_epoch {
             if cached_epoch != epoch {
-                cache.min_inclusion_prices.clear();
+                cache.block_gas_stats.clear();
             }
         }
 
@@ -1335,10 +1516,7 @@ impl Context {
         }
         let mut cached_blocks_hit = false;
         for _i in 0..max_block_history {
-            if cache
-                .min_inclusion_prices
-                .contains_key(&(epoch, lookup_version))
-            {
+            if cache.block_gas_stats.contains_key(&(epoch, lookup_version)) {
                 cached_blocks_hit = true;
                 break;
             }
@@ -1366,28 +1544,35 @@ impl Context {
         let blocks_len = blocks.len();
         let remaining = max_block_history - blocks_len;
 
-        // 2. Get gas prices per block
-        let mut min_inclusion_prices = vec![];
+        // 2. Get gas prices and fullness per block
+        let mut block_gas_stats = vec![];
         // TODO: if multiple calls to db is a perf issue, combine into a single call and then split
         for (first, last) in blocks {
-            let min_inclusion_price = self
-                .block_min_inclusion_price(ledger_info, first, last, config, &execution_config)
-                .unwrap_or(min_gas_unit_price);
-            min_inclusion_prices.push(min_inclusion_price);
-            cache
-                .min_inclusion_prices
-                .insert((epoch, last), min_inclusion_price);
+            let stats = self.block_gas_stats(
+                ledger_info,
+                first,
+                last,
+                min_gas_unit_price,
+                config,
+                &execution_config,
+            );
+            block_gas_stats.push(stats);
+            cache.block_gas_stats.insert((epoch, last), stats);
         }
         if cached_blocks_hit {
             for (_, v) in cache
-                .min_inclusion_prices
+                .block_gas_stats
                 .range((Included(&(epoch, 0)), Included(&(epoch, lookup_version))))
                 .rev()
                 .take(remaining)
             {
-                min_inclusion_prices.push(*v);
+                block_gas_stats.push(*v);
             }
         }
+        let mut min_inclusion_prices: Vec<_> = block_gas_stats
+            .iter()
+            .map(|stats| stats.min_inclusion_price)
+            .collect();
 
         // 3. Get values
         // (1) low
@@ -1436,25 +1621,150 @@ impl Context {
             },
             Some(price) => market_price.max(*price),
         };
+
+        // (4) confidence interval around market
+        let tail_percent = (100 - config.confidence_interval_percent) / 2;
+        let lower_bound = percentile(&latest_prices, tail_percent).min(market_price);
+        let upper_bound = percentile(&latest_prices, 100 - tail_percent).max(market_price);
+
+        // (5) raise the market and aggressive estimates (and the interval) based on how full
+        // recent blocks were, if the block fullness strategy is used
+        let adjustment_percent = match config.strategy {
+            GasEstimationStrategy::Percentile => 0,
+            GasEstimationStrategy::BlockFullness => block_fullness_adjustment_percent(
+                &block_gas_stats[..latest_prices.len()],
+                config.target_block_fullness_percent,
+                config.max_block_fullness_adjustment_percent,
+            ),
+        };
+        let adjust = |price: u64| price.saturating_mul(100 + adjustment_percent) / 100;
+
         // round up to next bucket
-        let aggressive_price = self.next_bucket(p90_price);
+        let aggressive_price = self.next_bucket(adjust(p90_price));
 
         let estimation = GasEstimation {
             deprioritized_gas_estimate: Some(low_price),
-            gas_estimate: market_price,
+            gas_estimate: adjust(market_price),
             prioritized_gas_estimate: Some(aggressive_price),
+            gas_estimate_lower_bound: Some(adjust(lower_bound)),
+            gas_estimate_upper_bound: Some(adjust(upper_bound)),
         };
         // 4. Update cache
         // GC old entries
-        if cache.min_inclusion_prices.len() > max_block_history {
-            for _i in max_block_history..cache.min_inclusion_prices.len() {
-                cache.min_inclusion_prices.pop_first();
+        if cache.block_gas_stats.len() > max_block_history {
+            for _i in max_block_history..cache.block_gas_stats.len() {
+                cache.block_gas_stats.pop_first();
             }
         }
         self.update_cached_gas_estimation(&mut cache, epoch, estimation);
         Ok(estimation)
     }
 
+    /// Records the gas unit price and gas used of every entry function call committed since the
+    /// last update, reading at most `entry_function_history_txns` of the latest transactions.
+    fn update_entry_function_gas_stats(
+        &self,
+        stats: &mut EntryFunctionGasStats,
+        ledger_version: Version,
+        config: &GasEstimationConfig,
+    ) {
+        let history_start_version =
+            (ledger_version + 1).saturating_sub(config.entry_function_history_txns);
+        let start_version = stats.next_version.max(history_start_version);
+        if start_version > ledger_version {
+            return;
+        }
+
+        // This is just an estimation, so we can just skip over errors
+        let limit = ledger_version - start_version + 1;
+        let (Ok(txns), Ok(infos)) = (
+            self.db.get_transaction_iterator(start_version, limit),
+            self.db.get_transaction_info_iterator(start_version, limit),
+        ) else {
+            return;
+        };
+        for (version, (txn, info)) in (start_version..).zip(txns.zip(infos)) {
+            if let (Ok(Transaction::UserTransaction(txn)), Ok(info)) = (txn, info) {
+                if let Ok(TransactionExecutableRef::EntryFunction(entry_function)) =
+                    txn.executable_ref()
+                {
+                    stats.record(
+                        (
+                            entry_function.module().clone(),
+                            entry_function.function().to_owned(),
+                        ),
+                        version,
+                        txn.gas_unit_price(),
+                        info.gas_used(),
+                        config,
+                    );
+                }
+            }
+        }
+        stats.next_version = ledger_version + 1;
+    }
+
+    /// Estimates the gas unit price and max gas amount for calling the given entry function,
+    /// based on its recently committed calls. Falls back to the global gas price estimation
+    /// if there are no recent calls.
+    pub fn estimate_entry_function_gas<E: InternalError>(
+        &self,
+        ledger_info: &LedgerInfo,
+        function: &EntryFunctionId,
+    ) -> Result<EntryFunctionGasEstimation, E> {
+        let config = &self.node_config.api.gas_estimation;
+        let gas_estimation = self.estimate_gas_price::<E>(ledger_info)?;
+
+        let samples = if config.enabled && config.static_override.is_none() {
+            let mut stats = self.entry_function_gas_stats.write().unwrap();
+            self.update_entry_function_gas_stats(&mut stats, ledger_info.ledger_version.0, config);
+            let key = (
+                ModuleId::from(function.module.clone()),
+                function.name.0.clone(),
+            );
+            stats
+                .samples
+                .get(&key)
+                .map(|samples| samples.samples.iter().cloned().collect::<Vec<_>>())
+                .unwrap_or_default()
+        } else {
+            vec![]
+        };
+        if samples.is_empty() {
+            let prioritized_gas_estimate = gas_estimation
+                .prioritized_gas_estimate
+                .unwrap_or(gas_estimation.gas_estimate);
+            return Ok(EntryFunctionGasEstimation {
+                function: function.clone(),
+                sample_size: 0,
+                max_gas_amount: None,
+                gas_unit_price_p50: gas_estimation.gas_estimate,
+                gas_unit_price_p90: prioritized_gas_estimate,
+                gas_unit_price_p99: prioritized_gas_estimate,
+            });
+        }
+
+        let min_gas_unit_price = self.min_gas_unit_price(ledger_info)?;
+        let mut gas_unit_prices: Vec<_> = samples.iter().map(|(price, _)| *price).collect();
+        gas_unit_prices.sort();
+        let mut gas_used: Vec<_> = samples.iter().map(|(_, used)| *used).collect();
+        gas_used.sort();
+        let percentile =
+            |sorted: &[u64], percentile: usize| sorted[sorted.len() * percentile / 100];
+
+        let max_gas_amount = percentile(&gas_used, 99)
+            .saturating_mul(100 + config.entry_function_max_gas_margin_percent)
+            / 100;
+        Ok(EntryFunctionGasEstimation {
+            function: function.clone(),
+            sample_size: samples.len() as u64,
+            max_gas_amount: Some(max_gas_amount.max(1)),
+            gas_unit_price_p50: percentile(&gas_unit_prices, 50).max(min_gas_unit_price),
+            gas_unit_price_p90: percentile(&gas_unit_prices, 90).max(min_gas_unit_price),
+            gas_unit_price_p99: percentile(&gas_unit_prices, 99).max(min_gas_unit_price),
+        })
+    }
+
     fn min_gas_unit_price<E: InternalError>(&self, ledger_info: &LedgerInfo) -> Result<u64, E> {
         let (_, gas_schedule) = self.get_gas_schedule(ledger_info)?;
         Ok(gas_schedule.vm.txn.min_price_per_gas_unit.into())
@@ -1610,7 +1920,7 @@ impl Context {
         self.gas_estimation_cache
             .read()
             .unwrap()
-            .min_inclusion_prices
+            .block_gas_stats
             .len()
     }
 
@@ -1632,8 +1942,56 @@ pub struct GasEstimationCache {
     last_updated_epoch: Option<u64>,
     last_updated_time: Option<Instant>,
     estimation: Option<GasEstimation>,
-    /// (epoch, lookup_version) -> min_inclusion_price
-    min_inclusion_prices: BTreeMap<(u64, u64), u64>,
+    /// (epoch, lookup_version) -> block gas stats
+    block_gas_stats: BTreeMap<(u64, u64), BlockGasStats>,
+}
+
+/// The gas statistics of a single block, used for gas estimation
+#[derive(Clone, Copy)]
+struct BlockGasStats {
+    /// The minimum gas unit price needed for inclusion in the block
+    min_inclusion_price: u64,
+    /// How full the block was, in percent of its transaction count or gas limit
+    fullness_percent: u64,
+    /// Whether the block was classified as full
+    limit_reached: bool,
+}
+
+/// Returns the given percentile of the (sorted, non-empty) prices
+fn percentile(sorted_prices: &[u64], percent: u64) -> u64 {
+    let index = sorted_prices.len() * percent as usize / 100;
+    sorted_prices[index.min(sorted_prices.len() - 1)]
+}
+
+/// Returns how much (in percent) to raise the estimates given the recent blocks. The pressure is
+/// the higher of the average block fullness and the frequency of full blocks. Above the target,
+/// the adjustment grows linearly with the pressure, up to the maximum when all blocks are full.
+fn block_fullness_adjustment_percent(
+    block_gas_stats: &[BlockGasStats],
+    target_fullness_percent: u64,
+    max_adjustment_percent: u64,
+) -> u64 {
+    let num_blocks = block_gas_stats.len() as u64;
+    if num_blocks == 0 {
+        return 0;
+    }
+    let average_fullness_percent = block_gas_stats
+        .iter()
+        .map(|stats| stats.fullness_percent)
+        .sum::<u64>()
+        / num_blocks;
+    let limit_reached_percent = block_gas_stats
+        .iter()
+        .filter(|stats| stats.limit_reached)
+        .count() as u64
+        * 100
+        / num_blocks;
+    let pressure_percent = average_fullness_percent.max(limit_reached_percent);
+    if pressure_percent <= target_fullness_percent {
+        return 0;
+    }
+    (pressure_percent - target_fullness_percent) * max_adjustment_percent
+        / (100 - target_fullness_percent)
 }
 
 pub struct GasLimitCache {
@@ -1641,6 +1999,61 @@ pub struct GasLimitCache {
     execution_onchain_config: OnChainExecutionConfig,
 }
 
+pub struct EntryFunctionGasStats {
+    /// The next version to read when updating the stats
+    next_version: Version,
+    /// (module, function) -> recent calls
+    samples: HashMap<(ModuleId, Identifier), EntryFunctionGasSamples>,
+}
+
+pub struct EntryFunctionGasSamples {
+    /// The version of the latest recorded call
+    last_version: Version,
+    /// (gas_unit_price, gas_used) of the latest calls, oldest first
+    samples: VecDeque<(u64, u64)>,
+}
+
+impl EntryFunctionGasStats {
+    fn record(
+        &mut self,
+        function: (ModuleId, Identifier),
+        version: Version,
+        gas_unit_price: u64,
+        gas_used: u64,
+        config: &GasEstimationConfig,
+    ) {
+        // Evict the least recently called entry function if a new one needs to be tracked
+        if !self.samples.contains_key(&function)
+            && self.samples.len() >= config.entry_function_max_tracked
+        {
+            let least_recent = self
+                .samples
+                .iter()
+                .min_by_key(|(_, samples)| samples.last_version)
+                .map(|(function, _)| function.clone());
+            match least_recent {
+                Some(least_recent) => {
+                    self.samples.remove(&least_recent);
+                },
+                None => return,
+            }
+        }
+
+        let samples = self
+            .samples
+            .entry(function)
+            .or_insert_with(|| EntryFunctionGasSamples {
+                last_version: version,
+                samples: VecDeque::new(),
+            });
+        samples.last_version = version;
+        samples.samples.push_back((gas_unit_price, gas_used));
+        while samples.samples.len() > config.entry_function_max_samples {
+            samples.samples.pop_front();
+        }
+    }
+}
+
 /// This function just calls tokio::task::spawn_blocking with the given closure and in
 /// the case of an error when joining the task converts it into a 500.
 pub async fn api_spawn_blocking<F, T, E>(func: F) -> Result<T, E>
@@ -5,14 +5,15 @@
 use crate::{
     accept_type::AcceptType,
     context::{api_spawn_blocking, Context},
-    response::{BasicResponse, BasicResponseStatus, BasicResult},
+    response::{BasicResponse, BasicResponseStatus, BasicResult, BasicResultWith404},
     ApiTags,
 };
-use aptos_api_types::{IndexResponse, IndexResponseBcs};
-use poem_openapi::OpenApi;
+use aptos_api_types::{AccumulatorConsistencyProof, IndexResponse, IndexResponseBcs, U64};
+use poem_openapi::{param::Query, OpenApi};
 use std::sync::Arc;
 
 /// API for the index, to retrieve the ledger information
+#[derive(Clone)]
 pub struct IndexApi {
     pub context: Arc<Context>,
 }
@@ -55,4 +56,61 @@ impl IndexApi {
         })
         .await
     }
+
+    /// Get ledger consistency proof
+    ///
+    /// Get a proof that the transaction accumulator at the target version
+    /// extends the one at the client known version, so that clients can verify
+    /// that the ledger history extends a root hash they know of (e.g. from a
+    /// waypoint) without downloading the transactions in between.
+    ///
+    /// If the client known version is not provided, the proof starts from an
+    /// empty accumulator. If the target version is not provided, it defaults
+    /// to the latest ledger version.
+    #[oai(
+        path = "/ledger/consistency_proof",
+        method = "get",
+        operation_id = "get_ledger_consistency_proof",
+        tag = "ApiTags::General"
+    )]
+    async fn get_ledger_consistency_proof(
+        &self,
+        accept_type: AcceptType,
+        /// Ledger version the client knows the accumulator root hash at
+        client_known_version: Query<Option<U64>>,
+        /// Ledger version to prove the accumulator at
+        target_version: Query<Option<U64>>,
+    ) -> BasicResultWith404<AccumulatorConsistencyProof> {
+        self.context
+            .check_api_output_enabled("Get ledger consistency proof", &accept_type)?;
+        let api = self.clone();
+        api_spawn_blocking(move || {
+            let (latest_ledger_info, target_version, proof, target_root_hash) =
+                api.context.get_accumulator_consistency_proof(
+                    client_known_version.0.map(|v| v.0),
+                    target_version.0.map(|v| v.0),
+                )?;
+            match accept_type {
+                AcceptType::Json => {
+                    let proof = AccumulatorConsistencyProof::new(
+                        client_known_version.0.map(|v| v.0),
+                        target_version,
+                        target_root_hash,
+                        proof,
+                    );
+                    BasicResponse::try_from_json((
+                        proof,
+                        &latest_ledger_info,
+                        BasicResponseStatus::Ok,
+                    ))
+                },
+                AcceptType::Bcs => BasicResponse::try_from_bcs((
+                    proof,
+                    &latest_ledger_info,
+                    BasicResponseStatus::Ok,
+                )),
+            }
+        })
+        .await
+    }
 }
@@ -6,6 +6,7 @@ use poem_openapi::Tags;
 
 mod accept_type;
 mod accounts;
+mod auth;
 mod basic;
 mod bcs_payload;
 mod blocks;
@@ -17,6 +18,8 @@ mod failpoint;
 mod index;
 mod log;
 pub mod metrics;
+pub mod module_cache;
+mod network;
 mod page;
 mod response;
 mod runtime;
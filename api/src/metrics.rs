@@ -49,6 +49,15 @@ pub static RESPONSE_STATUS: Lazy<HistogramVec> = Lazy::new(|| {
     .unwrap()
 });
 
+pub static API_KEY_REQUESTS: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "aptos_api_key_requests",
+        "Number of requests to API key protected endpoints, by scope, API key name and result",
+        &["scope", "key", "result"]
+    )
+    .unwrap()
+});
+
 pub static POST_BODY_BYTES: Lazy<HistogramVec> = Lazy::new(|| {
     register_histogram_vec!(
         "aptos_api_post_body_bytes",
@@ -88,6 +97,15 @@ pub static GAS_USED: Lazy<HistogramVec> = Lazy::new(|| {
     .unwrap()
 });
 
+pub static MODULE_METADATA_CACHE: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "aptos_api_module_metadata_cache",
+        "Lookups of the module metadata cache, grouped by result (hit, miss or invalidated)",
+        &["result"]
+    )
+    .unwrap()
+});
+
 pub static WAIT_TRANSACTION_GAUGE: Lazy<IntGauge> = Lazy::new(|| {
     register_int_gauge!(
         "aptos_api_wait_transaction",
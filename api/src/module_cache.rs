@@ -0,0 +1,203 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::metrics::MODULE_METADATA_CACHE;
+use anyhow::{Context as AnyhowContext, Result};
+use aptos_api_types::{MoveModule, MoveModuleBytecode};
+use aptos_crypto::HashValue;
+use aptos_framework::natives::code::{PackageRegistry, UpgradePolicy};
+use aptos_types::{
+    account_address::AccountAddress,
+    on_chain_config::OnChainConfig,
+    state_store::{state_key::StateKey, TStateView},
+};
+use mini_moka::sync::Cache;
+use move_core_types::identifier::Identifier;
+use std::sync::Arc;
+
+/// Metadata extracted from a published module and the package it belongs to.
+#[derive(Debug)]
+pub struct ModuleMetadata {
+    /// The ABI, if the bytecode could be deserialized.
+    pub abi: Option<MoveModule>,
+    /// The upgrade policy of the package, if the module was published as part of one.
+    pub upgrade_policy: Option<UpgradePolicy>,
+    /// The (compressed) source map of the module, if it was published with the package.
+    pub source_map: Option<Vec<u8>>,
+}
+
+struct CachedModule {
+    /// The hash of the bytecode the metadata was extracted from.
+    bytecode_hash: HashValue,
+    metadata: Arc<ModuleMetadata>,
+}
+
+/// Caches the metadata of published modules, so that it doesn't need to be extracted (i.e., the
+/// module deserialized, and its package looked up) on every request.
+///
+/// Entries are keyed by module, and remember the hash of the bytecode they were extracted from.
+/// An entry is invalidated as soon as it is looked up with different bytecode, i.e., once the
+/// module has been republished with new code (or when looking up an older version of it).
+pub struct ModuleMetadataCache {
+    modules: Option<Cache<(AccountAddress, Identifier), Arc<CachedModule>>>,
+}
+
+impl ModuleMetadataCache {
+    /// Creates a cache holding up to `max_size` modules. A size of 0 disables caching.
+    pub fn new(max_size: u64) -> Self {
+        Self {
+            modules: (max_size > 0).then(|| Cache::new(max_size)),
+        }
+    }
+
+    /// Returns the metadata of the module with the given bytecode, read from `state_view`,
+    /// extracting it if it's not cached.
+    pub fn get_or_extract(
+        &self,
+        state_view: &impl TStateView<Key = StateKey>,
+        address: AccountAddress,
+        name: &Identifier,
+        bytecode: &[u8],
+    ) -> Result<Arc<ModuleMetadata>> {
+        let Some(modules) = &self.modules else {
+            return Ok(Arc::new(extract_metadata(
+                state_view, address, name, bytecode,
+            )?));
+        };
+
+        let key = (address, name.clone());
+        let bytecode_hash = HashValue::sha3_256_of(bytecode);
+        if let Some(cached) = modules.get(&key) {
+            if cached.bytecode_hash == bytecode_hash {
+                MODULE_METADATA_CACHE.with_label_values(&["hit"]).inc();
+                return Ok(cached.metadata.clone());
+            }
+            MODULE_METADATA_CACHE
+                .with_label_values(&["invalidated"])
+                .inc();
+        } else {
+            MODULE_METADATA_CACHE.with_label_values(&["miss"]).inc();
+        }
+
+        let metadata = Arc::new(extract_metadata(state_view, address, name, bytecode)?);
+        modules.insert(
+            key,
+            Arc::new(CachedModule {
+                bytecode_hash,
+                metadata: metadata.clone(),
+            }),
+        );
+        Ok(metadata)
+    }
+}
+
+fn extract_metadata(
+    state_view: &impl TStateView<Key = StateKey>,
+    address: AccountAddress,
+    name: &Identifier,
+    bytecode: &[u8],
+) -> Result<ModuleMetadata> {
+    let abi = MoveModuleBytecode::new(bytecode.to_vec())
+        .try_parse_abi()
+        .context("Failed to parse move module ABI")?
+        .abi;
+
+    let registry_key = StateKey::resource(&address, &PackageRegistry::struct_tag())
+        .context("Failed to build the package registry state key")?;
+    let registry = state_view
+        .get_state_value_bytes(&registry_key)
+        .context("Failed to read the package registry")?
+        .map(|bytes| bcs::from_bytes::<PackageRegistry>(&bytes))
+        .transpose()
+        .context("Failed to deserialize the package registry")?;
+    let package_module = registry
+        .iter()
+        .flat_map(|registry| &registry.packages)
+        .find_map(|package| {
+            package
+                .modules
+                .iter()
+                .find(|module| module.name == name.as_str())
+                .map(|module| (package.upgrade_policy, module))
+        });
+
+    Ok(ModuleMetadata {
+        abi,
+        upgrade_policy: package_module.map(|(upgrade_policy, _)| upgrade_policy),
+        source_map: package_module
+            .map(|(_, module)| module.source_map.clone())
+            .filter(|source_map| !source_map.is_empty()),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aptos_framework::natives::code::{ModuleMetadata as PackageModule, PackageMetadata};
+    use aptos_types::state_store::{state_value::StateValue, MockStateView};
+    use std::collections::HashMap;
+
+    fn package_registry_view(
+        address: AccountAddress,
+        module_name: &str,
+    ) -> MockStateView<StateKey> {
+        let registry = PackageRegistry {
+            packages: vec![PackageMetadata {
+                name: "package".to_string(),
+                upgrade_policy: UpgradePolicy::compat(),
+                upgrade_number: 0,
+                source_digest: String::new(),
+                manifest: vec![],
+                modules: vec![PackageModule {
+                    name: module_name.to_string(),
+                    source: vec![],
+                    source_map: vec![1, 2, 3],
+                    extension: Default::default(),
+                }],
+                deps: vec![],
+                extension: Default::default(),
+            }],
+        };
+        let registry_key = StateKey::resource(&address, &PackageRegistry::struct_tag()).unwrap();
+        MockStateView::new(HashMap::from([(
+            registry_key,
+            StateValue::new_legacy(bcs::to_bytes(&registry).unwrap().into()),
+        )]))
+    }
+
+    #[test]
+    fn test_module_metadata_cache() {
+        let address = AccountAddress::random();
+        let name = Identifier::new("module").unwrap();
+        let state_view = package_registry_view(address, "module");
+        let cache = ModuleMetadataCache::new(10);
+
+        // The bytecode doesn't deserialize, but the package metadata is still extracted
+        let metadata = cache
+            .get_or_extract(&state_view, address, &name, b"bytecode")
+            .unwrap();
+        assert!(metadata.abi.is_none());
+        assert_eq!(metadata.upgrade_policy, Some(UpgradePolicy::compat()));
+        assert_eq!(metadata.source_map, Some(vec![1, 2, 3]));
+
+        // Looking up the same bytecode again hits the cache
+        let cached = cache
+            .get_or_extract(&state_view, address, &name, b"bytecode")
+            .unwrap();
+        assert!(Arc::ptr_eq(&metadata, &cached));
+
+        // Republishing the module invalidates the entry
+        let republished = cache
+            .get_or_extract(&state_view, address, &name, b"new bytecode")
+            .unwrap();
+        assert!(!Arc::ptr_eq(&metadata, &republished));
+
+        // Modules outside of a package have no package metadata
+        let other = Identifier::new("other").unwrap();
+        let metadata = cache
+            .get_or_extract(&state_view, address, &other, b"bytecode")
+            .unwrap();
+        assert!(metadata.upgrade_policy.is_none());
+        assert!(metadata.source_map.is_none());
+    }
+}
@@ -0,0 +1,273 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    accept_type::AcceptType,
+    context::{api_spawn_blocking, Context},
+    response::{bcs_api_disabled, BasicResponse, BasicResponseStatus, BasicResult},
+    ApiTags,
+};
+use aptos_api_types::{CommitLatency, NetworkHealth, StateSyncLag, ValidatorParticipation, U64};
+use poem_openapi::OpenApi;
+use prometheus::proto::{Metric, MetricFamily};
+use std::{
+    collections::VecDeque,
+    sync::{Arc, Mutex},
+};
+
+/// The histogram tracing the time between the proposal of a block and each of its stages
+const BLOCK_TRACING_METRIC: &str = "aptos_consensus_block_tracing";
+const BLOCK_TRACING_COMMITTED_STAGE: &str = "committed";
+/// The gauge holding the versions processed by state sync
+const STATE_SYNC_VERSION_METRIC: &str = "aptos_state_sync_version";
+const STATE_SYNC_SYNCED_TYPE: &str = "synced";
+/// The gauge holding the highest data advertised by the peers
+const HIGHEST_ADVERTISED_DATA_METRIC: &str = "aptos_data_client_highest_advertised_data";
+const HIGHEST_ADVERTISED_TRANSACTIONS_TYPE: &str = "transactions";
+
+/// API for the health of the network, for explorer status pages
+#[derive(Clone)]
+pub struct NetworkApi {
+    pub context: Arc<Context>,
+}
+
+#[OpenApi]
+impl NetworkApi {
+    /// Get network health
+    ///
+    /// Get an aggregated view of the health of the network, as observed by
+    /// this node: the consensus round rate, the commit latency percentiles,
+    /// the validator participation in the latest block and the state sync lag.
+    ///
+    /// The commit latency and state sync lag are computed from the metrics
+    /// sampled over a recent window, and are absent if the node did not
+    /// record them (e.g., commit latency on a fullnode).
+    #[oai(
+        path = "/network/health",
+        method = "get",
+        operation_id = "get_network_health",
+        tag = "ApiTags::General"
+    )]
+    async fn get_network_health(&self, accept_type: AcceptType) -> BasicResult<NetworkHealth> {
+        self.context
+            .check_api_output_enabled("Get network health", &accept_type)?;
+        if accept_type == AcceptType::Bcs {
+            return Err(bcs_api_disabled("Get network health"));
+        }
+        let ledger_info = self.context.get_latest_ledger_info()?;
+
+        let api = self.clone();
+        api_spawn_blocking(move || {
+            let (rounds_per_second, validator_participation) =
+                api.recent_blocks_health(ledger_info.version());
+            let collector = api.context.network_health_collector();
+            let network_health = NetworkHealth {
+                rounds_per_second,
+                commit_latency: collector.commit_latency(),
+                validator_participation,
+                state_sync: collector.state_sync_lag(),
+            };
+            BasicResponse::try_from_json((network_health, &ledger_info, BasicResponseStatus::Ok))
+        })
+        .await
+    }
+}
+
+impl NetworkApi {
+    /// Returns the consensus round rate over the recent blocks of the current epoch, and the
+    /// validator participation in the latest block. This is just for display, so errors
+    /// reading the blocks are skipped.
+    fn recent_blocks_health(
+        &self,
+        ledger_version: u64,
+    ) -> (Option<f64>, Option<ValidatorParticipation>) {
+        let db = &self.context.db;
+        let latest_block = match db.get_block_info_by_version(ledger_version) {
+            Ok((_, _, latest_block)) => latest_block,
+            Err(_) => return (None, None),
+        };
+
+        let history = self.context.node_config.api.network_health_block_history;
+        let rounds_per_second = db
+            .get_block_info_by_height(latest_block.height().saturating_sub(history))
+            .ok()
+            .and_then(|(_, _, earlier_block)| {
+                // Rounds restart with every epoch
+                let elapsed_usecs = latest_block
+                    .proposed_time()
+                    .checked_sub(earlier_block.proposed_time())?;
+                if earlier_block.epoch() != latest_block.epoch() || elapsed_usecs == 0 {
+                    return None;
+                }
+                let rounds = latest_block.round().saturating_sub(earlier_block.round());
+                Some(rounds as f64 * 1_000_000.0 / elapsed_usecs as f64)
+            });
+
+        let votes_bitvec = latest_block.previous_block_votes_bitvec();
+        let validator_participation = ValidatorParticipation {
+            block_height: latest_block.height().into(),
+            round: latest_block.round().into(),
+            proposer: latest_block.proposer().into(),
+            previous_block_votes_bitvec: votes_bitvec.clone().into(),
+            num_votes: votes_bitvec
+                .iter()
+                .map(|byte| byte.count_ones() as u64)
+                .sum::<u64>()
+                .into(),
+            failed_proposer_indices: latest_block
+                .failed_proposer_indices()
+                .iter()
+                .map(|index| U64::from(*index))
+                .collect(),
+        };
+
+        (rounds_per_second, Some(validator_participation))
+    }
+}
+
+/// A sample of the consensus and state sync metrics
+#[derive(Clone, Default)]
+struct MetricsSample {
+    /// The cumulative (upper bound in seconds, count) buckets of the commit latency histogram
+    commit_latency_buckets: Vec<(f64, u64)>,
+    /// The number of commit latency observations (including those above the last bucket)
+    commit_latency_count: u64,
+    synced_version: Option<u64>,
+    highest_advertised_version: Option<u64>,
+}
+
+impl MetricsSample {
+    fn new(metric_families: &[MetricFamily]) -> Self {
+        let mut sample = Self::default();
+        for metric_family in metric_families {
+            match metric_family.get_name() {
+                BLOCK_TRACING_METRIC => {
+                    if let Some(metric) =
+                        find_metric(metric_family, "stage", BLOCK_TRACING_COMMITTED_STAGE)
+                    {
+                        let histogram = metric.get_histogram();
+                        sample.commit_latency_buckets = histogram
+                            .get_bucket()
+                            .iter()
+                            .map(|bucket| (bucket.get_upper_bound(), bucket.get_cumulative_count()))
+                            .collect();
+                        sample.commit_latency_count = histogram.get_sample_count();
+                    }
+                },
+                STATE_SYNC_VERSION_METRIC => {
+                    sample.synced_version =
+                        find_metric(metric_family, "type", STATE_SYNC_SYNCED_TYPE)
+                            .map(|metric| metric.get_gauge().get_value() as u64);
+                },
+                HIGHEST_ADVERTISED_DATA_METRIC => {
+                    sample.highest_advertised_version = find_metric(
+                        metric_family,
+                        "data_type",
+                        HIGHEST_ADVERTISED_TRANSACTIONS_TYPE,
+                    )
+                    .map(|metric| metric.get_gauge().get_value() as u64);
+                },
+                _ => {},
+            }
+        }
+        sample
+    }
+}
+
+/// Returns the metric of the family with the given label value
+fn find_metric<'a>(
+    metric_family: &'a MetricFamily,
+    label_name: &str,
+    label_value: &str,
+) -> Option<&'a Metric> {
+    metric_family.get_metric().iter().find(|metric| {
+        metric
+            .get_label()
+            .iter()
+            .any(|label| label.get_name() == label_name && label.get_value() == label_value)
+    })
+}
+
+/// Periodically samples the consensus and state sync metrics, so that the network health
+/// reflects a recent window rather than the whole lifetime of the node
+pub struct NetworkHealthCollector {
+    window_samples: usize,
+    samples: Mutex<VecDeque<MetricsSample>>,
+}
+
+impl NetworkHealthCollector {
+    pub fn new(window_samples: usize) -> Self {
+        Self {
+            window_samples: window_samples.max(1),
+            samples: Mutex::new(VecDeque::new()),
+        }
+    }
+
+    /// Samples the current metrics, evicting the samples that fall outside the window
+    pub fn sample(&self) {
+        let sample = MetricsSample::new(&aptos_metrics_core::gather());
+        let mut samples = self.samples.lock().unwrap();
+        samples.push_back(sample);
+        while samples.len() > self.window_samples {
+            samples.pop_front();
+        }
+    }
+
+    /// Returns the commit latency percentiles of the blocks committed within the window
+    fn commit_latency(&self) -> Option<CommitLatency> {
+        let samples = self.samples.lock().unwrap();
+        let latest = samples.back()?;
+        let oldest = samples.front().filter(|_| samples.len() > 1);
+
+        // Only count the observations made within the window
+        let count = latest
+            .commit_latency_count
+            .saturating_sub(oldest.map_or(0, |oldest| oldest.commit_latency_count));
+        if count == 0 {
+            return None;
+        }
+        let buckets: Vec<_> = latest
+            .commit_latency_buckets
+            .iter()
+            .enumerate()
+            .map(|(index, (upper_bound, cumulative_count))| {
+                let oldest_count = oldest
+                    .and_then(|oldest| oldest.commit_latency_buckets.get(index))
+                    .map_or(0, |(_, count)| *count);
+                (*upper_bound, cumulative_count.saturating_sub(oldest_count))
+            })
+            .collect();
+
+        Some(CommitLatency {
+            p50_ms: latency_percentile_ms(&buckets, count, 50).into(),
+            p90_ms: latency_percentile_ms(&buckets, count, 90).into(),
+            p99_ms: latency_percentile_ms(&buckets, count, 99).into(),
+        })
+    }
+
+    /// Returns the state sync lag at the latest sample
+    fn state_sync_lag(&self) -> StateSyncLag {
+        let samples = self.samples.lock().unwrap();
+        let latest = samples.back().cloned().unwrap_or_default();
+        let lag_versions = latest
+            .synced_version
+            .zip(latest.highest_advertised_version)
+            .map(|(synced, highest)| highest.saturating_sub(synced).into());
+        StateSyncLag {
+            synced_version: latest.synced_version.map(U64::from),
+            highest_advertised_version: latest.highest_advertised_version.map(U64::from),
+            lag_versions,
+        }
+    }
+}
+
+/// Returns the upper bound (in milliseconds) of the histogram bucket holding the given
+/// percentile. Observations above the last bucket are reported at the last bucket.
+fn latency_percentile_ms(buckets: &[(f64, u64)], count: u64, percent: u64) -> u64 {
+    let rank = (count * percent).div_ceil(100);
+    buckets
+        .iter()
+        .find(|(_, cumulative_count)| *cumulative_count >= rank)
+        .or(buckets.last())
+        .map_or(0, |(upper_bound, _)| (upper_bound * 1000.0) as u64)
+}
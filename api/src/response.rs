@@ -783,6 +783,18 @@ pub fn block_not_found_by_version<E: NotFoundError>(
     )
 }
 
+pub fn block_not_found_by_timestamp<E: NotFoundError>(
+    timestamp_usecs: u64,
+    ledger_info: &LedgerInfo,
+) -> E {
+    build_not_found(
+        "Block",
+        format!("Timestamp({})", timestamp_usecs),
+        AptosErrorCode::BlockNotFound,
+        ledger_info,
+    )
+}
+
 pub fn block_pruned_by_height<E: GoneError>(block_height: u64, ledger_info: &LedgerInfo) -> E {
     E::gone_with_code(
         format!("Block({}) has been pruned", block_height),
@@ -4,6 +4,7 @@
 
 use crate::{
     accounts::AccountsApi,
+    auth::ApiKeyAuth,
     basic::BasicApi,
     blocks::BlocksApi,
     check_size::PostSizeLimit,
@@ -12,6 +13,7 @@ use crate::{
     events::EventsApi,
     index::IndexApi,
     log::middleware_log,
+    network::NetworkApi,
     set_failpoints,
     spec::{spec_endpoint_json, spec_endpoint_yaml},
     state::StateApi,
@@ -97,6 +99,22 @@ pub fn bootstrap(
         });
     }
 
+    let context_cloned = context.clone();
+    if let Some(period_ms) = config.api.periodic_network_health_sample_ms {
+        runtime.spawn(async move {
+            let mut interval = tokio::time::interval(tokio::time::Duration::from_millis(period_ms));
+            loop {
+                interval.tick().await;
+                let context_cloned = context_cloned.clone();
+                tokio::task::spawn_blocking(move || {
+                    context_cloned.network_health_collector().sample();
+                })
+                .await
+                .unwrap_or(());
+            }
+        });
+    }
+
     Ok(runtime)
 }
 
@@ -116,6 +134,7 @@ pub fn get_api_service(
         BlocksApi,
         EventsApi,
         IndexApi,
+        NetworkApi,
         StateApi,
         TransactionsApi,
         ViewFunctionApi,
@@ -139,6 +158,9 @@ pub fn get_api_service(
         IndexApi {
             context: context.clone(),
         },
+        NetworkApi {
+            context: context.clone(),
+        },
         StateApi {
             context: context.clone(),
         },
@@ -254,6 +276,7 @@ pub fn attach_poem_to_runtime(
             .with(cors)
             .with_if(config.api.compression_enabled, Compression::new())
             .with(PostSizeLimit::new(size_limit))
+            .with(ApiKeyAuth::new(&config.api.authentication))
             // NOTE: Make sure to keep this after all the `with` middleware.
             .catch_all_error(convert_error)
             .around(middleware_log);
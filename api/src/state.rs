@@ -15,11 +15,14 @@ use crate::{
 use anyhow::Context as AnyhowContext;
 use aptos_api_types::{
     verify_module_identifier, Address, AptosErrorCode, AsConverter, IdentifierWrapper,
-    MoveModuleBytecode, MoveResource, MoveStructTag, MoveValue, RawStateValueRequest,
-    RawTableItemRequest, TableItemRequest, VerifyInput, VerifyInputWithRecursion, U64,
+    MoveModuleBytecode, MovePackage, MoveResource, MoveStructTag, MoveType, MoveValue,
+    RawStateValueRequest, RawTableItemRequest, TableItemRequest, VerifyInput,
+    VerifyInputWithRecursion, U64,
 };
-use aptos_types::state_store::{state_key::StateKey, table::TableHandle, TStateView};
-use move_core_types::language_storage::StructTag;
+use aptos_types::state_store::{
+    state_key::StateKey, state_value::StateValueWithProof, table::TableHandle, TStateView,
+};
+use move_core_types::language_storage::{StructTag, TypeTag};
 use poem_openapi::{
     param::{Path, Query},
     payload::Json,
@@ -123,6 +126,40 @@ impl StateApi {
         .await
     }
 
+    /// Get packages by name
+    ///
+    /// Retrieves the packages published under the given name, across all accounts, at a specific
+    /// ledger version. If the ledger version is not specified in the request, the latest ledger
+    /// version is used.
+    ///
+    /// This is served from the package index of the node's internal indexer, and so is only
+    /// available on nodes with the package index enabled.
+    #[oai(
+        path = "/packages/:package_name",
+        method = "get",
+        operation_id = "get_packages_by_name",
+        tag = "ApiTags::General"
+    )]
+    async fn get_packages_by_name(
+        &self,
+        accept_type: AcceptType,
+        /// Name of the package e.g. `AptosFramework`
+        package_name: Path<String>,
+        /// Ledger version to get the packages at
+        ///
+        /// If not provided, it will be the latest version
+        ledger_version: Query<Option<U64>>,
+    ) -> BasicResultWith404<Vec<MovePackage>> {
+        fail_point_poem("endpoint_get_packages_by_name")?;
+        self.context
+            .check_api_output_enabled("Get packages by name", &accept_type)?;
+        let api = self.clone();
+        api_spawn_blocking(move || {
+            api.packages_by_name(&accept_type, package_name.0, ledger_version.0)
+        })
+        .await
+    }
+
     /// Get table item
     ///
     /// Get a table item at a specific ledger version from the table identified by {table_handle}
@@ -133,6 +170,10 @@ impl StateApi {
     /// fields could themselves be composed of other structs. This makes it
     /// impractical to express using query params, meaning GET isn't an option.
     ///
+    /// The key and value types can be omitted from the request, in which case they're looked up
+    /// in the table info index. If the node doesn't index table info, or hasn't indexed the table,
+    /// the server responds with a 400, and the types need to be provided.
+    ///
     /// The Aptos nodes prune account state history, via a configurable time window.
     /// If the requested ledger version has been pruned, the server responds with a 410.
     #[oai(
@@ -264,6 +305,53 @@ impl StateApi {
         let api = self.clone();
         api_spawn_blocking(move || api.raw_value(&accept_type, request.0, ledger_version.0)).await
     }
+
+    /// Get raw state value with proof.
+    ///
+    /// Get a state value at a specific ledger version, identified by the key provided in the
+    /// request body, along with a BCS encoded `StateValueWithProof`. The proof links the value to
+    /// the latest ledger info, and includes the epoch changes since `known_version` so that the
+    /// client can verify it from a trusted waypoint.
+    ///
+    /// The ledger version must be a state checkpoint, e.g. the last version of a block.
+    #[oai(
+        path = "/experimental/state_values/raw_with_proof",
+        method = "post",
+        operation_id = "get_raw_state_value_with_proof",
+        tag = "ApiTags::Experimental",
+        hidden
+    )]
+    async fn get_raw_state_value_with_proof(
+        &self,
+        accept_type: AcceptType,
+        /// Request that carries the state key.
+        request: Json<RawStateValueRequest>,
+        /// Ledger version at which the value is got.
+        ///
+        /// If not provided, it will be the latest version
+        ledger_version: Query<Option<U64>>,
+        /// Version of the waypoint trusted by the client, for the epoch change proof.
+        ///
+        /// If not provided, epoch changes since genesis are included
+        known_version: Query<Option<U64>>,
+    ) -> BasicResultWith404<MoveValue> {
+        fail_point_poem("endpoint_get_raw_state_value_with_proof")?;
+
+        if AcceptType::Json == accept_type {
+            return Err(api_forbidden(
+                "Get raw state value with proof",
+                "Only BCS is supported as an AcceptType.",
+            ));
+        }
+        self.context
+            .check_api_output_enabled("Get raw state value with proof", &accept_type)?;
+
+        let api = self.clone();
+        api_spawn_blocking(move || {
+            api.raw_value_with_proof(request.0, ledger_version.0, known_version.0)
+        })
+        .await
+    }
 }
 
 impl StateApi {
@@ -356,9 +444,11 @@ impl StateApi {
 
         match accept_type {
             AcceptType::Json => {
-                let module = MoveModuleBytecode::new(bytes.to_vec())
-                    .try_parse_abi()
-                    .context("Failed to parse move module ABI from bytes retrieved from storage")
+                let metadata = self
+                    .context
+                    .module_metadata_cache()
+                    .get_or_extract(&state_view, address.inner(), &name, &bytes)
+                    .context("Failed to extract move module metadata from storage")
                     .map_err(|err| {
                         BasicErrorWith404::internal_with_code(
                             err,
@@ -366,6 +456,10 @@ impl StateApi {
                             &ledger_info,
                         )
                     })?;
+                let module = MoveModuleBytecode {
+                    bytecode: bytes.to_vec().into(),
+                    abi: metadata.abi.clone(),
+                };
 
                 BasicResponse::try_from_json((module, &ledger_info, BasicResponseStatus::Ok))
             },
@@ -377,6 +471,93 @@ impl StateApi {
         }
     }
 
+    /// Retrieve the packages with the given name from the package index
+    pub fn packages_by_name(
+        &self,
+        accept_type: &AcceptType,
+        package_name: String,
+        ledger_version: Option<U64>,
+    ) -> BasicResultWith404<Vec<MovePackage>> {
+        let (ledger_info, ledger_version) = self
+            .context
+            .get_latest_ledger_info_and_verify_lookup_version(
+                ledger_version.map(|inner| inner.0),
+            )?;
+        let indexer_reader = self.context.get_indexer_reader().ok_or_else(|| {
+            api_forbidden(
+                "Get packages by name",
+                "The internal indexer is not enabled on this node.",
+            )
+        })?;
+        let packages = indexer_reader
+            .get_packages_by_name(&package_name, ledger_version)
+            .context("Failed to query the package index")
+            .map_err(|err| {
+                BasicErrorWith404::internal_with_code(
+                    err,
+                    AptosErrorCode::InternalError,
+                    &ledger_info,
+                )
+            })?;
+
+        match accept_type {
+            AcceptType::Json => BasicResponse::try_from_json((
+                packages.into_iter().map(MovePackage::from).collect(),
+                &ledger_info,
+                BasicResponseStatus::Ok,
+            )),
+            AcceptType::Bcs => {
+                BasicResponse::try_from_bcs((packages, &ledger_info, BasicResponseStatus::Ok))
+            },
+        }
+    }
+
+    /// Returns the key and value types of the table item request. The types that aren't provided
+    /// are looked up in the table info index.
+    fn table_item_types(
+        &self,
+        table_handle: Address,
+        table_item_request: &TableItemRequest,
+    ) -> anyhow::Result<(TypeTag, TypeTag)> {
+        let parse_key_type = |key_type: &MoveType| -> anyhow::Result<TypeTag> {
+            key_type.try_into().context("Failed to parse key_type")
+        };
+        let parse_value_type = |value_type: &MoveType| -> anyhow::Result<TypeTag> {
+            value_type.try_into().context("Failed to parse value_type")
+        };
+        if let (Some(key_type), Some(value_type)) =
+            (&table_item_request.key_type, &table_item_request.value_type)
+        {
+            return Ok((parse_key_type(key_type)?, parse_value_type(value_type)?));
+        }
+
+        let table_info = self
+            .context
+            .indexer_reader
+            .as_ref()
+            .and_then(|reader| {
+                reader
+                    .get_table_info(TableHandle(table_handle.into()))
+                    .unwrap_or(None)
+            })
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "The types of table {} aren't indexed by this node, \
+                    'key_type' and 'value_type' are required",
+                    table_handle
+                )
+            })?;
+        let key_type = match &table_item_request.key_type {
+            Some(key_type) => parse_key_type(key_type)?,
+            None => table_info.key_type,
+        };
+        let value_type = match &table_item_request.value_type {
+            Some(value_type) => parse_value_type(value_type)?,
+            None => table_info.value_type,
+        };
+        Ok((key_type, value_type))
+    }
+
     /// Retrieve table item for a specific ledger version
     pub fn table_item(
         &self,
@@ -385,20 +566,13 @@ impl StateApi {
         table_item_request: TableItemRequest,
         ledger_version: Option<U64>,
     ) -> BasicResultWith404<MoveValue> {
-        // Parse the key and value types for the table
-        let key_type = (&table_item_request.key_type)
-            .try_into()
-            .context("Failed to parse key_type")
+        // Parse the key and value types for the table, or look them up in the table info index
+        let (key_type, value_type) = self
+            .table_item_types(table_handle, &table_item_request)
             .map_err(|err| {
                 BasicErrorWith404::bad_request_with_code_no_info(err, AptosErrorCode::InvalidInput)
             })?;
         let key = table_item_request.key;
-        let value_type = (&table_item_request.value_type)
-            .try_into()
-            .context("Failed to parse value_type")
-            .map_err(|err| {
-                BasicErrorWith404::bad_request_with_code_no_info(err, AptosErrorCode::InvalidInput)
-            })?;
 
         // Retrieve local state
         let (ledger_info, ledger_version, state_view) = self
@@ -589,4 +763,98 @@ impl StateApi {
             },
         }
     }
+
+    pub fn raw_value_with_proof(
+        &self,
+        request: RawStateValueRequest,
+        ledger_version: Option<U64>,
+        known_version: Option<U64>,
+    ) -> BasicResultWith404<MoveValue> {
+        let ledger_info = self.context.get_latest_ledger_info()?;
+        let internal_error = |err: anyhow::Error| {
+            BasicErrorWith404::internal_with_code(err, AptosErrorCode::InternalError, &ledger_info)
+        };
+
+        let latest_ledger_info = self
+            .context
+            .get_latest_ledger_info_with_signatures()
+            .map_err(internal_error)?;
+        let latest_version = latest_ledger_info.ledger_info().version();
+        let version = ledger_version.map_or(latest_version, |version| version.0);
+        let known_version = known_version.map_or(0, |version| version.0);
+        if version > latest_version || known_version > latest_version {
+            return Err(BasicErrorWith404::bad_request_with_code(
+                format!(
+                    "Ledger version {} and known version {} must not be greater than the latest \
+                     version {}",
+                    version, known_version, latest_version
+                ),
+                AptosErrorCode::InvalidInput,
+                &ledger_info,
+            ));
+        }
+
+        let state_key: StateKey = bcs::from_bytes(&request.key.0)
+            .context(format!(
+                "Failed deserializing state key. key: {}",
+                request.key
+            ))
+            .map_err(|err| {
+                BasicErrorWith404::bad_request_with_code(
+                    err,
+                    AptosErrorCode::InvalidInput,
+                    &ledger_info,
+                )
+            })?;
+        let transaction_info_with_proof = self
+            .context
+            .db
+            .get_transaction_by_version(version, latest_version, false)
+            .context(format!(
+                "Failed fetching transaction at version {}",
+                version
+            ))
+            .map_err(internal_error)?
+            .proof;
+        if transaction_info_with_proof
+            .transaction_info()
+            .state_checkpoint_hash()
+            .is_none()
+        {
+            return Err(BasicErrorWith404::bad_request_with_code(
+                format!("Ledger version {} is not a state checkpoint", version),
+                AptosErrorCode::InvalidInput,
+                &ledger_info,
+            ));
+        }
+        let (value, proof) = self
+            .context
+            .db
+            .get_state_value_with_proof_by_version(&state_key, version)
+            .context(format!(
+                "Failed fetching state value with proof. key: {}",
+                request.key
+            ))
+            .map_err(internal_error)?;
+        let state_proof = self
+            .context
+            .db
+            .get_state_proof_with_ledger_info(known_version, latest_ledger_info)
+            .context("Failed fetching state proof")
+            .map_err(internal_error)?;
+
+        let bytes = bcs::to_bytes(&StateValueWithProof {
+            version,
+            value,
+            proof,
+            transaction_info_with_proof,
+            state_proof,
+        })
+        .context(format!(
+            "Failed serializing state value with proof. key: {}",
+            request.key
+        ))
+        .map_err(internal_error)?;
+        BasicResponse::try_from_encoded((bytes, &ledger_info, BasicResponseStatus::Ok))
+    }
 }
@@ -680,6 +680,78 @@ async fn test_get_account_modules_with_pagination() {
     assert_eq!(modules, all_modules[10..].to_vec());
 }
 
+// Pages one resource at a time across the members of a resource group, the primary fungible
+// store of an account.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_get_account_resources_with_pagination_in_resource_group() {
+    let mut context = new_test_context(current_function_name!());
+    let root_account = context.root_account().await;
+    let txn = root_account.sign_with_transaction_builder(
+        context
+            .transaction_factory()
+            .payload(aptos_stdlib::coin_migrate_to_fungible_store(
+                AptosCoinType::type_tag(),
+            ))
+            .expiration_timestamp_secs(context.get_expiration_time()),
+    );
+    context.commit_block(&vec![txn]).await;
+    let address = primary_apt_store(root_account.address()).to_hex_literal();
+
+    let req = warp::test::request()
+        .method("GET")
+        .path(&format!("/v1{}", account_resources(&address)));
+    let resp = context.reply(req).await;
+    assert_eq!(resp.status(), 200);
+    let all_resources: Vec<MoveResource> = serde_json::from_slice(resp.body()).unwrap();
+    // The object and the store itself are both members of the object group.
+    assert!(all_resources.len() >= 2);
+
+    let mut cursor: Option<String> = None;
+    for (i, resource) in all_resources.iter().enumerate() {
+        let path = match &cursor {
+            Some(cursor) => format!(
+                "/v1{}?limit=1&start={}",
+                account_resources(&address),
+                cursor
+            ),
+            None => format!("/v1{}?limit=1", account_resources(&address)),
+        };
+        let resp = context
+            .reply(warp::test::request().method("GET").path(&path))
+            .await;
+        assert_eq!(resp.status(), 200);
+        let resources: Vec<MoveResource> = serde_json::from_slice(resp.body()).unwrap();
+        assert_eq!(resources, vec![resource.clone()]);
+        let next_cursor = resp
+            .headers()
+            .get("X-Aptos-Cursor")
+            .map(|cursor| cursor.to_str().unwrap().to_string());
+
+        // Requesting the same page again returns the same resources and cursor.
+        let resp = context
+            .reply(warp::test::request().method("GET").path(&path))
+            .await;
+        assert_eq!(resp.status(), 200);
+        let resources_again: Vec<MoveResource> = serde_json::from_slice(resp.body()).unwrap();
+        assert_eq!(resources_again, resources);
+        assert_eq!(
+            resp.headers()
+                .get("X-Aptos-Cursor")
+                .map(|cursor| cursor.to_str().unwrap().to_string()),
+            next_cursor
+        );
+
+        if i + 1 == all_resources.len() {
+            assert!(next_cursor.is_none());
+        } else {
+            let next_cursor = next_cursor.expect("Cursor header was missing");
+            StateKeyWrapper::from_str(&next_cursor).unwrap();
+            assert_ne!(Some(&next_cursor), cursor.as_ref());
+            cursor = Some(next_cursor);
+        }
+    }
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn test_get_account_items_limit_params() {
     let context = new_test_context(current_function_name!());
@@ -23,6 +23,41 @@ async fn test_get_unknown_block_by_height() {
     context.check_golden_output(resp);
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_get_block_by_timestamp() {
+    let mut context = new_test_context(current_function_name!());
+    let account = context.gen_account();
+    let txn = context.create_user_account(&account).await;
+    context.commit_block(&vec![txn]).await;
+
+    let block = context.get(&blocks_by_height(1)).await;
+    let timestamp: u64 = block["block_timestamp"].as_str().unwrap().parse().unwrap();
+
+    for (timestamp, search, height) in [
+        (timestamp, None, "1"),
+        (timestamp, Some("closest_after"), "1"),
+        (timestamp - 1, None, "0"),
+        (timestamp - 1, Some("closest_before"), "0"),
+        (timestamp - 1, Some("closest_after"), "1"),
+        (timestamp + 1, None, "1"),
+    ] {
+        let resp = context.get(&blocks_by_timestamp(timestamp, search)).await;
+        assert_eq!(resp["block_height"], height);
+    }
+
+    context
+        .expect_status_code(404)
+        .get(&blocks_by_timestamp(timestamp + 1, Some("closest_after")))
+        .await;
+}
+
 fn blocks_by_height(height: u64) -> String {
     format!("/blocks/by_height/{}", height)
 }
+
+fn blocks_by_timestamp(timestamp: u64, search: Option<&str>) -> String {
+    match search {
+        Some(search) => format!("/blocks/by_timestamp/{}?search={}", timestamp, search),
+        None => format!("/blocks/by_timestamp/{}", timestamp),
+    }
+}
@@ -4,6 +4,9 @@
 
 use super::new_test_context;
 use aptos_api_test_context::current_function_name;
+use aptos_api_types::{AccumulatorConsistencyProof, NetworkHealth};
+use aptos_crypto::HashValue;
+use aptos_storage_interface::DbReader;
 use serde_json::json;
 
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
@@ -13,6 +16,84 @@ async fn test_get_index() {
     context.check_golden_output(resp);
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_get_network_health() {
+    let mut context = new_test_context(current_function_name!());
+    for _ in 0..3 {
+        context.commit_block(&[]).await;
+    }
+    let block_height = context
+        .db
+        .get_block_info_by_version(context.get_latest_ledger_info().version())
+        .unwrap()
+        .2
+        .height();
+
+    let resp = context.get("/network/health").await;
+    let network_health: NetworkHealth = serde_json::from_value(resp).unwrap();
+    let validator_participation = network_health.validator_participation.unwrap();
+    assert_eq!(validator_participation.block_height.0, block_height);
+    // The metrics aren't sampled by the test context
+    assert_eq!(network_health.commit_latency, None);
+    assert_eq!(network_health.state_sync.lag_versions, None);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_get_ledger_consistency_proof() {
+    let mut context = new_test_context(current_function_name!());
+    let client_known_version = context.get_latest_ledger_info().version();
+    let account = context.gen_account();
+    let txn = context.create_user_account(&account).await;
+    context.commit_block(&vec![txn]).await;
+    let target_version = context.get_latest_ledger_info().version();
+
+    let resp = context
+        .get(&format!(
+            "/ledger/consistency_proof?client_known_version={}",
+            client_known_version
+        ))
+        .await;
+    let proof: AccumulatorConsistencyProof = serde_json::from_value(resp).unwrap();
+    assert_eq!(proof.target_version.0, target_version);
+
+    // Appending the subtrees to the accumulator the client knows of results in the target one.
+    let client_accumulator = context
+        .db
+        .get_accumulator_summary(client_known_version)
+        .unwrap()
+        .0;
+    let subtrees: Vec<HashValue> = proof.subtrees.into_iter().map(Into::into).collect();
+    let target_accumulator = client_accumulator
+        .append_subtrees(&subtrees, target_version - client_known_version)
+        .unwrap();
+    assert_eq!(
+        target_accumulator.root_hash(),
+        proof.target_root_hash.into()
+    );
+    assert_eq!(
+        target_accumulator.root_hash(),
+        context
+            .db
+            .get_accumulator_root_hash(target_version)
+            .unwrap()
+    );
+
+    context
+        .expect_status_code(400)
+        .get(&format!(
+            "/ledger/consistency_proof?client_known_version={}&target_version={}",
+            target_version, client_known_version
+        ))
+        .await;
+    context
+        .expect_status_code(404)
+        .get(&format!(
+            "/ledger/consistency_proof?target_version={}",
+            target_version + 1
+        ))
+        .await;
+}
+
 // TODO: Un-ignore this pending https://github.com/poem-web/poem/issues/343.
 #[ignore]
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
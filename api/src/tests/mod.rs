@@ -37,7 +37,8 @@ fn new_test_context_with_config(
     use_txn_payload_v2_format: bool,
     use_orderless_transactions: bool,
 ) -> TestContext {
-    node_config.indexer_db_config = InternalIndexerDBConfig::new(true, true, true, 0, true, 10);
+    node_config.indexer_db_config =
+        InternalIndexerDBConfig::new(true, true, true, 0, true, true, true, 10);
     let test_context = super_new_test_context(
         test_name,
         node_config,
@@ -336,6 +336,32 @@ async fn test_get_table_item() {
     assert_table_item(ctx, &nested_table, "u8", "u8", 2, 3).await;
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_get_table_item_without_types_not_indexed() {
+    let mut context = new_test_context(current_function_name!());
+    let ctx = &mut context;
+    let mut acc = ctx.root_account().await;
+    make_test_tables(ctx, &mut acc).await;
+
+    let tt = ctx
+        .api_get_account_resource(
+            acc.address(),
+            &acc.address().to_hex_literal(),
+            "TableTestData",
+            "TestTables",
+        )
+        .await["data"]
+        .to_owned();
+
+    // The test node doesn't index table info, so the types are required
+    let handle = tt["u8_table"]["handle"].as_str().unwrap().parse().unwrap();
+    let response = ctx
+        .expect_status_code(400)
+        .post(&get_table_item(handle), json!({ "key": 1u8 }))
+        .await;
+    assert_eq!(response["error_code"], json!("invalid_input"));
+}
+
 fn get_account_resource(address: &str, struct_tag: &str) -> String {
     format!("/accounts/{}/resource/{}", address, struct_tag)
 }
@@ -5,7 +5,9 @@
 use super::new_test_context;
 use crate::tests::{new_test_context_with_config, new_test_context_with_orderless_flags};
 use aptos_api_test_context::{assert_json, current_function_name, pretty, TestContext};
-use aptos_config::config::{GasEstimationStaticOverride, NodeConfig, TransactionFilterConfig};
+use aptos_config::config::{
+    GasEstimationStaticOverride, GasEstimationStrategy, NodeConfig, TransactionFilterConfig,
+};
 use aptos_crypto::{
     ed25519::{Ed25519PrivateKey, Ed25519Signature},
     multi_ed25519::{MultiEd25519PrivateKey, MultiEd25519PublicKey},
@@ -2048,6 +2050,37 @@ async fn test_gas_estimation_ten_blocks(
     context.check_golden_output(resp);
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_gas_estimation_block_fullness() {
+    let mut node_config = NodeConfig::default();
+    node_config.api.gas_estimation.enabled = true;
+    node_config.api.gas_estimation.strategy = GasEstimationStrategy::BlockFullness;
+    let mut context =
+        new_test_context_with_config(current_function_name!(), node_config, false, false);
+
+    let ctx = &mut context;
+    let creator = &mut ctx.gen_account();
+    let mint_txn = ctx.mint_user_account(creator).await;
+
+    // Include the mint txn in the first block
+    let mut block = vec![mint_txn];
+    // First block is ignored in gas estimate, so make 11
+    for _i in 0..11 {
+        fill_block(&mut block, ctx, creator).await;
+        ctx.commit_block(&block).await;
+        block.clear();
+    }
+
+    // All blocks are full, so the percentile estimates (see the ten blocks golden) are raised by
+    // the maximum adjustment of 100%
+    let resp = context.get("/estimate_gas_price").await;
+    assert_eq!(resp["deprioritized_gas_estimate"].as_u64(), Some(150));
+    assert_eq!(resp["gas_estimate"].as_u64(), Some(300));
+    assert_eq!(resp["gas_estimate_lower_bound"].as_u64(), Some(300));
+    assert_eq!(resp["gas_estimate_upper_bound"].as_u64(), Some(300));
+    assert!(resp["prioritized_gas_estimate"].as_u64().unwrap() > 300);
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 #[rstest(
     use_txn_payload_v2_format,
@@ -2248,6 +2281,56 @@ async fn test_gas_estimation_static_override(
     context.check_golden_output(resp);
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+#[rstest(
+    use_txn_payload_v2_format,
+    use_orderless_transactions,
+    case(false, false),
+    case(true, false),
+    case(true, true)
+)]
+async fn test_entry_function_gas_estimation(
+    use_txn_payload_v2_format: bool,
+    use_orderless_transactions: bool,
+) {
+    let mut node_config = NodeConfig::default();
+    node_config.api.gas_estimation.enabled = true;
+    let mut context = new_test_context_with_config(
+        current_function_name!(),
+        node_config,
+        use_txn_payload_v2_format,
+        use_orderless_transactions,
+    );
+
+    // Without recent calls, no max gas amount is suggested
+    let path = "/estimate_gas_price/0x1::aptos_account::transfer";
+    let resp = context.get(path).await;
+    assert_eq!(resp["function"], "0x1::aptos_account::transfer");
+    assert_eq!(resp["sample_size"], 0);
+    assert!(resp["max_gas_amount"].is_null());
+
+    // Commit the mint txn along with a few transfers
+    let ctx = &mut context;
+    let creator = &mut ctx.gen_account();
+    let owner = &mut ctx.gen_account();
+    let mut block = vec![ctx.mint_user_account(creator).await];
+    for _i in 0..9 {
+        block.push(ctx.account_transfer(creator, owner, 1));
+    }
+    ctx.commit_block(&block).await;
+
+    // The estimation is now based on the committed calls
+    let resp = context.get(path).await;
+    assert_eq!(resp["sample_size"], 10);
+    assert!(resp["max_gas_amount"].as_u64().unwrap() > 0);
+
+    // Entry functions without recent calls are unaffected
+    let resp = context
+        .get("/estimate_gas_price/0x1::aptos_account::create_account")
+        .await;
+    assert_eq!(resp["sample_size"], 0);
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 #[rstest(
     use_txn_payload_v2_format,
@@ -2567,6 +2650,30 @@ async fn test_simulation_filter_allow_sender(
     context.check_golden_output(resp);
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_submit_transaction_denylist() {
+    let mut node_config = NodeConfig::default();
+
+    // Deny transfers bigger than some payload size.
+    node_config.transaction_filters.transaction_denylist =
+        "entry_function == 0x1::aptos_account::transfer && payload_size > 0"
+            .parse()
+            .unwrap();
+
+    let mut context =
+        new_test_context_with_config(current_function_name!(), node_config, false, false);
+    let mut sender = context.gen_account();
+    let receiver = context.gen_account();
+    let txn = context.account_transfer(&mut sender, &receiver, 1);
+
+    // The transaction is rejected before reaching mempool
+    let resp = context
+        .expect_status_code(403)
+        .post_bcs_txn("/transactions", bcs::to_bytes(&txn).unwrap())
+        .await;
+    assert_eq!(resp["error_code"], json!("rejected_by_filter"));
+}
+
 fn gen_string(len: u64) -> String {
     let mut rng = thread_rng();
     std::iter::repeat(())
@@ -23,11 +23,11 @@ use crate::{
 use anyhow::Context as AnyhowContext;
 use aptos_api_types::{
     transaction::TransactionSummary, verify_function_identifier, verify_module_identifier, Address,
-    AptosError, AptosErrorCode, AsConverter, EncodeSubmissionRequest, GasEstimation,
-    GasEstimationBcs, HashValue, HexEncodedBytes, LedgerInfo, MoveType, PendingTransaction,
-    SubmitTransactionRequest, Transaction, TransactionData, TransactionOnChainData,
-    TransactionsBatchSingleSubmissionFailure, TransactionsBatchSubmissionResult, UserTransaction,
-    VerifyInput, VerifyInputWithRecursion, U64,
+    AptosError, AptosErrorCode, AsConverter, EncodeSubmissionRequest, EntryFunctionGasEstimation,
+    EntryFunctionId, GasEstimation, GasEstimationBcs, HashValue, HexEncodedBytes, LedgerInfo,
+    MoveType, PendingTransaction, SubmitTransactionRequest, Transaction, TransactionData,
+    TransactionOnChainData, TransactionsBatchSingleSubmissionFailure,
+    TransactionsBatchSubmissionResult, UserTransaction, VerifyInput, VerifyInputWithRecursion, U64,
 };
 use aptos_crypto::{hash::CryptoHash, signing_message};
 use aptos_logger::error;
@@ -810,6 +810,53 @@ impl TransactionsApi {
         })
         .await
     }
+
+    /// Estimate gas for an entry function
+    ///
+    /// Gives an estimate of the gas unit price and the max gas amount for calling the given entry
+    /// function, based on the gas unit prices paid and the gas used by its recently committed
+    /// calls. The gas unit price is given as the 50th, 90th and 99th percentiles of recent calls,
+    /// and the max gas amount covers the gas used by nearly all recent calls with a margin.
+    ///
+    /// If the entry function has not been called recently, the gas unit prices fall back to the
+    /// general gas price estimation, and no max gas amount is suggested.
+    #[oai(
+        path = "/estimate_gas_price/:function",
+        method = "get",
+        operation_id = "estimate_entry_function_gas",
+        tag = "ApiTags::Transactions"
+    )]
+    async fn estimate_entry_function_gas(
+        &self,
+        accept_type: AcceptType,
+        /// Entry function id in the format `{address}::{module name}::{function name}`
+        function: Path<EntryFunctionId>,
+    ) -> BasicResult<EntryFunctionGasEstimation> {
+        fail_point_poem("endpoint_estimate_entry_function_gas")?;
+        self.context
+            .check_api_output_enabled("Estimate entry function gas", &accept_type)?;
+
+        let context = self.context.clone();
+        api_spawn_blocking(move || {
+            let latest_ledger_info = context.get_latest_ledger_info()?;
+            let gas_estimation =
+                context.estimate_entry_function_gas(&latest_ledger_info, &function.0)?;
+
+            match accept_type {
+                AcceptType::Json => BasicResponse::try_from_json((
+                    gas_estimation,
+                    &latest_ledger_info,
+                    BasicResponseStatus::Ok,
+                )),
+                AcceptType::Bcs => BasicResponse::try_from_bcs((
+                    gas_estimation,
+                    &latest_ledger_info,
+                    BasicResponseStatus::Ok,
+                )),
+            }
+        })
+        .await
+    }
 }
 
 impl TransactionsApi {
@@ -1361,6 +1408,13 @@ impl TransactionsApi {
 
     /// Submits a single transaction, and converts mempool codes to errors
     async fn create_internal(&self, txn: SignedTransaction) -> Result<(), AptosError> {
+        if self.context.is_transaction_denylisted(&txn) {
+            return Err(AptosError::new_with_error_code(
+                "Transaction is denied by the node's transaction denylist",
+                AptosErrorCode::RejectedByFilter,
+            ));
+        }
+
         let (mempool_status, vm_status_opt) = self
             .context
             .submit_transaction(txn)
@@ -1477,6 +1531,9 @@ impl TransactionsApi {
                         ledger_info,
                     ),
                 ),
+                AptosErrorCode::RejectedByFilter => Err(
+                    SubmitTransactionError::forbidden_from_aptos_error(error, ledger_info),
+                ),
                 _ => Err(SubmitTransactionError::internal_from_aptos_error(
                     error,
                     ledger_info,
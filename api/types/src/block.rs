@@ -2,7 +2,8 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{HashValue, Transaction, TransactionOnChainData, U64};
-use poem_openapi::Object;
+use aptos_storage_interface::{block_execution_stats, block_info};
+use poem_openapi::{Enum, Object};
 use serde::{Deserialize, Serialize};
 
 /// A Block with or without transactions
@@ -19,6 +20,73 @@ pub struct Block {
     /// The transactions in the block in sequential order
     #[serde(skip_serializing_if = "Option::is_none")]
     pub transactions: Option<Vec<Transaction>>,
+    /// Statistics about the execution of the block, if recorded by the node
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub execution_stats: Option<BlockExecutionStats>,
+}
+
+/// Which block to return when looking up blocks by timestamp
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Enum)]
+#[serde(rename_all = "snake_case")]
+#[oai(rename_all = "snake_case")]
+pub enum BlockTimestampSearch {
+    /// The latest block with a timestamp at or before the requested one
+    ClosestBefore,
+    /// The earliest block with a timestamp at or after the requested one
+    ClosestAfter,
+}
+
+impl From<BlockTimestampSearch> for block_info::BlockTimestampSearch {
+    fn from(search: BlockTimestampSearch) -> Self {
+        match search {
+            BlockTimestampSearch::ClosestBefore => block_info::BlockTimestampSearch::ClosestBefore,
+            BlockTimestampSearch::ClosestAfter => block_info::BlockTimestampSearch::ClosestAfter,
+        }
+    }
+}
+
+/// Statistics about the execution of a block
+///
+/// Only recorded by nodes that executed the block themselves, rather than synced its outputs
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Object)]
+pub struct BlockExecutionStats {
+    /// Number of transactions in the block, not counting the block epilogue
+    pub num_txns: U64,
+    pub num_succeeded: U64,
+    /// Transactions kept, but aborted or otherwise failed
+    pub num_failed: U64,
+    pub num_discarded: U64,
+    /// Transactions not executed because the block hit a limit or an epoch change
+    pub num_retried: U64,
+    /// Total gas units used by the committed transactions
+    pub total_gas_used: U64,
+    /// Committed transactions executed more than once, if the block was executed in parallel
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub num_conflicting_txns: Option<U64>,
+    /// Aborted speculative executions, if the block was executed in parallel
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub num_speculative_aborts: Option<U64>,
+    /// Change of the number of state items
+    pub state_items_delta: i64,
+    /// Change of the total size of the state in bytes
+    pub state_bytes_delta: i64,
+}
+
+impl From<block_execution_stats::BlockExecutionStats> for BlockExecutionStats {
+    fn from(stats: block_execution_stats::BlockExecutionStats) -> Self {
+        Self {
+            num_txns: stats.num_txns.into(),
+            num_succeeded: stats.num_succeeded.into(),
+            num_failed: stats.num_failed.into(),
+            num_discarded: stats.num_discarded.into(),
+            num_retried: stats.num_retried.into(),
+            total_gas_used: stats.total_gas_used.into(),
+            num_conflicting_txns: stats.num_conflicting_txns.map(Into::into),
+            num_speculative_aborts: stats.num_speculative_aborts.map(Into::into),
+            state_items_delta: stats.state_items_delta,
+            state_bytes_delta: stats.state_bytes_delta,
+        }
+    }
 }
 
 /// A Block with or without transactions for encoding in BCS
@@ -13,8 +13,9 @@ use crate::{
     Address, Bytecode, DirectWriteSet, EntryFunctionId, EntryFunctionPayload, Event,
     HexEncodedBytes, MoveFunction, MoveModuleBytecode, MoveResource, MoveScriptBytecode, MoveType,
     MoveValue, PendingTransaction, ResourceGroup, ScriptPayload, ScriptWriteSet,
-    SubmitTransactionRequest, Transaction, TransactionInfo, TransactionOnChainData,
-    TransactionPayload, VersionedEvent, WriteSet, WriteSetChange, WriteSetPayload,
+    SubmitTransactionRequest, Transaction, TransactionAuxiliaryRecord, TransactionInfo,
+    TransactionOnChainData, TransactionPayload, VersionedEvent, WriteSet, WriteSetChange,
+    WriteSetPayload,
 };
 use anyhow::{bail, ensure, format_err, Context as AnyhowContext, Result};
 use aptos_crypto::{hash::CryptoHash, HashValue};
@@ -110,6 +111,11 @@ impl<'a, S: StateView> MoveConverter<'a, S> {
         false
     }
 
+    /// Returns the resource group of the struct, if it's a resource group member.
+    pub fn find_resource_group(&self, tag: &StructTag) -> Option<StructTag> {
+        self.inner.view_resource_group_member(tag)
+    }
+
     pub fn find_resource(
         &self,
         state_view: &impl StateView,
@@ -250,6 +256,17 @@ impl<'a, S: StateView> MoveConverter<'a, S> {
         write_set: aptos_types::write_set::WriteSet,
         txn_aux_data: Option<TransactionAuxiliaryData>,
     ) -> TransactionInfo {
+        // Records of kinds this node can't decode are left out.
+        let auxiliary_records = txn_aux_data
+            .as_ref()
+            .map(|aux_data| {
+                aux_data
+                    .records()
+                    .iter()
+                    .filter_map(|record| TransactionAuxiliaryRecord::try_from(record).ok())
+                    .collect::<Vec<_>>()
+            })
+            .filter(|records| !records.is_empty());
         TransactionInfo {
             version: version.into(),
             hash: info.transaction_hash().into(),
@@ -266,6 +283,7 @@ impl<'a, S: StateView> MoveConverter<'a, S> {
                 .filter_map(|(sk, wo)| self.try_into_write_set_changes(sk, wo).ok())
                 .flatten()
                 .collect(),
+            auxiliary_records,
             block_height: None,
             epoch: None,
         }
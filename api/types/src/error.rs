@@ -112,6 +112,10 @@ pub enum AptosErrorCode {
     BcsNotSupported = 602,
     /// API Disabled
     ApiDisabled = 603,
+    /// The request doesn't have a valid API key for the endpoint
+    Unauthorized = 604,
+    /// The request's API key doesn't grant access to the endpoint
+    Forbidden = 605,
 }
 
 impl AptosErrorCode {
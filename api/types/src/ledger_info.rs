@@ -2,8 +2,11 @@
 // Parts of the project are originally copyright © Meta Platforms, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::U64;
-use aptos_types::{chain_id::ChainId, ledger_info::LedgerInfoWithSignatures};
+use crate::{HashValue, U64};
+use aptos_types::{
+    chain_id::ChainId, ledger_info::LedgerInfoWithSignatures,
+    proof::AccumulatorConsistencyProof as AccumulatorConsistencyProofBcs,
+};
 use poem_openapi::Object as PoemObject;
 use serde::{Deserialize, Serialize};
 
@@ -76,3 +79,35 @@ impl LedgerInfo {
         self.ledger_timestamp.into()
     }
 }
+
+/// A proof that the transaction accumulator at the target version extends the
+/// one at the client known version
+///
+/// Appending the subtrees to the accumulator the client knows of results in the
+/// accumulator at the target version, with the root hash given here.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq, PoemObject)]
+pub struct AccumulatorConsistencyProof {
+    /// Version the client knows the accumulator at, if any
+    pub client_known_version: Option<U64>,
+    pub target_version: U64,
+    /// Root hash of the accumulator at the target version
+    pub target_root_hash: HashValue,
+    /// Roots of the subtrees of the transactions after the client known version, from left to right
+    pub subtrees: Vec<HashValue>,
+}
+
+impl AccumulatorConsistencyProof {
+    pub fn new(
+        client_known_version: Option<u64>,
+        target_version: u64,
+        target_root_hash: aptos_crypto::HashValue,
+        proof: AccumulatorConsistencyProofBcs,
+    ) -> Self {
+        Self {
+            client_known_version: client_known_version.map(U64::from),
+            target_version: target_version.into(),
+            target_root_hash: target_root_hash.into(),
+            subtrees: proof.into_subtrees().into_iter().map(Into::into).collect(),
+        }
+    }
+}
@@ -17,6 +17,7 @@ mod index;
 mod ledger_info;
 pub mod mime_types;
 mod move_types;
+mod network;
 mod state;
 mod table;
 pub mod transaction;
@@ -25,21 +26,22 @@ mod wrappers;
 
 pub use account::{AccountData, AssetType};
 pub use address::Address;
-pub use block::{BcsBlock, Block};
+pub use block::{BcsBlock, Block, BlockExecutionStats, BlockTimestampSearch};
 pub use bytecode::Bytecode;
 pub use convert::{new_vm_utf8_string, AsConverter, MoveConverter};
 pub use error::{AptosError, AptosErrorCode};
 pub use hash::HashValue;
 pub use headers::*;
 pub use index::{IndexResponse, IndexResponseBcs};
-pub use ledger_info::LedgerInfo;
+pub use ledger_info::{AccumulatorConsistencyProof, LedgerInfo};
 pub use move_types::{
     verify_field_identifier, verify_function_identifier, verify_module_identifier, EntryFunctionId,
     HexEncodedBytes, MoveAbility, MoveFunction, MoveFunctionGenericTypeParam,
-    MoveFunctionVisibility, MoveModule, MoveModuleBytecode, MoveModuleId, MoveResource,
-    MoveScriptBytecode, MoveStruct, MoveStructField, MoveStructTag, MoveType, MoveValue,
-    ResourceGroup, MAX_RECURSIVE_TYPES_ALLOWED, U128, U256, U64,
+    MoveFunctionVisibility, MoveModule, MoveModuleBytecode, MoveModuleId, MovePackage,
+    MovePackageDep, MoveResource, MoveScriptBytecode, MoveStruct, MoveStructField, MoveStructTag,
+    MoveType, MoveValue, ResourceGroup, MAX_RECURSIVE_TYPES_ALLOWED, U128, U256, U64,
 };
+pub use network::{CommitLatency, NetworkHealth, StateSyncLag, ValidatorParticipation};
 use serde::{Deserialize, Deserializer};
 pub use state::RawStateValueRequest;
 use std::str::FromStr;
@@ -47,16 +49,16 @@ pub use table::{RawTableItemRequest, TableItemRequest};
 pub use transaction::{
     AbstractionSignature, AccountSignature, BlockMetadataTransaction, DeleteModule, DeleteResource,
     DeleteTableItem, DirectWriteSet, Ed25519Signature, EncodeSubmissionRequest,
-    EntryFunctionPayload, Event, FeePayerSignature, GasEstimation, GasEstimationBcs,
-    GenesisPayload, GenesisTransaction, MultiAgentSignature, MultiEd25519Signature,
-    MultiKeySignature, MultisigPayload, MultisigTransactionPayload, NoAccountSignature,
-    PendingTransaction, PublicKey, ScriptPayload, ScriptWriteSet, Signature, SingleKeySignature,
-    SubmitTransactionRequest, Transaction, TransactionData, TransactionId, TransactionInfo,
-    TransactionOnChainData, TransactionPayload, TransactionSignature, TransactionSigningMessage,
-    TransactionSummary, TransactionsBatchSingleSubmissionFailure,
-    TransactionsBatchSubmissionResult, UserCreateSigningMessageRequest, UserTransaction,
-    UserTransactionRequest, VersionedEvent, WriteModule, WriteResource, WriteSet, WriteSetChange,
-    WriteSetPayload, WriteTableItem,
+    EntryFunctionGasEstimation, EntryFunctionPayload, Event, FeePayerSignature, GasEstimation,
+    GasEstimationBcs, GenesisPayload, GenesisTransaction, MultiAgentSignature,
+    MultiEd25519Signature, MultiKeySignature, MultisigPayload, MultisigTransactionPayload,
+    NoAccountSignature, PendingTransaction, PublicKey, ScriptPayload, ScriptWriteSet, Signature,
+    SingleKeySignature, SubmitTransactionRequest, Transaction, TransactionAuxiliaryRecord,
+    TransactionData, TransactionId, TransactionInfo, TransactionOnChainData, TransactionPayload,
+    TransactionSignature, TransactionSigningMessage, TransactionSummary,
+    TransactionsBatchSingleSubmissionFailure, TransactionsBatchSubmissionResult,
+    UserCreateSigningMessageRequest, UserTransaction, UserTransactionRequest, VersionedEvent,
+    WriteModule, WriteResource, WriteSet, WriteSetChange, WriteSetPayload, WriteTableItem,
 };
 pub use view::{ViewFunction, ViewRequest};
 pub use wrappers::{EventGuid, IdentifierWrapper, StateKeyWrapper};
@@ -7,7 +7,10 @@ use anyhow::{bail, format_err};
 use aptos_resource_viewer::{
     AnnotatedMoveClosure, AnnotatedMoveStruct, AnnotatedMoveValue, RawMoveStruct,
 };
-use aptos_types::{account_config::CORE_CODE_ADDRESS, event::EventKey, transaction::Module};
+use aptos_types::{
+    account_config::CORE_CODE_ADDRESS, event::EventKey, indexer::indexer_db_reader::IndexedPackage,
+    transaction::Module,
+};
 use move_binary_format::{
     access::ModuleAccess,
     file_format::{CompiledModule, CompiledScript, StructTypeParameter, Visibility},
@@ -1250,6 +1253,52 @@ impl From<Module> for MoveModuleBytecode {
     }
 }
 
+/// A Move package published at an address
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Object)]
+pub struct MovePackage {
+    pub address: Address,
+    pub name: String,
+    pub upgrade_number: U64,
+    /// Upgrade policy of the package: `arbitrary`, `compatible` or `immutable`
+    pub upgrade_policy: String,
+    /// Names of the modules of the package
+    pub modules: Vec<String>,
+    /// Packages the package depends on
+    pub deps: Vec<MovePackageDep>,
+}
+
+/// A dependency of a Move package
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Object)]
+pub struct MovePackageDep {
+    pub address: Address,
+    pub package_name: String,
+}
+
+impl From<IndexedPackage> for MovePackage {
+    fn from(package: IndexedPackage) -> Self {
+        let upgrade_policy = match package.upgrade_policy {
+            0 => "arbitrary",
+            1 => "compatible",
+            _ => "immutable",
+        };
+        Self {
+            address: package.address.into(),
+            name: package.name,
+            upgrade_number: package.upgrade_number.into(),
+            upgrade_policy: upgrade_policy.to_string(),
+            modules: package.modules,
+            deps: package
+                .deps
+                .into_iter()
+                .map(|(address, package_name)| MovePackageDep {
+                    address: address.into(),
+                    package_name,
+                })
+                .collect(),
+        }
+    }
+}
+
 /// Move script bytecode
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Object)]
 pub struct MoveScriptBytecode {
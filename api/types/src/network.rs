@@ -0,0 +1,54 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{Address, HexEncodedBytes, U64};
+use poem_openapi::Object;
+use serde::{Deserialize, Serialize};
+
+/// The health of the network, as observed by this node
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize, Object)]
+pub struct NetworkHealth {
+    /// Average number of consensus rounds per second over the recent blocks of the
+    /// current epoch. Absent if there are not enough blocks in the epoch.
+    pub rounds_per_second: Option<f64>,
+    /// Percentiles of the time between the proposal of recent blocks and their commit.
+    /// Absent if no blocks were committed by consensus on this node recently.
+    pub commit_latency: Option<CommitLatency>,
+    /// Validator participation in the latest block
+    pub validator_participation: Option<ValidatorParticipation>,
+    /// How far state sync on this node is behind the rest of the network
+    pub state_sync: StateSyncLag,
+}
+
+/// Percentiles of the commit latency of recent blocks
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize, Object)]
+pub struct CommitLatency {
+    pub p50_ms: U64,
+    pub p90_ms: U64,
+    pub p99_ms: U64,
+}
+
+/// Validator participation in a block
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize, Object)]
+pub struct ValidatorParticipation {
+    pub block_height: U64,
+    pub round: U64,
+    pub proposer: Address,
+    /// Bitmap (by validator index) of the validators that voted for the previous block
+    pub previous_block_votes_bitvec: HexEncodedBytes,
+    /// Number of validators that voted for the previous block
+    pub num_votes: U64,
+    /// Indices of the validators that failed to propose since the previous block
+    pub failed_proposer_indices: Vec<U64>,
+}
+
+/// The state sync progress of the node compared to the rest of the network
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize, Object)]
+pub struct StateSyncLag {
+    /// The latest version synced by this node
+    pub synced_version: Option<U64>,
+    /// The highest version advertised by the peers of this node
+    pub highest_advertised_version: Option<U64>,
+    /// The number of versions this node is behind the highest advertised version
+    pub lag_versions: Option<U64>,
+}
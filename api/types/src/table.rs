@@ -9,16 +9,21 @@ use serde_json::Value;
 /// Table Item request for the GetTableItem API
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Object)]
 pub struct TableItemRequest {
-    pub key_type: MoveType,
-    pub value_type: MoveType,
+    pub key_type: Option<MoveType>,
+    pub value_type: Option<MoveType>,
     /// The value of the table item's key
     pub key: Value,
 }
 
 impl VerifyInput for TableItemRequest {
     fn verify(&self) -> anyhow::Result<()> {
-        self.key_type.verify(0)?;
-        self.value_type.verify(0)
+        if let Some(key_type) = &self.key_type {
+            key_type.verify(0)?;
+        }
+        if let Some(value_type) = &self.value_type {
+            value_type.verify(0)?;
+        }
+        Ok(())
     }
 }
 
@@ -31,7 +31,7 @@ use aptos_types::{
             SingleKeyAuthenticator, TransactionAuthenticator, MAX_NUM_OF_SIGS,
         },
         webauthn::{PartialAuthenticatorAssertionResponse, MAX_WEBAUTHN_SIGNATURE_BYTES},
-        Script, SignedTransaction, TransactionOutput, TransactionWithProof,
+        AuxiliaryRecord, Script, SignedTransaction, TransactionOutput, TransactionWithProof,
     },
 };
 use bcs::to_bytes;
@@ -371,6 +371,9 @@ pub struct TransactionInfo {
     pub accumulator_root_hash: HashValue,
     /// Final state of resources changed by the transaction
     pub changes: Vec<WriteSetChange>,
+    /// Typed records attached by the VM, these are not part of the transaction hash
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub auxiliary_records: Option<Vec<TransactionAuxiliaryRecord>>,
     /// Block height that the transaction belongs in, this field will not be present through the API
     #[oai(skip)]
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -381,6 +384,28 @@ pub struct TransactionInfo {
     pub epoch: Option<U64>,
 }
 
+/// A typed record attached to a transaction by the VM
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Object)]
+pub struct TransactionAuxiliaryRecord {
+    /// The kind of the record, e.g. `fee_breakdown`
+    pub kind: String,
+    pub version: u16,
+    /// The JSON representation of the record
+    pub data: serde_json::Value,
+}
+
+impl TryFrom<&AuxiliaryRecord> for TransactionAuxiliaryRecord {
+    type Error = anyhow::Error;
+
+    fn try_from(record: &AuxiliaryRecord) -> Result<Self> {
+        Ok(Self {
+            kind: record.kind().name().to_string(),
+            version: record.version(),
+            data: record.to_json()?,
+        })
+    }
+}
+
 /// A transaction waiting in mempool
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Object)]
 pub struct PendingTransaction {
@@ -2398,4 +2423,32 @@ pub struct GasEstimation {
     pub gas_estimate: u64,
     /// The prioritized estimate for the gas unit price
     pub prioritized_gas_estimate: Option<u64>,
+    /// The lower bound of the confidence interval around the current estimate.
+    /// Absent if the estimate was not computed from recent blocks.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[oai(skip_serializing_if_is_none)]
+    pub gas_estimate_lower_bound: Option<u64>,
+    /// The upper bound of the confidence interval around the current estimate.
+    /// Absent if the estimate was not computed from recent blocks.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[oai(skip_serializing_if_is_none)]
+    pub gas_estimate_upper_bound: Option<u64>,
+}
+
+/// Struct holding the outputs of the estimate gas API for a specific entry function
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Object)]
+pub struct EntryFunctionGasEstimation {
+    /// The entry function the estimation is for
+    pub function: EntryFunctionId,
+    /// The number of recently committed calls the estimation is based on
+    pub sample_size: u64,
+    /// The suggested max gas amount, covering nearly all recent calls with a margin.
+    /// Absent if there are no recent calls.
+    pub max_gas_amount: Option<u64>,
+    /// The median gas unit price paid by recent calls
+    pub gas_unit_price_p50: u64,
+    /// The 90th percentile gas unit price paid by recent calls
+    pub gas_unit_price_p90: u64,
+    /// The 99th percentile gas unit price paid by recent calls
+    pub gas_unit_price_p99: u64,
 }
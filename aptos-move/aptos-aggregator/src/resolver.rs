@@ -121,7 +121,7 @@ where
         state_key: &Self::Identifier,
     ) -> PartialVMResult<Option<StateValue>> {
         self.get_state_value(state_key).map_err(|e| {
-            PartialVMError::new(StatusCode::STORAGE_ERROR).with_message(format!(
+            PartialVMError::new(e.status_code()).with_message(format!(
                 "Aggregator value not found for {:?}: {:?}",
                 state_key, e
             ))
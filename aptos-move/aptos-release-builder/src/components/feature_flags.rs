@@ -150,6 +150,10 @@ pub enum FeatureFlag {
     CalculateTransactionFeeForDistribution,
     DistributeTransactionFee,
     MonotonicallyIncreasingCounter,
+    WebAuthnEd25519Signature,
+    SponsorshipQuotas,
+    Precompiles,
+    WebAuthnAssertionCeremony,
 }
 
 fn generate_features_blob(writer: &CodeWriter, data: &[u64]) {
@@ -399,6 +403,10 @@ impl From<FeatureFlag> for AptosFeatureFlag {
             FeatureFlag::MonotonicallyIncreasingCounter => {
                 AptosFeatureFlag::MONOTONICALLY_INCREASING_COUNTER
             },
+            FeatureFlag::WebAuthnEd25519Signature => AptosFeatureFlag::WEBAUTHN_ED25519_SIGNATURE,
+            FeatureFlag::SponsorshipQuotas => AptosFeatureFlag::SPONSORSHIP_QUOTAS,
+            FeatureFlag::Precompiles => AptosFeatureFlag::PRECOMPILES,
+            FeatureFlag::WebAuthnAssertionCeremony => AptosFeatureFlag::WEBAUTHN_ASSERTION_CEREMONY,
         }
     }
 }
@@ -575,6 +583,10 @@ impl From<AptosFeatureFlag> for FeatureFlag {
             AptosFeatureFlag::MONOTONICALLY_INCREASING_COUNTER => {
                 FeatureFlag::MonotonicallyIncreasingCounter
             },
+            AptosFeatureFlag::WEBAUTHN_ED25519_SIGNATURE => FeatureFlag::WebAuthnEd25519Signature,
+            AptosFeatureFlag::SPONSORSHIP_QUOTAS => FeatureFlag::SponsorshipQuotas,
+            AptosFeatureFlag::PRECOMPILES => FeatureFlag::Precompiles,
+            AptosFeatureFlag::WEBAUTHN_ASSERTION_CEREMONY => FeatureFlag::WebAuthnAssertionCeremony,
         }
     }
 }
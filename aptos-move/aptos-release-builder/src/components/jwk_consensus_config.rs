@@ -40,6 +40,18 @@ pub fn generate_jwk_consensus_config_update_proposal(
                         signer_arg
                     );
                 },
+                OnChainJWKConsensusConfig::V2(v2) => {
+                    emitln!(writer, "let config = jwk_consensus_config::new_v2(vector[");
+                    for p in v2.oidc_providers.iter() {
+                        emitln!(writer, "jwk_consensus_config::new_oidc_provider_v2(utf8(b\"{}\"), utf8(b\"{}\"), {}, {}, utf8(b\"{}\")),", p.name, p.config_url, p.config_url_kind, p.fetch_interval_secs, p.default_alg);
+                    }
+                    emitln!(writer, "]);");
+                    emitln!(
+                        writer,
+                        "jwk_consensus_config::set_for_next_epoch({}, config);",
+                        signer_arg
+                    );
+                },
             }
             emitln!(writer, "aptos_governance::reconfigure({});", signer_arg);
         },
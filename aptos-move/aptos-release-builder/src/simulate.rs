@@ -19,6 +19,10 @@
 //! In other words, this simulation is intended for checking whether a governance
 //! proposal will execute successfully, assuming it gets approved, not whether the
 //! governance framework itself is working as intended.
+//!
+//! Once all scripts of a proposal have been executed, the resources under `0x1` that the
+//! proposal changed (e.g., on-chain configs) are reported as a diff against the original
+//! chain state.
 
 use crate::aptos_framework_path;
 use anyhow::{anyhow, bail, Context, Result};
@@ -30,17 +34,23 @@ use aptos_gas_profiling::GasProfiler;
 use aptos_gas_schedule::{AptosGasParameters, FromOnChainGasSchedule};
 use aptos_language_e2e_tests::account::AccountData;
 use aptos_move_debugger::aptos_debugger::AptosDebugger;
+use aptos_resource_viewer::AptosValueAnnotator;
 use aptos_rest_client::{AptosBaseUrl, Client};
 use aptos_transaction_simulation::{DeltaStateStore, SimulationStateStore};
 use aptos_types::{
+    access_path::Path as AccessPathType,
     account_address::AccountAddress,
     account_config::ChainIdResource,
     on_chain_config::{ApprovedExecutionHashes, GasScheduleV2, OnChainConfig},
-    state_store::TStateView,
+    state_store::{
+        state_key::{inner::StateKeyInner, StateKey},
+        TStateView,
+    },
     transaction::{
         AuxiliaryInfo, ExecutionStatus, PersistedAuxiliaryInfo, Script, TransactionArgument,
         TransactionStatus,
     },
+    write_set::WriteSet,
 };
 use aptos_vm::{data_cache::AsMoveResolver, move_vm_ext::SessionId, AptosVM};
 use aptos_vm_environment::environment::AptosEnvironment;
@@ -50,6 +60,7 @@ use aptos_vm_types::{
     storage::change_set_configs::ChangeSetConfigs,
 };
 use clap::Parser;
+use difference::{Changeset, Difference};
 use move_binary_format::{
     access::ModuleAccess,
     file_format::{
@@ -61,13 +72,14 @@ use move_binary_format::{
 };
 use move_core_types::{
     identifier::{IdentStr, Identifier},
-    language_storage::ModuleId,
+    language_storage::{ModuleId, StructTag},
     value::MoveValue,
 };
 use move_vm_runtime::module_traversal::{TraversalContext, TraversalStorage};
 use move_vm_types::gas::UnmeteredGasMeter;
 use once_cell::sync::Lazy;
 use std::{
+    collections::HashSet,
     io::Write,
     path::{Path, PathBuf},
 };
@@ -324,7 +336,7 @@ fn add_script_execution_hash(
  * Simulation Workflow
  *
  **************************************************************************************************/
-fn force_end_epoch(state_view: &impl SimulationStateStore) -> Result<()> {
+fn force_end_epoch(state_view: &impl SimulationStateStore) -> Result<WriteSet> {
     let env = AptosEnvironment::new_with_injected_create_signer_for_gov_sim(&state_view);
     let vm = AptosVM::new(&env, &state_view);
     let resolver = state_view.as_move_resolver();
@@ -360,6 +372,74 @@ fn force_end_epoch(state_view: &impl SimulationStateStore) -> Result<()> {
 
     state_view.apply_write_set(&write_set)?;
 
+    Ok(write_set)
+}
+
+/// Renders the resource stored under the given key, or an empty string if it does not exist.
+fn view_resource(
+    state_view: &impl TStateView<Key = StateKey>,
+    state_key: &StateKey,
+    tag: &StructTag,
+) -> Result<String> {
+    Ok(match state_view.get_state_value_bytes(state_key)? {
+        Some(bytes) => AptosValueAnnotator::new(state_view)
+            .view_resource(tag, &bytes)?
+            .to_string(),
+        None => String::new(),
+    })
+}
+
+/// Prints a line diff for each of the given keys that holds a resource under `0x1` and whose
+/// value differs between the two state views.
+fn print_framework_resource_diffs(
+    base_state_view: &impl TStateView<Key = StateKey>,
+    state_view: &impl TStateView<Key = StateKey>,
+    state_keys: &HashSet<StateKey>,
+) -> Result<()> {
+    let mut resources = state_keys
+        .iter()
+        .filter_map(|state_key| match state_key.inner() {
+            StateKeyInner::AccessPath(access_path)
+                if access_path.address == AccountAddress::ONE =>
+            {
+                match access_path.get_path() {
+                    AccessPathType::Resource(tag) => Some((tag, state_key)),
+                    _ => None,
+                }
+            },
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    resources.sort_by_key(|(tag, _)| tag.to_string());
+
+    println!("On-chain resource changes:");
+    let mut num_changes = 0;
+    for (tag, state_key) in resources {
+        let before = view_resource(base_state_view, state_key, &tag)
+            .with_context(|| format!("failed to view the original {}", tag))?;
+        let after = view_resource(state_view, state_key, &tag)
+            .with_context(|| format!("failed to view the updated {}", tag))?;
+        if before == after {
+            continue;
+        }
+
+        num_changes += 1;
+        println!("    {}", tag);
+        for diff in Changeset::new(&before, &after, "\n").diffs {
+            let (prefix, lines) = match diff {
+                Difference::Same(_) => continue,
+                Difference::Add(lines) => ("+", lines),
+                Difference::Rem(lines) => ("-", lines),
+            };
+            for line in lines.lines() {
+                println!("        {} {}", prefix, line);
+            }
+        }
+    }
+    if num_changes == 0 {
+        println!("    None");
+    }
+
     Ok(())
 }
 
@@ -411,6 +491,7 @@ pub async fn simulate_multistep_proposal(
         AptosDebugger::rest_client(client.clone()).context("failed to create AptosDebugger")?;
     let state = client.get_ledger_information().await?.into_inner();
 
+    let base_state_view = debugger.state_view_at_version(state.version);
     let state_view = DeltaStateStore::new_with_base(debugger.state_view_at_version(state.version));
 
     // Create and fund a sender account that is used to send the governance scripts.
@@ -426,6 +507,8 @@ pub async fn simulate_multistep_proposal(
     // Execute the governance scripts in sorted order.
     println!("Executing governance scripts...");
 
+    let mut changed_state_keys = HashSet::new();
+
     for (script_idx, (script_path, (script_blob, script_hash))) in
         proposal_scripts.iter().zip(compiled_scripts).enumerate()
     {
@@ -570,13 +653,57 @@ pub async fn simulate_multistep_proposal(
 
         let (write_set, _events) = txn_output.into();
         state_view.apply_write_set(&write_set)?;
+        changed_state_keys.extend(write_set.write_op_iter().map(|(key, _)| key.clone()));
     }
 
     println!("All scripts succeeded!");
 
+    // Force-end the epoch once more, so that configuration changes buffered by the last script
+    // get applied before the diff is taken.
+    let write_set = force_end_epoch(&state_view).context("failed to force end epoch")?;
+    changed_state_keys.extend(write_set.write_op_iter().map(|(key, _)| key.clone()));
+    print_framework_resource_diffs(&base_state_view, &state_view, &changed_state_keys)?;
+
     Ok(())
 }
 
+/// Simulates the governance proposals under a directory against the current state of a
+/// network, as if they had already been approved, and reports the resulting on-chain changes.
+#[derive(Parser)]
+pub struct SimulateProposals {
+    /// Directory that may contain one or more proposals at any level
+    /// within its sub-directory hierarchy.
+    #[clap(short, long)]
+    path: PathBuf,
+
+    /// Url of the rest endpoint of the network to simulate on,
+    /// e.g. https://fullnode.mainnet.aptoslabs.com
+    #[clap(long)]
+    rest_endpoint: Url,
+
+    /// Set this flag to enable the gas profiler
+    #[clap(long, default_value_t = false)]
+    profile_gas: bool,
+
+    /// Key to use for ratelimiting purposes with the node API. This value will be used
+    /// as `Authorization: Bearer <key>`. You may also set this with the NODE_API_KEY
+    /// environment variable.
+    #[clap(long, env)]
+    node_api_key: Option<String>,
+}
+
+impl SimulateProposals {
+    pub async fn run(self) -> Result<()> {
+        simulate_all_proposals(
+            self.rest_endpoint,
+            &self.path,
+            self.profile_gas,
+            self.node_api_key,
+        )
+        .await
+    }
+}
+
 pub fn collect_proposals(root_dir: &Path) -> Result<Vec<(PathBuf, Vec<PathBuf>)>> {
     let mut result = Vec::new();
 
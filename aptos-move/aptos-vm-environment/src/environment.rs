@@ -13,9 +13,11 @@ use aptos_gas_algebra::DynamicExpression;
 use aptos_gas_schedule::{AptosGasParameters, MiscGasParameters, NativeGasParameters};
 use aptos_native_interface::SafeNativeBuilder;
 use aptos_types::{
+    account_address::AccountAddress,
     chain_id::ChainId,
     on_chain_config::{
-        ConfigurationResource, Features, OnChainConfig, TimedFeatures, TimedFeaturesBuilder,
+        ConfigurationResource, FeatureActivationSchedule, FeatureFlag, Features, OnChainConfig,
+        TimedFeatures, TimedFeaturesBuilder,
     },
     state_store::StateView,
 };
@@ -75,6 +77,16 @@ impl AptosEnvironment {
         &self.0.features
     }
 
+    /// Returns true if the feature is enabled for the account in this environment, which is the
+    /// case for all accounts if it's enabled in [Features], and for a percentage of them if it's
+    /// partially rolled out.
+    #[inline]
+    pub fn is_feature_enabled_for(&self, flag: FeatureFlag, account: &AccountAddress) -> bool {
+        self.0
+            .feature_activation_schedule
+            .is_enabled_for(&self.0.features, flag, account)
+    }
+
     /// Returns the [TimedFeatures] used by this environment.
     #[inline]
     pub fn timed_features(&self) -> &TimedFeatures {
@@ -148,6 +160,8 @@ struct Environment {
 
     /// Set of features enabled in this environment.
     features: Features,
+    /// Feature flags that are only enabled for a percentage of the accounts in this environment.
+    feature_activation_schedule: FeatureActivationSchedule,
     /// Set of timed features enabled in this environment.
     timed_features: TimedFeatures,
 
@@ -194,6 +208,9 @@ impl Environment {
             fetch_config_and_update_hash::<ConfigurationResource>(&mut sha3_256, state_view)
                 .map(|config| config.last_reconfiguration_time_micros())
                 .unwrap_or(0);
+        let feature_activation_schedule =
+            fetch_config_and_update_hash::<FeatureActivationSchedule>(&mut sha3_256, state_view)
+                .unwrap_or_default();
 
         let mut timed_features_builder = TimedFeaturesBuilder::new(chain_id, timestamp_micros);
         if let Some(profile) = get_timed_feature_override() {
@@ -253,6 +270,7 @@ impl Environment {
         Self {
             chain_id,
             features,
+            feature_activation_schedule,
             timed_features,
             gas_feature_version,
             gas_params,
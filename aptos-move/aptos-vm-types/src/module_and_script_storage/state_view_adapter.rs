@@ -62,7 +62,7 @@ where
         let state_key = StateKey::module(address, module_name);
         self.state_view
             .get_state_value_bytes(&state_key)
-            .map_err(|e| module_storage_error!(address, module_name, e))
+            .map_err(|e| module_storage_error!(address, module_name, e, e.status_code()))
     }
 }
 
@@ -172,7 +172,9 @@ impl<S: StateView, E: WithRuntimeEnvironment> AptosModuleStorage
             .byte_storage()
             .state_view
             .get_state_value(&state_key)
-            .map_err(|err| module_storage_error!(address, module_name, err).to_partial())?
+            .map_err(|err| {
+                module_storage_error!(address, module_name, err, err.status_code()).to_partial()
+            })?
             .map(|state_value| state_value.into_metadata()))
     }
 }
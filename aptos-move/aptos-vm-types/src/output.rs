@@ -14,7 +14,9 @@ use aptos_types::{
     error::{code_invariant_error, PanicError},
     fee_statement::FeeStatement,
     state_store::state_key::StateKey,
-    transaction::{TransactionAuxiliaryData, TransactionOutput, TransactionStatus},
+    transaction::{
+        TAuxiliaryRecord, TransactionAuxiliaryData, TransactionOutput, TransactionStatus,
+    },
     write_set::WriteOp,
 };
 use move_core_types::{
@@ -34,6 +36,7 @@ pub struct VMOutput {
     module_write_set: ModuleWriteSet,
     fee_statement: FeeStatement,
     status: TransactionStatus,
+    auxiliary_data: TransactionAuxiliaryData,
 }
 
 impl VMOutput {
@@ -48,6 +51,7 @@ impl VMOutput {
             module_write_set,
             fee_statement,
             status,
+            auxiliary_data: TransactionAuxiliaryData::default(),
         }
     }
 
@@ -57,6 +61,7 @@ impl VMOutput {
             module_write_set: ModuleWriteSet::empty(),
             fee_statement: FeeStatement::zero(),
             status,
+            auxiliary_data: TransactionAuxiliaryData::default(),
         }
     }
 
@@ -98,6 +103,21 @@ impl VMOutput {
         &self.status
     }
 
+    pub fn auxiliary_data(&self) -> &TransactionAuxiliaryData {
+        &self.auxiliary_data
+    }
+
+    /// Attaches an auxiliary record to the output. Records are not part of the transaction hash,
+    /// but are persisted, so they must be computed deterministically.
+    pub fn add_auxiliary_record<T: TAuxiliaryRecord>(
+        &mut self,
+        record: &T,
+    ) -> Result<(), PanicError> {
+        self.auxiliary_data
+            .add_record(record)
+            .map_err(|e| code_invariant_error(format!("Invalid auxiliary record: {}", e)))
+    }
+
     pub fn materialized_size(&self) -> u64 {
         let mut size = 0;
         for (state_key, write_size) in self
@@ -171,6 +191,7 @@ impl VMOutput {
             module_write_set,
             fee_statement,
             status,
+            auxiliary_data,
         } = self;
         let (write_set, events) = change_set
             .try_combine_into_storage_change_set(module_write_set)?
@@ -180,7 +201,7 @@ impl VMOutput {
             events,
             fee_statement.gas_used(),
             status,
-            TransactionAuxiliaryData::default(),
+            auxiliary_data,
         ))
     }
 
@@ -15,7 +15,7 @@ use aptos_types::{
 };
 use bytes::Bytes;
 use move_binary_format::errors::{PartialVMError, PartialVMResult};
-use move_core_types::{language_storage::StructTag, value::MoveTypeLayout, vm_status::StatusCode};
+use move_core_types::{language_storage::StructTag, value::MoveTypeLayout};
 use move_vm_types::delayed_values::delayed_field_id::DelayedFieldID;
 use std::collections::{BTreeMap, HashMap};
 
@@ -242,8 +242,8 @@ where
     }
 }
 
-fn map_storage_error<E: std::fmt::Debug>(state_key: &StateKey, e: E) -> PartialVMError {
-    PartialVMError::new(StatusCode::STORAGE_ERROR).with_message(format!(
+fn map_storage_error(state_key: &StateKey, e: StateViewError) -> PartialVMError {
+    PartialVMError::new(e.status_code()).with_message(format!(
         "Unexpected storage error for resource at {:?}: {:?}",
         state_key, e
     ))
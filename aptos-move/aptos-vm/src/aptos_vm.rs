@@ -3,6 +3,10 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
+    authenticator_dispatch::{
+        check_authenticator_enabled, check_webauthn_assertions, verify_account_authenticator,
+        AuthScheme,
+    },
     block_executor::{AptosTransactionOutput, AptosVMBlockExecutorWrapper},
     counters::*,
     data_cache::{AsMoveResolver, StorageAdapter},
@@ -52,7 +56,7 @@ use aptos_types::{
     block_executor::{
         config::{
             BlockExecutorConfig, BlockExecutorConfigFromOnchain, BlockExecutorLocalConfig,
-            BlockExecutorModuleCacheLocalConfig,
+            BlockExecutorModuleCacheLocalConfig, HotAccountDetectionConfig,
         },
         partitioner::PartitionedTransactions,
         transaction_slice_metadata::TransactionSliceMetadata,
@@ -71,7 +75,7 @@ use aptos_types::{
     randomness::Randomness,
     state_store::{StateView, TStateView},
     transaction::{
-        authenticator::{AbstractionAuthData, AnySignature, AuthenticationProof},
+        authenticator::{AbstractionAuthData, AuthenticationProof},
         block_epilogue::{BlockEpiloguePayload, FeeDistribution},
         signature_verified_transaction::SignatureVerifiedTransaction,
         AuxiliaryInfo, AuxiliaryInfoTrait, BlockOutput, EntryFunction, ExecutionError,
@@ -155,6 +159,7 @@ static NUM_EXECUTION_SHARD: OnceCell<usize> = OnceCell::new();
 static NUM_PROOF_READING_THREADS: OnceCell<usize> = OnceCell::new();
 static DISCARD_FAILED_BLOCKS: OnceCell<bool> = OnceCell::new();
 static PROCESSED_TRANSACTIONS_DETAILED_COUNTERS: OnceCell<bool> = OnceCell::new();
+static HOT_ACCOUNT_DETECTION: OnceCell<bool> = OnceCell::new();
 
 macro_rules! deprecated_module_bundle {
     () => {
@@ -428,6 +433,21 @@ impl AptosVM {
         }
     }
 
+    /// Sets whether transactions of a hot sender are executed in a sequential lane, when invoked
+    /// the first time.
+    pub fn set_hot_account_detection(enable: bool) {
+        // Only the first call succeeds, due to OnceCell semantics.
+        HOT_ACCOUNT_DETECTION.set(enable).ok();
+    }
+
+    /// Get the hot account detection flag if already set, otherwise return default (false)
+    pub fn get_hot_account_detection() -> bool {
+        match HOT_ACCOUNT_DETECTION.get() {
+            Some(enable) => *enable,
+            None => false,
+        }
+    }
+
     /// Sets the # of async proof reading threads.
     pub fn set_num_proof_reading_threads_once(mut num_threads: usize) {
         // TODO(grao): Do more analysis to tune this magic number.
@@ -1708,6 +1728,12 @@ impl AptosVM {
             ));
         }
 
+        check_authenticator_enabled(transaction.authenticator_ref(), self.features())
+            .and_then(|()| {
+                check_webauthn_assertions(transaction.authenticator_ref(), self.features())
+            })
+            .map_err(|status_code| VMStatus::error(status_code, None))?;
+
         let keyless_authenticators = aptos_types::keyless::get_authenticators(transaction)
             .map_err(|_| VMStatus::error(StatusCode::INVALID_SIGNATURE, None))?;
 
@@ -1733,14 +1759,7 @@ impl AptosVM {
                     function_info,
                     auth_data,
                 }) => {
-                    let enabled = match auth_data {
-                        AbstractionAuthData::V1 { .. } => {
-                            self.features().is_account_abstraction_enabled()
-                        },
-                        AbstractionAuthData::DerivableV1 { .. } => {
-                            self.features().is_derivable_account_abstraction_enabled()
-                        },
-                    };
+                    let enabled = AuthScheme::of_abstraction(auth_data).is_enabled(self.features());
                     if enabled {
                         dispatchable_authenticate(
                             session,
@@ -1773,14 +1792,7 @@ impl AptosVM {
                     function_info,
                     auth_data,
                 } => {
-                    let enabled = match auth_data {
-                        AbstractionAuthData::V1 { .. } => {
-                            self.features().is_account_abstraction_enabled()
-                        },
-                        AbstractionAuthData::DerivableV1 { .. } => {
-                            self.features().is_derivable_account_abstraction_enabled()
-                        },
-                    };
+                    let enabled = AuthScheme::of_abstraction(auth_data).is_enabled(self.features());
                     if enabled {
                         dispatchable_authenticate(
                             session,
@@ -1820,8 +1832,13 @@ impl AptosVM {
             ));
         }
 
-        if !self.features().is_orderless_txns_enabled() {
-            if let ReplayProtector::Nonce(_) = transaction.replay_protector() {
+        // Orderless transactions can be rolled out to a percentage of the senders first.
+        if let ReplayProtector::Nonce(_) = transaction.replay_protector() {
+            if !self
+                .move_vm
+                .env
+                .is_feature_enabled_for(FeatureFlag::ORDERLESS_TRANSACTIONS, &transaction.sender())
+            {
                 return Err(VMStatus::error(
                     StatusCode::FEATURE_UNDER_GATING,
                     Some("Orderless transactions are not yet supported".to_string()),
@@ -2213,7 +2230,7 @@ impl AptosVM {
         // form the transaction payload).
         for state_key in module_write_set.writes().keys() {
             executor_view.read_state_value(state_key).map_err(|err| {
-                PartialVMError::new(StatusCode::STORAGE_ERROR)
+                PartialVMError::new(err.status_code())
                     .with_message(format!("Cannot read module at {:?}: {:?}", state_key, err))
             })?;
         }
@@ -2963,6 +2980,8 @@ impl VMBlockExecutor for AptosVMBlockExecutor {
                 allow_fallback: true,
                 discard_failed_blocks: AptosVM::get_discard_failed_blocks(),
                 module_cache_config: BlockExecutorModuleCacheLocalConfig::default(),
+                hot_account_detection: AptosVM::get_hot_account_detection()
+                    .then(HotAccountDetectionConfig::default),
             },
             onchain: onchain_config,
         };
@@ -3018,28 +3037,10 @@ impl VMValidator for AptosVM {
         let _timer = TXN_VALIDATION_SECONDS.start_timer();
         let log_context = AdapterLogSchema::new(state_view.id(), 0);
 
-        if !self
-            .features()
-            .is_enabled(FeatureFlag::SINGLE_SENDER_AUTHENTICATOR)
+        if let Err(status_code) =
+            check_authenticator_enabled(transaction.authenticator_ref(), self.features())
         {
-            if let aptos_types::transaction::authenticator::TransactionAuthenticator::SingleSender{ .. } = transaction.authenticator_ref() {
-                return VMValidatorResult::error(StatusCode::FEATURE_UNDER_GATING);
-            }
-        }
-
-        if !self.features().is_enabled(FeatureFlag::WEBAUTHN_SIGNATURE) {
-            if let Ok(sk_authenticators) = transaction
-                .authenticator_ref()
-                .to_single_key_authenticators()
-            {
-                for authenticator in sk_authenticators {
-                    if let AnySignature::WebAuthn { .. } = authenticator.signature() {
-                        return VMValidatorResult::error(StatusCode::FEATURE_UNDER_GATING);
-                    }
-                }
-            } else {
-                return VMValidatorResult::error(StatusCode::INVALID_SIGNATURE);
-            }
+            return VMValidatorResult::error(status_code);
         }
 
         if !self
@@ -3057,7 +3058,7 @@ impl VMValidator for AptosVM {
             }
         }
 
-        let txn = match transaction.check_signature() {
+        let txn = match transaction.check_signature_with(verify_account_authenticator) {
             Ok(t) => t,
             _ => {
                 return VMValidatorResult::error(StatusCode::INVALID_SIGNATURE);
@@ -0,0 +1,500 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Dispatch table for the authentication schemes a transaction can use.
+//!
+//! Every scheme has a single entry below, which lists the on-chain features it requires and how
+//! its signatures are verified. Adding a new scheme means adding a variant to [`AuthScheme`], its
+//! entry in [`AUTH_SCHEME_HANDLERS`], and mapping the authenticators that use it in
+//! [`AuthScheme::of_signature`] or [`AuthScheme::of_account_authenticator`].
+
+use anyhow::bail;
+use aptos_crypto::{
+    ed25519::{Ed25519PublicKey, Ed25519Signature},
+    multi_ed25519::{MultiEd25519PublicKey, MultiEd25519Signature},
+    traits::Signature,
+};
+use aptos_types::{
+    on_chain_config::{FeatureFlag, Features},
+    transaction::{
+        authenticator::{
+            AbstractionAuthData, AccountAuthenticator, AnySignature, EphemeralSignature,
+            SingleKeyAuthenticator, TransactionAuthenticator, TransactionSigningMessage,
+        },
+        webauthn::{AssertionSignature, PartialAuthenticatorAssertionResponse},
+    },
+};
+use move_core_types::vm_status::StatusCode;
+
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum AuthScheme {
+    Ed25519,
+    MultiEd25519,
+    Secp256k1Ecdsa,
+    /// WebAuthn assertion as the transaction signature.
+    WebAuthn,
+    /// WebAuthn assertion signed with an Ed25519 key, either as the transaction signature or as
+    /// the ephemeral signature of a keyless account.
+    WebAuthnEd25519,
+    Keyless,
+    /// Authentication by a Move function registered by the account.
+    Abstraction,
+    /// Authentication by a Move function registered for a domain, for derived accounts.
+    DerivableAbstraction,
+}
+
+/// A signature to verify with the handler of its scheme.
+#[derive(Clone, Copy, Debug)]
+pub enum SchemeSignature<'a> {
+    Ed25519 {
+        public_key: &'a Ed25519PublicKey,
+        signature: &'a Ed25519Signature,
+    },
+    MultiEd25519 {
+        public_key: &'a MultiEd25519PublicKey,
+        signature: &'a MultiEd25519Signature,
+    },
+    SingleKey(&'a SingleKeyAuthenticator),
+    Abstraction(&'a AccountAuthenticator),
+}
+
+pub struct AuthSchemeHandler {
+    pub scheme: AuthScheme,
+    /// All of these need to be enabled for the scheme to be accepted.
+    pub required_features: &'static [FeatureFlag],
+    /// Verifies a signature of the scheme over the message signed by its authenticator.
+    pub verify: fn(SchemeSignature<'_>, TransactionSigningMessage<'_>) -> anyhow::Result<()>,
+}
+
+impl AuthSchemeHandler {
+    pub fn is_enabled(&self, features: &Features) -> bool {
+        self.required_features
+            .iter()
+            .all(|feature| features.is_enabled(*feature))
+    }
+}
+
+pub static AUTH_SCHEME_HANDLERS: &[AuthSchemeHandler] = &[
+    AuthSchemeHandler {
+        scheme: AuthScheme::Ed25519,
+        required_features: &[],
+        verify: verify_ed25519,
+    },
+    AuthSchemeHandler {
+        scheme: AuthScheme::MultiEd25519,
+        required_features: &[],
+        verify: verify_multi_ed25519,
+    },
+    AuthSchemeHandler {
+        scheme: AuthScheme::Secp256k1Ecdsa,
+        required_features: &[],
+        verify: verify_single_key,
+    },
+    AuthSchemeHandler {
+        scheme: AuthScheme::WebAuthn,
+        required_features: &[FeatureFlag::WEBAUTHN_SIGNATURE],
+        verify: verify_single_key,
+    },
+    AuthSchemeHandler {
+        scheme: AuthScheme::WebAuthnEd25519,
+        required_features: &[FeatureFlag::WEBAUTHN_ED25519_SIGNATURE],
+        verify: verify_single_key,
+    },
+    // The ZK and OpenID flavors are gated separately during keyless validation, which also
+    // verifies the OpenID signature in the prologue.
+    AuthSchemeHandler {
+        scheme: AuthScheme::Keyless,
+        required_features: &[],
+        verify: verify_single_key,
+    },
+    // Abstracted accounts authenticate by running a Move function in the prologue, only the
+    // digest of the signing message is checked here.
+    AuthSchemeHandler {
+        scheme: AuthScheme::Abstraction,
+        required_features: &[FeatureFlag::ACCOUNT_ABSTRACTION],
+        verify: verify_abstraction,
+    },
+    AuthSchemeHandler {
+        scheme: AuthScheme::DerivableAbstraction,
+        required_features: &[FeatureFlag::DERIVABLE_ACCOUNT_ABSTRACTION],
+        verify: verify_abstraction,
+    },
+];
+
+/// Calls `$body` with `$message` bound to the message inside a `TransactionSigningMessage`.
+macro_rules! with_signing_message {
+    ($signing_message:expr, |$message:ident| $body:expr) => {
+        match $signing_message {
+            TransactionSigningMessage::RawTransaction($message) => $body,
+            TransactionSigningMessage::RawTransactionWithData($message) => $body,
+        }
+    };
+}
+
+fn verify_ed25519(
+    signature: SchemeSignature<'_>,
+    message: TransactionSigningMessage<'_>,
+) -> anyhow::Result<()> {
+    match signature {
+        SchemeSignature::Ed25519 {
+            public_key,
+            signature,
+        } => with_signing_message!(message, |message| signature.verify(message, public_key)),
+        SchemeSignature::SingleKey(authenticator) => {
+            verify_single_key(SchemeSignature::SingleKey(authenticator), message)
+        },
+        _ => bail!("Not an Ed25519 signature: {:?}", signature),
+    }
+}
+
+fn verify_multi_ed25519(
+    signature: SchemeSignature<'_>,
+    message: TransactionSigningMessage<'_>,
+) -> anyhow::Result<()> {
+    match signature {
+        SchemeSignature::MultiEd25519 {
+            public_key,
+            signature,
+        } => with_signing_message!(message, |message| signature.verify(message, public_key)),
+        _ => bail!("Not a MultiEd25519 signature: {:?}", signature),
+    }
+}
+
+fn verify_single_key(
+    signature: SchemeSignature<'_>,
+    message: TransactionSigningMessage<'_>,
+) -> anyhow::Result<()> {
+    match signature {
+        SchemeSignature::SingleKey(authenticator) => {
+            with_signing_message!(message, |message| authenticator.verify(message))
+        },
+        _ => bail!("Not a single key signature: {:?}", signature),
+    }
+}
+
+fn verify_abstraction(
+    signature: SchemeSignature<'_>,
+    message: TransactionSigningMessage<'_>,
+) -> anyhow::Result<()> {
+    match signature {
+        SchemeSignature::Abstraction(authenticator) => {
+            with_signing_message!(message, |message| authenticator.verify(message))
+        },
+        _ => bail!("Not an abstraction authenticator: {:?}", signature),
+    }
+}
+
+impl AuthScheme {
+    pub fn handler(&self) -> &'static AuthSchemeHandler {
+        AUTH_SCHEME_HANDLERS
+            .iter()
+            .find(|handler| handler.scheme == *self)
+            .expect("Every authentication scheme has a handler")
+    }
+
+    pub fn is_enabled(&self, features: &Features) -> bool {
+        self.handler().is_enabled(features)
+    }
+
+    pub fn of_abstraction(auth_data: &AbstractionAuthData) -> Self {
+        match auth_data {
+            AbstractionAuthData::V1 { .. } => AuthScheme::Abstraction,
+            AbstractionAuthData::DerivableV1 { .. } => AuthScheme::DerivableAbstraction,
+        }
+    }
+
+    /// The scheme whose handler verifies a single signature.
+    pub fn verifying_signature(signature: &AnySignature) -> Self {
+        match signature {
+            AnySignature::Ed25519 { .. } => AuthScheme::Ed25519,
+            AnySignature::Secp256k1Ecdsa { .. } => AuthScheme::Secp256k1Ecdsa,
+            AnySignature::WebAuthn { .. } => AuthScheme::WebAuthn,
+            AnySignature::Keyless { .. } => AuthScheme::Keyless,
+        }
+    }
+
+    /// The schemes a single signature relies on.
+    pub fn of_signature(signature: &AnySignature) -> Vec<Self> {
+        let is_ed25519_assertion = |assertion: &PartialAuthenticatorAssertionResponse| {
+            matches!(assertion.signature(), AssertionSignature::Ed25519 { .. })
+        };
+        match signature {
+            AnySignature::Ed25519 { .. } => vec![AuthScheme::Ed25519],
+            AnySignature::Secp256k1Ecdsa { .. } => vec![AuthScheme::Secp256k1Ecdsa],
+            AnySignature::WebAuthn { signature } if is_ed25519_assertion(signature) => {
+                vec![AuthScheme::WebAuthn, AuthScheme::WebAuthnEd25519]
+            },
+            AnySignature::WebAuthn { .. } => vec![AuthScheme::WebAuthn],
+            AnySignature::Keyless { signature } => match &signature.ephemeral_signature {
+                EphemeralSignature::WebAuthn { signature } if is_ed25519_assertion(signature) => {
+                    vec![AuthScheme::Keyless, AuthScheme::WebAuthnEd25519]
+                },
+                // Passkey backed keyless accounts are gated during keyless validation.
+                _ => vec![AuthScheme::Keyless],
+            },
+        }
+    }
+
+    /// The schemes an account authenticator relies on. Fails for malformed multi-key
+    /// authenticators.
+    pub fn of_account_authenticator(
+        authenticator: &AccountAuthenticator,
+    ) -> anyhow::Result<Vec<Self>> {
+        Ok(match authenticator {
+            AccountAuthenticator::Ed25519 { .. } => vec![AuthScheme::Ed25519],
+            AccountAuthenticator::MultiEd25519 { .. } => vec![AuthScheme::MultiEd25519],
+            AccountAuthenticator::SingleKey { authenticator } => {
+                Self::of_signature(authenticator.signature())
+            },
+            AccountAuthenticator::MultiKey { authenticator } => authenticator
+                .to_single_key_authenticators()?
+                .iter()
+                .flat_map(|authenticator| Self::of_signature(authenticator.signature()))
+                .collect(),
+            AccountAuthenticator::NoAccountAuthenticator => vec![],
+            AccountAuthenticator::Abstraction { auth_data, .. } => {
+                vec![Self::of_abstraction(auth_data)]
+            },
+        })
+    }
+}
+
+fn verify_single_key_authenticator(
+    authenticator: &SingleKeyAuthenticator,
+    message: TransactionSigningMessage<'_>,
+) -> anyhow::Result<()> {
+    let scheme = AuthScheme::verifying_signature(authenticator.signature());
+    (scheme.handler().verify)(SchemeSignature::SingleKey(authenticator), message)
+}
+
+/// Verifies the signatures of an account authenticator with the handlers of their schemes. Used
+/// as the signature check of `SignedTransaction::check_signature_with`, the handlers use the same
+/// primitives as `AccountAuthenticator::verify`, so both accept the same transactions.
+pub fn verify_account_authenticator(
+    authenticator: &AccountAuthenticator,
+    message: TransactionSigningMessage<'_>,
+) -> anyhow::Result<()> {
+    match authenticator {
+        AccountAuthenticator::Ed25519 {
+            public_key,
+            signature,
+        } => (AuthScheme::Ed25519.handler().verify)(
+            SchemeSignature::Ed25519 {
+                public_key,
+                signature,
+            },
+            message,
+        ),
+        AccountAuthenticator::MultiEd25519 {
+            public_key,
+            signature,
+        } => (AuthScheme::MultiEd25519.handler().verify)(
+            SchemeSignature::MultiEd25519 {
+                public_key,
+                signature,
+            },
+            message,
+        ),
+        AccountAuthenticator::SingleKey { authenticator } => {
+            verify_single_key_authenticator(authenticator, message)
+        },
+        AccountAuthenticator::MultiKey { authenticator } => authenticator
+            .to_single_key_authenticators()?
+            .iter()
+            .try_for_each(|authenticator| verify_single_key_authenticator(authenticator, message)),
+        AccountAuthenticator::NoAccountAuthenticator => bail!("No signature to verify."),
+        AccountAuthenticator::Abstraction { auth_data, .. } => {
+            (AuthScheme::of_abstraction(auth_data).handler().verify)(
+                SchemeSignature::Abstraction(authenticator),
+                message,
+            )
+        },
+    }
+}
+
+/// Rejects transactions using authenticators that are not enabled on chain. Only checks gating,
+/// authentication happens in the signature check and the prologue.
+pub fn check_authenticator_enabled(
+    authenticator: &TransactionAuthenticator,
+    features: &Features,
+) -> Result<(), StatusCode> {
+    if let TransactionAuthenticator::SingleSender { .. } = authenticator {
+        if !features.is_enabled(FeatureFlag::SINGLE_SENDER_AUTHENTICATOR) {
+            return Err(StatusCode::FEATURE_UNDER_GATING);
+        }
+    }
+
+    for account_authenticator in authenticator.all_signers() {
+        let schemes = AuthScheme::of_account_authenticator(&account_authenticator)
+            .map_err(|_| StatusCode::INVALID_SIGNATURE)?;
+        if !schemes.iter().all(|scheme| scheme.is_enabled(features)) {
+            return Err(StatusCode::FEATURE_UNDER_GATING);
+        }
+    }
+    Ok(())
+}
+
+/// Requires every WebAuthn assertion of a transaction to come from an assertion ceremony with
+/// the user present, once `WEBAUTHN_ASSERTION_CEREMONY` is enabled. Runs both during validation
+/// and execution, as the signature check itself doesn't know about features.
+pub fn check_webauthn_assertions(
+    authenticator: &TransactionAuthenticator,
+    features: &Features,
+) -> Result<(), StatusCode> {
+    if !features.is_enabled(FeatureFlag::WEBAUTHN_ASSERTION_CEREMONY) {
+        return Ok(());
+    }
+    let authenticators = authenticator
+        .to_single_key_authenticators()
+        .map_err(|_| StatusCode::INVALID_SIGNATURE)?;
+    for authenticator in authenticators {
+        let assertion = match authenticator.signature() {
+            AnySignature::WebAuthn { signature } => signature,
+            AnySignature::Keyless { signature } => match &signature.ephemeral_signature {
+                EphemeralSignature::WebAuthn { signature } => signature,
+                EphemeralSignature::Ed25519 { .. } => continue,
+            },
+            AnySignature::Ed25519 { .. } | AnySignature::Secp256k1Ecdsa { .. } => continue,
+        };
+        if assertion.verify_assertion_ceremony().is_err() {
+            return Err(StatusCode::INVALID_SIGNATURE);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aptos_crypto::{ed25519::Ed25519PrivateKey, PrivateKey, SigningKey, Uniform};
+    use aptos_types::{
+        account_address::AccountAddress,
+        chain_id::ChainId,
+        transaction::{
+            authenticator::AnyPublicKey, webauthn::AuthenticatorData, RawTransaction, Script,
+            SignedTransaction, TransactionPayload,
+        },
+    };
+
+    #[test]
+    fn test_every_scheme_has_one_handler() {
+        for handler in AUTH_SCHEME_HANDLERS {
+            assert_eq!(
+                AUTH_SCHEME_HANDLERS
+                    .iter()
+                    .filter(|other| other.scheme == handler.scheme)
+                    .count(),
+                1
+            );
+            assert_eq!(handler.scheme.handler().scheme, handler.scheme);
+        }
+    }
+
+    fn webauthn_ed25519_transaction(ty: &str, flags: u8) -> SignedTransaction {
+        let private_key = Ed25519PrivateKey::generate_for_testing();
+        let raw_txn = RawTransaction::new(
+            AccountAddress::random(),
+            0,
+            TransactionPayload::Script(Script::new(vec![], vec![], vec![])),
+            0,
+            1,
+            0,
+            ChainId::new(10),
+        );
+        let assertion = PartialAuthenticatorAssertionResponse::ed25519_for_testing(
+            &private_key,
+            &raw_txn,
+            ty,
+            flags,
+        );
+        SignedTransaction::new_single_sender(
+            raw_txn,
+            AccountAuthenticator::single_key(SingleKeyAuthenticator::new(
+                AnyPublicKey::ed25519(private_key.public_key()),
+                AnySignature::webauthn(assertion),
+            )),
+        )
+    }
+
+    fn check_webauthn(txn: &SignedTransaction, features: &Features) -> Result<(), StatusCode> {
+        check_authenticator_enabled(txn.authenticator_ref(), features)?;
+        check_webauthn_assertions(txn.authenticator_ref(), features)
+    }
+
+    #[test]
+    fn test_webauthn_assertions_gating() {
+        let txn =
+            webauthn_ed25519_transaction("webauthn.get", AuthenticatorData::FLAG_USER_PRESENT);
+        txn.verify_signature().unwrap();
+        let no_user_present = webauthn_ed25519_transaction("webauthn.get", 0);
+        let registration =
+            webauthn_ed25519_transaction("webauthn.create", AuthenticatorData::FLAG_USER_PRESENT);
+
+        let mut features = Features::default();
+        features.disable(FeatureFlag::WEBAUTHN_ED25519_SIGNATURE);
+        assert_eq!(
+            check_webauthn(&txn, &features),
+            Err(StatusCode::FEATURE_UNDER_GATING)
+        );
+
+        features.enable(FeatureFlag::WEBAUTHN_ED25519_SIGNATURE);
+        assert_eq!(check_webauthn(&txn, &features), Ok(()));
+        assert_eq!(
+            check_webauthn(&no_user_present, &features),
+            Err(StatusCode::INVALID_SIGNATURE)
+        );
+        assert_eq!(
+            check_webauthn(&registration, &features),
+            Err(StatusCode::INVALID_SIGNATURE)
+        );
+
+        // Chains that accepted these before the ceremony was enforced keep accepting them
+        features.disable(FeatureFlag::WEBAUTHN_ASSERTION_CEREMONY);
+        assert_eq!(check_webauthn(&no_user_present, &features), Ok(()));
+        assert_eq!(check_webauthn(&registration, &features), Ok(()));
+    }
+
+    #[test]
+    fn test_verify_through_handlers() {
+        let private_key = Ed25519PrivateKey::generate_for_testing();
+        let raw_txn = RawTransaction::new(
+            AccountAddress::random(),
+            0,
+            TransactionPayload::Script(Script::new(vec![], vec![], vec![])),
+            0,
+            1,
+            0,
+            ChainId::new(10),
+        );
+        let txn = SignedTransaction::new(
+            raw_txn.clone(),
+            private_key.public_key(),
+            private_key.sign(&raw_txn).unwrap(),
+        );
+        assert!(txn
+            .verify_signature_with(verify_account_authenticator)
+            .is_ok());
+
+        let txn = SignedTransaction::new(
+            raw_txn,
+            private_key.public_key(),
+            private_key.sign_arbitrary_message(b"message"),
+        );
+        assert!(txn
+            .verify_signature_with(verify_account_authenticator)
+            .is_err());
+    }
+
+    #[test]
+    fn test_feature_gating() {
+        let mut features = Features::default();
+        features.disable(FeatureFlag::ACCOUNT_ABSTRACTION);
+        features.disable(FeatureFlag::WEBAUTHN_SIGNATURE);
+        assert!(AuthScheme::Ed25519.is_enabled(&features));
+        assert!(!AuthScheme::Abstraction.is_enabled(&features));
+        assert!(!AuthScheme::WebAuthn.is_enabled(&features));
+
+        features.enable(FeatureFlag::ACCOUNT_ABSTRACTION);
+        assert!(AuthScheme::Abstraction.is_enabled(&features));
+    }
+}
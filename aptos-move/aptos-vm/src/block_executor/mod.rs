@@ -578,6 +578,7 @@ impl<
         );
         match ret {
             Ok(block_output) => {
+                let conflict_stats = block_output.conflict_stats();
                 let (transaction_outputs, block_epilogue_txn) = block_output.into_inner();
                 let output_vec: Vec<_> = transaction_outputs
                     .into_iter()
@@ -593,7 +594,8 @@ impl<
                     flush_speculative_logs(pos);
                 }
 
-                Ok(BlockOutput::new(output_vec, block_epilogue_txn))
+                Ok(BlockOutput::new(output_vec, block_epilogue_txn)
+                    .with_conflict_stats(conflict_stats))
             },
             Err(BlockExecutionError::FatalBlockExecutorError(PanicError::CodeInvariantError(
                 err_msg,
@@ -41,6 +41,8 @@ pub const EINSUFFICIENT_BALANCE_FOR_REQUIRED_DEPOSIT: u64 = 1011;
 pub const ENONCE_ALREADY_USED: u64 = 1012;
 // Transaction expiration time is too far in the future.
 pub const ETRANSACTION_EXPIRATION_TOO_FAR_IN_FUTURE: u64 = 1013;
+// Fee payer's sponsorship quota for the sender or the entry function is exhausted.
+pub const ESPONSORSHIP_QUOTA_EXCEEDED: u64 = 1014;
 
 // Specified account is not a multisig account.
 const EACCOUNT_NOT_MULTISIG: u64 = 2002;
@@ -142,6 +144,9 @@ pub fn convert_prologue_error(
                     StatusCode::TRANSACTION_EXPIRATION_TOO_FAR_IN_FUTURE
                 },
                 (INVALID_ARGUMENT, ENONCE_ALREADY_USED) => StatusCode::NONCE_ALREADY_USED,
+                (LIMIT_EXCEEDED, ESPONSORSHIP_QUOTA_EXCEEDED) => {
+                    StatusCode::SPONSORSHIP_QUOTA_EXCEEDED
+                },
                 (category, reason) => {
                     let err_msg = format!("[aptos_vm] Unexpected prologue Move abort: {:?}::{:?} (Category: {:?} Reason: {:?})",
                     location, code, category, reason);
@@ -107,6 +107,7 @@ pub mod counters;
 pub mod data_cache;
 
 pub mod aptos_vm;
+mod authenticator_dispatch;
 pub mod block_executor;
 mod errors;
 pub mod gas;
@@ -118,7 +118,7 @@ impl<'r> WriteOpConverter<'r> {
                     "Error when enforcing read-before-write for module {}::{}: {:?}",
                     addr, name, err
                 );
-                PartialVMError::new(StatusCode::STORAGE_ERROR).with_message(msg)
+                PartialVMError::new(err.status_code()).with_message(msg)
             })?;
 
             writes.insert(state_key, ModuleWrite::new(module_id, write_op));
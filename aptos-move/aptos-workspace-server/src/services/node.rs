@@ -83,6 +83,9 @@ pub fn start_node(
     node_config.admin_service.address = IP_LOCAL_HOST.to_string();
     node_config.inspection_service.address = IP_LOCAL_HOST.to_string();
 
+    // The workspace server handles the termination signals itself
+    node_config.node_shutdown.graceful_shutdown = false;
+
     let (api_port_tx, api_port_rx) = oneshot::channel();
     let (indexer_grpc_port_tx, indexer_grpc_port_rx) = oneshot::channel();
 
@@ -79,6 +79,24 @@ pub static SPECULATIVE_ABORT_COUNT: Lazy<IntCounter> = Lazy::new(|| {
     .unwrap()
 });
 
+/// Count of blocks executed with a sequential lane for a hot sender.
+pub static HOT_ACCOUNT_LANE_BLOCKS: Lazy<IntCounter> = Lazy::new(|| {
+    register_int_counter!(
+        "aptos_execution_hot_account_lane_blocks",
+        "Number of blocks executed with a sequential lane for a hot sender"
+    )
+    .unwrap()
+});
+
+/// Count of transactions executed in a sequential lane for a hot sender.
+pub static HOT_ACCOUNT_LANE_TXNS: Lazy<IntCounter> = Lazy::new(|| {
+    register_int_counter!(
+        "aptos_execution_hot_account_lane_txns",
+        "Number of transactions executed in a sequential lane for a hot sender"
+    )
+    .unwrap()
+});
+
 /// Count of times the BlockSTM is early halted due to exceeding the per-block gas limit.
 pub static EXCEED_PER_BLOCK_GAS_LIMIT_COUNT: Lazy<IntCounterVec> = Lazy::new(|| {
     register_int_counter_vec!(
@@ -99,6 +117,26 @@ pub static EXCEED_PER_BLOCK_OUTPUT_LIMIT_COUNT: Lazy<IntCounterVec> = Lazy::new(
     .unwrap()
 });
 
+/// Count of times the BlockSTM is early halted due to exceeding the per-block execution gas limit.
+pub static EXCEED_PER_BLOCK_EXECUTION_GAS_LIMIT_COUNT: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "aptos_execution_execution_gas_limit_count",
+        "Count of times the BlockSTM is early halted due to exceeding the per-block execution gas limit",
+        &["mode"]
+    )
+    .unwrap()
+});
+
+/// Count of times the BlockSTM is early halted due to exceeding the per-block IO gas limit.
+pub static EXCEED_PER_BLOCK_IO_GAS_LIMIT_COUNT: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "aptos_execution_io_gas_limit_count",
+        "Count of times the BlockSTM is early halted due to exceeding the per-block IO gas limit",
+        &["mode"]
+    )
+    .unwrap()
+});
+
 pub static PARALLEL_EXECUTION_SECONDS: Lazy<Histogram> = Lazy::new(|| {
     register_histogram!(
         // metric name
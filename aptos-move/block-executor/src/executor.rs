@@ -14,6 +14,7 @@ use crate::{
     errors::*,
     executor_utilities::*,
     explicit_sync_wrapper::ExplicitSyncWrapper,
+    hot_account_detector::HotAccountLane,
     limit_processor::BlockGasLimitProcessor,
     scheduler::{DependencyStatus, ExecutionTaskType, Scheduler, SchedulerTask, Wave},
     scheduler_v2::{AbortManager, SchedulerV2, TaskKind},
@@ -23,7 +24,7 @@ use crate::{
     txn_last_input_output::TxnLastInputOutput,
     txn_provider::TxnProvider,
     types::ReadWriteSummary,
-    view::{LatestView, ParallelState, SequentialState, ViewState},
+    view::{wait_for_dependency, LatestView, ParallelState, SequentialState, ViewState},
 };
 use aptos_aggregator::{
     delayed_change::{ApplyBase, DelayedChange},
@@ -31,7 +32,7 @@ use aptos_aggregator::{
 };
 use aptos_crypto::HashValue;
 use aptos_drop_helper::DEFAULT_DROPPER;
-use aptos_logger::{error, info};
+use aptos_logger::{debug, error, info};
 use aptos_mvhashmap::{
     types::{Incarnation, MVDelayedFieldsError, TxnIndex, ValueWithLayout},
     unsync_map::UnsyncMap,
@@ -46,8 +47,8 @@ use aptos_types::{
     on_chain_config::{BlockGasLimitType, Features},
     state_store::{state_value::StateValue, TStateView},
     transaction::{
-        block_epilogue::TBlockEndInfoExt, AuxiliaryInfoTrait, BlockExecutableTransaction,
-        BlockOutput, FeeDistribution,
+        block_epilogue::TBlockEndInfoExt, AuxiliaryInfoTrait, BlockConflictStats,
+        BlockExecutableTransaction, BlockOutput, FeeDistribution,
     },
     vm::modules::AptosModuleExtension,
     write_set::{TransactionWrite, WriteOp},
@@ -126,6 +127,10 @@ where
             config.local.concurrency_level,
             num_cpus,
         );
+        assert!(
+            !(config.local.blockstm_v2 && config.local.hot_account_detection.is_some()),
+            "Hot account detection is not supported by BlockSTMv2"
+        );
         Self {
             config,
             executor_thread_pool,
@@ -869,6 +874,7 @@ where
     ) -> Result<(), PanicOr<ParallelBlockExecutionError>> {
         let block_limit_processor = &mut block_limit_processor.acquire();
         let mut side_effect_at_commit = false;
+        let mut committed_incarnation = incarnation;
 
         if !Self::validate_and_commit_delayed_fields(
             txn_idx,
@@ -878,6 +884,7 @@ where
         )? {
             // Transaction needs to be re-executed, one final time.
             side_effect_at_commit = true;
+            committed_incarnation = incarnation + 1;
             counters::SPECULATIVE_ABORT_COUNT.inc();
 
             let parallel_state = ParallelState::new(
@@ -959,6 +966,7 @@ where
         if side_effect_at_commit {
             scheduler.wake_dependencies_and_decrease_validation_idx(txn_idx)?;
         }
+        block_limit_processor.record_committed_incarnation(committed_incarnation);
 
         last_input_output
             .check_fatal_vm_error(txn_idx)
@@ -1253,6 +1261,7 @@ where
         total_txns_to_materialize: &AtomicU32,
         num_running_workers: &AtomicU32,
         num_workers: usize,
+        hot_account_lane: Option<&HotAccountLane>,
     ) -> Result<(), PanicOr<ParallelBlockExecutionError>> {
         defer!( num_running_workers.fetch_sub(1, Ordering::SeqCst); );
 
@@ -1478,26 +1487,39 @@ where
                     incarnation,
                     ExecutionTaskType::Execution,
                 ) => {
-                    let needs_suffix_validation = Self::execute(
-                        txn_idx,
-                        incarnation,
-                        block.get_txn(txn_idx),
-                        &block.get_auxiliary_info(txn_idx),
-                        last_input_output,
-                        versioned_cache,
-                        &executor,
-                        base_view,
-                        global_module_cache,
-                        runtime_environment,
-                        ParallelState::new(
-                            versioned_cache,
-                            scheduler_wrapper,
-                            start_shared_counter,
-                            shared_counter,
+                    // Transactions in the sequential lane of a hot account only start executing
+                    // once their predecessor in the lane has been executed.
+                    let can_execute =
+                        match hot_account_lane.and_then(|lane| lane.predecessor(txn_idx)) {
+                            Some(dep_idx) => wait_for_dependency(scheduler, txn_idx, dep_idx)?,
+                            None => true,
+                        };
+
+                    if can_execute {
+                        let needs_suffix_validation = Self::execute(
+                            txn_idx,
                             incarnation,
-                        ),
-                    )?;
-                    scheduler.finish_execution(txn_idx, incarnation, needs_suffix_validation)?
+                            block.get_txn(txn_idx),
+                            &block.get_auxiliary_info(txn_idx),
+                            last_input_output,
+                            versioned_cache,
+                            &executor,
+                            base_view,
+                            global_module_cache,
+                            runtime_environment,
+                            ParallelState::new(
+                                versioned_cache,
+                                scheduler_wrapper,
+                                start_shared_counter,
+                                shared_counter,
+                                incarnation,
+                            ),
+                        )?;
+                        scheduler.finish_execution(txn_idx, incarnation, needs_suffix_validation)?
+                    } else {
+                        // The execution has been halted.
+                        scheduler.next_task()
+                    }
                 },
                 SchedulerTask::ExecutionTask(_, _, ExecutionTaskType::Wakeup(condvar)) => {
                     {
@@ -1661,6 +1683,7 @@ where
         has_remaining_commit_tasks: bool,
         final_results: ExplicitSyncWrapper<Vec<E::Output>>,
         block_epilogue_txn: Option<T>,
+        conflict_stats: BlockConflictStats,
         mut versioned_cache: MVHashMap<T::Key, T::Tag, T::Value, DelayedFieldID>,
         scheduler: impl Send + 'static,
         last_input_output: TxnLastInputOutput<T, E::Output, E::Error>,
@@ -1694,10 +1717,10 @@ where
         DEFAULT_DROPPER.schedule_drop((last_input_output, scheduler, versioned_cache));
 
         // Return final result
-        Ok(BlockOutput::new(
-            final_results.into_inner(),
-            block_epilogue_txn,
-        ))
+        Ok(
+            BlockOutput::new(final_results.into_inner(), block_epilogue_txn)
+                .with_conflict_stats(Some(conflict_stats)),
+        )
     }
 
     #[allow(dead_code)]
@@ -1791,6 +1814,7 @@ where
             !scheduler.post_commit_processing_queue_is_empty(),
             final_results,
             None, // BlockSTMv2 doesn't handle block epilogue yet.
+            block_limit_processor.into_inner().get_conflict_stats(),
             versioned_cache,
             scheduler,
             last_input_output,
@@ -1852,6 +1876,18 @@ where
         let num_txns_materialized = AtomicU32::new(0);
         let total_txns_to_materialize = AtomicU32::new(num_txns);
         let num_running_workers = AtomicU32::new(num_workers as u32);
+        let hot_account_lane = self
+            .config
+            .local
+            .hot_account_detection
+            .as_ref()
+            .and_then(|config| HotAccountLane::detect(signature_verified_block, config));
+        if let Some(lane) = &hot_account_lane {
+            debug!(
+                "Executing transactions of {} in a sequential lane",
+                lane.sender()
+            );
+        }
 
         let timer = RAYON_EXECUTION_SECONDS.start_timer();
         self.executor_thread_pool.scope(|s| {
@@ -1876,6 +1912,7 @@ where
                         &total_txns_to_materialize,
                         &num_running_workers,
                         num_workers,
+                        hot_account_lane.as_ref(),
                     ) {
                         // If there are multiple errors, they all get logged:
                         // ModulePathReadWriteError and FatalVMError variant is logged at construction,
@@ -1898,6 +1935,7 @@ where
             scheduler.pop_from_commit_queue().is_ok(),
             final_results,
             block_epilogue_txn.into_inner(),
+            block_limit_processor.into_inner().get_conflict_stats(),
             versioned_cache,
             scheduler,
             last_input_output,
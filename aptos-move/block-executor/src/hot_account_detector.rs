@@ -0,0 +1,165 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    counters::{HOT_ACCOUNT_LANE_BLOCKS, HOT_ACCOUNT_LANE_TXNS},
+    txn_provider::TxnProvider,
+};
+use aptos_mvhashmap::types::TxnIndex;
+use aptos_types::{
+    block_executor::config::HotAccountDetectionConfig,
+    transaction::{AuxiliaryInfoTrait, BlockExecutableTransaction as Transaction},
+};
+use move_core_types::account_address::AccountAddress;
+use std::collections::HashMap;
+
+/// Transactions of a hot sender, which are executed in a sequential lane: a transaction of the
+/// lane only starts executing once its predecessor in the lane has been executed. Transactions
+/// outside of the lane are not affected.
+///
+/// Only senders are considered, since their transactions are known to conflict (at least on the
+/// sequence number or nonce of the sender), so the lane never serializes independent
+/// transactions.
+pub(crate) struct HotAccountLane {
+    sender: AccountAddress,
+    /// For every transaction in the block, the previous transaction of the lane, if the
+    /// transaction belongs to the lane and is not the first one.
+    predecessors: Vec<Option<TxnIndex>>,
+}
+
+impl HotAccountLane {
+    /// Returns the lane of the sender dominating the block, if there is one.
+    pub(crate) fn detect<T: Transaction, A: AuxiliaryInfoTrait>(
+        block: &impl TxnProvider<T, A>,
+        config: &HotAccountDetectionConfig,
+    ) -> Option<Self> {
+        let mut groups: HashMap<AccountAddress, Vec<TxnIndex>> = HashMap::new();
+        let mut num_user_txns = 0;
+        for idx in 0..block.num_txns() as TxnIndex {
+            let Some(txn) = block.get_txn(idx).try_as_signed_user_txn() else {
+                continue;
+            };
+            num_user_txns += 1;
+            groups.entry(txn.sender()).or_default().push(idx);
+        }
+        if num_user_txns < config.min_num_user_txns {
+            return None;
+        }
+
+        let (sender, indices) = groups
+            .into_iter()
+            .max_by_key(|(_, indices)| indices.len())?;
+        if indices.len() * 100 < num_user_txns * config.min_hot_percentage {
+            return None;
+        }
+
+        let mut predecessors = vec![None; block.num_txns()];
+        for window in indices.windows(2) {
+            predecessors[window[1] as usize] = Some(window[0]);
+        }
+        HOT_ACCOUNT_LANE_BLOCKS.inc();
+        HOT_ACCOUNT_LANE_TXNS.inc_by(indices.len() as u64);
+
+        Some(Self {
+            sender,
+            predecessors,
+        })
+    }
+
+    pub(crate) fn sender(&self) -> AccountAddress {
+        self.sender
+    }
+
+    /// Returns the transaction that needs to be executed before the given one can be.
+    pub(crate) fn predecessor(&self, txn_idx: TxnIndex) -> Option<TxnIndex> {
+        self.predecessors.get(txn_idx as usize).copied().flatten()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::txn_provider::default::DefaultTxnProvider;
+    use aptos_crypto::{ed25519::Ed25519PrivateKey, PrivateKey, Uniform};
+    use aptos_types::{
+        test_helpers::transaction_test_helpers::get_test_signed_transaction,
+        transaction::{
+            signature_verified_transaction::SignatureVerifiedTransaction, AuxiliaryInfo,
+            EntryFunction, Transaction as AptosTransaction, TransactionPayload,
+        },
+    };
+    use move_core_types::{identifier::Identifier, language_storage::ModuleId};
+
+    fn user_txn(sender: AccountAddress, module: &str) -> SignatureVerifiedTransaction {
+        let private_key = Ed25519PrivateKey::generate_for_testing();
+        let payload = TransactionPayload::EntryFunction(EntryFunction::new(
+            ModuleId::new(AccountAddress::ONE, Identifier::new(module).unwrap()),
+            Identifier::new("f").unwrap(),
+            vec![],
+            vec![],
+        ));
+        let txn = get_test_signed_transaction(
+            sender,
+            0,
+            &private_key,
+            private_key.public_key(),
+            Some(payload),
+            u64::MAX,
+            100,
+            None,
+        );
+        AptosTransaction::UserTransaction(txn).into()
+    }
+
+    fn detect(
+        txns: Vec<SignatureVerifiedTransaction>,
+        min_hot_percentage: usize,
+    ) -> Option<HotAccountLane> {
+        let config = HotAccountDetectionConfig {
+            min_hot_percentage,
+            min_num_user_txns: 4,
+        };
+        let block = DefaultTxnProvider::<_, AuxiliaryInfo>::new_without_info(txns);
+        HotAccountLane::detect(&block, &config)
+    }
+
+    #[test]
+    fn test_detect_hot_sender() {
+        let hot = AccountAddress::random();
+        let txns: Vec<_> = (0..8)
+            .map(|i| {
+                let sender = if i % 2 == 0 {
+                    hot
+                } else {
+                    AccountAddress::random()
+                };
+                user_txn(sender, &format!("m{}", i))
+            })
+            .collect();
+
+        assert!(detect(txns.clone(), 60).is_none());
+        let lane = detect(txns, 50).unwrap();
+        assert_eq!(lane.sender(), hot);
+        assert_eq!(lane.predecessor(0), None);
+        assert_eq!(lane.predecessor(1), None);
+        assert_eq!(lane.predecessor(2), Some(0));
+        assert_eq!(lane.predecessor(6), Some(4));
+        assert_eq!(lane.predecessor(7), None);
+    }
+
+    #[test]
+    fn test_shared_module_is_not_hot() {
+        // Transfers from distinct senders all call the same module, but don't conflict.
+        let txns: Vec<_> = (0..8)
+            .map(|_| user_txn(AccountAddress::random(), "coin"))
+            .collect();
+        assert!(detect(txns, 10).is_none());
+    }
+
+    #[test]
+    fn test_small_blocks_are_not_analyzed() {
+        let hot = AccountAddress::random();
+        let txns: Vec<_> = (0..3).map(|_| user_txn(hot, "m")).collect();
+        assert!(detect(txns, 50).is_none());
+    }
+}
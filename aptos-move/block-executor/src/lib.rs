@@ -147,6 +147,7 @@ pub mod errors;
 pub mod executor;
 mod executor_utilities;
 pub mod explicit_sync_wrapper;
+mod hot_account_detector;
 pub mod hot_state_op_accumulator;
 mod limit_processor;
 mod scheduler;
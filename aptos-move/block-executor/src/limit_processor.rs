@@ -6,13 +6,14 @@ use crate::{
 };
 use aptos_logger::{info, warn};
 use aptos_metrics_core::IntCounterVecHelper;
+use aptos_mvhashmap::types::Incarnation;
 use aptos_types::{
     fee_statement::FeeStatement,
     on_chain_config::BlockGasLimitType,
     state_store::{state_slot::StateSlot, TStateView},
     transaction::{
         block_epilogue::{BlockEndInfo, TBlockEndInfoExt},
-        BlockExecutableTransaction as Transaction,
+        BlockConflictStats, BlockExecutableTransaction as Transaction,
     },
 };
 use claims::{assert_le, assert_none};
@@ -27,6 +28,8 @@ pub struct BlockGasLimitProcessor<'s, T: Transaction, S> {
     block_gas_limit_override: Option<u64>,
     accumulated_raw_block_gas: u64,
     accumulated_effective_block_gas: u64,
+    accumulated_execution_gas: u64,
+    accumulated_io_gas: u64,
     accumulated_approx_output_size: u64,
     accumulated_fee_statement: FeeStatement,
     txn_fee_statements: Vec<FeeStatement>,
@@ -34,6 +37,7 @@ pub struct BlockGasLimitProcessor<'s, T: Transaction, S> {
     start_time: Instant,
     print_conflicts_info: bool,
     hot_state_op_accumulator: Option<BlockHotStateOpAccumulator<'s, T::Key, S>>,
+    conflict_stats: BlockConflictStats,
 }
 
 impl<'s, T: Transaction, S: TStateView<Key = T::Key>> BlockGasLimitProcessor<'s, T, S> {
@@ -51,6 +55,8 @@ impl<'s, T: Transaction, S: TStateView<Key = T::Key>> BlockGasLimitProcessor<'s,
             block_gas_limit_override,
             accumulated_raw_block_gas: 0,
             accumulated_effective_block_gas: 0,
+            accumulated_execution_gas: 0,
+            accumulated_io_gas: 0,
             accumulated_approx_output_size: 0,
             accumulated_fee_statement: FeeStatement::zero(),
             txn_fee_statements: Vec::with_capacity(init_size),
@@ -59,9 +65,23 @@ impl<'s, T: Transaction, S: TStateView<Key = T::Key>> BlockGasLimitProcessor<'s,
             // TODO: have a configuration for it.
             print_conflicts_info: *PRINT_CONFLICTS_INFO,
             hot_state_op_accumulator,
+            conflict_stats: BlockConflictStats::default(),
         }
     }
 
+    /// Records the incarnation a transaction got committed at, i.e. how many of its speculative
+    /// executions were aborted.
+    pub(crate) fn record_committed_incarnation(&mut self, incarnation: Incarnation) {
+        if incarnation > 0 {
+            self.conflict_stats.num_conflicting_txns += 1;
+            self.conflict_stats.num_speculative_aborts += incarnation as u64;
+        }
+    }
+
+    pub(crate) fn get_conflict_stats(&self) -> BlockConflictStats {
+        self.conflict_stats
+    }
+
     pub(crate) fn accumulate_fee_statement(
         &mut self,
         fee_statement: FeeStatement,
@@ -109,6 +129,8 @@ impl<'s, T: Transaction, S: TStateView<Key = T::Key>> BlockGasLimitProcessor<'s,
             + fee_statement.io_gas_used() * self.block_gas_limit_type.io_gas_effective_multiplier();
         self.accumulated_raw_block_gas += raw_gas_used;
         self.accumulated_effective_block_gas += conflict_multiplier * raw_gas_used;
+        self.accumulated_execution_gas += fee_statement.execution_gas_used();
+        self.accumulated_io_gas += fee_statement.io_gas_used();
 
         if self.block_gas_limit_type.block_output_limit().is_some() {
             self.accumulated_approx_output_size += approx_output_size
@@ -142,6 +164,32 @@ impl<'s, T: Transaction, S: TStateView<Key = T::Key>> BlockGasLimitProcessor<'s,
             }
         }
 
+        if let Some(per_block_execution_gas_limit) =
+            self.block_gas_limit_type.block_execution_gas_limit()
+        {
+            if self.accumulated_execution_gas >= per_block_execution_gas_limit {
+                counters::EXCEED_PER_BLOCK_EXECUTION_GAS_LIMIT_COUNT.inc_with(&[mode]);
+                info!(
+                    "[BlockSTM]: execution ({}) early halted due to \
+                    accumulated_execution_gas {} >= PER_BLOCK_EXECUTION_GAS_LIMIT {}",
+                    mode, self.accumulated_execution_gas, per_block_execution_gas_limit,
+                );
+                return true;
+            }
+        }
+
+        if let Some(per_block_io_gas_limit) = self.block_gas_limit_type.block_io_gas_limit() {
+            if self.accumulated_io_gas >= per_block_io_gas_limit {
+                counters::EXCEED_PER_BLOCK_IO_GAS_LIMIT_COUNT.inc_with(&[mode]);
+                info!(
+                    "[BlockSTM]: execution ({}) early halted due to \
+                    accumulated_io_gas {} >= PER_BLOCK_IO_GAS_LIMIT {}",
+                    mode, self.accumulated_io_gas, per_block_io_gas_limit,
+                );
+                return true;
+            }
+        }
+
         if let Some(per_block_output_limit) = self.block_gas_limit_type.block_output_limit() {
             let accumulated_output = self.get_accumulated_approx_output_size();
             if accumulated_output >= per_block_output_limit {
@@ -227,6 +275,8 @@ impl<'s, T: Transaction, S: TStateView<Key = T::Key>> BlockGasLimitProcessor<'s,
         info!(
             effective_block_gas = accumulated_effective_block_gas,
             raw_block_gas = accumulated_raw_block_gas,
+            execution_gas = self.accumulated_execution_gas,
+            io_gas = self.accumulated_io_gas,
             block_gas_limit = self.block_gas_limit_type.block_gas_limit().unwrap_or(0),
             block_gas_limit_override = self.block_gas_limit_override.unwrap_or(0),
             block_gas_limit_exceeded = self
@@ -272,12 +322,17 @@ impl<'s, T: Transaction, S: TStateView<Key = T::Key>> BlockGasLimitProcessor<'s,
 
     pub(crate) fn get_block_end_info(&self) -> TBlockEndInfoExt<T::Key> {
         let inner = BlockEndInfo::V0 {
-            block_gas_limit_reached: self
-                .block_gas_limit()
-                .map(|per_block_gas_limit| {
-                    self.get_effective_accumulated_block_gas() >= per_block_gas_limit
-                })
-                .unwrap_or(false),
+            // Any of the gas limits (effective, execution or IO gas) being reached
+            block_gas_limit_reached: self.block_gas_limit().is_some_and(|per_block_gas_limit| {
+                self.get_effective_accumulated_block_gas() >= per_block_gas_limit
+            }) || self
+                .block_gas_limit_type
+                .block_execution_gas_limit()
+                .is_some_and(|limit| self.accumulated_execution_gas >= limit)
+                || self
+                    .block_gas_limit_type
+                    .block_io_gas_limit()
+                    .is_some_and(|limit| self.accumulated_io_gas >= limit),
             block_output_limit_reached: self
                 .block_gas_limit_type
                 .block_output_limit()
@@ -415,6 +470,38 @@ mod test {
         assert!(processor.should_end_block_parallel());
     }
 
+    #[test]
+    fn test_execution_and_io_gas_limits() {
+        let block_gas_limit = BlockGasLimitType::ComplexLimitV2 {
+            effective_block_gas_limit: 1000000,
+            execution_gas_effective_multiplier: 1,
+            io_gas_effective_multiplier: 1,
+            conflict_penalty_window: 1,
+            use_module_publishing_block_conflict: false,
+            block_output_limit: None,
+            include_user_txn_size_in_block_output: true,
+            add_block_limit_outcome_onchain: false,
+            use_granular_resource_group_conflicts: false,
+            block_execution_gas_limit: Some(100),
+            block_io_gas_limit: Some(50),
+        };
+
+        // The execution gas saturates first
+        let mut processor =
+            TestProcessor::new(&EMPTY_STATE_VIEW, block_gas_limit.clone(), None, 10);
+        processor.accumulate_fee_statement(FeeStatement::new(70, 60, 10, 0, 0), None, None);
+        assert!(!processor.should_end_block_parallel());
+        processor.accumulate_fee_statement(FeeStatement::new(40, 40, 0, 0, 0), None, None);
+        assert!(processor.should_end_block_parallel());
+
+        // The IO gas saturates first
+        let mut processor = TestProcessor::new(&EMPTY_STATE_VIEW, block_gas_limit, None, 10);
+        processor.accumulate_fee_statement(FeeStatement::new(40, 10, 30, 0, 0), None, None);
+        assert!(!processor.should_end_block_parallel());
+        processor.accumulate_fee_statement(FeeStatement::new(20, 0, 20, 0, 0), None, None);
+        assert!(processor.should_end_block_parallel());
+    }
+
     fn to_map(reads: &[InputOutputKey<u64, u32>]) -> HashSet<InputOutputKey<KeyType<u64>, u32>> {
         reads
             .iter()
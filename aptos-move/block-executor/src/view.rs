@@ -486,7 +486,7 @@ fn delayed_field_try_add_delta_outcome_impl<T: Transaction>(
 // txn_idx is estimated to have a r/w dependency on dep_idx.
 // Returns after the dependency has been resolved, the returned indicator is true if
 // it is safe to continue, and false if the execution has been halted.
-fn wait_for_dependency(
+pub(crate) fn wait_for_dependency(
     wait_for: &dyn TWaitForDependency,
     txn_idx: TxnIndex,
     dep_idx: TxnIndex,
@@ -1144,7 +1144,7 @@ impl<'a, T: Transaction, S: TStateView<Key = T::Key>> LatestView<'a, T, S> {
         state_key: &T::Key,
     ) -> PartialVMResult<Option<StateValue>> {
         let ret = self.base_view.get_state_value(state_key).map_err(|e| {
-            PartialVMError::new(StatusCode::STORAGE_ERROR).with_message(format!(
+            PartialVMError::new(e.status_code()).with_message(format!(
                 "Unexpected storage error for {:?}: {:?}",
                 state_key, e
             ))
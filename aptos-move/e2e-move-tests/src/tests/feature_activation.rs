@@ -0,0 +1,68 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::MoveHarness;
+use aptos_cached_packages::aptos_stdlib;
+use aptos_language_e2e_tests::account::Account;
+use aptos_types::{
+    account_address::AccountAddress,
+    on_chain_config::{
+        FeatureActivationSchedule, FeatureFlag, FeatureRollout, Features, OnChainConfig,
+    },
+};
+use move_core_types::vm_status::StatusCode;
+
+fn orderless_transaction_gated(h: &mut MoveHarness, sender: &Account, nonce: u64) -> bool {
+    let payload = aptos_stdlib::aptos_account_transfer(AccountAddress::ONE, 1)
+        .set_replay_protection_nonce(nonce);
+    let txn = h.create_transaction_payload(sender, payload);
+    h.executor.validate_transaction(txn).status() == Some(StatusCode::FEATURE_UNDER_GATING)
+}
+
+#[test]
+fn test_orderless_transactions_partial_rollout() {
+    let mut h = MoveHarness::new_with_features(
+        vec![FeatureFlag::TRANSACTION_PAYLOAD_V2],
+        vec![FeatureFlag::ORDERLESS_TRANSACTIONS],
+    );
+    let schedule = FeatureActivationSchedule {
+        scheduled: vec![],
+        rollouts: vec![FeatureRollout {
+            feature: FeatureFlag::ORDERLESS_TRANSACTIONS as u64,
+            rollout_percentage: 50,
+        }],
+    };
+    h.set_resource(
+        AccountAddress::ONE,
+        FeatureActivationSchedule::struct_tag(),
+        &schedule,
+    );
+    let features = Features::fetch_config(h.executor.get_state_view()).unwrap();
+
+    // Only the senders in the rollout can use orderless transactions.
+    let senders: Vec<_> = (0..20).map(|_| h.new_account_with_key_pair()).collect();
+    let mut num_enabled = 0;
+    for (nonce, sender) in senders.iter().enumerate() {
+        let enabled = schedule.is_enabled_for(
+            &features,
+            FeatureFlag::ORDERLESS_TRANSACTIONS,
+            sender.address(),
+        );
+        assert_eq!(
+            orderless_transaction_gated(&mut h, sender, nonce as u64),
+            !enabled
+        );
+        num_enabled += enabled as usize;
+    }
+    assert!(num_enabled > 0 && num_enabled < senders.len());
+
+    // Once enabled on chain, the flag applies to every sender.
+    h.enable_features(vec![FeatureFlag::ORDERLESS_TRANSACTIONS], vec![]);
+    for (nonce, sender) in senders.iter().enumerate() {
+        assert!(!orderless_transaction_gated(
+            &mut h,
+            sender,
+            (senders.len() + nonce) as u64
+        ));
+    }
+}
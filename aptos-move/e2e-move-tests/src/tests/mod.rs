@@ -22,6 +22,7 @@ mod enum_upgrade_fv;
 mod enum_variant_count;
 mod error_map;
 mod events;
+mod feature_activation;
 mod fee_payer;
 mod friends;
 mod function_value_depth;
@@ -57,6 +58,7 @@ mod rotate_auth_key;
 mod scripts;
 mod simple_defi;
 mod smart_data_structures;
+mod sponsorship_quota;
 mod stake;
 mod state_metadata;
 mod storage_refund;
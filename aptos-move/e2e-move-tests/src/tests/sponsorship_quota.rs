@@ -0,0 +1,141 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{assert_abort, assert_success, MoveHarness};
+use aptos_cached_packages::aptos_stdlib;
+use aptos_language_e2e_tests::account::{Account, TransactionBuilder};
+use aptos_types::{
+    account_address::AccountAddress, move_utils::MemberId, on_chain_config::FeatureFlag,
+    transaction::TransactionStatus,
+};
+use move_core_types::vm_status::StatusCode;
+use std::str::FromStr;
+
+const GAS_UNIT_PRICE: u64 = 100;
+const MAX_GAS_AMOUNT: u64 = 10_000;
+const MAX_FEE: u64 = GAS_UNIT_PRICE * MAX_GAS_AMOUNT;
+
+fn remaining_sender_quota(h: &mut MoveHarness, sponsor: &Account, sender: &Account) -> Option<u64> {
+    let output = h.execute_view_function(
+        MemberId::from_str("0x1::sponsorship_quota::remaining_sender_quota").unwrap(),
+        vec![],
+        vec![
+            bcs::to_bytes(sponsor.address()).unwrap(),
+            bcs::to_bytes(sender.address()).unwrap(),
+        ],
+    );
+    bcs::from_bytes(&output.values.expect("View function should succeed")[0]).unwrap()
+}
+
+fn sponsored_transfer(
+    h: &mut MoveHarness,
+    sender: &Account,
+    sponsor: &Account,
+    amount: u64,
+) -> TransactionStatus {
+    let transaction = TransactionBuilder::new(sender.clone())
+        .fee_payer(sponsor.clone())
+        .payload(aptos_stdlib::aptos_coin_transfer(
+            *sponsor.address(),
+            amount,
+        ))
+        .sequence_number(h.sequence_number(sender.address()))
+        .max_gas_amount(MAX_GAS_AMOUNT)
+        .gas_unit_price(GAS_UNIT_PRICE)
+        .sign_fee_payer();
+    h.run_raw(transaction).status().to_owned()
+}
+
+fn set_sender_quota(h: &mut MoveHarness, sponsor: &Account, sender: &Account, max_octas: u64) {
+    assert_success!(h.run_entry_function(
+        sponsor,
+        MemberId::from_str("0x1::sponsorship_quota::set_sender_quota").unwrap(),
+        vec![],
+        vec![
+            bcs::to_bytes(sender.address()).unwrap(),
+            bcs::to_bytes(&max_octas).unwrap(),
+            bcs::to_bytes(&3600u64).unwrap(),
+        ],
+    ));
+}
+
+fn test_sender_quota_charged_with_fee_paid(mut h: MoveHarness) {
+    let alice = h.new_account_with_balance_and_sequence_number(0, 0);
+    let bob = h.new_account_at(AccountAddress::from_hex_literal("0xb0b").unwrap());
+
+    set_sender_quota(&mut h, &bob, &alice, MAX_FEE);
+    assert_eq!(remaining_sender_quota(&mut h, &bob, &alice), Some(MAX_FEE));
+
+    // The prologue checks the maximum fee, but only the fee the sponsor actually paid is charged.
+    // Alice has nothing to transfer, so this one only pays the sponsor's gas.
+    let bob_balance = h.read_aptos_balance(bob.address());
+    assert_success!(sponsored_transfer(&mut h, &alice, &bob, 0));
+    let fee_paid = bob_balance - h.read_aptos_balance(bob.address());
+    assert!(fee_paid > 0 && fee_paid < MAX_FEE);
+    let remaining = MAX_FEE - fee_paid;
+    assert_eq!(
+        remaining_sender_quota(&mut h, &bob, &alice),
+        Some(remaining)
+    );
+
+    // What is left does not cover the maximum fee of another transaction, so it is discarded
+    // without the sponsor paying for it.
+    let bob_balance = h.read_aptos_balance(bob.address());
+    assert_eq!(
+        sponsored_transfer(&mut h, &alice, &bob, 0),
+        TransactionStatus::Discard(StatusCode::SPONSORSHIP_QUOTA_EXCEEDED),
+    );
+    assert_eq!(h.read_aptos_balance(bob.address()), bob_balance);
+
+    // Raising the quota keeps the usage. Aborted transactions are sponsored too, so they count
+    // against the quota as well.
+    set_sender_quota(&mut h, &bob, &alice, 2 * MAX_FEE);
+    let remaining = remaining + MAX_FEE;
+    assert_eq!(
+        remaining_sender_quota(&mut h, &bob, &alice),
+        Some(remaining)
+    );
+    let bob_balance = h.read_aptos_balance(bob.address());
+    assert_abort!(sponsored_transfer(&mut h, &alice, &bob, 1), 65540);
+    let fee_paid = bob_balance - h.read_aptos_balance(bob.address());
+    assert_eq!(
+        remaining_sender_quota(&mut h, &bob, &alice),
+        Some(remaining - fee_paid)
+    );
+
+    // The usage goes away with the quota.
+    assert_success!(h.run_entry_function(
+        &bob,
+        MemberId::from_str("0x1::sponsorship_quota::remove_sender_quota").unwrap(),
+        vec![],
+        vec![bcs::to_bytes(alice.address()).unwrap()],
+    ));
+    assert_eq!(remaining_sender_quota(&mut h, &bob, &alice), None);
+    assert_success!(sponsored_transfer(&mut h, &alice, &bob, 0));
+}
+
+#[test]
+fn test_sender_quota_unified_prologue() {
+    test_sender_quota_charged_with_fee_paid(MoveHarness::new_with_features(
+        vec![
+            FeatureFlag::GAS_PAYER_ENABLED,
+            FeatureFlag::SPONSORSHIP_QUOTAS,
+            FeatureFlag::ACCOUNT_ABSTRACTION,
+        ],
+        vec![],
+    ));
+}
+
+#[test]
+fn test_sender_quota_fee_payer_prologue() {
+    test_sender_quota_charged_with_fee_paid(MoveHarness::new_with_features(
+        vec![
+            FeatureFlag::GAS_PAYER_ENABLED,
+            FeatureFlag::SPONSORSHIP_QUOTAS,
+        ],
+        vec![
+            FeatureFlag::ACCOUNT_ABSTRACTION,
+            FeatureFlag::DERIVABLE_ACCOUNT_ABSTRACTION,
+        ],
+    ));
+}
@@ -742,6 +742,7 @@ impl FakeExecutor {
                 allow_fallback: self.allow_block_executor_fallback,
                 discard_failed_blocks: false,
                 module_cache_config: BlockExecutorModuleCacheLocalConfig::default(),
+                hot_account_detection: None,
             },
             onchain: onchain_config,
         };
@@ -0,0 +1,275 @@
+/// Staged activation of feature flags.
+///
+/// Governance can schedule a feature flag to be rolled out once the chain reaches a future epoch and
+/// timestamp, instead of timing a proposal with the desired activation. A rollout can be staged over
+/// several epochs, each stage enabling the flag for a larger percentage of the accounts, until the
+/// last stage enables it in `std::features` for everyone. The stages that are due are applied on
+/// reconfiguration, and the VM reads the partial rollouts through the on-chain config registry.
+module aptos_framework::feature_activation {
+    use std::bcs;
+    use std::error;
+    use std::features;
+    use std::hash;
+    use aptos_framework::reconfiguration;
+    use aptos_framework::system_addresses;
+    use aptos_framework::timestamp;
+
+    friend aptos_framework::reconfiguration_with_dkg;
+
+    /// The activation epoch must be after the current epoch.
+    const EACTIVATION_EPOCH_NOT_IN_FUTURE: u64 = 1;
+    /// The rollout percentage must be between 1 and 100.
+    const EINVALID_ROLLOUT_PERCENTAGE: u64 = 2;
+
+    /// The rollout percentage at which a feature flag is enabled in `std::features`.
+    const FULL_ROLLOUT_PERCENTAGE: u64 = 100;
+
+    /// A stage of the rollout of a feature flag, which takes effect in the first epoch that starts
+    /// at or after both the activation epoch and the activation time.
+    struct ScheduledFeature has copy, drop, store {
+        feature: u64,
+        activation_epoch: u64,
+        activation_time_microseconds: u64,
+        rollout_percentage: u64,
+    }
+
+    /// A feature flag that is enabled for a percentage of the accounts only.
+    struct FeatureRollout has copy, drop, store {
+        feature: u64,
+        rollout_percentage: u64,
+    }
+
+    /// The stages of the feature flags that are scheduled, and the partial rollouts in effect.
+    struct FeatureActivationSchedule has key {
+        scheduled: vector<ScheduledFeature>,
+        rollouts: vector<FeatureRollout>,
+    }
+
+    /// Schedules the feature flag to be enabled for everyone at the given epoch and time. This can
+    /// be called by on-chain governance.
+    public fun schedule(
+        framework: &signer,
+        feature: u64,
+        activation_epoch: u64,
+        activation_time_microseconds: u64,
+    ) acquires FeatureActivationSchedule {
+        schedule_stage(
+            framework,
+            feature,
+            activation_epoch,
+            activation_time_microseconds,
+            FULL_ROLLOUT_PERCENTAGE
+        );
+    }
+
+    /// Schedules the feature flag to be enabled for the given percentage of the accounts at the
+    /// given epoch and time, replacing any stage of the flag at the same epoch. This can be called
+    /// by on-chain governance.
+    public fun schedule_stage(
+        framework: &signer,
+        feature: u64,
+        activation_epoch: u64,
+        activation_time_microseconds: u64,
+        rollout_percentage: u64,
+    ) acquires FeatureActivationSchedule {
+        system_addresses::assert_aptos_framework(framework);
+        assert!(
+            activation_epoch > reconfiguration::current_epoch(),
+            error::invalid_argument(EACTIVATION_EPOCH_NOT_IN_FUTURE)
+        );
+        assert!(
+            rollout_percentage > 0 && rollout_percentage <= FULL_ROLLOUT_PERCENTAGE,
+            error::invalid_argument(EINVALID_ROLLOUT_PERCENTAGE)
+        );
+        if (!exists<FeatureActivationSchedule>(@aptos_framework)) {
+            move_to(framework, FeatureActivationSchedule { scheduled: vector[], rollouts: vector[] });
+        };
+        let scheduled = &mut FeatureActivationSchedule[@aptos_framework].scheduled;
+        let (found, index) = scheduled.find(|scheduled| {
+            scheduled.feature == feature && scheduled.activation_epoch == activation_epoch
+        });
+        if (found) {
+            scheduled.swap_remove(index);
+        };
+        scheduled.push_back(
+            ScheduledFeature { feature, activation_epoch, activation_time_microseconds, rollout_percentage }
+        );
+    }
+
+    /// Cancels the scheduled stages and the partial rollout of the feature flag, if there are any.
+    /// A flag that is already enabled in `std::features` stays enabled. This can be called by
+    /// on-chain governance.
+    public fun cancel(framework: &signer, feature: u64) acquires FeatureActivationSchedule {
+        system_addresses::assert_aptos_framework(framework);
+        if (exists<FeatureActivationSchedule>(@aptos_framework)) {
+            let schedule = &mut FeatureActivationSchedule[@aptos_framework];
+            remove_scheduled(&mut schedule.scheduled, feature);
+            remove_rollout(&mut schedule.rollouts, feature);
+        }
+    }
+
+    #[view]
+    /// Check whether the feature flag is scheduled to be rolled out further.
+    public fun is_scheduled(feature: u64): bool acquires FeatureActivationSchedule {
+        exists<FeatureActivationSchedule>(@aptos_framework) &&
+            FeatureActivationSchedule[@aptos_framework].scheduled.any(|scheduled| scheduled.feature == feature)
+    }
+
+    #[view]
+    /// Returns the percentage of the accounts the feature flag is enabled for.
+    public fun rollout_percentage(feature: u64): u64 acquires FeatureActivationSchedule {
+        if (features::is_enabled(feature)) {
+            return FULL_ROLLOUT_PERCENTAGE
+        };
+        if (!exists<FeatureActivationSchedule>(@aptos_framework)) {
+            return 0
+        };
+        let rollouts = &FeatureActivationSchedule[@aptos_framework].rollouts;
+        let (found, index) = rollouts.find(|rollout| rollout.feature == feature);
+        if (found) { rollouts[index].rollout_percentage } else { 0 }
+    }
+
+    #[view]
+    /// Check whether the feature flag is enabled for the account.
+    public fun is_enabled_for(feature: u64, account: address): bool acquires FeatureActivationSchedule {
+        rollout_bucket(account) < rollout_percentage(feature)
+    }
+
+    /// Only used in reconfigurations to apply the stages that are due in the new epoch. Must be
+    /// called before `features::on_new_epoch()`, which applies the pending feature flags.
+    public(friend) fun on_new_epoch(framework: &signer) acquires FeatureActivationSchedule {
+        system_addresses::assert_aptos_framework(framework);
+        if (!exists<FeatureActivationSchedule>(@aptos_framework)) {
+            return
+        };
+
+        let new_epoch = reconfiguration::current_epoch() + 1;
+        let now = timestamp::now_microseconds();
+        let schedule = &mut FeatureActivationSchedule[@aptos_framework];
+        let enable = vector[];
+        let i = 0;
+        while (i < schedule.scheduled.length()) {
+            let ScheduledFeature {
+                feature,
+                activation_epoch,
+                activation_time_microseconds,
+                rollout_percentage,
+            } = schedule.scheduled[i];
+            if (activation_epoch <= new_epoch && activation_time_microseconds <= now) {
+                if (rollout_percentage == FULL_ROLLOUT_PERCENTAGE) {
+                    enable.push_back(feature);
+                } else {
+                    raise_rollout(&mut schedule.rollouts, feature, rollout_percentage);
+                };
+                schedule.scheduled.swap_remove(i);
+            } else {
+                i = i + 1;
+            }
+        };
+
+        // Fully rolled out flags are tracked by `std::features` from now on.
+        enable.for_each_ref(|feature| remove_rollout(&mut schedule.rollouts, *feature));
+        if (!enable.is_empty()) {
+            features::change_feature_flags_for_next_epoch(framework, enable, vector[]);
+        }
+    }
+
+    /// The bucket of the account among 100, which the flags rolled out to a larger percentage are
+    /// enabled for.
+    fun rollout_bucket(account: address): u64 {
+        let digest = hash::sha3_256(bcs::to_bytes(&account));
+        (((digest[0] as u64) << 8) | (digest[1] as u64)) % FULL_ROLLOUT_PERCENTAGE
+    }
+
+    /// Rollouts only ever grow, regardless of the order in which the stages become due.
+    fun raise_rollout(rollouts: &mut vector<FeatureRollout>, feature: u64, rollout_percentage: u64) {
+        let (found, index) = rollouts.find(|rollout| rollout.feature == feature);
+        if (!found) {
+            rollouts.push_back(FeatureRollout { feature, rollout_percentage });
+        } else if (rollouts[index].rollout_percentage < rollout_percentage) {
+            rollouts[index].rollout_percentage = rollout_percentage;
+        }
+    }
+
+    fun remove_scheduled(scheduled: &mut vector<ScheduledFeature>, feature: u64) {
+        let i = 0;
+        while (i < scheduled.length()) {
+            if (scheduled[i].feature == feature) {
+                scheduled.swap_remove(i);
+            } else {
+                i = i + 1;
+            }
+        }
+    }
+
+    fun remove_rollout(rollouts: &mut vector<FeatureRollout>, feature: u64) {
+        let (found, index) = rollouts.find(|rollout| rollout.feature == feature);
+        if (found) {
+            rollouts.swap_remove(index);
+        }
+    }
+
+    #[test(framework = @aptos_framework)]
+    fun test_scheduled_activation(framework: signer) acquires FeatureActivationSchedule {
+        timestamp::set_time_has_started_for_testing(&framework);
+        reconfiguration::initialize_for_test(&framework);
+
+        schedule(&framework, 1, 1, 0);
+        schedule(&framework, 2, 2, 0);
+        assert!(is_scheduled(1) && is_scheduled(2), 0);
+
+        // Only the flag that is due in the next epoch is enabled.
+        on_new_epoch(&framework);
+        assert!(!is_scheduled(1) && is_scheduled(2), 1);
+
+        cancel(&framework, 2);
+        assert!(!is_scheduled(2), 2);
+    }
+
+    #[test(framework = @aptos_framework)]
+    fun test_staged_rollout(framework: signer) acquires FeatureActivationSchedule {
+        timestamp::set_time_has_started_for_testing(&framework);
+        reconfiguration::initialize_for_test(&framework);
+
+        schedule_stage(&framework, 1, 1, 0, 50);
+        schedule_stage(&framework, 1, 1, 0, 20);
+        schedule(&framework, 1, 2, 0);
+        assert!(rollout_percentage(1) == 0, 0);
+
+        // The stage scheduled last at the same epoch replaces the former.
+        on_new_epoch(&framework);
+        assert!(rollout_percentage(1) == 20 && is_scheduled(1), 1);
+
+        let (enabled, disabled) = (0, 0);
+        let i = 0;
+        while (i < 100) {
+            let account = aptos_std::from_bcs::to_address(hash::sha3_256(bcs::to_bytes(&i)));
+            if (is_enabled_for(1, account)) {
+                enabled = enabled + 1;
+            } else {
+                disabled = disabled + 1;
+            };
+            i = i + 1;
+        };
+        assert!(enabled > 0 && disabled > 0, 2);
+
+        cancel(&framework, 1);
+        assert!(rollout_percentage(1) == 0 && !is_scheduled(1), 3);
+    }
+
+    #[test(framework = @aptos_framework)]
+    #[expected_failure(abort_code = 0x10001, location = Self)]
+    fun test_schedule_in_current_epoch_fails(framework: signer) acquires FeatureActivationSchedule {
+        timestamp::set_time_has_started_for_testing(&framework);
+        reconfiguration::initialize_for_test(&framework);
+        schedule(&framework, 1, 0, 0);
+    }
+
+    #[test(framework = @aptos_framework)]
+    #[expected_failure(abort_code = 0x10002, location = Self)]
+    fun test_schedule_empty_rollout_fails(framework: signer) acquires FeatureActivationSchedule {
+        timestamp::set_time_has_started_for_testing(&framework);
+        reconfiguration::initialize_for_test(&framework);
+        schedule_stage(&framework, 1, 1, 0, 0);
+    }
+}
@@ -18,6 +18,18 @@ module aptos_framework::jwk_consensus_config {
 
     /// `ConfigV1` creation failed with duplicated providers given.
     const EDUPLICATE_PROVIDERS: u64 = 1;
+    /// `OIDCProviderV2` creation failed with an unknown config URL kind.
+    const EINVALID_CONFIG_URL_KIND: u64 = 2;
+    /// `OIDCProviderV2` creation failed with a fetch interval shorter than `MIN_FETCH_INTERVAL_SECS`.
+    const EFETCH_INTERVAL_TOO_SHORT: u64 = 3;
+
+    /// `OIDCProviderV2::config_url` is an OpenID configuration URL, whose `jwks_uri` is then fetched.
+    const CONFIG_URL_KIND_OPENID_CONFIGURATION: u8 = 0;
+    /// `OIDCProviderV2::config_url` is the URL of the JWK set itself.
+    const CONFIG_URL_KIND_JWKS: u8 = 1;
+
+    /// The shortest interval at which validators may be asked to fetch the JWKs of a provider.
+    const MIN_FETCH_INTERVAL_SECS: u64 = 5;
 
     /// The configuration of the JWK consensus feature.
     struct JWKConsensusConfig has drop, key, store {
@@ -25,6 +37,7 @@ module aptos_framework::jwk_consensus_config {
         /// Currently the variant type is one of the following.
         /// - `ConfigOff`
         /// - `ConfigV1`
+        /// - `ConfigV2`
         variant: Any,
     }
 
@@ -41,6 +54,24 @@ module aptos_framework::jwk_consensus_config {
         oidc_providers: vector<OIDCProvider>,
     }
 
+    /// An OIDC provider, along with how validators should fetch its JWKs.
+    struct OIDCProviderV2 has copy, drop, store {
+        name: String,
+        /// Where to fetch the JWKs from, interpreted according to `config_url_kind`.
+        config_url: String,
+        /// One of `CONFIG_URL_KIND_OPENID_CONFIGURATION` and `CONFIG_URL_KIND_JWKS`.
+        config_url_kind: u8,
+        /// How often validators fetch the JWKs, in seconds.
+        fetch_interval_secs: u64,
+        /// The `alg` assumed for keys published without one. If empty, such keys are kept as unsupported.
+        default_alg: String,
+    }
+
+    /// Same as `ConfigV1`, but with providers that also describe how their JWKs are fetched.
+    struct ConfigV2 has copy, drop, store {
+        oidc_providers: vector<OIDCProviderV2>,
+    }
+
     /// Initialize the configuration. Used in genesis or governance.
     public fun initialize(framework: &signer, config: JWKConsensusConfig) {
         system_addresses::assert_aptos_framework(framework);
@@ -106,6 +137,41 @@ module aptos_framework::jwk_consensus_config {
         OIDCProvider { name, config_url }
     }
 
+    /// Construct a `JWKConsensusConfig` of variant `ConfigV2`.
+    ///
+    /// Abort if the given provider list contains duplicated provider names.
+    public fun new_v2(oidc_providers: vector<OIDCProviderV2>): JWKConsensusConfig {
+        let name_set = simple_map::new<String, u64>();
+        vector::for_each_ref(&oidc_providers, |provider| {
+            let provider: &OIDCProviderV2 = provider;
+            let (_, old_value) = simple_map::upsert(&mut name_set, provider.name, 0);
+            if (option::is_some(&old_value)) {
+                abort(error::invalid_argument(EDUPLICATE_PROVIDERS))
+            }
+        });
+        JWKConsensusConfig {
+            variant: copyable_any::pack( ConfigV2 { oidc_providers } )
+        }
+    }
+
+    /// Construct an `OIDCProviderV2` object.
+    ///
+    /// Abort if `config_url_kind` is unknown or `fetch_interval_secs` is below `MIN_FETCH_INTERVAL_SECS`.
+    public fun new_oidc_provider_v2(
+        name: String,
+        config_url: String,
+        config_url_kind: u8,
+        fetch_interval_secs: u64,
+        default_alg: String,
+    ): OIDCProviderV2 {
+        assert!(
+            config_url_kind == CONFIG_URL_KIND_OPENID_CONFIGURATION || config_url_kind == CONFIG_URL_KIND_JWKS,
+            error::invalid_argument(EINVALID_CONFIG_URL_KIND)
+        );
+        assert!(fetch_interval_secs >= MIN_FETCH_INTERVAL_SECS, error::invalid_argument(EFETCH_INTERVAL_TOO_SHORT));
+        OIDCProviderV2 { name, config_url, config_url_kind, fetch_interval_secs, default_alg }
+    }
+
     #[test_only]
     fun enabled(): bool acquires JWKConsensusConfig {
         let variant= borrow_global<JWKConsensusConfig>(@aptos_framework).variant;
@@ -135,6 +201,39 @@ module aptos_framework::jwk_consensus_config {
         assert!(!enabled(), 2)
     }
 
+    #[test(framework = @0x1)]
+    fun init_buffer_apply_v2(framework: signer) acquires JWKConsensusConfig {
+        initialize_for_testing(&framework);
+        let config = new_v2(vector[
+            new_oidc_provider_v2(utf8(b"Bob"), utf8(b"https://bob.dev"), CONFIG_URL_KIND_OPENID_CONFIGURATION, 10, utf8(b"")),
+            new_oidc_provider_v2(utf8(b"Alice"), utf8(b"https://alice.io/jwks"), CONFIG_URL_KIND_JWKS, 60, utf8(b"RS256")),
+        ]);
+        set_for_next_epoch(&framework, config);
+        on_new_epoch(&framework);
+        assert!(enabled(), 1);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 0x010001, location = Self)]
+    fun name_uniqueness_in_config_v2() {
+        new_v2(vector[
+            new_oidc_provider_v2(utf8(b"Alice"), utf8(b"https://alice.info"), CONFIG_URL_KIND_OPENID_CONFIGURATION, 10, utf8(b"")),
+            new_oidc_provider_v2(utf8(b"Alice"), utf8(b"https://alice.io"), CONFIG_URL_KIND_OPENID_CONFIGURATION, 10, utf8(b"")),
+        ]);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 0x010002, location = Self)]
+    fun unknown_config_url_kind() {
+        new_oidc_provider_v2(utf8(b"Alice"), utf8(b"https://alice.io"), 2, 10, utf8(b""));
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 0x010003, location = Self)]
+    fun fetch_interval_too_short() {
+        new_oidc_provider_v2(utf8(b"Alice"), utf8(b"https://alice.io"), CONFIG_URL_KIND_JWKS, 1, utf8(b""));
+    }
+
     #[test]
     #[expected_failure(abort_code = 0x010001, location = Self)]
     fun name_uniqueness_in_config_v1() {
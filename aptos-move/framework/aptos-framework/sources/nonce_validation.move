@@ -203,9 +203,19 @@ module aptos_framework::nonce_validation {
         true
     }
 
+    #[view]
+    /// Returns true if the nonce of the sender is still in the nonce history, i.e., an orderless transaction
+    /// with the nonce was committed and its expiration time plus `NONCE_REPLAY_PROTECTION_OVERLAP_INTERVAL_SECS`
+    /// has not passed yet. A used nonce is not necessarily rejected: once the previous transaction has expired,
+    /// a new transaction with the same nonce is accepted if its expiration time is more than
+    /// `NONCE_REPLAY_PROTECTION_OVERLAP_INTERVAL_SECS` seconds after the previous one.
+    /// Clients submitting orderless transactions in parallel can use this to pick unused nonces.
+    public fun is_nonce_used(sender_address: address, nonce: u64): bool acquires NonceHistory {
+        exists<NonceHistory>(@aptos_framework) && !check_if_nonce_exists_in_history(sender_address, nonce)
+    }
+
     // Returns true if the input (address, nonce) pair doesn't exist in the nonce history.
     // Returns false if the input (address, nonce) pair already exists in the nonce history.
-    #[test_only]
     fun check_if_nonce_exists_in_history(
         sender_address: address,
         nonce: u64,
@@ -241,6 +251,8 @@ module aptos_framework::nonce_validation {
         assert!(!check_and_insert_nonce(@0x5, 1234, begin_time + 51));
         assert!(!check_if_nonce_exists_in_history(@0x5, 1234));
         assert!(check_if_nonce_exists_in_history(@0x5, 1235));
+        assert!(is_nonce_used(@0x5, 1234));
+        assert!(!is_nonce_used(@0x5, 1235));
 
         timestamp::fast_forward_seconds(30);
         assert!(!check_and_insert_nonce(@0x5, 1234, begin_time + 85));
@@ -5,6 +5,7 @@ module aptos_framework::reconfiguration_with_dkg {
     use aptos_framework::consensus_config;
     use aptos_framework::dkg;
     use aptos_framework::execution_config;
+    use aptos_framework::feature_activation;
     use aptos_framework::gas_schedule;
     use aptos_framework::jwk_consensus_config;
     use aptos_framework::jwks;
@@ -50,6 +51,7 @@ module aptos_framework::reconfiguration_with_dkg {
         execution_config::on_new_epoch(framework);
         gas_schedule::on_new_epoch(framework);
         std::version::on_new_epoch(framework);
+        feature_activation::on_new_epoch(framework);
         features::on_new_epoch(framework);
         jwk_consensus_config::on_new_epoch(framework);
         jwks::on_new_epoch(framework);
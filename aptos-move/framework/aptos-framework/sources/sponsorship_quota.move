@@ -0,0 +1,374 @@
+/// Gas sponsorship quotas for fee payers.
+///
+/// An account that pays gas for other accounts (a gas station) can cap how much gas it sponsors
+/// per sender and per entry function, over a rolling period. The quotas are checked in every fee
+/// payer prologue: a transaction whose maximum fee (`gas_unit_price * max_gas_amount`) does not
+/// fit in the remaining quota is rejected before it executes, so the sponsor does not need an
+/// off-chain co-signing service just for basic abuse control. The fee payer epilogue then charges
+/// the quotas with the fee actually paid, whether the transaction succeeds or fails.
+///
+/// Quotas only apply to transactions where the fee payer differs from the sender. Function
+/// quotas only apply to entry function payloads, and only if the transaction context extension
+/// is enabled.
+module aptos_framework::sponsorship_quota {
+    use std::error;
+    use std::features;
+    use std::option::{Self, Option};
+    use std::signer;
+    use std::string::String;
+    use aptos_std::table::{Self, Table};
+    use aptos_framework::timestamp;
+    use aptos_framework::transaction_context;
+
+    friend aptos_framework::transaction_validation;
+
+    /// Quota periods need to be at least one second long.
+    const EZERO_PERIOD: u64 = 1;
+
+    const MAX_U64: u128 = 18446744073709551615;
+
+    /// At most `max_octas_per_period` can be sponsored every `period_secs` seconds.
+    struct Quota has copy, drop, store {
+        max_octas_per_period: u64,
+        period_secs: u64,
+    }
+
+    /// Octas charged against a quota in the period starting at `period_start_secs`.
+    struct Usage has copy, drop, store {
+        period_start_secs: u64,
+        used_octas: u64,
+    }
+
+    /// Identifies an entry function, e.g. `0x1::aptos_account::transfer`.
+    struct FunctionId has copy, drop, store {
+        account_address: address,
+        module_name: String,
+        function_name: String,
+    }
+
+    /// Quotas configured by a fee payer, stored under its account.
+    struct SponsorshipQuotas has key {
+        /// Applies to senders without a quota of their own.
+        default_sender_quota: Option<Quota>,
+        sender_quotas: Table<address, Quota>,
+        sender_usage: Table<address, Usage>,
+        function_quotas: Table<FunctionId, Quota>,
+        function_usage: Table<FunctionId, Usage>,
+    }
+
+    /// Sets the quota for senders the sponsor has no specific quota for.
+    public entry fun set_default_sender_quota(
+        sponsor: &signer,
+        max_octas_per_period: u64,
+        period_secs: u64,
+    ) acquires SponsorshipQuotas {
+        let quotas = borrow_or_create_quotas(sponsor);
+        quotas.default_sender_quota = option::some(new_quota(max_octas_per_period, period_secs));
+    }
+
+    public entry fun remove_default_sender_quota(sponsor: &signer) acquires SponsorshipQuotas {
+        let quotas = borrow_or_create_quotas(sponsor);
+        quotas.default_sender_quota = option::none();
+    }
+
+    public entry fun set_sender_quota(
+        sponsor: &signer,
+        sender: address,
+        max_octas_per_period: u64,
+        period_secs: u64,
+    ) acquires SponsorshipQuotas {
+        let quotas = borrow_or_create_quotas(sponsor);
+        table::upsert(&mut quotas.sender_quotas, sender, new_quota(max_octas_per_period, period_secs));
+    }
+
+    /// Removes the quota specific to `sender`, the default sender quota applies afterwards, starting
+    /// with a new period.
+    public entry fun remove_sender_quota(sponsor: &signer, sender: address) acquires SponsorshipQuotas {
+        let quotas = borrow_or_create_quotas(sponsor);
+        if (table::contains(&quotas.sender_quotas, sender)) {
+            table::remove(&mut quotas.sender_quotas, sender);
+        };
+        if (table::contains(&quotas.sender_usage, sender)) {
+            table::remove(&mut quotas.sender_usage, sender);
+        };
+    }
+
+    public entry fun set_function_quota(
+        sponsor: &signer,
+        account_address: address,
+        module_name: String,
+        function_name: String,
+        max_octas_per_period: u64,
+        period_secs: u64,
+    ) acquires SponsorshipQuotas {
+        let quotas = borrow_or_create_quotas(sponsor);
+        let function = FunctionId { account_address, module_name, function_name };
+        table::upsert(&mut quotas.function_quotas, function, new_quota(max_octas_per_period, period_secs));
+    }
+
+    public entry fun remove_function_quota(
+        sponsor: &signer,
+        account_address: address,
+        module_name: String,
+        function_name: String,
+    ) acquires SponsorshipQuotas {
+        let quotas = borrow_or_create_quotas(sponsor);
+        let function = FunctionId { account_address, module_name, function_name };
+        if (table::contains(&quotas.function_quotas, function)) {
+            table::remove(&mut quotas.function_quotas, function);
+        };
+        if (table::contains(&quotas.function_usage, function)) {
+            table::remove(&mut quotas.function_usage, function);
+        };
+    }
+
+    #[view]
+    /// Returns how many octas `sponsor` still sponsors for `sender` in the current period, or none
+    /// if the sender is not limited.
+    public fun remaining_sender_quota(sponsor: address, sender: address): Option<u64> acquires SponsorshipQuotas {
+        if (!exists<SponsorshipQuotas>(sponsor)) {
+            return option::none()
+        };
+        let quotas = borrow_global<SponsorshipQuotas>(sponsor);
+        let quota = quota_for_sender(quotas, sender);
+        if (option::is_none(&quota)) {
+            return option::none()
+        };
+        let usage = if (table::contains(&quotas.sender_usage, sender)) {
+            option::some(*table::borrow(&quotas.sender_usage, sender))
+        } else {
+            option::none()
+        };
+        option::some(remaining(option::borrow(&quota), &usage))
+    }
+
+    #[view]
+    /// Returns how many octas `sponsor` still sponsors for calls to the given entry function in the
+    /// current period, or none if the function is not limited.
+    public fun remaining_function_quota(
+        sponsor: address,
+        account_address: address,
+        module_name: String,
+        function_name: String,
+    ): Option<u64> acquires SponsorshipQuotas {
+        if (!exists<SponsorshipQuotas>(sponsor)) {
+            return option::none()
+        };
+        let quotas = borrow_global<SponsorshipQuotas>(sponsor);
+        let function = FunctionId { account_address, module_name, function_name };
+        if (!table::contains(&quotas.function_quotas, function)) {
+            return option::none()
+        };
+        let usage = if (table::contains(&quotas.function_usage, function)) {
+            option::some(*table::borrow(&quotas.function_usage, function))
+        } else {
+            option::none()
+        };
+        option::some(remaining(table::borrow(&quotas.function_quotas, function), &usage))
+    }
+
+    /// Whether the quotas `fee_payer` configured for `sender` and the called entry function have
+    /// room for `max_fee`. Called by the fee payer prologues, which must not write anything.
+    public(friend) fun has_room(fee_payer: address, sender: address, max_fee: u64): bool acquires SponsorshipQuotas {
+        if (!exists<SponsorshipQuotas>(fee_payer)) {
+            return true
+        };
+        let quotas = borrow_global<SponsorshipQuotas>(fee_payer);
+        let now = timestamp::now_seconds();
+
+        let sender_quota = quota_for_sender(quotas, sender);
+        if (option::is_some(&sender_quota)) {
+            let quota = option::borrow(&sender_quota);
+            if (!fits(quota, &current_usage(&quotas.sender_usage, sender, quota, now), max_fee)) {
+                return false
+            };
+        };
+
+        let function = called_function();
+        if (option::is_some(&function) && table::contains(&quotas.function_quotas, *option::borrow(&function))) {
+            let function = option::destroy_some(function);
+            let quota = table::borrow(&quotas.function_quotas, function);
+            if (!fits(quota, &current_usage(&quotas.function_usage, function, quota, now), max_fee)) {
+                return false
+            };
+        };
+        true
+    }
+
+    /// Charges the fee the fee payer paid for the transaction against the quotas checked by
+    /// `has_room`. Called by the fee payer epilogues, which must not abort, so the usage saturates
+    /// instead.
+    public(friend) fun charge(fee_payer: address, sender: address, fee_paid: u64) acquires SponsorshipQuotas {
+        if (!exists<SponsorshipQuotas>(fee_payer)) {
+            return
+        };
+        let quotas = borrow_global_mut<SponsorshipQuotas>(fee_payer);
+        let now = timestamp::now_seconds();
+
+        let sender_quota = quota_for_sender(quotas, sender);
+        if (option::is_some(&sender_quota)) {
+            let usage = current_usage(&quotas.sender_usage, sender, option::borrow(&sender_quota), now);
+            add_usage(&mut usage, fee_paid);
+            table::upsert(&mut quotas.sender_usage, sender, usage);
+        };
+
+        let function = called_function();
+        if (option::is_some(&function) && table::contains(&quotas.function_quotas, *option::borrow(&function))) {
+            let function = option::destroy_some(function);
+            let quota = *table::borrow(&quotas.function_quotas, function);
+            let usage = current_usage(&quotas.function_usage, function, &quota, now);
+            add_usage(&mut usage, fee_paid);
+            table::upsert(&mut quotas.function_usage, function, usage);
+        };
+    }
+
+    fun borrow_or_create_quotas(sponsor: &signer): &mut SponsorshipQuotas acquires SponsorshipQuotas {
+        let sponsor_address = signer::address_of(sponsor);
+        if (!exists<SponsorshipQuotas>(sponsor_address)) {
+            move_to(sponsor, SponsorshipQuotas {
+                default_sender_quota: option::none(),
+                sender_quotas: table::new(),
+                sender_usage: table::new(),
+                function_quotas: table::new(),
+                function_usage: table::new(),
+            });
+        };
+        borrow_global_mut<SponsorshipQuotas>(sponsor_address)
+    }
+
+    fun new_quota(max_octas_per_period: u64, period_secs: u64): Quota {
+        assert!(period_secs > 0, error::invalid_argument(EZERO_PERIOD));
+        Quota { max_octas_per_period, period_secs }
+    }
+
+    fun quota_for_sender(quotas: &SponsorshipQuotas, sender: address): Option<Quota> {
+        if (table::contains(&quotas.sender_quotas, sender)) {
+            option::some(*table::borrow(&quotas.sender_quotas, sender))
+        } else {
+            quotas.default_sender_quota
+        }
+    }
+
+    /// The entry function the transaction calls, if any.
+    fun called_function(): Option<FunctionId> {
+        if (!features::transaction_context_extension_enabled()) {
+            return option::none()
+        };
+        let payload = transaction_context::entry_function_payload();
+        if (option::is_none(&payload)) {
+            return option::none()
+        };
+        let payload = option::extract(&mut payload);
+        option::some(FunctionId {
+            account_address: transaction_context::account_address(&payload),
+            module_name: transaction_context::module_name(&payload),
+            function_name: transaction_context::function_name(&payload),
+        })
+    }
+
+    /// The usage in the period `now` falls in. A new period starts once the previous one ended.
+    fun current_usage<K: copy + drop>(usages: &Table<K, Usage>, key: K, quota: &Quota, now: u64): Usage {
+        if (table::contains(usages, key)) {
+            let usage = *table::borrow(usages, key);
+            if (now - usage.period_start_secs < quota.period_secs) {
+                return usage
+            };
+        };
+        Usage { period_start_secs: now, used_octas: 0 }
+    }
+
+    fun add_usage(usage: &mut Usage, amount: u64) {
+        let used_octas = (usage.used_octas as u128) + (amount as u128);
+        usage.used_octas = if (used_octas > MAX_U64) { (MAX_U64 as u64) } else { (used_octas as u64) };
+    }
+
+    fun fits(quota: &Quota, usage: &Usage, amount: u64): bool {
+        usage.used_octas <= quota.max_octas_per_period
+            && amount <= quota.max_octas_per_period - usage.used_octas
+    }
+
+    fun remaining(quota: &Quota, usage: &Option<Usage>): u64 {
+        let used = if (option::is_some(usage)) {
+            let usage = option::borrow(usage);
+            if (timestamp::now_seconds() - usage.period_start_secs < quota.period_secs) {
+                usage.used_octas
+            } else {
+                0
+            }
+        } else {
+            0
+        };
+        if (used >= quota.max_octas_per_period) {
+            0
+        } else {
+            quota.max_octas_per_period - used
+        }
+    }
+
+    #[test_only]
+    use std::string;
+    #[test_only]
+    /// Checks `max_fee` the way the prologue does and charges it the way the epilogue does, for a
+    /// transaction that spent all its gas.
+    fun try_charge(fee_payer: address, sender: address, max_fee: u64): bool acquires SponsorshipQuotas {
+        if (!has_room(fee_payer, sender, max_fee)) {
+            return false
+        };
+        charge(fee_payer, sender, max_fee);
+        true
+    }
+
+    #[test(aptos_framework = @aptos_framework, sponsor = @0xcafe)]
+    fun test_sender_quota(aptos_framework: &signer, sponsor: &signer) acquires SponsorshipQuotas {
+        timestamp::set_time_has_started_for_testing(aptos_framework);
+        let sponsor_address = signer::address_of(sponsor);
+        assert!(try_charge(sponsor_address, @0x1234, 1000), 0);
+
+        set_default_sender_quota(sponsor, 100, 60);
+        set_sender_quota(sponsor, @0x1234, 300, 60);
+        assert!(try_charge(sponsor_address, @0x1234, 200), 1);
+        assert!(!try_charge(sponsor_address, @0x1234, 200), 2);
+        assert!(remaining_sender_quota(sponsor_address, @0x1234) == option::some(100), 3);
+
+        // Other senders fall back to the default quota.
+        assert!(!try_charge(sponsor_address, @0x5678, 200), 4);
+        assert!(try_charge(sponsor_address, @0x5678, 100), 5);
+
+        // Usage is reset once the period is over.
+        timestamp::fast_forward_seconds(60);
+        assert!(try_charge(sponsor_address, @0x1234, 300), 6);
+        assert!(remaining_sender_quota(sponsor_address, @0x1234) == option::some(0), 7);
+
+        // The usage under the removed quota does not count against the default quota.
+        remove_sender_quota(sponsor, @0x1234);
+        assert!(remaining_sender_quota(sponsor_address, @0x1234) == option::some(100), 8);
+
+        remove_default_sender_quota(sponsor);
+        assert!(remaining_sender_quota(sponsor_address, @0x1234) == option::none(), 9);
+        assert!(try_charge(sponsor_address, @0x1234, 1000), 10);
+    }
+
+    #[test(aptos_framework = @aptos_framework, sponsor = @0xcafe)]
+    fun test_charge_fee_paid(aptos_framework: &signer, sponsor: &signer) acquires SponsorshipQuotas {
+        timestamp::set_time_has_started_for_testing(aptos_framework);
+        let sponsor_address = signer::address_of(sponsor);
+        set_default_sender_quota(sponsor, 100, 60);
+
+        // Only the fee paid counts, not the maximum fee that was checked.
+        assert!(has_room(sponsor_address, @0x1234, 100), 0);
+        charge(sponsor_address, @0x1234, 30);
+        assert!(remaining_sender_quota(sponsor_address, @0x1234) == option::some(70), 1);
+        assert!(!has_room(sponsor_address, @0x1234, 100), 2);
+
+        // Charging never aborts, even past the quota.
+        charge(sponsor_address, @0x1234, 18446744073709551615);
+        assert!(remaining_sender_quota(sponsor_address, @0x1234) == option::some(0), 3);
+        assert!(!has_room(sponsor_address, @0x1234, 0), 4);
+    }
+
+    #[test(sponsor = @0xcafe)]
+    #[expected_failure(abort_code = 0x10001, location = Self)]
+    fun test_zero_period(sponsor: &signer) acquires SponsorshipQuotas {
+        set_function_quota(sponsor, @0x1, string::utf8(b"aptos_account"), string::utf8(b"transfer"), 100, 0);
+    }
+}
@@ -14,6 +14,7 @@ module aptos_framework::transaction_validation {
     use aptos_framework::coin;
     use aptos_framework::create_signer;
     use aptos_framework::permissioned_signer;
+    use aptos_framework::sponsorship_quota;
     use aptos_framework::system_addresses;
     use aptos_framework::timestamp;
     use aptos_framework::transaction_fee;
@@ -71,6 +72,7 @@ module aptos_framework::transaction_validation {
     const PROLOGUE_PERMISSIONED_GAS_LIMIT_INSUFFICIENT: u64 = 1011;
     const PROLOGUE_ENONCE_ALREADY_USED: u64 = 1012;
     const PROLOGUE_ETRANSACTION_EXPIRATION_TOO_FAR_IN_FUTURE: u64 = 1013;
+    const PROLOGUE_ESPONSORSHIP_QUOTA_EXCEEDED: u64 = 1014;
 
     /// Permission management
     ///
@@ -472,6 +474,13 @@ module aptos_framework::transaction_validation {
             fee_payer_public_key_hash == account::get_authentication_key(fee_payer_address),
             error::invalid_argument(PROLOGUE_EINVALID_ACCOUNT_AUTH_KEY),
         );
+        check_sponsorship_quota(
+            signer::address_of(&sender),
+            fee_payer_address,
+            txn_gas_price,
+            txn_max_gas_units,
+            false,
+        );
     }
 
     // This function extends the fee_payer_script_prologue by adding a parameter to indicate simulation mode.
@@ -513,7 +522,14 @@ module aptos_framework::transaction_validation {
                     fee_payer_public_key_hash == account::get_authentication_key(fee_payer_address),
                     error::invalid_argument(PROLOGUE_EINVALID_ACCOUNT_AUTH_KEY),
                 )
-        }
+        };
+        check_sponsorship_quota(
+            signer::address_of(&sender),
+            fee_payer_address,
+            txn_gas_price,
+            txn_max_gas_units,
+            is_simulation,
+        );
     }
 
     /// Epilogue function is run after a transaction is successfully executed.
@@ -626,12 +642,60 @@ module aptos_framework::transaction_validation {
                 transaction_fee::mint_and_refund(gas_payer, mint_amount);
             };
         };
+        charge_sponsorship_quota(
+            signer::address_of(&account),
+            gas_payer,
+            transaction_fee_amount,
+            storage_fee_refunded,
+            is_simulation,
+        );
 
         // Increment sequence number
         let addr = signer::address_of(&account);
         account::increment_sequence_number(addr);
     }
 
+    /// Checks that the sponsorship quotas of the fee payer have room for the maximum fee of the
+    /// transaction. Run by every fee payer prologue, so that no transaction is charged against a
+    /// quota without having been checked first.
+    fun check_sponsorship_quota(
+        sender_address: address,
+        fee_payer_address: address,
+        txn_gas_price: u64,
+        txn_max_gas_units: u64,
+        is_simulation: bool,
+    ) {
+        if (features::is_sponsorship_quotas_enabled()
+            && sender_address != fee_payer_address
+            && !skip_gas_payment(is_simulation, fee_payer_address)) {
+            assert!(
+                sponsorship_quota::has_room(fee_payer_address, sender_address, txn_gas_price * txn_max_gas_units),
+                error::limit_exceeded(PROLOGUE_ESPONSORSHIP_QUOTA_EXCEEDED)
+            );
+        };
+    }
+
+    /// Charges the fee the gas payer paid, net of storage refunds, against its sponsorship quotas.
+    /// Run by every epilogue for the quotas checked by `check_sponsorship_quota`.
+    fun charge_sponsorship_quota(
+        sender_address: address,
+        gas_payer_address: address,
+        transaction_fee_amount: u64,
+        storage_fee_refunded: u64,
+        is_simulation: bool,
+    ) {
+        if (features::is_sponsorship_quotas_enabled()
+            && sender_address != gas_payer_address
+            && !skip_gas_payment(is_simulation, gas_payer_address)) {
+            let fee_paid = if (transaction_fee_amount > storage_fee_refunded) {
+                transaction_fee_amount - storage_fee_refunded
+            } else {
+                0
+            };
+            sponsorship_quota::charge(gas_payer_address, sender_address, fee_paid);
+        };
+    }
+
     inline fun skip_auth_key_check(is_simulation: bool, auth_key: &Option<vector<u8>>): bool {
         is_simulation && (option::is_none(auth_key) || vector::is_empty(option::borrow(auth_key)))
     }
@@ -797,7 +861,14 @@ module aptos_framework::transaction_validation {
                     error::invalid_argument(PROLOGUE_EINVALID_ACCOUNT_AUTH_KEY)
                 )
             };
-        }
+        };
+        check_sponsorship_quota(
+            signer::address_of(&sender),
+            signer::address_of(&fee_payer),
+            txn_gas_price,
+            txn_max_gas_units,
+            is_simulation,
+        );
     }
 
     fun unified_epilogue_v2(
@@ -856,6 +927,13 @@ module aptos_framework::transaction_validation {
                 );
             };
         };
+        charge_sponsorship_quota(
+            signer::address_of(&account),
+            gas_payer_address,
+            transaction_fee_amount,
+            storage_fee_refunded,
+            is_simulation,
+        );
 
         if (!is_orderless_txn) {
             // Increment sequence number
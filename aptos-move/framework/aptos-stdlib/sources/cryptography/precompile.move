@@ -0,0 +1,89 @@
+/// Precompiles: deterministic functions over bytes, implemented natively and identified by an ID.
+///
+/// New cryptographic primitives are registered as precompiles in the framework natives, together
+/// with their gas metering and feature flag, and become callable through `call` without a new
+/// native function. Calling a precompile aborts if it is unknown, or if it (or precompiles as a
+/// whole) is not enabled on chain.
+module aptos_std::precompile {
+    #[test_only]
+    use std::features;
+
+    //
+    // Constants
+    //
+
+    /// Precompiles are not enabled.
+    const E_PRECOMPILES_DISABLED: u64 = 1;
+    /// No precompile is registered with the given ID.
+    const E_UNKNOWN_PRECOMPILE: u64 = 2;
+    /// The precompile is registered, but its feature flag is not enabled.
+    const E_PRECOMPILE_DISABLED: u64 = 3;
+    /// The input is malformed for the precompile.
+    const E_INVALID_INPUT: u64 = 4;
+
+    /// Keccak-256
+    const KECCAK256: u64 = 1;
+    /// SHA2-512
+    const SHA2_512: u64 = 2;
+    /// RIPEMD-160
+    const RIPEMD160: u64 = 3;
+    /// BLAKE2B-256
+    const BLAKE2B_256: u64 = 4;
+
+    //
+    // Functions
+    //
+
+    public fun keccak256(): u64 { KECCAK256 }
+
+    public fun sha2_512(): u64 { SHA2_512 }
+
+    public fun ripemd160(): u64 { RIPEMD160 }
+
+    public fun blake2b_256(): u64 { BLAKE2B_256 }
+
+    /// Runs the precompile with the given ID on `input`, and returns its output.
+    public fun call(id: u64, input: vector<u8>): vector<u8> {
+        call_internal(id, input)
+    }
+
+    //
+    // Native functions
+    //
+
+    native fun call_internal(id: u64, input: vector<u8>): vector<u8>;
+
+    spec call_internal {
+        pragma opaque;
+    }
+
+    //
+    // Tests
+    //
+
+    #[test(fx = @std)]
+    fun test_call_keccak256(fx: signer) {
+        features::change_feature_flags_for_testing(&fx, vector[features::get_precompiles_feature()], vector[]);
+
+        assert!(
+            call(keccak256(), b"") == x"c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470",
+            1
+        );
+    }
+
+    #[test(fx = @std)]
+    #[expected_failure(abort_code = E_UNKNOWN_PRECOMPILE, location = Self)]
+    fun test_call_unknown(fx: signer) {
+        features::change_feature_flags_for_testing(&fx, vector[features::get_precompiles_feature()], vector[]);
+
+        call(0, b"");
+    }
+
+    #[test(fx = @std)]
+    #[expected_failure(abort_code = E_PRECOMPILES_DISABLED, location = Self)]
+    fun test_call_disabled(fx: signer) {
+        features::change_feature_flags_for_testing(&fx, vector[], vector[features::get_precompiles_feature()]);
+
+        call(keccak256(), b"");
+    }
+}
@@ -741,6 +741,48 @@ module std::features {
         is_enabled(MONOTONICALLY_INCREASING_COUNTER)
     }
 
+    /// Whether WebAuthn assertions signed by Ed25519 (COSE EdDSA) passkeys are accepted.
+    /// Lifetime: transient
+    const WEBAUTHN_ED25519_SIGNATURE: u64 = 99;
+
+    public fun get_webauthn_ed25519_signature_feature(): u64 { WEBAUTHN_ED25519_SIGNATURE }
+
+    public fun is_webauthn_ed25519_signature_enabled(): bool acquires Features {
+        is_enabled(WEBAUTHN_ED25519_SIGNATURE)
+    }
+
+    /// Whether gas sponsorship quotas configured by fee payers are enforced in the prologue.
+    /// Lifetime: transient
+    const SPONSORSHIP_QUOTAS: u64 = 100;
+
+    public fun get_sponsorship_quotas_feature(): u64 { SPONSORSHIP_QUOTAS }
+
+    public fun is_sponsorship_quotas_enabled(): bool acquires Features {
+        is_enabled(SPONSORSHIP_QUOTAS)
+    }
+
+    /// Whether the precompiles registered in the framework natives can be called through
+    /// `aptos_std::precompile`.
+    /// Lifetime: transient
+    const PRECOMPILES: u64 = 101;
+
+    public fun get_precompiles_feature(): u64 { PRECOMPILES }
+
+    public fun precompiles_enabled(): bool acquires Features {
+        is_enabled(PRECOMPILES)
+    }
+
+    /// Whether WebAuthn assertions are required to come from an assertion ceremony with the user
+    /// present.
+    /// Lifetime: transient
+    const WEBAUTHN_ASSERTION_CEREMONY: u64 = 102;
+
+    public fun get_webauthn_assertion_ceremony_feature(): u64 { WEBAUTHN_ASSERTION_CEREMONY }
+
+    public fun is_webauthn_assertion_ceremony_enabled(): bool acquires Features {
+        is_enabled(WEBAUTHN_ASSERTION_CEREMONY)
+    }
+
     // ============================================================================================
     // Feature Flag Implementation
 
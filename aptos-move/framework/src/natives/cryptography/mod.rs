@@ -7,6 +7,7 @@ pub mod bulletproofs;
 pub mod ed25519;
 mod helpers;
 pub mod multi_ed25519;
+pub mod precompiles;
 pub mod ristretto255;
 pub mod ristretto255_point;
 pub mod ristretto255_scalar;
@@ -0,0 +1,200 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! A registry of "precompiles": deterministic functions over bytes implemented in Rust, exposed
+//! to Move through a single native in `0x1::precompile`. New cryptographic primitives are added
+//! by registering an entry in `PRECOMPILES`, with its gas metering and feature flag, rather than
+//! by adding a new native function (and Move wrapper) for each of them.
+
+use aptos_gas_schedule::gas_params::natives::aptos_framework::*;
+use aptos_native_interface::{
+    safely_pop_arg, RawSafeNative, SafeNativeBuilder, SafeNativeContext, SafeNativeError,
+    SafeNativeResult,
+};
+use aptos_types::on_chain_config::FeatureFlag;
+use move_core_types::gas_algebra::NumBytes;
+use move_vm_runtime::native_functions::NativeFunction;
+use move_vm_types::{loaded_data::runtime_types::Type, values::Value};
+use ripemd::Digest as OtherDigest;
+use sha2::Digest;
+use smallvec::{smallvec, SmallVec};
+use std::collections::VecDeque;
+use tiny_keccak::{Hasher as KeccakHasher, Keccak};
+
+/// Abort codes, kept in sync with `0x1::precompile`.
+pub const E_PRECOMPILES_DISABLED: u64 = 1;
+pub const E_UNKNOWN_PRECOMPILE: u64 = 2;
+pub const E_PRECOMPILE_DISABLED: u64 = 3;
+pub const E_INVALID_INPUT: u64 = 4;
+
+/// Precompile IDs, kept in sync with `0x1::precompile`. IDs must never be reused.
+pub const KECCAK256: u64 = 1;
+pub const SHA2_512: u64 = 2;
+pub const RIPEMD160: u64 = 3;
+pub const BLAKE2B_256: u64 = 4;
+
+/// A precompile registration.
+pub struct Precompile {
+    /// The ID used to call the precompile from Move.
+    pub id: u64,
+    pub name: &'static str,
+    /// The feature flag that needs to be enabled for the precompile to be callable, if any.
+    pub feature_flag: Option<FeatureFlag>,
+    /// Charges the gas for running the precompile on the given input. This is called before the
+    /// precompile is executed, so that the cost of the execution is always paid for.
+    pub charge_gas: fn(&mut SafeNativeContext, &[u8]) -> SafeNativeResult<()>,
+    /// Runs the precompile. Errors are abort codes, e.g., `E_INVALID_INPUT` for malformed input.
+    pub execute: fn(&[u8]) -> Result<Vec<u8>, u64>,
+}
+
+/// All registered precompiles.
+pub static PRECOMPILES: &[Precompile] = &[
+    Precompile {
+        id: KECCAK256,
+        name: "keccak256",
+        feature_flag: None,
+        charge_gas: |context, input| {
+            context.charge(
+                HASH_KECCAK256_BASE + HASH_KECCAK256_PER_BYTE * NumBytes::new(input.len() as u64),
+            )
+        },
+        execute: |input| {
+            let mut hasher = Keccak::v256();
+            hasher.update(input);
+            let mut output = [0u8; 32];
+            hasher.finalize(&mut output);
+            Ok(output.to_vec())
+        },
+    },
+    Precompile {
+        id: SHA2_512,
+        name: "sha2_512",
+        feature_flag: Some(FeatureFlag::SHA_512_AND_RIPEMD_160_NATIVES),
+        charge_gas: |context, input| {
+            context.charge(
+                HASH_SHA2_512_BASE + HASH_SHA2_512_PER_BYTE * NumBytes::new(input.len() as u64),
+            )
+        },
+        execute: |input| Ok(sha2::Sha512::digest(input).to_vec()),
+    },
+    Precompile {
+        id: RIPEMD160,
+        name: "ripemd160",
+        feature_flag: Some(FeatureFlag::SHA_512_AND_RIPEMD_160_NATIVES),
+        charge_gas: |context, input| {
+            context.charge(
+                HASH_RIPEMD160_BASE + HASH_RIPEMD160_PER_BYTE * NumBytes::new(input.len() as u64),
+            )
+        },
+        execute: |input| Ok(ripemd::Ripemd160::digest(input).to_vec()),
+    },
+    Precompile {
+        id: BLAKE2B_256,
+        name: "blake2b_256",
+        feature_flag: Some(FeatureFlag::BLAKE2B_256_NATIVE),
+        charge_gas: |context, input| {
+            context.charge(
+                HASH_BLAKE2B_256_BASE
+                    + HASH_BLAKE2B_256_PER_BYTE * NumBytes::new(input.len() as u64),
+            )
+        },
+        execute: |input| {
+            Ok(blake2_rfc::blake2b::blake2b(32, &[], input)
+                .as_bytes()
+                .to_vec())
+        },
+    },
+];
+
+pub fn get_precompile(id: u64) -> Option<&'static Precompile> {
+    PRECOMPILES.iter().find(|precompile| precompile.id == id)
+}
+
+/***************************************************************************************************
+ * native fun call_internal
+ *
+ *   gas cost: as charged by the precompile
+ *
+ **************************************************************************************************/
+fn native_call_internal(
+    context: &mut SafeNativeContext,
+    _ty_args: Vec<Type>,
+    mut args: VecDeque<Value>,
+) -> SafeNativeResult<SmallVec<[Value; 1]>> {
+    debug_assert!(_ty_args.is_empty());
+    debug_assert!(args.len() == 2);
+
+    if !context
+        .get_feature_flags()
+        .is_enabled(FeatureFlag::PRECOMPILES)
+    {
+        return Err(SafeNativeError::Abort {
+            abort_code: E_PRECOMPILES_DISABLED,
+        });
+    }
+
+    let input = safely_pop_arg!(args, Vec<u8>);
+    let id = safely_pop_arg!(args, u64);
+
+    let precompile = get_precompile(id).ok_or(SafeNativeError::Abort {
+        abort_code: E_UNKNOWN_PRECOMPILE,
+    })?;
+    if let Some(feature_flag) = precompile.feature_flag {
+        if !context.get_feature_flags().is_enabled(feature_flag) {
+            return Err(SafeNativeError::Abort {
+                abort_code: E_PRECOMPILE_DISABLED,
+            });
+        }
+    }
+
+    (precompile.charge_gas)(context, &input)?;
+    let output =
+        (precompile.execute)(&input).map_err(|abort_code| SafeNativeError::Abort { abort_code })?;
+
+    Ok(smallvec![Value::vector_u8(output)])
+}
+
+/***************************************************************************************************
+ * module
+ *
+ **************************************************************************************************/
+pub fn make_all(
+    builder: &SafeNativeBuilder,
+) -> impl Iterator<Item = (String, NativeFunction)> + '_ {
+    let natives = [("call_internal", native_call_internal as RawSafeNative)];
+
+    builder.make_named_natives(natives)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_precompile_ids_are_unique() {
+        let ids: HashSet<_> = PRECOMPILES.iter().map(|precompile| precompile.id).collect();
+        assert_eq!(ids.len(), PRECOMPILES.len());
+        let names: HashSet<_> = PRECOMPILES
+            .iter()
+            .map(|precompile| precompile.name)
+            .collect();
+        assert_eq!(names.len(), PRECOMPILES.len());
+    }
+
+    #[test]
+    fn test_execute_precompiles() {
+        assert!(get_precompile(0).is_none());
+
+        let keccak256 = get_precompile(KECCAK256).unwrap();
+        assert_eq!(
+            hex::encode((keccak256.execute)(b"").unwrap()),
+            "c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470"
+        );
+
+        for (id, output_len) in [(SHA2_512, 64), (RIPEMD160, 20), (BLAKE2B_256, 32)] {
+            let output = (get_precompile(id).unwrap().execute)(b"aptos").unwrap();
+            assert_eq!(output.len(), output_len);
+        }
+    }
+}
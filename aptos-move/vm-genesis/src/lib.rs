@@ -140,6 +140,9 @@ pub fn encode_aptos_mainnet_genesis_transaction(
     framework: &ReleaseBundle,
     chain_id: ChainId,
     genesis_config: &GenesisConfiguration,
+    consensus_config: &OnChainConsensusConfig,
+    execution_config: &OnChainExecutionConfig,
+    gas_schedule: &GasScheduleV2,
 ) -> Transaction {
     assert!(!genesis_config.is_test, "This is mainnet!");
     validate_genesis_config(genesis_config);
@@ -163,18 +166,15 @@ pub fn encode_aptos_mainnet_genesis_transaction(
     let mut traversal_context = TraversalContext::new(&traversal_storage);
 
     // On-chain genesis process.
-    let consensus_config = OnChainConsensusConfig::default_for_genesis();
-    let execution_config = OnChainExecutionConfig::default_for_genesis();
-    let gas_schedule = default_gas_schedule();
     initialize(
         &mut session,
         &module_storage,
         &mut traversal_context,
         chain_id,
         genesis_config,
-        &consensus_config,
-        &execution_config,
-        &gas_schedule,
+        consensus_config,
+        execution_config,
+        gas_schedule,
     );
     initialize_features(
         &mut session,
@@ -1716,6 +1716,9 @@ pub fn test_mainnet_end_to_end() {
         aptos_cached_packages::head_release_bundle(),
         ChainId::mainnet(),
         &mainnet_genesis_config(),
+        &OnChainConsensusConfig::default_for_genesis(),
+        &OnChainExecutionConfig::default_for_genesis(),
+        &default_gas_schedule(),
     );
 
     let direct_writeset = if let Transaction::GenesisTransaction(direct_writeset) = transaction {
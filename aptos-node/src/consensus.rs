@@ -24,7 +24,7 @@ use aptos_consensus::{
 use aptos_consensus_notifications::ConsensusNotifier;
 use aptos_dkg_runtime::{start_dkg_runtime, DKGMessage};
 use aptos_event_notifications::{
-    DbBackedOnChainConfig, EventNotificationListener, ReconfigNotificationListener,
+    EventNotificationListener, InMemoryOnChainConfig, ReconfigNotificationListener,
 };
 use aptos_jwk_consensus::{start_jwk_consensus_runtime, types::JWKConsensusMsg};
 use aptos_mempool::QuorumStoreRequest;
@@ -39,7 +39,7 @@ use tokio::runtime::Runtime;
 pub fn create_consensus_runtime(
     node_config: &NodeConfig,
     db_rw: DbReaderWriter,
-    consensus_reconfig_subscription: Option<ReconfigNotificationListener<DbBackedOnChainConfig>>,
+    consensus_reconfig_subscription: Option<ReconfigNotificationListener<InMemoryOnChainConfig>>,
     consensus_network_interfaces: Option<ApplicationNetworkInterfaces<ConsensusMsg>>,
     consensus_notifier: ConsensusNotifier,
     consensus_to_mempool_sender: Sender<QuorumStoreRequest>,
@@ -68,7 +68,7 @@ pub fn create_consensus_runtime(
 pub fn create_dkg_runtime(
     node_config: &mut NodeConfig,
     dkg_subscriptions: Option<(
-        ReconfigNotificationListener<DbBackedOnChainConfig>,
+        ReconfigNotificationListener<InMemoryOnChainConfig>,
         EventNotificationListener,
     )>,
     dkg_network_interfaces: Option<ApplicationNetworkInterfaces<DKGMessage>>,
@@ -107,7 +107,7 @@ pub fn create_dkg_runtime(
 pub fn create_jwk_consensus_runtime(
     node_config: &mut NodeConfig,
     jwk_consensus_subscriptions: Option<(
-        ReconfigNotificationListener<DbBackedOnChainConfig>,
+        ReconfigNotificationListener<InMemoryOnChainConfig>,
         EventNotificationListener,
     )>,
     jwk_consensus_network_interfaces: Option<ApplicationNetworkInterfaces<JWKConsensusMsg>>,
@@ -147,7 +147,7 @@ pub fn create_consensus_observer_and_publisher(
     consensus_to_mempool_sender: Sender<QuorumStoreRequest>,
     db_rw: DbReaderWriter,
     consensus_observer_reconfig_subscription: Option<
-        ReconfigNotificationListener<DbBackedOnChainConfig>,
+        ReconfigNotificationListener<InMemoryOnChainConfig>,
     >,
 ) -> (
     Option<Runtime>,
@@ -216,7 +216,7 @@ fn create_consensus_observer(
     state_sync_notifier: ConsensusNotifier,
     consensus_to_mempool_sender: Sender<QuorumStoreRequest>,
     db_rw: DbReaderWriter,
-    observer_reconfig_subscription: Option<ReconfigNotificationListener<DbBackedOnChainConfig>>,
+    observer_reconfig_subscription: Option<ReconfigNotificationListener<InMemoryOnChainConfig>>,
 ) {
     // If the observer is not enabled, return early
     if !node_config.consensus_observer.observer_enabled {
@@ -0,0 +1,191 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::{anyhow, Result};
+use aptos_api_types::MoveResource;
+use aptos_backup_cli::storage::{
+    cloud_storage::{CloudStorage, CloudStorageOpt},
+    local_fs::LocalFs,
+    BackupStorage, ShellSafeName,
+};
+use aptos_config::config::EpochSnapshotConfig;
+use aptos_event_notifications::{
+    InMemoryOnChainConfig, ReconfigNotification, ReconfigNotificationListener,
+};
+use aptos_logger::{error, info};
+use aptos_resource_viewer::AptosValueAnnotator;
+use aptos_storage_interface::{
+    state_store::state_view::db_state_view::{DbStateView, DbStateViewAtVersion},
+    DbReader,
+};
+use aptos_types::{
+    account_address::AccountAddress,
+    on_chain_config::ValidatorSet,
+    state_store::{state_key::StateKey, TStateView},
+    transaction::Version,
+};
+use flate2::{write::GzEncoder, Compression};
+use futures::StreamExt;
+use move_core_types::language_storage::StructTag;
+use serde::Serialize;
+use std::{collections::BTreeMap, io::Write, str::FromStr, sync::Arc};
+use tokio::{io::AsyncWriteExt, runtime::Runtime};
+
+/// The name of the snapshot file written for each epoch
+const SNAPSHOT_FILE_NAME: &str = "snapshot.json.gz";
+
+/// A snapshot of the selected on-chain state at the end of an epoch
+#[derive(Serialize)]
+struct EpochSnapshot {
+    epoch: u64,
+    version: Version,
+    resources: Vec<MoveResource>,
+    validators: BTreeMap<AccountAddress, Vec<MoveResource>>,
+}
+
+/// Exports a snapshot of the configured resources to the sink whenever an epoch ends
+struct EpochSnapshotExporter {
+    db_reader: Arc<dyn DbReader>,
+    storage: Arc<dyn BackupStorage>,
+    resources: Vec<StructTag>,
+    validator_resources: Vec<StructTag>,
+}
+
+impl EpochSnapshotExporter {
+    fn new(config: &EpochSnapshotConfig, db_reader: Arc<dyn DbReader>) -> Result<Self> {
+        let storage: Arc<dyn BackupStorage> = if config.sink.contains("://") {
+            Arc::new(CloudStorage::new_with_opt(CloudStorageOpt::from_str(
+                &config.sink,
+            )?)?)
+        } else {
+            Arc::new(LocalFs::new(config.sink.clone().into()))
+        };
+
+        Ok(Self {
+            db_reader,
+            storage,
+            resources: parse_struct_tags(&config.resources)?,
+            validator_resources: parse_struct_tags(&config.validator_resources)?,
+        })
+    }
+
+    /// Exports the state at the end of the epoch that was closed by the given reconfiguration
+    async fn export(
+        &self,
+        notification: &ReconfigNotification<InMemoryOnChainConfig>,
+    ) -> Result<()> {
+        // The reconfiguration starts the next epoch, so its version ends the previous one
+        let epoch = notification.on_chain_configs.epoch().saturating_sub(1);
+        let version = notification.version;
+        let validator_set = notification.on_chain_configs.get::<ValidatorSet>()?;
+
+        // Read the configured resources at the epoch ending version
+        let state_view = self.db_reader.state_view_at_version(Some(version))?;
+        let mut resources = vec![];
+        for tag in &self.resources {
+            if let Some(resource) = read_resource(&state_view, tag.address, tag)? {
+                resources.push(resource);
+            }
+        }
+        let mut validators = BTreeMap::new();
+        for validator in validator_set.active_validators() {
+            let mut validator_resources = vec![];
+            for tag in &self.validator_resources {
+                if let Some(resource) = read_resource(&state_view, validator.account_address, tag)?
+                {
+                    validator_resources.push(resource);
+                }
+            }
+            validators.insert(validator.account_address, validator_resources);
+        }
+
+        // Compress the snapshot and write it to the sink
+        let snapshot = EpochSnapshot {
+            epoch,
+            version,
+            resources,
+            validators,
+        };
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        serde_json::to_writer(&mut encoder, &snapshot)?;
+        encoder.flush()?;
+        let bytes = encoder.finish()?;
+
+        let backup_name = ShellSafeName::from_str(&format!("epoch_{}_snapshot", epoch))?;
+        let backup_handle = self.storage.create_backup(&backup_name).await?;
+        let (file_handle, mut file) = self
+            .storage
+            .create_for_write(
+                &backup_handle,
+                &ShellSafeName::from_str(SNAPSHOT_FILE_NAME)?,
+            )
+            .await?;
+        file.write_all(&bytes).await?;
+        file.shutdown().await?;
+
+        info!(
+            "Exported the snapshot for epoch {} (version {}) to {}",
+            epoch, version, file_handle
+        );
+        Ok(())
+    }
+}
+
+/// Parses the given resource strings into struct tags
+fn parse_struct_tags(resources: &[String]) -> Result<Vec<StructTag>> {
+    resources
+        .iter()
+        .map(|resource| {
+            StructTag::from_str(resource)
+                .map_err(|error| anyhow!("Invalid resource {}: {}", resource, error))
+        })
+        .collect()
+}
+
+/// Reads and annotates the resource stored under the given address (if it exists)
+fn read_resource(
+    state_view: &DbStateView,
+    address: AccountAddress,
+    tag: &StructTag,
+) -> Result<Option<MoveResource>> {
+    let state_key = StateKey::resource(&address, tag)?;
+    match state_view.get_state_value_bytes(&state_key)? {
+        Some(bytes) => {
+            let annotated = AptosValueAnnotator::new(state_view).view_resource(tag, &bytes)?;
+            Ok(Some(MoveResource::try_from(annotated)?))
+        },
+        None => Ok(None),
+    }
+}
+
+/// Starts the epoch snapshot exporter (if enabled) and returns the runtime
+pub fn start_epoch_snapshot_exporter(
+    config: &EpochSnapshotConfig,
+    db_reader: Arc<dyn DbReader>,
+    reconfig_subscription: Option<ReconfigNotificationListener<InMemoryOnChainConfig>>,
+) -> Option<Runtime> {
+    let mut reconfig_subscription = reconfig_subscription?;
+    let exporter = match EpochSnapshotExporter::new(config, db_reader) {
+        Ok(exporter) => exporter,
+        Err(error) => {
+            error!("Failed to create the epoch snapshot exporter: {:?}", error);
+            return None;
+        },
+    };
+
+    let runtime = aptos_runtimes::spawn_named_runtime("epoch-snap".into(), Some(1));
+    runtime.spawn(async move {
+        // The first notification carries the configs at startup, not an epoch ending
+        let _ = reconfig_subscription.next().await;
+
+        while let Some(notification) = reconfig_subscription.next().await {
+            if let Err(error) = exporter.export(&notification).await {
+                error!(
+                    "Failed to export the epoch snapshot at version {}: {:?}",
+                    notification.version, error
+                );
+            }
+        }
+    });
+    Some(runtime)
+}
@@ -6,10 +6,12 @@
 #![forbid(unsafe_code)]
 
 mod consensus;
+mod epoch_snapshot;
 mod indexer;
 mod logger;
 mod network;
 mod services;
+mod shutdown;
 mod state_sync;
 mod storage;
 pub mod utils;
@@ -22,11 +24,12 @@ use anyhow::{anyhow, Context};
 use aptos_admin_service::AdminService;
 use aptos_api::bootstrap as bootstrap_api;
 use aptos_build_info::build_information;
-use aptos_config::config::{merge_node_config, NodeConfig, PersistableConfig};
+use aptos_config::config::{merge_node_config, reloadable_config, NodeConfig, PersistableConfig};
 use aptos_framework::ReleaseBundle;
 use aptos_genesis::builder::GenesisConfiguration;
 use aptos_logger::{prelude::*, telemetry_log_writer::TelemetryLog, Level, LoggerFilterUpdater};
 use aptos_state_sync_driver::driver_factory::StateSyncRuntimes;
+use aptos_storage_interface::DbReaderWriter;
 use aptos_types::{
     chain_id::ChainId, keyless::Groth16VerificationKey, on_chain_config::OnChainJWKConsensusConfig,
 };
@@ -38,6 +41,7 @@ use serde_json::Value;
 use std::{
     env, fs,
     io::Write,
+    num::NonZeroUsize,
     path::{Path, PathBuf},
     sync::{
         atomic::{AtomicBool, Ordering},
@@ -175,6 +179,7 @@ impl AptosNodeArgs {
             }
 
             // A config file exists, attempt to parse the config
+            reloadable_config::set_node_config_path(config_path.clone());
             let config = NodeConfig::load_from_path(config_path.clone()).unwrap_or_else(|error| {
                 panic!(
                     "Failed to load the node config file! Given file path: {:?}. Error: {:?}",
@@ -203,6 +208,7 @@ pub struct AptosHandle {
     _consensus_publisher_runtime: Option<Runtime>,
     _consensus_runtime: Option<Runtime>,
     _dkg_runtime: Option<Runtime>,
+    _epoch_snapshot_runtime: Option<Runtime>,
     _indexer_grpc_runtime: Option<Runtime>,
     _indexer_runtime: Option<Runtime>,
     _indexer_table_info_runtime: Option<Runtime>,
@@ -213,6 +219,7 @@ pub struct AptosHandle {
     _state_sync_runtimes: StateSyncRuntimes,
     _telemetry_runtime: Option<Runtime>,
     _indexer_db_runtime: Option<Runtime>,
+    db_rw: DbReaderWriter,
 }
 
 pub fn start(
@@ -274,21 +281,42 @@ pub fn start_and_report_ports(
     }
 
     // Set up the node environment and start it
-    let _node_handle = setup_environment_and_start_node(
+    let shutdown_config = config.node_shutdown;
+    let node_handle = setup_environment_and_start_node(
         config,
         remote_log_receiver,
         Some(logger_filter_update),
         api_port_tx,
         indexer_grpc_port_tx,
     )?;
-    let term = Arc::new(AtomicBool::new(false));
-    while !term.load(Ordering::Acquire) {
-        thread::park();
+
+    // Without a graceful shutdown, the node runs until the process is killed
+    if !shutdown_config.graceful_shutdown {
+        let term = Arc::new(AtomicBool::new(false));
+        while !term.load(Ordering::Acquire) {
+            thread::park();
+        }
     }
 
+    // Otherwise, shut down the node once a termination signal is received
+    shutdown::wait_for_shutdown_signal()?;
+    node_handle.shutdown(&shutdown_config);
+
     Ok(())
 }
 
+/// Starts a node in a process that already runs a node started by `start()`, e.g., one of the
+/// other validators of a localnet. The process wide state (panic handler, global rayon pool,
+/// node identity and logger) is not initialized again, so it is shared with the first node.
+pub fn start_additional_node(config: NodeConfig) -> anyhow::Result<AptosHandle> {
+    if aptos_node_identity::chain_id().is_none() {
+        return Err(anyhow!(
+            "Additional nodes can only be started once the first node is running"
+        ));
+    }
+    setup_environment_and_start_node(config, None, None, None, None)
+}
+
 /// Load a config based on a variety of different ways to provide config options. For
 /// more information about each argument and its precedence, see
 /// `setup_test_environment_and_start_node`.
@@ -305,18 +333,64 @@ pub fn load_node_config<R>(
 where
     R: rand::RngCore + rand::CryptoRng,
 {
-    // The validator builder puts the first node in the 0 directory
-    let validator_config_path = test_dir.join("0").join("node.yaml");
+    let mut configs = load_node_configs(
+        config_path,
+        test_config_override_path,
+        test_dir,
+        NonZeroUsize::new(1).unwrap(),
+        random_ports,
+        enable_lazy_mode,
+        enable_performance_mode,
+        framework,
+        rng,
+    )?;
+    Ok(configs.remove(0))
+}
 
-    let config = if validator_config_path.exists() {
-        NodeConfig::load_from_path(&validator_config_path)
-            .map_err(|error| anyhow!("Unable to load config: {:?}", error))?
+/// Like `load_node_config`, but for a local network of `num_validators` validators. The
+/// configs are returned in validator index order. If configs already exist in `test_dir`,
+/// the network they describe must have the requested number of validators.
+pub fn load_node_configs<R>(
+    config_path: &Option<PathBuf>,
+    test_config_override_path: &Option<PathBuf>,
+    test_dir: &Path,
+    num_validators: NonZeroUsize,
+    random_ports: bool,
+    enable_lazy_mode: bool,
+    enable_performance_mode: bool,
+    framework: &ReleaseBundle,
+    rng: R,
+) -> anyhow::Result<Vec<NodeConfig>>
+where
+    R: rand::RngCore + rand::CryptoRng,
+{
+    // The validator builder puts each node in a directory named after its index
+    let validator_config_path = |index: usize| test_dir.join(index.to_string()).join("node.yaml");
+
+    let configs = if validator_config_path(0).exists() {
+        if validator_config_path(num_validators.get()).exists()
+            || !validator_config_path(num_validators.get() - 1).exists()
+        {
+            return Err(anyhow!(
+                "The existing network in {:?} does not have {} validators. \
+                Restart it from genesis to change the number of validators.",
+                test_dir,
+                num_validators
+            ));
+        }
+        (0..num_validators.get())
+            .map(|index| {
+                NodeConfig::load_from_path(validator_config_path(index))
+                    .map_err(|error| anyhow!("Unable to load config: {:?}", error))
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?
     } else {
-        // Create a test only config for a single validator node.
-        let config = create_single_node_test_config(
+        // Create test only configs for the validator nodes.
+        let configs = create_multi_node_test_config(
             config_path,
             test_config_override_path,
             test_dir,
+            num_validators,
             random_ports,
             enable_lazy_mode,
             enable_performance_mode,
@@ -332,10 +406,10 @@ where
         if let Some(ref config_path) = config_path {
             println!("\tUsed user-provided config from path: {:?}", config_path);
         }
-        config
+        configs
     };
 
-    Ok(config)
+    Ok(configs)
 }
 
 /// Print details about a node config configured for a test environment and start it.
@@ -451,6 +525,37 @@ pub fn create_single_node_test_config<R>(
     framework: &ReleaseBundle,
     rng: R,
 ) -> anyhow::Result<NodeConfig>
+where
+    R: rand::RngCore + rand::CryptoRng,
+{
+    let mut configs = create_multi_node_test_config(
+        config_path,
+        test_config_override_path,
+        test_dir,
+        NonZeroUsize::new(1).unwrap(),
+        random_ports,
+        enable_lazy_mode,
+        enable_performance_mode,
+        framework,
+        rng,
+    )?;
+    Ok(configs.remove(0))
+}
+
+/// Creates the configs for a local network of `num_validators` validators sharing one
+/// genesis. The first validator uses the ports of the template (unless `random_ports` is
+/// set), all others get random ports so the nodes can run side by side.
+pub fn create_multi_node_test_config<R>(
+    config_path: &Option<PathBuf>,
+    test_config_override_path: &Option<PathBuf>,
+    test_dir: &Path,
+    num_validators: NonZeroUsize,
+    random_ports: bool,
+    enable_lazy_mode: bool,
+    enable_performance_mode: bool,
+    framework: &ReleaseBundle,
+    rng: R,
+) -> anyhow::Result<Vec<NodeConfig>>
 where
     R: rand::RngCore + rand::CryptoRng,
 {
@@ -582,8 +687,9 @@ where
     // The validator builder puts the first node in the 0 directory
     let aptos_root_key_path = test_dir.join("mint.key");
 
-    // Build genesis and the validator node
+    // Build genesis and the validator nodes
     let builder = aptos_genesis::builder::Builder::new(test_dir, framework.clone())?
+        .with_num_validators(num_validators)
         .with_init_config(Some(Arc::new(move |_, config, _| {
             *config = node_config.clone();
         })))
@@ -628,14 +734,19 @@ where
         genesis_waypoint.to_string().as_bytes(),
     )?;
 
-    aptos_config::config::sanitize_node_config(validators[0].config.override_config_mut())?;
+    validators
+        .iter_mut()
+        .map(|validator| {
+            aptos_config::config::sanitize_node_config(validator.config.override_config_mut())?;
 
-    let mut node_config = validators[0].config.override_config().clone();
+            let mut node_config = validator.config.override_config().clone();
 
-    // Enable the AdminService.
-    node_config.admin_service.enabled = Some(true);
+            // Enable the AdminService.
+            node_config.admin_service.enabled = Some(true);
 
-    Ok(node_config)
+            Ok(node_config)
+        })
+        .collect()
 }
 
 fn configure_keyless_with_vk(
@@ -698,6 +809,9 @@ pub fn setup_environment_and_start_node(
     // Log the node config at node startup
     node_config.log_all_configs();
 
+    // Initialize the subset of the config that can be reloaded at runtime
+    reloadable_config::initialize(&node_config);
+
     // Starts the admin service
     let mut admin_service = services::start_admin_service(&node_config);
 
@@ -713,8 +827,12 @@ pub fn setup_environment_and_start_node(
     // Obtain the chain_id from the DB
     let chain_id = utils::fetch_chain_id(&db_rw)?;
 
-    // Set the chain_id in global AptosNodeIdentity
-    aptos_node_identity::set_chain_id(chain_id)?;
+    // Set the chain_id in global AptosNodeIdentity. Additional nodes in the same process (see
+    // `start_additional_node`) find it already set by the first node.
+    match aptos_node_identity::chain_id() {
+        Some(existing_chain_id) if existing_chain_id == chain_id => {},
+        _ => aptos_node_identity::set_chain_id(chain_id)?,
+    }
 
     // Start the telemetry service (as early as possible and before any blocking calls)
     let telemetry_runtime = services::start_telemetry_service(
@@ -732,8 +850,16 @@ pub fn setup_environment_and_start_node(
         consensus_reconfig_subscription,
         dkg_subscriptions,
         jwk_consensus_subscriptions,
+        epoch_snapshot_reconfig_subscription,
     ) = state_sync::create_event_subscription_service(&node_config, &db_rw);
 
+    // Start the epoch snapshot exporter (if enabled)
+    let epoch_snapshot_runtime = epoch_snapshot::start_epoch_snapshot_exporter(
+        &node_config.epoch_snapshot,
+        db_rw.reader.clone(),
+        epoch_snapshot_reconfig_subscription,
+    );
+
     // Set up the networks and gather the application network handles
     let peers_and_metadata = network::create_peers_and_metadata(&node_config);
     let (
@@ -774,6 +900,7 @@ pub fn setup_environment_and_start_node(
         &node_config,
         aptos_data_client,
         peers_and_metadata.clone(),
+        db_rw.reader.clone(),
     );
 
     // Bootstrap the API and indexer
@@ -859,6 +986,7 @@ pub fn setup_environment_and_start_node(
         _consensus_publisher_runtime: consensus_publisher_runtime,
         _consensus_runtime: consensus_runtime,
         _dkg_runtime: dkg_runtime,
+        _epoch_snapshot_runtime: epoch_snapshot_runtime,
         _indexer_grpc_runtime: indexer_grpc_runtime,
         _indexer_runtime: indexer_runtime,
         _indexer_table_info_runtime: indexer_table_info_runtime,
@@ -869,6 +997,7 @@ pub fn setup_environment_and_start_node(
         _state_sync_runtimes: state_sync_runtimes,
         _telemetry_runtime: telemetry_runtime,
         _indexer_db_runtime: internal_indexer_db_runtime,
+        db_rw,
     })
 }
 
@@ -40,7 +40,8 @@ pub fn create_logger(
         .level(node_config.logger.level)
         .telemetry_level(node_config.logger.telemetry_level)
         .enable_telemetry_flush(node_config.logger.enable_telemetry_flush)
-        .tokio_console_port(node_config.logger.tokio_console_port);
+        .tokio_console_port(node_config.logger.tokio_console_port)
+        .otlp_trace_endpoint(node_config.logger.otlp_trace_endpoint.clone());
     if node_config.logger.enable_backtrace {
         logger_builder.enable_backtrace();
     }
@@ -82,6 +83,7 @@ fn log_config_and_build_information(node_config: &NodeConfig) {
         "default",
         "failpoints",
         "indexer",
+        "otel",
         "tokio-console"
     );
 
@@ -21,16 +21,22 @@ use aptos_network::{
         interface::{NetworkClient, NetworkServiceEvents},
         storage::PeersAndMetadata,
     },
-    protocols::network::{
-        NetworkApplicationConfig, NetworkClientConfig, NetworkEvents, NetworkSender,
-        NetworkServiceConfig,
+    protocols::{
+        health_checker::probe::{RpcApplicationProbe, TApplicationProbe},
+        network::{
+            NetworkApplicationConfig, NetworkClientConfig, NetworkEvents, NetworkSender,
+            NetworkServiceConfig,
+        },
     },
     ProtocolId,
 };
 use aptos_network_benchmark::NetbenchMessage;
 use aptos_network_builder::builder::NetworkBuilder;
 use aptos_peer_monitoring_service_types::PeerMonitoringServiceMessage;
-use aptos_storage_service_types::StorageServiceMessage;
+use aptos_storage_service_types::{
+    requests::{DataRequest, StorageServiceRequest},
+    StorageServiceMessage,
+};
 use aptos_time_service::TimeService;
 use aptos_types::chain_id::ChainId;
 use serde::{Deserialize, Serialize};
@@ -192,7 +198,7 @@ pub fn consensus_observer_network_configuration(
 pub fn netbench_network_configuration(
     node_config: &NodeConfig,
 ) -> Option<NetworkApplicationConfig> {
-    let cfg = node_config.netbench?;
+    let cfg = node_config.netbench.as_ref()?;
     if !cfg.enabled {
         return None;
     }
@@ -385,6 +391,13 @@ pub fn setup_networks_and_get_interfaces(
             storage_service_network_configuration(node_config),
             true,
         );
+        if network_config.enable_application_probes {
+            network_builder.add_application_probe(create_storage_service_probe(
+                &storage_service_network_handle,
+                node_config,
+                peers_and_metadata.clone(),
+            ));
+        }
         storage_service_network_handles.push(storage_service_network_handle);
 
         // Register the network benchmark test service
@@ -436,7 +449,11 @@ pub fn setup_networks_and_get_interfaces(
             netbench_network_configuration(node_config).unwrap(),
             peers_and_metadata,
         );
-        let netbench_service_threads = node_config.netbench.unwrap().netbench_service_threads;
+        let netbench_service_threads = node_config
+            .netbench
+            .as_ref()
+            .unwrap()
+            .netbench_service_threads;
         let netbench_runtime =
             aptos_runtimes::spawn_named_runtime("benchmark".into(), netbench_service_threads);
         start_netbench_service(node_config, netbench_interfaces, netbench_runtime.handle());
@@ -490,6 +507,35 @@ fn register_client_and_service_with_network<
     }
 }
 
+/// Creates a health checker probe that measures how quickly the storage service
+/// of each peer responds to (cheap) storage server summary requests.
+fn create_storage_service_probe(
+    storage_service_network_handle: &ApplicationNetworkHandle<StorageServiceMessage>,
+    node_config: &NodeConfig,
+    peers_and_metadata: Arc<PeersAndMetadata>,
+) -> Arc<dyn TApplicationProbe> {
+    let network_client_config =
+        storage_service_network_configuration(node_config).network_client_config;
+    let network_client = NetworkClient::new(
+        network_client_config.direct_send_protocols_and_preferences,
+        network_client_config.rpc_protocols_and_preferences,
+        HashMap::from([(
+            storage_service_network_handle.network_id,
+            storage_service_network_handle.network_sender.clone(),
+        )]),
+        peers_and_metadata,
+    );
+    let request = StorageServiceMessage::Request(StorageServiceRequest::new(
+        DataRequest::GetStorageServerSummary,
+        false, /* use_compression */
+    ));
+    Arc::new(RpcApplicationProbe::new(
+        "storage_service",
+        network_client,
+        request,
+    ))
+}
+
 /// Transforms the given network handles into interfaces that can
 /// be used by the applications themselves.
 fn transform_network_handles_into_interfaces(
@@ -13,7 +13,7 @@ use aptos_consensus::{
 use aptos_consensus_notifications::ConsensusNotifier;
 use aptos_data_client::client::AptosDataClient;
 use aptos_db_indexer::{db_indexer::InternalIndexerDB, indexer_reader::IndexerReaders};
-use aptos_event_notifications::{DbBackedOnChainConfig, ReconfigNotificationListener};
+use aptos_event_notifications::{InMemoryOnChainConfig, ReconfigNotificationListener};
 use aptos_indexer_grpc_fullnode::runtime::bootstrap as bootstrap_indexer_grpc;
 use aptos_indexer_grpc_table_info::runtime::{
     bootstrap as bootstrap_indexer_table_info, bootstrap_internal_indexer_db,
@@ -143,7 +143,7 @@ pub fn bootstrap_api_and_indexer(
 pub fn start_consensus_runtime(
     node_config: &NodeConfig,
     db_rw: DbReaderWriter,
-    consensus_reconfig_subscription: Option<ReconfigNotificationListener<DbBackedOnChainConfig>>,
+    consensus_reconfig_subscription: Option<ReconfigNotificationListener<InMemoryOnChainConfig>>,
     consensus_network_interfaces: ApplicationNetworkInterfaces<ConsensusMsg>,
     consensus_notifier: ConsensusNotifier,
     consensus_to_mempool_sender: Sender<QuorumStoreRequest>,
@@ -175,7 +175,7 @@ pub fn start_consensus_runtime(
 pub fn start_mempool_runtime_and_get_consensus_sender(
     node_config: &mut NodeConfig,
     db_rw: &DbReaderWriter,
-    mempool_reconfig_subscription: ReconfigNotificationListener<DbBackedOnChainConfig>,
+    mempool_reconfig_subscription: ReconfigNotificationListener<InMemoryOnChainConfig>,
     network_interfaces: ApplicationNetworkInterfaces<MempoolSyncMsg>,
     mempool_listener: MempoolNotificationListener,
     mempool_client_receiver: Receiver<MempoolClientRequest>,
@@ -190,6 +190,7 @@ pub fn start_mempool_runtime_and_get_consensus_sender(
     let mempool = aptos_mempool::bootstrap(
         node_config,
         Arc::clone(&db_rw.reader),
+        Arc::clone(&db_rw.writer),
         network_interfaces.network_client,
         network_interfaces.network_service_events,
         mempool_client_receiver,
@@ -213,11 +214,13 @@ pub fn start_node_inspection_service(
     node_config: &NodeConfig,
     aptos_data_client: AptosDataClient,
     peers_and_metadata: Arc<PeersAndMetadata>,
+    db_reader: Arc<dyn DbReader>,
 ) {
     aptos_inspection_service::start_inspection_service(
         node_config.clone(),
         aptos_data_client,
         peers_and_metadata,
+        db_reader,
     )
 }
 
@@ -0,0 +1,283 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::AptosHandle;
+use aptos_config::config::NodeShutdownConfig;
+use aptos_logger::prelude::*;
+use aptos_storage_interface::DbReaderWriter;
+use std::{
+    process, thread,
+    time::{Duration, Instant},
+};
+use tokio::runtime::Runtime;
+
+/// Blocks the current thread until the process receives SIGINT (or SIGTERM on unix)
+pub fn wait_for_shutdown_signal() -> anyhow::Result<()> {
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()?;
+    runtime.block_on(async {
+        #[cfg(unix)]
+        {
+            use tokio::signal::unix::{signal, SignalKind};
+
+            let mut sigterm = signal(SignalKind::terminate())?;
+            tokio::select! {
+                result = tokio::signal::ctrl_c() => result?,
+                _ = sigterm.recv() => {},
+            }
+        }
+        #[cfg(not(unix))]
+        tokio::signal::ctrl_c().await?;
+
+        Ok(())
+    })
+}
+
+/// The deadline by which the graceful shutdown must complete
+struct ShutdownDeadline {
+    deadline: Instant,
+}
+
+impl ShutdownDeadline {
+    fn new(shutdown_config: &NodeShutdownConfig) -> Self {
+        let deadline = Instant::now() + Duration::from_millis(shutdown_config.shutdown_deadline_ms);
+        Self { deadline }
+    }
+
+    fn remaining(&self) -> Duration {
+        self.deadline.saturating_duration_since(Instant::now())
+    }
+
+    fn has_passed(&self) -> bool {
+        self.remaining().is_zero()
+    }
+
+    /// Spawns a watchdog that exits the process if the shutdown overruns the deadline
+    /// (e.g., because a runtime is stuck on a blocking task).
+    fn spawn_watchdog(&self) {
+        let deadline = self.deadline;
+        thread::spawn(move || {
+            thread::sleep(deadline.saturating_duration_since(Instant::now()));
+            error!("Graceful shutdown did not complete before the deadline! Exiting.");
+            process::exit(1);
+        });
+    }
+}
+
+impl AptosHandle {
+    /// Shuts down the node gracefully, so that it restarts with as little as possible to
+    /// truncate from the databases: (i) stops accepting new transactions; (ii) waits for the
+    /// blocks that are already pre-committed to be committed, while consensus and state sync
+    /// are still running to commit them; (iii) stops consensus, state sync and the remaining
+    /// services; and (iv) flushes the buffered state to the databases. If the deadline passes,
+    /// the process exits.
+    pub fn shutdown(self, shutdown_config: &NodeShutdownConfig) {
+        let deadline = ShutdownDeadline::new(shutdown_config);
+        deadline.spawn_watchdog();
+        info!(
+            "Shutting down the node gracefully. Deadline: {:?}",
+            deadline.remaining()
+        );
+
+        // Stop accepting new transactions
+        shutdown_runtime(self._api_runtime, &deadline);
+        shutdown_runtime(self._indexer_grpc_runtime, &deadline);
+        shutdown_runtime(Some(self._mempool_runtime), &deadline);
+
+        // Wait for the in-flight commits, while consensus and state sync can still finish them
+        wait_for_commit_drain(&self.db_rw, shutdown_config, &deadline);
+
+        // Stop everything that can produce new blocks or write to the databases
+        shutdown_runtime(self._consensus_runtime, &deadline);
+        shutdown_runtime(self._consensus_observer_runtime, &deadline);
+        shutdown_runtime(self._consensus_publisher_runtime, &deadline);
+        shutdown_runtime(self._dkg_runtime, &deadline);
+        shutdown_runtime(self._jwk_consensus_runtime, &deadline);
+        drop(self._state_sync_runtimes);
+
+        // Stop the remaining services
+        shutdown_runtime(self._backup_runtime, &deadline);
+        shutdown_runtime(self._epoch_snapshot_runtime, &deadline);
+        shutdown_runtime(self._indexer_runtime, &deadline);
+        shutdown_runtime(self._indexer_table_info_runtime, &deadline);
+        shutdown_runtime(self._indexer_db_runtime, &deadline);
+        shutdown_runtime(Some(self._peer_monitoring_service_runtime), &deadline);
+        for network_runtime in self._network_runtimes {
+            shutdown_runtime(Some(network_runtime), &deadline);
+        }
+        drop(self._admin_service);
+
+        flush_db(&self.db_rw);
+        info!("Graceful shutdown complete!");
+        shutdown_runtime(self._telemetry_runtime, &deadline);
+    }
+}
+
+/// Shuts down the given runtime, waiting (at most until the deadline) for its blocking tasks
+fn shutdown_runtime(runtime: Option<Runtime>, deadline: &ShutdownDeadline) {
+    if let Some(runtime) = runtime {
+        runtime.shutdown_timeout(deadline.remaining());
+    }
+}
+
+/// Waits until the synced version reaches the version that was pre-committed when the wait
+/// started, or until the deadline passes. Blocks pre-committed during the wait are not waited
+/// for, as consensus keeps producing (empty) blocks until it is stopped. Returns whether the
+/// commit pipeline is drained.
+fn wait_for_commit_drain(
+    db_rw: &DbReaderWriter,
+    shutdown_config: &NodeShutdownConfig,
+    deadline: &ShutdownDeadline,
+) -> bool {
+    let poll_interval = Duration::from_millis(shutdown_config.drain_poll_interval_ms);
+    let target_version = match db_rw.reader.get_pre_committed_version() {
+        Ok(pre_committed_version) => pre_committed_version,
+        Err(error) => {
+            warn!("Failed to read the pre-committed version: {:?}", error);
+            return false;
+        },
+    };
+    loop {
+        match db_rw.reader.get_synced_version() {
+            Ok(synced_version) => {
+                if target_version <= synced_version {
+                    info!(
+                        "The commit pipeline is drained at version {:?}",
+                        synced_version
+                    );
+                    return true;
+                }
+                debug!(
+                    "Waiting for the commit pipeline to drain. Target version: {:?}, synced \
+                    version: {:?}",
+                    target_version, synced_version
+                );
+            },
+            Err(error) => {
+                warn!("Failed to read the synced version: {:?}", error);
+                return false;
+            },
+        }
+
+        if deadline.has_passed() {
+            warn!(
+                "The commit pipeline was not drained before the deadline! The versions after \
+                the synced version are truncated on restart."
+            );
+            return false;
+        }
+        thread::sleep(poll_interval.min(deadline.remaining()));
+    }
+}
+
+/// Flushes the buffered state to the databases. Returns whether the flush succeeded.
+fn flush_db(db_rw: &DbReaderWriter) -> bool {
+    match db_rw.writer.flush_buffered_state() {
+        Ok(()) => {
+            info!("Flushed the buffered state to the databases");
+            true
+        },
+        Err(error) => {
+            warn!("Failed to flush the buffered state: {:?}", error);
+            false
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aptos_storage_interface::{DbReader, DbWriter};
+    use aptos_types::transaction::Version;
+    use std::sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Arc,
+    };
+
+    /// A database whose versions only move when the test pre-commits or commits
+    #[derive(Default)]
+    struct MockDatabase {
+        pre_committed_version: AtomicU64,
+        synced_version: AtomicU64,
+        flushed: AtomicBool,
+    }
+
+    impl MockDatabase {
+        fn pre_commit(&self, version: Version) {
+            self.pre_committed_version.store(version, Ordering::SeqCst);
+        }
+
+        fn commit(&self, version: Version) {
+            self.synced_version.store(version, Ordering::SeqCst);
+        }
+    }
+
+    impl DbReader for MockDatabase {
+        fn get_synced_version(&self) -> aptos_storage_interface::Result<Option<Version>> {
+            Ok(Some(self.synced_version.load(Ordering::SeqCst)))
+        }
+
+        fn get_pre_committed_version(&self) -> aptos_storage_interface::Result<Option<Version>> {
+            Ok(Some(self.pre_committed_version.load(Ordering::SeqCst)))
+        }
+    }
+
+    impl DbWriter for MockDatabase {
+        fn flush_buffered_state(&self) -> aptos_storage_interface::Result<()> {
+            self.flushed.store(true, Ordering::SeqCst);
+            Ok(())
+        }
+    }
+
+    fn shutdown_config(shutdown_deadline_ms: u64) -> NodeShutdownConfig {
+        NodeShutdownConfig {
+            graceful_shutdown: true,
+            shutdown_deadline_ms,
+            drain_poll_interval_ms: 1,
+        }
+    }
+
+    fn database(pre_committed_version: Version, synced_version: Version) -> Arc<MockDatabase> {
+        let db = MockDatabase::default();
+        db.pre_commit(pre_committed_version);
+        db.commit(synced_version);
+        Arc::new(db)
+    }
+
+    #[test]
+    fn test_wait_for_commit_drain() {
+        let shutdown_config = shutdown_config(10_000);
+        let deadline = ShutdownDeadline::new(&shutdown_config);
+        let db = database(10, 5);
+        let db_rw = DbReaderWriter::from_arc(db.clone());
+
+        // Commit the pre-committed blocks while waiting, and keep pre-committing new blocks
+        let committer = thread::spawn(move || {
+            thread::sleep(Duration::from_millis(20));
+            db.pre_commit(20);
+            db.commit(10);
+        });
+        assert!(wait_for_commit_drain(&db_rw, &shutdown_config, &deadline));
+        committer.join().unwrap();
+        assert!(!deadline.has_passed());
+    }
+
+    #[test]
+    fn test_wait_for_commit_drain_deadline() {
+        // Nothing commits the pre-committed blocks, so the wait stops at the deadline
+        let shutdown_config = shutdown_config(50);
+        let deadline = ShutdownDeadline::new(&shutdown_config);
+        let db_rw = DbReaderWriter::from_arc(database(10, 5));
+        assert!(!wait_for_commit_drain(&db_rw, &shutdown_config, &deadline));
+        assert!(deadline.has_passed());
+    }
+
+    #[test]
+    fn test_flush_db() {
+        let db = database(10, 10);
+        let db_rw = DbReaderWriter::from_arc(db.clone());
+        assert!(flush_db(&db_rw));
+        assert!(db.flushed.load(Ordering::SeqCst));
+    }
+}
@@ -10,7 +10,7 @@ use aptos_data_streaming_service::{
     streaming_service::DataStreamingService,
 };
 use aptos_event_notifications::{
-    DbBackedOnChainConfig, EventNotificationListener, EventSubscriptionService,
+    EventNotificationListener, EventSubscriptionService, InMemoryOnChainConfig,
     ReconfigNotificationListener,
 };
 use aptos_executor::chunk_executor::ChunkExecutor;
@@ -44,17 +44,18 @@ pub fn create_event_subscription_service(
     db_rw: &DbReaderWriter,
 ) -> (
     EventSubscriptionService,
-    ReconfigNotificationListener<DbBackedOnChainConfig>,
-    Option<ReconfigNotificationListener<DbBackedOnChainConfig>>,
-    Option<ReconfigNotificationListener<DbBackedOnChainConfig>>,
+    ReconfigNotificationListener<InMemoryOnChainConfig>,
+    Option<ReconfigNotificationListener<InMemoryOnChainConfig>>,
+    Option<ReconfigNotificationListener<InMemoryOnChainConfig>>,
     Option<(
-        ReconfigNotificationListener<DbBackedOnChainConfig>,
+        ReconfigNotificationListener<InMemoryOnChainConfig>,
         EventNotificationListener,
     )>, // (reconfig_events, dkg_start_events) for DKG
     Option<(
-        ReconfigNotificationListener<DbBackedOnChainConfig>,
+        ReconfigNotificationListener<InMemoryOnChainConfig>,
         EventNotificationListener,
     )>, // (reconfig_events, jwk_updated_events) for JWK consensus
+    Option<ReconfigNotificationListener<InMemoryOnChainConfig>>,
 ) {
     // Create the event subscription service
     let mut event_subscription_service =
@@ -114,6 +115,17 @@ pub fn create_event_subscription_service(
         None
     };
 
+    // Create a reconfiguration subscription for the epoch snapshot exporter (if enabled)
+    let epoch_snapshot_reconfig_subscription = if node_config.epoch_snapshot.enabled {
+        Some(
+            event_subscription_service
+                .subscribe_to_reconfigurations()
+                .expect("Epoch snapshot exporter must subscribe to reconfigurations"),
+        )
+    } else {
+        None
+    };
+
     (
         event_subscription_service,
         mempool_reconfig_subscription,
@@ -121,6 +133,7 @@ pub fn create_event_subscription_service(
         consensus_reconfig_subscription,
         dkg_subscriptions,
         jwk_consensus_subscriptions,
+        epoch_snapshot_reconfig_subscription,
     )
 }
 
@@ -2,20 +2,38 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use anyhow::{anyhow, Result};
+use aptos_backup_cli::{
+    coordinators::restore::{RestoreCoordinator, RestoreCoordinatorOpt},
+    metadata::cache::MetadataCacheOpt,
+    storage::local_fs::LocalFs,
+    utils::{GlobalRestoreOptions, RestoreRunMode},
+};
 use aptos_backup_service::start_backup_service;
-use aptos_config::{config::NodeConfig, utils::get_genesis_txn};
-use aptos_db::{fast_sync_storage_wrapper::FastSyncStorageWrapper, AptosDB};
+use aptos_config::{
+    config::{NodeConfig, NO_OP_STORAGE_PRUNER_CONFIG},
+    utils::get_genesis_txn,
+};
+use aptos_db::{
+    db::crash_recovery::CrashRecoveryReport, fast_sync_storage_wrapper::FastSyncStorageWrapper,
+    get_restore_handler::GetRestoreHandler, AptosDB,
+};
 use aptos_db_indexer::db_indexer::InternalIndexerDB;
 use aptos_executor::db_bootstrapper::maybe_bootstrap;
 use aptos_indexer_grpc_table_info::internal_indexer_db_service::InternalIndexerDBService;
-use aptos_logger::{debug, info};
+use aptos_logger::{debug, info, warn};
 use aptos_storage_interface::{DbReader, DbReaderWriter};
 use aptos_types::{
     ledger_info::LedgerInfoWithSignatures, transaction::Version, waypoint::Waypoint,
 };
 use aptos_vm::aptos_vm::AptosVMBlockExecutor;
 use either::Either;
-use std::{fs, path::Path, sync::Arc, time::Instant};
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::{Instant, SystemTime, UNIX_EPOCH},
+};
 use tokio::{
     runtime::Runtime,
     sync::watch::{channel, Receiver as WatchReceiver},
@@ -42,6 +60,132 @@ pub(crate) fn maybe_apply_genesis(
     }
 }
 
+/// Restores the DB from the local backup in `storage.bootstrap_snapshot_dir` (if specified),
+/// unless the DB already exists. Interrupted restores are resumed on the next startup.
+#[cfg(not(feature = "consensus-only-perf-test"))]
+fn maybe_restore_from_local_backup(node_config: &NodeConfig) -> Result<()> {
+    const RESTORE_IN_PROGRESS_FILE: &str = "restore_from_backup_in_progress";
+
+    let Some(backup_dir) = &node_config.storage.bootstrap_snapshot_dir else {
+        return Ok(());
+    };
+    let db_dir = node_config.storage.dir();
+    let restore_in_progress_file = db_dir.join(RESTORE_IN_PROGRESS_FILE);
+    let db_exists = fs::read_dir(&db_dir).is_ok_and(|mut entries| entries.next().is_some());
+    if db_exists && !restore_in_progress_file.exists() {
+        debug!(
+            "DB already exists in {:?}, not restoring it from the local backup.",
+            db_dir
+        );
+        return Ok(());
+    }
+
+    info!(
+        "Restoring the DB in {:?} from the local backup in {:?}.",
+        db_dir, backup_dir
+    );
+    fs::create_dir_all(&db_dir)?;
+    fs::write(&restore_in_progress_file, [])?;
+
+    // The epoch history in the backup is verified against the waypoints, and everything else in
+    // the backup against the epoch history.
+    let mut trusted_waypoints = HashMap::new();
+    for waypoint in [
+        node_config
+            .execution
+            .genesis_waypoint
+            .as_ref()
+            .unwrap_or(&node_config.base.waypoint)
+            .genesis_waypoint(),
+        node_config.base.waypoint.waypoint(),
+    ] {
+        trusted_waypoints.insert(waypoint.version(), waypoint);
+    }
+    let restore_handler = Arc::new(AptosDB::open_kv_only(
+        node_config.storage.get_dir_paths(),
+        false, /* readonly */
+        NO_OP_STORAGE_PRUNER_CONFIG,
+        node_config.storage.rocksdb_configs,
+        false, /* indexer */
+        node_config.storage.buffered_state_target_items,
+        node_config.storage.max_num_nodes_per_lru_cache_shard,
+        None, /* internal_indexer_db */
+    )?)
+    .get_restore_handler();
+    let global_opt = GlobalRestoreOptions {
+        target_version: Version::MAX,
+        trusted_waypoints: Arc::new(trusted_waypoints),
+        run_mode: Arc::new(RestoreRunMode::Restore { restore_handler }),
+        concurrent_downloads: num_cpus::get(),
+        replay_concurrency_level: num_cpus::get(),
+    };
+    let opt = RestoreCoordinatorOpt {
+        metadata_cache_opt: MetadataCacheOpt::new(None::<PathBuf>),
+        replay_all: false,
+        ledger_history_start_version: None,
+        skip_epoch_endings: false,
+    };
+    let storage = Arc::new(LocalFs::new(backup_dir.clone()));
+
+    let runtime = aptos_runtimes::spawn_named_runtime("db-restore".into(), None);
+    runtime
+        .block_on(RestoreCoordinator::new(opt, global_opt, storage).run())
+        .map_err(|err| anyhow!("Failed to restore the DB from the local backup: {}", err))?;
+    fs::remove_file(&restore_in_progress_file)?;
+
+    info!("Restored the DB from the local backup, state sync continues from there.");
+    Ok(())
+}
+
+/// Truncates any partially committed data in the DB (if crash recovery is enabled), and writes
+/// a report of the removed data to the `crash_recovery_reports` directory of the DB.
+#[cfg(not(feature = "consensus-only-perf-test"))]
+fn maybe_recover_from_crash(node_config: &NodeConfig) -> Result<()> {
+    const CRASH_RECOVERY_REPORTS_DIR: &str = "crash_recovery_reports";
+
+    if !node_config.storage.enable_crash_recovery {
+        return Ok(());
+    }
+    let db_dir = node_config.storage.dir();
+    let db_exists = fs::read_dir(&db_dir).is_ok_and(|mut entries| entries.next().is_some());
+    if !db_exists {
+        return Ok(());
+    }
+
+    // The internal indexer DB is closed again before the DB is bootstrapped
+    let internal_indexer_db = InternalIndexerDBService::get_indexer_db(node_config);
+    let report = AptosDB::recover_from_crash(
+        &node_config.storage.get_dir_paths(),
+        node_config.storage.rocksdb_configs,
+        internal_indexer_db.as_ref(),
+        node_config.storage.crash_recovery_max_truncated_versions,
+    )
+    .map_err(|err| anyhow!("Failed to recover the DB from a crash: {}", err))?;
+    if let Some(report) = report {
+        let reports_dir = db_dir.join(CRASH_RECOVERY_REPORTS_DIR);
+        let report_path = write_crash_recovery_report(&reports_dir, &report)?;
+        warn!(
+            "Truncated the DB to version {} after a crash. Recovery report: {:?}",
+            report.consistent_version, report_path
+        );
+    }
+    Ok(())
+}
+
+/// Writes the crash recovery report to a new file (named by the current time) in the given
+/// directory, and returns the path of the file
+#[cfg(not(feature = "consensus-only-perf-test"))]
+fn write_crash_recovery_report(
+    reports_dir: &Path,
+    report: &CrashRecoveryReport,
+) -> Result<PathBuf> {
+    fs::create_dir_all(reports_dir)?;
+    let timestamp_secs = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+    let report_path = reports_dir.join(format!("{}.json", timestamp_secs));
+    fs::write(&report_path, serde_json::to_vec_pretty(report)?)?;
+    Ok(report_path)
+}
+
 #[cfg(not(feature = "consensus-only-perf-test"))]
 pub(crate) fn bootstrap_db(
     node_config: &NodeConfig,
@@ -52,6 +196,10 @@ pub(crate) fn bootstrap_db(
     Option<InternalIndexerDB>,
     Option<WatchReceiver<(Instant, Version)>>,
 )> {
+    maybe_restore_from_local_backup(node_config)?;
+    maybe_recover_from_crash(node_config)?;
+    aptos_scratchpad::set_rss_budget_bytes(node_config.storage.rss_budget_bytes);
+
     let internal_indexer_db = InternalIndexerDBService::get_indexer_db(node_config);
     let (update_sender, update_receiver) = if internal_indexer_db.is_some() {
         let (sender, receiver) = channel::<(Instant, Version)>((Instant::now(), 0 as Version));
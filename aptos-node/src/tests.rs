@@ -1,7 +1,10 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{create_single_node_test_config, network};
+use crate::{
+    create_multi_node_test_config, create_single_node_test_config, network,
+    setup_environment_and_start_node, start_additional_node,
+};
 use aptos_config::config::{NodeConfig, WaypointConfig};
 use aptos_event_notifications::EventSubscriptionService;
 use aptos_infallible::RwLock;
@@ -9,7 +12,7 @@ use aptos_storage_interface::{DbReader, DbReaderWriter, DbWriter};
 use aptos_temppath::TempPath;
 use aptos_types::{chain_id::ChainId, waypoint::Waypoint};
 use rand::SeedableRng;
-use std::{fs, sync::Arc};
+use std::{collections::HashSet, fs, num::NonZeroUsize, sync::Arc};
 
 /// A mock database implementing DbReader and DbWriter
 pub struct MockDatabase;
@@ -115,6 +118,74 @@ fn test_create_single_node_test_config() {
     );
 }
 
+#[test]
+fn test_create_multi_node_test_config() {
+    let test_dir = aptos_temppath::TempPath::new().as_ref().to_path_buf();
+    fs::DirBuilder::new()
+        .recursive(true)
+        .create(&test_dir)
+        .expect("Failed to create test_dir");
+
+    let configs = create_multi_node_test_config(
+        &None,
+        &None,
+        &test_dir,
+        NonZeroUsize::new(3).unwrap(),
+        false,
+        false,
+        false,
+        aptos_cached_packages::head_release_bundle(),
+        rand::rngs::StdRng::from_entropy(),
+    )
+    .unwrap();
+
+    // Every validator has its own data dir and API port, but they share a genesis
+    assert_eq!(configs.len(), 3);
+    let data_dirs: HashSet<_> = configs.iter().map(|c| c.storage.dir()).collect();
+    assert_eq!(data_dirs.len(), 3);
+    let api_ports: HashSet<_> = configs.iter().map(|c| c.api.address.port()).collect();
+    assert_eq!(api_ports.len(), 3);
+    for config in &configs {
+        assert_eq!(
+            config.base.waypoint.genesis_waypoint(),
+            configs[0].base.waypoint.genesis_waypoint()
+        );
+    }
+}
+
+#[test]
+fn test_start_multiple_validators_in_process() {
+    let test_dir = aptos_temppath::TempPath::new().as_ref().to_path_buf();
+    fs::DirBuilder::new()
+        .recursive(true)
+        .create(&test_dir)
+        .expect("Failed to create test_dir");
+
+    let mut configs = create_multi_node_test_config(
+        &None,
+        &None,
+        &test_dir,
+        NonZeroUsize::new(2).unwrap(),
+        false,
+        false,
+        false,
+        aptos_cached_packages::head_release_bundle(),
+        rand::rngs::StdRng::from_entropy(),
+    )
+    .unwrap();
+    let second_config = configs.pop().unwrap();
+    let first_config = configs.pop().unwrap();
+
+    // The process wide state is set up by the first node only
+    assert!(start_additional_node(second_config.clone()).is_err());
+    aptos_node_identity::init(first_config.get_peer_id()).unwrap();
+    let _first_handle =
+        setup_environment_and_start_node(first_config, None, None, None, None).unwrap();
+
+    // Starting another node must not initialize the process wide state again
+    let _second_handle = start_additional_node(second_config).unwrap();
+}
+
 #[test]
 fn test_verifier_cache_enabled_for_aptos_node() {
     use std::process::{Command, Stdio};
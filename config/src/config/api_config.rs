@@ -11,7 +11,7 @@ use crate::{
 };
 use aptos_types::{account_address::AccountAddress, chain_id::ChainId};
 use serde::{Deserialize, Serialize};
-use std::net::SocketAddr;
+use std::{collections::HashSet, net::SocketAddr};
 
 #[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
 #[serde(default, deny_unknown_fields)]
@@ -83,12 +83,22 @@ pub struct ApiConfig {
     pub view_filter: ViewFilter,
     /// Periodically log stats for view function and simulate transaction usage
     pub periodic_function_stats_sec: Option<u64>,
+    /// Periodically sample the consensus and state sync metrics reported by the network health
+    pub periodic_network_health_sample_ms: Option<u64>,
+    /// Number of metric samples (i.e., the window) used to compute the network health
+    pub network_health_window_samples: usize,
+    /// Number of recent blocks used to compute the consensus round rate
+    pub network_health_block_history: u64,
     /// The time wait_by_hash will wait before returning 404.
     pub wait_by_hash_timeout_ms: u64,
     /// The interval at which wait_by_hash will poll the storage for the transaction.
     pub wait_by_hash_poll_interval_ms: u64,
     /// The number of active wait_by_hash requests that can be active at any given time.
     pub wait_by_hash_max_active_connections: usize,
+    /// The number of modules whose metadata (e.g., ABI) is cached. 0 disables the cache.
+    pub module_metadata_cache_size: u64,
+    /// API keys required to access privileged endpoints
+    pub authentication: ApiAuthenticationConfig,
 }
 
 const DEFAULT_ADDRESS: &str = "127.0.0.1";
@@ -138,9 +148,14 @@ impl Default for ApiConfig {
             periodic_gas_estimation_ms: Some(30_000),
             view_filter: ViewFilter::default(),
             periodic_function_stats_sec: Some(60),
+            periodic_network_health_sample_ms: Some(1_000),
+            network_health_window_samples: 60,
+            network_health_block_history: 100,
             wait_by_hash_timeout_ms: 1_000,
             wait_by_hash_poll_interval_ms: 20,
             wait_by_hash_max_active_connections: 100,
+            module_metadata_cache_size: 1_000,
+            authentication: ApiAuthenticationConfig::default(),
         }
     }
 }
@@ -190,6 +205,35 @@ impl ConfigSanitizer for ApiConfig {
             ));
         }
 
+        // Validate the API keys
+        let authentication = &api_config.authentication;
+        if !authentication.protected_scopes.is_empty() && authentication.api_keys.is_empty() {
+            return Err(Error::ConfigSanitizerFailed(
+                sanitizer_name,
+                "API key protected scopes are set, but no API keys are configured!".into(),
+            ));
+        }
+        let mut key_names = HashSet::new();
+        for api_key in &authentication.api_keys {
+            if !key_names.insert(&api_key.name) {
+                return Err(Error::ConfigSanitizerFailed(
+                    sanitizer_name,
+                    format!("Duplicate API key name: {}", api_key.name),
+                ));
+            }
+            if api_key.key_sha256.len() != 64
+                || !api_key.key_sha256.chars().all(|c| c.is_ascii_hexdigit())
+            {
+                return Err(Error::ConfigSanitizerFailed(
+                    sanitizer_name,
+                    format!(
+                        "The key_sha256 of API key {} must be a hex encoded SHA-256!",
+                        api_key.name
+                    ),
+                ));
+            }
+        }
+
         // Sanitize the gas estimation config
         GasEstimationConfig::sanitize(node_config, node_type, chain_id)?;
 
@@ -197,6 +241,55 @@ impl ConfigSanitizer for ApiConfig {
     }
 }
 
+/// Authentication of the requests to privileged endpoints, using API keys. The key is passed in
+/// the `x-aptos-api-key` header, or as a bearer token in the `Authorization` header.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ApiAuthenticationConfig {
+    /// The scopes whose endpoints require an API key. The endpoints of the other scopes (and
+    /// the endpoints that don't belong to any scope) don't require one.
+    pub protected_scopes: Vec<ApiScope>,
+    /// The API keys, along with the scopes they grant access to
+    pub api_keys: Vec<ApiKeyConfig>,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct ApiKeyConfig {
+    /// The name of the key, used in logs and metrics
+    pub name: String,
+    /// The hex encoded SHA-256 of the key, so that the key itself isn't stored in the config.
+    /// To calculate it, use e.g., `printf <key> | sha256sum`.
+    pub key_sha256: String,
+    /// The scopes the key grants access to
+    pub scopes: Vec<ApiScope>,
+}
+
+/// Groups of privileged endpoints that can be protected by API keys
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ApiScope {
+    /// Administrative endpoints (e.g., setting failpoints)
+    Admin,
+    /// Transaction submission (single and batch)
+    SubmitTransactions,
+    /// Transaction simulation
+    SimulateTransactions,
+    /// Long-polling endpoints holding connections open (e.g., waiting for a transaction)
+    Streaming,
+}
+
+impl ApiScope {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ApiScope::Admin => "admin",
+            ApiScope::SubmitTransactions => "submit_transactions",
+            ApiScope::SimulateTransactions => "simulate_transactions",
+            ApiScope::Streaming => "streaming",
+        }
+    }
+}
+
 // This is necessary because we can't import the EntryFunctionId type from the API types.
 #[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
 #[serde(rename_all = "snake_case")]
@@ -305,4 +398,44 @@ mod tests {
                 .unwrap_err();
         assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
     }
+
+    #[test]
+    fn test_sanitize_invalid_api_keys() {
+        let api_key = ApiKeyConfig {
+            name: "submitter".into(),
+            key_sha256: "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad".into(),
+            scopes: vec![ApiScope::SubmitTransactions],
+        };
+        let node_config_with_keys = |protected_scopes, api_keys| NodeConfig {
+            api: ApiConfig {
+                authentication: ApiAuthenticationConfig {
+                    protected_scopes,
+                    api_keys,
+                },
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        // A valid config
+        let node_config =
+            node_config_with_keys(vec![ApiScope::SubmitTransactions], vec![api_key.clone()]);
+        ApiConfig::sanitize(&node_config, NodeType::PublicFullnode, None).unwrap();
+
+        // Protected scopes without any keys
+        let node_config = node_config_with_keys(vec![ApiScope::SubmitTransactions], vec![]);
+        ApiConfig::sanitize(&node_config, NodeType::PublicFullnode, None).unwrap_err();
+
+        // Duplicate key names
+        let node_config = node_config_with_keys(vec![], vec![api_key.clone(), api_key.clone()]);
+        ApiConfig::sanitize(&node_config, NodeType::PublicFullnode, None).unwrap_err();
+
+        // A key that isn't a SHA-256
+        let invalid_key = ApiKeyConfig {
+            key_sha256: "abc".into(),
+            ..api_key
+        };
+        let node_config = node_config_with_keys(vec![], vec![invalid_key]);
+        ApiConfig::sanitize(&node_config, NodeType::PublicFullnode, None).unwrap_err();
+    }
 }
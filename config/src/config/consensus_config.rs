@@ -92,6 +92,16 @@ pub struct ConsensusConfig {
     pub max_blocks_per_sending_request_quorum_store_override: u64,
     pub max_blocks_per_receiving_request: u64,
     pub max_blocks_per_receiving_request_quorum_store_override: u64,
+    /// A node lagging by up to this many rounds catches up by fetching the missing ordered blocks
+    /// (and their quorum certs) from its peers and executing them, rather than by state syncing.
+    /// 0 (the default) disables catching up through consensus.
+    pub max_catch_up_rounds: u64,
+    /// Proposals with a timestamp further than this ahead of the local clock are rejected
+    /// (reconfiguration suffix blocks excepted, as they reuse the timestamp of their parent)
+    pub max_proposal_timestamp_drift_ms: u64,
+    /// Operators are alerted (via logs and the `aptos_consensus_local_clock_skew_alert` metric)
+    /// when the local clock is skewed by more than this vs the median of the other validators
+    pub local_clock_skew_alert_threshold_ms: u64,
     pub broadcast_vote: bool,
     pub proof_cache_capacity: u64,
     pub rand_rb_config: ReliableBroadcastConfig,
@@ -369,6 +379,9 @@ impl Default for ConsensusConfig {
             max_blocks_per_sending_request_quorum_store_override: 10,
             max_blocks_per_receiving_request: 10,
             max_blocks_per_receiving_request_quorum_store_override: 100,
+            max_catch_up_rounds: 0,
+            max_proposal_timestamp_drift_ms: 30_000,
+            local_clock_skew_alert_threshold_ms: 1_000,
             broadcast_vote: true,
             proof_cache_capacity: 10_000,
             rand_rb_config: ReliableBroadcastConfig {
@@ -0,0 +1,108 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::config::{
+    config_sanitizer::ConfigSanitizer, node_config_loader::NodeType, Error, NodeConfig,
+};
+use aptos_types::chain_id::ChainId;
+use serde::{Deserialize, Serialize};
+
+/// Configuration for exporting a snapshot of selected on-chain state at
+/// the end of every epoch (e.g., for analytics pipelines).
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct EpochSnapshotConfig {
+    /// Whether or not epoch ending snapshots should be exported
+    pub enabled: bool,
+    /// Where to write the snapshots. Either a local directory, or a cloud
+    /// storage URL (e.g., s3://bucket/prefix, gs://bucket/prefix).
+    pub sink: String,
+    /// Resources to export from their module address (e.g., 0x1::stake::ValidatorSet)
+    pub resources: Vec<String>,
+    /// Resources to export from each validator's address (e.g., 0x1::stake::StakePool)
+    pub validator_resources: Vec<String>,
+}
+
+impl Default for EpochSnapshotConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            sink: String::new(),
+            resources: vec![
+                "0x1::stake::ValidatorSet".into(),
+                "0x1::staking_config::StakingConfig".into(),
+                "0x1::aptos_governance::GovernanceConfig".into(),
+                "0x1::aptos_governance::VotingRecords".into(),
+                "0x1::voting::VotingForum<0x1::governance_proposal::GovernanceProposal>".into(),
+            ],
+            validator_resources: vec![
+                "0x1::stake::StakePool".into(),
+                "0x1::stake::ValidatorConfig".into(),
+            ],
+        }
+    }
+}
+
+impl ConfigSanitizer for EpochSnapshotConfig {
+    fn sanitize(
+        node_config: &NodeConfig,
+        _node_type: NodeType,
+        _chain_id: Option<ChainId>,
+    ) -> Result<(), Error> {
+        let sanitizer_name = Self::get_sanitizer_name();
+        let epoch_snapshot_config = &node_config.epoch_snapshot;
+
+        // If the exporter is disabled, there's nothing to do
+        if !epoch_snapshot_config.enabled {
+            return Ok(());
+        }
+
+        // Verify that a sink has been specified
+        if epoch_snapshot_config.sink.is_empty() {
+            return Err(Error::ConfigSanitizerFailed(
+                sanitizer_name,
+                "A sink must be specified when epoch snapshots are enabled!".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_sanitize_missing_sink() {
+        // Create a node config with the exporter enabled, but no sink
+        let node_config = NodeConfig {
+            epoch_snapshot: EpochSnapshotConfig {
+                enabled: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        // Verify that the config fails sanitization
+        let error =
+            EpochSnapshotConfig::sanitize(&node_config, NodeType::Validator, None).unwrap_err();
+        assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
+    }
+
+    #[test]
+    fn test_sanitize_default_resources() {
+        // Create a node config with the default resources
+        let node_config = NodeConfig {
+            epoch_snapshot: EpochSnapshotConfig {
+                enabled: true,
+                sink: "s3://bucket/epoch_snapshots".into(),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        // Verify that the config passes sanitization
+        EpochSnapshotConfig::sanitize(&node_config, NodeType::Validator, None).unwrap();
+    }
+}
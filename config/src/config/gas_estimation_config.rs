@@ -15,6 +15,17 @@ pub struct GasEstimationStaticOverride {
     pub aggressive: u64,
 }
 
+/// The strategy used to compute the gas price estimates
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GasEstimationStrategy {
+    /// Percentiles of the minimum inclusion prices of recent blocks
+    Percentile,
+    /// Percentiles of the minimum inclusion prices of recent blocks, raised according to how
+    /// full the recent blocks were and how often they hit the block gas limit
+    BlockFullness,
+}
+
 #[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
 #[serde(default, deny_unknown_fields)]
 pub struct GasEstimationConfig {
@@ -34,6 +45,22 @@ pub struct GasEstimationConfig {
     pub cache_expiration_ms: u64,
     /// Whether to account which TransactionShufflerType is used onchain, and how it affects gas estimation
     pub incorporate_reordering_effects: bool,
+    /// Maximum number of recent transactions read for entry function gas estimation
+    pub entry_function_history_txns: u64,
+    /// Maximum number of recent calls kept per entry function for gas estimation
+    pub entry_function_max_samples: usize,
+    /// Maximum number of entry functions tracked for gas estimation
+    pub entry_function_max_tracked: usize,
+    /// Margin (in percent) added on top of the gas used by recent calls to suggest a max gas amount
+    pub entry_function_max_gas_margin_percent: u64,
+    /// The strategy used to compute the estimates
+    pub strategy: GasEstimationStrategy,
+    /// Block fullness (in percent) above which the block fullness strategy raises the estimates
+    pub target_block_fullness_percent: u64,
+    /// Maximum increase (in percent) applied by the block fullness strategy when blocks are full
+    pub max_block_fullness_adjustment_percent: u64,
+    /// Confidence level (in percent) of the interval returned around the market estimate
+    pub confidence_interval_percent: u64,
 }
 
 impl Default for GasEstimationConfig {
@@ -47,6 +74,14 @@ impl Default for GasEstimationConfig {
             aggressive_block_history: 120,
             cache_expiration_ms: 500,
             incorporate_reordering_effects: true,
+            entry_function_history_txns: 10_000,
+            entry_function_max_samples: 100,
+            entry_function_max_tracked: 1_000,
+            entry_function_max_gas_margin_percent: 50,
+            strategy: GasEstimationStrategy::Percentile,
+            target_block_fullness_percent: 50,
+            max_block_fullness_adjustment_percent: 100,
+            confidence_interval_percent: 80,
         }
     }
 }
@@ -91,6 +126,26 @@ impl ConfigSanitizer for GasEstimationConfig {
             ));
         }
 
+        // Validate the block fullness model and confidence interval percentages
+        if gas_estimation_config.target_block_fullness_percent >= 100 {
+            return Err(Error::ConfigSanitizerFailed(
+                sanitizer_name,
+                format!(
+                    "target block fullness {} must be < 100",
+                    gas_estimation_config.target_block_fullness_percent
+                ),
+            ));
+        }
+        if gas_estimation_config.confidence_interval_percent > 100 {
+            return Err(Error::ConfigSanitizerFailed(
+                sanitizer_name,
+                format!(
+                    "confidence interval {} must be <= 100",
+                    gas_estimation_config.confidence_interval_percent
+                ),
+            ));
+        }
+
         Ok(())
     }
 }
@@ -217,4 +272,25 @@ mod tests {
             GasEstimationConfig::sanitize(&node_config, NodeType::Validator, None).unwrap_err();
         assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
     }
+
+    #[test]
+    fn test_sanitize_invalid_target_block_fullness() {
+        // Create a node config with a target block fullness that can never be exceeded
+        let node_config = NodeConfig {
+            api: ApiConfig {
+                gas_estimation: GasEstimationConfig {
+                    strategy: GasEstimationStrategy::BlockFullness,
+                    target_block_fullness_percent: 100,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        // Sanitize the config and verify that it fails
+        let error =
+            GasEstimationConfig::sanitize(&node_config, NodeType::Validator, None).unwrap_err();
+        assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
+    }
 }
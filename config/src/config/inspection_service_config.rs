@@ -20,7 +20,14 @@ pub struct InspectionServiceConfig {
     pub expose_configuration: bool,
     pub expose_identity_information: bool,
     pub expose_peer_information: bool,
+    pub expose_profiling_information: bool,
     pub expose_system_information: bool,
+    /// The max number of series (i.e., label combinations) exported per metric
+    /// family. Series beyond that are aggregated into a single overflow series,
+    /// to protect Prometheus from unbounded label cardinality. 0 disables this.
+    pub metrics_max_series_per_family: usize,
+    /// The metric families that are exempt from `metrics_max_series_per_family`
+    pub metrics_cardinality_allowlist: Vec<String>,
 }
 
 impl Default for InspectionServiceConfig {
@@ -31,7 +38,10 @@ impl Default for InspectionServiceConfig {
             expose_configuration: false,
             expose_identity_information: true,
             expose_peer_information: true,
+            expose_profiling_information: false, // Task dumps briefly pause the runtimes
             expose_system_information: true,
+            metrics_max_series_per_family: 2000,
+            metrics_cardinality_allowlist: vec![],
         }
     }
 }
@@ -15,6 +15,8 @@ pub struct InternalIndexerDBConfig {
     pub enable_event_v2_translation: bool,
     pub event_v2_translation_ignores_below_version: u64,
     pub enable_statekeys: bool,
+    pub enable_balances: bool,
+    pub enable_packages: bool,
     pub batch_size: usize,
 }
 
@@ -25,6 +27,8 @@ impl InternalIndexerDBConfig {
         enable_event_v2_translation: bool,
         event_v2_translation_ignores_below_version: u64,
         enable_statekeys: bool,
+        enable_balances: bool,
+        enable_packages: bool,
         batch_size: usize,
     ) -> Self {
         Self {
@@ -33,6 +37,8 @@ impl InternalIndexerDBConfig {
             enable_event_v2_translation,
             event_v2_translation_ignores_below_version,
             enable_statekeys,
+            enable_balances,
+            enable_packages,
             batch_size,
         }
     }
@@ -57,8 +63,20 @@ impl InternalIndexerDBConfig {
         self.enable_statekeys
     }
 
+    pub fn enable_balances(&self) -> bool {
+        self.enable_balances
+    }
+
+    pub fn enable_packages(&self) -> bool {
+        self.enable_packages
+    }
+
     pub fn is_internal_indexer_db_enabled(&self) -> bool {
-        self.enable_transaction || self.enable_event || self.enable_statekeys
+        self.enable_transaction
+            || self.enable_event
+            || self.enable_statekeys
+            || self.enable_balances
+            || self.enable_packages
     }
 
     pub fn batch_size(&self) -> usize {
@@ -74,6 +92,8 @@ impl Default for InternalIndexerDBConfig {
             enable_event_v2_translation: false,
             event_v2_translation_ignores_below_version: 0,
             enable_statekeys: false,
+            enable_balances: false,
+            enable_packages: false,
             batch_size: 10_000,
         }
     }
@@ -17,7 +17,7 @@ use serde_yaml::Value;
 // Useful constants for the logger config
 const DEFAULT_TOKIO_CONSOLE_PORT: u16 = 6669;
 
-#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq, Serialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
 #[serde(default, deny_unknown_fields)]
 pub struct LoggerConfig {
     /// Channel size for asynchronous node logging
@@ -36,6 +36,9 @@ pub struct LoggerConfig {
     pub telemetry_level: Level,
     /// Tokio console port for local debugging
     pub tokio_console_port: Option<u16>,
+    /// OTLP (gRPC) endpoint to export tracing spans to, e.g. http://localhost:4317.
+    /// Requires the node to be built with the "otel" feature.
+    pub otlp_trace_endpoint: Option<String>,
 }
 
 impl Default for LoggerConfig {
@@ -53,6 +56,7 @@ impl Default for LoggerConfig {
             // Setting this to None will disable tokio-console
             // even if the "tokio-console" feature is enabled.
             tokio_console_port: None,
+            otlp_trace_endpoint: None,
         }
     }
 }
@@ -91,6 +95,15 @@ impl ConfigSanitizer for LoggerConfig {
             ));
         }
 
+        // Verify that tracing isn't requested from both tokio console and OTLP export
+        if logger_config.tokio_console_port.is_some() && logger_config.otlp_trace_endpoint.is_some()
+        {
+            return Err(Error::ConfigSanitizerFailed(
+                sanitizer_name,
+                "The tokio console port and the OTLP trace endpoint can't both be set!".into(),
+            ));
+        }
+
         Ok(())
     }
 }
@@ -202,4 +215,23 @@ mod tests {
                 .unwrap_err();
         assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
     }
+
+    #[test]
+    fn test_sanitize_tokio_console_and_otlp() {
+        // Create a logger config with both the tokio console port and the OTLP endpoint set
+        let node_config = NodeConfig {
+            logger: LoggerConfig {
+                tokio_console_port: Some(100),
+                otlp_trace_endpoint: Some("http://localhost:4317".into()),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        // Verify that the config fails sanitization
+        let error =
+            LoggerConfig::sanitize(&node_config, NodeType::Validator, Some(ChainId::testnet()))
+                .unwrap_err();
+        assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
+    }
 }
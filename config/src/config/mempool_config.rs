@@ -89,6 +89,10 @@ pub struct MempoolConfig {
     /// Uses the BroadcastTransactionsRequestWithReadyTime instead of BroadcastTransactionsRequest when sending
     /// mempool transactions to upstream nodes.
     pub include_ready_time_in_broadcast: bool,
+    /// Uses the BroadcastTransactionsResponseWithRejections instead of BroadcastTransactionsResponse
+    /// when acking broadcasts, so that downstream nodes stop rebroadcasting rejected transactions.
+    /// Note: this should only be enabled once all downstream nodes understand the new response.
+    pub include_rejection_reasons_in_ack: bool,
     pub usecase_stats_num_blocks_to_track: usize,
     pub usecase_stats_num_top_to_track: usize,
     /// We divide the transactions into buckets based on hash of the sender address.
@@ -102,6 +106,13 @@ pub struct MempoolConfig {
     pub enable_max_load_balancing_at_any_load: bool,
     /// Maximum number of orderless transactions allowed in the Mempool per user
     pub orderless_txn_capacity_per_user: usize,
+    /// Whether to record the latest broadcast ACK round-trip time of each peer in the peer's
+    /// metadata (as its "mempool_ack" application latency), where it is used for peer scoring.
+    pub report_broadcast_ack_latency: bool,
+    /// Maximum number of seconds to remember the hashes of accepted transactions in storage, so
+    /// that they are not broadcast again if resubmitted after a restart. The hashes are also
+    /// dropped once the transactions expire. If 0, the transactions are not remembered.
+    pub persisted_dedup_cache_ttl_secs: u64,
 }
 
 impl Default for MempoolConfig {
@@ -130,6 +141,7 @@ impl Default for MempoolConfig {
             eager_expire_threshold_ms: Some(15_000),
             eager_expire_time_ms: 6_000,
             include_ready_time_in_broadcast: false,
+            include_rejection_reasons_in_ack: false,
             usecase_stats_num_blocks_to_track: 40,
             usecase_stats_num_top_to_track: 5,
             num_sender_buckets: 4,
@@ -167,6 +179,8 @@ impl Default for MempoolConfig {
             ],
             enable_max_load_balancing_at_any_load: false,
             orderless_txn_capacity_per_user: 1000,
+            report_broadcast_ack_latency: false,
+            persisted_dedup_cache_ttl_secs: 0,
         }
     }
 }
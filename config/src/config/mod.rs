@@ -12,6 +12,7 @@ mod consensus_config;
 mod consensus_observer_config;
 mod dag_consensus_config;
 mod dkg_config;
+mod epoch_snapshot_config;
 mod error;
 mod execution_config;
 mod gas_estimation_config;
@@ -28,11 +29,13 @@ mod netbench_config;
 mod network_config;
 mod node_config;
 mod node_config_loader;
+mod node_shutdown_config;
 mod node_startup_config;
 mod override_node_config;
 mod peer_monitoring_config;
 mod persistable_config;
 mod quorum_store_config;
+pub mod reloadable_config;
 mod safety_rules_config;
 mod secure_backend_config;
 mod state_sync_config;
@@ -47,6 +50,7 @@ pub use base_config::*;
 pub use consensus_config::*;
 pub use consensus_observer_config::*;
 pub use dag_consensus_config::*;
+pub use epoch_snapshot_config::*;
 pub use error::*;
 pub use execution_config::*;
 pub use gas_estimation_config::*;
@@ -61,6 +65,7 @@ pub use netbench_config::*;
 pub use network_config::*;
 pub use node_config::*;
 pub use node_config_loader::{sanitize_node_config, NodeType};
+pub use node_shutdown_config::*;
 pub use override_node_config::*;
 pub use peer_monitoring_config::*;
 pub use persistable_config::*;
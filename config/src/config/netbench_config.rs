@@ -4,10 +4,10 @@
 use crate::config::{
     config_sanitizer::ConfigSanitizer, node_config_loader::NodeType, Error, NodeConfig,
 };
-use aptos_types::chain_id::ChainId;
+use aptos_types::{chain_id::ChainId, PeerId};
 use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq, Serialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
 #[serde(default, deny_unknown_fields)]
 pub struct NetbenchConfig {
     pub enabled: bool,
@@ -22,6 +22,13 @@ pub struct NetbenchConfig {
     pub rpc_data_size: usize,
     pub rpc_per_second: u64,
     pub rpc_in_flight: usize,
+
+    pub enable_scheduled_probes: bool, // Whether or not to probe peers in the background
+    pub probe_interval_ms: u64,        // The interval (ms) between probes of each peer
+    pub probe_num_messages: u64,       // The number of RPCs sent (sequentially) in each probe
+    pub probe_data_size: usize,        // The amount of data to send in each probe RPC
+    pub probe_timeout_ms: u64,         // The timeout (ms) for each probe RPC
+    pub probe_peers: Vec<PeerId>, // The peers to probe (if empty, all connected peers are probed)
 }
 
 impl Default for NetbenchConfig {
@@ -39,6 +46,13 @@ impl Default for NetbenchConfig {
             rpc_data_size: 100 * 1024, // 100 KB
             rpc_per_second: 1_000,
             rpc_in_flight: 8,
+
+            enable_scheduled_probes: false,
+            probe_interval_ms: 60_000, // 1 minute
+            probe_num_messages: 10,
+            probe_data_size: 10 * 1024, // 10 KB
+            probe_timeout_ms: 10_000,   // 10 seconds
+            probe_peers: vec![],
         }
     }
 }
@@ -57,7 +71,7 @@ impl ConfigSanitizer for NetbenchConfig {
         }
 
         // If netbench is disabled, there's nothing to do
-        let netbench_config = node_config.netbench.unwrap();
+        let netbench_config = node_config.netbench.as_ref().unwrap();
         if !netbench_config.enabled {
             return Ok(());
         }
@@ -73,6 +87,16 @@ impl ConfigSanitizer for NetbenchConfig {
             }
         }
 
+        // Verify that the scheduled probes are actually low-rate
+        if netbench_config.enable_scheduled_probes
+            && (netbench_config.probe_interval_ms == 0 || netbench_config.probe_num_messages == 0)
+        {
+            return Err(Error::ConfigSanitizerFailed(
+                sanitizer_name,
+                "The probe interval and number of probe messages must be non-zero!".to_string(),
+            ));
+        }
+
         Ok(())
     }
 }
@@ -107,4 +131,22 @@ mod test {
         // Verify that the config passes sanitization (for an unknown network)
         NetbenchConfig::sanitize(&node_config, NodeType::Validator, None).unwrap();
     }
+
+    #[test]
+    fn test_sanitize_scheduled_probes() {
+        // Create a netbench config with scheduled probes and a zero interval
+        let node_config = NodeConfig {
+            netbench: Some(NetbenchConfig {
+                enabled: true,
+                enable_scheduled_probes: true,
+                probe_interval_ms: 0,
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        // Verify that the config fails sanitization
+        let error = NetbenchConfig::sanitize(&node_config, NodeType::Validator, None).unwrap_err();
+        assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
+    }
 }
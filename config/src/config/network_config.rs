@@ -35,6 +35,9 @@ use std::{
 /// Current supported protocol negotiation handshake version. See
 /// [`aptos_network::protocols::wire::v1`](../../network/protocols/wire/handshake/v1/index.html).
 pub const HANDSHAKE_VERSION: u8 = 0;
+/// The handshake version advertised by peers that accept the post-quantum hybrid
+/// (X25519+ML-KEM-768) Noise handshake, on top of the classic one.
+pub const HYBRID_HANDSHAKE_VERSION: u8 = 1;
 pub const NETWORK_CHANNEL_SIZE: usize = 1024;
 pub const PING_INTERVAL_MS: u64 = 10_000;
 pub const PING_TIMEOUT_MS: u64 = 20_000;
@@ -104,12 +107,26 @@ pub struct NetworkConfig {
     pub max_frame_size: usize,
     /// Enables proxy protocol on incoming connections to get original source addresses
     pub enable_proxy_protocol: bool,
+    /// Enables the post-quantum hybrid (X25519+ML-KEM-768) Noise handshake. When enabled, the
+    /// node advertises `HYBRID_HANDSHAKE_VERSION` and uses the hybrid handshake to dial peers
+    /// that advertise it too. Note that peers running older releases cannot dial addresses
+    /// advertising `HYBRID_HANDSHAKE_VERSION`.
+    pub enable_hybrid_key_exchange: bool,
+    /// Enables the QUIC transport. When enabled, the node also listens for QUIC on the UDP
+    /// port of the same number as its (TCP) listen address, and can dial
+    /// `/udp/<port>/quic` addresses. Note that peers running older releases cannot parse
+    /// QUIC addresses, so these should only be advertised once all peers have upgraded.
+    pub enable_quic_transport: bool,
     /// Interval to send healthcheck pings to peers
     pub ping_interval_ms: u64,
     /// Timeout until a healthcheck ping is rejected
     pub ping_timeout_ms: u64,
     /// Number of failed healthcheck pings until a peer is marked unhealthy
     pub ping_failures_tolerated: u64,
+    /// Also probe the responsiveness of applications on each peer (e.g., the storage service)
+    /// alongside every healthcheck ping, and score peers by the slower of the two. Failed
+    /// application probes don't mark a peer unhealthy.
+    pub enable_application_probes: bool,
     /// Maximum number of outbound connections, limited by ConnectivityManager
     pub max_outbound_connections: usize,
     /// Maximum number of outbound connections, limited by PeerManager
@@ -118,6 +135,8 @@ pub struct NetworkConfig {
     pub inbound_rate_limit_config: Option<RateLimitConfig>,
     /// Outbound rate limiting configuration, if not specified, no rate limiting
     pub outbound_rate_limit_config: Option<RateLimitConfig>,
+    /// Accounting and shaping of the application traffic of each peer connection
+    pub peer_traffic: PeerTrafficConfig,
     /// The maximum size of an inbound or outbound message (it may be divided into multiple frame)
     pub max_message_size: usize,
     /// The maximum number of parallel message deserialization tasks that can run (per application)
@@ -147,6 +166,8 @@ impl NetworkConfig {
             seeds: PeerSet::default(),
             max_frame_size: MAX_FRAME_SIZE,
             enable_proxy_protocol: false,
+            enable_hybrid_key_exchange: false,
+            enable_quic_transport: false,
             max_connection_delay_ms: MAX_CONNECTION_DELAY_MS,
             connectivity_check_interval_ms: CONNECTIVITY_CHECK_INTERVAL_MS,
             network_channel_size: NETWORK_CHANNEL_SIZE,
@@ -154,10 +175,12 @@ impl NetworkConfig {
             ping_interval_ms: PING_INTERVAL_MS,
             ping_timeout_ms: PING_TIMEOUT_MS,
             ping_failures_tolerated: PING_FAILURES_TOLERATED,
+            enable_application_probes: false,
             max_outbound_connections: MAX_FULLNODE_OUTBOUND_CONNECTIONS,
             max_inbound_connections: MAX_INBOUND_CONNECTIONS,
             inbound_rate_limit_config: None,
             outbound_rate_limit_config: None,
+            peer_traffic: PeerTrafficConfig::default(),
             max_message_size: MAX_MESSAGE_SIZE,
             inbound_rx_buffer_size_bytes: None,
             inbound_tx_buffer_size_bytes: None,
@@ -388,6 +411,29 @@ impl Default for RateLimitConfig {
     }
 }
 
+/// Per-peer accounting and shaping of the application traffic of a network
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PeerTrafficConfig {
+    /// Exports the bytes exchanged with each connected peer, labeled by peer id. Disabled by
+    /// default, as every peer that connects adds its own metric series
+    pub enable_peer_traffic_metrics: bool,
+    /// Shaping of the bytes read from each peer connection, if not specified, no shaping
+    pub inbound_shaping: Option<PeerShapingConfig>,
+    /// Shaping of the bytes written to each peer connection, if not specified, no shaping
+    pub outbound_shaping: Option<PeerShapingConfig>,
+}
+
+/// Token bucket (of bytes) shaping one direction of a peer connection
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct PeerShapingConfig {
+    /// Maximum number of bytes/s for a peer connection
+    pub byte_rate: usize,
+    /// Maximum burst of bytes for a peer connection (raised to `byte_rate` if smaller)
+    pub byte_burst: usize,
+}
+
 pub type PeerSet = HashMap<PeerId, Peer>;
 
 // TODO: Combine with RoleType?
@@ -5,10 +5,11 @@ use super::{DagConsensusConfig, IndexerTableInfoConfig};
 use crate::{
     config::{
         consensus_observer_config::ConsensusObserverConfig, dkg_config::DKGConfig,
+        epoch_snapshot_config::EpochSnapshotConfig,
         internal_indexer_db_config::InternalIndexerDBConfig,
         jwk_consensus_config::JWKConsensusConfig, netbench_config::NetbenchConfig,
-        node_config_loader::NodeConfigLoader, node_startup_config::NodeStartupConfig,
-        persistable_config::PersistableConfig,
+        node_config_loader::NodeConfigLoader, node_shutdown_config::NodeShutdownConfig,
+        node_startup_config::NodeStartupConfig, persistable_config::PersistableConfig,
         transaction_filters_config::TransactionFiltersConfig, utils::RootPath, AdminServiceConfig,
         ApiConfig, BaseConfig, ConsensusConfig, Error, ExecutionConfig, IndexerConfig,
         IndexerGrpcConfig, InspectionServiceConfig, LoggerConfig, MempoolConfig, NetworkConfig,
@@ -50,6 +51,8 @@ pub struct NodeConfig {
     #[serde(default)]
     pub dkg: DKGConfig,
     #[serde(default)]
+    pub epoch_snapshot: EpochSnapshotConfig,
+    #[serde(default)]
     pub execution: ExecutionConfig,
     #[serde(default)]
     pub failpoints: Option<HashMap<String, String>>,
@@ -72,6 +75,8 @@ pub struct NodeConfig {
     #[serde(default)]
     pub netbench: Option<NetbenchConfig>,
     #[serde(default)]
+    pub node_shutdown: NodeShutdownConfig,
+    #[serde(default)]
     pub node_startup: NodeStartupConfig,
     #[serde(default)]
     pub peer_monitoring_service: PeerMonitoringServiceConfig,
@@ -0,0 +1,46 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct NodeShutdownConfig {
+    /// Whether or not to shut down gracefully (i.e., drain the commit pipeline and flush the
+    /// databases) on SIGINT or SIGTERM. Otherwise, the node runs until the process is killed.
+    pub graceful_shutdown: bool,
+    /// The maximum time (ms) to spend on the graceful shutdown before exiting anyway
+    pub shutdown_deadline_ms: u64,
+    /// The interval (ms) at which to check whether the commit pipeline has been drained
+    pub drain_poll_interval_ms: u64,
+}
+
+impl Default for NodeShutdownConfig {
+    fn default() -> Self {
+        Self {
+            graceful_shutdown: false,
+            shutdown_deadline_ms: 30_000,
+            drain_poll_interval_ms: 100,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_node_shutdown_config_deserialize_defaults() {
+        // Deserialize a config that only sets the deadline
+        let config: NodeShutdownConfig =
+            serde_yaml::from_str("shutdown_deadline_ms: 5000").unwrap();
+
+        // Verify the deadline is set and the other fields use the defaults
+        assert!(!config.graceful_shutdown);
+        assert_eq!(config.shutdown_deadline_ms, 5_000);
+        assert_eq!(
+            config.drain_poll_interval_ms,
+            NodeShutdownConfig::default().drain_poll_interval_ms
+        );
+    }
+}
@@ -3,10 +3,11 @@
 
 use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq, Serialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
 #[serde(default, deny_unknown_fields)]
 pub struct PeerMonitoringServiceConfig {
     pub enable_peer_monitoring_client: bool, // Whether or not to spawn the monitoring client
+    pub geo_hint: Option<String>, // An optional location hint (e.g., a region) reported to peers
     pub latency_monitoring: LatencyMonitoringConfig,
     pub max_concurrent_requests: u64, // Max num of concurrent server tasks
     pub max_network_channel_size: u64, // Max num of pending network messages
@@ -22,6 +23,7 @@ impl Default for PeerMonitoringServiceConfig {
     fn default() -> Self {
         Self {
             enable_peer_monitoring_client: true,
+            geo_hint: None,
             latency_monitoring: LatencyMonitoringConfig::default(),
             max_concurrent_requests: 1000,
             max_network_channel_size: 1000,
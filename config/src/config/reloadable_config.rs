@@ -0,0 +1,246 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::config::{Error, NodeConfig};
+use aptos_logger::Level;
+use aptos_transaction_filters::filter_expression::TransactionDenylist;
+use arc_swap::ArcSwapOption;
+use serde::{Deserialize, Serialize};
+use std::{
+    fmt::Display,
+    path::{Path, PathBuf},
+    str::FromStr,
+    sync::{Arc, Mutex},
+};
+
+/// The reloadable config of the running node (if it was initialized)
+static RELOADABLE_CONFIG: ArcSwapOption<ReloadableConfig> = ArcSwapOption::const_empty();
+
+/// The path of the config file the node was started with (if any)
+static NODE_CONFIG_PATH: Mutex<Option<PathBuf>> = Mutex::new(None);
+
+/// The vetted subset of the node config that can safely be changed while the
+/// node is running (e.g., via the admin service), without requiring a restart.
+/// Components that support this read the current values via `get()` and fall
+/// back to their own config if the reloadable config was never initialized.
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct ReloadableConfig {
+    /// See `ApiConfig::max_account_resources_page_size`
+    pub api_max_account_resources_page_size: u16,
+    /// See `ApiConfig::max_events_page_size`
+    pub api_max_events_page_size: u16,
+    /// See `ApiConfig::max_submit_transaction_batch_size`
+    pub api_max_submit_transaction_batch_size: usize,
+    /// See `ApiConfig::max_transactions_page_size`
+    pub api_max_transactions_page_size: u16,
+    /// See `LoggerConfig::level`
+    pub logger_level: Level,
+    /// See `MempoolConfig::capacity` (applied at the next mempool GC)
+    pub mempool_capacity: usize,
+    /// See `MempoolConfig::capacity_bytes` (applied at the next mempool GC)
+    pub mempool_capacity_bytes: usize,
+    /// See `MempoolConfig::capacity_per_user` (applied at the next mempool GC)
+    pub mempool_capacity_per_user: usize,
+    /// See `DataStreamingServiceConfig::max_concurrent_requests` (applied to new streams)
+    pub state_sync_max_concurrent_requests: u64,
+    /// See `TransactionFiltersConfig::transaction_denylist`
+    pub transaction_denylist: TransactionDenylist,
+}
+
+impl ReloadableConfig {
+    /// Extracts the reloadable subset of the given node config
+    pub fn from_node_config(node_config: &NodeConfig) -> Self {
+        Self {
+            api_max_account_resources_page_size: node_config.api.max_account_resources_page_size,
+            api_max_events_page_size: node_config.api.max_events_page_size,
+            api_max_submit_transaction_batch_size: node_config
+                .api
+                .max_submit_transaction_batch_size,
+            api_max_transactions_page_size: node_config.api.max_transactions_page_size,
+            logger_level: node_config.logger.level,
+            mempool_capacity: node_config.mempool.capacity,
+            mempool_capacity_bytes: node_config.mempool.capacity_bytes,
+            mempool_capacity_per_user: node_config.mempool.capacity_per_user,
+            state_sync_max_concurrent_requests: node_config
+                .state_sync
+                .data_streaming_service
+                .max_concurrent_requests,
+            transaction_denylist: node_config.transaction_filters.transaction_denylist.clone(),
+        }
+    }
+
+    /// Loads the reloadable subset of the node config at the given path. All
+    /// other (non-reloadable) changes to the file are ignored.
+    pub fn load_from_path<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+        let node_config = NodeConfig::load_from_path(path)?;
+        Ok(Self::from_node_config(&node_config))
+    }
+
+    /// Sets a single field of the config (by name) from its string representation
+    pub fn set_field(&mut self, name: &str, value: &str) -> Result<(), Error> {
+        match name {
+            "api_max_account_resources_page_size" => {
+                self.api_max_account_resources_page_size = parse_field(name, value)?
+            },
+            "api_max_events_page_size" => self.api_max_events_page_size = parse_field(name, value)?,
+            "api_max_submit_transaction_batch_size" => {
+                self.api_max_submit_transaction_batch_size = parse_field(name, value)?
+            },
+            "api_max_transactions_page_size" => {
+                self.api_max_transactions_page_size = parse_field(name, value)?
+            },
+            "logger_level" => self.logger_level = parse_field(name, value)?,
+            "mempool_capacity" => self.mempool_capacity = parse_field(name, value)?,
+            "mempool_capacity_bytes" => self.mempool_capacity_bytes = parse_field(name, value)?,
+            "mempool_capacity_per_user" => {
+                self.mempool_capacity_per_user = parse_field(name, value)?
+            },
+            "state_sync_max_concurrent_requests" => {
+                self.state_sync_max_concurrent_requests = parse_field(name, value)?
+            },
+            "transaction_denylist" => self.transaction_denylist = parse_field(name, value)?,
+            _ => {
+                return Err(Error::InvariantViolation(format!(
+                    "{} is not a reloadable config field!",
+                    name
+                )))
+            },
+        }
+        Ok(())
+    }
+
+    /// Verifies that the config values are usable, i.e., that nothing is set to zero
+    pub fn verify(&self) -> Result<(), Error> {
+        let zero_fields: Vec<&str> = [
+            (
+                "api_max_account_resources_page_size",
+                self.api_max_account_resources_page_size as u64,
+            ),
+            (
+                "api_max_events_page_size",
+                self.api_max_events_page_size as u64,
+            ),
+            (
+                "api_max_submit_transaction_batch_size",
+                self.api_max_submit_transaction_batch_size as u64,
+            ),
+            (
+                "api_max_transactions_page_size",
+                self.api_max_transactions_page_size as u64,
+            ),
+            ("mempool_capacity", self.mempool_capacity as u64),
+            ("mempool_capacity_bytes", self.mempool_capacity_bytes as u64),
+            (
+                "mempool_capacity_per_user",
+                self.mempool_capacity_per_user as u64,
+            ),
+            (
+                "state_sync_max_concurrent_requests",
+                self.state_sync_max_concurrent_requests,
+            ),
+        ]
+        .into_iter()
+        .filter(|(_, value)| *value == 0)
+        .map(|(name, _)| name)
+        .collect();
+
+        if zero_fields.is_empty() {
+            Ok(())
+        } else {
+            Err(Error::InvariantViolation(format!(
+                "Reloadable config fields must be non-zero: {}",
+                zero_fields.join(", ")
+            )))
+        }
+    }
+}
+
+fn parse_field<T: FromStr>(name: &str, value: &str) -> Result<T, Error>
+where
+    T::Err: Display,
+{
+    value.parse().map_err(|error| {
+        Error::InvariantViolation(format!("Invalid value {:?} for {}: {}", value, name, error))
+    })
+}
+
+/// Initializes the reloadable config from the node config the node is started with
+pub fn initialize(node_config: &NodeConfig) {
+    RELOADABLE_CONFIG.store(Some(Arc::new(ReloadableConfig::from_node_config(
+        node_config,
+    ))));
+}
+
+/// Returns the current reloadable config, or None if it was never initialized
+pub fn get() -> Option<Arc<ReloadableConfig>> {
+    RELOADABLE_CONFIG.load_full()
+}
+
+/// Replaces the current reloadable config (after verifying it), and returns the old one
+pub fn update(reloadable_config: ReloadableConfig) -> Result<Arc<ReloadableConfig>, Error> {
+    reloadable_config.verify()?;
+    RELOADABLE_CONFIG
+        .swap(Some(Arc::new(reloadable_config)))
+        .ok_or_else(|| Error::Unexpected("The reloadable config was never initialized!".into()))
+}
+
+/// Sets the path of the config file the node was started with, to reload from
+pub fn set_node_config_path(path: PathBuf) {
+    *NODE_CONFIG_PATH.lock().unwrap() = Some(path);
+}
+
+/// Returns the path of the config file the node was started with (if any)
+pub fn node_config_path() -> Option<PathBuf> {
+    NODE_CONFIG_PATH.lock().unwrap().clone()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_set_field() {
+        let mut reloadable_config = ReloadableConfig::from_node_config(&NodeConfig::default());
+
+        // Set valid values and verify they are applied
+        reloadable_config
+            .set_field("mempool_capacity", "1234")
+            .unwrap();
+        reloadable_config
+            .set_field("logger_level", "debug")
+            .unwrap();
+        assert_eq!(reloadable_config.mempool_capacity, 1234);
+        assert_eq!(reloadable_config.logger_level, Level::Debug);
+        reloadable_config.verify().unwrap();
+
+        // Verify the transaction denylist is parsed from semicolon separated expressions
+        reloadable_config
+            .set_field(
+                "transaction_denylist",
+                "sender == 0xcafe; payload_size > 65536",
+            )
+            .unwrap();
+        assert_eq!(
+            reloadable_config.transaction_denylist.to_string(),
+            "sender == 0xcafe; payload_size > 65536"
+        );
+
+        // Verify invalid values and unknown fields are rejected
+        reloadable_config
+            .set_field("api_max_events_page_size", "100000")
+            .unwrap_err();
+        reloadable_config
+            .set_field("transaction_denylist", "sender > 0xcafe")
+            .unwrap_err();
+        reloadable_config
+            .set_field("consensus_round_initial_timeout_ms", "1000")
+            .unwrap_err();
+
+        // Verify zero values don't pass verification
+        reloadable_config
+            .set_field("mempool_capacity", "0")
+            .unwrap();
+        reloadable_config.verify().unwrap_err();
+    }
+}
@@ -32,6 +32,8 @@ pub struct SafetyRulesConfig {
     pub network_timeout_ms: u64,
     pub enable_cached_safety_data: bool,
     pub initial_safety_rules_config: InitialSafetyRulesConfig,
+    /// If set, the consensus key is split across several backends instead of held in `backend`
+    pub threshold_signing: Option<ThresholdSigningConfig>,
 }
 
 impl Default for SafetyRulesConfig {
@@ -45,15 +47,14 @@ impl Default for SafetyRulesConfig {
             network_timeout_ms: 30_000,
             enable_cached_safety_data: true,
             initial_safety_rules_config: InitialSafetyRulesConfig::None,
+            threshold_signing: None,
         }
     }
 }
 
 impl SafetyRulesConfig {
     pub fn set_data_dir(&mut self, data_dir: PathBuf) {
-        if let SecureBackend::OnDiskStorage(backend) = &mut self.backend {
-            backend.set_data_dir(data_dir);
-        }
+        self.backend.set_data_dir(data_dir);
     }
 
     #[cfg(test)]
@@ -113,6 +114,52 @@ impl ConfigSanitizer for SafetyRulesConfig {
             }
         }
 
+        if let SafetyRulesService::Process(service) = &safety_rules_config.service {
+            // Verify that signers sharing a storage backend don't cache their safety data, as a
+            // signer could otherwise sign based on data another signer has since updated
+            if !service.failover_addresses.is_empty()
+                && safety_rules_config.enable_cached_safety_data
+            {
+                return Err(Error::ConfigSanitizerFailed(
+                    sanitizer_name,
+                    "Remote signer failover requires cached safety data to be disabled!"
+                        .to_string(),
+                ));
+            }
+        }
+
+        // Verify that the threshold signing config can actually produce signatures
+        if let Some(threshold_signing) = &safety_rules_config.threshold_signing {
+            if threshold_signing.threshold == 0
+                || threshold_signing.threshold > threshold_signing.shards.len()
+            {
+                return Err(Error::ConfigSanitizerFailed(
+                    sanitizer_name,
+                    format!(
+                        "The threshold signing threshold must be between 1 and the number of shards ({}), given: {}",
+                        threshold_signing.shards.len(),
+                        threshold_signing.threshold
+                    ),
+                ));
+            }
+
+            // Verify that the key shards are only reachable over mutually authenticated TLS, as
+            // they sign for any client that reaches them
+            if let Some(shard) = threshold_signing
+                .shards
+                .iter()
+                .find(|shard| shard.tls.is_none())
+            {
+                return Err(Error::ConfigSanitizerFailed(
+                    sanitizer_name,
+                    format!(
+                        "The threshold signing shards must be configured with TLS! Shard without TLS: {}",
+                        shard.server_address
+                    ),
+                ));
+            }
+        }
+
         Ok(())
     }
 }
@@ -227,11 +274,29 @@ impl SafetyRulesService {
 #[serde(deny_unknown_fields)]
 pub struct RemoteService {
     pub server_address: NetworkAddress,
+    /// Signers to fail over to, in order, if the current one is unreachable. These must share the
+    /// secure storage backend of the primary, otherwise they could sign conflicting messages, and
+    /// all signers must run with `enable_cached_safety_data` disabled.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub failover_addresses: Vec<NetworkAddress>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tls: Option<RemoteServiceTlsConfig>,
 }
 
 impl RemoteService {
     pub fn server_address(&self) -> SocketAddr {
-        self.server_address
+        Self::socket_address(&self.server_address)
+    }
+
+    pub fn failover_addresses(&self) -> Vec<SocketAddr> {
+        self.failover_addresses
+            .iter()
+            .map(Self::socket_address)
+            .collect()
+    }
+
+    fn socket_address(address: &NetworkAddress) -> SocketAddr {
+        address
             .to_socket_addrs()
             .expect("server_address invalid")
             .next()
@@ -239,6 +304,32 @@ impl RemoteService {
     }
 }
 
+/// Mutually-authenticated TLS between consensus and a remote safety rules signer. Each side
+/// presents its own certificate and only accepts peers whose certificate is issued by the CA.
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct RemoteServiceTlsConfig {
+    /// PEM encoded certificate of the CA issuing both the client and the signer certificates
+    pub ca_certificate: PathBuf,
+    /// PEM encoded certificate of this side
+    pub certificate: PathBuf,
+    /// PEM encoded private key of this side
+    pub private_key: PathBuf,
+    /// The name the signer certificate is issued for, only used by the client
+    pub server_name: String,
+}
+
+/// The remote signers holding the shares of a consensus key split with
+/// `bls12381::split_private_key`, any `threshold` of which are needed to sign. Each signer keeps
+/// its share in its own secure storage, requires TLS and only ever returns signature shares.
+/// Consensus keys are BLS12-381 keys, so only BLS threshold signing is supported.
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct ThresholdSigningConfig {
+    pub threshold: usize,
+    pub shards: Vec<RemoteService>,
+}
+
 #[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct SafetyRulesTestConfig {
     pub author: PeerId,
@@ -269,6 +360,7 @@ impl SafetyRulesTestConfig {
 mod tests {
     use super::*;
     use crate::config::ConsensusConfig;
+    use std::str::FromStr;
 
     #[test]
     fn test_sanitize_invalid_backend_for_mainnet() {
@@ -364,4 +456,94 @@ mod tests {
         .unwrap_err();
         assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
     }
+
+    #[test]
+    fn test_sanitize_threshold_signing() {
+        // Create a node config with a threshold above the number of shards
+        let key_shard = RemoteService {
+            server_address: NetworkAddress::from_str("/ip4/127.0.0.1/tcp/5555").unwrap(),
+            failover_addresses: vec![],
+            tls: Some(RemoteServiceTlsConfig {
+                ca_certificate: PathBuf::from("ca.pem"),
+                certificate: PathBuf::from("validator.pem"),
+                private_key: PathBuf::from("validator.key"),
+                server_name: "key-shard".to_string(),
+            }),
+        };
+        let mut node_config = NodeConfig {
+            consensus: ConsensusConfig {
+                safety_rules: SafetyRulesConfig {
+                    threshold_signing: Some(ThresholdSigningConfig {
+                        threshold: 3,
+                        shards: vec![key_shard.clone(); 2],
+                    }),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        // Verify that the config sanitizer fails
+        let error =
+            SafetyRulesConfig::sanitize(&node_config, NodeType::Validator, None).unwrap_err();
+        assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
+
+        // Verify that the config sanitizer passes once there are enough shards
+        node_config
+            .consensus
+            .safety_rules
+            .threshold_signing
+            .as_mut()
+            .unwrap()
+            .shards
+            .push(key_shard.clone());
+        SafetyRulesConfig::sanitize(&node_config, NodeType::Validator, None).unwrap();
+
+        // Verify that the config sanitizer fails if a shard has no TLS config
+        node_config
+            .consensus
+            .safety_rules
+            .threshold_signing
+            .as_mut()
+            .unwrap()
+            .shards
+            .push(RemoteService {
+                tls: None,
+                ..key_shard
+            });
+        let error =
+            SafetyRulesConfig::sanitize(&node_config, NodeType::Validator, None).unwrap_err();
+        assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
+    }
+
+    #[test]
+    fn test_sanitize_failover_with_cached_safety_data() {
+        // Create a node config with a remote signer that fails over while caching safety data
+        let remote_service = RemoteService {
+            server_address: NetworkAddress::from_str("/ip4/127.0.0.1/tcp/5555").unwrap(),
+            failover_addresses: vec![NetworkAddress::from_str("/ip4/127.0.0.1/tcp/5556").unwrap()],
+            tls: None,
+        };
+        let mut node_config = NodeConfig {
+            consensus: ConsensusConfig {
+                safety_rules: SafetyRulesConfig {
+                    service: SafetyRulesService::Process(remote_service),
+                    enable_cached_safety_data: true,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        // Verify that the config sanitizer fails
+        let error =
+            SafetyRulesConfig::sanitize(&node_config, NodeType::Validator, None).unwrap_err();
+        assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
+
+        // Verify that the config sanitizer passes once the cache is disabled
+        node_config.consensus.safety_rules.enable_cached_safety_data = false;
+        SafetyRulesConfig::sanitize(&node_config, NodeType::Validator, None).unwrap();
+    }
 }
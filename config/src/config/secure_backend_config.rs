@@ -3,7 +3,10 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::config::Error;
-use aptos_secure_storage::{InMemoryStorage, Namespaced, OnDiskStorage, Storage, VaultStorage};
+use aptos_secure_storage::{
+    EncryptedOnDiskStorage, InMemoryStorage, Namespaced, OnDiskStorage, SqliteStorage, Storage,
+    VaultStorage, ENCRYPTION_KEY_LENGTH,
+};
 use serde::{Deserialize, Serialize};
 use std::{
     fs::File,
@@ -13,6 +16,8 @@ use std::{
 
 // The default file name for the secure storage file
 pub const SECURE_STORAGE_FILENAME: &str = "secure_storage.json";
+// The default file name for the secure storage database, when stored in SQLite
+pub const SECURE_STORAGE_DB_FILENAME: &str = "secure_storage.db";
 
 #[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
 #[serde(rename_all = "snake_case", tag = "type")]
@@ -20,13 +25,20 @@ pub enum SecureBackend {
     InMemoryStorage,
     Vault(VaultConfig),
     OnDiskStorage(OnDiskStorageConfig),
+    EncryptedOnDiskStorage(EncryptedOnDiskStorageConfig),
+    SqliteStorage(SqliteStorageConfig),
 }
 
 impl SecureBackend {
     pub fn namespace(&self) -> Option<&str> {
         match self {
             SecureBackend::Vault(VaultConfig { namespace, .. })
-            | SecureBackend::OnDiskStorage(OnDiskStorageConfig { namespace, .. }) => {
+            | SecureBackend::OnDiskStorage(OnDiskStorageConfig { namespace, .. })
+            | SecureBackend::EncryptedOnDiskStorage(EncryptedOnDiskStorageConfig {
+                namespace,
+                ..
+            })
+            | SecureBackend::SqliteStorage(SqliteStorageConfig { namespace, .. }) => {
                 namespace.as_deref()
             },
             SecureBackend::InMemoryStorage => None,
@@ -36,7 +48,12 @@ impl SecureBackend {
     pub fn clear_namespace(&mut self) {
         match self {
             SecureBackend::Vault(VaultConfig { namespace, .. })
-            | SecureBackend::OnDiskStorage(OnDiskStorageConfig { namespace, .. }) => {
+            | SecureBackend::OnDiskStorage(OnDiskStorageConfig { namespace, .. })
+            | SecureBackend::EncryptedOnDiskStorage(EncryptedOnDiskStorageConfig {
+                namespace,
+                ..
+            })
+            | SecureBackend::SqliteStorage(SqliteStorageConfig { namespace, .. }) => {
                 *namespace = None;
             },
             SecureBackend::InMemoryStorage => {},
@@ -47,6 +64,16 @@ impl SecureBackend {
     pub fn is_in_memory(&self) -> bool {
         matches!(self, SecureBackend::InMemoryStorage)
     }
+
+    /// Sets the directory relative paths of local backends are resolved against
+    pub fn set_data_dir(&mut self, data_dir: PathBuf) {
+        match self {
+            SecureBackend::OnDiskStorage(config) => config.set_data_dir(data_dir),
+            SecureBackend::EncryptedOnDiskStorage(config) => config.set_data_dir(data_dir),
+            SecureBackend::SqliteStorage(config) => config.set_data_dir(data_dir),
+            SecureBackend::InMemoryStorage | SecureBackend::Vault(_) => {},
+        }
+    }
 }
 
 #[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
@@ -97,6 +124,97 @@ pub struct OnDiskStorageConfig {
     data_dir: PathBuf,
 }
 
+/// An on-disk storage file encrypted under a key, see `EncryptedOnDiskStorage`.
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct EncryptedOnDiskStorageConfig {
+    // Required path for on disk storage
+    pub path: PathBuf,
+    /// The file holding the (hex-encoded, 32 byte) encryption key, e.g., a data key unwrapped by
+    /// a cloud KMS and mounted by its secrets manager. This is an absolute path and not relative
+    /// to data_dir.
+    pub encryption_key_file: PathBuf,
+    /// A namespace is an optional portion of the path to a key stored within the storage, as for
+    /// OnDiskStorage.
+    pub namespace: Option<String>,
+    #[serde(skip)]
+    data_dir: PathBuf,
+}
+
+impl EncryptedOnDiskStorageConfig {
+    pub fn new(path: PathBuf, encryption_key_file: PathBuf) -> Self {
+        Self {
+            path,
+            encryption_key_file,
+            namespace: None,
+            data_dir: PathBuf::from("/opt/aptos/data"),
+        }
+    }
+
+    pub fn path(&self) -> PathBuf {
+        if self.path.is_relative() {
+            self.data_dir.join(&self.path)
+        } else {
+            self.path.clone()
+        }
+    }
+
+    pub fn set_data_dir(&mut self, data_dir: PathBuf) {
+        self.data_dir = data_dir;
+    }
+
+    pub fn encryption_key(&self) -> Result<[u8; ENCRYPTION_KEY_LENGTH], Error> {
+        let path = self.encryption_key_file.to_str().unwrap().to_string();
+        let contents = read_file(&self.encryption_key_file)?;
+        hex::decode(contents.trim())
+            .ok()
+            .and_then(|key| key.try_into().ok())
+            .ok_or_else(|| {
+                Error::Unexpected(format!(
+                    "{} does not hold a hex-encoded {} byte key",
+                    path, ENCRYPTION_KEY_LENGTH
+                ))
+            })
+    }
+}
+
+/// A SQLite database, see `SqliteStorage`.
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct SqliteStorageConfig {
+    // Required path for the database
+    pub path: PathBuf,
+    /// A namespace is an optional portion of the path to a key stored within the storage, as for
+    /// OnDiskStorage.
+    pub namespace: Option<String>,
+    #[serde(skip)]
+    data_dir: PathBuf,
+}
+
+impl Default for SqliteStorageConfig {
+    fn default() -> Self {
+        Self {
+            namespace: None,
+            path: PathBuf::from(SECURE_STORAGE_DB_FILENAME),
+            data_dir: PathBuf::from("/opt/aptos/data"),
+        }
+    }
+}
+
+impl SqliteStorageConfig {
+    pub fn path(&self) -> PathBuf {
+        if self.path.is_relative() {
+            self.data_dir.join(&self.path)
+        } else {
+            self.path.clone()
+        }
+    }
+
+    pub fn set_data_dir(&mut self, data_dir: PathBuf) {
+        self.data_dir = data_dir;
+    }
+}
+
 /// Tokens can either be directly within this config or stored somewhere on disk.
 #[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
 #[serde(rename_all = "snake_case")]
@@ -172,6 +290,27 @@ impl From<&SecureBackend> for Storage {
                     storage
                 }
             },
+            SecureBackend::EncryptedOnDiskStorage(config) => {
+                let storage = Storage::from(EncryptedOnDiskStorage::new(
+                    config.path(),
+                    &config
+                        .encryption_key()
+                        .expect("Unable to read encryption key"),
+                ));
+                if let Some(namespace) = &config.namespace {
+                    Storage::from(Namespaced::new(namespace, Box::new(storage)))
+                } else {
+                    storage
+                }
+            },
+            SecureBackend::SqliteStorage(config) => {
+                let storage = Storage::from(SqliteStorage::new(&config.path()));
+                if let Some(namespace) = &config.namespace {
+                    Storage::from(Namespaced::new(namespace, Box::new(storage)))
+                } else {
+                    storage
+                }
+            },
             SecureBackend::Vault(config) => {
                 let storage = Storage::from(VaultStorage::new(
                     config.server.clone(),
@@ -290,6 +429,40 @@ vault:
         serde_yaml::to_string(&from_disk).unwrap();
     }
 
+    #[test]
+    fn test_encrypted_on_disk_config() {
+        let text = r#"
+type: "encrypted_on_disk_storage"
+path: "secure_storage.enc"
+encryption_key_file: "/secrets/safety_rules_key"
+namespace: "validator"
+        "#;
+        let mut backend: SecureBackend = serde_yaml::from_str(text).unwrap();
+        assert_eq!(backend.namespace(), Some("validator"));
+        backend.set_data_dir(PathBuf::from("/data"));
+        let SecureBackend::EncryptedOnDiskStorage(config) = backend else {
+            panic!("Unexpected backend: {:?}", backend);
+        };
+        assert_eq!(config.path(), PathBuf::from("/data/secure_storage.enc"));
+
+        let temppath = aptos_temppath::TempPath::new();
+        temppath.create_as_file().unwrap();
+        let config = EncryptedOnDiskStorageConfig::new(
+            PathBuf::from("secure_storage.enc"),
+            temppath.path().to_path_buf(),
+        );
+        File::create(temppath.path())
+            .unwrap()
+            .write_all(b"0102")
+            .unwrap();
+        config.encryption_key().unwrap_err();
+        File::create(temppath.path())
+            .unwrap()
+            .write_all(format!("{}\n", hex::encode([7u8; 32])).as_bytes())
+            .unwrap();
+        assert_eq!(config.encryption_key().unwrap(), [7u8; 32]);
+    }
+
     #[test]
     fn test_token_reading() {
         let temppath = aptos_temppath::TempPath::new();
@@ -26,6 +26,7 @@ const MAX_TRANSACTION_OUTPUT_CHUNK_SIZE: u64 = 3000;
 // The maximum number of concurrent requests to send
 const MAX_CONCURRENT_REQUESTS: u64 = 6;
 const MAX_CONCURRENT_STATE_REQUESTS: u64 = 6;
+const MAX_TOTAL_CONCURRENT_REQUESTS: u64 = 60;
 
 #[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Eq, Serialize)]
 #[serde(default, deny_unknown_fields)]
@@ -157,6 +158,10 @@ pub struct StorageServiceConfig {
     pub max_epoch_chunk_size: u64,
     /// Maximum number of invalid requests per peer
     pub max_invalid_requests_per_peer: u64,
+    /// Maximum number of bytes served to a public peer per quota window (0 is unlimited)
+    pub max_bytes_served_per_peer_quota: u64,
+    /// Maximum processing time (ms) spent on a public peer per quota window (0 is unlimited)
+    pub max_processing_time_ms_per_peer_quota: u64,
     /// Maximum number of items in the lru cache before eviction
     pub max_lru_cache_size: u64,
     /// Maximum number of pending network messages
@@ -179,6 +184,8 @@ pub struct StorageServiceConfig {
     pub max_transaction_output_chunk_size: u64,
     /// Minimum time (secs) to ignore peers after too many invalid requests
     pub min_time_to_ignore_peers_secs: u64,
+    /// The window (secs) over which per-peer request costs are accounted against quotas
+    pub peer_quota_window_secs: u64,
     /// The interval (ms) to refresh the request moderator state
     pub request_moderator_refresh_interval_ms: u64,
     /// The interval (ms) to refresh the storage summary
@@ -191,6 +198,8 @@ impl Default for StorageServiceConfig {
             enable_transaction_data_v2: true,
             max_epoch_chunk_size: MAX_EPOCH_CHUNK_SIZE,
             max_invalid_requests_per_peer: 500,
+            max_bytes_served_per_peer_quota: 1024 * 1024 * 1024, // 1 GiB per window
+            max_processing_time_ms_per_peer_quota: 20_000,       // 20 seconds per window
             max_lru_cache_size: 500, // At ~0.6MiB per chunk, this should take no more than 0.5GiB
             max_network_channel_size: 4000,
             max_network_chunk_bytes: SERVER_MAX_MESSAGE_SIZE as u64,
@@ -202,6 +211,7 @@ impl Default for StorageServiceConfig {
             max_transaction_chunk_size: MAX_TRANSACTION_CHUNK_SIZE,
             max_transaction_output_chunk_size: MAX_TRANSACTION_OUTPUT_CHUNK_SIZE,
             min_time_to_ignore_peers_secs: 300, // 5 minutes
+            peer_quota_window_secs: 10,         // 10 seconds
             request_moderator_refresh_interval_ms: 1000, // 1 second
             storage_summary_refresh_interval_ms: 100, // Optimal for <= 10 blocks per second
         }
@@ -226,6 +236,10 @@ pub struct DataStreamingServiceConfig {
     /// Maximum number of in-flight data client requests (per stream) for state keys/values.
     pub max_concurrent_state_requests: u64,
 
+    /// Maximum number of in-flight data client requests (across all streams). The
+    /// budget is shared between the active streams based on their priority weights.
+    pub max_total_concurrent_requests: u64,
+
     /// Maximum channel sizes for each data stream listener (per stream).
     pub max_data_stream_channel_sizes: u64,
 
@@ -261,6 +275,7 @@ impl Default for DataStreamingServiceConfig {
             global_summary_refresh_interval_ms: 50,
             max_concurrent_requests: MAX_CONCURRENT_REQUESTS,
             max_concurrent_state_requests: MAX_CONCURRENT_STATE_REQUESTS,
+            max_total_concurrent_requests: MAX_TOTAL_CONCURRENT_REQUESTS,
             max_data_stream_channel_sizes: 50,
             max_notification_id_mappings: 300,
             max_num_consecutive_subscriptions: 45, // At ~3 blocks per second, this should last ~15 seconds
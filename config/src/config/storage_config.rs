@@ -167,6 +167,11 @@ pub struct RocksdbConfigs {
     pub index_db_config: RocksdbConfig,
     #[serde(default = "default_to_true")]
     pub enable_storage_sharding: bool,
+    /// Read back the state values written to each state KV shard after committing it and check
+    /// them against the checksums computed during execution. This costs a read per written
+    /// value, so it's meant for debugging suspected corruption.
+    #[serde(default)]
+    pub verify_state_kv_commits: bool,
 }
 
 fn default_to_true() -> bool {
@@ -187,6 +192,7 @@ impl Default for RocksdbConfigs {
                 ..Default::default()
             },
             enable_storage_sharding: true,
+            verify_state_kv_commits: false,
         }
     }
 }
@@ -226,6 +232,24 @@ pub struct StorageConfig {
     pub ensure_rlimit_nofile: u64,
     /// panic if failed to ensure `ulimit -n`
     pub assert_rlimit_nofile: bool,
+    /// Local directory holding a backup created by the backup tooling (e.g., a state snapshot
+    /// with the epoch endings and transactions leading up to it). If specified and the DB is
+    /// empty, the DB is restored from the backup (verified against the waypoints) on startup,
+    /// and state sync continues from the restored version.
+    pub bootstrap_snapshot_dir: Option<PathBuf>,
+    /// On startup, truncate any data that was only partially committed (e.g., because the
+    /// node crashed in the middle of a commit) to the last fully committed version, and write a
+    /// report of the removed data to the `crash_recovery_reports` directory under `dir`.
+    /// Otherwise, the node refuses to start if too much data has to be truncated, and the
+    /// db-tool truncation is required.
+    pub enable_crash_recovery: bool,
+    /// The maximum number of versions the crash recovery may remove from any DB (including the
+    /// internal indexer DB). The node refuses to start if more would have to be removed.
+    pub crash_recovery_max_truncated_versions: u64,
+    /// The RSS budget of the process, in bytes. When the process approaches it, the buffered
+    /// state is committed at every checkpoint, so that the in-memory state summary retains fewer
+    /// generations. 0 means no budget.
+    pub rss_budget_bytes: u64,
 }
 
 pub const NO_OP_STORAGE_PRUNER_CONFIG: PrunerConfig = PrunerConfig {
@@ -375,6 +399,10 @@ impl Default for StorageConfig {
             max_num_nodes_per_lru_cache_shard: DEFAULT_MAX_NUM_NODES_PER_LRU_CACHE_SHARD,
             ensure_rlimit_nofile: 0,
             assert_rlimit_nofile: false,
+            bootstrap_snapshot_dir: None,
+            enable_crash_recovery: false,
+            crash_recovery_max_truncated_versions: 10_000_000,
+            rss_budget_bytes: 0,
         }
     }
 }
@@ -3,7 +3,8 @@
 
 use aptos_transaction_filters::{
     batch_transaction_filter::BatchTransactionFilter,
-    block_transaction_filter::BlockTransactionFilter, transaction_filter::TransactionFilter,
+    block_transaction_filter::BlockTransactionFilter, filter_expression::TransactionDenylist,
+    transaction_filter::TransactionFilter,
 };
 use serde::{Deserialize, Serialize};
 
@@ -15,6 +16,7 @@ pub struct TransactionFiltersConfig {
     pub execution_filter: BlockTransactionFilterConfig, // Filter for execution (e.g., block execution)
     pub mempool_filter: TransactionFilterConfig,        // Filter for mempool (e.g., txn submission)
     pub quorum_store_filter: BatchTransactionFilterConfig, // Filter for quorum store (e.g., batch voting)
+    pub transaction_denylist: TransactionDenylist, // Denylist for API submission and quorum store (hot-reloadable)
 }
 
 #[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
@@ -3,7 +3,9 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    payload::{OptBatches, OptQuorumStorePayload, PayloadExecutionLimit, TxnAndGasLimits},
+    payload::{
+        InlineBatch, OptBatches, OptQuorumStorePayload, PayloadExecutionLimit, TxnAndGasLimits,
+    },
     proof_of_store::{BatchInfo, ProofCache, ProofOfStore},
 };
 use anyhow::ensure;
@@ -281,6 +283,36 @@ impl Payload {
         }
     }
 
+    /// Appends an inline batch to a quorum store payload, turning a proofs-only payload into a
+    /// hybrid one.
+    pub fn with_inline_batch(self, batch_info: BatchInfo, txns: Vec<SignedTransaction>) -> Self {
+        match self {
+            Payload::InQuorumStore(proof_with_data) => {
+                Payload::QuorumStoreInlineHybrid(vec![(batch_info, txns)], proof_with_data, None)
+            },
+            Payload::InQuorumStoreWithLimit(proof_with_status) => Payload::QuorumStoreInlineHybrid(
+                vec![(batch_info, txns)],
+                proof_with_status.proof_with_data,
+                proof_with_status.max_txns_to_execute,
+            ),
+            Payload::QuorumStoreInlineHybrid(mut inline_batches, proof_with_data, max_txns) => {
+                inline_batches.push((batch_info, txns));
+                Payload::QuorumStoreInlineHybrid(inline_batches, proof_with_data, max_txns)
+            },
+            Payload::QuorumStoreInlineHybridV2(mut inline_batches, proof_with_data, limit) => {
+                inline_batches.push((batch_info, txns));
+                Payload::QuorumStoreInlineHybridV2(inline_batches, proof_with_data, limit)
+            },
+            Payload::OptQuorumStore(mut opt_qs_payload) => {
+                opt_qs_payload.push_inline_batch(InlineBatch::new(batch_info, txns));
+                Payload::OptQuorumStore(opt_qs_payload)
+            },
+            Payload::DirectMempool(_) => {
+                panic!("Payload is in direct mempool format");
+            },
+        }
+    }
+
     pub fn len(&self) -> usize {
         match self {
             Payload::DirectMempool(txns) => txns.len(),
@@ -393,6 +393,10 @@ impl OptQuorumStorePayload {
     pub fn set_execution_limit(&mut self, execution_limits: PayloadExecutionLimit) {
         self.execution_limits = execution_limits;
     }
+
+    pub(crate) fn push_inline_batch(&mut self, inline_batch: InlineBatch) {
+        self.inline_batches.push(inline_batch);
+    }
 }
 
 impl Deref for OptQuorumStorePayload {
@@ -0,0 +1,407 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{counters, Error};
+use aptos_config::config::RemoteService;
+use aptos_consensus_types::{
+    block_data::BlockData, common::Author, timeout_2chain::TimeoutSigningRepr,
+};
+use aptos_crypto::{bls12381, hash::CryptoHash, signing_message, Signature};
+use aptos_global_constants::CONSENSUS_KEY_SHARE;
+use aptos_infallible::Mutex;
+use aptos_logger::prelude::*;
+use aptos_secure_net::{tls::ClientTlsConfig, NetworkClient};
+use aptos_secure_storage::{KVStorage, Storage};
+use aptos_types::{ledger_info::LedgerInfo, validator_signer::ValidatorSigner};
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+/// A consensus message SafetyRules signs. Key shards are sent these instead of the bytes to sign
+/// and compute the signing message themselves, so that they only ever sign consensus messages.
+#[derive(Debug, Deserialize, Serialize)]
+pub enum SigningRequest {
+    Block(BlockData),
+    LedgerInfo(LedgerInfo),
+    Timeout(TimeoutSigningRepr),
+}
+
+impl SigningRequest {
+    pub fn signing_message(&self) -> Result<Vec<u8>, Error> {
+        match self {
+            Self::Block(block_data) => signing_message(block_data),
+            Self::LedgerInfo(ledger_info) => signing_message(ledger_info),
+            Self::Timeout(timeout) => signing_message(timeout),
+        }
+        .map_err(|err| Error::SerializationError(err.to_string()))
+    }
+}
+
+/// The messages SafetyRules signs with its consensus key, see `SigningRequest`.
+pub trait ConsensusMessage: Serialize + CryptoHash {
+    fn signing_request(&self) -> SigningRequest;
+}
+
+impl ConsensusMessage for BlockData {
+    fn signing_request(&self) -> SigningRequest {
+        SigningRequest::Block(self.clone())
+    }
+}
+
+impl ConsensusMessage for LedgerInfo {
+    fn signing_request(&self) -> SigningRequest {
+        SigningRequest::LedgerInfo(self.clone())
+    }
+}
+
+impl ConsensusMessage for TimeoutSigningRepr {
+    fn signing_request(&self) -> SigningRequest {
+        SigningRequest::Timeout(TimeoutSigningRepr {
+            epoch: self.epoch,
+            round: self.round,
+            hqc_round: self.hqc_round,
+        })
+    }
+}
+
+/// The signer SafetyRules signs consensus messages with: either the whole consensus key, or a
+/// threshold of the shards the consensus key has been split across.
+pub(crate) enum ConsensusSigner {
+    Local(ValidatorSigner),
+    Threshold(ThresholdSigner),
+}
+
+impl ConsensusSigner {
+    pub fn author(&self) -> Author {
+        match self {
+            Self::Local(signer) => signer.author(),
+            Self::Threshold(signer) => signer.author,
+        }
+    }
+
+    pub fn public_key(&self) -> bls12381::PublicKey {
+        match self {
+            Self::Local(signer) => signer.public_key(),
+            Self::Threshold(signer) => signer.public_key.clone(),
+        }
+    }
+
+    pub fn sign<T: ConsensusMessage>(&self, message: &T) -> Result<bls12381::Signature, Error> {
+        match self {
+            Self::Local(signer) => signer
+                .sign(message)
+                .map_err(|err| Error::SerializationError(err.to_string())),
+            Self::Threshold(signer) => signer.sign(&message.signing_request()),
+        }
+    }
+}
+
+/// A holder of one share of a consensus key, e.g., an HSM or a remote signer.
+pub trait TKeyShard: Send + Sync {
+    fn sign_share(&self, request: &SigningRequest) -> Result<bls12381::SignatureShare, Error>;
+}
+
+/// A shard held by a key shard signer in another process, see
+/// `remote_service::execute_key_shard`, so that the share never leaves that process.
+pub struct RemoteKeyShard {
+    network_client: Mutex<NetworkClient>,
+}
+
+impl RemoteKeyShard {
+    pub fn new(network_client: NetworkClient) -> Self {
+        Self {
+            network_client: Mutex::new(network_client),
+        }
+    }
+
+    /// Tries each of the signer's addresses once per signature share, so that an unreachable
+    /// shard does not hold up signing with the others. Fails if the signer has no TLS config or it
+    /// cannot be loaded, as shards only sign for mutually authenticated clients.
+    pub fn from_config(service: &RemoteService, network_timeout_ms: u64) -> Result<Self, Error> {
+        let tls = service.tls.as_ref().ok_or_else(|| {
+            Error::ThresholdSigningError(format!(
+                "key shard {} has no TLS config",
+                service.server_address
+            ))
+        })?;
+        let failover_addresses = service.failover_addresses();
+        let network_client = NetworkClient::new(
+            "key-shard".to_string(),
+            service.server_address(),
+            network_timeout_ms,
+        )
+        .with_max_connection_attempts(failover_addresses.len() + 1)
+        .with_failover(failover_addresses)
+        .with_tls(ClientTlsConfig::new(
+            &tls.ca_certificate,
+            &tls.certificate,
+            &tls.private_key,
+            tls.server_name.clone(),
+        )?);
+        Ok(Self::new(network_client))
+    }
+}
+
+impl TKeyShard for RemoteKeyShard {
+    fn sign_share(&self, request: &SigningRequest) -> Result<bls12381::SignatureShare, Error> {
+        let mut network_client = self.network_client.lock();
+        network_client.write(&serde_json::to_vec(request)?)?;
+        Ok(serde_json::from_slice(&network_client.read()?)?)
+    }
+}
+
+/// Reads the share of `public_key`'s private key from `storage`, under either
+/// `CONSENSUS_KEY_SHARE_{pk_hex}` or `CONSENSUS_KEY_SHARE`, mirroring how the whole consensus key
+/// is stored.
+pub(crate) fn key_share(
+    storage: &Storage,
+    public_key: &bls12381::PublicKey,
+) -> Result<bls12381::PrivateKeyShare, Error> {
+    let _timer = counters::start_timer("get", CONSENSUS_KEY_SHARE);
+    let explicit_storage_key = format!(
+        "{}_{}",
+        CONSENSUS_KEY_SHARE,
+        hex::encode(public_key.to_bytes())
+    );
+    match storage.get::<bls12381::PrivateKeyShare>(&explicit_storage_key) {
+        Ok(response) => Ok(response.value),
+        Err(_) => Ok(storage
+            .get::<bls12381::PrivateKeyShare>(CONSENSUS_KEY_SHARE)?
+            .value),
+    }
+}
+
+/// Signs with a consensus key split across `shards`, by combining the signature shares of the
+/// first `threshold` shards to respond into a valid signature.
+pub struct ThresholdSigner {
+    author: Author,
+    public_key: bls12381::PublicKey,
+    threshold: usize,
+    shards: Vec<Arc<dyn TKeyShard>>,
+}
+
+impl ThresholdSigner {
+    pub fn new(
+        author: Author,
+        public_key: bls12381::PublicKey,
+        threshold: usize,
+        shards: Vec<Arc<dyn TKeyShard>>,
+    ) -> Result<Self, Error> {
+        if threshold == 0 || threshold > shards.len() {
+            return Err(Error::ThresholdSigningError(format!(
+                "invalid threshold {} for {} shards",
+                threshold,
+                shards.len()
+            )));
+        }
+        Ok(Self {
+            author,
+            public_key,
+            threshold,
+            shards,
+        })
+    }
+
+    /// Asks the shards for signature shares one at a time, until `threshold` of them combine into
+    /// a signature that verifies. A bad share thus only costs asking the remaining shards.
+    pub fn sign(&self, request: &SigningRequest) -> Result<bls12381::Signature, Error> {
+        let signing_message = request.signing_message()?;
+
+        let mut shares: Vec<bls12381::SignatureShare> = Vec::with_capacity(self.shards.len());
+        for shard in &self.shards {
+            let share = match shard.sign_share(request) {
+                Ok(share) => share,
+                Err(error) => {
+                    warn!("Unable to get a signature share: {}", error);
+                    continue;
+                },
+            };
+            // The combinations without the new share have already been tried
+            for mut combination in shares.iter().cloned().combinations(self.threshold - 1) {
+                combination.push(share.clone());
+                let signature =
+                    match bls12381::combine_signature_shares(&combination, self.threshold) {
+                        Ok(signature) => signature,
+                        Err(error) => {
+                            warn!("Unable to combine signature shares: {}", error);
+                            continue;
+                        },
+                    };
+                if signature
+                    .verify_arbitrary_msg(&signing_message, &self.public_key)
+                    .is_ok()
+                {
+                    return Ok(signature);
+                }
+            }
+            shares.push(share);
+        }
+
+        Err(Error::ThresholdSigningError(format!(
+            "no {} of the {} signature shares received combine into a valid signature",
+            self.threshold,
+            shares.len()
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::remote_service;
+    use aptos_config::{config::RemoteServiceTlsConfig, utils};
+    use aptos_crypto::Uniform;
+    use aptos_secure_storage::InMemoryStorage;
+    use aptos_types::network_address::NetworkAddress;
+    use rcgen::{BasicConstraints, CertificateParams, IsCa, KeyPair};
+    use std::{fs, net::SocketAddr, path::Path, str::FromStr, thread};
+
+    /// Read, Write, Connect timeout in milliseconds.
+    const TIMEOUT: u64 = 5_000;
+    const KEY_SHARD_NAME: &str = "key-shard";
+
+    struct LocalKeyShard(bls12381::PrivateKeyShare);
+
+    impl TKeyShard for LocalKeyShard {
+        fn sign_share(&self, request: &SigningRequest) -> Result<bls12381::SignatureShare, Error> {
+            Ok(self.0.sign_signing_message(&request.signing_message()?))
+        }
+    }
+
+    struct FailingKeyShard;
+
+    impl TKeyShard for FailingKeyShard {
+        fn sign_share(&self, _request: &SigningRequest) -> Result<bls12381::SignatureShare, Error> {
+            Err(Error::SecureStorageUnexpectedError("unavailable".into()))
+        }
+    }
+
+    fn signing_request() -> SigningRequest {
+        SigningRequest::Timeout(TimeoutSigningRepr {
+            epoch: 1,
+            round: 2,
+            hqc_round: 1,
+        })
+    }
+
+    /// Issues the certificates of a key shard and of its client from a new CA, and returns the
+    /// TLS configs of both
+    fn tls_configs(dir: &Path) -> (RemoteServiceTlsConfig, RemoteServiceTlsConfig) {
+        let ca_key = KeyPair::generate().unwrap();
+        let mut ca_params = CertificateParams::new(Vec::<String>::new()).unwrap();
+        ca_params.is_ca = IsCa::Ca(BasicConstraints::Unconstrained);
+        let ca = ca_params.self_signed(&ca_key).unwrap();
+        let ca_certificate = dir.join("ca.pem");
+        fs::write(&ca_certificate, ca.pem()).unwrap();
+
+        let tls_config = |name: &str| {
+            let key = KeyPair::generate().unwrap();
+            let certificate = CertificateParams::new(vec![KEY_SHARD_NAME.to_string()])
+                .unwrap()
+                .signed_by(&key, &ca, &ca_key)
+                .unwrap();
+            let certificate_path = dir.join(format!("{}.pem", name));
+            let private_key_path = dir.join(format!("{}.key", name));
+            fs::write(&certificate_path, certificate.pem()).unwrap();
+            fs::write(&private_key_path, key.serialize_pem()).unwrap();
+            RemoteServiceTlsConfig {
+                ca_certificate: ca_certificate.clone(),
+                certificate: certificate_path,
+                private_key: private_key_path,
+                server_name: KEY_SHARD_NAME.to_string(),
+            }
+        };
+        (tls_config("key-shard"), tls_config("client"))
+    }
+
+    #[test]
+    fn test_threshold_signer() {
+        let mut rng = rand::thread_rng();
+        let private_key = bls12381::PrivateKey::generate(&mut rng);
+        let public_key = bls12381::PublicKey::from(&private_key);
+        let shares = bls12381::split_private_key(&private_key, 2, 4, &mut rng).unwrap();
+
+        // The first shard is unavailable and the second one signs with a share of another key
+        let other_private_key = bls12381::PrivateKey::generate(&mut rng);
+        let other_share = bls12381::split_private_key(&other_private_key, 2, 4, &mut rng)
+            .unwrap()
+            .remove(1);
+        let mut shards: Vec<Arc<dyn TKeyShard>> = vec![
+            Arc::new(FailingKeyShard),
+            Arc::new(LocalKeyShard(other_share)),
+        ];
+        for share in shares.into_iter().skip(2) {
+            shards.push(Arc::new(LocalKeyShard(share)));
+        }
+
+        let request = signing_request();
+        let signer =
+            ThresholdSigner::new(Author::random(), public_key.clone(), 2, shards.clone()).unwrap();
+        let signature = signer.sign(&request).unwrap();
+        signature
+            .verify_arbitrary_msg(&request.signing_message().unwrap(), &public_key)
+            .unwrap();
+
+        // Only two of the four shards sign with a share of the key
+        assert!(
+            ThresholdSigner::new(Author::random(), public_key.clone(), 5, shards.clone()).is_err()
+        );
+        let signer = ThresholdSigner::new(Author::random(), public_key, 3, shards).unwrap();
+        assert!(matches!(
+            signer.sign(&request),
+            Err(Error::ThresholdSigningError(_))
+        ));
+    }
+
+    #[test]
+    fn test_remote_key_shards() {
+        let mut rng = rand::thread_rng();
+        let private_key = bls12381::PrivateKey::generate(&mut rng);
+        let public_key = bls12381::PublicKey::from(&private_key);
+        let shares = bls12381::split_private_key(&private_key, 2, 2, &mut rng).unwrap();
+        let tls_dir = tempfile::tempdir().unwrap();
+        let (key_shard_tls, client_tls) = tls_configs(tls_dir.path());
+
+        // Each share only lives in the storage of its own signer
+        let mut shards: Vec<Arc<dyn TKeyShard>> = vec![];
+        for share in shares {
+            let mut storage = Storage::from(InMemoryStorage::new());
+            storage.set(CONSENSUS_KEY_SHARE, share).unwrap();
+            let listen_port = utils::get_available_port();
+            let shard_public_key = public_key.clone();
+            let shard_tls = key_shard_tls.clone();
+            thread::spawn(move || {
+                remote_service::execute_key_shard(
+                    &storage,
+                    &shard_public_key,
+                    SocketAddr::from(([127, 0, 0, 1], listen_port)),
+                    &shard_tls,
+                    TIMEOUT,
+                )
+            });
+
+            let mut service = RemoteService {
+                server_address: NetworkAddress::from_str(&format!(
+                    "/ip4/127.0.0.1/tcp/{}",
+                    listen_port
+                ))
+                .unwrap(),
+                failover_addresses: vec![],
+                tls: None,
+            };
+            // Shards are only reachable over TLS
+            assert!(RemoteKeyShard::from_config(&service, TIMEOUT).is_err());
+            service.tls = Some(client_tls.clone());
+            shards.push(Arc::new(
+                RemoteKeyShard::from_config(&service, TIMEOUT).unwrap(),
+            ));
+        }
+
+        let request = signing_request();
+        let signer = ThresholdSigner::new(Author::random(), public_key.clone(), 2, shards).unwrap();
+        let signature = signer.sign(&request).unwrap();
+        signature
+            .verify_arbitrary_msg(&request.signing_message().unwrap(), &public_key)
+            .unwrap();
+    }
+}
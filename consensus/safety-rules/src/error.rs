@@ -61,6 +61,8 @@ pub enum Error {
     InvalidTimeout(String),
     #[error("Incorrect 1-chain Quorum Certificate provided for signing order votes. Quorum Certificate: {0}, block id: {1}")]
     InvalidOneChainQuorumCertificate(HashValue, HashValue),
+    #[error("Threshold signing failed: {0}")]
+    ThresholdSigningError(String),
 }
 
 impl From<serde_json::Error> for Error {
@@ -4,6 +4,7 @@
 
 #![forbid(unsafe_code)]
 
+mod consensus_signer;
 mod consensus_state;
 mod counters;
 mod error;
@@ -16,13 +17,20 @@ mod safety_rules;
 mod safety_rules_2chain;
 pub mod safety_rules_manager;
 mod serializer;
+mod storage_migration;
 mod t_safety_rules;
 mod thread;
 
 pub use crate::{
-    consensus_state::ConsensusState, error::Error,
-    persistent_safety_storage::PersistentSafetyStorage, process::Process,
-    safety_rules::SafetyRules, safety_rules_manager::SafetyRulesManager,
+    consensus_signer::{RemoteKeyShard, SigningRequest, TKeyShard, ThresholdSigner},
+    consensus_state::ConsensusState,
+    error::Error,
+    persistent_safety_storage::PersistentSafetyStorage,
+    process::Process,
+    remote_service::execute_key_shard,
+    safety_rules::SafetyRules,
+    safety_rules_manager::SafetyRulesManager,
+    storage_migration::migrate_safety_storage,
     t_safety_rules::TSafetyRules,
 };
 
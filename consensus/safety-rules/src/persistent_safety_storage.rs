@@ -3,6 +3,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
+    consensus_signer::{TKeyShard, ThresholdSigner},
     counters,
     logging::{self, LogEntry, LogEvent},
     Error,
@@ -13,6 +14,7 @@ use aptos_global_constants::{CONSENSUS_KEY, OWNER_ACCOUNT, SAFETY_DATA, WAYPOINT
 use aptos_logger::prelude::*;
 use aptos_secure_storage::{KVStorage, Storage};
 use aptos_types::waypoint::Waypoint;
+use std::sync::Arc;
 
 /// SafetyRules needs an abstract storage interface to act as a common utility for storing
 /// persistent data to local disk, cloud, secrets managers, or even memory (for tests)
@@ -22,10 +24,14 @@ use aptos_types::waypoint::Waypoint;
 /// only ever be used by safety rules, we maintain an in-memory copy to avoid issuing reads
 /// to the internal storage if the SafetyData hasn't changed. On writes, we update the
 /// cache and internal storage.
+///
+/// Note: if key_shards is set, the consensus key is not read from internal storage but is split
+/// across the given shards, any threshold of which are needed to sign.
 pub struct PersistentSafetyStorage {
     enable_cached_safety_data: bool,
     cached_safety_data: Option<SafetyData>,
     internal_store: Storage,
+    key_shards: Option<(usize, Vec<Arc<dyn TKeyShard>>)>,
 }
 
 impl PersistentSafetyStorage {
@@ -48,6 +54,7 @@ impl PersistentSafetyStorage {
             enable_cached_safety_data,
             cached_safety_data: Some(safety_data.clone()),
             internal_store,
+            key_shards: None,
         };
 
         // Initialize the safety data and waypoint
@@ -88,9 +95,16 @@ impl PersistentSafetyStorage {
             enable_cached_safety_data,
             cached_safety_data: None,
             internal_store,
+            key_shards: None,
         }
     }
 
+    /// Splits the consensus key across `shards`, see `bls12381::split_private_key`.
+    pub fn with_key_shards(mut self, threshold: usize, shards: Vec<Arc<dyn TKeyShard>>) -> Self {
+        self.key_shards = Some((threshold, shards));
+        self
+    }
+
     pub fn author(&self) -> Result<Author, Error> {
         let _timer = counters::start_timer("get", OWNER_ACCOUNT);
         Ok(self.internal_store.get(OWNER_ACCOUNT).map(|v| v.value)?)
@@ -132,6 +146,17 @@ impl PersistentSafetyStorage {
         Ok(key)
     }
 
+    /// Returns a signer for the shards of the consensus key `pk`, if the key has been split.
+    pub(crate) fn threshold_signer_by_pk(
+        &self,
+        author: Author,
+        pk: bls12381::PublicKey,
+    ) -> Option<Result<ThresholdSigner, Error>> {
+        self.key_shards
+            .as_ref()
+            .map(|(threshold, shards)| ThresholdSigner::new(author, pk, *threshold, shards.clone()))
+    }
+
     pub fn safety_data(&mut self) -> Result<SafetyData, Error> {
         if !self.enable_cached_safety_data {
             let _timer = counters::start_timer("get", SAFETY_DATA);
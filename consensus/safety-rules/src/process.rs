@@ -5,9 +5,13 @@
 use crate::{
     persistent_safety_storage::PersistentSafetyStorage,
     remote_service::{self, RemoteService},
-    safety_rules_manager,
+    safety_rules_manager, Error,
 };
-use aptos_config::config::{SafetyRulesConfig, SafetyRulesService};
+use aptos_config::config::{
+    RemoteService as RemoteServiceConfig, RemoteServiceTlsConfig, SafetyRulesConfig,
+    SafetyRulesService,
+};
+use aptos_secure_net::tls::ClientTlsConfig;
 use std::net::SocketAddr;
 
 pub struct Process {
@@ -27,20 +31,27 @@ impl Process {
         Self {
             data: Some(ProcessData {
                 server_addr,
+                tls: service.tls.clone(),
                 storage,
                 network_timeout: config.network_timeout_ms,
             }),
         }
     }
 
-    pub fn start(&mut self) {
+    pub fn start(&mut self) -> Result<(), Error> {
         let data = self.data.take().expect("Unable to retrieve ProcessData");
-        remote_service::execute(data.storage, data.server_addr, data.network_timeout);
+        remote_service::execute_remote(
+            data.storage,
+            data.server_addr,
+            data.tls.as_ref(),
+            data.network_timeout,
+        )
     }
 }
 
 struct ProcessData {
     server_addr: SocketAddr,
+    tls: Option<RemoteServiceTlsConfig>,
     storage: PersistentSafetyStorage,
     // Timeout in Seconds for network operations
     network_timeout: u64,
@@ -48,6 +59,8 @@ struct ProcessData {
 
 pub struct ProcessService {
     server_addr: SocketAddr,
+    failover_addrs: Vec<SocketAddr>,
+    tls: Option<ClientTlsConfig>,
     network_timeout_ms: u64,
 }
 
@@ -55,9 +68,33 @@ impl ProcessService {
     pub fn new(server_addr: SocketAddr, network_timeout: u64) -> Self {
         Self {
             server_addr,
+            failover_addrs: vec![],
+            tls: None,
             network_timeout_ms: network_timeout,
         }
     }
+
+    /// Fails if the TLS config cannot be loaded
+    pub fn from_config(service: &RemoteServiceConfig, network_timeout: u64) -> Result<Self, Error> {
+        let tls = service
+            .tls
+            .as_ref()
+            .map(|tls| {
+                ClientTlsConfig::new(
+                    &tls.ca_certificate,
+                    &tls.certificate,
+                    &tls.private_key,
+                    tls.server_name.clone(),
+                )
+            })
+            .transpose()?;
+        Ok(Self {
+            server_addr: service.server_address(),
+            failover_addrs: service.failover_addresses(),
+            tls,
+            network_timeout_ms: network_timeout,
+        })
+    }
 }
 
 impl RemoteService for ProcessService {
@@ -65,6 +102,14 @@ impl RemoteService for ProcessService {
         self.server_addr
     }
 
+    fn failover_addresses(&self) -> Vec<SocketAddr> {
+        self.failover_addrs.clone()
+    }
+
+    fn tls(&self) -> Option<&ClientTlsConfig> {
+        self.tls.as_ref()
+    }
+
     fn network_timeout_ms(&self) -> u64 {
         self.network_timeout_ms
     }
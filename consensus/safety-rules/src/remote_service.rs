@@ -3,41 +3,127 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
+    consensus_signer::{self, SigningRequest},
     persistent_safety_storage::PersistentSafetyStorage,
     serializer::{SafetyRulesInput, SerializerClient, SerializerService, TSerializerClient},
     Error, SafetyRules, TSafetyRules,
 };
+use aptos_config::config::RemoteServiceTlsConfig;
+use aptos_crypto::bls12381;
 use aptos_logger::warn;
-use aptos_secure_net::{NetworkClient, NetworkServer};
+use aptos_secure_net::{tls, tls::ClientTlsConfig, NetworkClient, NetworkServer};
+use aptos_secure_storage::Storage;
 use std::net::SocketAddr;
 
 pub trait RemoteService {
     fn client(&self) -> SerializerClient {
-        let network_client = NetworkClient::new(
+        let mut network_client = NetworkClient::new(
             "safety-rules".to_string(),
             self.server_address(),
             self.network_timeout_ms(),
-        );
+        )
+        .with_failover(self.failover_addresses());
+        if let Some(tls) = self.tls() {
+            network_client = network_client.with_tls(tls.clone());
+        }
         let service = Box::new(RemoteClient::new(network_client));
         SerializerClient::new_client(service)
     }
 
     fn server_address(&self) -> SocketAddr;
 
+    /// Servers to fail over to, in order, if the current one is unreachable.
+    fn failover_addresses(&self) -> Vec<SocketAddr> {
+        vec![]
+    }
+
+    fn tls(&self) -> Option<&ClientTlsConfig> {
+        None
+    }
+
     /// Network Timeout in milliseconds.
     fn network_timeout_ms(&self) -> u64;
 }
 
 pub fn execute(storage: PersistentSafetyStorage, listen_addr: SocketAddr, network_timeout_ms: u64) {
+    let network_server =
+        NetworkServer::new("safety-rules".to_string(), listen_addr, network_timeout_ms);
+    serve(
+        SerializerService::new(safety_rules(storage)),
+        network_server,
+    )
+}
+
+/// Runs SafetyRules as a remote signer, over TLS if `tls_config` is given. Only returns if the
+/// TLS config cannot be loaded.
+pub fn execute_remote(
+    storage: PersistentSafetyStorage,
+    listen_addr: SocketAddr,
+    tls_config: Option<&RemoteServiceTlsConfig>,
+    network_timeout_ms: u64,
+) -> Result<(), Error> {
+    let network_server =
+        network_server("safety-rules", listen_addr, tls_config, network_timeout_ms)?;
+    serve(
+        SerializerService::new(safety_rules(storage)),
+        network_server,
+    );
+    Ok(())
+}
+
+/// Runs a key shard signer for the share of `public_key`'s private key held in `storage`. The
+/// share never leaves this process: the signer only serves mutually authenticated clients, is only
+/// sent consensus messages to sign and only answers with signature shares. Only returns if the key
+/// share or the TLS config cannot be loaded.
+pub fn execute_key_shard(
+    storage: &Storage,
+    public_key: &bls12381::PublicKey,
+    listen_addr: SocketAddr,
+    tls_config: &RemoteServiceTlsConfig,
+    network_timeout_ms: u64,
+) -> Result<(), Error> {
+    let key_share = consensus_signer::key_share(storage, public_key)?;
+    let mut network_server = network_server(
+        "key-shard",
+        listen_addr,
+        Some(tls_config),
+        network_timeout_ms,
+    )?;
+    loop {
+        if let Err(e) = process_one_signing_message(&mut network_server, &key_share) {
+            warn!("Failed to process message: {}", e);
+        }
+    }
+}
+
+fn network_server(
+    service: &str,
+    listen_addr: SocketAddr,
+    tls_config: Option<&RemoteServiceTlsConfig>,
+    network_timeout_ms: u64,
+) -> Result<NetworkServer, Error> {
+    let mut network_server =
+        NetworkServer::new(service.to_string(), listen_addr, network_timeout_ms);
+    if let Some(tls_config) = tls_config {
+        let server_config = tls::server_config(
+            &tls_config.ca_certificate,
+            &tls_config.certificate,
+            &tls_config.private_key,
+        )?;
+        network_server = network_server.with_tls(server_config);
+    }
+    Ok(network_server)
+}
+
+fn safety_rules(storage: PersistentSafetyStorage) -> SafetyRules {
     let mut safety_rules = SafetyRules::new(storage, false);
     if let Err(e) = safety_rules.consensus_state() {
         warn!("Unable to print consensus state: {}", e);
     }
+    safety_rules
+}
 
-    let mut serializer_service = SerializerService::new(safety_rules);
-    let mut network_server =
-        NetworkServer::new("safety-rules".to_string(), listen_addr, network_timeout_ms);
-
+fn serve(mut serializer_service: SerializerService, mut network_server: NetworkServer) {
     loop {
         if let Err(e) = process_one_message(&mut network_server, &mut serializer_service) {
             warn!("Failed to process message: {}", e);
@@ -55,6 +141,16 @@ fn process_one_message(
     Ok(())
 }
 
+fn process_one_signing_message(
+    network_server: &mut NetworkServer,
+    key_share: &bls12381::PrivateKeyShare,
+) -> Result<(), Error> {
+    let request: SigningRequest = serde_json::from_slice(&network_server.read()?)?;
+    let signature_share = key_share.sign_signing_message(&request.signing_message()?);
+    network_server.write(&serde_json::to_vec(&signature_share)?)?;
+    Ok(())
+}
+
 struct RemoteClient {
     network_client: NetworkClient,
 }
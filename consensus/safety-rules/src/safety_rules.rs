@@ -3,6 +3,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
+    consensus_signer::{ConsensusMessage, ConsensusSigner},
     consensus_state::ConsensusState,
     counters,
     error::Error,
@@ -22,7 +23,7 @@ use aptos_consensus_types::{
     vote_data::VoteData,
     vote_proposal::VoteProposal,
 };
-use aptos_crypto::{bls12381, hash::CryptoHash};
+use aptos_crypto::bls12381;
 use aptos_logger::prelude::*;
 use aptos_types::{
     epoch_change::EpochChangeProof,
@@ -31,7 +32,6 @@ use aptos_types::{
     validator_signer::ValidatorSigner,
     waypoint::Waypoint,
 };
-use serde::Serialize;
 use std::{cmp::Ordering, sync::Arc};
 
 pub(crate) fn next_round(round: Round) -> Result<Round, Error> {
@@ -41,7 +41,7 @@ pub(crate) fn next_round(round: Round) -> Result<Round, Error> {
 /// @TODO consider a cache of verified QCs to cut down on verification costs
 pub struct SafetyRules {
     pub(crate) persistent_storage: PersistentSafetyStorage,
-    pub(crate) validator_signer: Option<ValidatorSigner>,
+    pub(crate) validator_signer: Option<ConsensusSigner>,
     pub(crate) epoch_state: Option<EpochState>,
     // Skip verification of signatures and well-formed, this can be set if it's used in local mode
     // where consensus already verifies.
@@ -111,17 +111,14 @@ impl SafetyRules {
         Ok(())
     }
 
-    pub(crate) fn sign<T: Serialize + CryptoHash>(
+    pub(crate) fn sign<T: ConsensusMessage>(
         &self,
         message: &T,
     ) -> Result<bls12381::Signature, Error> {
-        let signer = self.signer()?;
-        signer
-            .sign(message)
-            .map_err(|err| Error::SerializationError(err.to_string()))
+        self.signer()?.sign(message)
     }
 
-    pub(crate) fn signer(&self) -> Result<&ValidatorSigner, Error> {
+    pub(crate) fn signer(&self) -> Result<&ConsensusSigner, Error> {
         self.validator_signer
             .as_ref()
             .ok_or_else(|| Error::NotInitialized("validator_signer".into()))
@@ -323,11 +320,26 @@ impl SafetyRules {
                     );
                     Ok(())
                 } else {
-                    // Try to export the consensus key directly from storage.
-                    match self.persistent_storage.consensus_sk_by_pk(expected_key) {
-                        Ok(consensus_key) => {
-                            self.validator_signer =
-                                Some(ValidatorSigner::new(author, Arc::new(consensus_key)));
+                    // Sign with the shards of the consensus key if it has been split, otherwise try
+                    // to export the consensus key directly from storage.
+                    let signer = match self
+                        .persistent_storage
+                        .threshold_signer_by_pk(author, expected_key.clone())
+                    {
+                        Some(threshold_signer) => threshold_signer.map(ConsensusSigner::Threshold),
+                        None => self
+                            .persistent_storage
+                            .consensus_sk_by_pk(expected_key)
+                            .map(|consensus_key| {
+                                ConsensusSigner::Local(ValidatorSigner::new(
+                                    author,
+                                    Arc::new(consensus_key),
+                                ))
+                            }),
+                    };
+                    match signer {
+                        Ok(signer) => {
+                            self.validator_signer = Some(signer);
                             Ok(())
                         },
                         Err(Error::SecureStorageMissingDataError(error)) => {
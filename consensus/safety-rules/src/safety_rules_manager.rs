@@ -3,13 +3,14 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
+    consensus_signer::{RemoteKeyShard, TKeyShard},
     local_client::LocalClient,
     persistent_safety_storage::PersistentSafetyStorage,
     process::ProcessService,
     remote_service::RemoteService,
     serializer::{SerializerClient, SerializerService},
     thread::ThreadService,
-    SafetyRules, TSafetyRules,
+    Error, SafetyRules, TSafetyRules,
 };
 use aptos_config::config::{InitialSafetyRulesConfig, SafetyRulesConfig, SafetyRulesService};
 use aptos_crypto::bls12381::PublicKey;
@@ -26,7 +27,7 @@ pub fn storage(config: &SafetyRulesConfig) -> PersistentSafetyStorage {
         panic!("Storage is not available: {:?}", error);
     }
 
-    if let Some(test_config) = &config.test {
+    let storage = if let Some(test_config) = &config.test {
         let author = test_config.author;
         let consensus_private_key = test_config
             .consensus_key
@@ -100,6 +101,22 @@ pub fn storage(config: &SafetyRulesConfig) -> PersistentSafetyStorage {
         info!("Overriding key work time: {:?}", timer.elapsed());
 
         storage
+    };
+
+    match &config.threshold_signing {
+        Some(threshold_signing) => {
+            let shards = threshold_signing
+                .shards
+                .iter()
+                .map(|shard| {
+                    let shard = RemoteKeyShard::from_config(shard, config.network_timeout_ms)
+                        .unwrap_or_else(|error| panic!("Key shard config is invalid: {:?}", error));
+                    Arc::new(shard) as Arc<dyn TKeyShard>
+                })
+                .collect();
+            storage.with_key_shards(threshold_signing.threshold, shards)
+        },
+        None => storage,
     }
 }
 
@@ -115,18 +132,22 @@ pub struct SafetyRulesManager {
 }
 
 impl SafetyRulesManager {
-    pub fn new(config: &SafetyRulesConfig) -> Self {
+    /// Fails if the config of a remote service cannot be loaded
+    pub fn new(config: &SafetyRulesConfig) -> Result<Self, Error> {
         if let SafetyRulesService::Process(conf) = &config.service {
-            return Self::new_process(conf.server_address(), config.network_timeout_ms);
+            let process_service = ProcessService::from_config(conf, config.network_timeout_ms)?;
+            return Ok(Self {
+                internal_safety_rules: SafetyRulesWrapper::Process(process_service),
+            });
         }
 
         let storage = storage(config);
-        match config.service {
+        Ok(match config.service {
             SafetyRulesService::Local => Self::new_local(storage),
             SafetyRulesService::Serializer => Self::new_serializer(storage),
             SafetyRulesService::Thread => Self::new_thread(storage, config.network_timeout_ms),
             _ => panic!("Unimplemented SafetyRulesService: {:?}", config.service),
-        }
+        })
     }
 
     pub fn new_local(storage: PersistentSafetyStorage) -> Self {
@@ -0,0 +1,113 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Migration of the persistent safety storage between backends, e.g., when moving a validator
+//! from a local file to storage shared with a standby replica.
+
+use crate::Error;
+use aptos_consensus_types::safety_data::SafetyData;
+use aptos_global_constants::{
+    CONSENSUS_KEY, CONSENSUS_KEY_SHARE, GENESIS_WAYPOINT, OWNER_ACCOUNT, SAFETY_DATA, WAYPOINT,
+};
+use aptos_logger::prelude::*;
+use aptos_secure_storage::{KVStorage, Storage};
+use serde_json::Value;
+
+/// The keys SafetyRules persists, in the order they are migrated. The safety data is migrated
+/// last, so that a target holding it is always fully migrated.
+const SAFETY_STORAGE_KEYS: &[&str] = &[
+    CONSENSUS_KEY,
+    CONSENSUS_KEY_SHARE,
+    OWNER_ACCOUNT,
+    GENESIS_WAYPOINT,
+    WAYPOINT,
+    SAFETY_DATA,
+];
+
+/// Copies the safety rules state from `source` to `target`, and returns the migrated safety data.
+///
+/// The migration is refused if `target` already holds safety data that is ahead of the source,
+/// i.e., if the validator has voted in a later epoch or round using the target: overwriting it
+/// would roll back the voting state and allow the validator to equivocate. Migrating again to a
+/// target that has already been migrated to (e.g., after a failure) is safe.
+pub fn migrate_safety_storage(source: &Storage, target: &mut Storage) -> Result<SafetyData, Error> {
+    source.available()?;
+    target.available()?;
+
+    let source_safety_data: SafetyData = source.get(SAFETY_DATA)?.value;
+    match target.get::<SafetyData>(SAFETY_DATA) {
+        Ok(response) if is_ahead(&response.value, &source_safety_data) => {
+            return Err(Error::InternalError(format!(
+                "The target safety data is ahead of the source, refusing to roll it back. \
+                Source: {}, target: {}",
+                source_safety_data, response.value
+            )));
+        },
+        Ok(_) | Err(aptos_secure_storage::Error::KeyNotSet(_)) => {},
+        Err(error) => return Err(error.into()),
+    }
+
+    for key in SAFETY_STORAGE_KEYS {
+        match source.get::<Value>(key) {
+            Ok(response) => target.set(key, response.value)?,
+            // Not every key is set, e.g., the key share is only set for threshold signing
+            Err(aptos_secure_storage::Error::KeyNotSet(_)) => continue,
+            Err(error) => return Err(error.into()),
+        }
+        info!("Migrated {} to the target safety storage", key);
+    }
+
+    Ok(source_safety_data)
+}
+
+fn is_ahead(safety_data: &SafetyData, other: &SafetyData) -> bool {
+    (
+        safety_data.epoch,
+        safety_data.last_voted_round,
+        safety_data.highest_timeout_round,
+    ) > (
+        other.epoch,
+        other.last_voted_round,
+        other.highest_timeout_round,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{test_utils, PersistentSafetyStorage};
+    use aptos_crypto::PrivateKey;
+    use aptos_secure_storage::InMemoryStorage;
+    use aptos_types::validator_signer::ValidatorSigner;
+
+    #[test]
+    fn test_migrate_safety_storage() {
+        let signer = ValidatorSigner::from_int(0);
+        let mut source = test_utils::test_storage(&signer);
+        source
+            .set_safety_data(SafetyData::new(2, 10, 0, 0, None, 0))
+            .unwrap();
+
+        let mut target = Storage::from(InMemoryStorage::new());
+        let safety_data = migrate_safety_storage(source.internal_store(), &mut target).unwrap();
+        assert_eq!(safety_data.last_voted_round, 10);
+        // Migrating again (e.g., after a failure) is fine
+        migrate_safety_storage(source.internal_store(), &mut target).unwrap();
+
+        let mut target = PersistentSafetyStorage::new(target, false);
+        assert_eq!(target.author().unwrap(), signer.author());
+        assert_eq!(
+            target.default_consensus_sk().unwrap().public_key(),
+            signer.public_key()
+        );
+        assert_eq!(target.waypoint().unwrap(), source.waypoint().unwrap());
+        assert_eq!(target.safety_data().unwrap(), safety_data);
+
+        // Once the validator has voted using the target, it can't be rolled back
+        target
+            .set_safety_data(SafetyData::new(2, 11, 0, 0, None, 0))
+            .unwrap();
+        migrate_safety_storage(source.internal_store(), target.internal_store()).unwrap_err();
+        assert_eq!(target.safety_data().unwrap().last_voted_round, 11);
+    }
+}
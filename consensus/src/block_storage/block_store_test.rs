@@ -19,15 +19,18 @@ use aptos_consensus_types::{
         Block,
     },
     common::{Author, Payload},
+    pipelined_block::PipelinedBlock,
+    quorum_cert::QuorumCert,
     vote::Vote,
     vote_data::VoteData,
+    wrapped_ledger_info::WrappedLedgerInfo,
 };
 use aptos_crypto::{HashValue, PrivateKey};
 use aptos_types::{
     validator_signer::ValidatorSigner, validator_verifier::random_validator_verifier,
 };
 use proptest::prelude::*;
-use std::{cmp::min, collections::HashSet};
+use std::{cmp::min, collections::HashSet, sync::Arc};
 
 #[tokio::test]
 async fn test_highest_block_and_quorum_cert() {
@@ -485,3 +488,79 @@ async fn test_need_sync_for_ledger_info() {
     let not_too_far = create_ledger_info(round_not_too_far);
     assert!(!block_store.need_sync_for_ledger_info(&not_too_far));
 }
+
+/// Builds the tree of the peers, genesis <- b1 <- b2 <- b3, and returns its blocks along with the
+/// highest quorum cert (certifying b3) and the highest commit cert (committing b1)
+async fn build_tree_to_catch_up() -> (Vec<Arc<PipelinedBlock>>, QuorumCert, WrappedLedgerInfo) {
+    let mut inserter = TreeInserter::default();
+    let genesis = inserter.block_store().ordered_root();
+    let b1 = inserter
+        .insert_block_with_qc(certificate_for_genesis(), &genesis, 1)
+        .await;
+    let b2 = inserter.insert_block(&b1, 2, None).await;
+    let b3 = inserter.insert_block(&b2, 3, None).await;
+    let highest_quorum_cert = inserter.create_qc_for_block(&b3, Some(b1.block_info()));
+    let highest_commit_cert = highest_quorum_cert.into_wrapped_ledger_info();
+    (
+        vec![genesis, b1, b2, b3],
+        highest_quorum_cert,
+        highest_commit_cert,
+    )
+}
+
+/// The blocks as retrieved from the peers, from the highest certified block down to the root
+fn retrieved_blocks(blocks: &[&Arc<PipelinedBlock>]) -> Vec<Block> {
+    blocks.iter().map(|block| block.block().clone()).collect()
+}
+
+#[tokio::test]
+async fn test_catch_up_from_peers() {
+    let (blocks, highest_quorum_cert, highest_commit_cert) = build_tree_to_catch_up().await;
+    let (genesis, b1, b2, b3) = (&blocks[0], &blocks[1], &blocks[2], &blocks[3]);
+
+    // A node that is still at genesis
+    let block_store = build_default_empty_tree();
+    assert_eq!(block_store.ordered_root().id(), genesis.id());
+
+    // The node catches up if it's at most the given number of rounds behind
+    assert!(!block_store.should_catch_up(&highest_quorum_cert, &highest_commit_cert, 0));
+    assert!(!block_store.should_catch_up(&highest_quorum_cert, &highest_commit_cert, 2));
+    assert!(block_store.should_catch_up(&highest_quorum_cert, &highest_commit_cert, 3));
+
+    block_store
+        .insert_catch_up_blocks(genesis.id(), retrieved_blocks(&[b3, b2, b1, genesis]))
+        .await
+        .unwrap();
+    for block in [b1, b2, b3] {
+        assert!(block_store.block_exists(block.id()));
+    }
+    assert_eq!(block_store.highest_certified_block().id(), b2.id());
+
+    // The node no longer needs to catch up once the committed block is in its tree
+    assert!(!block_store.should_catch_up(&highest_quorum_cert, &highest_commit_cert, 3));
+}
+
+#[tokio::test]
+async fn test_catch_up_from_peers_inserts_nothing_on_failure() {
+    let (blocks, _, _) = build_tree_to_catch_up().await;
+    let (genesis, b1, b2, b3) = (&blocks[0], &blocks[1], &blocks[2], &blocks[3]);
+    let block_store = build_default_empty_tree();
+
+    // The blocks don't reach down to the ordered root
+    assert!(block_store
+        .insert_catch_up_blocks(genesis.id(), retrieved_blocks(&[b3, b2, b1]))
+        .await
+        .is_err());
+
+    // A block is missing from the middle of the chain
+    assert!(block_store
+        .insert_catch_up_blocks(genesis.id(), retrieved_blocks(&[b3, b1, genesis]))
+        .await
+        .is_err());
+
+    // Nothing was inserted, so the node falls back to fast forward sync from its current tree
+    for block in [b1, b2, b3] {
+        assert!(!block_store.block_exists(block.id()));
+    }
+    assert_eq!(block_store.ordered_root().id(), genesis.id());
+}
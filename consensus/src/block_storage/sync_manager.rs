@@ -11,8 +11,9 @@ use crate::{
     counters::{
         BLOCKS_FETCHED_FROM_NETWORK_IN_BLOCK_RETRIEVER,
         BLOCKS_FETCHED_FROM_NETWORK_WHILE_FAST_FORWARD_SYNC,
-        BLOCKS_FETCHED_FROM_NETWORK_WHILE_INSERTING_QUORUM_CERT, LATE_EXECUTION_WITH_ORDER_VOTE_QC,
-        SUCCESSFUL_EXECUTED_WITH_ORDER_VOTE_QC, SUCCESSFUL_EXECUTED_WITH_REGULAR_QC,
+        BLOCKS_FETCHED_FROM_NETWORK_WHILE_INSERTING_QUORUM_CERT, CONSENSUS_CATCH_UP_COUNT,
+        LATE_EXECUTION_WITH_ORDER_VOTE_QC, SUCCESSFUL_EXECUTED_WITH_ORDER_VOTE_QC,
+        SUCCESSFUL_EXECUTED_WITH_REGULAR_QC,
     },
     epoch_manager::LivenessStorageData,
     logging::{LogEvent, LogSchema},
@@ -126,12 +127,13 @@ impl BlockStore {
         // highest_quorum_cert.certified_block().id() into the block store and storage
         // (3) insert the quorum cert for all the above blocks into the block store and storage
         // (4) executes all the blocks that are ordered while inserting the above quorum certs
-        self.sync_to_highest_quorum_cert(
-            sync_info.highest_quorum_cert().clone(),
-            sync_info.highest_commit_cert().clone(),
-            &mut retriever,
-        )
-        .await?;
+        let caught_up = self
+            .sync_to_highest_quorum_cert(
+                sync_info.highest_quorum_cert().clone(),
+                sync_info.highest_commit_cert().clone(),
+                &mut retriever,
+            )
+            .await?;
 
         // The insert_ordered_cert(order_cert) function call expects that order_cert.commit_info().id() block
         // is already stored in block_store. So, we first call insert_quorum_cert(highest_quorum_cert).
@@ -165,6 +167,16 @@ impl BlockStore {
         if let Some(tc) = sync_info.highest_2chain_timeout_cert() {
             self.insert_2chain_timeout_certificate(Arc::new(tc.clone()))?;
         }
+
+        // If we caught up on the ordered blocks above, forward the commit cert to the pipeline
+        // now that the committed block is ordered, so it doesn't need to wait for commit votes.
+        if caught_up {
+            self.sync_to_highest_commit_cert(
+                sync_info.highest_commit_cert().ledger_info(),
+                retriever.network.clone(),
+            )
+            .await;
+        }
         Ok(())
     }
 
@@ -272,14 +284,16 @@ impl BlockStore {
     /// 2. We persist the gap blocks to storage before start sync to ensure we could restart if we
     /// crash in the middle of the sync.
     /// 3. We prune the old tree and replace with a new tree built with the 3-chain.
+    ///
+    /// Returns true if we caught up from peers instead, see `catch_up_from_peers`.
     async fn sync_to_highest_quorum_cert(
         &self,
         highest_quorum_cert: QuorumCert,
         highest_commit_cert: WrappedLedgerInfo,
         retriever: &mut BlockRetriever,
-    ) -> anyhow::Result<()> {
+    ) -> anyhow::Result<bool> {
         if !self.need_sync_for_ledger_info(highest_commit_cert.ledger_info()) {
-            return Ok(());
+            return Ok(false);
         }
 
         if let Some(pre_commit_status) = self.pre_commit_status() {
@@ -288,6 +302,16 @@ impl BlockStore {
             }
         }
 
+        if self
+            .catch_up_from_peers(&highest_quorum_cert, &highest_commit_cert, retriever)
+            .await
+        {
+            CONSENSUS_CATCH_UP_COUNT
+                .with_label_values(&["success"])
+                .inc();
+            return Ok(true);
+        }
+
         let (root, root_metadata, blocks, quorum_certs) = Self::fast_forward_sync(
             &highest_quorum_cert,
             &highest_commit_cert,
@@ -317,6 +341,123 @@ impl BlockStore {
                 ))
                 .await;
         }
+        Ok(false)
+    }
+
+    /// Catches up with the peers if we're only a few rounds behind (e.g., after a brief outage),
+    /// by fetching the blocks ordered since our ordered root along with their quorum certs in a
+    /// single retrieval, and inserting them into the tree. The blocks are then executed and
+    /// committed through the pipeline as usual, without resetting the tree and state syncing.
+    ///
+    /// Returns false if we're too far behind, or the blocks can't be fetched or inserted, in
+    /// which case the caller falls back to fast forward sync.
+    async fn catch_up_from_peers(
+        &self,
+        highest_quorum_cert: &QuorumCert,
+        highest_commit_cert: &WrappedLedgerInfo,
+        retriever: &mut BlockRetriever,
+    ) -> bool {
+        if !self.should_catch_up(
+            highest_quorum_cert,
+            highest_commit_cert,
+            retriever.max_catch_up_rounds,
+        ) {
+            return false;
+        }
+
+        let ordered_root = self.ordered_root();
+        let target_round = highest_quorum_cert.certified_block().round();
+
+        info!(
+            LogSchema::new(LogEvent::CatchUp).remote_peer(retriever.preferred_peer),
+            "Catching up from ordered root {} to quorum cert: {}",
+            ordered_root.block(),
+            highest_quorum_cert,
+        );
+        // The chain from the highest certified block back to (and including) our ordered root.
+        // Rounds may have been skipped, so there can be fewer blocks than rounds.
+        let blocks = match retriever
+            .retrieve_blocks_in_range(
+                highest_quorum_cert.certified_block().id(),
+                target_round - ordered_root.round() + 1,
+                TargetBlockRetrieval::TargetBlockId(ordered_root.id()),
+                highest_quorum_cert
+                    .ledger_info()
+                    .get_voters(&retriever.validator_addresses()),
+            )
+            .await
+        {
+            Ok(blocks) => blocks,
+            Err(error) => {
+                warn!(
+                    error = ?error,
+                    "Failed to fetch the blocks to catch up, falling back to fast forward sync"
+                );
+                CONSENSUS_CATCH_UP_COUNT
+                    .with_label_values(&["fallback"])
+                    .inc();
+                return false;
+            },
+        };
+
+        if let Err(error) = self.insert_catch_up_blocks(ordered_root.id(), blocks).await {
+            warn!(
+                error = ?error,
+                "Failed to insert the blocks to catch up, falling back to fast forward sync"
+            );
+            CONSENSUS_CATCH_UP_COUNT
+                .with_label_values(&["fallback"])
+                .inc();
+            return false;
+        }
+        true
+    }
+
+    /// Whether we're behind the highest quorum cert by at most `max_catch_up_rounds` (and not
+    /// at all behind the highest commit cert's block), so that we can catch up from peers.
+    pub(crate) fn should_catch_up(
+        &self,
+        highest_quorum_cert: &QuorumCert,
+        highest_commit_cert: &WrappedLedgerInfo,
+        max_catch_up_rounds: u64,
+    ) -> bool {
+        let ordered_root_round = self.ordered_root().round();
+        let target_round = highest_quorum_cert.certified_block().round();
+        max_catch_up_rounds != 0
+            && !self.block_exists(highest_commit_cert.commit_info().id())
+            && target_round > ordered_root_round
+            && target_round - ordered_root_round <= max_catch_up_rounds
+    }
+
+    /// Inserts the blocks fetched to catch up, which are ordered from the highest certified block
+    /// back to (and including) our ordered root. Nothing is inserted unless the blocks form a
+    /// chain down to the ordered root. Should inserting a block still fail, the blocks inserted
+    /// before it are certified descendants of the ordered root, so the tree stays consistent, and
+    /// the fast forward sync the caller falls back to rebuilds it anyway.
+    pub(crate) async fn insert_catch_up_blocks(
+        &self,
+        ordered_root_id: HashValue,
+        blocks: Vec<Block>,
+    ) -> anyhow::Result<()> {
+        ensure!(
+            blocks.last().map(|block| block.id()) == Some(ordered_root_id),
+            "The blocks to catch up don't end at the ordered root {}",
+            ordered_root_id
+        );
+        for pair in blocks.windows(2) {
+            ensure!(
+                pair[0].parent_id() == pair[1].id(),
+                "Block {} to catch up doesn't extend block {}",
+                pair[0].id(),
+                pair[1].id()
+            );
+        }
+
+        // Insert the qc <- block pairs, parents first, skipping our ordered root
+        for block in blocks.into_iter().rev().skip(1) {
+            self.insert_single_quorum_cert(block.quorum_cert().clone())?;
+            self.insert_block(block).await?;
+        }
         Ok(())
     }
 
@@ -607,6 +748,8 @@ pub struct BlockRetriever {
     preferred_peer: Author,
     validator_addresses: Vec<AccountAddress>,
     max_blocks_to_request: u64,
+    /// See `ConsensusConfig::max_catch_up_rounds`
+    max_catch_up_rounds: u64,
     pending_blocks: Arc<Mutex<PendingBlocks>>,
 }
 
@@ -643,10 +786,16 @@ impl BlockRetriever {
             preferred_peer,
             validator_addresses,
             max_blocks_to_request,
+            max_catch_up_rounds: 0,
             pending_blocks,
         }
     }
 
+    pub fn with_max_catch_up_rounds(mut self, max_catch_up_rounds: u64) -> Self {
+        self.max_catch_up_rounds = max_catch_up_rounds;
+        self
+    }
+
     pub fn validator_addresses(&self) -> Vec<AccountAddress> {
         self.validator_addresses.clone()
     }
@@ -42,7 +42,7 @@ use aptos_consensus_types::{
     wrapped_ledger_info::WrappedLedgerInfo,
 };
 use aptos_crypto::{bls12381, Genesis};
-use aptos_event_notifications::{DbBackedOnChainConfig, ReconfigNotificationListener};
+use aptos_event_notifications::{InMemoryOnChainConfig, ReconfigNotificationListener};
 use aptos_infallible::Mutex;
 use aptos_logger::{debug, error, info, warn};
 use aptos_network::{
@@ -100,7 +100,7 @@ impl ConsensusObserver {
         db_reader: Arc<dyn DbReader>,
         execution_client: Arc<dyn TExecutionClient>,
         state_sync_notification_sender: UnboundedSender<StateSyncNotification>,
-        reconfig_events: Option<ReconfigNotificationListener<DbBackedOnChainConfig>>,
+        reconfig_events: Option<ReconfigNotificationListener<InMemoryOnChainConfig>>,
         consensus_publisher: Option<Arc<ConsensusPublisher>>,
         time_service: TimeService,
     ) -> Self {
@@ -12,7 +12,7 @@ use crate::{
     },
 };
 use aptos_config::config::NodeConfig;
-use aptos_event_notifications::{DbBackedOnChainConfig, ReconfigNotificationListener};
+use aptos_event_notifications::{InMemoryOnChainConfig, ReconfigNotificationListener};
 use aptos_infallible::Mutex;
 use aptos_logger::{error, info, warn};
 use aptos_types::{
@@ -43,13 +43,13 @@ pub struct ObserverEpochState {
     quorum_store_enabled: bool,
 
     // The reconfiguration event listener to refresh on-chain configs
-    reconfig_events: ReconfigNotificationListener<DbBackedOnChainConfig>,
+    reconfig_events: ReconfigNotificationListener<InMemoryOnChainConfig>,
 }
 
 impl ObserverEpochState {
     pub fn new(
         node_config: NodeConfig,
-        reconfig_events: ReconfigNotificationListener<DbBackedOnChainConfig>,
+        reconfig_events: ReconfigNotificationListener<InMemoryOnChainConfig>,
         consensus_publisher: Option<Arc<ConsensusPublisher>>,
     ) -> Self {
         Self {
@@ -130,7 +130,7 @@ impl ObserverEpochState {
 /// A simple helper function that extracts the on-chain configs from the reconfig events
 async fn extract_on_chain_configs(
     node_config: &NodeConfig,
-    reconfig_events: &mut ReconfigNotificationListener<DbBackedOnChainConfig>,
+    reconfig_events: &mut ReconfigNotificationListener<InMemoryOnChainConfig>,
 ) -> (
     Arc<EpochState>,
     OnChainConsensusConfig,
@@ -251,8 +251,8 @@ mod test {
 
     /// Creates and returns a reconfig notifier and listener
     fn create_reconfig_notifier_and_listener() -> (
-        aptos_channel::Sender<(), ReconfigNotification<DbBackedOnChainConfig>>,
-        ReconfigNotificationListener<DbBackedOnChainConfig>,
+        aptos_channel::Sender<(), ReconfigNotification<InMemoryOnChainConfig>>,
+        ReconfigNotificationListener<InMemoryOnChainConfig>,
     ) {
         let (notification_sender, notification_receiver) =
             aptos_channel::new(QueueStyle::LIFO, 1, None);
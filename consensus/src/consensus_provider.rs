@@ -27,7 +27,7 @@ use aptos_bounded_executor::BoundedExecutor;
 use aptos_channels::aptos_channel::Receiver;
 use aptos_config::config::NodeConfig;
 use aptos_consensus_notifications::ConsensusNotificationSender;
-use aptos_event_notifications::{DbBackedOnChainConfig, ReconfigNotificationListener};
+use aptos_event_notifications::{InMemoryOnChainConfig, ReconfigNotificationListener};
 use aptos_executor::block_executor::BlockExecutor;
 use aptos_logger::prelude::*;
 use aptos_mempool::QuorumStoreRequest;
@@ -50,7 +50,7 @@ pub fn start_consensus(
     state_sync_notifier: Arc<dyn ConsensusNotificationSender>,
     consensus_to_mempool_sender: mpsc::Sender<QuorumStoreRequest>,
     aptos_db: DbReaderWriter,
-    reconfig_events: ReconfigNotificationListener<DbBackedOnChainConfig>,
+    reconfig_events: ReconfigNotificationListener<InMemoryOnChainConfig>,
     vtxn_pool: VTxnPoolState,
     consensus_publisher: Option<Arc<ConsensusPublisher>>,
 ) -> (Runtime, Arc<StorageWriteProxy>, Arc<QuorumStoreDB>) {
@@ -135,7 +135,7 @@ pub fn start_consensus_observer(
     state_sync_notifier: Arc<dyn ConsensusNotificationSender>,
     consensus_to_mempool_sender: mpsc::Sender<QuorumStoreRequest>,
     aptos_db: DbReaderWriter,
-    reconfig_events: Option<ReconfigNotificationListener<DbBackedOnChainConfig>>,
+    reconfig_events: Option<ReconfigNotificationListener<InMemoryOnChainConfig>>,
 ) {
     // Create the (dummy) consensus network client
     let (self_sender, _self_receiver) =
@@ -796,6 +796,17 @@ pub static BLOCKS_FETCHED_FROM_NETWORK_WHILE_FAST_FORWARD_SYNC: Lazy<IntCounter>
         .unwrap()
     });
 
+/// Number of times a lagging node caught up by fetching the missing ordered blocks from its
+/// peers (result = "success"), or had to fall back to fast forward sync (result = "fallback")
+pub static CONSENSUS_CATCH_UP_COUNT: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "aptos_consensus_catch_up_count",
+        "Number of times a lagging node caught up by fetching ordered blocks from its peers",
+        &["result"]
+    )
+    .unwrap()
+});
+
 //////////////////////
 // RECONFIGURATION COUNTERS
 //////////////////////
@@ -931,6 +942,14 @@ pub static PREPARE_BLOCK_SIG_VERIFICATION_TIME: Lazy<DurationHistogram> = Lazy::
     )
 });
 
+pub static PREPARE_BLOCK_SIG_VERIFICATION_CACHE_HITS: Lazy<IntCounter> = Lazy::new(|| {
+    register_int_counter!(
+        "aptos_consensus_prepare_block_sig_verification_cache_hits",
+        "Count of the prepared transactions whose signatures were already verified on this node"
+    )
+    .unwrap()
+});
+
 pub static PREPARE_BLOCK_WAIT_TIME: Lazy<DurationHistogram> = Lazy::new(|| {
     DurationHistogram::new(
         register_histogram!(
@@ -1268,6 +1287,45 @@ pub static REJECTED_PROPOSAL_DENY_TXN_COUNT: Lazy<IntCounter> = Lazy::new(|| {
     .unwrap()
 });
 
+/// Count of the number of rejected proposals due to their timestamp drifting too far from the
+/// local clock
+pub static REJECTED_PROPOSAL_TIMESTAMP_DRIFT_COUNT: Lazy<IntCounter> = Lazy::new(|| {
+    register_int_counter!(
+        "aptos_consensus_rejected_proposal_timestamp_drift_count",
+        "Count of the number of rejected proposals due to their timestamp drifting too far"
+    )
+    .unwrap()
+});
+
+/// The clock skew of each proposer, i.e., how far ahead of the local clock the timestamp of its
+/// last proposal was when received (in ms)
+pub static PROPOSER_CLOCK_SKEW_MS: Lazy<IntGaugeVec> = Lazy::new(|| {
+    register_int_gauge_vec!(
+        "aptos_consensus_proposer_clock_skew_ms",
+        "Clock skew of the last proposal of each proposer, vs the local clock",
+        &["proposer"]
+    )
+    .unwrap()
+});
+
+/// How far ahead of the other validators the local clock is estimated to be (in ms)
+pub static LOCAL_CLOCK_SKEW_MS: Lazy<IntGauge> = Lazy::new(|| {
+    register_int_gauge!(
+        "aptos_consensus_local_clock_skew_ms",
+        "Estimated clock skew of the local clock vs the median of the other validators"
+    )
+    .unwrap()
+});
+
+/// Set to 1 when the local clock skew exceeds the alert threshold
+pub static LOCAL_CLOCK_SKEW_ALERT: Lazy<IntGauge> = Lazy::new(|| {
+    register_int_gauge!(
+        "aptos_consensus_local_clock_skew_alert",
+        "Whether the local clock skew exceeds the configured alert threshold"
+    )
+    .unwrap()
+});
+
 /// Histogram for the number of txns to be executed in a block.
 pub static MAX_TXNS_FROM_BLOCK_TO_EXECUTE: Lazy<Histogram> = Lazy::new(|| {
     register_histogram!(
@@ -1278,6 +1336,15 @@ pub static MAX_TXNS_FROM_BLOCK_TO_EXECUTE: Lazy<Histogram> = Lazy::new(|| {
     .unwrap()
 });
 
+pub static NUM_DEFERRED_TXNS_CARRIED_OVER: Lazy<Histogram> = Lazy::new(|| {
+    register_histogram!(
+        "aptos_consensus_num_deferred_txns_carried_over",
+        "Histogram for the number of deferred txns carried over into a proposed block payload.",
+        exponential_buckets(/*start=*/ 1.5, /*factor=*/ 1.5, /*count=*/ 25).unwrap(),
+    )
+    .unwrap()
+});
+
 /// Count of the number of `DKG` validator transactions received while the feature is disabled.
 pub static UNEXPECTED_DKG_VTXN_COUNT: Lazy<IntCounter> = Lazy::new(|| {
     register_int_counter!(
@@ -204,7 +204,8 @@ impl<P: OnChainConfigProvider> EpochManager<P> {
         let config = node_config.consensus.clone();
         let dag_config = node_config.dag_consensus.clone();
         let sr_config = &node_config.consensus.safety_rules;
-        let safety_rules_manager = SafetyRulesManager::new(sr_config);
+        let safety_rules_manager = SafetyRulesManager::new(sr_config)
+            .unwrap_or_else(|error| panic!("Unable to create the safety rules manager: {}", error));
         let key_storage = safety_rules_manager::storage(sr_config);
         let consensus_txn_filter_config = node_config.transaction_filters.consensus_filter.clone();
         let quorum_store_txn_filter_config =
@@ -937,6 +938,7 @@ impl<P: OnChainConfigProvider> EpochManager<P> {
                 .quorum_store
                 .allow_batches_without_pos_in_proposal,
             opt_qs_payload_param_provider,
+            onchain_execution_config.carry_over_deferred_txns(),
         );
         let (round_manager_tx, round_manager_rx) = aptos_channel::new(
             QueueStyle::KLAST,
@@ -0,0 +1,76 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::counters::{LOCAL_CLOCK_SKEW_ALERT, LOCAL_CLOCK_SKEW_MS, PROPOSER_CLOCK_SKEW_MS};
+use aptos_consensus_types::common::Author;
+use aptos_logger::prelude::*;
+use aptos_types::epoch_state::EpochState;
+use std::{collections::HashMap, sync::Arc, time::Duration};
+
+/// Tracks the clock skew of proposers, i.e., how far ahead of the local clock the timestamps of
+/// their proposals are when received, to detect when the local clock drifts away from the rest
+/// of the validators.
+pub struct ClockSkewMonitor {
+    epoch_state: Arc<EpochState>,
+    /// The local clock skew above which operators are alerted.
+    alert_threshold_ms: u64,
+    /// The skew of the last proposal received from each proposer, in milliseconds.
+    proposer_skews_ms: HashMap<Author, i64>,
+}
+
+impl ClockSkewMonitor {
+    pub fn new(epoch_state: Arc<EpochState>, alert_threshold_ms: u64) -> Self {
+        Self {
+            epoch_state,
+            alert_threshold_ms,
+            proposer_skews_ms: HashMap::new(),
+        }
+    }
+
+    /// Records the skew of a proposal from `proposer`, with the given timestamp, received at
+    /// local time `now`. Returns the skew, in milliseconds.
+    pub fn observe_proposal(
+        &mut self,
+        proposer: Author,
+        timestamp_usecs: u64,
+        now: Duration,
+    ) -> i64 {
+        let skew_ms = (timestamp_usecs / 1000) as i64 - now.as_millis() as i64;
+        PROPOSER_CLOCK_SKEW_MS
+            .with_label_values(&[&proposer.to_hex()])
+            .set(skew_ms);
+        self.proposer_skews_ms.insert(proposer, skew_ms);
+
+        if let Some(local_skew_ms) = self.local_clock_skew_ms() {
+            LOCAL_CLOCK_SKEW_MS.set(local_skew_ms);
+            let exceeded = local_skew_ms.unsigned_abs() > self.alert_threshold_ms;
+            LOCAL_CLOCK_SKEW_ALERT.set(exceeded as i64);
+            if exceeded {
+                sample!(
+                    SampleRate::Duration(Duration::from_secs(30)),
+                    error!(
+                        local_clock_skew_ms = local_skew_ms,
+                        threshold_ms = self.alert_threshold_ms,
+                        "[sampled] The local clock is skewed vs the other validators, \
+                        check that the clock of the node is synchronized (e.g., via NTP)"
+                    )
+                );
+            }
+        }
+        skew_ms
+    }
+
+    /// Estimates how far ahead of the other validators the local clock is, as the median of the
+    /// proposer skews (negated), once proposals have been received from validators holding a
+    /// quorum of the voting power. Taking the median means a minority of proposers with skewed
+    /// clocks don't affect the estimate.
+    pub fn local_clock_skew_ms(&self) -> Option<i64> {
+        self.epoch_state
+            .verifier
+            .check_voting_power(self.proposer_skews_ms.keys(), true)
+            .ok()?;
+        let mut skews_ms: Vec<_> = self.proposer_skews_ms.values().copied().collect();
+        skews_ms.sort_unstable();
+        Some(-skews_ms[skews_ms.len() / 2])
+    }
+}
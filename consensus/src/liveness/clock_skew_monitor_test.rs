@@ -0,0 +1,35 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::liveness::clock_skew_monitor::ClockSkewMonitor;
+use aptos_types::{epoch_state::EpochState, validator_verifier::random_validator_verifier};
+use std::{sync::Arc, time::Duration};
+
+#[test]
+fn test_local_clock_skew() {
+    let (signers, verifier) = random_validator_verifier(4, None, false);
+    let mut monitor = ClockSkewMonitor::new(Arc::new(EpochState::new(1, verifier)), 500);
+    let now = Duration::from_secs(1_000);
+    let now_usecs = now.as_micros() as u64;
+
+    // The proposal of a proposer whose clock is 2s ahead
+    assert_eq!(
+        monitor.observe_proposal(signers[0].author(), now_usecs + 2_000_000, now),
+        2_000
+    );
+    // Not enough proposers to estimate the local clock skew yet
+    assert_eq!(monitor.local_clock_skew_ms(), None);
+
+    // The other proposers' clocks are 1s ahead of ours (or 50ms, within network delays), so
+    // ours is 1s behind
+    monitor.observe_proposal(signers[1].author(), now_usecs + 1_000_000, now);
+    monitor.observe_proposal(signers[2].author(), now_usecs + 1_000_000, now);
+    assert_eq!(monitor.local_clock_skew_ms(), Some(-1_000));
+    monitor.observe_proposal(signers[3].author(), now_usecs + 50_000, now);
+    assert_eq!(monitor.local_clock_skew_ms(), Some(-1_000));
+
+    // Once our clock is fixed, the skew is within network delays
+    monitor.observe_proposal(signers[1].author(), now_usecs - 10_000, now);
+    monitor.observe_proposal(signers[2].author(), now_usecs - 20_000, now);
+    assert_eq!(monitor.local_clock_skew_ms(), Some(-50));
+}
@@ -3,6 +3,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 pub(crate) mod cached_proposer_election;
+pub(crate) mod clock_skew_monitor;
 pub(crate) mod leader_reputation;
 pub(crate) mod proposal_generator;
 pub(crate) mod proposal_status_tracker;
@@ -15,6 +16,8 @@ pub(crate) mod unequivocal_proposer_election;
 #[cfg(test)]
 mod cached_proposer_election_test;
 #[cfg(test)]
+mod clock_skew_monitor_test;
+#[cfg(test)]
 mod leader_reputation_test;
 #[cfg(test)]
 mod rotating_proposer_test;
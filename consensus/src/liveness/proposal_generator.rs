@@ -9,12 +9,15 @@ use crate::{
     block_storage::BlockReader,
     counters::{
         CHAIN_HEALTH_BACKOFF_TRIGGERED, EXECUTION_BACKPRESSURE_ON_PROPOSAL_TRIGGERED,
-        PIPELINE_BACKPRESSURE_ON_PROPOSAL_TRIGGERED, PROPOSER_DELAY_PROPOSAL,
-        PROPOSER_ESTIMATED_CALIBRATED_BLOCK_GAS, PROPOSER_ESTIMATED_CALIBRATED_BLOCK_TXNS,
-        PROPOSER_MAX_BLOCK_TXNS_AFTER_FILTERING, PROPOSER_MAX_BLOCK_TXNS_TO_EXECUTE,
-        PROPOSER_PENDING_BLOCKS_COUNT, PROPOSER_PENDING_BLOCKS_FILL_FRACTION,
+        NUM_DEFERRED_TXNS_CARRIED_OVER, PIPELINE_BACKPRESSURE_ON_PROPOSAL_TRIGGERED,
+        PROPOSER_DELAY_PROPOSAL, PROPOSER_ESTIMATED_CALIBRATED_BLOCK_GAS,
+        PROPOSER_ESTIMATED_CALIBRATED_BLOCK_TXNS, PROPOSER_MAX_BLOCK_TXNS_AFTER_FILTERING,
+        PROPOSER_MAX_BLOCK_TXNS_TO_EXECUTE, PROPOSER_PENDING_BLOCKS_COUNT,
+        PROPOSER_PENDING_BLOCKS_FILL_FRACTION,
     },
     payload_client::PayloadClient,
+    payload_manager::get_inline_transactions,
+    quorum_store::types::Batch,
     util::time_service::TimeService,
 };
 use anyhow::{bail, ensure, format_err, Context};
@@ -28,7 +31,7 @@ use aptos_consensus_types::{
     common::{Author, Payload, PayloadFilter, Round},
     opt_block_data::OptBlockData,
     payload_pull_params::{OptQSPayloadPullParams, PayloadPullParameters},
-    pipelined_block::ExecutionSummary,
+    pipelined_block::{ExecutionSummary, PipelinedBlock},
     quorum_cert::QuorumCert,
     utils::PayloadTxnsSize,
 };
@@ -36,7 +39,8 @@ use aptos_crypto::{hash::CryptoHash, HashValue};
 use aptos_infallible::Mutex;
 use aptos_logger::{error, sample, sample::SampleRate, warn};
 use aptos_types::{
-    block_info::BlockInfo, on_chain_config::ValidatorTxnConfig, validator_txn::ValidatorTransaction,
+    block_info::BlockInfo, on_chain_config::ValidatorTxnConfig, quorum_store::BatchId,
+    transaction::SignedTransaction, validator_txn::ValidatorTransaction,
 };
 use aptos_validator_transaction_pool as vtxn_pool;
 use itertools::Itertools;
@@ -50,6 +54,10 @@ use std::{
 #[path = "proposal_generator_test.rs"]
 mod proposal_generator_test;
 
+/// Nonce of the inline batch carrying over deferred txns, which keeps its id apart from the ids of
+/// the batches the author creates in quorum store.
+const CARRIED_OVER_BATCH_NONCE: u64 = u64::MAX;
+
 #[derive(Clone)]
 pub struct ChainHealthBackoffConfig {
     backoffs: BTreeMap<usize, ChainHealthBackoffValues>,
@@ -407,6 +415,8 @@ pub struct ProposalGenerator {
 
     allow_batches_without_pos_in_proposal: bool,
     opt_qs_payload_param_provider: Arc<dyn TOptQSPullParamsProvider>,
+    // Whether the txns deferred by the last executed pending block are carried over inline.
+    carry_over_deferred_txns: bool,
 }
 
 impl ProposalGenerator {
@@ -429,6 +439,7 @@ impl ProposalGenerator {
         vtxn_config: ValidatorTxnConfig,
         allow_batches_without_pos_in_proposal: bool,
         opt_qs_payload_param_provider: Arc<dyn TOptQSPullParamsProvider>,
+        carry_over_deferred_txns: bool,
     ) -> Self {
         Self {
             author,
@@ -449,6 +460,7 @@ impl ProposalGenerator {
             vtxn_config,
             allow_batches_without_pos_in_proposal,
             opt_qs_payload_param_provider,
+            carry_over_deferred_txns,
         }
     }
 
@@ -634,6 +646,25 @@ impl ProposalGenerator {
         PROPOSER_PENDING_BLOCKS_COUNT.set(pending_blocks.len() as i64);
         PROPOSER_PENDING_BLOCKS_FILL_FRACTION.set(max_fill_fraction as f64);
 
+        // The carried over txns are proposed as an inline batch, so the rest of the payload is
+        // pulled with the remaining room.
+        let carried_over_txns = if self.carry_over_deferred_txns && self.quorum_store_enabled {
+            Self::deferred_txns_to_carry_over(
+                &pending_blocks,
+                self.max_inline_txns.minimum(max_block_txns),
+            )
+        } else {
+            vec![]
+        };
+        let carried_over_size = PayloadTxnsSize::new(
+            carried_over_txns.len() as u64,
+            carried_over_txns
+                .iter()
+                .map(|txn| txn.txn_bytes_len() as u64)
+                .sum(),
+        );
+        NUM_DEFERRED_TXNS_CARRIED_OVER.observe(carried_over_txns.len() as f64);
+
         let pending_validator_txn_hashes: HashSet<HashValue> = pending_blocks
             .iter()
             .filter_map(|block| block.validator_txns())
@@ -648,11 +679,13 @@ impl ProposalGenerator {
             .pull_payload(
                 PayloadPullParameters {
                     max_poll_time: self.quorum_store_poll_time.saturating_sub(proposal_delay),
-                    max_txns: max_block_txns,
-                    max_txns_after_filtering: max_block_txns_after_filtering,
+                    max_txns: max_block_txns.saturating_sub(carried_over_size),
+                    max_txns_after_filtering: max_block_txns_after_filtering
+                        .saturating_sub(carried_over_size.count()),
                     soft_max_txns_after_filtering: max_txns_from_block_to_execute
-                        .unwrap_or(max_block_txns_after_filtering),
-                    max_inline_txns: self.max_inline_txns,
+                        .unwrap_or(max_block_txns_after_filtering)
+                        .saturating_sub(carried_over_size.count()),
+                    max_inline_txns: self.max_inline_txns.saturating_sub(carried_over_size),
                     maybe_optqs_payload_pull_params,
                     user_txn_filter: payload_filter,
                     pending_ordering,
@@ -665,6 +698,22 @@ impl ProposalGenerator {
             .await
             .context("Fail to retrieve payload")?;
 
+        if !carried_over_txns.is_empty() {
+            let batch = Batch::new(
+                BatchId {
+                    id: round,
+                    nonce: CARRIED_OVER_BATCH_NONCE,
+                },
+                carried_over_txns,
+                self.block_store.commit_root().epoch(),
+                timestamp.as_micros() as u64,
+                self.author,
+                0,
+            );
+            let batch_info = batch.batch_info().clone();
+            payload = payload.with_inline_batch(batch_info, batch.into_transactions());
+        }
+
         if !payload.is_direct()
             && max_txns_from_block_to_execute.is_some()
             && max_txns_from_block_to_execute.is_some_and(|v| payload.len() as u64 > v)
@@ -730,6 +779,43 @@ impl ProposalGenerator {
         Ok(block)
     }
 
+    /// Returns the user txns that the most recently executed pending block deferred because it
+    /// reached the block limits, except for those a later pending block already has inline, up to
+    /// `max_txns`. Pending blocks go from the oldest uncommitted ancestor to the parent, followed
+    /// by the commit root.
+    fn deferred_txns_to_carry_over(
+        pending_blocks: &[Arc<PipelinedBlock>],
+        max_txns: PayloadTxnsSize,
+    ) -> Vec<SignedTransaction> {
+        let Some((commit_root, path)) = pending_blocks.split_last() else {
+            return vec![];
+        };
+        let mut included_later = HashSet::new();
+        for block in path.iter().rev().chain(std::iter::once(commit_root)) {
+            if let Some(futs) = block.pipeline_futs() {
+                if let Some(Ok((compute_result, _, _))) = futs.ledger_update_fut.peek() {
+                    let mut size = PayloadTxnsSize::zero();
+                    return compute_result
+                        .user_txns_to_retry()
+                        .filter(|txn| !included_later.contains(&txn.committed_hash()))
+                        .take_while(|txn| {
+                            size += PayloadTxnsSize::new(1, txn.txn_bytes_len() as u64);
+                            size.count() <= max_txns.count()
+                                && size.size_in_bytes() <= max_txns.size_in_bytes()
+                        })
+                        .cloned()
+                        .collect();
+                }
+            }
+            included_later.extend(
+                get_inline_transactions(block.block())
+                    .iter()
+                    .map(SignedTransaction::committed_hash),
+            );
+        }
+        vec![]
+    }
+
     async fn calculate_max_block_sizes(
         &self,
         voting_power_ratio: f64,
@@ -56,6 +56,7 @@ async fn test_proposal_generation_empty_tree() {
         ValidatorTxnConfig::default_disabled(),
         true,
         Arc::new(MockOptQSPayloadProvider {}),
+        false,
     );
     let proposer_election = Arc::new(UnequivocalProposerElection::new(Arc::new(
         RotatingProposer::new(vec![signer.author()], 1),
@@ -103,6 +104,7 @@ async fn test_proposal_generation_parent() {
         ValidatorTxnConfig::default_disabled(),
         true,
         Arc::new(MockOptQSPayloadProvider {}),
+        false,
     );
     let proposer_election = Arc::new(UnequivocalProposerElection::new(Arc::new(
         RotatingProposer::new(vec![inserter.signer().author()], 1),
@@ -180,6 +182,7 @@ async fn test_old_proposal_generation() {
         ValidatorTxnConfig::default_disabled(),
         true,
         Arc::new(MockOptQSPayloadProvider {}),
+        false,
     );
     let proposer_election = Arc::new(UnequivocalProposerElection::new(Arc::new(
         RotatingProposer::new(vec![inserter.signer().author()], 1),
@@ -222,6 +225,7 @@ async fn test_correct_failed_authors() {
         ValidatorTxnConfig::default_disabled(),
         true,
         Arc::new(MockOptQSPayloadProvider {}),
+        false,
     );
     let proposer_election = Arc::new(UnequivocalProposerElection::new(Arc::new(
         RotatingProposer::new(vec![author, peer1, peer2], 1),
@@ -229,6 +229,11 @@ impl RoundState {
         self.current_round_deadline
     }
 
+    /// Returns the current local time, from the time service the round deadlines are based on
+    pub fn current_timestamp(&self) -> Duration {
+        self.time_service.get_current_timestamp()
+    }
+
     /// In case the local timeout corresponds to the current round, reset the timeout and
     /// return true. Otherwise ignore and return false.
     pub fn process_local_timeout(&mut self, round: Round) -> bool {
@@ -21,6 +21,7 @@ pub struct LogSchema {
 #[derive(Serialize)]
 pub enum LogEvent {
     BroadcastOrderVote,
+    CatchUp,
     CommitViaBlock,
     CommitViaSync,
     IncrementalProofExpired,
@@ -532,7 +532,7 @@ impl TPayloadManager for QuorumStorePayloadManager {
 }
 
 /// Extracts and returns all inline transactions from the payload in the given block
-fn get_inline_transactions(block: &Block) -> Vec<SignedTransaction> {
+pub(crate) fn get_inline_transactions(block: &Block) -> Vec<SignedTransaction> {
     // If the block has no payload, return an empty vector
     let Some(payload) = block.payload() else {
         return vec![];
@@ -35,7 +35,10 @@ use aptos_types::{
     ledger_info::{LedgerInfo, LedgerInfoWithSignatures},
     randomness::Randomness,
     transaction::{
-        signature_verified_transaction::{SignatureVerifiedTransaction, TransactionProvider},
+        signature_verified_transaction::{
+            is_signature_verified, verify_user_txn_signatures, SignatureVerifiedTransaction,
+            TransactionProvider,
+        },
         AuxiliaryInfo, EphemeralAuxiliaryInfo, PersistedAuxiliaryInfo, SignedTransaction,
         Transaction,
     },
@@ -44,13 +47,13 @@ use aptos_types::{
 use futures::FutureExt;
 use move_core_types::account_address::AccountAddress;
 use once_cell::sync::Lazy;
-use rayon::prelude::*;
 use std::{
     future::Future,
     sync::Arc,
     time::{Duration, Instant},
 };
 use tokio::{select, sync::oneshot, task::AbortHandle};
+use tracing::{info_span, Span};
 
 static SIG_VERIFY_POOL: Lazy<Arc<rayon::ThreadPool>> = Lazy::new(|| {
     Arc::new(
@@ -170,6 +173,9 @@ struct Tracker {
     created_at: Instant,
     started_at: Option<Instant>,
     running_guard: Option<IntGaugeGuard>,
+    /// Covers the work of the stage, traced under the block id so that all stages of a
+    /// block (and the execution and storage work they trigger) form a single trace.
+    span: Span,
 }
 
 impl Tracker {
@@ -182,6 +188,7 @@ impl Tracker {
             created_at: Instant::now(),
             started_at: None,
             running_guard: None,
+            span: Span::none(),
         }
     }
 
@@ -190,6 +197,14 @@ impl Tracker {
         self.running_guard = Some(IntGaugeGuard::new(
             counters::OP_COUNTERS.gauge(&format!("{}_running", self.name)),
         ));
+        self.span = info_span!(
+            "pipeline",
+            otel.name = self.name,
+            block_id = %self.block_id,
+            epoch = self.epoch,
+            round = self.round,
+        );
+        aptos_logger::otel::set_trace_id(&self.span, self.block_id.as_ref());
         self.log_start();
     }
 
@@ -535,13 +550,14 @@ impl PipelineBuilder {
             }
         };
         let sig_verification_start = Instant::now();
+        let num_cached = input_txns
+            .iter()
+            .filter(|txn| is_signature_verified(&txn.committed_hash()))
+            .count();
+        counters::PREPARE_BLOCK_SIG_VERIFICATION_CACHE_HITS.inc_by(num_cached as u64);
         let sig_verified_txns: Vec<SignatureVerifiedTransaction> = SIG_VERIFY_POOL.install(|| {
             let num_txns = input_txns.len();
-            input_txns
-                .into_par_iter()
-                .with_min_len(optimal_min_len(num_txns, 32))
-                .map(|t| Transaction::UserTransaction(t).into())
-                .collect::<Vec<_>>()
+            verify_user_txn_signatures(input_txns, optimal_min_len(num_txns, 32))
         });
         counters::PREPARE_BLOCK_SIG_VERIFICATION_TIME
             .observe_duration(sig_verification_start.elapsed());
@@ -621,14 +637,17 @@ impl PipelineBuilder {
             .collect();
 
         let start = Instant::now();
+        let span = tracker.span.clone();
         tokio::task::spawn_blocking(move || {
-            executor
-                .execute_and_update_state(
-                    (block.id(), txns, auxiliary_info).into(),
-                    block.parent_id(),
-                    onchain_execution_config,
-                )
-                .map_err(anyhow::Error::from)
+            span.in_scope(|| {
+                executor
+                    .execute_and_update_state(
+                        (block.id(), txns, auxiliary_info).into(),
+                        block.parent_id(),
+                        onchain_execution_config,
+                    )
+                    .map_err(anyhow::Error::from)
+            })
         })
         .await
         .expect("spawn blocking failed")?;
@@ -650,10 +669,13 @@ impl PipelineBuilder {
 
         tracker.start_working();
         let timestamp = block.timestamp_usecs();
+        let span = tracker.span.clone();
         let result = tokio::task::spawn_blocking(move || {
-            executor
-                .ledger_update(block.id(), block.parent_id())
-                .map_err(anyhow::Error::from)
+            span.in_scope(|| {
+                executor
+                    .ledger_update(block.id(), block.parent_id())
+                    .map_err(anyhow::Error::from)
+            })
         })
         .await
         .expect("spawn blocking failed")?;
@@ -807,10 +829,13 @@ impl PipelineBuilder {
         }
 
         tracker.start_working();
+        let span = tracker.span.clone();
         tokio::task::spawn_blocking(move || {
-            executor
-                .pre_commit_block(block.id())
-                .map_err(anyhow::Error::from)
+            span.in_scope(|| {
+                executor
+                    .pre_commit_block(block.id())
+                    .map_err(anyhow::Error::from)
+            })
         })
         .await
         .expect("spawn blocking failed")?;
@@ -828,8 +853,22 @@ impl PipelineBuilder {
     ) -> TaskResult<CommitLedgerResult> {
         let mut tracker = Tracker::start_waiting("commit_ledger", &block);
         parent_block_commit_fut.await?;
-        pre_commit_fut.await?;
-        let ledger_info_with_sigs = commit_proof_fut.await?;
+        let ledger_info_with_sigs = select! {
+            result = pre_commit_fut.clone() => {
+                result?;
+                commit_proof_fut.await?
+            },
+            result = commit_proof_fut.clone() => {
+                let ledger_info_with_sigs = result?;
+                // The commit proof arrived before the block is pre-committed (e.g., on consensus
+                // observers), so hand it to the executor to commit right after the pre-commit.
+                if ledger_info_with_sigs.commit_info().id() == block.id() {
+                    Self::commit_ledger_ahead(&executor, &ledger_info_with_sigs, &block).await;
+                }
+                pre_commit_fut.await?;
+                ledger_info_with_sigs
+            },
+        };
 
         // it's committed as prefix
         if ledger_info_with_sigs.commit_info().id() != block.id() {
@@ -838,16 +877,43 @@ impl PipelineBuilder {
 
         tracker.start_working();
         let ledger_info_with_sigs_clone = ledger_info_with_sigs.clone();
+        let span = tracker.span.clone();
         tokio::task::spawn_blocking(move || {
-            executor
-                .commit_ledger(ledger_info_with_sigs_clone)
-                .map_err(anyhow::Error::from)
+            span.in_scope(|| {
+                executor
+                    .commit_ledger(ledger_info_with_sigs_clone)
+                    .map_err(anyhow::Error::from)
+            })
         })
         .await
         .expect("spawn blocking failed")?;
         Ok(Some(ledger_info_with_sigs))
     }
 
+    /// Hands the ledger info to the executor ahead of the pre-commit of its block. This is best
+    /// effort, `commit_ledger` still commits the ledger info after the pre-commit (which is a
+    /// no-op if the executor has already done so), so failures are only logged.
+    async fn commit_ledger_ahead(
+        executor: &Arc<dyn BlockExecutorTrait>,
+        ledger_info_with_sigs: &LedgerInfoWithSignatures,
+        block: &Block,
+    ) {
+        let executor = executor.clone();
+        let ledger_info_with_sigs = ledger_info_with_sigs.clone();
+        let result = tokio::task::spawn_blocking(move || {
+            executor.commit_ledger_ahead(ledger_info_with_sigs)
+        })
+        .await
+        .expect("spawn blocking failed");
+        if let Err(e) = result {
+            warn!(
+                "[Pipeline] Failed to commit ledger ahead for block {}: {:?}",
+                block.id(),
+                e
+            );
+        }
+    }
+
     /// Precondition: 1. commit ledger finishes, 2. parent block's phase finishes 3. post pre commit finishes
     /// What it does: Update counters for the block, and notify block tree about the commit
     async fn post_commit_ledger(
@@ -14,7 +14,7 @@ use crate::{
     },
 };
 use anyhow::ensure;
-use aptos_config::config::BatchTransactionFilterConfig;
+use aptos_config::config::{reloadable_config, BatchTransactionFilterConfig};
 use aptos_consensus_types::payload::TDataInfo;
 use aptos_logger::prelude::*;
 use aptos_short_hex_str::AsShortHexStr;
@@ -187,6 +187,28 @@ impl BatchCoordinator {
             }
         }
 
+        // Drop the message if any transaction is denied by the node's (hot-reloadable) denylist
+        if let Some(reloadable_config) = reloadable_config::get() {
+            let transaction_denylist = &reloadable_config.transaction_denylist;
+            if !transaction_denylist.is_empty() {
+                for batch in batches.iter() {
+                    for transaction in batch.txns() {
+                        if transaction_denylist.denies_transaction(transaction) {
+                            error!(
+                                "Transaction {}, in batch {}, from {}, was denied by the denylist. Dropping {} batches!",
+                                transaction.committed_hash(),
+                                batch.batch_info().batch_id(),
+                                author.short_str().as_str(),
+                                batches.len()
+                            );
+                            counters::RECEIVED_BATCH_REJECTED_BY_DENYLIST.inc();
+                            return;
+                        }
+                    }
+                }
+            }
+        }
+
         let approx_created_ts_usecs = batch
             .info()
             .expiration()
@@ -722,6 +722,15 @@ pub static RECEIVED_BATCH_REJECTED_BY_FILTER: Lazy<IntCounter> = Lazy::new(|| {
     .unwrap()
 });
 
+/// Count of the batch messages that contained transactions denied by the transaction denylist
+pub static RECEIVED_BATCH_REJECTED_BY_DENYLIST: Lazy<IntCounter> = Lazy::new(|| {
+    register_int_counter!(
+        "quorum_store_received_batch_rejected_by_denylist",
+        "Count of the batch messages that contained transactions denied by the transaction denylist"
+    )
+    .unwrap()
+});
+
 /// Count of the missed batches when execute.
 pub static MISSED_BATCHES_COUNT: Lazy<IntCounter> = Lazy::new(|| {
     register_int_counter!(
@@ -15,6 +15,7 @@ use crate::{
     },
     error::{error_kind, VerifyError},
     liveness::{
+        clock_skew_monitor::ClockSkewMonitor,
         proposal_generator::ProposalGenerator,
         proposal_status_tracker::TPastProposalStatusTracker,
         proposer_election::ProposerElection,
@@ -291,6 +292,7 @@ pub struct RoundManager {
     proposal_status_tracker: Arc<dyn TPastProposalStatusTracker>,
     pending_opt_proposals: BTreeMap<Round, OptBlockData>,
     opt_proposal_loopback_tx: aptos_channels::UnboundedSender<OptBlockData>,
+    clock_skew_monitor: ClockSkewMonitor,
 }
 
 impl RoundManager {
@@ -324,6 +326,10 @@ impl RoundManager {
             .set(onchain_config.decoupled_execution() as i64);
         let vtxn_config = onchain_config.effective_validator_txn_config();
         debug!("vtxn_config={:?}", vtxn_config);
+        let clock_skew_monitor = ClockSkewMonitor::new(
+            epoch_state.clone(),
+            local_config.local_clock_skew_alert_threshold_ms,
+        );
         Self {
             epoch_state,
             block_store,
@@ -349,6 +355,7 @@ impl RoundManager {
             proposal_status_tracker,
             pending_opt_proposals: BTreeMap::new(),
             opt_proposal_loopback_tx,
+            clock_skew_monitor,
         }
     }
 
@@ -365,6 +372,7 @@ impl RoundManager {
                 .max_blocks_per_sending_request(self.onchain_config.quorum_store_enabled()),
             self.block_store.pending_blocks(),
         )
+        .with_max_catch_up_rounds(self.local_config.max_catch_up_rounds)
     }
 
     /// Leader:
@@ -701,6 +709,19 @@ impl RoundManager {
             block_hash = proposal_msg.proposal().id(),
             block_parent_hash = proposal_msg.proposal().quorum_cert().certified_block().id(),
         );
+        // Reconfiguration suffix blocks reuse the timestamp of their parent, which says nothing
+        // about the clock of their proposer
+        if !proposal_msg
+            .proposal()
+            .block_data()
+            .is_reconfiguration_suffix()
+        {
+            self.clock_skew_monitor.observe_proposal(
+                proposal_msg.proposer(),
+                proposal_msg.proposal().timestamp_usecs(),
+                self.round_state.current_timestamp(),
+            );
+        }
 
         let in_correct_round = self
             .ensure_round_and_sync_up(
@@ -726,6 +747,30 @@ impl RoundManager {
         }
     }
 
+    /// Checks that the timestamp of `proposal` is at most `max_drift` ahead of the local clock.
+    /// Timestamps in the past are not rejected: the ones of reconfiguration suffix blocks are the
+    /// timestamp of their parent, which gets arbitrarily old while the reconfiguration commits.
+    fn check_proposal_timestamp_drift(
+        proposal: &Block,
+        now: Duration,
+        max_drift: Duration,
+    ) -> anyhow::Result<()> {
+        if proposal.block_data().is_reconfiguration_suffix() {
+            return Ok(());
+        }
+        let block_time_since_epoch = Duration::from_micros(proposal.timestamp_usecs());
+        ensure!(
+            block_time_since_epoch <= now + max_drift,
+            "[RoundManager] Proposal for block {} has timestamp usecs {:?}, more than {:?} ahead \
+            of the local clock {:?}, the clock of the proposer or ours is skewed",
+            proposal.id(),
+            block_time_since_epoch,
+            max_drift,
+            now,
+        );
+        Ok(())
+    }
+
     pub async fn process_delayed_proposal_msg(&mut self, proposal: Block) -> anyhow::Result<()> {
         if proposal.round() != self.round_state.current_round() {
             bail!(
@@ -1194,6 +1239,15 @@ impl RoundManager {
 
         let block_time_since_epoch = Duration::from_micros(proposal.timestamp_usecs());
 
+        if let Err(error) = Self::check_proposal_timestamp_drift(
+            &proposal,
+            self.round_state.current_timestamp(),
+            Duration::from_millis(self.local_config.max_proposal_timestamp_drift_ms),
+        ) {
+            counters::REJECTED_PROPOSAL_TIMESTAMP_DRIFT_COUNT.inc();
+            return Err(error);
+        }
+
         ensure!(
             block_time_since_epoch < self.round_state.current_round_deadline(),
             "[RoundManager] Waiting until proposal block timestamp usecs {:?} \
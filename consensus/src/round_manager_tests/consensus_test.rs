@@ -9,9 +9,12 @@ use crate::{
     network::IncomingBlockRetrievalRequest,
     network_interface::ConsensusMsg,
     network_tests::{NetworkPlayground, TwinId},
-    round_manager::round_manager_tests::{
-        config_with_round_timeout_msg_disabled, start_replying_to_block_retreival, NodeSetup,
-        ProposalMsgType,
+    round_manager::{
+        round_manager_tests::{
+            config_with_round_timeout_msg_disabled, start_replying_to_block_retreival, NodeSetup,
+            ProposalMsgType,
+        },
+        RoundManager,
     },
     test_utils::{consensus_runtime, timed_block_on, TreeInserter},
 };
@@ -25,6 +28,7 @@ use aptos_consensus_types::{
     common::{Author, Payload, Round},
     opt_proposal_msg::OptProposalMsg,
     proposal_msg::ProposalMsg,
+    quorum_cert::QuorumCert,
     sync_info::SyncInfo,
     timeout_2chain::{TwoChainTimeout, TwoChainTimeoutWithPartialSignatures},
 };
@@ -34,7 +38,10 @@ use aptos_logger::prelude::info;
 use aptos_network::{protocols::network::Event, ProtocolId};
 use aptos_safety_rules::{PersistentSafetyStorage, SafetyRulesManager};
 use aptos_secure_storage::Storage;
-use aptos_types::validator_verifier::generate_validator_verifier;
+use aptos_types::{
+    block_info::BlockInfo, epoch_state::EpochState, validator_signer::ValidatorSigner,
+    validator_verifier::generate_validator_verifier,
+};
 use futures::{channel::oneshot, StreamExt};
 use std::{sync::Arc, time::Duration};
 use tokio::{runtime::Runtime, time::timeout};
@@ -807,6 +814,56 @@ fn reject_invalid_failed_authors() {
     });
 }
 
+#[test]
+/// Proposals too far ahead of the local clock are rejected, while old timestamps are not, which
+/// reconfiguration suffix blocks have when the reconfiguration takes long to commit
+fn reject_proposal_timestamp_drift() {
+    let signer = ValidatorSigner::random(None);
+    let genesis_qc = certificate_for_genesis();
+    let now = aptos_infallible::duration_since_epoch();
+    let max_drift = Duration::from_secs(30);
+    let proposal_at = |timestamp: Duration, quorum_cert: QuorumCert| {
+        Block::new_proposal(
+            Payload::empty(false, true),
+            quorum_cert.certified_block().round() + 1,
+            timestamp.as_micros() as u64,
+            quorum_cert,
+            &signer,
+            Vec::new(),
+        )
+        .unwrap()
+    };
+    let check =
+        |proposal: &Block| RoundManager::check_proposal_timestamp_drift(proposal, now, max_drift);
+
+    assert!(check(&proposal_at(now, genesis_qc.clone())).is_ok());
+    assert!(check(&proposal_at(now + max_drift, genesis_qc.clone())).is_ok());
+    assert!(check(&proposal_at(now + 2 * max_drift, genesis_qc.clone())).is_err());
+    assert!(check(&proposal_at(now - 10 * max_drift, genesis_qc.clone())).is_ok());
+
+    // a reconfiguration suffix keeps the timestamp of the reconfiguration block, however long
+    // the reconfiguration takes to commit
+    let reconfiguration_timestamp = now - 10 * max_drift;
+    let reconfiguration_block = BlockInfo::new(
+        1,
+        1,
+        HashValue::random(),
+        HashValue::zero(),
+        0,
+        reconfiguration_timestamp.as_micros() as u64,
+        Some(EpochState::empty()),
+    );
+    let reconfiguration_qc = gen_test_certificate(
+        &[signer.clone()],
+        reconfiguration_block,
+        genesis_qc.certified_block().clone(),
+        None,
+    );
+    let suffix = proposal_at(reconfiguration_timestamp, reconfiguration_qc);
+    assert!(suffix.block_data().is_reconfiguration_suffix());
+    assert!(check(&suffix).is_ok());
+}
+
 #[test]
 fn response_on_block_retrieval() {
     let runtime = consensus_runtime();
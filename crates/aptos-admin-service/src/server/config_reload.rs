@@ -0,0 +1,102 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use aptos_config::config::{reloadable_config, reloadable_config::ReloadableConfig};
+use aptos_logger::{info, Logger};
+use aptos_system_utils::utils::{reply_with, reply_with_status};
+use hyper::{Body, Request, Response, StatusCode};
+use std::collections::HashMap;
+
+/// Returns the current reloadable config (as YAML)
+pub fn handle_get_reloadable_config_request() -> hyper::Result<Response<Body>> {
+    let Some(reloadable_config) = reloadable_config::get() else {
+        return Ok(reloadable_config_unavailable());
+    };
+
+    match serde_yaml::to_string(reloadable_config.as_ref()) {
+        Ok(reloadable_config) => Ok(reply_with(vec![], reloadable_config)),
+        Err(error) => Ok(reply_with_status(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            error.to_string(),
+        )),
+    }
+}
+
+/// Reloads the reloadable config from the config file the node was started with.
+/// Changes to any other (non-reloadable) part of the file are ignored.
+pub fn handle_reload_config_request() -> hyper::Result<Response<Body>> {
+    let Some(node_config_path) = reloadable_config::node_config_path() else {
+        return Ok(reply_with_status(
+            StatusCode::NOT_FOUND,
+            "The node was not started from a config file.",
+        ));
+    };
+
+    info!("Reloading the config from {}.", node_config_path.display());
+    match ReloadableConfig::load_from_path(&node_config_path) {
+        Ok(reloadable_config) => update_reloadable_config(reloadable_config),
+        Err(error) => Ok(reply_with_status(
+            StatusCode::BAD_REQUEST,
+            format!("Failed to load the config: {}", error),
+        )),
+    }
+}
+
+/// Sets a single field of the reloadable config, e.g.,
+/// `/config/set?field=mempool_capacity&value=1000000`.
+pub fn handle_set_config_request(req: Request<Body>) -> hyper::Result<Response<Body>> {
+    let Some(reloadable_config) = reloadable_config::get() else {
+        return Ok(reloadable_config_unavailable());
+    };
+
+    let query = req.uri().query().unwrap_or("");
+    let query_pairs: HashMap<_, _> = url::form_urlencoded::parse(query.as_bytes()).collect();
+    let (Some(field), Some(value)) = (query_pairs.get("field"), query_pairs.get("value")) else {
+        return Ok(reply_with_status(
+            StatusCode::BAD_REQUEST,
+            "Missing field and/or value parameter.",
+        ));
+    };
+
+    info!("Setting reloadable config field {} to {}.", field, value);
+    let mut reloadable_config = reloadable_config.as_ref().clone();
+    if let Err(error) = reloadable_config.set_field(field, value) {
+        return Ok(reply_with_status(
+            StatusCode::BAD_REQUEST,
+            error.to_string(),
+        ));
+    }
+    update_reloadable_config(reloadable_config)
+}
+
+/// Replaces the reloadable config and applies the changes that aren't picked up
+/// by the components themselves (i.e., the log level).
+fn update_reloadable_config(reloadable_config: ReloadableConfig) -> hyper::Result<Response<Body>> {
+    let logger_level = reloadable_config.logger_level;
+    let old_reloadable_config = match reloadable_config::update(reloadable_config) {
+        Ok(old_reloadable_config) => old_reloadable_config,
+        Err(error) => {
+            return Ok(reply_with_status(
+                StatusCode::BAD_REQUEST,
+                error.to_string(),
+            ))
+        },
+    };
+
+    // Only override the log level if it changed, to keep any module
+    // specific levels (e.g., from RUST_LOG) otherwise.
+    if old_reloadable_config.logger_level != logger_level {
+        if let Some(logger) = Logger::global() {
+            logger.set_level_override(None, logger_level.into(), None);
+        }
+    }
+
+    handle_get_reloadable_config_request()
+}
+
+fn reloadable_config_unavailable() -> Response<Body> {
+    reply_with_status(
+        StatusCode::NOT_FOUND,
+        "The reloadable config was not initialized.",
+    )
+}
@@ -0,0 +1,122 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use aptos_logger::{info, LevelFilter, Logger};
+use aptos_system_utils::utils::{reply_with, reply_with_status};
+use hyper::{Body, Request, Response, StatusCode};
+use std::{collections::HashMap, time::Duration};
+
+/// Lists the log levels that are currently overridden
+pub fn handle_get_log_levels_request() -> hyper::Result<Response<Body>> {
+    let Some(logger) = Logger::global() else {
+        return Ok(logger_unavailable());
+    };
+
+    let mut log_levels = vec![];
+    for level_override in logger.level_overrides() {
+        let expires_in = level_override
+            .expires_at
+            .map(|expires_at| {
+                format!(
+                    " (expires in {}s)",
+                    expires_at
+                        .saturating_duration_since(std::time::Instant::now())
+                        .as_secs()
+                )
+            })
+            .unwrap_or_default();
+        log_levels.push(format!(
+            "{}={:?}{}",
+            level_override.module.as_deref().unwrap_or("*"),
+            level_override.level,
+            expires_in
+        ));
+    }
+    if log_levels.is_empty() {
+        log_levels.push("No log levels are overridden.".to_string());
+    }
+
+    Ok(reply_with(vec![], log_levels.join("\n")))
+}
+
+/// Overrides the log level of a module (or of all modules, if no module is given),
+/// optionally only for the given number of seconds. E.g.,
+/// `/log/set_level?module=aptos_consensus&level=debug&duration_secs=600`.
+pub fn handle_set_log_level_request(req: Request<Body>) -> hyper::Result<Response<Body>> {
+    let Some(logger) = Logger::global() else {
+        return Ok(logger_unavailable());
+    };
+
+    let query = req.uri().query().unwrap_or("");
+    let query_pairs: HashMap<_, _> = url::form_urlencoded::parse(query.as_bytes()).collect();
+
+    let level: LevelFilter = match query_pairs.get("level").map(|level| level.parse()) {
+        Some(Ok(level)) => level,
+        Some(Err(_)) => {
+            return Ok(reply_with_status(
+                StatusCode::BAD_REQUEST,
+                "Invalid level! Expected one of: off, error, warn, info, debug, trace.",
+            ))
+        },
+        None => {
+            return Ok(reply_with_status(
+                StatusCode::BAD_REQUEST,
+                "Missing level parameter.",
+            ))
+        },
+    };
+    let duration = match query_pairs.get("duration_secs") {
+        Some(duration_secs) => match duration_secs.parse() {
+            Ok(duration_secs) => Some(Duration::from_secs(duration_secs)),
+            Err(err) => return Ok(reply_with_status(StatusCode::BAD_REQUEST, err.to_string())),
+        },
+        None => None,
+    };
+    let module = query_pairs.get("module").map(|module| module.as_ref());
+
+    info!(
+        "Overriding the log level of {} with {:?} for {:?}.",
+        module.unwrap_or("all modules"),
+        level,
+        duration
+    );
+    logger.set_level_override(module, level, duration);
+
+    Ok(reply_with(vec![], "Log level overridden."))
+}
+
+/// Removes the log level override of a module, or all overrides if no module is given
+pub fn handle_reset_log_level_request(req: Request<Body>) -> hyper::Result<Response<Body>> {
+    let Some(logger) = Logger::global() else {
+        return Ok(logger_unavailable());
+    };
+
+    let query = req.uri().query().unwrap_or("");
+    let query_pairs: HashMap<_, _> = url::form_urlencoded::parse(query.as_bytes()).collect();
+
+    match query_pairs.get("module") {
+        Some(module) => {
+            info!("Resetting the log level of {}.", module);
+            if logger.remove_level_override(Some(module)) {
+                Ok(reply_with(vec![], "Log level reset."))
+            } else {
+                Ok(reply_with_status(
+                    StatusCode::NOT_FOUND,
+                    format!("The log level of {} is not overridden.", module),
+                ))
+            }
+        },
+        None => {
+            info!("Resetting all log levels.");
+            logger.clear_level_overrides();
+            Ok(reply_with(vec![], "All log levels reset."))
+        },
+    }
+}
+
+fn logger_unavailable() -> Response<Body> {
+    reply_with_status(
+        StatusCode::NOT_FOUND,
+        "Log levels can't be changed, the global logger is not an AptosData logger.",
+    )
+}
@@ -26,7 +26,9 @@ use std::{
 };
 use tokio::runtime::Runtime;
 
+mod config_reload;
 mod consensus;
+mod logging;
 #[cfg(unix)]
 mod malloc;
 mod mempool;
@@ -191,6 +193,16 @@ impl AdminService {
             },
             #[cfg(unix)]
             (hyper::Method::GET, "/malloc/dump_profile") => malloc::handle_dump_profile_request(),
+            (hyper::Method::GET, "/config/reloadable") => {
+                config_reload::handle_get_reloadable_config_request()
+            },
+            (hyper::Method::GET, "/config/reload") => config_reload::handle_reload_config_request(),
+            (hyper::Method::GET, "/config/set") => config_reload::handle_set_config_request(req),
+            (hyper::Method::GET, "/log/levels") => logging::handle_get_log_levels_request(),
+            (hyper::Method::GET, "/log/set_level") => logging::handle_set_log_level_request(req),
+            (hyper::Method::GET, "/log/reset_level") => {
+                logging::handle_reset_log_level_request(req)
+            },
             (hyper::Method::GET, "/debug/consensus/consensusdb") => {
                 let consensus_db = context.consensus_db.read().clone();
                 if let Some(consensus_db) = consensus_db {
@@ -0,0 +1,220 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! This module provides t-out-of-n threshold BLS signatures over the same curve, hash-to-curve
+//! and DST as the rest of `bls12381`, so that a combined threshold signature is a normal
+//! `Signature` verifiable under the normal `PublicKey` of the split key.
+//!
+//! A `PrivateKey` is split into `n` shares with Shamir secret sharing via `split_private_key`.
+//! Each share-holder (e.g., an HSM or a remote signer) signs with its `PrivateKeyShare` and any
+//! `t` of the resulting `SignatureShare`s are combined via `combine_signature_shares`, using
+//! Lagrange interpolation "in the exponent".
+//!
+//! WARNING: `combine_signature_shares` does not verify the shares it is given, so a single bad
+//! share yields a bad signature. Callers should verify the combined signature, or each share
+//! against its `PrivateKeyShare::public_key_share`.
+//!
+//! The polynomial whose constant term is the secret key is wiped once the shares are dealt.
+
+use crate::{
+    bls12381::{PrivateKey, PublicKey, Signature, DST_BLS_SIG_IN_G2_WITH_POP},
+    hash::CryptoHash,
+    signing_message, CryptoMaterialError, Uniform,
+};
+use anyhow::{anyhow, bail, ensure, Result};
+use blstrs::{G2Projective, Scalar};
+use ff::Field;
+use group::{Curve, Group};
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeSet, convert::TryFrom};
+use zeroize::{DefaultIsZeroes, Zeroizing};
+
+/// One share of a `PrivateKey` split via `split_private_key`. The `index` is the evaluation point
+/// of the share and is always in `1..=n`.
+#[derive(Debug, Deserialize, Serialize)]
+#[cfg_attr(any(test, feature = "cloneable-private-keys"), derive(Clone))]
+pub struct PrivateKeyShare {
+    index: u32,
+    share: PrivateKey,
+}
+
+/// A coefficient of the Shamir polynomial, which is zeroized on drop when held in `Zeroizing`
+#[derive(Clone, Copy)]
+struct Coefficient(Scalar);
+
+impl Default for Coefficient {
+    fn default() -> Self {
+        Self(Scalar::ZERO)
+    }
+}
+
+impl DefaultIsZeroes for Coefficient {}
+
+/// A signature by one `PrivateKeyShare`
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct SignatureShare {
+    index: u32,
+    signature: Signature,
+}
+
+impl PrivateKeyShare {
+    pub fn new(index: u32, share: PrivateKey) -> Result<Self> {
+        ensure!(index > 0, "share indices start at 1");
+        Ok(Self { index, share })
+    }
+
+    pub fn index(&self) -> u32 {
+        self.index
+    }
+
+    /// The public key of this share, against which its `SignatureShare`s verify.
+    pub fn public_key_share(&self) -> PublicKey {
+        PublicKey::from(&self.share)
+    }
+
+    pub fn sign<T: CryptoHash + Serialize>(
+        &self,
+        message: &T,
+    ) -> Result<SignatureShare, CryptoMaterialError> {
+        Ok(self.sign_signing_message(&signing_message(message)?))
+    }
+
+    /// Signs the output of `signing_message` on some message. This is for share-holders that are
+    /// handed the already domain-separated bytes to sign, e.g., remote signers.
+    pub fn sign_signing_message(&self, signing_message: &[u8]) -> SignatureShare {
+        SignatureShare {
+            index: self.index,
+            signature: Signature {
+                sig: self
+                    .share
+                    .privkey
+                    .sign(signing_message, DST_BLS_SIG_IN_G2_WITH_POP, &[]),
+            },
+        }
+    }
+
+    #[cfg(any(test, feature = "fuzzing"))]
+    pub fn sign_arbitrary_message(&self, message: &[u8]) -> SignatureShare {
+        self.sign_signing_message(message)
+    }
+}
+
+impl SignatureShare {
+    pub fn new(index: u32, signature: Signature) -> Self {
+        Self { index, signature }
+    }
+
+    pub fn index(&self) -> u32 {
+        self.index
+    }
+
+    pub fn signature(&self) -> &Signature {
+        &self.signature
+    }
+}
+
+/// Splits `private_key` into `num_shares` shares, any `threshold` of which can sign on its behalf.
+pub fn split_private_key<R>(
+    private_key: &PrivateKey,
+    threshold: usize,
+    num_shares: usize,
+    rng: &mut R,
+) -> Result<Vec<PrivateKeyShare>>
+where
+    R: ::rand::RngCore + ::rand::CryptoRng,
+{
+    ensure!(
+        threshold > 0 && threshold <= num_shares,
+        "invalid threshold {} for {} shares",
+        threshold,
+        num_shares
+    );
+    ensure!(
+        num_shares <= u32::MAX as usize,
+        "too many shares: {}",
+        num_shares
+    );
+
+    // f(X) = sk + a_1 X + ... + a_{t-1} X^{t-1}, where the a_i are uniform
+    let mut coefficients = Zeroizing::new(Vec::with_capacity(threshold));
+    coefficients.push(Coefficient(scalar_from_private_key(private_key)?));
+    for _ in 1..threshold {
+        coefficients.push(Coefficient(scalar_from_private_key(
+            &PrivateKey::generate(rng),
+        )?));
+    }
+
+    (1..=num_shares as u32)
+        .map(|index| {
+            let x = Scalar::from(index as u64);
+            let y = coefficients
+                .iter()
+                .rev()
+                .fold(Scalar::ZERO, |acc, coefficient| acc * x + coefficient.0);
+            let y = Zeroizing::new(Coefficient(y));
+            let share = PrivateKey::try_from(Zeroizing::new(y.0.to_bytes_be()).as_slice())
+                .map_err(|e| anyhow!("invalid key share {}: {:?}", index, e))?;
+            Ok(PrivateKeyShare { index, share })
+        })
+        .collect()
+}
+
+/// Combines `threshold` signature shares on the same message into a signature under the public
+/// key of the split `PrivateKey`. Extra shares beyond the first `threshold` ones are ignored.
+pub fn combine_signature_shares(shares: &[SignatureShare], threshold: usize) -> Result<Signature> {
+    ensure!(threshold > 0, "threshold must be positive");
+
+    let mut indices = BTreeSet::new();
+    let shares: Vec<_> = shares
+        .iter()
+        .filter(|share| indices.insert(share.index))
+        .take(threshold)
+        .collect();
+    if shares.len() < threshold {
+        bail!(
+            "not enough distinct signature shares: {} < {}",
+            shares.len(),
+            threshold
+        );
+    }
+    if shares.iter().any(|share| share.index == 0) {
+        bail!("share indices start at 1");
+    }
+
+    let xs: Vec<_> = shares
+        .iter()
+        .map(|share| Scalar::from(share.index as u64))
+        .collect();
+    let mut combined = G2Projective::identity();
+    for (i, share) in shares.iter().enumerate() {
+        let point = Option::<G2Projective>::from(G2Projective::from_compressed(
+            &share.signature.to_bytes(),
+        ))
+        .ok_or_else(|| anyhow!("invalid signature share {}", share.index))?;
+        combined += point * lagrange_coefficient_at_zero(&xs, i)?;
+    }
+
+    Signature::try_from(combined.to_affine().to_compressed().as_slice())
+        .map_err(|e| anyhow!("invalid combined signature: {:?}", e))
+}
+
+/// The Lagrange coefficient of `xs[i]` for interpolating at 0, i.e., prod_{j != i} x_j / (x_j - x_i).
+fn lagrange_coefficient_at_zero(xs: &[Scalar], i: usize) -> Result<Scalar> {
+    let (numerator, denominator) = xs
+        .iter()
+        .enumerate()
+        .filter(|(j, _)| *j != i)
+        .fold((Scalar::ONE, Scalar::ONE), |(num, den), (_, x_j)| {
+            (num * x_j, den * (*x_j - xs[i]))
+        });
+    let inverse = Option::<Scalar>::from(denominator.invert())
+        .ok_or_else(|| anyhow!("duplicate share indices"))?;
+    Ok(numerator * inverse)
+}
+
+fn scalar_from_private_key(private_key: &PrivateKey) -> Result<Scalar> {
+    Option::from(Scalar::from_bytes_be(&Zeroizing::new(
+        private_key.to_bytes(),
+    )))
+    .ok_or_else(|| anyhow!("private key is not a canonical scalar"))
+}
@@ -422,9 +422,13 @@ pub const DST_BLS_SIG_IN_G2_WITH_POP: &[u8] = b"BLS_SIG_BLS12381G2_XMD:SHA-256_S
 pub mod bls12381_keys;
 pub mod bls12381_pop;
 pub mod bls12381_sigs;
+pub mod bls12381_threshold;
 pub mod bls12381_validatable;
 
 pub use bls12381_keys::{PrivateKey, PublicKey};
 pub use bls12381_pop::ProofOfPossession;
-pub use bls12381_sigs::Signature;
+pub use bls12381_sigs::{verify_aggregate_signatures_batch, Signature};
+pub use bls12381_threshold::{
+    combine_signature_shares, split_private_key, PrivateKeyShare, SignatureShare,
+};
 pub use bls12381_validatable::UnvalidatedPublicKey;
@@ -8,6 +8,10 @@
 //! This file implements a stripped-down version of Noise_IK_25519_AESGCM_SHA256.
 //! This means that only the parts that we care about (the IK handshake) are implemented.
 //!
+//! Optionally, the handshake can be run as a post-quantum hybrid (see [`KeyExchange`]), where an
+//! ephemeral ML-KEM-768 (FIPS 203) key encapsulation is performed on top of the usual
+//! Diffie-Hellman operations.
+//!
 //! Note that to benefit from hardware support for AES, you must build this crate with the following
 //! flags: `RUSTFLAGS="-Ctarget-cpu=skylake -Ctarget-feature=+aes,+sse2,+sse4.1,+ssse3"`.
 //!
@@ -64,6 +68,10 @@
 #![allow(clippy::arithmetic_side_effects)]
 
 use crate::{hash::HashValue, hkdf::Hkdf, traits::Uniform as _, x25519, ValidCryptoMaterial};
+use libcrux_ml_kem::{
+    mlkem768::{self, MlKem768Ciphertext, MlKem768PrivateKey, MlKem768PublicKey},
+    ENCAPS_SEED_SIZE, KEY_GENERATION_SEED_SIZE,
+};
 use ring::aead::{self, Aad, LessSafeKey, UnboundKey};
 use sha2::Digest;
 use std::{
@@ -86,6 +94,16 @@ pub const AES_GCM_TAGLEN: usize = 16;
 /// The only Noise handshake protocol that we implement in this file.
 const PROTOCOL_NAME: &[u8] = b"Noise_IK_25519_AESGCM_SHA256\0\0\0\0";
 
+/// The hybrid handshake protocol, see [`KeyExchange::X25519MlKem768`]. As it is longer than the
+/// hash output, it is hashed to initialize the handshake state.
+const MLKEM768_HYBRID_PROTOCOL_NAME: &[u8] = b"Noise_IKhybrid_25519+MLKEM768_AESGCM_SHA256";
+
+/// The size of an ML-KEM-768 encapsulation (public) key.
+pub const MLKEM768_PUBLIC_KEY_SIZE: usize = 1184;
+
+/// The size of an ML-KEM-768 ciphertext.
+pub const MLKEM768_CIPHERTEXT_SIZE: usize = 1088;
+
 /// The nonce size we use for AES-GCM.
 const AES_NONCE_SIZE: usize = 12;
 
@@ -121,6 +139,47 @@ pub const fn handshake_resp_msg_len(payload_len: usize) -> usize {
     e_len + enc_payload_len
 }
 
+/// The key exchange performed during a handshake. Both peers must use the same one.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum KeyExchange {
+    /// The classic Noise_IK_25519_AESGCM_SHA256 handshake
+    X25519,
+    /// The IK handshake, where the initiator additionally sends an ephemeral ML-KEM-768
+    /// encapsulation key at the end of its message and the responder encapsulates a shared secret
+    /// to it. That secret is mixed into the chaining key after the Diffie-Hellman outputs, so the
+    /// session keys remain secret as long as either X25519 or ML-KEM-768 is unbroken.
+    X25519MlKem768,
+}
+
+impl KeyExchange {
+    /// The size of the first handshake message
+    pub const fn init_msg_len(self, payload_len: usize) -> usize {
+        match self {
+            KeyExchange::X25519 => handshake_init_msg_len(payload_len),
+            KeyExchange::X25519MlKem768 => {
+                handshake_init_msg_len(payload_len) + MLKEM768_PUBLIC_KEY_SIZE
+            },
+        }
+    }
+
+    /// The size of the second handshake message
+    pub const fn resp_msg_len(self, payload_len: usize) -> usize {
+        match self {
+            KeyExchange::X25519 => handshake_resp_msg_len(payload_len),
+            KeyExchange::X25519MlKem768 => {
+                handshake_resp_msg_len(payload_len) + MLKEM768_CIPHERTEXT_SIZE
+            },
+        }
+    }
+
+    fn protocol_name(self) -> Vec<u8> {
+        match self {
+            KeyExchange::X25519 => PROTOCOL_NAME.to_vec(),
+            KeyExchange::X25519MlKem768 => hash(MLKEM768_HYBRID_PROTOCOL_NAME),
+        }
+    }
+}
+
 /// Convenience method to wrap an `&[u8]` AES key into a `LessSafeKey` type of the `ring` crate
 fn aes_key(key: &[u8]) -> LessSafeKey {
     LessSafeKey::new(
@@ -237,6 +296,8 @@ pub struct InitiatorHandshakeState {
     e: x25519::PrivateKey,
     /// remote static key used
     rs: x25519::PublicKey,
+    /// ephemeral KEM key, only set in hybrid handshakes
+    kem_sk: Option<MlKem768PrivateKey>,
 }
 
 /// Refer to the Noise protocol framework specification in order to understand these fields.
@@ -250,6 +311,8 @@ pub struct ResponderHandshakeState {
     rs: x25519::PublicKey,
     /// remote ephemeral key receiced
     re: x25519::PublicKey,
+    /// remote ephemeral KEM key received, only set in hybrid handshakes
+    rkem: Option<MlKem768PublicKey>,
 }
 
 impl NoiseConfig {
@@ -280,10 +343,31 @@ impl NoiseConfig {
         remote_public: x25519::PublicKey,
         payload: Option<&[u8]>,
         response_buffer: &mut [u8],
+    ) -> Result<InitiatorHandshakeState, NoiseError> {
+        self.initiate_connection_with_key_exchange(
+            rng,
+            KeyExchange::X25519,
+            prologue,
+            remote_public,
+            payload,
+            response_buffer,
+        )
+    }
+
+    /// Same as `initiate_connection`, but lets the initiator pick the key exchange.
+    /// The responder must parse the message with the same key exchange.
+    pub fn initiate_connection_with_key_exchange(
+        &self,
+        rng: &mut (impl rand::RngCore + rand::CryptoRng),
+        key_exchange: KeyExchange,
+        prologue: &[u8],
+        remote_public: x25519::PublicKey,
+        payload: Option<&[u8]>,
+        response_buffer: &mut [u8],
     ) -> Result<InitiatorHandshakeState, NoiseError> {
         // checks
         let payload_len = payload.map(<[u8]>::len).unwrap_or(0);
-        let buffer_size_required = handshake_init_msg_len(payload_len);
+        let buffer_size_required = key_exchange.init_msg_len(payload_len);
         if buffer_size_required > MAX_SIZE_NOISE_MSG {
             return Err(NoiseError::PayloadTooLarge);
         }
@@ -291,8 +375,8 @@ impl NoiseConfig {
             return Err(NoiseError::ResponseBufferTooSmall);
         }
         // initialize
-        let mut h = PROTOCOL_NAME.to_vec();
-        let mut ck = PROTOCOL_NAME.to_vec();
+        let mut h = key_exchange.protocol_name();
+        let mut ck = key_exchange.protocol_name();
         let rs = remote_public; // for naming consistency with the specification
         mix_hash(&mut h, prologue);
         mix_hash(&mut h, rs.as_slice());
@@ -342,8 +426,29 @@ impl NoiseConfig {
             .write(&in_out[..])
             .map_err(|_| NoiseError::ResponseBufferTooSmall)?;
 
+        // -> ekem
+        let kem_sk = match key_exchange {
+            KeyExchange::X25519 => None,
+            KeyExchange::X25519MlKem768 => {
+                let mut seed = [0u8; KEY_GENERATION_SEED_SIZE];
+                rng.fill_bytes(&mut seed);
+                let (kem_sk, kem_pk) = mlkem768::generate_key_pair(seed).into_parts();
+                mix_hash(&mut h, kem_pk.as_slice());
+                response_buffer
+                    .write(kem_pk.as_slice())
+                    .map_err(|_| NoiseError::ResponseBufferTooSmall)?;
+                Some(kem_sk)
+            },
+        };
+
         // return
-        let handshake_state = InitiatorHandshakeState { h, ck, e, rs };
+        let handshake_state = InitiatorHandshakeState {
+            h,
+            ck,
+            e,
+            rs,
+            kem_sk,
+        };
         Ok(handshake_state)
     }
 
@@ -363,6 +468,7 @@ impl NoiseConfig {
             mut ck,
             e,
             rs,
+            kem_sk,
         } = handshake_state;
 
         // <- e
@@ -380,7 +486,19 @@ impl NoiseConfig {
 
         // <- se
         let dh_output = self.private_key.diffie_hellman(&re);
-        let k = mix_key(&mut ck, &dh_output)?;
+        let mut k = mix_key(&mut ck, &dh_output)?;
+
+        // <- kem
+        if let Some(kem_sk) = kem_sk {
+            let mut ciphertext = [0u8; MLKEM768_CIPHERTEXT_SIZE];
+            cursor
+                .read_exact(&mut ciphertext)
+                .map_err(|_| NoiseError::MsgTooShort)?;
+            mix_hash(&mut h, &ciphertext);
+            let ciphertext = MlKem768Ciphertext::from(ciphertext);
+            let shared_secret = mlkem768::decapsulate(&kem_sk, &ciphertext);
+            k = mix_key(&mut ck, &shared_secret)?;
+        }
 
         // <- payload
         let offset = cursor.position() as usize;
@@ -424,14 +542,48 @@ impl NoiseConfig {
             Vec<u8>,                 // payload received
         ),
         NoiseError,
+    > {
+        self.parse_client_init_message_with_key_exchange(
+            KeyExchange::X25519,
+            prologue,
+            received_message,
+        )
+    }
+
+    /// Same as `parse_client_init_message`, for a message sent with the given key exchange.
+    /// The response built by `respond_to_client` then uses the same key exchange.
+    pub fn parse_client_init_message_with_key_exchange(
+        &self,
+        key_exchange: KeyExchange,
+        prologue: &[u8],
+        received_message: &[u8],
+    ) -> Result<
+        (
+            x25519::PublicKey,       // initiator's public key
+            ResponderHandshakeState, // state to be used in respond_to_client
+            Vec<u8>,                 // payload received
+        ),
+        NoiseError,
     > {
         // checks
         if received_message.len() > MAX_SIZE_NOISE_MSG {
             return Err(NoiseError::ReceivedMsgTooLarge);
         }
+        // the KEM key trails the message, after the payload
+        let (received_message, received_kem_key) = match key_exchange {
+            KeyExchange::X25519 => (received_message, None),
+            KeyExchange::X25519MlKem768 => {
+                let kem_offset = received_message
+                    .len()
+                    .checked_sub(MLKEM768_PUBLIC_KEY_SIZE)
+                    .ok_or(NoiseError::MsgTooShort)?;
+                let (message, kem_key) = received_message.split_at(kem_offset);
+                (message, Some(kem_key))
+            },
+        };
         // initialize
-        let mut h = PROTOCOL_NAME.to_vec();
-        let mut ck = PROTOCOL_NAME.to_vec();
+        let mut h = key_exchange.protocol_name();
+        let mut ck = key_exchange.protocol_name();
         mix_hash(&mut h, prologue);
         mix_hash(&mut h, self.public_key.as_slice());
 
@@ -482,8 +634,29 @@ impl NoiseConfig {
             .map_err(|_| NoiseError::Decrypt)?;
         mix_hash(&mut h, received_encrypted_payload);
 
+        // <- ekem
+        let rkem = match received_kem_key {
+            Some(kem_key) => {
+                mix_hash(&mut h, kem_key);
+                let kem_key = MlKem768PublicKey::try_from(kem_key)
+                    .map_err(|_| NoiseError::WrongPublicKeyReceived)?;
+                // FIPS 203 requires the encapsulation key to be checked before it is used
+                if !mlkem768::validate_public_key(&kem_key) {
+                    return Err(NoiseError::WrongPublicKeyReceived);
+                }
+                Some(kem_key)
+            },
+            None => None,
+        };
+
         // return
-        let handshake_state = ResponderHandshakeState { h, ck, rs, re };
+        let handshake_state = ResponderHandshakeState {
+            h,
+            ck,
+            rs,
+            re,
+            rkem,
+        };
         Ok((rs, handshake_state, received_payload.to_vec()))
     }
 
@@ -498,7 +671,12 @@ impl NoiseConfig {
     ) -> Result<NoiseSession, NoiseError> {
         // checks
         let payload_len = payload.map(<[u8]>::len).unwrap_or(0);
-        let buffer_size_required = handshake_resp_msg_len(payload_len);
+        let key_exchange = if handshake_state.rkem.is_some() {
+            KeyExchange::X25519MlKem768
+        } else {
+            KeyExchange::X25519
+        };
+        let buffer_size_required = key_exchange.resp_msg_len(payload_len);
         if buffer_size_required > MAX_SIZE_NOISE_MSG {
             return Err(NoiseError::PayloadTooLarge);
         }
@@ -512,6 +690,7 @@ impl NoiseConfig {
             mut ck,
             rs,
             re,
+            rkem,
         } = handshake_state;
 
         // -> e
@@ -530,7 +709,19 @@ impl NoiseConfig {
 
         // -> se
         let dh_output = e.diffie_hellman(&rs);
-        let k = mix_key(&mut ck, &dh_output)?;
+        let mut k = mix_key(&mut ck, &dh_output)?;
+
+        // -> kem
+        if let Some(rkem) = rkem {
+            let mut seed = [0u8; ENCAPS_SEED_SIZE];
+            rng.fill_bytes(&mut seed);
+            let (ciphertext, shared_secret) = mlkem768::encapsulate(&rkem, seed);
+            mix_hash(&mut h, ciphertext.as_slice());
+            response_buffer
+                .write(ciphertext.as_slice())
+                .map_err(|_| NoiseError::ResponseBufferTooSmall)?;
+            k = mix_key(&mut ck, &shared_secret)?;
+        }
 
         // -> payload
         let aead = aes_key(&k[..]);
@@ -315,6 +315,63 @@ fn bls12381_multisig_wrong_pks_aggregated() {
     assert!(multisig2.verify(&message1, &aggpk1).is_err());
 }
 
+/// Tests that a batch of signature shares and multisignatures on different messages verifies, and
+/// that it stops verifying if any one signature is swapped for a signature on another message.
+#[test]
+fn bls12381_batch_verify() {
+    let mut rng = OsRng;
+    let num_sigs = 20;
+    let num_signers = 5;
+
+    let messages = random_messages_for_signing(&mut rng, num_sigs);
+    let messages_bytes: Vec<Vec<u8>> = messages
+        .iter()
+        .map(|message| crate::signing_message(message).unwrap())
+        .collect();
+    let messages_wrong = random_messages_for_signing(&mut rng, num_sigs);
+
+    let mut aggpks = vec![];
+    let mut signatures = vec![];
+    let mut signatures_wrong = vec![];
+    for (i, (message, message_wrong)) in zip(&messages, &messages_wrong).enumerate() {
+        // Alternate between signature shares and multisignatures
+        let key_pairs = bls12381_keygen(1 + (i % 2) * (num_signers - 1), &mut rng);
+        let pubkeys: Vec<&PublicKey> = key_pairs.iter().map(|keys| &keys.public_key).collect();
+        aggpks.push(PublicKey::aggregate(pubkeys).unwrap());
+        signatures.push(
+            bls12381::Signature::aggregate(
+                key_pairs
+                    .iter()
+                    .map(|keys| keys.private_key.sign(message).unwrap())
+                    .collect(),
+            )
+            .unwrap(),
+        );
+        signatures_wrong.push(
+            bls12381::Signature::aggregate(
+                key_pairs
+                    .iter()
+                    .map(|keys| keys.private_key.sign(message_wrong).unwrap())
+                    .collect(),
+            )
+            .unwrap(),
+        );
+    }
+
+    let msgs: Vec<&[u8]> = messages_bytes.iter().map(|m| m.as_slice()).collect();
+    let pks: Vec<&PublicKey> = aggpks.iter().collect();
+    let mut sigs: Vec<&bls12381::Signature> = signatures.iter().collect();
+    assert!(bls12381::verify_aggregate_signatures_batch(&msgs, &pks, &sigs).is_ok());
+    assert!(bls12381::verify_aggregate_signatures_batch(&[], &[], &[]).is_ok());
+    assert!(bls12381::verify_aggregate_signatures_batch(&msgs[1..], &pks, &sigs).is_err());
+
+    for i in [0, 1, num_sigs - 1] {
+        sigs[i] = &signatures_wrong[i];
+        assert!(bls12381::verify_aggregate_signatures_batch(&msgs, &pks, &sigs).is_err());
+        sigs[i] = &signatures[i];
+    }
+}
+
 /// Tests that a randomly generated multisig does not verify under a randomly generated PK.
 #[test]
 fn bls12381_random_multisig_dont_verify_with_random_pk() {
@@ -471,6 +528,67 @@ fn bls12381_sample_doc_test_for_normal_sigs() {
     );
 }
 
+/// Tests that any `t` of the `n` shares of a split key combine into a signature that verifies
+/// under the original public key, and that fewer than `t` distinct shares are rejected.
+#[test]
+fn bls12381_threshold_sign_and_combine() {
+    let mut rng = OsRng;
+    let message = b"Hello world";
+
+    let key_pair = KeyPair::<PrivateKey, PublicKey>::generate(&mut rng);
+    let shares = bls12381::split_private_key(&key_pair.private_key, 3, 5, &mut rng).unwrap();
+    let sig_shares: Vec<_> = shares
+        .iter()
+        .map(|share| share.sign_arbitrary_message(message))
+        .collect();
+
+    // each share verifies under its own public key share
+    for (share, sig_share) in zip(&shares, &sig_shares) {
+        assert!(sig_share
+            .signature()
+            .verify_arbitrary_msg(message, &share.public_key_share())
+            .is_ok());
+    }
+
+    for subset in [[0, 1, 2], [4, 2, 0], [1, 3, 4]] {
+        let subset: Vec<_> = subset.iter().map(|i| sig_shares[*i].clone()).collect();
+        let signature = bls12381::combine_signature_shares(&subset, 3).unwrap();
+        assert_eq!(
+            signature,
+            key_pair.private_key.sign_arbitrary_message(message)
+        );
+        assert!(signature
+            .verify_arbitrary_msg(message, &key_pair.public_key)
+            .is_ok());
+    }
+
+    // duplicates do not count towards the threshold
+    let duplicated = vec![
+        sig_shares[0].clone(),
+        sig_shares[0].clone(),
+        sig_shares[1].clone(),
+    ];
+    assert!(bls12381::combine_signature_shares(&duplicated, 3).is_err());
+    assert!(bls12381::combine_signature_shares(&sig_shares[..2], 3).is_err());
+
+    // a bad share yields a signature that does not verify
+    let mut tampered = sig_shares[..3].to_vec();
+    tampered[2] = bls12381::SignatureShare::new(
+        tampered[2].index(),
+        shares[0]
+            .sign_arbitrary_message(message)
+            .signature()
+            .clone(),
+    );
+    let signature = bls12381::combine_signature_shares(&tampered, 3).unwrap();
+    assert!(signature
+        .verify_arbitrary_msg(message, &key_pair.public_key)
+        .is_err());
+
+    assert!(bls12381::split_private_key(&key_pair.private_key, 0, 5, &mut rng).is_err());
+    assert!(bls12381::split_private_key(&key_pair.private_key, 6, 5, &mut rng).is_err());
+}
+
 #[test]
 #[ignore]
 /// Not an actual test: only used to generate test cases for testing the BLS Move module in
@@ -2,7 +2,10 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    noise::{handshake_init_msg_len, handshake_resp_msg_len, NoiseConfig, MAX_SIZE_NOISE_MSG},
+    noise::{
+        handshake_init_msg_len, handshake_resp_msg_len, KeyExchange, NoiseConfig, NoiseError,
+        MAX_SIZE_NOISE_MSG,
+    },
     test_utils::TEST_SEED,
     x25519, Uniform as _,
 };
@@ -106,6 +109,86 @@ fn simple_handshake() {
     }
 }
 
+#[test]
+fn hybrid_handshake() {
+    // setup peers
+    let mut rng = ::rand::rngs::StdRng::from_seed(TEST_SEED);
+    let initiator_private = x25519::PrivateKey::generate(&mut rng);
+    let initiator_public = initiator_private.public_key();
+    let responder_private = x25519::PrivateKey::generate(&mut rng);
+    let responder_public = responder_private.public_key();
+    let initiator = NoiseConfig::new(initiator_private);
+    let responder = NoiseConfig::new(responder_private);
+
+    // initiator sends first message
+    let prologue = b"prologue";
+    let payload1 = b"payload1";
+    let key_exchange = KeyExchange::X25519MlKem768;
+    let mut first_message = vec![0u8; key_exchange.init_msg_len(payload1.len())];
+    let initiator_state = initiator
+        .initiate_connection_with_key_exchange(
+            &mut rng,
+            key_exchange,
+            prologue,
+            responder_public,
+            Some(payload1),
+            &mut first_message,
+        )
+        .unwrap();
+
+    // a responder expecting the classic handshake cannot parse it
+    assert!(matches!(
+        responder.parse_client_init_message(
+            prologue,
+            &first_message[..handshake_init_msg_len(payload1.len())]
+        ),
+        Err(NoiseError::Decrypt)
+    ));
+
+    // responder parses the first message and responds
+    let (remote_static, handshake_state, received_payload) = responder
+        .parse_client_init_message_with_key_exchange(key_exchange, prologue, &first_message)
+        .unwrap();
+    assert_eq!(remote_static, initiator_public);
+    assert_eq!(received_payload, b"payload1");
+
+    let payload2 = b"payload2";
+    let mut second_message = vec![0u8; key_exchange.resp_msg_len(payload2.len())];
+    let mut responder_session = responder
+        .respond_to_client(
+            &mut rng,
+            handshake_state,
+            Some(payload2),
+            &mut second_message,
+        )
+        .unwrap();
+
+    // initiator parses the response
+    let (received_payload, mut initiator_session) = initiator
+        .finalize_connection(initiator_state.clone(), &second_message)
+        .unwrap();
+    assert_eq!(received_payload, b"payload2");
+
+    // session usage
+    let mut message = b"payload".to_vec();
+    let auth_tag = initiator_session
+        .write_message_in_place(&mut message)
+        .unwrap();
+    message.extend_from_slice(&auth_tag);
+    assert_eq!(
+        responder_session
+            .read_message_in_place(&mut message)
+            .unwrap(),
+        b"payload"
+    );
+
+    // a tampered KEM ciphertext is detected
+    second_message[x25519::PUBLIC_KEY_SIZE] ^= 1;
+    assert!(initiator
+        .finalize_connection(initiator_state, &second_message)
+        .is_err());
+}
+
 #[test]
 fn test_vectors() {
     // structures needed to deserialize test vectors
@@ -15,6 +15,8 @@ pub enum Cmd {
 
     #[clap(subcommand)]
     Move(aptos_move_debugger::common::Command),
+
+    SimulateProposals(aptos_release_builder::simulate::SimulateProposals),
 }
 
 impl Cmd {
@@ -24,6 +26,7 @@ impl Cmd {
             Cmd::Decode(cmd) => cmd.run().await,
             Cmd::DumpPendingTxns(cmd) => cmd.run().await,
             Cmd::Move(cmd) => cmd.run().await,
+            Cmd::SimulateProposals(cmd) => cmd.run().await,
         }
     }
 }
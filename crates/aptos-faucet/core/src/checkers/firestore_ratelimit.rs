@@ -0,0 +1,369 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use super::{
+    redis_ratelimit::{RatelimitKeyProvider, RatelimitKeyProviderConfig},
+    CheckerData, CheckerTrait, CompleteData,
+};
+use crate::{
+    endpoints::{AptosTapError, AptosTapErrorCode, RejectionReason, RejectionReasonCode},
+    firebase_jwt::FirebaseJwtVerifier,
+    helpers::{days_since_tap_epoch, get_current_time_secs, seconds_until_next_day},
+};
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use chrono::{SecondsFormat, TimeZone, Utc};
+use reqwest::{StatusCode, Url};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::time::{Duration, Instant};
+use tokio::sync::Mutex;
+
+const METADATA_SERVER_TOKEN_URL: &str =
+    "http://metadata.google.internal/computeMetadata/v1/instance/service-accounts/default/token";
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct FirestoreRatelimitCheckerConfig {
+    /// The GCP project the Firestore database lives in.
+    pub gcp_project: String,
+
+    /// The Firestore database to use.
+    #[serde(default = "FirestoreRatelimitCheckerConfig::default_database")]
+    pub database: String,
+
+    /// The collection in which we store a document per key per day. To have
+    /// Firestore clean up old documents, configure a TTL policy on the
+    /// `expire_at` field of this collection.
+    #[serde(default = "FirestoreRatelimitCheckerConfig::default_collection")]
+    pub collection: String,
+
+    /// The Firestore API to talk to. Point this at an emulator for testing,
+    /// e.g. http://127.0.0.1:8088.
+    #[serde(default = "FirestoreRatelimitCheckerConfig::default_endpoint")]
+    pub endpoint: Url,
+
+    /// If set, we get access tokens for the Firestore API from the GCE metadata
+    /// server, i.e. we use the service account the faucet is running as. Unset
+    /// this when using the emulator, which doesn't need credentials.
+    #[serde(default = "FirestoreRatelimitCheckerConfig::default_use_metadata_server")]
+    pub use_metadata_server: bool,
+
+    /// Max number of requests per key per day. 500s are not counted, because they are
+    /// not the user's fault, but everything else is.
+    pub max_requests_per_day: u32,
+
+    /// This defines how we ratelimit, e.g. either by IP or by JWT (Firebase UID).
+    #[serde(default)]
+    pub ratelimit_key_provider_config: RatelimitKeyProviderConfig,
+}
+
+impl FirestoreRatelimitCheckerConfig {
+    fn default_database() -> String {
+        "(default)".to_string()
+    }
+
+    fn default_collection() -> String {
+        "faucet_ratelimits".to_string()
+    }
+
+    fn default_endpoint() -> Url {
+        Url::parse("https://firestore.googleapis.com").unwrap()
+    }
+
+    fn default_use_metadata_server() -> bool {
+        true
+    }
+
+    fn documents_path(&self) -> String {
+        format!(
+            "projects/{}/databases/{}/documents",
+            self.gcp_project, self.database
+        )
+    }
+}
+
+#[derive(Deserialize)]
+struct AccessTokenResponse {
+    access_token: String,
+    expires_in: u64,
+}
+
+/// The FirestoreRatelimitChecker works the same way as the RedisRatelimitChecker,
+/// see the docstring there for the order of operations, but keeps the counters
+/// in Firestore instead. This is useful for ecosystems that already run on GCP
+/// and don't want to operate a Redis just for the faucet.
+///
+/// Each key (e.g. IP / Firebase UID) gets a document per day holding a `count`
+/// field. We increment it atomically using a field transform in a commit, which
+/// returns the new value, so we can do the same get / set race check as with
+/// Redis. Firestore has no per-document expiration like Redis does, instead we
+/// set an `expire_at` field that a TTL policy on the collection can use.
+pub struct FirestoreRatelimitChecker {
+    args: FirestoreRatelimitCheckerConfig,
+    client: reqwest::Client,
+    ratelimit_key_provider: RatelimitKeyProvider,
+
+    /// Cached access token from the metadata server and when it expires.
+    access_token: Mutex<Option<(String, Instant)>>,
+}
+
+impl FirestoreRatelimitChecker {
+    pub async fn new(args: FirestoreRatelimitCheckerConfig) -> Result<Self> {
+        let ratelimit_key_provider = match args.ratelimit_key_provider_config.clone() {
+            RatelimitKeyProviderConfig::Ip => RatelimitKeyProvider::Ip,
+            RatelimitKeyProviderConfig::Jwt(config) => {
+                RatelimitKeyProvider::Jwt(FirebaseJwtVerifier::new(config).await?)
+            },
+        };
+
+        let checker = Self {
+            args,
+            client: reqwest::Client::new(),
+            ratelimit_key_provider,
+            access_token: Mutex::new(None),
+        };
+
+        // Ensure we can authenticate.
+        checker
+            .get_access_token()
+            .await
+            .context("Failed to get access token for Firestore on startup")?;
+
+        Ok(checker)
+    }
+
+    /// Returns None if we're not configured to use credentials.
+    async fn get_access_token(&self) -> Result<Option<String>> {
+        if !self.args.use_metadata_server {
+            return Ok(None);
+        }
+
+        let mut access_token = self.access_token.lock().await;
+        if let Some((token, expires_at)) = access_token.as_ref() {
+            if Instant::now() < *expires_at {
+                return Ok(Some(token.clone()));
+            }
+        }
+
+        let response: AccessTokenResponse = self
+            .client
+            .get(METADATA_SERVER_TOKEN_URL)
+            .header("Metadata-Flavor", "Google")
+            .send()
+            .await
+            .context("Failed to request access token from the metadata server")?
+            .error_for_status()?
+            .json()
+            .await
+            .context("Failed to parse access token from the metadata server")?;
+
+        // Refresh a minute early so we never send a token that expires in flight.
+        let expires_at =
+            Instant::now() + Duration::from_secs(response.expires_in.saturating_sub(60));
+        *access_token = Some((response.access_token.clone(), expires_at));
+        Ok(Some(response.access_token))
+    }
+
+    async fn request(
+        &self,
+        builder: reqwest::RequestBuilder,
+    ) -> Result<reqwest::Response, AptosTapError> {
+        let builder = match self.get_access_token().await.map_err(storage_error)? {
+            Some(token) => builder.bearer_auth(token),
+            None => builder,
+        };
+        builder.send().await.map_err(|e| storage_error(e.into()))
+    }
+
+    fn api_url(&self, path: &str) -> Result<Url, AptosTapError> {
+        self.args
+            .endpoint
+            .join(&format!("v1/{}", path))
+            .map_err(|e| storage_error(e.into()))
+    }
+
+    // Returns the document ID and the seconds until the next day.
+    fn get_document_id_and_secs_until_next_day(
+        &self,
+        ratelimit_key_prefix: &str,
+        ratelimit_key_value: &str,
+    ) -> (String, u64) {
+        let now_secs = get_current_time_secs();
+        let seconds_until_next_day = seconds_until_next_day(now_secs);
+        let document_id = format!(
+            "{}:{}:{}",
+            ratelimit_key_prefix,
+            ratelimit_key_value,
+            days_since_tap_epoch(now_secs)
+        );
+        (document_id, seconds_until_next_day)
+    }
+
+    fn document_name(&self, document_id: &str) -> String {
+        format!(
+            "{}/{}/{}",
+            self.args.documents_path(),
+            self.args.collection,
+            document_id
+        )
+    }
+
+    /// Get the current count for the document, None if it doesn't exist yet.
+    async fn get_count(&self, document_id: &str) -> Result<Option<i64>, AptosTapError> {
+        let url = self.api_url(&self.document_name(document_id))?;
+        let response = self.request(self.client.get(url)).await?;
+        if response.status() == StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+        let document: Value = response
+            .error_for_status()
+            .map_err(|e| storage_error(e.into()))?
+            .json()
+            .await
+            .map_err(|e| storage_error(e.into()))?;
+        parse_integer_value(&document["fields"]["count"])
+            .map(Some)
+            .map_err(storage_error)
+    }
+
+    /// Atomically add `delta` to the count for the document, creating it if
+    /// necessary, and return the new count.
+    async fn increment_count(
+        &self,
+        document_id: &str,
+        delta: i64,
+        seconds_until_next_day: u64,
+    ) -> Result<i64, AptosTapError> {
+        let expire_at = Utc
+            .timestamp_opt((get_current_time_secs() + seconds_until_next_day) as i64, 0)
+            .unwrap()
+            .to_rfc3339_opts(SecondsFormat::Secs, true);
+        let body = json!({
+            "writes": [{
+                "update": {
+                    "name": self.document_name(document_id),
+                    "fields": { "expire_at": { "timestampValue": expire_at } },
+                },
+                "updateMask": { "fieldPaths": ["expire_at"] },
+                "updateTransforms": [{
+                    "fieldPath": "count",
+                    "increment": { "integerValue": delta.to_string() },
+                }],
+            }],
+        });
+
+        let url = self.api_url(&format!("{}:commit", self.args.documents_path()))?;
+        let response: Value = self
+            .request(self.client.post(url).json(&body))
+            .await?
+            .error_for_status()
+            .map_err(|e| storage_error(e.into()))?
+            .json()
+            .await
+            .map_err(|e| storage_error(e.into()))?;
+        parse_integer_value(&response["writeResults"][0]["transformResults"][0])
+            .map_err(storage_error)
+    }
+
+    fn check_limit_value(
+        &self,
+        limit_value: Option<i64>,
+        seconds_until_next_day: u64,
+    ) -> Option<RejectionReason> {
+        if limit_value.unwrap_or(0) > self.args.max_requests_per_day as i64 {
+            Some(
+                RejectionReason::new(
+                    format!(
+                        "You have reached the maximum allowed number of requests per day: {}",
+                        self.args.max_requests_per_day
+                    ),
+                    RejectionReasonCode::UsageLimitExhausted,
+                )
+                .retry_after(seconds_until_next_day),
+            )
+        } else {
+            None
+        }
+    }
+}
+
+#[async_trait]
+impl CheckerTrait for FirestoreRatelimitChecker {
+    async fn check(
+        &self,
+        data: CheckerData,
+        dry_run: bool,
+    ) -> Result<Vec<RejectionReason>, AptosTapError> {
+        // Generate a document ID corresponding to this identifier and the current day.
+        let key_prefix = self.ratelimit_key_provider.ratelimit_key_prefix();
+        let key_value = self
+            .ratelimit_key_provider
+            .ratelimit_key_value(&data)
+            .await?;
+        let (document_id, seconds_until_next_day) =
+            self.get_document_id_and_secs_until_next_day(key_prefix, &key_value);
+
+        // Get the count, indicating how many non-500 requests we have serviced
+        // for this key today, rejecting early if it is already over the limit.
+        let limit_value = self.get_count(&document_id).await?;
+        if let Some(rejection_reason) = self.check_limit_value(limit_value, seconds_until_next_day)
+        {
+            return Ok(vec![rejection_reason]);
+        }
+
+        if !dry_run {
+            let incremented_limit_value = self
+                .increment_count(&document_id, 1, seconds_until_next_day)
+                .await?;
+
+            // Check limit again, to ensure there wasn't a get / set race.
+            if let Some(rejection_reason) =
+                self.check_limit_value(Some(incremented_limit_value), seconds_until_next_day)
+            {
+                return Ok(vec![rejection_reason]);
+            }
+        }
+
+        Ok(vec![])
+    }
+
+    /// All we have to do here is decrement the counter if the request was a failure due
+    /// to something wrong on our end.
+    async fn complete(&self, data: CompleteData) -> Result<(), AptosTapError> {
+        if !data.response_is_500 {
+            return Ok(());
+        }
+
+        let key_prefix = self.ratelimit_key_provider.ratelimit_key_prefix();
+        let key_value = self
+            .ratelimit_key_provider
+            .ratelimit_key_value(&data.checker_data)
+            .await?;
+        let (document_id, seconds_until_next_day) =
+            self.get_document_id_and_secs_until_next_day(key_prefix, &key_value);
+
+        self.increment_count(&document_id, -1, seconds_until_next_day)
+            .await?;
+        Ok(())
+    }
+
+    fn cost(&self) -> u8 {
+        100
+    }
+}
+
+/// Firestore encodes int64 values as strings, e.g. `{"integerValue": "3"}`.
+fn parse_integer_value(value: &Value) -> Result<i64> {
+    value["integerValue"]
+        .as_str()
+        .with_context(|| format!("Expected an integerValue, got {}", value))?
+        .parse()
+        .with_context(|| format!("Failed to parse integerValue {}", value))
+}
+
+fn storage_error(e: anyhow::Error) -> AptosTapError {
+    AptosTapError::new_with_error_code(
+        format!("Failed to talk to Firestore: {:#}", e),
+        AptosTapErrorCode::StorageError,
+    )
+}
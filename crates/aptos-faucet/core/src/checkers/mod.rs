@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 mod auth_token;
+mod firestore_ratelimit;
 mod google_captcha;
 mod ip_blocklist;
 mod magic_header;
@@ -13,6 +14,7 @@ mod tap_captcha;
 pub use self::tap_captcha::CaptchaManager;
 use self::{
     auth_token::AuthTokenChecker,
+    firestore_ratelimit::{FirestoreRatelimitChecker, FirestoreRatelimitCheckerConfig},
     google_captcha::{CaptchaChecker as GoogleCaptchaChecker, GoogleCaptchaCheckerConfig},
     ip_blocklist::IpBlocklistChecker,
     magic_header::{MagicHeaderChecker, MagicHeaderCheckerConfig},
@@ -84,6 +86,9 @@ pub enum CheckerConfig {
     /// Requires that an auth token is included in the Authorization header.
     AuthToken(ListManagerConfig),
 
+    /// Ratelimiter that uses Firestore.
+    FirestoreRatelimit(FirestoreRatelimitCheckerConfig),
+
     /// Requires a legitimate Google ReCaptcha token.
     GoogleCaptcha(GoogleCaptchaCheckerConfig),
 
@@ -110,6 +115,9 @@ impl CheckerConfig {
     pub async fn build(self, captcha_manager: Arc<Mutex<CaptchaManager>>) -> Result<Checker> {
         Ok(match self {
             CheckerConfig::AuthToken(config) => Checker::from(AuthTokenChecker::new(config)?),
+            CheckerConfig::FirestoreRatelimit(config) => {
+                Checker::from(FirestoreRatelimitChecker::new(config).await?)
+            },
             CheckerConfig::GoogleCaptcha(config) => {
                 Checker::from(GoogleCaptchaChecker::new(config)?)
             },
@@ -135,6 +143,7 @@ impl CheckerConfig {
 #[enum_dispatch(CheckerTrait)]
 pub enum Checker {
     AuthTokenChecker,
+    FirestoreRatelimitChecker,
     GoogleCaptchaChecker,
     IpBlocklistChecker,
     MagicHeaderChecker,
@@ -692,6 +692,47 @@ mod test {
         Ok(())
     }
 
+    // Requires the Firestore emulator, see DEV.md.
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_firestore_ratelimiter() -> Result<()> {
+        init();
+        let config_content = include_str!("../../../configs/testing_firestore.yaml");
+        let (port, _handle) = start_server(config_content).await?;
+
+        // Assert that the first 3 requests work.
+        for _ in 0..3 {
+            unwrap_reqwest_result(
+                reqwest::Client::new()
+                    .post(get_fund_endpoint(port))
+                    .body(get_fund_request(Some(10)).to_json_string())
+                    .header(CONTENT_TYPE, "application/json")
+                    .send()
+                    .await,
+            )
+            .await?;
+        }
+
+        // But the fourth does not, specifically with a 429 and the correct
+        // rejection reasons in the body.
+        let response = reqwest::Client::new()
+            .post(get_fund_endpoint(port))
+            .body(get_fund_request(Some(10)).to_json_string())
+            .header(CONTENT_TYPE, "application/json")
+            .send()
+            .await?;
+        assert_eq!(response.status(), reqwest::StatusCode::TOO_MANY_REQUESTS);
+        let aptos_error = AptosTapError::parse_from_json_string(&response.text().await?)
+            .expect("Failed to read response as AptosError");
+        let rejection_reason_codes: HashSet<RejectionReasonCode> = aptos_error
+            .rejection_reasons
+            .into_iter()
+            .map(|r| r.get_code())
+            .collect();
+        assert!(rejection_reason_codes.contains(&RejectionReasonCode::UsageLimitExhausted));
+
+        Ok(())
+    }
+
     // We skip this for now since we have no current need to use the TransferFunder.
     #[ignore]
     #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
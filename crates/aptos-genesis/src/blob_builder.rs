@@ -0,0 +1,229 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    builder::GenesisConfiguration, config::ValidatorConfiguration, mainnet::MainnetGenesisInfo,
+    GenesisInfo,
+};
+use anyhow::ensure;
+use aptos_crypto::ed25519::Ed25519PublicKey;
+use aptos_framework::ReleaseBundle;
+use aptos_types::{
+    account_address::AccountAddress,
+    chain_id::ChainId,
+    on_chain_config::{
+        Features, GasScheduleV2, OnChainConsensusConfig, OnChainExecutionConfig,
+        OnChainJWKConsensusConfig, OnChainRandomnessConfig,
+    },
+    transaction::Transaction,
+    waypoint::Waypoint,
+};
+use aptos_vm_genesis::{AccountBalance, EmployeePool, ValidatorWithCommissionRate};
+use std::{convert::TryFrom, fs::File, io::Write, path::Path};
+
+/// Builder that assembles a genesis transaction in code, without going through the layout and
+/// validator YAML files consumed by the CLI.
+///
+/// There are two flavors of genesis, picked by whether a root key is set:
+/// * With a root key (see [`GenesisBlobBuilder::with_root_key`]) we generate a test genesis, where
+///   the core resources account owns the mint capability and funds every validator owner. Initial
+///   account balances are not supported, since only the root account can mint.
+/// * Without a root key we generate a mainnet-style genesis, where every account (including
+///   validator owners and operators) must be funded through the initial balances.
+#[derive(Clone)]
+pub struct GenesisBlobBuilder {
+    chain_id: ChainId,
+    framework: ReleaseBundle,
+    root_key: Option<Ed25519PublicKey>,
+    validators: Vec<ValidatorWithCommissionRate>,
+    accounts: Vec<AccountBalance>,
+    employee_vesting_accounts: Vec<EmployeePool>,
+    genesis_config: GenesisConfiguration,
+}
+
+impl GenesisBlobBuilder {
+    pub fn new(chain_id: ChainId, framework: ReleaseBundle) -> Self {
+        Self {
+            chain_id,
+            framework,
+            root_key: None,
+            validators: vec![],
+            accounts: vec![],
+            employee_vesting_accounts: vec![],
+            genesis_config: GenesisConfiguration::default_for_test(),
+        }
+    }
+
+    pub fn with_root_key(mut self, root_key: Ed25519PublicKey) -> Self {
+        self.root_key = Some(root_key);
+        self
+    }
+
+    pub fn with_validator(mut self, validator: ValidatorWithCommissionRate) -> Self {
+        self.validators.push(validator);
+        self
+    }
+
+    pub fn with_validators(
+        mut self,
+        validators: impl IntoIterator<Item = ValidatorWithCommissionRate>,
+    ) -> Self {
+        self.validators.extend(validators);
+        self
+    }
+
+    /// Adds validators in the same format the CLI reads from the genesis repository.
+    pub fn with_validator_configs(
+        mut self,
+        configs: impl IntoIterator<Item = ValidatorConfiguration>,
+    ) -> anyhow::Result<Self> {
+        for config in configs {
+            self.validators
+                .push(ValidatorWithCommissionRate::try_from(config)?);
+        }
+        Ok(self)
+    }
+
+    pub fn with_account(mut self, account_address: AccountAddress, balance: u64) -> Self {
+        self.accounts.push(AccountBalance {
+            account_address,
+            balance,
+        });
+        self
+    }
+
+    pub fn with_accounts(mut self, accounts: impl IntoIterator<Item = AccountBalance>) -> Self {
+        self.accounts.extend(accounts);
+        self
+    }
+
+    /// Employee vesting pools require `employee_vesting_start` and
+    /// `employee_vesting_period_duration` to be set in the genesis configuration.
+    pub fn with_employee_vesting_accounts(
+        mut self,
+        employee_vesting_accounts: impl IntoIterator<Item = EmployeePool>,
+    ) -> Self {
+        self.employee_vesting_accounts
+            .extend(employee_vesting_accounts);
+        self
+    }
+
+    /// Replaces the whole genesis configuration, including on-chain configs.
+    pub fn with_genesis_config(mut self, genesis_config: GenesisConfiguration) -> Self {
+        self.genesis_config = genesis_config;
+        self
+    }
+
+    /// Modifies the genesis configuration in place, for anything without a dedicated setter.
+    pub fn with_init_genesis_config(
+        mut self,
+        init_genesis_config: impl FnOnce(&mut GenesisConfiguration),
+    ) -> Self {
+        (init_genesis_config)(&mut self.genesis_config);
+        self
+    }
+
+    pub fn with_epoch_duration_secs(mut self, epoch_duration_secs: u64) -> Self {
+        self.genesis_config.epoch_duration_secs = epoch_duration_secs;
+        self
+    }
+
+    pub fn with_consensus_config(mut self, consensus_config: OnChainConsensusConfig) -> Self {
+        self.genesis_config.consensus_config = consensus_config;
+        self
+    }
+
+    pub fn with_execution_config(mut self, execution_config: OnChainExecutionConfig) -> Self {
+        self.genesis_config.execution_config = execution_config;
+        self
+    }
+
+    pub fn with_gas_schedule(mut self, gas_schedule: GasScheduleV2) -> Self {
+        self.genesis_config.gas_schedule = gas_schedule;
+        self
+    }
+
+    pub fn with_features(mut self, features: Features) -> Self {
+        self.genesis_config.initial_features_override = Some(features);
+        self
+    }
+
+    pub fn with_randomness_config(mut self, randomness_config: OnChainRandomnessConfig) -> Self {
+        self.genesis_config.randomness_config_override = Some(randomness_config);
+        self
+    }
+
+    pub fn with_jwk_consensus_config(
+        mut self,
+        jwk_consensus_config: OnChainJWKConsensusConfig,
+    ) -> Self {
+        self.genesis_config.jwk_consensus_config_override = Some(jwk_consensus_config);
+        self
+    }
+
+    /// Generates the genesis transaction and its waypoint
+    pub fn build(self) -> anyhow::Result<(Transaction, Waypoint)> {
+        ensure!(
+            !self.validators.is_empty(),
+            "Genesis requires at least one validator"
+        );
+
+        match self.root_key {
+            Some(root_key) => {
+                ensure!(
+                    self.accounts.is_empty() && self.employee_vesting_accounts.is_empty(),
+                    "Initial accounts are only supported without a root key, fund them with the root account after genesis instead"
+                );
+                ensure!(
+                    self.validators.iter().all(|v| v.join_during_genesis),
+                    "All validators must join during genesis when using a root key"
+                );
+                let validators = self.validators.into_iter().map(|v| v.validator).collect();
+                let mut genesis_info = GenesisInfo::new_with_validators(
+                    self.chain_id,
+                    root_key,
+                    validators,
+                    self.framework,
+                    &self.genesis_config,
+                );
+                let waypoint = genesis_info.generate_waypoint()?;
+                Ok((genesis_info.get_genesis().clone(), waypoint))
+            },
+            None => {
+                let mut genesis_config = self.genesis_config;
+                genesis_config.is_test = false;
+                if self.employee_vesting_accounts.is_empty() {
+                    // Unused without employee pools, but still required by the genesis module.
+                    genesis_config.employee_vesting_start.get_or_insert(0);
+                    genesis_config
+                        .employee_vesting_period_duration
+                        .get_or_insert(1);
+                } else {
+                    ensure!(
+                        genesis_config.employee_vesting_start.is_some()
+                            && genesis_config.employee_vesting_period_duration.is_some(),
+                        "Employee vesting start and period duration must be set for employee vesting accounts"
+                    );
+                }
+                let mut genesis_info = MainnetGenesisInfo::new_with_validators(
+                    self.chain_id,
+                    self.accounts,
+                    self.employee_vesting_accounts,
+                    self.validators,
+                    self.framework,
+                    &genesis_config,
+                )?;
+                let waypoint = genesis_info.generate_waypoint()?;
+                Ok((genesis_info.get_genesis().clone(), waypoint))
+            },
+        }
+    }
+
+    /// Generates the genesis transaction and writes it as a BCS encoded `genesis.blob` style file
+    pub fn build_to_file(self, path: &Path) -> anyhow::Result<Waypoint> {
+        let (genesis, waypoint) = self.build()?;
+        let mut file = File::create(path)?;
+        file.write_all(&bcs::to_bytes(&genesis)?)?;
+        Ok(waypoint)
+    }
+}
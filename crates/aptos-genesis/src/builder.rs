@@ -447,6 +447,36 @@ pub struct GenesisConfiguration {
     pub keyless_groth16_vk: Option<Groth16VerificationKey>,
 }
 
+impl GenesisConfiguration {
+    /// Defaults used for local and test networks: short lockups, no minimum stake and a fixed
+    /// validator set.
+    pub fn default_for_test() -> Self {
+        Self {
+            allow_new_validators: false,
+            epoch_duration_secs: ONE_DAY,
+            is_test: true,
+            min_stake: 0,
+            min_voting_threshold: 0,
+            max_stake: u64::MAX,
+            recurring_lockup_duration_secs: ONE_DAY,
+            required_proposer_stake: 0,
+            rewards_apy_percentage: 10,
+            voting_duration_secs: ONE_DAY / 24,
+            voting_power_increase_limit: 50,
+            employee_vesting_start: None,
+            employee_vesting_period_duration: None,
+            consensus_config: OnChainConsensusConfig::default_for_genesis(),
+            execution_config: OnChainExecutionConfig::default_for_genesis(),
+            gas_schedule: default_gas_schedule(),
+            initial_features_override: None,
+            randomness_config_override: None,
+            jwk_consensus_config_override: None,
+            initial_jwks: vec![],
+            keyless_groth16_vk: None,
+        }
+    }
+}
+
 pub type InitConfigFn = Arc<dyn Fn(usize, &mut NodeConfig, &mut NodeConfig) + Send + Sync>;
 pub type InitGenesisStakeFn = Arc<dyn Fn(usize, &mut u64) + Send + Sync>;
 pub type InitGenesisConfigFn = Arc<dyn Fn(&mut GenesisConfiguration) + Send + Sync>;
@@ -646,29 +676,7 @@ impl Builder {
             configs.push(validator.try_into()?);
         }
 
-        let mut genesis_config = GenesisConfiguration {
-            allow_new_validators: false,
-            epoch_duration_secs: ONE_DAY,
-            is_test: true,
-            min_stake: 0,
-            min_voting_threshold: 0,
-            max_stake: u64::MAX,
-            recurring_lockup_duration_secs: ONE_DAY,
-            required_proposer_stake: 0,
-            rewards_apy_percentage: 10,
-            voting_duration_secs: ONE_DAY / 24,
-            voting_power_increase_limit: 50,
-            employee_vesting_start: None,
-            employee_vesting_period_duration: None,
-            consensus_config: OnChainConsensusConfig::default_for_genesis(),
-            execution_config: OnChainExecutionConfig::default_for_genesis(),
-            gas_schedule: default_gas_schedule(),
-            initial_features_override: None,
-            randomness_config_override: None,
-            jwk_consensus_config_override: None,
-            initial_jwks: vec![],
-            keyless_groth16_vk: None,
-        };
+        let mut genesis_config = GenesisConfiguration::default_for_test();
         if let Some(init_genesis_config) = &self.init_genesis_config {
             (init_genesis_config)(&mut genesis_config);
         }
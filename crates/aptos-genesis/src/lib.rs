@@ -3,6 +3,7 @@
 
 #![forbid(unsafe_code)]
 
+pub mod blob_builder;
 pub mod builder;
 pub mod config;
 pub mod keys;
@@ -96,7 +97,25 @@ impl GenesisInfo {
             validators.push(config.try_into()?)
         }
 
-        Ok(GenesisInfo {
+        Ok(Self::new_with_validators(
+            chain_id,
+            root_key,
+            validators,
+            framework,
+            genesis_config,
+        ))
+    }
+
+    /// Same as [`GenesisInfo::new`], for validators that have already been converted, e.g. when
+    /// they are assembled programmatically rather than read from configuration files.
+    pub fn new_with_validators(
+        chain_id: ChainId,
+        root_key: Ed25519PublicKey,
+        validators: Vec<Validator>,
+        framework: ReleaseBundle,
+        genesis_config: &GenesisConfiguration,
+    ) -> GenesisInfo {
+        GenesisInfo {
             chain_id,
             root_key,
             validators,
@@ -121,7 +140,7 @@ impl GenesisInfo {
             jwk_consensus_config_override: genesis_config.jwk_consensus_config_override.clone(),
             initial_jwks: genesis_config.initial_jwks.clone(),
             keyless_groth16_vk: genesis_config.keyless_groth16_vk.clone(),
-        })
+        }
     }
 
     pub fn get_genesis(&mut self) -> &Transaction {
@@ -12,7 +12,10 @@ use aptos_storage_interface::DbReaderWriter;
 use aptos_temppath::TempPath;
 use aptos_types::{
     chain_id::ChainId,
-    on_chain_config::{Features, OnChainJWKConsensusConfig, OnChainRandomnessConfig},
+    on_chain_config::{
+        Features, GasScheduleV2, OnChainConsensusConfig, OnChainExecutionConfig,
+        OnChainJWKConsensusConfig, OnChainRandomnessConfig,
+    },
     transaction::Transaction,
     waypoint::Waypoint,
 };
@@ -48,6 +51,10 @@ pub struct MainnetGenesisInfo {
     /// Percent of current epoch's total voting power that can be added in this epoch.
     pub voting_power_increase_limit: u64,
 
+    pub consensus_config: OnChainConsensusConfig,
+    pub execution_config: OnChainExecutionConfig,
+    pub gas_schedule: GasScheduleV2,
+
     // MAINNET SPECIFIC FIELDS.
     /// Initial accounts and balances.
     accounts: Vec<AccountBalance>,
@@ -75,6 +82,30 @@ impl MainnetGenesisInfo {
         validators: Vec<ValidatorConfiguration>,
         framework: ReleaseBundle,
         genesis_config: &GenesisConfiguration,
+    ) -> anyhow::Result<MainnetGenesisInfo> {
+        let validators = validators
+            .into_iter()
+            .map(ValidatorWithCommissionRate::try_from)
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        Self::new_with_validators(
+            chain_id,
+            accounts,
+            employee_vesting_accounts,
+            validators,
+            framework,
+            genesis_config,
+        )
+    }
+
+    /// Same as [`MainnetGenesisInfo::new`], for validators that have already been converted,
+    /// e.g. when they are assembled programmatically rather than read from configuration files.
+    pub fn new_with_validators(
+        chain_id: ChainId,
+        accounts: Vec<AccountBalance>,
+        employee_vesting_accounts: Vec<EmployeePool>,
+        validators: Vec<ValidatorWithCommissionRate>,
+        framework: ReleaseBundle,
+        genesis_config: &GenesisConfiguration,
     ) -> anyhow::Result<MainnetGenesisInfo> {
         let employee_vesting_start = genesis_config
             .employee_vesting_start
@@ -87,10 +118,7 @@ impl MainnetGenesisInfo {
             chain_id,
             accounts,
             employee_vesting_accounts,
-            validators: validators
-                .into_iter()
-                .map(|v| ValidatorWithCommissionRate::try_from(v).unwrap())
-                .collect(),
+            validators,
             framework,
             genesis: None,
             epoch_duration_secs: genesis_config.epoch_duration_secs,
@@ -102,6 +130,9 @@ impl MainnetGenesisInfo {
             rewards_apy_percentage: genesis_config.rewards_apy_percentage,
             voting_duration_secs: genesis_config.voting_duration_secs,
             voting_power_increase_limit: genesis_config.voting_power_increase_limit,
+            consensus_config: genesis_config.consensus_config.clone(),
+            execution_config: genesis_config.execution_config.clone(),
+            gas_schedule: genesis_config.gas_schedule.clone(),
             employee_vesting_start,
             employee_vesting_period_duration,
             initial_features_override: genesis_config.initial_features_override.clone(),
@@ -146,6 +177,9 @@ impl MainnetGenesisInfo {
                 initial_jwks: vec![],
                 keyless_groth16_vk: None,
             },
+            &self.consensus_config,
+            &self.execution_config,
+            &self.gas_schedule,
         )
     }
 
@@ -2,12 +2,16 @@
 // Parts of the project are originally copyright © Meta Platforms, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+mod lock_profiling;
 mod math;
 mod mutex;
 mod nonzero;
 mod rwlock;
 mod time;
 
+pub use lock_profiling::{
+    is_enabled as is_lock_profiling_enabled, lock_contention, reset_lock_contention, LockContention,
+};
 pub use math::ArithmeticError;
 pub use mutex::{Mutex, MutexGuard};
 pub use rwlock::{RwLock, RwLockReadGuard, RwLockWriteGuard};
@@ -0,0 +1,120 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Contention statistics for the locks in this crate, to help find locks that are
+//! held for too long when debugging stalls.
+//!
+//! Statistics are only recorded when the crate is built with the `lock-profiling`
+//! feature. Every acquisition that has to wait for another holder is attributed to
+//! the call site of `lock()`, `read()` or `write()`. Uncontended acquisitions don't
+//! pay anything beyond a `try_lock()`.
+
+use std::{collections::BTreeMap, time::Duration};
+#[cfg(feature = "lock-profiling")]
+use std::{panic::Location, sync::Mutex as StdMutex};
+
+/// The contention statistics of a single call site
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct LockContention {
+    /// The kind of acquisition, i.e., "mutex", "read" or "write"
+    pub kind: &'static str,
+    /// The source location of the acquisition, e.g., "src/lib.rs:10:5"
+    pub location: String,
+    /// The number of acquisitions that had to wait
+    pub num_contended: u64,
+    /// The total time spent waiting for the lock
+    pub total_wait: Duration,
+    /// The longest time spent waiting for the lock
+    pub max_wait: Duration,
+}
+
+type CallSite = (&'static str, &'static str, u32, u32); // (kind, file, line, column)
+
+#[cfg(feature = "lock-profiling")]
+static LOCK_CONTENTION: StdMutex<BTreeMap<CallSite, LockContention>> =
+    StdMutex::new(BTreeMap::new());
+
+/// Returns true iff lock contention is being recorded
+pub const fn is_enabled() -> bool {
+    cfg!(feature = "lock-profiling")
+}
+
+/// Returns the contention statistics of all call sites that had to wait
+/// for a lock, sorted by the total time spent waiting (longest first).
+pub fn lock_contention() -> Vec<LockContention> {
+    #[cfg(feature = "lock-profiling")]
+    let contention: BTreeMap<CallSite, LockContention> = LOCK_CONTENTION
+        .lock()
+        .unwrap_or_else(|error| error.into_inner())
+        .clone();
+    #[cfg(not(feature = "lock-profiling"))]
+    let contention: BTreeMap<CallSite, LockContention> = BTreeMap::new();
+
+    let mut contention: Vec<_> = contention.into_values().collect();
+    contention.sort_by(|a, b| b.total_wait.cmp(&a.total_wait));
+    contention
+}
+
+/// Clears all recorded contention statistics
+pub fn reset_lock_contention() {
+    #[cfg(feature = "lock-profiling")]
+    LOCK_CONTENTION
+        .lock()
+        .unwrap_or_else(|error| error.into_inner())
+        .clear();
+}
+
+/// Records that an acquisition at the given location waited for the given duration
+#[cfg(feature = "lock-profiling")]
+pub(crate) fn record_contention(
+    kind: &'static str,
+    location: &'static Location<'static>,
+    wait: Duration,
+) {
+    let call_site = (kind, location.file(), location.line(), location.column());
+    let mut contention = LOCK_CONTENTION
+        .lock()
+        .unwrap_or_else(|error| error.into_inner());
+    let entry = contention
+        .entry(call_site)
+        .or_insert_with(|| LockContention {
+            kind,
+            location: location.to_string(),
+            ..Default::default()
+        });
+    entry.num_contended += 1;
+    entry.total_wait += wait;
+    entry.max_wait = entry.max_wait.max(wait);
+}
+
+#[cfg(all(test, feature = "lock-profiling"))]
+mod tests {
+    use super::*;
+    use crate::Mutex;
+    use std::{sync::Arc, thread, time::Duration};
+
+    #[test]
+    fn test_record_contention() {
+        let mutex = Arc::new(Mutex::new(0u8));
+        let guard = mutex.lock();
+
+        // Contend on the lock from another thread while we hold it
+        let mutex_clone = mutex.clone();
+        let thread = thread::spawn(move || {
+            *mutex_clone.lock() += 1;
+        });
+        thread::sleep(Duration::from_millis(50));
+        drop(guard);
+        thread.join().unwrap();
+
+        // Verify the contention was attributed to the call site in this file
+        let contention = lock_contention();
+        let entry = contention
+            .iter()
+            .find(|entry| entry.location.contains("lock_profiling.rs"))
+            .unwrap();
+        assert_eq!(entry.kind, "mutex");
+        assert_eq!(entry.num_contended, 1);
+        assert!(entry.max_wait >= Duration::from_millis(10));
+    }
+}
@@ -17,7 +17,27 @@ impl<T> Mutex<T> {
     }
 
     /// lock the mutex
+    #[cfg_attr(feature = "lock-profiling", track_caller)]
     pub fn lock(&self) -> MutexGuard<'_, T> {
+        #[cfg(feature = "lock-profiling")]
+        match self.0.try_lock() {
+            Ok(guard) => return guard,
+            Err(std::sync::TryLockError::WouldBlock) => {
+                let start = std::time::Instant::now();
+                let guard = self
+                    .0
+                    .lock()
+                    .expect("Cannot currently handle a poisoned lock");
+                crate::lock_profiling::record_contention(
+                    "mutex",
+                    std::panic::Location::caller(),
+                    start.elapsed(),
+                );
+                return guard;
+            },
+            Err(std::sync::TryLockError::Poisoned(_)) => {},
+        }
+
         self.0
             .lock()
             .expect("Cannot currently handle a poisoned lock")
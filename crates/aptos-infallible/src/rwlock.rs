@@ -17,14 +17,54 @@ impl<T> RwLock<T> {
     }
 
     /// lock the rwlock in read mode
+    #[cfg_attr(feature = "lock-profiling", track_caller)]
     pub fn read(&self) -> RwLockReadGuard<'_, T> {
+        #[cfg(feature = "lock-profiling")]
+        match self.0.try_read() {
+            Ok(guard) => return guard,
+            Err(std::sync::TryLockError::WouldBlock) => {
+                let start = std::time::Instant::now();
+                let guard = self
+                    .0
+                    .read()
+                    .expect("Cannot currently handle a poisoned lock");
+                crate::lock_profiling::record_contention(
+                    "read",
+                    std::panic::Location::caller(),
+                    start.elapsed(),
+                );
+                return guard;
+            },
+            Err(std::sync::TryLockError::Poisoned(_)) => {},
+        }
+
         self.0
             .read()
             .expect("Cannot currently handle a poisoned lock")
     }
 
     /// lock the rwlock in write mode
+    #[cfg_attr(feature = "lock-profiling", track_caller)]
     pub fn write(&self) -> RwLockWriteGuard<'_, T> {
+        #[cfg(feature = "lock-profiling")]
+        match self.0.try_write() {
+            Ok(guard) => return guard,
+            Err(std::sync::TryLockError::WouldBlock) => {
+                let start = std::time::Instant::now();
+                let guard = self
+                    .0
+                    .write()
+                    .expect("Cannot currently handle a poisoned lock");
+                crate::lock_profiling::record_contention(
+                    "write",
+                    std::panic::Location::caller(),
+                    start.elapsed(),
+                );
+                return guard;
+            },
+            Err(std::sync::TryLockError::Poisoned(_)) => {},
+        }
+
         self.0
             .write()
             .expect("Cannot currently handle a poisoned lock")
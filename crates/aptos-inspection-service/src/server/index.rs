@@ -3,8 +3,9 @@
 
 use crate::{
     server::utils::CONTENT_TYPE_TEXT, CONFIGURATION_PATH, CONSENSUS_HEALTH_CHECK_PATH,
-    FORGE_METRICS_PATH, IDENTITY_INFORMATION_PATH, JSON_METRICS_PATH, METRICS_PATH,
-    PEER_INFORMATION_PATH, SYSTEM_INFORMATION_PATH,
+    FORGE_METRICS_PATH, IDENTITY_INFORMATION_PATH, JSON_METRICS_PATH, LOCK_CONTENTION_PATH,
+    METRICS_PATH, PEER_INFORMATION_PATH, PEER_TOPOLOGY_PATH, RUNTIME_INFORMATION_PATH,
+    SYSTEM_INFORMATION_PATH, TASK_DUMP_PATH, USAGE_HISTORY_PATH,
 };
 use hyper::{Body, StatusCode};
 
@@ -30,9 +31,14 @@ fn get_index_response() -> String {
     index_response.push(format!("\t- {}", FORGE_METRICS_PATH));
     index_response.push(format!("\t- {}", IDENTITY_INFORMATION_PATH));
     index_response.push(format!("\t- {}", JSON_METRICS_PATH));
+    index_response.push(format!("\t- {}", LOCK_CONTENTION_PATH));
     index_response.push(format!("\t- {}", METRICS_PATH));
     index_response.push(format!("\t- {}", PEER_INFORMATION_PATH));
+    index_response.push(format!("\t- {}", PEER_TOPOLOGY_PATH));
+    index_response.push(format!("\t- {}", RUNTIME_INFORMATION_PATH));
     index_response.push(format!("\t- {}", SYSTEM_INFORMATION_PATH));
+    index_response.push(format!("\t- {}", TASK_DUMP_PATH));
+    index_response.push(format!("\t- {}", USAGE_HISTORY_PATH));
 
     index_response.join("\n") // Separate each entry with a newline
 }
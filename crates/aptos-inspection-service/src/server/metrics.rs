@@ -64,13 +64,19 @@ pub fn handle_forge_metrics() -> (StatusCode, Body, String) {
 }
 
 /// Handles a new metrics request (with JSON encoding)
-pub fn handle_json_metrics_request() -> (StatusCode, Body, String) {
-    let buffer = utils::get_encoded_metrics(JsonEncoder);
+pub fn handle_json_metrics_request(node_config: &NodeConfig) -> (StatusCode, Body, String) {
+    let buffer = utils::get_encoded_metrics_with_cardinality_guard(
+        JsonEncoder,
+        &node_config.inspection_service,
+    );
     (StatusCode::OK, Body::from(buffer), CONTENT_TYPE_JSON.into())
 }
 
 /// Handles a new metrics request (with text encoding)
-pub fn handle_metrics_request() -> (StatusCode, Body, String) {
-    let buffer = utils::get_encoded_metrics(TextEncoder::new());
+pub fn handle_metrics_request(node_config: &NodeConfig) -> (StatusCode, Body, String) {
+    let buffer = utils::get_encoded_metrics_with_cardinality_guard(
+        TextEncoder::new(),
+        &node_config.inspection_service,
+    );
     (StatusCode::OK, Body::from(buffer), CONTENT_TYPE_TEXT.into())
 }
@@ -6,6 +6,7 @@ use aptos_config::config::NodeConfig;
 use aptos_data_client::client::AptosDataClient;
 use aptos_logger::debug;
 use aptos_network::application::storage::PeersAndMetadata;
+use aptos_storage_interface::DbReader;
 use hyper::{
     service::{make_service_fn, service_fn},
     Body, Method, Request, Response, Server, StatusCode,
@@ -23,7 +24,10 @@ mod index;
 mod json_encoder;
 mod metrics;
 mod peer_information;
+mod peer_topology;
+mod profiling_information;
 mod system_information;
+mod usage_history;
 pub mod utils;
 
 #[cfg(test)]
@@ -36,9 +40,14 @@ pub const FORGE_METRICS_PATH: &str = "/forge_metrics";
 pub const IDENTITY_INFORMATION_PATH: &str = "/identity_information";
 pub const INDEX_PATH: &str = "/";
 pub const JSON_METRICS_PATH: &str = "/json_metrics";
+pub const LOCK_CONTENTION_PATH: &str = "/lock_contention";
 pub const METRICS_PATH: &str = "/metrics";
 pub const PEER_INFORMATION_PATH: &str = "/peer_information";
+pub const PEER_TOPOLOGY_PATH: &str = "/peer_topology";
+pub const RUNTIME_INFORMATION_PATH: &str = "/runtime_information";
 pub const SYSTEM_INFORMATION_PATH: &str = "/system_information";
+pub const TASK_DUMP_PATH: &str = "/task_dump";
+pub const USAGE_HISTORY_PATH: &str = "/usage_history";
 
 // Useful string constants
 pub const HEADER_CONTENT_TYPE: &str = "Content-Type";
@@ -51,6 +60,7 @@ pub fn start_inspection_service(
     node_config: NodeConfig,
     aptos_data_client: AptosDataClient,
     peers_and_metadata: Arc<PeersAndMetadata>,
+    db_reader: Arc<dyn DbReader>,
 ) {
     // Fetch the service port and address
     let service_port = node_config.inspection_service.port;
@@ -78,6 +88,7 @@ pub fn start_inspection_service(
             let node_config = node_config.clone();
             let aptos_data_client = aptos_data_client.clone();
             let peers_and_metadata = peers_and_metadata.clone();
+            let db_reader = db_reader.clone();
             async move {
                 Ok::<_, Infallible>(service_fn(move |request| {
                     serve_requests(
@@ -85,6 +96,7 @@ pub fn start_inspection_service(
                         node_config.clone(),
                         aptos_data_client.clone(),
                         peers_and_metadata.clone(),
+                        db_reader.clone(),
                     )
                 }))
             }
@@ -106,6 +118,7 @@ async fn serve_requests(
     node_config: NodeConfig,
     aptos_data_client: AptosDataClient,
     peers_and_metadata: Arc<PeersAndMetadata>,
+    db_reader: Arc<dyn DbReader>,
 ) -> Result<Response<Body>, hyper::Error> {
     // Process the request and get the response components
     let (status_code, body, content_type) = match req.uri().path() {
@@ -137,12 +150,17 @@ async fn serve_requests(
         JSON_METRICS_PATH => {
             // /json_metrics
             // Exposes JSON encoded metrics
-            metrics::handle_json_metrics_request()
+            metrics::handle_json_metrics_request(&node_config)
+        },
+        LOCK_CONTENTION_PATH => {
+            // /lock_contention
+            // Exposes the call sites that had to wait for locks
+            profiling_information::handle_lock_contention_request(&node_config)
         },
         METRICS_PATH => {
             // /metrics
             // Exposes text encoded metrics
-            metrics::handle_metrics_request()
+            metrics::handle_metrics_request(&node_config)
         },
         PEER_INFORMATION_PATH => {
             // /peer_information
@@ -153,11 +171,35 @@ async fn serve_requests(
                 peers_and_metadata,
             )
         },
+        PEER_TOPOLOGY_PATH => {
+            // /peer_topology
+            // Exposes a topology snapshot of the connected peers
+            peer_topology::handle_peer_topology_request(
+                &node_config,
+                req.uri().query(),
+                peers_and_metadata,
+            )
+        },
+        RUNTIME_INFORMATION_PATH => {
+            // /runtime_information
+            // Exposes the tasks and queue depths of the tokio runtimes
+            profiling_information::handle_runtime_information_request(&node_config)
+        },
         SYSTEM_INFORMATION_PATH => {
             // /system_information
             // Exposes the system and build information
             system_information::handle_system_information_request(node_config)
         },
+        TASK_DUMP_PATH => {
+            // /task_dump
+            // Exposes the backtraces of all tokio tasks
+            profiling_information::handle_task_dump_request(&node_config).await
+        },
+        USAGE_HISTORY_PATH => {
+            // /usage_history
+            // Exposes the state storage usage over time
+            usage_history::handle_usage_history_request(req.uri().query(), db_reader)
+        },
         _ => {
             // Handle the invalid path
             (
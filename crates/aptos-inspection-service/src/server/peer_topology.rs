@@ -0,0 +1,150 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::server::{
+    peer_information::PEER_INFO_DISABLED_MESSAGE,
+    utils::{CONTENT_TYPE_JSON, CONTENT_TYPE_TEXT},
+};
+use aptos_config::{config::NodeConfig, network_id::NetworkId};
+use aptos_network::application::storage::PeersAndMetadata;
+use hyper::{Body, StatusCode};
+use serde_json::{json, Value};
+use std::{collections::HashMap, str::FromStr, sync::Arc};
+
+/// Handles a new peer topology request, e.g.,
+/// /peer_topology?network=public&max_distance=2
+///
+/// All query parameters are optional. The snapshot can be filtered by the
+/// network of the peers and by their distance from the validators.
+pub fn handle_peer_topology_request(
+    node_config: &NodeConfig,
+    query: Option<&str>,
+    peers_and_metadata: Arc<PeersAndMetadata>,
+) -> (StatusCode, Body, String) {
+    // Only return the peer topology if the peer information endpoint is enabled
+    if !node_config.inspection_service.expose_peer_information {
+        return (
+            StatusCode::FORBIDDEN,
+            Body::from(PEER_INFO_DISABLED_MESSAGE),
+            CONTENT_TYPE_TEXT.into(),
+        );
+    }
+
+    match get_peer_topology_json(query, peers_and_metadata) {
+        Ok(peer_topology) => (
+            StatusCode::OK,
+            Body::from(peer_topology.to_string()),
+            CONTENT_TYPE_JSON.into(),
+        ),
+        Err(error) => (
+            StatusCode::BAD_REQUEST,
+            Body::from(format!("Failed to get the peer topology: {}", error)),
+            CONTENT_TYPE_TEXT.into(),
+        ),
+    }
+}
+
+/// Returns a topology snapshot of the connected peers as JSON. Each peer entry
+/// holds the distance from the validators, geo hint and latency reported by (or
+/// measured for) the peer, as well as the peers it is connected to. Together,
+/// the entries describe the edges of the node's view of the network tree.
+fn get_peer_topology_json(
+    query: Option<&str>,
+    peers_and_metadata: Arc<PeersAndMetadata>,
+) -> anyhow::Result<Value> {
+    // Parse the query parameters
+    let params: HashMap<&str, &str> = query
+        .unwrap_or_default()
+        .split('&')
+        .filter_map(|param| param.split_once('='))
+        .collect();
+    let network_filter = params
+        .get("network")
+        .map(|network| {
+            NetworkId::from_str(network)
+                .map_err(|error| anyhow::anyhow!("Invalid network: {}", error))
+        })
+        .transpose()?;
+    let max_distance = params
+        .get("max_distance")
+        .map(|value| {
+            value
+                .parse::<u64>()
+                .map_err(|error| anyhow::anyhow!("Invalid max_distance: {}", error))
+        })
+        .transpose()?;
+
+    // Get all peers (sorted by peer ID)
+    let mut all_peers = peers_and_metadata.get_all_peers();
+    all_peers.sort();
+
+    // Create the topology entry for each peer that matches the filters
+    let mut peers = vec![];
+    for peer in all_peers {
+        if network_filter.is_some_and(|network_id| network_id != peer.network_id()) {
+            continue;
+        }
+        let Ok(peer_metadata) = peers_and_metadata.get_metadata_for_peer(peer) else {
+            continue;
+        };
+        let peer_monitoring_metadata = peer_metadata.get_peer_monitoring_metadata();
+
+        // Filter the peer by its distance from the validators (if known)
+        let network_info_response = &peer_monitoring_metadata.latest_network_info_response;
+        let distance_from_validators = network_info_response
+            .as_ref()
+            .map(|response| response.distance_from_validators);
+        if let Some(max_distance) = max_distance {
+            if distance_from_validators.is_none_or(|distance| distance > max_distance) {
+                continue;
+            }
+        }
+
+        // Collect the peers that the peer is connected to
+        let connected_peers: Vec<Value> = network_info_response
+            .iter()
+            .flat_map(|response| response.connected_peers.iter())
+            .map(|(connected_peer, connection_metadata)| {
+                json!({
+                    "network_id": connected_peer.network_id().to_string(),
+                    "peer_id": connected_peer.peer_id().to_string(),
+                    "peer_role": connection_metadata.peer_role.to_string(),
+                    "network_address": connection_metadata.network_address.to_string(),
+                })
+            })
+            .collect();
+
+        // Create the topology entry for the peer
+        let connection_metadata = peer_metadata.get_connection_metadata();
+        peers.push(json!({
+            "network_id": peer.network_id().to_string(),
+            "peer_id": peer.peer_id().to_string(),
+            "peer_role": connection_metadata.role.to_string(),
+            "network_address": connection_metadata.addr.to_string(),
+            "connection_state": format!("{:?}", peer_metadata.get_connection_state()),
+            "distance_from_validators": distance_from_validators,
+            "geo_hint": peer_monitoring_metadata
+                .latest_node_info_response
+                .as_ref()
+                .and_then(|response| response.get_geo_hint()),
+            "average_ping_latency_secs": peer_monitoring_metadata.average_ping_latency_secs,
+            "latest_ping_latency_secs": peer_monitoring_metadata.latest_ping_latency_secs,
+            "connected_peers": connected_peers,
+        }));
+    }
+
+    Ok(json!({
+        "num_peers": peers.len(),
+        "num_edges": count_edges(&peers),
+        "peers": peers,
+    }))
+}
+
+/// Returns the total number of edges (i.e., peer connections) in the snapshot
+fn count_edges(peers: &[Value]) -> usize {
+    peers
+        .iter()
+        .filter_map(|peer| peer["connected_peers"].as_array())
+        .map(|connected_peers| connected_peers.len())
+        .sum()
+}
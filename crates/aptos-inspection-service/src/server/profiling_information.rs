@@ -0,0 +1,159 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::server::utils::{CONTENT_TYPE_JSON, CONTENT_TYPE_TEXT};
+use aptos_config::config::NodeConfig;
+use hyper::{Body, StatusCode};
+use serde_json::{json, Value};
+
+// The message to display when the profiling information endpoints are disabled
+pub const PROFILING_INFO_DISABLED_MESSAGE: &str =
+    "This endpoint is disabled! Enable it in the node config at inspection_service.expose_profiling_information: true";
+
+// The message to display when task dumps are not supported by the build
+pub const TASK_DUMP_UNSUPPORTED_MESSAGE: &str =
+    "Task dumps are not supported by this build! Rebuild the node (on Linux) with RUSTFLAGS=\"--cfg tokio_unstable --cfg tokio_taskdump\"";
+
+/// Handles a new lock contention request
+pub fn handle_lock_contention_request(node_config: &NodeConfig) -> (StatusCode, Body, String) {
+    if !node_config.inspection_service.expose_profiling_information {
+        return profiling_info_disabled_response();
+    }
+
+    (
+        StatusCode::OK,
+        Body::from(get_lock_contention_json().to_string()),
+        CONTENT_TYPE_JSON.into(),
+    )
+}
+
+/// Handles a new runtime information request
+pub fn handle_runtime_information_request(node_config: &NodeConfig) -> (StatusCode, Body, String) {
+    if !node_config.inspection_service.expose_profiling_information {
+        return profiling_info_disabled_response();
+    }
+
+    (
+        StatusCode::OK,
+        Body::from(get_runtime_information_json().to_string()),
+        CONTENT_TYPE_JSON.into(),
+    )
+}
+
+/// Handles a new task dump request
+pub async fn handle_task_dump_request(node_config: &NodeConfig) -> (StatusCode, Body, String) {
+    if !node_config.inspection_service.expose_profiling_information {
+        return profiling_info_disabled_response();
+    }
+
+    match get_task_dump().await {
+        Some(task_dump) => (
+            StatusCode::OK,
+            Body::from(task_dump),
+            CONTENT_TYPE_TEXT.into(),
+        ),
+        None => (
+            StatusCode::NOT_IMPLEMENTED,
+            Body::from(TASK_DUMP_UNSUPPORTED_MESSAGE),
+            CONTENT_TYPE_TEXT.into(),
+        ),
+    }
+}
+
+/// Returns the response for when the profiling information endpoints are disabled
+fn profiling_info_disabled_response() -> (StatusCode, Body, String) {
+    (
+        StatusCode::FORBIDDEN,
+        Body::from(PROFILING_INFO_DISABLED_MESSAGE),
+        CONTENT_TYPE_TEXT.into(),
+    )
+}
+
+/// Returns the lock contention statistics, i.e., the call sites that had to
+/// wait for a lock (and for how long), sorted by the total time spent waiting.
+fn get_lock_contention_json() -> Value {
+    let lock_contention: Vec<Value> = aptos_infallible::lock_contention()
+        .into_iter()
+        .map(|contention| {
+            json!({
+                "kind": contention.kind,
+                "location": contention.location,
+                "num_contended": contention.num_contended,
+                "total_wait_us": contention.total_wait.as_micros() as u64,
+                "max_wait_us": contention.max_wait.as_micros() as u64,
+            })
+        })
+        .collect();
+
+    json!({
+        "enabled": aptos_infallible::is_lock_profiling_enabled(),
+        "lock_contention": lock_contention,
+    })
+}
+
+/// Returns the number of workers, tasks and the queue depths of each runtime
+fn get_runtime_information_json() -> Value {
+    let runtimes: Vec<Value> = aptos_runtimes::named_runtime_handles()
+        .into_iter()
+        .map(|(name, handle)| {
+            let metrics = handle.metrics();
+            #[allow(unused_mut)]
+            let mut runtime_information = json!({
+                "name": name,
+                "num_workers": metrics.num_workers(),
+                "num_alive_tasks": metrics.num_alive_tasks(),
+                "global_queue_depth": metrics.global_queue_depth(),
+            });
+
+            // Add the metrics that are only available with tokio_unstable
+            #[cfg(tokio_unstable)]
+            {
+                let worker_local_queue_depths: Vec<usize> = (0..metrics.num_workers())
+                    .map(|worker| metrics.worker_local_queue_depth(worker))
+                    .collect();
+                runtime_information["worker_local_queue_depths"] = json!(worker_local_queue_depths);
+                runtime_information["num_blocking_threads"] = json!(metrics.num_blocking_threads());
+                runtime_information["num_idle_blocking_threads"] =
+                    json!(metrics.num_idle_blocking_threads());
+                runtime_information["blocking_queue_depth"] = json!(metrics.blocking_queue_depth());
+            }
+
+            runtime_information
+        })
+        .collect();
+
+    json!({ "runtimes": runtimes })
+}
+
+/// Returns the backtraces of all tasks in each runtime, or None if
+/// task dumps are not supported by this build.
+#[cfg(all(tokio_unstable, tokio_taskdump))]
+async fn get_task_dump() -> Option<String> {
+    // Tasks are only traced once they yield, so a runtime with blocked
+    // workers would never finish the dump. Don't wait forever on those.
+    const TASK_DUMP_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(5);
+
+    let mut task_dump = Vec::new();
+    for (name, handle) in aptos_runtimes::named_runtime_handles() {
+        match tokio::time::timeout(TASK_DUMP_TIMEOUT, handle.dump()).await {
+            Ok(dump) => {
+                let tasks = dump.tasks().iter().collect::<Vec<_>>();
+                task_dump.push(format!("Runtime {} ({} tasks):", name, tasks.len()));
+                for (index, task) in tasks.iter().enumerate() {
+                    task_dump.push(format!("Task {}:\n{}\n", index, task.trace()));
+                }
+            },
+            Err(_) => task_dump.push(format!(
+                "Runtime {}: timed out after {:?}! Are all workers blocked?\n",
+                name, TASK_DUMP_TIMEOUT
+            )),
+        }
+    }
+
+    Some(task_dump.join("\n"))
+}
+
+#[cfg(not(all(tokio_unstable, tokio_taskdump)))]
+async fn get_task_dump() -> Option<String> {
+    None
+}
@@ -5,25 +5,29 @@ use crate::{
     server::{
         configuration::CONFIGURATION_DISABLED_MESSAGE,
         identity_information::IDENTITY_INFO_DISABLED_MESSAGE,
-        peer_information::PEER_INFO_DISABLED_MESSAGE, serve_requests,
+        peer_information::PEER_INFO_DISABLED_MESSAGE,
+        profiling_information::PROFILING_INFO_DISABLED_MESSAGE, serve_requests,
         system_information::SYS_INFO_DISABLED_MESSAGE, utils::get_all_metrics,
     },
     CONFIGURATION_PATH, FORGE_METRICS_PATH, IDENTITY_INFORMATION_PATH, INDEX_PATH,
-    JSON_METRICS_PATH, METRICS_PATH, PEER_INFORMATION_PATH, SYSTEM_INFORMATION_PATH,
+    JSON_METRICS_PATH, LOCK_CONTENTION_PATH, METRICS_PATH, PEER_INFORMATION_PATH,
+    PEER_TOPOLOGY_PATH, RUNTIME_INFORMATION_PATH, SYSTEM_INFORMATION_PATH, TASK_DUMP_PATH,
+    USAGE_HISTORY_PATH,
 };
 use aptos_config::config::{AptosDataClientConfig, BaseConfig, Identity, NodeConfig};
 use aptos_data_client::client::AptosDataClient;
 use aptos_network::application::{interface::NetworkClient, storage::PeersAndMetadata};
-use aptos_storage_interface::DbReader;
+use aptos_storage_interface::{DbReader, Result};
 use aptos_storage_service_client::StorageServiceClient;
 use aptos_time_service::TimeService;
+use aptos_types::{state_store::state_storage_usage::StateStorageUsage, transaction::Version};
 use assert_approx_eq::assert_approx_eq;
 use futures::executor::block_on;
 use hyper::{body, Body, Method, Request, Response, StatusCode};
 use once_cell::sync::Lazy;
 use prometheus::{proto::MetricFamily, register_int_counter, Counter, IntCounter, Opts, Registry};
 use rusty_fork::rusty_fork_test;
-use std::{collections::HashMap, io::read_to_string, string::String, sync::Arc};
+use std::{collections::HashMap, io::read_to_string, ops::Range, string::String, sync::Arc};
 
 // This metrics counter only exists in this test context; the rest of the
 // system's metrics counters don't exist, so we need to add this for tests.
@@ -90,9 +94,14 @@ async fn test_inspect_index() {
     assert!(response_body_string.contains(FORGE_METRICS_PATH));
     assert!(response_body_string.contains(IDENTITY_INFORMATION_PATH));
     assert!(response_body_string.contains(JSON_METRICS_PATH));
+    assert!(response_body_string.contains(LOCK_CONTENTION_PATH));
     assert!(response_body_string.contains(METRICS_PATH));
     assert!(response_body_string.contains(PEER_INFORMATION_PATH));
+    assert!(response_body_string.contains(PEER_TOPOLOGY_PATH));
+    assert!(response_body_string.contains(RUNTIME_INFORMATION_PATH));
     assert!(response_body_string.contains(SYSTEM_INFORMATION_PATH));
+    assert!(response_body_string.contains(TASK_DUMP_PATH));
+    assert!(response_body_string.contains(USAGE_HISTORY_PATH));
 }
 
 #[tokio::test]
@@ -159,6 +168,50 @@ async fn test_inspect_metrics() {
     assert!(response_body_string.contains(INT_COUNTER_NAME));
 }
 
+#[tokio::test]
+async fn test_inspect_profiling_information() {
+    // Create a validator node config
+    let mut config = NodeConfig::get_default_validator_config();
+
+    // Disable the profiling information endpoints and ping them
+    config.inspection_service.expose_profiling_information = false;
+    for path in [
+        LOCK_CONTENTION_PATH,
+        RUNTIME_INFORMATION_PATH,
+        TASK_DUMP_PATH,
+    ] {
+        let mut response = send_get_request_to_path(&config, path).await;
+        let response_body = body::to_bytes(response.body_mut()).await.unwrap();
+
+        // Verify that the response contains an error
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+        assert_eq!(response_body, PROFILING_INFO_DISABLED_MESSAGE);
+    }
+
+    // Spawn a named runtime so that there is something to inspect
+    let runtime = aptos_runtimes::spawn_named_runtime("inspected".into(), Some(2));
+
+    // Enable the profiling information endpoints and ping the runtime information
+    config.inspection_service.expose_profiling_information = true;
+    let mut response = send_get_request_to_path(&config, RUNTIME_INFORMATION_PATH).await;
+    let response_body = body::to_bytes(response.body_mut()).await.unwrap();
+    let response_body_string = read_to_string(response_body.as_ref()).unwrap();
+
+    // Verify that the response contains the expected information
+    assert_eq!(response.status(), StatusCode::OK);
+    assert!(response_body_string.contains("inspected"));
+    assert!(response_body_string.contains("global_queue_depth"));
+
+    // Ping the lock contention endpoint and verify the response
+    let mut response = send_get_request_to_path(&config, LOCK_CONTENTION_PATH).await;
+    let response_body = body::to_bytes(response.body_mut()).await.unwrap();
+    let response_body_string = read_to_string(response_body.as_ref()).unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert!(response_body_string.contains("lock_contention"));
+
+    runtime.shutdown_background();
+}
+
 #[tokio::test]
 async fn test_inspect_system_information() {
     // Create a validator node config
@@ -214,6 +267,79 @@ async fn test_inspect_peer_information() {
     assert!(response_body_string.contains("State sync metadata"));
 }
 
+#[tokio::test]
+async fn test_inspect_peer_topology() {
+    // Create a validator node config
+    let mut config = NodeConfig::get_default_validator_config();
+
+    // Disable the peer information endpoint and ping the topology endpoint
+    config.inspection_service.expose_peer_information = false;
+    let mut response = send_get_request_to_path(&config, PEER_TOPOLOGY_PATH).await;
+    let response_body = body::to_bytes(response.body_mut()).await.unwrap();
+
+    // Verify that the response contains an error
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    assert_eq!(response_body, PEER_INFO_DISABLED_MESSAGE);
+
+    // Enable the peer information endpoint and ping the topology endpoint
+    config.inspection_service.expose_peer_information = true;
+    let path = format!("{}?network=public&max_distance=2", PEER_TOPOLOGY_PATH);
+    let mut response = send_get_request_to_path(&config, &path).await;
+    let response_body = body::to_bytes(response.body_mut()).await.unwrap();
+    let peer_topology: serde_json::Value = serde_json::from_slice(&response_body).unwrap();
+
+    // Verify that the response contains the (empty) topology
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(peer_topology["num_peers"], 0);
+    assert_eq!(peer_topology["peers"], serde_json::json!([]));
+
+    // Ping the topology endpoint with an invalid filter and verify the error
+    let path = format!("{}?max_distance=far", PEER_TOPOLOGY_PATH);
+    let response = send_get_request_to_path(&config, &path).await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_inspect_usage_history() {
+    // Create a validator node config
+    let config = NodeConfig::get_default_validator_config();
+
+    // Ping the usage history endpoint with a range and granularity
+    let path = format!(
+        "{}?start_version=10&end_version=50&granularity=20",
+        USAGE_HISTORY_PATH
+    );
+    let mut response = send_get_request_to_path(&config, &path).await;
+    let response_body = body::to_bytes(response.body_mut()).await.unwrap();
+    let usage_history: serde_json::Value = serde_json::from_slice(&response_body).unwrap();
+
+    // Verify that the response contains the expected samples
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        usage_history["usage_history"],
+        serde_json::json!([
+            {"version": 10, "items": 10, "bytes": 100},
+            {"version": 30, "items": 30, "bytes": 300},
+        ])
+    );
+
+    // Ping the endpoint without any parameters and verify all versions are sampled
+    let mut response = send_get_request_to_path(&config, USAGE_HISTORY_PATH).await;
+    let response_body = body::to_bytes(response.body_mut()).await.unwrap();
+    let usage_history: serde_json::Value = serde_json::from_slice(&response_body).unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(usage_history["granularity"], 1);
+    assert_eq!(
+        usage_history["usage_history"].as_array().unwrap().len(),
+        MOCK_SYNCED_VERSION as usize + 1
+    );
+
+    // Ping the endpoint with an invalid parameter and verify the error
+    let path = format!("{}?granularity=invalid", USAGE_HISTORY_PATH);
+    let response = send_get_request_to_path(&config, &path).await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
+
 rusty_fork_test! {
 #[test]
 fn test_gather_metrics() {
@@ -310,11 +436,33 @@ async fn send_get_request_to_path(config: &NodeConfig, endpoint: &str) -> Respon
         config.clone(),
         aptos_data_client,
         peers_and_metadata,
+        Arc::new(MockDatabaseReader {}),
     )
     .await
     .unwrap()
 }
 
+// The synced version reported by the mock database reader
+const MOCK_SYNCED_VERSION: Version = 99;
+
 /// A simple mock database reader
 pub struct MockDatabaseReader {}
-impl DbReader for MockDatabaseReader {}
+impl DbReader for MockDatabaseReader {
+    fn get_synced_version(&self) -> Result<Option<Version>> {
+        Ok(Some(MOCK_SYNCED_VERSION))
+    }
+
+    fn get_usage_history(
+        &self,
+        range: Range<Version>,
+        granularity: u64,
+    ) -> Result<Vec<(Version, StateStorageUsage)>> {
+        Ok(range
+            .step_by(granularity as usize)
+            .map(|version| {
+                let items = version as usize;
+                (version, StateStorageUsage::new(items, items * 10))
+            })
+            .collect())
+    }
+}
@@ -0,0 +1,90 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::server::utils::{CONTENT_TYPE_JSON, CONTENT_TYPE_TEXT};
+use aptos_storage_interface::DbReader;
+use hyper::{Body, StatusCode};
+use serde_json::{json, Value};
+use std::{collections::HashMap, sync::Arc};
+
+// The number of samples returned if the granularity is not specified
+const DEFAULT_NUM_SAMPLES: u64 = 1000;
+
+/// Handles a new usage history request, e.g.,
+/// /usage_history?start_version=0&end_version=1000000&granularity=10000
+///
+/// All query parameters are optional. The range defaults to all versions
+/// synced so far, and the granularity to one that returns about a thousand
+/// samples.
+pub fn handle_usage_history_request(
+    query: Option<&str>,
+    db_reader: Arc<dyn DbReader>,
+) -> (StatusCode, Body, String) {
+    match get_usage_history_json(query, db_reader) {
+        Ok(usage_history) => (
+            StatusCode::OK,
+            Body::from(usage_history.to_string()),
+            CONTENT_TYPE_JSON.into(),
+        ),
+        Err(error) => (
+            StatusCode::BAD_REQUEST,
+            Body::from(format!("Failed to get the usage history: {}", error)),
+            CONTENT_TYPE_TEXT.into(),
+        ),
+    }
+}
+
+/// Returns the state storage usage history as JSON
+fn get_usage_history_json(
+    query: Option<&str>,
+    db_reader: Arc<dyn DbReader>,
+) -> anyhow::Result<Value> {
+    // Parse the query parameters
+    let params: HashMap<&str, &str> = query
+        .unwrap_or_default()
+        .split('&')
+        .filter_map(|param| param.split_once('='))
+        .collect();
+    let parse_param = |name: &str| -> anyhow::Result<Option<u64>> {
+        params
+            .get(name)
+            .map(|value| {
+                value
+                    .parse::<u64>()
+                    .map_err(|error| anyhow::anyhow!("Invalid {}: {}", name, error))
+            })
+            .transpose()
+    };
+
+    // Identify the range and granularity of the samples
+    let start_version = parse_param("start_version")?.unwrap_or(0);
+    let end_version = match parse_param("end_version")? {
+        Some(end_version) => end_version,
+        None => db_reader
+            .get_synced_version()?
+            .map_or(0, |version| version + 1),
+    };
+    let granularity = match parse_param("granularity")? {
+        Some(granularity) => granularity,
+        None => (end_version.saturating_sub(start_version) / DEFAULT_NUM_SAMPLES).max(1),
+    };
+
+    // Fetch the usage history, skipping untracked usage
+    let usage_history: Vec<Value> = db_reader
+        .get_usage_history(start_version..end_version, granularity)?
+        .into_iter()
+        .filter(|(_, usage)| !usage.is_untracked())
+        .map(|(version, usage)| {
+            json!({
+                "version": version,
+                "items": usage.items(),
+                "bytes": usage.bytes(),
+            })
+        })
+        .collect();
+
+    Ok(json!({
+        "granularity": granularity,
+        "usage_history": usage_history,
+    }))
+}
@@ -1,14 +1,15 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
-use aptos_logger::{error, warn};
-use aptos_metrics_core::{register_int_counter_vec, IntCounterVec};
+use aptos_config::config::InspectionServiceConfig;
+use aptos_logger::{error, sample, sample::SampleRate, warn};
+use aptos_metrics_core::{cardinality::CardinalityGuard, register_int_counter_vec, IntCounterVec};
 use once_cell::sync::Lazy;
 use prometheus::{
     proto::{MetricFamily, MetricType},
     Encoder,
 };
-use std::collections::HashMap;
+use std::{collections::HashMap, time::Duration};
 
 // Useful string constants
 pub const CONTENT_TYPE_JSON: &str = "application/json";
@@ -30,8 +31,49 @@ pub fn get_all_metrics() -> HashMap<String, String> {
 
 /// A simple utility function that encodes the metrics using the given encoder
 pub fn get_encoded_metrics(encoder: impl Encoder) -> Vec<u8> {
-    // Gather and encode the metrics
     let metric_families = get_metric_families();
+    encode_metrics(encoder, metric_families)
+}
+
+/// Encodes the metrics using the given encoder, after truncating the metric
+/// families with too many series (as configured), for export to Prometheus.
+pub fn get_encoded_metrics_with_cardinality_guard(
+    encoder: impl Encoder,
+    inspection_service_config: &InspectionServiceConfig,
+) -> Vec<u8> {
+    let mut metric_families = get_metric_families();
+
+    // Truncate the offending metric families
+    let cardinality_guard = CardinalityGuard::new(
+        inspection_service_config.metrics_max_series_per_family,
+        inspection_service_config
+            .metrics_cardinality_allowlist
+            .iter()
+            .cloned(),
+    );
+    let truncated_families = cardinality_guard.apply(&mut metric_families);
+    for (name, num_series) in &truncated_families {
+        sample!(
+            SampleRate::Duration(Duration::from_secs(600)),
+            warn!(
+                count = num_series,
+                metric_family = name,
+                "Metric family '{}' has {} series, truncated to {}! Add it to the metrics_cardinality_allowlist if this is expected.",
+                name,
+                num_series,
+                inspection_service_config.metrics_max_series_per_family
+            )
+        );
+    }
+    NUM_METRICS
+        .with_label_values(&["truncated_families"])
+        .inc_by(truncated_families.len() as u64);
+
+    encode_metrics(encoder, metric_families)
+}
+
+/// Encodes the given metric families using the given encoder
+fn encode_metrics(encoder: impl Encoder, metric_families: Vec<MetricFamily>) -> Vec<u8> {
     let mut encoded_buffer = vec![];
     if let Err(error) = encoder.encode(&metric_families, &mut encoded_buffer) {
         error!("Failed to encode metrics! Error: {}", error);
@@ -4,6 +4,7 @@
 use crate::{
     jwk_manager::IssuerLevelConsensusManager,
     jwk_manager_per_key::KeyLevelConsensusManager,
+    jwk_provider::providers_from_configs,
     network::{IncomingRpcRequest, NetworkReceivers, NetworkSender},
     network_interface::JWKConsensusNetworkClient,
     types::JWKConsensusMsg,
@@ -26,11 +27,10 @@ use aptos_safety_rules::{safety_rules_manager::storage, PersistentSafetyStorage}
 use aptos_types::{
     account_address::AccountAddress,
     epoch_state::EpochState,
-    jwks,
     jwks::{ObservedJWKs, ObservedJWKsUpdated, SupportedOIDCProviders},
     on_chain_config::{
-        FeatureFlag, Features, OnChainConfigPayload, OnChainConfigProvider, OnChainConsensusConfig,
-        OnChainJWKConsensusConfig, ValidatorSet,
+        FeatureFlag, Features, OIDCProvider, OIDCProviderV2, OnChainConfigPayload,
+        OnChainConfigProvider, OnChainConsensusConfig, OnChainJWKConsensusConfig, ValidatorSet,
     },
 };
 use aptos_validator_transaction_pool::VTxnPoolState;
@@ -174,22 +174,24 @@ impl<P: OnChainConfigProvider> EpochManager<P> {
         let onchain_observed_jwks = payload.get::<ObservedJWKs>().ok();
         let onchain_consensus_config = payload.get::<OnChainConsensusConfig>().unwrap_or_default();
 
-        let (jwk_manager_should_run, oidc_providers) = match jwk_consensus_config {
+        let (jwk_manager_should_run, provider_configs) = match jwk_consensus_config {
             Ok(config) => {
                 let should_run =
                     config.jwk_consensus_enabled() && onchain_consensus_config.is_vtxn_enabled();
-                let providers = config
-                    .oidc_providers_cloned()
-                    .into_iter()
-                    .map(jwks::OIDCProvider::from)
-                    .collect();
-                (should_run, Some(SupportedOIDCProviders { providers }))
+                (should_run, config.oidc_providers_v2_cloned())
             },
             Err(_) => {
                 //TODO: remove this case once the framework change of this commit is published.
                 let should_run = features.is_enabled(FeatureFlag::JWK_CONSENSUS)
                     && onchain_consensus_config.is_vtxn_enabled();
-                let providers = payload.get::<SupportedOIDCProviders>().ok();
+                let providers = payload
+                    .get::<SupportedOIDCProviders>()
+                    .unwrap_or_default()
+                    .into_provider_vec()
+                    .into_iter()
+                    .filter_map(|provider| OIDCProvider::try_from(provider).ok())
+                    .map(OIDCProviderV2::from)
+                    .collect();
                 (should_run, providers)
             },
         };
@@ -245,7 +247,7 @@ impl<P: OnChainConfigProvider> EpochManager<P> {
                     ))
                 };
             tokio::spawn(jwk_consensus_manager.run(
-                oidc_providers,
+                providers_from_configs(provider_configs),
                 onchain_observed_jwks,
                 jwk_event_rx,
                 jwk_rpc_msg_rx,
@@ -3,6 +3,7 @@
 
 use crate::{
     jwk_observer::JWKObserver,
+    jwk_provider::TJWKProvider,
     mode::per_issuer::PerIssuerMode,
     network::IncomingRpcRequest,
     types::{
@@ -15,13 +16,13 @@ use crate::{
 use anyhow::{anyhow, bail, Context, Result};
 use aptos_channels::{aptos_channel, message_queues::QueueStyle};
 use aptos_crypto::{bls12381::PrivateKey, SigningKey};
-use aptos_logger::{debug, error, info, warn};
+use aptos_logger::{debug, error, info};
 use aptos_types::{
     account_address::AccountAddress,
     epoch_state::EpochState,
     jwks::{
-        jwk::JWKMoveStruct, AllProvidersJWKs, Issuer, OIDCProvider, ObservedJWKs,
-        ObservedJWKsUpdated, ProviderJWKs, QuorumCertifiedUpdate, SupportedOIDCProviders,
+        jwk::JWKMoveStruct, AllProvidersJWKs, Issuer, ObservedJWKs, ObservedJWKsUpdated,
+        ProviderJWKs, QuorumCertifiedUpdate,
     },
     validator_txn::{Topic, ValidatorTransaction},
 };
@@ -31,7 +32,6 @@ use futures_util::{future::join_all, FutureExt, StreamExt};
 use std::{
     collections::{HashMap, HashSet},
     sync::Arc,
-    time::Duration,
 };
 
 /// `JWKManager` executes per-issuer JWK consensus sessions
@@ -89,7 +89,7 @@ impl IssuerLevelConsensusManager {
 impl TConsensusManager for IssuerLevelConsensusManager {
     async fn run(
         self: Box<Self>,
-        oidc_providers: Option<SupportedOIDCProviders>,
+        jwk_providers: Vec<Arc<dyn TJWKProvider>>,
         observed_jwks: Option<ObservedJWKs>,
         mut jwk_updated_rx: aptos_channel::Receiver<(), ObservedJWKsUpdated>,
         mut rpc_req_rx: aptos_channel::Receiver<
@@ -105,31 +105,15 @@ impl TConsensusManager for IssuerLevelConsensusManager {
         let (local_observation_tx, mut local_observation_rx) =
             aptos_channel::new(QueueStyle::KLAST, 100, None);
 
-        this.jwk_observers = oidc_providers
-            .unwrap_or_default()
-            .into_provider_vec()
+        this.jwk_observers = jwk_providers
             .into_iter()
-            .filter_map(|provider| {
-                let OIDCProvider { name, config_url } = provider;
-                let maybe_issuer = String::from_utf8(name);
-                let maybe_config_url = String::from_utf8(config_url);
-                match (maybe_issuer, maybe_config_url) {
-                    (Ok(issuer), Ok(config_url)) => Some(JWKObserver::spawn(
-                        this.epoch_state.epoch,
-                        this.my_addr,
-                        issuer,
-                        config_url,
-                        Duration::from_secs(10),
-                        local_observation_tx.clone(),
-                    )),
-                    (maybe_issuer, maybe_config_url) => {
-                        warn!(
-                            "unable to spawn observer, issuer={:?}, config_url={:?}",
-                            maybe_issuer, maybe_config_url
-                        );
-                        None
-                    },
-                }
+            .map(|provider| {
+                JWKObserver::spawn(
+                    this.epoch_state.epoch,
+                    this.my_addr,
+                    provider,
+                    local_observation_tx.clone(),
+                )
             })
             .collect();
 
@@ -1,15 +1,13 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::counters::OBSERVATION_SECONDS;
-use anyhow::{anyhow, Result};
+use crate::{counters::OBSERVATION_SECONDS, jwk_provider::TJWKProvider};
 use aptos_channels::aptos_channel;
-use aptos_jwk_utils::{fetch_jwks_from_jwks_uri, fetch_jwks_uri_from_openid_config};
 use aptos_logger::{debug, info};
 use aptos_types::jwks::{jwk::JWK, Issuer};
 use futures::{FutureExt, StreamExt};
 use move_core_types::account_address::AccountAddress;
-use std::time::{Duration, Instant};
+use std::{sync::Arc, time::Instant};
 use tokio::{sync::oneshot, task::JoinHandle, time::MissedTickBehavior};
 
 /// A process thread that periodically fetch JWKs of a provider and push it back to JWKManager.
@@ -22,24 +20,17 @@ impl JWKObserver {
     pub fn spawn(
         epoch: u64,
         my_addr: AccountAddress,
-        issuer: String,
-        config_url: String,
-        fetch_interval: Duration,
+        provider: Arc<dyn TJWKProvider>,
         observation_tx: aptos_channel::Sender<(), (Issuer, Vec<JWK>)>,
     ) -> Self {
+        let issuer = String::from_utf8_lossy(&provider.issuer()).to_string();
+        let fetch_interval = provider.fetch_interval();
         let (close_tx, close_rx) = oneshot::channel();
-        let join_handle = tokio::spawn(Self::start(
-            fetch_interval,
-            my_addr,
-            issuer.clone(),
-            config_url.clone(),
-            observation_tx,
-            close_rx,
-        ));
+        let join_handle = tokio::spawn(Self::start(my_addr, provider, observation_tx, close_rx));
         info!(
             epoch = epoch,
             issuer = issuer,
-            config_url = config_url,
+            fetch_interval_secs = fetch_interval.as_secs(),
             "JWKObserver spawned."
         );
         Self {
@@ -49,14 +40,14 @@ impl JWKObserver {
     }
 
     async fn start(
-        fetch_interval: Duration,
         my_addr: AccountAddress,
-        issuer: String,
-        open_id_config_url: String,
+        provider: Arc<dyn TJWKProvider>,
         observation_tx: aptos_channel::Sender<(), (Issuer, Vec<JWK>)>,
         close_rx: oneshot::Receiver<()>,
     ) {
-        let mut interval = tokio::time::interval(fetch_interval);
+        let issuer_bytes = provider.issuer();
+        let issuer = String::from_utf8_lossy(&issuer_bytes).to_string();
+        let mut interval = tokio::time::interval(provider.fetch_interval());
         interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
         let mut close_rx = close_rx.into_stream();
         let my_addr = if cfg!(feature = "smoke-test") {
@@ -71,13 +62,13 @@ impl JWKObserver {
             tokio::select! {
                 _ = interval.tick().fuse() => {
                     let timer = Instant::now();
-                    let result = fetch_jwks(open_id_config_url.as_str(), my_addr).await;
+                    let result = provider.fetch_jwks(my_addr).await;
                     debug!(issuer = issuer, "observe_result={:?}", result);
                     let secs = timer.elapsed().as_secs_f64();
                     if let Ok(mut jwks) = result {
                         OBSERVATION_SECONDS.with_label_values(&[issuer.as_str(), "ok"]).observe(secs);
                         jwks.sort();
-                        let _ = observation_tx.push((), (issuer.as_bytes().to_vec(), jwks));
+                        let _ = observation_tx.push((), (issuer_bytes.clone(), jwks));
                     } else {
                         OBSERVATION_SECONDS.with_label_values(&[issuer.as_str(), "err"]).observe(secs);
                     }
@@ -98,13 +89,3 @@ impl JWKObserver {
         let _ = join_handle.await;
     }
 }
-
-async fn fetch_jwks(open_id_config_url: &str, my_addr: Option<AccountAddress>) -> Result<Vec<JWK>> {
-    let jwks_uri = fetch_jwks_uri_from_openid_config(open_id_config_url)
-        .await
-        .map_err(|e| anyhow!("fetch_jwks failed with open-id config request: {e}"))?;
-    let jwks = fetch_jwks_from_jwks_uri(my_addr, jwks_uri.as_str())
-        .await
-        .map_err(|e| anyhow!("fetch_jwks failed with jwks uri request: {e}"))?;
-    Ok(jwks)
-}
@@ -0,0 +1,206 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::{anyhow, bail, Result};
+use aptos_jwk_utils::{fetch_jwks_uri_from_openid_config, fetch_raw_jwks_from_jwks_uri};
+use aptos_logger::warn;
+use aptos_types::{
+    jwks::{jwk::JWK, Issuer},
+    on_chain_config::OIDCProviderV2,
+};
+use move_core_types::account_address::AccountAddress;
+use std::{sync::Arc, time::Duration};
+
+/// A source of JWKs for one issuer, periodically polled by a `JWKObserver`.
+#[async_trait::async_trait]
+pub trait TJWKProvider: Send + Sync {
+    fn issuer(&self) -> Issuer;
+
+    fn fetch_interval(&self) -> Duration;
+
+    /// Fetch the current JWKs of the issuer.
+    ///
+    /// `my_addr` is only given in smoke tests, see `fetch_jwks_from_jwks_uri`.
+    async fn fetch_jwks(&self, my_addr: Option<AccountAddress>) -> Result<Vec<JWK>>;
+}
+
+/// A provider that fetches JWKs the way its on-chain `OIDCProviderV2` describes,
+/// so that onboarding an issuer only takes a config change.
+#[derive(Debug)]
+pub struct OnChainConfiguredJWKProvider {
+    issuer: String,
+    config_url: String,
+    config_url_kind: u8,
+    fetch_interval: Duration,
+    default_alg: Option<String>,
+}
+
+impl OnChainConfiguredJWKProvider {
+    pub fn new(config: OIDCProviderV2) -> Result<Self> {
+        let OIDCProviderV2 {
+            name,
+            config_url,
+            config_url_kind,
+            fetch_interval_secs,
+            default_alg,
+        } = config;
+        if config_url_kind != OIDCProviderV2::CONFIG_URL_KIND_OPENID_CONFIGURATION
+            && config_url_kind != OIDCProviderV2::CONFIG_URL_KIND_JWKS
+        {
+            bail!("unknown config url kind {config_url_kind}");
+        }
+        if fetch_interval_secs < OIDCProviderV2::MIN_FETCH_INTERVAL_SECS {
+            bail!(
+                "fetch interval {fetch_interval_secs}s is shorter than the minimum {}s",
+                OIDCProviderV2::MIN_FETCH_INTERVAL_SECS
+            );
+        }
+        Ok(Self {
+            issuer: name,
+            config_url,
+            config_url_kind,
+            fetch_interval: Duration::from_secs(fetch_interval_secs),
+            default_alg: (!default_alg.is_empty()).then_some(default_alg),
+        })
+    }
+
+    /// Fill in `alg` for keys that don't carry one, if the provider is configured to.
+    fn apply_default_alg(&self, mut key: serde_json::Value) -> serde_json::Value {
+        if let (Some(default_alg), Some(object)) = (&self.default_alg, key.as_object_mut()) {
+            object
+                .entry("alg")
+                .or_insert_with(|| serde_json::Value::String(default_alg.clone()));
+        }
+        key
+    }
+}
+
+#[async_trait::async_trait]
+impl TJWKProvider for OnChainConfiguredJWKProvider {
+    fn issuer(&self) -> Issuer {
+        self.issuer.as_bytes().to_vec()
+    }
+
+    fn fetch_interval(&self) -> Duration {
+        self.fetch_interval
+    }
+
+    async fn fetch_jwks(&self, my_addr: Option<AccountAddress>) -> Result<Vec<JWK>> {
+        let jwks_uri = if self.config_url_kind == OIDCProviderV2::CONFIG_URL_KIND_JWKS {
+            self.config_url.clone()
+        } else {
+            fetch_jwks_uri_from_openid_config(self.config_url.as_str())
+                .await
+                .map_err(|e| anyhow!("fetch_jwks failed with open-id config request: {e}"))?
+        };
+        let keys = fetch_raw_jwks_from_jwks_uri(my_addr, jwks_uri.as_str())
+            .await
+            .map_err(|e| anyhow!("fetch_jwks failed with jwks uri request: {e}"))?;
+        Ok(keys
+            .into_iter()
+            .map(|key| JWK::from(self.apply_default_alg(key)))
+            .collect())
+    }
+}
+
+/// Build the providers for the given on-chain configs, skipping the ones this node
+/// cannot handle, e.g. those with a config url kind added in a later release.
+pub fn providers_from_configs(configs: Vec<OIDCProviderV2>) -> Vec<Arc<dyn TJWKProvider>> {
+    configs
+        .into_iter()
+        .filter_map(|config| {
+            let name = config.name.clone();
+            match OnChainConfiguredJWKProvider::new(config) {
+                Ok(provider) => Some(Arc::new(provider) as Arc<dyn TJWKProvider>),
+                Err(e) => {
+                    warn!("unable to create jwk provider, issuer={}, err={}", name, e);
+                    None
+                },
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aptos_types::jwks::rsa::RSA_JWK;
+
+    fn new_provider(
+        config_url_kind: u8,
+        default_alg: &str,
+    ) -> Result<OnChainConfiguredJWKProvider> {
+        OnChainConfiguredJWKProvider::new(OIDCProviderV2 {
+            name: "https://alice.io".to_string(),
+            config_url: "https://alice.io/jwks".to_string(),
+            config_url_kind,
+            fetch_interval_secs: 30,
+            default_alg: default_alg.to_string(),
+        })
+    }
+
+    #[test]
+    fn default_alg_is_applied_to_keys_without_one() {
+        let key = serde_json::json!({"kty": "RSA", "kid": "kid1", "e": "AQAB", "n": "abcd"});
+
+        let provider = new_provider(OIDCProviderV2::CONFIG_URL_KIND_JWKS, "").unwrap();
+        assert!(matches!(
+            JWK::from(provider.apply_default_alg(key.clone())),
+            JWK::Unsupported(_)
+        ));
+
+        let provider = new_provider(OIDCProviderV2::CONFIG_URL_KIND_JWKS, "RS256").unwrap();
+        assert_eq!(
+            JWK::from(provider.apply_default_alg(key)),
+            JWK::RSA(RSA_JWK::new_from_strs(
+                "kid1", "RSA", "RS256", "AQAB", "abcd"
+            ))
+        );
+
+        // An existing `alg` is kept.
+        let key = serde_json::json!({"kty": "RSA", "kid": "kid1", "alg": "RS384", "e": "AQAB", "n": "abcd"});
+        assert_eq!(
+            JWK::from(provider.apply_default_alg(key)),
+            JWK::RSA(RSA_JWK::new_from_strs(
+                "kid1", "RSA", "RS384", "AQAB", "abcd"
+            ))
+        );
+    }
+
+    #[test]
+    fn unknown_config_url_kind_is_rejected() {
+        assert!(new_provider(2, "").is_err());
+        assert_eq!(
+            providers_from_configs(vec![OIDCProviderV2 {
+                name: "https://bob.dev".to_string(),
+                config_url: "https://bob.dev/.well-known/openid-configuration".to_string(),
+                config_url_kind: 7,
+                fetch_interval_secs: 10,
+                default_alg: String::new(),
+            }])
+            .len(),
+            0
+        );
+    }
+
+    #[test]
+    fn fetch_interval_below_minimum_is_rejected() {
+        let new_provider = |fetch_interval_secs| {
+            OnChainConfiguredJWKProvider::new(OIDCProviderV2 {
+                name: "https://alice.io".to_string(),
+                config_url: "https://alice.io/jwks".to_string(),
+                config_url_kind: OIDCProviderV2::CONFIG_URL_KIND_JWKS,
+                fetch_interval_secs,
+                default_alg: String::new(),
+            })
+        };
+        assert!(new_provider(0).is_err());
+        assert!(new_provider(OIDCProviderV2::MIN_FETCH_INTERVAL_SECS - 1).is_err());
+        assert_eq!(
+            new_provider(OIDCProviderV2::MIN_FETCH_INTERVAL_SECS)
+                .unwrap()
+                .fetch_interval(),
+            Duration::from_secs(OIDCProviderV2::MIN_FETCH_INTERVAL_SECS)
+        );
+    }
+}
@@ -3,6 +3,7 @@
 
 use crate::{
     epoch_manager::EpochManager,
+    jwk_provider::TJWKProvider,
     network::{IncomingRpcRequest, NetworkTask},
     network_interface::JWKConsensusNetworkClient,
     types::JWKConsensusMsg,
@@ -10,15 +11,16 @@ use crate::{
 use aptos_channels::aptos_channel;
 use aptos_config::config::SafetyRulesConfig;
 use aptos_event_notifications::{
-    DbBackedOnChainConfig, EventNotificationListener, ReconfigNotificationListener,
+    EventNotificationListener, InMemoryOnChainConfig, ReconfigNotificationListener,
 };
 use aptos_network::application::interface::{NetworkClient, NetworkServiceEvents};
 use aptos_types::{
     account_address::AccountAddress,
-    jwks::{ObservedJWKs, ObservedJWKsUpdated, SupportedOIDCProviders},
+    jwks::{ObservedJWKs, ObservedJWKsUpdated},
 };
 use aptos_validator_transaction_pool::VTxnPoolState;
 use futures_channel::oneshot;
+use std::sync::Arc;
 use tokio::runtime::Runtime;
 
 #[allow(clippy::let_and_return)]
@@ -27,7 +29,7 @@ pub fn start_jwk_consensus_runtime(
     safety_rules_config: &SafetyRulesConfig,
     network_client: NetworkClient<JWKConsensusMsg>,
     network_service_events: NetworkServiceEvents<JWKConsensusMsg>,
-    reconfig_events: ReconfigNotificationListener<DbBackedOnChainConfig>,
+    reconfig_events: ReconfigNotificationListener<InMemoryOnChainConfig>,
     jwk_updated_events: EventNotificationListener,
     vtxn_pool_writer: VTxnPoolState,
 ) -> Runtime {
@@ -54,6 +56,7 @@ pub mod epoch_manager;
 pub mod jwk_manager; //TODO: rename to issuer_level_consensus
 pub mod jwk_manager_per_key; //TODO: rename to key_level_consensus
 pub mod jwk_observer;
+pub mod jwk_provider;
 pub mod mode;
 pub mod network;
 pub mod network_interface;
@@ -65,7 +68,7 @@ pub mod update_certifier;
 trait TConsensusManager: Send + Sync {
     async fn run(
         self: Box<Self>,
-        oidc_providers: Option<SupportedOIDCProviders>,
+        jwk_providers: Vec<Arc<dyn TJWKProvider>>,
         observed_jwks: Option<ObservedJWKs>,
         mut jwk_updated_rx: aptos_channel::Receiver<(), ObservedJWKsUpdated>,
         mut rpc_req_rx: aptos_channel::Receiver<
@@ -18,11 +18,11 @@ use crate::{
     Event, Filter, Key, Level, LevelFilter, Metadata, ERROR_LOG_COUNT, INFO_LOG_COUNT,
     WARN_LOG_COUNT,
 };
-use aptos_infallible::RwLock;
+use aptos_infallible::{Mutex, RwLock};
 use backtrace::Backtrace;
 use chrono::{SecondsFormat, Utc};
 use futures::channel;
-use once_cell::sync::Lazy;
+use once_cell::sync::{Lazy, OnceCell};
 use serde::{ser::SerializeStruct, Serialize, Serializer};
 use std::{
     collections::BTreeMap,
@@ -33,7 +33,7 @@ use std::{
     str::FromStr,
     sync::{self, Arc},
     thread,
-    time::Duration,
+    time::{Duration, Instant},
 };
 use strum_macros::EnumString;
 use tokio::time;
@@ -298,6 +298,7 @@ impl LogEntry {
 pub struct AptosDataBuilder {
     channel_size: usize,
     tokio_console_port: Option<u16>,
+    otlp_trace_endpoint: Option<String>,
     enable_backtrace: bool,
     level: Level,
     remote_level: Level,
@@ -315,6 +316,7 @@ impl AptosDataBuilder {
         Self {
             channel_size: CHANNEL_SIZE,
             tokio_console_port: None,
+            otlp_trace_endpoint: None,
             enable_backtrace: false,
             level: Level::Info,
             remote_level: Level::Info,
@@ -362,6 +364,11 @@ impl AptosDataBuilder {
         self
     }
 
+    pub fn otlp_trace_endpoint(&mut self, otlp_trace_endpoint: Option<String>) -> &mut Self {
+        self.otlp_trace_endpoint = otlp_trace_endpoint;
+        self
+    }
+
     pub fn remote_log_tx(
         &mut self,
         remote_log_tx: channel::mpsc::Sender<TelemetryLog>,
@@ -423,6 +430,7 @@ impl AptosDataBuilder {
         FilterTuple {
             local_filter,
             telemetry_filter,
+            level_overrides: None,
         }
     }
 
@@ -449,6 +457,7 @@ impl AptosDataBuilder {
                 sender: Some(sender),
                 printer: None,
                 filter: RwLock::new(filter),
+                level_overrides: Mutex::new(Vec::new()),
                 enable_telemetry_flush: self.enable_telemetry_flush,
                 formatter: self.custom_format.take().unwrap_or(text_format),
             });
@@ -467,6 +476,7 @@ impl AptosDataBuilder {
                 sender: None,
                 printer: self.printer.take(),
                 filter: RwLock::new(filter),
+                level_overrides: Mutex::new(Vec::new()),
                 enable_telemetry_flush: self.enable_telemetry_flush,
                 formatter: self.custom_format.take().unwrap_or(text_format),
             })
@@ -482,22 +492,55 @@ impl AptosDataBuilder {
             None
         };
 
-        crate::logger::set_global_logger(logger.clone(), tokio_console_port);
+        crate::logger::set_global_logger(
+            logger.clone(),
+            tokio_console_port,
+            self.otlp_trace_endpoint.clone(),
+        );
+        let _ = GLOBAL_APTOS_DATA.set(logger.clone());
         logger
     }
 }
 
+/// The global `AptosData` (if it is the global `Logger`), so that log levels
+/// can be changed at runtime, e.g., by the admin service.
+static GLOBAL_APTOS_DATA: OnceCell<Arc<AptosData>> = OnceCell::new();
+
+/// A log level set at runtime for a module (or for all modules, if no module is
+/// given). Overrides take precedence over the local filter (i.e., `RUST_LOG` or
+/// the configured level) and are removed once they expire (if ever).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LogLevelOverride {
+    pub module: Option<String>,
+    pub level: LevelFilter,
+    pub expires_at: Option<Instant>,
+}
+
 /// A combination of `Filter`s to control where logs are written
 pub struct FilterTuple {
     /// The local printer `Filter` to control what is logged in text output
     local_filter: Filter,
     /// The logging `Filter` to control what is sent to telemetry service
     telemetry_filter: Filter,
+    /// The `Filter` built from the `LogLevelOverride`s, which takes precedence
+    /// over the local printer `Filter` (for the modules it has directives for)
+    level_overrides: Option<Filter>,
 }
 
 impl FilterTuple {
     fn enabled(&self, metadata: &Metadata) -> bool {
-        self.local_filter.enabled(metadata) || self.telemetry_filter.enabled(metadata)
+        self.local_enabled(metadata) || self.telemetry_filter.enabled(metadata)
+    }
+
+    fn local_enabled(&self, metadata: &Metadata) -> bool {
+        match self
+            .level_overrides
+            .as_ref()
+            .and_then(|level_overrides| level_overrides.level_for(metadata))
+        {
+            Some(level) => LevelFilter::from(metadata.level()) <= level,
+            None => self.local_filter.enabled(metadata),
+        }
     }
 }
 
@@ -506,6 +549,7 @@ pub struct AptosData {
     sender: Option<sync::mpsc::SyncSender<LoggerServiceEvent>>,
     printer: Option<Box<dyn Writer>>,
     filter: RwLock<FilterTuple>,
+    level_overrides: Mutex<Vec<LogLevelOverride>>,
     enable_telemetry_flush: bool,
     pub(crate) formatter: fn(&LogEntry) -> Result<String, fmt::Error>,
 }
@@ -536,8 +580,16 @@ impl AptosData {
         builder.build();
     }
 
-    pub fn set_filter(&self, filter_tuple: FilterTuple) {
-        *self.filter.write() = filter_tuple;
+    /// Returns the global `AptosData`, if the global `Logger` was built by `AptosDataBuilder`
+    pub fn global() -> Option<Arc<AptosData>> {
+        GLOBAL_APTOS_DATA.get().cloned()
+    }
+
+    pub fn set_filter(&self, mut filter_tuple: FilterTuple) {
+        // Keep the level overrides, as they don't come from the builder
+        let mut filter = self.filter.write();
+        filter_tuple.level_overrides = filter.level_overrides.take();
+        *filter = filter_tuple;
     }
 
     pub fn set_local_filter(&self, filter: Filter) {
@@ -548,6 +600,87 @@ impl AptosData {
         self.filter.write().telemetry_filter = filter;
     }
 
+    /// Sets the log level for the given module (or for all modules, if none is given),
+    /// replacing any previous override for it. If a duration is given, the override is
+    /// removed again after it, e.g., to temporarily elevate the log level of a module.
+    pub fn set_level_override(
+        self: &Arc<Self>,
+        module: Option<&str>,
+        level: LevelFilter,
+        duration: Option<Duration>,
+    ) {
+        {
+            let mut level_overrides = self.level_overrides.lock();
+            level_overrides.retain(|level_override| level_override.module.as_deref() != module);
+            level_overrides.push(LogLevelOverride {
+                module: module.map(Into::into),
+                level,
+                expires_at: duration.map(|duration| Instant::now() + duration),
+            });
+            self.apply_level_overrides(&level_overrides);
+        }
+
+        if let Some(duration) = duration {
+            let logger = Arc::downgrade(self);
+            thread::spawn(move || {
+                thread::sleep(duration);
+                if let Some(logger) = logger.upgrade() {
+                    logger.remove_expired_level_overrides();
+                }
+            });
+        }
+    }
+
+    /// Removes the log level override for the given module (or the one for all modules,
+    /// if none is given). Returns true iff there was such an override.
+    pub fn remove_level_override(&self, module: Option<&str>) -> bool {
+        let mut level_overrides = self.level_overrides.lock();
+        let num_level_overrides = level_overrides.len();
+        level_overrides.retain(|level_override| level_override.module.as_deref() != module);
+        self.apply_level_overrides(&level_overrides);
+        level_overrides.len() != num_level_overrides
+    }
+
+    /// Removes all log level overrides
+    pub fn clear_level_overrides(&self) {
+        let mut level_overrides = self.level_overrides.lock();
+        level_overrides.clear();
+        self.apply_level_overrides(&level_overrides);
+    }
+
+    /// Returns the log level overrides that are currently in effect
+    pub fn level_overrides(&self) -> Vec<LogLevelOverride> {
+        self.remove_expired_level_overrides();
+        self.level_overrides.lock().clone()
+    }
+
+    fn remove_expired_level_overrides(&self) {
+        let now = Instant::now();
+        let mut level_overrides = self.level_overrides.lock();
+        let num_level_overrides = level_overrides.len();
+        level_overrides.retain(|level_override| {
+            level_override
+                .expires_at
+                .is_none_or(|expires_at| expires_at > now)
+        });
+        if level_overrides.len() != num_level_overrides {
+            self.apply_level_overrides(&level_overrides);
+        }
+    }
+
+    fn apply_level_overrides(&self, level_overrides: &[LogLevelOverride]) {
+        let filter = if level_overrides.is_empty() {
+            None
+        } else {
+            let mut filter_builder = Filter::builder();
+            for level_override in level_overrides {
+                filter_builder.filter(level_override.module.as_deref(), level_override.level);
+            }
+            Some(filter_builder.build())
+        };
+        self.filter.write().level_overrides = filter;
+    }
+
     fn send_entry(&self, entry: LogEntry) {
         if let Some(printer) = &self.printer {
             let s = (self.formatter)(&entry).expect("Unable to format");
@@ -628,13 +761,7 @@ impl LoggerService {
                     }
 
                     if let Some(printer) = &mut self.printer {
-                        if self
-                            .facade
-                            .filter
-                            .read()
-                            .local_filter
-                            .enabled(&entry.metadata)
-                        {
+                        if self.facade.filter.read().local_enabled(&entry.metadata) {
                             let s = (self.facade.formatter)(&entry).expect("Unable to format");
                             printer.write_buferred(s);
                         }
@@ -830,8 +957,8 @@ mod tests {
         debug, error, info,
         logger::Logger,
         telemetry_log_writer::TelemetryLog,
-        trace, warn, AptosDataBuilder, Event, Key, KeyValue, Level, LoggerFilterUpdater, Metadata,
-        Schema, Value, Visitor, Writer,
+        trace, warn, AptosDataBuilder, Event, Key, KeyValue, Level, LevelFilter,
+        LoggerFilterUpdater, Metadata, Schema, Value, Visitor, Writer,
     };
     use chrono::{DateTime, Utc};
     use futures::StreamExt;
@@ -925,7 +1052,7 @@ mod tests {
     fn set_test_logger() -> Receiver<LogEntry> {
         let (logger, receiver) = LogStream::new(true);
         let logger = Arc::new(logger);
-        crate::logger::set_global_logger(logger, None);
+        crate::logger::set_global_logger(logger, None, None);
         receiver
     }
 
@@ -1189,6 +1316,39 @@ mod tests {
             )));
     }
 
+    #[test]
+    fn test_level_overrides() {
+        let (logger_builder, logger) = new_async_logger();
+        let debug_metadata = &Metadata::new(Level::Debug, "target", "crate1::mod1", "source_path");
+        let other_debug_metadata = &Metadata::new(Level::Debug, "target", "crate2", "source_path");
+        assert!(!logger.filter.read().local_enabled(debug_metadata));
+
+        // Elevate the log level of a module and verify only that module is affected
+        logger.set_level_override(Some("crate1"), LevelFilter::Debug, None);
+        assert!(logger.filter.read().local_enabled(debug_metadata));
+        assert!(!logger.filter.read().local_enabled(other_debug_metadata));
+
+        // Verify the override survives rebuilding the filter
+        LoggerFilterUpdater::new(logger.clone(), logger_builder).update_filter();
+        assert!(logger.filter.read().local_enabled(debug_metadata));
+
+        // Remove the override and verify the module is back to the default level
+        assert!(logger.remove_level_override(Some("crate1")));
+        assert!(!logger.remove_level_override(Some("crate1")));
+        assert!(!logger.filter.read().local_enabled(debug_metadata));
+
+        // Temporarily elevate the log level and verify the override expires
+        logger.set_level_override(
+            Some("crate1"),
+            LevelFilter::Debug,
+            Some(Duration::from_millis(10)),
+        );
+        assert_eq!(logger.level_overrides().len(), 1);
+        thread::sleep(Duration::from_millis(50));
+        assert!(logger.level_overrides().is_empty());
+        assert!(!logger.filter.read().local_enabled(debug_metadata));
+    }
+
     #[test]
     fn test_log_event_truncation() {
         let log_entry = LogEntry::new(
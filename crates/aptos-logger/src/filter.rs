@@ -135,14 +135,21 @@ impl Filter {
     }
 
     pub fn enabled(&self, metadata: &Metadata) -> bool {
+        self.level_for(metadata)
+            .map(|level| LevelFilter::from(metadata.level()) <= level)
+            .unwrap_or(false)
+    }
+
+    /// Returns the level of the most specific directive matching the metadata, if any
+    pub fn level_for(&self, metadata: &Metadata) -> Option<LevelFilter> {
         // Search for the longest match, the vector is assumed to be pre-sorted.
         for directive in self.directives.iter().rev() {
             match &directive.name {
                 Some(name) if !metadata.module_path().starts_with(name) => {},
-                Some(..) | None => return LevelFilter::from(metadata.level()) <= directive.level,
+                Some(..) | None => return Some(directive.level),
             }
         }
-        false
+        None
     }
 }
 
@@ -258,6 +265,26 @@ mod tests {
         assert!(!logger.enabled(&make_metadata(Level::Debug, "crate2")));
     }
 
+    #[test]
+    fn level_for_longest_match() {
+        let filter = Builder::new()
+            .filter(Some("crate2"), LevelFilter::Info)
+            .filter(Some("crate2::mod"), LevelFilter::Debug)
+            .build();
+        assert_eq!(
+            filter.level_for(&make_metadata(Level::Info, "crate2::mod1")),
+            Some(LevelFilter::Debug)
+        );
+        assert_eq!(
+            filter.level_for(&make_metadata(Level::Info, "crate2")),
+            Some(LevelFilter::Info)
+        );
+        assert_eq!(
+            filter.level_for(&make_metadata(Level::Info, "crate3")),
+            None
+        );
+    }
+
     #[test]
     fn match_default() {
         let logger = Builder::new()
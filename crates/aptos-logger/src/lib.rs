@@ -151,6 +151,7 @@ mod kv;
 mod logger;
 mod macros;
 mod metadata;
+pub mod otel;
 pub mod sample;
 pub mod telemetry_log_writer;
 pub mod tracing_adapter;
@@ -158,7 +159,8 @@ pub mod tracing_adapter;
 mod security;
 
 pub use crate::aptos_logger::{
-    AptosData as Logger, AptosDataBuilder, LoggerFilterUpdater, Writer, CHANNEL_SIZE,
+    AptosData as Logger, AptosDataBuilder, LogLevelOverride, LoggerFilterUpdater, Writer,
+    CHANNEL_SIZE,
 };
 pub use aptos_log_derive::Schema;
 pub use event::Event;
@@ -41,7 +41,11 @@ pub(crate) fn enabled(metadata: &Metadata) -> bool {
 }
 
 /// Sets the global `Logger` exactly once
-pub fn set_global_logger(logger: Arc<dyn Logger>, tokio_console_port: Option<u16>) {
+pub fn set_global_logger(
+    logger: Arc<dyn Logger>,
+    tokio_console_port: Option<u16>,
+    otlp_trace_endpoint: Option<String>,
+) {
     if LOGGER.set(logger).is_err() {
         eprintln!("Global logger has already been set");
         error!("Global logger has already been set");
@@ -63,14 +67,33 @@ pub fn set_global_logger(logger: Arc<dyn Logger>, tokio_console_port: Option<u16
             return;
         }
     }
-    if tokio_console_port.is_none() {
-        let _ = tracing::subscriber::set_global_default(
-            crate::tracing_adapter::TracingToAptosDataLayer
-                .with_subscriber(tracing_subscriber::Registry::default()),
-        );
-    } else {
+    if tokio_console_port.is_some() {
         error!("tokio_console_port was set but has no effect! Build the crate with the 'tokio-console' feature enabled!");
+        return;
     }
+
+    // Spans are additionally exported to an OpenTelemetry collector, if configured.
+    #[cfg(feature = "otel")]
+    let otel_layer = otlp_trace_endpoint.and_then(|endpoint| match crate::otel::layer(&endpoint) {
+        Ok(layer) => Some(layer),
+        Err(e) => {
+            error!("Failed to set up OTLP trace export to {}: {}", endpoint, e);
+            None
+        },
+    });
+    #[cfg(not(feature = "otel"))]
+    let otel_layer: Option<tracing_subscriber::layer::Identity> = {
+        if otlp_trace_endpoint.is_some() {
+            error!("otlp_trace_endpoint was set but has no effect! Build the crate with the 'otel' feature enabled!");
+        }
+        None
+    };
+
+    let _ = tracing::subscriber::set_global_default(
+        tracing_subscriber::Registry::default()
+            .with(crate::tracing_adapter::TracingToAptosDataLayer)
+            .with(otel_layer),
+    );
 }
 
 /// Flush the global `Logger`. Note this is expensive, only use off the critical path.
@@ -78,4 +101,5 @@ pub fn flush() {
     if let Some(logger) = LOGGER.get() {
         logger.flush();
     }
+    crate::otel::flush();
 }
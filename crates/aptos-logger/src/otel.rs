@@ -0,0 +1,123 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Export of `tracing` spans to an OpenTelemetry collector over OTLP.
+//!
+//! Spans are exported only when the crate is built with the `otel` feature and an
+//! endpoint is configured, otherwise everything here is a no-op, so that callers can
+//! instrument their code unconditionally.
+
+use tracing::Span;
+
+/// Makes `span` part of the trace identified by the first 16 bytes of `trace_id`
+/// (zero padded), e.g. a block id. Spans created independently by different
+/// components for the same block or chunk thus end up in the same trace, without
+/// having to pass the span around. Must be called before any child span is created.
+pub fn set_trace_id(span: &Span, trace_id: &[u8]) {
+    #[cfg(feature = "otel")]
+    imp::set_trace_id(span, trace_id);
+    #[cfg(not(feature = "otel"))]
+    let _ = (span, trace_id);
+}
+
+/// Flushes the spans buffered for export, if any.
+pub(crate) fn flush() {
+    #[cfg(feature = "otel")]
+    imp::flush();
+}
+
+#[cfg(feature = "otel")]
+pub(crate) use imp::layer;
+
+#[cfg(feature = "otel")]
+mod imp {
+    use once_cell::sync::OnceCell;
+    use opentelemetry::{
+        trace::{
+            SpanContext, SpanId, TraceContextExt, TraceFlags, TraceId, TraceState,
+            TracerProvider as _,
+        },
+        Context, KeyValue,
+    };
+    use opentelemetry_otlp::{SpanExporter, WithExportConfig};
+    use opentelemetry_sdk::{runtime, trace::TracerProvider, Resource};
+    use tokio::runtime::Runtime;
+    use tracing::{Span, Subscriber};
+    use tracing_opentelemetry::OpenTelemetrySpanExt;
+    use tracing_subscriber::{filter::LevelFilter, registry::LookupSpan, Layer};
+
+    /// The logger is set up before any of the node's runtimes exist, so the batch
+    /// exporter gets a small runtime of its own.
+    static EXPORT_RUNTIME: OnceCell<Runtime> = OnceCell::new();
+
+    static TRACER_PROVIDER: OnceCell<TracerProvider> = OnceCell::new();
+
+    /// Builds a layer that exports INFO and above spans to the OTLP (gRPC) endpoint.
+    pub(crate) fn layer<S>(
+        endpoint: &str,
+    ) -> Result<impl Layer<S> + Send + Sync, Box<dyn std::error::Error>>
+    where
+        S: Subscriber + for<'span> LookupSpan<'span>,
+    {
+        let runtime = EXPORT_RUNTIME.get_or_try_init(|| {
+            tokio::runtime::Builder::new_multi_thread()
+                .worker_threads(1)
+                .thread_name("otel-export")
+                .enable_all()
+                .build()
+        })?;
+        let _guard = runtime.enter();
+
+        let exporter = SpanExporter::builder()
+            .with_tonic()
+            .with_endpoint(endpoint)
+            .build()?;
+        let service_name = std::env::current_exe()
+            .ok()
+            .and_then(|exe| exe.file_stem().map(|s| s.to_string_lossy().into_owned()))
+            .unwrap_or_else(|| "aptos".to_string());
+        let provider = TracerProvider::builder()
+            .with_batch_exporter(exporter, runtime::Tokio)
+            .with_resource(Resource::new(vec![KeyValue::new(
+                "service.name",
+                service_name,
+            )]))
+            .build();
+        let tracer = provider.tracer("aptos");
+        let _ = TRACER_PROVIDER.set(provider);
+
+        Ok(tracing_opentelemetry::layer()
+            .with_tracer(tracer)
+            .with_filter(LevelFilter::INFO))
+    }
+
+    pub(super) fn set_trace_id(span: &Span, trace_id: &[u8]) {
+        let mut bytes = [0u8; 16];
+        let len = trace_id.len().min(bytes.len());
+        bytes[..len].copy_from_slice(&trace_id[..len]);
+        let trace_id = TraceId::from_bytes(bytes);
+        if trace_id == TraceId::INVALID {
+            return;
+        }
+
+        // There is no actual span to be the parent, but the remote parent needs a
+        // valid span id, so we derive a stable one from the trace id.
+        let mut span_id = [0u8; 8];
+        span_id.copy_from_slice(&bytes[8..]);
+        span_id[0] |= 1;
+        let parent = SpanContext::new(
+            trace_id,
+            SpanId::from_bytes(span_id),
+            TraceFlags::SAMPLED,
+            true,
+            TraceState::default(),
+        );
+        span.set_parent(Context::new().with_remote_span_context(parent));
+    }
+
+    pub(super) fn flush() {
+        if let Some(provider) = TRACER_PROVIDER.get() {
+            let _ = provider.force_flush();
+        }
+    }
+}
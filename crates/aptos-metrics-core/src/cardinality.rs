@@ -0,0 +1,200 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use prometheus::proto::{LabelPair, Metric, MetricFamily, MetricType};
+use std::collections::HashSet;
+
+/// The label value of the series that aggregates all truncated series of a family
+pub const OVERFLOW_LABEL_VALUE: &str = "__overflow__";
+
+/// Guards against metric families with unbounded label cardinality (e.g., labels
+/// per peer or per key) when exporting metrics, so that a long-running node can't
+/// blow up the memory of the Prometheus server scraping it.
+///
+/// Families with more series than allowed keep their first series, and the rest are
+/// aggregated into a single series with all labels set to `OVERFLOW_LABEL_VALUE`.
+/// Counters, gauges and histograms are summed up, other types are simply dropped.
+/// Families in the allowlist are never truncated.
+#[derive(Clone, Debug)]
+pub struct CardinalityGuard {
+    max_series_per_family: usize,
+    allowlist: HashSet<String>,
+}
+
+impl CardinalityGuard {
+    /// Creates a new guard. A `max_series_per_family` of 0 disables the guard.
+    pub fn new(max_series_per_family: usize, allowlist: impl IntoIterator<Item = String>) -> Self {
+        Self {
+            max_series_per_family,
+            allowlist: allowlist.into_iter().collect(),
+        }
+    }
+
+    /// Truncates all offending families, and returns the names of the truncated
+    /// families together with their original number of series.
+    pub fn apply(&self, metric_families: &mut [MetricFamily]) -> Vec<(String, usize)> {
+        let mut truncated_families = vec![];
+        if self.max_series_per_family == 0 {
+            return truncated_families;
+        }
+
+        for metric_family in metric_families {
+            let num_series = metric_family.get_metric().len();
+            if num_series <= self.max_series_per_family
+                || self.allowlist.contains(metric_family.get_name())
+            {
+                continue;
+            }
+
+            self.truncate(metric_family);
+            truncated_families.push((metric_family.get_name().to_string(), num_series));
+        }
+
+        truncated_families
+    }
+
+    fn truncate(&self, metric_family: &mut MetricFamily) {
+        let metric_type = metric_family.get_field_type();
+        let mut metrics = metric_family.take_metric().into_vec();
+        let overflow = metrics.split_off(self.max_series_per_family - 1);
+        if let Some(overflow) = aggregate(metric_type, overflow) {
+            metrics.push(overflow);
+        }
+        metric_family.set_metric(metrics.into());
+    }
+}
+
+/// Aggregates the given series (of the same family) into a single overflow series
+fn aggregate(metric_type: MetricType, metrics: Vec<Metric>) -> Option<Metric> {
+    let mut metrics = metrics.into_iter();
+    let mut aggregate = metrics.next()?;
+
+    // Replace all label values, so that the series can't be mistaken for the original
+    let labels: Vec<LabelPair> = aggregate
+        .get_label()
+        .iter()
+        .map(|label| {
+            let mut label = label.clone();
+            label.set_value(OVERFLOW_LABEL_VALUE.into());
+            label
+        })
+        .collect();
+    aggregate.set_label(labels.into());
+
+    for metric in metrics {
+        match metric_type {
+            MetricType::COUNTER => {
+                let value = aggregate.get_counter().get_value() + metric.get_counter().get_value();
+                aggregate.mut_counter().set_value(value);
+            },
+            MetricType::GAUGE => {
+                let value = aggregate.get_gauge().get_value() + metric.get_gauge().get_value();
+                aggregate.mut_gauge().set_value(value);
+            },
+            MetricType::HISTOGRAM => {
+                let histogram = aggregate.mut_histogram();
+                let other = metric.get_histogram();
+                histogram.set_sample_count(histogram.get_sample_count() + other.get_sample_count());
+                histogram.set_sample_sum(histogram.get_sample_sum() + other.get_sample_sum());
+                // All series of a family have the same buckets
+                for (bucket, other_bucket) in
+                    histogram.mut_bucket().iter_mut().zip(other.get_bucket())
+                {
+                    bucket.set_cumulative_count(
+                        bucket.get_cumulative_count() + other_bucket.get_cumulative_count(),
+                    );
+                }
+            },
+            MetricType::SUMMARY | MetricType::UNTYPED => return None,
+        }
+    }
+
+    Some(aggregate)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CardinalityGuard, OVERFLOW_LABEL_VALUE};
+    use prometheus::{
+        histogram_opts, proto::MetricFamily, Encoder, HistogramVec, IntCounterVec, Opts, Registry,
+        TextEncoder,
+    };
+
+    fn gather_counter_family(num_series: usize) -> Vec<MetricFamily> {
+        let registry = Registry::new();
+        let counter =
+            IntCounterVec::new(Opts::new("test_counter", "A test counter"), &["peer"]).unwrap();
+        registry.register(Box::new(counter.clone())).unwrap();
+        for peer in 0..num_series {
+            counter.with_label_values(&[&peer.to_string()]).inc_by(2);
+        }
+        registry.gather()
+    }
+
+    #[test]
+    fn test_cardinality_guard_truncates() {
+        let mut metric_families = gather_counter_family(10);
+        let truncated = CardinalityGuard::new(4, vec![]).apply(&mut metric_families);
+        assert_eq!(truncated, vec![("test_counter".to_string(), 10)]);
+
+        // The first 3 series are kept, and the other 7 are summed up
+        let metrics = metric_families[0].get_metric();
+        assert_eq!(metrics.len(), 4);
+        let overflow = metrics.last().unwrap();
+        assert_eq!(overflow.get_label()[0].get_value(), OVERFLOW_LABEL_VALUE);
+        assert_eq!(overflow.get_counter().get_value(), 14.0);
+
+        // The truncated family can still be encoded
+        let mut buffer = vec![];
+        TextEncoder::new()
+            .encode(&metric_families, &mut buffer)
+            .unwrap();
+    }
+
+    #[test]
+    fn test_cardinality_guard_allowlist_and_limit() {
+        // Families within the limit are untouched
+        let mut metric_families = gather_counter_family(4);
+        assert!(CardinalityGuard::new(4, vec![])
+            .apply(&mut metric_families)
+            .is_empty());
+        assert_eq!(metric_families[0].get_metric().len(), 4);
+
+        // Allowlisted families are untouched
+        let mut metric_families = gather_counter_family(10);
+        assert!(CardinalityGuard::new(4, vec!["test_counter".into()])
+            .apply(&mut metric_families)
+            .is_empty());
+        assert_eq!(metric_families[0].get_metric().len(), 10);
+
+        // A limit of 0 disables the guard
+        assert!(CardinalityGuard::new(0, vec![])
+            .apply(&mut metric_families)
+            .is_empty());
+    }
+
+    #[test]
+    fn test_cardinality_guard_aggregates_histograms() {
+        let registry = Registry::new();
+        let histogram = HistogramVec::new(
+            histogram_opts!("test_histogram", "A test histogram", vec![1.0, 10.0]),
+            &["key"],
+        )
+        .unwrap();
+        registry.register(Box::new(histogram.clone())).unwrap();
+        for key in 0..5 {
+            histogram
+                .with_label_values(&[&key.to_string()])
+                .observe(5.0);
+        }
+
+        let mut metric_families = registry.gather();
+        CardinalityGuard::new(2, vec![]).apply(&mut metric_families);
+
+        let overflow = metric_families[0].get_metric()[1].get_histogram();
+        assert_eq!(overflow.get_sample_count(), 4);
+        assert_eq!(overflow.get_sample_sum(), 20.0);
+        assert_eq!(overflow.get_bucket()[0].get_cumulative_count(), 0);
+        assert_eq!(overflow.get_bucket()[1].get_cumulative_count(), 4);
+    }
+}
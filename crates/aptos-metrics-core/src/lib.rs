@@ -16,6 +16,7 @@ pub use prometheus::{
 };
 
 mod avg_counter;
+pub mod cardinality;
 pub mod const_metric;
 pub mod op_counters;
 pub mod thread_local;
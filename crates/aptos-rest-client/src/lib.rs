@@ -24,9 +24,9 @@ pub use aptos_api_types::{
 use aptos_api_types::{
     deserialize_from_string,
     mime_types::{BCS, BCS_SIGNED_TRANSACTION, BCS_VIEW_FUNCTION, JSON},
-    AptosError, AptosErrorCode, BcsBlock, Block, GasEstimation, HexEncodedBytes, IndexResponse,
-    MoveModuleId, TransactionData, TransactionOnChainData, TransactionsBatchSubmissionResult,
-    UserTransaction, VersionedEvent, ViewFunction, ViewRequest,
+    AptosError, AptosErrorCode, BcsBlock, Block, BlockTimestampSearch, GasEstimation,
+    HexEncodedBytes, IndexResponse, MoveModuleId, TransactionData, TransactionOnChainData,
+    TransactionsBatchSubmissionResult, UserTransaction, VersionedEvent, ViewFunction, ViewRequest,
 };
 use aptos_crypto::HashValue;
 use aptos_logger::{debug, info, sample, sample::SampleRate};
@@ -34,8 +34,10 @@ use aptos_types::{
     account_address::AccountAddress,
     account_config::{AccountResource, NewBlockEvent, CORE_CODE_ADDRESS},
     contract_event::EventWithVersion,
+    indexer::indexer_db_reader::IndexedPackage,
     keyless::{Groth16Proof, Pepper, ZeroKnowledgeSig, ZKP},
-    state_store::state_key::StateKey,
+    proof::AccumulatorConsistencyProof,
+    state_store::{state_key::StateKey, state_value::StateValueWithProof},
     transaction::{
         authenticator::EphemeralSignature, IndexedTransactionSummary, SignedTransaction,
     },
@@ -268,6 +270,23 @@ impl Client {
         Ok(response.and_then(|inner| bcs::from_bytes(&inner))?)
     }
 
+    pub async fn get_block_by_timestamp(
+        &self,
+        timestamp_usecs: u64,
+        search: BlockTimestampSearch,
+        with_transactions: bool,
+    ) -> AptosResult<Response<Block>> {
+        let search = match search {
+            BlockTimestampSearch::ClosestBefore => "closest_before",
+            BlockTimestampSearch::ClosestAfter => "closest_after",
+        };
+        self.get(self.build_path(&format!(
+            "blocks/by_timestamp/{}?search={}&with_transactions={}",
+            timestamp_usecs, search, with_transactions
+        ))?)
+        .await
+    }
+
     pub async fn lookup_address(
         &self,
         address_key: AccountAddress,
@@ -414,6 +433,26 @@ impl Client {
         Ok(response)
     }
 
+    /// Gets a proof that the transaction accumulator at `target_version` (or the latest version)
+    /// extends the one at `client_known_version` (or the empty accumulator).
+    pub async fn get_ledger_consistency_proof_bcs(
+        &self,
+        client_known_version: Option<u64>,
+        target_version: Option<u64>,
+    ) -> AptosResult<Response<AccumulatorConsistencyProof>> {
+        let mut url = self.build_path("ledger/consistency_proof")?;
+        if let Some(client_known_version) = client_known_version {
+            url.query_pairs_mut()
+                .append_pair("client_known_version", &client_known_version.to_string());
+        }
+        if let Some(target_version) = target_version {
+            url.query_pairs_mut()
+                .append_pair("target_version", &target_version.to_string());
+        }
+        let response = self.get_bcs(url).await?;
+        Ok(response.and_then(|inner| bcs::from_bytes(&inner))?)
+    }
+
     pub async fn view(
         &self,
         request: &ViewRequest,
@@ -1337,6 +1376,17 @@ impl Client {
         self.get_bcs(url).await
     }
 
+    /// Gets the packages published under `package_name` at any address, from the node's package
+    /// index.
+    pub async fn get_packages_by_name_bcs(
+        &self,
+        package_name: &str,
+    ) -> AptosResult<Response<Vec<IndexedPackage>>> {
+        let url = self.build_path(&format!("packages/{}", package_name))?;
+        let response = self.get_bcs(url).await?;
+        Ok(response.and_then(|inner| bcs::from_bytes(&inner))?)
+    }
+
     pub async fn get_account_events(
         &self,
         address: AccountAddress,
@@ -1575,6 +1625,30 @@ impl Client {
         Ok(response.map(|inner| inner.to_vec()))
     }
 
+    /// Fetches a state value with a proof, which can be verified with
+    /// `StateValueWithProof::verify` from a waypoint at `known_version`
+    pub async fn get_raw_state_value_with_proof(
+        &self,
+        state_key: &StateKey,
+        version: Option<u64>,
+        known_version: u64,
+    ) -> AptosResult<Response<StateValueWithProof>> {
+        let mut path = format!(
+            "experimental/state_values/raw_with_proof?known_version={}",
+            known_version
+        );
+        if let Some(version) = version {
+            path.push_str(&format!("&ledger_version={}", version));
+        }
+        let url = self.build_path(&path)?;
+        let data = json!({
+            "key": hex::encode(bcs::to_bytes(state_key)?),
+        });
+
+        let response = self.post_bcs(url, data).await?;
+        Ok(response.and_then(|inner| bcs::from_bytes(&inner))?)
+    }
+
     pub async fn get_account(&self, address: AccountAddress) -> AptosResult<Response<Account>> {
         let url = self.build_path(&format!("accounts/{}", address.to_hex()))?;
         let response = self.inner.get(url).send().await?;
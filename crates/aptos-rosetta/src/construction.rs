@@ -610,6 +610,11 @@ async fn construction_parse(
                 (AccountAddress::ONE, DELEGATION_POOL_MODULE, DELEGATION_POOL_UNLOCK_FUNCTION) => {
                     parse_delegation_pool_unlock_operation(sender, &type_args, &args)?
                 },
+                (
+                    AccountAddress::ONE,
+                    DELEGATION_POOL_MODULE,
+                    DELEGATION_POOL_REACTIVATE_STAKE_FUNCTION,
+                ) => parse_delegation_pool_reactivate_stake_operation(sender, &type_args, &args)?,
                 _ => {
                     return Err(ApiError::TransactionParseError(Some(format!(
                         "Unsupported entry function type {:x}::{}::{}",
@@ -1140,6 +1145,31 @@ pub fn parse_delegation_pool_unlock_operation(
     )])
 }
 
+/// Parses 0x1::delegation_pool::reactivate_stake(pool_address: address, amount: u64)
+pub fn parse_delegation_pool_reactivate_stake_operation(
+    delegator: AccountAddress,
+    type_args: &[TypeTag],
+    args: &[Vec<u8>],
+) -> ApiResult<Vec<Operation>> {
+    if !type_args.is_empty() {
+        return Err(ApiError::TransactionParseError(Some(format!(
+            "Reactivate delegated stake should not have type arguments: {:?}",
+            type_args
+        ))));
+    }
+
+    let pool_address: AccountAddress = parse_function_arg("reactivate_delegated_stake", args, 0)?;
+    let amount: u64 = parse_function_arg("reactivate_delegated_stake", args, 1)?;
+
+    Ok(vec![Operation::reactivate_delegated_stake(
+        0,
+        None,
+        delegator,
+        AccountIdentifier::base_account(pool_address),
+        Some(amount),
+    )])
+}
+
 /// Parses 0x1::delegation_pool::withdraw(pool_address: address, amount: u64)
 pub fn parse_delegation_pool_withdraw_operation(
     delegator: AccountAddress,
@@ -1358,6 +1388,25 @@ async fn construction_payloads(
                 ))));
             }
         },
+        InternalOperation::ReactivateDelegatedStake(inner) => {
+            if let InternalOperation::ReactivateDelegatedStake(ref metadata_op) =
+                metadata.internal_operation
+            {
+                if inner.delegator != metadata_op.delegator
+                    || inner.pool_address != metadata_op.pool_address
+                {
+                    return Err(ApiError::InvalidInput(Some(format!(
+                        "Reactivate delegated stake operation doesn't match metadata {:?} vs {:?}",
+                        inner, metadata.internal_operation
+                    ))));
+                }
+            } else {
+                return Err(ApiError::InvalidInput(Some(format!(
+                    "Reactivate delegated stake operation doesn't match metadata {:?} vs {:?}",
+                    inner, metadata.internal_operation
+                ))));
+            }
+        },
         InternalOperation::WithdrawUndelegated(inner) => {
             if let InternalOperation::WithdrawUndelegated(ref metadata_op) =
                 metadata.internal_operation
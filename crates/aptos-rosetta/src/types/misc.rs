@@ -125,6 +125,7 @@ pub enum OperationType {
     DistributeStakingRewards,
     AddDelegatedStake,
     UnlockDelegatedStake,
+    ReactivateDelegatedStake,
     // Fee must always be last for ordering
     Fee,
 }
@@ -136,6 +137,7 @@ impl OperationType {
     const DISTRIBUTE_STAKING_REWARDS: &'static str = "distribute_staking_rewards";
     const FEE: &'static str = "fee";
     const INITIALIZE_STAKE_POOL: &'static str = "initialize_stake_pool";
+    const REACTIVATE_DELEGATED_STAKE: &'static str = "reactivate_delegated_stake";
     const RESET_LOCKUP: &'static str = "reset_lockup";
     const SET_OPERATOR: &'static str = "set_operator";
     const SET_VOTER: &'static str = "set_voter";
@@ -164,6 +166,7 @@ impl OperationType {
             DistributeStakingRewards,
             AddDelegatedStake,
             UnlockDelegatedStake,
+            ReactivateDelegatedStake,
         ]
     }
 }
@@ -188,6 +191,7 @@ impl FromStr for OperationType {
             Self::DISTRIBUTE_STAKING_REWARDS => Ok(OperationType::DistributeStakingRewards),
             Self::ADD_DELEGATED_STAKE => Ok(OperationType::AddDelegatedStake),
             Self::UNLOCK_DELEGATED_STAKE => Ok(OperationType::UnlockDelegatedStake),
+            Self::REACTIVATE_DELEGATED_STAKE => Ok(OperationType::ReactivateDelegatedStake),
             Self::WITHDRAW_UNDELEGATED_FUNDS => Ok(OperationType::WithdrawUndelegatedFunds),
             _ => Err(ApiError::DeserializationFailed(Some(format!(
                 "Invalid OperationType: {}",
@@ -214,6 +218,7 @@ impl Display for OperationType {
             DistributeStakingRewards => Self::DISTRIBUTE_STAKING_REWARDS,
             AddDelegatedStake => Self::ADD_DELEGATED_STAKE,
             UnlockDelegatedStake => Self::UNLOCK_DELEGATED_STAKE,
+            ReactivateDelegatedStake => Self::REACTIVATE_DELEGATED_STAKE,
             WithdrawUndelegatedFunds => Self::WITHDRAW_UNDELEGATED_FUNDS,
             Fee => Self::FEE,
         })
@@ -9,10 +9,10 @@ use crate::{
     common::{find_coin_currency, find_fa_currency, native_coin},
     construction::{
         parse_create_stake_pool_operation, parse_delegation_pool_add_stake_operation,
-        parse_delegation_pool_unlock_operation, parse_delegation_pool_withdraw_operation,
-        parse_distribute_staking_rewards_operation, parse_reset_lockup_operation,
-        parse_set_operator_operation, parse_set_voter_operation, parse_unlock_stake_operation,
-        parse_update_commission_operation,
+        parse_delegation_pool_reactivate_stake_operation, parse_delegation_pool_unlock_operation,
+        parse_delegation_pool_withdraw_operation, parse_distribute_staking_rewards_operation,
+        parse_reset_lockup_operation, parse_set_operator_operation, parse_set_voter_operation,
+        parse_unlock_stake_operation, parse_update_commission_operation,
     },
     error::ApiResult,
     types::{
@@ -604,6 +604,26 @@ impl Operation {
         )
     }
 
+    pub fn reactivate_delegated_stake(
+        operation_index: u64,
+        status: Option<OperationStatusType>,
+        delegator: AccountAddress,
+        pool_address: AccountIdentifier,
+        amount: Option<u64>,
+    ) -> Operation {
+        Operation::new(
+            OperationType::ReactivateDelegatedStake,
+            operation_index,
+            status,
+            AccountIdentifier::base_account(delegator),
+            None,
+            Some(OperationMetadata::reactivate_delegated_stake(
+                pool_address,
+                amount,
+            )),
+        )
+    }
+
     pub fn withdraw_undelegated_stake(
         operation_index: u64,
         status: Option<OperationStatusType>,
@@ -776,6 +796,17 @@ impl OperationMetadata {
         }
     }
 
+    pub fn reactivate_delegated_stake(
+        pool_address: AccountIdentifier,
+        amount: Option<u64>,
+    ) -> Self {
+        OperationMetadata {
+            pool_address: Some(pool_address),
+            amount: amount.map(U64::from),
+            ..Default::default()
+        }
+    }
+
     pub fn withdraw_undelegated_stake(
         pool_address: AccountIdentifier,
         amount: Option<u64>,
@@ -1235,6 +1266,26 @@ fn parse_failed_operations_from_txn_payload(
                     warn!("Failed to parse delegation_pool::unlock {:?}", inner);
                 }
             },
+            (
+                AccountAddress::ONE,
+                DELEGATION_POOL_MODULE,
+                DELEGATION_POOL_REACTIVATE_STAKE_FUNCTION,
+            ) => {
+                if let Ok(mut ops) = parse_delegation_pool_reactivate_stake_operation(
+                    sender,
+                    inner.ty_args(),
+                    inner.args(),
+                ) {
+                    if let Some(operation) = ops.get_mut(0) {
+                        operation.status = Some(OperationStatusType::Failure.to_string());
+                    }
+                } else {
+                    warn!(
+                        "Failed to parse delegation_pool::reactivate_stake {:?}",
+                        inner
+                    );
+                }
+            },
             _ => {
                 // If we don't recognize the transaction payload, then we can't parse operations
             },
@@ -2342,6 +2393,7 @@ pub enum InternalOperation {
     DistributeStakingRewards(DistributeStakingRewards),
     AddDelegatedStake(AddDelegatedStake),
     UnlockDelegatedStake(UnlockDelegatedStake),
+    ReactivateDelegatedStake(ReactivateDelegatedStake),
 }
 
 impl InternalOperation {
@@ -2570,6 +2622,25 @@ impl InternalOperation {
                                 }));
                             }
                         },
+                        Ok(OperationType::ReactivateDelegatedStake) => {
+                            if let (
+                                Some(OperationMetadata {
+                                    pool_address: Some(pool_address),
+                                    amount,
+                                    ..
+                                }),
+                                Some(account),
+                            ) = (&operation.metadata, &operation.account)
+                            {
+                                return Ok(Self::ReactivateDelegatedStake(
+                                    ReactivateDelegatedStake {
+                                        delegator: account.account_address()?,
+                                        pool_address: pool_address.account_address()?,
+                                        amount: amount.map(u64::from).unwrap_or_default(),
+                                    },
+                                ));
+                            }
+                        },
                         Ok(OperationType::WithdrawUndelegatedFunds) => {
                             if let (
                                 Some(OperationMetadata {
@@ -2625,6 +2696,7 @@ impl InternalOperation {
             Self::DistributeStakingRewards(inner) => inner.sender,
             Self::AddDelegatedStake(inner) => inner.delegator,
             Self::UnlockDelegatedStake(inner) => inner.delegator,
+            Self::ReactivateDelegatedStake(inner) => inner.delegator,
         }
     }
 
@@ -2782,6 +2854,13 @@ impl InternalOperation {
                 ),
                 unlock_delegated_stake.delegator,
             ),
+            InternalOperation::ReactivateDelegatedStake(reactivate_delegated_stake) => (
+                aptos_stdlib::delegation_pool_reactivate_stake(
+                    reactivate_delegated_stake.pool_address,
+                    reactivate_delegated_stake.amount,
+                ),
+                reactivate_delegated_stake.delegator,
+            ),
             InternalOperation::WithdrawUndelegated(withdraw_undelegated) => (
                 aptos_stdlib::delegation_pool_withdraw(
                     withdraw_undelegated.pool_address,
@@ -2997,3 +3076,10 @@ pub struct UnlockDelegatedStake {
     pub pool_address: AccountAddress,
     pub amount: u64,
 }
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct ReactivateDelegatedStake {
+    pub delegator: AccountAddress,
+    pub pool_address: AccountAddress,
+    pub amount: u64,
+}
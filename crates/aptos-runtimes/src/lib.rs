@@ -2,14 +2,41 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use rayon::{ThreadPool, ThreadPoolBuilder};
-use std::sync::atomic::{AtomicUsize, Ordering};
-use tokio::runtime::{Builder, Runtime};
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc, Mutex,
+};
+use tokio::runtime::{Builder, Handle, Runtime};
 
 /// The max thread name length before the name will be truncated
 /// when it's displayed. Note: the max display length is 15, but
 /// we need to leave space for the thread IDs.
 const MAX_THREAD_NAME_LENGTH: usize = 12;
 
+/// All runtimes spawned via this crate, so that they can be inspected
+/// (e.g., queue depths and task dumps) while debugging a running node.
+static NAMED_RUNTIMES: Mutex<Vec<NamedRuntime>> = Mutex::new(Vec::new());
+
+struct NamedRuntime {
+    name: String,
+    handle: Handle,
+    // The number of threads (workers and blocking) the runtime currently has.
+    // Worker threads live as long as the runtime, so zero means it was dropped.
+    num_live_threads: Arc<AtomicUsize>,
+}
+
+/// Returns the names and handles of all named runtimes that are still alive
+pub fn named_runtime_handles() -> Vec<(String, Handle)> {
+    let mut named_runtimes = NAMED_RUNTIMES
+        .lock()
+        .expect("Cannot currently handle a poisoned lock");
+    named_runtimes.retain(|runtime| runtime.num_live_threads.load(Ordering::SeqCst) > 0);
+    named_runtimes
+        .iter()
+        .map(|runtime| (runtime.name.clone(), runtime.handle.clone()))
+        .collect()
+}
+
 /// Returns a tokio runtime with named threads.
 /// This is useful for tracking threads when debugging.
 pub fn spawn_named_runtime(thread_name: String, num_worker_threads: Option<usize>) -> Runtime {
@@ -37,13 +64,22 @@ where
     // Create the runtime builder
     let atomic_id = AtomicUsize::new(0);
     let thread_name_clone = thread_name.clone();
+    let num_live_threads = Arc::new(AtomicUsize::new(0));
+    let num_started_threads = num_live_threads.clone();
+    let num_stopped_threads = num_live_threads.clone();
     let mut builder = Builder::new_multi_thread();
     builder
         .thread_name_fn(move || {
             let id = atomic_id.fetch_add(1, Ordering::SeqCst);
             format!("{}-{}", thread_name_clone, id)
         })
-        .on_thread_start(on_thread_start)
+        .on_thread_start(move || {
+            num_started_threads.fetch_add(1, Ordering::SeqCst);
+            on_thread_start()
+        })
+        .on_thread_stop(move || {
+            num_stopped_threads.fetch_sub(1, Ordering::SeqCst);
+        })
         .disable_lifo_slot()
         // Limit concurrent blocking tasks from spawn_blocking(), in case, for example, too many
         // Rest API calls overwhelm the node.
@@ -53,13 +89,24 @@ where
         builder.worker_threads(num_worker_threads);
     }
 
-    // Spawn and return the runtime
-    builder.build().unwrap_or_else(|error| {
+    // Spawn the runtime
+    let runtime = builder.build().unwrap_or_else(|error| {
         panic!(
             "Failed to spawn named runtime! Name: {:?}, Error: {:?}",
             thread_name, error
         )
-    })
+    });
+
+    // Register the runtime so that it can be inspected, and return it
+    NAMED_RUNTIMES
+        .lock()
+        .expect("Cannot currently handle a poisoned lock")
+        .push(NamedRuntime {
+            name: thread_name,
+            handle: runtime.handle().clone(),
+            num_live_threads,
+        });
+    runtime
 }
 
 /// Returns a rayon threadpool with threads.
@@ -0,0 +1,432 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::transaction_filter::{
+    matches_entry_function, matches_entry_function_module_address, matches_sender_address,
+};
+use aptos_types::transaction::SignedTransaction;
+use move_core_types::account_address::AccountAddress;
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use std::{
+    fmt::{Display, Formatter},
+    str::FromStr,
+};
+
+/// A boolean expression over the properties of a transaction, e.g.,
+/// `sender == 0xcafe && payload_size > 4096`, or
+/// `entry_function == 0x1::aptos_account::transfer || module_address == 0xbeef`.
+///
+/// The supported fields are `sender`, `module_address`, `entry_function` and
+/// `payload_size` (the BCS size of the payload, in bytes). All fields support
+/// `==` and `!=`, and `payload_size` also supports `<`, `<=`, `>` and `>=`.
+/// Comparisons can be combined with `!`, `&&` and `||` (in order of precedence),
+/// and grouped with parentheses.
+///
+/// Expressions are (de)serialized as strings, so they can be written directly
+/// into the node config.
+#[derive(Clone, Debug)]
+pub struct FilterExpression {
+    source: String,
+    expression: Expression,
+}
+
+impl FilterExpression {
+    /// Returns true iff the expression matches the given transaction
+    pub fn matches(&self, signed_transaction: &SignedTransaction) -> bool {
+        self.expression.matches(signed_transaction)
+    }
+}
+
+impl FromStr for FilterExpression {
+    type Err = FilterExpressionError;
+
+    fn from_str(source: &str) -> Result<Self, Self::Err> {
+        let tokens = tokenize(source)?;
+        let mut parser = Parser {
+            tokens: &tokens,
+            position: 0,
+        };
+        let expression = parser.parse_expression()?;
+        if let Some(token) = parser.peek() {
+            return Err(FilterExpressionError(format!(
+                "Unexpected token {:?} after the end of the expression",
+                token
+            )));
+        }
+
+        Ok(Self {
+            source: source.trim().to_string(),
+            expression,
+        })
+    }
+}
+
+impl Display for FilterExpression {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.source)
+    }
+}
+
+impl PartialEq for FilterExpression {
+    fn eq(&self, other: &Self) -> bool {
+        self.expression == other.expression
+    }
+}
+
+impl Eq for FilterExpression {}
+
+impl Serialize for FilterExpression {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.source)
+    }
+}
+
+impl<'de> Deserialize<'de> for FilterExpression {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let source = String::deserialize(deserializer)?;
+        source.parse().map_err(de::Error::custom)
+    }
+}
+
+/// A list of filter expressions used to deny transactions at the node level
+/// (e.g., to respond to spam campaigns). Transactions matching any of the
+/// expressions are denied, all other transactions are allowed.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(transparent)]
+pub struct TransactionDenylist {
+    expressions: Vec<FilterExpression>,
+}
+
+impl TransactionDenylist {
+    pub fn new(expressions: Vec<FilterExpression>) -> Self {
+        Self { expressions }
+    }
+
+    /// Returns true iff the denylist is empty (i.e., denies nothing)
+    pub fn is_empty(&self) -> bool {
+        self.expressions.is_empty()
+    }
+
+    /// Returns true iff any of the expressions matches the given transaction
+    pub fn denies_transaction(&self, signed_transaction: &SignedTransaction) -> bool {
+        self.expressions
+            .iter()
+            .any(|expression| expression.matches(signed_transaction))
+    }
+}
+
+/// Parses a denylist from expressions separated by semicolons, e.g.,
+/// `sender == 0xcafe; payload_size > 65536` (as used by the admin service).
+impl FromStr for TransactionDenylist {
+    type Err = FilterExpressionError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let expressions = value
+            .split(';')
+            .filter(|expression| !expression.trim().is_empty())
+            .map(FilterExpression::from_str)
+            .collect::<Result<_, _>>()?;
+        Ok(Self { expressions })
+    }
+}
+
+impl Display for TransactionDenylist {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let expressions: Vec<String> = self.expressions.iter().map(|e| e.to_string()).collect();
+        write!(f, "{}", expressions.join("; "))
+    }
+}
+
+/// An error encountered while parsing a filter expression
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FilterExpressionError(String);
+
+impl Display for FilterExpressionError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Invalid filter expression: {}", self.0)
+    }
+}
+
+impl std::error::Error for FilterExpressionError {}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+enum Expression {
+    Not(Box<Expression>),
+    And(Box<Expression>, Box<Expression>),
+    Or(Box<Expression>, Box<Expression>),
+    Comparison(Comparison),
+}
+
+impl Expression {
+    fn matches(&self, signed_transaction: &SignedTransaction) -> bool {
+        match self {
+            Expression::Not(expression) => !expression.matches(signed_transaction),
+            Expression::And(left, right) => {
+                left.matches(signed_transaction) && right.matches(signed_transaction)
+            },
+            Expression::Or(left, right) => {
+                left.matches(signed_transaction) || right.matches(signed_transaction)
+            },
+            Expression::Comparison(comparison) => comparison.matches(signed_transaction),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Operator {
+    Equal,
+    NotEqual,
+    LessThan,
+    LessThanOrEqual,
+    GreaterThan,
+    GreaterThanOrEqual,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+enum Comparison {
+    Sender(Operator, AccountAddress),
+    ModuleAddress(Operator, AccountAddress),
+    EntryFunction(Operator, AccountAddress, String, String),
+    PayloadSize(Operator, u64),
+}
+
+impl Comparison {
+    fn matches(&self, signed_transaction: &SignedTransaction) -> bool {
+        match self {
+            Comparison::Sender(operator, address) => apply_equality(
+                *operator,
+                matches_sender_address(signed_transaction, address),
+            ),
+            Comparison::ModuleAddress(operator, address) => apply_equality(
+                *operator,
+                matches_entry_function_module_address(signed_transaction, address),
+            ),
+            Comparison::EntryFunction(operator, address, module_name, function) => apply_equality(
+                *operator,
+                matches_entry_function(signed_transaction, address, module_name, function),
+            ),
+            Comparison::PayloadSize(operator, size) => {
+                // A payload that can't be serialized is never matched
+                let Ok(payload_size) = bcs::serialized_size(signed_transaction.payload()) else {
+                    return false;
+                };
+                let payload_size = payload_size as u64;
+                match operator {
+                    Operator::Equal => payload_size == *size,
+                    Operator::NotEqual => payload_size != *size,
+                    Operator::LessThan => payload_size < *size,
+                    Operator::LessThanOrEqual => payload_size <= *size,
+                    Operator::GreaterThan => payload_size > *size,
+                    Operator::GreaterThanOrEqual => payload_size >= *size,
+                }
+            },
+        }
+    }
+}
+
+/// Applies an equality operator (verified during parsing) to the result of a match
+fn apply_equality(operator: Operator, matches: bool) -> bool {
+    match operator {
+        Operator::NotEqual => !matches,
+        _ => matches,
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+enum Token {
+    Word(String),
+    Operator(Operator),
+    And,
+    Or,
+    Not,
+    OpenParen,
+    CloseParen,
+}
+
+/// Splits the expression into tokens. Words are runs of alphanumeric characters,
+/// underscores and colons (i.e., field names, addresses, function names and numbers).
+fn tokenize(source: &str) -> Result<Vec<Token>, FilterExpressionError> {
+    let mut tokens = vec![];
+    let mut chars = source.chars().peekable();
+    while let Some(c) = chars.next() {
+        let token = match c {
+            c if c.is_whitespace() => continue,
+            '(' => Token::OpenParen,
+            ')' => Token::CloseParen,
+            '&' | '|' | '=' => {
+                if chars.next() != Some(c) {
+                    return Err(FilterExpressionError(format!("Expected {}{}", c, c)));
+                }
+                match c {
+                    '&' => Token::And,
+                    '|' => Token::Or,
+                    _ => Token::Operator(Operator::Equal),
+                }
+            },
+            '!' | '<' | '>' => {
+                let or_equal = chars.next_if_eq(&'=').is_some();
+                match (c, or_equal) {
+                    ('!', false) => Token::Not,
+                    ('!', true) => Token::Operator(Operator::NotEqual),
+                    ('<', false) => Token::Operator(Operator::LessThan),
+                    ('<', true) => Token::Operator(Operator::LessThanOrEqual),
+                    ('>', false) => Token::Operator(Operator::GreaterThan),
+                    _ => Token::Operator(Operator::GreaterThanOrEqual),
+                }
+            },
+            c if is_word_char(c) => {
+                let mut word = c.to_string();
+                while let Some(c) = chars.next_if(|c| is_word_char(*c)) {
+                    word.push(c);
+                }
+                Token::Word(word)
+            },
+            c => {
+                return Err(FilterExpressionError(format!(
+                    "Unexpected character {:?}",
+                    c
+                )))
+            },
+        };
+        tokens.push(token);
+    }
+    Ok(tokens)
+}
+
+fn is_word_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_' || c == ':'
+}
+
+/// A recursive descent parser over the tokens of an expression
+struct Parser<'a> {
+    tokens: &'a [Token],
+    position: usize,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position)
+    }
+
+    fn next(&mut self) -> Result<&Token, FilterExpressionError> {
+        let token = self
+            .tokens
+            .get(self.position)
+            .ok_or_else(|| FilterExpressionError("Unexpected end of the expression".into()))?;
+        self.position += 1;
+        Ok(token)
+    }
+
+    /// expression := term ("||" term)*
+    fn parse_expression(&mut self) -> Result<Expression, FilterExpressionError> {
+        let mut expression = self.parse_term()?;
+        while self.peek() == Some(&Token::Or) {
+            self.position += 1;
+            expression = Expression::Or(Box::new(expression), Box::new(self.parse_term()?));
+        }
+        Ok(expression)
+    }
+
+    /// term := factor ("&&" factor)*
+    fn parse_term(&mut self) -> Result<Expression, FilterExpressionError> {
+        let mut expression = self.parse_factor()?;
+        while self.peek() == Some(&Token::And) {
+            self.position += 1;
+            expression = Expression::And(Box::new(expression), Box::new(self.parse_factor()?));
+        }
+        Ok(expression)
+    }
+
+    /// factor := "!" factor | "(" expression ")" | field operator value
+    fn parse_factor(&mut self) -> Result<Expression, FilterExpressionError> {
+        match self.next()?.clone() {
+            Token::Not => Ok(Expression::Not(Box::new(self.parse_factor()?))),
+            Token::OpenParen => {
+                let expression = self.parse_expression()?;
+                match self.next()? {
+                    Token::CloseParen => Ok(expression),
+                    token => Err(FilterExpressionError(format!(
+                        "Expected a closing parenthesis, got {:?}",
+                        token
+                    ))),
+                }
+            },
+            Token::Word(field) => {
+                let operator = match self.next()? {
+                    Token::Operator(operator) => *operator,
+                    token => {
+                        return Err(FilterExpressionError(format!(
+                            "Expected an operator after {}, got {:?}",
+                            field, token
+                        )))
+                    },
+                };
+                let value = match self.next()? {
+                    Token::Word(value) => value.clone(),
+                    token => {
+                        return Err(FilterExpressionError(format!(
+                            "Expected a value after {}, got {:?}",
+                            field, token
+                        )))
+                    },
+                };
+                parse_comparison(&field, operator, &value).map(Expression::Comparison)
+            },
+            token => Err(FilterExpressionError(format!(
+                "Unexpected token {:?}",
+                token
+            ))),
+        }
+    }
+}
+
+fn parse_comparison(
+    field: &str,
+    operator: Operator,
+    value: &str,
+) -> Result<Comparison, FilterExpressionError> {
+    if field != "payload_size" && !matches!(operator, Operator::Equal | Operator::NotEqual) {
+        return Err(FilterExpressionError(format!(
+            "Only == and != are supported for {}",
+            field
+        )));
+    }
+
+    let comparison = match field {
+        "sender" => Comparison::Sender(operator, parse_address(value)?),
+        "module_address" => Comparison::ModuleAddress(operator, parse_address(value)?),
+        "entry_function" => {
+            let parts: Vec<&str> = value.split("::").collect();
+            let [address, module_name, function] = parts[..] else {
+                return Err(FilterExpressionError(format!(
+                    "Expected an entry function of the form address::module::function, got {}",
+                    value
+                )));
+            };
+            Comparison::EntryFunction(
+                operator,
+                parse_address(address)?,
+                module_name.to_string(),
+                function.to_string(),
+            )
+        },
+        "payload_size" => {
+            let size = value.parse().map_err(|error| {
+                FilterExpressionError(format!("Invalid payload size {}: {}", value, error))
+            })?;
+            Comparison::PayloadSize(operator, size)
+        },
+        _ => {
+            return Err(FilterExpressionError(format!(
+                "Unknown field {}, expected sender, module_address, entry_function or payload_size",
+                field
+            )))
+        },
+    };
+    Ok(comparison)
+}
+
+fn parse_address(value: &str) -> Result<AccountAddress, FilterExpressionError> {
+    AccountAddress::from_str(value)
+        .map_err(|error| FilterExpressionError(format!("Invalid address {}: {}", value, error)))
+}
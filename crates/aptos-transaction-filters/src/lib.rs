@@ -5,6 +5,7 @@
 
 pub mod batch_transaction_filter;
 pub mod block_transaction_filter;
+pub mod filter_expression;
 pub mod transaction_filter;
 
 #[cfg(test)]
@@ -0,0 +1,124 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    filter_expression::{FilterExpression, TransactionDenylist},
+    tests::utils,
+};
+use std::str::FromStr;
+
+#[test]
+fn test_filter_expression_entry_function() {
+    for use_new_txn_payload_format in [false, true] {
+        // Create an expression that matches the entry function of txn 0
+        let transactions = utils::create_entry_function_transactions(use_new_txn_payload_format);
+        let entry_function = format!(
+            "{}::{}::{}",
+            utils::get_module_address(&transactions[0]).to_hex_literal(),
+            utils::get_module_name(&transactions[0]),
+            utils::get_function_name(&transactions[0])
+        );
+        let expression =
+            FilterExpression::from_str(&format!("entry_function == {}", entry_function)).unwrap();
+
+        // Verify that only txn 0 is matched
+        assert!(expression.matches(&transactions[0]));
+        for transaction in &transactions[1..] {
+            assert!(!expression.matches(transaction));
+        }
+
+        // Verify that the negated expression matches all other transactions
+        let expression =
+            FilterExpression::from_str(&format!("entry_function != {}", entry_function)).unwrap();
+        assert!(!expression.matches(&transactions[0]));
+        for transaction in &transactions[1..] {
+            assert!(expression.matches(transaction));
+        }
+    }
+}
+
+#[test]
+fn test_filter_expression_combinations() {
+    for use_new_txn_payload_format in [false, true] {
+        // Create an expression that matches the sender of txn 0 or the module address of txn 1,
+        // but only if the payload is not empty.
+        let transactions = utils::create_entry_function_transactions(use_new_txn_payload_format);
+        let expression = FilterExpression::from_str(&format!(
+            "(sender == {} || module_address == {}) && !(payload_size == 0)",
+            transactions[0].sender().to_hex_literal(),
+            utils::get_module_address(&transactions[1]).to_hex_literal(),
+        ))
+        .unwrap();
+
+        // Verify that only txns 0 and 1 are matched
+        assert!(expression.matches(&transactions[0]));
+        assert!(expression.matches(&transactions[1]));
+        for transaction in &transactions[2..] {
+            assert!(!expression.matches(transaction));
+        }
+
+        // Verify that && binds tighter than ||
+        let expression = FilterExpression::from_str(&format!(
+            "sender == {} || sender == {} && payload_size < 1",
+            transactions[0].sender().to_hex_literal(),
+            transactions[1].sender().to_hex_literal(),
+        ))
+        .unwrap();
+        assert!(expression.matches(&transactions[0]));
+        assert!(!expression.matches(&transactions[1]));
+    }
+}
+
+#[test]
+fn test_filter_expression_parse_errors() {
+    for invalid_expression in [
+        "",
+        "sender",
+        "sender ==",
+        "sender = 0x1",
+        "sender > 0x1",
+        "sender == not_an_address",
+        "entry_function == 0x1::coin",
+        "payload_size >= -1",
+        "receiver == 0x1",
+        "(sender == 0x1",
+        "sender == 0x1 sender == 0x2",
+        "sender == 0x1 & payload_size > 10",
+    ] {
+        assert!(
+            FilterExpression::from_str(invalid_expression).is_err(),
+            "{} should not parse",
+            invalid_expression
+        );
+    }
+}
+
+#[test]
+fn test_transaction_denylist() {
+    let transactions = utils::create_entry_function_transactions(false);
+
+    // Verify that an empty denylist denies nothing
+    let denylist = TransactionDenylist::from_str("").unwrap();
+    assert!(denylist.is_empty());
+    assert!(!denylist.denies_transaction(&transactions[0]));
+
+    // Create a denylist that denies the senders of txns 0 and 2
+    let denylist = TransactionDenylist::from_str(&format!(
+        "sender == {}; sender == {}",
+        transactions[0].sender().to_hex_literal(),
+        transactions[2].sender().to_hex_literal(),
+    ))
+    .unwrap();
+    for (index, transaction) in transactions.iter().enumerate() {
+        assert_eq!(
+            denylist.denies_transaction(transaction),
+            index == 0 || index == 2
+        );
+    }
+
+    // Verify that the denylist round trips through the config format
+    let serialized = serde_yaml::to_string(&denylist).unwrap();
+    let deserialized: TransactionDenylist = serde_yaml::from_str(&serialized).unwrap();
+    assert_eq!(denylist, deserialized);
+    serde_yaml::from_str::<TransactionDenylist>("- sender >> 0x1").unwrap_err();
+}
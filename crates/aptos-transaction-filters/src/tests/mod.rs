@@ -5,6 +5,7 @@ mod batch_transaction_filter;
 mod batch_transaction_filter_config;
 mod block_transaction_filter;
 mod block_transaction_filter_config;
+mod filter_expression;
 mod transaction_filter;
 mod transaction_filter_config;
 mod utils;
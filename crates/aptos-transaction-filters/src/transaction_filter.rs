@@ -314,7 +314,7 @@ fn matches_any_public_key_address(any_public_key: &AnyPublicKey, address: &Accou
 }
 
 /// Returns true iff the transaction's entry function matches the given account address, module name, and function name
-fn matches_entry_function(
+pub(crate) fn matches_entry_function(
     signed_transaction: &SignedTransaction,
     address: &AccountAddress,
     module_name: &String,
@@ -347,7 +347,7 @@ fn matches_entry_function(
 }
 
 /// Returns true iff the transaction's module address matches the given account address
-fn matches_entry_function_module_address(
+pub(crate) fn matches_entry_function_module_address(
     signed_transaction: &SignedTransaction,
     module_address: &AccountAddress,
 ) -> bool {
@@ -425,7 +425,10 @@ fn matches_script_argument_address(
 }
 
 /// Returns true iff the transaction's sender matches the given account address
-fn matches_sender_address(signed_transaction: &SignedTransaction, sender: &AccountAddress) -> bool {
+pub(crate) fn matches_sender_address(
+    signed_transaction: &SignedTransaction,
+    sender: &AccountAddress,
+) -> bool {
     signed_transaction.sender() == *sender
 }
 
@@ -303,6 +303,7 @@ impl CliCommand<RotateSummary> for RotateKey {
             public_key: Some(new_public_key),
             account: Some(current_address),
             private_key: new_private_key,
+            encrypted_private_key: None,
             derivation_path: new_derivation_path,
             ..self.txn_options.profile_options.profile()?
         };
@@ -56,8 +56,10 @@ pub enum MultisigAccountTool {
     Execute(multisig_account::Execute),
     ExecuteReject(multisig_account::ExecuteReject),
     ExecuteWithPayload(multisig_account::ExecuteWithPayload),
+    ListPending(multisig_account::ListPending),
     Reject(multisig_account::Reject),
     VerifyProposal(multisig_account::VerifyProposal),
+    Vote(multisig_account::Vote),
 }
 
 impl MultisigAccountTool {
@@ -69,8 +71,10 @@ impl MultisigAccountTool {
             MultisigAccountTool::Execute(tool) => tool.execute_serialized().await,
             MultisigAccountTool::ExecuteReject(tool) => tool.execute_serialized().await,
             MultisigAccountTool::ExecuteWithPayload(tool) => tool.execute_serialized().await,
+            MultisigAccountTool::ListPending(tool) => tool.execute_serialized().await,
             MultisigAccountTool::Reject(tool) => tool.execute_serialized().await,
             MultisigAccountTool::VerifyProposal(tool) => tool.execute_serialized().await,
+            MultisigAccountTool::Vote(tool) => tool.execute_serialized().await,
         }
     }
 }
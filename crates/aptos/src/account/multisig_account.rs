@@ -1,19 +1,23 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::common::{
-    types::{
-        CliCommand, CliError, CliTypedResult, EntryFunctionArguments, MultisigAccount,
-        MultisigAccountWithSequenceNumber, TransactionOptions, TransactionSummary,
+use crate::{
+    common::{
+        types::{
+            CliCommand, CliError, CliTypedResult, EntryFunctionArguments, MultisigAccount,
+            MultisigAccountWithSequenceNumber, ProfileOptions, RestOptions, TransactionOptions,
+            TransactionSummary,
+        },
+        utils::view_json_option_str,
     },
-    utils::view_json_option_str,
+    transaction::decode::decode_payload,
 };
 use aptos_api_types::ViewFunction;
 use aptos_cached_packages::aptos_stdlib;
 use aptos_crypto::HashValue;
 use aptos_rest_client::{
     aptos_api_types::{HexEncodedBytes, WriteResource, WriteSetChange},
-    Transaction,
+    Client, Transaction,
 };
 use aptos_types::{
     account_address::AccountAddress,
@@ -21,10 +25,11 @@ use aptos_types::{
 };
 use async_trait::async_trait;
 use bcs::to_bytes;
-use clap::Parser;
-use move_core_types::{ident_str, language_storage::ModuleId};
+use clap::{ArgGroup, Parser};
+use move_core_types::{ident_str, identifier::IdentStr, language_storage::ModuleId};
 use serde::Serialize;
-use serde_json::json;
+use serde_json::{json, Value};
+use std::str::FromStr;
 
 /// Create a new multisig account (v2) on-chain.
 ///
@@ -287,6 +292,262 @@ impl CliCommand<TransactionSummary> for Reject {
     }
 }
 
+/// Approve or reject one or a range of multisig transactions.
+///
+/// As one of the owners of the multisig, vote on every transaction from `--sequence-number` up to
+/// and including `--final-sequence-number` in a single transaction.
+#[derive(Debug, Parser)]
+#[clap(group(ArgGroup::new("vote").required(true).args(&["approve", "reject"])))]
+pub struct Vote {
+    #[clap(flatten)]
+    pub(crate) multisig_account_with_sequence_number: MultisigAccountWithSequenceNumber,
+    /// Last sequence number to vote on, defaults to `--sequence-number`
+    #[clap(long)]
+    pub(crate) final_sequence_number: Option<u64>,
+    /// Approve the transactions
+    #[clap(long)]
+    pub(crate) approve: bool,
+    /// Reject the transactions
+    #[clap(long)]
+    pub(crate) reject: bool,
+    #[clap(flatten)]
+    pub(crate) txn_options: TransactionOptions,
+}
+
+#[async_trait]
+impl CliCommand<TransactionSummary> for Vote {
+    fn command_name(&self) -> &'static str {
+        "VoteMultisig"
+    }
+
+    async fn execute(self) -> CliTypedResult<TransactionSummary> {
+        let payload = self.payload()?;
+        self.txn_options
+            .submit_transaction(payload)
+            .await
+            .map(|inner| inner.into())
+    }
+}
+
+impl Vote {
+    fn payload(&self) -> CliTypedResult<TransactionPayload> {
+        let MultisigAccountWithSequenceNumber {
+            multisig_account,
+            sequence_number,
+        } = &self.multisig_account_with_sequence_number;
+        let sequence_number = *sequence_number;
+        Ok(match self.final_sequence_number {
+            Some(final_sequence_number) if final_sequence_number < sequence_number => {
+                return Err(CliError::CommandArgumentError(format!(
+                    "--final-sequence-number {} is lower than --sequence-number {}",
+                    final_sequence_number, sequence_number
+                )));
+            },
+            Some(final_sequence_number) if final_sequence_number > sequence_number => {
+                aptos_stdlib::multisig_account_vote_transactions(
+                    multisig_account.multisig_address,
+                    sequence_number,
+                    final_sequence_number,
+                    self.approve,
+                )
+            },
+            _ => aptos_stdlib::multisig_account_vote_transaction(
+                multisig_account.multisig_address,
+                sequence_number,
+                self.approve,
+            ),
+        })
+    }
+}
+
+/// List the pending transactions of a multisig account.
+///
+/// Payloads stored on-chain are decoded, with entry function arguments named after the on-chain
+/// ABI.  Transactions proposed with only a payload hash show the hash instead.
+#[derive(Debug, Parser)]
+pub struct ListPending {
+    #[clap(flatten)]
+    pub(crate) multisig_account: MultisigAccount,
+    #[clap(flatten)]
+    pub(crate) rest_options: RestOptions,
+    #[clap(flatten)]
+    pub(crate) profile_options: ProfileOptions,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PendingTransactions {
+    pub num_signatures_required: u64,
+    pub owners: Vec<AccountAddress>,
+    pub transactions: Vec<PendingTransaction>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PendingTransaction {
+    pub sequence_number: u64,
+    pub creator: AccountAddress,
+    pub creation_time_secs: u64,
+    pub approvals: Vec<AccountAddress>,
+    pub rejections: Vec<AccountAddress>,
+    /// Whether there are enough approvals to execute the transaction
+    pub executable: bool,
+    /// Whether there are enough rejections to remove the transaction
+    pub rejectable: bool,
+    pub payload: Option<Value>,
+    pub payload_hash: Option<String>,
+}
+
+impl ListPending {
+    async fn view(
+        &self,
+        client: &Client,
+        function: &IdentStr,
+    ) -> CliTypedResult<Vec<serde_json::Value>> {
+        Ok(client
+            .view_bcs_with_json_response(
+                &ViewFunction {
+                    module: ModuleId::new(
+                        AccountAddress::ONE,
+                        ident_str!("multisig_account").to_owned(),
+                    ),
+                    function: function.to_owned(),
+                    ty_args: vec![],
+                    args: vec![bcs::to_bytes(&self.multisig_account.multisig_address)?],
+                },
+                None,
+            )
+            .await?
+            .into_inner())
+    }
+}
+
+#[async_trait]
+impl CliCommand<PendingTransactions> for ListPending {
+    fn command_name(&self) -> &'static str {
+        "ListPendingMultisig"
+    }
+
+    async fn execute(self) -> CliTypedResult<PendingTransactions> {
+        let client = self.rest_options.client(&self.profile_options)?;
+        let num_signatures_required = parse_u64(
+            &self
+                .view(&client, ident_str!("num_signatures_required"))
+                .await?[0],
+        )?;
+        let owners = parse_addresses(&self.view(&client, ident_str!("owners")).await?[0])?;
+        let last_resolved_sequence_number = parse_u64(
+            &self
+                .view(&client, ident_str!("last_resolved_sequence_number"))
+                .await?[0],
+        )?;
+        let pending = self
+            .view(&client, ident_str!("get_pending_transactions"))
+            .await?;
+        let pending = pending[0].as_array().ok_or_else(|| {
+            CliError::UnexpectedError(format!(
+                "Pending transactions are not a list: {}",
+                pending[0]
+            ))
+        })?;
+
+        let mut transactions = Vec::with_capacity(pending.len());
+        for (sequence_number, transaction) in (last_resolved_sequence_number + 1..).zip(pending) {
+            let payload = match view_json_option_str(&transaction["payload"])? {
+                Some(payload) => Some(decode_multisig_payload(&payload, &client).await?),
+                None => None,
+            };
+            transactions.push(parse_pending_transaction(
+                sequence_number,
+                transaction,
+                num_signatures_required,
+                payload,
+            )?);
+        }
+
+        Ok(PendingTransactions {
+            num_signatures_required,
+            owners,
+            transactions,
+        })
+    }
+}
+
+/// Decodes a hex encoded `MultisigTransactionPayload`, leaving it as hex if it can't be parsed
+async fn decode_multisig_payload(payload: &str, client: &Client) -> CliTypedResult<Value> {
+    let bytes = payload.parse::<HexEncodedBytes>()?;
+    match bcs::from_bytes::<MultisigTransactionPayload>(bytes.inner()) {
+        Ok(MultisigTransactionPayload::EntryFunction(entry_function)) => {
+            decode_payload(
+                &TransactionPayload::EntryFunction(entry_function),
+                Some(client),
+            )
+            .await
+        },
+        Err(_) => Ok(json!(payload)),
+    }
+}
+
+/// Parses a transaction returned by `multisig_account::get_pending_transactions`, with its payload
+/// already decoded
+fn parse_pending_transaction(
+    sequence_number: u64,
+    transaction: &Value,
+    num_signatures_required: u64,
+    payload: Option<Value>,
+) -> CliTypedResult<PendingTransaction> {
+    let (mut approvals, mut rejections) = (vec![], vec![]);
+    let votes = transaction["votes"]["data"].as_array().ok_or_else(|| {
+        CliError::UnexpectedError(format!("Votes are not a list: {}", transaction))
+    })?;
+    for vote in votes {
+        let voter = parse_address(&vote["key"])?;
+        match vote["value"].as_bool() {
+            Some(true) => approvals.push(voter),
+            Some(false) => rejections.push(voter),
+            None => {
+                return Err(CliError::UnexpectedError(format!(
+                    "Vote is not a bool: {}",
+                    vote
+                )))
+            },
+        }
+    }
+
+    Ok(PendingTransaction {
+        sequence_number,
+        creator: parse_address(&transaction["creator"])?,
+        creation_time_secs: parse_u64(&transaction["creation_time_secs"])?,
+        executable: approvals.len() as u64 >= num_signatures_required,
+        rejectable: rejections.len() as u64 >= num_signatures_required,
+        approvals,
+        rejections,
+        payload,
+        payload_hash: view_json_option_str(&transaction["payload_hash"])?,
+    })
+}
+
+fn parse_u64(value: &Value) -> CliTypedResult<u64> {
+    value
+        .as_str()
+        .and_then(|value| value.parse().ok())
+        .ok_or_else(|| CliError::UnexpectedError(format!("Not a u64: {}", value)))
+}
+
+fn parse_address(value: &Value) -> CliTypedResult<AccountAddress> {
+    value
+        .as_str()
+        .and_then(|value| AccountAddress::from_str(value).ok())
+        .ok_or_else(|| CliError::UnexpectedError(format!("Not an address: {}", value)))
+}
+
+fn parse_addresses(value: &Value) -> CliTypedResult<Vec<AccountAddress>> {
+    value
+        .as_array()
+        .ok_or_else(|| CliError::UnexpectedError(format!("Not a list: {}", value)))?
+        .iter()
+        .map(parse_address)
+        .collect()
+}
+
 /// Execute a proposed multisig transaction that has a full payload stored on-chain.
 #[derive(Debug, Parser)]
 pub struct Execute {
@@ -368,3 +629,121 @@ impl CliCommand<TransactionSummary> for ExecuteReject {
             .map(|inner| inner.into())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MULTISIG: &str = "0xcafe";
+
+    fn parse_vote(args: &[&str]) -> Result<Vote, clap::Error> {
+        Vote::try_parse_from(
+            [
+                "vote",
+                "--multisig-address",
+                MULTISIG,
+                "--sequence-number",
+                "3",
+            ]
+            .iter()
+            .chain(args.iter()),
+        )
+    }
+
+    #[test]
+    fn test_vote_args() {
+        assert!(parse_vote(&["--approve"]).unwrap().approve);
+        assert!(parse_vote(&["--reject"]).unwrap().reject);
+        assert!(parse_vote(&[]).is_err());
+        assert!(parse_vote(&["--approve", "--reject"]).is_err());
+    }
+
+    #[test]
+    fn test_vote_payload() {
+        let multisig_address = AccountAddress::from_str(MULTISIG).unwrap();
+        assert_eq!(
+            parse_vote(&["--approve"]).unwrap().payload().unwrap(),
+            aptos_stdlib::multisig_account_vote_transaction(multisig_address, 3, true)
+        );
+        assert_eq!(
+            parse_vote(&["--reject", "--final-sequence-number", "3"])
+                .unwrap()
+                .payload()
+                .unwrap(),
+            aptos_stdlib::multisig_account_vote_transaction(multisig_address, 3, false)
+        );
+        assert_eq!(
+            parse_vote(&["--approve", "--final-sequence-number", "5"])
+                .unwrap()
+                .payload()
+                .unwrap(),
+            aptos_stdlib::multisig_account_vote_transactions(multisig_address, 3, 5, true)
+        );
+        assert!(matches!(
+            parse_vote(&["--approve", "--final-sequence-number", "2"])
+                .unwrap()
+                .payload(),
+            Err(CliError::CommandArgumentError(_))
+        ));
+    }
+
+    #[test]
+    fn test_parse_helpers() {
+        assert_eq!(parse_u64(&json!("42")).unwrap(), 42);
+        assert!(parse_u64(&json!(42)).is_err());
+        assert!(parse_u64(&json!("-1")).is_err());
+
+        assert_eq!(parse_address(&json!("0x1")).unwrap(), AccountAddress::ONE);
+        assert!(parse_address(&json!("not an address")).is_err());
+        assert!(parse_address(&json!(1)).is_err());
+
+        assert_eq!(parse_addresses(&json!(["0x1", "0x2"])).unwrap(), vec![
+            AccountAddress::ONE,
+            AccountAddress::TWO
+        ]);
+        assert!(parse_addresses(&json!([])).unwrap().is_empty());
+        assert!(parse_addresses(&json!("0x1")).is_err());
+        assert!(parse_addresses(&json!(["0x1", 2])).is_err());
+    }
+
+    #[test]
+    fn test_parse_pending_transaction() {
+        let transaction = json!({
+            "creator": "0x1",
+            "creation_time_secs": "1700000000",
+            "payload": { "vec": [] },
+            "payload_hash": { "vec": ["0xabcd"] },
+            "votes": {
+                "data": [
+                    { "key": "0x1", "value": true },
+                    { "key": "0x2", "value": false },
+                    { "key": "0x3", "value": true },
+                ]
+            },
+        });
+
+        let pending = parse_pending_transaction(7, &transaction, 2, None).unwrap();
+        assert_eq!(pending.sequence_number, 7);
+        assert_eq!(pending.creator, AccountAddress::ONE);
+        assert_eq!(pending.creation_time_secs, 1_700_000_000);
+        assert_eq!(pending.approvals, vec![
+            AccountAddress::ONE,
+            AccountAddress::from_str("0x3").unwrap()
+        ]);
+        assert_eq!(pending.rejections, vec![AccountAddress::TWO]);
+        assert!(pending.executable);
+        assert!(!pending.rejectable);
+        assert_eq!(pending.payload, None);
+        assert_eq!(pending.payload_hash.as_deref(), Some("0xabcd"));
+
+        let pending = parse_pending_transaction(7, &transaction, 1, None).unwrap();
+        assert!(pending.executable && pending.rejectable);
+
+        let mut invalid_vote = transaction.clone();
+        invalid_vote["votes"]["data"][0]["value"] = json!("yes");
+        assert!(parse_pending_transaction(7, &invalid_vote, 2, None).is_err());
+        let mut no_votes = transaction;
+        no_votes["votes"] = json!([]);
+        assert!(parse_pending_transaction(7, &no_votes, 2, None).is_err());
+    }
+}
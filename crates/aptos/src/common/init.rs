@@ -217,11 +217,11 @@ impl CliCommand<()> for InitTool {
                 eprintln!("Using command line argument for private key");
                 key
             } else {
-                eprintln!("Enter your private key as a hex literal (0x...) [Current: {} | No input: Generate new key (or keep one if present)]", profile_config.private_key.as_ref().map(|_| "Redacted").unwrap_or("None"));
+                eprintln!("Enter your private key as a hex literal (0x...) [Current: {} | No input: Generate new key (or keep one if present)]", if profile_config.has_private_key() { "Redacted" } else { "None" });
                 let input = read_line("Private key")?;
                 let input = input.trim();
                 if input.is_empty() {
-                    if let Some(key) = profile_config.private_key {
+                    if let Some(key) = profile_config.decrypted_private_key(profile_name)? {
                         eprintln!("No key given, keeping existing key...");
                         key
                     } else {
@@ -278,7 +278,15 @@ impl CliCommand<()> for InitTool {
         let derived_address = account_address_from_public_key(&public_key);
         let address = lookup_address(&client, derived_address, false).await?;
 
-        profile_config.private_key = private_key;
+        // Keep an existing encrypted key encrypted, unless it has been replaced
+        if profile_config.encrypted_private_key.is_none()
+            || profile_config.public_key.as_ref() != Some(&public_key)
+        {
+            profile_config.private_key = private_key;
+            if let Some(encrypted_private_key) = profile_config.encrypted_private_key.take() {
+                encrypted_private_key.delete_keychain_entry()?;
+            }
+        }
         profile_config.public_key = Some(public_key);
         profile_config.account = Some(address);
 
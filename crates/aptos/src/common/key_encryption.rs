@@ -0,0 +1,253 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Encryption of profile private keys at rest
+//!
+//! Keys are encrypted with AES-256-GCM, under a key derived from a passphrase with
+//! PBKDF2-HMAC-SHA256.  The passphrase is either a random one kept in the OS keychain, or read from
+//! `APTOS_PROFILE_PASSPHRASE` if set, and prompted for otherwise.
+
+use crate::common::{
+    types::{CliError, CliTypedResult},
+    utils::read_line,
+};
+use aes_gcm::{
+    aead::{rand_core::RngCore, Aead, OsRng},
+    AeadCore, Aes256Gcm, Key, KeyInit, Nonce,
+};
+use aptos_api_types::HexEncodedBytes;
+use aptos_crypto::ed25519::Ed25519PrivateKey;
+use crossterm::{
+    event::{Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers},
+    terminal,
+};
+use serde::{Deserialize, Serialize};
+use std::num::NonZeroU32;
+use zeroize::Zeroizing;
+
+/// Environment variable holding the passphrase, for non-interactive use
+pub const PASSPHRASE_ENV_VAR: &str = "APTOS_PROFILE_PASSPHRASE";
+
+/// Service under which the passphrases are stored in the OS keychain
+const KEYCHAIN_SERVICE: &str = "aptos-cli";
+
+const KDF_ITERATIONS: u32 = 600_000;
+const SALT_LENGTH: usize = 16;
+const KEYCHAIN_ENTRY_LENGTH: usize = 16;
+const KEYCHAIN_PASSPHRASE_LENGTH: usize = 32;
+
+/// A private key encrypted under a passphrase
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
+pub struct EncryptedPrivateKey {
+    pub kdf_iterations: u32,
+    pub salt: HexEncodedBytes,
+    pub nonce: HexEncodedBytes,
+    pub ciphertext: HexEncodedBytes,
+    /// Name of the OS keychain entry holding the passphrase, if it isn't prompted for
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub keychain_entry: Option<String>,
+}
+
+impl EncryptedPrivateKey {
+    pub fn encrypt(private_key: &Ed25519PrivateKey, passphrase: &str) -> CliTypedResult<Self> {
+        let mut salt = vec![0; SALT_LENGTH];
+        OsRng.fill_bytes(&mut salt);
+        let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+        let ciphertext = cipher(passphrase, &salt, KDF_ITERATIONS)?
+            .encrypt(&nonce, Zeroizing::new(private_key.to_bytes()).as_slice())
+            .map_err(|err| {
+                CliError::UnexpectedError(format!("Failed to encrypt private key: {}", err))
+            })?;
+
+        Ok(Self {
+            kdf_iterations: KDF_ITERATIONS,
+            salt: salt.into(),
+            nonce: nonce.to_vec().into(),
+            ciphertext: ciphertext.into(),
+            keychain_entry: None,
+        })
+    }
+
+    /// Encrypts `private_key` under a random passphrase, which is stored in the OS keychain
+    pub fn encrypt_with_keychain(private_key: &Ed25519PrivateKey) -> CliTypedResult<Self> {
+        let mut entry = [0; KEYCHAIN_ENTRY_LENGTH];
+        OsRng.fill_bytes(&mut entry);
+        let entry = hex::encode(entry);
+        let mut passphrase = Zeroizing::new([0; KEYCHAIN_PASSPHRASE_LENGTH]);
+        OsRng.fill_bytes(&mut passphrase[..]);
+        let passphrase = Zeroizing::new(hex::encode(&passphrase[..]));
+
+        keychain_entry(&entry)?
+            .set_password(&passphrase)
+            .map_err(|err| keychain_error("store the passphrase in", err))?;
+        Ok(Self {
+            keychain_entry: Some(entry),
+            ..Self::encrypt(private_key, &passphrase)?
+        })
+    }
+
+    /// Decrypts the private key of `profile`, with the passphrase from the OS keychain if it's
+    /// kept there, and prompting for it otherwise
+    pub fn decrypt_profile_key(&self, profile: &str) -> CliTypedResult<Ed25519PrivateKey> {
+        let passphrase = match &self.keychain_entry {
+            Some(entry) => Zeroizing::new(
+                keychain_entry(entry)?
+                    .get_password()
+                    .map_err(|err| keychain_error("read the passphrase from", err))?,
+            ),
+            None => read_passphrase(profile, false)?,
+        };
+        self.decrypt(&passphrase)
+    }
+
+    /// Removes the passphrase from the OS keychain, if it's kept there
+    pub fn delete_keychain_entry(&self) -> CliTypedResult<()> {
+        if let Some(entry) = &self.keychain_entry {
+            keychain_entry(entry)?
+                .delete_credential()
+                .map_err(|err| keychain_error("delete the passphrase from", err))?;
+        }
+        Ok(())
+    }
+
+    pub fn decrypt(&self, passphrase: &str) -> CliTypedResult<Ed25519PrivateKey> {
+        if self.nonce.inner().len() != 12 {
+            return Err(CliError::UnableToParse(
+                "EncryptedPrivateKey",
+                format!("invalid nonce length {}", self.nonce.inner().len()),
+            ));
+        }
+        let bytes = Zeroizing::new(
+            cipher(passphrase, self.salt.inner(), self.kdf_iterations)?
+                .decrypt(
+                    Nonce::from_slice(self.nonce.inner()),
+                    self.ciphertext.inner(),
+                )
+                .map_err(|_| {
+                    CliError::CommandArgumentError(
+                        "Failed to decrypt private key, is the passphrase correct?".to_string(),
+                    )
+                })?,
+        );
+        Ed25519PrivateKey::try_from(bytes.as_slice())
+            .map_err(|err| CliError::UnableToParse("Ed25519PrivateKey", err.to_string()))
+    }
+}
+
+fn cipher(passphrase: &str, salt: &[u8], iterations: u32) -> CliTypedResult<Aes256Gcm> {
+    let iterations = NonZeroU32::new(iterations).ok_or_else(|| {
+        CliError::UnableToParse("EncryptedPrivateKey", "zero KDF iterations".to_string())
+    })?;
+    let mut key = Zeroizing::new([0; 32]);
+    ring::pbkdf2::derive(
+        ring::pbkdf2::PBKDF2_HMAC_SHA256,
+        iterations,
+        salt,
+        passphrase.as_bytes(),
+        &mut key[..],
+    );
+    Ok(Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&key[..])))
+}
+
+fn keychain_entry(entry: &str) -> CliTypedResult<keyring::Entry> {
+    keyring::Entry::new(KEYCHAIN_SERVICE, entry)
+        .map_err(|err| keychain_error("access the entry in", err))
+}
+
+fn keychain_error(action: &str, err: keyring::Error) -> CliError {
+    CliError::UnexpectedError(format!("Failed to {} the OS keychain: {}", action, err))
+}
+
+/// Reads the passphrase for `profile`, asking for it twice if `confirm` is set
+pub fn read_passphrase(profile: &str, confirm: bool) -> CliTypedResult<Zeroizing<String>> {
+    if let Ok(passphrase) = std::env::var(PASSPHRASE_ENV_VAR) {
+        return Ok(Zeroizing::new(passphrase));
+    }
+
+    eprintln!(
+        "Enter the passphrase for the private key of profile {}:",
+        profile
+    );
+    let passphrase = read_hidden_line()?;
+    if confirm {
+        eprintln!("Enter the passphrase again:");
+        if read_hidden_line()? != passphrase {
+            return Err(CliError::CommandArgumentError(
+                "Passphrases do not match".to_string(),
+            ));
+        }
+    }
+    if passphrase.is_empty() {
+        return Err(CliError::CommandArgumentError(
+            "Passphrase must not be empty".to_string(),
+        ));
+    }
+    Ok(passphrase)
+}
+
+/// Reads a line without echoing it, falling back to a plain read if stdin isn't a terminal
+fn read_hidden_line() -> CliTypedResult<Zeroizing<String>> {
+    if terminal::enable_raw_mode().is_err() {
+        let line = Zeroizing::new(read_line("Passphrase")?);
+        return Ok(Zeroizing::new(
+            line.trim_end_matches(['\r', '\n']).to_string(),
+        ));
+    }
+
+    let mut input = Zeroizing::new(String::new());
+    let result = loop {
+        match crossterm::event::read() {
+            Ok(Event::Key(KeyEvent {
+                code,
+                modifiers,
+                kind: KeyEventKind::Press,
+                ..
+            })) => match code {
+                KeyCode::Enter => break Ok(()),
+                KeyCode::Char('c') if modifiers.contains(KeyModifiers::CONTROL) => {
+                    break Err(CliError::AbortedError)
+                },
+                KeyCode::Backspace => {
+                    input.pop();
+                },
+                KeyCode::Char(char) => input.push(char),
+                _ => {},
+            },
+            Ok(_) => {},
+            Err(err) => break Err(CliError::IO("Passphrase".to_string(), err)),
+        }
+    };
+    let _ = terminal::disable_raw_mode();
+    eprintln!();
+    result.map(|_| input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aptos_crypto::Uniform;
+
+    #[test]
+    fn test_encrypt_decrypt() {
+        let private_key = Ed25519PrivateKey::generate(&mut rand::thread_rng());
+        let encrypted = EncryptedPrivateKey::encrypt(&private_key, "passphrase").unwrap();
+        assert_eq!(
+            encrypted.decrypt("passphrase").unwrap().to_bytes(),
+            private_key.to_bytes()
+        );
+        assert!(encrypted.decrypt("wrong passphrase").is_err());
+    }
+
+    #[test]
+    fn test_passphrase_encrypted_key_has_no_keychain_entry() {
+        let private_key = Ed25519PrivateKey::generate(&mut rand::thread_rng());
+        let encrypted = EncryptedPrivateKey::encrypt(&private_key, "passphrase").unwrap();
+        let yaml = serde_yaml::to_string(&encrypted).unwrap();
+        assert!(!yaml.contains("keychain_entry"));
+        assert_eq!(
+            serde_yaml::from_str::<EncryptedPrivateKey>(&yaml).unwrap(),
+            encrypted
+        );
+        assert!(encrypted.delete_keychain_entry().is_ok());
+    }
+}
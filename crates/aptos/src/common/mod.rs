@@ -2,7 +2,9 @@
 // SPDX-License-Identifier: Apache-2.0
 
 pub mod init;
+pub mod key_encryption;
 pub mod local_simulation;
+pub mod signer;
 pub mod transactions;
 pub mod types;
 pub mod utils;
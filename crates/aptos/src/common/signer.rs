@@ -0,0 +1,286 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::common::types::{
+    account_address_from_public_key, CliConfig, CliError, CliTypedResult, ConfigSearchMode,
+    PrivateKeyInputOptions, ProfileConfig, ProfileOptions, DEFAULT_PROFILE,
+};
+use aptos_crypto::{
+    ed25519::Ed25519PublicKey, encoding_type::EncodingType, signing_message, PrivateKey, SigningKey,
+};
+use aptos_sdk::types::{
+    HardwareWalletAccount, HardwareWalletType, LocalAccount, TransactionSigner,
+};
+use aptos_types::transaction::{
+    authenticator::AccountAuthenticator, RawTransaction, RawTransactionWithData, SignedTransaction,
+};
+use move_core_types::account_address::AccountAddress;
+
+/// An account able to sign transactions, either with a private key or on a Ledger device
+///
+/// Every signer of a transaction, i.e. the sender, secondary signers and the fee payer, can be
+/// backed by either kind of account.
+#[derive(Debug)]
+pub enum AccountSigner {
+    Local(LocalAccount),
+    HardwareWallet(HardwareWalletAccount),
+}
+
+impl AccountSigner {
+    pub fn ledger(
+        address: AccountAddress,
+        public_key: Ed25519PublicKey,
+        derivation_path: String,
+    ) -> Self {
+        Self::HardwareWallet(HardwareWalletAccount::new(
+            address,
+            public_key,
+            derivation_path,
+            HardwareWalletType::Ledger,
+            0,
+        ))
+    }
+
+    /// Loads the signer from `--private-key` or `--private-key-file`, falling back to the profile
+    ///
+    /// `maybe_address` overrides the account address, e.g. when its key has been rotated.
+    pub fn from_options(
+        private_key_options: &PrivateKeyInputOptions,
+        encoding: EncodingType,
+        profile_options: &ProfileOptions,
+        maybe_address: Option<AccountAddress>,
+    ) -> CliTypedResult<Self> {
+        if private_key_options.has_key_or_file() {
+            let (private_key, address) = private_key_options.extract_private_key_and_address(
+                encoding,
+                profile_options,
+                maybe_address,
+            )?;
+            return Ok(Self::Local(LocalAccount::new(address, private_key, 0)));
+        }
+
+        let name = profile_options.profile_name().unwrap_or(DEFAULT_PROFILE);
+        let profile = profile_options.profile()?;
+        Self::from_profile_config(name, profile, maybe_address)
+    }
+
+    /// Loads the signer of a profile, preferring its private key over its derivation path
+    pub fn from_profile(name: &str) -> CliTypedResult<Self> {
+        let profile = CliConfig::load_profile(Some(name), ConfigSearchMode::CurrentDirAndParents)?
+            .ok_or_else(|| CliError::ConfigNotFoundError(name.to_string()))?;
+        Self::from_profile_config(name, profile, None)
+    }
+
+    fn from_profile_config(
+        name: &str,
+        profile: ProfileConfig,
+        maybe_address: Option<AccountAddress>,
+    ) -> CliTypedResult<Self> {
+        let private_key = profile.decrypted_private_key(name)?;
+        let ProfileConfig {
+            public_key,
+            account,
+            derivation_path,
+            ..
+        } = profile;
+        let account = maybe_address.or(account);
+
+        match (private_key, public_key, derivation_path) {
+            (Some(private_key), _, _) => {
+                let address = account
+                    .unwrap_or_else(|| account_address_from_public_key(&private_key.public_key()));
+                Ok(Self::Local(LocalAccount::new(address, private_key, 0)))
+            },
+            (None, Some(public_key), Some(derivation_path)) => {
+                let address =
+                    account.unwrap_or_else(|| account_address_from_public_key(&public_key));
+                Ok(Self::ledger(address, public_key, derivation_path))
+            },
+            _ => Err(CliError::CommandArgumentError(format!(
+                "Profile {} has neither a private key nor a Ledger derivation path",
+                name
+            ))),
+        }
+    }
+
+    pub fn address(&self) -> AccountAddress {
+        match self {
+            Self::Local(account) => account.address(),
+            Self::HardwareWallet(account) => account.address(),
+        }
+    }
+
+    pub fn public_key(&self) -> &Ed25519PublicKey {
+        match self {
+            Self::Local(account) => account.public_key(),
+            Self::HardwareWallet(account) => account.public_key(),
+        }
+    }
+
+    /// Signs a transaction that has no other signers
+    pub fn sign_transaction(&self, txn: RawTransaction) -> CliTypedResult<SignedTransaction> {
+        match self {
+            Self::Local(account) => Ok(account.sign_transaction(txn)),
+            Self::HardwareWallet(account) => Ok(TransactionSigner::sign_transaction(account, txn)?),
+        }
+    }
+
+    /// Signs a transaction that has no other signers, returning only the authenticator so that
+    /// the transaction can be assembled elsewhere
+    pub fn sign_raw_transaction(
+        &self,
+        txn: &RawTransaction,
+    ) -> CliTypedResult<AccountAuthenticator> {
+        let signature = match self {
+            Self::Local(account) => account.private_key().sign(txn)?,
+            Self::HardwareWallet(account) => {
+                account.sign_arbitrary_message(signing_message(txn)?.as_ref())?
+            },
+        };
+        Ok(AccountAuthenticator::ed25519(
+            self.public_key().clone(),
+            signature,
+        ))
+    }
+
+    /// Signs a multi-agent or fee payer transaction as any one of its signers
+    pub fn sign_transaction_with_data(
+        &self,
+        txn: &RawTransactionWithData,
+    ) -> CliTypedResult<AccountAuthenticator> {
+        match self {
+            Self::Local(account) => Ok(AccountAuthenticator::ed25519(
+                account.public_key().clone(),
+                account.private_key().sign(txn)?,
+            )),
+            Self::HardwareWallet(account) => Ok(account.sign_raw_transaction_with_data(txn)?),
+        }
+    }
+}
+
+/// Signs `txn` as its sender, together with any secondary signers and fee payer
+///
+/// Transactions with neither are signed as plain single sender transactions.
+pub fn sign_transaction(
+    txn: RawTransaction,
+    sender: &AccountSigner,
+    secondary_signers: &[AccountSigner],
+    fee_payer: Option<&AccountSigner>,
+) -> CliTypedResult<SignedTransaction> {
+    if secondary_signers.is_empty() && fee_payer.is_none() {
+        return sender.sign_transaction(txn);
+    }
+
+    let secondary_signer_addresses: Vec<_> = secondary_signers
+        .iter()
+        .map(AccountSigner::address)
+        .collect();
+    let message = match fee_payer {
+        Some(fee_payer) => RawTransactionWithData::new_fee_payer(
+            txn.clone(),
+            secondary_signer_addresses.clone(),
+            fee_payer.address(),
+        ),
+        None => {
+            RawTransactionWithData::new_multi_agent(txn.clone(), secondary_signer_addresses.clone())
+        },
+    };
+
+    let sender_authenticator = sender.sign_transaction_with_data(&message)?;
+    let secondary_authenticators = secondary_signers
+        .iter()
+        .map(|signer| signer.sign_transaction_with_data(&message))
+        .collect::<CliTypedResult<Vec<_>>>()?;
+    Ok(match fee_payer {
+        Some(fee_payer) => SignedTransaction::new_fee_payer(
+            txn,
+            sender_authenticator,
+            secondary_signer_addresses,
+            secondary_authenticators,
+            fee_payer.address(),
+            fee_payer.sign_transaction_with_data(&message)?,
+        ),
+        None => SignedTransaction::new_multi_agent(
+            txn,
+            sender_authenticator,
+            secondary_signer_addresses,
+            secondary_authenticators,
+        ),
+    })
+}
+
+/// Wraps a multi-agent or fee payer `txn` with empty authenticators, for simulation
+pub fn unsigned_transaction(
+    txn: RawTransaction,
+    secondary_signers: &[AccountSigner],
+    fee_payer: Option<&AccountSigner>,
+) -> SignedTransaction {
+    let secondary_signer_addresses: Vec<_> = secondary_signers
+        .iter()
+        .map(AccountSigner::address)
+        .collect();
+    let secondary_authenticators =
+        vec![AccountAuthenticator::NoAccountAuthenticator; secondary_signer_addresses.len()];
+    match fee_payer {
+        Some(fee_payer) => SignedTransaction::new_fee_payer(
+            txn,
+            AccountAuthenticator::NoAccountAuthenticator,
+            secondary_signer_addresses,
+            secondary_authenticators,
+            fee_payer.address(),
+            AccountAuthenticator::NoAccountAuthenticator,
+        ),
+        None => SignedTransaction::new_multi_agent(
+            txn,
+            AccountAuthenticator::NoAccountAuthenticator,
+            secondary_signer_addresses,
+            secondary_authenticators,
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aptos_types::{
+        chain_id::ChainId,
+        transaction::{Script, TransactionPayload},
+    };
+
+    fn raw_transaction(sender: AccountAddress) -> RawTransaction {
+        RawTransaction::new(
+            sender,
+            0,
+            TransactionPayload::Script(Script::new(vec![], vec![], vec![])),
+            1_000,
+            100,
+            u64::MAX,
+            ChainId::test(),
+        )
+    }
+
+    #[test]
+    fn test_sign_transaction_with_additional_signers() {
+        let mut rng = rand::thread_rng();
+        let sender = AccountSigner::Local(LocalAccount::generate(&mut rng));
+        let secondary_signers = vec![AccountSigner::Local(LocalAccount::generate(&mut rng))];
+        let fee_payer = AccountSigner::Local(LocalAccount::generate(&mut rng));
+
+        let txn = raw_transaction(sender.address());
+        let signed = sign_transaction(txn.clone(), &sender, &[], None).unwrap();
+        signed.verify_signature().unwrap();
+
+        let signed = sign_transaction(txn.clone(), &sender, &secondary_signers, None).unwrap();
+        signed.verify_signature().unwrap();
+        assert_eq!(signed.authenticator().secondary_signer_addresses(), vec![
+            secondary_signers[0].address()
+        ]);
+
+        let signed = sign_transaction(txn, &sender, &secondary_signers, Some(&fee_payer)).unwrap();
+        signed.verify_signature().unwrap();
+        assert_eq!(
+            signed.authenticator().fee_payer_address(),
+            Some(fee_payer.address())
+        );
+    }
+}
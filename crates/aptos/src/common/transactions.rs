@@ -99,7 +99,7 @@ impl TxnOptions {
             self.profile_options.profile_name(),
             ConfigSearchMode::CurrentDirAndParents,
         )? {
-            if profile.private_key.is_some() {
+            if profile.has_private_key() {
                 Ok(AccountType::Local)
             } else {
                 Ok(AccountType::HardwareWallet)
@@ -140,7 +140,7 @@ impl TxnOptions {
     }
 
     pub fn sender_address(&self) -> CliTypedResult<AccountAddress> {
-        Ok(self.get_key_and_address()?.1)
+        self.get_address()
     }
 
     pub fn get_public_key(&self) -> CliTypedResult<Ed25519PublicKey> {
@@ -5,7 +5,9 @@ use super::utils::{explorer_transaction_link, fund_account, strip_private_key_pr
 use crate::{
     common::{
         init::Network,
+        key_encryption::EncryptedPrivateKey,
         local_simulation,
+        signer::{self, AccountSigner},
         transactions::ReplayProtectionType,
         utils::{
             check_if_file_exists, create_dir_if_not_exist, deserialize_address_str,
@@ -39,10 +41,7 @@ use aptos_rest_client::{
     error::RestError,
     AptosBaseUrl, Client, Transaction,
 };
-use aptos_sdk::{
-    transaction_builder::TransactionFactory,
-    types::{HardwareWalletAccount, HardwareWalletType, LocalAccount, TransactionSigner},
-};
+use aptos_sdk::{transaction_builder::TransactionFactory, types::LocalAccount};
 use aptos_types::{
     chain_id::ChainId,
     transaction::{
@@ -277,6 +276,9 @@ pub struct ProfileConfig {
         deserialize_with = "deserialize_material_with_prefix"
     )]
     pub private_key: Option<Ed25519PrivateKey>,
+    /// Private key for commands, encrypted under a passphrase
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub encrypted_private_key: Option<EncryptedPrivateKey>,
     /// Public key for commands
     #[serde(
         skip_serializing_if = "Option::is_none",
@@ -307,6 +309,7 @@ pub struct ProfileSummary {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub network: Option<Network>,
     pub has_private_key: bool,
+    pub has_encrypted_private_key: bool,
     #[serde(
         skip_serializing_if = "Option::is_none",
         serialize_with = "serialize_material_with_prefix",
@@ -328,7 +331,8 @@ impl From<&ProfileConfig> for ProfileSummary {
     fn from(config: &ProfileConfig) -> Self {
         ProfileSummary {
             network: config.network,
-            has_private_key: config.private_key.is_some(),
+            has_private_key: config.has_private_key(),
+            has_encrypted_private_key: config.encrypted_private_key.is_some(),
             public_key: config.public_key.clone(),
             account: config.account,
             rest_url: config.rest_url.clone(),
@@ -337,6 +341,25 @@ impl From<&ProfileConfig> for ProfileSummary {
     }
 }
 
+impl ProfileConfig {
+    /// Whether the profile has a private key, either in plain text or encrypted
+    pub fn has_private_key(&self) -> bool {
+        self.private_key.is_some() || self.encrypted_private_key.is_some()
+    }
+
+    /// Returns the private key of the profile `name`, prompting for the passphrase if it's
+    /// encrypted and the passphrase isn't kept in the OS keychain
+    pub fn decrypted_private_key(&self, name: &str) -> CliTypedResult<Option<Ed25519PrivateKey>> {
+        match (&self.private_key, &self.encrypted_private_key) {
+            (Some(private_key), _) => Ok(Some(private_key.clone())),
+            (None, Some(encrypted_private_key)) => {
+                encrypted_private_key.decrypt_profile_key(name).map(Some)
+            },
+            (None, None) => Ok(None),
+        }
+    }
+}
+
 impl Default for CliConfig {
     fn default() -> Self {
         CliConfig {
@@ -931,7 +954,11 @@ impl PrivateKeyInputOptions {
             profile.profile_name(),
             ConfigSearchMode::CurrentDirAndParents,
         )?
-        .map(|p| (p.private_key, p.account))
+        .map(|p| {
+            p.decrypted_private_key(profile.profile_name().unwrap_or(DEFAULT_PROFILE))
+                .map(|key| (key, p.account))
+        })
+        .transpose()?
         {
             match (maybe_address, maybe_config_address) {
                 (Some(address), _) => Ok((key, address)),
@@ -960,7 +987,8 @@ impl PrivateKeyInputOptions {
             profile.profile_name(),
             ConfigSearchMode::CurrentDirAndParents,
         )?
-        .map(|p| p.private_key)
+        .map(|p| p.decrypted_private_key(profile.profile_name().unwrap_or(DEFAULT_PROFILE)))
+        .transpose()?
         {
             Ok(private_key)
         } else {
@@ -1806,6 +1834,19 @@ pub struct TransactionOptions {
     /// When "seqnum" is chosen, the transaction will contain a sequence number that matches with the sender's onchain sequence number.
     #[clap(long, default_value_t = ReplayProtectionType::Seqnum)]
     pub(crate) replay_protection_type: ReplayProtectionType,
+
+    /// Profiles of the secondary signers of a multi-agent transaction
+    ///
+    /// Like the sender, each profile may be backed by either a private key or a Ledger
+    /// derivation path.
+    #[clap(long, num_args = 0..)]
+    pub(crate) secondary_signer_profiles: Vec<String>,
+
+    /// Profile of the account paying for the gas of the transaction
+    ///
+    /// Defaults to the sender paying for its own gas.
+    #[clap(long)]
+    pub(crate) fee_payer_profile: Option<String>,
 }
 
 impl TransactionOptions {
@@ -1823,7 +1864,7 @@ impl TransactionOptions {
             self.profile_options.profile_name(),
             ConfigSearchMode::CurrentDirAndParents,
         )? {
-            if profile.private_key.is_some() {
+            if profile.has_private_key() {
                 Ok(AccountType::Local)
             } else {
                 Ok(AccountType::HardwareWallet)
@@ -1855,8 +1896,14 @@ impl TransactionOptions {
             )
     }
 
+    /// Retrieves the sender's address, which doesn't require its private key so that it also
+    /// works for profiles backed by a Ledger
     pub fn sender_address(&self) -> CliTypedResult<AccountAddress> {
-        Ok(self.get_key_and_address()?.1)
+        self.private_key_options.extract_address(
+            self.encoding_options.encoding,
+            &self.profile_options,
+            self.sender_account,
+        )
     }
 
     pub fn get_public_key(&self) -> CliTypedResult<Ed25519PublicKey> {
@@ -1864,6 +1911,33 @@ impl TransactionOptions {
             .extract_public_key(self.encoding_options.encoding, &self.profile_options)
     }
 
+    /// Retrieves the signer of the sender, either from its private key or its Ledger
+    pub fn sender_signer(&self) -> CliTypedResult<AccountSigner> {
+        AccountSigner::from_options(
+            &self.private_key_options,
+            self.encoding_options.encoding,
+            &self.profile_options,
+            self.sender_account,
+        )
+    }
+
+    /// Retrieves the signers of the secondary signer and fee payer profiles
+    pub fn additional_signers(
+        &self,
+    ) -> CliTypedResult<(Vec<AccountSigner>, Option<AccountSigner>)> {
+        let secondary_signers = self
+            .secondary_signer_profiles
+            .iter()
+            .map(|profile| AccountSigner::from_profile(profile))
+            .collect::<CliTypedResult<Vec<_>>>()?;
+        let fee_payer = self
+            .fee_payer_profile
+            .as_deref()
+            .map(AccountSigner::from_profile)
+            .transpose()?;
+        Ok((secondary_signers, fee_payer))
+    }
+
     /// Gets the auth key by account address. We need to fetch the auth key from Rest API rather than creating an
     /// auth key out of the public key.
     pub(crate) async fn auth_key(
@@ -1894,6 +1968,9 @@ impl TransactionOptions {
     ) -> CliTypedResult<Transaction> {
         let client = self.rest_client()?;
         let (sender_public_key, sender_address) = self.get_public_key_and_address()?;
+        let sender = self.sender_signer()?;
+        let (secondary_signers, fee_payer) = self.additional_signers()?;
+        let has_additional_signers = !secondary_signers.is_empty() || fee_payer.is_some();
 
         // Ask to confirm price if the gas unit price is estimated above the lowest value when
         // it is automatically estimated
@@ -1956,11 +2033,19 @@ impl TransactionOptions {
                 txn_builder.build()
             };
 
-            let signed_transaction = SignedTransaction::new(
-                unsigned_transaction,
-                sender_public_key.clone(),
-                Ed25519Signature::try_from([0u8; 64].as_ref()).unwrap(),
-            );
+            let signed_transaction = if has_additional_signers {
+                signer::unsigned_transaction(
+                    unsigned_transaction,
+                    &secondary_signers,
+                    fee_payer.as_ref(),
+                )
+            } else {
+                SignedTransaction::new(
+                    unsigned_transaction,
+                    sender_public_key.clone(),
+                    Ed25519Signature::try_from([0u8; 64].as_ref()).unwrap(),
+                )
+            };
 
             let txns = client
                 .simulate_with_gas_estimation(&signed_transaction, true, false)
@@ -2000,39 +2085,21 @@ impl TransactionOptions {
             .with_max_gas_amount(max_gas)
             .with_transaction_expiration_time(self.gas_options.expiration_secs);
 
-        // Sign it with the appropriate signer
-        let transaction = match self.get_transaction_account_type() {
-            Ok(AccountType::Local) => {
-                let (private_key, _) = self.get_key_and_address()?;
-                let sender_account =
-                    &mut LocalAccount::new(sender_address, private_key, sequence_number);
-                let mut txn_builder = transaction_factory.payload(payload);
-                if self.replay_protection_type == ReplayProtectionType::Nonce {
-                    let mut rng = rand::thread_rng();
-                    txn_builder = txn_builder.upgrade_payload_with_rng(&mut rng, true, true);
-                };
-                sender_account.sign_with_transaction_builder(txn_builder)
-            },
-            Ok(AccountType::HardwareWallet) => {
-                let sender_account = &mut HardwareWalletAccount::new(
-                    sender_address,
-                    sender_public_key,
-                    self.profile_options
-                        .derivation_path()
-                        .expect("derivative path is missing from profile")
-                        .unwrap(),
-                    HardwareWalletType::Ledger,
-                    sequence_number,
-                );
-                let mut txn_builder = transaction_factory.payload(payload);
-                if self.replay_protection_type == ReplayProtectionType::Nonce {
-                    let mut rng = rand::thread_rng();
-                    txn_builder = txn_builder.upgrade_payload_with_rng(&mut rng, true, true);
-                };
-                sender_account.sign_with_transaction_builder(txn_builder)?
-            },
-            Err(err) => return Err(err),
+        // Sign it with the appropriate signers, each of which may be a Ledger
+        let mut txn_builder = transaction_factory
+            .payload(payload)
+            .sender(sender_address)
+            .sequence_number(sequence_number);
+        if self.replay_protection_type == ReplayProtectionType::Nonce {
+            let mut rng = rand::thread_rng();
+            txn_builder = txn_builder.upgrade_payload_with_rng(&mut rng, true, true);
         };
+        let transaction = signer::sign_transaction(
+            txn_builder.build(),
+            &sender,
+            &secondary_signers,
+            fee_payer.as_ref(),
+        )?;
 
         // Submit the transaction, printing out a useful transaction link
         client
@@ -3,6 +3,7 @@
 
 use crate::{
     common::{
+        key_encryption::{read_passphrase, EncryptedPrivateKey},
         types::{
             CliCommand, CliConfig, CliError, CliResult, CliTypedResult, ConfigSearchMode,
             ProfileSummary, APTOS_FOLDER_GIT_IGNORE, CONFIG_FOLDER, GIT_IGNORE,
@@ -26,6 +27,8 @@ use std::{collections::BTreeMap, fmt::Formatter, path::PathBuf, str::FromStr};
 /// default configuration, and user specific settings.
 #[derive(Parser)]
 pub enum ConfigTool {
+    DecryptProfile(DecryptProfile),
+    EncryptProfile(EncryptProfile),
     GenerateShellCompletions(GenerateShellCompletions),
     ShowGlobalConfig(ShowGlobalConfig),
     SetGlobalConfig(SetGlobalConfig),
@@ -38,7 +41,9 @@ pub enum ConfigTool {
 impl ConfigTool {
     pub async fn execute(self) -> CliResult {
         match self {
+            ConfigTool::DecryptProfile(tool) => tool.execute_serialized().await,
             ConfigTool::DeleteProfile(tool) => tool.execute_serialized().await,
+            ConfigTool::EncryptProfile(tool) => tool.execute_serialized().await,
             ConfigTool::GenerateShellCompletions(tool) => tool.execute_serialized_success().await,
             ConfigTool::RenameProfile(tool) => tool.execute_serialized().await,
             ConfigTool::SetGlobalConfig(tool) => tool.execute_serialized().await,
@@ -138,7 +143,7 @@ impl CliCommand<String> for ShowPrivateKey {
 
         if let Some(profiles) = &config.profiles {
             if let Some(profile) = profiles.get(&self.profile.clone()) {
-                if let Some(private_key) = &profile.private_key {
+                if let Some(private_key) = profile.decrypted_private_key(&self.profile)? {
                     Ok(private_key.to_aip_80_string()?)
                 } else {
                     Err(CliError::CommandArgumentError(format!(
@@ -160,6 +165,125 @@ impl CliCommand<String> for ShowPrivateKey {
     }
 }
 
+/// Encrypt the private keys of profiles with a passphrase
+///
+/// The passphrase is prompted for, or read from the `APTOS_PROFILE_PASSPHRASE` environment
+/// variable, whenever the private key is used.  With `--keychain`, a random passphrase is kept in
+/// the OS keychain instead, and nothing is prompted for.
+#[derive(Parser, Debug)]
+pub struct EncryptProfile {
+    /// Which profile's private key to encrypt
+    ///
+    /// If not provided, all profiles with a plain text private key are encrypted with the same
+    /// passphrase
+    #[clap(long)]
+    profile: Option<String>,
+
+    /// Keep a random passphrase for each profile in the OS keychain, instead of asking for one
+    #[clap(long)]
+    keychain: bool,
+}
+
+#[async_trait]
+impl CliCommand<Vec<String>> for EncryptProfile {
+    fn command_name(&self) -> &'static str {
+        "EncryptProfile"
+    }
+
+    async fn execute(self) -> CliTypedResult<Vec<String>> {
+        let mut config = CliConfig::load(ConfigSearchMode::CurrentDir)?;
+        let profiles = config.profiles.get_or_insert_with(BTreeMap::new);
+
+        let names: Vec<String> = if let Some(name) = self.profile {
+            match profiles.get(&name) {
+                Some(profile) if profile.private_key.is_some() => vec![name],
+                Some(profile) if profile.encrypted_private_key.is_some() => {
+                    return Err(CliError::CommandArgumentError(format!(
+                        "Profile {} is already encrypted",
+                        name
+                    )))
+                },
+                Some(_) => {
+                    return Err(CliError::CommandArgumentError(format!(
+                        "Profile {} does not have a private key",
+                        name
+                    )))
+                },
+                None => {
+                    return Err(CliError::CommandArgumentError(format!(
+                        "Profile {} does not exist",
+                        name
+                    )))
+                },
+            }
+        } else {
+            profiles
+                .iter()
+                .filter(|(_, profile)| profile.private_key.is_some())
+                .map(|(name, _)| name.clone())
+                .collect()
+        };
+        if names.is_empty() {
+            return Ok(names);
+        }
+
+        let passphrase = if self.keychain {
+            None
+        } else {
+            Some(read_passphrase(&names.join(", "), true)?)
+        };
+        for name in &names {
+            let profile = profiles.get_mut(name).expect("Profile must exist");
+            if let Some(private_key) = profile.private_key.take() {
+                profile.encrypted_private_key = Some(match &passphrase {
+                    Some(passphrase) => EncryptedPrivateKey::encrypt(&private_key, passphrase)?,
+                    None => EncryptedPrivateKey::encrypt_with_keychain(&private_key)?,
+                });
+            }
+        }
+        config.save()?;
+        Ok(names)
+    }
+}
+
+/// Decrypt the private key of a profile, storing it in plain text again
+#[derive(Parser, Debug)]
+pub struct DecryptProfile {
+    /// Which profile's private key to decrypt
+    #[clap(long)]
+    profile: String,
+}
+
+#[async_trait]
+impl CliCommand<String> for DecryptProfile {
+    fn command_name(&self) -> &'static str {
+        "DecryptProfile"
+    }
+
+    async fn execute(self) -> CliTypedResult<String> {
+        let mut config = CliConfig::load(ConfigSearchMode::CurrentDir)?;
+        let profile = config
+            .profiles
+            .as_mut()
+            .and_then(|profiles| profiles.get_mut(&self.profile))
+            .ok_or_else(|| {
+                CliError::CommandArgumentError(format!("Profile {} does not exist", self.profile))
+            })?;
+        let Some(encrypted_private_key) = profile.encrypted_private_key.clone() else {
+            return Err(CliError::CommandArgumentError(format!(
+                "Profile {} is not encrypted",
+                self.profile
+            )));
+        };
+
+        profile.private_key = profile.decrypted_private_key(&self.profile)?;
+        profile.encrypted_private_key = None;
+        config.save()?;
+        encrypted_private_key.delete_keychain_entry()?;
+        Ok(format!("Decrypted profile {}", self.profile))
+    }
+}
+
 /// Shows the current profiles available
 ///
 /// This will only show public information and will not show
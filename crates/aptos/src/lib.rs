@@ -14,6 +14,7 @@ pub mod op;
 pub mod stake;
 #[cfg(any(test, feature = "fuzzing"))]
 pub mod test;
+pub mod transaction;
 pub mod update;
 pub mod workspace;
 
@@ -51,6 +52,8 @@ pub enum Tool {
     #[clap(subcommand)]
     Stake(stake::StakeTool),
     #[clap(subcommand)]
+    Transaction(transaction::TransactionTool),
+    #[clap(subcommand)]
     Update(update::UpdateTool),
     #[clap(subcommand, hide(true))]
     Workspace(WorkspaceCommand),
@@ -72,6 +75,7 @@ impl Tool {
             Multisig(tool) => tool.execute().await,
             Node(tool) => tool.execute().await,
             Stake(tool) => tool.execute().await,
+            Transaction(tool) => tool.execute().await,
             Update(tool) => tool.execute().await,
             Workspace(workspace) => workspace.execute_serialized_without_logger().await,
         }
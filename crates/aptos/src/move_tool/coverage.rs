@@ -2,7 +2,10 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    common::types::{CliCommand, CliError, CliResult, CliTypedResult, MovePackageOptions},
+    common::{
+        types::{CliCommand, CliError, CliResult, CliTypedResult, MovePackageOptions},
+        utils::{read_from_file, write_to_file},
+    },
     move_tool::fix_bytecode_version,
 };
 use aptos_framework::extended_checks;
@@ -18,6 +21,11 @@ use move_coverage::{
 use move_disassembler::disassembler::Disassembler;
 use move_model::metadata::{CompilerVersion, LanguageVersion};
 use move_package::{compilation::compiled_package::CompiledPackage, BuildConfig, CompilerConfig};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    path::Path,
+};
 
 /// Display a coverage summary for all modules in a package
 ///
@@ -39,7 +47,8 @@ pub struct SummaryCoverage {
 }
 
 impl SummaryCoverage {
-    pub fn coverage(self) -> CliTypedResult<()> {
+    /// Prints the coverage summary, and returns the instruction coverage of each module
+    pub fn coverage(self) -> CliTypedResult<CoverageBaseline> {
         let (coverage_map, package) = compile_coverage(self.move_options)?;
         let modules: Vec<_> = package
             .root_modules()
@@ -75,7 +84,133 @@ impl SummaryCoverage {
                 self.summarize_functions,
             )
         }
-        Ok(())
+
+        let modules = modules
+            .iter()
+            .map(|module| {
+                let summary = summarize_inst_cov(module, &coverage_map);
+                let (covered, total) = summary
+                    .function_summaries
+                    .values()
+                    .filter(|function| !function.fn_is_native)
+                    .fold((0, 0), |(covered, total), function| {
+                        (covered + function.covered, total + function.total)
+                    });
+                let name = summary.module_name;
+                (
+                    format!("{}::{}", name.address().to_hex_literal(), name.name()),
+                    ModuleCoverage { covered, total },
+                )
+            })
+            .collect();
+        Ok(CoverageBaseline { modules })
+    }
+}
+
+/// Instruction coverage of each module of a package, as stored in a coverage baseline file
+#[derive(Debug, Default, Deserialize, PartialEq, Eq, Serialize)]
+pub struct CoverageBaseline {
+    pub modules: BTreeMap<String, ModuleCoverage>,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq, Serialize)]
+pub struct ModuleCoverage {
+    pub covered: u64,
+    pub total: u64,
+}
+
+impl ModuleCoverage {
+    pub fn percent_coverage(&self) -> f64 {
+        if self.total == 0 {
+            100.0
+        } else {
+            (self.covered as f64) / (self.total as f64) * 100.0
+        }
+    }
+}
+
+/// Change in coverage of a module against the baseline, for consumption by CI
+#[derive(Debug, PartialEq, Serialize)]
+pub struct ModuleCoverageDiff {
+    pub module: String,
+    /// Absent for modules that are new since the baseline
+    pub baseline_percent: Option<f64>,
+    /// Absent for modules that have been removed since the baseline
+    pub current_percent: Option<f64>,
+    pub regressed: bool,
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+pub struct CoverageDiff {
+    pub modules: Vec<ModuleCoverageDiff>,
+    pub regressed: bool,
+}
+
+impl CoverageBaseline {
+    pub fn load(path: &Path) -> CliTypedResult<Self> {
+        serde_json::from_slice(&read_from_file(path)?).map_err(|err| {
+            CliError::UnableToParse("coverage baseline", format!("{}: {}", path.display(), err))
+        })
+    }
+
+    pub fn save(&self, path: &Path) -> CliTypedResult<()> {
+        write_to_file(
+            path,
+            "coverage baseline",
+            serde_json::to_string_pretty(self)
+                .map_err(|err| CliError::UnexpectedError(err.to_string()))?
+                .as_bytes(),
+        )
+    }
+
+    /// Compares `current` against this baseline.  A module regresses when its percentage of
+    /// covered instructions drops; new and removed modules never count as regressions.
+    pub fn diff(&self, current: &CoverageBaseline) -> CoverageDiff {
+        // Percentages are only compared up to the precision they are displayed with
+        const EPSILON: f64 = 0.005;
+
+        let names: BTreeSet<_> = self.modules.keys().chain(current.modules.keys()).collect();
+        let modules: Vec<_> = names
+            .into_iter()
+            .map(|name| {
+                let baseline_percent = self.modules.get(name).map(ModuleCoverage::percent_coverage);
+                let current_percent = current
+                    .modules
+                    .get(name)
+                    .map(ModuleCoverage::percent_coverage);
+                let regressed = matches!(
+                    (baseline_percent, current_percent),
+                    (Some(baseline), Some(current)) if current + EPSILON < baseline
+                );
+                ModuleCoverageDiff {
+                    module: name.clone(),
+                    baseline_percent,
+                    current_percent,
+                    regressed,
+                }
+            })
+            .collect();
+        CoverageDiff {
+            regressed: modules.iter().any(|module| module.regressed),
+            modules,
+        }
+    }
+}
+
+impl CoverageDiff {
+    pub fn print_human(&self) {
+        for module in &self.modules {
+            let percent = |percent: Option<f64>| {
+                percent.map_or_else(|| "-".to_string(), |percent| format!("{:.2}%", percent))
+            };
+            println!(
+                "{}{}: {} -> {}",
+                if module.regressed { "REGRESSED " } else { "" },
+                module.module,
+                percent(module.baseline_percent),
+                percent(module.current_percent),
+            );
+        }
     }
 }
 
@@ -86,7 +221,7 @@ impl CliCommand<()> for SummaryCoverage {
     }
 
     async fn execute(self) -> CliTypedResult<()> {
-        self.coverage()
+        self.coverage().map(|_| ())
     }
 }
 
@@ -226,3 +361,46 @@ impl CoveragePackage {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn baseline(modules: &[(&str, u64, u64)]) -> CoverageBaseline {
+        CoverageBaseline {
+            modules: modules
+                .iter()
+                .map(|(name, covered, total)| {
+                    (name.to_string(), ModuleCoverage {
+                        covered: *covered,
+                        total: *total,
+                    })
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_coverage_diff() {
+        let old = baseline(&[("0x1::a", 50, 100), ("0x1::b", 10, 10), ("0x1::c", 1, 2)]);
+
+        let diff = old.diff(&baseline(&[("0x1::a", 60, 100), ("0x1::b", 10, 10)]));
+        assert!(!diff.regressed);
+        assert_eq!(diff.modules.len(), 3);
+        assert_eq!(diff.modules[2].current_percent, None);
+
+        let diff = old.diff(&baseline(&[
+            ("0x1::a", 50, 100),
+            ("0x1::b", 10, 11),
+            ("0x1::d", 0, 10),
+        ]));
+        assert!(diff.regressed);
+        let regressed: Vec<_> = diff
+            .modules
+            .iter()
+            .filter(|module| module.regressed)
+            .map(|module| module.module.as_str())
+            .collect();
+        assert_eq!(regressed, vec!["0x1::b"]);
+    }
+}
@@ -21,7 +21,7 @@ use crate::{
     governance::CompileScriptFunction,
     move_tool::{
         bytecode::{Decompile, Disassemble},
-        coverage::SummaryCoverage,
+        coverage::{CoverageBaseline, SummaryCoverage},
         fmt::Fmt,
         lint::LintPackage,
         manifest::{Dependency, ManifestNamedAddress, MovePackageManifest, PackageInfo},
@@ -535,6 +535,20 @@ pub struct TestPackage {
     #[clap(long = "coverage")]
     pub compute_coverage: bool,
 
+    /// Compare coverage against a baseline file, failing if any module's coverage regresses
+    ///
+    /// The baseline is a JSON file as written by `--update-coverage-baseline`
+    #[clap(long, value_parser, requires = "compute_coverage")]
+    pub coverage_baseline: Option<PathBuf>,
+
+    /// Overwrite the `--coverage-baseline` file with the coverage of this run
+    #[clap(long, requires = "coverage_baseline")]
+    pub update_coverage_baseline: bool,
+
+    /// Write the per-module coverage comparison as JSON to this file, for CI tooling
+    #[clap(long, value_parser, requires = "coverage_baseline")]
+    pub coverage_diff_output: Option<PathBuf>,
+
     /// Dump storage state on failure.
     #[clap(long = "dump")]
     pub dump_state: bool,
@@ -634,9 +648,40 @@ impl CliCommand<&'static str> for TestPackage {
                 filter: self.filter,
                 move_options: self.move_options,
             };
-            summary.coverage()?;
+            let current = summary.coverage()?;
 
             println!("Please use `aptos move coverage -h` for more detailed source or bytecode test coverage of this package");
+
+            if let Some(baseline_path) = &self.coverage_baseline {
+                if self.update_coverage_baseline {
+                    current.save(baseline_path)?;
+                    println!("Updated coverage baseline {}", baseline_path.display());
+                } else {
+                    let diff = CoverageBaseline::load(baseline_path)?.diff(&current);
+                    diff.print_human();
+                    if let Some(output) = &self.coverage_diff_output {
+                        write_to_file(
+                            output,
+                            "coverage diff",
+                            serde_json::to_string_pretty(&diff)
+                                .map_err(|err| CliError::UnexpectedError(err.to_string()))?
+                                .as_bytes(),
+                        )?;
+                    }
+                    if diff.regressed {
+                        let regressed: Vec<_> = diff
+                            .modules
+                            .iter()
+                            .filter(|module| module.regressed)
+                            .map(|module| module.module.as_str())
+                            .collect();
+                        return Err(CliError::CoverageError(format!(
+                            "coverage regressed against baseline in {}",
+                            regressed.join(", ")
+                        )));
+                    }
+                }
+            }
         }
 
         match result {
@@ -1876,8 +1921,11 @@ impl CliCommand<TransactionSummary> for CreateResourceAccountAndPublishPackage {
 #[derive(Parser)]
 pub struct DownloadPackage {
     /// Address of the account containing the package
+    ///
+    /// If not provided, the account is looked up by package name in the package index of the
+    /// node, which needs to have it enabled.
     #[clap(long, value_parser = crate::common::types::load_account_arg)]
-    pub(crate) account: AccountAddress,
+    pub(crate) account: Option<AccountAddress>,
 
     /// Name of the package
     #[clap(long)]
@@ -1908,7 +1956,13 @@ impl CliCommand<&'static str> for DownloadPackage {
 
     async fn execute(self) -> CliTypedResult<&'static str> {
         let url = self.rest_options.url(&self.profile_options)?;
-        let registry = CachedPackageRegistry::create(url, self.account, self.bytecode).await?;
+        let account = match self.account {
+            Some(account) => account,
+            None => resolve_package_address(url.clone(), &self.package)
+                .await
+                .map_err(|err| CliError::CommandArgumentError(err.to_string()))?,
+        };
+        let registry = CachedPackageRegistry::create(url, account, self.bytecode).await?;
         let output_dir = dir_default_to_current(self.output_dir)?;
 
         let package = registry
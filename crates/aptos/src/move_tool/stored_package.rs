@@ -38,6 +38,31 @@ impl fmt::Display for CachedPackageMetadata<'_> {
     }
 }
 
+/// Finds the address the package with the given name is published at, from the package index of
+/// the node.
+pub async fn resolve_package_address(
+    url: Url,
+    package_name: &str,
+) -> anyhow::Result<AccountAddress> {
+    let packages = Client::new(url)
+        .get_packages_by_name_bcs(package_name)
+        .await?
+        .into_inner();
+    match packages.as_slice() {
+        [] => bail!("package `{}` not found in the package index", package_name),
+        [package] => Ok(package.address),
+        _ => bail!(
+            "package `{}` is published at multiple addresses ({}), use --account to pick one",
+            package_name,
+            packages
+                .iter()
+                .map(|package| package.address.to_hex_literal())
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+    }
+}
+
 impl CachedPackageRegistry {
     /// Creates a new registry.
     pub async fn create(
@@ -57,8 +57,9 @@ const TESTNET_FOLDER: &str = "testnet";
 /// Run a localnet
 ///
 /// This localnet will run it's own genesis and run as a single node network
-/// locally. A faucet and grpc transaction stream will run alongside the node unless
-/// you specify otherwise with --no-faucet and --no-txn-stream respectively.
+/// locally, or as a network of several validators with --num-validators. A faucet and
+/// grpc transaction stream will run alongside the node unless you specify otherwise
+/// with --no-faucet and --no-txn-stream respectively.
 #[derive(Parser)]
 pub struct RunLocalnet {
     /// The directory to save all files for the node
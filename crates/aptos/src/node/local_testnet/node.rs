@@ -3,9 +3,9 @@
 
 use super::{health_checker::HealthChecker, traits::ServiceManager, RunLocalnet};
 use crate::node::local_testnet::utils::socket_addr_to_url;
-use anyhow::{anyhow, Context, Result};
-use aptos_config::config::{NodeConfig, DEFAULT_GRPC_STREAM_PORT};
-use aptos_node::{load_node_config, start_test_environment_node};
+use anyhow::{anyhow, bail, Context, Result};
+use aptos_config::config::{merge_node_config, NodeConfig, DEFAULT_GRPC_STREAM_PORT};
+use aptos_node::{load_node_config, load_node_configs, start_test_environment_node};
 use async_trait::async_trait;
 use clap::Parser;
 use maplit::hashset;
@@ -13,7 +13,9 @@ use rand::{rngs::StdRng, SeedableRng};
 use reqwest::Url;
 use std::{
     collections::HashSet,
+    fs::File,
     net::{IpAddr, Ipv4Addr},
+    num::NonZeroUsize,
     path::PathBuf,
     thread,
     time::Duration,
@@ -49,6 +51,23 @@ pub struct NodeArgs {
     #[clap(long, value_parser = aptos_node::load_seed)]
     pub seed: Option<[u8; 32]>,
 
+    /// Number of validators to run in the localnet
+    ///
+    /// All validators share one genesis and run in this process. The first validator
+    /// uses the default ports and serves the faucet, txn stream and indexer, every
+    /// other validator gets random free ports, which are printed on startup. Since the
+    /// validators share a process, validator.log and the metrics endpoint of the first
+    /// validator show the combined logs and metrics of all of them.
+    #[clap(long, default_value = "1")]
+    pub num_validators: NonZeroUsize,
+
+    /// Config overrides for a single validator, in the form `<INDEX>=<PATH>`
+    ///
+    /// The YAML file at PATH is merged into the config of the validator with the given
+    /// index (starting at 0). Can be given multiple times.
+    #[clap(long, value_parser = parse_node_config_override)]
+    pub node_config_override: Vec<(usize, PathBuf)>,
+
     /// Do not run a transaction stream service alongside the node.
     ///
     /// Note: In reality this is not the same as running a Transaction Stream Service,
@@ -86,10 +105,34 @@ pub struct NodeArgs {
 #[derive(Clone, Debug)]
 pub struct NodeManager {
     config: NodeConfig,
+    /// Configs of the validators other than the first one, if any.
+    additional_validator_configs: Vec<NodeConfig>,
     test_dir: PathBuf,
     no_node: bool,
 }
 
+fn parse_node_config_override(s: &str) -> Result<(usize, PathBuf)> {
+    let (index, path) = s
+        .split_once('=')
+        .ok_or_else(|| anyhow!("Expected <INDEX>=<PATH>, got {}", s))?;
+    let index = index
+        .parse()
+        .with_context(|| format!("Invalid validator index {}", index))?;
+    Ok((index, PathBuf::from(path)))
+}
+
+fn apply_node_config_override(config: NodeConfig, override_path: &PathBuf) -> Result<NodeConfig> {
+    let reader = File::open(override_path)
+        .with_context(|| format!("Unable to open config override file {:?}", override_path))?;
+    let values: serde_yaml::Value = serde_yaml::from_reader(reader).with_context(|| {
+        format!(
+            "Unable to read config override file as YAML {:?}",
+            override_path
+        )
+    })?;
+    Ok(merge_node_config(config, values)?)
+}
+
 pub fn build_node_config(
     rng: StdRng,
     config_path: &Option<PathBuf>,
@@ -113,6 +156,29 @@ pub fn build_node_config(
     .context("Failed to load / create config for node")
 }
 
+/// Like `build_node_config`, but for a localnet with `num_validators` validators.
+pub fn build_node_configs(
+    rng: StdRng,
+    config_path: &Option<PathBuf>,
+    test_config_override: &Option<PathBuf>,
+    performance: bool,
+    test_dir: PathBuf,
+    num_validators: NonZeroUsize,
+) -> Result<Vec<NodeConfig>> {
+    load_node_configs(
+        config_path,
+        test_config_override,
+        &test_dir,
+        num_validators,
+        false,
+        false,
+        performance,
+        aptos_cached_packages::head_release_bundle(),
+        rng,
+    )
+    .context("Failed to load / create configs for nodes")
+}
+
 impl NodeManager {
     pub fn new(args: &RunLocalnet, bind_to: Ipv4Addr, test_dir: PathBuf) -> Result<Self> {
         let rng = args
@@ -121,21 +187,50 @@ impl NodeManager {
             .map(StdRng::from_seed)
             .unwrap_or_else(StdRng::from_entropy);
 
-        let node_config = build_node_config(
+        let num_validators = args.node_args.num_validators;
+        let mut node_configs = build_node_configs(
             rng,
             &args.node_args.config_path,
             &args.node_args.test_config_override,
             args.node_args.performance,
             test_dir.clone(),
+            num_validators,
         )?;
-        Self::new_with_config(
+        for (index, override_path) in &args.node_args.node_config_override {
+            if *index >= num_validators.get() {
+                bail!(
+                    "Config override given for validator {}, but there are only {} validators",
+                    index,
+                    num_validators
+                );
+            }
+            node_configs[*index] =
+                apply_node_config_override(node_configs[*index].clone(), override_path)?;
+        }
+
+        let node_config = node_configs.remove(0);
+        let mut manager = Self::new_with_config(
             node_config,
             bind_to,
             test_dir,
             !args.node_args.no_txn_stream,
             args.node_args.txn_stream_port,
             args.node_args.no_node,
-        )
+        )?;
+        manager.additional_validator_configs = node_configs
+            .into_iter()
+            .map(|mut config| {
+                // Only the first validator serves the txn stream.
+                config.indexer_grpc.enabled = false;
+                config.indexer_table_info.table_info_service_mode =
+                    aptos_config::config::TableInfoServiceMode::Disabled;
+                config.api.address.set_ip(IpAddr::V4(bind_to));
+                config.admin_service.address = bind_to.to_string();
+                config.inspection_service.address = bind_to.to_string();
+                config
+            })
+            .collect();
+        Ok(manager)
     }
 
     pub fn new_with_config(
@@ -170,6 +265,7 @@ impl NodeManager {
 
         Ok(NodeManager {
             config: node_config,
+            additional_validator_configs: vec![],
             test_dir,
             no_node,
         })
@@ -202,6 +298,12 @@ impl ServiceManager for NodeManager {
                 socket_addr_to_url(&self.config.indexer_grpc.address, "http").unwrap();
             checkers.insert(HealthChecker::DataServiceGrpc(data_service_url));
         }
+        for (index, config) in self.additional_validator_configs.iter().enumerate() {
+            checkers.insert(HealthChecker::Http(
+                socket_addr_to_url(&config.api.address, "http").unwrap(),
+                format!("Validator {} API", index + 1),
+            ));
+        }
         checkers
     }
 
@@ -221,14 +323,53 @@ impl ServiceManager for NodeManager {
             }
         }
 
-        let node_thread_handle = thread::spawn(move || {
-            let result = start_test_environment_node(self.config, self.test_dir, false);
+        // The first validator sets up the process wide state (logger, node identity, etc.),
+        // so the logs of all validators end up in its validator.log. The other validators
+        // are only started once it is up, and share that state.
+        let node_api_checker = HealthChecker::NodeApi(self.get_node_api_url());
+        let NodeManager {
+            config,
+            additional_validator_configs,
+            test_dir,
+            ..
+        } = *self;
+        let mut node_thread_handles = vec![thread::spawn(move || {
+            let result = start_test_environment_node(config, test_dir, false);
             eprintln!("Node stopped unexpectedly {:#?}", result);
-        });
+        })];
+        if !additional_validator_configs.is_empty() {
+            node_api_checker
+                .wait(Some("the other validators"))
+                .await
+                .context("The first validator did not start up")?;
+        }
+        for (index, config) in additional_validator_configs.into_iter().enumerate() {
+            println!(
+                "\tValidator {}: REST API endpoint: http://{}, metrics endpoint: http://{}:{}/metrics",
+                index + 1,
+                config.api.address,
+                config.inspection_service.address,
+                config.inspection_service.port,
+            );
+            node_thread_handles.push(thread::spawn(move || {
+                match aptos_node::start_additional_node(config) {
+                    // The handle keeps the validator running, so it is never dropped.
+                    Ok(_handle) => loop {
+                        thread::park();
+                    },
+                    Err(error) => {
+                        eprintln!("Validator {} stopped unexpectedly {:#?}", index + 1, error)
+                    },
+                }
+            }));
+        }
 
-        // This just waits for the node thread forever.
+        // This just waits for the node threads forever.
         loop {
-            if node_thread_handle.is_finished() {
+            if node_thread_handles
+                .iter()
+                .any(|handle| handle.is_finished())
+            {
                 return Err(anyhow!("Node thread finished unexpectedly"));
             }
             tokio::time::sleep(Duration::from_millis(500)).await;
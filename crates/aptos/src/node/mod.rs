@@ -25,13 +25,19 @@ use aptos_backup_cli::{
     utils::GlobalRestoreOpt,
 };
 use aptos_cached_packages::aptos_stdlib;
+use aptos_config::config::SecureBackend;
 use aptos_crypto::{bls12381, bls12381::PublicKey, x25519, ValidCryptoMaterialStringExt};
 use aptos_genesis::config::{HostAndPort, OperatorConfiguration};
 use aptos_logger::Level;
 use aptos_network_checker::args::{
     validate_address, CheckEndpointArgs, HandshakeArgs, NodeAddressArgs,
 };
-use aptos_rest_client::{aptos_api_types::VersionedEvent, Client, State};
+use aptos_rest_client::{
+    aptos_api_types::{HexEncodedBytes, VersionedEvent},
+    Client, State,
+};
+use aptos_safety_rules::migrate_safety_storage;
+use aptos_secure_storage::Storage;
 use aptos_types::{
     account_address::AccountAddress,
     account_config::{BlockResource, CORE_CODE_ADDRESS},
@@ -40,19 +46,24 @@ use aptos_types::{
     on_chain_config::{ConfigurationResource, ConsensusScheme, ValidatorSet},
     stake_pool::StakePool,
     staking_contract::StakingContractStore,
+    state_store::{state_key::StateKey, table::TableHandle},
+    trusted_state::TrustedState,
     validator_info::ValidatorInfo,
     validator_performances::ValidatorPerformances,
     vesting::VestingAdminStore,
+    waypoint::Waypoint,
 };
 use async_trait::async_trait;
 use bcs::Result;
 use chrono::{DateTime, NaiveDateTime, Utc};
 use clap::Parser;
+use move_core_types::{identifier::IdentStr, language_storage::StructTag};
 use serde::{Deserialize, Serialize};
 use std::{
     collections::HashMap,
     convert::{TryFrom, TryInto},
-    path::PathBuf,
+    path::{Path, PathBuf},
+    str::FromStr,
     time::Duration,
 };
 
@@ -72,10 +83,12 @@ pub enum NodeTool {
     InitializeValidator(InitializeValidator),
     JoinValidatorSet(JoinValidatorSet),
     LeaveValidatorSet(LeaveValidatorSet),
+    MigrateSafetyStorage(MigrateSafetyStorage),
     ShowEpochInfo(ShowEpochInfo),
     ShowValidatorConfig(ShowValidatorConfig),
     ShowValidatorSet(ShowValidatorSet),
     ShowValidatorStake(ShowValidatorStake),
+    StateGet(StateGet),
     #[clap(aliases = &["run-local-testnet"])]
     RunLocalnet(RunLocalnet),
     UpdateConsensusKey(UpdateConsensusKey),
@@ -97,10 +110,12 @@ impl NodeTool {
             InitializeValidator(tool) => tool.execute_serialized().await,
             JoinValidatorSet(tool) => tool.execute_serialized().await,
             LeaveValidatorSet(tool) => tool.execute_serialized().await,
+            MigrateSafetyStorage(tool) => tool.execute_serialized().await,
             ShowEpochInfo(tool) => tool.execute_serialized().await,
             ShowValidatorSet(tool) => tool.execute_serialized().await,
             ShowValidatorStake(tool) => tool.execute_serialized().await,
             ShowValidatorConfig(tool) => tool.execute_serialized().await,
+            StateGet(tool) => tool.execute_serialized().await,
             RunLocalnet(tool) => tool
                 .execute_serialized_without_logger()
                 .await
@@ -1356,6 +1371,56 @@ impl CliCommand<()> for BootstrapDb {
     }
 }
 
+/// Migrate the safety rules storage of a validator to another backend
+///
+/// Copies the consensus key, waypoints and safety data (i.e., the voting state) of a validator
+/// from one secure storage backend to another, e.g., from a local file to storage shared with a
+/// standby replica. The validator must be stopped while migrating, and configured to use the new
+/// backend before being restarted. The migration is refused if the target storage holds safety
+/// data that is ahead of the source, as that would allow the validator to equivocate.
+#[derive(Parser)]
+pub struct MigrateSafetyStorage {
+    /// Path to the YAML config of the backend to migrate from
+    ///
+    /// This is the `backend` section of the node's `safety_rules` config, e.g.:
+    /// `{ type: on_disk_storage, path: /opt/aptos/data/secure-data.json }`
+    #[clap(long, value_parser)]
+    pub(crate) source_config: PathBuf,
+
+    /// Path to the YAML config of the backend to migrate to
+    #[clap(long, value_parser)]
+    pub(crate) target_config: PathBuf,
+}
+
+impl MigrateSafetyStorage {
+    fn load_backend(path: &Path) -> CliTypedResult<Storage> {
+        let backend: SecureBackend =
+            from_yaml(&String::from_utf8(read_from_file(path)?).map_err(CliError::from)?)?;
+        if backend.is_in_memory() {
+            return Err(CliError::CommandArgumentError(format!(
+                "Cannot migrate from or to in memory storage: {}",
+                path.display()
+            )));
+        }
+        Ok(Storage::from(&backend))
+    }
+}
+
+#[async_trait]
+impl CliCommand<String> for MigrateSafetyStorage {
+    fn command_name(&self) -> &'static str {
+        "MigrateSafetyStorage"
+    }
+
+    async fn execute(self) -> CliTypedResult<String> {
+        let source = Self::load_backend(&self.source_config)?;
+        let mut target = Self::load_backend(&self.target_config)?;
+        let safety_data = migrate_safety_storage(&source, &mut target)
+            .map_err(|err| CliError::UnexpectedError(err.to_string()))?;
+        Ok(format!("Migrated safety storage at {}", safety_data))
+    }
+}
+
 /// Checks the network connectivity of a node
 ///
 /// Checks network connectivity by dialing the node and attempting
@@ -1489,10 +1554,160 @@ impl Time {
     }
 }
 
+/// Fetch a state value by its raw state key, optionally verifying its proof
+///
+/// The key is either the hex encoded BCS bytes of a `StateKey`, or one of the following
+/// descriptors:
+///   resource:<address>:<struct tag>
+///   resource_group:<address>:<struct tag>
+///   module:<address>:<module name>
+///   table_item:<table handle>:<hex encoded key>
+///
+/// With `--verify`, the value is checked against a sparse Merkle proof, the ledger info it is
+/// proven against, and the epoch changes since `--waypoint`.
+#[derive(Parser)]
+pub struct StateGet {
+    /// The state key, as hex encoded BCS bytes or a descriptor
+    #[clap(long, value_parser = parse_state_key)]
+    pub(crate) key: StateKey,
+
+    /// Version to read the state at, defaults to the latest version
+    ///
+    /// Proofs are only available at state checkpoints, e.g. the last version of a block.
+    #[clap(long)]
+    pub(crate) version: Option<u64>,
+
+    /// Verify the value against the proof returned by the fullnode
+    #[clap(long, requires = "waypoint")]
+    pub(crate) verify: bool,
+
+    /// Trusted waypoint to verify from, e.g. the genesis waypoint
+    #[clap(long)]
+    pub(crate) waypoint: Option<Waypoint>,
+
+    #[clap(flatten)]
+    pub(crate) profile_options: ProfileOptions,
+    #[clap(flatten)]
+    pub(crate) rest_options: RestOptions,
+}
+
+#[derive(Debug, Serialize)]
+pub struct StateValueSummary {
+    pub state_key: HexEncodedBytes,
+    pub version: u64,
+    /// Absent if the key has no value at `version`
+    pub value: Option<HexEncodedBytes>,
+    pub ledger_version: u64,
+    pub verified: bool,
+}
+
+#[async_trait]
+impl CliCommand<StateValueSummary> for StateGet {
+    fn command_name(&self) -> &'static str {
+        "StateGet"
+    }
+
+    async fn execute(self) -> CliTypedResult<StateValueSummary> {
+        let client = self.rest_options.client(&self.profile_options)?;
+        let known_version = self.waypoint.map_or(0, |waypoint| waypoint.version());
+        let state_value_with_proof = client
+            .get_raw_state_value_with_proof(&self.key, self.version, known_version)
+            .await?
+            .into_inner();
+
+        if self.verify {
+            let waypoint = self.waypoint.ok_or_else(|| {
+                CliError::CommandArgumentError("--verify requires --waypoint".to_string())
+            })?;
+            state_value_with_proof
+                .verify(&self.key, &TrustedState::from_epoch_waypoint(waypoint))
+                .map_err(|err| {
+                    CliError::UnexpectedError(format!("Failed to verify state proof: {:#}", err))
+                })?;
+        }
+
+        Ok(StateValueSummary {
+            state_key: HexEncodedBytes::from(bcs::to_bytes(&self.key)?),
+            version: state_value_with_proof.version,
+            value: state_value_with_proof
+                .value
+                .map(|value| HexEncodedBytes::from(value.bytes().to_vec())),
+            ledger_version: state_value_with_proof
+                .state_proof
+                .latest_ledger_info()
+                .version(),
+            verified: self.verify,
+        })
+    }
+}
+
+fn parse_state_key(str: &str) -> CliTypedResult<StateKey> {
+    let invalid = |reason: String| {
+        CliError::CommandArgumentError(format!("Invalid state key '{}': {}", str, reason))
+    };
+
+    let mut parts = str.splitn(3, ':');
+    match (parts.next(), parts.next(), parts.next()) {
+        (Some("resource"), Some(address), Some(struct_tag)) => StateKey::resource(
+            &AccountAddress::from_str(address).map_err(|err| invalid(err.to_string()))?,
+            &StructTag::from_str(struct_tag).map_err(|err| invalid(err.to_string()))?,
+        )
+        .map_err(|err| invalid(err.to_string())),
+        (Some("resource_group"), Some(address), Some(struct_tag)) => Ok(StateKey::resource_group(
+            &AccountAddress::from_str(address).map_err(|err| invalid(err.to_string()))?,
+            &StructTag::from_str(struct_tag).map_err(|err| invalid(err.to_string()))?,
+        )),
+        (Some("module"), Some(address), Some(name)) => Ok(StateKey::module(
+            &AccountAddress::from_str(address).map_err(|err| invalid(err.to_string()))?,
+            IdentStr::new(name).map_err(|err| invalid(err.to_string()))?,
+        )),
+        (Some("table_item"), Some(handle), Some(key)) => Ok(StateKey::table_item(
+            &TableHandle(AccountAddress::from_str(handle).map_err(|err| invalid(err.to_string()))?),
+            HexEncodedBytes::from_str(key)
+                .map_err(|err| invalid(err.to_string()))?
+                .inner(),
+        )),
+        (Some(bytes), None, None) => {
+            let bytes = HexEncodedBytes::from_str(bytes).map_err(|err| invalid(err.to_string()))?;
+            bcs::from_bytes(bytes.inner()).map_err(|err| invalid(err.to_string()))
+        },
+        _ => Err(invalid("unknown descriptor".to_string())),
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use super::parse_state_key;
     use crate::{CliResult, Tool};
+    use aptos_types::{account_address::AccountAddress, state_store::state_key::StateKey};
     use clap::Parser;
+    use move_core_types::{ident_str, language_storage::StructTag};
+    use std::str::FromStr;
+
+    #[test]
+    fn test_parse_state_key() {
+        let address = AccountAddress::from_str("0xcafe").unwrap();
+        let struct_tag = StructTag::from_str("0x1::account::Account").unwrap();
+        let resource = StateKey::resource(&address, &struct_tag).unwrap();
+        assert_eq!(
+            parse_state_key("resource:0xcafe:0x1::account::Account").unwrap(),
+            resource
+        );
+        assert_eq!(
+            parse_state_key(&format!(
+                "0x{}",
+                hex::encode(bcs::to_bytes(&resource).unwrap())
+            ))
+            .unwrap(),
+            resource
+        );
+        assert_eq!(
+            parse_state_key("module:0x1:coin").unwrap(),
+            StateKey::module(&AccountAddress::ONE, ident_str!("coin"))
+        );
+        assert!(parse_state_key("event:0x1:0").is_err());
+        assert!(parse_state_key("resource:0xcafe:not_a_struct").is_err());
+    }
 
     // TODO: there have to be cleaner ways to test things. Maybe a CLI test framework?
 
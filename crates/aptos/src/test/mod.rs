@@ -900,7 +900,7 @@ impl CliTestFramework {
         DownloadPackage {
             rest_options: self.rest_options(),
             profile_options: Default::default(),
-            account: self.account_id(index),
+            account: Some(self.account_id(index)),
             package,
             output_dir: Some(output_dir),
             print_metadata: false,
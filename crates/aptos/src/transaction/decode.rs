@@ -0,0 +1,589 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::common::{
+    types::{CliCommand, CliError, CliTypedResult, ProfileOptions, RestOptions},
+    utils::read_from_file,
+};
+use aptos_api_types::{
+    MoveFunction, MoveStructTag, MoveType, MoveValue, Transaction, UserTransactionRequest, U64,
+};
+use aptos_crypto::HashValue;
+use aptos_rest_client::Client;
+use aptos_types::transaction::{
+    EntryFunction, MultisigTransactionPayload, ReplayProtector, Script, SignedTransaction,
+    TransactionExecutable, TransactionPayload, TransactionPayloadInner,
+};
+use async_trait::async_trait;
+use clap::Parser;
+use move_core_types::{account_address::AccountAddress, language_storage::TypeTag, u256::U256};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::path::PathBuf;
+
+const FEE_STATEMENT_TYPE: &str = "0x1::transaction_fee::FeeStatement";
+
+/// Decode a transaction into a human readable form
+///
+/// The transaction is either a BCS serialized signed transaction, or the hash of a transaction
+/// known to the fullnode.  Entry function arguments of signed transactions are decoded with the
+/// on-chain ABI of the called module when a fullnode is reachable, and are otherwise shown as hex.
+#[derive(Parser)]
+pub struct DecodeTransaction {
+    /// BCS serialized signed transaction, encoded as hex (with optional 0x prefix) or base64
+    #[clap(long, group = "input")]
+    pub(crate) signed_transaction: Option<String>,
+
+    /// Path to a file containing the raw BCS bytes of a signed transaction
+    #[clap(long, group = "input", value_parser)]
+    pub(crate) signed_transaction_file: Option<PathBuf>,
+
+    /// Hash of a transaction to fetch from the fullnode
+    #[clap(long, group = "input")]
+    pub(crate) hash: Option<HashValue>,
+
+    #[clap(flatten)]
+    pub(crate) rest_options: RestOptions,
+
+    #[clap(flatten)]
+    pub(crate) profile_options: ProfileOptions,
+}
+
+/// A decoded transaction, along with its execution results if it has been committed
+#[derive(Debug, Serialize)]
+pub struct DecodedTransaction {
+    pub hash: HashValue,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<u64>,
+    pub sender: AccountAddress,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sequence_number: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub replay_protection_nonce: Option<u64>,
+    pub expiration_timestamp_secs: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub chain_id: Option<u8>,
+    pub payload: Value,
+    pub fee: FeeBreakdown,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub success: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub vm_status: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub events: Vec<Value>,
+}
+
+/// Gas parameters of a transaction, and what it was actually charged once committed
+#[derive(Debug, Serialize)]
+pub struct FeeBreakdown {
+    pub max_gas_amount: u64,
+    pub gas_unit_price: u64,
+    pub max_fee_octas: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gas_used: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fee_octas: Option<u64>,
+    #[serde(flatten, skip_serializing_if = "Option::is_none")]
+    pub statement: Option<FeeStatement>,
+}
+
+/// The fields of the `0x1::transaction_fee::FeeStatement` event
+#[derive(Debug, Deserialize, Serialize)]
+pub struct FeeStatement {
+    pub execution_gas_units: U64,
+    pub io_gas_units: U64,
+    pub storage_fee_octas: U64,
+    pub storage_fee_refund_octas: U64,
+}
+
+#[async_trait]
+impl CliCommand<DecodedTransaction> for DecodeTransaction {
+    fn command_name(&self) -> &'static str {
+        "DecodeTransaction"
+    }
+
+    async fn execute(self) -> CliTypedResult<DecodedTransaction> {
+        if let Some(hash) = self.hash {
+            let client = self.rest_options.client(&self.profile_options)?;
+            let txn = client.get_transaction_by_hash(hash).await?.into_inner();
+            return decode_api_transaction(txn);
+        }
+
+        let bytes = if let Some(encoded) = self.signed_transaction {
+            decode_encoded_bytes(&encoded)?
+        } else if let Some(path) = self.signed_transaction_file {
+            read_from_file(&path)?
+        } else {
+            return Err(CliError::CommandArgumentError(
+                "One of --signed-transaction, --signed-transaction-file or --hash must be provided"
+                    .to_string(),
+            ));
+        };
+        let txn: SignedTransaction =
+            bcs::from_bytes(&bytes).map_err(|err| CliError::BCS("signed transaction", err))?;
+
+        // ABIs are only needed to decode arguments, so decoding still works offline.
+        let client = self.rest_options.client(&self.profile_options).ok();
+        decode_signed_transaction(txn, client.as_ref()).await
+    }
+}
+
+/// Parses hex (with or without a `0x` prefix), falling back to base64
+fn decode_encoded_bytes(encoded: &str) -> CliTypedResult<Vec<u8>> {
+    let encoded = encoded.trim();
+    if let Ok(bytes) = hex::decode(encoded.strip_prefix("0x").unwrap_or(encoded)) {
+        return Ok(bytes);
+    }
+    base64::decode(encoded).map_err(|err| {
+        CliError::UnableToParse("signed transaction", format!("not hex or base64: {}", err))
+    })
+}
+
+async fn decode_signed_transaction(
+    txn: SignedTransaction,
+    client: Option<&Client>,
+) -> CliTypedResult<DecodedTransaction> {
+    let (sequence_number, replay_protection_nonce) = match txn.replay_protector() {
+        ReplayProtector::SequenceNumber(sequence_number) => (Some(sequence_number), None),
+        ReplayProtector::Nonce(nonce) => (None, Some(nonce)),
+    };
+    let payload = decode_payload(txn.payload(), client).await?;
+
+    Ok(DecodedTransaction {
+        hash: txn.committed_hash(),
+        version: None,
+        sender: txn.sender(),
+        sequence_number,
+        replay_protection_nonce,
+        expiration_timestamp_secs: txn.expiration_timestamp_secs(),
+        chain_id: Some(txn.chain_id().id()),
+        payload,
+        fee: FeeBreakdown {
+            max_gas_amount: txn.max_gas_amount(),
+            gas_unit_price: txn.gas_unit_price(),
+            max_fee_octas: txn.max_gas_amount().saturating_mul(txn.gas_unit_price()),
+            gas_used: None,
+            fee_octas: None,
+            statement: None,
+        },
+        success: None,
+        vm_status: None,
+        events: vec![],
+    })
+}
+
+fn decode_api_transaction(txn: Transaction) -> CliTypedResult<DecodedTransaction> {
+    match txn {
+        Transaction::PendingTransaction(txn) => {
+            decoded_from_request(txn.hash.into(), &txn.request, None)
+        },
+        Transaction::UserTransaction(txn) => {
+            let mut decoded =
+                decoded_from_request(txn.info.hash.into(), &txn.request, Some(txn.info.version.0))?;
+            let gas_used = txn.info.gas_used.0;
+            decoded.fee.gas_used = Some(gas_used);
+            decoded.fee.fee_octas = Some(gas_used.saturating_mul(decoded.fee.gas_unit_price));
+            decoded.fee.statement = txn
+                .events
+                .iter()
+                .find(|event| event.typ.to_string() == FEE_STATEMENT_TYPE)
+                .and_then(|event| serde_json::from_value(event.data.clone()).ok());
+            decoded.success = Some(txn.info.success);
+            decoded.vm_status = Some(txn.info.vm_status);
+            decoded.events = txn
+                .events
+                .into_iter()
+                .map(|event| {
+                    json!({
+                        "type": event.typ.to_string(),
+                        "data": event.data,
+                    })
+                })
+                .collect();
+            Ok(decoded)
+        },
+        _ => Err(CliError::CommandArgumentError(format!(
+            "Transaction {} is not a user transaction",
+            txn.transaction_info()
+                .map(|info| info.hash.to_string())
+                .unwrap_or_default()
+        ))),
+    }
+}
+
+fn decoded_from_request(
+    hash: HashValue,
+    request: &UserTransactionRequest,
+    version: Option<u64>,
+) -> CliTypedResult<DecodedTransaction> {
+    let (sequence_number, replay_protection_nonce) = match request.replay_protector() {
+        ReplayProtector::SequenceNumber(sequence_number) => (Some(sequence_number), None),
+        ReplayProtector::Nonce(nonce) => (None, Some(nonce)),
+    };
+    let max_gas_amount = request.max_gas_amount.0;
+    let gas_unit_price = request.gas_unit_price.0;
+
+    Ok(DecodedTransaction {
+        hash,
+        version,
+        sender: request.sender.into(),
+        sequence_number,
+        replay_protection_nonce,
+        expiration_timestamp_secs: request.expiration_timestamp_secs.0,
+        chain_id: None,
+        // The fullnode already decodes the payload with the on-chain ABIs.
+        payload: serde_json::to_value(&request.payload)
+            .map_err(|err| CliError::UnexpectedError(err.to_string()))?,
+        fee: FeeBreakdown {
+            max_gas_amount,
+            gas_unit_price,
+            max_fee_octas: max_gas_amount.saturating_mul(gas_unit_price),
+            gas_used: None,
+            fee_octas: None,
+            statement: None,
+        },
+        success: None,
+        vm_status: None,
+        events: vec![],
+    })
+}
+
+pub(crate) async fn decode_payload(
+    payload: &TransactionPayload,
+    client: Option<&Client>,
+) -> CliTypedResult<Value> {
+    Ok(match payload {
+        TransactionPayload::EntryFunction(entry_function) => {
+            decode_entry_function(entry_function, client).await
+        },
+        TransactionPayload::Script(script) => decode_script(script),
+        TransactionPayload::Multisig(multisig) => {
+            let transaction_payload = match &multisig.transaction_payload {
+                Some(MultisigTransactionPayload::EntryFunction(entry_function)) => {
+                    decode_entry_function(entry_function, client).await
+                },
+                None => Value::Null,
+            };
+            json!({
+                "type": "multisig_payload",
+                "multisig_address": multisig.multisig_address,
+                "transaction_payload": transaction_payload,
+            })
+        },
+        TransactionPayload::Payload(TransactionPayloadInner::V1 {
+            executable,
+            extra_config,
+        }) => {
+            let mut decoded = match executable {
+                TransactionExecutable::EntryFunction(entry_function) => {
+                    decode_entry_function(entry_function, client).await
+                },
+                TransactionExecutable::Script(script) => decode_script(script),
+                TransactionExecutable::Empty => json!({ "type": "empty_payload" }),
+            };
+            if let Some(multisig_address) = extra_config.multisig_address() {
+                decoded["multisig_address"] = json!(multisig_address);
+            }
+            decoded
+        },
+        TransactionPayload::ModuleBundle(_) => {
+            return Err(CliError::UnexpectedError(
+                "Module bundle payloads are deprecated".to_string(),
+            ))
+        },
+    })
+}
+
+fn decode_script(script: &Script) -> Value {
+    json!({
+        "type": "script_payload",
+        "code_hash": HashValue::sha3_256_of(script.code()),
+        "type_arguments": script.ty_args().iter().map(|ty| ty.to_canonical_string()).collect::<Vec<_>>(),
+        "arguments": script.args().iter().cloned().map(MoveValue::from).collect::<Vec<_>>(),
+    })
+}
+
+async fn decode_entry_function(entry_function: &EntryFunction, client: Option<&Client>) -> Value {
+    let module = entry_function.module();
+    let function = match client {
+        Some(client) => fetch_function_abi(
+            client,
+            *module.address(),
+            module.name().as_str(),
+            entry_function.function().as_str(),
+        )
+        .await
+        .ok()
+        .flatten(),
+        None => None,
+    };
+
+    let arguments = decode_arguments(
+        function.as_ref(),
+        entry_function.ty_args(),
+        entry_function.args(),
+    );
+    json!({
+        "type": "entry_function_payload",
+        "function": format!(
+            "{}::{}::{}",
+            module.address().to_hex_literal(),
+            module.name(),
+            entry_function.function()
+        ),
+        "type_arguments": entry_function
+            .ty_args()
+            .iter()
+            .map(|ty| ty.to_canonical_string())
+            .collect::<Vec<_>>(),
+        "arguments": arguments,
+    })
+}
+
+async fn fetch_function_abi(
+    client: &Client,
+    address: AccountAddress,
+    module_name: &str,
+    function_name: &str,
+) -> CliTypedResult<Option<MoveFunction>> {
+    let module = client
+        .get_account_module(address, module_name)
+        .await?
+        .into_inner()
+        .try_parse_abi()?;
+    Ok(module.abi.and_then(|abi| {
+        abi.exposed_functions
+            .into_iter()
+            .find(|function| function.name.as_str() == function_name)
+    }))
+}
+
+/// Decodes BCS encoded arguments against the function's parameters, leaving out the signers
+/// which are not part of the payload.  Arguments that can't be decoded are shown as hex.
+fn decode_arguments(
+    function: Option<&MoveFunction>,
+    ty_args: &[TypeTag],
+    args: &[Vec<u8>],
+) -> Vec<Value> {
+    let params: Vec<&MoveType> = function
+        .map(|function| {
+            function
+                .params
+                .iter()
+                .filter(|param| !is_signer(param))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    args.iter()
+        .enumerate()
+        .map(|(i, arg)| {
+            params
+                .get(i)
+                .and_then(|param| decode_argument(param, ty_args, arg))
+                .unwrap_or_else(|| json!(format!("0x{}", hex::encode(arg))))
+        })
+        .collect()
+}
+
+fn is_signer(ty: &MoveType) -> bool {
+    match ty {
+        MoveType::Signer => true,
+        MoveType::Reference { to, .. } => matches!(to.as_ref(), MoveType::Signer),
+        _ => false,
+    }
+}
+
+/// Decodes a single argument, which must consume all of `bytes`
+fn decode_argument(ty: &MoveType, ty_args: &[TypeTag], mut bytes: &[u8]) -> Option<Value> {
+    let value = decode_value(ty, ty_args, &mut bytes, 0)?;
+    bytes.is_empty().then_some(value)
+}
+
+/// Mirrors the JSON representation used by the REST API: 64 bit and larger integers are strings,
+/// `String` is a string, `Object<T>` is an address, and `Option<T>` is `{"vec": [..]}`.
+fn decode_value(
+    ty: &MoveType,
+    ty_args: &[TypeTag],
+    bytes: &mut &[u8],
+    depth: usize,
+) -> Option<Value> {
+    // Type arguments can nest arbitrarily, so avoid unbounded recursion on malformed input.
+    const MAX_DEPTH: usize = 16;
+    if depth > MAX_DEPTH {
+        return None;
+    }
+
+    Some(match ty {
+        MoveType::Bool => match take(bytes, 1)?[0] {
+            0 => json!(false),
+            1 => json!(true),
+            _ => return None,
+        },
+        MoveType::U8 => json!(take(bytes, 1)?[0]),
+        MoveType::U16 => json!(u16::from_le_bytes(take(bytes, 2)?.try_into().ok()?)),
+        MoveType::U32 => json!(u32::from_le_bytes(take(bytes, 4)?.try_into().ok()?)),
+        MoveType::U64 => json!(u64::from_le_bytes(take(bytes, 8)?.try_into().ok()?).to_string()),
+        MoveType::U128 => {
+            json!(u128::from_le_bytes(take(bytes, 16)?.try_into().ok()?).to_string())
+        },
+        MoveType::U256 => json!(U256::from_le_bytes(take(bytes, 32)?.try_into().ok()?).to_string()),
+        MoveType::Address => json!(decode_address(bytes)?.to_hex_literal()),
+        MoveType::Vector { items } => match items.as_ref() {
+            MoveType::U8 => {
+                let len = read_uleb128(bytes)?;
+                json!(format!("0x{}", hex::encode(take(bytes, len)?)))
+            },
+            items => json!(decode_vector(items, ty_args, bytes, depth)?),
+        },
+        MoveType::Struct(tag) => decode_struct(tag, ty_args, bytes, depth)?,
+        MoveType::GenericTypeParam { index } => {
+            let ty = MoveType::from(ty_args.get(*index as usize)?);
+            decode_value(&ty, &[], bytes, depth + 1)?
+        },
+        MoveType::Signer
+        | MoveType::Function { .. }
+        | MoveType::Reference { .. }
+        | MoveType::Unparsable(_) => return None,
+    })
+}
+
+fn decode_vector(
+    items: &MoveType,
+    ty_args: &[TypeTag],
+    bytes: &mut &[u8],
+    depth: usize,
+) -> Option<Vec<Value>> {
+    let len = read_uleb128(bytes)?;
+    // Every element takes at least one byte, which bounds the allocation.
+    if len > bytes.len() {
+        return None;
+    }
+    (0..len)
+        .map(|_| decode_value(items, ty_args, bytes, depth + 1))
+        .collect()
+}
+
+fn decode_struct(
+    tag: &MoveStructTag,
+    ty_args: &[TypeTag],
+    bytes: &mut &[u8],
+    depth: usize,
+) -> Option<Value> {
+    if tag.address.inner() != &AccountAddress::ONE {
+        return None;
+    }
+    match (tag.module.as_str(), tag.name.as_str()) {
+        ("string", "String") => {
+            let len = read_uleb128(bytes)?;
+            Some(json!(std::str::from_utf8(take(bytes, len)?).ok()?))
+        },
+        ("object", "Object") => Some(json!(decode_address(bytes)?.to_hex_literal())),
+        ("option", "Option") => {
+            let inner = tag.generic_type_params.first()?;
+            let vec = decode_vector(inner, ty_args, bytes, depth)?;
+            (vec.len() <= 1).then(|| json!({ "vec": vec }))
+        },
+        _ => None,
+    }
+}
+
+fn decode_address(bytes: &mut &[u8]) -> Option<AccountAddress> {
+    AccountAddress::from_bytes(take(bytes, AccountAddress::LENGTH)?).ok()
+}
+
+fn take<'a>(bytes: &mut &'a [u8], len: usize) -> Option<&'a [u8]> {
+    if bytes.len() < len {
+        return None;
+    }
+    let (head, tail) = bytes.split_at(len);
+    *bytes = tail;
+    Some(head)
+}
+
+fn read_uleb128(bytes: &mut &[u8]) -> Option<usize> {
+    let mut value: u64 = 0;
+    for shift in (0..32).step_by(7) {
+        let byte = take(bytes, 1)?[0];
+        value |= u64::from(byte & 0x7F) << shift;
+        if byte & 0x80 == 0 {
+            return usize::try_from(value).ok();
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use move_core_types::language_storage::StructTag;
+    use std::str::FromStr;
+
+    fn struct_type(s: &str) -> MoveType {
+        MoveType::from(&TypeTag::Struct(Box::new(StructTag::from_str(s).unwrap())))
+    }
+
+    #[test]
+    fn test_decode_arguments() {
+        let address = AccountAddress::from_hex_literal("0xcafe").unwrap();
+        let function_params = [
+            MoveType::Reference {
+                mutable: false,
+                to: Box::new(MoveType::Signer),
+            },
+            MoveType::Address,
+            MoveType::U64,
+            struct_type("0x1::string::String"),
+            MoveType::Vector {
+                items: Box::new(MoveType::GenericTypeParam { index: 0 }),
+            },
+            struct_type("0x1::option::Option<u8>"),
+        ];
+        let params: Vec<&MoveType> = function_params
+            .iter()
+            .filter(|param| !is_signer(param))
+            .collect();
+        let args = [
+            bcs::to_bytes(&address).unwrap(),
+            bcs::to_bytes(&100u64).unwrap(),
+            bcs::to_bytes("hello").unwrap(),
+            bcs::to_bytes(&vec![true, false]).unwrap(),
+            bcs::to_bytes(&Some(7u8)).unwrap(),
+        ];
+        let decoded: Vec<Value> = params
+            .iter()
+            .zip(args.iter())
+            .map(|(param, arg)| decode_argument(param, &[TypeTag::Bool], arg).unwrap())
+            .collect();
+
+        assert_eq!(decoded, vec![
+            json!("0xcafe"),
+            json!("100"),
+            json!("hello"),
+            json!([true, false]),
+            json!({ "vec": [7] }),
+        ]);
+    }
+
+    #[test]
+    fn test_decode_argument_rejects_malformed_bytes() {
+        // Trailing bytes
+        assert!(decode_argument(&MoveType::U8, &[], &[1, 2]).is_none());
+        // Truncated
+        assert!(decode_argument(&MoveType::U64, &[], &[1, 2]).is_none());
+        // Invalid bool
+        assert!(decode_argument(&MoveType::Bool, &[], &[2]).is_none());
+        // Vector length larger than the remaining bytes
+        let vector = MoveType::Vector {
+            items: Box::new(MoveType::U64),
+        };
+        assert!(decode_argument(&vector, &[], &[0xFF, 0xFF, 0x03]).is_none());
+        // Unresolved type parameter
+        assert!(decode_argument(&MoveType::GenericTypeParam { index: 0 }, &[], &[1]).is_none());
+
+        // Arguments without a matching parameter fall back to hex
+        assert_eq!(decode_arguments(None, &[], &[vec![0xAB]]), vec![json!(
+            "0xab"
+        )]);
+    }
+}
@@ -0,0 +1,30 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::common::types::{CliCommand, CliResult};
+use clap::Subcommand;
+
+pub mod decode;
+pub mod offline;
+
+/// Tool for inspecting transactions
+///
+#[derive(Subcommand)]
+pub enum TransactionTool {
+    Build(offline::BuildTransaction),
+    Decode(decode::DecodeTransaction),
+    Sign(offline::SignTransaction),
+    SubmitSigned(offline::SubmitSignedTransaction),
+}
+
+impl TransactionTool {
+    pub async fn execute(self) -> CliResult {
+        use TransactionTool::*;
+        match self {
+            Build(tool) => tool.execute_serialized().await,
+            Decode(tool) => tool.execute_serialized().await,
+            Sign(tool) => tool.execute_serialized().await,
+            SubmitSigned(tool) => tool.execute_serialized().await,
+        }
+    }
+}
@@ -0,0 +1,685 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Offline signing, where a transaction is built on an online machine, signed by each of its
+//! signers on machines that never connect to the network, and then submitted from an online
+//! machine again.
+
+use crate::{
+    common::{
+        signer::AccountSigner,
+        types::{
+            ArgWithTypeVec, CliCommand, CliError, CliTypedResult, EncodingOptions,
+            EntryFunctionArguments, GasOptions, PrivateKeyInputOptions, ProfileOptions,
+            PromptOptions, RestOptions, SaveFile, ScriptFunctionArguments, TransactionSummary,
+            TypeArgVec,
+        },
+        utils::{get_account_with_state, get_auth_key, prompt_yes_with_override, read_from_file},
+    },
+    move_tool::MemberId,
+    transaction::decode::decode_payload,
+};
+use aptos_api_types::HexEncodedBytes;
+use aptos_global_constants::adjust_gas_headroom;
+use aptos_sdk::transaction_builder::TransactionFactory;
+use aptos_types::{
+    chain_id::ChainId,
+    transaction::{
+        authenticator::{AccountAuthenticator, AuthenticationKey, TransactionAuthenticator},
+        RawTransaction, RawTransactionWithData, SignedTransaction, TransactionPayload,
+    },
+};
+use async_trait::async_trait;
+use clap::Parser;
+use move_core_types::account_address::AccountAddress;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    path::PathBuf,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// A transaction being signed offline, as passed between `build`, `sign` and `submit-signed`
+#[derive(Debug, Deserialize, Serialize)]
+pub struct OfflineTransaction {
+    /// BCS serialized `RawTransaction`
+    pub raw_transaction: HexEncodedBytes,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub secondary_signer_addresses: Vec<AccountAddress>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fee_payer_address: Option<AccountAddress>,
+    /// BCS serialized `AccountAuthenticator`s of the signers that have signed so far
+    #[serde(default)]
+    pub signatures: BTreeMap<AccountAddress, HexEncodedBytes>,
+}
+
+impl OfflineTransaction {
+    pub fn new(
+        raw_transaction: &RawTransaction,
+        secondary_signer_addresses: Vec<AccountAddress>,
+        fee_payer_address: Option<AccountAddress>,
+    ) -> CliTypedResult<Self> {
+        Ok(Self {
+            raw_transaction: bcs::to_bytes(raw_transaction)
+                .map_err(|err| CliError::BCS("raw transaction", err))?
+                .into(),
+            secondary_signer_addresses,
+            fee_payer_address,
+            signatures: BTreeMap::new(),
+        })
+    }
+
+    pub fn load(path: &std::path::Path) -> CliTypedResult<Self> {
+        serde_json::from_slice(&read_from_file(path)?).map_err(|err| {
+            CliError::UnableToParse(
+                "offline transaction",
+                format!("{}: {}", path.display(), err),
+            )
+        })
+    }
+
+    pub fn save(&self, save_file: &SaveFile) -> CliTypedResult<()> {
+        save_file.save_to_file(
+            "Offline transaction",
+            serde_json::to_string_pretty(self)
+                .map_err(|err| CliError::UnexpectedError(err.to_string()))?
+                .as_bytes(),
+        )
+    }
+
+    pub fn raw_transaction(&self) -> CliTypedResult<RawTransaction> {
+        bcs::from_bytes(self.raw_transaction.inner())
+            .map_err(|err| CliError::BCS("raw transaction", err))
+    }
+
+    fn has_additional_signers(&self) -> bool {
+        !self.secondary_signer_addresses.is_empty() || self.fee_payer_address.is_some()
+    }
+
+    /// Every address that has to sign, starting with the sender
+    pub fn signer_addresses(&self) -> CliTypedResult<Vec<AccountAddress>> {
+        let mut addresses = vec![self.raw_transaction()?.sender()];
+        addresses.extend(self.secondary_signer_addresses.iter().copied());
+        addresses.extend(self.fee_payer_address);
+        Ok(addresses)
+    }
+
+    /// Adds the signature of `signer`, which has to be one of the transaction's signers
+    pub fn sign(&mut self, signer: &AccountSigner) -> CliTypedResult<()> {
+        let address = signer.address();
+        if !self.signer_addresses()?.contains(&address) {
+            return Err(CliError::CommandArgumentError(format!(
+                "Account {} is not a signer of this transaction",
+                address
+            )));
+        }
+
+        let raw_transaction = self.raw_transaction()?;
+        let authenticator = if self.has_additional_signers() {
+            let message = match self.fee_payer_address {
+                Some(fee_payer_address) => RawTransactionWithData::new_fee_payer(
+                    raw_transaction,
+                    self.secondary_signer_addresses.clone(),
+                    fee_payer_address,
+                ),
+                None => RawTransactionWithData::new_multi_agent(
+                    raw_transaction,
+                    self.secondary_signer_addresses.clone(),
+                ),
+            };
+            signer.sign_transaction_with_data(&message)?
+        } else {
+            signer.sign_raw_transaction(&raw_transaction)?
+        };
+        let authenticator = bcs::to_bytes(&authenticator)
+            .map_err(|err| CliError::BCS("account authenticator", err))?;
+        self.signatures.insert(address, authenticator.into());
+        Ok(())
+    }
+
+    fn authenticator(&self, address: &AccountAddress) -> CliTypedResult<AccountAuthenticator> {
+        let bytes = self.signatures.get(address).ok_or_else(|| {
+            CliError::CommandArgumentError(format!(
+                "Transaction has not been signed by {} yet",
+                address
+            ))
+        })?;
+        bcs::from_bytes(bytes.inner()).map_err(|err| CliError::BCS("account authenticator", err))
+    }
+
+    /// Assembles the signed transaction, once all of its signers have signed
+    pub fn into_signed_transaction(self) -> CliTypedResult<SignedTransaction> {
+        let raw_transaction = self.raw_transaction()?;
+        let sender = self.authenticator(&raw_transaction.sender())?;
+        let secondary_signers = self
+            .secondary_signer_addresses
+            .iter()
+            .map(|address| self.authenticator(address))
+            .collect::<CliTypedResult<Vec<_>>>()?;
+
+        let txn = if let Some(fee_payer_address) = self.fee_payer_address {
+            let fee_payer = self.authenticator(&fee_payer_address)?;
+            SignedTransaction::new_fee_payer(
+                raw_transaction,
+                sender,
+                self.secondary_signer_addresses,
+                secondary_signers,
+                fee_payer_address,
+                fee_payer,
+            )
+        } else if !secondary_signers.is_empty() {
+            SignedTransaction::new_multi_agent(
+                raw_transaction,
+                sender,
+                self.secondary_signer_addresses,
+                secondary_signers,
+            )
+        } else if let AccountAuthenticator::Ed25519 {
+            public_key,
+            signature,
+        } = sender
+        {
+            SignedTransaction::new(raw_transaction, public_key, signature)
+        } else {
+            SignedTransaction::new_signed_transaction(
+                raw_transaction,
+                TransactionAuthenticator::single_sender(sender),
+            )
+        };
+        txn.verify_signature().map_err(|err| {
+            CliError::UnexpectedError(format!(
+                "Signatures of the transaction are invalid: {}",
+                err
+            ))
+        })?;
+        Ok(txn)
+    }
+}
+
+/// Build an unsigned transaction to be signed offline
+///
+/// The payload is either an entry function (`--function-id`) or a compiled script
+/// (`--compiled-script-path`).  Only the sequence number, chain ID and gas estimates are fetched
+/// from the network, so passing `--sequence-number`, `--chain-id`, `--gas-unit-price` and
+/// `--max-gas` builds the transaction fully offline.  The transaction is written to `--output`,
+/// for `aptos transaction sign`.
+#[derive(Parser)]
+pub struct BuildTransaction {
+    /// Function name as `<ADDRESS>::<MODULE_ID>::<FUNCTION_NAME>`, for an entry function payload
+    ///
+    /// Example: `0x1::aptos_account::transfer`
+    #[clap(
+        long,
+        required_unless_present_any = ["json_file", "compiled_script_path"],
+        conflicts_with = "compiled_script_path"
+    )]
+    pub(crate) function_id: Option<MemberId>,
+
+    /// Path to a compiled script (`.mv`), for a script payload
+    #[clap(long, value_parser)]
+    pub(crate) compiled_script_path: Option<PathBuf>,
+
+    #[clap(flatten)]
+    pub(crate) type_arg_vec: TypeArgVec,
+    #[clap(flatten)]
+    pub(crate) arg_vec: ArgWithTypeVec,
+
+    /// JSON file specifying the type arguments and arguments (and the function ID, for an entry
+    /// function payload)
+    #[clap(long, value_parser, conflicts_with_all = &["function_id", "args", "type_args"])]
+    pub(crate) json_file: Option<PathBuf>,
+
+    /// Sender account address
+    ///
+    /// Defaults to the account of the profile
+    #[clap(long, value_parser = crate::common::types::load_account_arg)]
+    pub(crate) sender_account: Option<AccountAddress>,
+
+    /// Sequence number of the transaction
+    ///
+    /// Defaults to the current sequence number of the sender on chain
+    #[clap(long)]
+    pub(crate) sequence_number: Option<u64>,
+
+    /// Chain ID of the network the transaction is for
+    ///
+    /// Defaults to the chain ID of the fullnode
+    #[clap(long)]
+    pub(crate) chain_id: Option<ChainId>,
+
+    /// Addresses of the secondary signers of a multi-agent transaction
+    #[clap(long, num_args = 0.., value_parser = crate::common::types::load_account_arg)]
+    pub(crate) secondary_signers: Vec<AccountAddress>,
+
+    /// Address of the account paying for the gas of the transaction
+    #[clap(long, value_parser = crate::common::types::load_account_arg)]
+    pub(crate) fee_payer: Option<AccountAddress>,
+
+    /// Unix timestamp (in seconds) at which the transaction expires
+    ///
+    /// Collecting the signatures offline usually takes longer than `--expiration-secs` allows,
+    /// so this sets an absolute expiration time instead.
+    #[clap(long, conflicts_with = "expiration_secs")]
+    pub(crate) expiration_timestamp_secs: Option<u64>,
+
+    /// File to write the unsigned transaction to
+    #[clap(long, value_parser)]
+    pub(crate) output: PathBuf,
+
+    #[clap(flatten)]
+    pub(crate) gas_options: GasOptions,
+    #[clap(flatten)]
+    pub(crate) rest_options: RestOptions,
+    #[clap(flatten)]
+    pub(crate) profile_options: ProfileOptions,
+    #[clap(flatten)]
+    pub(crate) prompt_options: PromptOptions,
+}
+
+#[async_trait]
+impl CliCommand<String> for BuildTransaction {
+    fn command_name(&self) -> &'static str {
+        "BuildTransaction"
+    }
+
+    async fn execute(self) -> CliTypedResult<String> {
+        let save_file = SaveFile {
+            output_file: self.output,
+            prompt_options: self.prompt_options,
+        };
+        save_file.check_file()?;
+
+        let sender = match self.sender_account {
+            Some(sender) => sender,
+            None => self.profile_options.account_address()?,
+        };
+        let payload = match self.compiled_script_path {
+            Some(compiled_script_path) => ScriptFunctionArguments {
+                type_arg_vec: self.type_arg_vec,
+                arg_vec: self.arg_vec,
+                json_file: self.json_file,
+            }
+            .create_script_payload(read_from_file(&compiled_script_path)?)?,
+            None => TransactionPayload::EntryFunction(
+                EntryFunctionArguments {
+                    function_id: self.function_id,
+                    type_arg_vec: self.type_arg_vec,
+                    arg_vec: self.arg_vec,
+                    json_file: self.json_file,
+                }
+                .try_into()?,
+            ),
+        };
+        if let Some(expiration_timestamp_secs) = self.expiration_timestamp_secs {
+            let now = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs();
+            if expiration_timestamp_secs <= now {
+                return Err(CliError::CommandArgumentError(format!(
+                    "--expiration-timestamp-secs {} is in the past",
+                    expiration_timestamp_secs
+                )));
+            }
+        }
+
+        // Only go online for what hasn't been provided
+        let (sequence_number, chain_id) = match (self.sequence_number, self.chain_id) {
+            (Some(sequence_number), Some(chain_id)) => (sequence_number, chain_id),
+            (sequence_number, chain_id) => {
+                let client = self.rest_options.client(&self.profile_options)?;
+                let (account, state) = get_account_with_state(&client, sender).await?;
+                (
+                    sequence_number.unwrap_or(account.sequence_number),
+                    chain_id.unwrap_or_else(|| ChainId::new(state.chain_id)),
+                )
+            },
+        };
+        let gas_unit_price = match self.gas_options.gas_unit_price {
+            Some(gas_unit_price) => gas_unit_price,
+            None => {
+                self.rest_options
+                    .client(&self.profile_options)?
+                    .estimate_gas_price()
+                    .await?
+                    .into_inner()
+                    .gas_estimate
+            },
+        };
+
+        let transaction_factory = TransactionFactory::new(chain_id)
+            .with_gas_unit_price(gas_unit_price)
+            .with_transaction_expiration_time(self.gas_options.expiration_secs);
+        let build = |max_gas: u64| {
+            let builder = transaction_factory
+                .clone()
+                .with_max_gas_amount(max_gas)
+                .payload(payload.clone())
+                .sender(sender)
+                .sequence_number(sequence_number);
+            match self.expiration_timestamp_secs {
+                Some(expiration_timestamp_secs) => {
+                    builder.expiration_timestamp_secs(expiration_timestamp_secs)
+                },
+                None => builder,
+            }
+            .build()
+        };
+
+        let max_gas = match self.gas_options.max_gas {
+            Some(max_gas) => max_gas,
+            None => {
+                // Simulate with empty signatures to estimate the gas
+                let raw_transaction = build(transaction_factory.get_max_gas_amount());
+                let authenticator = if self.secondary_signers.is_empty() && self.fee_payer.is_none()
+                {
+                    TransactionAuthenticator::single_sender(
+                        AccountAuthenticator::NoAccountAuthenticator,
+                    )
+                } else {
+                    let no_authenticators = vec![
+                        AccountAuthenticator::NoAccountAuthenticator;
+                        self.secondary_signers.len()
+                    ];
+                    match self.fee_payer {
+                        Some(fee_payer) => TransactionAuthenticator::fee_payer(
+                            AccountAuthenticator::NoAccountAuthenticator,
+                            self.secondary_signers.clone(),
+                            no_authenticators,
+                            fee_payer,
+                            AccountAuthenticator::NoAccountAuthenticator,
+                        ),
+                        None => TransactionAuthenticator::multi_agent(
+                            AccountAuthenticator::NoAccountAuthenticator,
+                            self.secondary_signers.clone(),
+                            no_authenticators,
+                        ),
+                    }
+                };
+                let simulated = self
+                    .rest_options
+                    .client(&self.profile_options)?
+                    .simulate_bcs_with_gas_estimation(
+                        &SignedTransaction::new_signed_transaction(raw_transaction, authenticator),
+                        true,
+                        false,
+                    )
+                    .await?
+                    .into_inner();
+                if !simulated.info.status().is_success() {
+                    return Err(CliError::SimulationError(format!(
+                        "{:?}",
+                        simulated.info.status()
+                    )));
+                }
+                adjust_gas_headroom(
+                    simulated.info.gas_used(),
+                    simulated
+                        .transaction
+                        .try_as_signed_user_txn()
+                        .map(|txn| txn.max_gas_amount())
+                        .unwrap_or_else(|| transaction_factory.get_max_gas_amount()),
+                )
+            },
+        };
+
+        let raw_transaction = build(max_gas);
+        OfflineTransaction::new(&raw_transaction, self.secondary_signers, self.fee_payer)?
+            .save(&save_file)?;
+        Ok(format!(
+            "Unsigned transaction written to {}",
+            save_file.output_file.display()
+        ))
+    }
+}
+
+/// Sign a transaction built with `aptos transaction build`
+///
+/// The signer may be the sender, a secondary signer or the fee payer, and may be backed by a
+/// private key or a Ledger.  The signature is added to the transaction file, which is passed on
+/// to the next signer, and finally to `aptos transaction submit-signed`.
+#[derive(Parser)]
+pub struct SignTransaction {
+    /// File containing the transaction to sign
+    #[clap(long, value_parser)]
+    pub(crate) input: PathBuf,
+
+    /// File to write the signed transaction to
+    ///
+    /// Defaults to overwriting the input file
+    #[clap(long, value_parser)]
+    pub(crate) output: Option<PathBuf>,
+
+    /// Don't connect to the network
+    ///
+    /// Otherwise, the signer's key is checked against the authentication key of its account on
+    /// chain, which catches signing with a key that has since been rotated.
+    #[clap(long)]
+    pub(crate) offline: bool,
+
+    /// Address of the signing account
+    ///
+    /// This allows you to override the account address from the derived account address
+    /// in the event that the authentication key was rotated or for a resource account
+    #[clap(long, value_parser = crate::common::types::load_account_arg)]
+    pub(crate) signer_account: Option<AccountAddress>,
+
+    #[clap(flatten)]
+    pub(crate) private_key_options: PrivateKeyInputOptions,
+    #[clap(flatten)]
+    pub(crate) encoding_options: EncodingOptions,
+    #[clap(flatten)]
+    pub(crate) profile_options: ProfileOptions,
+    #[clap(flatten)]
+    pub(crate) rest_options: RestOptions,
+    #[clap(flatten)]
+    pub(crate) prompt_options: PromptOptions,
+}
+
+#[async_trait]
+impl CliCommand<String> for SignTransaction {
+    fn command_name(&self) -> &'static str {
+        "SignTransaction"
+    }
+
+    async fn execute(self) -> CliTypedResult<String> {
+        let mut txn = OfflineTransaction::load(&self.input)?;
+        let signer = AccountSigner::from_options(
+            &self.private_key_options,
+            self.encoding_options.encoding,
+            &self.profile_options,
+            self.signer_account,
+        )?;
+
+        if !self.offline {
+            let client = self.rest_options.client(&self.profile_options)?;
+            let auth_key = get_auth_key(&client, signer.address()).await?;
+            if auth_key != AuthenticationKey::ed25519(signer.public_key()) {
+                return Err(CliError::CommandArgumentError(format!(
+                    "The key doesn't match the authentication key {} of account {}",
+                    auth_key,
+                    signer.address()
+                )));
+            }
+        }
+
+        // Show what is being signed, without fetching ABIs so that it works offline
+        let raw_transaction = txn.raw_transaction()?;
+        let summary = serde_json::json!({
+            "sender": raw_transaction.sender(),
+            "sequence_number": raw_transaction.sequence_number(),
+            "chain_id": raw_transaction.chain_id().id(),
+            "max_gas_amount": raw_transaction.max_gas_amount(),
+            "gas_unit_price": raw_transaction.gas_unit_price(),
+            "expiration_timestamp_secs": raw_transaction.expiration_timestamp_secs(),
+            "payload": decode_payload(raw_transaction.payload(), None).await?,
+            "secondary_signers": txn.secondary_signer_addresses,
+            "fee_payer": txn.fee_payer_address,
+        });
+        eprintln!(
+            "{}",
+            serde_json::to_string_pretty(&summary)
+                .map_err(|err| CliError::UnexpectedError(err.to_string()))?
+        );
+        prompt_yes_with_override(
+            &format!(
+                "Do you want to sign this transaction as {}?",
+                signer.address()
+            ),
+            self.prompt_options,
+        )?;
+
+        txn.sign(&signer)?;
+        let signer_addresses = txn.signer_addresses()?;
+        let save_file = SaveFile {
+            output_file: self.output.unwrap_or(self.input),
+            prompt_options: self.prompt_options,
+        };
+        txn.save(&save_file)?;
+        Ok(format!(
+            "Signed as {}, {} of {} signatures collected, written to {}",
+            signer.address(),
+            signer_addresses
+                .iter()
+                .filter(|address| txn.signatures.contains_key(address))
+                .count(),
+            signer_addresses.len(),
+            save_file.output_file.display()
+        ))
+    }
+}
+
+/// Submit a transaction that has been signed with `aptos transaction sign`
+#[derive(Parser)]
+pub struct SubmitSignedTransaction {
+    /// File containing the signed transaction
+    #[clap(long, value_parser)]
+    pub(crate) input: PathBuf,
+
+    #[clap(flatten)]
+    pub(crate) rest_options: RestOptions,
+    #[clap(flatten)]
+    pub(crate) profile_options: ProfileOptions,
+}
+
+#[async_trait]
+impl CliCommand<TransactionSummary> for SubmitSignedTransaction {
+    fn command_name(&self) -> &'static str {
+        "SubmitSignedTransaction"
+    }
+
+    async fn execute(self) -> CliTypedResult<TransactionSummary> {
+        let txn = OfflineTransaction::load(&self.input)?.into_signed_transaction()?;
+        let client = self.rest_options.client(&self.profile_options)?;
+        client.submit_bcs(&txn).await?;
+        let response = client
+            .wait_for_signed_transaction(&txn)
+            .await
+            .map_err(|err| CliError::ApiError(err.to_string()))?;
+        Ok(TransactionSummary::from(&response.into_inner()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aptos_sdk::types::LocalAccount;
+    use aptos_types::transaction::Script;
+
+    fn raw_transaction(sender: AccountAddress) -> RawTransaction {
+        RawTransaction::new(
+            sender,
+            0,
+            TransactionPayload::Script(Script::new(vec![], vec![], vec![])),
+            1_000,
+            100,
+            u64::MAX,
+            ChainId::test(),
+        )
+    }
+
+    #[test]
+    fn test_offline_transaction_round_trip() {
+        let mut rng = rand::thread_rng();
+        let sender = AccountSigner::Local(LocalAccount::generate(&mut rng));
+        let fee_payer = AccountSigner::Local(LocalAccount::generate(&mut rng));
+        let stranger = AccountSigner::Local(LocalAccount::generate(&mut rng));
+
+        let mut txn = OfflineTransaction::new(
+            &raw_transaction(sender.address()),
+            vec![],
+            Some(fee_payer.address()),
+        )
+        .unwrap();
+        txn.sign(&sender).unwrap();
+        assert!(txn.sign(&stranger).is_err());
+
+        // Survives being written out between signers, and can't be submitted until everyone
+        // has signed
+        let json = serde_json::to_string(&txn).unwrap();
+        let partial: OfflineTransaction = serde_json::from_str(&json).unwrap();
+        assert!(partial.into_signed_transaction().is_err());
+
+        let mut txn: OfflineTransaction = serde_json::from_str(&json).unwrap();
+        txn.sign(&fee_payer).unwrap();
+        let signed = txn.into_signed_transaction().unwrap();
+        assert_eq!(
+            signed.authenticator().fee_payer_address(),
+            Some(fee_payer.address())
+        );
+    }
+
+    #[test]
+    fn test_build_transaction_args() {
+        let parse = |args: &[&str]| {
+            BuildTransaction::try_parse_from(
+                ["build", "--output", "txn.json"].iter().chain(args.iter()),
+            )
+        };
+
+        let build = parse(&[
+            "--compiled-script-path",
+            "script.mv",
+            "--expiration-timestamp-secs",
+            "2000000000",
+        ])
+        .unwrap();
+        assert_eq!(build.compiled_script_path, Some(PathBuf::from("script.mv")));
+        assert_eq!(build.expiration_timestamp_secs, Some(2_000_000_000));
+        assert!(parse(&["--function-id", "0x1::aptos_account::transfer"]).is_ok());
+
+        // Exactly one payload, and one way to set the expiration
+        assert!(parse(&[]).is_err());
+        assert!(parse(&[
+            "--function-id",
+            "0x1::aptos_account::transfer",
+            "--compiled-script-path",
+            "script.mv",
+        ])
+        .is_err());
+        assert!(parse(&[
+            "--compiled-script-path",
+            "script.mv",
+            "--expiration-secs",
+            "60",
+            "--expiration-timestamp-secs",
+            "2000000000",
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn test_offline_transaction_single_sender() {
+        let mut rng = rand::thread_rng();
+        let sender = AccountSigner::Local(LocalAccount::generate(&mut rng));
+
+        let mut txn =
+            OfflineTransaction::new(&raw_transaction(sender.address()), vec![], None).unwrap();
+        txn.sign(&sender).unwrap();
+        let signed = txn.into_signed_transaction().unwrap();
+        assert!(matches!(
+            signed.authenticator(),
+            TransactionAuthenticator::Ed25519 { .. }
+        ));
+    }
+}
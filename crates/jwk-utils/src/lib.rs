@@ -26,14 +26,24 @@ pub async fn fetch_jwks_from_jwks_uri(
     my_addr: Option<AccountAddress>,
     jwks_uri: &str,
 ) -> Result<Vec<JWK>> {
+    let keys = fetch_raw_jwks_from_jwks_uri(my_addr, jwks_uri).await?;
+    let jwks = keys.into_iter().map(JWK::from).collect();
+    Ok(jwks)
+}
+
+/// Same as `fetch_jwks_from_jwks_uri`, but returns the keys as JSON objects, so that
+/// provider-specific quirks can be handled before they are parsed.
+pub async fn fetch_raw_jwks_from_jwks_uri(
+    my_addr: Option<AccountAddress>,
+    jwks_uri: &str,
+) -> Result<Vec<serde_json::Value>> {
     let client = reqwest::Client::new();
     let mut request_builder = client.get(jwks_uri);
     if let Some(addr) = my_addr {
         request_builder = request_builder.header(COOKIE, addr.to_hex());
     }
     let JWKsResponse { keys } = request_builder.send().await?.json().await?;
-    let jwks = keys.into_iter().map(JWK::from).collect();
-    Ok(jwks)
+    Ok(keys)
 }
 
 /// Given an Open ID configuration URL, fetch its JWK url.
@@ -180,6 +180,16 @@ pub struct EmitArgs {
     #[clap(long)]
     pub coordination_delay_between_instances: Option<u64>,
 
+    /// Address of the leader (see the `coordinate` command) to register with, to run as one of
+    /// the instances of a coordinated load test. The leader assigns the accounts to use and the
+    /// time at which to start emitting.
+    #[clap(long, conflicts_with_all = &["account_minter_seed", "coordination_delay_between_instances"])]
+    pub coordinator_address: Option<String>,
+
+    /// Region the instance runs in, reported to the leader of a coordinated load test
+    #[clap(long, requires = "coordinator_address")]
+    pub region: Option<String>,
+
     #[clap(long)]
     pub account_minter_seed: Option<String>,
 
@@ -208,6 +218,27 @@ pub struct AccountTypeArgs {
     pub keyless_jwt: Option<String>,
 }
 
+#[derive(Clone, Debug, Default, Deserialize, Parser, Serialize)]
+pub struct CoordinatorArgs {
+    /// Address to listen on for emitter instances to register, e.g. `0.0.0.0:9000`
+    #[clap(long)]
+    pub listen_address: String,
+
+    /// Number of emitter instances taking part in the load test
+    #[clap(long)]
+    pub num_instances: usize,
+
+    /// Seed the account minter seeds of the instances are derived from. If no seed is provided,
+    /// a random seed is used.
+    #[clap(long)]
+    pub account_minter_seed: Option<String>,
+
+    /// Time (secs) from all instances having registered until they start emitting. This needs to
+    /// leave the instances enough time to create their accounts.
+    #[clap(long, default_value_t = 300)]
+    pub start_delay_secs: u64,
+}
+
 #[derive(Clone, Debug, Default, Deserialize, Parser, Serialize)]
 pub struct CreateAccountsArgs {
     /// Number of accounts to create
@@ -0,0 +1,284 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Coordinated load mode, for load tests emitting from multiple regions at once. Emitter
+//! instances register with a leader, which assigns each of them its own accounts and a shared
+//! start time, and merges the stats the instances report once they are done.
+
+use crate::{
+    args::CoordinatorArgs,
+    emitter::{parse_seed, stats::TxnStats},
+};
+use anyhow::{bail, ensure, Context, Result};
+use aptos_crypto::HashValue;
+use futures::future::try_join_all;
+use log::{info, warn};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tokio::{
+    io::{AsyncBufReadExt, AsyncWriteExt, BufReader},
+    net::{
+        tcp::{OwnedReadHalf, OwnedWriteHalf},
+        TcpListener, TcpStream,
+    },
+};
+
+/// Messages exchanged between the leader and the emitter instances (one JSON object per line).
+#[derive(Debug, Deserialize, Serialize)]
+enum CoordinatorMessage {
+    /// Registers an emitter instance with the leader.
+    Register { region: String },
+    /// Assigns an instance its share of the load test, once all instances have registered.
+    Assignment(InstanceAssignment),
+    /// Reports the stats of an instance, once it is done emitting.
+    Report(TxnStats),
+    /// The stats merged across all instances.
+    MergedStats(TxnStats),
+}
+
+/// The share of a coordinated load test assigned to an emitter instance.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct InstanceAssignment {
+    pub instance_index: usize,
+    pub num_instances: usize,
+    /// Account minter seeds are derived per instance, so that instances never share accounts
+    /// (and so never contend on sequence numbers).
+    pub account_minter_seed: String,
+    /// The time (ms since the unix epoch) at which all instances start emitting.
+    pub start_time_unix_ms: u64,
+}
+
+impl InstanceAssignment {
+    pub fn start_time(&self) -> SystemTime {
+        UNIX_EPOCH + Duration::from_millis(self.start_time_unix_ms)
+    }
+}
+
+struct Connection {
+    reader: BufReader<OwnedReadHalf>,
+    writer: OwnedWriteHalf,
+}
+
+impl Connection {
+    fn new(stream: TcpStream) -> Self {
+        let (reader, writer) = stream.into_split();
+        Self {
+            reader: BufReader::new(reader),
+            writer,
+        }
+    }
+
+    async fn send(&mut self, message: &CoordinatorMessage) -> Result<()> {
+        let mut line = serde_json::to_vec(message)?;
+        line.push(b'\n');
+        self.writer.write_all(&line).await?;
+        Ok(())
+    }
+
+    async fn receive(&mut self) -> Result<CoordinatorMessage> {
+        let mut line = String::new();
+        if self.reader.read_line(&mut line).await? == 0 {
+            bail!("Connection closed");
+        }
+        Ok(serde_json::from_str(&line)?)
+    }
+
+    async fn receive_report(&mut self) -> Result<TxnStats> {
+        match self.receive().await? {
+            CoordinatorMessage::Report(stats) => Ok(stats),
+            message => bail!("Expected a report from the instance, got {:?}", message),
+        }
+    }
+}
+
+/// An emitter instance taking part in a coordinated load test.
+pub struct CoordinatedInstance {
+    connection: Connection,
+    assignment: InstanceAssignment,
+}
+
+impl CoordinatedInstance {
+    /// Registers with the leader, and waits for all other instances to register.
+    pub async fn register(leader_address: &str, region: &str) -> Result<Self> {
+        let stream = TcpStream::connect(leader_address)
+            .await
+            .with_context(|| format!("Failed to connect to the leader at {}", leader_address))?;
+        let mut connection = Connection::new(stream);
+        connection
+            .send(&CoordinatorMessage::Register {
+                region: region.to_string(),
+            })
+            .await?;
+
+        info!("Registered with the leader at {}", leader_address);
+        match connection.receive().await? {
+            CoordinatorMessage::Assignment(assignment) => {
+                info!(
+                    "Assigned instance {} out of {}, starting at {:?}",
+                    assignment.instance_index,
+                    assignment.num_instances,
+                    assignment.start_time()
+                );
+                Ok(Self {
+                    connection,
+                    assignment,
+                })
+            },
+            message => bail!("Expected an assignment from the leader, got {:?}", message),
+        }
+    }
+
+    pub fn assignment(&self) -> &InstanceAssignment {
+        &self.assignment
+    }
+
+    /// Reports the stats of this instance, and returns the stats merged across all instances.
+    pub async fn report(mut self, stats: &TxnStats) -> Result<TxnStats> {
+        self.connection
+            .send(&CoordinatorMessage::Report(stats.clone()))
+            .await?;
+        match self.connection.receive().await? {
+            CoordinatorMessage::MergedStats(stats) => Ok(stats),
+            message => bail!("Expected merged stats from the leader, got {:?}", message),
+        }
+    }
+}
+
+/// Runs the leader of a coordinated load test, and returns the stats merged across all instances.
+pub async fn run_coordinator(args: &CoordinatorArgs) -> Result<TxnStats> {
+    let listener = TcpListener::bind(&args.listen_address)
+        .await
+        .with_context(|| format!("Failed to listen on {}", args.listen_address))?;
+    run_coordinator_with_listener(listener, args).await
+}
+
+async fn run_coordinator_with_listener(
+    listener: TcpListener,
+    args: &CoordinatorArgs,
+) -> Result<TxnStats> {
+    ensure!(args.num_instances > 0, "At least one instance is required");
+
+    info!(
+        "Waiting for {} emitter instances to register",
+        args.num_instances
+    );
+    let mut connections = Vec::with_capacity(args.num_instances);
+    while connections.len() < args.num_instances {
+        let (stream, address) = listener.accept().await?;
+        let mut connection = Connection::new(stream);
+        match connection.receive().await {
+            Ok(CoordinatorMessage::Register { region }) => {
+                info!(
+                    "Registered instance {} from {} (region: {})",
+                    connections.len(),
+                    address,
+                    region
+                );
+                connections.push(connection);
+            },
+            Ok(message) => warn!("Unexpected message from {}: {:?}", address, message),
+            Err(error) => warn!("Failed to register instance from {}: {:?}", address, error),
+        }
+    }
+
+    // Assign every instance its own accounts, and the same start time
+    let base_seed = args
+        .account_minter_seed
+        .as_deref()
+        .map(parse_seed)
+        .unwrap_or_else(|| rand::thread_rng().gen());
+    let start_time = SystemTime::now() + Duration::from_secs(args.start_delay_secs);
+    let start_time_unix_ms = start_time.duration_since(UNIX_EPOCH)?.as_millis() as u64;
+    for (instance_index, connection) in connections.iter_mut().enumerate() {
+        let assignment = InstanceAssignment {
+            instance_index,
+            num_instances: args.num_instances,
+            account_minter_seed: derive_instance_seed(&base_seed, instance_index),
+            start_time_unix_ms,
+        };
+        connection
+            .send(&CoordinatorMessage::Assignment(assignment))
+            .await?;
+    }
+    info!(
+        "All instances registered, starting the load at {:?}",
+        start_time
+    );
+
+    // Wait for all instances to report, and share the merged stats with them
+    let reports = try_join_all(connections.iter_mut().map(Connection::receive_report)).await?;
+    let merged_stats = reports
+        .iter()
+        .skip(1)
+        .fold(reports[0].clone(), |merged_stats, stats| {
+            merged_stats.merge_concurrent(stats)
+        });
+    for connection in connections.iter_mut() {
+        connection
+            .send(&CoordinatorMessage::MergedStats(merged_stats.clone()))
+            .await?;
+    }
+    Ok(merged_stats)
+}
+
+/// Derives the account minter seed of an instance (in the format expected by `parse_seed`)
+fn derive_instance_seed(base_seed: &[u8; 32], instance_index: usize) -> String {
+    let seed =
+        HashValue::sha3_256_of(&[base_seed.as_slice(), &instance_index.to_le_bytes()].concat());
+    format!("{:?}", seed.to_vec())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_coordinated_load() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let leader_address = listener.local_addr().unwrap().to_string();
+        let args = CoordinatorArgs {
+            listen_address: leader_address.clone(),
+            num_instances: 2,
+            account_minter_seed: None,
+            start_delay_secs: 60,
+        };
+        let leader =
+            tokio::spawn(async move { run_coordinator_with_listener(listener, &args).await });
+
+        // Register both instances and verify their assignments
+        let (first, second) = tokio::join!(
+            CoordinatedInstance::register(&leader_address, "us-west"),
+            CoordinatedInstance::register(&leader_address, "eu-central"),
+        );
+        let (first, second) = (first.unwrap(), second.unwrap());
+        assert_ne!(
+            first.assignment().instance_index,
+            second.assignment().instance_index
+        );
+        assert_ne!(
+            first.assignment().account_minter_seed,
+            second.assignment().account_minter_seed
+        );
+        assert_eq!(
+            first.assignment().start_time(),
+            second.assignment().start_time()
+        );
+        parse_seed(&first.assignment().account_minter_seed);
+
+        // Report the stats of both instances and verify they are merged
+        let stats = |committed, lasted_secs| TxnStats {
+            submitted: committed,
+            committed,
+            lasted: Duration::from_secs(lasted_secs),
+            ..TxnStats::default()
+        };
+        let (first, second) =
+            tokio::join!(first.report(&stats(100, 60)), second.report(&stats(50, 61)),);
+        let merged_stats = leader.await.unwrap().unwrap();
+        assert_eq!(merged_stats.committed, 150);
+        assert_eq!(merged_stats.lasted, Duration::from_secs(61));
+        assert_eq!(first.unwrap().committed, 150);
+        assert_eq!(second.unwrap().committed, 150);
+    }
+}
@@ -586,7 +586,8 @@ impl From<&EmitJobRequest> for BulkAccountCreationConfig {
             mint_to_root: req.mint_to_root,
             prompt_before_spending: req.prompt_before_spending,
             create_secondary_source_account: req.mint_to_root
-                || !req.coordination_delay_between_instances.is_zero(),
+                || !req.coordination_delay_between_instances.is_zero()
+                || req.coordinated_start_time.is_some(),
             expected_gas_per_transfer: req.get_expected_gas_per_transfer(),
             expected_gas_per_account_create: req.get_expected_gas_per_account_create(),
         }
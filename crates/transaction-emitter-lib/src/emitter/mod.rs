@@ -48,7 +48,7 @@ use std::{
         atomic::{AtomicBool, Ordering},
         Arc,
     },
-    time::{Duration, Instant},
+    time::{Duration, Instant, SystemTime},
 };
 use tokio::{runtime::Handle, task::JoinHandle, time};
 
@@ -185,6 +185,8 @@ pub struct EmitJobRequest {
     prompt_before_spending: bool,
 
     coordination_delay_between_instances: Duration,
+    // The time at which to start emitting, shared by all instances of a coordinated load test.
+    coordinated_start_time: Option<SystemTime>,
 
     latency_polling_interval: Duration,
     // Default additional wait is (txn_expiration_time_secs + 5). Override to wait for different length.
@@ -228,6 +230,7 @@ impl Default for EmitJobRequest {
             expected_gas_per_account_create: EXPECTED_GAS_PER_ACCOUNT_CREATE,
             prompt_before_spending: false,
             coordination_delay_between_instances: Duration::from_secs(0),
+            coordinated_start_time: None,
             latency_polling_interval: Duration::from_millis(300),
             tps_wait_after_expiration_secs: None,
             account_minter_seed: None,
@@ -376,6 +379,11 @@ impl EmitJobRequest {
         self
     }
 
+    pub fn coordinated_start_time(mut self, coordinated_start_time: SystemTime) -> Self {
+        self.coordinated_start_time = Some(coordinated_start_time);
+        self
+    }
+
     pub fn latency_polling_interval(mut self, latency_polling_interval: Duration) -> Self {
         self.latency_polling_interval = latency_polling_interval;
         self
@@ -846,6 +854,18 @@ impl TxnEmitter {
             );
             tokio::time::sleep(req.coordination_delay_between_instances).await;
         }
+        if let Some(start_time) = req.coordinated_start_time {
+            match start_time.duration_since(SystemTime::now()) {
+                Ok(delay) => {
+                    info!(
+                        "Sleeping until the coordinated start time for {}s",
+                        delay.as_secs()
+                    );
+                    tokio::time::sleep(delay).await;
+                },
+                Err(_) => warn!("Coordinated start time has already passed, starting right away"),
+            }
+        }
 
         let check_account_sequence_only_once_for = (0..num_accounts)
             .choose_multiple(
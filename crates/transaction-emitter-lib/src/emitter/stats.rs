@@ -1,6 +1,7 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
+use serde::{Deserialize, Serialize};
 use std::{
     fmt,
     ops::{Add, Sub},
@@ -11,7 +12,7 @@ use std::{
     time::{Duration, Instant},
 };
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
 pub struct TxnStats {
     pub submitted: u64,
     pub committed: u64,
@@ -73,6 +74,16 @@ impl TxnStats {
     }
 }
 
+impl TxnStats {
+    /// Merges the stats of emitters that ran at the same time (e.g., on different machines)
+    pub fn merge_concurrent(&self, other: &TxnStats) -> TxnStats {
+        TxnStats {
+            lasted: self.lasted.max(other.lasted),
+            ..self + other
+        }
+    }
+}
+
 impl fmt::Display for TxnStats {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
@@ -200,7 +211,7 @@ impl AtomicHistogramAccumulator {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct AtomicHistogramSnapshot {
     capacity: usize,
     step_width: u64,
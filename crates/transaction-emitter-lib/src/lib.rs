@@ -5,14 +5,16 @@
 
 mod args;
 mod cluster;
+mod coordinator;
 pub mod emitter;
 mod instance;
 mod wrappers;
 
 // These are the top level things you should need to run the emitter.
-pub use args::{ClusterArgs, CoinSourceArgs, CreateAccountsArgs, EmitArgs};
+pub use args::{ClusterArgs, CoinSourceArgs, CoordinatorArgs, CreateAccountsArgs, EmitArgs};
 // We export these if you want finer grained control.
 pub use cluster::Cluster;
+pub use coordinator::{run_coordinator, CoordinatedInstance, InstanceAssignment};
 pub use emitter::{
     query_sequence_number, query_sequence_numbers,
     stats::{TxnStats, TxnStatsRate},
@@ -4,6 +4,7 @@
 use crate::{
     args::{ClusterArgs, EmitArgs},
     cluster::Cluster,
+    coordinator::CoordinatedInstance,
     emitter::{
         account_minter::bulk_create_accounts,
         get_needed_balance_per_account_from_req,
@@ -23,7 +24,7 @@ use log::{error, info};
 use rand::{rngs::StdRng, SeedableRng};
 use std::{
     sync::Arc,
-    time::{Duration, Instant},
+    time::{Duration, Instant, SystemTime},
 };
 
 pub async fn emit_transactions(
@@ -31,6 +32,16 @@ pub async fn emit_transactions(
     emit_args: &EmitArgs,
     transaction_mix_per_phase: Vec<Vec<(TransactionType, usize)>>,
 ) -> Result<TxnStats> {
+    if let Some(coordinator_address) = &emit_args.coordinator_address {
+        return emit_coordinated_transactions(
+            cluster_args,
+            emit_args,
+            transaction_mix_per_phase,
+            coordinator_address,
+        )
+        .await;
+    }
+
     if emit_args.coordination_delay_between_instances.is_none() {
         let cluster = Cluster::try_from_cluster_args(cluster_args)
             .await
@@ -79,10 +90,47 @@ pub async fn emit_transactions(
     }
 }
 
+/// Emits transactions as one of the instances of a coordinated load test, and returns the stats
+/// merged across all instances.
+async fn emit_coordinated_transactions(
+    cluster_args: &ClusterArgs,
+    emit_args: &EmitArgs,
+    transaction_mix_per_phase: Vec<Vec<(TransactionType, usize)>>,
+    coordinator_address: &str,
+) -> Result<TxnStats> {
+    let region = emit_args.region.as_deref().unwrap_or("unknown");
+    let instance = CoordinatedInstance::register(coordinator_address, region).await?;
+
+    let mut emit_args = emit_args.clone();
+    emit_args.account_minter_seed = Some(instance.assignment().account_minter_seed.clone());
+    let cluster = Cluster::try_from_cluster_args(cluster_args)
+        .await
+        .context("Failed to build cluster")?;
+    let stats = emit_transactions_with_cluster_impl(
+        &cluster,
+        &emit_args,
+        transaction_mix_per_phase,
+        Some(instance.assignment().start_time()),
+    )
+    .await?;
+    info!("Instance stats: {}, rate: {}", stats, stats.rate());
+
+    instance.report(&stats).await
+}
+
 pub async fn emit_transactions_with_cluster(
     cluster: &Cluster,
     args: &EmitArgs,
     transaction_mix_per_phase: Vec<Vec<(TransactionType, usize)>>,
+) -> Result<TxnStats> {
+    emit_transactions_with_cluster_impl(cluster, args, transaction_mix_per_phase, None).await
+}
+
+async fn emit_transactions_with_cluster_impl(
+    cluster: &Cluster,
+    args: &EmitArgs,
+    transaction_mix_per_phase: Vec<Vec<(TransactionType, usize)>>,
+    coordinated_start_time: Option<SystemTime>,
 ) -> Result<TxnStats> {
     let emitter_mode = EmitJobMode::create(args.mempool_backlog, args.target_tps);
 
@@ -187,6 +235,10 @@ pub async fn emit_transactions_with_cluster(
         emit_job_request = emit_job_request.skip_funding_accounts();
     }
 
+    if let Some(start_time) = coordinated_start_time {
+        emit_job_request = emit_job_request.coordinated_start_time(start_time);
+    }
+
     let coin_source_account = std::sync::Arc::new(coin_source_account);
     let stats = emitter
         .emit_txn_for_with_stats(
@@ -7,7 +7,8 @@ mod diag;
 use anyhow::{Context, Result};
 use aptos_logger::{Level, Logger};
 use aptos_transaction_emitter_lib::{
-    create_accounts_command, emit_transactions, Cluster, ClusterArgs, CreateAccountsArgs, EmitArgs,
+    create_accounts_command, emit_transactions, run_coordinator, Cluster, ClusterArgs,
+    CoordinatorArgs, CreateAccountsArgs, EmitArgs,
 };
 use aptos_transaction_workloads_lib::args::EmitWorkloadArgs;
 use clap::{Parser, Subcommand};
@@ -29,6 +30,11 @@ enum TxnEmitterCommand {
     /// Create test accounts, for use with EmitTx
     CreateAccounts(CreateAccounts),
 
+    /// Runs the leader of a coordinated load test, where multiple EmitTx instances (e.g., in
+    /// different regions) register with the leader via --coordinator-address, start emitting
+    /// at the same time and report their stats to be merged.
+    Coordinate(Coordinate),
+
     /// This runs the transaction emitter in diag mode, where the focus is on
     /// FullNodes instead of ValidatorNodes. This performs a simple health check.
     Diag(Diag),
@@ -59,6 +65,12 @@ struct CreateAccounts {
     create_accounts_args: CreateAccountsArgs,
 }
 
+#[derive(Parser, Debug)]
+struct Coordinate {
+    #[clap(flatten)]
+    coordinator_args: CoordinatorArgs,
+}
+
 #[derive(Parser, Debug)]
 struct PingEndPoints {
     #[clap(flatten)]
@@ -99,6 +111,14 @@ pub async fn main() -> Result<()> {
                 .unwrap();
             Ok(())
         },
+        TxnEmitterCommand::Coordinate(args) => {
+            let stats = run_coordinator(&args.coordinator_args)
+                .await
+                .context("Coordinated load test failed")?;
+            println!("Merged stats: {}", stats);
+            println!("Merged rate: {}", stats.rate());
+            Ok(())
+        },
         TxnEmitterCommand::Diag(args) => {
             let cluster = Cluster::try_from_cluster_args(&args.cluster_args)
                 .await
@@ -4,11 +4,13 @@
 use crate::{
     move_workloads::{LoopType, PreBuiltPackagesImpl},
     token_workflow::TokenWorkflowKind,
+    trace_mix::TraceMix,
     EntryPoints, MonotonicCounterType, OrderBookState,
 };
 use aptos_transaction_generator_lib::{TransactionType, WorkflowProgress};
 use clap::{Parser, ValueEnum};
 use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
 
 /// Utility class for specifying transaction type with predefined configurations through CLI
 #[derive(Debug, Copy, Clone, ValueEnum, Default, Deserialize, Parser, Serialize)]
@@ -568,10 +570,29 @@ pub struct EmitWorkloadArgs {
 
     #[clap(long, num_args = 0..)]
     pub transaction_phases: Vec<usize>,
+
+    /// Path to a (YAML or JSON) trace of mainnet traffic to replay the transaction mix of,
+    /// instead of the given transaction types. See `trace_mix` for the format.
+    #[clap(long, conflicts_with_all = ["transaction_weights", "transaction_phases"])]
+    pub transaction_mix_trace: Option<PathBuf>,
 }
 
 impl EmitWorkloadArgs {
     pub fn args_to_transaction_mix_per_phase(&self) -> Vec<Vec<(TransactionType, usize)>> {
+        if let Some(trace_path) = &self.transaction_mix_trace {
+            let trace = TraceMix::load(trace_path).unwrap_or_else(|e| {
+                panic!(
+                    "Failed to load the transaction mix trace {}: {:#}",
+                    trace_path.display(),
+                    e
+                )
+            });
+            return vec![trace.to_transaction_mix(
+                self.module_working_set_size.unwrap_or(1),
+                self.sender_use_account_pool.unwrap_or(false),
+            )];
+        }
+
         TransactionTypeArg::args_to_transaction_mix_per_phase(
             &self.transaction_type,
             &self.transaction_weights,
@@ -7,5 +7,6 @@ pub mod args;
 mod move_workloads;
 mod prebuilt_packages;
 mod token_workflow;
+pub mod trace_mix;
 
 pub use move_workloads::{EntryPoints, LoopType, MapType, MonotonicCounterType, OrderBookState};
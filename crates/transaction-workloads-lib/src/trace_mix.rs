@@ -0,0 +1,172 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Workload mixes derived from (anonymized) mainnet traffic traces.
+//!
+//! A trace summarizes the traffic over some period as a list of entry functions,
+//! each with the number of transactions calling it, the fraction of those that
+//! conflict with other transactions and their average payload size. Framework
+//! functions map to the equivalent native workloads, while all other functions
+//! (whose addresses are anonymized) are approximated by creating objects of the
+//! traced payload size, either conflicting or not. For example:
+//!
+//! ```yaml
+//! entries:
+//!   - function: "0x1::aptos_account::transfer"
+//!     count: 5200
+//!     conflict_ratio: 0.02
+//!   - function: "0xa11ce::exchange::place_order"
+//!     count: 1800
+//!     conflict_ratio: 0.6
+//!     avg_payload_bytes: 420
+//! ```
+
+use crate::{move_workloads::PreBuiltPackagesImpl, EntryPoints};
+use anyhow::{ensure, Context, Result};
+use aptos_transaction_generator_lib::{entry_point_trait::EntryPointTrait, TransactionType};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// The approximate size of a single recipient (address and amount) in a batch transfer
+const BATCH_TRANSFER_BYTES_PER_RECIPIENT: u64 = 40;
+/// The maximum number of recipients in a single batch transfer
+const MAX_BATCH_TRANSFER_SIZE: usize = 100;
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct TraceMix {
+    pub entries: Vec<TraceMixEntry>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct TraceMixEntry {
+    /// The entry function, e.g., `0x1::aptos_account::transfer`
+    pub function: String,
+    /// The number of transactions in the trace calling the function
+    pub count: u64,
+    /// The fraction (0 to 1) of the transactions that conflict with other transactions
+    #[serde(default)]
+    pub conflict_ratio: f64,
+    /// The average size of the transaction payload (in bytes)
+    #[serde(default)]
+    pub avg_payload_bytes: u64,
+}
+
+impl TraceMix {
+    /// Loads a trace from the given (YAML or JSON) file
+    pub fn load(path: &Path) -> Result<Self> {
+        let contents = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read trace file {}", path.display()))?;
+        let trace: Self = serde_yaml::from_str(&contents)
+            .with_context(|| format!("Failed to parse trace file {}", path.display()))?;
+        trace.verify()?;
+        Ok(trace)
+    }
+
+    fn verify(&self) -> Result<()> {
+        ensure!(!self.entries.is_empty(), "The trace has no entries!");
+        for entry in &self.entries {
+            ensure!(
+                (0.0..=1.0).contains(&entry.conflict_ratio),
+                "The conflict ratio for {} must be between 0 and 1, got {}",
+                entry.function,
+                entry.conflict_ratio
+            );
+        }
+        ensure!(
+            self.entries.iter().any(|entry| entry.count > 0),
+            "All entries in the trace have a count of 0!"
+        );
+        Ok(())
+    }
+
+    /// Converts the trace into a (single phase) weighted transaction mix
+    pub fn to_transaction_mix(
+        &self,
+        module_working_set_size: usize,
+        sender_use_account_pool: bool,
+    ) -> Vec<(TransactionType, usize)> {
+        self.entries
+            .iter()
+            .filter(|entry| entry.count > 0)
+            .map(|entry| {
+                (
+                    entry.to_transaction_type(module_working_set_size, sender_use_account_pool),
+                    entry.count as usize,
+                )
+            })
+            .collect()
+    }
+}
+
+impl TraceMixEntry {
+    fn to_transaction_type(
+        &self,
+        module_working_set_size: usize,
+        sender_use_account_pool: bool,
+    ) -> TransactionType {
+        // Transfers are only conflict free if the mix is dominated by non-conflicting ones
+        let non_conflicting = self.conflict_ratio < 0.5;
+        match self.function.as_str() {
+            "0x1::aptos_account::transfer"
+            | "0x1::aptos_account::transfer_coins"
+            | "0x1::coin::transfer" => TransactionType::CoinTransfer {
+                invalid_transaction_ratio: 0,
+                sender_use_account_pool,
+                non_conflicting,
+                use_fa_transfer: false,
+            },
+            "0x1::aptos_account::fungible_transfer_only"
+            | "0x1::primary_fungible_store::transfer" => TransactionType::CoinTransfer {
+                invalid_transaction_ratio: 0,
+                sender_use_account_pool,
+                non_conflicting,
+                use_fa_transfer: true,
+            },
+            "0x1::aptos_account::create_account" => TransactionType::AccountGeneration {
+                add_created_accounts_to_pool: true,
+                max_account_working_set: 1_000_000,
+                creation_balance: 0,
+            },
+            "0x1::aptos_account::batch_transfer" | "0x1::aptos_account::batch_transfer_coins" => {
+                TransactionType::BatchTransfer {
+                    batch_size: ((self.avg_payload_bytes / BATCH_TRANSFER_BYTES_PER_RECIPIENT)
+                        as usize)
+                        .clamp(1, MAX_BATCH_TRANSFER_SIZE),
+                }
+            },
+            "0x1::code::publish_package_txn" => TransactionType::PublishPackage {
+                use_account_pool: sender_use_account_pool,
+                pre_built: &PreBuiltPackagesImpl,
+                package_name: "simple".to_string(),
+            },
+            _ => {
+                // Split the calls according to the conflict ratio, keeping the traced payload size
+                let conflicting_weight = (self.conflict_ratio * 100.0).round() as usize;
+                let entry_points: Vec<(Box<dyn EntryPointTrait>, usize)> = vec![
+                    (
+                        Box::new(EntryPoints::CreateObjects {
+                            num_objects: 1,
+                            object_payload_size: self.avg_payload_bytes,
+                        }),
+                        100 - conflicting_weight,
+                    ),
+                    (
+                        Box::new(EntryPoints::CreateObjectsConflict {
+                            num_objects: 1,
+                            object_payload_size: self.avg_payload_bytes,
+                        }),
+                        conflicting_weight,
+                    ),
+                ];
+                TransactionType::CallCustomModulesMix {
+                    entry_points: entry_points
+                        .into_iter()
+                        .filter(|(_, weight)| *weight > 0)
+                        .collect(),
+                    num_modules: module_working_set_size,
+                    use_account_pool: sender_use_account_pool,
+                }
+            },
+        }
+    }
+}
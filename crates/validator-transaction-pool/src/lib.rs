@@ -1,6 +1,7 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
+use anyhow::{bail, ensure};
 use aptos_channels::aptos_channel;
 use aptos_crypto::{hash::CryptoHash, HashValue};
 use aptos_infallible::Mutex;
@@ -40,6 +41,97 @@ impl Default for TransactionFilter {
     }
 }
 
+/// The rules that apply to the validator txns of a registered type. Registering rules
+/// allows new validator txn types to be introduced without changing the pool itself.
+pub trait ValidatorTxnRules: Send + Sync {
+    /// Validates a txn before it is put into the pool.
+    fn validate(&self, _txn: &ValidatorTransaction) -> anyhow::Result<()> {
+        Ok(())
+    }
+
+    /// Returns true if the new txn duplicates the pooled txn with the same topic.
+    /// Duplicates are rejected, instead of replacing the pooled txn.
+    fn is_duplicate(
+        &self,
+        pooled_txn: &ValidatorTransaction,
+        new_txn: &ValidatorTransaction,
+    ) -> bool {
+        pooled_txn == new_txn
+    }
+
+    /// The max number of txns of this type that can be pulled for a single block.
+    fn per_block_limit_txn_count(&self) -> Option<u64> {
+        None
+    }
+
+    /// The max number of bytes of txns of this type that can be pulled for a single block.
+    fn per_block_limit_total_bytes(&self) -> Option<u64> {
+        None
+    }
+}
+
+/// The rules of each registered validator txn type, keyed by the txn type name.
+/// Txns of unregistered types are only subject to the pool-wide rules.
+#[derive(Clone, Default)]
+pub struct ValidatorTxnRegistry {
+    rules_by_type: HashMap<&'static str, Arc<dyn ValidatorTxnRules>>,
+}
+
+impl ValidatorTxnRegistry {
+    /// Registers the rules for the given txn type (replacing any existing rules)
+    pub fn register(&mut self, type_name: &'static str, rules: Arc<dyn ValidatorTxnRules>) {
+        self.rules_by_type.insert(type_name, rules);
+    }
+
+    /// Returns the rules for the type of the given txn (if registered)
+    pub fn get_rules(&self, txn: &ValidatorTransaction) -> Option<&Arc<dyn ValidatorTxnRules>> {
+        self.rules_by_type.get(txn.type_name())
+    }
+
+    /// Validates the given txn against the rules of its type
+    pub fn validate(&self, txn: &ValidatorTransaction) -> anyhow::Result<()> {
+        match self.get_rules(txn) {
+            Some(rules) => rules.validate(txn),
+            None => Ok(()),
+        }
+    }
+
+    /// Verifies that the given block txns respect the per-block limits of each type
+    pub fn check_block_limits(&self, txns: &[ValidatorTransaction]) -> anyhow::Result<()> {
+        let mut usage_by_type: HashMap<&'static str, (u64, u64)> = HashMap::new();
+        for txn in txns {
+            let (count, bytes) = usage_by_type.entry(txn.type_name()).or_default();
+            *count += 1;
+            *bytes += txn.size_in_bytes() as u64;
+        }
+
+        for (type_name, (count, bytes)) in usage_by_type {
+            let Some(rules) = self.rules_by_type.get(type_name) else {
+                continue;
+            };
+            if let Some(limit) = rules.per_block_limit_txn_count() {
+                ensure!(
+                    count <= limit,
+                    "Per-block {} count limit exceeded: limit={}, actual={}",
+                    type_name,
+                    limit,
+                    count
+                );
+            }
+            if let Some(limit) = rules.per_block_limit_total_bytes() {
+                ensure!(
+                    bytes <= limit,
+                    "Per-block {} bytes limit exceeded: limit={}, actual={}",
+                    type_name,
+                    limit,
+                    bytes
+                );
+            }
+        }
+        Ok(())
+    }
+}
+
 #[derive(Clone)]
 pub struct VTxnPoolState {
     inner: Arc<Mutex<PoolStateInner>>,
@@ -47,12 +139,50 @@ pub struct VTxnPoolState {
 
 impl Default for VTxnPoolState {
     fn default() -> Self {
-        Self {
-            inner: Arc::new(Mutex::new(PoolStateInner::default())),
-        }
+        Self::new(ValidatorTxnRegistry::default())
     }
 }
 impl VTxnPoolState {
+    pub fn new(registry: ValidatorTxnRegistry) -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(PoolStateInner {
+                registry,
+                ..Default::default()
+            })),
+        }
+    }
+
+    /// Returns the rules registered for each validator txn type
+    pub fn registry(&self) -> ValidatorTxnRegistry {
+        self.inner.lock().registry.clone()
+    }
+
+    /// Append a txn to the pool, after validating it against the rules of its type.
+    /// Fails if the txn is invalid, or if it duplicates the pooled txn of the same topic.
+    pub fn try_put(
+        &self,
+        topic: Topic,
+        txn: Arc<ValidatorTransaction>,
+        pull_notification_tx: Option<aptos_channel::Sender<(), Arc<ValidatorTransaction>>>,
+    ) -> anyhow::Result<TxnGuard> {
+        {
+            let pool = self.inner.lock();
+            if let Some(rules) = pool.registry.get_rules(&txn) {
+                rules.validate(&txn)?;
+                let pooled_item = pool
+                    .seq_nums_by_topic
+                    .get(&topic)
+                    .and_then(|seq_num| pool.txn_queue.get(seq_num));
+                if let Some(pooled_item) = pooled_item {
+                    if rules.is_duplicate(&pooled_item.txn, &txn) {
+                        bail!("Duplicate {} for topic {:?}", txn.type_name(), topic);
+                    }
+                }
+            }
+        }
+        Ok(self.put(topic, txn, pull_notification_tx))
+    }
+
     /// Append a txn to the pool.
     /// Return a txn guard that allows you to later delete the txn from the pool.
     pub fn put(
@@ -112,6 +242,9 @@ struct PoolItem {
 /// `(seq_num=i, topic=T)` exists in `txn_queue` if and only if it exists in `seq_nums_by_topic`.
 #[derive(Default)]
 pub struct PoolStateInner {
+    /// The rules of each registered txn type.
+    registry: ValidatorTxnRegistry,
+
     /// Incremented every time a txn is pushed in. The txn gets the old value as its sequence number.
     next_seq_num: u64,
 
@@ -158,6 +291,7 @@ impl PoolStateInner {
     ) -> Vec<ValidatorTransaction> {
         let mut ret = vec![];
         let mut seq_num_lower_bound = 0;
+        let mut usage_by_type: HashMap<&'static str, (u64, u64)> = HashMap::new();
 
         // Check deadline at the end of every iteration to ensure validator txns get a chance no matter what current proposal delay is.
         while max_items >= 1 && max_bytes >= 1 {
@@ -168,6 +302,7 @@ impl PoolStateInner {
                 .filter(|(_, item)| {
                     item.txn.size_in_bytes() as u64 <= max_bytes
                         && !filter.should_exclude(&item.txn)
+                        && self.within_type_limits(&item.txn, &usage_by_type)
                 })
                 .map(|(seq_num, _)| *seq_num)
                 .next()
@@ -184,6 +319,9 @@ impl PoolStateInner {
                 }
                 max_items -= 1;
                 max_bytes -= txn.size_in_bytes() as u64;
+                let (count, bytes) = usage_by_type.entry(txn.type_name()).or_default();
+                *count += 1;
+                *bytes += txn.size_in_bytes() as u64;
                 seq_num_lower_bound = seq_num + 1;
                 ret.push(txn.as_ref().clone());
 
@@ -197,6 +335,27 @@ impl PoolStateInner {
 
         ret
     }
+
+    /// Returns true if pulling the txn keeps its type within its per-block limits
+    fn within_type_limits(
+        &self,
+        txn: &ValidatorTransaction,
+        usage_by_type: &HashMap<&'static str, (u64, u64)>,
+    ) -> bool {
+        let Some(rules) = self.registry.get_rules(txn) else {
+            return true;
+        };
+        let (count, bytes) = usage_by_type
+            .get(txn.type_name())
+            .copied()
+            .unwrap_or_default();
+        rules
+            .per_block_limit_txn_count()
+            .is_none_or(|limit| count < limit)
+            && rules
+                .per_block_limit_total_bytes()
+                .is_none_or(|limit| bytes + txn.size_in_bytes() as u64 <= limit)
+    }
 }
 
 impl Drop for TxnGuard {
@@ -1,12 +1,12 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{TransactionFilter, VTxnPoolState};
+use crate::{TransactionFilter, VTxnPoolState, ValidatorTxnRegistry, ValidatorTxnRules};
 use aptos_channels::{aptos_channel, message_queues::QueueStyle};
 use aptos_crypto::hash::CryptoHash;
 use aptos_types::{
     dkg::DKGTranscript,
-    jwks::{dummy_issuer, QuorumCertifiedUpdate},
+    jwks::{dummy_issuer, issuer_from_str, QuorumCertifiedUpdate},
     validator_txn::{Topic, ValidatorTransaction},
 };
 use futures_util::StreamExt;
@@ -187,3 +187,111 @@ fn pull_filter_should_be_respected() {
     );
     assert_eq!(vec![txn_1], pulled);
 }
+
+struct TestRules {
+    max_payload_bytes: usize,
+    per_block_limit_txn_count: Option<u64>,
+}
+
+impl ValidatorTxnRules for TestRules {
+    fn validate(&self, txn: &ValidatorTransaction) -> anyhow::Result<()> {
+        anyhow::ensure!(
+            txn.size_in_bytes() <= self.max_payload_bytes,
+            "txn too large"
+        );
+        Ok(())
+    }
+
+    fn per_block_limit_txn_count(&self) -> Option<u64> {
+        self.per_block_limit_txn_count
+    }
+}
+
+fn create_pool_with_jwk_rules(rules: TestRules) -> VTxnPoolState {
+    let txn = ValidatorTransaction::ObservedJWKUpdate(QuorumCertifiedUpdate::dummy());
+    let mut registry = ValidatorTxnRegistry::default();
+    registry.register(txn.type_name(), Arc::new(rules));
+    VTxnPoolState::new(registry)
+}
+
+#[test]
+fn registered_rules_should_be_enforced_on_put() {
+    let pool = create_pool_with_jwk_rules(TestRules {
+        max_payload_bytes: 1024,
+        per_block_limit_txn_count: None,
+    });
+    let txn_0 = ValidatorTransaction::ObservedJWKUpdate(QuorumCertifiedUpdate::dummy());
+    let topic = Topic::JWK_CONSENSUS(dummy_issuer());
+
+    // The first txn is valid and accepted.
+    let _guard_0 = pool
+        .try_put(topic.clone(), Arc::new(txn_0.clone()), None)
+        .unwrap();
+
+    // A duplicate of the pooled txn is rejected.
+    assert!(pool
+        .try_put(topic.clone(), Arc::new(txn_0.clone()), None)
+        .is_err());
+
+    // Unregistered types are only subject to the pool-wide rules.
+    let txn_1 = ValidatorTransaction::dummy(vec![0xFF; 2048]);
+    let _guard_1 = pool
+        .try_put(Topic::DKG, Arc::new(txn_1.clone()), None)
+        .unwrap();
+
+    let pulled = pool.pull(
+        Instant::now().add(Duration::from_secs(10)),
+        99,
+        4096,
+        TransactionFilter::default(),
+    );
+    assert_eq!(vec![txn_0, txn_1], pulled);
+}
+
+#[test]
+fn registered_rules_should_fail_invalid_txns() {
+    let pool = create_pool_with_jwk_rules(TestRules {
+        max_payload_bytes: 1,
+        per_block_limit_txn_count: None,
+    });
+    let txn = ValidatorTransaction::ObservedJWKUpdate(QuorumCertifiedUpdate::dummy());
+    assert!(pool
+        .try_put(Topic::JWK_CONSENSUS(dummy_issuer()), Arc::new(txn), None)
+        .is_err());
+}
+
+#[test]
+fn per_type_block_limits_should_be_respected() {
+    let pool = create_pool_with_jwk_rules(TestRules {
+        max_payload_bytes: 1024,
+        per_block_limit_txn_count: Some(1),
+    });
+    let txn_0 = ValidatorTransaction::ObservedJWKUpdate(QuorumCertifiedUpdate::dummy());
+    let txn_1 = ValidatorTransaction::ObservedJWKUpdate(QuorumCertifiedUpdate::dummy());
+    let txn_2 = ValidatorTransaction::dummy(vec![0xFF; 100]);
+    let _guard_0 = pool.put(
+        Topic::JWK_CONSENSUS(dummy_issuer()),
+        Arc::new(txn_0.clone()),
+        None,
+    );
+    let _guard_1 = pool.put(
+        Topic::JWK_CONSENSUS(issuer_from_str("https:://another.issuer")),
+        Arc::new(txn_1.clone()),
+        None,
+    );
+    let _guard_2 = pool.put(Topic::DKG, Arc::new(txn_2.clone()), None);
+
+    // Only a single JWK update can be pulled per block.
+    let pulled = pool.pull(
+        Instant::now().add(Duration::from_secs(10)),
+        99,
+        4096,
+        TransactionFilter::default(),
+    );
+    assert_eq!(vec![txn_0.clone(), txn_2.clone()], pulled);
+
+    // The block limits are also enforced on the block txns.
+    let registry = pool.registry();
+    assert!(registry.check_block_limits(&[txn_0.clone(), txn_2]).is_ok());
+    assert!(registry.check_block_limits(&[txn_0, txn_1]).is_err());
+}
@@ -15,7 +15,7 @@ use crate::{
 };
 use aptos_config::config::{ReliableBroadcastConfig, SafetyRulesConfig};
 use aptos_event_notifications::{
-    DbBackedOnChainConfig, EventNotificationListener, ReconfigNotificationListener,
+    EventNotificationListener, InMemoryOnChainConfig, ReconfigNotificationListener,
 };
 use aptos_network::application::interface::{NetworkClient, NetworkServiceEvents};
 use aptos_validator_transaction_pool::VTxnPoolState;
@@ -28,7 +28,7 @@ pub fn start_dkg_runtime(
     safety_rules_config: &SafetyRulesConfig,
     network_client: NetworkClient<DKGMessage>,
     network_service_events: NetworkServiceEvents<DKGMessage>,
-    reconfig_events: ReconfigNotificationListener<DbBackedOnChainConfig>,
+    reconfig_events: ReconfigNotificationListener<InMemoryOnChainConfig>,
     dkg_start_events: EventNotificationListener,
     vtxn_pool: VTxnPoolState,
     rb_config: ReliableBroadcastConfig,
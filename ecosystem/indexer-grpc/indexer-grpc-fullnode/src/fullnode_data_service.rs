@@ -16,13 +16,17 @@ use aptos_protos::{
     indexer::v1::FullnodeInfo,
     internal::fullnode::v1::{
         fullnode_data_server::FullnodeData, stream_status::StatusType,
-        transactions_from_node_response, GetTransactionsFromNodeRequest, PingFullnodeRequest,
+        transactions_from_node_response, AccountBalance, GetAccountBalancesRequest,
+        GetAccountBalancesResponse, GetTransactionsFromNodeRequest, PingFullnodeRequest,
         PingFullnodeResponse, StreamStatus, TransactionsFromNodeResponse,
     },
 };
+use aptos_types::{account_address::AccountAddress, indexer::indexer_db_reader::BalanceAsset};
 use futures::Stream;
+use move_core_types::language_storage::TypeTag;
 use std::{
     pin::Pin,
+    str::FromStr,
     time::{Duration, SystemTime, UNIX_EPOCH},
 };
 use tokio::sync::mpsc;
@@ -41,6 +45,7 @@ pub const DEFAULT_NUM_RETRIES: usize = 3;
 pub const RETRY_TIME_MILLIS: u64 = 100;
 const TRANSACTION_CHANNEL_SIZE: usize = 35;
 const DEFAULT_EMIT_SIZE: usize = 1000;
+const DEFAULT_BALANCES_LIMIT: u64 = 100;
 const SERVICE_TYPE: &str = "indexer_fullnode";
 
 #[tonic::async_trait]
@@ -213,6 +218,80 @@ impl FullnodeData for FullnodeDataService {
         let response = PingFullnodeResponse { info: Some(info) };
         Ok(Response::new(response))
     }
+
+    async fn get_account_balances(
+        &self,
+        request: Request<GetAccountBalancesRequest>,
+    ) -> Result<Response<GetAccountBalancesResponse>, Status> {
+        let request = request.into_inner();
+        let indexer_reader = self
+            .service_context
+            .context
+            .indexer_reader
+            .as_ref()
+            .filter(|r| r.is_internal_indexer_enabled())
+            .ok_or_else(|| Status::unavailable("Internal indexer is not enabled"))?;
+
+        let address = AccountAddress::from_str(&request.address)
+            .map_err(|e| Status::invalid_argument(format!("Invalid address: {e}")))?;
+        let start_asset = request
+            .start_asset_type
+            .as_deref()
+            .map(parse_balance_asset)
+            .transpose()?;
+        let limit = request.limit.unwrap_or(DEFAULT_BALANCES_LIMIT);
+
+        let indexed_version = indexer_reader
+            .get_latest_internal_indexer_ledger_version()
+            .map_err(|e| Status::internal(format!("{e}")))?
+            .ok_or_else(|| Status::unavailable("Internal indexer has not indexed any version"))?;
+        let ledger_version = match request.ledger_version {
+            Some(version) if version > indexed_version => {
+                return Err(Status::out_of_range(format!(
+                    "Ledger version {version} is ahead of the indexed version {indexed_version}"
+                )));
+            },
+            Some(version) => version,
+            None => indexed_version,
+        };
+
+        let (balances, next_asset) = indexer_reader
+            .get_account_balances(address, start_asset.as_ref(), limit, ledger_version)
+            .map_err(|e| Status::internal(format!("{e}")))?;
+        let response = GetAccountBalancesResponse {
+            balances: balances
+                .into_iter()
+                .map(|(asset, amount)| AccountBalance {
+                    asset_type: balance_asset_type(&asset),
+                    amount,
+                })
+                .collect(),
+            ledger_version,
+            next_asset_type: next_asset.as_ref().map(balance_asset_type),
+        };
+        Ok(Response::new(response))
+    }
+}
+
+/// Parses an asset type as returned in `AccountBalance::asset_type`: a coin type if it is a
+/// Move type, e.g. `0x1::aptos_coin::AptosCoin`, otherwise the fungible asset metadata address.
+fn parse_balance_asset(asset_type: &str) -> Result<BalanceAsset, Status> {
+    if asset_type.contains("::") {
+        TypeTag::from_str(asset_type)
+            .map(BalanceAsset::Coin)
+            .map_err(|e| Status::invalid_argument(format!("Invalid coin type: {e}")))
+    } else {
+        AccountAddress::from_str(asset_type)
+            .map(BalanceAsset::FungibleAsset)
+            .map_err(|e| Status::invalid_argument(format!("Invalid metadata address: {e}")))
+    }
+}
+
+fn balance_asset_type(asset: &BalanceAsset) -> String {
+    match asset {
+        BalanceAsset::Coin(coin_type) => coin_type.to_canonical_string(),
+        BalanceAsset::FungibleAsset(metadata) => metadata.to_hex_literal(),
+    }
 }
 
 pub fn get_status(
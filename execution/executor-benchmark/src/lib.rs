@@ -12,6 +12,7 @@ pub mod measurements;
 mod metrics;
 pub mod native;
 pub mod pipeline;
+pub mod state_backend;
 pub mod transaction_committer;
 pub mod transaction_executor;
 pub mod transaction_generator;
@@ -145,7 +146,7 @@ where
     config.storage.dir = checkpoint_dir.as_ref().to_path_buf();
     config.storage.storage_pruner_config = pruner_config;
     config.storage.rocksdb_configs.enable_storage_sharding = enable_storage_sharding;
-    let db = init_db(&config);
+    let db = pipeline_config.state_backend.wrap(init_db(&config));
     let root_account = TransactionGenerator::read_root_account(genesis_key, &db);
     let root_account = Arc::new(root_account);
 
@@ -676,6 +677,7 @@ mod tests {
             },
         },
         pipeline::PipelineConfig,
+        state_backend::StateBackend,
         transaction_executor::BENCHMARKS_BLOCK_EXECUTOR_ONCHAIN_CONFIG,
         transaction_generator::TransactionGenerator,
         BenchmarkWorkload,
@@ -705,6 +707,7 @@ mod tests {
     use std::{
         collections::{BTreeMap, HashMap},
         fs,
+        time::Duration,
     };
 
     #[test]
@@ -931,6 +934,20 @@ mod tests {
         verify_sequence_numbers: bool,
     ) where
         E: VMBlockExecutor + 'static,
+    {
+        test_generic_benchmark_with_backend::<E>(
+            transaction_type,
+            verify_sequence_numbers,
+            StateBackend::AptosDb,
+        );
+    }
+
+    fn test_generic_benchmark_with_backend<E>(
+        transaction_type: Option<TransactionTypeArg>,
+        verify_sequence_numbers: bool,
+        state_backend: StateBackend,
+    ) where
+        E: VMBlockExecutor + 'static,
     {
         aptos_logger::Logger::new().init();
 
@@ -983,7 +1000,10 @@ mod tests {
             verify_sequence_numbers,
             NO_OP_STORAGE_PRUNER_CONFIG,
             false,
-            PipelineConfig::default(),
+            PipelineConfig {
+                state_backend,
+                ..Default::default()
+            },
             features,
             false,
         );
@@ -994,6 +1014,27 @@ mod tests {
         test_generic_benchmark::<AptosVMBlockExecutor>(None, true);
     }
 
+    #[test]
+    fn test_benchmark_in_memory_backend() {
+        test_generic_benchmark_with_backend::<AptosVMBlockExecutor>(
+            None,
+            true,
+            StateBackend::InMemory,
+        );
+    }
+
+    #[test]
+    fn test_benchmark_latency_injected_backend() {
+        test_generic_benchmark_with_backend::<AptosVMBlockExecutor>(
+            None,
+            true,
+            StateBackend::LatencyInjected {
+                read_latency: Duration::from_micros(10),
+                write_latency: Duration::from_millis(1),
+            },
+        );
+    }
+
     #[test]
     fn test_publish_transaction() {
         AptosVM::set_num_shards_once(1);
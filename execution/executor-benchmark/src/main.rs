@@ -25,6 +25,7 @@ use aptos_executor_benchmark::{
         },
     },
     pipeline::PipelineConfig,
+    state_backend::{print_backend_comparison_table, StateBackend},
     BenchmarkWorkload,
 };
 use aptos_executor_service::remote_executor_client;
@@ -44,7 +45,7 @@ use once_cell::sync::Lazy;
 use std::{
     net::SocketAddr,
     path::PathBuf,
-    time::{SystemTime, UNIX_EPOCH},
+    time::{Duration, SystemTime, UNIX_EPOCH},
 };
 
 #[cfg(unix)]
@@ -166,6 +167,7 @@ impl PipelineOpt {
             partitioner_config: self.sharding_opt.partitioner_config(),
             num_sig_verify_threads: self.num_sig_verify_threads,
             print_transactions: false,
+            state_backend: StateBackend::default(),
         }
     }
 }
@@ -230,6 +232,48 @@ impl ShardingOpt {
     }
 }
 
+#[derive(Parser, Debug, ValueEnum, Clone, Copy, PartialEq, Eq)]
+enum StateBackendOpt {
+    /// State reads go to AptosDB
+    AptosDb,
+    /// State reads are served from memory (after the first read of each key)
+    InMemory,
+    /// State reads and commits go to AptosDB, with the injected latency added
+    LatencyInjected,
+}
+
+#[derive(Parser, Debug)]
+struct StateBackendArgs {
+    /// State backends to run the workload against, one after another, each starting
+    /// from the same DB checkpoint. A comparison table is printed at the end.
+    #[clap(long, value_enum, num_args = 1.., default_value = "aptos-db", ignore_case = true)]
+    state_backends: Vec<StateBackendOpt>,
+
+    /// Latency added to every state read, for the latency-injected backend
+    #[clap(long, default_value_t = 100)]
+    injected_read_latency_us: u64,
+
+    /// Latency added to every pre-commit and commit, for the latency-injected backend
+    #[clap(long, default_value_t = 10_000)]
+    injected_write_latency_us: u64,
+}
+
+impl StateBackendArgs {
+    fn state_backends(&self) -> Vec<StateBackend> {
+        self.state_backends
+            .iter()
+            .map(|backend| match backend {
+                StateBackendOpt::AptosDb => StateBackend::AptosDb,
+                StateBackendOpt::InMemory => StateBackend::InMemory,
+                StateBackendOpt::LatencyInjected => StateBackend::LatencyInjected {
+                    read_latency: Duration::from_micros(self.injected_read_latency_us),
+                    write_latency: Duration::from_micros(self.injected_write_latency_us),
+                },
+            })
+            .collect()
+    }
+}
+
 #[derive(Parser, Debug)]
 struct ProfilerOpt {
     #[clap(long)]
@@ -402,6 +446,9 @@ enum Command {
         #[clap(long, value_parser)]
         checkpoint_dir: PathBuf,
 
+        #[clap(flatten)]
+        state_backend_args: StateBackendArgs,
+
         #[clap(
             long,
             num_args=1..,
@@ -488,6 +535,7 @@ where
             use_sender_account_pool,
             data_dir,
             checkpoint_dir,
+            state_backend_args,
             enable_feature,
             disable_feature,
         } => {
@@ -496,23 +544,25 @@ where
             //     disable_feature,
             // );
 
-            let workload = if transaction_type.is_empty() {
-                BenchmarkWorkload::Transfer {
-                    connected_tx_grps: opt.connected_tx_grps,
-                    shuffle_connected_txns: opt.shuffle_connected_txns,
-                    hotspot_probability: opt.hotspot_probability,
+            let workload = || {
+                if transaction_type.is_empty() {
+                    BenchmarkWorkload::Transfer {
+                        connected_tx_grps: opt.connected_tx_grps,
+                        shuffle_connected_txns: opt.shuffle_connected_txns,
+                        hotspot_probability: opt.hotspot_probability,
+                    }
+                } else {
+                    let mix_per_phase = TransactionTypeArg::args_to_transaction_mix_per_phase(
+                        &transaction_type,
+                        &transaction_weights,
+                        &[],
+                        module_working_set_size,
+                        use_sender_account_pool,
+                        WorkflowProgress::MoveByPhases,
+                    );
+                    assert!(mix_per_phase.len() == 1);
+                    BenchmarkWorkload::TransactionMix(mix_per_phase[0].clone())
                 }
-            } else {
-                let mix_per_phase = TransactionTypeArg::args_to_transaction_mix_per_phase(
-                    &transaction_type,
-                    &transaction_weights,
-                    &[],
-                    module_working_set_size,
-                    use_sender_account_pool,
-                    WorkflowProgress::MoveByPhases,
-                );
-                assert!(mix_per_phase.len() == 1);
-                BenchmarkWorkload::TransactionMix(mix_per_phase[0].clone())
             };
 
             if let Some(hotspot_probability) = opt.hotspot_probability {
@@ -523,22 +573,34 @@ where
                 }
             }
 
-            aptos_executor_benchmark::run_benchmark::<E>(
-                opt.block_size,
-                blocks,
-                workload,
-                opt.transactions_per_sender,
-                main_signer_accounts,
-                additional_dst_pool_accounts,
-                data_dir,
-                checkpoint_dir,
-                opt.verify_sequence_numbers,
-                opt.pruner_opt.pruner_config(),
-                opt.enable_storage_sharding,
-                opt.pipeline_opt.pipeline_config(),
-                get_init_features(enable_feature, disable_feature),
-                opt.use_keyless_accounts,
-            );
+            let state_backends = state_backend_args.state_backends();
+            let mut results = vec![];
+            for state_backend in state_backends {
+                println!("Running the workload against the {} backend", state_backend);
+                let result = aptos_executor_benchmark::run_benchmark::<E>(
+                    opt.block_size,
+                    blocks,
+                    workload(),
+                    opt.transactions_per_sender,
+                    main_signer_accounts,
+                    additional_dst_pool_accounts,
+                    &data_dir,
+                    &checkpoint_dir,
+                    opt.verify_sequence_numbers,
+                    opt.pruner_opt.pruner_config(),
+                    opt.enable_storage_sharding,
+                    PipelineConfig {
+                        state_backend,
+                        ..opt.pipeline_opt.pipeline_config()
+                    },
+                    get_init_features(enable_feature.clone(), disable_feature.clone()),
+                    opt.use_keyless_accounts,
+                );
+                results.push((state_backend, result));
+            }
+            if results.len() > 1 {
+                print_backend_comparison_table(&results);
+            }
         },
         Command::AddAccounts {
             data_dir,
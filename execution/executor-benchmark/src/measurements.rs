@@ -240,6 +240,18 @@ impl OverallMeasurement {
         self.num_txns as f64 / self.elapsed
     }
 
+    pub fn get_execution_secs(&self) -> f64 {
+        self.delta_execution.execution_total_time
+    }
+
+    pub fn get_ledger_update_secs(&self) -> f64 {
+        self.delta_execution.ledger_update_total
+    }
+
+    pub fn get_commit_secs(&self) -> f64 {
+        self.delta_execution.commit_total_time
+    }
+
     pub fn get_gps(&self) -> f64 {
         self.delta_gas.gas / self.elapsed
     }
@@ -6,6 +6,7 @@ use crate::{
     ledger_update_stage::{CommitProcessing, LedgerUpdateStage},
     measurements::{EventMeasurements, OverallMeasuring},
     metrics::NUM_TXNS,
+    state_backend::StateBackend,
     OverallMeasurement, TransactionCommitter, TransactionExecutor,
 };
 use aptos_block_partitioner::v2::config::PartitionerV2Config;
@@ -51,6 +52,9 @@ pub struct PipelineConfig {
     pub num_sig_verify_threads: usize,
 
     pub print_transactions: bool,
+
+    /// The state store to execute against
+    pub state_backend: StateBackend,
 }
 
 pub struct Pipeline<V> {
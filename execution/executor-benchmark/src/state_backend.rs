@@ -0,0 +1,186 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::SingleRunResults;
+use aptos_storage_interface::{
+    chunk_to_commit::ChunkToCommit, DbReader, DbReaderWriter, DbWriter, Result,
+};
+use aptos_types::{
+    ledger_info::LedgerInfoWithSignatures,
+    state_store::{state_key::StateKey, state_value::StateValue},
+    transaction::Version,
+};
+use dashmap::DashMap;
+use std::{
+    fmt::{Display, Formatter},
+    sync::Arc,
+    time::Duration,
+};
+
+/// The state store the benchmark executes against. Running the same workload against
+/// different backends allows to isolate storage from execution bottlenecks.
+#[derive(Clone, Copy, Debug, Default)]
+pub enum StateBackend {
+    /// The real AptosDB, i.e., state reads go to RocksDB (behind the usual caches).
+    #[default]
+    AptosDb,
+    /// State reads are served from memory, filled upon first read of each key and updated
+    /// on every pre-commit. Commits still go to AptosDB (use `skip_commit` to skip those).
+    InMemory,
+    /// Same as `AptosDb`, but every state read and commit is delayed by the given latency,
+    /// to see how sensitive the pipeline is to storage latency.
+    LatencyInjected {
+        read_latency: Duration,
+        write_latency: Duration,
+    },
+}
+
+impl StateBackend {
+    pub fn wrap(self, db: DbReaderWriter) -> DbReaderWriter {
+        match self {
+            StateBackend::AptosDb => db,
+            StateBackend::InMemory => DbReaderWriter::new(BackendDb {
+                inner: db,
+                state_cache: Some(DashMap::new()),
+                read_latency: Duration::ZERO,
+                write_latency: Duration::ZERO,
+            }),
+            StateBackend::LatencyInjected {
+                read_latency,
+                write_latency,
+            } => DbReaderWriter::new(BackendDb {
+                inner: db,
+                state_cache: None,
+                read_latency,
+                write_latency,
+            }),
+        }
+    }
+}
+
+impl Display for StateBackend {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StateBackend::AptosDb => write!(f, "AptosDB"),
+            StateBackend::InMemory => write!(f, "InMemory"),
+            StateBackend::LatencyInjected {
+                read_latency,
+                write_latency,
+            } => write!(
+                f,
+                "Latency(r={}us,w={}us)",
+                read_latency.as_micros(),
+                write_latency.as_micros()
+            ),
+        }
+    }
+}
+
+/// Wraps the real DB, serving state reads from memory and/or injecting latency.
+/// Everything not related to state reads or commits is passed through.
+struct BackendDb {
+    inner: DbReaderWriter,
+    /// The latest value (and the version it was written at) of every key read or written so far.
+    /// It is safe to serve reads at older versions from it, as the executor only reads from the
+    /// DB what hasn't changed since the (persisted) version it reads at.
+    state_cache: Option<DashMap<StateKey, Option<(Version, StateValue)>>>,
+    read_latency: Duration,
+    write_latency: Duration,
+}
+
+impl BackendDb {
+    fn inject_latency(latency: Duration) {
+        if !latency.is_zero() {
+            std::thread::sleep(latency);
+        }
+    }
+
+    fn update_state_cache(&self, chunk: &ChunkToCommit) {
+        if let Some(state_cache) = &self.state_cache {
+            for (idx, output) in chunk.transaction_outputs.iter().enumerate() {
+                let version = chunk.first_version + idx as Version;
+                for (key, value) in output.write_set().state_update_refs() {
+                    state_cache.insert(key.clone(), value.map(|value| (version, value.clone())));
+                }
+            }
+        }
+    }
+}
+
+impl DbReader for BackendDb {
+    fn get_read_delegatee(&self) -> &dyn DbReader {
+        self.inner.reader.as_ref()
+    }
+
+    fn get_state_value_by_version(
+        &self,
+        state_key: &StateKey,
+        version: Version,
+    ) -> Result<Option<StateValue>> {
+        Ok(self
+            .get_state_value_with_version_by_version(state_key, version)?
+            .map(|(_, value)| value))
+    }
+
+    fn get_state_value_with_version_by_version(
+        &self,
+        state_key: &StateKey,
+        version: Version,
+    ) -> Result<Option<(Version, StateValue)>> {
+        if let Some(state_cache) = &self.state_cache {
+            if let Some(entry) = state_cache.get(state_key) {
+                return Ok(entry.clone());
+            }
+        }
+
+        Self::inject_latency(self.read_latency);
+        let value = self
+            .inner
+            .reader
+            .get_state_value_with_version_by_version(state_key, version)?;
+        if let Some(state_cache) = &self.state_cache {
+            state_cache.insert(state_key.clone(), value.clone());
+        }
+        Ok(value)
+    }
+}
+
+impl DbWriter for BackendDb {
+    fn pre_commit_ledger(&self, chunk: ChunkToCommit, sync_commit: bool) -> Result<()> {
+        Self::inject_latency(self.write_latency);
+        self.update_state_cache(&chunk);
+        self.inner.writer.pre_commit_ledger(chunk, sync_commit)
+    }
+
+    fn commit_ledger(
+        &self,
+        version: Version,
+        ledger_info_with_sigs: Option<&LedgerInfoWithSignatures>,
+        chunk_opt: Option<ChunkToCommit>,
+    ) -> Result<()> {
+        Self::inject_latency(self.write_latency);
+        self.inner
+            .writer
+            .commit_ledger(version, ledger_info_with_sigs, chunk_opt)
+    }
+}
+
+/// Prints the results of running the same workload against different backends side by side.
+pub fn print_backend_comparison_table(results: &[(StateBackend, SingleRunResults)]) {
+    println!("Backend comparison:");
+    println!(
+        "{: <32}{: >12}{: >12}{: >12}{: >12}",
+        "backend", "TPS", "exe s", "ledger s", "commit s"
+    );
+    for (backend, result) in results {
+        let measurements = &result.measurements;
+        println!(
+            "{: <32}{: >12.2}{: >12.2}{: >12.2}{: >12.2}",
+            backend.to_string(),
+            measurements.get_tps(),
+            measurements.get_execution_secs(),
+            measurements.get_ledger_update_secs(),
+            measurements.get_commit_secs(),
+        );
+    }
+}
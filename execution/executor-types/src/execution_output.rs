@@ -9,8 +9,9 @@ use crate::{
     transactions_with_output::{TransactionsToKeep, TransactionsWithOutput},
 };
 use aptos_drop_helper::DropHelper;
-use aptos_storage_interface::state_store::{
-    state::LedgerState, state_view::cached_state_view::ShardedStateCache,
+use aptos_storage_interface::{
+    block_execution_stats::BlockExecutionStats,
+    state_store::{state::LedgerState, state_view::cached_state_view::ShardedStateCache},
 };
 use aptos_types::{
     contract_event::ContractEvent,
@@ -39,6 +40,7 @@ impl ExecutionOutput {
         result_state: LedgerState,
         state_reads: ShardedStateCache,
         block_end_info: Option<BlockEndInfo>,
+        block_execution_stats: Option<BlockExecutionStats>,
         next_epoch_state: Option<EpochState>,
         subscribable_events: Planned<Vec<ContractEvent>>,
     ) -> Self {
@@ -63,6 +65,7 @@ impl ExecutionOutput {
             result_state,
             state_reads,
             block_end_info,
+            block_execution_stats,
             next_epoch_state,
             subscribable_events,
         })
@@ -79,6 +82,7 @@ impl ExecutionOutput {
             state_reads: ShardedStateCache::new_empty(state.version()),
             result_state: state,
             block_end_info: None,
+            block_execution_stats: None,
             next_epoch_state: None,
             subscribable_events: Planned::ready(vec![]),
         })
@@ -97,6 +101,7 @@ impl ExecutionOutput {
             result_state: LedgerState::new_empty(),
             state_reads: ShardedStateCache::new_empty(None),
             block_end_info: None,
+            block_execution_stats: None,
             next_epoch_state: None,
             subscribable_events: Planned::ready(vec![]),
         })
@@ -117,6 +122,7 @@ impl ExecutionOutput {
             result_state: self.result_state.clone(),
             state_reads: ShardedStateCache::new_empty(self.next_version().checked_sub(1)),
             block_end_info: None,
+            block_execution_stats: None,
             next_epoch_state: self.next_epoch_state.clone(),
             subscribable_events: Planned::ready(vec![]),
         })
@@ -161,6 +167,8 @@ pub struct Inner {
 
     /// Optional StateCheckpoint payload
     pub block_end_info: Option<BlockEndInfo>,
+    /// Only present if the input is a block.
+    pub block_execution_stats: Option<BlockExecutionStats>,
     /// Optional EpochState payload.
     /// Only present if the block is the last block of an epoch, and is parsed output of the
     /// state cache.
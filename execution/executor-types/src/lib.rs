@@ -40,6 +40,7 @@ pub mod planned;
 pub mod state_checkpoint_output;
 pub mod state_compute_result;
 pub mod transactions_with_output;
+pub mod versioned_output;
 
 pub trait ChunkExecutorTrait: Send + Sync {
     /// Verifies the transactions based on the provided proofs and ledger info. If the transactions
@@ -168,6 +169,16 @@ pub trait BlockExecutorTrait: Send + Sync {
 
     fn commit_ledger(&self, ledger_info_with_sigs: LedgerInfoWithSignatures) -> ExecutorResult<()>;
 
+    /// Like `commit_ledger`, but the ledger info can arrive before its block is executed. In that
+    /// case it's buffered and committed by `pre_commit_block` once the block is pre-committed.
+    /// This is best effort: buffered ledger infos are dropped on `reset`, when the buffer is full
+    /// or when their blocks are not executed, so callers still `commit_ledger` after the
+    /// pre-commit, which is a no-op if the ledger info has been committed already.
+    fn commit_ledger_ahead(
+        &self,
+        ledger_info_with_sigs: LedgerInfoWithSignatures,
+    ) -> ExecutorResult<()>;
+
     /// Finishes the block executor by releasing memory held by inner data structures(SMT).
     fn finish(&self);
 }
@@ -56,6 +56,11 @@ impl<T> Planned<T> {
             self.value.get().expect("Must have been set.")
         }
     }
+
+    pub fn into_inner(self, name_for_timer: Option<&str>) -> T {
+        self.get(name_for_timer);
+        self.value.into_inner().expect("Must have been set.")
+    }
 }
 
 impl<T> Deref for Planned<T> {
@@ -14,7 +14,7 @@ use aptos_types::{
     contract_event::ContractEvent,
     epoch_state::EpochState,
     proof::{accumulator::InMemoryTransactionAccumulator, AccumulatorExtensionProof},
-    transaction::{Transaction, TransactionStatus, Version},
+    transaction::{SignedTransaction, Transaction, TransactionStatus, Version},
 };
 use std::sync::Arc;
 
@@ -119,6 +119,20 @@ impl StateComputeResult {
         &self.execution_output.to_commit.transactions
     }
 
+    /// The user transactions of the block that were not executed, e.g., because the block gas
+    /// limit was reached, in order. None are returned for a block ending the epoch, as the rest of
+    /// its transactions belong to the next epoch.
+    pub fn user_txns_to_retry(&self) -> impl Iterator<Item = &SignedTransaction> {
+        let to_retry = if self.has_reconfiguration() {
+            &[][..]
+        } else {
+            &self.execution_output.to_retry.transactions[..]
+        };
+        to_retry
+            .iter()
+            .filter_map(Transaction::try_as_signed_user_txn)
+    }
+
     pub fn transaction_info_hashes(&self) -> &Vec<HashValue> {
         &self.ledger_update_output.transaction_info_hashes
     }
@@ -167,6 +181,7 @@ impl StateComputeResult {
             state_update_refs: self.execution_output.to_commit.state_update_refs(),
             state_reads: &self.execution_output.state_reads,
             is_reconfig: self.execution_output.next_epoch_state.is_some(),
+            block_execution_stats: self.execution_output.block_execution_stats.as_ref(),
         }
     }
 }
@@ -0,0 +1,404 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Versioned, BCS-serializable forms of the executor outputs, to exchange them between processes
+//! that may run different binary versions (e.g., the sharded/remote executor and out-of-process
+//! committers).
+//!
+//! The in-memory outputs hold derived structures (sparse Merkle trees, state caches, pending
+//! futures) that can't be serialized, so the serialized forms only carry what can't be derived
+//! again by the receiver from its own parent state.
+//!
+//! BCS encodes the index of an enum variant, so the enums below are upgrade-safe as long as
+//! variants are only ever appended: a newer binary can still read the older variants, and an
+//! older binary fails cleanly on the newer ones. Never reorder or modify existing variants.
+
+use crate::{
+    execution_output::ExecutionOutput,
+    planned::Planned,
+    should_forward_to_subscription_service,
+    state_checkpoint_output::StateCheckpointOutput,
+    transactions_with_output::{TransactionsToKeep, TransactionsWithOutput},
+    LedgerUpdateOutput,
+};
+use anyhow::{ensure, Result};
+use aptos_crypto::{hash::CryptoHash, HashValue};
+use aptos_storage_interface::state_store::{
+    state::LedgerState, state_view::cached_state_view::CachedStateView,
+};
+use aptos_types::{
+    epoch_state::EpochState,
+    proof::accumulator::InMemoryTransactionAccumulator,
+    transaction::{
+        block_epilogue::BlockEndInfo, PersistedAuxiliaryInfo, Transaction, TransactionInfo,
+        TransactionOutput, TransactionStatus, Version,
+    },
+};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub enum VersionedExecutionOutput {
+    V0(ExecutionOutputV0),
+}
+
+/// The serializable part of an `ExecutionOutput`. The result state and the state reads are
+/// derived again by the receiver, by applying the write sets of `to_commit` to its parent state.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct ExecutionOutputV0 {
+    pub is_block: bool,
+    pub first_version: Version,
+    pub statuses_for_input_txns: Vec<TransactionStatus>,
+    pub to_commit: TransactionsWithOutputV0,
+    pub to_commit_is_reconfig: bool,
+    pub to_discard: TransactionsWithOutputV0,
+    pub to_retry: TransactionsWithOutputV0,
+    pub block_end_info: Option<BlockEndInfo>,
+    pub next_epoch_state: Option<EpochState>,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub struct TransactionsWithOutputV0 {
+    pub transactions: Vec<Transaction>,
+    pub transaction_outputs: Vec<TransactionOutput>,
+    pub persisted_auxiliary_infos: Vec<PersistedAuxiliaryInfo>,
+}
+
+impl From<&TransactionsWithOutput> for TransactionsWithOutputV0 {
+    fn from(txns_with_output: &TransactionsWithOutput) -> Self {
+        Self {
+            transactions: txns_with_output.transactions.clone(),
+            transaction_outputs: txns_with_output.transaction_outputs.clone(),
+            persisted_auxiliary_infos: txns_with_output.persisted_auxiliary_infos.clone(),
+        }
+    }
+}
+
+impl TryFrom<TransactionsWithOutputV0> for TransactionsWithOutput {
+    type Error = anyhow::Error;
+
+    fn try_from(txns_with_output: TransactionsWithOutputV0) -> Result<Self> {
+        let TransactionsWithOutputV0 {
+            transactions,
+            transaction_outputs,
+            persisted_auxiliary_infos,
+        } = txns_with_output;
+        ensure!(
+            transactions.len() == transaction_outputs.len()
+                && transactions.len() == persisted_auxiliary_infos.len(),
+            "Lengths don't match. transactions: {}, outputs: {}, auxiliary infos: {}",
+            transactions.len(),
+            transaction_outputs.len(),
+            persisted_auxiliary_infos.len(),
+        );
+        Ok(Self::new(
+            transactions,
+            transaction_outputs,
+            persisted_auxiliary_infos,
+        ))
+    }
+}
+
+impl From<&ExecutionOutput> for VersionedExecutionOutput {
+    fn from(output: &ExecutionOutput) -> Self {
+        Self::V0(ExecutionOutputV0 {
+            is_block: output.is_block,
+            first_version: output.first_version,
+            statuses_for_input_txns: output.statuses_for_input_txns.clone(),
+            to_commit: (&*output.to_commit).into(),
+            to_commit_is_reconfig: output.to_commit.is_reconfig(),
+            to_discard: (&output.to_discard).into(),
+            to_retry: (&output.to_retry).into(),
+            block_end_info: output.block_end_info.clone(),
+            next_epoch_state: output.next_epoch_state.clone(),
+        })
+    }
+}
+
+impl VersionedExecutionOutput {
+    /// Rebuilds the `ExecutionOutput`, deriving the result state and the state reads by applying
+    /// the write sets of the transactions to commit on top of `parent_state`, reading the old
+    /// values through `base_state_view`, which must be created on top of `parent_state`.
+    pub fn into_execution_output(
+        self,
+        parent_state: &LedgerState,
+        base_state_view: CachedStateView,
+    ) -> Result<ExecutionOutput> {
+        match self {
+            Self::V0(output) => {
+                ensure!(
+                    output.first_version == parent_state.next_version(),
+                    "First version {} doesn't follow the parent state, whose next version is {}.",
+                    output.first_version,
+                    parent_state.next_version(),
+                );
+                let to_commit = TransactionsToKeep::index(
+                    output.first_version,
+                    output.to_commit.try_into()?,
+                    output.to_commit_is_reconfig,
+                );
+                let to_discard: TransactionsWithOutput = output.to_discard.try_into()?;
+                let to_retry: TransactionsWithOutput = output.to_retry.try_into()?;
+
+                base_state_view.prime_cache(to_commit.state_update_refs())?;
+                let result_state = parent_state.update_with_memorized_reads(
+                    base_state_view.persisted_state(),
+                    to_commit.state_update_refs(),
+                    base_state_view.memorized_reads(),
+                );
+                let state_reads = base_state_view.into_memorized_reads();
+
+                // Checked here so that a malformed input fails cleanly instead of tripping the
+                // assertions in `ExecutionOutput::new`.
+                if output.is_block {
+                    ensure!(
+                        to_commit.is_empty() || to_commit.ends_with_sole_checkpoint(),
+                        "Block doesn't end with a sole state checkpoint.",
+                    );
+                    ensure!(
+                        result_state.is_checkpoint(),
+                        "Block doesn't result in a checkpoint."
+                    );
+                } else {
+                    ensure!(
+                        to_discard.is_empty() && to_retry.is_empty(),
+                        "Chunk has transactions to discard or retry.",
+                    );
+                }
+
+                let subscribable_events = to_commit
+                    .transaction_outputs
+                    .iter()
+                    .flat_map(TransactionOutput::events)
+                    .filter(|e| should_forward_to_subscription_service(e))
+                    .cloned()
+                    .collect();
+
+                Ok(ExecutionOutput::new(
+                    output.is_block,
+                    output.first_version,
+                    output.statuses_for_input_txns,
+                    to_commit,
+                    to_discard,
+                    to_retry,
+                    result_state,
+                    state_reads,
+                    output.block_end_info,
+                    None, /* block_execution_stats */
+                    output.next_epoch_state,
+                    Planned::ready(subscribable_events),
+                ))
+            },
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub enum VersionedStateCheckpointOutput {
+    V0(StateCheckpointOutputV0),
+}
+
+/// The root hashes of a `StateCheckpointOutput`, which the receiver checks the state summary it
+/// derives against.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct StateCheckpointOutputV0 {
+    pub next_version: Version,
+    pub root_hash: HashValue,
+    pub last_checkpoint_next_version: Version,
+    pub last_checkpoint_root_hash: HashValue,
+    pub state_checkpoint_hashes: Vec<Option<HashValue>>,
+}
+
+impl From<&StateCheckpointOutput> for VersionedStateCheckpointOutput {
+    fn from(output: &StateCheckpointOutput) -> Self {
+        Self::V0(StateCheckpointOutputV0 {
+            next_version: output.state_summary.next_version(),
+            root_hash: output.state_summary.latest().root_hash(),
+            last_checkpoint_next_version: output.state_summary.last_checkpoint().next_version(),
+            last_checkpoint_root_hash: output.state_summary.last_checkpoint().root_hash(),
+            state_checkpoint_hashes: output.state_checkpoint_hashes.clone(),
+        })
+    }
+}
+
+impl VersionedStateCheckpointOutput {
+    /// Ensures the state checkpoint output derived locally matches this one.
+    pub fn ensure_match(&self, output: &StateCheckpointOutput) -> Result<()> {
+        let local = Self::from(output);
+        ensure!(
+            self == &local,
+            "State checkpoint outputs don't match. received: {:?}, local: {:?}",
+            self,
+            local,
+        );
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub enum VersionedLedgerUpdateOutput {
+    V0(LedgerUpdateOutputV0),
+}
+
+/// The transaction infos of a `LedgerUpdateOutput` and the accumulator they are appended to, from
+/// which the rest of the output is derived again.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct LedgerUpdateOutputV0 {
+    pub transaction_infos: Vec<TransactionInfo>,
+    pub parent_accumulator_frozen_subtree_roots: Vec<HashValue>,
+    pub parent_accumulator_num_leaves: u64,
+}
+
+impl From<&LedgerUpdateOutput> for VersionedLedgerUpdateOutput {
+    fn from(output: &LedgerUpdateOutput) -> Self {
+        Self::V0(LedgerUpdateOutputV0 {
+            transaction_infos: output.transaction_infos.clone(),
+            parent_accumulator_frozen_subtree_roots: output
+                .parent_accumulator
+                .frozen_subtree_roots
+                .clone(),
+            parent_accumulator_num_leaves: output.parent_accumulator.num_leaves,
+        })
+    }
+}
+
+impl TryFrom<VersionedLedgerUpdateOutput> for LedgerUpdateOutput {
+    type Error = anyhow::Error;
+
+    fn try_from(output: VersionedLedgerUpdateOutput) -> Result<Self> {
+        match output {
+            VersionedLedgerUpdateOutput::V0(output) => {
+                // Validates the accumulator, unlike deserializing it directly.
+                let parent_accumulator = Arc::new(InMemoryTransactionAccumulator::new(
+                    output.parent_accumulator_frozen_subtree_roots,
+                    output.parent_accumulator_num_leaves,
+                )?);
+                let transaction_info_hashes: Vec<_> = output
+                    .transaction_infos
+                    .iter()
+                    .map(CryptoHash::hash)
+                    .collect();
+                let transaction_accumulator =
+                    Arc::new(parent_accumulator.append(&transaction_info_hashes));
+                Ok(Self::new(
+                    output.transaction_infos,
+                    transaction_info_hashes,
+                    transaction_accumulator,
+                    parent_accumulator,
+                ))
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aptos_types::{
+        state_store::{state_key::StateKey, state_value::StateValue},
+        transaction::ExecutionStatus,
+        write_set::WriteSet,
+    };
+
+    fn versioned_execution_output() -> VersionedExecutionOutput {
+        let num_txns = 3;
+        let transaction_outputs = (0..num_txns)
+            .map(|i| {
+                TransactionOutput::new_success_with_write_set(WriteSet::new_for_test([(
+                    StateKey::raw(&[i]),
+                    Some(StateValue::new_legacy(vec![i].into())),
+                )]))
+            })
+            .collect();
+        let persisted_auxiliary_infos = (0..num_txns)
+            .map(|i| PersistedAuxiliaryInfo::V1 {
+                transaction_index: i as u32,
+            })
+            .collect();
+
+        VersionedExecutionOutput::V0(ExecutionOutputV0 {
+            is_block: false,
+            first_version: 0,
+            statuses_for_input_txns: vec![
+                TransactionStatus::Keep(ExecutionStatus::Success);
+                num_txns as usize
+            ],
+            to_commit: TransactionsWithOutputV0 {
+                transactions: vec![Transaction::dummy(); num_txns as usize],
+                transaction_outputs,
+                persisted_auxiliary_infos,
+            },
+            to_commit_is_reconfig: false,
+            to_discard: TransactionsWithOutputV0::default(),
+            to_retry: TransactionsWithOutputV0::default(),
+            block_end_info: None,
+            next_epoch_state: None,
+        })
+    }
+
+    #[test]
+    fn test_execution_output_round_trip() {
+        let versioned = versioned_execution_output();
+        let bytes = bcs::to_bytes(&versioned).unwrap();
+        let deserialized: VersionedExecutionOutput = bcs::from_bytes(&bytes).unwrap();
+        assert_eq!(deserialized, versioned);
+
+        let parent_state = LedgerState::new_empty();
+        let output = deserialized
+            .into_execution_output(
+                &parent_state,
+                CachedStateView::new_dummy(parent_state.latest()),
+            )
+            .unwrap();
+        assert_eq!(output.next_version(), 3);
+        assert_eq!(output.result_state.latest().next_version(), 3);
+        assert_eq!(VersionedExecutionOutput::from(&output), versioned);
+    }
+
+    #[test]
+    fn test_execution_output_not_following_parent() {
+        let mut versioned = versioned_execution_output();
+        let VersionedExecutionOutput::V0(output) = &mut versioned;
+        output.first_version = 1;
+
+        let parent_state = LedgerState::new_empty();
+        assert!(versioned
+            .into_execution_output(
+                &parent_state,
+                CachedStateView::new_dummy(parent_state.latest()),
+            )
+            .is_err());
+    }
+
+    #[test]
+    fn test_ledger_update_output_round_trip() {
+        let transaction_infos = vec![
+            TransactionInfo::new_placeholder(1, None, ExecutionStatus::Success),
+            TransactionInfo::new_placeholder(2, Some(HashValue::zero()), ExecutionStatus::Success),
+        ];
+        let transaction_info_hashes: Vec<_> =
+            transaction_infos.iter().map(CryptoHash::hash).collect();
+        let parent_accumulator =
+            Arc::new(InMemoryTransactionAccumulator::new_empty().append(&[HashValue::zero()]));
+        let transaction_accumulator = Arc::new(parent_accumulator.append(&transaction_info_hashes));
+        let output = LedgerUpdateOutput::new(
+            transaction_infos,
+            transaction_info_hashes,
+            transaction_accumulator,
+            parent_accumulator,
+        );
+
+        let versioned = VersionedLedgerUpdateOutput::from(&output);
+        let bytes = bcs::to_bytes(&versioned).unwrap();
+        let deserialized: VersionedLedgerUpdateOutput = bcs::from_bytes(&bytes).unwrap();
+        assert_eq!(deserialized, versioned);
+
+        let rebuilt = LedgerUpdateOutput::try_from(deserialized).unwrap();
+        assert_eq!(rebuilt.transaction_infos, output.transaction_infos);
+        assert_eq!(
+            rebuilt.transaction_accumulator.root_hash(),
+            output.transaction_accumulator.root_hash(),
+        );
+        assert_eq!(VersionedLedgerUpdateOutput::from(&rebuilt), versioned);
+    }
+}
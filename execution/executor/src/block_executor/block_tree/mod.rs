@@ -55,6 +55,10 @@ impl Block {
             .num_leaves())
     }
 
+    pub fn children(&self) -> Vec<Arc<Block>> {
+        self.children.lock().clone()
+    }
+
     pub fn ensure_has_child(&self, child_id: HashValue) -> Result<()> {
         ensure!(
             self.children.lock().iter().any(|c| c.id == child_id),
@@ -0,0 +1,135 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::metrics::{COMMIT_AHEAD_BUFFER_SIZE, COMMIT_AHEAD_LEDGER_INFOS};
+use anyhow::{bail, Result};
+use aptos_infallible::Mutex;
+use aptos_types::{ledger_info::LedgerInfoWithSignatures, transaction::Version};
+use std::collections::BTreeMap;
+
+/// Holds ledger infos that arrived before the blocks they certify were executed, e.g. on the
+/// consensus observer path, until the blocks are pre-committed.
+///
+/// When the buffer is full, the ledger info with the lowest version is dropped, since any later
+/// ledger info commits its block as well. Ledger infos ending an epoch are never dropped, as they
+/// need to be persisted, so new ledger infos are rejected if there is nothing else to drop.
+pub struct CommitAheadBuffer {
+    ledger_infos: Mutex<BTreeMap<Version, LedgerInfoWithSignatures>>,
+    max_size: usize,
+}
+
+impl CommitAheadBuffer {
+    pub const DEFAULT_MAX_SIZE: usize = 128;
+
+    pub fn new(max_size: usize) -> Self {
+        Self {
+            ledger_infos: Mutex::new(BTreeMap::new()),
+            max_size,
+        }
+    }
+
+    pub fn push(&self, ledger_info_with_sigs: LedgerInfoWithSignatures) -> Result<()> {
+        let version = ledger_info_with_sigs.ledger_info().version();
+        let mut ledger_infos = self.ledger_infos.lock();
+        ledger_infos.insert(version, ledger_info_with_sigs);
+
+        if ledger_infos.len() > self.max_size {
+            let to_evict = ledger_infos
+                .iter()
+                .find(|(_, li)| !li.ledger_info().ends_epoch())
+                .map(|(version, _)| *version);
+            match to_evict {
+                Some(to_evict) if to_evict != version => {
+                    ledger_infos.remove(&to_evict);
+                    COMMIT_AHEAD_LEDGER_INFOS
+                        .with_label_values(&["evicted"])
+                        .inc();
+                },
+                _ => {
+                    ledger_infos.remove(&version);
+                    COMMIT_AHEAD_LEDGER_INFOS
+                        .with_label_values(&["rejected"])
+                        .inc();
+                    bail!(
+                        "Commit ahead buffer is full, rejecting ledger info at version {}.",
+                        version,
+                    );
+                },
+            }
+        }
+
+        COMMIT_AHEAD_LEDGER_INFOS
+            .with_label_values(&["buffered"])
+            .inc();
+        COMMIT_AHEAD_BUFFER_SIZE.set(ledger_infos.len() as i64);
+        Ok(())
+    }
+
+    /// Takes the ledger infos with versions up to `version` out of the buffer, in order.
+    pub fn take_up_to(&self, version: Version) -> Vec<LedgerInfoWithSignatures> {
+        let mut ledger_infos = self.ledger_infos.lock();
+        let later = ledger_infos.split_off(&(version + 1));
+        let ready = std::mem::replace(&mut *ledger_infos, later);
+        COMMIT_AHEAD_BUFFER_SIZE.set(ledger_infos.len() as i64);
+
+        ready.into_values().collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.ledger_infos.lock().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aptos_crypto::HashValue;
+    use aptos_types::{
+        aggregate_signature::AggregateSignature, block_info::BlockInfo, epoch_state::EpochState,
+        ledger_info::LedgerInfo,
+    };
+
+    fn ledger_info(version: Version, ends_epoch: bool) -> LedgerInfoWithSignatures {
+        LedgerInfoWithSignatures::new(
+            LedgerInfo::new(
+                BlockInfo::new(
+                    1,
+                    version,
+                    HashValue::random(),
+                    HashValue::random(),
+                    version,
+                    0,
+                    ends_epoch.then(EpochState::empty),
+                ),
+                HashValue::zero(),
+            ),
+            AggregateSignature::empty(),
+        )
+    }
+
+    #[test]
+    fn test_commit_ahead_buffer() {
+        let buffer = CommitAheadBuffer::new(2);
+        buffer.push(ledger_info(10, true)).unwrap();
+        buffer.push(ledger_info(20, false)).unwrap();
+        // The lowest version that doesn't end an epoch is evicted.
+        buffer.push(ledger_info(30, true)).unwrap();
+        assert_eq!(buffer.len(), 2);
+        // Nothing but the new one can be evicted.
+        assert!(buffer.push(ledger_info(40, false)).is_err());
+        assert_eq!(buffer.len(), 2);
+
+        assert!(buffer.take_up_to(9).is_empty());
+        let versions = |lis: Vec<LedgerInfoWithSignatures>| {
+            lis.iter()
+                .map(|li| li.ledger_info().version())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(versions(buffer.take_up_to(35)), vec![10, 30]);
+        assert!(buffer.is_empty());
+    }
+}
@@ -7,9 +7,9 @@
 use crate::{
     logging::{LogEntry, LogSchema},
     metrics::{
-        BLOCK_EXECUTION_WORKFLOW_WHOLE, COMMIT_BLOCKS, CONCURRENCY_GAUGE,
-        GET_BLOCK_EXECUTION_OUTPUT_BY_EXECUTING, OTHER_TIMERS, SAVE_TRANSACTIONS,
-        TRANSACTIONS_SAVED, UPDATE_LEDGER,
+        BLOCK_EXECUTION_WORKFLOW_WHOLE, COMMIT_AHEAD_LEDGER_INFOS, COMMIT_BLOCKS,
+        CONCURRENCY_GAUGE, GET_BLOCK_EXECUTION_OUTPUT_BY_EXECUTING, OTHER_TIMERS,
+        SAVE_TRANSACTIONS, TRANSACTIONS_SAVED, UPDATE_LEDGER,
     },
     types::partial_state_compute_result::PartialStateComputeResult,
     workflow::{
@@ -20,7 +20,8 @@ use crate::{
 use anyhow::Result;
 use aptos_crypto::HashValue;
 use aptos_executor_types::{
-    state_compute_result::StateComputeResult, BlockExecutorTrait, ExecutorError, ExecutorResult,
+    state_checkpoint_output::StateCheckpointOutput, state_compute_result::StateComputeResult,
+    BlockExecutorTrait, ExecutorError, ExecutorResult,
 };
 use aptos_experimental_runtimes::thread_manager::THREAD_MANAGER;
 use aptos_infallible::RwLock;
@@ -28,9 +29,10 @@ use aptos_logger::prelude::*;
 use aptos_metrics_core::{IntGaugeVecHelper, TimerHelper};
 use aptos_storage_interface::{
     state_store::{
-        state_summary::ProvableStateSummary, state_view::cached_state_view::CachedStateView,
+        state_summary::{LedgerStateSummary, ProvableStateSummary},
+        state_view::cached_state_view::CachedStateView,
     },
-    DbReaderWriter,
+    DbReader, DbReaderWriter,
 };
 use aptos_types::{
     block_executor::{
@@ -39,13 +41,17 @@ use aptos_types::{
     },
     ledger_info::LedgerInfoWithSignatures,
     state_store::StateViewId,
+    transaction::Version,
 };
 use aptos_vm::VMBlockExecutor;
-use block_tree::BlockTree;
+use block_tree::{Block, BlockTree};
+use commit_ahead_buffer::CommitAheadBuffer;
 use fail::fail_point;
 use std::sync::Arc;
+use tracing::info_span;
 
 pub mod block_tree;
+pub mod commit_ahead_buffer;
 
 pub struct BlockExecutor<V> {
     pub db: DbReaderWriter,
@@ -143,6 +149,20 @@ where
             .commit_ledger(ledger_info_with_sigs)
     }
 
+    fn commit_ledger_ahead(
+        &self,
+        ledger_info_with_sigs: LedgerInfoWithSignatures,
+    ) -> ExecutorResult<()> {
+        let _guard = CONCURRENCY_GAUGE.concurrency_with(&["block", "commit_ledger_ahead"]);
+
+        self.maybe_initialize()?;
+        self.inner
+            .read()
+            .as_ref()
+            .expect("BlockExecutor is not reset")
+            .commit_ledger_ahead(ledger_info_with_sigs)
+    }
+
     fn finish(&self) {
         let _guard = CONCURRENCY_GAUGE.concurrency_with(&["block", "finish"]);
 
@@ -153,6 +173,7 @@ where
 struct BlockExecutorInner<V> {
     db: DbReaderWriter,
     block_tree: BlockTree,
+    commit_ahead_buffer: CommitAheadBuffer,
     block_executor: V,
 }
 
@@ -165,6 +186,7 @@ where
         Ok(Self {
             db,
             block_tree,
+            commit_ahead_buffer: CommitAheadBuffer::new(CommitAheadBuffer::DEFAULT_MAX_SIZE),
             block_executor: V::new(),
         })
     }
@@ -190,6 +212,7 @@ where
             transactions,
             auxiliary_info,
         } = block;
+        let _span = info_span!("execute_block", block_id = %block_id).entered();
         let mut block_vec = self
             .block_tree
             .get_blocks_opt(&[block_id, parent_block_id])?;
@@ -204,46 +227,54 @@ where
             "execute_block"
         );
         let committed_block_id = self.committed_block_id();
-        let execution_output =
-            if parent_block_id != committed_block_id && parent_output.has_reconfiguration() {
-                // ignore reconfiguration suffix, even if the block is non-empty
-                info!(
-                    LogSchema::new(LogEntry::BlockExecutor).block_id(block_id),
-                    "reconfig_descendant_block_received"
-                );
-                parent_output.execution_output.reconfig_suffix()
-            } else {
-                let state_view = {
-                    let _timer = OTHER_TIMERS.timer_with(&["get_state_view"]);
-                    CachedStateView::new(
-                        StateViewId::BlockExecution { block_id },
-                        Arc::clone(&self.db.reader),
-                        parent_output.result_state().latest().clone(),
-                    )?
-                };
-
-                let _timer = GET_BLOCK_EXECUTION_OUTPUT_BY_EXECUTING.start_timer();
-                fail_point!("executor::block_executor_execute_block", |_| {
-                    Err(ExecutorError::from(anyhow::anyhow!(
-                        "Injected error in block_executor_execute_block"
-                    )))
-                });
-
-                DoGetExecutionOutput::by_transaction_execution(
-                    &self.block_executor,
-                    transactions,
-                    auxiliary_info,
-                    parent_output.result_state(),
-                    state_view,
-                    onchain_config.clone(),
-                    TransactionSliceMetadata::block(parent_block_id, block_id),
+        let is_reconfig_suffix =
+            parent_block_id != committed_block_id && parent_output.has_reconfiguration();
+        let execution_output = if is_reconfig_suffix {
+            // ignore reconfiguration suffix, even if the block is non-empty
+            info!(
+                LogSchema::new(LogEntry::BlockExecutor).block_id(block_id),
+                "reconfig_descendant_block_received"
+            );
+            parent_output.execution_output.reconfig_suffix()
+        } else {
+            let state_view = {
+                let _timer = OTHER_TIMERS.timer_with(&["get_state_view"]);
+                CachedStateView::new(
+                    StateViewId::BlockExecution { block_id },
+                    Arc::clone(&self.db.reader),
+                    parent_output.result_state().latest().clone(),
                 )?
             };
 
+            let _timer = GET_BLOCK_EXECUTION_OUTPUT_BY_EXECUTING.start_timer();
+            fail_point!("executor::block_executor_execute_block", |_| {
+                Err(ExecutorError::from(anyhow::anyhow!(
+                    "Injected error in block_executor_execute_block"
+                )))
+            });
+
+            DoGetExecutionOutput::by_transaction_execution(
+                &self.block_executor,
+                transactions,
+                auxiliary_info,
+                parent_output.result_state(),
+                state_view,
+                onchain_config.clone(),
+                TransactionSliceMetadata::block(parent_block_id, block_id),
+            )?
+        };
+
         let output = PartialStateComputeResult::new(execution_output);
-        let _ = self
+        let block = self
             .block_tree
             .add_block(parent_block_id, block_id, output)?;
+
+        // If the state of the parent is already hashed, the state of the block can be as well.
+        if !is_reconfig_suffix {
+            if let Ok(parent_state_summary) = parent_output.ensure_result_state_summary() {
+                spawn_state_checkpoint(self.db.reader.clone(), parent_state_summary.clone(), block);
+            }
+        }
         Ok(())
     }
 
@@ -253,6 +284,7 @@ where
         parent_block_id: HashValue,
     ) -> ExecutorResult<StateComputeResult> {
         let _timer = UPDATE_LEDGER.start_timer();
+        let _span = info_span!("ledger_update", block_id = %block_id).entered();
         info!(
             LogSchema::new(LogEntry::BlockExecutor).block_id(block_id),
             "ledger_update"
@@ -294,24 +326,50 @@ where
             );
         } else {
             THREAD_MANAGER.get_non_exe_cpu_pool().install(|| {
-                // TODO(aldenhu): remove? no known strategy to recover from this failure
-                fail_point!("executor::block_state_checkpoint", |_| {
-                    Err(anyhow::anyhow!("Injected error in block state checkpoint."))
-                });
-                output.set_state_checkpoint_output(DoStateCheckpoint::run(
-                    &output.execution_output,
-                    parent_block.output.ensure_result_state_summary()?,
-                    &ProvableStateSummary::new_persisted(self.db.reader.as_ref())?,
-                    None,
-                )?);
-                output.set_ledger_update_output(DoLedgerUpdate::run(
-                    &output.execution_output,
-                    output.ensure_state_checkpoint_output()?,
-                    parent_out
-                        .ensure_ledger_update_output()?
-                        .transaction_accumulator
-                        .clone(),
-                )?);
+                let parent_accumulator = parent_out
+                    .ensure_ledger_update_output()?
+                    .transaction_accumulator
+                    .clone();
+
+                // Unless the state of the block has been hashed ahead, the state after the last
+                // checkpoint is hashed alongside the ledger update.
+                let mut ledger_update_output = None;
+                let state_checkpoint_output =
+                    output.state_checkpoint_output.get_or_try_init(|| {
+                        fail_point_state_checkpoint()?;
+                        let pending_state_checkpoint = DoStateCheckpoint::run_checkpoint(
+                            &output.execution_output,
+                            parent_out.ensure_result_state_summary()?,
+                            self.db.reader.clone(),
+                            None,
+                        )?;
+                        ledger_update_output = Some(DoLedgerUpdate::run(
+                            &output.execution_output,
+                            pending_state_checkpoint.state_checkpoint_hashes().to_vec(),
+                            parent_accumulator.clone(),
+                        )?);
+                        pending_state_checkpoint.finalize()
+                    })?;
+
+                // Had the state of the block been hashed ahead, the state checkpoint stage would
+                // have been scheduled for the children already.
+                if ledger_update_output.is_some() {
+                    spawn_state_checkpoint_for_children(
+                        self.db.reader.clone(),
+                        state_checkpoint_output,
+                        &block,
+                    );
+                }
+
+                let ledger_update_output = match ledger_update_output {
+                    Some(ledger_update_output) => ledger_update_output,
+                    None => DoLedgerUpdate::run(
+                        &output.execution_output,
+                        state_checkpoint_output.state_checkpoint_hashes.clone(),
+                        parent_accumulator,
+                    )?,
+                };
+                output.set_ledger_update_output(ledger_update_output);
                 Result::<_>::Ok(())
             })?;
         }
@@ -321,6 +379,7 @@ where
 
     fn pre_commit_block(&self, block_id: HashValue) -> ExecutorResult<()> {
         let _timer = COMMIT_BLOCKS.start_timer();
+        let _span = info_span!("pre_commit_block", block_id = %block_id).entered();
         info!(
             LogSchema::new(LogEntry::BlockExecutor).block_id(block_id),
             "pre_commit_block",
@@ -342,6 +401,8 @@ where
             TRANSACTIONS_SAVED.observe(num_txns as f64);
         }
 
+        self.commit_buffered_ledger_infos(output.last_version_or_0());
+
         Ok(())
     }
 
@@ -349,6 +410,7 @@ where
         let _timer = OTHER_TIMERS.timer_with(&["commit_ledger"]);
 
         let block_id = ledger_info_with_sigs.ledger_info().consensus_block_id();
+        let _span = info_span!("commit_ledger", block_id = %block_id).entered();
         info!(
             LogSchema::new(LogEntry::BlockExecutor).block_id(block_id),
             "commit_ledger"
@@ -379,4 +441,163 @@ where
 
         Ok(())
     }
+
+    fn commit_ledger_ahead(
+        &self,
+        ledger_info_with_sigs: LedgerInfoWithSignatures,
+    ) -> ExecutorResult<()> {
+        if self.is_committed(&ledger_info_with_sigs)? {
+            return Ok(());
+        }
+        if self.is_ready_to_commit(&ledger_info_with_sigs)? {
+            return self.commit_ledger(ledger_info_with_sigs);
+        }
+
+        info!(
+            LogSchema::new(LogEntry::BlockExecutor)
+                .block_id(ledger_info_with_sigs.ledger_info().consensus_block_id()),
+            version = ledger_info_with_sigs.ledger_info().version(),
+            "Buffering ledger info ahead of its block."
+        );
+        self.commit_ahead_buffer.push(ledger_info_with_sigs)?;
+        Ok(())
+    }
+
+    /// Commits the buffered ledger infos up to `version`, which has just been pre-committed. The
+    /// pre-commit has succeeded at this point, so failures are only logged: the ledger infos are
+    /// committed again by the pipeline once it sees the blocks pre-committed.
+    ///
+    /// Ledger infos that are already committed, or whose blocks are not in the tree (e.g. a
+    /// forked block, or an empty reconfiguration suffix that's not executed yet), are dropped
+    /// rather than buffered again.
+    fn commit_buffered_ledger_infos(&self, version: Version) {
+        if self.commit_ahead_buffer.is_empty() {
+            return;
+        }
+        for ledger_info_with_sigs in self.commit_ahead_buffer.take_up_to(version) {
+            let version = ledger_info_with_sigs.ledger_info().version();
+            let label = match self.commit_buffered_ledger_info(ledger_info_with_sigs) {
+                Ok(true) => "committed",
+                Ok(false) => "dropped",
+                Err(err) => {
+                    warn!(
+                        LogSchema::new(LogEntry::BlockExecutor),
+                        version = version,
+                        error = ?err,
+                        "Failed to commit ledger info buffered ahead of its block."
+                    );
+                    "failed"
+                },
+            };
+            COMMIT_AHEAD_LEDGER_INFOS.with_label_values(&[label]).inc();
+        }
+    }
+
+    /// Commits the buffered ledger info if its block is ready, returns whether it did.
+    fn commit_buffered_ledger_info(
+        &self,
+        ledger_info_with_sigs: LedgerInfoWithSignatures,
+    ) -> Result<bool> {
+        if self.is_committed(&ledger_info_with_sigs)?
+            || !self.is_ready_to_commit(&ledger_info_with_sigs)?
+        {
+            return Ok(false);
+        }
+        self.commit_ledger(ledger_info_with_sigs)?;
+        Ok(true)
+    }
+
+    /// Whether the ledger info, or a later one, is already committed.
+    fn is_committed(&self, ledger_info_with_sigs: &LedgerInfoWithSignatures) -> Result<bool> {
+        let num_committed_txns = self.block_tree.root_block().num_persisted_transactions()?;
+        Ok(ledger_info_with_sigs.ledger_info().version() < num_committed_txns)
+    }
+
+    /// Whether the block of the ledger info is executed and pre-committed.
+    fn is_ready_to_commit(&self, ledger_info_with_sigs: &LedgerInfoWithSignatures) -> Result<bool> {
+        let ledger_info = ledger_info_with_sigs.ledger_info();
+        let pre_committed = self
+            .db
+            .reader
+            .get_pre_committed_version()?
+            .is_some_and(|version| version >= ledger_info.version());
+        let executed = self
+            .block_tree
+            .get_blocks_opt(&[ledger_info.consensus_block_id()])?
+            .pop()
+            .expect("Must exist.")
+            .is_some();
+        Ok(pre_committed && executed)
+    }
+}
+
+/// The state checkpoint stage of the block pipeline: hashes the state of the block as a task
+/// on the background thread pool, whose workers steal the tasks when idle. The stage only
+/// depends on the state of the parent, so once it's done, it's scheduled for the executed
+/// children right away, and the state of block N+1 is hashed while the ledger of block N is
+/// updated. The ledger update of the block picks the result up, or waits for it if it's still
+/// in progress.
+///
+/// The ledger update runs on the non-execution pool, where it may be initializing the same
+/// output (and waiting for the background pool in `PendingStateCheckpoint::finalize`). Were the
+/// stage on that pool too, an idle worker of the ledger update could steal it and initialize
+/// the output reentrantly, so it must stay off that pool.
+fn spawn_state_checkpoint(
+    db: Arc<dyn DbReader>,
+    parent_state_summary: LedgerStateSummary,
+    block: Arc<Block>,
+) {
+    if block.output.state_checkpoint_output.get().is_some() {
+        return;
+    }
+    THREAD_MANAGER.get_background_pool().spawn(move || {
+        let _timer = OTHER_TIMERS.timer_with(&["state_checkpoint_ahead"]);
+        let result = block.output.state_checkpoint_output.get_or_try_init(|| {
+            fail_point_state_checkpoint()?;
+            let persisted_state_summary = ProvableStateSummary::new_persisted(db.as_ref())?;
+            DoStateCheckpoint::run(
+                &block.output.execution_output,
+                &parent_state_summary,
+                &persisted_state_summary,
+                None,
+            )
+        });
+        match result {
+            Ok(state_checkpoint_output) => {
+                spawn_state_checkpoint_for_children(db, state_checkpoint_output, &block)
+            },
+            // The ledger update of the block tries again.
+            Err(err) => warn!(
+                LogSchema::new(LogEntry::BlockExecutor).block_id(block.id),
+                error = ?err,
+                "Failed to hash the state of the block ahead of its ledger update."
+            ),
+        }
+    });
+}
+
+fn spawn_state_checkpoint_for_children(
+    db: Arc<dyn DbReader>,
+    state_checkpoint_output: &StateCheckpointOutput,
+    block: &Block,
+) {
+    // The children of a reconfiguration are suffixes, which take the output of the parent.
+    if block.output.has_reconfiguration() {
+        return;
+    }
+    for child in block.children() {
+        spawn_state_checkpoint(
+            db.clone(),
+            state_checkpoint_output.state_summary.clone(),
+            child,
+        );
+    }
+}
+
+fn fail_point_state_checkpoint() -> Result<()> {
+    // TODO(aldenhu): remove? no known strategy to recover from this failure
+    fail_point!("executor::block_state_checkpoint", |_| {
+        Err(anyhow::anyhow!("Injected error in block state checkpoint."))
+    });
+    Ok(())
 }
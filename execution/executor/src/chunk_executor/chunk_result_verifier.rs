@@ -8,7 +8,7 @@ use aptos_types::{
     epoch_state::EpochState,
     ledger_info::LedgerInfoWithSignatures,
     proof::{accumulator::InMemoryTransactionAccumulator, TransactionInfoListWithProof},
-    transaction::TransactionInfo,
+    transaction::{TransactionInfo, Version},
 };
 
 pub trait ChunkResultVerifier {
@@ -31,6 +31,9 @@ pub struct StateSyncChunkVerifier {
     pub txn_infos_with_proof: TransactionInfoListWithProof,
     pub verified_target_li: LedgerInfoWithSignatures,
     pub epoch_change_li: Option<LedgerInfoWithSignatures>,
+    /// Number of transactions at the beginning of the chunk that had already been applied and
+    /// were skipped. Their infos are still verified to be the ones in the local ledger.
+    pub num_txns_skipped: usize,
 }
 
 impl ChunkResultVerifier for StateSyncChunkVerifier {
@@ -46,27 +49,27 @@ impl ChunkResultVerifier for StateSyncChunkVerifier {
         }
 
         THREAD_MANAGER.get_exe_cpu_pool().install(|| {
-            let first_version = parent_accumulator.num_leaves();
+            let num_txns_in_ledger = parent_accumulator.num_leaves();
+            let first_version = num_txns_in_ledger - self.num_txns_skipped as Version;
 
-            // Verify the chunk extends the parent accumulator.
+            // Verify the chunk extends the parent accumulator, including the skipped part.
             let parent_root_hash = parent_accumulator.root_hash();
             let num_overlap = self.txn_infos_with_proof.verify_extends_ledger(
-                first_version,
+                num_txns_in_ledger,
                 parent_root_hash,
                 Some(first_version),
             )?;
-            assert_eq!(num_overlap, 0, "overlapped chunks");
+            assert_eq!(num_overlap, self.num_txns_skipped, "overlapped chunks");
 
             // Verify transaction infos match
-            ledger_update_output
-                .ensure_transaction_infos_match(&self.txn_infos_with_proof.transaction_infos)?;
+            ledger_update_output.ensure_transaction_infos_match(self.transaction_infos())?;
 
             Ok(())
         })
     }
 
     fn transaction_infos(&self) -> &[TransactionInfo] {
-        &self.txn_infos_with_proof.transaction_infos
+        &self.txn_infos_with_proof.transaction_infos[self.num_txns_skipped..]
     }
 
     fn maybe_select_chunk_ending_ledger_info(
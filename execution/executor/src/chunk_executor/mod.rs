@@ -24,10 +24,7 @@ use aptos_infallible::{Mutex, RwLock};
 use aptos_logger::prelude::*;
 use aptos_metrics_core::{IntGaugeVecHelper, TimerHelper};
 use aptos_storage_interface::{
-    state_store::{
-        state::State, state_summary::ProvableStateSummary,
-        state_view::cached_state_view::CachedStateView,
-    },
+    state_store::{state::State, state_view::cached_state_view::CachedStateView},
     DbReaderWriter,
 };
 use aptos_types::{
@@ -61,6 +58,7 @@ use std::{
     },
     time::Instant,
 };
+use tracing::{info_span, Span};
 use transaction_chunk::{ChunkToApply, ChunkToExecute, TransactionChunk};
 
 pub mod chunk_commit_queue;
@@ -141,15 +139,17 @@ impl<V: VMBlockExecutor> ChunkExecutorTrait for ChunkExecutor<V> {
             proof: txn_infos_with_proof,
         } = txn_list_with_proof;
 
-        let chunk = ChunkToExecute {
+        let mut chunk = ChunkToExecute {
             transactions,
             persisted_aux_info,
             first_version: v.ok_or_else(|| anyhow!("first version is None"))?,
         };
+        let num_txns_skipped = self.with_inner(|inner| inner.skip_applied_txns(&mut chunk))?;
         let chunk_verifier = Arc::new(StateSyncChunkVerifier {
             txn_infos_with_proof,
             verified_target_li: verified_target_li.clone(),
             epoch_change_li: epoch_change_li.cloned(),
+            num_txns_skipped,
         });
 
         // Call the shared implementation.
@@ -184,16 +184,18 @@ impl<V: VMBlockExecutor> ChunkExecutorTrait for ChunkExecutor<V> {
         } = txn_output_list_with_proof;
         let (transactions, transaction_outputs): (Vec<_>, Vec<_>) =
             transactions_and_outputs.into_iter().unzip();
-        let chunk = ChunkToApply {
+        let mut chunk = ChunkToApply {
             transactions,
             transaction_outputs,
             persisted_aux_info,
             first_version: v.ok_or_else(|| anyhow!("first version is None"))?,
         };
+        let num_txns_skipped = self.with_inner(|inner| inner.skip_applied_txns(&mut chunk))?;
         let chunk_verifier = Arc::new(StateSyncChunkVerifier {
             txn_infos_with_proof,
             verified_target_li: verified_target_li.clone(),
             epoch_change_li: epoch_change_li.cloned(),
+            num_txns_skipped,
         });
 
         // Call the shared implementation.
@@ -266,6 +268,8 @@ impl<V: VMBlockExecutor> ChunkExecutorInner<V> {
                 CHUNK_OTHER_TIMERS.timer_with(&["commit_chunk_impl__next_chunk_to_commit"]);
             self.commit_queue.lock().next_chunk_to_commit()?
         };
+        let _span =
+            chunk_span("commit_chunk", chunk.output.execution_output.first_version).entered();
 
         let output = chunk.output.expect_complete_result();
         let num_txns = output.num_transactions_to_commit();
@@ -293,6 +297,36 @@ impl<V: VMBlockExecutor> ChunkExecutorInner<V> {
     }
 
     // ************************* Chunk Executor Implementation *************************
+    /// Skips the transactions at the beginning of the chunk that are already applied, which
+    /// happens when state sync re-delivers an overlapping chunk after a retry. Returns the number
+    /// of transactions skipped.
+    fn skip_applied_txns<Chunk: TransactionChunk>(&self, chunk: &mut Chunk) -> Result<usize> {
+        let expecting_version = self.commit_queue.lock().expecting_version();
+        if chunk.first_version() >= expecting_version {
+            return Ok(0);
+        }
+
+        let num_txns_applied = (expecting_version - chunk.first_version()) as usize;
+        ensure!(
+            num_txns_applied < chunk.len(),
+            "Chunk is already applied. Expecting version: {}, got chunk of {} transactions \
+             starting at {}",
+            expecting_version,
+            chunk.len(),
+            chunk.first_version(),
+        );
+        info!(
+            LogSchema::new(LogEntry::ChunkExecutor)
+                .first_version_in_request(Some(chunk.first_version()))
+                .num_txns_in_request(chunk.len()),
+            num_txns_applied = num_txns_applied,
+            "Skipping already applied transactions of the chunk.",
+        );
+        chunk.skip(num_txns_applied);
+
+        Ok(num_txns_applied)
+    }
+
     fn enqueue_chunk<Chunk: TransactionChunk + Sync>(
         &self,
         chunk: Chunk,
@@ -310,6 +344,7 @@ impl<V: VMBlockExecutor> ChunkExecutorInner<V> {
         );
 
         let num_txns = chunk.len();
+        let _span = chunk_span(mode_for_log, first_version).entered();
 
         let state_view = self.state_view(parent_state.latest())?;
         let execution_output = chunk.into_output::<V>(&parent_state, state_view)?;
@@ -343,11 +378,12 @@ impl<V: VMBlockExecutor> ChunkExecutorInner<V> {
             output,
             chunk_verifier,
         } = chunk;
+        let _span = chunk_span("update_ledger", output.execution_output.first_version).entered();
 
-        let state_checkpoint_output = DoStateCheckpoint::run(
+        let pending_state_checkpoint = DoStateCheckpoint::run_checkpoint(
             &output.execution_output,
             &parent_state_summary,
-            &ProvableStateSummary::new_persisted(self.db.reader.as_ref())?,
+            self.db.reader.clone(),
             Some(
                 chunk_verifier
                     .transaction_infos()
@@ -359,9 +395,10 @@ impl<V: VMBlockExecutor> ChunkExecutorInner<V> {
 
         let ledger_update_output = DoLedgerUpdate::run(
             &output.execution_output,
-            &state_checkpoint_output,
+            pending_state_checkpoint.state_checkpoint_hashes().to_vec(),
             parent_accumulator.clone(),
         )?;
+        let state_checkpoint_output = pending_state_checkpoint.finalize()?;
 
         chunk_verifier.verify_chunk_result(&parent_accumulator, &ledger_update_output)?;
 
@@ -410,6 +447,14 @@ impl<V: VMBlockExecutor> ChunkExecutorInner<V> {
     }
 }
 
+/// The stages of a chunk are traced under its first version, so that they form a single trace
+/// together with the storage work they trigger.
+fn chunk_span(stage: &'static str, first_version: Version) -> Span {
+    let span = info_span!("chunk", otel.name = stage, first_version = first_version);
+    aptos_logger::otel::set_trace_id(&span, &first_version.to_be_bytes());
+    span
+}
+
 impl<V: VMBlockExecutor> TransactionReplayer for ChunkExecutor<V> {
     fn enqueue_chunks(
         &self,
@@ -3,7 +3,7 @@
 
 use crate::{
     metrics::{CHUNK_OTHER_TIMERS, VM_EXECUTE_CHUNK},
-    workflow::do_get_execution_output::DoGetExecutionOutput,
+    workflow::{do_get_execution_output::DoGetExecutionOutput, do_prefetch_state::DoPrefetchState},
 };
 use anyhow::Result;
 use aptos_executor_types::execution_output::ExecutionOutput;
@@ -39,6 +39,9 @@ pub trait TransactionChunk {
 
     fn len(&self) -> usize;
 
+    /// Drops the first `num_txns` transactions of the chunk.
+    fn skip(&mut self, num_txns: usize);
+
     fn is_empty(&self) -> bool {
         self.len() == 0
     }
@@ -65,6 +68,12 @@ impl TransactionChunk for ChunkToExecute {
         self.transactions.len()
     }
 
+    fn skip(&mut self, num_txns: usize) {
+        self.transactions.drain(..num_txns);
+        self.persisted_aux_info.drain(..num_txns);
+        self.first_version += num_txns as Version;
+    }
+
     fn into_output<V: VMBlockExecutor>(
         self,
         parent_state: &LedgerState,
@@ -84,7 +93,9 @@ impl TransactionChunk for ChunkToExecute {
 
         // TODO(skedia) In the chunk executor path, we ideally don't need to verify the signature
         // as only transactions with verified signatures are committed to the storage.
-        let sig_verified_txns = {
+        // The state the chunk is most likely to read is fetched in the meantime.
+        let prefetch_keys = DoPrefetchState::keys_to_prefetch(&transactions);
+        let sig_verified_txns = state_view.prefetch_while(&prefetch_keys, || {
             let _timer = CHUNK_OTHER_TIMERS.timer_with(&["sig_verify"]);
 
             let num_txns = transactions.len();
@@ -95,7 +106,7 @@ impl TransactionChunk for ChunkToExecute {
                     .map(|t| t.into())
                     .collect::<Vec<_>>()
             })
-        };
+        });
 
         let _timer = VM_EXECUTE_CHUNK.start_timer();
         DoGetExecutionOutput::by_transaction_execution::<V>(
@@ -129,6 +140,13 @@ impl TransactionChunk for ChunkToApply {
         self.transactions.len()
     }
 
+    fn skip(&mut self, num_txns: usize) {
+        self.transactions.drain(..num_txns);
+        self.transaction_outputs.drain(..num_txns);
+        self.persisted_aux_info.drain(..num_txns);
+        self.first_version += num_txns as Version;
+    }
+
     fn into_output<V: VMBlockExecutor>(
         self,
         parent_state: &LedgerState,
@@ -12,8 +12,8 @@ use anyhow::{anyhow, ensure, format_err, Result};
 use aptos_crypto::HashValue;
 use aptos_logger::prelude::*;
 use aptos_storage_interface::{
-    state_store::state_view::cached_state_view::CachedStateView, DbReaderWriter, DbWriter,
-    LedgerSummary,
+    state_store::{state::State, state_view::cached_state_view::CachedStateView},
+    DbReader, DbReaderWriter, DbWriter, LedgerSummary,
 };
 use aptos_types::{
     account_config::CORE_CODE_ADDRESS,
@@ -25,13 +25,15 @@ use aptos_types::{
     block_info::{BlockInfo, GENESIS_EPOCH, GENESIS_ROUND, GENESIS_TIMESTAMP_USECS},
     ledger_info::{LedgerInfo, LedgerInfoWithSignatures},
     on_chain_config::ConfigurationResource,
-    state_store::{state_key::StateKey, StateViewId, TStateView},
+    state_store::{
+        state_key::StateKey, state_storage_usage::StateStorageUsage, StateViewId, TStateView,
+    },
     timestamp::TimestampResource,
-    transaction::{AuxiliaryInfo, AuxiliaryInfoTrait, Transaction},
+    transaction::{AuxiliaryInfo, AuxiliaryInfoTrait, Transaction, Version},
     waypoint::Waypoint,
 };
 use aptos_vm::VMBlockExecutor;
-use std::sync::Arc;
+use std::{collections::BTreeMap, sync::Arc};
 
 pub fn generate_waypoint<V: VMBlockExecutor>(
     db: &DbReaderWriter,
@@ -71,6 +73,34 @@ pub fn maybe_bootstrap<V: VMBlockExecutor>(
     Ok(ledger_info)
 }
 
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum StateChange {
+    Creation,
+    Modification,
+    Deletion,
+}
+
+/// What committing a genesis would do to the state in the DB, to validate it beforehand.
+#[derive(Debug)]
+pub struct GenesisDryRunReport {
+    pub version: Version,
+    /// The state keys whose values would change, writes of the existing values are left out.
+    pub state_changes: Vec<(StateKey, StateChange)>,
+    pub state_root_hash: HashValue,
+    pub usage_before: StateStorageUsage,
+    pub usage_after: StateStorageUsage,
+}
+
+impl GenesisDryRunReport {
+    pub fn items_delta(&self) -> i64 {
+        self.usage_after.items() as i64 - self.usage_before.items() as i64
+    }
+
+    pub fn bytes_delta(&self) -> i64 {
+        self.usage_after.bytes() as i64 - self.usage_before.bytes() as i64
+    }
+}
+
 pub struct GenesisCommitter {
     db: Arc<dyn DbWriter>,
     output: ExecutedChunk,
@@ -97,6 +127,57 @@ impl GenesisCommitter {
         self.waypoint
     }
 
+    /// Reports the state changes the genesis would make on top of `base_state`, the state it's
+    /// calculated on.
+    pub fn dry_run_report(
+        &self,
+        db_reader: Arc<dyn DbReader>,
+        base_state: State,
+    ) -> Result<GenesisDryRunReport> {
+        let usage_before = base_state.usage();
+        let base_state_view =
+            CachedStateView::new(StateViewId::Miscellaneous, db_reader, base_state)?;
+
+        // Only the last write to each key counts.
+        let output = &self.output.output;
+        let to_commit = &output.execution_output.to_commit;
+        let mut writes = BTreeMap::new();
+        for txn_output in &to_commit.transaction_outputs {
+            for (key, write_op) in txn_output.write_set().write_op_iter() {
+                writes.insert(key, write_op.as_state_value_opt());
+            }
+        }
+
+        let mut state_changes = vec![];
+        for (key, new_value) in writes {
+            let old_value = base_state_view.get_state_value(key)?;
+            let change = match (old_value.as_ref(), new_value) {
+                (None, None) => continue,
+                (None, Some(_)) => StateChange::Creation,
+                (Some(_), None) => StateChange::Deletion,
+                (Some(old_value), Some(new_value)) => {
+                    if old_value == new_value {
+                        continue;
+                    }
+                    StateChange::Modification
+                },
+            };
+            state_changes.push((key.clone(), change));
+        }
+
+        Ok(GenesisDryRunReport {
+            version: self.waypoint.version(),
+            state_changes,
+            state_root_hash: output
+                .ensure_state_checkpoint_output()?
+                .state_summary
+                .last_checkpoint()
+                .root_hash(),
+            usage_before,
+            usage_after: output.result_state().latest().usage(),
+        })
+    }
+
     pub fn commit(self) -> Result<()> {
         self.db.save_transactions(
             self.output
@@ -5,8 +5,8 @@
 use aptos_logger::{prelude::*, sample, warn};
 use aptos_metrics_core::{
     exponential_buckets, register_histogram, register_histogram_vec, register_int_counter,
-    register_int_counter_vec, register_int_gauge_vec, Histogram, HistogramVec, IntCounter,
-    IntCounterVec, IntGaugeVec, TimerHelper,
+    register_int_counter_vec, register_int_gauge, register_int_gauge_vec, Histogram, HistogramVec,
+    IntCounter, IntCounterVec, IntGauge, IntGaugeVec, TimerHelper,
 };
 use aptos_types::{
     contract_event::ContractEvent,
@@ -158,6 +158,23 @@ pub static TRANSACTIONS_SAVED: Lazy<Histogram> = Lazy::new(|| {
     .unwrap()
 });
 
+pub static COMMIT_AHEAD_BUFFER_SIZE: Lazy<IntGauge> = Lazy::new(|| {
+    register_int_gauge!(
+        "aptos_executor_commit_ahead_buffer_size",
+        "Number of ledger infos waiting for their blocks to be pre-committed."
+    )
+    .unwrap()
+});
+
+pub static COMMIT_AHEAD_LEDGER_INFOS: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "aptos_executor_commit_ahead_ledger_infos",
+        "Number of ledger infos that arrived ahead of their blocks, by what happened to them.",
+        &["result"]
+    )
+    .unwrap()
+});
+
 //////////////////////////////////////
 // EXECUTED TRANSACTION STATS COUNTERS
 //////////////////////////////////////
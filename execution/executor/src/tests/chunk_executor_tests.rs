@@ -207,6 +207,43 @@ fn test_executor_execute_and_commit_chunk_restart() {
     }
 }
 
+#[test]
+#[cfg_attr(feature = "consensus-only-perf-test", ignore)]
+fn test_executor_execute_and_commit_overlapping_chunks() {
+    let (chunks, ledger_info) = create_transaction_chunks(vec![1..=30, 21..=60, 51..=70]);
+
+    let TestExecutor {
+        _path,
+        db,
+        executor,
+    } = TestExecutor::new();
+
+    executor
+        .execute_chunk(chunks[0].clone(), &ledger_info, None)
+        .unwrap();
+    executor.commit_chunk().unwrap();
+    assert_eq!(db.reader.expect_synced_version(), 30);
+
+    // The already applied transactions at the beginning of the chunk are skipped.
+    executor
+        .execute_chunk(chunks[1].clone(), &ledger_info, None)
+        .unwrap();
+    executor.commit_chunk().unwrap();
+    assert_eq!(db.reader.expect_synced_version(), 60);
+
+    // A chunk that is applied entirely is rejected.
+    assert!(executor
+        .execute_chunk(chunks[0].clone(), &ledger_info, None)
+        .is_err());
+
+    executor
+        .execute_chunk(chunks[2].clone(), &ledger_info, None)
+        .unwrap();
+    executor.commit_chunk().unwrap();
+    let li = db.reader.get_latest_ledger_info().unwrap();
+    assert_eq!(li, ledger_info);
+}
+
 #[test]
 #[cfg_attr(feature = "consensus-only-perf-test", ignore)]
 fn test_executor_execute_and_commit_chunk_local_result_mismatch() {
@@ -70,7 +70,7 @@ impl VMBlockExecutor for MockVM {
         &self,
         txn_provider: &DefaultTxnProvider<SignatureVerifiedTransaction, AuxiliaryInfo>,
         state_view: &impl StateView,
-        _onchain_config: BlockExecutorConfigFromOnchain,
+        onchain_config: BlockExecutorConfigFromOnchain,
         transaction_slice_metadata: TransactionSliceMetadata,
     ) -> Result<BlockOutput<SignatureVerifiedTransaction, TransactionOutput>, VMStatus> {
         // output_cache is used to store the output of transactions so they are visible to later
@@ -78,9 +78,16 @@ impl VMBlockExecutor for MockVM {
         let mut output_cache = HashMap::new();
         let mut outputs = vec![];
 
+        // Every user transaction uses one unit of gas towards the block gas limit, if any. The
+        // transactions past the limit are retried, like with the real block executor.
+        let block_gas_limit = onchain_config.block_gas_limit_type.block_gas_limit();
+        let mut num_user_txns_executed = 0;
         let mut skip_rest = false;
         for idx in 0..txn_provider.num_txns() {
-            if skip_rest {
+            let txn = txn_provider.get_txn(idx as u32).expect_valid();
+            let block_limit_reached = txn.try_as_signed_user_txn().is_some()
+                && block_gas_limit.is_some_and(|limit| num_user_txns_executed >= limit);
+            if skip_rest || block_limit_reached {
                 outputs.push(TransactionOutput::new(
                     WriteSet::default(),
                     vec![],
@@ -91,7 +98,6 @@ impl VMBlockExecutor for MockVM {
                 continue;
             }
 
-            let txn = txn_provider.get_txn(idx as u32).expect_valid();
             if matches!(
                 txn,
                 Transaction::StateCheckpoint(_) | Transaction::BlockEpilogue(_)
@@ -132,6 +138,7 @@ impl VMBlockExecutor for MockVM {
                 continue;
             }
 
+            num_user_txns_executed += 1;
             match decode_transaction(txn.try_as_signed_user_txn().unwrap()) {
                 MockVMTransaction::Mint { sender, amount } => {
                     let old_balance = read_balance(&output_cache, state_view, sender);
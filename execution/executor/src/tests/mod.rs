@@ -5,7 +5,10 @@
 use crate::{
     block_executor::BlockExecutor,
     db_bootstrapper::{generate_waypoint, maybe_bootstrap},
-    workflow::{do_get_execution_output::DoGetExecutionOutput, ApplyExecutionOutput},
+    workflow::{
+        do_get_execution_output::DoGetExecutionOutput, do_state_checkpoint::DoStateCheckpoint,
+        ApplyExecutionOutput,
+    },
 };
 use aptos_crypto::{ed25519::Ed25519PrivateKey, HashValue, PrivateKey, SigningKey, Uniform};
 use aptos_db::AptosDB;
@@ -13,12 +16,15 @@ use aptos_executor_types::{
     BlockExecutorTrait, ChunkExecutorTrait, TransactionReplayer, VerifyExecutionMode,
 };
 use aptos_storage_interface::{
-    state_store::state_view::cached_state_view::CachedStateView, DbReaderWriter, LedgerSummary,
-    Result,
+    state_store::{
+        state_summary::ProvableStateSummary, state_view::cached_state_view::CachedStateView,
+    },
+    DbReaderWriter, LedgerSummary, Result,
 };
 use aptos_types::{
     account_address::AccountAddress,
     aggregate_signature::AggregateSignature,
+    block_executor::config::BlockExecutorConfigFromOnchain,
     block_info::BlockInfo,
     bytes::NumToBytes,
     chain_id::ChainId,
@@ -300,6 +306,80 @@ fn test_executor_commit_twice() {
     executor.commit_ledger(ledger_info).unwrap();
 }
 
+#[test]
+fn test_executor_commit_ledger_ahead() {
+    let executor = TestExecutor::new();
+    let parent_block_id = executor.committed_block_id();
+    let block1_id = gen_block_id(1);
+    let block2_id = gen_block_id(2);
+
+    let output1 = executor
+        .execute_block(
+            (
+                block1_id,
+                block(vec![encode_mint_transaction(gen_address(1), 100)]),
+            )
+                .into(),
+            parent_block_id,
+            TEST_BLOCK_EXECUTOR_ONCHAIN_CONFIG,
+        )
+        .unwrap();
+    let output2 = executor
+        .execute_block(
+            (
+                block2_id,
+                block(vec![encode_mint_transaction(gen_address(2), 100)]),
+            )
+                .into(),
+            block1_id,
+            TEST_BLOCK_EXECUTOR_ONCHAIN_CONFIG,
+        )
+        .unwrap();
+
+    // The ledger infos arrive before the blocks are pre-committed.
+    let ledger_info1 = gen_ledger_info(
+        output1.expect_last_version(),
+        output1.root_hash(),
+        block1_id,
+        1,
+    );
+    let ledger_info2 = gen_ledger_info(
+        output2.expect_last_version(),
+        output2.root_hash(),
+        block2_id,
+        2,
+    );
+    // A ledger info of a block that's never executed here, e.g. on a fork.
+    let forked_ledger_info = gen_ledger_info(
+        output1.expect_last_version() - 1,
+        HashValue::random(),
+        gen_block_id(100),
+        1,
+    );
+    executor.commit_ledger_ahead(ledger_info2.clone()).unwrap();
+    executor.commit_ledger_ahead(forked_ledger_info).unwrap();
+    executor.commit_ledger_ahead(ledger_info1.clone()).unwrap();
+    assert_eq!(
+        executor.db.reader.get_latest_ledger_info_version().unwrap(),
+        0
+    );
+
+    // The forked ledger info is dropped without failing the pre-commit.
+    executor.pre_commit_block(block1_id).unwrap();
+    assert_eq!(
+        executor.db.reader.get_latest_ledger_info().unwrap(),
+        ledger_info1
+    );
+    executor.pre_commit_block(block2_id).unwrap();
+    assert_eq!(
+        executor.db.reader.get_latest_ledger_info().unwrap(),
+        ledger_info2
+    );
+
+    // Ledger infos of already committed blocks are ignored.
+    executor.commit_ledger_ahead(ledger_info1).unwrap();
+}
+
 #[test]
 fn test_executor_execute_same_block_multiple_times() {
     let executor = TestExecutor::new();
@@ -624,6 +704,151 @@ fn test_deleted_key_from_state_store() {
     assert_eq!(state_value_from_db1, StateValue::from(dummy_value1));
 }
 
+#[test]
+fn test_state_checkpoint_in_two_phases() {
+    let executor = TestExecutor::new();
+    let db = &executor.db;
+    let ledger_summary: LedgerSummary = db.reader.get_pre_committed_ledger_summary().unwrap();
+
+    let write = |writes: &[(&str, u64)]| {
+        TransactionOutput::new(
+            WriteSetMut::new(
+                writes
+                    .iter()
+                    .map(|(key, value)| {
+                        (
+                            StateKey::raw(key.as_bytes()),
+                            WriteOp::legacy_modification(value.le_bytes()),
+                        )
+                    })
+                    .collect(),
+            )
+            .freeze()
+            .unwrap(),
+            vec![],
+            0,
+            TransactionStatus::Keep(ExecutionStatus::Success),
+            TransactionAuxiliaryData::default(),
+        )
+    };
+    // Writes on both sides of the checkpoint, so that both phases have something to hash.
+    let txns = vec![
+        create_test_transaction(0),
+        Transaction::StateCheckpoint(HashValue::random()),
+        create_test_transaction(1),
+    ];
+    let txn_outs = vec![
+        write(&[("test_key1", 1), ("test_key2", 2)]),
+        write(&[]),
+        write(&[("test_key1", 3)]),
+    ];
+    let aux_info = (0..txns.len())
+        .map(|i| {
+            AuxiliaryInfo::new(
+                PersistedAuxiliaryInfo::V1 {
+                    transaction_index: i as u32,
+                },
+                None,
+            )
+        })
+        .collect();
+    let state_view = CachedStateView::new(
+        StateViewId::Miscellaneous,
+        db.reader.clone(),
+        ledger_summary.state.latest().clone(),
+    )
+    .unwrap();
+    let execution_output = DoGetExecutionOutput::by_transaction_output(
+        txns,
+        txn_outs,
+        aux_info,
+        &ledger_summary.state,
+        state_view,
+    )
+    .unwrap();
+
+    let pending = DoStateCheckpoint::run_checkpoint(
+        &execution_output,
+        &ledger_summary.state_summary,
+        db.reader.clone(),
+        None,
+    )
+    .unwrap();
+    let state_checkpoint_hashes = pending.state_checkpoint_hashes().to_vec();
+    let two_phases = pending.finalize().unwrap();
+
+    let one_pass = ledger_summary
+        .state_summary
+        .update(
+            &ProvableStateSummary::new_persisted(db.reader.as_ref()).unwrap(),
+            execution_output.to_commit.state_update_refs(),
+        )
+        .unwrap();
+
+    assert_eq!(state_checkpoint_hashes, vec![
+        None,
+        Some(one_pass.last_checkpoint().root_hash()),
+        None
+    ]);
+    assert_eq!(two_phases.state_checkpoint_hashes, state_checkpoint_hashes);
+    assert_eq!(
+        two_phases.state_summary.last_checkpoint().root_hash(),
+        one_pass.last_checkpoint().root_hash()
+    );
+    assert_eq!(
+        two_phases.state_summary.latest().root_hash(),
+        one_pass.latest().root_hash()
+    );
+    assert_ne!(
+        one_pass.last_checkpoint().root_hash(),
+        one_pass.latest().root_hash()
+    );
+}
+
+#[test]
+fn test_state_checkpoint_stage_ahead_of_ledger_update() {
+    let executor = TestExecutor::new();
+    let executor_sequential = TestExecutor::new();
+    let mut parent_block_id = executor.committed_block_id();
+
+    // All blocks are executed before any ledger update, so that the state checkpoint stage runs
+    // ahead through the chain, overlapping with the ledger updates below.
+    let block_ids = (0..5)
+        .map(|i| {
+            let block_id = gen_block_id(i + 1);
+            let txns = (0..10)
+                .map(|j| encode_mint_transaction(gen_address(i * 10 + j), 100))
+                .collect::<Vec<_>>();
+            executor
+                .execute_and_update_state(
+                    (block_id, block(txns.clone())).into(),
+                    parent_block_id,
+                    TEST_BLOCK_EXECUTOR_ONCHAIN_CONFIG,
+                )
+                .unwrap();
+            let expected = executor_sequential
+                .execute_block(
+                    (block_id, block(txns)).into(),
+                    parent_block_id,
+                    TEST_BLOCK_EXECUTOR_ONCHAIN_CONFIG,
+                )
+                .unwrap();
+            let parent = parent_block_id;
+            parent_block_id = block_id;
+            (block_id, parent, expected)
+        })
+        .collect::<Vec<_>>();
+
+    for (block_id, parent_block_id, expected) in block_ids {
+        let output = executor.ledger_update(block_id, parent_block_id).unwrap();
+        assert_eq!(output.root_hash(), expected.root_hash());
+        assert_eq!(
+            output.transaction_info_hashes(),
+            expected.transaction_info_hashes()
+        );
+    }
+}
+
 #[test]
 fn test_reconfig_suffix_empty_blocks() {
     let TestExecutor {
@@ -681,6 +906,52 @@ fn test_reconfig_suffix_empty_blocks() {
         .unwrap();
 }
 
+#[test]
+fn test_user_txns_to_retry_after_block_gas_limit() {
+    let TestExecutor {
+        _path,
+        db: _,
+        executor,
+    } = TestExecutor::new();
+    let onchain_config = BlockExecutorConfigFromOnchain::new_maybe_block_limit(Some(3));
+    let block_a = TestBlock::new(5, 1, gen_block_id(1));
+    let output_a = executor
+        .execute_block(
+            (block_a.id, block_a.txns.clone()).into(),
+            executor.committed_block_id(),
+            onchain_config.clone(),
+        )
+        .unwrap();
+
+    // The transactions past the block gas limit are to be retried, in order
+    let expected_to_retry: Vec<_> = block_a.inner_txns()[3..]
+        .iter()
+        .map(|txn| txn.try_as_signed_user_txn().unwrap().clone())
+        .collect();
+    assert_eq!(
+        output_a.user_txns_to_retry().cloned().collect_vec(),
+        expected_to_retry
+    );
+
+    // Once carried over into the next block, they are executed
+    let block_b = TestBlock {
+        txns: block_a.txns[3..].to_vec(),
+        id: gen_block_id(2),
+    };
+    let output_b = executor
+        .execute_block(
+            (block_b.id, block_b.txns).into(),
+            block_a.id,
+            onchain_config,
+        )
+        .unwrap();
+    assert_eq!(output_b.user_txns_to_retry().count(), 0);
+    assert!(output_b
+        .compute_status_for_input_txns()
+        .iter()
+        .all(|status| matches!(status, TransactionStatus::Keep(_))));
+}
+
 struct TestBlock {
     txns: Vec<SignatureVerifiedTransaction>,
     id: HashValue,
@@ -4,6 +4,7 @@
 use crate::{
     metrics,
     metrics::{EXECUTOR_ERRORS, OTHER_TIMERS},
+    workflow::do_prefetch_state::DoPrefetchState,
 };
 use anyhow::{anyhow, ensure, Result};
 use aptos_block_executor::txn_provider::default::DefaultTxnProvider;
@@ -23,8 +24,9 @@ use aptos_executor_types::{
 use aptos_experimental_runtimes::thread_manager::THREAD_MANAGER;
 use aptos_logger::prelude::*;
 use aptos_metrics_core::TimerHelper;
-use aptos_storage_interface::state_store::{
-    state::LedgerState, state_view::cached_state_view::CachedStateView,
+use aptos_storage_interface::{
+    block_execution_stats::BlockExecutionStats,
+    state_store::{state::LedgerState, state_view::cached_state_view::CachedStateView},
 };
 #[cfg(feature = "consensus-only-perf-test")]
 use aptos_types::transaction::ExecutionStatus;
@@ -43,14 +45,15 @@ use aptos_types::{
     },
     transaction::{
         signature_verified_transaction::SignatureVerifiedTransaction, AuxiliaryInfo,
-        AuxiliaryInfoTrait, BlockOutput, PersistedAuxiliaryInfo, Transaction, TransactionOutput,
-        TransactionStatus, Version,
+        AuxiliaryInfoTrait, BlockConflictStats, BlockOutput, PersistedAuxiliaryInfo,
+        StateBytesDeltaRecord, Transaction, TransactionOutput, TransactionStatus, Version,
     },
     write_set::{HotStateOp, TransactionWrite, WriteSet},
 };
 use aptos_vm::VMBlockExecutor;
 use itertools::Itertools;
-use std::sync::Arc;
+use rayon::prelude::*;
+use std::{collections::HashMap, sync::Arc};
 
 pub struct DoGetExecutionOutput;
 
@@ -111,14 +114,23 @@ impl DoGetExecutionOutput {
         onchain_config: BlockExecutorConfigFromOnchain,
         transaction_slice_metadata: TransactionSliceMetadata,
     ) -> Result<ExecutionOutput> {
+        let prefetch_keys = DoPrefetchState::keys_to_prefetch(
+            transactions
+                .iter()
+                .map(SignatureVerifiedTransaction::borrow_into_inner),
+        );
         let txn_provider = DefaultTxnProvider::new(transactions, auxiliary_infos.clone());
-        let block_output = Self::execute_block::<V>(
-            executor,
-            &txn_provider,
-            &state_view,
-            onchain_config,
-            transaction_slice_metadata,
-        )?;
+        // Warm the state view alongside execution, which reads through the same cache.
+        let block_output = state_view.prefetch_while(&prefetch_keys, || {
+            Self::execute_block::<V>(
+                executor,
+                &txn_provider,
+                &state_view,
+                onchain_config,
+                transaction_slice_metadata,
+            )
+        })?;
+        let conflict_stats = block_output.conflict_stats();
         let (mut transaction_outputs, block_epilogue_txn) = block_output.into_inner();
         let (transactions, mut auxiliary_infos) = txn_provider.into_inner();
         let mut transactions = transactions
@@ -181,6 +193,7 @@ impl DoGetExecutionOutput {
             transaction_slice_metadata
                 .append_state_checkpoint_to_block()
                 .is_some(),
+            conflict_stats,
         )
     }
 
@@ -218,6 +231,7 @@ impl DoGetExecutionOutput {
             state_view,
             false, // prime_state_cache
             append_state_checkpoint_to_block.is_some(),
+            None, // conflict_stats
         )
     }
 
@@ -237,6 +251,7 @@ impl DoGetExecutionOutput {
             state_view,
             true,  // prime state cache
             false, // is_block
+            None,  // conflict_stats
         )?;
 
         let ret = out.clone();
@@ -350,6 +365,7 @@ impl Parser {
         base_state_view: CachedStateView,
         prime_state_cache: bool,
         is_block: bool,
+        conflict_stats: Option<BlockConflictStats>,
     ) -> Result<ExecutionOutput> {
         let _timer = OTHER_TIMERS.timer_with(&["parse_raw_output"]);
 
@@ -375,6 +391,8 @@ impl Parser {
             &mut persisted_auxiliary_infos,
         );
 
+        Self::attach_state_bytes_deltas(&mut transaction_outputs, &base_state_view)?;
+
         let mut block_end_info = None;
         if is_block && !has_reconfig {
             if let Some(Transaction::BlockEpilogue(payload)) = transactions.last() {
@@ -410,6 +428,16 @@ impl Parser {
             base_state_view.memorized_reads(),
         );
         let state_reads = base_state_view.into_memorized_reads();
+        let block_execution_stats = is_block.then(|| {
+            BlockExecutionStats::new(
+                first_version,
+                &statuses_for_input_txns,
+                &to_commit.transaction_outputs,
+                conflict_stats,
+                parent_state.latest().usage(),
+                result_state.latest().usage(),
+            )
+        });
 
         let out = ExecutionOutput::new(
             is_block,
@@ -421,6 +449,7 @@ impl Parser {
             result_state,
             state_reads,
             block_end_info,
+            block_execution_stats,
             next_epoch_state,
             Planned::place_holder(),
         );
@@ -432,6 +461,61 @@ impl Parser {
         Ok(ret)
     }
 
+    /// Attaches to each output the state items and bytes it adds, so that users can tell how much
+    /// of the fee is driven by storage. The replaced values are the ones written earlier in the
+    /// block, or otherwise the ones in the parent state.
+    fn attach_state_bytes_deltas(
+        transaction_outputs: &mut [TransactionOutput],
+        base_state_view: &CachedStateView,
+    ) -> Result<()> {
+        let _timer = OTHER_TIMERS.timer_with(&["parse_raw_output__state_bytes_deltas"]);
+
+        let records = {
+            // The size of the latest value of each key written in the block, `None` if deleted,
+            // starting from the ones in the parent state, which are read in parallel upfront.
+            let mut sizes: HashMap<&StateKey, Option<usize>> = {
+                let keys = transaction_outputs
+                    .iter()
+                    .flat_map(|output| output.write_set().write_op_iter().map(|(key, _op)| key))
+                    .unique()
+                    .collect_vec();
+                THREAD_MANAGER.get_exe_cpu_pool().install(|| {
+                    keys.into_par_iter()
+                        .map(|key| -> Result<_> {
+                            let slot = base_state_view.get_state_slot(key)?;
+                            Ok((key, slot.is_occupied().then(|| slot.size())))
+                        })
+                        .collect::<Result<_>>()
+                })?
+            };
+            transaction_outputs
+                .iter()
+                .map(|output| {
+                    let mut record = StateBytesDeltaRecord::default();
+                    for (key, op) in output.write_set().write_op_iter() {
+                        let key_size = key.size() as i64;
+                        if let Some(size) = sizes[key] {
+                            record.items_delta -= 1;
+                            record.bytes_delta -= key_size + size as i64;
+                        }
+                        let new_size = op.as_state_value_opt().map(StateValue::size);
+                        if let Some(size) = new_size {
+                            record.items_delta += 1;
+                            record.bytes_delta += key_size + size as i64;
+                        }
+                        sizes.insert(key, new_size);
+                    }
+                    record
+                })
+                .collect_vec()
+        };
+
+        for (output, record) in transaction_outputs.iter_mut().zip_eq(records) {
+            output.add_auxiliary_record(&record)?;
+        }
+        Ok(())
+    }
+
     fn get_subscribable_events(out: &ExecutionOutput) -> Vec<ContractEvent> {
         out.to_commit
             .transaction_outputs
@@ -564,13 +648,16 @@ mod tests {
     };
     use aptos_types::{
         contract_event::ContractEvent,
+        state_store::state_key::StateKey,
         transaction::{
-            AuxiliaryInfo, ExecutionStatus, PersistedAuxiliaryInfo, Transaction,
-            TransactionAuxiliaryData, TransactionOutput, TransactionStatus,
+            AuxiliaryInfo, AuxiliaryInfoTrait, ExecutionStatus, PersistedAuxiliaryInfo,
+            StateBytesDeltaRecord, Transaction, TransactionAuxiliaryData, TransactionOutput,
+            TransactionStatus,
         },
         vm_status::StatusCode,
-        write_set::WriteSet,
+        write_set::{WriteOp, WriteSet, WriteSetMut},
     };
+    use itertools::Itertools;
 
     #[test]
     fn should_filter_subscribable_events() {
@@ -624,6 +711,7 @@ mod tests {
             CachedStateView::new_dummy(&state),
             false,
             false,
+            None,
         )
         .unwrap();
         assert_eq!(
@@ -632,6 +720,64 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_attach_state_bytes_deltas() {
+        let key = StateKey::raw(b"key");
+        let key_size = key.size() as i64;
+        let write_set = |op: WriteOp| WriteSetMut::new(vec![(key.clone(), op)]).freeze().unwrap();
+        let txn_outs = [
+            write_set(WriteOp::legacy_creation(vec![0; 10].into())),
+            write_set(WriteOp::legacy_modification(vec![0; 4].into())),
+            write_set(WriteOp::legacy_deletion()),
+        ]
+        .into_iter()
+        .map(|write_set| {
+            TransactionOutput::new(
+                write_set,
+                vec![],
+                0,
+                TransactionStatus::Keep(ExecutionStatus::Success),
+                TransactionAuxiliaryData::default(),
+            )
+        })
+        .collect_vec();
+        let txns = vec![Transaction::dummy(); txn_outs.len()];
+        let auxiliary_infos = vec![AuxiliaryInfo::new_empty(); txn_outs.len()];
+
+        let state = LedgerState::new_empty();
+        let execution_output = Parser::parse(
+            0,
+            txns,
+            txn_outs,
+            auxiliary_infos,
+            &state,
+            CachedStateView::new_dummy(&state),
+            false,
+            false,
+            None,
+        )
+        .unwrap();
+
+        let deltas = execution_output
+            .to_commit
+            .transaction_outputs
+            .iter()
+            .map(|output| {
+                let record = output
+                    .auxiliary_data()
+                    .get_record::<StateBytesDeltaRecord>()
+                    .unwrap()
+                    .unwrap();
+                (record.items_delta, record.bytes_delta)
+            })
+            .collect_vec();
+        assert_eq!(deltas, vec![
+            (1, key_size + 10),
+            (0, -6),
+            (-1, -(key_size + 4))
+        ]);
+    }
+
     #[test]
     fn test_extract_retry_and_discard_no_reconfig() {
         let mut txns = vec![
@@ -5,8 +5,8 @@ use crate::metrics::OTHER_TIMERS;
 use anyhow::Result;
 use aptos_crypto::{hash::CryptoHash, HashValue};
 use aptos_executor_types::{
-    execution_output::ExecutionOutput, state_checkpoint_output::StateCheckpointOutput,
-    transactions_with_output::TransactionsWithOutput, LedgerUpdateOutput,
+    execution_output::ExecutionOutput, transactions_with_output::TransactionsWithOutput,
+    LedgerUpdateOutput,
 };
 use aptos_experimental_runtimes::thread_manager::optimal_min_len;
 use aptos_metrics_core::TimerHelper;
@@ -23,7 +23,7 @@ pub struct DoLedgerUpdate;
 impl DoLedgerUpdate {
     pub fn run(
         execution_output: &ExecutionOutput,
-        state_checkpoint_output: &StateCheckpointOutput,
+        state_checkpoint_hashes: Vec<Option<HashValue>>,
         parent_accumulator: Arc<InMemoryTransactionAccumulator>,
     ) -> Result<LedgerUpdateOutput> {
         let _timer = OTHER_TIMERS.timer_with(&["do_ledger_update"]);
@@ -36,7 +36,7 @@ impl DoLedgerUpdate {
         // Assemble `TransactionInfo`s
         let transaction_infos = Self::assemble_transaction_infos(
             &execution_output.to_commit,
-            state_checkpoint_output.state_checkpoint_hashes.clone(),
+            state_checkpoint_hashes,
             event_hashes,
             writeset_hashes,
         );
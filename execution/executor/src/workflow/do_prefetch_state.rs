@@ -0,0 +1,156 @@
+// Copyright (c) Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::metrics::OTHER_TIMERS;
+use aptos_metrics_core::TimerHelper;
+use aptos_types::{
+    account_address::AccountAddress,
+    account_config::{primary_apt_store, AccountResource, CoinStoreResource, ObjectGroupResource},
+    state_store::state_key::StateKey,
+    transaction::{SignedTransaction, Transaction, TransactionExecutableRef},
+    AptosCoinType,
+};
+use move_core_types::move_resource::MoveStructType;
+use std::collections::HashSet;
+
+/// Derives the keys a block's transactions are most likely to read, for the state view to warm
+/// its cache with (see `CachedStateView::prefetch_while`), so that the storage reads overlap with
+/// signature verification or execution instead of stalling the latter. Only keys that can be
+/// derived from the transactions themselves are considered: the accounts, coin stores and primary
+/// APT stores of the signers, and the same for any address passed to an entry function (which
+/// also covers objects passed as arguments).
+pub struct DoPrefetchState;
+
+impl DoPrefetchState {
+    pub fn keys_to_prefetch<'a>(
+        transactions: impl IntoIterator<Item = &'a Transaction>,
+    ) -> Vec<StateKey> {
+        let _timer = OTHER_TIMERS.timer_with(&["do_prefetch_state__keys"]);
+
+        let mut addresses = HashSet::new();
+        for txn in transactions {
+            if let Transaction::UserTransaction(txn) = txn {
+                Self::collect_addresses(txn, &mut addresses);
+            }
+        }
+
+        addresses
+            .into_iter()
+            .flat_map(|address| {
+                [
+                    StateKey::resource_typed::<AccountResource>(&address),
+                    StateKey::resource_typed::<CoinStoreResource<AptosCoinType>>(&address),
+                ]
+                .into_iter()
+                .filter_map(Result::ok)
+                .chain([
+                    StateKey::resource_group(&address, &ObjectGroupResource::struct_tag()),
+                    StateKey::resource_group(
+                        &primary_apt_store(address),
+                        &ObjectGroupResource::struct_tag(),
+                    ),
+                ])
+            })
+            .collect()
+    }
+
+    fn collect_addresses(txn: &SignedTransaction, addresses: &mut HashSet<AccountAddress>) {
+        let authenticator = txn.authenticator_ref();
+        addresses.insert(txn.sender());
+        addresses.extend(authenticator.secondary_signer_addresses());
+        addresses.extend(authenticator.fee_payer_address());
+
+        if let Ok(TransactionExecutableRef::EntryFunction(entry_function)) = txn.executable_ref() {
+            addresses.extend(
+                entry_function
+                    .args()
+                    .iter()
+                    .filter(|arg| arg.len() == AccountAddress::LENGTH)
+                    .filter_map(|arg| bcs::from_bytes::<AccountAddress>(arg).ok()),
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DoPrefetchState;
+    use aptos_crypto::{ed25519::Ed25519PrivateKey, PrivateKey, SigningKey, Uniform};
+    use aptos_storage_interface::state_store::{
+        state::State, state_view::cached_state_view::CachedStateView,
+    };
+    use aptos_types::{
+        account_address::AccountAddress,
+        account_config::{AccountResource, ObjectGroupResource},
+        chain_id::ChainId,
+        state_store::state_key::StateKey,
+        transaction::{EntryFunction, RawTransaction, SignedTransaction, Transaction},
+    };
+    use move_core_types::{
+        identifier::Identifier, language_storage::ModuleId, move_resource::MoveStructType,
+    };
+
+    fn transfer(sender: AccountAddress, recipient: AccountAddress) -> Transaction {
+        let private_key = Ed25519PrivateKey::generate_for_testing();
+        let entry_function = EntryFunction::new(
+            ModuleId::new(AccountAddress::ONE, Identifier::new("coin").unwrap()),
+            Identifier::new("transfer").unwrap(),
+            vec![],
+            vec![
+                bcs::to_bytes(&recipient).unwrap(),
+                bcs::to_bytes(&1u64).unwrap(),
+            ],
+        );
+        let raw_transaction = RawTransaction::new_entry_function(
+            sender,
+            0,
+            entry_function,
+            0,
+            0,
+            0,
+            ChainId::new(10),
+        );
+        let signature = private_key.sign(&raw_transaction).unwrap();
+        Transaction::UserTransaction(SignedTransaction::new(
+            raw_transaction,
+            private_key.public_key(),
+            signature,
+        ))
+    }
+
+    #[test]
+    fn test_keys_to_prefetch() {
+        let sender = AccountAddress::random();
+        let recipient = AccountAddress::random();
+        let transactions = [
+            Transaction::dummy(),
+            transfer(sender, recipient),
+            transfer(recipient, sender),
+        ];
+
+        let keys = DoPrefetchState::keys_to_prefetch(&transactions);
+        // The accounts, coin stores, object groups and primary APT stores of both addresses.
+        assert_eq!(keys.len(), 8);
+        for address in [sender, recipient] {
+            assert!(keys.contains(&StateKey::resource_typed::<AccountResource>(&address).unwrap()));
+            assert!(keys.contains(&StateKey::resource_group(
+                &address,
+                &ObjectGroupResource::struct_tag()
+            )));
+        }
+    }
+
+    #[test]
+    fn test_prefetch_while() {
+        let keys = DoPrefetchState::keys_to_prefetch(&[transfer(
+            AccountAddress::random(),
+            AccountAddress::random(),
+        )]);
+        let state_view = CachedStateView::new_dummy(&State::new_empty());
+
+        assert_eq!(state_view.prefetch_while(&keys, || 42), 42);
+        for key in &keys {
+            assert!(state_view.memorized_reads().get_cloned(key).is_some());
+        }
+    }
+}
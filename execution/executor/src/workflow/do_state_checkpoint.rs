@@ -5,13 +5,26 @@ use crate::metrics::OTHER_TIMERS;
 use anyhow::{ensure, Result};
 use aptos_crypto::HashValue;
 use aptos_executor_types::{
-    execution_output::ExecutionOutput, state_checkpoint_output::StateCheckpointOutput,
+    execution_output::ExecutionOutput,
+    planned::{Plan, Planned},
+    state_checkpoint_output::StateCheckpointOutput,
 };
+use aptos_experimental_runtimes::thread_manager::THREAD_MANAGER;
 use aptos_metrics_core::TimerHelper;
-use aptos_storage_interface::state_store::state_summary::{
-    LedgerStateSummary, ProvableStateSummary,
+use aptos_storage_interface::{
+    state_store::state_summary::{LedgerStateSummary, ProvableStateSummary, StateSummary},
+    DbReader,
 };
+use std::sync::Arc;
 
+/// State hashing happens in two phases. The first one hashes the state up to the last checkpoint
+/// of the chunk, the root hash of which goes into the `TransactionInfo`s, so the ledger update
+/// can start right after it. The second one hashes the state after the last checkpoint, which
+/// is only needed by the next chunk and at commit, so it runs on a background pool meanwhile.
+///
+/// The split is by version rather than by tree level: the root hash the ledger commits to
+/// depends on every subtree below it, so no part of the state up to the last checkpoint can be
+/// left for later.
 pub struct DoStateCheckpoint;
 
 impl DoStateCheckpoint {
@@ -23,15 +36,19 @@ impl DoStateCheckpoint {
     ) -> Result<StateCheckpointOutput> {
         let _timer = OTHER_TIMERS.timer_with(&["do_state_checkpoint"]);
 
-        let state_summary = parent_state_summary.update(
+        let last_checkpoint = parent_state_summary.update_last_checkpoint(
             persisted_state_summary,
             execution_output.to_commit.state_update_refs(),
         )?;
-
         let state_checkpoint_hashes = Self::get_state_checkpoint_hashes(
             execution_output,
             known_state_checkpoints,
-            &state_summary,
+            &last_checkpoint,
+        )?;
+        let state_summary = parent_state_summary.update_latest(
+            last_checkpoint,
+            persisted_state_summary,
+            execution_output.to_commit.state_update_refs(),
         )?;
 
         Ok(StateCheckpointOutput::new(
@@ -40,10 +57,52 @@ impl DoStateCheckpoint {
         ))
     }
 
+    /// Runs the first phase and leaves the second one on the background thread pool, see
+    /// [`PendingStateCheckpoint::finalize`]. Callers run on the non-execution pool and wait for
+    /// the second phase, so it must not be planned onto that pool.
+    pub fn run_checkpoint(
+        execution_output: &ExecutionOutput,
+        parent_state_summary: &LedgerStateSummary,
+        db: Arc<dyn DbReader>,
+        known_state_checkpoints: Option<Vec<Option<HashValue>>>,
+    ) -> Result<PendingStateCheckpoint> {
+        let _timer = OTHER_TIMERS.timer_with(&["do_state_checkpoint__checkpoint"]);
+
+        let persisted_state_summary = ProvableStateSummary::new_persisted(db.as_ref())?;
+        let last_checkpoint = parent_state_summary.update_last_checkpoint(
+            &persisted_state_summary,
+            execution_output.to_commit.state_update_refs(),
+        )?;
+
+        let state_checkpoint_hashes = Self::get_state_checkpoint_hashes(
+            execution_output,
+            known_state_checkpoints,
+            &last_checkpoint,
+        )?;
+
+        let execution_output = execution_output.clone();
+        let parent_state_summary = parent_state_summary.clone();
+        let persisted_state_summary = (*persisted_state_summary).clone();
+        let state_summary = THREAD_MANAGER.get_background_pool().plan(move || {
+            let _timer = OTHER_TIMERS.timer_with(&["do_state_checkpoint__latest"]);
+
+            parent_state_summary.update_latest(
+                last_checkpoint,
+                &ProvableStateSummary::new(persisted_state_summary, db.as_ref()),
+                execution_output.to_commit.state_update_refs(),
+            )
+        });
+
+        Ok(PendingStateCheckpoint {
+            state_summary,
+            state_checkpoint_hashes,
+        })
+    }
+
     fn get_state_checkpoint_hashes(
         execution_output: &ExecutionOutput,
         known_state_checkpoints: Option<Vec<Option<HashValue>>>,
-        state_summary: &LedgerStateSummary,
+        last_checkpoint: &StateSummary,
     ) -> Result<Vec<Option<HashValue>>> {
         let _timer = OTHER_TIMERS.timer_with(&["get_state_checkpoint_hashes"]);
 
@@ -62,10 +121,10 @@ impl DoStateCheckpoint {
             );
             if let Some(idx) = last_checkpoint_index {
                 ensure!(
-                    known[idx] == Some(state_summary.last_checkpoint().root_hash()),
+                    known[idx] == Some(last_checkpoint.root_hash()),
                     "Root hash mismatch with known hashes passed in. {:?} vs {:?}",
                     known[idx],
-                    Some(&state_summary.last_checkpoint().root_hash()),
+                    Some(&last_checkpoint.root_hash()),
                 );
             }
 
@@ -79,10 +138,34 @@ impl DoStateCheckpoint {
             let mut out = vec![None; num_txns];
 
             if let Some(index) = last_checkpoint_index {
-                out[index] = Some(state_summary.last_checkpoint().root_hash());
+                out[index] = Some(last_checkpoint.root_hash());
             }
 
             Ok(out)
         }
     }
 }
+
+/// The result of the first phase of [`DoStateCheckpoint`], with the second one in flight.
+pub struct PendingStateCheckpoint {
+    state_summary: Planned<Result<LedgerStateSummary>>,
+    state_checkpoint_hashes: Vec<Option<HashValue>>,
+}
+
+impl PendingStateCheckpoint {
+    pub fn state_checkpoint_hashes(&self) -> &[Option<HashValue>] {
+        &self.state_checkpoint_hashes
+    }
+
+    /// Waits for the second phase, which hashes the state after the last checkpoint.
+    pub fn finalize(self) -> Result<StateCheckpointOutput> {
+        let state_summary = self
+            .state_summary
+            .into_inner(Some("do_state_checkpoint__finalize"))?;
+
+        Ok(StateCheckpointOutput::new(
+            state_summary,
+            self.state_checkpoint_hashes,
+        ))
+    }
+}
@@ -15,6 +15,7 @@ use do_state_checkpoint::DoStateCheckpoint;
 
 pub mod do_get_execution_output;
 pub mod do_ledger_update;
+pub mod do_prefetch_state;
 pub mod do_state_checkpoint;
 
 pub struct ApplyExecutionOutput;
@@ -33,7 +34,7 @@ impl ApplyExecutionOutput {
         )?;
         let ledger_update_output = DoLedgerUpdate::run(
             &execution_output,
-            &state_checkpoint_output,
+            state_checkpoint_output.state_checkpoint_hashes.clone(),
             base_view.transaction_accumulator,
         )?;
         let output = PartialStateComputeResult::new(execution_output);
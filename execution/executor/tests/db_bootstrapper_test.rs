@@ -9,7 +9,7 @@ use aptos_crypto::{ed25519::Ed25519PrivateKey, HashValue, PrivateKey, Uniform};
 use aptos_db::AptosDB;
 use aptos_executor::{
     block_executor::BlockExecutor,
-    db_bootstrapper::{generate_waypoint, maybe_bootstrap},
+    db_bootstrapper::{calculate_genesis, generate_waypoint, maybe_bootstrap, StateChange},
 };
 use aptos_executor_test_helpers::{
     bootstrap_genesis, gen_ledger_info_with_sigs, get_test_signed_transaction,
@@ -33,7 +33,7 @@ use aptos_types::{
     trusted_state::TrustedState,
     validator_signer::ValidatorSigner,
     waypoint::Waypoint,
-    write_set::{WriteOp, WriteSetMut},
+    write_set::WriteSetBuilder,
 };
 use aptos_vm::aptos_vm::AptosVMBlockExecutor;
 use move_core_types::{language_storage::TypeTag, move_resource::MoveStructType};
@@ -220,41 +220,34 @@ fn test_new_genesis() {
     // New genesis transaction: set validator set, bump epoch and overwrite account1 balance.
     let configuration = get_configuration(&db);
     let genesis_txn = Transaction::GenesisTransaction(WriteSetPayload::Direct(ChangeSet::new(
-        WriteSetMut::new(vec![
-            (
+        WriteSetBuilder::new()
+            .modify_value(
                 StateKey::on_chain_config::<ValidatorSet>().unwrap(),
-                WriteOp::legacy_modification(
-                    bcs::to_bytes(&ValidatorSet::new(vec![])).unwrap().into(),
-                ),
-            ),
-            (
+                &ValidatorSet::new(vec![]),
+            )
+            .unwrap()
+            .modify_value(
                 StateKey::on_chain_config::<ConfigurationResource>().unwrap(),
-                WriteOp::legacy_modification(
-                    bcs::to_bytes(&configuration.bump_epoch_for_test())
-                        .unwrap()
-                        .into(),
-                ),
-            ),
-            (
+                &configuration.bump_epoch_for_test(),
+            )
+            .unwrap()
+            .modification(
                 StateKey::resource_group(
                     &primary_apt_store(account1),
                     &ObjectGroupResource::struct_tag(),
                 ),
-                WriteOp::legacy_modification(
-                    aptos_transaction_simulation::FungibleStore::new(
-                        account1,
-                        AccountAddress::TEN,
-                        100_000_000,
-                        false,
-                        false,
-                    )
-                    .to_bytes()
-                    .into(),
-                ),
-            ),
-        ])
-        .freeze()
-        .unwrap(),
+                aptos_transaction_simulation::FungibleStore::new(
+                    account1,
+                    AccountAddress::TEN,
+                    100_000_000,
+                    false,
+                    false,
+                )
+                .to_bytes()
+                .into(),
+            )
+            .build()
+            .unwrap(),
         vec![
             ContractEvent::new_v2(NEW_EPOCH_EVENT_V2_MOVE_TYPE_TAG.clone(), vec![]).unwrap(),
             ContractEvent::new_v1(
@@ -267,6 +260,25 @@ fn test_new_genesis() {
         ],
     )));
 
+    // Dry run the new genesis, nothing is committed.
+    let ledger_summary = db.reader.get_pre_committed_ledger_summary().unwrap();
+    let base_state = ledger_summary.state.latest().clone();
+    let report = calculate_genesis::<AptosVMBlockExecutor>(&db, ledger_summary, &genesis_txn)
+        .unwrap()
+        .dry_run_report(db.reader.clone(), base_state)
+        .unwrap();
+    assert_eq!(report.version, 6);
+    assert!(report.state_changes.contains(&(
+        StateKey::on_chain_config::<ConfigurationResource>().unwrap(),
+        StateChange::Modification
+    )));
+    assert!(report
+        .state_changes
+        .iter()
+        .all(|(_, change)| *change == StateChange::Modification));
+    assert_eq!(report.items_delta(), 0);
+    assert_eq!(get_balance(&account1, &db), 200_000_000);
+
     // Bootstrap DB into new genesis.
     let waypoint = generate_waypoint::<AptosVMBlockExecutor>(&db, &genesis_txn).unwrap();
     assert!(
@@ -593,6 +593,18 @@ impl Mempool {
         self.transactions.gc_by_system_ttl(now);
     }
 
+    /// Updates the capacities of the mempool, e.g., after the config was reloaded.
+    /// Transactions already in the mempool are kept, even if they exceed the capacities.
+    pub(crate) fn update_capacities(
+        &mut self,
+        capacity: usize,
+        capacity_bytes: usize,
+        capacity_per_user: usize,
+    ) {
+        self.transactions
+            .update_capacities(capacity, capacity_bytes, capacity_per_user);
+    }
+
     /// Garbage collection based on client-specified expiration time.
     pub(crate) fn gc_by_expiration_time(&mut self, block_time: Duration) {
         self.transactions.gc_by_expiration_time(block_time);
@@ -902,6 +902,30 @@ impl TransactionStore {
     }
 
     /// Garbage collect old transactions.
+    pub(crate) fn update_capacities(
+        &mut self,
+        capacity: usize,
+        capacity_bytes: usize,
+        capacity_per_user: usize,
+    ) {
+        if (capacity, capacity_bytes, capacity_per_user)
+            != (self.capacity, self.capacity_bytes, self.capacity_per_user)
+        {
+            info!(
+                "Updating mempool capacities! Capacity: {} -> {}, capacity bytes: {} -> {}, capacity per user: {} -> {}",
+                self.capacity,
+                capacity,
+                self.capacity_bytes,
+                capacity_bytes,
+                self.capacity_per_user,
+                capacity_per_user
+            );
+            self.capacity = capacity;
+            self.capacity_bytes = capacity_bytes;
+            self.capacity_per_user = capacity_per_user;
+        }
+    }
+
     pub(crate) fn gc_by_system_ttl(&mut self, gc_time: Duration) {
         self.gc(gc_time, true);
     }
@@ -607,6 +607,21 @@ pub fn shared_mempool_ack_inc(network_id: NetworkId, direction: &str, label: &'s
         .inc();
 }
 
+static SHARED_MEMPOOL_BROADCAST_REJECTION_COUNT: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "aptos_shared_mempool_broadcast_rejection_count",
+        "Number of broadcast transactions rejected by peers, by rejection reason",
+        &["network", "reason"]
+    )
+    .unwrap()
+});
+
+pub fn shared_mempool_broadcast_rejection_inc(network_id: NetworkId, reason: &'static str) {
+    SHARED_MEMPOOL_BROADCAST_REJECTION_COUNT
+        .with_label_values(&[network_id.as_str(), reason])
+        .inc();
+}
+
 static TASK_SPAWN_LATENCY: Lazy<HistogramVec> = Lazy::new(|| {
     register_histogram_vec!(
         "aptos_mempool_bounded_executor_spawn_latency",
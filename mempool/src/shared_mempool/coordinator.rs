@@ -20,7 +20,10 @@ use crate::{
     MempoolEventsReceiver, QuorumStoreRequest,
 };
 use aptos_bounded_executor::BoundedExecutor;
-use aptos_config::network_id::{NetworkId, PeerNetworkId};
+use aptos_config::{
+    config::reloadable_config,
+    network_id::{NetworkId, PeerNetworkId},
+};
 use aptos_event_notifications::ReconfigNotificationListener;
 use aptos_infallible::{Mutex, RwLock};
 use aptos_logger::prelude::*;
@@ -400,6 +403,23 @@ async fn handle_network_event<NetworkClient, TransactionValidator>(
                         message_id,
                         retry,
                         backoff,
+                        vec![],
+                        ack_timestamp,
+                    );
+                },
+                MempoolSyncMsg::BroadcastTransactionsResponseWithRejections {
+                    message_id,
+                    retry,
+                    backoff,
+                    rejected_transactions,
+                } => {
+                    let ack_timestamp = SystemTime::now();
+                    smp.network_interface.process_broadcast_ack(
+                        PeerNetworkId::new(network_id, peer_id),
+                        message_id,
+                        retry,
+                        backoff,
+                        rejected_transactions,
                         ack_timestamp,
                     );
                 },
@@ -451,7 +471,17 @@ pub(crate) async fn gc_coordinator(mempool: Arc<Mutex<CoreMempool>>, gc_interval
             SampleRate::Duration(Duration::from_secs(60)),
             debug!(LogSchema::event_log(LogEntry::GCRuntime, LogEvent::Live))
         );
-        mempool.lock().gc();
+        let mut mempool = mempool.lock();
+
+        // Apply any reloaded capacities before collecting garbage
+        if let Some(reloadable_config) = reloadable_config::get() {
+            mempool.update_capacities(
+                reloadable_config.mempool_capacity,
+                reloadable_config.mempool_capacity_bytes,
+                reloadable_config.mempool_capacity_per_user,
+            );
+        }
+        mempool.gc();
     }
 
     error!(LogSchema::event_log(
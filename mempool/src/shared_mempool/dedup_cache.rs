@@ -0,0 +1,165 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    counters,
+    logging::{LogEntry, LogSchema},
+};
+use aptos_crypto::HashValue;
+use aptos_logger::prelude::*;
+use aptos_storage_interface::{DbReader, DbWriter};
+use aptos_types::transaction::SignedTransaction;
+use std::{
+    sync::Arc,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+/// The prefix of the dedup cache keys in the storage scratch space
+const KEY_PREFIX: &[u8] = b"mempool_dedup/";
+
+/// A cache of the hashes of recently accepted transactions, kept in the scratch space of
+/// storage so that it survives restarts. This prevents a restarting node from re-broadcasting
+/// the transactions it already accepted and forwarded before the restart. Such transactions are
+/// still added to the mempool again, as the node may not have forwarded them successfully.
+///
+/// Each hash is kept until the transaction expires (bounded by `max_ttl`), after which the
+/// transaction can't be committed anyway. The cache is best effort: entries written just
+/// before a crash may be lost.
+pub(crate) struct TransactionDedupCache {
+    db_reader: Arc<dyn DbReader>,
+    db_writer: Arc<dyn DbWriter>,
+    max_ttl: Duration,
+}
+
+impl TransactionDedupCache {
+    pub fn new(
+        db_reader: Arc<dyn DbReader>,
+        db_writer: Arc<dyn DbWriter>,
+        max_ttl: Duration,
+    ) -> Self {
+        Self {
+            db_reader,
+            db_writer,
+            max_ttl,
+        }
+    }
+
+    /// Returns true iff the transaction with the given hash was accepted recently
+    pub fn contains(&self, txn_hash: HashValue) -> bool {
+        match self.db_reader.get_scratch_value(&Self::key(txn_hash)) {
+            Ok(value) => value.is_some(),
+            Err(error) => {
+                error!(LogSchema::new(LogEntry::DBError).error(&error.into()));
+                counters::DB_ERROR.inc();
+                false
+            },
+        }
+    }
+
+    /// Records the accepted transactions (until they expire), in a single write
+    pub fn insert_all<'a>(&self, transactions: impl IntoIterator<Item = &'a SignedTransaction>) {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default();
+        let values: Vec<_> = transactions
+            .into_iter()
+            .filter_map(|transaction| {
+                let ttl = Duration::from_secs(transaction.expiration_timestamp_secs())
+                    .saturating_sub(now)
+                    .min(self.max_ttl);
+                // Skip the transactions that have already expired
+                (!ttl.is_zero()).then(|| (Self::key(transaction.committed_hash()), vec![], ttl))
+            })
+            .collect();
+        if values.is_empty() {
+            return;
+        }
+
+        if let Err(error) = self.db_writer.put_scratch_values(&values) {
+            error!(LogSchema::new(LogEntry::DBError).error(&error.into()));
+            counters::DB_ERROR.inc();
+        }
+    }
+
+    fn key(txn_hash: HashValue) -> Vec<u8> {
+        [KEY_PREFIX, txn_hash.as_ref()].concat()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aptos_crypto::{ed25519::Ed25519PrivateKey, PrivateKey, Uniform};
+    use aptos_storage_interface::Result;
+    use aptos_types::{
+        account_address::AccountAddress,
+        test_helpers::transaction_test_helpers::get_test_signed_transaction,
+    };
+    use std::{collections::HashMap, sync::Mutex};
+
+    /// A scratch space that counts the writes made to it
+    #[derive(Default)]
+    struct MockScratchSpace {
+        values: Mutex<HashMap<Vec<u8>, Duration>>,
+        num_writes: Mutex<usize>,
+    }
+
+    impl DbReader for MockScratchSpace {
+        fn get_scratch_value(&self, key: &[u8]) -> Result<Option<Vec<u8>>> {
+            Ok(self.values.lock().unwrap().get(key).map(|_| vec![]))
+        }
+    }
+
+    impl DbWriter for MockScratchSpace {
+        fn put_scratch_values(&self, values: &[(Vec<u8>, Vec<u8>, Duration)]) -> Result<()> {
+            *self.num_writes.lock().unwrap() += 1;
+            let mut stored_values = self.values.lock().unwrap();
+            for (key, _, ttl) in values {
+                stored_values.insert(key.clone(), *ttl);
+            }
+            Ok(())
+        }
+    }
+
+    fn transaction(sequence_number: u64, expiration_timestamp_secs: u64) -> SignedTransaction {
+        let private_key = Ed25519PrivateKey::generate_for_testing();
+        get_test_signed_transaction(
+            AccountAddress::random(),
+            sequence_number,
+            &private_key,
+            private_key.public_key(),
+            None,
+            expiration_timestamp_secs,
+            100,
+            None,
+        )
+    }
+
+    #[test]
+    fn test_insert_all() {
+        let scratch_space = Arc::new(MockScratchSpace::default());
+        let cache = TransactionDedupCache::new(
+            scratch_space.clone(),
+            scratch_space.clone(),
+            Duration::from_secs(60),
+        );
+
+        let live = transaction(0, u64::MAX);
+        let expired = transaction(1, 0);
+        cache.insert_all([&live, &expired]);
+
+        // All transactions are written at once, and only the live one is kept
+        assert_eq!(*scratch_space.num_writes.lock().unwrap(), 1);
+        assert!(cache.contains(live.committed_hash()));
+        assert!(!cache.contains(expired.committed_hash()));
+
+        // The TTL is bounded by the maximum TTL
+        let ttl = scratch_space.values.lock().unwrap()
+            [&TransactionDedupCache::key(live.committed_hash())];
+        assert_eq!(ttl, Duration::from_secs(60));
+
+        // Nothing is written if there is nothing to remember
+        cache.insert_all([&expired]);
+        assert_eq!(*scratch_space.num_writes.lock().unwrap(), 1);
+    }
+}
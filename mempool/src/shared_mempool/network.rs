@@ -20,6 +20,7 @@ use aptos_config::{
     config::{MempoolConfig, NodeType},
     network_id::PeerNetworkId,
 };
+use aptos_crypto::HashValue;
 use aptos_infallible::RwLock;
 use aptos_logger::prelude::*;
 use aptos_netcore::transport::ConnectionOrigin;
@@ -44,6 +45,9 @@ use std::{
 };
 use thiserror::Error;
 
+/// The name broadcast ACK round-trip times are recorded under in the peer metadata
+const MEMPOOL_ACK_APPLICATION_LATENCY: &str = "mempool_ack";
+
 /// Container for exchanging transactions with other Mempools.
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub enum MempoolSyncMsg {
@@ -72,6 +76,51 @@ pub enum MempoolSyncMsg {
         /// to reach the upstream node.
         transactions: Vec<(SignedTransaction, u64, BroadcastPeerPriority)>,
     },
+    /// Broadcast ack issued by the receiver, including the reasons transactions were rejected.
+    BroadcastTransactionsResponseWithRejections {
+        message_id: MempoolMessageId,
+        /// Retry signal from recipient if there are txns in corresponding broadcast
+        /// that were rejected from mempool but may succeed on resend.
+        retry: bool,
+        /// A backpressure signal from the recipient when it is overwhelmed (e.g., mempool is full).
+        backoff: bool,
+        /// The committed hashes of the rejected transactions, and why they were rejected.
+        rejected_transactions: Vec<(HashValue, BroadcastRejectionReason)>,
+    },
+}
+
+/// The reason a broadcast transaction was rejected by the recipient.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub enum BroadcastRejectionReason {
+    /// The transaction failed validation (or was filtered), so it will never be accepted.
+    Invalid,
+    /// The recipient mempool is full, so the transaction may be accepted on resend.
+    MempoolFull,
+    /// The recipient already has the transaction.
+    Duplicate,
+    /// The sequence number of the transaction has already been used.
+    SequenceNumberTooOld,
+}
+
+impl BroadcastRejectionReason {
+    /// Returns true iff rebroadcasting the transaction to the recipient is pointless
+    pub fn is_final(&self) -> bool {
+        match self {
+            BroadcastRejectionReason::Invalid
+            | BroadcastRejectionReason::Duplicate
+            | BroadcastRejectionReason::SequenceNumberTooOld => true,
+            BroadcastRejectionReason::MempoolFull => false,
+        }
+    }
+
+    pub fn get_label(&self) -> &'static str {
+        match self {
+            BroadcastRejectionReason::Invalid => "invalid",
+            BroadcastRejectionReason::MempoolFull => "mempool_full",
+            BroadcastRejectionReason::Duplicate => "duplicate",
+            BroadcastRejectionReason::SequenceNumberTooOld => "sequence_number_too_old",
+        }
+    }
 }
 
 #[derive(Debug, Error)]
@@ -226,9 +275,37 @@ impl<NetworkClient: NetworkClientInterface<MempoolSyncMsg>> MempoolNetworkInterf
         let peers_changed = !to_add.is_empty() || !to_disable.is_empty();
         self.update_prioritized_peers(all_connected_peers, peers_changed);
 
+        if self.mempool_config.report_broadcast_ack_latency {
+            self.report_broadcast_ack_latencies();
+        }
+
         (to_add.iter().map(|(peer, _)| *peer).collect(), to_disable)
     }
 
+    /// Records the latest broadcast ACK round-trip time of each peer in the peer
+    /// metadata, so that peer scoring accounts for how responsive mempool is.
+    fn report_broadcast_ack_latencies(&self) {
+        let latencies: Vec<_> = self
+            .sync_states
+            .write()
+            .iter_mut()
+            .filter_map(|(peer, sync_state)| {
+                let rtt = sync_state.broadcast_info.latest_ack_rtt.take()?;
+                Some((*peer, rtt))
+            })
+            .collect();
+
+        let peers_and_metadata = self.network_client.get_peers_and_metadata();
+        for (peer, rtt) in latencies {
+            // The peer may have just disconnected, so errors are ignored
+            let _ = peers_and_metadata.update_application_latency(
+                peer,
+                MEMPOOL_ACK_APPLICATION_LATENCY,
+                rtt,
+            );
+        }
+    }
+
     /// Updates the prioritized peers list
     fn update_prioritized_peers(
         &mut self,
@@ -301,6 +378,7 @@ impl<NetworkClient: NetworkClientInterface<MempoolSyncMsg>> MempoolNetworkInterf
         message_id: MempoolMessageId,
         retry: bool,
         backoff: bool,
+        rejected_transactions: Vec<(HashValue, BroadcastRejectionReason)>,
         timestamp: SystemTime,
     ) {
         let mut sync_states = self.sync_states.write();
@@ -321,6 +399,7 @@ impl<NetworkClient: NetworkClientInterface<MempoolSyncMsg>> MempoolNetworkInterf
             counters::SHARED_MEMPOOL_BROADCAST_RTT
                 .with_label_values(&[network_id.as_str()])
                 .observe(rtt.as_secs_f64());
+            sync_state.broadcast_info.latest_ack_rtt = Some(rtt);
 
             counters::shared_mempool_pending_broadcasts(&peer).dec();
         } else {
@@ -346,6 +425,20 @@ impl<NetworkClient: NetworkClientInterface<MempoolSyncMsg>> MempoolNetworkInterf
             sync_state.broadcast_info.retry_messages.insert(message_id);
         }
 
+        // Stop broadcasting transactions to the peer that it will never accept
+        for (txn_hash, rejection_reason) in rejected_transactions {
+            counters::shared_mempool_broadcast_rejection_inc(
+                peer.network_id(),
+                rejection_reason.get_label(),
+            );
+            if rejection_reason.is_final() {
+                sync_state
+                    .broadcast_info
+                    .rejected_transactions
+                    .insert(txn_hash);
+            }
+        }
+
         // Backoff mode can only be turned off by executing a broadcast that was scheduled
         // as a backoff broadcast.
         // This ensures backpressure request from remote peer is honored at least once.
@@ -419,6 +512,10 @@ impl<NetworkClient: NetworkClientInterface<MempoolSyncMsg>> MempoolNetworkInterf
                     .is_empty()
             })
             .collect::<BTreeSet<MempoolMessageId>>();
+        state
+            .broadcast_info
+            .rejected_transactions
+            .retain(|txn_hash| mempool.get_by_hash(*txn_hash).is_some());
 
         // Check for batch to rebroadcast:
         // 1. Batch that did not receive ACK in configured window of time
@@ -449,7 +546,7 @@ impl<NetworkClient: NetworkClientInterface<MempoolSyncMsg>> MempoolNetworkInterf
         }
         let retry_message_id = state.broadcast_info.retry_messages.iter().next_back();
 
-        let (message_id, transactions, metric_label) =
+        let (message_id, mut transactions, metric_label) =
             match std::cmp::max(expired_message_id, retry_message_id) {
                 Some(message_id) => {
                     let metric_label = if Some(message_id) == expired_message_id {
@@ -562,6 +659,16 @@ impl<NetworkClient: NetworkClientInterface<MempoolSyncMsg>> MempoolNetworkInterf
                 },
             };
 
+        // Don't send the transactions that the peer has already rejected for good
+        if !state.broadcast_info.rejected_transactions.is_empty() {
+            transactions.retain(|(txn, _, _)| {
+                !state
+                    .broadcast_info
+                    .rejected_transactions
+                    .contains(&txn.committed_hash())
+            });
+        }
+
         if transactions.is_empty() {
             return Err(BroadcastError::NoTransactions(peer));
         }
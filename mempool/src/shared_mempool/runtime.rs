@@ -7,12 +7,13 @@ use crate::{
     network::MempoolSyncMsg,
     shared_mempool::{
         coordinator::{coordinator, gc_coordinator, snapshot_job},
+        dedup_cache::TransactionDedupCache,
         types::{MempoolEventsReceiver, SharedMempool, SharedMempoolNotification},
     },
     QuorumStoreRequest,
 };
 use aptos_config::config::{NodeConfig, NodeType};
-use aptos_event_notifications::{DbBackedOnChainConfig, ReconfigNotificationListener};
+use aptos_event_notifications::{InMemoryOnChainConfig, ReconfigNotificationListener};
 use aptos_infallible::{Mutex, RwLock};
 use aptos_logger::Level;
 use aptos_mempool_notifications::MempoolNotificationListener;
@@ -20,11 +21,11 @@ use aptos_network::application::{
     interface::{NetworkClient, NetworkServiceEvents},
     storage::PeersAndMetadata,
 };
-use aptos_storage_interface::DbReader;
+use aptos_storage_interface::{DbReader, DbWriter};
 use aptos_types::on_chain_config::OnChainConfigProvider;
 use aptos_vm_validator::vm_validator::{PooledVMValidator, TransactionValidation};
 use futures::channel::mpsc::{Receiver, UnboundedSender};
-use std::sync::Arc;
+use std::{sync::Arc, time::Duration};
 use tokio::runtime::{Handle, Runtime};
 
 /// Bootstrap of SharedMempool.
@@ -46,6 +47,7 @@ pub(crate) fn start_shared_mempool<TransactionValidator, ConfigProvider>(
     validator: Arc<RwLock<TransactionValidator>>,
     subscribers: Vec<UnboundedSender<SharedMempoolNotification>>,
     peers_and_metadata: Arc<PeersAndMetadata>,
+    dedup_cache: Option<Arc<TransactionDedupCache>>,
 ) where
     TransactionValidator: TransactionValidation + 'static,
     ConfigProvider: OnChainConfigProvider,
@@ -62,6 +64,7 @@ pub(crate) fn start_shared_mempool<TransactionValidator, ConfigProvider>(
             validator,
             subscribers,
             node_type,
+            dedup_cache,
         );
 
     executor.spawn(coordinator(
@@ -92,12 +95,13 @@ pub(crate) fn start_shared_mempool<TransactionValidator, ConfigProvider>(
 pub fn bootstrap(
     config: &NodeConfig,
     db: Arc<dyn DbReader>,
+    db_writer: Arc<dyn DbWriter>,
     network_client: NetworkClient<MempoolSyncMsg>,
     network_service_events: NetworkServiceEvents<MempoolSyncMsg>,
     client_events: MempoolEventsReceiver,
     quorum_store_requests: Receiver<QuorumStoreRequest>,
     mempool_listener: MempoolNotificationListener,
-    mempool_reconfig_events: ReconfigNotificationListener<DbBackedOnChainConfig>,
+    mempool_reconfig_events: ReconfigNotificationListener<InMemoryOnChainConfig>,
     peers_and_metadata: Arc<PeersAndMetadata>,
 ) -> Runtime {
     let runtime = aptos_runtimes::spawn_named_runtime("shared-mem".into(), None);
@@ -106,6 +110,13 @@ pub fn bootstrap(
         Arc::clone(&db),
         num_cpus::get(),
     )));
+    let dedup_cache = (config.mempool.persisted_dedup_cache_ttl_secs > 0).then(|| {
+        Arc::new(TransactionDedupCache::new(
+            Arc::clone(&db),
+            db_writer,
+            Duration::from_secs(config.mempool.persisted_dedup_cache_ttl_secs),
+        ))
+    });
     start_shared_mempool(
         runtime.handle(),
         config,
@@ -120,6 +131,7 @@ pub fn bootstrap(
         vm_validator,
         vec![],
         peers_and_metadata,
+        dedup_cache,
     );
     runtime
 }
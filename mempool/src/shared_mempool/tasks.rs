@@ -8,7 +8,7 @@ use crate::{
     core_mempool::{CoreMempool, TimelineState},
     counters,
     logging::{LogEntry, LogEvent, LogSchema},
-    network::{BroadcastError, BroadcastPeerPriority, MempoolSyncMsg},
+    network::{BroadcastError, BroadcastPeerPriority, BroadcastRejectionReason, MempoolSyncMsg},
     shared_mempool::{
         types::{
             notify_subscribers, ScheduledBroadcast, SharedMempool, SharedMempoolNotification,
@@ -41,6 +41,7 @@ use futures::{channel::oneshot, stream::FuturesUnordered};
 use rayon::prelude::*;
 use std::{
     cmp,
+    collections::HashSet,
     sync::Arc,
     time::{Duration, Instant},
 };
@@ -228,10 +229,31 @@ pub(crate) async fn process_transaction_broadcast<NetworkClient, TransactionVali
 {
     timer.stop_and_record();
     let _timer = counters::process_txn_submit_latency_timer(peer.network_id());
+
+    // Identify the transactions that are already in mempool, as re-adding them is idempotent
+    // (i.e., they are accepted) but the peer should still stop broadcasting them.
+    let include_rejection_reasons = smp.config.include_rejection_reasons_in_ack;
+    let duplicate_txns = if include_rejection_reasons {
+        let mempool = smp.mempool.lock();
+        transactions
+            .iter()
+            .map(|(txn, _, _)| txn.committed_hash())
+            .filter(|txn_hash| mempool.get_by_hash(*txn_hash).is_some())
+            .collect()
+    } else {
+        HashSet::new()
+    };
+
     let results = process_incoming_transactions(&smp, transactions, timeline_state, false);
     log_txn_process_results(&results, Some(peer));
 
-    let ack_response = gen_ack_response(message_id, results, &peer);
+    let ack_response = gen_ack_response(
+        message_id,
+        results,
+        &peer,
+        include_rejection_reasons,
+        &duplicate_txns,
+    );
 
     // Respond to the peer with an ack. Note: ack response messages should be
     // small enough that they always fit within the maximum network message
@@ -252,16 +274,30 @@ pub(crate) async fn process_transaction_broadcast<NetworkClient, TransactionVali
 }
 
 /// If `MempoolIsFull` on any of the transactions, provide backpressure to the downstream peer.
+/// If `include_rejection_reasons` is set, the ack also carries the reason each rejected
+/// transaction was rejected, so that the downstream peer can stop rebroadcasting it.
 fn gen_ack_response(
     message_id: MempoolMessageId,
     results: Vec<SubmissionStatusBundle>,
     peer: &PeerNetworkId,
+    include_rejection_reasons: bool,
+    duplicate_txns: &HashSet<HashValue>,
 ) -> MempoolSyncMsg {
     let mut backoff_and_retry = false;
-    for (_, (mempool_status, _)) in results.into_iter() {
-        if mempool_status.code == MempoolStatusCode::MempoolIsFull {
+    let mut rejected_transactions = vec![];
+    for (txn, submission_status) in results.into_iter() {
+        if submission_status.0.code == MempoolStatusCode::MempoolIsFull {
             backoff_and_retry = true;
-            break;
+            if !include_rejection_reasons {
+                break;
+            }
+        }
+        if include_rejection_reasons {
+            let txn_hash = txn.committed_hash();
+            let is_duplicate = duplicate_txns.contains(&txn_hash);
+            if let Some(reason) = get_rejection_reason(&submission_status, is_duplicate) {
+                rejected_transactions.push((txn_hash, reason));
+            }
         }
     }
 
@@ -271,10 +307,42 @@ fn gen_ack_response(
         backoff_and_retry,
         backoff_and_retry,
     );
-    MempoolSyncMsg::BroadcastTransactionsResponse {
-        message_id,
-        retry: backoff_and_retry,
-        backoff: backoff_and_retry,
+    if include_rejection_reasons {
+        MempoolSyncMsg::BroadcastTransactionsResponseWithRejections {
+            message_id,
+            retry: backoff_and_retry,
+            backoff: backoff_and_retry,
+            rejected_transactions,
+        }
+    } else {
+        MempoolSyncMsg::BroadcastTransactionsResponse {
+            message_id,
+            retry: backoff_and_retry,
+            backoff: backoff_and_retry,
+        }
+    }
+}
+
+/// Returns the reason the given broadcast transaction was rejected (if it was)
+fn get_rejection_reason(
+    submission_status: &SubmissionStatus,
+    is_duplicate: bool,
+) -> Option<BroadcastRejectionReason> {
+    let (mempool_status, vm_status) = submission_status;
+    match mempool_status.code {
+        MempoolStatusCode::Accepted => is_duplicate.then_some(BroadcastRejectionReason::Duplicate),
+        MempoolStatusCode::MempoolIsFull => Some(BroadcastRejectionReason::MempoolFull),
+        MempoolStatusCode::InvalidSeqNumber => Some(BroadcastRejectionReason::SequenceNumberTooOld),
+        MempoolStatusCode::VmError
+            if *vm_status == Some(DiscardedVMStatus::SEQUENCE_NUMBER_TOO_OLD) =>
+        {
+            Some(BroadcastRejectionReason::SequenceNumberTooOld)
+        },
+        MempoolStatusCode::VmError
+        | MempoolStatusCode::InvalidUpdate
+        | MempoolStatusCode::RejectedByFilter => Some(BroadcastRejectionReason::Invalid),
+        // The account may have room for the transaction later on
+        MempoolStatusCode::TooManyTransactions | MempoolStatusCode::UnknownStatus => None,
     }
 }
 
@@ -393,6 +461,12 @@ where
         })
         .collect();
 
+    // The transactions that were already accepted before a restart are added again (as they
+    // may not have been forwarded successfully), but are not broadcast again
+    let (recently_accepted_transactions, transactions) =
+        partition_recently_accepted_transactions(smp, transactions);
+
+    let num_previous_statuses = statuses.len();
     validate_and_add_transactions(
         transactions,
         smp,
@@ -400,10 +474,78 @@ where
         &mut statuses,
         client_submitted,
     );
+    remember_accepted_transactions(smp, &statuses[num_previous_statuses..]);
+    if !recently_accepted_transactions.is_empty() {
+        validate_and_add_transactions(
+            recently_accepted_transactions,
+            smp,
+            TimelineState::NonQualified,
+            &mut statuses,
+            client_submitted,
+        );
+    }
     notify_subscribers(SharedMempoolNotification::NewTransactions, &smp.subscribers);
     statuses
 }
 
+/// Splits the transactions into those found in the dedup cache, i.e., that were accepted
+/// before the node restarted, and the others.
+#[allow(clippy::type_complexity)]
+fn partition_recently_accepted_transactions<NetworkClient, TransactionValidator>(
+    smp: &SharedMempool<NetworkClient, TransactionValidator>,
+    transactions: Vec<(
+        SignedTransaction,
+        Option<u64>,
+        Option<u64>,
+        Option<BroadcastPeerPriority>,
+    )>,
+) -> (
+    Vec<(
+        SignedTransaction,
+        Option<u64>,
+        Option<u64>,
+        Option<BroadcastPeerPriority>,
+    )>,
+    Vec<(
+        SignedTransaction,
+        Option<u64>,
+        Option<u64>,
+        Option<BroadcastPeerPriority>,
+    )>,
+)
+where
+    NetworkClient: NetworkClientInterface<MempoolSyncMsg>,
+    TransactionValidator: TransactionValidation,
+{
+    match &smp.dedup_cache {
+        Some(dedup_cache) => transactions
+            .into_iter()
+            .partition(|(transaction, _, _, _)| dedup_cache.contains(transaction.committed_hash())),
+        None => (vec![], transactions),
+    }
+}
+
+/// Records the transactions that were accepted into the mempool in the dedup cache (if
+/// enabled), in a single write.
+fn remember_accepted_transactions<NetworkClient, TransactionValidator>(
+    smp: &SharedMempool<NetworkClient, TransactionValidator>,
+    statuses: &[(SignedTransaction, (MempoolStatus, Option<StatusCode>))],
+) where
+    NetworkClient: NetworkClientInterface<MempoolSyncMsg>,
+    TransactionValidator: TransactionValidation,
+{
+    if let Some(dedup_cache) = &smp.dedup_cache {
+        dedup_cache.insert_all(
+            statuses
+                .iter()
+                .filter(|(_, (mempool_status, _))| {
+                    mempool_status.code == MempoolStatusCode::Accepted
+                })
+                .map(|(transaction, _)| transaction),
+        );
+    }
+}
+
 /// Filters transactions based on the transaction filter configuration. Any
 /// transactions that are filtered out will have their statuses marked accordingly.
 fn filter_transactions(
@@ -797,11 +939,13 @@ pub(crate) async fn process_config_update<V, P>(
 #[cfg(test)]
 mod test {
     use super::*;
+    use aptos_config::network_id::NetworkId;
     use aptos_crypto::{ed25519::Ed25519PrivateKey, PrivateKey, SigningKey, Uniform};
     use aptos_transaction_filters::transaction_filter::TransactionFilter;
     use aptos_types::{
         chain_id::ChainId,
         transaction::{RawTransaction, Script, TransactionPayload},
+        PeerId,
     };
 
     #[test]
@@ -898,6 +1042,83 @@ mod test {
         }
     }
 
+    #[test]
+    fn test_gen_ack_response_with_rejections() {
+        // Create test transactions and their submission statuses
+        let statuses = [
+            (MempoolStatusCode::Accepted, None),
+            (MempoolStatusCode::Accepted, None),
+            (MempoolStatusCode::MempoolIsFull, None),
+            (
+                MempoolStatusCode::VmError,
+                Some(DiscardedVMStatus::SEQUENCE_NUMBER_TOO_OLD),
+            ),
+            (
+                MempoolStatusCode::VmError,
+                Some(DiscardedVMStatus::INVALID_SIGNATURE),
+            ),
+            (MempoolStatusCode::TooManyTransactions, None),
+        ];
+        let results: Vec<_> = statuses
+            .into_iter()
+            .map(|(code, vm_status)| {
+                (
+                    create_signed_transaction(),
+                    (MempoolStatus::new(code), vm_status),
+                )
+            })
+            .collect();
+        let txn_hashes: Vec<_> = results
+            .iter()
+            .map(|(txn, _)| txn.committed_hash())
+            .collect();
+
+        // Generate an ack with rejection reasons (the second transaction is a duplicate)
+        let message_id = MempoolMessageId::from_timeline_ids(vec![]);
+        let peer = PeerNetworkId::new(NetworkId::Public, PeerId::random());
+        let duplicate_txns = HashSet::from([txn_hashes[1]]);
+        let ack = gen_ack_response(
+            message_id.clone(),
+            results.clone(),
+            &peer,
+            true,
+            &duplicate_txns,
+        );
+
+        // Verify the rejection reasons
+        match ack {
+            MempoolSyncMsg::BroadcastTransactionsResponseWithRejections {
+                retry,
+                backoff,
+                rejected_transactions,
+                ..
+            } => {
+                assert!(retry && backoff);
+                assert_eq!(rejected_transactions, vec![
+                    (txn_hashes[1], BroadcastRejectionReason::Duplicate),
+                    (txn_hashes[2], BroadcastRejectionReason::MempoolFull),
+                    (
+                        txn_hashes[3],
+                        BroadcastRejectionReason::SequenceNumberTooOld
+                    ),
+                    (txn_hashes[4], BroadcastRejectionReason::Invalid),
+                ]);
+            },
+            ack => panic!("Unexpected ack: {:?}", ack),
+        }
+
+        // Verify the legacy ack is generated if rejection reasons are disabled
+        let ack = gen_ack_response(message_id, results, &peer, false, &duplicate_txns);
+        assert!(matches!(
+            ack,
+            MempoolSyncMsg::BroadcastTransactionsResponse {
+                retry: true,
+                backoff: true,
+                ..
+            }
+        ));
+    }
+
     fn create_raw_transaction() -> RawTransaction {
         RawTransaction::new(
             AccountAddress::random(),
@@ -6,7 +6,7 @@
 use crate::{
     core_mempool::{CoreMempool, TimelineId},
     network::{MempoolNetworkInterface, MempoolSyncMsg},
-    shared_mempool::use_case_history::UseCaseHistory,
+    shared_mempool::{dedup_cache::TransactionDedupCache, use_case_history::UseCaseHistory},
 };
 use anyhow::Result;
 use aptos_config::{
@@ -33,12 +33,12 @@ use futures::{
 use serde::{Deserialize, Serialize};
 use std::{
     cmp::Ordering,
-    collections::{BTreeMap, BTreeSet, HashMap},
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet},
     fmt,
     pin::Pin,
     sync::Arc,
     task::Waker,
-    time::{Instant, SystemTime},
+    time::{Duration, Instant, SystemTime},
 };
 use tokio::runtime::Handle;
 
@@ -57,6 +57,8 @@ pub(crate) struct SharedMempool<NetworkClient, TransactionValidator> {
     pub broadcast_within_validator_network: Arc<RwLock<bool>>,
     pub use_case_history: Arc<Mutex<UseCaseHistory>>,
     pub transaction_filter_config: TransactionFilterConfig,
+    /// Remembers the recently accepted transactions across restarts (if enabled)
+    pub dedup_cache: Option<Arc<TransactionDedupCache>>,
 }
 
 impl<
@@ -73,6 +75,7 @@ impl<
         validator: Arc<RwLock<TransactionValidator>>,
         subscribers: Vec<UnboundedSender<SharedMempoolNotification>>,
         node_type: NodeType,
+        dedup_cache: Option<Arc<TransactionDedupCache>>,
     ) -> Self {
         let network_interface =
             MempoolNetworkInterface::new(network_client, node_type, config.clone());
@@ -90,6 +93,7 @@ impl<
             broadcast_within_validator_network: Arc::new(RwLock::new(true)),
             use_case_history: Arc::new(Mutex::new(use_case_history)),
             transaction_filter_config,
+            dedup_cache,
         }
     }
 
@@ -480,6 +484,10 @@ pub struct BroadcastInfo {
     pub retry_messages: BTreeSet<MempoolMessageId>,
     // Whether broadcasting to this peer is in backoff mode, e.g. broadcasting at longer intervals.
     pub backoff_mode: bool,
+    // The round-trip time of the latest acked broadcast, not yet reported to the peer metadata.
+    pub latest_ack_rtt: Option<Duration>,
+    // Transactions (by committed hash) that the peer rejected and will never accept.
+    pub rejected_transactions: HashSet<HashValue>,
 }
 
 impl BroadcastInfo {
@@ -488,6 +496,8 @@ impl BroadcastInfo {
             sent_messages: BTreeMap::new(),
             retry_messages: BTreeSet::new(),
             backoff_mode: false,
+            latest_ack_rtt: None,
+            rejected_transactions: HashSet::new(),
         }
     }
 }
@@ -92,6 +92,7 @@ pub fn test_mempool_process_incoming_transactions_impl(
         vm_validator,
         vec![],
         NodeType::extract_from_config(&config),
+        None,
     );
 
     let _ = tasks::process_incoming_transactions(&smp, txns, timeline_state, false);
@@ -169,6 +169,7 @@ impl MockSharedMempool {
             Arc::new(RwLock::new(validator)),
             vec![],
             peers_and_metadata,
+            None,
         );
 
         (ac_client, mempool, quorum_store_sender, mempool_notifier)
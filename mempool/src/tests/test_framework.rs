@@ -436,7 +436,8 @@ impl MempoolNode {
                 }
                 message_id
             },
-            MempoolSyncMsg::BroadcastTransactionsResponse { .. } => {
+            MempoolSyncMsg::BroadcastTransactionsResponse { .. }
+            | MempoolSyncMsg::BroadcastTransactionsResponseWithRejections { .. } => {
                 panic!("We aren't supposed to be getting as response here");
             },
         };
@@ -669,6 +670,7 @@ fn setup_mempool(
         vm_validator,
         vec![sender],
         peers_and_metadata,
+        None,
     );
 
     (
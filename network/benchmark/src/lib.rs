@@ -10,7 +10,9 @@ use aptos_logger::{
     prelude::{sample, SampleRate},
     warn,
 };
-use aptos_metrics_core::{register_int_counter_vec, IntCounter, IntCounterVec};
+use aptos_metrics_core::{
+    register_int_counter_vec, register_int_gauge_vec, IntCounter, IntCounterVec, IntGaugeVec,
+};
 use aptos_network::{
     application::interface::{NetworkClient, NetworkClientInterface, NetworkServiceEvents},
     peer_manager::ConnectionNotification,
@@ -229,7 +231,7 @@ pub async fn run_netbench_service(
     time_service: TimeService,
 ) {
     let shared = Arc::new(RwLock::new(NetbenchSharedState::new()));
-    let config = node_config.netbench.unwrap();
+    let config = node_config.netbench.as_ref().unwrap();
     let benchmark_service_threads = config.netbench_service_threads;
     let num_threads = match benchmark_service_threads {
         Some(x) => x,
@@ -286,7 +288,7 @@ async fn connection_listener(
     shared: Arc<RwLock<NetbenchSharedState>>,
     handle: Handle,
 ) {
-    let config = node_config.netbench.unwrap();
+    let config = node_config.netbench.as_ref().unwrap();
     let peers_and_metadata = network_client.get_peers_and_metadata();
     let mut connected_peers = HashSet::new();
     let mut connection_notifications = peers_and_metadata.subscribe();
@@ -326,6 +328,18 @@ async fn connection_listener(
                             shared.clone(),
                         ));
                     }
+                    if config.enable_scheduled_probes
+                        && (config.probe_peers.is_empty()
+                            || config.probe_peers.contains(&meta.remote_peer_id))
+                    {
+                        handle.spawn(probe_sender(
+                            node_config.clone(),
+                            network_client.clone(),
+                            time_service.clone(),
+                            network_id,
+                            meta.remote_peer_id,
+                        ));
+                    }
                     connected_peers.insert(peer_network_id);
                 },
                 ConnectionNotification::LostPeer(meta, network_id) => {
@@ -499,6 +513,143 @@ pub async fn rpc_sender(
     }
 }
 
+/// Periodically probes the peer with a small number of sequential RPCs and
+/// records the observed latency and throughput. Unlike the direct send and
+/// RPC senders, probes are low-rate and meant to run in the background.
+pub async fn probe_sender(
+    node_config: NodeConfig,
+    network_client: NetworkClient<NetbenchMessage>,
+    time_service: TimeService,
+    network_id: NetworkId,
+    peer_id: PeerId,
+) {
+    let config = node_config.netbench.unwrap();
+    let ticker = time_service.interval(Duration::from_millis(config.probe_interval_ms));
+    futures::pin_mut!(ticker);
+    let probe_timeout = Duration::from_millis(config.probe_timeout_ms);
+    let peer_network_id = PeerNetworkId::new(network_id, peer_id);
+    let peers_and_metadata = network_client.get_peers_and_metadata();
+
+    // random payload filler
+    let data_size = config.probe_data_size;
+    let mut rng = OsRng;
+    let mut blob: Vec<u8> = (0..data_size).map(|_| rng.gen()).collect();
+
+    let mut counter: u64 = rng.gen();
+
+    loop {
+        ticker.next().await;
+
+        // Stop probing once the peer disconnects (probing restarts on reconnection)
+        let is_connected = peers_and_metadata
+            .get_metadata_for_peer(peer_network_id)
+            .is_ok_and(|peer_metadata| peer_metadata.is_connected());
+        if !is_connected {
+            info!(
+                "netbench [{},{}] probe stopped: peer disconnected",
+                network_id, peer_id
+            );
+            return;
+        }
+
+        // Send the probe RPCs one at a time, so they don't compete for bandwidth
+        let mut latencies = Vec::with_capacity(config.probe_num_messages as usize);
+        for _ in 0..config.probe_num_messages {
+            counter = counter.wrapping_add(1);
+            if blob.len() >= 8 {
+                // tweak the random payload a little on every send
+                let (dest, _) = blob.deref_mut().split_at_mut(8);
+                dest.copy_from_slice(&counter.to_le_bytes());
+            }
+
+            let msg = NetbenchDataSend {
+                request_counter: counter,
+                send_micros: time_service.now_unix_time().as_micros() as u64,
+                data: blob.clone(),
+            };
+            let send_time = time_service.now();
+            let result = network_client
+                .send_to_peer_rpc(
+                    NetbenchMessage::DataSend(msg),
+                    probe_timeout,
+                    peer_network_id,
+                )
+                .await;
+            match result {
+                Ok(NetbenchMessage::DataReply(reply)) if reply.request_counter == counter => {
+                    probe_messages("ok");
+                    latencies.push(time_service.now().duration_since(send_time));
+                },
+                Ok(_) => {
+                    probe_messages("bad");
+                    info!("netbench [{}] probe garbage reply", counter);
+                },
+                Err(err) => {
+                    probe_messages("err");
+                    sample!(
+                        SampleRate::Duration(Duration::from_millis(BLAB_MILLIS)),
+                        info!("netbench [{},{}] probe err: {}", network_id, peer_id, err)
+                    );
+                },
+            }
+        }
+
+        record_probe_result(
+            peer_network_id,
+            &latencies,
+            config.probe_num_messages,
+            data_size,
+        );
+    }
+}
+
+/// Records the results of a single probe, i.e., the latency and the
+/// throughput observed by the successful probe RPCs.
+fn record_probe_result(
+    peer_network_id: PeerNetworkId,
+    latencies: &[Duration],
+    num_messages: u64,
+    data_size: usize,
+) {
+    let peer_label = peer_network_id.to_string();
+    let num_succeeded = latencies.len() as u64;
+    if num_succeeded == 0 {
+        warn!(
+            "netbench [{}] probe failed: 0 of {} messages succeeded",
+            peer_network_id, num_messages
+        );
+        return;
+    }
+
+    // Calculate the latency and throughput
+    let total_latency: Duration = latencies.iter().sum();
+    let avg_latency_micros = total_latency.as_micros() as u64 / num_succeeded;
+    let max_latency_micros = latencies.iter().max().unwrap().as_micros() as u64;
+    let bytes_sent = num_succeeded * data_size as u64;
+    let throughput_bytes_per_sec = (bytes_sent as f64 / total_latency.as_secs_f64()) as u64;
+
+    // Record the results
+    APTOS_NETWORK_BENCHMARK_PROBE_LATENCY_MICROS
+        .with_label_values(&[&peer_label, "avg"])
+        .set(avg_latency_micros as i64);
+    APTOS_NETWORK_BENCHMARK_PROBE_LATENCY_MICROS
+        .with_label_values(&[&peer_label, "max"])
+        .set(max_latency_micros as i64);
+    APTOS_NETWORK_BENCHMARK_PROBE_THROUGHPUT
+        .with_label_values(&[&peer_label])
+        .set(throughput_bytes_per_sec as i64);
+    info!(
+        "netbench [{}] probe: {} of {} messages succeeded, avg latency {} µs, \
+        max latency {} µs, throughput {} B/s",
+        peer_network_id,
+        num_succeeded,
+        num_messages,
+        avg_latency_micros,
+        max_latency_micros,
+        throughput_bytes_per_sec
+    );
+}
+
 pub struct NetbenchSharedState {
     // Circular buffer of sent records
     sent: Vec<SendRecord>,
@@ -651,3 +802,36 @@ pub static APTOS_NETWORK_BENCHMARK_RPC_MICROS: Lazy<IntCounterVec> = Lazy::new(|
 pub fn rpc_micros(state_label: &'static str) -> IntCounter {
     APTOS_NETWORK_BENCHMARK_RPC_MICROS.with_label_values(&[state_label])
 }
+
+pub static APTOS_NETWORK_BENCHMARK_PROBE_MESSAGES: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "aptos_network_benchmark_probe_messages",
+        "Number of net benchmark scheduled probe messages",
+        &["state"]
+    )
+    .unwrap()
+});
+
+fn probe_messages(state_label: &'static str) {
+    APTOS_NETWORK_BENCHMARK_PROBE_MESSAGES
+        .with_label_values(&[state_label])
+        .inc();
+}
+
+pub static APTOS_NETWORK_BENCHMARK_PROBE_LATENCY_MICROS: Lazy<IntGaugeVec> = Lazy::new(|| {
+    register_int_gauge_vec!(
+        "aptos_network_benchmark_probe_latency_micros",
+        "Latency (microseconds) of the latest net benchmark scheduled probe of each peer",
+        &["peer", "statistic"]
+    )
+    .unwrap()
+});
+
+pub static APTOS_NETWORK_BENCHMARK_PROBE_THROUGHPUT: Lazy<IntGaugeVec> = Lazy::new(|| {
+    register_int_gauge_vec!(
+        "aptos_network_benchmark_probe_throughput",
+        "Throughput (bytes per second) of the latest net benchmark scheduled probe of each peer",
+        &["peer"]
+    )
+    .unwrap()
+});
@@ -12,13 +12,14 @@
 //! long as the latter is in its trusted peers set.
 use aptos_config::{
     config::{
-        DiscoveryMethod, NetworkConfig, Peer, PeerRole, PeerSet, RoleType, CONNECTION_BACKOFF_BASE,
-        CONNECTIVITY_CHECK_INTERVAL_MS, MAX_CONNECTION_DELAY_MS, MAX_FRAME_SIZE,
-        MAX_FULLNODE_OUTBOUND_CONNECTIONS, MAX_INBOUND_CONNECTIONS, NETWORK_CHANNEL_SIZE,
+        DiscoveryMethod, NetworkConfig, Peer, PeerRole, PeerSet, PeerTrafficConfig, RoleType,
+        CONNECTION_BACKOFF_BASE, CONNECTIVITY_CHECK_INTERVAL_MS, MAX_CONNECTION_DELAY_MS,
+        MAX_FRAME_SIZE, MAX_FULLNODE_OUTBOUND_CONNECTIONS, MAX_INBOUND_CONNECTIONS,
+        NETWORK_CHANNEL_SIZE,
     },
     network_id::NetworkContext,
 };
-use aptos_event_notifications::{DbBackedOnChainConfig, EventSubscriptionService};
+use aptos_event_notifications::{EventSubscriptionService, InMemoryOnChainConfig};
 use aptos_logger::prelude::*;
 use aptos_netcore::transport::tcp::TCPBufferCfg;
 use aptos_network::{
@@ -31,7 +32,7 @@ use aptos_network::{
         ConnectionRequestSender,
     },
     protocols::{
-        health_checker::{self, builder::HealthCheckerBuilder},
+        health_checker::{self, builder::HealthCheckerBuilder, probe::TApplicationProbe},
         network::{
             NetworkApplicationConfig, NetworkClientConfig, NetworkServiceConfig, NewNetworkEvents,
             NewNetworkSender,
@@ -61,7 +62,7 @@ pub struct NetworkBuilder {
     executor: Option<Handle>,
     time_service: TimeService,
     network_context: NetworkContext,
-    discovery_listeners: Option<Vec<DiscoveryChangeListener<DbBackedOnChainConfig>>>,
+    discovery_listeners: Option<Vec<DiscoveryChangeListener<InMemoryOnChainConfig>>>,
     connectivity_manager_builder: Option<ConnectivityManagerBuilder>,
     health_checker_builder: Option<HealthCheckerBuilder>,
     peer_manager_builder: PeerManagerBuilder,
@@ -82,8 +83,11 @@ impl NetworkBuilder {
         max_frame_size: usize,
         max_message_size: usize,
         enable_proxy_protocol: bool,
+        enable_hybrid_key_exchange: bool,
+        enable_quic_transport: bool,
         network_channel_size: usize,
         inbound_connection_limit: usize,
+        peer_traffic_config: PeerTrafficConfig,
         tcp_buffer_cfg: TCPBufferCfg,
     ) -> Self {
         // A network cannot exist without a PeerManager
@@ -99,7 +103,10 @@ impl NetworkBuilder {
             max_frame_size,
             max_message_size,
             enable_proxy_protocol,
+            enable_hybrid_key_exchange,
+            enable_quic_transport,
             inbound_connection_limit,
+            peer_traffic_config,
             tcp_buffer_cfg,
         );
 
@@ -137,8 +144,11 @@ impl NetworkBuilder {
             MAX_FRAME_SIZE,
             MAX_MESSAGE_SIZE,
             false, /* Disable proxy protocol */
+            false, /* Disable hybrid key exchange */
+            false, /* Disable QUIC transport */
             NETWORK_CHANNEL_SIZE,
             MAX_INBOUND_CONNECTIONS,
+            PeerTrafficConfig::default(),
             TCPBufferCfg::default(),
         );
 
@@ -187,8 +197,11 @@ impl NetworkBuilder {
             config.max_frame_size,
             config.max_message_size,
             config.enable_proxy_protocol,
+            config.enable_hybrid_key_exchange,
+            config.enable_quic_transport,
             config.network_channel_size,
             config.max_inbound_connections,
+            config.peer_traffic,
             TCPBufferCfg::new_configs(
                 config.inbound_rx_buffer_size_bytes,
                 config.inbound_tx_buffer_size_bytes,
@@ -356,11 +369,17 @@ impl NetworkBuilder {
         for discovery_method in config.discovery_methods() {
             let listener = match discovery_method {
                 DiscoveryMethod::Onchain => {
-                    let reconfig_events = reconfig_subscription_service
-                        .as_mut()
-                        .expect("An event subscription service is required for on-chain discovery!")
+                    let event_subscription_service = reconfig_subscription_service.as_mut().expect(
+                        "An event subscription service is required for on-chain discovery!",
+                    );
+                    let reconfig_events = event_subscription_service
                         .subscribe_to_reconfigurations()
                         .expect("On-chain discovery is unable to subscribe to reconfigurations!");
+                    let address_update_events = event_subscription_service
+                        .subscribe_to_events(vec![], vec![
+                            "0x1::stake::UpdateNetworkAndFullnodeAddresses".to_string(),
+                        ])
+                        .expect("On-chain discovery is unable to subscribe to address updates!");
                     let identity_key = config.identity_key();
                     let pubkey = identity_key.public_key();
                     DiscoveryChangeListener::validator_set(
@@ -368,6 +387,7 @@ impl NetworkBuilder {
                         conn_mgr_reqs_tx.clone(),
                         pubkey,
                         reconfig_events,
+                        Some(address_update_events),
                     )
                 },
                 DiscoveryMethod::File(file_discovery) => DiscoveryChangeListener::file(
@@ -395,6 +415,16 @@ impl NetworkBuilder {
         }
     }
 
+    /// Adds an application probe for the health checker to run against each peer.
+    pub fn add_application_probe(&mut self, probe: Arc<dyn TApplicationProbe>) -> &mut Self {
+        assert_eq!(self.state, State::CREATED);
+        self.health_checker_builder
+            .as_mut()
+            .expect("Health checker must exist")
+            .add_application_probe(probe);
+        self
+    }
+
     /// Add a HealthChecker to the network.
     fn add_connection_monitoring(
         &mut self,
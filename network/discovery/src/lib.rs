@@ -8,7 +8,7 @@ use crate::{
 };
 use aptos_config::{config::PeerSet, network_id::NetworkContext};
 use aptos_crypto::x25519;
-use aptos_event_notifications::ReconfigNotificationListener;
+use aptos_event_notifications::{EventNotificationListener, ReconfigNotificationListener};
 use aptos_logger::prelude::*;
 use aptos_network::{
     connectivity_manager::{ConnectivityRequest, DiscoverySource},
@@ -70,11 +70,13 @@ impl<P: OnChainConfigProvider> DiscoveryChangeListener<P> {
         update_channel: aptos_channels::Sender<ConnectivityRequest>,
         expected_pubkey: x25519::PublicKey,
         reconfig_events: ReconfigNotificationListener<P>,
+        address_update_events: Option<EventNotificationListener>,
     ) -> Self {
         let source_stream = DiscoveryChangeStream::ValidatorSet(ValidatorSetStream::new(
             network_context,
             expected_pubkey,
             reconfig_events,
+            address_update_events,
         ));
         DiscoveryChangeListener {
             discovery_source: DiscoverySource::OnChainValidatorSet,
@@ -11,14 +11,23 @@ use aptos_config::{
     network_id::NetworkContext,
 };
 use aptos_crypto::x25519;
-use aptos_event_notifications::ReconfigNotificationListener;
+use aptos_event_notifications::{
+    EventNotification, EventNotificationListener, ReconfigNotification,
+    ReconfigNotificationListener,
+};
 use aptos_logger::prelude::*;
 use aptos_network::{counters::inc_by_with_context, logging::NetworkSchema};
 use aptos_short_hex_str::AsShortHexStr;
-use aptos_types::on_chain_config::{OnChainConfigPayload, OnChainConfigProvider, ValidatorSet};
+use aptos_types::{
+    network_address::NetworkAddress,
+    on_chain_config::{OnChainConfigProvider, ValidatorSet},
+    stake_pool::UpdateNetworkAndFullnodeAddresses,
+    transaction::Version,
+    PeerId,
+};
 use futures::Stream;
 use std::{
-    collections::HashSet,
+    collections::{HashMap, HashSet},
     pin::Pin,
     task::{Context, Poll},
 };
@@ -27,6 +36,16 @@ pub struct ValidatorSetStream<P: OnChainConfigProvider> {
     pub(crate) network_context: NetworkContext,
     expected_pubkey: x25519::PublicKey,
     reconfig_events: ReconfigNotificationListener<P>,
+    /// Network address updates, applied as they commit rather than when the next epoch
+    /// brings them into the validator set.
+    address_update_events: Option<EventNotificationListener>,
+    /// The version of the last reconfiguration, whose validator set includes all address
+    /// updates up to it
+    reconfig_version: Option<Version>,
+    /// The address updates committed since the last reconfiguration
+    address_updates: HashMap<PeerId, (Version, Vec<NetworkAddress>)>,
+    /// The peer set last sent, if any
+    peer_set: Option<PeerSet>,
 }
 
 impl<P: OnChainConfigProvider> ValidatorSetStream<P> {
@@ -34,11 +53,16 @@ impl<P: OnChainConfigProvider> ValidatorSetStream<P> {
         network_context: NetworkContext,
         expected_pubkey: x25519::PublicKey,
         reconfig_events: ReconfigNotificationListener<P>,
+        address_update_events: Option<EventNotificationListener>,
     ) -> Self {
         Self {
             network_context,
             expected_pubkey,
             reconfig_events,
+            address_update_events,
+            reconfig_version: None,
+            address_updates: HashMap::new(),
+            peer_set: None,
         }
     }
 
@@ -66,14 +90,25 @@ impl<P: OnChainConfigProvider> ValidatorSetStream<P> {
             .set(mismatch);
     }
 
-    fn extract_updates(&mut self, payload: OnChainConfigPayload<P>) -> PeerSet {
+    /// Returns the new peer set, or `None` if it's the same as the last one sent.
+    fn extract_updates(&mut self, notification: ReconfigNotification<P>) -> Option<PeerSet> {
         let _process_timer = EVENT_PROCESSING_LOOP_BUSY_DURATION_S.start_timer();
 
-        let node_set: ValidatorSet = payload
+        let node_set: ValidatorSet = notification
+            .on_chain_configs
             .get()
             .expect("failed to get ValidatorSet from payload");
 
-        let peer_set = extract_validator_set_updates(self.network_context, node_set);
+        self.reconfig_version = Some(notification.version);
+        self.address_updates
+            .retain(|_, (version, _)| *version > notification.version);
+
+        let mut peer_set = extract_validator_set_updates(self.network_context, node_set);
+        for (peer_id, (_, addrs)) in &self.address_updates {
+            if let Some(peer) = peer_set.get_mut(peer_id) {
+                apply_address_update(peer, addrs.clone());
+            }
+        }
         // Ensure that the public key matches what's onchain for this peer
         self.find_key_mismatches(
             peer_set
@@ -88,7 +123,86 @@ impl<P: OnChainConfigProvider> ValidatorSetStream<P> {
             peer_set.len() as u64,
         );
 
-        peer_set
+        self.send_if_changed(peer_set)
+    }
+
+    /// Applies the address updates of validators in the current validator set, and returns the
+    /// new peer set, or `None` if nothing changed.
+    fn extract_address_updates(&mut self, notification: EventNotification) -> Option<PeerSet> {
+        let _process_timer = EVENT_PROCESSING_LOOP_BUSY_DURATION_S.start_timer();
+
+        // Updates up to the last reconfiguration are already in its validator set
+        if self
+            .reconfig_version
+            .is_some_and(|version| notification.version <= version)
+        {
+            return None;
+        }
+
+        let is_validator = self.network_context.network_id().is_validator_network();
+        let mut peer_set = self.peer_set.clone();
+        for event in &notification.subscribed_events {
+            let Ok(update) = UpdateNetworkAndFullnodeAddresses::try_from(event) else {
+                continue;
+            };
+            let addrs = if is_validator {
+                update.new_validator_network_addresses()
+            } else {
+                update.new_fullnode_network_addresses()
+            };
+            let addrs = match addrs {
+                Ok(addrs) => addrs,
+                Err(err) => {
+                    inc_by_with_context(
+                        &DISCOVERY_COUNTS,
+                        &self.network_context,
+                        "read_failure",
+                        1,
+                    );
+                    warn!(
+                        NetworkSchema::new(&self.network_context),
+                        "OnChainDiscovery: Failed to parse updated network addresses: peer: {}, err: {}",
+                        update.pool_address,
+                        err
+                    );
+                    continue;
+                },
+            };
+
+            if let Some(peer) = peer_set
+                .as_mut()
+                .and_then(|peer_set| peer_set.get_mut(&update.pool_address))
+            {
+                apply_address_update(peer, addrs.clone());
+                inc_by_with_context(&DISCOVERY_COUNTS, &self.network_context, "updated_nodes", 1);
+            }
+            self.address_updates
+                .insert(update.pool_address, (notification.version, addrs));
+        }
+
+        // Nothing to update until the first validator set is known
+        let peer_set = peer_set?;
+        self.find_key_mismatches(
+            peer_set
+                .get(&self.network_context.peer_id())
+                .map(|peer| &peer.keys),
+        );
+
+        self.send_if_changed(peer_set)
+    }
+
+    fn send_if_changed(&mut self, peer_set: PeerSet) -> Option<PeerSet> {
+        if self.peer_set.as_ref() == Some(&peer_set) {
+            inc_by_with_context(
+                &DISCOVERY_COUNTS,
+                &self.network_context,
+                "unchanged_updates",
+                1,
+            );
+            return None;
+        }
+        self.peer_set = Some(peer_set.clone());
+        Some(peer_set)
     }
 }
 
@@ -96,15 +210,46 @@ impl<P: OnChainConfigProvider> Stream for ValidatorSetStream<P> {
     type Item = Result<PeerSet, DiscoveryError>;
 
     fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
-        Pin::new(&mut self.reconfig_events)
-            .poll_next(cx)
-            .map(|maybe_notification| {
-                maybe_notification
-                    .map(|notification| Ok(self.extract_updates(notification.on_chain_configs)))
-            })
+        loop {
+            match Pin::new(&mut self.reconfig_events).poll_next(cx) {
+                Poll::Ready(Some(notification)) => {
+                    if let Some(peer_set) = self.extract_updates(notification) {
+                        return Poll::Ready(Some(Ok(peer_set)));
+                    }
+                    continue;
+                },
+                Poll::Ready(None) => return Poll::Ready(None),
+                Poll::Pending => {},
+            }
+
+            let Some(address_update_events) = self.address_update_events.as_mut() else {
+                return Poll::Pending;
+            };
+            match Pin::new(address_update_events).poll_next(cx) {
+                Poll::Ready(Some(notification)) => {
+                    if let Some(peer_set) = self.extract_address_updates(notification) {
+                        return Poll::Ready(Some(Ok(peer_set)));
+                    }
+                },
+                Poll::Ready(None) => {
+                    self.address_update_events = None;
+                    return Poll::Pending;
+                },
+                Poll::Pending => return Poll::Pending,
+            }
+        }
     }
 }
 
+/// Switches `peer` over to `addrs`. The keys in the validator set stay trusted alongside the
+/// new ones until the next epoch, so that validators may announce a new network key ahead of
+/// switching to it.
+fn apply_address_update(peer: &mut Peer, addrs: Vec<NetworkAddress>) {
+    let update = Peer::from_addrs(peer.role, addrs);
+    peer.addresses = update.addresses;
+    peer.keys.extend(update.keys);
+}
+
 /// Extracts a set of ConnectivityRequests from a ValidatorSet which are appropriate for a network with type role.
 pub(crate) fn extract_validator_set_updates(
     network_context: NetworkContext,
@@ -157,17 +302,16 @@ mod tests {
     use aptos_channels::{aptos_channel, message_queues::QueueStyle};
     use aptos_config::config::HANDSHAKE_VERSION;
     use aptos_crypto::{bls12381, x25519::PrivateKey, PrivateKey as PK, Uniform};
-    use aptos_event_notifications::ReconfigNotification;
     use aptos_types::{
-        network_address::NetworkAddress,
-        on_chain_config::{InMemoryOnChainConfig, OnChainConfig},
+        contract_event::ContractEvent,
+        on_chain_config::{InMemoryOnChainConfig, OnChainConfig, OnChainConfigPayload},
+        stake_pool::UPDATE_NETWORK_AND_FULLNODE_ADDRESSES_MOVE_TYPE_TAG,
         validator_config::ValidatorConfig,
         validator_info::ValidatorInfo,
-        PeerId,
     };
-    use futures::executor::block_on;
+    use futures::{executor::block_on, FutureExt, StreamExt};
     use rand::{rngs::StdRng, SeedableRng};
-    use std::{collections::HashMap, time::Instant};
+    use std::time::Instant;
     use tokio::{
         runtime::Runtime,
         time::{timeout_at, Duration},
@@ -195,6 +339,7 @@ mod tests {
             conn_mgr_reqs_tx,
             pubkey,
             reconfig_listener,
+            None,
         );
 
         // Build up and send an update with a different pubkey
@@ -221,6 +366,67 @@ mod tests {
         check_network_key_mismatch_metric(1, &network_context);
     }
 
+    #[test]
+    fn address_updates_apply_until_next_epoch() {
+        let consensus_pubkey = bls12381::PrivateKey::generate_for_testing().public_key();
+        let pubkey = test_pubkey([0u8; 32]);
+        let new_pubkey = test_pubkey([1u8; 32]);
+        let peer_id = aptos_types::account_address::from_identity_public_key(pubkey);
+
+        let (mut reconfig_sender, reconfig_events) = aptos_channel::new(QueueStyle::LIFO, 1, None);
+        let (mut event_sender, address_update_events) =
+            aptos_channel::new(QueueStyle::LIFO, 1, None);
+        let mut stream = ValidatorSetStream::new(
+            NetworkContext::mock(),
+            pubkey,
+            ReconfigNotificationListener {
+                notification_receiver: reconfig_events,
+            },
+            Some(EventNotificationListener {
+                notification_receiver: address_update_events,
+            }),
+        );
+        let old_address = NetworkAddress::mock().append_prod_protos(pubkey, HANDSHAKE_VERSION);
+        let new_address = NetworkAddress::mock().append_prod_protos(new_pubkey, HANDSHAKE_VERSION);
+
+        send_pubkey_update(
+            peer_id,
+            consensus_pubkey.clone(),
+            pubkey,
+            &mut reconfig_sender,
+        );
+        let peer_set = block_on(stream.next()).unwrap().unwrap();
+        assert_eq!(peer_set[&peer_id].addresses, vec![old_address.clone()]);
+
+        // The update is applied, and both keys are trusted until the next epoch
+        let new_addresses = bcs::to_bytes(&vec![new_address.clone()]).unwrap();
+        let update = UpdateNetworkAndFullnodeAddresses {
+            pool_address: peer_id,
+            old_network_addresses: vec![],
+            new_network_addresses: new_addresses.clone(),
+            old_fullnode_addresses: vec![],
+            new_fullnode_addresses: new_addresses,
+        };
+        let event = ContractEvent::new_v2(
+            UPDATE_NETWORK_AND_FULLNODE_ADDRESSES_MOVE_TYPE_TAG.clone(),
+            bcs::to_bytes(&update).unwrap(),
+        )
+        .unwrap();
+        event_sender
+            .push((), EventNotification {
+                version: 2,
+                subscribed_events: vec![event],
+            })
+            .unwrap();
+        let peer_set = block_on(stream.next()).unwrap().unwrap();
+        assert_eq!(peer_set[&peer_id].addresses, vec![new_address]);
+        assert_eq!(peer_set[&peer_id].keys, HashSet::from([pubkey, new_pubkey]));
+
+        // A reconfiguration before the update doesn't revert it, nor change anything
+        send_pubkey_update(peer_id, consensus_pubkey, pubkey, &mut reconfig_sender);
+        assert!(stream.next().now_or_never().is_none());
+    }
+
     fn check_network_key_mismatch_metric(expected: i64, network_context: &NetworkContext) {
         assert_eq!(
             expected,
@@ -7,6 +7,7 @@ use crate::{
 };
 use aptos_peer_monitoring_service_types::PeerMonitoringMetadata;
 use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, time::Duration};
 
 /// The current connection state of a peer
 /// TODO: Allow nodes that are unhealthy to stay connected
@@ -23,6 +24,9 @@ pub struct PeerMetadata {
     pub(crate) connection_state: ConnectionState,
     pub(crate) connection_metadata: ConnectionMetadata,
     pub(crate) peer_monitoring_metadata: PeerMonitoringMetadata,
+    /// The latest response time of each application probed on the peer
+    /// (e.g., mempool acks and storage service requests), keyed by probe name.
+    pub(crate) application_latencies: BTreeMap<String, Duration>,
 }
 
 impl PeerMetadata {
@@ -31,6 +35,7 @@ impl PeerMetadata {
             connection_state: ConnectionState::Connected,
             connection_metadata,
             peer_monitoring_metadata: PeerMonitoringMetadata::default(),
+            application_latencies: BTreeMap::new(),
         }
     }
 
@@ -44,6 +49,7 @@ impl PeerMetadata {
             connection_state: ConnectionState::Connected,
             connection_metadata,
             peer_monitoring_metadata,
+            application_latencies: BTreeMap::new(),
         }
     }
 
@@ -89,4 +95,21 @@ impl PeerMetadata {
     pub fn get_peer_monitoring_metadata(&self) -> &PeerMonitoringMetadata {
         &self.peer_monitoring_metadata
     }
+
+    /// Returns the latest application latencies, keyed by probe name
+    pub fn get_application_latencies(&self) -> &BTreeMap<String, Duration> {
+        &self.application_latencies
+    }
+
+    /// Returns the latency used to score the peer: the slowest of the average
+    /// ping latency and the latest application latencies. This ensures a peer
+    /// that answers pings quickly, but whose applications are slow, isn't preferred.
+    pub fn get_effective_latency_secs(&self) -> Option<f64> {
+        let ping_latency = self.peer_monitoring_metadata.average_ping_latency_secs;
+        self.application_latencies
+            .values()
+            .map(Duration::as_secs_f64)
+            .chain(ping_latency)
+            .reduce(f64::max)
+    }
 }
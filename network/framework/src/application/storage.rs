@@ -317,6 +317,37 @@ impl PeersAndMetadata {
         Ok(())
     }
 
+    /// Updates the latest latency of the given application (e.g., as measured
+    /// by a health checker probe) for the peer. If no peer metadata exists, an
+    /// error is returned.
+    pub fn update_application_latency(
+        &self,
+        peer_network_id: PeerNetworkId,
+        application: &str,
+        latency: Duration,
+    ) -> Result<(), Error> {
+        // Grab the write lock for the peer metadata
+        let mut peers_and_metadata = self.peers_and_metadata.write();
+
+        // Fetch the peer metadata for the given network
+        let peer_metadata_for_network =
+            get_peer_metadata_for_network(&peer_network_id, &mut peers_and_metadata)?;
+
+        // Update the application latency for the peer
+        if let Some(peer_metadata) = peer_metadata_for_network.get_mut(&peer_network_id.peer_id()) {
+            peer_metadata
+                .application_latencies
+                .insert(application.to_string(), latency);
+        } else {
+            return Err(missing_peer_metadata_error(&peer_network_id));
+        }
+
+        // Update the cached peers and metadata
+        self.set_cached_peers_and_metadata(peers_and_metadata.clone());
+
+        Ok(())
+    }
+
     /// Updates the cached peers and metadata using the given map
     fn set_cached_peers_and_metadata(
         &self,
@@ -455,10 +486,7 @@ impl PeersAndMetadata {
                 cached_peers_and_metadata
                     .get(&network_id)
                     .and_then(|peers| peers.get(peer))
-                    .and_then(|peer| {
-                        peer.get_peer_monitoring_metadata()
-                            .average_ping_latency_secs
-                    })
+                    .and_then(|peer| peer.get_effective_latency_secs())
                     .unwrap_or_default()
             };
 
@@ -390,6 +390,29 @@ pub static PENDING_HEALTH_CHECKER_NETWORK_EVENTS: Lazy<IntCounterVec> = Lazy::ne
     .unwrap()
 });
 
+/// Histogram of application probe latencies measured by the Health Checker.
+pub static APTOS_NETWORK_APPLICATION_PROBE_LATENCY: Lazy<HistogramVec> = Lazy::new(|| {
+    register_histogram_vec!(
+        "aptos_network_application_probe_latency_seconds",
+        "Application probe latency in seconds, by probe and result",
+        &["role_type", "network_id", "probe", "result"]
+    )
+    .unwrap()
+});
+
+pub fn application_probe_latency(
+    network_context: &NetworkContext,
+    probe: &str,
+    result: &str,
+) -> Histogram {
+    APTOS_NETWORK_APPLICATION_PROBE_LATENCY.with_label_values(&[
+        network_context.role().as_str(),
+        network_context.network_id().as_str(),
+        probe,
+        result,
+    ])
+}
+
 /// Counter of pending network events to Discovery.
 pub static PENDING_DISCOVERY_NETWORK_EVENTS: Lazy<IntCounterVec> = Lazy::new(|| {
     register_int_counter_vec!(
@@ -565,11 +588,22 @@ pub static NETWORK_APPLICATION_INBOUND_METRIC: Lazy<HistogramVec> = Lazy::new(||
     .unwrap()
 });
 
+/// If `remote_peer_id` is given, also records `APTOS_NETWORK_PEER_TRAFFIC_BYTES`
 pub fn network_application_inbound_traffic(
     network_context: NetworkContext,
+    remote_peer_id: Option<PeerId>,
     protocol_id: ProtocolId,
     size: u64,
 ) {
+    if let Some(remote_peer_id) = remote_peer_id {
+        peer_traffic_bytes(
+            &network_context,
+            &remote_peer_id,
+            protocol_id,
+            INBOUND_LABEL,
+        )
+        .inc_by(size);
+    }
     NETWORK_APPLICATION_INBOUND_METRIC
         .with_label_values(&[
             network_context.role().as_str(),
@@ -596,11 +630,22 @@ pub static NETWORK_APPLICATION_OUTBOUND_METRIC: Lazy<HistogramVec> = Lazy::new(|
     .unwrap()
 });
 
+/// If `remote_peer_id` is given, also records `APTOS_NETWORK_PEER_TRAFFIC_BYTES`
 pub fn network_application_outbound_traffic(
     network_context: NetworkContext,
+    remote_peer_id: Option<PeerId>,
     protocol_id: ProtocolId,
     size: u64,
 ) {
+    if let Some(remote_peer_id) = remote_peer_id {
+        peer_traffic_bytes(
+            &network_context,
+            &remote_peer_id,
+            protocol_id,
+            OUTBOUND_LABEL,
+        )
+        .inc_by(size);
+    }
     NETWORK_APPLICATION_OUTBOUND_METRIC
         .with_label_values(&[
             network_context.role().as_str(),
@@ -612,6 +657,49 @@ pub fn network_application_outbound_traffic(
         .observe(size as f64);
 }
 
+/// Application bytes exchanged with each connected peer, only recorded if enabled in the
+/// `PeerTrafficConfig` as every peer adds its own series. The series of a peer are removed when
+/// its connection closes (see `remove_peer_traffic_metrics`).
+pub static APTOS_NETWORK_PEER_TRAFFIC_BYTES: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "aptos_network_peer_traffic_bytes",
+        "Application bytes exchanged with each connected peer, by protocol and direction",
+        &["network_id", "remote_peer_id", "protocol_id", "direction"]
+    )
+    .unwrap()
+});
+
+pub fn peer_traffic_bytes(
+    network_context: &NetworkContext,
+    remote_peer_id: &PeerId,
+    protocol_id: ProtocolId,
+    direction: &str,
+) -> IntCounter {
+    APTOS_NETWORK_PEER_TRAFFIC_BYTES.with_label_values(&[
+        network_context.network_id().as_str(),
+        remote_peer_id.short_str().as_str(),
+        protocol_id.as_str(),
+        direction,
+    ])
+}
+
+/// Removes the traffic series of a peer whose connection has closed
+pub fn remove_peer_traffic_metrics(network_context: &NetworkContext, remote_peer_id: &PeerId) {
+    let network_id = network_context.network_id();
+    let remote_peer_id = remote_peer_id.short_str();
+    for protocol_id in ProtocolId::all() {
+        for direction in [INBOUND_LABEL, OUTBOUND_LABEL] {
+            // The series only exists if there was traffic
+            let _ = APTOS_NETWORK_PEER_TRAFFIC_BYTES.remove_label_values(&[
+                network_id.as_str(),
+                remote_peer_id.as_str(),
+                protocol_id.as_str(),
+                direction,
+            ]);
+        }
+    }
+}
+
 /// Time it takes to perform message serialization and deserialization
 pub static NETWORK_APPLICATION_SERIALIZATION_METRIC: Lazy<HistogramVec> = Lazy::new(|| {
     register_histogram_vec!(
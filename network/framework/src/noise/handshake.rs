@@ -150,16 +150,20 @@ pub struct NoiseUpgrader {
     noise_config: noise::NoiseConfig,
     /// Handshake authentication can be either mutual or server-only authentication.
     auth_mode: HandshakeAuthMode,
+    /// Whether inbound handshakes may use the post-quantum hybrid key exchange.
+    /// Inbound handshakes using the classic key exchange are always accepted.
+    accept_hybrid_key_exchange: bool,
 }
 
 impl NoiseUpgrader {
     /// The client message consist of the prologue + a noise message with a timestamp as payload.
     const CLIENT_MESSAGE_SIZE: usize =
         Self::PROLOGUE_SIZE + noise::handshake_init_msg_len(AntiReplayTimestamps::TIMESTAMP_SIZE);
+    /// A hybrid client message is a classic one followed by the client's KEM key.
+    const HYBRID_CLIENT_MESSAGE_SIZE: usize =
+        Self::CLIENT_MESSAGE_SIZE + noise::MLKEM768_PUBLIC_KEY_SIZE;
     /// The prologue is the client's peer_id and the remote's expected public key.
     const PROLOGUE_SIZE: usize = PeerId::LENGTH + x25519::PUBLIC_KEY_SIZE;
-    /// The server's message contains no payload.
-    const SERVER_MESSAGE_SIZE: usize = noise::handshake_resp_msg_len(0);
 
     /// Create a new NoiseConfig with the provided keypair and authentication mode.
     pub fn new(
@@ -171,6 +175,21 @@ impl NoiseUpgrader {
             network_context,
             noise_config: noise::NoiseConfig::new(key),
             auth_mode,
+            accept_hybrid_key_exchange: false,
+        }
+    }
+
+    /// Accept inbound handshakes using the post-quantum hybrid key exchange, on top of the
+    /// classic ones.
+    pub fn with_hybrid_key_exchange(mut self) -> Self {
+        self.accept_hybrid_key_exchange = true;
+        self
+    }
+
+    fn client_message_size(key_exchange: noise::KeyExchange) -> usize {
+        match key_exchange {
+            noise::KeyExchange::X25519 => Self::CLIENT_MESSAGE_SIZE,
+            noise::KeyExchange::X25519MlKem768 => Self::HYBRID_CLIENT_MESSAGE_SIZE,
         }
     }
 
@@ -182,10 +201,34 @@ impl NoiseUpgrader {
     /// Noise handshake payload. Currently this counter is always a millisecond-
     /// granularity unix epoch timestamp.
     pub async fn upgrade_outbound<TSocket, F>(
+        &self,
+        socket: TSocket,
+        remote_peer_id: PeerId,
+        remote_public_key: x25519::PublicKey,
+        time_provider: F,
+    ) -> Result<(NoiseStream<TSocket>, PeerRole), NoiseHandshakeError>
+    where
+        TSocket: AsyncRead + AsyncWrite + Debug + Unpin,
+        F: Fn() -> [u8; AntiReplayTimestamps::TIMESTAMP_SIZE],
+    {
+        self.upgrade_outbound_with_key_exchange(
+            socket,
+            remote_peer_id,
+            remote_public_key,
+            noise::KeyExchange::X25519,
+            time_provider,
+        )
+        .await
+    }
+
+    /// Same as `upgrade_outbound`, using the given key exchange. The server must accept
+    /// `KeyExchange::X25519MlKem768`, which is advertised via its handshake version.
+    pub async fn upgrade_outbound_with_key_exchange<TSocket, F>(
         &self,
         mut socket: TSocket,
         remote_peer_id: PeerId,
         remote_public_key: x25519::PublicKey,
+        key_exchange: noise::KeyExchange,
         time_provider: F,
     ) -> Result<(NoiseStream<TSocket>, PeerRole), NoiseHandshakeError>
     where
@@ -193,7 +236,7 @@ impl NoiseUpgrader {
         F: Fn() -> [u8; AntiReplayTimestamps::TIMESTAMP_SIZE],
     {
         // buffer to hold prologue + first noise handshake message
-        let mut client_message = [0; Self::CLIENT_MESSAGE_SIZE];
+        let mut client_message = vec![0; Self::client_message_size(key_exchange)];
 
         // craft prologue = self_peer_id | expected_public_key
         client_message[..PeerId::LENGTH].copy_from_slice(self.network_context.peer_id().as_ref());
@@ -209,8 +252,9 @@ impl NoiseUpgrader {
         let mut rng = rand::rngs::OsRng;
         let initiator_state = self
             .noise_config
-            .initiate_connection(
+            .initiate_connection_with_key_exchange(
                 &mut rng,
+                key_exchange,
                 prologue_msg,
                 remote_public_key,
                 Some(&payload),
@@ -239,7 +283,7 @@ impl NoiseUpgrader {
             self.network_context,
             remote_public_key,
         );
-        let mut server_response = [0u8; Self::SERVER_MESSAGE_SIZE];
+        let mut server_response = vec![0u8; key_exchange.resp_msg_len(0)];
         socket
             .read_exact(&mut server_response)
             .await
@@ -319,7 +363,7 @@ impl NoiseUpgrader {
         TSocket: AsyncRead + AsyncWrite + Debug + Unpin,
     {
         // buffer to contain the client first message
-        let mut client_message = [0; Self::CLIENT_MESSAGE_SIZE];
+        let mut client_message = vec![0; Self::CLIENT_MESSAGE_SIZE];
 
         // receive the prologue + first noise handshake message
         trace!("{} noise server: handshake read", self.network_context);
@@ -357,12 +401,37 @@ impl NoiseUpgrader {
             ));
         }
 
-        // parse it
+        // parse it. A hybrid client message starts like a classic one, but is bound to a
+        // different protocol name, so decrypting it as a classic one fails.
         let (prologue, client_init_message) = client_message.split_at(Self::PROLOGUE_SIZE);
-        let (remote_public_key, handshake_state, payload) = self
+        let (remote_public_key, handshake_state, payload) = match self
             .noise_config
             .parse_client_init_message(prologue, client_init_message)
-            .map_err(|err| NoiseHandshakeError::ServerParseClient(remote_peer_short, err))?;
+        {
+            Err(noise::NoiseError::Decrypt) if self.accept_hybrid_key_exchange => {
+                // receive the client's KEM key
+                client_message.resize(Self::HYBRID_CLIENT_MESSAGE_SIZE, 0);
+                socket
+                    .read_exact(&mut client_message[Self::CLIENT_MESSAGE_SIZE..])
+                    .await
+                    .map_err(NoiseHandshakeError::ServerReadFailed)?;
+
+                let (prologue, client_init_message) = client_message.split_at(Self::PROLOGUE_SIZE);
+                self.noise_config
+                    .parse_client_init_message_with_key_exchange(
+                        noise::KeyExchange::X25519MlKem768,
+                        prologue,
+                        client_init_message,
+                    )
+            },
+            result => result,
+        }
+        .map_err(|err| NoiseHandshakeError::ServerParseClient(remote_peer_short, err))?;
+        let key_exchange = if client_message.len() == Self::HYBRID_CLIENT_MESSAGE_SIZE {
+            noise::KeyExchange::X25519MlKem768
+        } else {
+            noise::KeyExchange::X25519
+        };
 
         // if mutual auth mode, verify the remote pubkey is in our set of trusted peers
         let network_id = self.network_context.network_id();
@@ -456,7 +525,8 @@ impl NoiseUpgrader {
 
         // construct the response
         let mut rng = rand::rngs::OsRng;
-        let mut server_response = [0u8; Self::SERVER_MESSAGE_SIZE];
+        // (the server's message contains no payload)
+        let mut server_response = vec![0u8; key_exchange.resp_msg_len(0)];
         let session = self
             .noise_config
             .respond_to_client(&mut rng, handshake_state, None, &mut server_response)
@@ -717,6 +787,48 @@ mod test {
         test_handshake_success(true /* is_mutual_auth */);
     }
 
+    #[test]
+    fn test_hybrid_handshake() {
+        let ((client, client_public_key), (server, server_public_key)) = build_peers(true, None);
+        let server_peer_id = server.network_context.peer_id();
+        let hybrid_handshake = |server: &NoiseUpgrader, timestamp: u64| {
+            let (dialer_socket, listener_socket) = MemorySocket::new_pair();
+            block_on(join(
+                client.upgrade_outbound_with_key_exchange(
+                    dialer_socket,
+                    server_peer_id,
+                    server_public_key,
+                    noise::KeyExchange::X25519MlKem768,
+                    bad_timestamp(timestamp),
+                ),
+                server.upgrade_inbound(listener_socket),
+            ))
+        };
+
+        // a server that doesn't accept the hybrid key exchange rejects it
+        let (client_res, server_res) = hybrid_handshake(&server, 1);
+        client_res.unwrap_err();
+        assert!(matches!(
+            server_res,
+            Err(NoiseHandshakeError::ServerParseClient(
+                _,
+                noise::NoiseError::Decrypt
+            ))
+        ));
+
+        // a server that accepts it still accepts the classic key exchange too
+        let server = server.with_hybrid_key_exchange();
+        let (client_res, server_res) = hybrid_handshake(&server, 2);
+        let (client_stream, _) = client_res.unwrap();
+        let (server_stream, _, _) = server_res.unwrap();
+        assert_eq!(client_stream.get_remote_static(), server_public_key);
+        assert_eq!(server_stream.get_remote_static(), client_public_key);
+
+        let (client_res, server_res) = perform_handshake(&client, &server, server_public_key);
+        client_res.unwrap();
+        server_res.unwrap();
+    }
+
     fn test_handshake_self_fails(is_mutual_auth: bool) {
         let (_, (server, server_public_key)) = build_peers(is_mutual_auth, None);
         let (client_res, server_res) = perform_handshake(&server, &server, server_public_key);
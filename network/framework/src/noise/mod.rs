@@ -9,6 +9,10 @@
 //! (the client sends one message, then the server responds).
 //! For more information about Noise and our implementation, refer to the [crypto] crate.
 //!
+//! Peers can opt into a post-quantum hybrid (X25519+ML-KEM-768) variant of the handshake, which
+//! they advertise via the handshake version of their addresses. A server accepting the hybrid
+//! handshake still accepts the classic one.
+//!
 //! Usage example:
 //!
 //! ```
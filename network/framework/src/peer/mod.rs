@@ -18,7 +18,8 @@
 use crate::{
     counters::{
         self, network_application_inbound_traffic, network_application_outbound_traffic,
-        DECLINED_LABEL, FAILED_LABEL, RECEIVED_LABEL, SENT_LABEL, UNKNOWN_LABEL,
+        DECLINED_LABEL, FAILED_LABEL, INBOUND_LABEL, OUTBOUND_LABEL, RECEIVED_LABEL, SENT_LABEL,
+        UNKNOWN_LABEL,
     },
     logging::NetworkSchema,
     peer_manager::{PeerManagerError, TransportNotification},
@@ -36,8 +37,16 @@ use crate::{
     ProtocolId,
 };
 use aptos_channels::{aptos_channel, message_queues::QueueStyle};
-use aptos_config::network_id::{NetworkContext, PeerNetworkId};
+use aptos_config::{
+    config::{PeerShapingConfig, PeerTrafficConfig},
+    network_id::{NetworkContext, PeerNetworkId},
+};
+use aptos_infallible::Mutex;
 use aptos_logger::prelude::*;
+use aptos_rate_limiter::{
+    async_lib::AsyncRateLimiter,
+    rate_limit::{Bucket, SharedBucket},
+};
 use aptos_short_hex_str::AsShortHexStr;
 use aptos_time_service::{TimeService, TimeServiceTrait};
 use aptos_types::PeerId;
@@ -138,6 +147,8 @@ pub struct Peer<TSocket> {
     max_message_size: usize,
     /// Inbound stream buffer
     inbound_stream: InboundStreamBuffer,
+    /// Accounting and shaping of the traffic of the connection
+    peer_traffic_config: PeerTrafficConfig,
 }
 
 impl<TSocket> Peer<TSocket>
@@ -160,6 +171,7 @@ where
         max_concurrent_outbound_rpcs: u32,
         max_frame_size: usize,
         max_message_size: usize,
+        peer_traffic_config: PeerTrafficConfig,
     ) -> Self {
         let Connection {
             metadata: connection_metadata,
@@ -182,17 +194,20 @@ where
                 remote_peer_id,
                 inbound_rpc_timeout,
                 max_concurrent_inbound_rpcs,
+                peer_traffic_config.enable_peer_traffic_metrics,
             ),
             outbound_rpcs: OutboundRpcs::new(
                 network_context,
                 time_service,
                 remote_peer_id,
                 max_concurrent_outbound_rpcs,
+                peer_traffic_config.enable_peer_traffic_metrics,
             ),
             state: State::Connected,
             max_frame_size,
             max_message_size,
             inbound_stream: InboundStreamBuffer::new(max_fragments),
+            peer_traffic_config,
         }
     }
 
@@ -200,6 +215,34 @@ where
         self.connection_metadata.remote_peer_id
     }
 
+    /// Returns the peer to account the application traffic to, if per-peer metrics are enabled.
+    fn traffic_metrics_peer_id(&self) -> Option<PeerId> {
+        self.peer_traffic_config
+            .enable_peer_traffic_metrics
+            .then_some(self.remote_peer_id())
+    }
+
+    /// Returns the token bucket (of bytes) shaping this connection in the given direction,
+    /// or `None` if that direction isn't shaped.
+    fn rate_limit_bucket(
+        &self,
+        direction: &'static str,
+        config: Option<PeerShapingConfig>,
+    ) -> Option<SharedBucket> {
+        let config = config?;
+        // A bucket can't be smaller than its fill rate
+        let size = config.byte_burst.max(config.byte_rate);
+        Some(Arc::new(Mutex::new(Bucket::new(
+            direction.to_string(),
+            self.network_context.to_string(),
+            self.remote_peer_id().short_str().to_string(),
+            size,
+            size,
+            config.byte_rate,
+            Some(counters::NETWORK_RATE_LIMIT_METRICS.clone()),
+        ))))
+    }
+
     pub async fn start(mut self) {
         let remote_peer_id = self.remote_peer_id();
         trace!(
@@ -214,9 +257,18 @@ where
         let (read_socket, write_socket) =
             tokio::io::split(self.connection.take().unwrap().compat());
 
-        let mut reader =
-            MultiplexMessageStream::new(read_socket.compat(), self.max_frame_size).fuse();
-        let writer = MultiplexMessageSink::new(write_socket.compat_write(), self.max_frame_size);
+        // Shape each half independently
+        let read_socket = AsyncRateLimiter::new(
+            read_socket.compat(),
+            self.rate_limit_bucket(INBOUND_LABEL, self.peer_traffic_config.inbound_shaping),
+        );
+        let write_socket = AsyncRateLimiter::new(
+            write_socket.compat_write(),
+            self.rate_limit_bucket(OUTBOUND_LABEL, self.peer_traffic_config.outbound_shaping),
+        );
+
+        let mut reader = MultiplexMessageStream::new(read_socket, self.max_frame_size).fuse();
+        let writer = MultiplexMessageSink::new(write_socket, self.max_frame_size);
 
         // Start writer "process" as a separate task. We receive two handles to
         // communicate with the task:
@@ -454,6 +506,7 @@ where
                 let data_len = direct.raw_msg.len();
                 network_application_inbound_traffic(
                     self.network_context,
+                    self.traffic_metrics_peer_id(),
                     direct.protocol_id,
                     data_len as u64,
                 );
@@ -671,7 +724,12 @@ where
         counters::direct_send_bytes(&self.network_context, SENT_LABEL).inc_by(data_len);
 
         // Update the general network traffic metrics
-        network_application_outbound_traffic(self.network_context, protocol_id, data_len);
+        network_application_outbound_traffic(
+            self.network_context,
+            self.traffic_metrics_peer_id(),
+            protocol_id,
+            data_len,
+        );
     }
 
     fn shutdown(&mut self, reason: DisconnectReason) {
@@ -704,6 +762,10 @@ where
         }
 
         let remote_peer_id = self.remote_peer_id();
+        if self.peer_traffic_config.enable_peer_traffic_metrics {
+            counters::remove_peer_traffic_metrics(&self.network_context, &remote_peer_id);
+        }
+
         // Send a PeerDisconnected event to PeerManager.
         if let Err(e) = self
             .connection_notifs_tx
@@ -25,7 +25,10 @@ use crate::{
     ProtocolId,
 };
 use aptos_channels::{self, aptos_channel, message_queues::QueueStyle};
-use aptos_config::{config::PeerRole, network_id::NetworkContext};
+use aptos_config::{
+    config::{PeerRole, PeerShapingConfig, PeerTrafficConfig},
+    network_id::NetworkContext,
+};
 use aptos_logger::info;
 use aptos_memsocket::MemorySocket;
 use aptos_netcore::transport::ConnectionOrigin;
@@ -43,7 +46,7 @@ use std::{
     collections::{HashMap, HashSet},
     str::FromStr,
     sync::Arc,
-    time::Duration,
+    time::{Duration, Instant},
 };
 use tokio::runtime::{Handle, Runtime};
 use tokio_util::compat::{
@@ -64,6 +67,29 @@ fn build_test_peer(
     PeerHandle,
     MemorySocket,
     aptos_channels::Receiver<TransportNotification<MemorySocket>>,
+) {
+    build_test_peer_with_traffic_config(
+        executor,
+        time_service,
+        origin,
+        upstream_handlers,
+        PeerTrafficConfig::default(),
+    )
+}
+
+fn build_test_peer_with_traffic_config(
+    executor: Handle,
+    time_service: TimeService,
+    origin: ConnectionOrigin,
+    upstream_handlers: Arc<
+        HashMap<ProtocolId, aptos_channel::Sender<(PeerId, ProtocolId), ReceivedMessage>>,
+    >,
+    peer_traffic_config: PeerTrafficConfig,
+) -> (
+    Peer<MemorySocket>,
+    PeerHandle,
+    MemorySocket,
+    aptos_channels::Receiver<TransportNotification<MemorySocket>>,
 ) {
     let (a, b) = MemorySocket::new_pair();
     let peer_id = PeerId::random();
@@ -97,6 +123,7 @@ fn build_test_peer(
         MAX_CONCURRENT_OUTBOUND_RPCS,
         MAX_FRAME_SIZE,
         MAX_MESSAGE_SIZE,
+        peer_traffic_config,
     );
     let peer_handle = PeerHandle(peer_reqs_tx);
 
@@ -243,6 +270,63 @@ fn peer_send_message() {
     rt.block_on(future::join3(peer.start(), server, client));
 }
 
+// Sending outbound DirectSends faster than the peer's outbound shaping allows should hold them
+// back until the shaping lets them through.
+#[test]
+fn peer_send_message_shaped() {
+    ::aptos_logger::Logger::init_for_testing();
+    let rt = Runtime::new().unwrap();
+    let upstream_handlers = Arc::new(HashMap::new());
+    let peer_traffic_config = PeerTrafficConfig {
+        outbound_shaping: Some(PeerShapingConfig {
+            byte_rate: 1_000,
+            byte_burst: 1_000,
+        }),
+        ..PeerTrafficConfig::default()
+    };
+    let start = Instant::now();
+    let (peer, mut peer_handle, mut connection, _connection_notifs_rx) =
+        build_test_peer_with_traffic_config(
+            rt.handle().clone(),
+            TimeService::mock(),
+            ConnectionOrigin::Inbound,
+            upstream_handlers,
+            peer_traffic_config,
+        );
+    let (mut client_sink, mut client_stream) = build_network_sink_stream(&mut connection);
+
+    let send_msg = Message {
+        protocol_id: PROTOCOL,
+        mdata: Bytes::from(vec![0; 1_000]),
+    };
+    let recv_msg = MultiplexMessage::Message(NetworkMessage::DirectSendMsg(DirectSendMsg {
+        protocol_id: PROTOCOL,
+        priority: 0,
+        raw_msg: vec![0; 1_000],
+    }));
+
+    let client = async {
+        // Client should receive the direct send messages.
+        for _ in 0..2 {
+            let msg = client_stream.next().await.unwrap().unwrap();
+            assert_eq!(msg, recv_msg);
+        }
+        // The first 1000 bytes go out right away, but the rest (including the framing) has to
+        // wait for the bucket to be refilled twice.
+        assert!(start.elapsed() >= Duration::from_secs(2));
+        // Client then closes the connection.
+        client_sink.close().await.unwrap();
+    };
+
+    let server = async {
+        // Server sends the direct send messages at once.
+        for _ in 0..2 {
+            peer_handle.send_direct_send(send_msg.clone());
+        }
+    };
+    rt.block_on(future::join3(peer.start(), server, client));
+}
+
 fn test_upstream_handlers() -> (
     Arc<HashMap<ProtocolId, aptos_channel::Sender<(PeerId, ProtocolId), ReceivedMessage>>>,
     aptos_channel::Receiver<(PeerId, ProtocolId), ReceivedMessage>,
@@ -18,17 +18,22 @@ use crate::{
     ProtocolId,
 };
 use aptos_channels::{self, aptos_channel, message_queues::QueueStyle};
-use aptos_config::{config::HANDSHAKE_VERSION, network_id::NetworkContext};
+use aptos_config::{
+    config::{PeerTrafficConfig, HANDSHAKE_VERSION, HYBRID_HANDSHAKE_VERSION},
+    network_id::NetworkContext,
+};
 use aptos_crypto::x25519;
 use aptos_logger::prelude::*;
 #[cfg(any(test, feature = "testing", feature = "fuzzing"))]
 use aptos_netcore::transport::memory::MemoryTransport;
 use aptos_netcore::transport::{
+    quic::{QuicSocket, TcpAndQuicTransport},
     tcp::{TCPBufferCfg, TcpSocket, TcpTransport},
     Transport,
 };
 use aptos_time_service::TimeService;
 use aptos_types::{chain_id::ChainId, network_address::NetworkAddress, PeerId};
+use futures::future::Either;
 use std::{clone::Clone, collections::HashMap, fmt::Debug, sync::Arc};
 use tokio::runtime::Handle;
 
@@ -52,6 +57,8 @@ struct TransportContext {
     authentication_mode: AuthenticationMode,
     peers_and_metadata: Arc<PeersAndMetadata>,
     enable_proxy_protocol: bool,
+    enable_hybrid_key_exchange: bool,
+    enable_quic_transport: bool,
 }
 
 impl TransportContext {
@@ -77,6 +84,7 @@ struct PeerManagerContext {
     max_frame_size: usize,
     max_message_size: usize,
     inbound_connection_limit: usize,
+    peer_traffic_config: PeerTrafficConfig,
     tcp_buffer_cfg: TCPBufferCfg,
 }
 
@@ -99,6 +107,7 @@ impl PeerManagerContext {
         max_frame_size: usize,
         max_message_size: usize,
         inbound_connection_limit: usize,
+        peer_traffic_config: PeerTrafficConfig,
         tcp_buffer_cfg: TCPBufferCfg,
     ) -> Self {
         Self {
@@ -115,6 +124,7 @@ impl PeerManagerContext {
             max_frame_size,
             max_message_size,
             inbound_connection_limit,
+            peer_traffic_config,
             tcp_buffer_cfg,
         }
     }
@@ -139,11 +149,14 @@ impl PeerManagerContext {
 type MemoryPeerManager =
     PeerManager<AptosNetTransport<MemoryTransport>, NoiseStream<aptos_memsocket::MemorySocket>>;
 type TcpPeerManager = PeerManager<AptosNetTransport<TcpTransport>, NoiseStream<TcpSocket>>;
+type TcpAndQuicPeerManager =
+    PeerManager<AptosNetTransport<TcpAndQuicTransport>, NoiseStream<Either<TcpSocket, QuicSocket>>>;
 
 enum TransportPeerManager {
     #[cfg(any(test, feature = "testing", feature = "fuzzing"))]
     Memory(MemoryPeerManager),
     Tcp(TcpPeerManager),
+    TcpAndQuic(TcpAndQuicPeerManager),
 }
 
 pub struct PeerManagerBuilder {
@@ -171,7 +184,10 @@ impl PeerManagerBuilder {
         max_frame_size: usize,
         max_message_size: usize,
         enable_proxy_protocol: bool,
+        enable_hybrid_key_exchange: bool,
+        enable_quic_transport: bool,
         inbound_connection_limit: usize,
+        peer_traffic_config: PeerTrafficConfig,
         tcp_buffer_cfg: TCPBufferCfg,
     ) -> Self {
         // Setup channel to send requests to peer manager.
@@ -193,6 +209,8 @@ impl PeerManagerBuilder {
                 authentication_mode,
                 peers_and_metadata: peers_and_metadata.clone(),
                 enable_proxy_protocol,
+                enable_hybrid_key_exchange,
+                enable_quic_transport,
             }),
             peer_manager_context: Some(PeerManagerContext::new(
                 pm_reqs_tx,
@@ -206,6 +224,7 @@ impl PeerManagerBuilder {
                 max_frame_size,
                 max_message_size,
                 inbound_connection_limit,
+                peer_traffic_config,
                 tcp_buffer_cfg,
             )),
             peer_manager: None,
@@ -250,6 +269,11 @@ impl PeerManagerBuilder {
         let protos = transport_context.supported_protocols;
         let chain_id = transport_context.chain_id;
         let enable_proxy_protocol = transport_context.enable_proxy_protocol;
+        let handshake_version = if transport_context.enable_hybrid_key_exchange {
+            HYBRID_HANDSHAKE_VERSION
+        } else {
+            HANDSHAKE_VERSION
+        };
 
         let (key, auth_mode) = match transport_context.authentication_mode {
             AuthenticationMode::MaybeMutual(key) => (
@@ -267,6 +291,22 @@ impl PeerManagerBuilder {
         aptos_tcp_transport.set_tcp_buffers(&tcp_cfg);
 
         self.peer_manager = match self.listen_address.as_slice() {
+            [Ip4(_), Tcp(_)] | [Ip6(_), Tcp(_)] if transport_context.enable_quic_transport => {
+                Some(TransportPeerManager::TcpAndQuic(self.build_with_transport(
+                    AptosNetTransport::new(
+                        TcpAndQuicTransport::new(aptos_tcp_transport),
+                        self.network_context,
+                        self.time_service.clone(),
+                        key,
+                        auth_mode,
+                        handshake_version,
+                        chain_id,
+                        protos,
+                        enable_proxy_protocol,
+                    ),
+                    executor,
+                )))
+            },
             [Ip4(_), Tcp(_)] | [Ip6(_), Tcp(_)] => {
                 Some(TransportPeerManager::Tcp(self.build_with_transport(
                     AptosNetTransport::new(
@@ -275,7 +315,7 @@ impl PeerManagerBuilder {
                         self.time_service.clone(),
                         key,
                         auth_mode,
-                        HANDSHAKE_VERSION,
+                        handshake_version,
                         chain_id,
                         protos,
                         enable_proxy_protocol,
@@ -291,7 +331,7 @@ impl PeerManagerBuilder {
                     self.time_service.clone(),
                     key,
                     auth_mode,
-                    HANDSHAKE_VERSION,
+                    handshake_version,
                     chain_id,
                     protos,
                     enable_proxy_protocol,
@@ -340,6 +380,7 @@ impl PeerManagerBuilder {
             pm_context.max_frame_size,
             pm_context.max_message_size,
             pm_context.inbound_connection_limit,
+            pm_context.peer_traffic_config,
         );
 
         // PeerManager constructor appends a public key to the listen_address.
@@ -370,6 +411,7 @@ impl PeerManagerBuilder {
             #[cfg(any(test, feature = "testing", feature = "fuzzing"))]
             TransportPeerManager::Memory(pm) => self.start_peer_manager(pm, executor),
             TransportPeerManager::Tcp(pm) => self.start_peer_manager(pm, executor),
+            TransportPeerManager::TcpAndQuic(pm) => self.start_peer_manager(pm, executor),
         }
     }
 
@@ -58,7 +58,7 @@ use crate::{
     peer_manager::transport::{TransportHandler, TransportRequest},
     protocols::network::{ReceivedMessage, SerializedRequest},
 };
-use aptos_config::config::PeerRole;
+use aptos_config::config::{PeerRole, PeerTrafficConfig};
 use aptos_types::account_address::AccountAddress;
 pub use senders::*;
 pub use types::*;
@@ -117,6 +117,8 @@ where
     max_message_size: usize,
     /// Inbound connection limit separate of outbound connections
     inbound_connection_limit: usize,
+    /// Accounting and shaping of the traffic of each peer connection
+    peer_traffic_config: PeerTrafficConfig,
 }
 
 impl<TTransport, TSocket> PeerManager<TTransport, TSocket>
@@ -144,6 +146,7 @@ where
         max_frame_size: usize,
         max_message_size: usize,
         inbound_connection_limit: usize,
+        peer_traffic_config: PeerTrafficConfig,
     ) -> Self {
         let (transport_notifs_tx, transport_notifs_rx) = aptos_channels::new(
             channel_size,
@@ -185,6 +188,7 @@ where
             max_frame_size,
             max_message_size,
             inbound_connection_limit,
+            peer_traffic_config,
         }
     }
 
@@ -676,6 +680,7 @@ where
             constants::MAX_CONCURRENT_OUTBOUND_RPCS,
             self.max_frame_size,
             self.max_message_size,
+            self.peer_traffic_config,
         );
         self.executor.spawn(peer.start());
 
@@ -6,8 +6,8 @@ use crate::{
     application::{interface::NetworkClient, storage::PeersAndMetadata},
     protocols::{
         health_checker::{
-            interface::HealthCheckNetworkInterface, HealthChecker, HealthCheckerMsg,
-            HealthCheckerNetworkEvents,
+            interface::HealthCheckNetworkInterface, probe::TApplicationProbe, HealthChecker,
+            HealthCheckerMsg, HealthCheckerNetworkEvents,
         },
         network::NetworkSender,
         wire::handshake::v1::ProtocolId::HealthCheckerRpc,
@@ -55,6 +55,14 @@ impl HealthCheckerBuilder {
         }
     }
 
+    /// Adds an application probe to the health checker. Probes must be added before starting.
+    pub fn add_application_probe(&mut self, probe: Arc<dyn TApplicationProbe>) {
+        self.service
+            .as_mut()
+            .expect("Can only add application probes before starting")
+            .add_application_probe(probe);
+    }
+
     pub fn start(&mut self, executor: &Handle) {
         if let Some(service) = self.service.take() {
             spawn_named!("[Network] HC", executor, service.start());
@@ -12,6 +12,10 @@
 //! disconnect from the peer. It relies on ConnectivityManager or the remote peer to re-establish
 //! the connection.
 //!
+//! Optionally, the HealthChecker also runs application probes (see [`probe`]) against each peer
+//! every round, and records their latencies in the peer's metadata for peer scoring. Application
+//! probes never cause a disconnect.
+//!
 //! Future Work
 //! -----------
 //! We can make a few other improvements to the health checker. These are:
@@ -26,7 +30,7 @@ use crate::{
     peer::DisconnectReason,
     peer_manager::ConnectionNotification,
     protocols::{
-        health_checker::interface::HealthCheckNetworkInterface,
+        health_checker::{interface::HealthCheckNetworkInterface, probe::TApplicationProbe},
         network::{
             Event, NetworkApplicationConfig, NetworkClientConfig, NetworkEvents,
             NetworkServiceConfig,
@@ -44,15 +48,18 @@ use aptos_types::PeerId;
 use bytes::Bytes;
 use futures::{
     channel::oneshot,
+    future::BoxFuture,
     stream::{FuturesUnordered, StreamExt},
+    FutureExt,
 };
 use rand::{rngs::SmallRng, Rng, SeedableRng};
 use serde::{Deserialize, Serialize};
-use std::time::Duration;
+use std::{sync::Arc, time::Duration};
 use tokio::time::timeout;
 
 pub mod builder;
 mod interface;
+pub mod probe;
 #[cfg(test)]
 mod test;
 
@@ -110,6 +117,8 @@ pub struct HealthChecker<NetworkClient> {
     ping_failures_tolerated: u64,
     /// Counter incremented in each round of health checks
     round: u64,
+    /// Application probes run against each peer every round, alongside the ping.
+    application_probes: Vec<Arc<dyn TApplicationProbe>>,
 
     /// This should normally be None and is only used in testing to inject test events.
     connection_events_injection: Option<tokio::sync::mpsc::Receiver<ConnectionNotification>>,
@@ -134,10 +143,16 @@ impl<NetworkClient: NetworkClientInterface<HealthCheckerMsg> + Unpin> HealthChec
             ping_timeout,
             ping_failures_tolerated,
             round: 0,
+            application_probes: vec![],
             connection_events_injection: None,
         }
     }
 
+    /// Adds an application probe to run against each peer every round
+    pub fn add_application_probe(&mut self, probe: Arc<dyn TApplicationProbe>) {
+        self.application_probes.push(probe);
+    }
+
     #[cfg(test)]
     /// Set source of mock connection events for testing.
     pub fn set_connection_source(
@@ -150,6 +165,7 @@ impl<NetworkClient: NetworkClientInterface<HealthCheckerMsg> + Unpin> HealthChec
     /// testing_connection_events should be None except in unit test code
     pub async fn start(mut self) {
         let mut tick_handlers = FuturesUnordered::new();
+        let mut probe_handlers = FuturesUnordered::new();
         info!(
             NetworkSchema::new(&self.network_context),
             "{} Health checker actor started", self.network_context
@@ -261,12 +277,26 @@ impl<NetworkClient: NetworkClientInterface<HealthCheckerMsg> + Unpin> HealthChec
                             nonce,
                             self.ping_timeout,
                         ));
+
+                        for probe in &self.application_probes {
+                            probe_handlers.push(Self::probe_peer(
+                                self.network_context,
+                                self.time_service.clone(),
+                                probe.clone(),
+                                peer_id,
+                                self.ping_timeout,
+                            ));
+                        }
                     }
                 }
                 res = tick_handlers.select_next_some() => {
                     let (peer_id, round, nonce, ping_result) = res;
                     self.handle_ping_response(peer_id, round, nonce, ping_result).await;
                 }
+                res = probe_handlers.select_next_some() => {
+                    let (peer_id, probe_name, latency) = res;
+                    self.handle_probe_result(peer_id, probe_name, latency);
+                }
             }
         }
         warn!(
@@ -395,6 +425,63 @@ impl<NetworkClient: NetworkClientInterface<HealthCheckerMsg> + Unpin> HealthChec
         }
     }
 
+    fn handle_probe_result(
+        &mut self,
+        peer_id: PeerId,
+        probe_name: &'static str,
+        latency: Duration,
+    ) {
+        let peer_network_id = PeerNetworkId::new(self.network_context.network_id(), peer_id);
+        if let Err(error) = self
+            .network_interface
+            .get_peers_and_metadata()
+            .update_application_latency(peer_network_id, probe_name, latency)
+        {
+            // The peer may have disconnected while the probe was in flight
+            trace!(
+                NetworkSchema::new(&self.network_context).remote_peer(&peer_id),
+                "{} Unable to record the {} probe latency for peer: {}, error: {}",
+                self.network_context,
+                probe_name,
+                peer_id.short_str(),
+                error
+            );
+        }
+    }
+
+    /// Runs the given application probe against the peer and returns the measured latency. A
+    /// failed probe is reported as taking the whole timeout.
+    fn probe_peer(
+        network_context: NetworkContext,
+        time_service: TimeService,
+        probe: Arc<dyn TApplicationProbe>,
+        peer_id: PeerId,
+        probe_timeout: Duration,
+    ) -> BoxFuture<'static, (PeerId, &'static str, Duration)> {
+        async move {
+            let peer_network_id = PeerNetworkId::new(network_context.network_id(), peer_id);
+            let start_time = time_service.now();
+            let (result, latency) = match probe.probe(peer_network_id, probe_timeout).await {
+                Ok(()) => ("success", time_service.now().duration_since(start_time)),
+                Err(error) => {
+                    debug!(
+                        NetworkSchema::new(&network_context).remote_peer(&peer_id),
+                        "{} The {} probe failed for peer: {} with error: {}",
+                        network_context,
+                        probe.name(),
+                        peer_id.short_str(),
+                        error
+                    );
+                    ("failure", probe_timeout)
+                },
+            };
+            counters::application_probe_latency(&network_context, probe.name(), result)
+                .observe(latency.as_secs_f64());
+            (peer_id, probe.name(), latency)
+        }
+        .boxed()
+    }
+
     async fn ping_peer(
         network_context: NetworkContext,
         network_client: NetworkClient, // TODO: we shouldn't need to pass the client directly
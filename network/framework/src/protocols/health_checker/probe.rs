@@ -0,0 +1,62 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Application-level probes run by the HealthChecker.
+//!
+//! Ping/Pong only shows that a peer's network stack is alive. A peer can answer pings quickly
+//! while its applications are backed up, so the HealthChecker can also probe the responsiveness
+//! of applications on each peer (e.g., the storage service) every round. The measured latencies
+//! are recorded in the peer's metadata, where peer selection (e.g., in state sync) picks them up.
+//! A failed probe is recorded as taking the whole probe timeout.
+
+use crate::application::{
+    error::Error,
+    interface::{NetworkClientInterface, NetworkMessageTrait},
+};
+use aptos_config::network_id::PeerNetworkId;
+use async_trait::async_trait;
+use std::time::Duration;
+
+/// A probe of the responsiveness of an application on a peer
+#[async_trait]
+pub trait TApplicationProbe: Send + Sync {
+    /// The name the probe's latencies are recorded under
+    fn name(&self) -> &'static str;
+
+    /// Probes the application on the given peer, returning once the application has responded
+    async fn probe(&self, peer_network_id: PeerNetworkId, timeout: Duration) -> Result<(), Error>;
+}
+
+/// Probes an application by sending it an RPC request and waiting for any response. The request
+/// should be cheap for the peer to serve, e.g., a storage server summary request.
+pub struct RpcApplicationProbe<Message, Client> {
+    name: &'static str,
+    network_client: Client,
+    request: Message,
+}
+
+impl<Message, Client> RpcApplicationProbe<Message, Client> {
+    pub fn new(name: &'static str, network_client: Client, request: Message) -> Self {
+        Self {
+            name,
+            network_client,
+            request,
+        }
+    }
+}
+
+#[async_trait]
+impl<Message: NetworkMessageTrait, Client: NetworkClientInterface<Message>> TApplicationProbe
+    for RpcApplicationProbe<Message, Client>
+{
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    async fn probe(&self, peer_network_id: PeerNetworkId, timeout: Duration) -> Result<(), Error> {
+        self.network_client
+            .send_to_peer_rpc(self.request.clone(), timeout, peer_network_id)
+            .await
+            .map(|_| ())
+    }
+}
@@ -4,7 +4,7 @@
 
 use super::*;
 use crate::{
-    application::{interface::NetworkClient, storage::PeersAndMetadata},
+    application::{error::Error, interface::NetworkClient, storage::PeersAndMetadata},
     peer_manager::{
         self, ConnectionRequest, ConnectionRequestSender, PeerManagerRequest,
         PeerManagerRequestSender,
@@ -338,3 +338,74 @@ async fn outbound_failure_strict() {
     };
     future::join(health_checker.start(), test).await;
 }
+
+/// A probe that always succeeds (or always fails) immediately
+struct TestProbe {
+    name: &'static str,
+    succeed: bool,
+}
+
+#[async_trait::async_trait]
+impl TApplicationProbe for TestProbe {
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    async fn probe(
+        &self,
+        _peer_network_id: PeerNetworkId,
+        _timeout: Duration,
+    ) -> Result<(), Error> {
+        if self.succeed {
+            Ok(())
+        } else {
+            Err(Error::UnexpectedError("probe failed".into()))
+        }
+    }
+}
+
+#[tokio::test]
+async fn application_probes() {
+    let (mut harness, mut health_checker) = TestHarness::new_strict();
+    for (name, succeed) in [("responsive", true), ("unresponsive", false)] {
+        health_checker.add_application_probe(Arc::new(TestProbe { name, succeed }));
+    }
+
+    let test = async move {
+        // Notify HealthChecker of new connected node.
+        let peer_id = PeerId::new([0x42; PeerId::LENGTH]);
+        harness.send_new_peer_notification(peer_id).await;
+
+        // Trigger ping to the peer, which also runs the application probes
+        harness.trigger_ping().await;
+        harness.expect_ping_send_ok().await;
+
+        // Wait for the probe latencies to be recorded. Failed probes count as
+        // taking the whole timeout, and time doesn't advance otherwise.
+        let peer_network_id = PeerNetworkId::new(NetworkContext::mock().network_id(), peer_id);
+        loop {
+            let peer_metadata = harness
+                .peers_and_metadata
+                .get_metadata_for_peer(peer_network_id)
+                .unwrap();
+            if peer_metadata.get_application_latencies().len() == 2 {
+                assert_eq!(
+                    peer_metadata.get_application_latencies().clone(),
+                    [
+                        ("responsive".to_string(), Duration::ZERO),
+                        ("unresponsive".to_string(), PING_TIMEOUT),
+                    ]
+                    .into_iter()
+                    .collect()
+                );
+                assert_eq!(
+                    peer_metadata.get_effective_latency_secs(),
+                    Some(PING_TIMEOUT.as_secs_f64())
+                );
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    };
+    future::join(health_checker.start(), test).await;
+}
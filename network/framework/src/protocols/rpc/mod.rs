@@ -182,6 +182,8 @@ pub struct InboundRpcs {
     /// Only allow this many concurrent inbound rpcs at one time from this remote
     /// peer.  New inbound requests exceeding this limit will be dropped.
     max_concurrent_inbound_rpcs: u32,
+    /// Whether to also account the application traffic to the remote peer.
+    enable_peer_traffic_metrics: bool,
 }
 
 impl InboundRpcs {
@@ -191,6 +193,7 @@ impl InboundRpcs {
         remote_peer_id: PeerId,
         inbound_rpc_timeout: Duration,
         max_concurrent_inbound_rpcs: u32,
+        enable_peer_traffic_metrics: bool,
     ) -> Self {
         Self {
             network_context,
@@ -199,6 +202,7 @@ impl InboundRpcs {
             inbound_rpc_tasks: FuturesUnordered::new(),
             inbound_rpc_timeout,
             max_concurrent_inbound_rpcs,
+            enable_peer_traffic_metrics,
         }
     }
 
@@ -307,7 +311,13 @@ impl InboundRpcs {
         .inc_by(data_len);
 
         // Update the general network traffic metrics
-        network_application_inbound_traffic(self.network_context, protocol_id, data_len);
+        network_application_inbound_traffic(
+            self.network_context,
+            self.enable_peer_traffic_metrics
+                .then_some(self.remote_peer_id),
+            protocol_id,
+            data_len,
+        );
     }
 
     /// Method for `Peer` actor to drive the pending inbound rpc tasks forward.
@@ -378,7 +388,13 @@ impl InboundRpcs {
         .inc_by(data_len);
 
         // Update the general network traffic metrics
-        network_application_outbound_traffic(self.network_context, protocol_id, data_len);
+        network_application_outbound_traffic(
+            self.network_context,
+            self.enable_peer_traffic_metrics
+                .then_some(self.remote_peer_id),
+            protocol_id,
+            data_len,
+        );
     }
 }
 
@@ -410,6 +426,8 @@ pub struct OutboundRpcs {
     /// Only allow this many concurrent outbound rpcs at one time from this remote
     /// peer. New outbound requests exceeding this limit will be dropped.
     max_concurrent_outbound_rpcs: u32,
+    /// Whether to also account the application traffic to the remote peer.
+    enable_peer_traffic_metrics: bool,
 }
 
 impl OutboundRpcs {
@@ -418,6 +436,7 @@ impl OutboundRpcs {
         time_service: TimeService,
         remote_peer_id: PeerId,
         max_concurrent_outbound_rpcs: u32,
+        enable_peer_traffic_metrics: bool,
     ) -> Self {
         Self {
             network_context,
@@ -427,6 +446,7 @@ impl OutboundRpcs {
             outbound_rpc_tasks: FuturesUnordered::new(),
             pending_outbound_rpcs: HashMap::new(),
             max_concurrent_outbound_rpcs,
+            enable_peer_traffic_metrics,
         }
     }
 
@@ -588,7 +608,13 @@ impl OutboundRpcs {
         .inc_by(data_len);
 
         // Update the general network traffic metrics
-        network_application_outbound_traffic(self.network_context, protocol_id, data_len);
+        network_application_outbound_traffic(
+            self.network_context,
+            self.enable_peer_traffic_metrics
+                .then_some(self.remote_peer_id),
+            protocol_id,
+            data_len,
+        );
     }
 
     /// Method for `Peer` actor to drive the pending outbound rpc tasks forward.
@@ -750,6 +776,12 @@ impl OutboundRpcs {
         .inc_by(data_len);
 
         // Update the general network traffic metrics
-        network_application_inbound_traffic(self.network_context, protocol_id, data_len);
+        network_application_inbound_traffic(
+            self.network_context,
+            self.enable_peer_traffic_metrics
+                .then_some(self.remote_peer_id),
+            protocol_id,
+            data_len,
+        );
     }
 }
@@ -11,10 +11,10 @@ use crate::{
     },
 };
 use aptos_config::{
-    config::{PeerRole, HANDSHAKE_VERSION},
+    config::{PeerRole, HANDSHAKE_VERSION, HYBRID_HANDSHAKE_VERSION},
     network_id::{NetworkContext, NetworkId},
 };
-use aptos_crypto::x25519;
+use aptos_crypto::{noise::KeyExchange, x25519};
 use aptos_id_generator::{IdGenerator, U32IdGenerator};
 use aptos_logger::prelude::*;
 // Re-exposed for aptos-network-checker
@@ -24,7 +24,10 @@ use aptos_short_hex_str::AsShortHexStr;
 use aptos_time_service::{timeout, TimeService, TimeServiceTrait};
 use aptos_types::{
     chain_id::ChainId,
-    network_address::{parse_dns_tcp, parse_ip_tcp, parse_memory, NetworkAddress},
+    network_address::{
+        parse_dns_quic, parse_dns_tcp, parse_handshake, parse_ip_quic, parse_ip_tcp, parse_memory,
+        NetworkAddress,
+    },
     PeerId,
 };
 use futures::{
@@ -228,6 +231,20 @@ impl UpgradeContext {
             network_id,
         }
     }
+
+    /// The key exchange to use with a remote advertising `remote_handshake_version`, i.e.,
+    /// the hybrid one iff both sides accept it.
+    fn key_exchange(&self, remote_handshake_version: Option<u8>) -> KeyExchange {
+        match remote_handshake_version {
+            Some(version)
+                if version >= HYBRID_HANDSHAKE_VERSION
+                    && self.handshake_version >= HYBRID_HANDSHAKE_VERSION =>
+            {
+                KeyExchange::X25519MlKem768
+            },
+            _ => KeyExchange::X25519,
+        }
+    }
 }
 
 /// If we have proxy protocol enabled, then prepend the un-proxied address to the error.
@@ -349,12 +366,14 @@ pub async fn upgrade_outbound<T: TSocket>(
     let socket = fut_socket.await?;
 
     // noise handshake
+    let key_exchange = ctxt.key_exchange(parse_handshake(addr.as_slice()));
     let (mut socket, peer_role) = ctxt
         .noise
-        .upgrade_outbound(
+        .upgrade_outbound_with_key_exchange(
             socket,
             remote_peer_id,
             remote_pubkey,
+            key_exchange,
             AntiReplayTimestamps::now,
         )
         .await
@@ -456,8 +475,12 @@ where
 
         let identity_pubkey = identity_key.public_key();
 
+        let mut noise = NoiseUpgrader::new(network_context, identity_key, auth_mode);
+        if handshake_version >= HYBRID_HANDSHAKE_VERSION {
+            noise = noise.with_hybrid_key_exchange();
+        }
         let upgrade_context = UpgradeContext::new(
-            NoiseUpgrader::new(network_context, identity_key, auth_mode),
+            noise,
             handshake_version,
             supported_protocols,
             chain_id,
@@ -487,13 +510,15 @@ where
         let (base_transport_protos, base_transport_suffix) = parse_ip_tcp(protos)
             .map(|x| (&protos[..2], x.1))
             .or_else(|| parse_dns_tcp(protos).map(|x| (&protos[..2], x.1)))
+            .or_else(|| parse_ip_quic(protos).map(|x| (&protos[..3], x.1)))
+            .or_else(|| parse_dns_quic(protos).map(|x| (&protos[..3], x.1)))
             .or_else(|| parse_memory(protos).map(|x| (&protos[..1], x.1)))
             .ok_or_else(|| {
                 io::Error::new(
                     io::ErrorKind::InvalidInput,
                     format!(
                         "Unexpected dialing network address: '{}', expected: \
-                         memory, ip+tcp, or dns+tcp",
+                         memory, ip+tcp, dns+tcp, ip+udp+quic, or dns+udp+quic",
                         addr
                     ),
                 )
@@ -539,6 +564,9 @@ where
     /// `/dns/<ipaddr>/tcp/<port>` or
     /// `/dns4/<ipaddr>/tcp/<port>` or
     /// `/dns6/<ipaddr>/tcp/<port>`
+    ///
+    /// If the base transport is `TcpAndQuicTransport`, then `/<base_transport>` can
+    /// also be any of the above with `/udp/<port>/quic` in place of `/tcp/<port>`.
     pub fn dial(
         &self,
         peer_id: PeerId,
@@ -550,13 +578,14 @@ where
         // TODO(philiphayes): `Transport` trait should include parsing in `dial`?
         let (base_addr, pubkey, handshake_version) = Self::parse_dial_addr(&addr)?;
 
-        // Check that the parsed handshake version from the dial addr is supported.
-        if self.ctxt.handshake_version != handshake_version {
+        // Check that the parsed handshake version from the dial addr is supported. Remotes
+        // advertising the hybrid handshake version also accept the classic handshake.
+        if handshake_version != HANDSHAKE_VERSION && handshake_version != HYBRID_HANDSHAKE_VERSION {
             return Err(io::Error::new(
                 io::ErrorKind::InvalidData,
                 format!(
-                    "Attempting to dial remote with unsupported handshake version: {}, expected: {}",
-                    handshake_version, self.ctxt.handshake_version,
+                    "Attempting to dial remote with unsupported handshake version: {}, expected: {} or {}",
+                    handshake_version, HANDSHAKE_VERSION, HYBRID_HANDSHAKE_VERSION,
                 ),
             ));
         }
@@ -585,6 +614,9 @@ where
     ///
     /// `/ip4/<ipaddr>/tcp/<port>` or
     /// `/ip6/<ipaddr>/tcp/<port>`
+    ///
+    /// which is also what `TcpAndQuicTransport` expects, additionally listening for
+    /// QUIC on the UDP port of the same number.
     pub fn listen_on(
         &self,
         addr: NetworkAddress,
@@ -616,6 +648,9 @@ where
         let enable_proxy_protocol = self.enable_proxy_protocol;
         // stream of inbound upgrade tasks
         let inbounds = listener.map_ok(move |(fut_socket, addr)| {
+            // proxy protocol headers are only sent by tcp load balancers
+            let enable_proxy_protocol =
+                enable_proxy_protocol && parse_ip_quic(addr.as_slice()).is_none();
             // inbound upgrade task
             let fut_upgrade = upgrade_inbound(
                 ctxt.clone(),
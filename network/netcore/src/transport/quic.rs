@@ -0,0 +1,506 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! QUIC Transport
+//!
+//! Each QUIC connection carries a single bidirectional stream, which the rest of the stack uses
+//! just like a TCP socket. Over TCP, this gets QUIC's loss recovery and congestion control on
+//! lossy long-haul links, and [`QuicSocket::connection`] allows opening further streams that
+//! don't block each other.
+//!
+//! QUIC mandates TLS, but here TLS only encrypts the transport: endpoints present a throwaway
+//! self-signed certificate that is never verified. Peers are authenticated by the Noise handshake
+//! run over the stream, same as over TCP.
+use crate::transport::{
+    tcp::{TcpSocket, TcpTransport},
+    Transport,
+};
+use aptos_infallible::Mutex;
+use aptos_types::{
+    network_address::{
+        parse_dns_quic, parse_ip_quic, parse_ip_tcp, IpFilter, NetworkAddress, Protocol,
+    },
+    PeerId,
+};
+use futures::{
+    future::{BoxFuture, Either, FutureExt, TryFutureExt},
+    io::{AsyncRead, AsyncWrite},
+    stream::{self, BoxStream, StreamExt, TryStreamExt},
+};
+use quinn::{
+    crypto::rustls::QuicClientConfig, ClientConfig, Connection, Endpoint, IdleTimeout, RecvStream,
+    SendStream, ServerConfig, TransportConfig, VarInt,
+};
+use rustls::{
+    client::danger::{HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier},
+    crypto::{ring, verify_tls12_signature, verify_tls13_signature, CryptoProvider},
+    pki_types::{CertificateDer, PrivatePkcs8KeyDer, ServerName, UnixTime},
+    DigitallySignedStruct, SignatureScheme,
+};
+use std::{
+    io,
+    net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr},
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+    time::Duration,
+};
+use tokio::net::lookup_host;
+
+/// The name servers present in their certificate. As certificates aren't verified, it only needs
+/// to be the same everywhere.
+const SERVER_NAME: &str = "aptosnet";
+
+/// Connections without any traffic for this long are closed
+const MAX_IDLE_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Keeps connections whose peers only talk now and then from going idle
+const KEEP_ALIVE_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Transport to build QUIC connections
+#[derive(Clone, Debug, Default)]
+pub struct QuicTransport {
+    /// The endpoint listened on, if any. Dials go out of it too, so that peers see the UDP port
+    /// we're reachable on.
+    endpoint: Arc<Mutex<Option<Endpoint>>>,
+}
+
+impl Transport for QuicTransport {
+    type Error = io::Error;
+    type Inbound = BoxFuture<'static, io::Result<QuicSocket>>;
+    type Listener = BoxStream<'static, io::Result<(Self::Inbound, NetworkAddress)>>;
+    type Outbound = BoxFuture<'static, io::Result<QuicSocket>>;
+    type Output = QuicSocket;
+
+    fn listen_on(
+        &self,
+        addr: NetworkAddress,
+    ) -> Result<(Self::Listener, NetworkAddress), Self::Error> {
+        let ((ipaddr, port), addr_suffix) =
+            parse_ip_quic(addr.as_slice()).ok_or_else(|| invalid_addr_error(&addr))?;
+        if !addr_suffix.is_empty() {
+            return Err(invalid_addr_error(&addr));
+        }
+
+        let endpoint = Endpoint::server(server_config()?, SocketAddr::new(ipaddr, port))?;
+        let listen_addr = quic_addr(endpoint.local_addr()?);
+        *self.endpoint.lock() = Some(endpoint.clone());
+
+        let listener = stream::unfold(endpoint, |endpoint| async move {
+            let incoming = endpoint.accept().await?;
+            Some((incoming, endpoint))
+        })
+        .map(|incoming| {
+            let dialer_addr = quic_addr(incoming.remote_address());
+            let inbound = async move {
+                let connection = incoming.await.map_err(connection_error)?;
+                // The dialer's stream only shows up once the dialer writes to it, which it does
+                // right away with the first Noise handshake message.
+                let (send, recv) = connection.accept_bi().await.map_err(connection_error)?;
+                Ok(QuicSocket {
+                    connection,
+                    send,
+                    recv,
+                })
+            };
+            Ok((inbound.boxed(), dialer_addr))
+        })
+        .boxed();
+
+        Ok((listener, listen_addr))
+    }
+
+    fn dial(&self, _peer_id: PeerId, addr: NetworkAddress) -> Result<Self::Outbound, Self::Error> {
+        let protos = addr.as_slice();
+
+        // ensure addr is well formed to save some work before potentially
+        // spawning a dial task that will fail anyway.
+        parse_ip_quic(protos)
+            .map(|_| ())
+            .or_else(|| parse_dns_quic(protos).map(|_| ()))
+            .ok_or_else(|| invalid_addr_error(&addr))?;
+
+        Ok(resolve_and_connect(self.endpoint.clone(), addr).boxed())
+    }
+}
+
+fn server_config() -> io::Result<ServerConfig> {
+    let certificate =
+        rcgen::generate_simple_self_signed(vec![SERVER_NAME.to_string()]).map_err(other_error)?;
+    let private_key = PrivatePkcs8KeyDer::from(certificate.key_pair.serialize_der());
+    let mut config =
+        ServerConfig::with_single_cert(vec![certificate.cert.der().clone()], private_key.into())
+            .map_err(other_error)?;
+    config.transport_config(transport_config());
+    Ok(config)
+}
+
+fn client_config() -> io::Result<ClientConfig> {
+    let provider = Arc::new(ring::default_provider());
+    let crypto = rustls::ClientConfig::builder_with_provider(provider.clone())
+        .with_protocol_versions(&[&rustls::version::TLS13])
+        .map_err(other_error)?
+        .dangerous()
+        .with_custom_certificate_verifier(Arc::new(SkipServerVerification(provider)))
+        .with_no_client_auth();
+    let mut config = ClientConfig::new(Arc::new(
+        QuicClientConfig::try_from(crypto).map_err(other_error)?,
+    ));
+    config.transport_config(transport_config());
+    Ok(config)
+}
+
+fn transport_config() -> Arc<TransportConfig> {
+    let mut config = TransportConfig::default();
+    config
+        .max_idle_timeout(Some(
+            IdleTimeout::try_from(MAX_IDLE_TIMEOUT).expect("MAX_IDLE_TIMEOUT is a valid timeout"),
+        ))
+        .keep_alive_interval(Some(KEEP_ALIVE_INTERVAL))
+        .max_concurrent_uni_streams(VarInt::from_u32(0));
+    Arc::new(config)
+}
+
+/// Accepts any server certificate, while still checking that the server holds its key.
+#[derive(Debug)]
+struct SkipServerVerification(Arc<CryptoProvider>);
+
+impl ServerCertVerifier for SkipServerVerification {
+    fn verify_server_cert(
+        &self,
+        _end_entity: &CertificateDer<'_>,
+        _intermediates: &[CertificateDer<'_>],
+        _server_name: &ServerName<'_>,
+        _ocsp_response: &[u8],
+        _now: UnixTime,
+    ) -> Result<ServerCertVerified, rustls::Error> {
+        Ok(ServerCertVerified::assertion())
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        verify_tls12_signature(
+            message,
+            cert,
+            dss,
+            &self.0.signature_verification_algorithms,
+        )
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        verify_tls13_signature(
+            message,
+            cert,
+            dss,
+            &self.0.signature_verification_algorithms,
+        )
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.0.signature_verification_algorithms.supported_schemes()
+    }
+}
+
+/// Returns the listening endpoint if it can reach `remote`, or a new dial-only endpoint otherwise.
+fn dialing_endpoint(
+    listening_endpoint: &Mutex<Option<Endpoint>>,
+    remote: &SocketAddr,
+) -> io::Result<Endpoint> {
+    if let Some(endpoint) = listening_endpoint.lock().as_ref() {
+        let local = endpoint.local_addr()?;
+        if local.is_ipv4() == remote.is_ipv4()
+            && (local.ip().is_unspecified()
+                || local.ip().is_loopback() == remote.ip().is_loopback())
+        {
+            return Ok(endpoint.clone());
+        }
+    }
+
+    let unspecified = if remote.is_ipv4() {
+        IpAddr::V4(Ipv4Addr::UNSPECIFIED)
+    } else {
+        IpAddr::V6(Ipv6Addr::UNSPECIFIED)
+    };
+    Endpoint::client(SocketAddr::new(unspecified, 0))
+}
+
+async fn connect(
+    listening_endpoint: &Mutex<Option<Endpoint>>,
+    remote: SocketAddr,
+) -> io::Result<QuicSocket> {
+    let connection = dialing_endpoint(listening_endpoint, &remote)?
+        .connect_with(client_config()?, remote, SERVER_NAME)
+        .map_err(|error| io::Error::new(io::ErrorKind::InvalidInput, error))?
+        .await
+        .map_err(connection_error)?;
+    let (send, recv) = connection.open_bi().await.map_err(connection_error)?;
+    Ok(QuicSocket {
+        connection,
+        send,
+        recv,
+    })
+}
+
+/// Note: we need to take ownership of this `NetworkAddress` (instead of just
+/// borrowing the `&[Protocol]` slice) so this future can be `Send + 'static`.
+async fn resolve_and_connect(
+    listening_endpoint: Arc<Mutex<Option<Endpoint>>>,
+    addr: NetworkAddress,
+) -> io::Result<QuicSocket> {
+    let protos = addr.as_slice();
+
+    if let Some(((ipaddr, port), _addr_suffix)) = parse_ip_quic(protos) {
+        connect(&listening_endpoint, SocketAddr::new(ipaddr, port)).await
+    } else if let Some(((ip_filter, dns_name, port), _addr_suffix)) = parse_dns_quic(protos) {
+        let socketaddrs = lookup_host((dns_name.as_ref(), port))
+            .await?
+            .filter(|socketaddr| ip_filter.matches(socketaddr.ip()));
+        let mut last_err = None;
+
+        // try to connect until the first succeeds
+        for socketaddr in socketaddrs {
+            match connect(&listening_endpoint, socketaddr).await {
+                Ok(socket) => return Ok(socket),
+                Err(err) => last_err = Some(err),
+            }
+        }
+
+        Err(last_err.unwrap_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "could not resolve dns name to any address: name: {}, ip filter: {:?}",
+                    dns_name.as_ref(),
+                    ip_filter,
+                ),
+            )
+        }))
+    } else {
+        Err(invalid_addr_error(&addr))
+    }
+}
+
+fn quic_addr(socketaddr: SocketAddr) -> NetworkAddress {
+    NetworkAddress::from_protocols(vec![
+        Protocol::from(socketaddr.ip()),
+        Protocol::Udp(socketaddr.port()),
+        Protocol::Quic,
+    ])
+    .expect("protocols are non-empty")
+}
+
+fn connection_error(error: quinn::ConnectionError) -> io::Error {
+    io::Error::new(io::ErrorKind::ConnectionAborted, error)
+}
+
+fn other_error<E: std::error::Error + Send + Sync + 'static>(error: E) -> io::Error {
+    io::Error::new(io::ErrorKind::Other, error)
+}
+
+fn invalid_addr_error(addr: &NetworkAddress) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidInput,
+        format!("Invalid NetworkAddress: '{}'", addr),
+    )
+}
+
+/// The bidirectional stream of a QUIC connection
+#[derive(Debug)]
+pub struct QuicSocket {
+    connection: Connection,
+    send: SendStream,
+    recv: RecvStream,
+}
+
+impl QuicSocket {
+    pub fn connection(&self) -> &Connection {
+        &self.connection
+    }
+}
+
+impl AsyncRead for QuicSocket {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        context: &mut Context,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut self.recv).poll_read(context, buf)
+    }
+}
+
+impl AsyncWrite for QuicSocket {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        context: &mut Context,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut self.send).poll_write(context, buf)
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, context: &mut Context) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.send).poll_flush(context)
+    }
+
+    fn poll_close(mut self: Pin<&mut Self>, context: &mut Context) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.send).poll_close(context)
+    }
+}
+
+/// Transport that dials both TCP and QUIC addresses. It listens on its TCP listen address, and
+/// for QUIC connections on the UDP port of the same number.
+#[derive(Clone, Debug, Default)]
+pub struct TcpAndQuicTransport {
+    pub tcp: TcpTransport,
+    pub quic: QuicTransport,
+}
+
+impl TcpAndQuicTransport {
+    pub fn new(tcp: TcpTransport) -> Self {
+        Self {
+            tcp,
+            quic: QuicTransport::default(),
+        }
+    }
+}
+
+impl Transport for TcpAndQuicTransport {
+    type Error = io::Error;
+    type Inbound = BoxFuture<'static, io::Result<Self::Output>>;
+    type Listener = BoxStream<'static, io::Result<(Self::Inbound, NetworkAddress)>>;
+    type Outbound = BoxFuture<'static, io::Result<Self::Output>>;
+    type Output = Either<TcpSocket, QuicSocket>;
+
+    fn listen_on(
+        &self,
+        addr: NetworkAddress,
+    ) -> Result<(Self::Listener, NetworkAddress), Self::Error> {
+        let (tcp_listener, listen_addr) = self.tcp.listen_on(addr)?;
+        let ((ipaddr, port), _) = parse_ip_tcp(listen_addr.as_slice())
+            .expect("TcpTransport listens on an ip and tcp port");
+        let (quic_listener, _) = self
+            .quic
+            .listen_on(quic_addr(SocketAddr::new(ipaddr, port)))?;
+
+        let tcp_listener =
+            tcp_listener.map_ok(|(inbound, addr)| (inbound.map_ok(Either::Left).boxed(), addr));
+        let quic_listener =
+            quic_listener.map_ok(|(inbound, addr)| (inbound.map_ok(Either::Right).boxed(), addr));
+        Ok((
+            stream::select(tcp_listener, quic_listener).boxed(),
+            listen_addr,
+        ))
+    }
+
+    fn dial(&self, peer_id: PeerId, addr: NetworkAddress) -> Result<Self::Outbound, Self::Error> {
+        let protos = addr.as_slice();
+        if parse_ip_quic(protos).is_some() || parse_dns_quic(protos).is_some() {
+            Ok(self.quic.dial(peer_id, addr)?.map_ok(Either::Right).boxed())
+        } else {
+            Ok(self.tcp.dial(peer_id, addr)?.map_ok(Either::Left).boxed())
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::transport::{ConnectionOrigin, TransportExt};
+    use futures::{
+        future::join,
+        io::{AsyncReadExt, AsyncWriteExt},
+    };
+
+    #[tokio::test]
+    async fn simple_listen_and_dial() -> Result<(), ::std::io::Error> {
+        let t = QuicTransport::default().and_then(|mut out, _addr, origin| async move {
+            match origin {
+                ConnectionOrigin::Inbound => {
+                    let mut buf = [0; 5];
+                    out.read_exact(&mut buf).await?;
+                    assert_eq!(&buf, b"Earth");
+                    out.write_all(b"Air").await?;
+                },
+                ConnectionOrigin::Outbound => {
+                    out.write_all(b"Earth").await?;
+                    let mut buf = [0; 3];
+                    out.read_exact(&mut buf).await?;
+                    assert_eq!(&buf, b"Air");
+                },
+            }
+            Ok(())
+        });
+
+        let (listener, addr) = t.listen_on("/ip4/127.0.0.1/udp/0/quic".parse().unwrap())?;
+        let dial = t.dial(PeerId::random(), addr)?;
+        let listener = listener.into_future().then(|(maybe_result, _stream)| {
+            let (incoming, _addr) = maybe_result.unwrap().unwrap();
+            incoming.map(Result::unwrap)
+        });
+
+        let (outgoing, _incoming) = join(dial, listener).await;
+        assert!(outgoing.is_ok());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn tcp_and_quic_dial() {
+        let t = TcpAndQuicTransport::default();
+        let (mut listener, addr) = t
+            .listen_on("/ip4/127.0.0.1/tcp/0".parse().unwrap())
+            .unwrap();
+        let port = addr.find_port().unwrap();
+
+        let tcp_addr: NetworkAddress = format!("/ip4/127.0.0.1/tcp/{}", port).parse().unwrap();
+        let quic_addr: NetworkAddress =
+            format!("/ip4/127.0.0.1/udp/{}/quic", port).parse().unwrap();
+        for addr in [tcp_addr, quic_addr] {
+            let dial = async {
+                let mut socket = t
+                    .dial(PeerId::random(), addr.clone())
+                    .unwrap()
+                    .await
+                    .unwrap();
+                socket.write_all(b"Water").await.unwrap();
+                socket
+            };
+            let accept = async {
+                let (inbound, _addr) = listener.next().await.unwrap().unwrap();
+                let mut socket = inbound.await.unwrap();
+                let mut buf = [0; 5];
+                socket.read_exact(&mut buf).await.unwrap();
+                assert_eq!(&buf, b"Water");
+                socket
+            };
+            let (outbound, inbound) = join(dial, accept).await;
+            match (outbound, inbound) {
+                (Either::Left(_), Either::Left(_)) => {
+                    assert!(parse_ip_tcp(addr.as_slice()).is_some())
+                },
+                (Either::Right(_), Either::Right(_)) => {
+                    assert!(parse_ip_quic(addr.as_slice()).is_some())
+                },
+                _ => panic!("dialed and accepted sockets are of different kinds"),
+            }
+        }
+    }
+
+    #[test]
+    fn unsupported_multiaddrs() {
+        let t = QuicTransport::default();
+
+        let result = t.listen_on("/ip4/127.0.0.1/tcp/0".parse().unwrap());
+        assert!(result.is_err());
+
+        let result = t.dial(PeerId::random(), "/ip4/127.0.0.1/tcp/22".parse().unwrap());
+        assert!(result.is_err());
+    }
+}
@@ -21,6 +21,7 @@ use aptos_peer_monitoring_service_types::{
     response::{
         ConnectionMetadata, LatencyPingResponse, NetworkInformationResponse,
         NodeInformationResponse, PeerMonitoringServiceResponse, ServerProtocolVersionResponse,
+        GEO_HINT_KEY,
     },
     PeerMonitoringServiceError, Result, MAX_DISTANCE_FROM_VALIDATORS,
 };
@@ -46,6 +47,7 @@ pub const PEER_MONITORING_SERVER_VERSION: u64 = 1;
 pub struct PeerMonitoringServiceServer<T> {
     base_config: BaseConfig,
     bounded_executor: BoundedExecutor,
+    geo_hint: Option<String>,
     network_requests: PeerMonitoringServiceNetworkEvents,
     peers_and_metadata: Arc<PeersAndMetadata>,
     start_time: Instant,
@@ -67,11 +69,13 @@ impl<T: StorageReaderInterface> PeerMonitoringServiceServer<T> {
             node_config.peer_monitoring_service.max_concurrent_requests as usize,
             executor,
         );
+        let geo_hint = node_config.peer_monitoring_service.geo_hint;
         let start_time = time_service.now();
 
         Self {
             base_config,
             bounded_executor,
+            geo_hint,
             network_requests,
             peers_and_metadata,
             start_time,
@@ -98,6 +102,7 @@ impl<T: StorageReaderInterface> PeerMonitoringServiceServer<T> {
             // All handler methods are currently CPU-bound so we want
             // to spawn on the blocking thread pool.
             let base_config = self.base_config.clone();
+            let geo_hint = self.geo_hint.clone();
             let peers_and_metadata = self.peers_and_metadata.clone();
             let start_time = self.start_time;
             let storage = self.storage.clone();
@@ -106,6 +111,7 @@ impl<T: StorageReaderInterface> PeerMonitoringServiceServer<T> {
                 .spawn_blocking(move || {
                     let response = Handler::new(
                         base_config,
+                        geo_hint,
                         peers_and_metadata,
                         start_time,
                         storage,
@@ -129,6 +135,7 @@ impl<T: StorageReaderInterface> PeerMonitoringServiceServer<T> {
 #[derive(Clone)]
 pub struct Handler<T> {
     base_config: BaseConfig,
+    geo_hint: Option<String>,
     peers_and_metadata: Arc<PeersAndMetadata>,
     start_time: Instant,
     storage: T,
@@ -138,6 +145,7 @@ pub struct Handler<T> {
 impl<T: StorageReaderInterface> Handler<T> {
     pub fn new(
         base_config: BaseConfig,
+        geo_hint: Option<String>,
         peers_and_metadata: Arc<PeersAndMetadata>,
         start_time: Instant,
         storage: T,
@@ -145,6 +153,7 @@ impl<T: StorageReaderInterface> Handler<T> {
     ) -> Self {
         Self {
             base_config,
+            geo_hint,
             peers_and_metadata,
             start_time,
             storage,
@@ -258,7 +267,10 @@ impl<T: StorageReaderInterface> Handler<T> {
 
     fn get_node_information(&self) -> Result<PeerMonitoringServiceResponse, Error> {
         // Get the node information
-        let build_information = aptos_build_info::get_build_information();
+        let mut build_information = aptos_build_info::get_build_information();
+        if let Some(geo_hint) = &self.geo_hint {
+            build_information.insert(GEO_HINT_KEY.into(), geo_hint.clone());
+        }
         let current_time: Instant = self.time_service.now();
         let uptime = current_time.duration_since(self.start_time);
         let (highest_synced_epoch, highest_synced_version) =
@@ -366,6 +366,47 @@ async fn test_get_node_information() {
     }
 }
 
+#[tokio::test]
+async fn test_get_node_information_geo_hint() {
+    // Create the mock storage reader
+    let mut mock_db_reader = create_mock_db_reader();
+    let latest_ledger_info =
+        LedgerInfoWithSignatures::new(LedgerInfo::mock_genesis(None), AggregateSignature::empty());
+    mock_db_reader
+        .expect_get_latest_ledger_info()
+        .returning(move || Ok(latest_ledger_info.clone()));
+    mock_db_reader
+        .expect_get_first_txn_version()
+        .returning(|| Ok(Some(0)));
+
+    // Create the peer monitoring client and server (with a geo hint)
+    let geo_hint = "us-east".to_string();
+    let peer_monitoring_config = PeerMonitoringServiceConfig {
+        geo_hint: Some(geo_hint.clone()),
+        ..Default::default()
+    };
+    let storage_reader = StorageReader::new(Arc::new(mock_db_reader));
+    let (mut mock_client, service, _, _) =
+        MockClient::new(None, Some(peer_monitoring_config), Some(storage_reader));
+    tokio::spawn(service.start());
+
+    // Process a client request to fetch the node information
+    let request = PeerMonitoringServiceRequest::GetNodeInformation;
+    let response = mock_client.send_request(request).await.unwrap();
+
+    // Verify the geo hint is reported alongside the build information
+    match response {
+        PeerMonitoringServiceResponse::NodeInformation(node_information_response) => {
+            assert_eq!(node_information_response.get_geo_hint(), Some(&geo_hint));
+            assert_eq!(
+                node_information_response.build_information.len(),
+                aptos_build_info::get_build_information().len() + 1
+            );
+        },
+        _ => panic!("Expected node information response but got: {:?}", response),
+    }
+}
+
 #[tokio::test]
 async fn test_latency_ping_request() {
     // Create the peer monitoring client and server
@@ -490,7 +531,7 @@ impl MockClient {
         let peer_monitoring_config = peer_monitoring_config.unwrap_or_default();
         let node_config = NodeConfig {
             base: base_config,
-            peer_monitoring_service: peer_monitoring_config,
+            peer_monitoring_service: peer_monitoring_config.clone(),
             ..Default::default()
         };
 
@@ -90,6 +90,10 @@ pub struct ServerProtocolVersionResponse {
     pub version: u64, // The version of the peer monitoring service run by the server
 }
 
+/// The build information key under which the node reports its geo hint (if configured).
+/// Reporting it as an extra key keeps the response compatible with older clients.
+pub const GEO_HINT_KEY: &str = "geo_hint";
+
 /// A response for the node information request
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
 pub struct NodeInformationResponse {
@@ -101,6 +105,13 @@ pub struct NodeInformationResponse {
     pub uptime: Duration,            // The amount of time the peer has been running
 }
 
+impl NodeInformationResponse {
+    /// Returns the geo hint reported by the node (if any)
+    pub fn get_geo_hint(&self) -> Option<&String> {
+        self.build_information.get(GEO_HINT_KEY)
+    }
+}
+
 // Display formatting provides a high-level summary of the response
 impl Display for NodeInformationResponse {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
@@ -18,7 +18,7 @@ from aptos.transaction.v1 import (
 )
 
 DESCRIPTOR = _descriptor_pool.Default().AddSerializedFile(
-    b'\n.aptos/internal/fullnode/v1/fullnode_data.proto\x12\x1a\x61ptos.internal.fullnode.v1\x1a&aptos/transaction/v1/transaction.proto\x1a\x1b\x61ptos/indexer/v1/grpc.proto"M\n\x12TransactionsOutput\x12\x37\n\x0ctransactions\x18\x01 \x03(\x0b\x32!.aptos.transaction.v1.Transaction"\xf2\x01\n\x0cStreamStatus\x12\x41\n\x04type\x18\x01 \x01(\x0e\x32\x33.aptos.internal.fullnode.v1.StreamStatus.StatusType\x12\x15\n\rstart_version\x18\x02 \x01(\x04\x12\x1c\n\x0b\x65nd_version\x18\x03 \x01(\x04\x42\x02\x30\x01H\x00\x88\x01\x01"Z\n\nStatusType\x12\x1b\n\x17STATUS_TYPE_UNSPECIFIED\x10\x00\x12\x14\n\x10STATUS_TYPE_INIT\x10\x01\x12\x19\n\x15STATUS_TYPE_BATCH_END\x10\x02\x42\x0e\n\x0c_end_version"\x94\x01\n\x1eGetTransactionsFromNodeRequest\x12!\n\x10starting_version\x18\x01 \x01(\x04\x42\x02\x30\x01H\x00\x88\x01\x01\x12#\n\x12transactions_count\x18\x02 \x01(\x04\x42\x02\x30\x01H\x01\x88\x01\x01\x42\x13\n\x11_starting_versionB\x15\n\x13_transactions_count"\xb8\x01\n\x1cTransactionsFromNodeResponse\x12:\n\x06status\x18\x01 \x01(\x0b\x32(.aptos.internal.fullnode.v1.StreamStatusH\x00\x12>\n\x04\x64\x61ta\x18\x02 \x01(\x0b\x32..aptos.internal.fullnode.v1.TransactionsOutputH\x00\x12\x10\n\x08\x63hain_id\x18\x03 \x01(\rB\n\n\x08response"\x15\n\x13PingFullnodeRequest"R\n\x14PingFullnodeResponse\x12\x31\n\x04info\x18\x01 \x01(\x0b\x32\x1e.aptos.indexer.v1.FullnodeInfoH\x00\x88\x01\x01\x42\x07\n\x05_info2\x8d\x02\n\x0c\x46ullnodeData\x12i\n\x04Ping\x12/.aptos.internal.fullnode.v1.PingFullnodeRequest\x1a\x30.aptos.internal.fullnode.v1.PingFullnodeResponse\x12\x91\x01\n\x17GetTransactionsFromNode\x12:.aptos.internal.fullnode.v1.GetTransactionsFromNodeRequest\x1a\x38.aptos.internal.fullnode.v1.TransactionsFromNodeResponse0\x01\x62\x06proto3'
+    b'\n.aptos/internal/fullnode/v1/fullnode_data.proto\x12\x1a\x61ptos.internal.fullnode.v1\x1a&aptos/transaction/v1/transaction.proto\x1a\x1b\x61ptos/indexer/v1/grpc.proto"M\n\x12TransactionsOutput\x12\x37\n\x0ctransactions\x18\x01 \x03(\x0b\x32!.aptos.transaction.v1.Transaction"\xf2\x01\n\x0cStreamStatus\x12\x41\n\x04type\x18\x01 \x01(\x0e\x32\x33.aptos.internal.fullnode.v1.StreamStatus.StatusType\x12\x15\n\rstart_version\x18\x02 \x01(\x04\x12\x1c\n\x0b\x65nd_version\x18\x03 \x01(\x04\x42\x02\x30\x01H\x00\x88\x01\x01"Z\n\nStatusType\x12\x1b\n\x17STATUS_TYPE_UNSPECIFIED\x10\x00\x12\x14\n\x10STATUS_TYPE_INIT\x10\x01\x12\x19\n\x15STATUS_TYPE_BATCH_END\x10\x02\x42\x0e\n\x0c_end_version"\x94\x01\n\x1eGetTransactionsFromNodeRequest\x12!\n\x10starting_version\x18\x01 \x01(\x04\x42\x02\x30\x01H\x00\x88\x01\x01\x12#\n\x12transactions_count\x18\x02 \x01(\x04\x42\x02\x30\x01H\x01\x88\x01\x01\x42\x13\n\x11_starting_versionB\x15\n\x13_transactions_count"\xb8\x01\n\x1cTransactionsFromNodeResponse\x12:\n\x06status\x18\x01 \x01(\x0b\x32(.aptos.internal.fullnode.v1.StreamStatusH\x00\x12>\n\x04\x64\x61ta\x18\x02 \x01(\x0b\x32..aptos.internal.fullnode.v1.TransactionsOutputH\x00\x12\x10\n\x08\x63hain_id\x18\x03 \x01(\rB\n\n\x08response"\x15\n\x13PingFullnodeRequest"R\n\x14PingFullnodeResponse\x12\x31\n\x04info\x18\x01 \x01(\x0b\x32\x1e.aptos.indexer.v1.FullnodeInfoH\x00\x88\x01\x01\x42\x07\n\x05_info"\xb6\x01\n\x19GetAccountBalancesRequest\x12\x0f\n\x07\x61\x64\x64ress\x18\x01 \x01(\t\x12\x1d\n\x10start_asset_type\x18\x02 \x01(\tH\x00\x88\x01\x01\x12\x16\n\x05limit\x18\x03 \x01(\x04\x42\x02\x30\x01H\x01\x88\x01\x01\x12\x1f\n\x0eledger_version\x18\x04 \x01(\x04\x42\x02\x30\x01H\x02\x88\x01\x01\x42\x13\n\x11_start_asset_typeB\x08\n\x06_limitB\x11\n\x0f_ledger_version"8\n\x0e\x41\x63\x63ountBalance\x12\x12\n\nasset_type\x18\x01 \x01(\t\x12\x12\n\x06\x61mount\x18\x02 \x01(\x04\x42\x02\x30\x01"\xa8\x01\n\x1aGetAccountBalancesResponse\x12<\n\x08\x62\x61lances\x18\x01 \x03(\x0b\x32*.aptos.internal.fullnode.v1.AccountBalance\x12\x1a\n\x0eledger_version\x18\x02 \x01(\x04\x42\x02\x30\x01\x12\x1c\n\x0fnext_asset_type\x18\x03 \x01(\tH\x00\x88\x01\x01\x42\x12\n\x10_next_asset_type2\x93\x03\n\x0c\x46ullnodeData\x12i\n\x04Ping\x12/.aptos.internal.fullnode.v1.PingFullnodeRequest\x1a\x30.aptos.internal.fullnode.v1.PingFullnodeResponse\x12\x91\x01\n\x17GetTransactionsFromNode\x12:.aptos.internal.fullnode.v1.GetTransactionsFromNodeRequest\x1a\x38.aptos.internal.fullnode.v1.TransactionsFromNodeResponse0\x01\x12\x83\x01\n\x12GetAccountBalances\x12\x35.aptos.internal.fullnode.v1.GetAccountBalancesRequest\x1a\x36.aptos.internal.fullnode.v1.GetAccountBalancesResponseb\x06proto3'
 )
 
 _globals = globals()
@@ -38,6 +38,18 @@ if _descriptor._USE_C_DESCRIPTORS == False:
     _GETTRANSACTIONSFROMNODEREQUEST.fields_by_name[
         "transactions_count"
     ]._serialized_options = b"0\001"
+    _GETACCOUNTBALANCESREQUEST.fields_by_name["limit"]._options = None
+    _GETACCOUNTBALANCESREQUEST.fields_by_name["limit"]._serialized_options = b"0\001"
+    _GETACCOUNTBALANCESREQUEST.fields_by_name["ledger_version"]._options = None
+    _GETACCOUNTBALANCESREQUEST.fields_by_name[
+        "ledger_version"
+    ]._serialized_options = b"0\001"
+    _ACCOUNTBALANCE.fields_by_name["amount"]._options = None
+    _ACCOUNTBALANCE.fields_by_name["amount"]._serialized_options = b"0\001"
+    _GETACCOUNTBALANCESRESPONSE.fields_by_name["ledger_version"]._options = None
+    _GETACCOUNTBALANCESRESPONSE.fields_by_name[
+        "ledger_version"
+    ]._serialized_options = b"0\001"
     _globals["_TRANSACTIONSOUTPUT"]._serialized_start = 147
     _globals["_TRANSACTIONSOUTPUT"]._serialized_end = 224
     _globals["_STREAMSTATUS"]._serialized_start = 227
@@ -52,6 +64,12 @@ if _descriptor._USE_C_DESCRIPTORS == False:
     _globals["_PINGFULLNODEREQUEST"]._serialized_end = 830
     _globals["_PINGFULLNODERESPONSE"]._serialized_start = 832
     _globals["_PINGFULLNODERESPONSE"]._serialized_end = 914
-    _globals["_FULLNODEDATA"]._serialized_start = 917
-    _globals["_FULLNODEDATA"]._serialized_end = 1186
+    _globals["_GETACCOUNTBALANCESREQUEST"]._serialized_start = 917
+    _globals["_GETACCOUNTBALANCESREQUEST"]._serialized_end = 1099
+    _globals["_ACCOUNTBALANCE"]._serialized_start = 1101
+    _globals["_ACCOUNTBALANCE"]._serialized_end = 1157
+    _globals["_GETACCOUNTBALANCESRESPONSE"]._serialized_start = 1160
+    _globals["_GETACCOUNTBALANCESRESPONSE"]._serialized_end = 1328
+    _globals["_FULLNODEDATA"]._serialized_start = 1331
+    _globals["_FULLNODEDATA"]._serialized_end = 1734
 # @@protoc_insertion_point(module_scope)
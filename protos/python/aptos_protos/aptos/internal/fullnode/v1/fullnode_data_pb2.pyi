@@ -88,3 +88,46 @@ class PingFullnodeResponse(_message.Message):
     def __init__(
         self, info: _Optional[_Union[_grpc_pb2.FullnodeInfo, _Mapping]] = ...
     ) -> None: ...
+
+class GetAccountBalancesRequest(_message.Message):
+    __slots__ = ["address", "start_asset_type", "limit", "ledger_version"]
+    ADDRESS_FIELD_NUMBER: _ClassVar[int]
+    START_ASSET_TYPE_FIELD_NUMBER: _ClassVar[int]
+    LIMIT_FIELD_NUMBER: _ClassVar[int]
+    LEDGER_VERSION_FIELD_NUMBER: _ClassVar[int]
+    address: str
+    start_asset_type: str
+    limit: int
+    ledger_version: int
+    def __init__(
+        self,
+        address: _Optional[str] = ...,
+        start_asset_type: _Optional[str] = ...,
+        limit: _Optional[int] = ...,
+        ledger_version: _Optional[int] = ...,
+    ) -> None: ...
+
+class AccountBalance(_message.Message):
+    __slots__ = ["asset_type", "amount"]
+    ASSET_TYPE_FIELD_NUMBER: _ClassVar[int]
+    AMOUNT_FIELD_NUMBER: _ClassVar[int]
+    asset_type: str
+    amount: int
+    def __init__(
+        self, asset_type: _Optional[str] = ..., amount: _Optional[int] = ...
+    ) -> None: ...
+
+class GetAccountBalancesResponse(_message.Message):
+    __slots__ = ["balances", "ledger_version", "next_asset_type"]
+    BALANCES_FIELD_NUMBER: _ClassVar[int]
+    LEDGER_VERSION_FIELD_NUMBER: _ClassVar[int]
+    NEXT_ASSET_TYPE_FIELD_NUMBER: _ClassVar[int]
+    balances: _containers.RepeatedCompositeFieldContainer[AccountBalance]
+    ledger_version: int
+    next_asset_type: str
+    def __init__(
+        self,
+        balances: _Optional[_Iterable[_Union[AccountBalance, _Mapping]]] = ...,
+        ledger_version: _Optional[int] = ...,
+        next_asset_type: _Optional[str] = ...,
+    ) -> None: ...
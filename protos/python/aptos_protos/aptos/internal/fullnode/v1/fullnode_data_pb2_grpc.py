@@ -25,6 +25,11 @@ class FullnodeDataStub(object):
             request_serializer=aptos_dot_internal_dot_fullnode_dot_v1_dot_fullnode__data__pb2.GetTransactionsFromNodeRequest.SerializeToString,
             response_deserializer=aptos_dot_internal_dot_fullnode_dot_v1_dot_fullnode__data__pb2.TransactionsFromNodeResponse.FromString,
         )
+        self.GetAccountBalances = channel.unary_unary(
+            "/aptos.internal.fullnode.v1.FullnodeData/GetAccountBalances",
+            request_serializer=aptos_dot_internal_dot_fullnode_dot_v1_dot_fullnode__data__pb2.GetAccountBalancesRequest.SerializeToString,
+            response_deserializer=aptos_dot_internal_dot_fullnode_dot_v1_dot_fullnode__data__pb2.GetAccountBalancesResponse.FromString,
+        )
 
 
 class FullnodeDataServicer(object):
@@ -42,6 +47,12 @@ class FullnodeDataServicer(object):
         context.set_details("Method not implemented!")
         raise NotImplementedError("Method not implemented!")
 
+    def GetAccountBalances(self, request, context):
+        """Missing associated documentation comment in .proto file."""
+        context.set_code(grpc.StatusCode.UNIMPLEMENTED)
+        context.set_details("Method not implemented!")
+        raise NotImplementedError("Method not implemented!")
+
 
 def add_FullnodeDataServicer_to_server(servicer, server):
     rpc_method_handlers = {
@@ -55,6 +66,11 @@ def add_FullnodeDataServicer_to_server(servicer, server):
             request_deserializer=aptos_dot_internal_dot_fullnode_dot_v1_dot_fullnode__data__pb2.GetTransactionsFromNodeRequest.FromString,
             response_serializer=aptos_dot_internal_dot_fullnode_dot_v1_dot_fullnode__data__pb2.TransactionsFromNodeResponse.SerializeToString,
         ),
+        "GetAccountBalances": grpc.unary_unary_rpc_method_handler(
+            servicer.GetAccountBalances,
+            request_deserializer=aptos_dot_internal_dot_fullnode_dot_v1_dot_fullnode__data__pb2.GetAccountBalancesRequest.FromString,
+            response_serializer=aptos_dot_internal_dot_fullnode_dot_v1_dot_fullnode__data__pb2.GetAccountBalancesResponse.SerializeToString,
+        ),
     }
     generic_handler = grpc.method_handlers_generic_handler(
         "aptos.internal.fullnode.v1.FullnodeData", rpc_method_handlers
@@ -123,3 +139,32 @@ class FullnodeData(object):
             timeout,
             metadata,
         )
+
+    @staticmethod
+    def GetAccountBalances(
+        request,
+        target,
+        options=(),
+        channel_credentials=None,
+        call_credentials=None,
+        insecure=False,
+        compression=None,
+        wait_for_ready=None,
+        timeout=None,
+        metadata=None,
+    ):
+        return grpc.experimental.unary_unary(
+            request,
+            target,
+            "/aptos.internal.fullnode.v1.FullnodeData/GetAccountBalances",
+            aptos_dot_internal_dot_fullnode_dot_v1_dot_fullnode__data__pb2.GetAccountBalancesRequest.SerializeToString,
+            aptos_dot_internal_dot_fullnode_dot_v1_dot_fullnode__data__pb2.GetAccountBalancesResponse.FromString,
+            options,
+            channel_credentials,
+            insecure,
+            call_credentials,
+            compression,
+            wait_for_ready,
+            timeout,
+            metadata,
+        )
@@ -2,6 +2,394 @@
 // SPDX-License-Identifier: Apache-2.0
 
 // @generated
+impl serde::Serialize for AccountBalance {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if !self.asset_type.is_empty() {
+            len += 1;
+        }
+        if self.amount != 0 {
+            len += 1;
+        }
+        let mut struct_ser = serializer.serialize_struct("aptos.internal.fullnode.v1.AccountBalance", len)?;
+        if !self.asset_type.is_empty() {
+            struct_ser.serialize_field("assetType", &self.asset_type)?;
+        }
+        if self.amount != 0 {
+            struct_ser.serialize_field("amount", ToString::to_string(&self.amount).as_str())?;
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for AccountBalance {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "asset_type",
+            "assetType",
+            "amount",
+        ];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            AssetType,
+            Amount,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    #[allow(unused_variables)]
+                    fn visit_str<E>(self, value: &str) -> std::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "assetType" | "asset_type" => Ok(GeneratedField::AssetType),
+                            "amount" => Ok(GeneratedField::Amount),
+                            _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = AccountBalance;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("struct aptos.internal.fullnode.v1.AccountBalance")
+            }
+
+            fn visit_map<V>(self, mut map: V) -> std::result::Result<AccountBalance, V::Error>
+                where
+                    V: serde::de::MapAccess<'de>,
+            {
+                let mut asset_type__ = None;
+                let mut amount__ = None;
+                while let Some(k) = map.next_key()? {
+                    match k {
+                        GeneratedField::AssetType => {
+                            if asset_type__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("assetType"));
+                            }
+                            asset_type__ = Some(map.next_value()?);
+                        }
+                        GeneratedField::Amount => {
+                            if amount__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("amount"));
+                            }
+                            amount__ =
+                                Some(map.next_value::<::pbjson::private::NumberDeserialize<_>>()?.0)
+                            ;
+                        }
+                    }
+                }
+                Ok(AccountBalance {
+                    asset_type: asset_type__.unwrap_or_default(),
+                    amount: amount__.unwrap_or_default(),
+                })
+            }
+        }
+        deserializer.deserialize_struct("aptos.internal.fullnode.v1.AccountBalance", FIELDS, GeneratedVisitor)
+    }
+}
+impl serde::Serialize for GetAccountBalancesRequest {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if !self.address.is_empty() {
+            len += 1;
+        }
+        if self.start_asset_type.is_some() {
+            len += 1;
+        }
+        if self.limit.is_some() {
+            len += 1;
+        }
+        if self.ledger_version.is_some() {
+            len += 1;
+        }
+        let mut struct_ser = serializer.serialize_struct("aptos.internal.fullnode.v1.GetAccountBalancesRequest", len)?;
+        if !self.address.is_empty() {
+            struct_ser.serialize_field("address", &self.address)?;
+        }
+        if let Some(v) = self.start_asset_type.as_ref() {
+            struct_ser.serialize_field("startAssetType", v)?;
+        }
+        if let Some(v) = self.limit.as_ref() {
+            struct_ser.serialize_field("limit", ToString::to_string(&v).as_str())?;
+        }
+        if let Some(v) = self.ledger_version.as_ref() {
+            struct_ser.serialize_field("ledgerVersion", ToString::to_string(&v).as_str())?;
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for GetAccountBalancesRequest {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "address",
+            "start_asset_type",
+            "startAssetType",
+            "limit",
+            "ledger_version",
+            "ledgerVersion",
+        ];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            Address,
+            StartAssetType,
+            Limit,
+            LedgerVersion,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    #[allow(unused_variables)]
+                    fn visit_str<E>(self, value: &str) -> std::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "address" => Ok(GeneratedField::Address),
+                            "startAssetType" | "start_asset_type" => Ok(GeneratedField::StartAssetType),
+                            "limit" => Ok(GeneratedField::Limit),
+                            "ledgerVersion" | "ledger_version" => Ok(GeneratedField::LedgerVersion),
+                            _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = GetAccountBalancesRequest;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("struct aptos.internal.fullnode.v1.GetAccountBalancesRequest")
+            }
+
+            fn visit_map<V>(self, mut map: V) -> std::result::Result<GetAccountBalancesRequest, V::Error>
+                where
+                    V: serde::de::MapAccess<'de>,
+            {
+                let mut address__ = None;
+                let mut start_asset_type__ = None;
+                let mut limit__ = None;
+                let mut ledger_version__ = None;
+                while let Some(k) = map.next_key()? {
+                    match k {
+                        GeneratedField::Address => {
+                            if address__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("address"));
+                            }
+                            address__ = Some(map.next_value()?);
+                        }
+                        GeneratedField::StartAssetType => {
+                            if start_asset_type__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("startAssetType"));
+                            }
+                            start_asset_type__ = map.next_value()?;
+                        }
+                        GeneratedField::Limit => {
+                            if limit__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("limit"));
+                            }
+                            limit__ =
+                                map.next_value::<::std::option::Option<::pbjson::private::NumberDeserialize<_>>>()?.map(|x| x.0)
+                            ;
+                        }
+                        GeneratedField::LedgerVersion => {
+                            if ledger_version__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("ledgerVersion"));
+                            }
+                            ledger_version__ =
+                                map.next_value::<::std::option::Option<::pbjson::private::NumberDeserialize<_>>>()?.map(|x| x.0)
+                            ;
+                        }
+                    }
+                }
+                Ok(GetAccountBalancesRequest {
+                    address: address__.unwrap_or_default(),
+                    start_asset_type: start_asset_type__,
+                    limit: limit__,
+                    ledger_version: ledger_version__,
+                })
+            }
+        }
+        deserializer.deserialize_struct("aptos.internal.fullnode.v1.GetAccountBalancesRequest", FIELDS, GeneratedVisitor)
+    }
+}
+impl serde::Serialize for GetAccountBalancesResponse {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if !self.balances.is_empty() {
+            len += 1;
+        }
+        if self.ledger_version != 0 {
+            len += 1;
+        }
+        if self.next_asset_type.is_some() {
+            len += 1;
+        }
+        let mut struct_ser = serializer.serialize_struct("aptos.internal.fullnode.v1.GetAccountBalancesResponse", len)?;
+        if !self.balances.is_empty() {
+            struct_ser.serialize_field("balances", &self.balances)?;
+        }
+        if self.ledger_version != 0 {
+            struct_ser.serialize_field("ledgerVersion", ToString::to_string(&self.ledger_version).as_str())?;
+        }
+        if let Some(v) = self.next_asset_type.as_ref() {
+            struct_ser.serialize_field("nextAssetType", v)?;
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for GetAccountBalancesResponse {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "balances",
+            "ledger_version",
+            "ledgerVersion",
+            "next_asset_type",
+            "nextAssetType",
+        ];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            Balances,
+            LedgerVersion,
+            NextAssetType,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    #[allow(unused_variables)]
+                    fn visit_str<E>(self, value: &str) -> std::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "balances" => Ok(GeneratedField::Balances),
+                            "ledgerVersion" | "ledger_version" => Ok(GeneratedField::LedgerVersion),
+                            "nextAssetType" | "next_asset_type" => Ok(GeneratedField::NextAssetType),
+                            _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = GetAccountBalancesResponse;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("struct aptos.internal.fullnode.v1.GetAccountBalancesResponse")
+            }
+
+            fn visit_map<V>(self, mut map: V) -> std::result::Result<GetAccountBalancesResponse, V::Error>
+                where
+                    V: serde::de::MapAccess<'de>,
+            {
+                let mut balances__ = None;
+                let mut ledger_version__ = None;
+                let mut next_asset_type__ = None;
+                while let Some(k) = map.next_key()? {
+                    match k {
+                        GeneratedField::Balances => {
+                            if balances__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("balances"));
+                            }
+                            balances__ = Some(map.next_value()?);
+                        }
+                        GeneratedField::LedgerVersion => {
+                            if ledger_version__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("ledgerVersion"));
+                            }
+                            ledger_version__ =
+                                Some(map.next_value::<::pbjson::private::NumberDeserialize<_>>()?.0)
+                            ;
+                        }
+                        GeneratedField::NextAssetType => {
+                            if next_asset_type__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("nextAssetType"));
+                            }
+                            next_asset_type__ = map.next_value()?;
+                        }
+                    }
+                }
+                Ok(GetAccountBalancesResponse {
+                    balances: balances__.unwrap_or_default(),
+                    ledger_version: ledger_version__.unwrap_or_default(),
+                    next_asset_type: next_asset_type__,
+                })
+            }
+        }
+        deserializer.deserialize_struct("aptos.internal.fullnode.v1.GetAccountBalancesResponse", FIELDS, GeneratedVisitor)
+    }
+}
 impl serde::Serialize for GetTransactionsFromNodeRequest {
     #[allow(deprecated)]
     fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
@@ -153,6 +153,36 @@ pub mod fullnode_data_client {
                 );
             self.inner.server_streaming(req, path, codec).await
         }
+        ///
+        pub async fn get_account_balances(
+            &mut self,
+            request: impl tonic::IntoRequest<super::GetAccountBalancesRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::GetAccountBalancesResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/aptos.internal.fullnode.v1.FullnodeData/GetAccountBalances",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new(
+                        "aptos.internal.fullnode.v1.FullnodeData",
+                        "GetAccountBalances",
+                    ),
+                );
+            self.inner.unary(req, path, codec).await
+        }
     }
 }
 /// Generated server implementations.
@@ -193,6 +223,14 @@ pub mod fullnode_data_server {
             tonic::Response<Self::GetTransactionsFromNodeStream>,
             tonic::Status,
         >;
+        ///
+        async fn get_account_balances(
+            &self,
+            request: tonic::Request<super::GetAccountBalancesRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::GetAccountBalancesResponse>,
+            tonic::Status,
+        >;
     }
     ///
     #[derive(Debug)]
@@ -369,6 +407,55 @@ pub mod fullnode_data_server {
                     };
                     Box::pin(fut)
                 }
+                "/aptos.internal.fullnode.v1.FullnodeData/GetAccountBalances" => {
+                    #[allow(non_camel_case_types)]
+                    struct GetAccountBalancesSvc<T: FullnodeData>(pub Arc<T>);
+                    impl<
+                        T: FullnodeData,
+                    > tonic::server::UnaryService<super::GetAccountBalancesRequest>
+                    for GetAccountBalancesSvc<T> {
+                        type Response = super::GetAccountBalancesResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::GetAccountBalancesRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as FullnodeData>::get_account_balances(
+                                        &inner,
+                                        request,
+                                    )
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = GetAccountBalancesSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
                 _ => {
                     Box::pin(async move {
                         let mut response = http::Response::new(empty_body());
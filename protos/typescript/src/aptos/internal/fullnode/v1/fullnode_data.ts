@@ -108,6 +108,47 @@ export interface PingFullnodeResponse {
   info?: FullnodeInfo | undefined;
 }
 
+export interface GetAccountBalancesRequest {
+  /** Required; address of the account, as a hex string. */
+  address?:
+    | string
+    | undefined;
+  /**
+   * Optional; asset type to start the page from, as returned in `next_asset_type`.
+   * If not set, the page starts from the first asset of the account.
+   */
+  startAssetType?:
+    | string
+    | undefined;
+  /** Optional; maximum number of balances to return. */
+  limit?:
+    | bigint
+    | undefined;
+  /**
+   * Optional; ledger version to read the balances as of.
+   * If not set, the latest ledger version is used.
+   */
+  ledgerVersion?: bigint | undefined;
+}
+
+export interface AccountBalance {
+  /** Coin type, e.g. `0x1::aptos_coin::AptosCoin`, or address of the fungible asset metadata. */
+  assetType?: string | undefined;
+  amount?: bigint | undefined;
+}
+
+export interface GetAccountBalancesResponse {
+  balances?:
+    | AccountBalance[]
+    | undefined;
+  /** Ledger version the balances are read as of. */
+  ledgerVersion?:
+    | bigint
+    | undefined;
+  /** Asset type the next page starts from, if there are more balances. */
+  nextAssetType?: string | undefined;
+}
+
 function createBaseTransactionsOutput(): TransactionsOutput {
   return { transactions: [] };
 }
@@ -743,6 +784,389 @@ export const PingFullnodeResponse = {
   },
 };
 
+function createBaseGetAccountBalancesRequest(): GetAccountBalancesRequest {
+  return { address: "", startAssetType: undefined, limit: undefined, ledgerVersion: undefined };
+}
+
+export const GetAccountBalancesRequest = {
+  encode(message: GetAccountBalancesRequest, writer: _m0.Writer = _m0.Writer.create()): _m0.Writer {
+    if (message.address !== undefined && message.address !== "") {
+      writer.uint32(10).string(message.address);
+    }
+    if (message.startAssetType !== undefined) {
+      writer.uint32(18).string(message.startAssetType);
+    }
+    if (message.limit !== undefined) {
+      if (BigInt.asUintN(64, message.limit) !== message.limit) {
+        throw new globalThis.Error("value provided for field message.limit of type uint64 too large");
+      }
+      writer.uint32(24).uint64(message.limit.toString());
+    }
+    if (message.ledgerVersion !== undefined) {
+      if (BigInt.asUintN(64, message.ledgerVersion) !== message.ledgerVersion) {
+        throw new globalThis.Error("value provided for field message.ledgerVersion of type uint64 too large");
+      }
+      writer.uint32(32).uint64(message.ledgerVersion.toString());
+    }
+    return writer;
+  },
+
+  decode(input: _m0.Reader | Uint8Array, length?: number): GetAccountBalancesRequest {
+    const reader = input instanceof _m0.Reader ? input : _m0.Reader.create(input);
+    let end = length === undefined ? reader.len : reader.pos + length;
+    const message = createBaseGetAccountBalancesRequest();
+    while (reader.pos < end) {
+      const tag = reader.uint32();
+      switch (tag >>> 3) {
+        case 1:
+          if (tag !== 10) {
+            break;
+          }
+
+          message.address = reader.string();
+          continue;
+        case 2:
+          if (tag !== 18) {
+            break;
+          }
+
+          message.startAssetType = reader.string();
+          continue;
+        case 3:
+          if (tag !== 24) {
+            break;
+          }
+
+          message.limit = longToBigint(reader.uint64() as Long);
+          continue;
+        case 4:
+          if (tag !== 32) {
+            break;
+          }
+
+          message.ledgerVersion = longToBigint(reader.uint64() as Long);
+          continue;
+      }
+      if ((tag & 7) === 4 || tag === 0) {
+        break;
+      }
+      reader.skipType(tag & 7);
+    }
+    return message;
+  },
+
+  // encodeTransform encodes a source of message objects.
+  // Transform<GetAccountBalancesRequest, Uint8Array>
+  async *encodeTransform(
+    source:
+      | AsyncIterable<GetAccountBalancesRequest | GetAccountBalancesRequest[]>
+      | Iterable<GetAccountBalancesRequest | GetAccountBalancesRequest[]>,
+  ): AsyncIterable<Uint8Array> {
+    for await (const pkt of source) {
+      if (globalThis.Array.isArray(pkt)) {
+        for (const p of (pkt as any)) {
+          yield* [GetAccountBalancesRequest.encode(p).finish()];
+        }
+      } else {
+        yield* [GetAccountBalancesRequest.encode(pkt as any).finish()];
+      }
+    }
+  },
+
+  // decodeTransform decodes a source of encoded messages.
+  // Transform<Uint8Array, GetAccountBalancesRequest>
+  async *decodeTransform(
+    source: AsyncIterable<Uint8Array | Uint8Array[]> | Iterable<Uint8Array | Uint8Array[]>,
+  ): AsyncIterable<GetAccountBalancesRequest> {
+    for await (const pkt of source) {
+      if (globalThis.Array.isArray(pkt)) {
+        for (const p of (pkt as any)) {
+          yield* [GetAccountBalancesRequest.decode(p)];
+        }
+      } else {
+        yield* [GetAccountBalancesRequest.decode(pkt as any)];
+      }
+    }
+  },
+
+  fromJSON(object: any): GetAccountBalancesRequest {
+    return {
+      address: isSet(object.address) ? globalThis.String(object.address) : "",
+      startAssetType: isSet(object.startAssetType) ? globalThis.String(object.startAssetType) : undefined,
+      limit: isSet(object.limit) ? BigInt(object.limit) : undefined,
+      ledgerVersion: isSet(object.ledgerVersion) ? BigInt(object.ledgerVersion) : undefined,
+    };
+  },
+
+  toJSON(message: GetAccountBalancesRequest): unknown {
+    const obj: any = {};
+    if (message.address !== undefined && message.address !== "") {
+      obj.address = message.address;
+    }
+    if (message.startAssetType !== undefined) {
+      obj.startAssetType = message.startAssetType;
+    }
+    if (message.limit !== undefined) {
+      obj.limit = message.limit.toString();
+    }
+    if (message.ledgerVersion !== undefined) {
+      obj.ledgerVersion = message.ledgerVersion.toString();
+    }
+    return obj;
+  },
+
+  create(base?: DeepPartial<GetAccountBalancesRequest>): GetAccountBalancesRequest {
+    return GetAccountBalancesRequest.fromPartial(base ?? {});
+  },
+  fromPartial(object: DeepPartial<GetAccountBalancesRequest>): GetAccountBalancesRequest {
+    const message = createBaseGetAccountBalancesRequest();
+    message.address = object.address ?? "";
+    message.startAssetType = object.startAssetType ?? undefined;
+    message.limit = object.limit ?? undefined;
+    message.ledgerVersion = object.ledgerVersion ?? undefined;
+    return message;
+  },
+};
+
+function createBaseAccountBalance(): AccountBalance {
+  return { assetType: "", amount: BigInt("0") };
+}
+
+export const AccountBalance = {
+  encode(message: AccountBalance, writer: _m0.Writer = _m0.Writer.create()): _m0.Writer {
+    if (message.assetType !== undefined && message.assetType !== "") {
+      writer.uint32(10).string(message.assetType);
+    }
+    if (message.amount !== undefined && message.amount !== BigInt("0")) {
+      if (BigInt.asUintN(64, message.amount) !== message.amount) {
+        throw new globalThis.Error("value provided for field message.amount of type uint64 too large");
+      }
+      writer.uint32(16).uint64(message.amount.toString());
+    }
+    return writer;
+  },
+
+  decode(input: _m0.Reader | Uint8Array, length?: number): AccountBalance {
+    const reader = input instanceof _m0.Reader ? input : _m0.Reader.create(input);
+    let end = length === undefined ? reader.len : reader.pos + length;
+    const message = createBaseAccountBalance();
+    while (reader.pos < end) {
+      const tag = reader.uint32();
+      switch (tag >>> 3) {
+        case 1:
+          if (tag !== 10) {
+            break;
+          }
+
+          message.assetType = reader.string();
+          continue;
+        case 2:
+          if (tag !== 16) {
+            break;
+          }
+
+          message.amount = longToBigint(reader.uint64() as Long);
+          continue;
+      }
+      if ((tag & 7) === 4 || tag === 0) {
+        break;
+      }
+      reader.skipType(tag & 7);
+    }
+    return message;
+  },
+
+  // encodeTransform encodes a source of message objects.
+  // Transform<AccountBalance, Uint8Array>
+  async *encodeTransform(
+    source: AsyncIterable<AccountBalance | AccountBalance[]> | Iterable<AccountBalance | AccountBalance[]>,
+  ): AsyncIterable<Uint8Array> {
+    for await (const pkt of source) {
+      if (globalThis.Array.isArray(pkt)) {
+        for (const p of (pkt as any)) {
+          yield* [AccountBalance.encode(p).finish()];
+        }
+      } else {
+        yield* [AccountBalance.encode(pkt as any).finish()];
+      }
+    }
+  },
+
+  // decodeTransform decodes a source of encoded messages.
+  // Transform<Uint8Array, AccountBalance>
+  async *decodeTransform(
+    source: AsyncIterable<Uint8Array | Uint8Array[]> | Iterable<Uint8Array | Uint8Array[]>,
+  ): AsyncIterable<AccountBalance> {
+    for await (const pkt of source) {
+      if (globalThis.Array.isArray(pkt)) {
+        for (const p of (pkt as any)) {
+          yield* [AccountBalance.decode(p)];
+        }
+      } else {
+        yield* [AccountBalance.decode(pkt as any)];
+      }
+    }
+  },
+
+  fromJSON(object: any): AccountBalance {
+    return {
+      assetType: isSet(object.assetType) ? globalThis.String(object.assetType) : "",
+      amount: isSet(object.amount) ? BigInt(object.amount) : BigInt("0"),
+    };
+  },
+
+  toJSON(message: AccountBalance): unknown {
+    const obj: any = {};
+    if (message.assetType !== undefined && message.assetType !== "") {
+      obj.assetType = message.assetType;
+    }
+    if (message.amount !== undefined && message.amount !== BigInt("0")) {
+      obj.amount = message.amount.toString();
+    }
+    return obj;
+  },
+
+  create(base?: DeepPartial<AccountBalance>): AccountBalance {
+    return AccountBalance.fromPartial(base ?? {});
+  },
+  fromPartial(object: DeepPartial<AccountBalance>): AccountBalance {
+    const message = createBaseAccountBalance();
+    message.assetType = object.assetType ?? "";
+    message.amount = object.amount ?? BigInt("0");
+    return message;
+  },
+};
+
+function createBaseGetAccountBalancesResponse(): GetAccountBalancesResponse {
+  return { balances: [], ledgerVersion: BigInt("0"), nextAssetType: undefined };
+}
+
+export const GetAccountBalancesResponse = {
+  encode(message: GetAccountBalancesResponse, writer: _m0.Writer = _m0.Writer.create()): _m0.Writer {
+    if (message.balances !== undefined && message.balances.length !== 0) {
+      for (const v of message.balances) {
+        AccountBalance.encode(v!, writer.uint32(10).fork()).ldelim();
+      }
+    }
+    if (message.ledgerVersion !== undefined && message.ledgerVersion !== BigInt("0")) {
+      if (BigInt.asUintN(64, message.ledgerVersion) !== message.ledgerVersion) {
+        throw new globalThis.Error("value provided for field message.ledgerVersion of type uint64 too large");
+      }
+      writer.uint32(16).uint64(message.ledgerVersion.toString());
+    }
+    if (message.nextAssetType !== undefined) {
+      writer.uint32(26).string(message.nextAssetType);
+    }
+    return writer;
+  },
+
+  decode(input: _m0.Reader | Uint8Array, length?: number): GetAccountBalancesResponse {
+    const reader = input instanceof _m0.Reader ? input : _m0.Reader.create(input);
+    let end = length === undefined ? reader.len : reader.pos + length;
+    const message = createBaseGetAccountBalancesResponse();
+    while (reader.pos < end) {
+      const tag = reader.uint32();
+      switch (tag >>> 3) {
+        case 1:
+          if (tag !== 10) {
+            break;
+          }
+
+          message.balances!.push(AccountBalance.decode(reader, reader.uint32()));
+          continue;
+        case 2:
+          if (tag !== 16) {
+            break;
+          }
+
+          message.ledgerVersion = longToBigint(reader.uint64() as Long);
+          continue;
+        case 3:
+          if (tag !== 26) {
+            break;
+          }
+
+          message.nextAssetType = reader.string();
+          continue;
+      }
+      if ((tag & 7) === 4 || tag === 0) {
+        break;
+      }
+      reader.skipType(tag & 7);
+    }
+    return message;
+  },
+
+  // encodeTransform encodes a source of message objects.
+  // Transform<GetAccountBalancesResponse, Uint8Array>
+  async *encodeTransform(
+    source:
+      | AsyncIterable<GetAccountBalancesResponse | GetAccountBalancesResponse[]>
+      | Iterable<GetAccountBalancesResponse | GetAccountBalancesResponse[]>,
+  ): AsyncIterable<Uint8Array> {
+    for await (const pkt of source) {
+      if (globalThis.Array.isArray(pkt)) {
+        for (const p of (pkt as any)) {
+          yield* [GetAccountBalancesResponse.encode(p).finish()];
+        }
+      } else {
+        yield* [GetAccountBalancesResponse.encode(pkt as any).finish()];
+      }
+    }
+  },
+
+  // decodeTransform decodes a source of encoded messages.
+  // Transform<Uint8Array, GetAccountBalancesResponse>
+  async *decodeTransform(
+    source: AsyncIterable<Uint8Array | Uint8Array[]> | Iterable<Uint8Array | Uint8Array[]>,
+  ): AsyncIterable<GetAccountBalancesResponse> {
+    for await (const pkt of source) {
+      if (globalThis.Array.isArray(pkt)) {
+        for (const p of (pkt as any)) {
+          yield* [GetAccountBalancesResponse.decode(p)];
+        }
+      } else {
+        yield* [GetAccountBalancesResponse.decode(pkt as any)];
+      }
+    }
+  },
+
+  fromJSON(object: any): GetAccountBalancesResponse {
+    return {
+      balances: globalThis.Array.isArray(object?.balances)
+        ? object.balances.map((e: any) => AccountBalance.fromJSON(e))
+        : [],
+      ledgerVersion: isSet(object.ledgerVersion) ? BigInt(object.ledgerVersion) : BigInt("0"),
+      nextAssetType: isSet(object.nextAssetType) ? globalThis.String(object.nextAssetType) : undefined,
+    };
+  },
+
+  toJSON(message: GetAccountBalancesResponse): unknown {
+    const obj: any = {};
+    if (message.balances?.length) {
+      obj.balances = message.balances.map((e) => AccountBalance.toJSON(e));
+    }
+    if (message.ledgerVersion !== undefined && message.ledgerVersion !== BigInt("0")) {
+      obj.ledgerVersion = message.ledgerVersion.toString();
+    }
+    if (message.nextAssetType !== undefined) {
+      obj.nextAssetType = message.nextAssetType;
+    }
+    return obj;
+  },
+
+  create(base?: DeepPartial<GetAccountBalancesResponse>): GetAccountBalancesResponse {
+    return GetAccountBalancesResponse.fromPartial(base ?? {});
+  },
+  fromPartial(object: DeepPartial<GetAccountBalancesResponse>): GetAccountBalancesResponse {
+    const message = createBaseGetAccountBalancesResponse();
+    message.balances = object.balances?.map((e) => AccountBalance.fromPartial(e)) || [];
+    message.ledgerVersion = object.ledgerVersion ?? BigInt("0");
+    message.nextAssetType = object.nextAssetType ?? undefined;
+    return message;
+  },
+};
+
 export type FullnodeDataService = typeof FullnodeDataService;
 export const FullnodeDataService = {
   ping: {
@@ -765,11 +1189,23 @@ export const FullnodeDataService = {
       Buffer.from(TransactionsFromNodeResponse.encode(value).finish()),
     responseDeserialize: (value: Buffer) => TransactionsFromNodeResponse.decode(value),
   },
+  getAccountBalances: {
+    path: "/aptos.internal.fullnode.v1.FullnodeData/GetAccountBalances",
+    requestStream: false,
+    responseStream: false,
+    requestSerialize: (value: GetAccountBalancesRequest) =>
+      Buffer.from(GetAccountBalancesRequest.encode(value).finish()),
+    requestDeserialize: (value: Buffer) => GetAccountBalancesRequest.decode(value),
+    responseSerialize: (value: GetAccountBalancesResponse) =>
+      Buffer.from(GetAccountBalancesResponse.encode(value).finish()),
+    responseDeserialize: (value: Buffer) => GetAccountBalancesResponse.decode(value),
+  },
 } as const;
 
 export interface FullnodeDataServer extends UntypedServiceImplementation {
   ping: handleUnaryCall<PingFullnodeRequest, PingFullnodeResponse>;
   getTransactionsFromNode: handleServerStreamingCall<GetTransactionsFromNodeRequest, TransactionsFromNodeResponse>;
+  getAccountBalances: handleUnaryCall<GetAccountBalancesRequest, GetAccountBalancesResponse>;
 }
 
 export interface FullnodeDataClient extends Client {
@@ -797,6 +1233,21 @@ export interface FullnodeDataClient extends Client {
     metadata?: Metadata,
     options?: Partial<CallOptions>,
   ): ClientReadableStream<TransactionsFromNodeResponse>;
+  getAccountBalances(
+    request: GetAccountBalancesRequest,
+    callback: (error: ServiceError | null, response: GetAccountBalancesResponse) => void,
+  ): ClientUnaryCall;
+  getAccountBalances(
+    request: GetAccountBalancesRequest,
+    metadata: Metadata,
+    callback: (error: ServiceError | null, response: GetAccountBalancesResponse) => void,
+  ): ClientUnaryCall;
+  getAccountBalances(
+    request: GetAccountBalancesRequest,
+    metadata: Metadata,
+    options: Partial<CallOptions>,
+    callback: (error: ServiceError | null, response: GetAccountBalancesResponse) => void,
+  ): ClientUnaryCall;
 }
 
 export const FullnodeDataClient = makeGenericClientConstructor(
@@ -12,7 +12,10 @@ use crate::{
     transaction_builder::TransactionBuilder,
     types::{
         account_address::AccountAddress,
-        transaction::{authenticator::AuthenticationKey, RawTransaction, SignedTransaction},
+        transaction::{
+            authenticator::{AccountAuthenticator, AuthenticationKey},
+            RawTransaction, RawTransactionWithData, SignedTransaction,
+        },
     },
 };
 use anyhow::{Context, Result};
@@ -710,6 +713,19 @@ impl HardwareWalletAccount {
     ) -> Result<Ed25519Signature, AptosLedgerError> {
         aptos_ledger::sign_message(&self.derivation_path, message)
     }
+
+    /// Signs a multi-agent or fee payer transaction as one of its signers, i.e. the sender, a
+    /// secondary signer or the fee payer.
+    pub fn sign_raw_transaction_with_data(
+        &self,
+        txn: &RawTransactionWithData,
+    ) -> Result<AccountAuthenticator> {
+        let signature = self.sign_arbitrary_message(signing_message(txn)?.as_ref())?;
+        Ok(AccountAuthenticator::ed25519(
+            self.public_key().clone(),
+            signature,
+        ))
+    }
 }
 
 #[derive(Debug)]
@@ -15,17 +15,25 @@
 //!
 //! Internally both the client and server leverage a NetworkStream that communications in blocks
 //! where a block is a length prefixed array of bytes.
+//!
+//! Streams can optionally be secured with mutually-authenticated TLS, see `tls`.
 
 pub mod grpc_network_service;
 pub mod network_controller;
+pub mod tls;
 
 use aptos_logger::{info, trace, warn, Schema};
 use aptos_metrics_core::{register_int_counter_vec, IntCounterVec};
 use once_cell::sync::Lazy;
+use rustls::{
+    pki_types::ServerName, ClientConfig, ClientConnection, ServerConfig, ServerConnection,
+    StreamOwned,
+};
 use serde::Serialize;
 use std::{
     io::{Read, Write},
     net::{Shutdown, SocketAddr, TcpListener, TcpStream},
+    sync::Arc,
     thread, time,
 };
 use thiserror::Error;
@@ -145,14 +153,21 @@ pub enum Error {
     OverflowError(String),
     #[error("Remote stream cleanly closed")]
     RemoteStreamClosed,
+    #[error("TLS error: {0}")]
+    TlsError(String),
 }
 
 pub struct NetworkClient {
     service: String,
     server: SocketAddr,
+    /// Servers to connect to when the current one is unreachable, in order, including it.
+    servers: Vec<SocketAddr>,
     stream: Option<NetworkStream>,
+    tls: Option<tls::ClientTlsConfig>,
     /// Read, Write, Connect timeout in milliseconds.
     timeout_ms: u64,
+    /// If set, connecting fails after this many attempts instead of retrying until connected.
+    max_connection_attempts: Option<usize>,
 }
 
 impl NetworkClient {
@@ -160,11 +175,54 @@ impl NetworkClient {
         Self {
             service,
             server,
+            servers: vec![server],
             stream: None,
+            tls: None,
             timeout_ms,
+            max_connection_attempts: None,
         }
     }
 
+    /// Gives up connecting after `attempts` failed attempts, across all servers, instead of
+    /// retrying until one is reachable, e.g., if the caller can do without this client.
+    pub fn with_max_connection_attempts(mut self, attempts: usize) -> Self {
+        self.max_connection_attempts = Some(attempts);
+        self
+    }
+
+    /// Fails over to `servers`, in order, whenever the current server cannot be reached.
+    pub fn with_failover(mut self, servers: Vec<SocketAddr>) -> Self {
+        self.servers.extend(servers);
+        self
+    }
+
+    /// Secures the stream with TLS, see `tls::ClientTlsConfig`.
+    pub fn with_tls(mut self, tls: tls::ClientTlsConfig) -> Self {
+        self.tls = Some(tls);
+        self
+    }
+
+    /// Returns true if a stream to a server is currently open
+    pub fn is_connected(&self) -> bool {
+        self.stream.is_some()
+    }
+
+    /// Drops the stream to the current server and moves on to the next one, e.g., if the current
+    /// server turns out to be misbehaving.
+    pub fn fail_over(&mut self) {
+        self.stream = None;
+        self.next_server();
+    }
+
+    fn next_server(&mut self) {
+        let current = self
+            .servers
+            .iter()
+            .position(|server| *server == self.server)
+            .unwrap_or(0);
+        self.server = self.servers[(current + 1) % self.servers.len()];
+    }
+
     fn increment_counter(&self, method: Method, result: MethodResult) {
         increment_counter(&self.service, NetworkMode::Client, method, result)
     }
@@ -236,10 +294,9 @@ impl NetworkClient {
             )
             .remote_peer(&self.server));
 
-            let timeout = std::time::Duration::from_millis(self.timeout_ms);
-            let mut stream = TcpStream::connect_timeout(&self.server, timeout);
-
             let sleeptime = time::Duration::from_millis(100);
+            let mut attempts = 1;
+            let mut stream = self.connect();
             while let Err(err) = stream {
                 self.increment_counter(Method::Connect, MethodResult::Failure);
                 warn!(SecureNetLogSchema::new(
@@ -247,16 +304,22 @@ impl NetworkClient {
                     NetworkMode::Client,
                     LogEvent::ConnectionFailed,
                 )
-                .error(&err.into())
+                .error(&err)
                 .remote_peer(&self.server));
 
+                self.next_server();
+                if self
+                    .max_connection_attempts
+                    .is_some_and(|max_attempts| attempts >= max_attempts)
+                {
+                    return Err(err);
+                }
                 thread::sleep(sleeptime);
-                stream = TcpStream::connect_timeout(&self.server, timeout);
+                attempts += 1;
+                stream = self.connect();
             }
 
-            let stream = stream?;
-            stream.set_nodelay(true)?;
-            self.stream = Some(NetworkStream::new(stream, self.server, self.timeout_ms));
+            self.stream = Some(stream?);
             self.increment_counter(Method::Connect, MethodResult::Success);
             info!(SecureNetLogSchema::new(
                 &self.service,
@@ -268,12 +331,28 @@ impl NetworkClient {
 
         self.stream.as_mut().ok_or(Error::NoActiveStream)
     }
+
+    fn connect(&self) -> Result<NetworkStream, Error> {
+        let timeout = std::time::Duration::from_millis(self.timeout_ms);
+        let stream = TcpStream::connect_timeout(&self.server, timeout)?;
+        stream.set_nodelay(true)?;
+        set_timeouts(&stream, self.timeout_ms);
+
+        let transport = match &self.tls {
+            Some(tls) => {
+                Transport::tls_client(tls.config.clone(), tls.server_name.clone(), stream)?
+            },
+            None => Transport::Plain(stream),
+        };
+        Ok(NetworkStream::new(transport, self.server))
+    }
 }
 
 pub struct NetworkServer {
     service: String,
     listener: Option<TcpListener>,
     stream: Option<NetworkStream>,
+    tls: Option<Arc<ServerConfig>>,
     /// Read, Write, Connect timeout in milliseconds.
     timeout_ms: u64,
 }
@@ -285,10 +364,17 @@ impl NetworkServer {
             service,
             listener: Some(listener.unwrap()),
             stream: None,
+            tls: None,
             timeout_ms,
         }
     }
 
+    /// Secures accepted streams with TLS, see `tls::server_config`.
+    pub fn with_tls(mut self, config: Arc<ServerConfig>) -> Self {
+        self.tls = Some(config);
+        self
+    }
+
     fn increment_counter(&self, method: Method, result: MethodResult) {
         increment_counter(&self.service, NetworkMode::Server, method, result)
     }
@@ -389,6 +475,26 @@ impl NetworkServer {
                 },
             };
 
+            stream.set_nodelay(true)?;
+            set_timeouts(&stream, self.timeout_ms);
+            let transport = match &self.tls {
+                Some(config) => match Transport::tls_server(config.clone(), stream) {
+                    Ok(transport) => transport,
+                    Err(err) => {
+                        self.increment_counter(Method::Connect, MethodResult::Failure);
+                        warn!(SecureNetLogSchema::new(
+                            &self.service,
+                            NetworkMode::Server,
+                            LogEvent::ConnectionFailed,
+                        )
+                        .error(&err)
+                        .remote_peer(&stream_addr));
+                        return Err(err);
+                    },
+                },
+                None => Transport::Plain(stream),
+            };
+
             self.increment_counter(Method::Connect, MethodResult::Success);
             info!(SecureNetLogSchema::new(
                 &self.service,
@@ -397,28 +503,100 @@ impl NetworkServer {
             )
             .remote_peer(&stream_addr));
 
-            stream.set_nodelay(true)?;
-            self.stream = Some(NetworkStream::new(stream, stream_addr, self.timeout_ms));
+            self.stream = Some(NetworkStream::new(transport, stream_addr));
         }
 
         self.stream.as_mut().ok_or(Error::NoActiveStream)
     }
 }
 
+fn set_timeouts(stream: &TcpStream, timeout_ms: u64) {
+    let timeout = Some(std::time::Duration::from_millis(timeout_ms));
+    // These only fail if a duration of 0 is passed in.
+    stream.set_read_timeout(timeout).unwrap();
+    stream.set_write_timeout(timeout).unwrap();
+}
+
+/// The underlying stream, with the TLS handshake (if any) already completed
+enum Transport {
+    Plain(TcpStream),
+    TlsClient(Box<StreamOwned<ClientConnection, TcpStream>>),
+    TlsServer(Box<StreamOwned<ServerConnection, TcpStream>>),
+}
+
+impl Transport {
+    fn tls_client(
+        config: Arc<ClientConfig>,
+        server_name: ServerName<'static>,
+        mut stream: TcpStream,
+    ) -> Result<Self, Error> {
+        let mut connection = ClientConnection::new(config, server_name)
+            .map_err(|error| Error::TlsError(error.to_string()))?;
+        while connection.is_handshaking() {
+            connection.complete_io(&mut stream)?;
+        }
+        Ok(Self::TlsClient(Box::new(StreamOwned::new(
+            connection, stream,
+        ))))
+    }
+
+    fn tls_server(config: Arc<ServerConfig>, mut stream: TcpStream) -> Result<Self, Error> {
+        let mut connection =
+            ServerConnection::new(config).map_err(|error| Error::TlsError(error.to_string()))?;
+        while connection.is_handshaking() {
+            connection.complete_io(&mut stream)?;
+        }
+        Ok(Self::TlsServer(Box::new(StreamOwned::new(
+            connection, stream,
+        ))))
+    }
+
+    fn tcp_stream(&self) -> &TcpStream {
+        match self {
+            Self::Plain(stream) => stream,
+            Self::TlsClient(stream) => stream.get_ref(),
+            Self::TlsServer(stream) => stream.get_ref(),
+        }
+    }
+}
+
+impl Read for Transport {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        match self {
+            Self::Plain(stream) => stream.read(buf),
+            Self::TlsClient(stream) => stream.read(buf),
+            Self::TlsServer(stream) => stream.read(buf),
+        }
+    }
+}
+
+impl Write for Transport {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        match self {
+            Self::Plain(stream) => stream.write(buf),
+            Self::TlsClient(stream) => stream.write(buf),
+            Self::TlsServer(stream) => stream.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        match self {
+            Self::Plain(stream) => stream.flush(),
+            Self::TlsClient(stream) => stream.flush(),
+            Self::TlsServer(stream) => stream.flush(),
+        }
+    }
+}
+
 struct NetworkStream {
-    stream: TcpStream,
+    stream: Transport,
     remote: SocketAddr,
     buffer: Vec<u8>,
     temp_buffer: [u8; 1024],
 }
 
 impl NetworkStream {
-    pub fn new(stream: TcpStream, remote: SocketAddr, timeout_ms: u64) -> Self {
-        let timeout = Some(std::time::Duration::from_millis(timeout_ms));
-        // These only fail if a duration of 0 is passed in.
-        stream.set_read_timeout(timeout).unwrap();
-        stream.set_write_timeout(timeout).unwrap();
-
+    pub fn new(stream: Transport, remote: SocketAddr) -> Self {
         Self {
             stream,
             remote,
@@ -453,7 +631,7 @@ impl NetworkStream {
 
     /// Terminate the socket
     pub fn shutdown(&self) -> Result<(), Error> {
-        Ok(self.stream.shutdown(Shutdown::Both)?)
+        Ok(self.stream.tcp_stream().shutdown(Shutdown::Both)?)
     }
 
     /// Blocking write until able to successfully send an entire message
@@ -467,6 +645,8 @@ impl NetworkStream {
         self.write_all(&data_len.to_le_bytes())?;
         trace!("Attempting to write data, {},  to the stream", data_len);
         self.write_all(data)?;
+        // TLS streams may buffer records until flushed
+        self.stream.flush()?;
         trace!(
             "Successfully wrote length, {}, and data to the stream",
             data_len
@@ -540,6 +720,51 @@ mod test {
         assert_eq!(data, result);
     }
 
+    #[test]
+    fn test_client_failover() {
+        let first_port = utils::get_available_port();
+        let first_addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), first_port);
+        let server_port = utils::get_available_port();
+        let server_addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), server_port);
+        let mut server = NetworkServer::new("test".to_string(), server_addr, TIMEOUT);
+        let mut client = NetworkClient::new("test".to_string(), first_addr, TIMEOUT)
+            .with_failover(vec![server_addr]);
+
+        // The client moves on to the second server once the first one can't be reached
+        let data = vec![0, 1, 2, 3];
+        client.write(&data).unwrap();
+        assert!(client.is_connected());
+        let result = server.read().unwrap();
+        assert_eq!(data, result);
+
+        // And back to the first one once told to fail over
+        client.fail_over();
+        assert!(!client.is_connected());
+        let mut server2 = NetworkServer::new("test".to_string(), first_addr, TIMEOUT);
+        client.write(&data).unwrap();
+        let result = server2.read().unwrap();
+        assert_eq!(data, result);
+    }
+
+    #[test]
+    fn test_client_max_connection_attempts() {
+        let server_port = utils::get_available_port();
+        let server_addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), server_port);
+        let mut client = NetworkClient::new("test".to_string(), server_addr, TIMEOUT)
+            .with_max_connection_attempts(2);
+
+        // The client gives up while the server can't be reached
+        let data = vec![0, 1, 2, 3];
+        client.write(&data).unwrap_err();
+        assert!(!client.is_connected());
+
+        // And connects once it can
+        let mut server = NetworkServer::new("test".to_string(), server_addr, TIMEOUT);
+        client.write(&data).unwrap();
+        let result = server.read().unwrap();
+        assert_eq!(data, result);
+    }
+
     #[test]
     fn test_client_shutdown() {
         let server_port = utils::get_available_port();
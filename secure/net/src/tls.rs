@@ -0,0 +1,115 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Mutually-authenticated TLS for `NetworkClient` and `NetworkServer`. Both ends present a
+//! certificate issued by the given CA and reject peers that don't.
+
+use crate::Error;
+use rustls::{
+    crypto::{ring, CryptoProvider},
+    pki_types::{CertificateDer, PrivateKeyDer, ServerName},
+    server::WebPkiClientVerifier,
+    ClientConfig, RootCertStore, ServerConfig,
+};
+use std::{fs::File, io::BufReader, path::Path, sync::Arc};
+
+/// The TLS config of a client along with the name the server's certificate must be issued for
+#[derive(Clone)]
+pub struct ClientTlsConfig {
+    pub(crate) config: Arc<ClientConfig>,
+    pub(crate) server_name: ServerName<'static>,
+}
+
+impl ClientTlsConfig {
+    /// Loads the config of a client expecting a server issued for `server_name`, see
+    /// `client_config`.
+    pub fn new(
+        ca_certificate: &Path,
+        certificate: &Path,
+        private_key: &Path,
+        server_name: String,
+    ) -> Result<Self, Error> {
+        Ok(Self {
+            config: client_config(ca_certificate, certificate, private_key)?,
+            server_name: ServerName::try_from(server_name).map_err(tls_error)?,
+        })
+    }
+}
+
+/// Builds the TLS config of a client that trusts servers issued by `ca_certificate` and
+/// authenticates with `certificate` and `private_key`, all PEM encoded.
+pub fn client_config(
+    ca_certificate: &Path,
+    certificate: &Path,
+    private_key: &Path,
+) -> Result<Arc<ClientConfig>, Error> {
+    let config = ClientConfig::builder_with_provider(provider())
+        .with_safe_default_protocol_versions()
+        .map_err(tls_error)?
+        .with_root_certificates(root_store(ca_certificate)?)
+        .with_client_auth_cert(
+            load_certificates(certificate)?,
+            load_private_key(private_key)?,
+        )
+        .map_err(tls_error)?;
+    Ok(Arc::new(config))
+}
+
+/// Builds the TLS config of a server that only accepts clients issued by `ca_certificate` and
+/// authenticates with `certificate` and `private_key`, all PEM encoded.
+pub fn server_config(
+    ca_certificate: &Path,
+    certificate: &Path,
+    private_key: &Path,
+) -> Result<Arc<ServerConfig>, Error> {
+    let client_verifier = WebPkiClientVerifier::builder_with_provider(
+        Arc::new(root_store(ca_certificate)?),
+        provider(),
+    )
+    .build()
+    .map_err(tls_error)?;
+    let config = ServerConfig::builder_with_provider(provider())
+        .with_safe_default_protocol_versions()
+        .map_err(tls_error)?
+        .with_client_cert_verifier(client_verifier)
+        .with_single_cert(
+            load_certificates(certificate)?,
+            load_private_key(private_key)?,
+        )
+        .map_err(tls_error)?;
+    Ok(Arc::new(config))
+}
+
+fn provider() -> Arc<CryptoProvider> {
+    Arc::new(ring::default_provider())
+}
+
+fn root_store(ca_certificate: &Path) -> Result<RootCertStore, Error> {
+    let mut roots = RootCertStore::empty();
+    for certificate in load_certificates(ca_certificate)? {
+        roots.add(certificate).map_err(tls_error)?;
+    }
+    Ok(roots)
+}
+
+fn load_certificates(path: &Path) -> Result<Vec<CertificateDer<'static>>, Error> {
+    let mut reader = BufReader::new(File::open(path)?);
+    let certificates = rustls_pemfile::certs(&mut reader).collect::<Result<Vec<_>, _>>()?;
+    if certificates.is_empty() {
+        return Err(Error::TlsError(format!(
+            "No certificate found in {}",
+            path.display()
+        )));
+    }
+    Ok(certificates)
+}
+
+fn load_private_key(path: &Path) -> Result<PrivateKeyDer<'static>, Error> {
+    let mut reader = BufReader::new(File::open(path)?);
+    rustls_pemfile::private_key(&mut reader)?
+        .ok_or_else(|| Error::TlsError(format!("No private key found in {}", path.display())))
+}
+
+fn tls_error<E: std::fmt::Display>(error: E) -> Error {
+    Error::TlsError(error.to_string())
+}
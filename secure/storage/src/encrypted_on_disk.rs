@@ -0,0 +1,161 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{CryptoKVStorage, Error, GetResponse, KVStorage};
+use aes_gcm::{
+    aead::{Aead, OsRng},
+    AeadCore, Aes256Gcm, Key, KeyInit, Nonce,
+};
+use aptos_temppath::TempPath;
+use aptos_time_service::{TimeService, TimeServiceTrait};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::Value;
+use std::{
+    collections::HashMap,
+    fs::{self, File},
+    io::{Read, Write},
+    path::PathBuf,
+};
+
+/// The length of the encryption key, in bytes.
+pub const ENCRYPTION_KEY_LENGTH: usize = 32;
+const NONCE_LENGTH: usize = 12;
+
+/// EncryptedOnDiskStorage is a key value store persisted to the local filesystem, like
+/// OnDiskStorage, but that encrypts the whole file with AES-256-GCM.
+///
+/// The encryption key is meant to be a data key wrapped by a cloud KMS, and only unwrapped onto
+/// the host running the validator (e.g., by a secrets manager mounting it into the pod). The file
+/// itself can then be kept on storage shared between validator replicas (e.g., a network volume),
+/// without exposing the consensus key to anyone who can read that storage. Writes are atomic
+/// (the file is written to a temporary path, and then renamed).
+pub struct EncryptedOnDiskStorage {
+    file_path: PathBuf,
+    temp_path: TempPath,
+    cipher: Aes256Gcm,
+    time_service: TimeService,
+}
+
+/// The on-disk format of the storage file.
+#[derive(Deserialize, Serialize)]
+struct EncryptedData {
+    #[serde(
+        serialize_with = "crate::to_base64",
+        deserialize_with = "crate::from_base64"
+    )]
+    nonce: Vec<u8>,
+    #[serde(
+        serialize_with = "crate::to_base64",
+        deserialize_with = "crate::from_base64"
+    )]
+    ciphertext: Vec<u8>,
+}
+
+impl EncryptedOnDiskStorage {
+    pub fn new(file_path: PathBuf, encryption_key: &[u8; ENCRYPTION_KEY_LENGTH]) -> Self {
+        Self::new_with_time_service(file_path, encryption_key, TimeService::real())
+    }
+
+    fn new_with_time_service(
+        file_path: PathBuf,
+        encryption_key: &[u8; ENCRYPTION_KEY_LENGTH],
+        time_service: TimeService,
+    ) -> Self {
+        if !file_path.exists() {
+            File::create(&file_path)
+                .unwrap_or_else(|_| panic!("Unable to create storage at path: {:?}", file_path));
+        }
+
+        // The parent will be one when only a filename is supplied. Therefore use the current
+        // working directory provided by PathBuf::new().
+        let file_dir = file_path
+            .parent()
+            .map_or_else(PathBuf::new, |p| p.to_path_buf());
+
+        Self {
+            file_path,
+            temp_path: TempPath::new_with_temp_dir(file_dir),
+            cipher: Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(encryption_key)),
+            time_service,
+        }
+    }
+
+    fn read(&self) -> Result<HashMap<String, Value>, Error> {
+        let mut file = File::open(&self.file_path)?;
+        let mut contents = String::new();
+        file.read_to_string(&mut contents)?;
+        if contents.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        let encrypted: EncryptedData = serde_json::from_str(&contents)?;
+        if encrypted.nonce.len() != NONCE_LENGTH {
+            return Err(Error::SerializationError(format!(
+                "Invalid nonce length: {}",
+                encrypted.nonce.len()
+            )));
+        }
+        let plaintext = self
+            .cipher
+            .decrypt(
+                Nonce::from_slice(&encrypted.nonce),
+                encrypted.ciphertext.as_slice(),
+            )
+            .map_err(|_| {
+                Error::InternalError(format!(
+                    "Unable to decrypt storage at path {:?}, is the encryption key correct?",
+                    self.file_path
+                ))
+            })?;
+        Ok(serde_json::from_slice(&plaintext)?)
+    }
+
+    fn write(&self, data: &HashMap<String, Value>) -> Result<(), Error> {
+        let plaintext = serde_json::to_vec(data)?;
+        let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+        let ciphertext = self
+            .cipher
+            .encrypt(&nonce, plaintext.as_slice())
+            .map_err(|error| Error::InternalError(format!("Unable to encrypt: {}", error)))?;
+        let contents = serde_json::to_vec(&EncryptedData {
+            nonce: nonce.to_vec(),
+            ciphertext,
+        })?;
+
+        let mut file = File::create(self.temp_path.path())?;
+        file.write_all(&contents)?;
+        file.sync_all()?;
+        fs::rename(&self.temp_path, &self.file_path)?;
+        Ok(())
+    }
+}
+
+impl KVStorage for EncryptedOnDiskStorage {
+    fn available(&self) -> Result<(), Error> {
+        self.read().map(|_| ())
+    }
+
+    fn get<V: DeserializeOwned>(&self, key: &str) -> Result<GetResponse<V>, Error> {
+        let mut data = self.read()?;
+        data.remove(key)
+            .ok_or_else(|| Error::KeyNotSet(key.to_string()))
+            .and_then(|value| serde_json::from_value(value).map_err(|e| e.into()))
+    }
+
+    fn set<V: Serialize>(&mut self, key: &str, value: V) -> Result<(), Error> {
+        let now = self.time_service.now_secs();
+        let mut data = self.read()?;
+        data.insert(
+            key.to_string(),
+            serde_json::to_value(&GetResponse::new(value, now))?,
+        );
+        self.write(&data)
+    }
+
+    #[cfg(any(test, feature = "testing"))]
+    fn reset_and_clear(&mut self) -> Result<(), Error> {
+        self.write(&HashMap::new())
+    }
+}
+
+impl CryptoKVStorage for EncryptedOnDiskStorage {}
@@ -54,6 +54,12 @@ impl From<serde_json::Error> for Error {
     }
 }
 
+impl From<rusqlite::Error> for Error {
+    fn from(error: rusqlite::Error) -> Self {
+        Self::InternalError(format!("{}", error))
+    }
+}
+
 impl From<aptos_vault_client::Error> for Error {
     fn from(error: aptos_vault_client::Error) -> Self {
         match error {
@@ -6,24 +6,28 @@
 
 mod crypto_kv_storage;
 mod crypto_storage;
+mod encrypted_on_disk;
 mod error;
 mod in_memory;
 mod kv_storage;
 mod namespaced;
 mod on_disk;
 mod policy;
+mod sqlite;
 mod storage;
 mod vault;
 
 pub use crate::{
     crypto_kv_storage::CryptoKVStorage,
     crypto_storage::{CryptoStorage, PublicKeyResponse},
+    encrypted_on_disk::{EncryptedOnDiskStorage, ENCRYPTION_KEY_LENGTH},
     error::Error,
     in_memory::InMemoryStorage,
     kv_storage::{GetResponse, KVStorage},
     namespaced::Namespaced,
     on_disk::OnDiskStorage,
     policy::{Capability, Identity, Permission, Policy},
+    sqlite::SqliteStorage,
     storage::Storage,
     vault::VaultStorage,
 };
@@ -0,0 +1,81 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{CryptoKVStorage, Error, GetResponse, KVStorage};
+use aptos_infallible::Mutex;
+use aptos_time_service::{TimeService, TimeServiceTrait};
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::{de::DeserializeOwned, Serialize};
+use std::path::Path;
+
+/// SqliteStorage is a key value store persisted in a SQLite database. Unlike OnDiskStorage, which
+/// rewrites the whole file on every write, each write is a single transaction that is synced to
+/// disk (in WAL mode) before returning, and a crash can never leave the storage partially written.
+pub struct SqliteStorage {
+    connection: Mutex<Connection>,
+    time_service: TimeService,
+}
+
+impl SqliteStorage {
+    pub fn new(path: &Path) -> Self {
+        Self::new_with_time_service(path, TimeService::real())
+    }
+
+    fn new_with_time_service(path: &Path, time_service: TimeService) -> Self {
+        let connection = Connection::open(path)
+            .and_then(|connection| {
+                connection.pragma_update(None, "journal_mode", "WAL")?;
+                connection.pragma_update(None, "synchronous", "FULL")?;
+                connection.execute(
+                    "CREATE TABLE IF NOT EXISTS kv (key TEXT PRIMARY KEY, value BLOB NOT NULL)",
+                    [],
+                )?;
+                Ok(connection)
+            })
+            .unwrap_or_else(|error| panic!("Unable to open storage at path {:?}: {}", path, error));
+
+        Self {
+            connection: Mutex::new(connection),
+            time_service,
+        }
+    }
+}
+
+impl KVStorage for SqliteStorage {
+    fn available(&self) -> Result<(), Error> {
+        self.connection
+            .lock()
+            .query_row("SELECT 1", [], |_| Ok(()))
+            .map_err(|e| e.into())
+    }
+
+    fn get<V: DeserializeOwned>(&self, key: &str) -> Result<GetResponse<V>, Error> {
+        let value: Vec<u8> = self
+            .connection
+            .lock()
+            .query_row("SELECT value FROM kv WHERE key = ?1", params![key], |row| {
+                row.get(0)
+            })
+            .optional()?
+            .ok_or_else(|| Error::KeyNotSet(key.to_string()))?;
+        serde_json::from_slice(&value).map_err(|e| e.into())
+    }
+
+    fn set<V: Serialize>(&mut self, key: &str, value: V) -> Result<(), Error> {
+        let now = self.time_service.now_secs();
+        let value = serde_json::to_vec(&GetResponse::new(value, now))?;
+        self.connection.lock().execute(
+            "INSERT OR REPLACE INTO kv (key, value) VALUES (?1, ?2)",
+            params![key, value],
+        )?;
+        Ok(())
+    }
+
+    #[cfg(any(test, feature = "testing"))]
+    fn reset_and_clear(&mut self) -> Result<(), Error> {
+        self.connection.lock().execute("DELETE FROM kv", [])?;
+        Ok(())
+    }
+}
+
+impl CryptoKVStorage for SqliteStorage {}
@@ -6,8 +6,8 @@
 //! should support both key/value operations (e.g., get, set and create) and cryptographic key
 //! operations (e.g., generate_key, sign and rotate_key).
 use crate::{
-    CryptoStorage, Error, GetResponse, InMemoryStorage, KVStorage, Namespaced, OnDiskStorage,
-    PublicKeyResponse, VaultStorage,
+    CryptoStorage, EncryptedOnDiskStorage, Error, GetResponse, InMemoryStorage, KVStorage,
+    Namespaced, OnDiskStorage, PublicKeyResponse, SqliteStorage, VaultStorage,
 };
 use aptos_crypto::ed25519::{Ed25519PrivateKey, Ed25519PublicKey, Ed25519Signature};
 use enum_dispatch::enum_dispatch;
@@ -21,6 +21,8 @@ pub enum Storage {
     InMemoryStorage(InMemoryStorage),
     NamespacedStorage(Namespaced<Box<Storage>>),
     OnDiskStorage(OnDiskStorage),
+    EncryptedOnDiskStorage(EncryptedOnDiskStorage),
+    SqliteStorage(SqliteStorage),
 }
 
 impl KVStorage for Box<Storage> {
@@ -0,0 +1,30 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{tests::suite, EncryptedOnDiskStorage, Error, KVStorage, Storage};
+use aptos_temppath::TempPath;
+
+#[test]
+fn encrypted_on_disk() {
+    let path_buf = TempPath::new().path().to_path_buf();
+    let mut storage = Storage::from(EncryptedOnDiskStorage::new(path_buf, &[1; 32]));
+    suite::execute_all_storage_tests(&mut storage);
+}
+
+#[test]
+fn encrypted_on_disk_wrong_key() {
+    let path_buf = TempPath::new().path().to_path_buf();
+    let mut storage = EncryptedOnDiskStorage::new(path_buf.clone(), &[1; 32]);
+    storage.set("safety_data", 10u64).unwrap();
+    assert_eq!(storage.get::<u64>("safety_data").unwrap().value, 10);
+
+    // The contents are not readable in the clear, nor with another key
+    assert!(!std::fs::read_to_string(&path_buf)
+        .unwrap()
+        .contains("safety_data"));
+    let storage = EncryptedOnDiskStorage::new(path_buf, &[2; 32]);
+    assert!(matches!(
+        storage.get::<u64>("safety_data").unwrap_err(),
+        Error::InternalError(_)
+    ));
+}
@@ -0,0 +1,12 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{tests::suite, SqliteStorage, Storage};
+use aptos_temppath::TempPath;
+
+#[test]
+fn sqlite() {
+    let path = TempPath::new();
+    let mut storage = Storage::from(SqliteStorage::new(path.path()));
+    suite::execute_all_storage_tests(&mut storage);
+}
@@ -206,14 +206,14 @@ pub fn extend_with_random_peers(
     selected_peers
 }
 
-/// Gets the latency for the specified peer from the peer monitoring metadata
+/// Gets the latency for the specified peer from the peer metadata (i.e., the
+/// slower of the ping latency and any application probe latencies)
 fn get_latency_for_peer(
     peers_and_metadata: &Arc<PeersAndMetadata>,
     peer: PeerNetworkId,
 ) -> Option<f64> {
     if let Some(peer_metadata) = get_metadata_for_peer(peers_and_metadata, peer) {
-        let peer_monitoring_metadata = peer_metadata.get_peer_monitoring_metadata();
-        if let Some(latency) = peer_monitoring_metadata.average_ping_latency_secs {
+        if let Some(latency) = peer_metadata.get_effective_latency_secs() {
             return Some(latency); // The latency was found
         }
     }
@@ -239,7 +239,7 @@ fn get_distance_and_latency_for_peer(
             .latest_network_info_response
             .as_ref()
             .map(|response| response.distance_from_validators);
-        let latency = peer_monitoring_metadata.average_ping_latency_secs;
+        let latency = peer_metadata.get_effective_latency_secs();
 
         // Return the distance and latency if both were found
         if let (Some(distance), Some(latency)) = (distance, latency) {
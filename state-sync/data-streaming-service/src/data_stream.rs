@@ -80,6 +80,13 @@ pub struct DataStream<T> {
     // The engine for this data stream
     stream_engine: StreamEngine,
 
+    // The priority weight of this data stream (relative to other streams)
+    priority_weight: u64,
+
+    // The share of the in-flight request budget (across all streams)
+    // currently allotted to this data stream by the streaming service.
+    max_in_flight_requests_allotment: u64,
+
     // The stream update notifier (to notify the streaming service that
     // the stream has been updated, e.g., data is now ready to be processed).
     stream_update_notifier: aptos_channel::Sender<(), StreamUpdateNotification>,
@@ -156,6 +163,8 @@ impl<T: AptosDataClientInterface + Send + Clone + 'static> DataStream<T> {
             data_stream_id,
             aptos_data_client,
             stream_engine,
+            priority_weight: stream_request.get_priority_weight(),
+            max_in_flight_requests_allotment: data_stream_config.max_total_concurrent_requests,
             stream_update_notifier,
             sent_data_requests: None,
             spawned_tasks: vec![],
@@ -184,6 +193,21 @@ impl<T: AptosDataClientInterface + Send + Clone + 'static> DataStream<T> {
         self.abort_spawned_tasks();
     }
 
+    /// Returns the priority weight of the data stream
+    pub fn get_priority_weight(&self) -> u64 {
+        self.priority_weight
+    }
+
+    /// Returns the share of the in-flight request budget allotted to the data stream
+    pub fn get_max_in_flight_requests_allotment(&self) -> u64 {
+        self.max_in_flight_requests_allotment
+    }
+
+    /// Sets the share of the in-flight request budget allotted to the data stream
+    pub fn set_max_in_flight_requests_allotment(&mut self, max_in_flight_requests: u64) {
+        self.max_in_flight_requests_allotment = max_in_flight_requests;
+    }
+
     /// Returns true iff the first batch of data client requests has been sent
     pub fn data_requests_initialized(&self) -> bool {
         self.sent_data_requests.is_some()
@@ -281,9 +305,11 @@ impl<T: AptosDataClientInterface + Send + Clone + 'static> DataStream<T> {
         // Send the client requests iff we have enough room in the queue
         if max_num_requests_to_send > 0 {
             // Get the max number of in-flight requests from the prefetching state
+            // (bounded by the share of the budget allotted to this stream).
             let max_in_flight_requests = self
                 .dynamic_prefetching_state
-                .get_max_concurrent_requests(&self.stream_engine);
+                .get_max_concurrent_requests(&self.stream_engine)
+                .min(self.max_in_flight_requests_allotment);
 
             // Create the client requests
             let client_requests = self.stream_engine.create_data_client_requests(
@@ -201,6 +201,25 @@ impl StreamRequest {
             Self::TerminateStream(_) => "terminate_stream",
         }
     }
+
+    /// Returns the priority weight of the stream request. When multiple streams
+    /// are active, the in-flight request budget is shared between them in
+    /// proportion to their weights.
+    pub fn get_priority_weight(&self) -> u64 {
+        match self {
+            // Epoch ending ledger infos are required to verify all other data
+            Self::GetAllEpochEndingLedgerInfos(_) => 4,
+            // Continuous streams keep the node up-to-date with the network
+            Self::ContinuouslyStreamTransactions(_)
+            | Self::ContinuouslyStreamTransactionOutputs(_)
+            | Self::ContinuouslyStreamTransactionsOrOutputs(_) => 2,
+            Self::GetAllStates(_)
+            | Self::GetAllTransactions(_)
+            | Self::GetAllTransactionOutputs(_)
+            | Self::GetAllTransactionsOrOutputs(_) => 1,
+            Self::TerminateStream(_) => 0,
+        }
+    }
 }
 
 /// A client request for fetching all available epoch ending ledger infos.
@@ -12,7 +12,7 @@ use crate::{
     },
 };
 use aptos_channels::{aptos_channel, message_queues::QueueStyle};
-use aptos_config::config::{AptosDataClientConfig, DataStreamingServiceConfig};
+use aptos_config::config::{reloadable_config, AptosDataClientConfig, DataStreamingServiceConfig};
 use aptos_data_client::{
     global_summary::{GlobalDataSummary, OptimalChunkSizes},
     interface::AptosDataClientInterface,
@@ -269,12 +269,19 @@ impl<T: AptosDataClientInterface + Send + Clone + 'static> DataStreamingService<
             self.global_data_summary.clone(),
         );
 
+        // Apply any reloaded config values to the new stream
+        let mut streaming_service_config = self.streaming_service_config;
+        if let Some(reloadable_config) = reloadable_config::get() {
+            streaming_service_config.max_concurrent_requests =
+                reloadable_config.state_sync_max_concurrent_requests;
+        }
+
         // Create a new data stream
         let stream_id = self.stream_id_generator.next();
         let advertised_data = self.get_global_data_summary().advertised_data.clone();
         let (data_stream, stream_listener) = DataStream::new(
             self.data_client_config,
-            self.streaming_service_config,
+            streaming_service_config,
             stream_id,
             &request_message.stream_request,
             stream_update_notifier,
@@ -308,6 +315,9 @@ impl<T: AptosDataClientInterface + Send + Clone + 'static> DataStreamingService<
 
     /// Ensures that all existing data streams are making progress
     async fn check_progress_of_all_data_streams(&mut self) {
+        // Share the in-flight request budget between the streams
+        self.allot_in_flight_requests();
+
         // Drive the progress of each stream
         let data_stream_ids = self.get_all_data_stream_ids();
         for data_stream_id in &data_stream_ids {
@@ -384,6 +394,28 @@ impl<T: AptosDataClientInterface + Send + Clone + 'static> DataStreamingService<
         Ok(())
     }
 
+    /// Divides the in-flight request budget between all data streams, in
+    /// proportion to their priority weights. Every stream is allotted at
+    /// least one request, to ensure that all streams make progress.
+    fn allot_in_flight_requests(&mut self) {
+        let max_total_concurrent_requests =
+            self.streaming_service_config.max_total_concurrent_requests;
+        let total_priority_weight: u64 = self
+            .data_streams
+            .values()
+            .map(|data_stream| data_stream.get_priority_weight())
+            .sum();
+
+        for data_stream in self.data_streams.values_mut() {
+            let max_in_flight_requests = (max_total_concurrent_requests
+                * data_stream.get_priority_weight())
+            .checked_div(total_priority_weight)
+            .unwrap_or(max_total_concurrent_requests)
+            .max(1);
+            data_stream.set_max_in_flight_requests_allotment(max_in_flight_requests);
+        }
+    }
+
     fn get_all_data_stream_ids(&self) -> Vec<DataStreamId> {
         self.data_streams
             .keys()
@@ -499,16 +531,17 @@ mod streaming_service_tests {
         data_stream::{DataStreamId, DataStreamListener},
         error::Error,
         streaming_client::{
-            DataStreamingClient, GetAllStatesRequest, NotificationAndFeedback,
-            NotificationFeedback, StreamRequest, StreamRequestMessage, TerminateStreamRequest,
+            DataStreamingClient, GetAllEpochEndingLedgerInfosRequest, GetAllStatesRequest,
+            NotificationAndFeedback, NotificationFeedback, StreamRequest, StreamRequestMessage,
+            TerminateStreamRequest,
         },
-        streaming_service::StreamUpdateNotification,
+        streaming_service::{DataStreamingService, StreamUpdateNotification},
         tests,
         tests::{
             streaming_service,
             utils::{
-                get_data_notification, MIN_ADVERTISED_EPOCH_END, MIN_ADVERTISED_STATES,
-                MIN_ADVERTISED_TRANSACTION_OUTPUT,
+                get_data_notification, MockAptosDataClient, MIN_ADVERTISED_EPOCH_END,
+                MIN_ADVERTISED_STATES, MIN_ADVERTISED_TRANSACTION_OUTPUT,
             },
         },
     };
@@ -594,6 +627,71 @@ mod streaming_service_tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_allot_in_flight_requests() {
+        // Create a streaming service with a small in-flight request budget
+        let streaming_service_config = DataStreamingServiceConfig {
+            max_total_concurrent_requests: 10,
+            ..Default::default()
+        };
+        let (_, mut streaming_service) =
+            tests::streaming_service::create_streaming_client_and_server(
+                Some(streaming_service_config),
+                false,
+                false,
+                true,
+                false,
+            );
+
+        // Create a state value stream and verify it is allotted the entire budget
+        let (new_stream_request, response_receiver) = create_new_stream_request();
+        streaming_service
+            .handle_stream_request_message(new_stream_request, create_stream_update_notifier());
+        let state_stream_listener = response_receiver.now_or_never().unwrap().unwrap().unwrap();
+        streaming_service.allot_in_flight_requests();
+        verify_max_in_flight_requests_allotment(
+            &mut streaming_service,
+            state_stream_listener.data_stream_id,
+            10,
+        );
+
+        // Create an epoch ending stream (with a higher priority weight)
+        let (new_stream_request, response_receiver) = create_request_message_and_receiver(
+            StreamRequest::GetAllEpochEndingLedgerInfos(GetAllEpochEndingLedgerInfosRequest {
+                start_epoch: MIN_ADVERTISED_EPOCH_END,
+            }),
+        );
+        streaming_service
+            .handle_stream_request_message(new_stream_request, create_stream_update_notifier());
+        let epoch_stream_listener = response_receiver.now_or_never().unwrap().unwrap().unwrap();
+
+        // Verify the budget is shared based on the priority weights
+        streaming_service.allot_in_flight_requests();
+        verify_max_in_flight_requests_allotment(
+            &mut streaming_service,
+            state_stream_listener.data_stream_id,
+            2,
+        );
+        verify_max_in_flight_requests_allotment(
+            &mut streaming_service,
+            epoch_stream_listener.data_stream_id,
+            8,
+        );
+
+        // Create many more state value streams and verify they're all allotted a request
+        for _ in 0..20 {
+            let (new_stream_request, _) = create_new_stream_request();
+            streaming_service
+                .handle_stream_request_message(new_stream_request, create_stream_update_notifier());
+        }
+        streaming_service.allot_in_flight_requests();
+        verify_max_in_flight_requests_allotment(
+            &mut streaming_service,
+            state_stream_listener.data_stream_id,
+            1,
+        );
+    }
+
     #[tokio::test(flavor = "multi_thread")]
     async fn test_drop_data_streams() {
         for enable_subscription_stream in [false, true] {
@@ -801,6 +899,19 @@ mod streaming_service_tests {
         }
     }
 
+    /// Verifies the share of the in-flight request budget allotted to the given stream
+    fn verify_max_in_flight_requests_allotment(
+        streaming_service: &mut DataStreamingService<MockAptosDataClient>,
+        data_stream_id: DataStreamId,
+        expected_allotment: u64,
+    ) {
+        let data_stream = streaming_service.get_data_stream(&data_stream_id).unwrap();
+        assert_eq!(
+            data_stream.get_max_in_flight_requests_allotment(),
+            expected_allotment
+        );
+    }
+
     /// Creates a new stream request message for state values
     fn create_new_stream_request() -> (
         StreamRequestMessage,
@@ -3,27 +3,26 @@
 // SPDX-License-Identifier: Apache-2.0
 
 #![forbid(unsafe_code)]
-use anyhow::{anyhow, Result};
+use anyhow::Result;
 use aptos_channels::{aptos_channel, message_queues::QueueStyle};
 use aptos_id_generator::{IdGenerator, U64IdGenerator};
 use aptos_infallible::RwLock;
 use aptos_storage_interface::{
-    state_store::state_view::db_state_view::DbStateViewAtVersion, DbReader, DbReaderWriter,
+    state_store::state_view::db_state_view::DbStateViewAtVersion, DbReaderWriter,
 };
 use aptos_types::{
     contract_event::ContractEvent,
     event::EventKey,
     on_chain_config::{
         ConfigurationResource, OnChainConfig, OnChainConfigPayload, OnChainConfigProvider,
+        OnChainConfigRegistry,
     },
-    state_store::state_key::StateKey,
     transaction::Version,
 };
 use futures::{channel::mpsc::SendError, stream::FusedStream, Stream};
 use serde::{Deserialize, Serialize};
 use std::{
     collections::{HashMap, HashSet},
-    fmt,
     iter::FromIterator,
     pin::Pin,
     sync::Arc,
@@ -31,6 +30,9 @@ use std::{
 };
 use thiserror::Error;
 
+/// The provider of the configs sent to reconfiguration subscribers.
+pub use aptos_types::on_chain_config::InMemoryOnChainConfig;
+
 #[cfg(test)]
 mod tests;
 
@@ -85,6 +87,9 @@ pub struct EventSubscriptionService {
     // Database to fetch on-chain configuration data
     storage: Arc<RwLock<DbReaderWriter>>,
 
+    // The on-chain configs sent to reconfiguration subscribers
+    config_registry: OnChainConfigRegistry,
+
     // Internal subscription ID generator
     subscription_id_generator: U64IdGenerator,
 }
@@ -97,6 +102,7 @@ impl EventSubscriptionService {
             subscription_id_to_event_subscription: HashMap::new(),
             reconfig_subscriptions: HashMap::new(),
             storage,
+            config_registry: OnChainConfigRegistry::with_default_configs(),
             subscription_id_generator: U64IdGenerator::new(),
         }
     }
@@ -171,7 +177,7 @@ impl EventSubscriptionService {
     /// subscriber to ensure notifications are processed in a timely manner.
     pub fn subscribe_to_reconfigurations(
         &mut self,
-    ) -> Result<ReconfigNotificationListener<DbBackedOnChainConfig>, Error> {
+    ) -> Result<ReconfigNotificationListener<InMemoryOnChainConfig>, Error> {
         let (notification_sender, notification_receiver) =
             aptos_channel::new(QueueStyle::KLAST, RECONFIG_NOTIFICATION_CHANNEL_SIZE, None);
 
@@ -275,14 +281,14 @@ impl EventSubscriptionService {
         Ok(())
     }
 
-    /// Fetches the configs on-chain at the specified version.
-    /// Note: We cannot assume that all configs will exist on-chain. As such, we
-    /// must fetch each resource one at a time. Reconfig subscribers must be able
-    /// to handle on-chain configs not existing in a reconfiguration notification.
+    /// Fetches the registered configs on-chain at the specified version.
+    /// Note: We cannot assume that all configs will exist on-chain. Reconfig
+    /// subscribers must be able to handle on-chain configs not existing in a
+    /// reconfiguration notification.
     fn read_on_chain_configs(
         &self,
         version: Version,
-    ) -> Result<OnChainConfigPayload<DbBackedOnChainConfig>, Error> {
+    ) -> Result<OnChainConfigPayload<InMemoryOnChainConfig>, Error> {
         let db_state_view = &self
             .storage
             .read()
@@ -303,7 +309,7 @@ impl EventSubscriptionService {
         // Return the new on-chain config payload (containing all found configs at this version).
         Ok(OnChainConfigPayload::new(
             epoch,
-            DbBackedOnChainConfig::new(self.storage.read().reader.clone(), version),
+            self.config_registry.read_configs(db_state_view),
         ))
     }
 }
@@ -363,14 +369,14 @@ impl EventSubscription {
 /// corresponding notifications.
 struct ReconfigSubscription {
     pub notification_sender:
-        aptos_channels::aptos_channel::Sender<(), ReconfigNotification<DbBackedOnChainConfig>>,
+        aptos_channels::aptos_channel::Sender<(), ReconfigNotification<InMemoryOnChainConfig>>,
 }
 
 impl ReconfigSubscription {
     fn notify_subscriber_of_configs(
         &mut self,
         version: Version,
-        on_chain_configs: OnChainConfigPayload<DbBackedOnChainConfig>,
+        on_chain_configs: OnChainConfigPayload<InMemoryOnChainConfig>,
     ) -> Result<(), Error> {
         let reconfig_notification = ReconfigNotification {
             version,
@@ -383,42 +389,6 @@ impl ReconfigSubscription {
     }
 }
 
-#[derive(Clone)]
-pub struct DbBackedOnChainConfig {
-    pub reader: Arc<dyn DbReader>,
-    pub version: Version,
-}
-
-impl DbBackedOnChainConfig {
-    pub fn new(reader: Arc<dyn DbReader>, version: Version) -> Self {
-        Self { reader, version }
-    }
-}
-
-impl OnChainConfigProvider for DbBackedOnChainConfig {
-    fn get<T: OnChainConfig>(&self) -> Result<T> {
-        let bytes = self
-            .reader
-            .get_state_value_by_version(&StateKey::on_chain_config::<T>()?, self.version)?
-            .ok_or_else(|| {
-                anyhow!(
-                    "no config {} found in aptos root account state",
-                    T::CONFIG_ID
-                )
-            })?
-            .bytes()
-            .clone();
-
-        T::deserialize_into_config(&bytes)
-    }
-}
-
-impl fmt::Debug for DbBackedOnChainConfig {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "DbBackedOnChainConfig {{ version: {:?} }}", self.version)
-    }
-}
-
 /// A notification for events.
 #[derive(Debug)]
 pub struct EventNotification {
@@ -5,8 +5,8 @@
 #![forbid(unsafe_code)]
 
 use crate::{
-    DbBackedOnChainConfig, Error, EventNotificationListener, EventNotificationSender,
-    EventSubscriptionService, ReconfigNotificationListener,
+    Error, EventNotificationListener, EventNotificationSender, EventSubscriptionService,
+    InMemoryOnChainConfig, ReconfigNotificationListener,
 };
 use aptos_db::AptosDB;
 use aptos_executor_test_helpers::bootstrap_genesis;
@@ -453,7 +453,7 @@ fn count_event_notifications_and_ensure_ordering(listener: &mut EventNotificatio
 
 // Counts the number of reconfig notifications received by the listener.
 fn count_reconfig_notifications(
-    listener: &mut ReconfigNotificationListener<DbBackedOnChainConfig>,
+    listener: &mut ReconfigNotificationListener<InMemoryOnChainConfig>,
 ) -> u64 {
     let mut notification_received = true;
     let mut notification_count = 0;
@@ -478,7 +478,7 @@ fn verify_no_event_notifications(listeners: Vec<&mut EventNotificationListener>)
 
 // Ensures that no reconfig notifications have been received by the listeners
 fn verify_no_reconfig_notifications(
-    listeners: Vec<&mut ReconfigNotificationListener<DbBackedOnChainConfig>>,
+    listeners: Vec<&mut ReconfigNotificationListener<InMemoryOnChainConfig>>,
 ) {
     for listener in listeners {
         assert!(listener.select_next_some().now_or_never().is_none());
@@ -504,7 +504,7 @@ fn verify_event_notification_received(
 // Ensures that the specified listeners have received the expected notifications.
 // Also verifies that the reconfiguration notifications contain all on-chain configs.
 fn verify_reconfig_notifications_received(
-    listeners: Vec<&mut ReconfigNotificationListener<DbBackedOnChainConfig>>,
+    listeners: Vec<&mut ReconfigNotificationListener<InMemoryOnChainConfig>>,
     expected_version: Version,
     expected_epoch: u64,
 ) {
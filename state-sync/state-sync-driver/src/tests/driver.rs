@@ -17,7 +17,7 @@ use aptos_data_client::client::AptosDataClient;
 use aptos_data_streaming_service::streaming_client::new_streaming_service_client_listener_pair;
 use aptos_db::AptosDB;
 use aptos_event_notifications::{
-    DbBackedOnChainConfig, EventNotificationListener, EventSubscriptionService,
+    EventNotificationListener, EventSubscriptionService, InMemoryOnChainConfig,
     ReconfigNotificationListener,
 };
 use aptos_executor::chunk_executor::ChunkExecutor;
@@ -280,7 +280,7 @@ async fn create_validator_driver(
     UnboundedSender<CommitNotification>,
     ConsensusNotifier,
     MempoolNotificationListener,
-    ReconfigNotificationListener<DbBackedOnChainConfig>,
+    ReconfigNotificationListener<InMemoryOnChainConfig>,
     EventNotificationListener,
     StorageServiceNotificationListener,
     TimeService,
@@ -303,7 +303,7 @@ async fn create_full_node_driver(
     UnboundedSender<CommitNotification>,
     ConsensusNotifier,
     MempoolNotificationListener,
-    ReconfigNotificationListener<DbBackedOnChainConfig>,
+    ReconfigNotificationListener<InMemoryOnChainConfig>,
     EventNotificationListener,
     StorageServiceNotificationListener,
     TimeService,
@@ -324,7 +324,7 @@ async fn create_driver_for_tests(
     UnboundedSender<CommitNotification>,
     ConsensusNotifier,
     MempoolNotificationListener,
-    ReconfigNotificationListener<DbBackedOnChainConfig>,
+    ReconfigNotificationListener<InMemoryOnChainConfig>,
     EventNotificationListener,
     StorageServiceNotificationListener,
     TimeService,
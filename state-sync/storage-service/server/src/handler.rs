@@ -30,7 +30,7 @@ use aptos_storage_service_types::{
     },
     StorageServiceError,
 };
-use aptos_time_service::TimeService;
+use aptos_time_service::{TimeService, TimeServiceTrait};
 use aptos_types::transaction::Version;
 use arc_swap::ArcSwap;
 use dashmap::{mapref::entry::Entry, DashMap};
@@ -147,8 +147,21 @@ impl<T: StorageReaderInterface> Handler<T> {
     ) -> aptos_storage_service_types::Result<StorageServiceResponse> {
         // Process the request and time the operation
         let process_request = || {
-            // Process the request and handle any errors
-            match self.validate_and_handle_request(peer_network_id, &request) {
+            // Process the request and account its cost against the peer's quotas
+            let start_time = self.time_service.now();
+            let process_result = self.validate_and_handle_request(peer_network_id, &request);
+            let bytes_served = match &process_result {
+                Ok(response) => bcs::serialized_size(response).unwrap_or(0) as u64,
+                Err(_) => 0,
+            };
+            self.request_moderator.record_request_cost(
+                peer_network_id,
+                bytes_served,
+                self.time_service.now().duration_since(start_time),
+            );
+
+            // Handle any errors
+            match process_result {
                 Err(error) => {
                     // Update the error counter
                     increment_counter(
@@ -195,6 +208,7 @@ impl<T: StorageReaderInterface> Handler<T> {
         // Transform the request error into a storage service error (for the client)
         process_result.map_err(|error| match error {
             Error::InvalidRequest(error) => StorageServiceError::InvalidRequest(error),
+            Error::RequestQuotaExceeded(error) => StorageServiceError::RequestQuotaExceeded(error),
             Error::TooManyInvalidRequests(error) => {
                 StorageServiceError::TooManyInvalidRequests(error)
             },
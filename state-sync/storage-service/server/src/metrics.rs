@@ -130,6 +130,16 @@ pub static STORAGE_RESPONSES_SENT: Lazy<IntCounterVec> = Lazy::new(|| {
     .unwrap()
 });
 
+/// Counter for the bytes served to peers (used for request quotas)
+pub static STORAGE_BYTES_SERVED: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "aptos_storage_service_server_bytes_served",
+        "Counters for the bytes served to peers by the storage server",
+        &["network_id"]
+    )
+    .unwrap()
+});
+
 /// Time it takes to read data from the storage service DB
 pub static STORAGE_DB_READ_LATENCY: Lazy<HistogramVec> = Lazy::new(|| {
     register_histogram_vec!(
@@ -213,6 +223,16 @@ pub static SUBSCRIPTION_LATENCIES: Lazy<HistogramVec> = Lazy::new(|| {
     .unwrap()
 });
 
+/// Gauge for tracking the number of peers throttled for exceeding their request quotas
+pub static THROTTLED_PEER_COUNT: Lazy<IntGaugeVec> = Lazy::new(|| {
+    register_int_gauge_vec!(
+        "aptos_storage_service_server_throttled_peer_count",
+        "Gauge for tracking the number of peers throttled for exceeding their request quotas",
+        &["network_id"]
+    )
+    .unwrap()
+});
+
 /// Increments the network frame overflow counter for the given response
 pub fn increment_network_frame_overflow(response_type: &str) {
     NETWORK_FRAME_OVERFLOW
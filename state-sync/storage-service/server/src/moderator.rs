@@ -98,13 +98,72 @@ impl UnhealthyPeerState {
     }
 }
 
+/// A simple struct that tracks the cost of the requests served to a peer
+/// (i.e., bytes served and processing time) in the current quota window.
+#[derive(Clone, Debug)]
+pub struct PeerRequestUsage {
+    bytes_served: u64,          // The bytes served to the peer in the current window
+    processing_time: Duration,  // The time spent processing requests in the current window
+    window_start_time: Instant, // The time when the current window started
+}
+
+impl PeerRequestUsage {
+    pub fn new(window_start_time: Instant) -> Self {
+        Self {
+            bytes_served: 0,
+            processing_time: Duration::ZERO,
+            window_start_time,
+        }
+    }
+
+    /// Records the cost of a request served to the peer
+    pub fn record_request_cost(&mut self, bytes_served: u64, processing_time: Duration) {
+        self.bytes_served = self.bytes_served.saturating_add(bytes_served);
+        self.processing_time = self.processing_time.saturating_add(processing_time);
+    }
+
+    /// Starts a new quota window (if the current one has elapsed)
+    pub fn refresh_window(&mut self, now: Instant, quota_window: Duration) {
+        if now.duration_since(self.window_start_time) >= quota_window {
+            self.bytes_served = 0;
+            self.processing_time = Duration::ZERO;
+            self.window_start_time = now;
+        }
+    }
+
+    /// Returns a description of the exceeded quota (if the peer is over quota)
+    pub fn get_exceeded_quota(&self, config: &StorageServiceConfig) -> Option<String> {
+        let max_bytes_served = config.max_bytes_served_per_peer_quota;
+        if max_bytes_served > 0 && self.bytes_served >= max_bytes_served {
+            return Some(format!(
+                "Bytes served: {}, max bytes per window: {}",
+                self.bytes_served, max_bytes_served
+            ));
+        }
+
+        let max_processing_time =
+            Duration::from_millis(config.max_processing_time_ms_per_peer_quota);
+        if !max_processing_time.is_zero() && self.processing_time >= max_processing_time {
+            return Some(format!(
+                "Processing time: {:?}, max processing time per window: {:?}",
+                self.processing_time, max_processing_time
+            ));
+        }
+
+        None
+    }
+}
+
 /// The request moderator is responsible for validating inbound storage
 /// requests and ensuring that only valid (and satisfiable) requests are processed.
 /// If a peer sends too many invalid requests, the moderator will mark the peer as
-/// "unhealthy" and will ignore requests from that peer for some time.
+/// "unhealthy" and will ignore requests from that peer for some time. The moderator
+/// also accounts the cost of the requests served to each public peer, and throttles
+/// peers that exceed their quotas until the current quota window elapses.
 pub struct RequestModerator {
     aptos_data_client_config: AptosDataClientConfig,
     cached_storage_server_summary: Arc<ArcSwap<StorageServerSummary>>,
+    peer_request_usages: Arc<DashMap<PeerNetworkId, PeerRequestUsage>>,
     peers_and_metadata: Arc<PeersAndMetadata>,
     storage_service_config: StorageServiceConfig,
     time_service: TimeService,
@@ -123,6 +182,7 @@ impl RequestModerator {
             aptos_data_client_config,
             cached_storage_server_summary,
             unhealthy_peer_states: Arc::new(DashMap::new()),
+            peer_request_usages: Arc::new(DashMap::new()),
             peers_and_metadata,
             storage_service_config,
             time_service,
@@ -148,6 +208,20 @@ impl RequestModerator {
                 }
             }
 
+            // If the peer has exceeded its request quota, throttle the request
+            if let Some(mut peer_request_usage) = self.peer_request_usages.get_mut(peer_network_id)
+            {
+                peer_request_usage.refresh_window(self.time_service.now(), self.get_quota_window());
+                if let Some(exceeded_quota) =
+                    peer_request_usage.get_exceeded_quota(&self.storage_service_config)
+                {
+                    return Err(Error::RequestQuotaExceeded(format!(
+                        "Peer is temporarily throttled ({}). Unable to handle request: {:?}",
+                        exceeded_quota, request
+                    )));
+                }
+            }
+
             // Get the latest storage server summary
             let storage_server_summary = self.cached_storage_server_summary.load();
 
@@ -195,6 +269,37 @@ impl RequestModerator {
         )
     }
 
+    /// Records the cost of a request served to the given peer. Note: we only
+    /// account requests from peers on the public network.
+    pub fn record_request_cost(
+        &self,
+        peer_network_id: &PeerNetworkId,
+        bytes_served: u64,
+        processing_time: Duration,
+    ) {
+        if !peer_network_id.network_id().is_public_network() {
+            return;
+        }
+
+        let now = self.time_service.now();
+        let mut peer_request_usage = self
+            .peer_request_usages
+            .entry(*peer_network_id)
+            .or_insert_with(|| PeerRequestUsage::new(now));
+        peer_request_usage.refresh_window(now, self.get_quota_window());
+        peer_request_usage.record_request_cost(bytes_served, processing_time);
+
+        // Update the request cost metrics
+        metrics::STORAGE_BYTES_SERVED
+            .with_label_values(&[peer_network_id.network_id().as_str()])
+            .inc_by(bytes_served);
+    }
+
+    /// Returns the duration of the per-peer quota window
+    fn get_quota_window(&self) -> Duration {
+        Duration::from_secs(self.storage_service_config.peer_quota_window_secs)
+    }
+
     /// Refresh the unhealthy peer states and garbage collect disconnected peers
     pub fn refresh_unhealthy_peer_states(&self) -> Result<(), Error> {
         // Get the currently connected peers
@@ -234,6 +339,35 @@ impl RequestModerator {
             num_ignored_peers,
         );
 
+        // Remove disconnected peers and count the throttled peers
+        let now = self.time_service.now();
+        let quota_window = self.get_quota_window();
+        let mut num_throttled_peers = 0;
+        self.peer_request_usages
+            .retain(|peer_network_id, peer_request_usage| {
+                if connected_peers_and_metadata.contains_key(peer_network_id) {
+                    // Refresh the quota window and check if the peer is throttled
+                    peer_request_usage.refresh_window(now, quota_window);
+                    if peer_request_usage
+                        .get_exceeded_quota(&self.storage_service_config)
+                        .is_some()
+                    {
+                        num_throttled_peers += 1;
+                    }
+
+                    true // The peer is still connected, so we should keep it
+                } else {
+                    false // The peer is no longer connected, so we should remove it
+                }
+            });
+
+        // Update the number of throttled peers
+        metrics::set_gauge(
+            &metrics::THROTTLED_PEER_COUNT,
+            NetworkId::Public.as_str(),
+            num_throttled_peers,
+        );
+
         Ok(())
     }
 
@@ -408,4 +542,59 @@ mod tests {
             max_invalid_requests * 5
         );
     }
+
+    #[test]
+    fn test_peer_request_usage_quotas() {
+        // Create a storage service config with small quotas
+        let storage_service_config = StorageServiceConfig {
+            max_bytes_served_per_peer_quota: 1000,
+            max_processing_time_ms_per_peer_quota: 500,
+            ..Default::default()
+        };
+        let quota_window = Duration::from_secs(10);
+
+        // Serve requests to the peer and verify the byte quota is enforced
+        let time_service = TimeService::mock();
+        let mut peer_request_usage = PeerRequestUsage::new(time_service.now());
+        for _ in 0..9 {
+            peer_request_usage.record_request_cost(100, Duration::from_millis(10));
+        }
+        assert!(peer_request_usage
+            .get_exceeded_quota(&storage_service_config)
+            .is_none());
+        peer_request_usage.record_request_cost(100, Duration::from_millis(10));
+        assert!(peer_request_usage
+            .get_exceeded_quota(&storage_service_config)
+            .is_some());
+
+        // Verify the usage is only reset once the quota window elapses
+        let mock_time = time_service.into_mock();
+        mock_time.advance(quota_window / 2);
+        peer_request_usage.refresh_window(mock_time.now(), quota_window);
+        assert!(peer_request_usage
+            .get_exceeded_quota(&storage_service_config)
+            .is_some());
+        mock_time.advance(quota_window / 2);
+        peer_request_usage.refresh_window(mock_time.now(), quota_window);
+        assert!(peer_request_usage
+            .get_exceeded_quota(&storage_service_config)
+            .is_none());
+
+        // Verify the processing time quota is enforced
+        peer_request_usage.record_request_cost(0, Duration::from_millis(500));
+        assert!(peer_request_usage
+            .get_exceeded_quota(&storage_service_config)
+            .is_some());
+
+        // Verify that zero quotas are unlimited
+        let storage_service_config = StorageServiceConfig {
+            max_bytes_served_per_peer_quota: 0,
+            max_processing_time_ms_per_peer_quota: 0,
+            ..Default::default()
+        };
+        peer_request_usage.record_request_cost(u64::MAX, Duration::from_secs(1000));
+        assert!(peer_request_usage
+            .get_exceeded_quota(&storage_service_config)
+            .is_none());
+    }
 }
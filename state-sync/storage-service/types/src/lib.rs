@@ -35,6 +35,8 @@ pub enum StorageServiceError {
     InvalidRequest(String),
     #[error("Too many invalid requests! Back off required: {0}")]
     TooManyInvalidRequests(String),
+    #[error("Request quota exceeded! Back off required: {0}")]
+    RequestQuotaExceeded(String),
 }
 
 /// A single storage service message sent or received over AptosNet.
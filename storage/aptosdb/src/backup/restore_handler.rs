@@ -6,7 +6,7 @@ use crate::{
     backup::restore_utils,
     ledger_db::LedgerDb,
     schema::db_metadata::{DbMetadataKey, DbMetadataSchema},
-    state_restore::{StateSnapshotRestore, StateSnapshotRestoreMode},
+    state_restore::{KeyFilter, NoopTreeWriter, StateSnapshotRestore, StateSnapshotRestoreMode},
     state_store::StateStore,
     AptosDB,
 };
@@ -55,6 +55,24 @@ impl RestoreHandler {
         )
     }
 
+    /// Like `get_state_restore_receiver`, but only the values of the keys matching `kv_filter` are
+    /// written. The tree is verified against `expected_root_hash` as usual, but not persisted.
+    pub fn get_state_subset_restore_receiver(
+        &self,
+        version: Version,
+        expected_root_hash: HashValue,
+        kv_filter: KeyFilter<StateKey>,
+    ) -> Result<StateSnapshotRestore<StateKey, StateValue>> {
+        Ok(StateSnapshotRestore::new_overwrite(
+            &Arc::new(NoopTreeWriter),
+            &self.state_store,
+            version,
+            expected_root_hash,
+            StateSnapshotRestoreMode::Default,
+        )?
+        .with_kv_filter(kv_filter))
+    }
+
     pub fn reset_state_store(&self) {
         self.state_store.reset();
     }
@@ -164,10 +164,12 @@ pub(crate) fn save_transactions(
         // get the last version and commit to the state kv db
         // commit the state kv before ledger in case of failure happens
         let last_version = first_version + txns.len() as u64 - 1;
-        state_store
-            .state_db
-            .state_kv_db
-            .commit(last_version, None, sharded_kv_schema_batch)?;
+        state_store.state_db.state_kv_db.commit(
+            last_version,
+            None,
+            sharded_kv_schema_batch,
+            None,
+        )?;
 
         ledger_db.write_schemas(ledger_db_batch)?;
     }
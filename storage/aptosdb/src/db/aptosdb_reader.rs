@@ -12,6 +12,8 @@ use crate::{
 };
 use aptos_crypto::HashValue;
 use aptos_storage_interface::{
+    block_execution_stats::BlockExecutionStats,
+    block_info::BlockTimestampSearch,
     db_ensure as ensure, db_other_bail as bail,
     state_store::{
         state::State, state_summary::StateSummary, state_view::hot_state_view::HotStateView,
@@ -48,7 +50,7 @@ use aptos_types::{
     write_set::WriteSet,
 };
 use itertools::Itertools;
-use std::{iter::Iterator, sync::Arc};
+use std::{iter::Iterator, ops::Range, sync::Arc};
 
 impl DbReader for AptosDB {
     fn get_persisted_state(&self) -> Result<(Arc<dyn HotStateView>, State)> {
@@ -749,6 +751,51 @@ impl DbReader for AptosDB {
         })
     }
 
+    fn get_block_by_timestamp(
+        &self,
+        timestamp_usecs: u64,
+        search: BlockTimestampSearch,
+    ) -> Result<(Version, Version, NewBlockEvent)> {
+        gauged_api("get_block_by_timestamp", || {
+            let block_height = if !self.skip_index_and_usage {
+                self.event_store.get_block_height_by_timestamp(
+                    timestamp_usecs,
+                    search,
+                    self.ensure_synced_version()?,
+                )?
+            } else {
+                self.ledger_db
+                    .metadata_db()
+                    .get_block_height_by_timestamp(timestamp_usecs, search)?
+            }
+            .ok_or_else(|| {
+                AptosDbError::NotFound(format!(
+                    "No block found {search} timestamp {timestamp_usecs}"
+                ))
+            })?;
+
+            let block_info = self.get_raw_block_info_by_height(block_height)?;
+            self.to_api_block_info(block_height, block_info)
+        })
+    }
+
+    fn get_block_stats(&self, version: Version) -> Result<Option<BlockExecutionStats>> {
+        gauged_api("get_block_stats", || {
+            self.error_if_ledger_pruned("BlockExecutionStats", version)?;
+
+            let (_block_height, block_info) = self.get_raw_block_info_by_version(version)?;
+            self.ledger_db
+                .metadata_db()
+                .get_block_execution_stats(block_info.first_version())
+        })
+    }
+
+    fn get_scratch_value(&self, key: &[u8]) -> Result<Option<Vec<u8>>> {
+        gauged_api("get_scratch_value", || {
+            self.ledger_db.metadata_db().get_scratch_value(key)
+        })
+    }
+
     fn get_last_version_before_timestamp(
         &self,
         timestamp: u64,
@@ -894,6 +941,32 @@ impl DbReader for AptosDB {
         })
     }
 
+    fn get_usage_history(
+        &self,
+        range: Range<Version>,
+        granularity: u64,
+    ) -> Result<Vec<(Version, StateStorageUsage)>> {
+        gauged_api("get_usage_history", || {
+            ensure!(granularity > 0, "Granularity must be positive.");
+            let num_samples = range.end.saturating_sub(range.start).div_ceil(granularity);
+            error_if_too_many_requested(num_samples, MAX_REQUEST_LIMIT)?;
+
+            let min_readable_version = self.ledger_pruner.get_min_readable_version();
+            let next_version = self.get_synced_version()?.map_or(0, |v| v + 1);
+            let start = range.start.max(min_readable_version);
+            let end = range.end.min(next_version);
+            if start >= end {
+                return Ok(vec![]);
+            }
+            // Keep the samples aligned with the requested range.
+            let start = start + (granularity - (start - range.start) % granularity) % granularity;
+
+            self.ledger_db
+                .metadata_db()
+                .get_usage_history(start..end, granularity)
+        })
+    }
+
     fn get_event_by_version_and_index(
         &self,
         version: Version,
@@ -195,6 +195,7 @@ impl AptosDB {
             state_update_refs: transactions_to_keep.state_update_refs(),
             state_reads: &reads,
             is_reconfig,
+            block_execution_stats: None,
         };
 
         self.save_transactions(chunk, ledger_info_with_sigs, sync_commit)
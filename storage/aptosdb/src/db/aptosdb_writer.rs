@@ -38,7 +38,11 @@ use aptos_types::{
 };
 use itertools::Itertools;
 use rayon::prelude::*;
-use std::{iter::Iterator, time::Instant};
+use std::{
+    iter::Iterator,
+    time::{Duration, Instant},
+};
+use tracing::info_span;
 
 impl DbWriter for AptosDB {
     fn pre_commit_ledger(&self, chunk: ChunkToCommit, sync_commit: bool) -> Result<()> {
@@ -52,6 +56,12 @@ impl DbWriter for AptosDB {
                 .try_lock()
                 .expect("Concurrent committing detected.");
             let _timer = OTHER_TIMERS_SECONDS.timer_with(&["pre_commit_ledger"]);
+            let _span = info_span!(
+                "pre_commit_ledger",
+                first_version = chunk.first_version,
+                num_txns = chunk.len(),
+            )
+            .entered();
 
             chunk
                 .state_summary
@@ -91,6 +101,7 @@ impl DbWriter for AptosDB {
                 .try_lock()
                 .expect("Concurrent committing detected.");
             let _timer = OTHER_TIMERS_SECONDS.timer_with(&["commit_ledger"]);
+            let _span = info_span!("commit_ledger", version = version).entered();
 
             let old_committed_ver = self.get_and_check_commit_range(version)?;
 
@@ -111,6 +122,33 @@ impl DbWriter for AptosDB {
         })
     }
 
+    fn put_scratch_value(&self, key: &[u8], value: &[u8], ttl: Duration) -> Result<()> {
+        gauged_api("put_scratch_value", || {
+            self.ledger_db
+                .metadata_db()
+                .put_scratch_value(key, value, ttl)
+        })
+    }
+
+    fn put_scratch_values(&self, values: &[(Vec<u8>, Vec<u8>, Duration)]) -> Result<()> {
+        gauged_api("put_scratch_values", || {
+            self.ledger_db.metadata_db().put_scratch_values(values)
+        })
+    }
+
+    fn flush_buffered_state(&self) -> Result<()> {
+        gauged_api("flush_buffered_state", || {
+            self.state_store.buffered_state().lock().sync_commit();
+            Ok(())
+        })
+    }
+
+    fn delete_scratch_value(&self, key: &[u8]) -> Result<()> {
+        gauged_api("delete_scratch_value", || {
+            self.ledger_db.metadata_db().delete_scratch_value(key)
+        })
+    }
+
     fn get_state_snapshot_receiver(
         &self,
         version: Version,
@@ -357,6 +395,10 @@ impl AptosDB {
             }
         }
 
+        if let Some(stats) = chunk.block_execution_stats {
+            LedgerMetadataDb::put_block_execution_stats(stats, &mut ledger_metadata_batch)?;
+        }
+
         ledger_metadata_batch
             .put::<DbMetadataSchema>(
                 &DbMetadataKey::LedgerCommitProgress,
@@ -375,7 +417,12 @@ impl AptosDB {
             });
             s.spawn(|_| {
                 self.state_kv_db
-                    .commit(chunk.expect_last_version(), None, sharded_state_kv_batches)
+                    .commit(
+                        chunk.expect_last_version(),
+                        None,
+                        sharded_state_kv_batches,
+                        Some(&chunk.state_update_refs.per_version),
+                    )
                     .unwrap();
             });
         });
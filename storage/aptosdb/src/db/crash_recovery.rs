@@ -0,0 +1,295 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    db::AptosDB,
+    state_store::StateStore,
+    utils::truncation_helper::{
+        find_tree_root_at_or_before, get_max_version_in_state_merkle_db,
+        get_state_kv_commit_progress,
+    },
+};
+use aptos_config::config::{RocksdbConfigs, StorageDirPaths};
+use aptos_db_indexer::db_indexer::InternalIndexerDB;
+use aptos_logger::prelude::*;
+use aptos_storage_interface::{AptosDbError, Result};
+use aptos_types::transaction::Version;
+use serde::Serialize;
+use std::sync::Arc;
+
+/// The data removed by the crash recovery from one of the databases
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct TruncatedDb {
+    /// The name of the database (e.g., "ledger_db")
+    pub db: String,
+    /// The latest version in the database before the truncation
+    pub version_before: Version,
+    /// The latest version in the database after the truncation
+    pub version_after: Version,
+    /// The number of versions removed from the database
+    pub num_versions_removed: u64,
+    /// Why the versions were removed
+    pub reason: String,
+}
+
+/// A report of the data removed by the crash recovery
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct CrashRecoveryReport {
+    /// The last version fully committed across all databases, which the databases were
+    /// truncated to
+    pub consistent_version: Version,
+    pub truncated_dbs: Vec<TruncatedDb>,
+}
+
+impl TruncatedDb {
+    fn new(db: &str, version_before: Version, version_after: Version, reason: String) -> Self {
+        Self {
+            db: db.to_string(),
+            version_before,
+            version_after,
+            num_versions_removed: version_before - version_after,
+            reason,
+        }
+    }
+}
+
+impl AptosDB {
+    /// Detects data that was only partially committed across the ledger and state databases
+    /// (e.g., because the node crashed in the middle of a commit), and truncates every database,
+    /// including the internal indexer DB if given, to the last version that was fully committed.
+    /// Unlike the truncation performed when the DB is opened, up to `max_versions_to_truncate`
+    /// versions can be removed from each database, so that the node can recover without the
+    /// manual db-tool truncation. Fails without touching the databases if more would be removed.
+    ///
+    /// Returns a report of the removed data, or None if the databases were already consistent.
+    pub fn recover_from_crash(
+        db_paths: &StorageDirPaths,
+        rocksdb_configs: RocksdbConfigs,
+        internal_indexer_db: Option<&InternalIndexerDB>,
+        max_versions_to_truncate: u64,
+    ) -> Result<Option<CrashRecoveryReport>> {
+        let (ledger_db, state_merkle_db, state_kv_db) = Self::open_dbs(
+            db_paths,
+            rocksdb_configs,
+            /*readonly=*/ false,
+            /*max_num_nodes_per_lru_cache_shard=*/ 0,
+        )?;
+        let ledger_db = Arc::new(ledger_db);
+        let state_merkle_db = Arc::new(state_merkle_db);
+        let state_kv_db = Arc::new(state_kv_db);
+
+        let ledger_metadata_db = ledger_db.metadata_db();
+        let Some(consistent_version) = ledger_metadata_db.get_synced_version()? else {
+            // Nothing was ever committed
+            return Ok(None);
+        };
+
+        let mut truncated_dbs = vec![];
+        let ledger_commit_progress = ledger_metadata_db.get_ledger_commit_progress()?;
+        if ledger_commit_progress > consistent_version {
+            truncated_dbs.push(TruncatedDb::new(
+                "ledger_db",
+                ledger_commit_progress,
+                consistent_version,
+                format!(
+                    "The ledger commit progress ({}) is ahead of the overall commit progress ({}).",
+                    ledger_commit_progress, consistent_version
+                ),
+            ));
+        }
+
+        let state_kv_commit_progress = get_state_kv_commit_progress(&state_kv_db)?
+            .ok_or_else(|| AptosDbError::NotFound("No StateKvCommitProgress in db.".to_string()))?;
+        if state_kv_commit_progress > consistent_version {
+            truncated_dbs.push(TruncatedDb::new(
+                "state_kv_db",
+                state_kv_commit_progress,
+                consistent_version,
+                format!(
+                    "The state K/V commit progress ({}) is ahead of the overall commit progress \
+                    ({}).",
+                    state_kv_commit_progress, consistent_version
+                ),
+            ));
+        }
+
+        if let Some(state_merkle_max_version) =
+            get_max_version_in_state_merkle_db(&state_merkle_db)?
+        {
+            let state_merkle_target_version = find_tree_root_at_or_before(
+                ledger_metadata_db,
+                &state_merkle_db,
+                consistent_version,
+            )?
+            .ok_or_else(|| {
+                AptosDbError::NotFound(format!(
+                    "No state merkle root at or before version {}, maybe it was pruned?",
+                    consistent_version
+                ))
+            })?;
+            if state_merkle_target_version < state_merkle_max_version {
+                truncated_dbs.push(TruncatedDb::new(
+                    "state_merkle_db",
+                    state_merkle_max_version,
+                    state_merkle_target_version,
+                    format!(
+                        "The state merkle DB has nodes up to version {}, after its latest \
+                        complete root ({}) at or before the overall commit progress ({}).",
+                        state_merkle_max_version, state_merkle_target_version, consistent_version
+                    ),
+                ));
+            }
+        }
+
+        if let Some(internal_indexer_db) = internal_indexer_db {
+            if let Some(indexer_version) = internal_indexer_db.get_persisted_version()? {
+                if indexer_version > consistent_version {
+                    truncated_dbs.push(TruncatedDb::new(
+                        "internal_indexer_db",
+                        indexer_version,
+                        consistent_version,
+                        format!(
+                            "The internal indexer DB has indexed up to version {}, after the \
+                            overall commit progress ({}).",
+                            indexer_version, consistent_version
+                        ),
+                    ));
+                }
+            }
+        }
+
+        if truncated_dbs.is_empty() {
+            return Ok(None);
+        }
+        if let Some(truncated_db) = truncated_dbs
+            .iter()
+            .find(|truncated_db| truncated_db.num_versions_removed > max_versions_to_truncate)
+        {
+            return Err(AptosDbError::Other(format!(
+                "Refusing to remove {} versions from {} (at most {} are allowed), the db-tool \
+                truncation is required: {}",
+                truncated_db.num_versions_removed,
+                truncated_db.db,
+                max_versions_to_truncate,
+                truncated_db.reason
+            )));
+        }
+
+        warn!(
+            consistent_version = consistent_version,
+            truncated_dbs = ?truncated_dbs,
+            "Found partially committed data, truncating the DBs..."
+        );
+        StateStore::sync_commit_progress(
+            ledger_db,
+            state_kv_db,
+            state_merkle_db,
+            /*crash_if_difference_is_too_large=*/ false,
+        );
+        if let Some(internal_indexer_db) = internal_indexer_db {
+            internal_indexer_db.truncate(consistent_version)?;
+        }
+        info!("Crash recovery complete.");
+
+        Ok(Some(CrashRecoveryReport {
+            consistent_version,
+            truncated_dbs,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        db::test_helper::arb_blocks_to_commit_with_block_nums,
+        schema::db_metadata::{DbMetadataKey, DbMetadataSchema, DbMetadataValue},
+    };
+    use aptos_schemadb::batch::SchemaBatch;
+    use aptos_storage_interface::DbReader;
+    use aptos_temppath::TempPath;
+    use proptest::prelude::*;
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(1))]
+
+        #[test]
+        fn test_recover_from_crash(input in arb_blocks_to_commit_with_block_nums(20, 40)) {
+            let tmp_dir = TempPath::new();
+            let db = AptosDB::new_for_test(&tmp_dir);
+            let (blocks, _) = input;
+            let mut version = 0;
+            let mut consistent_version = None;
+            for (i, (txns_to_commit, ledger_info_with_sigs)) in blocks.iter().enumerate() {
+                db.save_transactions_for_test(
+                    txns_to_commit,
+                    version,
+                    Some(ledger_info_with_sigs),
+                    true,
+                )
+                .unwrap();
+                version += txns_to_commit.len() as u64;
+                if i == blocks.len() / 2 {
+                    consistent_version = Some(version - 1);
+                }
+            }
+            let consistent_version = consistent_version.unwrap();
+            let latest_version = db.expect_synced_version();
+            drop(db);
+
+            let db_paths = StorageDirPaths::from_path(tmp_dir.path());
+            let rocksdb_configs = RocksdbConfigs::default();
+
+            // The databases are consistent
+            prop_assert_eq!(
+                AptosDB::recover_from_crash(&db_paths, rocksdb_configs, None, u64::MAX).unwrap(),
+                None
+            );
+
+            // Simulate a crash before the overall commit progress of the latest blocks was written
+            let (ledger_db, _, _) =
+                AptosDB::open_dbs(&db_paths, rocksdb_configs, false, 0).unwrap();
+            let mut batch = SchemaBatch::new();
+            batch.put::<DbMetadataSchema>(
+                &DbMetadataKey::OverallCommitProgress,
+                &DbMetadataValue::Version(consistent_version),
+            )
+            .unwrap();
+            ledger_db.metadata_db().write_schemas(batch).unwrap();
+            drop(ledger_db);
+
+            // Nothing is removed if more versions would have to be removed than allowed
+            prop_assert!(AptosDB::recover_from_crash(&db_paths, rocksdb_configs, None, 0).is_err());
+            let (ledger_db, _, _) =
+                AptosDB::open_dbs(&db_paths, rocksdb_configs, false, 0).unwrap();
+            prop_assert_eq!(
+                ledger_db.metadata_db().get_ledger_commit_progress().unwrap(),
+                latest_version
+            );
+            drop(ledger_db);
+
+            let report = AptosDB::recover_from_crash(&db_paths, rocksdb_configs, None, u64::MAX)
+                .unwrap()
+                .unwrap();
+            prop_assert_eq!(report.consistent_version, consistent_version);
+            let ledger_db_truncation = report
+                .truncated_dbs
+                .iter()
+                .find(|truncated_db| truncated_db.db == "ledger_db")
+                .unwrap();
+            prop_assert_eq!(ledger_db_truncation.version_before, latest_version);
+            prop_assert_eq!(
+                ledger_db_truncation.num_versions_removed,
+                latest_version - consistent_version
+            );
+
+            // The databases are consistent again
+            prop_assert_eq!(
+                AptosDB::recover_from_crash(&db_paths, rocksdb_configs, None, u64::MAX).unwrap(),
+                None
+            );
+            let db = AptosDB::new_for_test(&tmp_dir);
+            prop_assert_eq!(db.expect_synced_version(), consistent_version);
+        }
+    }
+}
@@ -16,6 +16,8 @@ use aptos_infallible::Mutex;
 use aptos_logger::debug;
 use aptos_scratchpad::SparseMerkleTree;
 use aptos_storage_interface::{
+    block_execution_stats::BlockExecutionStats,
+    block_info::BlockTimestampSearch,
     db_ensure as ensure,
     state_store::{
         state_delta::StateDelta, state_update_refs::BatchedStateUpdateRefs,
@@ -59,6 +61,7 @@ use itertools::zip_eq;
 use move_core_types::move_resource::MoveStructType;
 use std::{
     borrow::Borrow,
+    ops::Range,
     sync::{
         atomic::{AtomicU64, Ordering},
         Arc,
@@ -696,6 +699,18 @@ impl DbReader for FakeAptosDB {
         self.inner.get_block_info_by_height(height)
     }
 
+    fn get_block_by_timestamp(
+        &self,
+        timestamp_usecs: u64,
+        search: BlockTimestampSearch,
+    ) -> Result<(Version, Version, NewBlockEvent)> {
+        self.inner.get_block_by_timestamp(timestamp_usecs, search)
+    }
+
+    fn get_block_stats(&self, version: Version) -> Result<Option<BlockExecutionStats>> {
+        self.inner.get_block_stats(version)
+    }
+
     fn get_last_version_before_timestamp(
         &self,
         timestamp: u64,
@@ -964,6 +979,14 @@ impl DbReader for FakeAptosDB {
     fn get_state_storage_usage(&self, version: Option<Version>) -> Result<StateStorageUsage> {
         self.inner.get_state_storage_usage(version)
     }
+
+    fn get_usage_history(
+        &self,
+        range: Range<Version>,
+        granularity: u64,
+    ) -> Result<Vec<(Version, StateStorageUsage)>> {
+        self.inner.get_usage_history(range, granularity)
+    }
 }
 
 /// This is necessary for constructing the [LedgerSummary] to serve [DbReader::get_pre_committed_ledger_summary]
@@ -47,6 +47,8 @@ mod aptosdb_reader;
 mod aptosdb_writer;
 // Other private methods.
 mod aptosdb_internal;
+// Truncation of partially committed data on startup.
+pub mod crash_recovery;
 // Testonly methods.
 #[cfg(any(test, feature = "fuzzing", feature = "consensus-only-perf-test"))]
 mod aptosdb_testonly;
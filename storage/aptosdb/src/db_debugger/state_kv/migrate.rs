@@ -0,0 +1,62 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::state_kv_db::StateKvDb;
+use aptos_config::config::{NodeConfig, StorageConfig, StorageDirPaths};
+use aptos_storage_interface::{AptosDbError, Result};
+use clap::Parser;
+use std::path::{Path, PathBuf};
+
+#[derive(Parser)]
+#[clap(
+    about = "Move the state kv db shards to the paths in the db_path_overrides of a node config, \
+    e.g. to place them on multiple disks. The node must be stopped."
+)]
+pub struct Cmd {
+    /// The default db dir of the node, which holds the shards that are not overridden.
+    #[clap(long, value_parser)]
+    db_dir: PathBuf,
+
+    /// The node config with the current db_path_overrides. If not set, all the shards are
+    /// expected under `db_dir`.
+    #[clap(long, value_parser)]
+    source_config: Option<PathBuf>,
+
+    /// The node config with the db_path_overrides to migrate to.
+    #[clap(long, value_parser)]
+    target_config: PathBuf,
+
+    /// Deletes the source dbs once they are copied over and verified.
+    #[clap(long)]
+    remove_source: bool,
+}
+
+impl Cmd {
+    pub fn run(self) -> Result<()> {
+        let from_paths = Self::dir_paths(&self.db_dir, self.source_config.as_deref())?;
+        let to_paths = Self::dir_paths(&self.db_dir, Some(&self.target_config))?;
+
+        let migrated_shards = StateKvDb::migrate(&from_paths, &to_paths, self.remove_source)?;
+        println!(
+            "Migrated {} state kv db shard(s): {:?}. Start the node with {:?}.",
+            migrated_shards.len(),
+            migrated_shards,
+            self.target_config,
+        );
+        Ok(())
+    }
+
+    fn dir_paths(db_dir: &Path, node_config: Option<&Path>) -> Result<StorageDirPaths> {
+        let mut storage_config = StorageConfig {
+            dir: std::path::absolute(db_dir)?,
+            ..Default::default()
+        };
+        if let Some(node_config) = node_config {
+            storage_config.db_path_overrides = NodeConfig::load_from_path(node_config)
+                .map_err(|e| AptosDbError::Other(format!("Failed to load {node_config:?}: {e}")))?
+                .storage
+                .db_path_overrides;
+        }
+        Ok(storage_config.get_dir_paths())
+    }
+}
@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 mod get_value;
+mod migrate;
 mod scan_snapshot;
 
 use aptos_storage_interface::Result;
@@ -9,6 +10,7 @@ use aptos_storage_interface::Result;
 #[derive(clap::Subcommand)]
 pub enum Cmd {
     GetValue(get_value::Cmd),
+    Migrate(migrate::Cmd),
     ScanSnapshot(scan_snapshot::Cmd),
 }
 
@@ -16,6 +18,7 @@ impl Cmd {
     pub fn run(self) -> Result<()> {
         match self {
             Self::GetValue(cmd) => cmd.run(),
+            Self::Migrate(cmd) => cmd.run(),
             Self::ScanSnapshot(cmd) => cmd.run(),
         }
     }
@@ -1,11 +1,11 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::schema::*;
+use crate::schema::{scratch::ScratchValue, *};
 use aptos_config::config::RocksdbConfig;
 use aptos_schemadb::{
-    BlockBasedOptions, Cache, ColumnFamilyDescriptor, ColumnFamilyName, DBCompressionType, Options,
-    SliceTransform, DEFAULT_COLUMN_FAMILY_NAME,
+    BlockBasedOptions, Cache, ColumnFamilyDescriptor, ColumnFamilyName, CompactionDecision,
+    DBCompressionType, Options, SliceTransform, DEFAULT_COLUMN_FAMILY_NAME,
 };
 use aptos_types::transaction::Version;
 
@@ -14,7 +14,9 @@ const VERSION_SIZE: usize = std::mem::size_of::<Version>();
 pub(super) fn ledger_db_column_families() -> Vec<ColumnFamilyName> {
     vec![
         /* empty cf */ DEFAULT_COLUMN_FAMILY_NAME,
+        BLOCK_BY_TIMESTAMP_CF_NAME,
         BLOCK_BY_VERSION_CF_NAME,
+        BLOCK_EXECUTION_STATS_CF_NAME,
         BLOCK_INFO_CF_NAME,
         EPOCH_BY_VERSION_CF_NAME,
         EVENT_ACCUMULATOR_CF_NAME,
@@ -23,6 +25,7 @@ pub(super) fn ledger_db_column_families() -> Vec<ColumnFamilyName> {
         EVENT_CF_NAME,
         LEDGER_INFO_CF_NAME,
         PERSISTED_AUXILIARY_INFO_CF_NAME,
+        SCRATCH_CF_NAME,
         STALE_STATE_VALUE_INDEX_CF_NAME,
         STATE_VALUE_CF_NAME,
         TRANSACTION_CF_NAME,
@@ -105,11 +108,14 @@ pub(super) fn write_set_db_column_families() -> Vec<ColumnFamilyName> {
 pub(super) fn ledger_metadata_db_column_families() -> Vec<ColumnFamilyName> {
     vec![
         /* empty cf */ DEFAULT_COLUMN_FAMILY_NAME,
+        BLOCK_BY_TIMESTAMP_CF_NAME,
         BLOCK_BY_VERSION_CF_NAME,
+        BLOCK_EXECUTION_STATS_CF_NAME,
         BLOCK_INFO_CF_NAME,
         DB_METADATA_CF_NAME,
         EPOCH_BY_VERSION_CF_NAME,
         LEDGER_INFO_CF_NAME,
+        SCRATCH_CF_NAME,
         VERSION_DATA_CF_NAME,
     ]
 }
@@ -197,6 +203,19 @@ fn with_state_key_extractor_processor(cf_name: ColumnFamilyName, cf_opts: &mut O
     }
 }
 
+/// Drops expired scratch values on compaction, so that they don't take space beyond their TTL.
+fn with_scratch_ttl_processor(cf_name: ColumnFamilyName, cf_opts: &mut Options) {
+    if cf_name == SCRATCH_CF_NAME {
+        cf_opts.set_compaction_filter("scratch_ttl", |_level, _key, value: &[u8]| {
+            if ScratchValue::is_encoded_value_expired(value) {
+                CompactionDecision::Remove
+            } else {
+                CompactionDecision::Keep
+            }
+        });
+    }
+}
+
 fn state_key_extractor(state_value_raw_key: &[u8]) -> &[u8] {
     &state_value_raw_key[..(state_value_raw_key.len() - VERSION_SIZE)]
 }
@@ -261,7 +280,7 @@ pub(super) fn gen_ledger_metadata_cfds(
     block_cache: Option<&Cache>,
 ) -> Vec<ColumnFamilyDescriptor> {
     let cfs = ledger_metadata_db_column_families();
-    gen_cfds(rocksdb_config, block_cache, cfs, |_, _| {})
+    gen_cfds(rocksdb_config, block_cache, cfs, with_scratch_ttl_processor)
 }
 
 pub(super) fn gen_ledger_cfds(
@@ -269,12 +288,10 @@ pub(super) fn gen_ledger_cfds(
     block_cache: Option<&Cache>,
 ) -> Vec<ColumnFamilyDescriptor> {
     let cfs = ledger_db_column_families();
-    gen_cfds(
-        rocksdb_config,
-        block_cache,
-        cfs,
-        with_state_key_extractor_processor,
-    )
+    gen_cfds(rocksdb_config, block_cache, cfs, |cf_name, cf_opts| {
+        with_state_key_extractor_processor(cf_name, cf_opts);
+        with_scratch_ttl_processor(cf_name, cf_opts);
+    })
 }
 
 pub(super) fn gen_state_merkle_cfds(
@@ -15,7 +15,9 @@ use aptos_db_indexer_schemas::schema::{
     event_by_key::EventByKeySchema, event_by_version::EventByVersionSchema,
 };
 use aptos_schemadb::{batch::SchemaBatch, schema::ValueCodec, DB};
-use aptos_storage_interface::{db_ensure as ensure, db_other_bail, AptosDbError, Result};
+use aptos_storage_interface::{
+    block_info::BlockTimestampSearch, db_ensure as ensure, db_other_bail, AptosDbError, Result,
+};
 use aptos_types::{
     account_address::AccountAddress,
     account_config::{new_block_event_key, NewBlockEvent},
@@ -317,6 +319,40 @@ impl EventStore {
         })
     }
 
+    /// Returns the height of the block closest to the timestamp, in the direction given by
+    /// `search`, by binary searching the `NewBlockEvent` stream.
+    pub(crate) fn get_block_height_by_timestamp(
+        &self,
+        timestamp_usecs: u64,
+        search: BlockTimestampSearch,
+        ledger_version: Version,
+    ) -> Result<Option<u64>> {
+        let event_key = new_block_event_key();
+        let proposed_time = |event: &ContractEvent| -> Result<u64> {
+            let new_block_event: NewBlockEvent = event.try_into()?;
+            Ok(new_block_event.proposed_time())
+        };
+
+        // The sequence numbers of the new block events are the block heights.
+        Ok(match search {
+            BlockTimestampSearch::ClosestBefore => {
+                match self.search_for_event_lower_bound(
+                    &event_key,
+                    |event| Ok(proposed_time(event)? <= timestamp_usecs),
+                    ledger_version,
+                )? {
+                    Some(first_after) => first_after.checked_sub(1),
+                    None => self.get_latest_sequence_number(ledger_version, &event_key)?,
+                }
+            },
+            BlockTimestampSearch::ClosestAfter => self.search_for_event_lower_bound(
+                &event_key,
+                |event| Ok(proposed_time(event)? < timestamp_usecs),
+                ledger_version,
+            )?,
+        })
+    }
+
     /// Prunes events by accumulator store for a range of version in [begin, end)
     pub(crate) fn prune_event_accumulator(
         &self,
@@ -16,7 +16,10 @@ use aptos_types::{
     transaction::{TransactionOutputListWithProofV2, Version},
 };
 use either::Either;
-use std::{sync::Arc, time::Instant};
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
 use tokio::sync::watch::Sender;
 pub const SECONDARY_DB_DIR: &str = "fast_sync_secondary";
 
@@ -181,10 +184,31 @@ impl DbWriter for FastSyncStorageWrapper {
         self.get_aptos_db_write_ref()
             .commit_ledger(version, ledger_info_with_sigs, chunk_opt)
     }
+
+    fn flush_buffered_state(&self) -> Result<()> {
+        self.get_aptos_db_write_ref().flush_buffered_state()
+    }
+
+    // The scratch space always lives in the db that is kept after fast sync.
+    fn put_scratch_value(&self, key: &[u8], value: &[u8], ttl: Duration) -> Result<()> {
+        self.db_for_fast_sync.put_scratch_value(key, value, ttl)
+    }
+
+    fn put_scratch_values(&self, values: &[(Vec<u8>, Vec<u8>, Duration)]) -> Result<()> {
+        self.db_for_fast_sync.put_scratch_values(values)
+    }
+
+    fn delete_scratch_value(&self, key: &[u8]) -> Result<()> {
+        self.db_for_fast_sync.delete_scratch_value(key)
+    }
 }
 
 impl DbReader for FastSyncStorageWrapper {
     fn get_read_delegatee(&self) -> &dyn DbReader {
         self.get_aptos_db_read_ref()
     }
+
+    fn get_scratch_value(&self, key: &[u8]) -> Result<Option<Vec<u8>>> {
+        self.db_for_fast_sync.get_scratch_value(key)
+    }
 }
@@ -3,25 +3,37 @@
 
 use crate::{
     schema::{
+        block_by_timestamp::BlockByTimestampSchema,
         block_by_version::BlockByVersionSchema,
+        block_execution_stats::BlockExecutionStatsSchema,
         block_info::BlockInfoSchema,
         db_metadata::{DbMetadataKey, DbMetadataSchema, DbMetadataValue},
         epoch_by_version::EpochByVersionSchema,
         ledger_info::LedgerInfoSchema,
+        scratch::{ScratchSchema, ScratchValue},
         version_data::VersionDataSchema,
     },
     utils::{get_progress, iterators::EpochEndingLedgerInfoIter},
 };
 use anyhow::anyhow;
 use aptos_schemadb::{batch::SchemaBatch, DB};
-use aptos_storage_interface::{block_info::BlockInfo, db_ensure as ensure, AptosDbError, Result};
+use aptos_storage_interface::{
+    block_execution_stats::BlockExecutionStats,
+    block_info::{BlockInfo, BlockTimestampSearch},
+    db_ensure as ensure, AptosDbError, Result,
+};
 use aptos_types::{
     account_config::NewBlockEvent, block_info::BlockHeight, contract_event::ContractEvent,
     epoch_state::EpochState, ledger_info::LedgerInfoWithSignatures,
     state_store::state_storage_usage::StateStorageUsage, transaction::Version,
 };
 use arc_swap::ArcSwap;
-use std::{ops::Deref, path::Path, sync::Arc};
+use std::{
+    ops::{Deref, Range},
+    path::Path,
+    sync::Arc,
+    time::Duration,
+};
 
 fn get_latest_ledger_info_in_db_impl(db: &DB) -> Result<Option<LedgerInfoWithSignatures>> {
     let mut iter = db.iter::<LedgerInfoSchema>()?;
@@ -293,6 +305,28 @@ impl LedgerMetadataDb {
         Ok((block_version, block_height))
     }
 
+    /// Returns the height of the block closest to the timestamp, in the direction given by
+    /// `search`, or None if there is no such block. Blocks are indexed as they are committed or
+    /// restored, so DBs written by older nodes need to be restored to index earlier blocks.
+    pub(crate) fn get_block_height_by_timestamp(
+        &self,
+        timestamp_usecs: u64,
+        search: BlockTimestampSearch,
+    ) -> Result<Option<BlockHeight>> {
+        let mut iter = self.db.iter::<BlockByTimestampSchema>()?;
+        match search {
+            BlockTimestampSearch::ClosestBefore => {
+                iter.seek_for_prev(&(timestamp_usecs, BlockHeight::MAX))?
+            },
+            BlockTimestampSearch::ClosestAfter => iter.seek(&(timestamp_usecs, 0))?,
+        }
+
+        Ok(iter
+            .next()
+            .transpose()?
+            .map(|((_timestamp_usecs, block_height), ())| block_height))
+    }
+
     pub(crate) fn put_block_info(
         version: Version,
         event: &ContractEvent,
@@ -303,9 +337,26 @@ impl LedgerMetadataDb {
         let block_info = BlockInfo::from_new_block_event(version, &new_block_event);
         batch.put::<BlockInfoSchema>(&block_height, &block_info)?;
         batch.put::<BlockByVersionSchema>(&version, &block_height)?;
+        batch.put::<BlockByTimestampSchema>(&(block_info.timestamp_usecs(), block_height), &())?;
 
         Ok(())
     }
+
+    /// Returns the execution stats of the block starting at `first_version`, or None if they
+    /// were not recorded.
+    pub(crate) fn get_block_execution_stats(
+        &self,
+        first_version: Version,
+    ) -> Result<Option<BlockExecutionStats>> {
+        self.db.get::<BlockExecutionStatsSchema>(&first_version)
+    }
+
+    pub(crate) fn put_block_execution_stats(
+        stats: &BlockExecutionStats,
+        batch: &mut SchemaBatch,
+    ) -> Result<()> {
+        batch.put::<BlockExecutionStatsSchema>(&stats.first_version, stats)
+    }
 }
 
 /// Usage APIs.
@@ -339,4 +390,54 @@ impl LedgerMetadataDb {
             )),
         }
     }
+
+    /// Returns the usage at or before every `granularity` versions in `range`, skipping
+    /// duplicates and samples without any usage before them.
+    pub(crate) fn get_usage_history(
+        &self,
+        range: Range<Version>,
+        granularity: u64,
+    ) -> Result<Vec<(Version, StateStorageUsage)>> {
+        let mut iter = self.db.iter::<VersionDataSchema>()?;
+        let mut history: Vec<(Version, StateStorageUsage)> = Vec::new();
+        for sample in range.step_by(granularity as usize) {
+            iter.seek_for_prev(&sample)?;
+            if let Some((version, data)) = iter.next().transpose()? {
+                if history.last().map_or(true, |(last, _)| *last != version) {
+                    history.push((version, data.get_state_storage_usage()));
+                }
+            }
+        }
+        Ok(history)
+    }
+
+    /// Returns the scratch value under `key`, unless it has expired.
+    pub(crate) fn get_scratch_value(&self, key: &[u8]) -> Result<Option<Vec<u8>>> {
+        Ok(self
+            .db
+            .get::<ScratchSchema>(&key.to_vec())?
+            .filter(|value| !value.is_expired())
+            .map(|value| value.bytes))
+    }
+
+    /// Puts a scratch value that expires after `ttl`. Scratch values bypass the WAL, so they can
+    /// be lost on a crash.
+    pub(crate) fn put_scratch_value(&self, key: &[u8], value: &[u8], ttl: Duration) -> Result<()> {
+        self.put_scratch_values(&[(key.to_vec(), value.to_vec(), ttl)])
+    }
+
+    /// Puts several scratch values in a single write, see `put_scratch_value`.
+    pub(crate) fn put_scratch_values(&self, values: &[(Vec<u8>, Vec<u8>, Duration)]) -> Result<()> {
+        let mut batch = SchemaBatch::new();
+        for (key, value, ttl) in values {
+            batch.put::<ScratchSchema>(key, &ScratchValue::new(value.clone(), *ttl))?;
+        }
+        self.db.write_schemas_ephemeral(batch)
+    }
+
+    pub(crate) fn delete_scratch_value(&self, key: &[u8]) -> Result<()> {
+        let mut batch = SchemaBatch::new();
+        batch.delete::<ScratchSchema>(&key.to_vec())?;
+        self.db.write_schemas_ephemeral(batch)
+    }
 }
@@ -3,7 +3,7 @@
 
 use crate::{ledger_db::ledger_metadata_db::LedgerMetadataDb, AptosDB};
 use aptos_schemadb::batch::SchemaBatch;
-use aptos_storage_interface::AptosDbError;
+use aptos_storage_interface::{block_info::BlockTimestampSearch, AptosDbError};
 use aptos_temppath::TempPath;
 use aptos_types::{
     account_address::AccountAddress,
@@ -20,7 +20,7 @@ use proptest::{
     collection::vec,
     prelude::{Strategy, *},
 };
-use std::path::Path;
+use std::{path::Path, time::Duration};
 
 fn arb_ledger_infos_with_sigs() -> impl Strategy<Value = Vec<LedgerInfoWithSignatures>> {
     (
@@ -319,6 +319,27 @@ fn test_block_api() {
             2
         );
     }
+
+    for (timestamp_usecs, before, after) in [
+        (999, None, Some(1)),
+        (1000, Some(1), Some(1)),
+        (1500, Some(1), Some(2)),
+        (2000, Some(2), Some(2)),
+        (2001, Some(2), None),
+    ] {
+        assert_eq!(
+            ledger_metadata_db
+                .get_block_height_by_timestamp(timestamp_usecs, BlockTimestampSearch::ClosestBefore)
+                .unwrap(),
+            before
+        );
+        assert_eq!(
+            ledger_metadata_db
+                .get_block_height_by_timestamp(timestamp_usecs, BlockTimestampSearch::ClosestAfter)
+                .unwrap(),
+            after
+        );
+    }
 }
 
 #[test]
@@ -332,3 +353,73 @@ fn test_usage() {
     assert_eq!(ledger_metadata_db.get_usage(1).unwrap(), usage);
     assert!(ledger_metadata_db.get_usage(0).is_err());
 }
+
+#[test]
+fn test_usage_history() {
+    let tmp_dir = TempPath::new();
+    let db = AptosDB::new_for_test(&tmp_dir);
+    let ledger_metadata_db = db.ledger_db.metadata_db();
+
+    for (version, items) in [(10, 1), (20, 2), (25, 3), (40, 4)] {
+        ledger_metadata_db
+            .put_usage(version, StateStorageUsage::new(items, items * 100))
+            .unwrap();
+    }
+
+    // Nothing before the first usage.
+    assert!(ledger_metadata_db
+        .get_usage_history(0..10, 5)
+        .unwrap()
+        .is_empty());
+    assert_eq!(
+        ledger_metadata_db.get_usage_history(0..50, 10).unwrap(),
+        vec![
+            (10, StateStorageUsage::new(1, 100)),
+            (20, StateStorageUsage::new(2, 200)),
+            (25, StateStorageUsage::new(3, 300)),
+            (40, StateStorageUsage::new(4, 400)),
+        ]
+    );
+    // Samples landing on the same version are deduped.
+    assert_eq!(
+        ledger_metadata_db.get_usage_history(26..40, 3).unwrap(),
+        vec![(25, StateStorageUsage::new(3, 300))]
+    );
+}
+
+#[test]
+fn test_scratch_value() {
+    let tmp_dir = TempPath::new();
+    let db = AptosDB::new_for_test(&tmp_dir);
+    let ledger_metadata_db = db.ledger_db.metadata_db();
+
+    ledger_metadata_db
+        .put_scratch_value(b"live", b"value", Duration::from_secs(3600))
+        .unwrap();
+    ledger_metadata_db
+        .put_scratch_value(b"expired", b"value", Duration::ZERO)
+        .unwrap();
+    assert_eq!(
+        ledger_metadata_db.get_scratch_value(b"live").unwrap(),
+        Some(b"value".to_vec())
+    );
+    assert_eq!(
+        ledger_metadata_db.get_scratch_value(b"expired").unwrap(),
+        None
+    );
+
+    ledger_metadata_db.delete_scratch_value(b"live").unwrap();
+    assert_eq!(ledger_metadata_db.get_scratch_value(b"live").unwrap(), None);
+
+    ledger_metadata_db
+        .put_scratch_values(&[
+            (b"a".to_vec(), b"1".to_vec(), Duration::from_secs(3600)),
+            (b"b".to_vec(), b"2".to_vec(), Duration::ZERO),
+        ])
+        .unwrap();
+    assert_eq!(
+        ledger_metadata_db.get_scratch_value(b"a").unwrap(),
+        Some(b"1".to_vec())
+    );
+    assert_eq!(ledger_metadata_db.get_scratch_value(b"b").unwrap(), None);
+}
@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::schema::{
+    block_execution_stats::BlockExecutionStatsSchema,
     db_metadata::{DbMetadataKey, DbMetadataSchema, DbMetadataValue},
     version_data::VersionDataSchema,
 };
@@ -47,6 +48,7 @@ impl LedgerMetadataPruner {
         let mut batch = SchemaBatch::new();
         for version in current_progress..target_version {
             batch.delete::<VersionDataSchema>(&version)?;
+            batch.delete::<BlockExecutionStatsSchema>(&version)?;
         }
         batch.put::<DbMetadataSchema>(
             &DbMetadataKey::LedgerPrunerProgress,
@@ -0,0 +1,60 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! This module defines physical storage schema for an index to help us find out which block was
+//! the latest at a given time, by storing the block_timestamp and block_height of every block.
+//! The height is part of the key because consecutive blocks can have the same timestamp.
+//!
+//! ```text
+//! |<---------------key--------------->|<-value->|
+//! | block_timestamp_usecs | block_height |   ()    |
+//! ```
+
+use crate::schema::{ensure_slice_len_eq, BLOCK_BY_TIMESTAMP_CF_NAME};
+use anyhow::Result;
+use aptos_schemadb::{
+    define_schema,
+    schema::{KeyCodec, ValueCodec},
+};
+use aptos_types::block_info::BlockHeight;
+use byteorder::{BigEndian, ReadBytesExt, WriteBytesExt};
+use std::mem::size_of;
+
+type TimestampUsecs = u64;
+type Key = (TimestampUsecs, BlockHeight);
+
+define_schema!(BlockByTimestampSchema, Key, (), BLOCK_BY_TIMESTAMP_CF_NAME);
+
+impl KeyCodec<BlockByTimestampSchema> for Key {
+    fn encode_key(&self) -> Result<Vec<u8>> {
+        let (timestamp_usecs, block_height) = self;
+
+        let mut encoded_key = Vec::with_capacity(size_of::<Self>());
+        encoded_key.write_u64::<BigEndian>(*timestamp_usecs)?;
+        encoded_key.write_u64::<BigEndian>(*block_height)?;
+        Ok(encoded_key)
+    }
+
+    fn decode_key(data: &[u8]) -> Result<Self> {
+        ensure_slice_len_eq(data, size_of::<Self>())?;
+
+        let timestamp_size = size_of::<TimestampUsecs>();
+        let timestamp_usecs = (&data[..timestamp_size]).read_u64::<BigEndian>()?;
+        let block_height = (&data[timestamp_size..]).read_u64::<BigEndian>()?;
+        Ok((timestamp_usecs, block_height))
+    }
+}
+
+impl ValueCodec<BlockByTimestampSchema> for () {
+    fn encode_value(&self) -> Result<Vec<u8>> {
+        Ok(Vec::new())
+    }
+
+    fn decode_value(data: &[u8]) -> Result<Self> {
+        ensure_slice_len_eq(data, 0)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test;
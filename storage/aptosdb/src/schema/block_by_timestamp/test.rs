@@ -0,0 +1,18 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use aptos_schemadb::{schema::fuzzing::assert_encode_decode, test_no_panic_decoding};
+use proptest::prelude::*;
+
+proptest! {
+    #[test]
+    fn test_encode_decode(
+        timestamp_usecs in any::<u64>(),
+        block_height in any::<BlockHeight>(),
+    ) {
+        assert_encode_decode::<BlockByTimestampSchema>(&(timestamp_usecs, block_height), &());
+    }
+}
+
+test_no_panic_decoding!(BlockByTimestampSchema);
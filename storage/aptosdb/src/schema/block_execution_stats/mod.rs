@@ -0,0 +1,58 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! This module defines physical storage schema for the execution statistics of blocks, keyed by
+//! the version of the first transaction of the block.
+//!
+//! ```text
+//! |<-----key----->|<--------value-------->|
+//! | first_version | block_execution_stats |
+//! ```
+//!
+//! `Version` is serialized in big endian so that records in RocksDB will be in order of their
+//! numeric value.
+
+use crate::schema::{ensure_slice_len_eq, BLOCK_EXECUTION_STATS_CF_NAME};
+use anyhow::Result;
+use aptos_schemadb::{
+    define_schema,
+    schema::{KeyCodec, ValueCodec},
+};
+use aptos_storage_interface::block_execution_stats::BlockExecutionStats;
+use aptos_types::transaction::Version;
+use byteorder::{BigEndian, ReadBytesExt};
+use std::mem::size_of;
+
+type Key = Version;
+type Value = BlockExecutionStats;
+
+define_schema!(
+    BlockExecutionStatsSchema,
+    Key,
+    Value,
+    BLOCK_EXECUTION_STATS_CF_NAME
+);
+
+impl KeyCodec<BlockExecutionStatsSchema> for Key {
+    fn encode_key(&self) -> Result<Vec<u8>> {
+        Ok(self.to_be_bytes().to_vec())
+    }
+
+    fn decode_key(mut data: &[u8]) -> Result<Self> {
+        ensure_slice_len_eq(data, size_of::<Self>())?;
+        Ok(data.read_u64::<BigEndian>()?)
+    }
+}
+
+impl ValueCodec<BlockExecutionStatsSchema> for Value {
+    fn encode_value(&self) -> Result<Vec<u8>> {
+        bcs::to_bytes(self).map_err(Into::into)
+    }
+
+    fn decode_value(data: &[u8]) -> Result<Self> {
+        bcs::from_bytes(data).map_err(Into::into)
+    }
+}
+
+#[cfg(test)]
+mod test;
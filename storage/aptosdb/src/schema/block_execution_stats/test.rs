@@ -0,0 +1,18 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use aptos_schemadb::{schema::fuzzing::assert_encode_decode, test_no_panic_decoding};
+use proptest::prelude::*;
+
+proptest! {
+    #[test]
+    fn test_encode_decode(
+        version in any::<Version>(),
+        stats in any::<BlockExecutionStats>(),
+    ) {
+        assert_encode_decode::<BlockExecutionStatsSchema>(&version, &stats);
+    }
+}
+
+test_no_panic_decoding!(BlockExecutionStatsSchema);
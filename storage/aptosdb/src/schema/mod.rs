@@ -6,7 +6,9 @@
 //!
 //! All schemas are `pub(crate)` so not shown in rustdoc, refer to the source code to see details.
 
+pub(crate) mod block_by_timestamp;
 pub(crate) mod block_by_version;
+pub(crate) mod block_execution_stats;
 pub(crate) mod block_info;
 pub(crate) mod db_metadata;
 pub(crate) mod epoch_by_version;
@@ -16,6 +18,7 @@ pub(crate) mod hot_state_value_by_key_hash;
 pub(crate) mod jellyfish_merkle_node;
 pub(crate) mod ledger_info;
 pub(crate) mod persisted_auxiliary_info;
+pub(crate) mod scratch;
 pub(crate) mod stale_node_index;
 pub(crate) mod stale_node_index_cross_epoch;
 pub(crate) mod stale_state_value_index;
@@ -35,7 +38,9 @@ pub(crate) mod write_set;
 use anyhow::{ensure, Result};
 use aptos_schemadb::ColumnFamilyName;
 
+pub const BLOCK_BY_TIMESTAMP_CF_NAME: ColumnFamilyName = "block_by_timestamp";
 pub const BLOCK_BY_VERSION_CF_NAME: ColumnFamilyName = "block_by_version";
+pub const BLOCK_EXECUTION_STATS_CF_NAME: ColumnFamilyName = "block_execution_stats";
 pub const BLOCK_INFO_CF_NAME: ColumnFamilyName = "block_info";
 pub const DB_METADATA_CF_NAME: ColumnFamilyName = "db_metadata";
 pub const EPOCH_BY_VERSION_CF_NAME: ColumnFamilyName = "epoch_by_version";
@@ -47,6 +52,7 @@ pub const HOT_STATE_VALUE_BY_KEY_HASH_CF_NAME: ColumnFamilyName = "hot_state_val
 pub const JELLYFISH_MERKLE_NODE_CF_NAME: ColumnFamilyName = "jellyfish_merkle_node";
 pub const LEDGER_INFO_CF_NAME: ColumnFamilyName = "ledger_info";
 pub const PERSISTED_AUXILIARY_INFO_CF_NAME: ColumnFamilyName = "persisted_auxiliary_info";
+pub const SCRATCH_CF_NAME: ColumnFamilyName = "scratch";
 pub const STALE_NODE_INDEX_CF_NAME: ColumnFamilyName = "stale_node_index";
 pub const STALE_NODE_INDEX_CROSS_EPOCH_CF_NAME: ColumnFamilyName = "stale_node_index_cross_epoch";
 pub const STALE_STATE_VALUE_INDEX_CF_NAME: ColumnFamilyName = "stale_state_value_index";
@@ -95,7 +101,11 @@ pub mod fuzzing {
     pub fn fuzz_decode(data: &[u8]) {
         #[allow(unused_must_use)]
         {
+            assert_no_panic_decoding::<super::block_by_timestamp::BlockByTimestampSchema>(data);
             assert_no_panic_decoding::<super::block_by_version::BlockByVersionSchema>(data);
+            assert_no_panic_decoding::<super::block_execution_stats::BlockExecutionStatsSchema>(
+                data,
+            );
             assert_no_panic_decoding::<super::block_info::BlockInfoSchema>(data);
             assert_no_panic_decoding::<super::epoch_by_version::EpochByVersionSchema>(data);
             assert_no_panic_decoding::<super::event::EventSchema>(data);
@@ -108,6 +118,7 @@ pub mod fuzzing {
             assert_no_panic_decoding::<super::persisted_auxiliary_info::PersistedAuxiliaryInfoSchema>(
                 data,
             );
+            assert_no_panic_decoding::<super::scratch::ScratchSchema>(data);
             assert_no_panic_decoding::<super::stale_node_index::StaleNodeIndexSchema>(data);
             assert_no_panic_decoding::<
                 super::stale_node_index_cross_epoch::StaleNodeIndexCrossEpochSchema,
@@ -0,0 +1,95 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! This module defines physical storage schema for the scratch space, which holds ephemeral
+//! data that is not part of the ledger and expires after a TTL.
+//!
+//! ```text
+//! |<-key->|<---------------value--------------->|
+//! |  key  | expiration_unix_secs |    bytes     |
+//! ```
+//!
+//! The expiration is serialized in big endian in front of the value, so that the TTL compaction
+//! filter can drop expired records without decoding the rest of the value.
+
+use super::SCRATCH_CF_NAME;
+use anyhow::Result;
+use aptos_schemadb::{
+    define_schema,
+    schema::{KeyCodec, ValueCodec},
+};
+use byteorder::{BigEndian, ReadBytesExt};
+#[cfg(any(test, feature = "fuzzing"))]
+use proptest_derive::Arbitrary;
+use std::{
+    mem::size_of,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(any(test, feature = "fuzzing"), derive(Arbitrary))]
+pub struct ScratchValue {
+    pub expiration_unix_secs: u64,
+    pub bytes: Vec<u8>,
+}
+
+impl ScratchValue {
+    pub fn new(bytes: Vec<u8>, ttl: Duration) -> Self {
+        Self {
+            expiration_unix_secs: now_unix_secs().saturating_add(ttl.as_secs()),
+            bytes,
+        }
+    }
+
+    pub fn is_expired(&self) -> bool {
+        self.expiration_unix_secs <= now_unix_secs()
+    }
+
+    /// Checks the expiration of an encoded value. Values that fail to decode are treated as
+    /// expired, as they can't be read anyway.
+    pub fn is_encoded_value_expired(mut data: &[u8]) -> bool {
+        data.read_u64::<BigEndian>()
+            .map_or(true, |expiration_unix_secs| {
+                expiration_unix_secs <= now_unix_secs()
+            })
+    }
+}
+
+fn now_unix_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+define_schema!(ScratchSchema, Vec<u8>, ScratchValue, SCRATCH_CF_NAME);
+
+impl KeyCodec<ScratchSchema> for Vec<u8> {
+    fn encode_key(&self) -> Result<Vec<u8>> {
+        Ok(self.clone())
+    }
+
+    fn decode_key(data: &[u8]) -> Result<Self> {
+        Ok(data.to_vec())
+    }
+}
+
+impl ValueCodec<ScratchSchema> for ScratchValue {
+    fn encode_value(&self) -> Result<Vec<u8>> {
+        let mut encoded = Vec::with_capacity(size_of::<u64>() + self.bytes.len());
+        encoded.extend_from_slice(&self.expiration_unix_secs.to_be_bytes());
+        encoded.extend_from_slice(&self.bytes);
+        Ok(encoded)
+    }
+
+    fn decode_value(mut data: &[u8]) -> Result<Self> {
+        let expiration_unix_secs = data.read_u64::<BigEndian>()?;
+        Ok(Self {
+            expiration_unix_secs,
+            bytes: data.to_vec(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod test;
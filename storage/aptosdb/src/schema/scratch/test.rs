@@ -0,0 +1,31 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use aptos_schemadb::{schema::fuzzing::assert_encode_decode, test_no_panic_decoding};
+use proptest::prelude::*;
+
+proptest! {
+    #[test]
+    fn test_encode_decode(key in any::<Vec<u8>>(), value in any::<ScratchValue>()) {
+        assert_encode_decode::<ScratchSchema>(&key, &value);
+    }
+}
+
+test_no_panic_decoding!(ScratchSchema);
+
+#[test]
+fn test_expiration() {
+    let value = ScratchValue::new(vec![1, 2, 3], Duration::from_secs(3600));
+    assert!(!value.is_expired());
+    assert!(!ScratchValue::is_encoded_value_expired(
+        &value.encode_value().unwrap()
+    ));
+
+    let expired = ScratchValue::new(vec![1, 2, 3], Duration::ZERO);
+    assert!(expired.is_expired());
+    assert!(ScratchValue::is_encoded_value_expired(
+        &expired.encode_value().unwrap()
+    ));
+    assert!(ScratchValue::is_encoded_value_expired(&[]));
+}
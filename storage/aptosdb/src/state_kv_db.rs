@@ -26,7 +26,11 @@ use aptos_schemadb::{
     batch::{SchemaBatch, WriteBatch},
     Cache, ReadOptions, DB,
 };
-use aptos_storage_interface::Result;
+use aptos_storage_interface::{
+    db_ensure as ensure,
+    state_store::state_update_refs::{PerVersionStateUpdateRefs, StateKvShardChecksum},
+    AptosDbError, Result,
+};
 use aptos_types::{
     state_store::{state_key::StateKey, state_value::StateValue, NUM_STATE_SHARDS},
     transaction::Version,
@@ -48,6 +52,8 @@ pub struct StateKvDb {
     #[allow(dead_code)] // TODO(HotState): can remove later.
     hot_state_kv_db_shards: Option<[Arc<DB>; NUM_STATE_SHARDS]>,
     enabled_sharding: bool,
+    // Whether committed shards are read back and checked, see `verify_shard`.
+    verify_commits: bool,
 }
 
 impl StateKvDb {
@@ -65,6 +71,7 @@ impl StateKvDb {
                 state_kv_db_shards: arr![Arc::clone(&ledger_db); 16],
                 hot_state_kv_db_shards: None,
                 enabled_sharding: false,
+                verify_commits: rocksdb_configs.verify_state_kv_commits,
             });
         }
 
@@ -72,12 +79,14 @@ impl StateKvDb {
             rocksdb_configs.state_kv_db_config.block_cache_size as usize,
             /* estimated_entry_charge = */ 0,
         );
-        Self::open_sharded(
+        let mut state_kv_db = Self::open_sharded(
             db_paths,
             rocksdb_configs.state_kv_db_config,
             Some(&block_cache),
             readonly,
-        )
+        )?;
+        state_kv_db.verify_commits = rocksdb_configs.verify_state_kv_commits;
+        Ok(state_kv_db)
     }
 
     pub(crate) fn open_sharded(
@@ -156,6 +165,7 @@ impl StateKvDb {
             state_kv_db_shards,
             hot_state_kv_db_shards,
             enabled_sharding: true,
+            verify_commits: false,
         };
 
         if !readonly {
@@ -171,11 +181,15 @@ impl StateKvDb {
         std::array::from_fn(|shard_id| self.db_shard(shard_id).new_native_batch())
     }
 
+    /// Commits the batches of all shards. If `state_update_refs` is provided and verification is
+    /// enabled, the state values written to each shard are read back and checked against the
+    /// checksums of the updates.
     pub(crate) fn commit(
         &self,
         version: Version,
         state_kv_metadata_batch: Option<SchemaBatch>,
         sharded_state_kv_batches: ShardedStateKvSchemaBatch,
+        state_update_refs: Option<&PerVersionStateUpdateRefs>,
     ) -> Result<()> {
         let _timer = OTHER_TIMERS_SECONDS.timer_with(&["state_kv_db__commit"]);
         {
@@ -189,6 +203,12 @@ impl StateKvDb {
                     s.spawn(move |_| {
                         // TODO(grao): Consider propagating the error instead of panic, if necessary.
                         self.commit_single_shard(version, shard_id, state_kv_batch)
+                            .and_then(|()| match state_update_refs {
+                                Some(refs) if self.verify_commits => {
+                                    self.verify_shard(shard_id, refs)
+                                },
+                                _ => Ok(()),
+                            })
                             .unwrap_or_else(|err| {
                                 panic!("Failed to commit shard {shard_id}: {err}.")
                             });
@@ -254,6 +274,111 @@ impl StateKvDb {
         Ok(())
     }
 
+    /// Moves the state kv metadata db and shards from the layout described by `from_paths` to
+    /// the one described by `to_paths`, e.g. to spread the shards across multiple disks. Each db
+    /// that changes location is copied over by a checkpoint (hardlinks on the same filesystem)
+    /// and verified by comparing its commit progress. Returns the ids of the moved shards.
+    pub(crate) fn migrate(
+        from_paths: &StorageDirPaths,
+        to_paths: &StorageDirPaths,
+        remove_source: bool,
+    ) -> Result<Vec<usize>> {
+        let state_kv_db_config = RocksdbConfig::default();
+
+        let from_metadata_path =
+            Self::metadata_db_path(from_paths.state_kv_db_metadata_root_path());
+        let to_metadata_path = Self::metadata_db_path(to_paths.state_kv_db_metadata_root_path());
+        if from_metadata_path != to_metadata_path {
+            let open = |path: PathBuf, readonly| {
+                Self::open_db(
+                    path,
+                    STATE_KV_METADATA_DB_NAME,
+                    &state_kv_db_config,
+                    None,
+                    readonly,
+                    /* is_hot = */ false,
+                )
+            };
+            Self::migrate_db(
+                &from_metadata_path,
+                &to_metadata_path,
+                &DbMetadataKey::StateKvCommitProgress,
+                open,
+                remove_source,
+            )?;
+        }
+
+        // TODO(HotState): should handle hot state as well.
+        (0..NUM_STATE_SHARDS)
+            .into_par_iter()
+            .filter_map(|shard_id| {
+                let from_root_path = from_paths.state_kv_db_shard_root_path(shard_id);
+                let to_root_path = to_paths.state_kv_db_shard_root_path(shard_id);
+                let from_path = Self::db_shard_path(from_root_path, shard_id, false);
+                let to_path = Self::db_shard_path(to_root_path, shard_id, false);
+                if from_path == to_path {
+                    return None;
+                }
+                let open = |path: PathBuf, readonly| {
+                    Self::open_db(
+                        path,
+                        &format!("state_kv_db_shard_{}", shard_id),
+                        &state_kv_db_config,
+                        None,
+                        readonly,
+                        /* is_hot = */ false,
+                    )
+                };
+                Some(
+                    Self::migrate_db(
+                        &from_path,
+                        &to_path,
+                        &DbMetadataKey::StateKvShardCommitProgress(shard_id),
+                        open,
+                        remove_source,
+                    )
+                    .map(|_| shard_id),
+                )
+            })
+            .collect()
+    }
+
+    fn migrate_db(
+        from_path: &Path,
+        to_path: &Path,
+        progress_key: &DbMetadataKey,
+        open: impl Fn(PathBuf, bool) -> Result<DB>,
+        remove_source: bool,
+    ) -> Result<()> {
+        ensure!(
+            from_path.exists(),
+            "Source db {from_path:?} does not exist."
+        );
+        ensure!(!to_path.exists(), "Target db {to_path:?} already exists.");
+
+        info!("Migrating state kv db from {from_path:?} to {to_path:?}.");
+        let expected_progress = {
+            let db = open(from_path.to_path_buf(), false)?;
+            if let Some(parent) = to_path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            db.create_checkpoint(to_path)?;
+            db.get::<DbMetadataSchema>(progress_key)?
+        };
+
+        let progress = open(to_path.to_path_buf(), true)?.get::<DbMetadataSchema>(progress_key)?;
+        ensure!(
+            progress == expected_progress,
+            "Commit progress of migrated db {to_path:?} ({progress:?}) does not match the source \
+            ({expected_progress:?})."
+        );
+
+        if remove_source {
+            std::fs::remove_dir_all(from_path)?;
+        }
+        Ok(())
+    }
+
     pub(crate) fn metadata_db(&self) -> &DB {
         &self.state_kv_metadata_db
     }
@@ -299,6 +424,47 @@ impl StateKvDb {
         self.state_kv_db_shards[shard_id].write_schemas(batch)
     }
 
+    /// Reads back the state values written to the shard and checks them against the checksum of
+    /// the updates produced by execution, to catch corruption between execution and storage.
+    fn verify_shard(
+        &self,
+        shard_id: usize,
+        state_update_refs: &PerVersionStateUpdateRefs,
+    ) -> Result<()> {
+        let _timer = OTHER_TIMERS_SECONDS.timer_with(&["state_kv_db__verify_shard"]);
+
+        let db = self.db_shard(shard_id);
+        let mut checksum = StateKvShardChecksum::default();
+        for (key, update) in state_update_refs.shards[shard_id].iter() {
+            if update.state_op.as_write_op_opt().is_none() {
+                continue;
+            }
+            let key_hash = key.crypto_hash_ref();
+            let value = if self.enabled_sharding {
+                db.get::<StateValueByKeyHashSchema>(&(*key_hash, update.version))?
+            } else {
+                db.get::<StateValueSchema>(&((*key).clone(), update.version))?
+            }
+            .ok_or_else(|| {
+                AptosDbError::Other(format!(
+                    "State value of {:?} at version {} is missing from shard {} after commit.",
+                    key, update.version, shard_id,
+                ))
+            })?;
+            checksum.add(key_hash, update.version, value.as_ref());
+        }
+
+        let expected = state_update_refs.checksum(shard_id);
+        ensure!(
+            checksum == expected,
+            "State KV checksum mismatch for shard {}: persisted {:?}, executed {:?}.",
+            shard_id,
+            checksum,
+            expected,
+        );
+        Ok(())
+    }
+
     fn open_shard<P: AsRef<Path>>(
         db_root_path: P,
         shard_id: usize,
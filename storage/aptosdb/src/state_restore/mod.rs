@@ -6,7 +6,9 @@ use anyhow::anyhow;
 use aptos_crypto::{hash::CryptoHash, HashValue};
 use aptos_db_indexer_schemas::metadata::StateSnapshotProgress;
 use aptos_infallible::Mutex;
-use aptos_jellyfish_merkle::{restore::JellyfishMerkleRestore, Key, TreeReader, TreeWriter, Value};
+use aptos_jellyfish_merkle::{
+    restore::JellyfishMerkleRestore, Key, NodeBatch, TreeReader, TreeWriter, Value,
+};
 use aptos_metrics_core::TimerHelper;
 use aptos_storage_interface::{Result, StateSnapshotReceiver};
 use aptos_types::{
@@ -142,10 +144,25 @@ impl<K: Key + CryptoHash + Eq + Hash, V: Value> StateValueRestore<K, V> {
     }
 }
 
+/// Decides which keys get their values written by a `StateSnapshotRestore`.
+pub type KeyFilter<K> = Arc<dyn Fn(&K) -> bool + Send + Sync>;
+
+/// A `TreeWriter` that discards all nodes, for restores that only verify the tree.
+pub struct NoopTreeWriter;
+
+impl<K: Key> TreeWriter<K> for NoopTreeWriter {
+    fn write_node_batch(&self, _node_batch: &NodeBatch<K>) -> Result<()> {
+        Ok(())
+    }
+}
+
 pub struct StateSnapshotRestore<K, V> {
     tree_restore: Arc<Mutex<Option<JellyfishMerkleRestore<K>>>>,
     kv_restore: Arc<Mutex<Option<StateValueRestore<K, V>>>>,
     restore_mode: StateSnapshotRestoreMode,
+    /// If set, only the values of the matching keys are written, while the tree is still built
+    /// from (and verified against) all of them.
+    kv_filter: Option<KeyFilter<K>>,
 }
 
 impl<K: Key + CryptoHash + Hash + Eq, V: Value> StateSnapshotRestore<K, V> {
@@ -169,6 +186,7 @@ impl<K: Key + CryptoHash + Hash + Eq, V: Value> StateSnapshotRestore<K, V> {
                 version,
             )))),
             restore_mode,
+            kv_filter: None,
         })
     }
 
@@ -190,9 +208,15 @@ impl<K: Key + CryptoHash + Hash + Eq, V: Value> StateSnapshotRestore<K, V> {
                 version,
             )))),
             restore_mode,
+            kv_filter: None,
         })
     }
 
+    pub fn with_kv_filter(mut self, kv_filter: KeyFilter<K>) -> Self {
+        self.kv_filter = Some(kv_filter);
+        self
+    }
+
     pub fn previous_key_hash(&self) -> Result<Option<HashValue>> {
         let hash_opt = match (
             self.kv_restore
@@ -228,11 +252,15 @@ impl<K: Key + CryptoHash + Hash + Eq, V: Value> StateSnapshotReceiver<K, V>
     fn add_chunk(&mut self, chunk: Vec<(K, V)>, proof: SparseMerkleRangeProof) -> Result<()> {
         let kv_fn = || {
             let _timer = OTHER_TIMERS_SECONDS.timer_with(&["state_value_add_chunk"]);
-            self.kv_restore
-                .lock()
-                .as_mut()
-                .unwrap()
-                .add_chunk(chunk.clone())
+            let kv_chunk = match &self.kv_filter {
+                Some(kv_filter) => chunk
+                    .iter()
+                    .filter(|(k, _v)| kv_filter(k))
+                    .cloned()
+                    .collect(),
+                None => chunk.clone(),
+            };
+            self.kv_restore.lock().as_mut().unwrap().add_chunk(kv_chunk)
         };
 
         let tree_fn = || {
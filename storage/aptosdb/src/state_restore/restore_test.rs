@@ -10,12 +10,15 @@ use aptos_infallible::RwLock;
 use aptos_jellyfish_merkle::{
     mock_tree_store::MockTreeStore,
     node_type::{LeafNode, Node, NodeKey},
+    restore::JellyfishMerkleShardedRestore,
     test_helper::{init_mock_db, ValueBlob},
     JellyfishMerkleTree, NodeBatch, TestKey, TestValue, TreeReader, TreeWriter,
 };
 use aptos_storage_interface::{Result, StateSnapshotReceiver};
 use aptos_types::{state_store::state_storage_usage::StateStorageUsage, transaction::Version};
+use itertools::Itertools;
 use proptest::{collection::btree_map, prelude::*};
+use rayon::prelude::*;
 use std::{
     collections::{BTreeMap, HashMap},
     sync::Arc,
@@ -216,6 +219,43 @@ proptest! {
         assert_success(&restore_db, expected_root_hash, &all, version);
     }
 
+    #[test]
+    fn test_sharded_restore(
+        btree in arb_btree_map(1),
+        chunk_size in 1usize..10,
+    ) {
+        let (db, version) = init_mock_store(&btree.clone().into_values().collect());
+        let tree = JellyfishMerkleTree::new(&db);
+        let expected_root_hash = tree.get_root_hash(version).unwrap();
+
+        let restore_db = Arc::new(MockTreeStore::default());
+        let restore =
+            JellyfishMerkleShardedRestore::new(restore_db.clone(), version, expected_root_hash);
+        // Ingest the shards in parallel, each one in order.
+        let shards = btree
+            .iter()
+            .into_group_map_by(|(hashed_key, _)| hashed_key.nibble(0));
+        shards.into_par_iter().for_each(|(_shard, states)| {
+            for chunk in states.chunks(chunk_size) {
+                restore
+                    .add_chunk(chunk.iter().map(|(_, (k, v))| (k, CryptoHash::hash(v))).collect())
+                    .unwrap();
+            }
+        });
+        restore.finish().unwrap();
+
+        let restored_tree = JellyfishMerkleTree::new(restore_db.as_ref());
+        prop_assert_eq!(restored_tree.get_root_hash(version).unwrap(), expected_root_hash);
+        for (hashed_key, (_k, v)) in &btree {
+            let (value_hash, _value_index) = restored_tree
+                .get_with_proof(*hashed_key, version)
+                .unwrap()
+                .0
+                .unwrap();
+            prop_assert_eq!(value_hash, CryptoHash::hash(v));
+        }
+    }
+
     #[test]
     fn test_overwrite(
         btree in arb_btree_map(1),
@@ -4,13 +4,13 @@
 //! This file defines state store buffered state that has been committed.
 
 use crate::{
-    metrics::{LATEST_CHECKPOINT_VERSION, OTHER_TIMERS_SECONDS},
+    metrics::{COUNTER, LATEST_CHECKPOINT_VERSION, OTHER_TIMERS_SECONDS},
     state_store::{
         persisted_state::PersistedState, state_snapshot_committer::StateSnapshotCommitter, StateDb,
     },
 };
 use aptos_infallible::Mutex;
-use aptos_metrics_core::TimerHelper;
+use aptos_metrics_core::{IntCounterVecHelper, TimerHelper};
 use aptos_storage_interface::{
     state_store::state_with_summary::{LedgerStateWithSummary, StateWithSummary},
     Result,
@@ -101,7 +101,8 @@ impl BufferedState {
             if !checkpoint.is_the_same(&self.last_snapshot)
                 && (sync_commit
                     || self.estimated_items >= self.target_items
-                    || self.buffered_versions() >= TARGET_SNAPSHOT_INTERVAL_IN_VERSION)
+                    || self.buffered_versions() >= TARGET_SNAPSHOT_INTERVAL_IN_VERSION
+                    || Self::under_memory_pressure())
             {
                 self.enqueue_commit(checkpoint);
             }
@@ -112,6 +113,17 @@ impl BufferedState {
         }
     }
 
+    /// Under memory pressure, every new checkpoint is committed, so that the older generations of
+    /// the in-memory state summary are dropped as soon as possible. As the commit channel is
+    /// bounded, this also slows execution down to the pace of the committer.
+    fn under_memory_pressure() -> bool {
+        let under_pressure = aptos_scratchpad::is_under_memory_pressure();
+        if under_pressure {
+            COUNTER.inc_with(&["buffered_state__commit_under_memory_pressure"]);
+        }
+        under_pressure
+    }
+
     fn current_state_locked(&self) -> MutexGuard<LedgerStateWithSummary> {
         self.current_state.lock()
     }
@@ -1344,7 +1344,12 @@ mod test_only {
                 .write_schemas(ledger_batch)
                 .unwrap();
             self.state_kv_db
-                .commit(last_version, None, sharded_state_kv_batches)
+                .commit(
+                    last_version,
+                    None,
+                    sharded_state_kv_batches,
+                    Some(&state_update_refs.per_version),
+                )
                 .unwrap();
 
             let current = self.current_state_locked().ledger_state_summary();
@@ -11,6 +11,10 @@ use crate::{
     state_restore::StateSnapshotRestore,
     AptosDB,
 };
+use aptos_config::config::{
+    RocksdbConfigs, StorageDirPaths, BUFFERED_STATE_TARGET_ITEMS_FOR_TEST,
+    NO_OP_STORAGE_PRUNER_CONFIG,
+};
 use aptos_jellyfish_merkle::{
     node_type::{Node, NodeKey},
     TreeReader,
@@ -22,6 +26,7 @@ use aptos_types::{
     account_config::{AccountResource, ChainIdResource, CoinInfoResource, CoinStoreResource},
     nibble::nibble_path::NibblePath,
     state_store::state_key::inner::StateKeyTag,
+    write_set::WriteSet,
     AptosCoinType,
 };
 use proptest::{collection::hash_map, prelude::*};
@@ -70,6 +75,49 @@ fn verify_value_index_in_store(
     assert_eq!(value.as_ref(), expected_value);
 }
 
+#[test]
+#[should_panic(expected = "State KV checksum mismatch")]
+fn test_state_kv_commit_detects_corruption() {
+    let tmp_dir = TempPath::new();
+    let db = AptosDB::open(
+        StorageDirPaths::from_path(&tmp_dir),
+        false,
+        NO_OP_STORAGE_PRUNER_CONFIG,
+        RocksdbConfigs {
+            enable_storage_sharding: true,
+            verify_state_kv_commits: true,
+            ..Default::default()
+        },
+        false,
+        BUFFERED_STATE_TARGET_ITEMS_FOR_TEST,
+        1000,
+        None,
+    )
+    .unwrap();
+    let store = &db.state_store;
+
+    let key = StateKey::raw(b"test_key");
+    let write_set =
+        WriteSet::new_for_test([(key.clone(), Some(StateValue::from(b"value".to_vec())))]);
+    let state_update_refs = StateUpdateRefs::index_write_sets(0, [&write_set], 1, Some(0));
+    let mut batches = store.state_kv_db.new_sharded_native_batches();
+    store
+        .put_state_values(&state_update_refs.per_version, &mut batches)
+        .unwrap();
+
+    // Flip a bit of the value after it's been handed over to the DB.
+    batches[key.get_shard_id()]
+        .put::<StateValueByKeyHashSchema>(
+            &(*key.crypto_hash_ref(), 0),
+            &Some(StateValue::from(b"valud".to_vec())),
+        )
+        .unwrap();
+    store
+        .state_kv_db
+        .commit(0, None, batches, Some(&state_update_refs.per_version))
+        .unwrap();
+}
+
 #[test]
 fn test_empty_store() {
     let tmp_dir = TempPath::new();
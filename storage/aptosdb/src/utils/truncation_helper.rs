@@ -9,6 +9,7 @@ use crate::{
         LedgerDbSchemaBatches,
     },
     schema::{
+        block_execution_stats::BlockExecutionStatsSchema,
         db_metadata::{DbMetadataKey, DbMetadataSchema, DbMetadataValue},
         epoch_by_version::EpochByVersionSchema,
         jellyfish_merkle_node::JellyfishMerkleNodeSchema,
@@ -452,6 +453,11 @@ fn delete_per_version_data(
         start_version,
         &mut batch.ledger_metadata_db_batches,
     )?;
+    delete_per_version_data_impl::<BlockExecutionStatsSchema>(
+        &ledger_db.metadata_db_arc(),
+        start_version,
+        &mut batch.ledger_metadata_db_batches,
+    )?;
     delete_per_version_data_impl::<WriteSetSchema>(
         ledger_db.write_set_db_raw(),
         start_version,
@@ -173,6 +173,61 @@ impl EpochEndingRestoreController {
     }
 }
 
+/// Spot-checks one chunk of an epoch ending backup: its `LedgerInfo`s must match the waypoints in
+/// the manifest, and each must be signed by the validator set the previous one in the chunk
+/// announces.
+pub(crate) async fn verify_epoch_ending_chunk(
+    storage: &Arc<dyn BackupStorage>,
+    manifest: &EpochEndingBackup,
+    chunk_idx: usize,
+) -> Result<()> {
+    manifest.verify()?;
+    let chunk = manifest
+        .chunks
+        .get(chunk_idx)
+        .ok_or_else(|| anyhow!("Chunk {} doesn't exist.", chunk_idx))?;
+
+    let mut file = storage.open_for_read(&chunk.ledger_infos).await?;
+    let mut lis: Vec<LedgerInfoWithSignatures> = vec![];
+    while let Some(record_bytes) = file.read_record_bytes().await? {
+        lis.push(bcs::from_bytes(&record_bytes)?);
+    }
+    ensure!(
+        chunk.first_epoch + lis.len() as u64 == chunk.last_epoch + 1,
+        "Number of items in chunks doesn't match that in manifest. \
+        first_epoch: {}, last_epoch: {}, items in chunk: {}",
+        chunk.first_epoch,
+        chunk.last_epoch,
+        lis.len(),
+    );
+
+    let waypoints = &manifest.waypoints[(chunk.first_epoch - manifest.first_epoch) as usize..];
+    let mut previous_li: Option<&LedgerInfoWithSignatures> = None;
+    for (li, wp_manifest) in lis.iter().zip(waypoints) {
+        let wp_li = Waypoint::new_epoch_boundary(li.ledger_info())?;
+        ensure!(
+            *wp_manifest == wp_li,
+            "Waypoints don't match. In manifest: {}, In chunk: {}",
+            wp_manifest,
+            wp_li,
+        );
+        if let Some(pre_li) = previous_li {
+            pre_li
+                .ledger_info()
+                .next_epoch_state()
+                .ok_or_else(|| {
+                    anyhow!(
+                        "Next epoch state not found from LI at epoch {}.",
+                        pre_li.ledger_info().epoch()
+                    )
+                })?
+                .verify(li)?;
+        }
+        previous_li = Some(li);
+    }
+    Ok(())
+}
+
 struct EpochEndingRestorePreheatData {
     manifest: EpochEndingBackup,
     ledger_infos: Vec<LedgerInfoWithSignatures>,
@@ -3,13 +3,20 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    backup_types::state_snapshot::manifest::{StateSnapshotBackup, StateSnapshotChunk},
+    backup_types::state_snapshot::manifest::{
+        IncrementalStateSnapshotBackup, IncrementalStateSnapshotChunk, StateSnapshotBackup,
+        StateSnapshotChunk, StateSnapshotDeltaChunk, StateSnapshotManifest,
+    },
     metadata::Metadata,
     metrics::backup::BACKUP_TIMER,
     storage::{BackupHandleRef, BackupStorage, FileHandle, ShellSafeName},
     utils::{
-        backup_service_client::BackupServiceClient, read_record_bytes::ReadRecordBytes,
-        should_cut_chunk, storage_ext::BackupStorageExt, stream::TryStreamX, GlobalBackupOpt,
+        backup_service_client::BackupServiceClient,
+        read_record_bytes::ReadRecordBytes,
+        should_cut_chunk,
+        storage_ext::BackupStorageExt,
+        stream::{StreamX, TryStreamX},
+        GlobalBackupOpt,
     },
 };
 use anyhow::{anyhow, ensure, Result};
@@ -17,16 +24,19 @@ use aptos_crypto::{hash::CryptoHash, HashValue};
 use aptos_logger::prelude::*;
 use aptos_metrics_core::TimerHelper;
 use aptos_types::{
+    contract_event::ContractEvent,
     ledger_info::LedgerInfoWithSignatures,
     proof::TransactionInfoWithProof,
     state_store::{state_key::StateKey, state_value::StateValue},
-    transaction::Version,
+    transaction::{PersistedAuxiliaryInfo, Transaction, TransactionInfo, Version},
+    write_set::WriteSet,
 };
 use bytes::{BufMut, Bytes, BytesMut};
 use clap::Parser;
-use futures::{StreamExt, TryStream, TryStreamExt};
+use futures::{stream, StreamExt, TryStream, TryStreamExt};
 use once_cell::sync::Lazy;
-use std::{convert::TryInto, str::FromStr, sync::Arc, time::Instant};
+use serde::Serialize;
+use std::{collections::BTreeMap, convert::TryInto, str::FromStr, sync::Arc, time::Instant};
 use tokio::{io::AsyncWriteExt, sync::mpsc::Sender};
 use tokio_stream::wrappers::ReceiverStream;
 
@@ -37,8 +47,18 @@ pub struct StateSnapshotBackupOpt {
         help = "Epoch at the end of which a state snapshot is to be taken."
     )]
     pub epoch: u64,
+    #[clap(
+        long = "state-snapshot-base-manifest",
+        help = "Manifest of an earlier state snapshot (full or incremental). If set, an incremental \
+        snapshot is taken on top of it, storing only the accounts changed since then."
+    )]
+    pub base_manifest: Option<FileHandle>,
 }
 
+/// The accounts changed since the base of an incremental snapshot, by key hash. `None` values
+/// are deletions.
+type StateChanges = BTreeMap<HashValue, (StateKey, Option<StateValue>)>;
+
 struct Chunk {
     bytes: Bytes,
     first_key: HashValue,
@@ -199,6 +219,7 @@ where
 
 pub struct StateSnapshotBackupController {
     epoch: u64,
+    base_manifest: Option<FileHandle>,
     version: Option<Version>, // initialize before using
     max_chunk_size: usize,
     client: Arc<BackupServiceClient>,
@@ -215,6 +236,7 @@ impl StateSnapshotBackupController {
     ) -> Self {
         Self {
             epoch: opt.epoch,
+            base_manifest: opt.base_manifest,
             version: None,
             max_chunk_size: global_opt.max_chunk_size,
             client,
@@ -235,6 +257,10 @@ impl StateSnapshotBackupController {
 
     async fn run_impl(mut self) -> Result<FileHandle> {
         self.version = Some(self.get_version_for_epoch_ending(self.epoch).await?);
+        if let Some(base_manifest) = self.base_manifest.clone() {
+            return self.run_incremental_impl(base_manifest).await;
+        }
+
         let backup_handle = self
             .storage
             .create_backup_with_random_suffix(&self.backup_name())
@@ -314,6 +340,210 @@ impl StateSnapshotBackupController {
     }
 }
 
+impl StateSnapshotBackupController {
+    /// Takes a snapshot consisting of the accounts changed since the base snapshot, and the range
+    /// proofs needed to restore the full state at `version` from the base snapshot and the
+    /// changes. The chunks of the full state follow those of the base snapshot, so the proofs
+    /// can be fetched without iterating over the state.
+    async fn run_incremental_impl(self, base_manifest_handle: FileHandle) -> Result<FileHandle> {
+        let base_manifest: StateSnapshotManifest =
+            self.storage.load_json_file(&base_manifest_handle).await?;
+        let base_version = base_manifest.version();
+        ensure!(
+            base_version < self.version(),
+            "Base state snapshot at version {} is not older than version {}.",
+            base_version,
+            self.version(),
+        );
+        let backup_handle = self
+            .storage
+            .create_backup_with_random_suffix(&format!("{}_incremental", self.backup_name()))
+            .await?;
+
+        let changes = self.get_state_changes(base_version).await?;
+        info!(
+            base_version = base_version,
+            changed_values = changes.len(),
+            "State changes since the base snapshot collected."
+        );
+        let delta_chunks = self.write_delta_chunks(&backup_handle, &changes).await?;
+
+        // Chunk boundaries of the base snapshot remain valid as long as the keys still exist.
+        let rightmost_key = self.get_rightmost_key().await?;
+        let mut chunk_last_keys: Vec<_> = base_manifest
+            .chunk_last_keys()
+            .into_iter()
+            .filter(|key| *key < rightmost_key && !matches!(changes.get(key), Some((_, None))))
+            .collect();
+        chunk_last_keys.push(rightmost_key);
+
+        let chunks: Vec<_> = stream::iter(chunk_last_keys.into_iter().enumerate())
+            .map(|(chunk_idx, last_key)| {
+                self.write_incremental_chunk_proof(&backup_handle, chunk_idx, last_key)
+            })
+            .buffered_x(8, 4) // 4 concurrently, at most 8 results in buffer.
+            .try_collect()
+            .await?;
+
+        let (root_hash, proof) = self.write_state_root_proof(&backup_handle).await?;
+        let manifest = IncrementalStateSnapshotBackup {
+            version: self.version(),
+            epoch: self.epoch,
+            root_hash,
+            base_version,
+            base_manifest: base_manifest_handle,
+            chain_length: base_manifest.chain_length() + 1,
+            delta_chunks,
+            chunks,
+            proof,
+        };
+        self.write_manifest_file(&backup_handle, &manifest).await
+    }
+
+    /// Collects the latest value of each account written by the transactions in
+    /// (`base_version`, `version`].
+    async fn get_state_changes(&self, base_version: Version) -> Result<StateChanges> {
+        let _timer = BACKUP_TIMER.timer_with(&["state_snapshot_get_state_changes"]);
+
+        let num_transactions = (self.version() - base_version) as usize;
+        let mut input = self
+            .client
+            .get_transactions(base_version + 1, num_transactions)
+            .await?;
+        let mut changes = StateChanges::new();
+        let mut count = 0;
+        while let Some(record_bytes) = input.read_record_bytes().await? {
+            let (_txn, _aux_info, _txn_info, _events, write_set): (
+                Transaction,
+                PersistedAuxiliaryInfo,
+                TransactionInfo,
+                Vec<ContractEvent>,
+                WriteSet,
+            ) = bcs::from_bytes(&record_bytes)?;
+            for (key, value) in write_set.state_update_refs() {
+                changes.insert(key.hash(), (key.clone(), value.cloned()));
+            }
+            count += 1;
+        }
+        ensure!(
+            count == num_transactions,
+            "expecting {} transactions, got {}",
+            num_transactions,
+            count
+        );
+        Ok(changes)
+    }
+
+    async fn write_delta_chunks(
+        &self,
+        backup_handle: &BackupHandleRef,
+        changes: &StateChanges,
+    ) -> Result<Vec<StateSnapshotDeltaChunk>> {
+        let mut delta_chunks = vec![];
+        let mut buf = BytesMut::new();
+        let mut chunk_keys: Option<(HashValue, HashValue)> = None;
+        for (key_hash, change) in changes {
+            let record_bytes = bcs::to_bytes(change)?;
+            if should_cut_chunk(&buf, &record_bytes, self.max_chunk_size) {
+                let (first_key, last_key) = chunk_keys.take().expect("Chunk can't be empty.");
+                delta_chunks.push(
+                    self.write_delta_chunk(
+                        backup_handle,
+                        delta_chunks.len(),
+                        &buf,
+                        first_key,
+                        last_key,
+                    )
+                    .await?,
+                );
+                buf.clear();
+            }
+            buf.put_slice(&(record_bytes.len() as u32).to_be_bytes());
+            buf.extend(record_bytes);
+            chunk_keys = Some((chunk_keys.map_or(*key_hash, |(first, _)| first), *key_hash));
+        }
+        if let Some((first_key, last_key)) = chunk_keys {
+            delta_chunks.push(
+                self.write_delta_chunk(
+                    backup_handle,
+                    delta_chunks.len(),
+                    &buf,
+                    first_key,
+                    last_key,
+                )
+                .await?,
+            );
+        }
+        Ok(delta_chunks)
+    }
+
+    async fn write_delta_chunk(
+        &self,
+        backup_handle: &BackupHandleRef,
+        chunk_idx: usize,
+        bytes: &[u8],
+        first_key: HashValue,
+        last_key: HashValue,
+    ) -> Result<StateSnapshotDeltaChunk> {
+        let (chunk_handle, mut chunk_file) = self
+            .storage
+            .create_for_write(backup_handle, &Self::delta_chunk_name(chunk_idx))
+            .await?;
+        chunk_file.write_all(bytes).await?;
+        chunk_file.shutdown().await?;
+
+        Ok(StateSnapshotDeltaChunk {
+            first_key,
+            last_key,
+            blobs: chunk_handle,
+        })
+    }
+
+    async fn write_incremental_chunk_proof(
+        &self,
+        backup_handle: &BackupHandleRef,
+        chunk_idx: usize,
+        last_key: HashValue,
+    ) -> Result<IncrementalStateSnapshotChunk> {
+        let (proof_handle, mut proof_file) = self
+            .storage
+            .create_for_write(
+                backup_handle,
+                &Self::incremental_chunk_proof_name(chunk_idx),
+            )
+            .await?;
+        tokio::io::copy(
+            &mut self
+                .client
+                .get_account_range_proof(last_key, self.version())
+                .await?,
+            &mut proof_file,
+        )
+        .await?;
+        proof_file.shutdown().await?;
+
+        Ok(IncrementalStateSnapshotChunk {
+            last_key,
+            proof: proof_handle,
+        })
+    }
+
+    /// Returns the largest key in the state at `version`.
+    async fn get_rightmost_key(&self) -> Result<HashValue> {
+        let count = self.client.get_state_item_count(self.version()).await?;
+        ensure!(count > 0, "State is empty.");
+        let record_bytes = self
+            .client
+            .get_state_snapshot_chunk(self.version(), count - 1, 1)
+            .await?
+            .read_record_bytes()
+            .await?
+            .ok_or_else(|| anyhow!("Failed to get the last state item."))?;
+        let (key, _): (StateKey, StateValue) = bcs::from_bytes(&record_bytes)?;
+        Ok(key.hash())
+    }
+}
+
 async fn send_records(
     client: Arc<BackupServiceClient>,
     version: Version,
@@ -386,6 +616,14 @@ impl StateSnapshotBackupController {
             .unwrap()
     }
 
+    fn delta_chunk_name(chunk_idx: usize) -> ShellSafeName {
+        format!("delta_{}.chunk", chunk_idx).try_into().unwrap()
+    }
+
+    fn incremental_chunk_proof_name(chunk_idx: usize) -> ShellSafeName {
+        format!("chunk_{}.proof", chunk_idx).try_into().unwrap()
+    }
+
     async fn get_version_for_epoch_ending(&self, epoch: u64) -> Result<u64> {
         let ledger_info: LedgerInfoWithSignatures = bcs::from_bytes(
             self.client
@@ -451,6 +689,23 @@ impl StateSnapshotBackupController {
         backup_handle: &BackupHandleRef,
         chunks: Vec<StateSnapshotChunk>,
     ) -> Result<FileHandle> {
+        let (root_hash, proof) = self.write_state_root_proof(backup_handle).await?;
+        let manifest = StateSnapshotBackup {
+            epoch: self.epoch,
+            version: self.version(),
+            root_hash,
+            chunks,
+            proof,
+        };
+        self.write_manifest_file(backup_handle, &manifest).await
+    }
+
+    /// Writes the proof of the state root hash at `version`, returning the root hash and the
+    /// proof file.
+    async fn write_state_root_proof(
+        &self,
+        backup_handle: &BackupHandleRef,
+    ) -> Result<(HashValue, FileHandle)> {
         let proof_bytes = self.client.get_state_root_proof(self.version()).await?;
         let (txn_info, _): (TransactionInfoWithProof, LedgerInfoWithSignatures) =
             bcs::from_bytes(&proof_bytes)?;
@@ -462,20 +717,23 @@ impl StateSnapshotBackupController {
         proof_file.write_all(&proof_bytes).await?;
         proof_file.shutdown().await?;
 
-        let manifest = StateSnapshotBackup {
-            epoch: self.epoch,
-            version: self.version(),
-            root_hash: txn_info.transaction_info().ensure_state_checkpoint_hash()?,
-            chunks,
-            proof: proof_handle,
-        };
+        Ok((
+            txn_info.transaction_info().ensure_state_checkpoint_hash()?,
+            proof_handle,
+        ))
+    }
 
+    async fn write_manifest_file<M: Serialize>(
+        &self,
+        backup_handle: &BackupHandleRef,
+        manifest: &M,
+    ) -> Result<FileHandle> {
         let (manifest_handle, mut manifest_file) = self
             .storage
             .create_for_write(backup_handle, Self::manifest_name())
             .await?;
         manifest_file
-            .write_all(&serde_json::to_vec(&manifest)?)
+            .write_all(&serde_json::to_vec(manifest)?)
             .await?;
         manifest_file.shutdown().await?;
 
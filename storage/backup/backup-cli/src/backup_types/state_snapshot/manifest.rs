@@ -50,3 +50,102 @@ pub struct StateSnapshotBackup {
     /// limits the requirement on such `EpochStateBackup` to no older than the same epoch.
     pub proof: FileHandle,
 }
+
+/// A chunk of the changes recorded by an incremental state snapshot: the keys in the range
+/// [`first_key`, `last_key`] (right side inclusive) whose values changed since the base snapshot.
+#[derive(Deserialize, Serialize)]
+pub struct StateSnapshotDeltaChunk {
+    /// key of the first changed account in this chunk.
+    pub first_key: HashValue,
+    /// key of the last changed account in this chunk.
+    pub last_key: HashValue,
+    /// Repeated `len(record) + record` where `record` is BCS serialized tuple
+    /// `(key, Option<state_value>)`, `None` meaning the key was deleted.
+    pub blobs: FileHandle,
+}
+
+/// A chunk of the state at the version of an incremental state snapshot, representing the
+/// accounts in the key range (`last_key` of the previous chunk, `last_key`]. Its values are
+/// reconstructed from the base snapshot and the changes.
+#[derive(Deserialize, Serialize)]
+pub struct IncrementalStateSnapshotChunk {
+    /// key of the last account in this chunk.
+    pub last_key: HashValue,
+    /// BCS serialized `SparseMerkleRangeProof` that proves this chunk adds up to the root hash
+    /// indicated in the backup (`IncrementalStateSnapshotBackup::root_hash`).
+    pub proof: FileHandle,
+}
+
+/// Incremental state snapshot backup manifest, representing a complete state view at specified
+/// version as the changes on top of the state snapshot at `base_version`. The base snapshot can
+/// itself be incremental, forming a chain that ends with a full `StateSnapshotBackup`.
+#[derive(Deserialize, Serialize)]
+pub struct IncrementalStateSnapshotBackup {
+    /// Version at which this state snapshot is taken.
+    pub version: Version,
+    /// Epoch in which this state snapshot is taken.
+    pub epoch: u64,
+    /// Hash of the state tree root.
+    pub root_hash: HashValue,
+    /// Version of the base snapshot.
+    pub base_version: Version,
+    /// Manifest of the base snapshot.
+    pub base_manifest: FileHandle,
+    /// Number of incremental snapshots in the chain up to and including this one.
+    pub chain_length: usize,
+    /// Accounts changed since `base_version`, in chunks.
+    pub delta_chunks: Vec<StateSnapshotDeltaChunk>,
+    /// Layout of the state at `version` in chunks, with their proofs.
+    pub chunks: Vec<IncrementalStateSnapshotChunk>,
+    /// BCS serialized `Tuple(TransactionInfoWithProof, LedgerInfoWithSignatures)`, same as
+    /// `StateSnapshotBackup::proof`.
+    pub proof: FileHandle,
+}
+
+/// Either kind of state snapshot manifest. Both are recorded as `StateSnapshotBackupMeta`, so
+/// restoring from a manifest handle accepts either.
+#[derive(Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum StateSnapshotManifest {
+    Incremental(IncrementalStateSnapshotBackup),
+    Full(StateSnapshotBackup),
+}
+
+impl StateSnapshotManifest {
+    pub fn version(&self) -> Version {
+        match self {
+            Self::Incremental(manifest) => manifest.version,
+            Self::Full(manifest) => manifest.version,
+        }
+    }
+
+    pub fn root_hash(&self) -> HashValue {
+        match self {
+            Self::Incremental(manifest) => manifest.root_hash,
+            Self::Full(manifest) => manifest.root_hash,
+        }
+    }
+
+    pub fn proof(&self) -> &FileHandle {
+        match self {
+            Self::Incremental(manifest) => &manifest.proof,
+            Self::Full(manifest) => &manifest.proof,
+        }
+    }
+
+    /// The last key of each chunk of the state at `version`.
+    pub fn chunk_last_keys(&self) -> Vec<HashValue> {
+        match self {
+            Self::Incremental(manifest) => manifest.chunks.iter().map(|c| c.last_key).collect(),
+            Self::Full(manifest) => manifest.chunks.iter().map(|c| c.last_key).collect(),
+        }
+    }
+
+    /// Number of incremental snapshots in the chain ending with this one, 0 for a full snapshot.
+    pub fn chain_length(&self) -> usize {
+        match self {
+            Self::Incremental(manifest) => manifest.chain_length,
+            Self::Full(_) => 0,
+        }
+    }
+}
@@ -4,7 +4,11 @@
 
 use crate::{
     backup_types::{
-        epoch_ending::restore::EpochHistory, state_snapshot::manifest::StateSnapshotBackup,
+        epoch_ending::restore::EpochHistory,
+        state_snapshot::manifest::{
+            IncrementalStateSnapshotBackup, IncrementalStateSnapshotChunk, StateSnapshotBackup,
+            StateSnapshotDeltaChunk, StateSnapshotManifest,
+        },
     },
     metrics::{
         restore::{
@@ -22,17 +26,20 @@ use crate::{
         GlobalRestoreOptions, RestoreRunMode,
     },
 };
-use anyhow::{anyhow, ensure, Result};
-use aptos_db::state_restore::StateSnapshotRestoreMode;
+use anyhow::{anyhow, bail, ensure, Result};
+use aptos_crypto::{hash::CryptoHash, HashValue};
+use aptos_db::state_restore::{KeyFilter, StateSnapshotRestore, StateSnapshotRestoreMode};
 use aptos_infallible::Mutex;
 use aptos_logger::prelude::*;
 use aptos_metrics_core::TimerHelper;
+use aptos_push_metrics::IntGauge;
 use aptos_storage_interface::StateSnapshotReceiver;
 use aptos_types::{
     access_path::Path,
+    account_address::AccountAddress,
     ledger_info::LedgerInfoWithSignatures,
     on_chain_config::Features,
-    proof::TransactionInfoWithProof,
+    proof::{SparseMerkleRangeProof, TransactionInfoWithProof},
     state_store::{
         state_key::{inner::StateKeyInner, StateKey},
         state_value::StateValue,
@@ -44,7 +51,10 @@ use clap::Parser;
 use futures::{stream, TryStreamExt};
 use move_binary_format::CompiledModule;
 use move_bytecode_verifier::verify_module_with_config;
-use std::sync::Arc;
+use std::{
+    collections::{BTreeMap, HashSet},
+    sync::Arc,
+};
 use tokio::time::Instant;
 
 #[derive(Parser)]
@@ -57,6 +67,51 @@ pub struct StateSnapshotRestoreOpt {
     pub validate_modules: bool,
     #[clap(long)]
     pub restore_mode: StateSnapshotRestoreMode,
+    #[clap(flatten)]
+    pub subset: StateSnapshotSubsetOpt,
+}
+
+/// Restricts a state snapshot restore to the state of some accounts, e.g. to audit them without a
+/// full restore. The whole snapshot is still read and verified against the root hash, but only
+/// the matching values are written, and the tree is not.
+#[derive(Clone, Default, Parser)]
+pub struct StateSnapshotSubsetOpt {
+    #[clap(
+        long = "subset-account",
+        help = "Only restore the resources and modules of this account. Can be repeated."
+    )]
+    pub accounts: Vec<AccountAddress>,
+    #[clap(
+        long = "subset-key-prefix",
+        help = "Only restore the state keys whose encoding starts with these hex encoded bytes, \
+        e.g. the items of a table. Can be repeated."
+    )]
+    pub key_prefixes: Vec<String>,
+}
+
+impl StateSnapshotSubsetOpt {
+    pub fn is_empty(&self) -> bool {
+        self.accounts.is_empty() && self.key_prefixes.is_empty()
+    }
+
+    fn key_filter(&self) -> Result<KeyFilter<StateKey>> {
+        let accounts: HashSet<_> = self.accounts.iter().cloned().collect();
+        let key_prefixes = self
+            .key_prefixes
+            .iter()
+            .map(|prefix| hex::decode(prefix.trim_start_matches("0x")))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Arc::new(move |key: &StateKey| {
+            let in_accounts = match key.inner() {
+                StateKeyInner::AccessPath(access_path) => accounts.contains(&access_path.address),
+                _ => false,
+            };
+            in_accounts
+                || key_prefixes
+                    .iter()
+                    .any(|prefix| key.encoded().starts_with(prefix))
+        }))
+    }
 }
 
 pub struct StateSnapshotRestoreController {
@@ -72,6 +127,7 @@ pub struct StateSnapshotRestoreController {
     concurrent_downloads: usize,
     validate_modules: bool,
     restore_mode: StateSnapshotRestoreMode,
+    subset: StateSnapshotSubsetOpt,
 }
 
 impl StateSnapshotRestoreController {
@@ -91,6 +147,7 @@ impl StateSnapshotRestoreController {
             concurrent_downloads: global_opt.concurrent_downloads,
             validate_modules: opt.validate_modules,
             restore_mode: opt.restore_mode,
+            subset: opt.subset,
         }
     }
 
@@ -121,29 +178,31 @@ impl StateSnapshotRestoreController {
             return Ok(());
         }
 
-        let manifest: StateSnapshotBackup =
+        let manifest: StateSnapshotManifest =
             self.storage.load_json_file(&self.manifest_handle).await?;
-        let (txn_info_with_proof, li): (TransactionInfoWithProof, LedgerInfoWithSignatures) =
-            self.storage.load_bcs_file(&manifest.proof).await?;
-        txn_info_with_proof.verify(li.ledger_info(), manifest.version)?;
-        let state_root_hash = txn_info_with_proof
-            .transaction_info()
-            .ensure_state_checkpoint_hash()?;
-        ensure!(
-            state_root_hash == manifest.root_hash,
-            "Root hash mismatch with that in proof. root hash: {}, expected: {}",
-            manifest.root_hash,
-            state_root_hash,
-        );
+        let li = verify_state_root(&self.storage, &manifest).await?;
         if let Some(epoch_history) = self.epoch_history.as_ref() {
             epoch_history.verify_ledger_info(&li)?;
         }
 
-        let receiver = Arc::new(Mutex::new(Some(self.run_mode.get_state_restore_receiver(
-            self.version,
-            manifest.root_hash,
-            self.restore_mode,
-        )?)));
+        let receiver = if self.subset.is_empty() {
+            self.run_mode.get_state_restore_receiver(
+                self.version,
+                manifest.root_hash(),
+                self.restore_mode,
+            )?
+        } else {
+            ensure!(
+                self.restore_mode == StateSnapshotRestoreMode::Default,
+                "Subset restore verifies the tree, so it requires the default restore mode."
+            );
+            self.run_mode.get_state_subset_restore_receiver(
+                self.version,
+                manifest.root_hash(),
+                self.subset.key_filter()?,
+            )?
+        };
+        let receiver = Arc::new(Mutex::new(Some(receiver)));
 
         let (ver_gauge, tgt_leaf_idx, leaf_idx) = if self.run_mode.is_verify() {
             (
@@ -158,12 +217,47 @@ impl StateSnapshotRestoreController {
                 &STATE_SNAPSHOT_LEAF_INDEX,
             )
         };
-
         ver_gauge.set(self.version as i64);
+
+        // The tree of a subset restore isn't persisted, so it always verifies from the start.
+        let resume_point_opt = if self.subset.is_empty() {
+            receiver.lock().as_mut().unwrap().previous_key_hash()?
+        } else {
+            None
+        };
+        match manifest {
+            StateSnapshotManifest::Full(manifest) => {
+                self.add_chunks(
+                    manifest,
+                    &receiver,
+                    resume_point_opt,
+                    tgt_leaf_idx,
+                    leaf_idx,
+                )
+                .await?
+            },
+            StateSnapshotManifest::Incremental(manifest) => {
+                self.add_incremental_chunks(manifest, &receiver, resume_point_opt, leaf_idx)
+                    .await?
+            },
+        }
+
+        tokio::task::spawn_blocking(move || receiver.lock().take().unwrap().finish()).await??;
+        self.run_mode.finish();
+        Ok(())
+    }
+
+    async fn add_chunks(
+        &self,
+        manifest: StateSnapshotBackup,
+        receiver: &Receiver,
+        resume_point_opt: Option<HashValue>,
+        tgt_leaf_idx: &IntGauge,
+        leaf_idx: &IntGauge,
+    ) -> Result<()> {
         tgt_leaf_idx.set(manifest.chunks.last().map_or(0, |c| c.last_idx as i64));
         let total_chunks = manifest.chunks.len();
 
-        let resume_point_opt = receiver.lock().as_mut().unwrap().previous_key_hash()?;
         let chunks = if let Some(resume_point) = resume_point_opt {
             manifest
                 .chunks
@@ -199,21 +293,9 @@ impl StateSnapshotRestoreController {
         let con = self.concurrent_downloads;
         let mut futs_stream = stream::iter(futs_iter).buffered_x(con * 2, con);
         let mut start = None;
-        while let Some((chunk_idx, chunk, mut blobs, proof)) = futs_stream.try_next().await? {
+        while let Some((chunk_idx, chunk, blobs, proof)) = futs_stream.try_next().await? {
             start = start.or_else(|| Some(Instant::now()));
-            let _timer = OTHER_TIMERS_SECONDS.timer_with(&["add_state_chunk"]);
-            let receiver = receiver.clone();
-            if self.validate_modules {
-                blobs = tokio::task::spawn_blocking(move || {
-                    Self::validate_modules(&blobs);
-                    blobs
-                })
-                .await?;
-            }
-            tokio::task::spawn_blocking(move || {
-                receiver.lock().as_mut().unwrap().add_chunk(blobs, proof)
-            })
-            .await??;
+            self.add_chunk(receiver, blobs, proof).await?;
             leaf_idx.set(chunk.last_idx as i64);
             info!(
                 chunk = chunk_idx,
@@ -225,9 +307,165 @@ impl StateSnapshotRestoreController {
                 "State chunk added.",
             );
         }
+        Ok(())
+    }
 
-        tokio::task::spawn_blocking(move || receiver.lock().take().unwrap().finish()).await??;
-        self.run_mode.finish();
+    /// Restores the state of an incremental snapshot: the values of the full snapshot at the end
+    /// of its chain are streamed in key order, with the changes of all the incremental snapshots
+    /// in the chain applied, and cut into the chunks the manifest has proofs for.
+    async fn add_incremental_chunks(
+        &self,
+        manifest: IncrementalStateSnapshotBackup,
+        receiver: &Receiver,
+        resume_point_opt: Option<HashValue>,
+        leaf_idx: &IntGauge,
+    ) -> Result<()> {
+        let (base_manifest, mut changes) = self
+            .load_snapshot_chain(
+                manifest.base_manifest,
+                manifest.base_version,
+                manifest.delta_chunks,
+                resume_point_opt,
+            )
+            .await?;
+        info!(
+            base_version = base_manifest.version,
+            chain_length = manifest.chain_length,
+            changed_values = changes.len(),
+            "State snapshot chain loaded."
+        );
+
+        let mut chunker = IncrementalChunker::new(manifest.chunks, resume_point_opt);
+        let base_chunks: Vec<_> = base_manifest
+            .chunks
+            .into_iter()
+            .skip_while(|chunk| resume_point_opt.is_some_and(|point| chunk.last_key <= point))
+            .collect();
+        let storage = self.storage.clone();
+        let futs_iter = base_chunks.into_iter().map(|chunk| {
+            let storage = storage.clone();
+            async move {
+                tokio::spawn(async move { Self::read_state_value(&storage, chunk.blobs).await })
+                    .await?
+            }
+        });
+        let con = self.concurrent_downloads;
+        let mut futs_stream = stream::iter(futs_iter).buffered_x(con * 2, con);
+
+        let start = Instant::now();
+        let mut num_added = 0;
+        let mut ready_chunks = vec![];
+        while let Some(blobs) = futs_stream.try_next().await? {
+            for (key, value) in blobs {
+                let key_hash = key.hash();
+                if resume_point_opt.is_some_and(|point| key_hash <= point) {
+                    continue;
+                }
+                while let Some((hash, (key, value))) = pop_first_if(&mut changes, |h| *h < key_hash)
+                {
+                    ready_chunks.extend(chunker.push(hash, key, value));
+                }
+                let value = match pop_first_if(&mut changes, |h| *h == key_hash) {
+                    Some((_, (_, new_value))) => new_value,
+                    None => Some(value),
+                };
+                ready_chunks.extend(chunker.push(key_hash, key, value));
+            }
+            for (blobs, proof) in ready_chunks.drain(..) {
+                num_added += blobs.len();
+                self.add_chunk(receiver, blobs, self.storage.load_bcs_file(&proof).await?)
+                    .await?;
+                leaf_idx.set(num_added as i64 - 1);
+                info!(
+                    values_added = num_added,
+                    values_per_second = (num_added as f64 / start.elapsed().as_secs_f64()) as u64,
+                    "State chunk added.",
+                );
+            }
+        }
+        // Accounts created after the last account in the base snapshot
+        for (hash, (key, value)) in changes {
+            ready_chunks.extend(chunker.push(hash, key, value));
+        }
+        for (blobs, proof) in ready_chunks {
+            num_added += blobs.len();
+            self.add_chunk(receiver, blobs, self.storage.load_bcs_file(&proof).await?)
+                .await?;
+            leaf_idx.set(num_added as i64 - 1);
+        }
+        chunker.ensure_finished()
+    }
+
+    /// Walks the chain of snapshots down to the full snapshot an incremental snapshot is based on,
+    /// returning the full snapshot and the combined changes since it (skipping keys up to
+    /// `resume_point_opt`).
+    async fn load_snapshot_chain(
+        &self,
+        mut base_manifest_handle: FileHandle,
+        mut base_version: Version,
+        delta_chunks: Vec<StateSnapshotDeltaChunk>,
+        resume_point_opt: Option<HashValue>,
+    ) -> Result<(StateSnapshotBackup, StateChanges)> {
+        let mut delta_chunks_by_snapshot = vec![delta_chunks];
+        let base_manifest = loop {
+            let base_manifest: StateSnapshotManifest =
+                self.storage.load_json_file(&base_manifest_handle).await?;
+            ensure!(
+                base_manifest.version() == base_version,
+                "Base state snapshot version mismatch. version: {}, expected: {}",
+                base_manifest.version(),
+                base_version,
+            );
+            match base_manifest {
+                StateSnapshotManifest::Full(base_manifest) => break base_manifest,
+                StateSnapshotManifest::Incremental(base_manifest) => {
+                    delta_chunks_by_snapshot.push(base_manifest.delta_chunks);
+                    base_manifest_handle = base_manifest.base_manifest;
+                    base_version = base_manifest.base_version;
+                },
+            }
+        };
+
+        // Apply the changes from the oldest snapshot to the newest, so newer values win.
+        let mut changes = StateChanges::new();
+        for delta_chunks in delta_chunks_by_snapshot.into_iter().rev() {
+            for chunk in delta_chunks {
+                if resume_point_opt.is_some_and(|point| chunk.last_key <= point) {
+                    continue;
+                }
+                let mut file = self.storage.open_for_read(&chunk.blobs).await?;
+                while let Some(record_bytes) = file.read_record_bytes().await? {
+                    let (key, value): (StateKey, Option<StateValue>) =
+                        bcs::from_bytes(&record_bytes)?;
+                    let key_hash = key.hash();
+                    if resume_point_opt.map_or(true, |point| key_hash > point) {
+                        changes.insert(key_hash, (key, value));
+                    }
+                }
+            }
+        }
+        Ok((base_manifest, changes))
+    }
+
+    async fn add_chunk(
+        &self,
+        receiver: &Receiver,
+        mut blobs: Vec<(StateKey, StateValue)>,
+        proof: SparseMerkleRangeProof,
+    ) -> Result<()> {
+        let _timer = OTHER_TIMERS_SECONDS.timer_with(&["add_state_chunk"]);
+        let receiver = receiver.clone();
+        if self.validate_modules {
+            blobs = tokio::task::spawn_blocking(move || {
+                Self::validate_modules(&blobs);
+                blobs
+            })
+            .await?;
+        }
+        tokio::task::spawn_blocking(move || {
+            receiver.lock().as_mut().unwrap().add_chunk(blobs, proof)
+        })
+        .await??;
         Ok(())
     }
 
@@ -266,3 +504,170 @@ impl StateSnapshotRestoreController {
         Ok(chunk)
     }
 }
+
+/// Verifies the root hash of a state snapshot against the `TransactionInfo` and the `LedgerInfo`
+/// it comes with, returning the latter. The `LedgerInfo` signatures are not verified.
+async fn verify_state_root(
+    storage: &Arc<dyn BackupStorage>,
+    manifest: &StateSnapshotManifest,
+) -> Result<LedgerInfoWithSignatures> {
+    let (txn_info_with_proof, li): (TransactionInfoWithProof, LedgerInfoWithSignatures) =
+        storage.load_bcs_file(manifest.proof()).await?;
+    txn_info_with_proof.verify(li.ledger_info(), manifest.version())?;
+    let state_root_hash = txn_info_with_proof
+        .transaction_info()
+        .ensure_state_checkpoint_hash()?;
+    ensure!(
+        state_root_hash == manifest.root_hash(),
+        "Root hash mismatch with that in proof. root hash: {}, expected: {}",
+        manifest.root_hash(),
+        state_root_hash,
+    );
+    Ok(li)
+}
+
+/// Spot-checks one chunk of a state snapshot backup: the chunk of a full snapshot (or the
+/// changes of an incremental one) at `chunk_idx`, along with the root hash of the snapshot.
+///
+/// A range proof can only be verified with all the values to the left of the chunk, so only the
+/// first chunk of a full snapshot is verified against the root hash. Other chunks are checked to
+/// hold the keys the manifest says they do, in order.
+pub(crate) async fn verify_state_snapshot_chunk(
+    storage: &Arc<dyn BackupStorage>,
+    manifest: &StateSnapshotManifest,
+    chunk_idx: usize,
+) -> Result<()> {
+    verify_state_root(storage, manifest).await?;
+    match manifest {
+        StateSnapshotManifest::Full(manifest) => {
+            let chunk = manifest
+                .chunks
+                .get(chunk_idx)
+                .ok_or_else(|| anyhow!("Chunk {} doesn't exist.", chunk_idx))?;
+            let blobs =
+                StateSnapshotRestoreController::read_state_value(storage, chunk.blobs.clone())
+                    .await?;
+            let proof: SparseMerkleRangeProof = storage.load_bcs_file(&chunk.proof).await?;
+            ensure!(
+                blobs.len() == chunk.last_idx + 1 - chunk.first_idx,
+                "Number of values in chunk doesn't match that in manifest. first_idx: {}, last_idx: {}, values in chunk: {}",
+                chunk.first_idx,
+                chunk.last_idx,
+                blobs.len(),
+            );
+            ensure_sorted_within(
+                blobs.iter().map(|(key, _value)| key.hash()),
+                chunk.first_key,
+                chunk.last_key,
+            )?;
+            if chunk_idx == 0 {
+                let mut receiver = RestoreRunMode::Verify.get_state_restore_receiver(
+                    manifest.version,
+                    manifest.root_hash,
+                    StateSnapshotRestoreMode::Default,
+                )?;
+                tokio::task::spawn_blocking(move || receiver.add_chunk(blobs, proof)).await??;
+            }
+        },
+        StateSnapshotManifest::Incremental(manifest) => {
+            let chunk = match manifest.delta_chunks.get(chunk_idx) {
+                Some(chunk) => chunk,
+                None if chunk_idx == 0 => return Ok(()),
+                None => bail!("Chunk {} doesn't exist.", chunk_idx),
+            };
+            let mut file = storage.open_for_read(&chunk.blobs).await?;
+            let mut key_hashes = vec![];
+            while let Some(record_bytes) = file.read_record_bytes().await? {
+                let (key, _value): (StateKey, Option<StateValue>) = bcs::from_bytes(&record_bytes)?;
+                key_hashes.push(key.hash());
+            }
+            ensure_sorted_within(key_hashes, chunk.first_key, chunk.last_key)?;
+        },
+    }
+    Ok(())
+}
+
+fn ensure_sorted_within(
+    key_hashes: impl IntoIterator<Item = HashValue>,
+    first_key: HashValue,
+    last_key: HashValue,
+) -> Result<()> {
+    let mut previous = None;
+    for key_hash in key_hashes {
+        ensure!(
+            previous.map_or(key_hash == first_key, |previous| key_hash > previous),
+            "Keys in chunk out of order or not matching the manifest at {}.",
+            key_hash,
+        );
+        previous = Some(key_hash);
+    }
+    ensure!(
+        previous == Some(last_key),
+        "Last key in chunk doesn't match that in manifest: {}.",
+        last_key,
+    );
+    Ok(())
+}
+
+type Receiver = Arc<Mutex<Option<StateSnapshotRestore<StateKey, StateValue>>>>;
+
+/// The accounts changed on top of a full snapshot, by key hash. `None` values are deletions.
+type StateChanges = BTreeMap<HashValue, (StateKey, Option<StateValue>)>;
+
+fn pop_first_if(
+    changes: &mut StateChanges,
+    predicate: impl FnOnce(&HashValue) -> bool,
+) -> Option<(HashValue, (StateKey, Option<StateValue>))> {
+    match changes.first_key_value() {
+        Some((key_hash, _)) if predicate(key_hash) => changes.pop_first(),
+        _ => None,
+    }
+}
+
+/// Cuts the (merged) values of an incremental snapshot into the chunks its manifest has proofs
+/// for.
+struct IncrementalChunker {
+    chunks: std::iter::Peekable<std::vec::IntoIter<IncrementalStateSnapshotChunk>>,
+    pending: Vec<(StateKey, StateValue)>,
+}
+
+impl IncrementalChunker {
+    fn new(
+        chunks: Vec<IncrementalStateSnapshotChunk>,
+        resume_point_opt: Option<HashValue>,
+    ) -> Self {
+        let chunks: Vec<_> = chunks
+            .into_iter()
+            .skip_while(|chunk| resume_point_opt.is_some_and(|point| chunk.last_key <= point))
+            .collect();
+        Self {
+            chunks: chunks.into_iter().peekable(),
+            pending: vec![],
+        }
+    }
+
+    /// Adds the next value (a `None` value is skipped), returning the values and proof of a
+    /// chunk if it is now complete.
+    fn push(
+        &mut self,
+        key_hash: HashValue,
+        key: StateKey,
+        value: Option<StateValue>,
+    ) -> Option<(Vec<(StateKey, StateValue)>, FileHandle)> {
+        self.pending.push((key, value?));
+        match self.chunks.next_if(|chunk| chunk.last_key == key_hash) {
+            Some(chunk) => Some((std::mem::take(&mut self.pending), chunk.proof)),
+            None => None,
+        }
+    }
+
+    fn ensure_finished(mut self) -> Result<()> {
+        ensure!(
+            self.pending.is_empty() && self.chunks.peek().is_none(),
+            "Incremental state snapshot doesn't add up: {} values left without a chunk, {} chunks left without values.",
+            self.pending.len(),
+            self.chunks.len(),
+        );
+        Ok(())
+    }
+}
@@ -5,11 +5,16 @@
 use crate::{
     backup_types::state_snapshot::{
         backup::{StateSnapshotBackupController, StateSnapshotBackupOpt},
-        restore::{StateSnapshotRestoreController, StateSnapshotRestoreOpt},
+        manifest::StateSnapshotManifest,
+        restore::{
+            verify_state_snapshot_chunk, StateSnapshotRestoreController, StateSnapshotRestoreOpt,
+            StateSnapshotSubsetOpt,
+        },
     },
     storage::{local_fs::LocalFs, BackupStorage},
     utils::{
         backup_service_client::BackupServiceClient,
+        storage_ext::BackupStorageExt,
         test_utils::{start_local_backup_service, tmp_db_with_random_content},
         ConcurrentDownloadsOpt, GlobalBackupOpt, GlobalRestoreOpt, ReplayConcurrencyLevelOpt,
         RocksdbOpt, TrustedWaypointOpt,
@@ -18,6 +23,7 @@ use crate::{
 use aptos_db::{state_restore::StateSnapshotRestoreMode, AptosDB};
 use aptos_storage_interface::DbReader;
 use aptos_temppath::TempPath;
+use aptos_types::state_store::state_key::inner::StateKeyInner;
 use std::{convert::TryInto, sync::Arc};
 use tokio::time::Duration;
 
@@ -62,7 +68,10 @@ fn end_to_end() {
     let manifest_handle = rt
         .block_on(
             StateSnapshotBackupController::new(
-                StateSnapshotBackupOpt { epoch },
+                StateSnapshotBackupOpt {
+                    epoch,
+                    base_manifest: None,
+                },
                 GlobalBackupOpt {
                     max_chunk_size: 500,
                     concurrent_data_requests: 2,
@@ -81,6 +90,7 @@ fn end_to_end() {
                 version,
                 validate_modules: false,
                 restore_mode: StateSnapshotRestoreMode::Default,
+                subset: StateSnapshotSubsetOpt::default(),
             },
             GlobalRestoreOpt {
                 dry_run: false,
@@ -112,3 +122,283 @@ fn end_to_end() {
 
     rt.shutdown_timeout(Duration::from_secs(1));
 }
+
+#[test]
+fn incremental_end_to_end() {
+    let (_src_db_dir, src_db, blocks) = tmp_db_with_random_content();
+    let tgt_db_dir = TempPath::new();
+    tgt_db_dir.create_as_dir().unwrap();
+    let backup_dir = TempPath::new();
+    backup_dir.create_as_dir().unwrap();
+    let store: Arc<dyn BackupStorage> = Arc::new(LocalFs::new(backup_dir.path().to_path_buf()));
+
+    let epoch_ending_lis: Vec<_> = blocks
+        .iter()
+        .map(|(_, li)| li.ledger_info())
+        .filter(|li| li.ends_epoch())
+        .collect();
+    if epoch_ending_lis.len() < 2 {
+        // Nothing to take an incremental snapshot on top of.
+        return;
+    }
+    let base_epoch = epoch_ending_lis[0].epoch();
+    let epoch = epoch_ending_lis.last().unwrap().epoch();
+    let version = epoch_ending_lis.last().unwrap().version();
+    let state_root_hash = src_db
+        .get_transactions(version, 1, version, false)
+        .unwrap()
+        .consume_transaction_list_with_proof()
+        .proof
+        .transaction_infos
+        .pop()
+        .unwrap()
+        .state_checkpoint_hash()
+        .unwrap();
+
+    let (rt, port) = start_local_backup_service(src_db);
+    let client = Arc::new(BackupServiceClient::new(format!(
+        "http://localhost:{}",
+        port
+    )));
+    let global_opt = GlobalBackupOpt {
+        max_chunk_size: 500,
+        concurrent_data_requests: 2,
+    };
+    let base_manifest_handle = rt
+        .block_on(
+            StateSnapshotBackupController::new(
+                StateSnapshotBackupOpt {
+                    epoch: base_epoch,
+                    base_manifest: None,
+                },
+                global_opt.clone(),
+                Arc::clone(&client),
+                Arc::clone(&store),
+            )
+            .run(),
+        )
+        .unwrap();
+    let manifest_handle = rt
+        .block_on(
+            StateSnapshotBackupController::new(
+                StateSnapshotBackupOpt {
+                    epoch,
+                    base_manifest: Some(base_manifest_handle),
+                },
+                global_opt,
+                client,
+                Arc::clone(&store),
+            )
+            .run(),
+        )
+        .unwrap();
+
+    rt.block_on(
+        StateSnapshotRestoreController::new(
+            StateSnapshotRestoreOpt {
+                manifest_handle,
+                version,
+                validate_modules: false,
+                restore_mode: StateSnapshotRestoreMode::Default,
+                subset: StateSnapshotSubsetOpt::default(),
+            },
+            GlobalRestoreOpt {
+                dry_run: false,
+                db_dir: Some(tgt_db_dir.path().to_path_buf()),
+                target_version: None, // max
+                trusted_waypoints: TrustedWaypointOpt::default(),
+                rocksdb_opt: RocksdbOpt::default(),
+                concurrent_downloads: ConcurrentDownloadsOpt::default(),
+                replay_concurrency_level: ReplayConcurrencyLevelOpt::default(),
+                enable_state_indices: false,
+            }
+            .try_into()
+            .unwrap(),
+            store,
+            None, /* epoch_history */
+        )
+        .run(),
+    )
+    .unwrap();
+
+    let tgt_db = AptosDB::new_readonly_for_test(&tgt_db_dir);
+    assert_eq!(
+        tgt_db
+            .get_state_snapshot_before(version + 1)
+            .unwrap()
+            .unwrap(),
+        (version, state_root_hash)
+    );
+
+    rt.shutdown_timeout(Duration::from_secs(1));
+}
+
+#[test]
+fn subset_end_to_end() {
+    let (_src_db_dir, src_db, _blocks) = tmp_db_with_random_content();
+    let tgt_db_dir = TempPath::new();
+    tgt_db_dir.create_as_dir().unwrap();
+    let backup_dir = TempPath::new();
+    backup_dir.create_as_dir().unwrap();
+    let store: Arc<dyn BackupStorage> = Arc::new(LocalFs::new(backup_dir.path().to_path_buf()));
+
+    let epoch = src_db
+        .get_latest_ledger_info()
+        .unwrap()
+        .ledger_info()
+        .next_block_epoch()
+        - 1;
+    let version = src_db
+        .get_epoch_ending_ledger_infos(epoch, epoch + 1)
+        .unwrap()
+        .ledger_info_with_sigs
+        .pop()
+        .unwrap()
+        .ledger_info()
+        .version();
+    let num_items = src_db.get_state_item_count(version).unwrap();
+    let state = src_db
+        .get_state_value_chunk_with_proof(version, 0, num_items)
+        .unwrap()
+        .raw_values;
+    let account = state
+        .iter()
+        .find_map(|(key, _value)| match key.inner() {
+            StateKeyInner::AccessPath(access_path) => Some(access_path.address),
+            _ => None,
+        })
+        .unwrap();
+
+    let (rt, port) = start_local_backup_service(src_db);
+    let client = Arc::new(BackupServiceClient::new(format!(
+        "http://localhost:{}",
+        port
+    )));
+    let manifest_handle = rt
+        .block_on(
+            StateSnapshotBackupController::new(
+                StateSnapshotBackupOpt {
+                    epoch,
+                    base_manifest: None,
+                },
+                GlobalBackupOpt {
+                    max_chunk_size: 500,
+                    concurrent_data_requests: 2,
+                },
+                client,
+                Arc::clone(&store),
+            )
+            .run(),
+        )
+        .unwrap();
+
+    rt.block_on(
+        StateSnapshotRestoreController::new(
+            StateSnapshotRestoreOpt {
+                manifest_handle,
+                version,
+                validate_modules: false,
+                restore_mode: StateSnapshotRestoreMode::Default,
+                subset: StateSnapshotSubsetOpt {
+                    accounts: vec![account],
+                    key_prefixes: vec![],
+                },
+            },
+            GlobalRestoreOpt {
+                dry_run: false,
+                db_dir: Some(tgt_db_dir.path().to_path_buf()),
+                target_version: None, // max
+                trusted_waypoints: TrustedWaypointOpt::default(),
+                rocksdb_opt: RocksdbOpt::default(),
+                concurrent_downloads: ConcurrentDownloadsOpt::default(),
+                replay_concurrency_level: ReplayConcurrencyLevelOpt::default(),
+                enable_state_indices: false,
+            }
+            .try_into()
+            .unwrap(),
+            store,
+            None, /* epoch_history */
+        )
+        .run(),
+    )
+    .unwrap();
+
+    let tgt_db = AptosDB::new_readonly_for_test(&tgt_db_dir);
+    for (key, value) in state {
+        let in_subset = matches!(
+            key.inner(),
+            StateKeyInner::AccessPath(access_path) if access_path.address == account
+        );
+        let restored = tgt_db.get_state_value_by_version(&key, version).unwrap();
+        assert_eq!(restored, in_subset.then_some(value));
+    }
+
+    rt.shutdown_timeout(Duration::from_secs(1));
+}
+
+#[test]
+fn spot_check() {
+    let (_src_db_dir, src_db, _blocks) = tmp_db_with_random_content();
+    let backup_dir = TempPath::new();
+    backup_dir.create_as_dir().unwrap();
+    let store: Arc<dyn BackupStorage> = Arc::new(LocalFs::new(backup_dir.path().to_path_buf()));
+
+    let epoch = src_db
+        .get_latest_ledger_info()
+        .unwrap()
+        .ledger_info()
+        .next_block_epoch()
+        - 1;
+
+    let (rt, port) = start_local_backup_service(src_db);
+    let client = Arc::new(BackupServiceClient::new(format!(
+        "http://localhost:{}",
+        port
+    )));
+    let manifest_handle = rt
+        .block_on(
+            StateSnapshotBackupController::new(
+                StateSnapshotBackupOpt {
+                    epoch,
+                    base_manifest: None,
+                },
+                GlobalBackupOpt {
+                    max_chunk_size: 500,
+                    concurrent_data_requests: 2,
+                },
+                client,
+                Arc::clone(&store),
+            )
+            .run(),
+        )
+        .unwrap();
+    let manifest: StateSnapshotManifest =
+        rt.block_on(store.load_json_file(&manifest_handle)).unwrap();
+    let StateSnapshotManifest::Full(full_manifest) = &manifest else {
+        panic!("Expecting a full state snapshot.");
+    };
+
+    for chunk_idx in 0..full_manifest.chunks.len() {
+        rt.block_on(verify_state_snapshot_chunk(&store, &manifest, chunk_idx))
+            .unwrap();
+    }
+
+    // Drop the last value of the first chunk.
+    let chunk = &full_manifest.chunks[0];
+    let blobs_path = backup_dir.path().join(&chunk.blobs);
+    let mut blobs = std::fs::read(&blobs_path).unwrap();
+    let mut offset = 0;
+    let mut last_record = 0;
+    while offset < blobs.len() {
+        last_record = offset;
+        let record_len = u32::from_be_bytes(blobs[offset..offset + 4].try_into().unwrap());
+        offset += 4 + record_len as usize;
+    }
+    blobs.truncate(last_record);
+    std::fs::write(&blobs_path, blobs).unwrap();
+    assert!(rt
+        .block_on(verify_state_snapshot_chunk(&store, &manifest, 0))
+        .is_err());
+
+    rt.shutdown_timeout(Duration::from_secs(1));
+}
@@ -8,7 +8,9 @@ use crate::{
     backup_types::{
         state_snapshot::{
             backup::{StateSnapshotBackupController, StateSnapshotBackupOpt},
-            restore::{StateSnapshotRestoreController, StateSnapshotRestoreOpt},
+            restore::{
+                StateSnapshotRestoreController, StateSnapshotRestoreOpt, StateSnapshotSubsetOpt,
+            },
         },
         transaction::{
             backup::{TransactionBackupController, TransactionBackupOpt},
@@ -95,7 +97,10 @@ fn test_end_to_end_impl(d: TestData) {
     let state_snapshot_manifest = d.state_snapshot_epoch.map(|epoch| {
         rt.block_on(
             StateSnapshotBackupController::new(
-                StateSnapshotBackupOpt { epoch },
+                StateSnapshotBackupOpt {
+                    epoch,
+                    base_manifest: None,
+                },
                 global_backup_opt.clone(),
                 Arc::clone(&client),
                 Arc::clone(&store),
@@ -139,6 +144,7 @@ fn test_end_to_end_impl(d: TestData) {
                     version,
                     validate_modules: false,
                     restore_mode: StateSnapshotRestoreMode::Default,
+                    subset: StateSnapshotSubsetOpt::default(),
                 },
                 global_restore_opt.clone(),
                 Arc::clone(&store),
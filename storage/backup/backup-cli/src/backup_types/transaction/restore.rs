@@ -97,6 +97,15 @@ struct LoadedChunk {
     pub range_proof: TransactionAccumulatorRangeProof,
 }
 
+/// Spot-checks one chunk of a transaction backup against the accumulator range proof and the
+/// `LedgerInfo` it comes with. The `LedgerInfo` signatures are not verified.
+pub(crate) async fn verify_transaction_chunk(
+    storage: &Arc<dyn BackupStorage>,
+    chunk: TransactionChunk,
+) -> Result<()> {
+    LoadedChunk::load(chunk, storage, None).await.map(|_| ())
+}
+
 impl LoadedChunk {
     async fn load(
         manifest: TransactionChunk,
@@ -5,7 +5,10 @@
 use crate::{
     backup_types::{
         epoch_ending::backup::{EpochEndingBackupController, EpochEndingBackupOpt},
-        state_snapshot::backup::{StateSnapshotBackupController, StateSnapshotBackupOpt},
+        state_snapshot::{
+            backup::{StateSnapshotBackupController, StateSnapshotBackupOpt},
+            manifest::StateSnapshotManifest,
+        },
         transaction::backup::{TransactionBackupController, TransactionBackupOpt},
     },
     metadata,
@@ -15,13 +18,13 @@ use crate::{
     },
     storage::{BackupStorage, FileHandle},
     utils::{
-        backup_service_client::BackupServiceClient, unix_timestamp_sec, ConcurrentDownloadsOpt,
-        GlobalBackupOpt,
+        backup_service_client::BackupServiceClient, storage_ext::BackupStorageExt,
+        unix_timestamp_sec, ConcurrentDownloadsOpt, GlobalBackupOpt,
     },
 };
 use anyhow::{anyhow, ensure, Result};
 use aptos_db::backup::backup_handler::DbState;
-use aptos_infallible::duration_since_epoch;
+use aptos_infallible::{duration_since_epoch, Mutex};
 use aptos_logger::prelude::*;
 use aptos_types::transaction::Version;
 use clap::Parser;
@@ -69,6 +72,15 @@ pub struct BackupCoordinatorOpt {
         many small files. "
     )]
     pub transaction_batch_size: usize,
+    #[clap(
+        long,
+        default_value_t = 0,
+        help = "Maximum number of incremental state snapshots to take in a row. An incremental \
+        state snapshot only stores the state changed since the previous snapshot, and restoring it \
+        requires every snapshot back to the last full one. After this many incremental snapshots, \
+        a full snapshot is taken again. 0 means always taking full snapshots."
+    )]
+    pub max_incremental_state_snapshots: usize,
     #[clap(flatten)]
     pub concurrent_downloads: ConcurrentDownloadsOpt,
 }
@@ -90,7 +102,10 @@ pub struct BackupCoordinator {
     metadata_cache_opt: MetadataCacheOpt,
     state_snapshot_interval_epochs: usize,
     transaction_batch_size: usize,
+    max_incremental_state_snapshots: usize,
     concurrent_downloads: usize,
+    /// Manifest of the latest state snapshot in the backup and its incremental chain length.
+    last_state_snapshot: Mutex<Option<(FileHandle, usize)>>,
 }
 
 impl BackupCoordinator {
@@ -108,19 +123,29 @@ impl BackupCoordinator {
             metadata_cache_opt: opt.metadata_cache_opt,
             state_snapshot_interval_epochs: opt.state_snapshot_interval_epochs,
             transaction_batch_size: opt.transaction_batch_size,
+            max_incremental_state_snapshots: opt.max_incremental_state_snapshots,
             concurrent_downloads: opt.concurrent_downloads.get(),
+            last_state_snapshot: Mutex::new(None),
         }
     }
 
     pub async fn run(&self) -> Result<()> {
         // Connect to both the local node and the backup storage.
-        let backup_state = metadata::cache::sync_and_load(
+        let metadata_view = metadata::cache::sync_and_load(
             &self.metadata_cache_opt,
             Arc::clone(&self.storage),
             self.concurrent_downloads,
         )
-        .await?
-        .get_storage_state()?;
+        .await?;
+        let backup_state = metadata_view.get_storage_state()?;
+        if self.max_incremental_state_snapshots > 0 {
+            if let Some(snapshot) = metadata_view.select_state_snapshot(Version::MAX)? {
+                let manifest: StateSnapshotManifest =
+                    self.storage.load_json_file(&snapshot.manifest).await?;
+                *self.last_state_snapshot.lock() =
+                    Some((snapshot.manifest, manifest.chain_length()));
+            }
+        }
 
         // On new DbState retrieved:
         // `watch_db_state` informs `backup_epoch_endings` via channel 1,
@@ -255,14 +280,26 @@ impl BackupCoordinator {
             return Ok(last_snapshot_epoch_in_backup);
         }
 
-        StateSnapshotBackupController::new(
-            StateSnapshotBackupOpt { epoch },
+        // Take an incremental snapshot on top of the last one, unless the chain is already as long
+        // as allowed.
+        let base = self
+            .last_state_snapshot
+            .lock()
+            .clone()
+            .filter(|(_, chain_length)| *chain_length < self.max_incremental_state_snapshots);
+        let manifest = StateSnapshotBackupController::new(
+            StateSnapshotBackupOpt {
+                epoch,
+                base_manifest: base.as_ref().map(|(manifest, _)| manifest.clone()),
+            },
             self.global_opt.clone(),
             Arc::clone(&self.client),
             Arc::clone(&self.storage),
         )
         .run()
         .await?;
+        let chain_length = base.map_or(0, |(_, chain_length)| chain_length + 1);
+        *self.last_state_snapshot.lock() = Some((manifest, chain_length));
 
         Ok(Some(epoch))
     }
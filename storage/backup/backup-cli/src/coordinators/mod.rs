@@ -5,4 +5,5 @@
 pub mod backup;
 pub mod replay_verify;
 pub mod restore;
+pub mod spot_check;
 pub mod verify;
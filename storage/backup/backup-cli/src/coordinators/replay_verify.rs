@@ -4,7 +4,9 @@
 
 use crate::{
     backup_types::{
-        state_snapshot::restore::{StateSnapshotRestoreController, StateSnapshotRestoreOpt},
+        state_snapshot::restore::{
+            StateSnapshotRestoreController, StateSnapshotRestoreOpt, StateSnapshotSubsetOpt,
+        },
         transaction::restore::TransactionRestoreBatchController,
     },
     metadata,
@@ -179,6 +181,7 @@ impl ReplayVerifyCoordinator {
                         version: backup.version,
                         validate_modules: self.validate_modules,
                         restore_mode: Default::default(),
+                        subset: StateSnapshotSubsetOpt::default(),
                     },
                     global_opt.clone(),
                     Arc::clone(&self.storage),
@@ -5,7 +5,9 @@
 use crate::{
     backup_types::{
         epoch_ending::restore::EpochHistoryRestoreController,
-        state_snapshot::restore::{StateSnapshotRestoreController, StateSnapshotRestoreOpt},
+        state_snapshot::restore::{
+            StateSnapshotRestoreController, StateSnapshotRestoreOpt, StateSnapshotSubsetOpt,
+        },
         transaction::restore::TransactionRestoreBatchController,
     },
     metadata,
@@ -251,6 +253,7 @@ impl RestoreCoordinator {
                         version: kv_snapshot.version,
                         validate_modules: false,
                         restore_mode: StateSnapshotRestoreMode::KvOnly,
+                        subset: StateSnapshotSubsetOpt::default(),
                     },
                     self.global_opt.clone(),
                     Arc::clone(&self.storage),
@@ -337,6 +340,7 @@ impl RestoreCoordinator {
                             version: tree_snapshot.version,
                             validate_modules: false,
                             restore_mode,
+                            subset: StateSnapshotSubsetOpt::default(),
                         },
                         self.global_opt.clone(),
                         Arc::clone(&self.storage),
@@ -0,0 +1,224 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    backup_types::{
+        epoch_ending::{manifest::EpochEndingBackup, restore::verify_epoch_ending_chunk},
+        state_snapshot::{manifest::StateSnapshotManifest, restore::verify_state_snapshot_chunk},
+        transaction::{manifest::TransactionBackup, restore::verify_transaction_chunk},
+    },
+    metadata,
+    metadata::{cache::MetadataCacheOpt, view::MetadataView},
+    metrics::verify::{
+        VERIFY_SPOT_CHECK_CHUNKS, VERIFY_SPOT_CHECK_FAIL_TS, VERIFY_SPOT_CHECK_ROUND_TS,
+    },
+    storage::{BackupStorage, FileHandle},
+    utils::{storage_ext::BackupStorageExt, unix_timestamp_sec},
+};
+use anyhow::{ensure, Result};
+use aptos_logger::prelude::*;
+use aptos_types::transaction::Version;
+use clap::Parser;
+use rand::{seq::SliceRandom, Rng};
+use std::{sync::Arc, time::Duration};
+
+#[derive(Clone, Parser)]
+pub struct SpotCheckCoordinatorOpt {
+    #[clap(flatten)]
+    pub metadata_cache_opt: MetadataCacheOpt,
+    #[clap(
+        long,
+        default_value_t = 3600,
+        help = "Interval (in seconds) between rounds of spot checks."
+    )]
+    pub spot_check_interval_secs: u64,
+    #[clap(
+        long,
+        default_value_t = 10,
+        help = "Number of random chunks to verify in each round, picked across all the epoch \
+        ending, state snapshot and transaction backups in the storage."
+    )]
+    pub chunks_per_round: usize,
+}
+
+impl SpotCheckCoordinatorOpt {
+    fn validate(&self) -> Result<()> {
+        ensure!(
+            self.spot_check_interval_secs > 0 && self.chunks_per_round > 0,
+            "Spot check interval and chunks per round must be greater than 0."
+        );
+        Ok(())
+    }
+}
+
+/// Continuously verifies random chunks of the existing backups against their proofs, so that
+/// corruption in the storage is noticed long before the backups are needed for a restore.
+pub struct SpotCheckCoordinator {
+    storage: Arc<dyn BackupStorage>,
+    metadata_cache_opt: MetadataCacheOpt,
+    concurrent_downloads: usize,
+    interval: Duration,
+    chunks_per_round: usize,
+}
+
+impl SpotCheckCoordinator {
+    pub fn new(
+        opt: SpotCheckCoordinatorOpt,
+        storage: Arc<dyn BackupStorage>,
+        concurrent_downloads: usize,
+    ) -> Result<Self> {
+        opt.validate()?;
+        Ok(Self {
+            storage,
+            metadata_cache_opt: opt.metadata_cache_opt,
+            concurrent_downloads,
+            interval: Duration::from_secs(opt.spot_check_interval_secs),
+            chunks_per_round: opt.chunks_per_round,
+        })
+    }
+
+    pub async fn run(self) -> Result<()> {
+        info!("Spot check coordinator started.");
+        let mut interval = tokio::time::interval(self.interval);
+        loop {
+            interval.tick().await;
+            if let Err(e) = self.run_round().await {
+                // Failing to list or load the backups is not corruption, try again next round.
+                warn!(error = ?e, "Spot check round failed.");
+            }
+            VERIFY_SPOT_CHECK_ROUND_TS.set(unix_timestamp_sec());
+        }
+    }
+
+    async fn run_round(&self) -> Result<()> {
+        let metadata_view = metadata::cache::sync_and_load(
+            &self.metadata_cache_opt,
+            Arc::clone(&self.storage),
+            self.concurrent_downloads,
+        )
+        .await?;
+        let targets = Self::sample(&metadata_view, self.chunks_per_round)?;
+
+        for target in targets {
+            let backup_type = target.backup_type();
+            match self.check(&target).await {
+                Ok(chunk_idx) => {
+                    VERIFY_SPOT_CHECK_CHUNKS
+                        .with_label_values(&[backup_type, "success"])
+                        .inc();
+                    debug!(
+                        backup_type = backup_type,
+                        manifest = target.manifest(),
+                        chunk = chunk_idx,
+                        "Backup chunk spot-checked."
+                    );
+                },
+                Err(e) => {
+                    VERIFY_SPOT_CHECK_CHUNKS
+                        .with_label_values(&[backup_type, "failure"])
+                        .inc();
+                    VERIFY_SPOT_CHECK_FAIL_TS.set(unix_timestamp_sec());
+                    error!(
+                        backup_type = backup_type,
+                        manifest = target.manifest(),
+                        error = ?e,
+                        "Backup spot check failed, the backup might be corrupted."
+                    );
+                },
+            }
+        }
+        info!("Spot check round finished.");
+        Ok(())
+    }
+
+    fn sample(metadata_view: &MetadataView, num_targets: usize) -> Result<Vec<SpotCheckTarget>> {
+        let all_targets: Vec<_> = metadata_view
+            .select_epoch_ending_backups(Version::MAX)?
+            .into_iter()
+            .map(|backup| SpotCheckTarget::EpochEnding(backup.manifest))
+            .chain(
+                metadata_view
+                    .all_state_snapshots()
+                    .iter()
+                    .map(|backup| SpotCheckTarget::StateSnapshot(backup.manifest.clone())),
+            )
+            .chain(
+                metadata_view
+                    .select_transaction_backups(0, Version::MAX)?
+                    .into_iter()
+                    .map(|backup| SpotCheckTarget::Transaction(backup.manifest)),
+            )
+            .collect();
+
+        let mut rng = rand::thread_rng();
+        Ok((0..num_targets)
+            .filter_map(|_| all_targets.choose(&mut rng).cloned())
+            .collect())
+    }
+
+    /// Verifies a random chunk of the backup, returning the index of the chunk.
+    async fn check(&self, target: &SpotCheckTarget) -> Result<usize> {
+        match target {
+            SpotCheckTarget::EpochEnding(manifest_handle) => {
+                let manifest: EpochEndingBackup =
+                    self.storage.load_json_file(manifest_handle).await?;
+                let chunk_idx = random_index(manifest.chunks.len())?;
+                verify_epoch_ending_chunk(&self.storage, &manifest, chunk_idx).await?;
+                Ok(chunk_idx)
+            },
+            SpotCheckTarget::StateSnapshot(manifest_handle) => {
+                let manifest: StateSnapshotManifest =
+                    self.storage.load_json_file(manifest_handle).await?;
+                let num_chunks = match &manifest {
+                    StateSnapshotManifest::Full(manifest) => manifest.chunks.len(),
+                    // Nothing might have changed since the base snapshot, still check the root.
+                    StateSnapshotManifest::Incremental(manifest) => {
+                        manifest.delta_chunks.len().max(1)
+                    },
+                };
+                let chunk_idx = random_index(num_chunks)?;
+                verify_state_snapshot_chunk(&self.storage, &manifest, chunk_idx).await?;
+                Ok(chunk_idx)
+            },
+            SpotCheckTarget::Transaction(manifest_handle) => {
+                let mut manifest: TransactionBackup =
+                    self.storage.load_json_file(manifest_handle).await?;
+                manifest.verify()?;
+                let chunk_idx = random_index(manifest.chunks.len())?;
+                verify_transaction_chunk(&self.storage, manifest.chunks.swap_remove(chunk_idx))
+                    .await?;
+                Ok(chunk_idx)
+            },
+        }
+    }
+}
+
+#[derive(Clone)]
+enum SpotCheckTarget {
+    EpochEnding(FileHandle),
+    StateSnapshot(FileHandle),
+    Transaction(FileHandle),
+}
+
+impl SpotCheckTarget {
+    fn backup_type(&self) -> &'static str {
+        match self {
+            Self::EpochEnding(_) => "epoch_ending",
+            Self::StateSnapshot(_) => "state_snapshot",
+            Self::Transaction(_) => "transaction",
+        }
+    }
+
+    fn manifest(&self) -> &str {
+        match self {
+            Self::EpochEnding(manifest)
+            | Self::StateSnapshot(manifest)
+            | Self::Transaction(manifest) => manifest,
+        }
+    }
+}
+
+fn random_index(len: usize) -> Result<usize> {
+    ensure!(len > 0, "No chunks in manifest.");
+    Ok(rand::thread_rng().gen_range(0, len))
+}
@@ -5,7 +5,9 @@
 use crate::{
     backup_types::{
         epoch_ending::restore::EpochHistoryRestoreController,
-        state_snapshot::restore::{StateSnapshotRestoreController, StateSnapshotRestoreOpt},
+        state_snapshot::restore::{
+            StateSnapshotRestoreController, StateSnapshotRestoreOpt, StateSnapshotSubsetOpt,
+        },
         transaction::restore::TransactionRestoreBatchController,
     },
     metadata,
@@ -133,6 +135,7 @@ impl VerifyCoordinator {
                     version: backup.version,
                     validate_modules: self.validate_modules,
                     restore_mode: StateSnapshotRestoreMode::Default,
+                    subset: StateSnapshotSubsetOpt::default(),
                 },
                 global_opt.clone(),
                 Arc::clone(&self.storage),
@@ -2,7 +2,7 @@
 // Parts of the project are originally copyright © Meta Platforms, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use aptos_push_metrics::{register_int_gauge, IntGauge};
+use aptos_push_metrics::{register_int_counter_vec, register_int_gauge, IntCounterVec, IntGauge};
 use once_cell::sync::Lazy;
 
 pub static VERIFY_EPOCH_ENDING_EPOCH: Lazy<IntGauge> = Lazy::new(|| {
@@ -76,3 +76,28 @@ pub static VERIFY_COORDINATOR_FAIL_TS: Lazy<IntGauge> = Lazy::new(|| {
     )
     .unwrap()
 });
+
+pub static VERIFY_SPOT_CHECK_CHUNKS: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "aptos_db_backup_verify_spot_check_chunks",
+        "Number of backup chunks spot-checked, by backup type and result.",
+        &["backup_type", "result"]
+    )
+    .unwrap()
+});
+
+pub static VERIFY_SPOT_CHECK_ROUND_TS: Lazy<IntGauge> = Lazy::new(|| {
+    register_int_gauge!(
+        "aptos_db_backup_verify_spot_check_round_timestamp_s",
+        "Timestamp when the last spot check round finished."
+    )
+    .unwrap()
+});
+
+pub static VERIFY_SPOT_CHECK_FAIL_TS: Lazy<IntGauge> = Lazy::new(|| {
+    register_int_gauge!(
+        "aptos_db_backup_verify_spot_check_fail_timestamp_s",
+        "Timestamp when a spot check last found a corrupted chunk."
+    )
+    .unwrap()
+});
@@ -0,0 +1,377 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! A storage backend that talks to S3 (or an S3-compatible service), GCS or Azure Blob Storage
+//! natively, without shelling out to the vendor CLIs like the `CommandAdapter` does.
+//!
+//! Large files are uploaded in parts, and failed requests are retried with exponential backoff.
+//! Along with each file, a `.sha256` object holding the SHA-256 of its content is saved, which is
+//! checked when the file is read back to catch corruption in storage or in transit.
+
+#[cfg(test)]
+mod tests;
+
+use super::{BackupHandle, BackupHandleRef, FileHandle, FileHandleRef};
+use crate::storage::{BackupStorage, ShellSafeName, TextLine};
+use anyhow::{bail, ensure, Result};
+use aptos_logger::{info, warn};
+use async_trait::async_trait;
+use clap::Parser;
+use futures::{future::BoxFuture, stream, StreamExt, TryStreamExt};
+use object_store::{
+    aws::AmazonS3Builder, azure::MicrosoftAzureBuilder, buffered::BufWriter,
+    gcp::GoogleCloudStorageBuilder, path::Path, prefix::PrefixStore, BackoffConfig, ObjectStore,
+    RetryConfig,
+};
+use sha2::{Digest, Sha256};
+use std::{
+    future::Future,
+    io,
+    pin::Pin,
+    str::FromStr,
+    sync::Arc,
+    task::{ready, Context, Poll},
+    time::Duration,
+};
+use tokio::io::{AsyncRead, AsyncWrite};
+use tokio_util::io::StreamReader;
+use url::Url;
+
+const DEFAULT_MAX_RETRIES: usize = 10;
+const DEFAULT_RETRY_TIMEOUT_SECS: u64 = 180;
+const DEFAULT_MULTIPART_CHUNK_SIZE: usize = 16 * 1024 * 1024;
+
+#[derive(Parser, Clone, Debug)]
+pub struct CloudStorageOpt {
+    #[clap(
+        long = "url",
+        help = "Location to hold backups, in the form of s3://bucket/prefix, gs://bucket/prefix or \
+        az://container/prefix. Credentials are read from the environment the same way the vendor \
+        SDKs do, e.g. AWS_ACCESS_KEY_ID, GOOGLE_APPLICATION_CREDENTIALS or AZURE_STORAGE_ACCOUNT_NAME."
+    )]
+    pub url: Url,
+    #[clap(
+        long,
+        help = "Endpoint of an S3-compatible service (e.g. MinIO or Cloudflare R2) to use instead \
+        of AWS S3."
+    )]
+    pub s3_endpoint: Option<String>,
+    #[clap(
+        long,
+        help = "Encrypt files at rest with this AWS KMS key (SSE-KMS). S3 only; GCS and Azure \
+        encrypt with the bucket's default key."
+    )]
+    pub sse_kms_key_id: Option<String>,
+    #[clap(
+        long,
+        default_value_t = DEFAULT_MAX_RETRIES,
+        help = "Maximum number of times a failed request is retried."
+    )]
+    pub max_retries: usize,
+    #[clap(
+        long,
+        default_value_t = DEFAULT_RETRY_TIMEOUT_SECS,
+        help = "Give up retrying a request after this many seconds."
+    )]
+    pub retry_timeout_secs: u64,
+    #[clap(
+        long,
+        default_value_t = DEFAULT_MULTIPART_CHUNK_SIZE,
+        help = "Files larger than this many bytes are uploaded in parts of this size."
+    )]
+    pub multipart_chunk_size: usize,
+}
+
+impl FromStr for CloudStorageOpt {
+    type Err = url::ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(CloudStorageOpt {
+            url: Url::parse(s)?,
+            s3_endpoint: None,
+            sse_kms_key_id: None,
+            max_retries: DEFAULT_MAX_RETRIES,
+            retry_timeout_secs: DEFAULT_RETRY_TIMEOUT_SECS,
+            multipart_chunk_size: DEFAULT_MULTIPART_CHUNK_SIZE,
+        })
+    }
+}
+
+/// A storage backend that stores everything under a prefix in a cloud object store.
+pub struct CloudStorage {
+    store: Arc<dyn ObjectStore>,
+    multipart_chunk_size: usize,
+}
+
+impl CloudStorage {
+    const CHECKSUM_SUFFIX: &'static str = ".sha256";
+    const METADATA_BACKUP_DIR: &'static str = "metadata_backup";
+    const METADATA_DIR: &'static str = "metadata";
+
+    pub fn new(store: Arc<dyn ObjectStore>, multipart_chunk_size: usize) -> Self {
+        Self {
+            store,
+            multipart_chunk_size,
+        }
+    }
+
+    pub fn new_with_opt(opt: CloudStorageOpt) -> Result<Self> {
+        let retry = RetryConfig {
+            backoff: BackoffConfig::default(),
+            max_retries: opt.max_retries,
+            retry_timeout: Duration::from_secs(opt.retry_timeout_secs),
+        };
+        ensure!(
+            opt.sse_kms_key_id.is_none() || opt.url.scheme() == "s3",
+            "Server-side encryption with a KMS key is only supported on S3."
+        );
+        let store: Arc<dyn ObjectStore> = match opt.url.scheme() {
+            "s3" => {
+                let mut builder = AmazonS3Builder::from_env()
+                    .with_url(opt.url.as_str())
+                    .with_retry(retry);
+                if let Some(endpoint) = opt.s3_endpoint {
+                    builder = builder
+                        .with_allow_http(endpoint.starts_with("http://"))
+                        .with_endpoint(endpoint);
+                }
+                if let Some(kms_key_id) = opt.sse_kms_key_id {
+                    builder = builder.with_sse_kms_encryption(kms_key_id);
+                }
+                Arc::new(builder.build()?)
+            },
+            "gs" => Arc::new(
+                GoogleCloudStorageBuilder::from_env()
+                    .with_url(opt.url.as_str())
+                    .with_retry(retry)
+                    .build()?,
+            ),
+            "az" | "azure" => Arc::new(
+                MicrosoftAzureBuilder::from_env()
+                    .with_url(opt.url.as_str())
+                    .with_retry(retry)
+                    .build()?,
+            ),
+            scheme => bail!("Unsupported cloud storage scheme: {}", scheme),
+        };
+        let prefix = Path::from(opt.url.path());
+        let store: Arc<dyn ObjectStore> = if prefix.as_ref().is_empty() {
+            store
+        } else {
+            Arc::new(PrefixStore::new(store, prefix))
+        };
+        Ok(Self::new(store, opt.multipart_chunk_size))
+    }
+
+    fn checksum_path(path: &Path) -> Path {
+        Path::from(format!("{}{}", path, Self::CHECKSUM_SUFFIX))
+    }
+
+    /// Returns the expected SHA-256 of the file at `path`, if one was saved along with it.
+    async fn expected_checksum(&self, path: &Path) -> Result<Option<Vec<u8>>> {
+        match self.store.get(&Self::checksum_path(path)).await {
+            Ok(result) => Ok(Some(hex::decode(result.bytes().await?)?)),
+            Err(object_store::Error::NotFound { .. }) => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+}
+
+#[async_trait]
+impl BackupStorage for CloudStorage {
+    async fn create_backup(&self, name: &ShellSafeName) -> Result<BackupHandle> {
+        // There are no directories in object stores, files are simply put under the name.
+        Ok(name.to_string())
+    }
+
+    async fn create_for_write(
+        &self,
+        backup_handle: &BackupHandleRef,
+        name: &ShellSafeName,
+    ) -> Result<(FileHandle, Box<dyn AsyncWrite + Send + Unpin>)> {
+        let path = Path::from(backup_handle).child(name.as_ref());
+        let writer = ChecksumWriter {
+            inner: Box::new(BufWriter::with_capacity(
+                self.store.clone(),
+                path.clone(),
+                self.multipart_chunk_size,
+            )),
+            hasher: Some(Sha256::new()),
+            save_checksum: None,
+            store: self.store.clone(),
+            checksum_path: Self::checksum_path(&path),
+        };
+        Ok((path.to_string(), Box::new(writer)))
+    }
+
+    async fn open_for_read(
+        &self,
+        file_handle: &FileHandleRef,
+    ) -> Result<Box<dyn AsyncRead + Send + Unpin>> {
+        let path = Path::from(file_handle);
+        let expected_checksum = self.expected_checksum(&path).await?;
+        if expected_checksum.is_none() {
+            warn!("No checksum found for {}, skipping verification.", path);
+        }
+        let content = self
+            .store
+            .get(&path)
+            .await?
+            .into_stream()
+            .map_err(|e| io::Error::new(io::ErrorKind::Other, e));
+        Ok(Box::new(StreamReader::new(verify_checksum(
+            path.to_string(),
+            content,
+            expected_checksum,
+        ))))
+    }
+
+    async fn list_metadata_files(&self) -> Result<Vec<FileHandle>> {
+        let dir = Path::from(Self::METADATA_DIR);
+        self.store
+            .list(Some(&dir))
+            .map_ok(|meta| meta.location.to_string())
+            .try_filter(|handle| futures::future::ready(!handle.ends_with(Self::CHECKSUM_SUFFIX)))
+            .try_collect()
+            .await
+            .map_err(Into::into)
+    }
+
+    async fn backup_metadata_file(&self, file_handle: &FileHandleRef) -> Result<()> {
+        let path = Path::from(file_handle);
+        let name = match path.filename() {
+            Some(name) => name.to_string(),
+            None => bail!("cannot extract filename from {}", file_handle),
+        };
+        let backup_path = Path::from(Self::METADATA_BACKUP_DIR).child(name);
+
+        if let Err(e) = self
+            .store
+            .rename(
+                &Self::checksum_path(&path),
+                &Self::checksum_path(&backup_path),
+            )
+            .await
+        {
+            if !matches!(e, object_store::Error::NotFound { .. }) {
+                return Err(e.into());
+            }
+        }
+        self.store.rename(&path, &backup_path).await?;
+
+        Ok(())
+    }
+
+    async fn save_metadata_lines(
+        &self,
+        name: &ShellSafeName,
+        lines: &[TextLine],
+    ) -> Result<FileHandle> {
+        let path = Path::from(Self::METADATA_DIR).child(name.as_ref());
+        match self.store.head(&path).await {
+            Ok(_) => {
+                info!("File {} already exists, Skip", name.as_ref());
+            },
+            Err(object_store::Error::NotFound { .. }) => {
+                let content = lines
+                    .iter()
+                    .map(|e| e.as_ref())
+                    .collect::<Vec<&str>>()
+                    .join("");
+                let checksum = hex::encode(Sha256::digest(content.as_bytes()));
+                self.store
+                    .put(&Self::checksum_path(&path), checksum.into())
+                    .await?;
+                self.store.put(&path, content.into()).await?;
+            },
+            Err(e) => return Err(e.into()),
+        }
+        Ok(path.to_string())
+    }
+}
+
+/// Uploads a file, then saves the SHA-256 of its content next to it on shutdown.
+struct ChecksumWriter {
+    inner: Box<BufWriter>,
+    hasher: Option<Sha256>,
+    save_checksum: Option<BoxFuture<'static, io::Result<()>>>,
+    store: Arc<dyn ObjectStore>,
+    checksum_path: Path,
+}
+
+impl AsyncWrite for ChecksumWriter {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        let written = ready!(Pin::new(&mut *self.inner).poll_write(cx, buf))?;
+        if let Some(hasher) = self.hasher.as_mut() {
+            hasher.update(&buf[..written]);
+        }
+        Poll::Ready(Ok(written))
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut *self.inner).poll_flush(cx)
+    }
+
+    fn poll_shutdown(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        if self.save_checksum.is_none() {
+            ready!(Pin::new(&mut *self.inner).poll_shutdown(cx))?;
+            let checksum = match self.hasher.take() {
+                Some(hasher) => hex::encode(hasher.finalize()),
+                None => return Poll::Ready(Ok(())),
+            };
+            let store = self.store.clone();
+            let checksum_path = self.checksum_path.clone();
+            self.save_checksum = Some(Box::pin(async move {
+                store
+                    .put(&checksum_path, checksum.into())
+                    .await
+                    .map(|_| ())
+                    .map_err(|e| io::Error::new(io::ErrorKind::Other, e))
+            }));
+        }
+        let result = ready!(self.save_checksum.as_mut().unwrap().as_mut().poll(cx));
+        self.save_checksum = None;
+        Poll::Ready(result)
+    }
+}
+
+/// Passes the content through, failing at the end of it if its SHA-256 doesn't match the expected
+/// one.
+fn verify_checksum(
+    file: String,
+    content: impl futures::Stream<Item = io::Result<bytes::Bytes>> + Send + Unpin + 'static,
+    expected_checksum: Option<Vec<u8>>,
+) -> impl futures::Stream<Item = io::Result<bytes::Bytes>> + Send + Unpin + 'static {
+    let state = Some((content, Sha256::new()));
+    stream::unfold(state, move |state| {
+        let file = file.clone();
+        let expected_checksum = expected_checksum.clone();
+        async move {
+            let (mut content, mut hasher) = state?;
+            match content.next().await {
+                Some(Ok(bytes)) => {
+                    hasher.update(&bytes);
+                    Some((Ok(bytes), Some((content, hasher))))
+                },
+                Some(Err(e)) => Some((Err(e), None)),
+                None => match expected_checksum {
+                    Some(expected) if hasher.finalize().as_slice() != expected.as_slice() => {
+                        Some((
+                            Err(io::Error::new(
+                                io::ErrorKind::InvalidData,
+                                format!("Checksum mismatch for {}", file),
+                            )),
+                            None,
+                        ))
+                    },
+                    _ => None,
+                },
+            }
+        }
+    })
+    .boxed()
+}
@@ -0,0 +1,75 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::storage::test_util::{
+    arb_backups, arb_metadata_files, test_save_and_list_metadata_files_impl,
+    test_write_and_read_impl,
+};
+use object_store::memory::InMemory;
+use proptest::prelude::*;
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    runtime::Runtime,
+};
+
+fn in_memory_storage() -> (Arc<dyn ObjectStore>, CloudStorage) {
+    let store: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
+    // Small parts, so that multipart uploads are exercised.
+    (store.clone(), CloudStorage::new(store, 128))
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(10))]
+
+    #[test]
+    fn test_write_and_read(
+        backups in arb_backups()
+    ) {
+        let (_, storage) = in_memory_storage();
+
+        let rt = Runtime::new().unwrap();
+        rt.block_on(test_write_and_read_impl(Box::new(storage), backups));
+    }
+
+    #[test]
+    fn test_save_list_metadata_files(
+        input in arb_metadata_files(),
+    ) {
+        let (_, storage) = in_memory_storage();
+
+        let rt = Runtime::new().unwrap();
+        rt.block_on(test_save_and_list_metadata_files_impl(Box::new(storage), input));
+    }
+}
+
+#[tokio::test]
+async fn test_verify_checksum_on_read() {
+    let (store, storage) = in_memory_storage();
+    let backup_handle = storage
+        .create_backup(&"backup".parse().unwrap())
+        .await
+        .unwrap();
+    let (file_handle, mut file) = storage
+        .create_for_write(&backup_handle, &"file".parse().unwrap())
+        .await
+        .unwrap();
+    file.write_all(&[1; 1000]).await.unwrap();
+    file.shutdown().await.unwrap();
+
+    // Corrupt the file behind the storage's back.
+    store
+        .put(&Path::from(file_handle.as_str()), vec![2; 1000].into())
+        .await
+        .unwrap();
+
+    let mut buf = Vec::new();
+    let err = storage
+        .open_for_read(&file_handle)
+        .await
+        .unwrap()
+        .read_to_end(&mut buf)
+        .await
+        .unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+}
@@ -2,6 +2,7 @@
 // Parts of the project are originally copyright © Meta Platforms, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+pub mod cloud_storage;
 pub mod command_adapter;
 pub mod local_fs;
 
@@ -11,6 +12,7 @@ mod test_util;
 mod tests;
 
 use crate::storage::{
+    cloud_storage::{CloudStorage, CloudStorageOpt},
     command_adapter::{CommandAdapter, CommandAdapterOpt},
     local_fs::{LocalFs, LocalFsOpt},
 };
@@ -198,6 +200,12 @@ pub enum StorageOpt {
     https://github.com/aptos-labs/aptos-core/tree/main/storage/backup/backup-cli/src/storage/command_adapter/sample_configs/"
     )]
     CommandAdapter(CommandAdapterOpt),
+    #[clap(
+        about = "Select the CloudStorage backup storage type, which talks to S3 (or an \
+    S3-compatible service), GCS or Azure Blob Storage directly, verifying the integrity of files \
+    on read."
+    )]
+    CloudStorage(CloudStorageOpt),
 }
 
 impl StorageOpt {
@@ -205,6 +213,7 @@ impl StorageOpt {
         Ok(match self {
             StorageOpt::LocalFs(opt) => Arc::new(LocalFs::new_with_opt(opt)),
             StorageOpt::CommandAdapter(opt) => Arc::new(CommandAdapter::new_with_opt(opt).await?),
+            StorageOpt::CloudStorage(opt) => Arc::new(CloudStorage::new_with_opt(opt)?),
         })
     }
 }
@@ -213,7 +222,7 @@ impl StorageOpt {
 #[clap(group(
     ArgGroup::new("storage")
     .required(true)
-    .args(&["local_fs_dir", "command_adapter_config"]),
+    .args(&["local_fs_dir", "command_adapter_config", "cloud_storage_url"]),
 ))]
 pub struct DBToolStorageOpt {
     #[clap(
@@ -229,12 +238,21 @@ pub struct DBToolStorageOpt {
     https://github.com/aptos-labs/aptos-networks/tree/main/testnet/backups "
     )]
     command_adapter_config: Option<CommandAdapterOpt>,
+    #[clap(
+        long,
+        help = "Select the CloudStorage backup storage type, pointing at a location like \
+    s3://bucket/prefix, gs://bucket/prefix or az://container/prefix. Credentials are read from the \
+    environment."
+    )]
+    cloud_storage_url: Option<CloudStorageOpt>,
 }
 
 impl DBToolStorageOpt {
     pub async fn init_storage(self) -> Result<Arc<dyn BackupStorage>> {
         Ok(if self.local_fs_dir.is_some() {
             Arc::new(LocalFs::new_with_opt(self.local_fs_dir.unwrap()))
+        } else if self.cloud_storage_url.is_some() {
+            Arc::new(CloudStorage::new_with_opt(self.cloud_storage_url.unwrap())?)
         } else {
             Arc::new(CommandAdapter::new_with_opt(self.command_adapter_config.unwrap()).await?)
         })
@@ -21,7 +21,8 @@ use aptos_db::{
     db::AptosDB,
     get_restore_handler::GetRestoreHandler,
     state_restore::{
-        StateSnapshotRestore, StateSnapshotRestoreMode, StateValueBatch, StateValueWriter,
+        KeyFilter, StateSnapshotRestore, StateSnapshotRestoreMode, StateValueBatch,
+        StateValueWriter,
     },
 };
 use aptos_db_indexer_schemas::metadata::StateSnapshotProgress;
@@ -241,6 +242,26 @@ impl RestoreRunMode {
         }
     }
 
+    pub fn get_state_subset_restore_receiver(
+        &self,
+        version: Version,
+        expected_root_hash: HashValue,
+        kv_filter: KeyFilter<StateKey>,
+    ) -> Result<StateSnapshotRestore<StateKey, StateValue>> {
+        match self {
+            Self::Restore { restore_handler } => restore_handler.get_state_subset_restore_receiver(
+                version,
+                expected_root_hash,
+                kv_filter,
+            ),
+            Self::Verify => self.get_state_restore_receiver(
+                version,
+                expected_root_hash,
+                StateSnapshotRestoreMode::Default,
+            ),
+        }
+    }
+
     pub fn finish(&self) {
         match self {
             Self::Restore { restore_handler } => {
@@ -11,6 +11,7 @@ use aptos_backup_cli::{
     },
     coordinators::{
         backup::{BackupCoordinator, BackupCoordinatorOpt},
+        spot_check::{SpotCheckCoordinator, SpotCheckCoordinatorOpt},
         verify::VerifyCoordinator,
     },
     metadata::{cache, cache::MetadataCacheOpt},
@@ -41,6 +42,11 @@ pub enum Command {
     Query(OneShotQueryType),
     #[clap(about = "verify the backup through restoring with the backup files")]
     Verify(VerifyOpt),
+    #[clap(
+        about = "Run continuously, verifying random chunks of the existing backups against their \
+    proofs to detect corruption."
+    )]
+    SpotCheck(SpotCheckOpt),
 }
 
 #[derive(Parser)]
@@ -165,6 +171,16 @@ pub struct VerifyOpt {
     output_transaction_analysis: Option<PathBuf>,
 }
 
+#[derive(Parser)]
+pub struct SpotCheckOpt {
+    #[clap(flatten)]
+    coordinator: SpotCheckCoordinatorOpt,
+    #[clap(flatten)]
+    storage: DBToolStorageOpt,
+    #[clap(flatten)]
+    concurrent_downloads: ConcurrentDownloadsOpt,
+}
+
 impl Command {
     pub async fn run(self) -> Result<()> {
         match self {
@@ -250,6 +266,15 @@ impl Command {
                 .run()
                 .await?
             },
+            Command::SpotCheck(opt) => {
+                SpotCheckCoordinator::new(
+                    opt.coordinator,
+                    opt.storage.init_storage().await?,
+                    opt.concurrent_downloads.get(),
+                )?
+                .run()
+                .await?
+            },
         }
         Ok(())
     }
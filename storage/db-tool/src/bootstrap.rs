@@ -8,7 +8,7 @@ use aptos_config::config::{
     DEFAULT_MAX_NUM_NODES_PER_LRU_CACHE_SHARD, NO_OP_STORAGE_PRUNER_CONFIG,
 };
 use aptos_db::AptosDB;
-use aptos_executor::db_bootstrapper::calculate_genesis;
+use aptos_executor::db_bootstrapper::{calculate_genesis, GenesisDryRunReport};
 use aptos_storage_interface::DbReaderWriter;
 use aptos_types::{transaction::Transaction, waypoint::Waypoint};
 use aptos_vm::aptos_vm::AptosVMBlockExecutor;
@@ -36,6 +36,15 @@ pub struct Command {
 
     #[clap(long, requires("waypoint_to_verify"))]
     commit: bool,
+
+    /// Report the state keys the genesis would change, the resulting state root hash and the
+    /// state storage usage delta. The DB is opened read-only and nothing is committed.
+    #[clap(long, conflicts_with("commit"))]
+    dry_run: bool,
+
+    /// Max number of changed state keys to print in the dry run report.
+    #[clap(long, default_value_t = 1000, requires("dry_run"))]
+    max_keys_to_print: usize,
 }
 
 impl Command {
@@ -48,10 +57,10 @@ impl Command {
         );
 
         // Opening the DB exclusively, it's not allowed to run this tool alongside a running node which
-        // operates on the same DB.
+        // operates on the same DB, unless it's a dry run.
         let db = AptosDB::open(
             StorageDirPaths::from_path(&self.db_dir),
-            false,
+            self.dry_run,                /* readonly */
             NO_OP_STORAGE_PRUNER_CONFIG, /* pruner */
             RocksdbConfigs::default(),
             false, /* indexer */
@@ -76,6 +85,7 @@ impl Command {
             )
         }
 
+        let base_state = ledger_summary.state.latest().clone();
         let committer =
             calculate_genesis::<AptosVMBlockExecutor>(&db, ledger_summary, &genesis_txn)
                 .with_context(|| format_err!("Failed to calculate genesis."))?;
@@ -84,6 +94,13 @@ impl Command {
             committer.waypoint()
         );
 
+        if self.dry_run {
+            let report = committer
+                .dry_run_report(db.reader.clone(), base_state)
+                .with_context(|| format_err!("Failed to generate the dry run report."))?;
+            print_dry_run_report(&report, self.max_keys_to_print);
+        }
+
         if let Some(waypoint) = self.waypoint_to_verify {
             ensure!(
                 waypoint == committer.waypoint(),
@@ -105,6 +122,28 @@ impl Command {
     }
 }
 
+fn print_dry_run_report(report: &GenesisDryRunReport, max_keys_to_print: usize) {
+    println!("Genesis version: {}", report.version);
+    println!("{} state keys would change:", report.state_changes.len());
+    for (key, change) in report.state_changes.iter().take(max_keys_to_print) {
+        println!("  {:?} {:?}", change, key);
+    }
+    if report.state_changes.len() > max_keys_to_print {
+        println!(
+            "  ... and {} more.",
+            report.state_changes.len() - max_keys_to_print
+        );
+    }
+    println!("Resulting state root hash: {}", report.state_root_hash);
+    println!(
+        "State storage usage: {:?} -> {:?} (items {:+}, bytes {:+})",
+        report.usage_before,
+        report.usage_after,
+        report.items_delta(),
+        report.bytes_delta(),
+    );
+}
+
 fn load_genesis_txn(path: &Path) -> Result<Transaction> {
     let mut file = File::open(path)?;
     let mut buffer = vec![];
@@ -0,0 +1,446 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::{ensure, format_err, Result};
+use aptos_config::config::{
+    RocksdbConfigs, StorageDirPaths, BUFFERED_STATE_TARGET_ITEMS,
+    DEFAULT_MAX_NUM_NODES_PER_LRU_CACHE_SHARD, NO_OP_STORAGE_PRUNER_CONFIG,
+};
+use aptos_db::{backup::backup_handler::BackupHandler, AptosDB};
+use aptos_resource_viewer::AptosValueAnnotator;
+use aptos_storage_interface::{
+    state_store::state_view::db_state_view::{DbStateView, DbStateViewAtVersion},
+    DbReader,
+};
+use aptos_types::{
+    access_path::Path,
+    account_address::AccountAddress,
+    state_store::{
+        state_key::{
+            inner::{StateKeyInner, StateKeyTag},
+            prefix::StateKeyPrefix,
+            StateKey,
+        },
+        state_value::StateValue,
+        table::TableHandle,
+        TStateView,
+    },
+    transaction::Version,
+};
+use clap::Parser;
+use move_core_types::language_storage::{StructTag, TypeTag};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    io::{self, BufRead, Write},
+    path::PathBuf,
+    sync::Arc,
+};
+
+#[derive(Parser)]
+#[clap(
+    about = "Open a DB read-only and interactively explore its state, e.g., to debug a stuck node."
+)]
+pub struct Command {
+    #[clap(long, value_parser)]
+    db_dir: PathBuf,
+
+    /// The version to explore initially. Defaults to the latest version in the DB.
+    #[clap(long)]
+    version: Option<Version>,
+}
+
+impl Command {
+    pub fn run(self) -> Result<()> {
+        let db = AptosDB::open(
+            StorageDirPaths::from_path(&self.db_dir),
+            true, /* readonly */
+            NO_OP_STORAGE_PRUNER_CONFIG,
+            RocksdbConfigs::default(),
+            false, /* indexer */
+            BUFFERED_STATE_TARGET_ITEMS,
+            DEFAULT_MAX_NUM_NODES_PER_LRU_CACHE_SHARD,
+            None,
+        )?;
+        let backup_handler = db.get_backup_handler();
+        let reader: Arc<dyn DbReader> = Arc::new(db);
+
+        let mut explorer = Explorer::new(reader, backup_handler)?;
+        if let Some(version) = self.version {
+            explorer.set_version(version)?;
+        }
+        println!(
+            "Exploring the DB at version {}. Type `help` for the commands.",
+            explorer.version
+        );
+
+        let mut lines = io::stdin().lock().lines();
+        loop {
+            print!("explore@{}> ", explorer.version);
+            io::stdout().flush()?;
+            let Some(line) = lines.next().transpose()? else {
+                break;
+            };
+            let words = line.split_whitespace();
+            if words.clone().next().is_none() {
+                continue;
+            }
+            match ReplCommand::try_parse_from(words) {
+                Ok(ReplCommand::Exit) => break,
+                Ok(command) => {
+                    if let Err(err) = explorer.execute(command) {
+                        println!("Error: {:#}", err);
+                    }
+                },
+                Err(err) => {
+                    let _ = err.print();
+                },
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// The commands available in the REPL
+#[derive(Parser)]
+#[clap(no_binary_name = true, disable_version_flag = true)]
+enum ReplCommand {
+    /// Show the explored version, or switch to another version.
+    Version { version: Option<Version> },
+
+    /// List the accounts with state in the latest state snapshot at or before the explored
+    /// version, in the order of the state key hashes.
+    Accounts {
+        /// The index of the state item to start scanning from.
+        #[clap(long, default_value_t = 0)]
+        start: usize,
+
+        #[clap(long, default_value_t = 20)]
+        limit: usize,
+    },
+
+    /// List the resources, resource groups and modules of an account.
+    Resources { address: AccountAddress },
+
+    /// Decode a resource (or resource group member) with the ABIs on the DB.
+    Resource {
+        address: AccountAddress,
+        /// The resource type, e.g. 0x1::account::Account.
+        tag: StructTag,
+    },
+
+    /// List the items of a table, decoded if the key and value types are provided.
+    Table {
+        handle: TableHandle,
+
+        /// The type of the table keys, e.g. address.
+        #[clap(long)]
+        key_type: Option<TypeTag>,
+
+        /// The type of the table values, e.g. u64.
+        #[clap(long)]
+        value_type: Option<TypeTag>,
+
+        #[clap(long, default_value_t = 20)]
+        limit: usize,
+    },
+
+    /// Show how the state of an account changed between two versions.
+    Diff {
+        address: AccountAddress,
+        from_version: Version,
+        /// Defaults to the explored version.
+        to_version: Option<Version>,
+    },
+
+    /// Exit the REPL.
+    #[clap(alias = "quit")]
+    Exit,
+}
+
+struct Explorer {
+    reader: Arc<dyn DbReader>,
+    backup_handler: BackupHandler,
+    /// The version explored by the commands
+    version: Version,
+}
+
+impl Explorer {
+    fn new(reader: Arc<dyn DbReader>, backup_handler: BackupHandler) -> Result<Self> {
+        let version = reader
+            .get_synced_version()?
+            .ok_or_else(|| format_err!("DB is empty."))?;
+        Ok(Self {
+            reader,
+            backup_handler,
+            version,
+        })
+    }
+
+    fn set_version(&mut self, version: Version) -> Result<()> {
+        self.ensure_version_exists(version)?;
+        self.version = version;
+        Ok(())
+    }
+
+    fn ensure_version_exists(&self, version: Version) -> Result<()> {
+        let synced_version = self.reader.expect_synced_version();
+        ensure!(
+            version <= synced_version,
+            "Version {} is after the latest version in the DB ({}).",
+            version,
+            synced_version,
+        );
+        Ok(())
+    }
+
+    fn execute(&mut self, command: ReplCommand) -> Result<()> {
+        match command {
+            ReplCommand::Version { version } => {
+                if let Some(version) = version {
+                    self.set_version(version)?;
+                }
+                println!("{}", self.version);
+            },
+            ReplCommand::Accounts { start, limit } => self.list_accounts(start, limit)?,
+            ReplCommand::Resources { address } => self.list_resources(address)?,
+            ReplCommand::Resource { address, tag } => self.show_resource(address, &tag)?,
+            ReplCommand::Table {
+                handle,
+                key_type,
+                value_type,
+                limit,
+            } => self.walk_table(handle, key_type.as_ref(), value_type.as_ref(), limit)?,
+            ReplCommand::Diff {
+                address,
+                from_version,
+                to_version,
+            } => self.diff(address, from_version, to_version.unwrap_or(self.version))?,
+            ReplCommand::Exit => {},
+        }
+        Ok(())
+    }
+
+    fn state_view(&self, version: Version) -> Result<DbStateView> {
+        Ok(self.reader.state_view_at_version(Some(version))?)
+    }
+
+    /// Returns the latest version at or before the given one with a state snapshot, which is
+    /// required to scan the state
+    fn snapshot_version(&self, version: Version) -> Result<Version> {
+        self.reader
+            .get_state_snapshot_before(version + 1)?
+            .map(|(snapshot_version, _)| snapshot_version)
+            .ok_or_else(|| format_err!("No state snapshot at or before version {}.", version))
+    }
+
+    fn list_accounts(&self, start: usize, limit: usize) -> Result<()> {
+        let snapshot_version = self.snapshot_version(self.version)?;
+        let num_items = self.backup_handler.get_state_item_count(snapshot_version)?;
+        println!(
+            "{} state items at snapshot version {}.",
+            num_items, snapshot_version
+        );
+
+        let mut accounts = BTreeSet::new();
+        let mut next_index = start;
+        for item in self.backup_handler.get_state_item_iter(
+            snapshot_version,
+            start,
+            num_items.saturating_sub(start),
+        )? {
+            let (state_key, _) = item?;
+            next_index += 1;
+            if let StateKeyInner::AccessPath(access_path) = state_key.inner() {
+                if accounts.insert(access_path.address) {
+                    println!("{}", access_path.address);
+                }
+            }
+            if accounts.len() >= limit {
+                break;
+            }
+        }
+        if next_index < num_items {
+            println!("More with `accounts --start {}`.", next_index);
+        }
+        Ok(())
+    }
+
+    fn list_resources(&self, address: AccountAddress) -> Result<()> {
+        let state_view = self.state_view(self.version)?;
+        for state_key in self.scan_state_keys(&StateKeyPrefix::from(address), self.version)? {
+            let StateKeyInner::AccessPath(access_path) = state_key.inner() else {
+                continue;
+            };
+            let Some(state_value) = state_view.get_state_value(&state_key)? else {
+                continue;
+            };
+            let size = state_value.bytes().len();
+            match access_path.get_path() {
+                Path::Resource(tag) => println!("resource {} ({} bytes)", tag, size),
+                Path::ResourceGroup(tag) => {
+                    println!("resource group {} ({} bytes)", tag, size);
+                    let members: BTreeMap<StructTag, Vec<u8>> =
+                        bcs::from_bytes(state_value.bytes())?;
+                    for (member_tag, member_bytes) in members {
+                        println!("  member {} ({} bytes)", member_tag, member_bytes.len());
+                    }
+                },
+                Path::Code(module_id) => println!("module {} ({} bytes)", module_id, size),
+            }
+        }
+        Ok(())
+    }
+
+    fn show_resource(&self, address: AccountAddress, tag: &StructTag) -> Result<()> {
+        let state_view = self.state_view(self.version)?;
+        let annotator = AptosValueAnnotator::new(&state_view);
+        let bytes = match annotator.view_resource_group_member(tag) {
+            Some(group_tag) => state_view
+                .get_state_value(&StateKey::resource_group(&address, &group_tag))?
+                .map(|group| bcs::from_bytes::<BTreeMap<StructTag, Vec<u8>>>(group.bytes()))
+                .transpose()?
+                .and_then(|mut members| members.remove(tag)),
+            None => state_view
+                .get_state_value(&StateKey::resource(&address, tag)?)?
+                .map(|state_value| state_value.bytes().to_vec()),
+        };
+        match bytes {
+            Some(bytes) => println!("{}", annotator.view_resource(tag, &bytes)?),
+            None => println!("Resource {} doesn't exist at {}.", tag, address),
+        }
+        Ok(())
+    }
+
+    fn walk_table(
+        &self,
+        handle: TableHandle,
+        key_type: Option<&TypeTag>,
+        value_type: Option<&TypeTag>,
+        limit: usize,
+    ) -> Result<()> {
+        let state_view = self.state_view(self.version)?;
+        let annotator = AptosValueAnnotator::new(&state_view);
+        let decode = |type_tag: Option<&TypeTag>, bytes: &[u8]| -> Result<String> {
+            Ok(match type_tag {
+                Some(type_tag) => annotator.view_value(type_tag, bytes)?.to_string(),
+                None => format!("0x{}", hex::encode(bytes)),
+            })
+        };
+
+        let prefix = StateKeyPrefix::new(StateKeyTag::TableItem, handle.0.to_vec());
+        let mut num_items = 0;
+        for state_key in self.scan_state_keys(&prefix, self.version)? {
+            let StateKeyInner::TableItem { key, .. } = state_key.inner() else {
+                continue;
+            };
+            let Some(state_value) = state_view.get_state_value(&state_key)? else {
+                continue;
+            };
+            if num_items >= limit {
+                println!("More items omitted, increase `--limit` to see them.");
+                break;
+            }
+            println!(
+                "{} => {}",
+                decode(key_type, key)?,
+                decode(value_type, state_value.bytes())?
+            );
+            num_items += 1;
+        }
+        Ok(())
+    }
+
+    fn diff(
+        &self,
+        address: AccountAddress,
+        from_version: Version,
+        to_version: Version,
+    ) -> Result<()> {
+        self.ensure_version_exists(from_version)?;
+        self.ensure_version_exists(to_version)?;
+        let prefix = StateKeyPrefix::from(address);
+        let state_keys: BTreeSet<_> = self
+            .scan_state_keys(&prefix, from_version)?
+            .into_iter()
+            .chain(self.scan_state_keys(&prefix, to_version)?)
+            .collect();
+
+        let from_state_view = self.state_view(from_version)?;
+        let to_state_view = self.state_view(to_version)?;
+        let from_annotator = AptosValueAnnotator::new(&from_state_view);
+        let to_annotator = AptosValueAnnotator::new(&to_state_view);
+        for state_key in state_keys {
+            let from_value = from_state_view.get_state_value(&state_key)?;
+            let to_value = to_state_view.get_state_value(&state_key)?;
+            if from_value.as_ref().map(StateValue::bytes)
+                == to_value.as_ref().map(StateValue::bytes)
+            {
+                continue;
+            }
+            let StateKeyInner::AccessPath(access_path) = state_key.inner() else {
+                continue;
+            };
+            let path = access_path.get_path();
+            let (marker, label) = match (&from_value, &to_value) {
+                (None, Some(_)) => ("+", "created"),
+                (Some(_), None) => ("-", "deleted"),
+                _ => ("~", "modified"),
+            };
+            println!("{} {} ({})", marker, path, label);
+
+            // Show the resources before and after the change
+            if let Path::Resource(tag) = &path {
+                if let Some(from_value) = &from_value {
+                    println!(
+                        "  before: {}",
+                        from_annotator.view_resource(tag, from_value.bytes())?
+                    );
+                }
+                if let Some(to_value) = &to_value {
+                    println!(
+                        "  after: {}",
+                        to_annotator.view_resource(tag, to_value.bytes())?
+                    );
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the state keys with the given prefix at the given version. Sharded DBs don't
+    /// support iterating by prefix, so the latest state snapshot at or before the version is
+    /// scanned instead (which misses the keys created after the snapshot).
+    fn scan_state_keys(&self, prefix: &StateKeyPrefix, version: Version) -> Result<Vec<StateKey>> {
+        if let Ok(iter) = self
+            .reader
+            .get_prefixed_state_value_iterator(prefix, None, version)
+        {
+            return iter.map(|item| Ok(item?.0)).collect::<Result<Vec<_>>>();
+        }
+
+        let snapshot_version = self.snapshot_version(version)?;
+        let num_items = self.backup_handler.get_state_item_count(snapshot_version)?;
+        println!(
+            "Scanning {} state items at snapshot version {}...",
+            num_items, snapshot_version
+        );
+        let mut state_keys = vec![];
+        for item in self
+            .backup_handler
+            .get_state_item_iter(snapshot_version, 0, num_items)?
+        {
+            let (state_key, _) = item?;
+            if prefix.is_prefix(&state_key)? {
+                state_keys.push(state_key);
+            }
+        }
+        Ok(state_keys)
+    }
+}
+
+#[test]
+fn verify_repl_commands() {
+    use clap::CommandFactory;
+    ReplCommand::command().debug_assert()
+}
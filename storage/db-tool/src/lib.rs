@@ -6,8 +6,11 @@ extern crate core;
 mod backup;
 mod backup_maintenance;
 mod bootstrap;
+mod explore;
 mod gen_replay_verify_jobs;
+mod migrate_resource_group;
 mod replay_on_archive;
+mod replay_range;
 mod replay_verify;
 pub mod restore;
 #[cfg(test)]
@@ -33,14 +36,20 @@ pub enum DBTool {
     #[clap(subcommand)]
     Debug(db_debugger::Cmd),
 
+    Explore(explore::Command),
+
     ReplayVerify(replay_verify::Opt),
 
     GenReplayVerifyJobs(gen_replay_verify_jobs::Opt),
 
+    MigrateResourceGroup(migrate_resource_group::Command),
+
     #[clap(subcommand)]
     Restore(restore::Command),
 
     ReplayOnArchive(replay_on_archive::Opt),
+
+    ReplayRange(replay_range::Opt),
 }
 
 impl DBTool {
@@ -50,14 +59,17 @@ impl DBTool {
             DBTool::BackupMaintenance(cmd) => cmd.run().await,
             DBTool::Bootstrap(cmd) => cmd.run(),
             DBTool::Debug(cmd) => Ok(cmd.run()?),
+            DBTool::Explore(cmd) => cmd.run(),
             DBTool::ReplayVerify(cmd) => {
                 let ret = cmd.run().await;
                 info!("Replay verify result: {:?}", ret);
                 ret
             },
             DBTool::GenReplayVerifyJobs(cmd) => cmd.run().await,
+            DBTool::MigrateResourceGroup(cmd) => cmd.run(),
             DBTool::Restore(cmd) => cmd.run().await,
             DBTool::ReplayOnArchive(cmd) => cmd.run().await,
+            DBTool::ReplayRange(cmd) => cmd.run(),
         }
     }
 }
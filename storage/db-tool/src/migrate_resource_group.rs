@@ -0,0 +1,470 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::{bail, ensure, format_err, Result};
+use aptos_config::config::{
+    RocksdbConfigs, StorageDirPaths, BUFFERED_STATE_TARGET_ITEMS,
+    DEFAULT_MAX_NUM_NODES_PER_LRU_CACHE_SHARD, NO_OP_STORAGE_PRUNER_CONFIG,
+};
+use aptos_db::AptosDB;
+use aptos_storage_interface::{
+    state_store::state_view::db_state_view::DbStateViewAtVersion, DbReader,
+};
+use aptos_types::{
+    access_path::Path,
+    account_address::AccountAddress,
+    account_config::NEW_EPOCH_EVENT_V2_MOVE_TYPE_TAG,
+    contract_event::ContractEvent,
+    on_chain_config::{ConfigurationResource, OnChainConfig},
+    state_store::{
+        state_key::{inner::StateKeyInner, StateKey},
+        state_value::{StateValue, StateValueMetadata},
+        StateView, TStateView,
+    },
+    timestamp::TimestampResource,
+    transaction::{ChangeSet, Transaction, WriteSetPayload},
+    write_set::{WriteOp, WriteSetMut},
+    CORE_CODE_ADDRESS,
+};
+use bytes::Bytes;
+use clap::{Parser, ValueEnum};
+use move_core_types::language_storage::StructTag;
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    path::PathBuf,
+    sync::Arc,
+};
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, ValueEnum)]
+pub enum Direction {
+    /// Moves the resources from their standalone slots into the group.
+    IntoGroup,
+    /// Moves the resources out of the group into standalone slots.
+    OutOfGroup,
+}
+
+#[derive(Parser)]
+#[clap(
+    about = "Generate a write set that moves resources of all accounts between standalone slots \
+    and a resource group, to be applied with the DB bootstrapper or by governance."
+)]
+pub struct Command {
+    #[clap(long, value_parser)]
+    db_dir: PathBuf,
+
+    /// The resource group, e.g. 0x1::object::ObjectGroup.
+    #[clap(long)]
+    group: StructTag,
+
+    /// The resources to move.
+    #[clap(long, num_args = 1.., required = true)]
+    resources: Vec<StructTag>,
+
+    #[clap(long, value_enum)]
+    direction: Direction,
+
+    /// Number of state items read from the DB at a time while scanning the state.
+    #[clap(long, default_value_t = 100_000)]
+    chunk_size: usize,
+
+    /// Writes the BCS encoded `WriteSetPayload` with the migration only, for governance.
+    #[clap(
+        long,
+        value_parser,
+        required_unless_present = "output_genesis_txn_file"
+    )]
+    output_write_set_file: Option<PathBuf>,
+
+    /// Writes a BCS encoded genesis transaction with the migration that also starts a new
+    /// epoch, to be applied with `aptos-db-bootstrapper`.
+    #[clap(long, value_parser)]
+    output_genesis_txn_file: Option<PathBuf>,
+}
+
+impl Command {
+    pub fn run(self) -> Result<()> {
+        let db = AptosDB::open(
+            StorageDirPaths::from_path(&self.db_dir),
+            true, /* readonly */
+            NO_OP_STORAGE_PRUNER_CONFIG,
+            RocksdbConfigs::default(),
+            false, /* indexer */
+            BUFFERED_STATE_TARGET_ITEMS,
+            DEFAULT_MAX_NUM_NODES_PER_LRU_CACHE_SHARD,
+            None,
+        )?;
+        let backup_handler = db.get_backup_handler();
+        let reader: Arc<dyn DbReader> = Arc::new(db);
+
+        // The write set is applied on top of the latest version, so the state scanned must be
+        // the latest.
+        let version = reader
+            .get_synced_version()?
+            .ok_or_else(|| format_err!("DB is empty."))?;
+        ensure!(
+            reader.get_latest_state_checkpoint_version()? == Some(version),
+            "The latest version {} is not a state checkpoint.",
+            version,
+        );
+        let state_view = reader.state_view_at_version(Some(version))?;
+
+        let migration = ResourceGroupMigration::new(
+            self.group.clone(),
+            self.resources.iter().cloned().collect(),
+            self.direction,
+        );
+
+        // Scan the state for the slots to migrate.
+        let mut accounts = BTreeMap::<AccountAddress, BTreeMap<StructTag, StateValue>>::new();
+        let num_items = backup_handler.get_state_item_count(version)?;
+        for start_idx in (0..num_items).step_by(self.chunk_size) {
+            for item in backup_handler.get_state_item_iter(version, start_idx, self.chunk_size)? {
+                let (state_key, state_value) = item?;
+                if let Some((address, tag)) = migration.source_slot(&state_key) {
+                    accounts
+                        .entry(address)
+                        .or_default()
+                        .insert(tag, state_value);
+                }
+            }
+            println!(
+                "Scanned {}/{} state items.",
+                (start_idx + self.chunk_size).min(num_items),
+                num_items
+            );
+        }
+
+        let mut write_set = WriteSetMut::default();
+        let mut usage_delta = UsageDelta::default();
+        for (address, source_values) in accounts {
+            let write_ops = match self.direction {
+                Direction::IntoGroup => {
+                    let group_value = state_view
+                        .get_state_value(&StateKey::resource_group(&address, &self.group))?;
+                    migration.into_group(&address, source_values, group_value)?
+                },
+                Direction::OutOfGroup => {
+                    let (_, group_value) = source_values
+                        .into_iter()
+                        .next()
+                        .expect("Group slot must have been scanned.");
+                    for tag in &self.resources {
+                        ensure!(
+                            state_view
+                                .get_state_value(&StateKey::resource(&address, tag)?)?
+                                .is_none(),
+                            "Resource {} to move out of the group already exists at {}.",
+                            tag,
+                            address,
+                        );
+                    }
+                    migration.out_of_group(&address, group_value)?
+                },
+            };
+            for (state_key, write_op) in write_ops {
+                usage_delta.add(
+                    &state_key,
+                    state_view.get_state_value(&state_key)?,
+                    &write_op,
+                );
+                write_set.insert((state_key, write_op));
+            }
+        }
+        println!(
+            "Migrating {} slots, state storage usage changes by {} items and {} bytes.",
+            write_set.as_inner_mut().len(),
+            usage_delta.items,
+            usage_delta.bytes,
+        );
+
+        let write_set = write_set.freeze()?;
+        if let Some(path) = self.output_write_set_file {
+            let payload = WriteSetPayload::Direct(ChangeSet::new(write_set.clone(), vec![]));
+            std::fs::write(&path, bcs::to_bytes(&payload)?)?;
+            println!("Write set written to {:?}.", path);
+        }
+        if let Some(path) = self.output_genesis_txn_file {
+            let txn = new_epoch_genesis_txn(&state_view, write_set)?;
+            std::fs::write(&path, bcs::to_bytes(&txn)?)?;
+            println!("Genesis transaction written to {:?}.", path);
+        }
+
+        Ok(())
+    }
+}
+
+/// Computes the write ops that move a set of resources between their standalone slots and a
+/// resource group, one account at a time.
+///
+/// Deposits paid for the slots are conserved: moving resources into a group adds their deposits to
+/// the group, while moving them out leaves the deposits with the group, unless the group is left
+/// empty, in which case they go with the first resource moved out.
+pub struct ResourceGroupMigration {
+    group: StructTag,
+    resources: BTreeSet<StructTag>,
+    direction: Direction,
+}
+
+impl ResourceGroupMigration {
+    pub fn new(group: StructTag, resources: BTreeSet<StructTag>, direction: Direction) -> Self {
+        Self {
+            group,
+            resources,
+            direction,
+        }
+    }
+
+    /// Returns the account and the tag of the slot, if it's one to migrate.
+    pub fn source_slot(&self, state_key: &StateKey) -> Option<(AccountAddress, StructTag)> {
+        let StateKeyInner::AccessPath(access_path) = state_key.inner() else {
+            return None;
+        };
+        match (self.direction, access_path.get_path()) {
+            (Direction::IntoGroup, Path::Resource(tag)) if self.resources.contains(&tag) => {
+                Some((access_path.address, tag))
+            },
+            (Direction::OutOfGroup, Path::ResourceGroup(tag)) if tag == self.group => {
+                Some((access_path.address, tag))
+            },
+            _ => None,
+        }
+    }
+
+    pub fn into_group(
+        &self,
+        address: &AccountAddress,
+        resources: BTreeMap<StructTag, StateValue>,
+        group_value: Option<StateValue>,
+    ) -> Result<Vec<(StateKey, WriteOp)>> {
+        let mut write_ops = Vec::new();
+        let group_exists = group_value.is_some();
+        let (mut group, mut metadata) = match group_value {
+            Some(value) => (
+                bcs::from_bytes::<BTreeMap<StructTag, Bytes>>(value.bytes())?,
+                Some(value.into_metadata()),
+            ),
+            None => (BTreeMap::new(), None),
+        };
+
+        for (tag, value) in resources {
+            write_ops.push((
+                StateKey::resource(address, &tag)?,
+                WriteOp::deletion(value.metadata().clone()),
+            ));
+            let (resource_metadata, bytes) = value.unpack();
+            if group.insert(tag.clone(), bytes).is_some() {
+                bail!(
+                    "Resource {} already exists in the group at {}.",
+                    tag,
+                    address
+                );
+            }
+            match metadata.as_mut() {
+                // A new group takes over the slot of the first resource.
+                None => metadata = Some(resource_metadata),
+                Some(metadata) => add_deposit(metadata, resource_metadata.total_deposit()),
+            }
+        }
+
+        let bytes: Bytes = bcs::to_bytes(&group)?.into();
+        let metadata = metadata.unwrap_or_else(StateValueMetadata::none);
+        write_ops.push((
+            StateKey::resource_group(address, &self.group),
+            if group_exists {
+                WriteOp::modification(bytes, metadata)
+            } else {
+                WriteOp::creation(bytes, metadata)
+            },
+        ));
+        Ok(write_ops)
+    }
+
+    pub fn out_of_group(
+        &self,
+        address: &AccountAddress,
+        group_value: StateValue,
+    ) -> Result<Vec<(StateKey, WriteOp)>> {
+        let mut write_ops = Vec::new();
+        let (group_metadata, bytes) = group_value.unpack();
+        let mut group = bcs::from_bytes::<BTreeMap<StructTag, Bytes>>(&bytes)?;
+        let moved = self
+            .resources
+            .iter()
+            .filter_map(|tag| group.remove_entry(tag))
+            .collect::<Vec<_>>();
+        if moved.is_empty() {
+            return Ok(write_ops);
+        }
+
+        let group_key = StateKey::resource_group(address, &self.group);
+        let mut resource_metadata = if group.is_empty() {
+            write_ops.push((group_key, WriteOp::deletion(group_metadata.clone())));
+            Some(group_metadata.clone())
+        } else {
+            write_ops.push((
+                group_key,
+                WriteOp::modification(bcs::to_bytes(&group)?.into(), group_metadata.clone()),
+            ));
+            None
+        };
+        for (tag, bytes) in moved {
+            let metadata = resource_metadata.take().unwrap_or_else(|| {
+                if group_metadata.is_none() {
+                    StateValueMetadata::none()
+                } else {
+                    let mut metadata = group_metadata.clone();
+                    metadata.set_slot_deposit(0);
+                    metadata.set_bytes_deposit(0);
+                    metadata
+                }
+            });
+            write_ops.push((
+                StateKey::resource(address, &tag)?,
+                WriteOp::creation(bytes, metadata),
+            ));
+        }
+        Ok(write_ops)
+    }
+}
+
+fn add_deposit(metadata: &mut StateValueMetadata, amount: u64) {
+    if amount > 0 {
+        let bytes_deposit = metadata.bytes_deposit() + amount;
+        metadata.maybe_upgrade().set_bytes_deposit(bytes_deposit);
+    }
+}
+
+/// The change of the state storage usage caused by the write set, for operators to check against
+/// the usage reported by the DB after applying it.
+#[derive(Default)]
+struct UsageDelta {
+    items: i64,
+    bytes: i64,
+}
+
+impl UsageDelta {
+    fn add(&mut self, state_key: &StateKey, old_value: Option<StateValue>, write_op: &WriteOp) {
+        if let Some(old_value) = old_value {
+            self.items -= 1;
+            self.bytes -= (state_key.size() + old_value.size()) as i64;
+        }
+        if let Some(bytes) = write_op.bytes() {
+            self.items += 1;
+            self.bytes += (state_key.size() + bytes.len()) as i64;
+        }
+    }
+}
+
+/// Wraps the write set in a genesis transaction that also starts a new epoch, which is required
+/// by the DB bootstrapper.
+fn new_epoch_genesis_txn(
+    state_view: &impl StateView,
+    write_set: aptos_types::write_set::WriteSet,
+) -> Result<Transaction> {
+    let configuration = ConfigurationResource::fetch_config(state_view)
+        .ok_or_else(|| format_err!("ConfigurationResource missing."))?;
+    let timestamp = bcs::from_bytes::<TimestampResource>(
+        &state_view
+            .get_state_value_bytes(&StateKey::resource_typed::<TimestampResource>(
+                &CORE_CODE_ADDRESS,
+            )?)?
+            .ok_or_else(|| format_err!("TimestampResource missing."))?,
+    )?;
+
+    let mut write_set = write_set.into_mut();
+    write_set.insert((
+        StateKey::on_chain_config::<ConfigurationResource>()?,
+        WriteOp::legacy_modification(
+            bcs::to_bytes(&configuration.bump_epoch(timestamp.timestamp.microseconds))?.into(),
+        ),
+    ));
+    Ok(Transaction::GenesisTransaction(WriteSetPayload::Direct(
+        ChangeSet::new(write_set.freeze()?, vec![ContractEvent::new_v2(
+            NEW_EPOCH_EVENT_V2_MOVE_TYPE_TAG.clone(),
+            vec![],
+        )?]),
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aptos_types::on_chain_config::CurrentTimeMicroseconds;
+
+    fn tag(name: &str) -> StructTag {
+        format!("0x1::test::{}", name).parse().unwrap()
+    }
+
+    fn apply(state: &mut BTreeMap<StateKey, StateValue>, write_ops: Vec<(StateKey, WriteOp)>) {
+        for (state_key, write_op) in write_ops {
+            match write_op.as_state_value_opt() {
+                Some(value) => state.insert(state_key, value.clone()),
+                None => state.remove(&state_key),
+            };
+        }
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let address = AccountAddress::random();
+        let group = tag("Group");
+        let resources: BTreeSet<_> = [tag("A"), tag("B")].into_iter().collect();
+        let time = CurrentTimeMicroseconds { microseconds: 1 };
+
+        let original: BTreeMap<_, _> = resources
+            .iter()
+            .map(|tag| {
+                (
+                    StateKey::resource(&address, tag).unwrap(),
+                    StateValue::new_with_metadata(
+                        vec![1, 2, 3].into(),
+                        StateValueMetadata::new(10, 20, &time),
+                    ),
+                )
+            })
+            .collect();
+        let total_deposit = |state: &BTreeMap<StateKey, StateValue>| {
+            state
+                .values()
+                .map(|value| value.metadata().total_deposit())
+                .sum::<u64>()
+        };
+
+        // Into the group.
+        let mut state = original.clone();
+        let migration =
+            ResourceGroupMigration::new(group.clone(), resources.clone(), Direction::IntoGroup);
+        let source_values = state
+            .iter()
+            .map(|(key, value)| (migration.source_slot(key).unwrap().1, value.clone()))
+            .collect();
+        let write_ops = migration.into_group(&address, source_values, None).unwrap();
+        apply(&mut state, write_ops);
+        let group_key = StateKey::resource_group(&address, &group);
+        assert!(state.keys().eq([&group_key]));
+        assert_eq!(total_deposit(&state), total_deposit(&original));
+
+        // Moving the same resources in again conflicts.
+        let source_values = original
+            .iter()
+            .map(|(key, value)| (migration.source_slot(key).unwrap().1, value.clone()))
+            .collect();
+        assert!(migration
+            .into_group(&address, source_values, state.get(&group_key).cloned())
+            .is_err());
+
+        // And back out.
+        let migration = ResourceGroupMigration::new(group, resources, Direction::OutOfGroup);
+        assert!(migration.source_slot(&group_key).is_some());
+        let write_ops = migration
+            .out_of_group(&address, state[&group_key].clone())
+            .unwrap();
+        apply(&mut state, write_ops);
+        assert!(state.keys().eq(original.keys()));
+        assert!(state
+            .values()
+            .map(StateValue::bytes)
+            .eq(original.values().map(StateValue::bytes)));
+        assert_eq!(total_deposit(&state), total_deposit(&original));
+    }
+}
@@ -0,0 +1,415 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::{ensure, format_err, Result};
+use aptos_backup_cli::utils::{ReplayConcurrencyLevelOpt, RocksdbOpt};
+use aptos_block_executor::txn_provider::default::DefaultTxnProvider;
+use aptos_config::config::{
+    StorageDirPaths, BUFFERED_STATE_TARGET_ITEMS, DEFAULT_MAX_NUM_NODES_PER_LRU_CACHE_SHARD,
+    NO_OP_STORAGE_PRUNER_CONFIG,
+};
+use aptos_db::{backup::backup_handler::BackupHandler, AptosDB};
+use aptos_storage_interface::{
+    state_store::state_view::db_state_view::DbStateViewAtVersion, DbReader,
+};
+use aptos_types::{
+    contract_event::ContractEvent,
+    transaction::{
+        signature_verified_transaction::SignatureVerifiedTransaction, AuxiliaryInfo,
+        PersistedAuxiliaryInfo, Transaction, TransactionInfo, TransactionOutput, TransactionStatus,
+        Version,
+    },
+    write_set::WriteSet,
+};
+use aptos_vm::{aptos_vm::AptosVMBlockExecutor, AptosVM, VMBlockExecutor};
+use clap::Parser;
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt,
+    path::PathBuf,
+    process,
+    sync::Arc,
+};
+
+#[derive(Parser)]
+#[clap(
+    about = "Re-execute a range of transactions from a local DB, and report every version whose \
+    outputs don't match the ones stored in the DB."
+)]
+pub struct Opt {
+    #[clap(long, value_parser)]
+    db_dir: PathBuf,
+
+    /// The first version to re-execute.
+    #[clap(long)]
+    start: Version,
+
+    /// The last version to re-execute (inclusive).
+    #[clap(long)]
+    end: Version,
+
+    /// Also verify that the state stored in the DB matches the stored write sets, and that the
+    /// stored state roots match the transaction infos.
+    #[clap(long)]
+    verify_state: bool,
+
+    /// The number of transactions executed as one block (blocks also end at epoch endings).
+    #[clap(long, default_value_t = 500)]
+    chunk_size: usize,
+
+    #[clap(flatten)]
+    replay_concurrency_level: ReplayConcurrencyLevelOpt,
+
+    #[clap(flatten)]
+    rocksdb_opt: RocksdbOpt,
+}
+
+impl Opt {
+    pub fn run(self) -> Result<()> {
+        ensure!(
+            self.start <= self.end,
+            "The start version {} is after the end version {}.",
+            self.start,
+            self.end,
+        );
+        let db = AptosDB::open(
+            StorageDirPaths::from_path(&self.db_dir),
+            true, /* readonly */
+            NO_OP_STORAGE_PRUNER_CONFIG,
+            self.rocksdb_opt.clone().into(),
+            false, /* indexer */
+            BUFFERED_STATE_TARGET_ITEMS,
+            DEFAULT_MAX_NUM_NODES_PER_LRU_CACHE_SHARD,
+            None,
+        )?;
+        let backup_handler = db.get_backup_handler();
+        let reader: Arc<dyn DbReader> = Arc::new(db);
+
+        let first_version = reader
+            .get_first_txn_version()?
+            .ok_or_else(|| format_err!("DB is empty."))?;
+        let synced_version = reader.expect_synced_version();
+        ensure!(
+            first_version <= self.start && self.end <= synced_version,
+            "The range [{}, {}] is not in the DB, which has versions [{}, {}].",
+            self.start,
+            self.end,
+            first_version,
+            synced_version,
+        );
+
+        AptosVM::set_concurrency_level_once(self.replay_concurrency_level.get());
+        let replayer = RangeReplayer {
+            reader,
+            backup_handler,
+            verify_state: self.verify_state,
+            chunk_size: self.chunk_size,
+        };
+        let mismatches = replayer.replay(self.start, self.end)?;
+
+        let num_versions = self.end - self.start + 1;
+        if mismatches.is_empty() {
+            println!("Replayed {} versions, all matched.", num_versions);
+            return Ok(());
+        }
+        for mismatch in &mismatches {
+            println!("{}", mismatch);
+        }
+        let num_mismatched_versions = mismatches
+            .iter()
+            .map(|mismatch| mismatch.version)
+            .collect::<BTreeSet<_>>()
+            .len();
+        println!(
+            "Replayed {} versions, {} of them mismatched.",
+            num_versions, num_mismatched_versions
+        );
+        process::exit(2);
+    }
+}
+
+/// A difference between the re-execution of a version and what is stored in the DB
+struct Mismatch {
+    version: Version,
+    /// What didn't match, e.g. "gas_used"
+    field: &'static str,
+    details: String,
+}
+
+impl Mismatch {
+    fn new(version: Version, field: &'static str, details: String) -> Self {
+        Self {
+            version,
+            field,
+            details,
+        }
+    }
+}
+
+impl fmt::Display for Mismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "version {}: {} mismatch: {}",
+            self.version, self.field, self.details
+        )
+    }
+}
+
+/// A transaction and everything stored in the DB about its execution
+struct StoredTransaction {
+    txn: Transaction,
+    persisted_aux_info: PersistedAuxiliaryInfo,
+    txn_info: TransactionInfo,
+    events: Vec<ContractEvent>,
+    write_set: WriteSet,
+}
+
+struct RangeReplayer {
+    reader: Arc<dyn DbReader>,
+    backup_handler: BackupHandler,
+    verify_state: bool,
+    chunk_size: usize,
+}
+
+impl RangeReplayer {
+    fn replay(&self, start: Version, end: Version) -> Result<Vec<Mismatch>> {
+        let mut mismatches = vec![];
+        let mut chunk = vec![];
+        let mut chunk_start_version = start;
+        let txn_iter = self
+            .backup_handler
+            .get_transaction_iter(start, (end - start + 1) as usize)?;
+        for item in txn_iter {
+            let (txn, persisted_aux_info, txn_info, events, write_set) = item?;
+            let is_epoch_ending = events.iter().any(ContractEvent::is_new_epoch_event);
+            chunk.push(StoredTransaction {
+                txn,
+                persisted_aux_info,
+                txn_info,
+                events,
+                write_set,
+            });
+            if is_epoch_ending || chunk.len() >= self.chunk_size {
+                self.replay_chunk(chunk_start_version, &chunk, &mut mismatches)?;
+                chunk_start_version += chunk.len() as u64;
+                chunk.clear();
+            }
+        }
+        self.replay_chunk(chunk_start_version, &chunk, &mut mismatches)?;
+        Ok(mismatches)
+    }
+
+    /// Re-executes the chunk on top of the state stored in the DB. After a mismatch, the rest of
+    /// the chunk is re-executed on top of the stored state again, so that one divergence doesn't
+    /// cascade into the following versions.
+    fn replay_chunk(
+        &self,
+        first_version: Version,
+        chunk: &[StoredTransaction],
+        mismatches: &mut Vec<Mismatch>,
+    ) -> Result<()> {
+        let mut next_index = 0;
+        while next_index < chunk.len() {
+            let remaining = &chunk[next_index..];
+            let remaining_first_version = first_version + next_index as u64;
+            let txns_provider = DefaultTxnProvider::new(
+                remaining
+                    .iter()
+                    .map(|stored| SignatureVerifiedTransaction::from(stored.txn.clone()))
+                    .collect(),
+                remaining
+                    .iter()
+                    .map(|stored| AuxiliaryInfo::new(stored.persisted_aux_info, None))
+                    .collect(),
+            );
+            let outputs = AptosVMBlockExecutor::new().execute_block_no_limit(
+                &txns_provider,
+                &self
+                    .reader
+                    .state_view_at_version(remaining_first_version.checked_sub(1))?,
+            )?;
+            ensure!(
+                outputs.len() == remaining.len(),
+                "Executed {} transactions, expected {}.",
+                outputs.len(),
+                remaining.len(),
+            );
+
+            let mut num_replayed = remaining.len();
+            for (index, (output, stored)) in outputs.iter().zip(remaining).enumerate() {
+                let version = remaining_first_version + index as u64;
+                if self.verify_state {
+                    self.verify_stored_state(version, stored, mismatches)?;
+                }
+                let output_mismatches = compare_output(version, output, stored);
+                if !output_mismatches.is_empty() {
+                    mismatches.extend(output_mismatches);
+                    num_replayed = index + 1;
+                    break;
+                }
+            }
+            next_index += num_replayed;
+        }
+        Ok(())
+    }
+
+    /// Verifies that the state stored in the DB at the version matches the stored write set,
+    /// and that the stored state root (if the version is a persisted state checkpoint) matches
+    /// the transaction info.
+    fn verify_stored_state(
+        &self,
+        version: Version,
+        stored: &StoredTransaction,
+        mismatches: &mut Vec<Mismatch>,
+    ) -> Result<()> {
+        for (state_key, write_op) in stored.write_set.write_op_iter() {
+            let stored_value = self.reader.get_state_value_by_version(state_key, version)?;
+            let expected_bytes = write_op.as_state_value_opt().map(|value| value.bytes());
+            if stored_value.as_ref().map(|value| value.bytes()) != expected_bytes {
+                mismatches.push(Mismatch::new(
+                    version,
+                    "state_value",
+                    format!(
+                        "{:?} differs, stored value size {:?}, written value size {:?}",
+                        state_key,
+                        stored_value.as_ref().map(|value| value.bytes().len()),
+                        expected_bytes.map(|bytes| bytes.len()),
+                    ),
+                ));
+            }
+        }
+
+        if let Some(expected_root_hash) = stored.txn_info.state_checkpoint_hash() {
+            if let Some((snapshot_version, root_hash)) =
+                self.reader.get_state_snapshot_before(version + 1)?
+            {
+                if snapshot_version == version && root_hash != expected_root_hash {
+                    mismatches.push(Mismatch::new(
+                        version,
+                        "state_root",
+                        format!(
+                            "stored state root {}, transaction info {}",
+                            root_hash, expected_root_hash
+                        ),
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Compares the re-executed output of a version with what is stored in the DB
+fn compare_output(
+    version: Version,
+    output: &TransactionOutput,
+    stored: &StoredTransaction,
+) -> Vec<Mismatch> {
+    let mut mismatches = vec![];
+
+    let expected_status: TransactionStatus = stored.txn_info.status().clone().into();
+    if output.status() != &expected_status {
+        mismatches.push(Mismatch::new(
+            version,
+            "status",
+            format!(
+                "executed {:?}, stored {:?}",
+                output.status(),
+                expected_status
+            ),
+        ));
+    }
+
+    if output.gas_used() != stored.txn_info.gas_used() {
+        mismatches.push(Mismatch::new(
+            version,
+            "gas_used",
+            format!(
+                "executed {}, stored {}",
+                output.gas_used(),
+                stored.txn_info.gas_used()
+            ),
+        ));
+    }
+
+    let executed_write_ops: BTreeMap<_, _> = output.write_set().write_op_iter().collect();
+    let stored_write_ops: BTreeMap<_, _> = stored.write_set.write_op_iter().collect();
+    for (state_key, executed_write_op) in &executed_write_ops {
+        match stored_write_ops.get(state_key) {
+            None => mismatches.push(Mismatch::new(
+                version,
+                "write_set",
+                format!(
+                    "{:?} was written, but not in the stored write set",
+                    state_key
+                ),
+            )),
+            Some(stored_write_op) if stored_write_op != executed_write_op => {
+                mismatches.push(Mismatch::new(
+                    version,
+                    "write_set",
+                    format!(
+                        "{:?} was written as {:?}, stored as {:?}",
+                        state_key, executed_write_op, stored_write_op
+                    ),
+                ))
+            },
+            Some(_) => {},
+        }
+    }
+    for state_key in stored_write_ops.keys() {
+        if !executed_write_ops.contains_key(state_key) {
+            mismatches.push(Mismatch::new(
+                version,
+                "write_set",
+                format!(
+                    "{:?} is in the stored write set, but was not written",
+                    state_key
+                ),
+            ));
+        }
+    }
+
+    let executed_events = output.events();
+    if executed_events.len() != stored.events.len() {
+        mismatches.push(Mismatch::new(
+            version,
+            "events",
+            format!(
+                "executed {} events, stored {}",
+                executed_events.len(),
+                stored.events.len()
+            ),
+        ));
+    }
+    for (index, (executed_event, stored_event)) in
+        executed_events.iter().zip(&stored.events).enumerate()
+    {
+        if executed_event != stored_event {
+            mismatches.push(Mismatch::new(
+                version,
+                "events",
+                format!(
+                    "event {} executed as {:?}, stored as {:?}",
+                    index, executed_event, stored_event
+                ),
+            ));
+        }
+    }
+
+    // The write set and events are also committed to by the transaction info, this catches
+    // anything the comparisons above don't.
+    if mismatches.is_empty() {
+        if let Err(err) = output.ensure_match_transaction_info(
+            version,
+            &stored.txn_info,
+            Some(&stored.write_set),
+            Some(&stored.events),
+        ) {
+            mismatches.push(Mismatch::new(version, "transaction_info", err.to_string()));
+        }
+    }
+
+    mismatches
+}
@@ -0,0 +1,242 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Extracts coin and fungible asset balance changes from write sets, for the balance index of
+//! the internal indexer.
+
+use aptos_storage_interface::Result;
+use aptos_types::{
+    access_path::Path,
+    account_address::{create_derived_object_address, AccountAddress},
+    account_config::{
+        CoinStoreResourceUntyped, ConcurrentFungibleBalanceResource, FungibleStoreResource,
+        ObjectCoreResource, ObjectGroupResource,
+    },
+    indexer::indexer_db_reader::BalanceAsset,
+    state_store::{
+        state_key::{inner::StateKeyInner, StateKey},
+        state_value::StateValue,
+    },
+    write_set::{TransactionWrite, WriteOp, WriteSet},
+};
+use move_core_types::{
+    ident_str, identifier::IdentStr, language_storage::StructTag, move_resource::MoveStructType,
+};
+
+const COIN_MODULE: &IdentStr = ident_str!("coin");
+const COIN_STORE_STRUCT: &IdentStr = ident_str!("CoinStore");
+
+/// Returns the (owner, asset, balance) triples written by `write_set`.
+///
+/// Fungible asset balances are only tracked for primary stores, matching the REST API's
+/// balance endpoint. Deleting a coin store or a primary store is reported as a zero balance. A
+/// deleted object group no longer tells whose store it was, so it is read through
+/// `get_previous_state_value` as of the version before the deletion.
+pub fn extract_balance_changes(
+    write_set: &WriteSet,
+    get_previous_state_value: impl Fn(&StateKey) -> Result<Option<StateValue>>,
+) -> Result<Vec<(AccountAddress, BalanceAsset, u64)>> {
+    let mut changes = Vec::new();
+    for (state_key, write_op) in write_set.write_op_iter() {
+        if let Some(change) = balance_change(state_key, write_op, &get_previous_state_value)? {
+            changes.push(change);
+        }
+    }
+    Ok(changes)
+}
+
+fn balance_change(
+    state_key: &StateKey,
+    write_op: &WriteOp,
+    get_previous_state_value: impl Fn(&StateKey) -> Result<Option<StateValue>>,
+) -> Result<Option<(AccountAddress, BalanceAsset, u64)>> {
+    let access_path = match state_key.inner() {
+        StateKeyInner::AccessPath(access_path) => access_path,
+        StateKeyInner::TableItem { .. } | StateKeyInner::Raw(_) => return Ok(None),
+    };
+
+    Ok(match access_path.get_path() {
+        Path::Resource(struct_tag) if is_coin_store(&struct_tag) => {
+            let Some(coin_type) = struct_tag.type_args.into_iter().next() else {
+                return Ok(None);
+            };
+            let balance = match write_op.bytes() {
+                Some(bytes) => match bcs::from_bytes::<CoinStoreResourceUntyped>(bytes) {
+                    Ok(coin_store) => coin_store.coin(),
+                    Err(_) => return Ok(None),
+                },
+                None => 0,
+            };
+            Some((access_path.address, BalanceAsset::Coin(coin_type), balance))
+        },
+        Path::ResourceGroup(struct_tag) if struct_tag == ObjectGroupResource::struct_tag() => {
+            match write_op.bytes() {
+                Some(bytes) => bcs::from_bytes::<ObjectGroupResource>(bytes)
+                    .ok()
+                    .and_then(|group| primary_store_balance(access_path.address, &group)),
+                None => get_previous_state_value(state_key)?
+                    .and_then(|state_value| {
+                        bcs::from_bytes::<ObjectGroupResource>(state_value.bytes()).ok()
+                    })
+                    .and_then(|group| primary_store_balance(access_path.address, &group))
+                    .map(|(owner, asset, _balance)| (owner, asset, 0)),
+            }
+        },
+        Path::Resource(_) | Path::ResourceGroup(_) | Path::Code(_) => None,
+    })
+}
+
+fn is_coin_store(struct_tag: &StructTag) -> bool {
+    struct_tag.address == AccountAddress::ONE
+        && struct_tag.module.as_ident_str() == COIN_MODULE
+        && struct_tag.name.as_ident_str() == COIN_STORE_STRUCT
+        && struct_tag.type_args.len() == 1
+}
+
+fn primary_store_balance(
+    store_address: AccountAddress,
+    group: &ObjectGroupResource,
+) -> Option<(AccountAddress, BalanceAsset, u64)> {
+    let store = bcs::from_bytes::<FungibleStoreResource>(
+        group.group.get(&FungibleStoreResource::struct_tag())?,
+    )
+    .ok()?;
+    let owner =
+        bcs::from_bytes::<ObjectCoreResource>(group.group.get(&ObjectCoreResource::struct_tag())?)
+            .ok()?
+            .owner;
+    if create_derived_object_address(owner, store.metadata()) != store_address {
+        return None;
+    }
+
+    let balance = if store.balance() != 0 {
+        store.balance()
+    } else if let Some(bytes) = group
+        .group
+        .get(&ConcurrentFungibleBalanceResource::struct_tag())
+    {
+        bcs::from_bytes::<ConcurrentFungibleBalanceResource>(bytes)
+            .ok()?
+            .balance()
+    } else {
+        0
+    };
+    Some((
+        owner,
+        BalanceAsset::FungibleAsset(store.metadata()),
+        balance,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aptos_types::{
+        account_config::CoinStoreResource,
+        event::EventHandle,
+        utility_coin::{AptosCoinType, CoinType},
+        write_set::WriteSetMut,
+    };
+
+    fn balance_changes(write_set: &WriteSet) -> Vec<(AccountAddress, BalanceAsset, u64)> {
+        extract_balance_changes(write_set, |_state_key| Ok(None)).unwrap()
+    }
+
+    #[test]
+    fn test_extract_coin_store_balance() {
+        let owner = AccountAddress::random();
+        let coin_store = CoinStoreResource::<AptosCoinType>::new(
+            42,
+            false,
+            EventHandle::random(0),
+            EventHandle::random(0),
+        );
+        let state_key =
+            StateKey::resource_typed::<CoinStoreResource<AptosCoinType>>(&owner).unwrap();
+        let write_set = WriteSetMut::new(vec![(
+            state_key.clone(),
+            WriteOp::legacy_modification(bcs::to_bytes(&coin_store).unwrap().into()),
+        )])
+        .freeze()
+        .unwrap();
+
+        assert_eq!(balance_changes(&write_set), vec![(
+            owner,
+            BalanceAsset::Coin(AptosCoinType::type_tag()),
+            42
+        )]);
+
+        let write_set = WriteSetMut::new(vec![(state_key, WriteOp::legacy_deletion())])
+            .freeze()
+            .unwrap();
+        assert_eq!(balance_changes(&write_set), vec![(
+            owner,
+            BalanceAsset::Coin(AptosCoinType::type_tag()),
+            0
+        )]);
+    }
+
+    #[test]
+    fn test_extract_primary_store_balance() {
+        let owner = AccountAddress::random();
+        let metadata = AccountAddress::random();
+        let store_address = create_derived_object_address(owner, metadata);
+
+        let mut group = ObjectGroupResource::default();
+        group.insert(
+            FungibleStoreResource::struct_tag(),
+            bcs::to_bytes(&FungibleStoreResource::new(metadata, 7, false)).unwrap(),
+        );
+        group.insert(
+            ObjectCoreResource::struct_tag(),
+            bcs::to_bytes(&ObjectCoreResource::new(
+                owner,
+                false,
+                EventHandle::random(0),
+            ))
+            .unwrap(),
+        );
+        let group_bytes = group.to_bytes().unwrap();
+        let state_key =
+            StateKey::resource_group(&store_address, &ObjectGroupResource::struct_tag());
+
+        let write_set = WriteSetMut::new(vec![(
+            state_key.clone(),
+            WriteOp::legacy_modification(group_bytes.clone().into()),
+        )])
+        .freeze()
+        .unwrap();
+        assert_eq!(balance_changes(&write_set), vec![(
+            owner,
+            BalanceAsset::FungibleAsset(metadata),
+            7
+        )]);
+
+        // A deleted store is found in the previous state, and reported as a zero balance.
+        let write_set = WriteSetMut::new(vec![(state_key.clone(), WriteOp::legacy_deletion())])
+            .freeze()
+            .unwrap();
+        assert!(balance_changes(&write_set).is_empty());
+        let previous_state_value = StateValue::new_legacy(group_bytes.clone().into());
+        assert_eq!(
+            extract_balance_changes(&write_set, |key| {
+                assert_eq!(key, &state_key);
+                Ok(Some(previous_state_value.clone()))
+            })
+            .unwrap(),
+            vec![(owner, BalanceAsset::FungibleAsset(metadata), 0)]
+        );
+
+        // Secondary stores are not indexed.
+        let write_set = WriteSetMut::new(vec![(
+            StateKey::resource_group(
+                &AccountAddress::random(),
+                &ObjectGroupResource::struct_tag(),
+            ),
+            WriteOp::legacy_modification(group_bytes.into()),
+        )])
+        .freeze()
+        .unwrap();
+        assert!(balance_changes(&write_set).is_empty());
+    }
+}
@@ -2,18 +2,19 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    event_v2_translator::EventV2TranslationEngine, metrics::TIMER,
-    utils::PrefixedStateValueIterator,
+    balance::extract_balance_changes, event_v2_translator::EventV2TranslationEngine,
+    metrics::TIMER, package::extract_package_updates, utils::PrefixedStateValueIterator,
 };
 use aptos_config::config::internal_indexer_db_config::InternalIndexerDBConfig;
 use aptos_db_indexer_schemas::{
     metadata::{MetadataKey, MetadataValue, StateSnapshotProgress},
     schema::{
-        event_by_key::EventByKeySchema, event_by_version::EventByVersionSchema,
-        event_sequence_number::EventSequenceNumberSchema,
+        balance_by_account::BalanceByAccountSchema, event_by_key::EventByKeySchema,
+        event_by_version::EventByVersionSchema, event_sequence_number::EventSequenceNumberSchema,
         indexer_metadata::InternalIndexerMetadataSchema,
         ordered_transaction_by_account::OrderedTransactionByAccountSchema,
-        state_keys::StateKeysSchema, translated_v1_event::TranslatedV1EventSchema,
+        package_by_name::PackageByNameSchema, state_keys::StateKeysSchema,
+        translated_v1_event::TranslatedV1EventSchema,
     },
     utils::{
         error_if_too_many_requested, get_first_seq_num_and_limit, AccountOrderedTransactionsIter,
@@ -31,7 +32,7 @@ use aptos_types::{
     account_config::{BURN_TYPE, MINT_TYPE},
     contract_event::{ContractEvent, ContractEventV1, ContractEventV2, EventWithVersion},
     event::EventKey,
-    indexer::indexer_db_reader::Order,
+    indexer::indexer_db_reader::{BalanceAsset, IndexedPackage, Order},
     state_store::{
         state_key::{prefix::StateKeyPrefix, StateKey},
         state_value::StateValue,
@@ -41,7 +42,7 @@ use aptos_types::{
 };
 use std::{
     cmp::min,
-    collections::HashSet,
+    collections::{BTreeMap, HashSet},
     sync::{
         mpsc::{self, Receiver, Sender},
         Arc,
@@ -127,6 +128,14 @@ impl InternalIndexerDB {
         self.get_version(&MetadataKey::EventV2TranslationVersion)
     }
 
+    pub fn get_balance_version(&self) -> Result<Option<Version>> {
+        self.get_version(&MetadataKey::BalanceVersion)
+    }
+
+    pub fn get_package_version(&self) -> Result<Option<Version>> {
+        self.get_version(&MetadataKey::PackageVersion)
+    }
+
     pub fn event_enabled(&self) -> bool {
         self.config.enable_event
     }
@@ -143,6 +152,14 @@ impl InternalIndexerDB {
         self.config.enable_statekeys
     }
 
+    pub fn balances_enabled(&self) -> bool {
+        self.config.enable_balances
+    }
+
+    pub fn packages_enabled(&self) -> bool {
+        self.config.enable_packages
+    }
+
     pub fn get_inner_db_ref(&self) -> &Arc<DB> {
         &self.db
     }
@@ -171,6 +188,107 @@ impl InternalIndexerDB {
         bail!("ledger version too new")
     }
 
+    /// Removes everything indexed from versions after `target_version`, e.g., because those
+    /// versions were truncated from the main DB. State keys are kept, as they aren't indexed by
+    /// version. Returns the latest indexed version before the truncation, if it was truncated.
+    pub fn truncate(&self, target_version: Version) -> Result<Option<Version>> {
+        let latest_version = match self.get_persisted_version()? {
+            Some(latest_version) if latest_version > target_version => latest_version,
+            _ => return Ok(None),
+        };
+
+        let mut batch = SchemaBatch::new();
+        let mut iter = self.db.iter::<OrderedTransactionByAccountSchema>()?;
+        iter.seek_to_first();
+        for res in iter {
+            let (key, version) = res?;
+            if version > target_version {
+                batch.delete::<OrderedTransactionByAccountSchema>(&key)?;
+            }
+        }
+
+        // The lowest removed sequence number of each event key, to roll back the sequence
+        // numbers of the translated events
+        let mut removed_sequence_numbers: BTreeMap<EventKey, u64> = BTreeMap::new();
+        let mut iter = self.db.iter::<EventByKeySchema>()?;
+        iter.seek_to_first();
+        for res in iter {
+            let ((event_key, sequence_number), (version, _idx)) = res?;
+            if version > target_version {
+                batch.delete::<EventByKeySchema>(&(event_key, sequence_number))?;
+                removed_sequence_numbers
+                    .entry(event_key)
+                    .and_modify(|removed| *removed = min(*removed, sequence_number))
+                    .or_insert(sequence_number);
+            }
+        }
+        let mut iter = self.db.iter::<EventByVersionSchema>()?;
+        iter.seek_to_first();
+        for res in iter {
+            let (key, _idx) = res?;
+            if key.1 > target_version {
+                batch.delete::<EventByVersionSchema>(&key)?;
+            }
+        }
+        let mut iter = self.db.iter::<TranslatedV1EventSchema>()?;
+        iter.seek(&(target_version + 1, 0))?;
+        for res in iter {
+            let (key, _event) = res?;
+            batch.delete::<TranslatedV1EventSchema>(&key)?;
+        }
+        for (event_key, removed_sequence_number) in removed_sequence_numbers {
+            if self
+                .db
+                .get::<EventSequenceNumberSchema>(&event_key)?
+                .is_none()
+            {
+                continue;
+            }
+            match removed_sequence_number.checked_sub(1) {
+                Some(sequence_number) => {
+                    batch.put::<EventSequenceNumberSchema>(&event_key, &sequence_number)?
+                },
+                None => batch.delete::<EventSequenceNumberSchema>(&event_key)?,
+            }
+        }
+
+        let mut iter = self.db.iter::<BalanceByAccountSchema>()?;
+        iter.seek_to_first();
+        for res in iter {
+            let (key, _amount) = res?;
+            if key.2 > target_version {
+                batch.delete::<BalanceByAccountSchema>(&key)?;
+            }
+        }
+        let mut iter = self.db.iter::<PackageByNameSchema>()?;
+        iter.seek_to_first();
+        for res in iter {
+            let (key, _package) = res?;
+            if key.2 > target_version {
+                batch.delete::<PackageByNameSchema>(&key)?;
+            }
+        }
+
+        for key in [
+            MetadataKey::LatestVersion,
+            MetadataKey::EventVersion,
+            MetadataKey::StateVersion,
+            MetadataKey::TransactionVersion,
+            MetadataKey::EventV2TranslationVersion,
+            MetadataKey::BalanceVersion,
+            MetadataKey::PackageVersion,
+        ] {
+            if self.get_version(&key)? > Some(target_version) {
+                batch.put::<InternalIndexerMetadataSchema>(
+                    &key,
+                    &MetadataValue::Version(target_version),
+                )?;
+            }
+        }
+        self.db.write_schemas(batch)?;
+        Ok(Some(latest_version))
+    }
+
     pub fn get_account_ordered_transactions_iter(
         &self,
         address: AccountAddress,
@@ -244,6 +362,70 @@ impl InternalIndexerDB {
         Ok(result)
     }
 
+    /// Returns the balances of up to `limit` assets `address` has held, as of `ledger_version`,
+    /// starting from `start_asset`, along with the asset the next page starts from. Records
+    /// written after `ledger_version` are ignored, so the result stays consistent with the ledger
+    /// even if the index is ahead of it.
+    pub fn get_account_balances(
+        &self,
+        address: AccountAddress,
+        start_asset: Option<&BalanceAsset>,
+        limit: u64,
+        ledger_version: Version,
+    ) -> Result<(Vec<(BalanceAsset, u64)>, Option<BalanceAsset>)> {
+        let mut iter = self.db.iter::<BalanceByAccountSchema>()?;
+        match start_asset {
+            Some(asset) => iter.seek(&(address, asset.clone(), 0))?,
+            None => iter.seek(&&address)?,
+        }
+
+        let mut balances = Vec::new();
+        while let Some(((owner, asset, _version), _amount)) = iter.next().transpose()? {
+            if owner != address {
+                break;
+            }
+            if balances.len() as u64 >= limit {
+                return Ok((balances, Some(asset)));
+            }
+            // Only the latest record of the asset as of `ledger_version` is read, the rest of
+            // its history is skipped.
+            iter.seek_for_prev(&(address, asset.clone(), ledger_version))?;
+            if let Some(((owner, found_asset, _version), amount)) = iter.next().transpose()? {
+                if owner == address && found_asset == asset {
+                    balances.push((found_asset, amount));
+                }
+            }
+            iter.seek(&(address, asset, Version::MAX))?;
+        }
+
+        Ok((balances, None))
+    }
+
+    /// Returns the packages named `package_name` at every address, as of `ledger_version`.
+    /// Records written after `ledger_version` are ignored, as for balances.
+    pub fn get_packages_by_name(
+        &self,
+        package_name: &str,
+        ledger_version: Version,
+    ) -> Result<Vec<IndexedPackage>> {
+        let mut iter = self.db.iter::<PackageByNameSchema>()?;
+        iter.seek(&package_name)?;
+
+        let mut packages = BTreeMap::new();
+        for res in iter {
+            let ((name, address, version), package) = res?;
+            if name != package_name {
+                break;
+            }
+            // Records of one address are ordered by version, so the last one visited wins.
+            if version <= ledger_version {
+                packages.insert(address, package);
+            }
+        }
+
+        Ok(packages.into_values().collect())
+    }
+
     #[cfg(any(test, feature = "fuzzing"))]
     pub fn get_restore_version_and_progress(
         &self,
@@ -495,6 +677,33 @@ impl DBIndexer {
                     }
                 });
             }
+
+            if self.indexer_db.balances_enabled() {
+                // A deleted store is looked up in the state it was deleted from.
+                let changes =
+                    extract_balance_changes(&writeset, |state_key| match version.checked_sub(1) {
+                        Some(previous_version) => self
+                            .main_db_reader
+                            .get_state_value_by_version(state_key, previous_version),
+                        None => Ok(None),
+                    })?;
+                for (owner, asset, amount) in changes {
+                    batch
+                        .put::<BalanceByAccountSchema>(&(owner, asset, version), &amount)
+                        .expect("Failed to put balances to a batch");
+                }
+            }
+
+            if self.indexer_db.packages_enabled() {
+                for package in extract_package_updates(&writeset) {
+                    batch
+                        .put::<PackageByNameSchema>(
+                            &(package.name.clone(), package.address, version),
+                            &package,
+                        )
+                        .expect("Failed to put packages to a batch");
+                }
+            }
             version += 1;
             Ok::<(), AptosDbError>(())
         })?;
@@ -539,6 +748,18 @@ impl DBIndexer {
                 &MetadataValue::Version(version - 1),
             )?;
         }
+        if self.indexer_db.balances_enabled() {
+            batch.put::<InternalIndexerMetadataSchema>(
+                &MetadataKey::BalanceVersion,
+                &MetadataValue::Version(version - 1),
+            )?;
+        }
+        if self.indexer_db.packages_enabled() {
+            batch.put::<InternalIndexerMetadataSchema>(
+                &MetadataKey::PackageVersion,
+                &MetadataValue::Version(version - 1),
+            )?;
+        }
         batch.put::<InternalIndexerMetadataSchema>(
             &MetadataKey::LatestVersion,
             &MetadataValue::Version(version - 1),
@@ -611,6 +832,31 @@ impl DBIndexer {
         Ok(AccountOrderedTransactionsWithProof::new(txns_with_proofs))
     }
 
+    pub fn get_account_balances(
+        &self,
+        address: AccountAddress,
+        start_asset: Option<&BalanceAsset>,
+        limit: u64,
+        ledger_version: Version,
+    ) -> Result<(Vec<(BalanceAsset, u64)>, Option<BalanceAsset>)> {
+        self.indexer_db
+            .ensure_cover_ledger_version(ledger_version)?;
+        error_if_too_many_requested(limit, MAX_REQUEST_LIMIT)?;
+        self.indexer_db
+            .get_account_balances(address, start_asset, limit, ledger_version)
+    }
+
+    pub fn get_packages_by_name(
+        &self,
+        package_name: &str,
+        ledger_version: Version,
+    ) -> Result<Vec<IndexedPackage>> {
+        self.indexer_db
+            .ensure_cover_ledger_version(ledger_version)?;
+        self.indexer_db
+            .get_packages_by_name(package_name, ledger_version)
+    }
+
     pub fn get_prefixed_state_value_iterator(
         &self,
         key_prefix: &StateKeyPrefix,
@@ -723,3 +969,141 @@ impl DBIndexer {
         Ok(events_with_version)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db_ops::open_internal_indexer_db;
+    use aptos_config::config::RocksdbConfig;
+    use aptos_temppath::TempPath;
+
+    #[test]
+    fn test_truncate() {
+        let tmp_dir = TempPath::new();
+        let db =
+            Arc::new(open_internal_indexer_db(tmp_dir.path(), &RocksdbConfig::default()).unwrap());
+        let indexer_db = InternalIndexerDB::new(db.clone(), InternalIndexerDBConfig::default());
+        let account = AccountAddress::random();
+        let event_key = EventKey::random();
+        let asset = BalanceAsset::FungibleAsset(AccountAddress::random());
+
+        let mut batch = SchemaBatch::new();
+        for version in 0..10 {
+            batch
+                .put::<OrderedTransactionByAccountSchema>(&(account, version), &version)
+                .unwrap();
+            batch
+                .put::<EventByKeySchema>(&(event_key, version), &(version, 0))
+                .unwrap();
+            batch
+                .put::<EventByVersionSchema>(&(event_key, version, version), &0)
+                .unwrap();
+            batch
+                .put::<BalanceByAccountSchema>(&(account, asset.clone(), version), &version)
+                .unwrap();
+        }
+        batch
+            .put::<EventSequenceNumberSchema>(&event_key, &9)
+            .unwrap();
+        for key in [MetadataKey::LatestVersion, MetadataKey::EventVersion] {
+            batch
+                .put::<InternalIndexerMetadataSchema>(&key, &MetadataValue::Version(9))
+                .unwrap();
+        }
+        db.write_schemas(batch).unwrap();
+
+        // Nothing is indexed after version 9
+        assert_eq!(indexer_db.truncate(9).unwrap(), None);
+
+        assert_eq!(indexer_db.truncate(4).unwrap(), Some(9));
+        assert_eq!(indexer_db.get_persisted_version().unwrap(), Some(4));
+        assert_eq!(indexer_db.get_event_version().unwrap(), Some(4));
+        assert_eq!(indexer_db.get_balance_version().unwrap(), None);
+        assert_eq!(
+            db.get::<OrderedTransactionByAccountSchema>(&(account, 4))
+                .unwrap(),
+            Some(4)
+        );
+        assert_eq!(
+            db.get::<OrderedTransactionByAccountSchema>(&(account, 5))
+                .unwrap(),
+            None
+        );
+        assert!(db
+            .get::<EventByKeySchema>(&(event_key, 5))
+            .unwrap()
+            .is_none());
+        assert!(db
+            .get::<EventByVersionSchema>(&(event_key, 5, 5))
+            .unwrap()
+            .is_none());
+        assert_eq!(
+            db.get::<EventSequenceNumberSchema>(&event_key).unwrap(),
+            Some(4)
+        );
+        assert!(db
+            .get::<BalanceByAccountSchema>(&(account, asset.clone(), 4))
+            .unwrap()
+            .is_some());
+        assert!(db
+            .get::<BalanceByAccountSchema>(&(account, asset, 5))
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn test_get_account_balances() {
+        let tmp_dir = TempPath::new();
+        let db =
+            Arc::new(open_internal_indexer_db(tmp_dir.path(), &RocksdbConfig::default()).unwrap());
+        let indexer_db = InternalIndexerDB::new(db.clone(), InternalIndexerDBConfig::default());
+        let account = AccountAddress::new([1; AccountAddress::LENGTH]);
+        let other_account = AccountAddress::new([2; AccountAddress::LENGTH]);
+        let assets: Vec<_> = (1..=3)
+            .map(|i| BalanceAsset::FungibleAsset(AccountAddress::new([i; AccountAddress::LENGTH])))
+            .collect();
+
+        let mut batch = SchemaBatch::new();
+        for (i, asset) in assets.iter().enumerate() {
+            // Asset i is first held at version i, and its balance changes at every version.
+            for version in i as Version..10 {
+                batch
+                    .put::<BalanceByAccountSchema>(&(account, asset.clone(), version), &version)
+                    .unwrap();
+            }
+        }
+        batch
+            .put::<BalanceByAccountSchema>(&(other_account, assets[0].clone(), 0), &100)
+            .unwrap();
+        db.write_schemas(batch).unwrap();
+
+        assert_eq!(
+            indexer_db
+                .get_account_balances(account, None, 2, 5)
+                .unwrap(),
+            (
+                vec![(assets[0].clone(), 5), (assets[1].clone(), 5)],
+                Some(assets[2].clone())
+            )
+        );
+        assert_eq!(
+            indexer_db
+                .get_account_balances(account, Some(&assets[2]), 2, 5)
+                .unwrap(),
+            (vec![(assets[2].clone(), 5)], None)
+        );
+        // Assets first held after the ledger version are skipped.
+        assert_eq!(
+            indexer_db
+                .get_account_balances(account, None, 10, 1)
+                .unwrap(),
+            (vec![(assets[0].clone(), 1), (assets[1].clone(), 1)], None)
+        );
+        assert_eq!(
+            indexer_db
+                .get_account_balances(other_account, None, 10, 9)
+                .unwrap(),
+            (vec![(assets[0].clone(), 100)], None)
+        );
+    }
+}
@@ -7,7 +7,7 @@ use aptos_types::{
     account_address::AccountAddress,
     contract_event::{ContractEventV1, ContractEventV2, EventWithVersion},
     event::EventKey,
-    indexer::indexer_db_reader::{IndexerReader, Order},
+    indexer::indexer_db_reader::{BalanceAsset, IndexedPackage, IndexerReader, Order},
     state_store::{
         state_key::{prefix::StateKeyPrefix, StateKey},
         state_value::StateValue,
@@ -137,6 +137,43 @@ impl IndexerReader for IndexerReaders {
         anyhow::bail!("DB indexer reader is not available")
     }
 
+    fn get_account_balances(
+        &self,
+        address: AccountAddress,
+        start_asset: Option<&BalanceAsset>,
+        limit: u64,
+        ledger_version: Version,
+    ) -> anyhow::Result<(Vec<(BalanceAsset, u64)>, Option<BalanceAsset>)> {
+        if let Some(db_indexer_reader) = &self.db_indexer_reader {
+            if db_indexer_reader.indexer_db.balances_enabled() {
+                return Ok(db_indexer_reader.get_account_balances(
+                    address,
+                    start_asset,
+                    limit,
+                    ledger_version,
+                )?);
+            } else {
+                anyhow::bail!("Internal balance index is not enabled")
+            }
+        }
+        anyhow::bail!("DB indexer reader is not available")
+    }
+
+    fn get_packages_by_name(
+        &self,
+        package_name: &str,
+        ledger_version: Version,
+    ) -> anyhow::Result<Vec<IndexedPackage>> {
+        if let Some(db_indexer_reader) = &self.db_indexer_reader {
+            if db_indexer_reader.indexer_db.packages_enabled() {
+                return Ok(db_indexer_reader.get_packages_by_name(package_name, ledger_version)?);
+            } else {
+                anyhow::bail!("Internal package index is not enabled")
+            }
+        }
+        anyhow::bail!("DB indexer reader is not available")
+    }
+
     fn get_prefixed_state_value_iterator(
         &self,
         key_prefix: &StateKeyPrefix,
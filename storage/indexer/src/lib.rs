@@ -1,6 +1,7 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
+pub mod balance;
 /// TODO(jill): deprecate Indexer once Indexer Async V2 is ready
 mod db;
 pub mod db_indexer;
@@ -9,6 +10,7 @@ pub mod db_v2;
 pub mod event_v2_translator;
 pub mod indexer_reader;
 mod metrics;
+pub mod package;
 pub mod utils;
 
 use crate::db::INDEX_DB_NAME;
@@ -0,0 +1,125 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Extracts published packages from write sets, for the package index of the internal indexer.
+
+use aptos_framework::natives::code::{PackageMetadata, PackageRegistry};
+use aptos_types::{
+    access_path::Path,
+    account_address::AccountAddress,
+    indexer::indexer_db_reader::IndexedPackage,
+    on_chain_config::OnChainConfig,
+    state_store::state_key::inner::StateKeyInner,
+    write_set::{TransactionWrite, WriteSet},
+};
+
+/// Returns the packages of every package registry written by `write_set`.
+///
+/// Packages are never removed from a registry, so a deleted registry (e.g., of a deleted code
+/// object) is skipped, and its packages remain indexed as last published.
+pub fn extract_package_updates(write_set: &WriteSet) -> Vec<IndexedPackage> {
+    let registry_tag = PackageRegistry::struct_tag();
+    write_set
+        .write_op_iter()
+        .filter_map(|(state_key, write_op)| {
+            let StateKeyInner::AccessPath(access_path) = state_key.inner() else {
+                return None;
+            };
+            match access_path.get_path() {
+                Path::Resource(struct_tag) if struct_tag == registry_tag => {
+                    let registry = bcs::from_bytes::<PackageRegistry>(write_op.bytes()?).ok()?;
+                    Some((access_path.address, registry))
+                },
+                _ => None,
+            }
+        })
+        .flat_map(|(address, registry)| {
+            registry
+                .packages
+                .into_iter()
+                .map(move |package| to_indexed_package(address, package))
+        })
+        .collect()
+}
+
+fn to_indexed_package(address: AccountAddress, package: PackageMetadata) -> IndexedPackage {
+    IndexedPackage {
+        address,
+        name: package.name,
+        upgrade_number: package.upgrade_number,
+        upgrade_policy: package.upgrade_policy.policy,
+        modules: package
+            .modules
+            .into_iter()
+            .map(|module| module.name)
+            .collect(),
+        deps: package
+            .deps
+            .into_iter()
+            .map(|dep| (dep.account, dep.package_name))
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aptos_framework::natives::code::{ModuleMetadata, PackageDep, UpgradePolicy};
+    use aptos_types::{
+        state_store::state_key::StateKey,
+        write_set::{WriteOp, WriteSetMut},
+    };
+
+    #[test]
+    fn test_extract_package_updates() {
+        let address = AccountAddress::random();
+        let registry = PackageRegistry {
+            packages: vec![PackageMetadata {
+                name: "package".to_string(),
+                upgrade_policy: UpgradePolicy::compat(),
+                upgrade_number: 3,
+                source_digest: String::new(),
+                manifest: vec![],
+                modules: vec![ModuleMetadata {
+                    name: "module".to_string(),
+                    source: vec![],
+                    source_map: vec![],
+                    extension: Default::default(),
+                }],
+                deps: vec![PackageDep {
+                    account: AccountAddress::ONE,
+                    package_name: "AptosFramework".to_string(),
+                }],
+                extension: Default::default(),
+            }],
+        };
+        let state_key = StateKey::resource(&address, &PackageRegistry::struct_tag()).unwrap();
+        let write_set = WriteSetMut::new(vec![
+            (
+                state_key.clone(),
+                WriteOp::legacy_modification(bcs::to_bytes(&registry).unwrap().into()),
+            ),
+            (
+                StateKey::raw(b"unrelated"),
+                WriteOp::legacy_modification(vec![].into()),
+            ),
+        ])
+        .freeze()
+        .unwrap();
+
+        assert_eq!(extract_package_updates(&write_set), vec![IndexedPackage {
+            address,
+            name: "package".to_string(),
+            upgrade_number: 3,
+            upgrade_policy: UpgradePolicy::compat().policy,
+            modules: vec!["module".to_string()],
+            deps: vec![(AccountAddress::ONE, "AptosFramework".to_string())],
+        }]);
+
+        // Deleted registries are skipped
+        let write_set = WriteSetMut::new(vec![(state_key, WriteOp::legacy_deletion())])
+            .freeze()
+            .unwrap();
+        assert!(extract_package_updates(&write_set).is_empty());
+    }
+}
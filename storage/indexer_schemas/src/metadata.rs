@@ -39,6 +39,8 @@ pub enum MetadataKey {
     StateVersion,
     TransactionVersion,
     EventV2TranslationVersion,
+    BalanceVersion,
+    PackageVersion,
 }
 
 #[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
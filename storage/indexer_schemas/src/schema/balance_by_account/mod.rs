@@ -0,0 +1,86 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! This module defines physical storage schema for a balance index via which the balance an
+//! account held of an asset can be found as of any version. A record is written at every version
+//! that changed the balance, so the balance at version `v` is the record with the largest version
+//! not greater than `v`.
+//!
+//! ```text
+//! |<-----------key----------->|<-value->|
+//! | address | asset | txn_ver |  amount |
+//! ```
+//!
+//! `asset` is BCS encoded, which is prefix-free, so all records of one (address, asset) pair are
+//! contiguous and ordered by version.
+
+use crate::{schema::BALANCE_BY_ACCOUNT_CF_NAME, utils::ensure_slice_len_eq};
+use anyhow::{ensure, Result};
+use aptos_schemadb::{
+    define_pub_schema,
+    schema::{KeyCodec, SeekKeyCodec, ValueCodec},
+};
+use aptos_types::{
+    account_address::AccountAddress, indexer::indexer_db_reader::BalanceAsset, transaction::Version,
+};
+use byteorder::{BigEndian, ReadBytesExt, WriteBytesExt};
+use std::{convert::TryFrom, mem::size_of};
+
+define_pub_schema!(
+    BalanceByAccountSchema,
+    Key,
+    Amount,
+    BALANCE_BY_ACCOUNT_CF_NAME
+);
+
+type Amount = u64;
+type Key = (AccountAddress, BalanceAsset, Version);
+
+impl KeyCodec<BalanceByAccountSchema> for Key {
+    fn encode_key(&self) -> Result<Vec<u8>> {
+        let (ref address, ref asset, version) = *self;
+
+        let mut encoded = address.to_vec();
+        bcs::serialize_into(&mut encoded, asset)?;
+        encoded.write_u64::<BigEndian>(version)?;
+
+        Ok(encoded)
+    }
+
+    fn decode_key(data: &[u8]) -> Result<Self> {
+        const VERSION_SIZE: usize = size_of::<Version>();
+        ensure!(
+            data.len() > AccountAddress::LENGTH + VERSION_SIZE,
+            "Unexpected data len {}.",
+            data.len(),
+        );
+
+        let version_offset = data.len() - VERSION_SIZE;
+        let address = AccountAddress::try_from(&data[..AccountAddress::LENGTH])?;
+        let asset = bcs::from_bytes(&data[AccountAddress::LENGTH..version_offset])?;
+        let version = (&data[version_offset..]).read_u64::<BigEndian>()?;
+
+        Ok((address, asset, version))
+    }
+}
+
+impl ValueCodec<BalanceByAccountSchema> for Amount {
+    fn encode_value(&self) -> Result<Vec<u8>> {
+        Ok(self.to_be_bytes().to_vec())
+    }
+
+    fn decode_value(mut data: &[u8]) -> Result<Self> {
+        ensure_slice_len_eq(data, size_of::<Self>())?;
+
+        Ok(data.read_u64::<BigEndian>()?)
+    }
+}
+
+impl SeekKeyCodec<BalanceByAccountSchema> for &AccountAddress {
+    fn encode_seek_key(&self) -> Result<Vec<u8>> {
+        Ok(self.to_vec())
+    }
+}
+
+#[cfg(test)]
+mod test;
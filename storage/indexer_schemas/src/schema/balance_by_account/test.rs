@@ -0,0 +1,20 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use aptos_schemadb::{schema::fuzzing::assert_encode_decode, test_no_panic_decoding};
+use proptest::prelude::*;
+
+proptest! {
+    #[test]
+    fn test_encode_decode(
+        address in any::<AccountAddress>(),
+        asset in any::<BalanceAsset>(),
+        version in any::<Version>(),
+        amount in any::<u64>(),
+    ) {
+        assert_encode_decode::<BalanceByAccountSchema>(&(address, asset, version), &amount);
+    }
+}
+
+test_no_panic_decoding!(BalanceByAccountSchema);
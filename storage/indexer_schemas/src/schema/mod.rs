@@ -6,11 +6,13 @@
 //!
 //! All schemas are `pub(crate)` so not shown in rustdoc, refer to the source code to see details.
 
+pub mod balance_by_account;
 pub mod event_by_key;
 pub mod event_by_version;
 pub mod event_sequence_number;
 pub mod indexer_metadata;
 pub mod ordered_transaction_by_account;
+pub mod package_by_name;
 pub mod state_keys;
 pub mod table_info;
 pub mod translated_v1_event;
@@ -28,6 +30,8 @@ pub const ORDERED_TRANSACTION_BY_ACCOUNT_CF_NAME: ColumnFamilyName = "transactio
 pub const STATE_KEYS_CF_NAME: ColumnFamilyName = "state_keys";
 pub const TRANSLATED_V1_EVENT_CF_NAME: ColumnFamilyName = "translated_v1_event";
 pub const EVENT_SEQUENCE_NUMBER_CF_NAME: ColumnFamilyName = "event_sequence_number";
+pub const BALANCE_BY_ACCOUNT_CF_NAME: ColumnFamilyName = "balance_by_account";
+pub const PACKAGE_BY_NAME_CF_NAME: ColumnFamilyName = "package_by_name";
 
 pub fn column_families() -> Vec<ColumnFamilyName> {
     vec![
@@ -47,6 +51,8 @@ pub fn internal_indexer_column_families() -> Vec<ColumnFamilyName> {
         STATE_KEYS_CF_NAME,
         TRANSLATED_V1_EVENT_CF_NAME,
         EVENT_SEQUENCE_NUMBER_CF_NAME,
+        BALANCE_BY_ACCOUNT_CF_NAME,
+        PACKAGE_BY_NAME_CF_NAME,
     ]
 }
 
@@ -0,0 +1,85 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! This module defines physical storage schema for a package index via which the packages
+//! published under a name can be found, across all addresses, as of any version. A record is
+//! written at every version that changed the package registry of the address, so the package at
+//! version `v` is the record with the largest version not greater than `v`.
+//!
+//! ```text
+//! |<------------key------------>|<--value-->|
+//! | name | address | txn_ver    |  package  |
+//! ```
+//!
+//! `name` is BCS encoded, which is prefix-free, so all records of one name are contiguous, and
+//! ordered by address then version.
+
+use crate::schema::PACKAGE_BY_NAME_CF_NAME;
+use anyhow::{ensure, Result};
+use aptos_schemadb::{
+    define_pub_schema,
+    schema::{KeyCodec, SeekKeyCodec, ValueCodec},
+};
+use aptos_types::{
+    account_address::AccountAddress, indexer::indexer_db_reader::IndexedPackage,
+    transaction::Version,
+};
+use byteorder::{BigEndian, ReadBytesExt, WriteBytesExt};
+use std::{convert::TryFrom, mem::size_of};
+
+define_pub_schema!(
+    PackageByNameSchema,
+    Key,
+    IndexedPackage,
+    PACKAGE_BY_NAME_CF_NAME
+);
+
+type Key = (String, AccountAddress, Version);
+
+impl KeyCodec<PackageByNameSchema> for Key {
+    fn encode_key(&self) -> Result<Vec<u8>> {
+        let (ref name, ref address, version) = *self;
+
+        let mut encoded = bcs::to_bytes(name)?;
+        encoded.extend_from_slice(address.as_ref());
+        encoded.write_u64::<BigEndian>(version)?;
+
+        Ok(encoded)
+    }
+
+    fn decode_key(data: &[u8]) -> Result<Self> {
+        const SUFFIX_SIZE: usize = AccountAddress::LENGTH + size_of::<Version>();
+        ensure!(
+            data.len() > SUFFIX_SIZE,
+            "Unexpected data len {}.",
+            data.len(),
+        );
+
+        let address_offset = data.len() - SUFFIX_SIZE;
+        let version_offset = address_offset + AccountAddress::LENGTH;
+        let name = bcs::from_bytes(&data[..address_offset])?;
+        let address = AccountAddress::try_from(&data[address_offset..version_offset])?;
+        let version = (&data[version_offset..]).read_u64::<BigEndian>()?;
+
+        Ok((name, address, version))
+    }
+}
+
+impl ValueCodec<PackageByNameSchema> for IndexedPackage {
+    fn encode_value(&self) -> Result<Vec<u8>> {
+        Ok(bcs::to_bytes(self)?)
+    }
+
+    fn decode_value(data: &[u8]) -> Result<Self> {
+        Ok(bcs::from_bytes(data)?)
+    }
+}
+
+impl SeekKeyCodec<PackageByNameSchema> for &str {
+    fn encode_seek_key(&self) -> Result<Vec<u8>> {
+        Ok(bcs::to_bytes(self)?)
+    }
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,20 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use aptos_schemadb::{schema::fuzzing::assert_encode_decode, test_no_panic_decoding};
+use proptest::prelude::*;
+
+proptest! {
+    #[test]
+    fn test_encode_decode(
+        name in any::<String>(),
+        address in any::<AccountAddress>(),
+        version in any::<Version>(),
+        package in any::<IndexedPackage>(),
+    ) {
+        assert_encode_decode::<PackageByNameSchema>(&(name, address, version), &package);
+    }
+}
+
+test_no_panic_decoding!(PackageByNameSchema);
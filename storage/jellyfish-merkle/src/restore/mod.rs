@@ -37,6 +37,10 @@ use std::{
     },
 };
 
+mod sharded;
+
+pub use sharded::JellyfishMerkleShardedRestore;
+
 static IO_POOL: Lazy<rayon::ThreadPool> = Lazy::new(|| {
     rayon::ThreadPoolBuilder::new()
         .num_threads(32)
@@ -131,6 +135,10 @@ pub struct JellyfishMerkleRestore<K> {
     /// The version of the tree we are restoring.
     version: Version,
 
+    /// The depth of the root of the restored (sub)tree, non-zero only when restoring a shard for
+    /// a `JellyfishMerkleShardedRestore`.
+    root_depth: usize,
+
     /// The nodes we have partially restored. Each `partial_nodes[i-1]` is the parent of
     /// `partial_nodes[i]`. If a node `partial_nodes[i-1]` has multiple children, only the
     /// rightmost known child will appear here as `partial_nodes[i]`, because any other children on
@@ -224,6 +232,7 @@ where
         Ok(Self {
             store,
             version,
+            root_depth: 0,
             partial_nodes,
             frozen_nodes: HashMap::new(),
             previous_leaf,
@@ -243,6 +252,7 @@ where
         Ok(Self {
             store,
             version,
+            root_depth: 0,
             partial_nodes: vec![InternalInfo::new_empty(NodeKey::new_empty_path(version))],
             frozen_nodes: HashMap::new(),
             previous_leaf: None,
@@ -371,6 +381,16 @@ where
             return Ok(());
         }
 
+        self.add_leaves(chunk)?;
+
+        // Verify what we have added so far is all correct.
+        self.verify(proof)?;
+
+        self.write_frozen_nodes()
+    }
+
+    /// Adds states in increasing order of their key hashes, without verifying them.
+    fn add_leaves(&mut self, chunk: Vec<(&K, HashValue)>) -> Result<()> {
         for (key, value_hash) in chunk {
             let hashed_key = key.hash();
             if let Some(ref prev_leaf) = self.previous_leaf {
@@ -387,11 +407,11 @@ where
             self.add_one(key, value_hash);
             self.num_keys_received += 1;
         }
+        Ok(())
+    }
 
-        // Verify what we have added so far is all correct.
-        self.verify(proof)?;
-
-        // Write the frozen nodes to storage.
+    /// Writes the frozen nodes to storage.
+    fn write_frozen_nodes(&mut self) -> Result<()> {
         if self.async_commit {
             self.wait_for_async_commit()?;
             let (tx, rx) = channel();
@@ -418,8 +438,12 @@ where
         let new_hashed_key = new_key.hash();
         let nibble_path = NibblePath::new_even(new_hashed_key.to_vec());
         let mut nibbles = nibble_path.nibbles();
+        // Skip the nibbles above the root of the (sub)tree.
+        for _ in 0..self.root_depth {
+            nibbles.next();
+        }
 
-        for i in 0..ROOT_NIBBLE_HEIGHT {
+        for i in 0..ROOT_NIBBLE_HEIGHT - self.root_depth {
             let child_index = u8::from(nibbles.next().expect("This nibble must exist.")) as usize;
 
             assert!(i < self.partial_nodes.len());
@@ -498,7 +522,7 @@ where
         let common_prefix_len = existing_leaf
             .account_key()
             .common_prefix_nibbles_len(new_hashed_key);
-        for _ in num_existing_partial_nodes..common_prefix_len {
+        for _ in self.root_depth + num_existing_partial_nodes..common_prefix_len {
             let visited_nibbles = remaining_nibbles.visited_nibbles().collect();
             let next_nibble = remaining_nibbles.next().expect("This nibble must exist.");
             let new_node_key = NodeKey::new(self.version, visited_nibbles);
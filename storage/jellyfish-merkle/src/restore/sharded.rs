@@ -0,0 +1,193 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! This module implements restoring a `JellyfishMerkleTree` from chunks of states for different
+//! key ranges in parallel.
+//!
+//! The tree is split into 16 shards by the first nibble of the key hashes. Every shard is
+//! restored by its own [`JellyfishMerkleRestore`] rooted one level below the root of the tree,
+//! so the states of different shards can be ingested, hashed and written by parallel workers.
+//! The root node is assembled from the roots of the shards once all of them are finished.
+
+use super::{ChildInfo, InternalInfo, JellyfishMerkleRestore};
+use crate::{
+    node_type::{Node, NodeKey},
+    NibbleExt, TreeWriter,
+};
+use aptos_crypto::{hash::CryptoHash, HashValue};
+use aptos_infallible::Mutex;
+use aptos_storage_interface::{db_ensure as ensure, Result};
+use aptos_types::transaction::Version;
+use itertools::Itertools;
+use rayon::prelude::*;
+use std::{collections::HashMap, sync::Arc};
+
+const NUM_SHARDS: usize = 16;
+
+pub struct JellyfishMerkleShardedRestore<K> {
+    store: Arc<dyn TreeWriter<K>>,
+    version: Version,
+    expected_root_hash: HashValue,
+    shards: Vec<Mutex<JellyfishMerkleRestore<K>>>,
+}
+
+impl<K> JellyfishMerkleShardedRestore<K>
+where
+    K: crate::Key + CryptoHash + 'static,
+{
+    /// Starts restoring the tree from scratch, a sharded restore can't be resumed.
+    pub fn new<D: 'static + TreeWriter<K>>(
+        store: Arc<D>,
+        version: Version,
+        expected_root_hash: HashValue,
+    ) -> Self {
+        let shards = (0..NUM_SHARDS)
+            .map(|shard_id| {
+                Mutex::new(JellyfishMerkleRestore::new_shard(
+                    store.clone(),
+                    version,
+                    shard_id,
+                ))
+            })
+            .collect();
+
+        Self {
+            store,
+            version,
+            expected_root_hash,
+            shards,
+        }
+    }
+
+    /// Adds a chunk of states sorted by key hash. Within a shard, states must be added in
+    /// increasing order, but chunks for different shards can be added concurrently.
+    ///
+    /// Unlike with [`JellyfishMerkleRestore`], chunks are not verified against range proofs,
+    /// since that requires all states on their left to be restored. The tree is only verified
+    /// against the expected root hash in [`Self::finish`].
+    pub fn add_chunk(&self, chunk: Vec<(&K, HashValue)>) -> Result<()> {
+        let mut shard_chunks = (0..NUM_SHARDS).map(|_| Vec::new()).collect_vec();
+        for (key, value_hash) in chunk {
+            let shard_id = u8::from(key.hash().get_nibble(0)) as usize;
+            shard_chunks[shard_id].push((key, value_hash));
+        }
+
+        for (shard_id, shard_chunk) in shard_chunks.into_iter().enumerate() {
+            if !shard_chunk.is_empty() {
+                let mut shard = self.shards[shard_id].lock();
+                shard.add_leaves(shard_chunk)?;
+                shard.write_frozen_nodes()?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Finishes all shards, then verifies and writes the root node.
+    pub fn finish(self) -> Result<()> {
+        let shard_roots = self
+            .shards
+            .into_par_iter()
+            .map(|shard| shard.into_inner().finish_shard())
+            .collect::<Result<Vec<_>>>()?;
+
+        let root_node_key = NodeKey::new_empty_path(self.version);
+        let mut root = InternalInfo::new_empty(root_node_key.clone());
+        for (shard_id, shard_root) in shard_roots.into_iter().enumerate() {
+            if let Some(child_info) = shard_root {
+                root.set_child(shard_id, child_info);
+            }
+        }
+
+        let mut node_batch = HashMap::new();
+        let children = root.children.iter().flatten().collect_vec();
+        let root_node: Node<K> = match children.as_slice() {
+            [] => Node::Null,
+            // A single leaf is moved up to the root.
+            [ChildInfo::Leaf(leaf)] => leaf.clone().into(),
+            _ => {
+                // Shards with a single leaf don't write it, since its position depends on the
+                // other shards.
+                for (shard_id, child_info) in root.children.iter().enumerate() {
+                    if let Some(ChildInfo::Leaf(leaf)) = child_info {
+                        node_batch.insert(
+                            root_node_key.gen_child_node_key(self.version, (shard_id as u8).into()),
+                            leaf.clone().into(),
+                        );
+                    }
+                }
+                root.into_internal_node(self.version).1.into()
+            },
+        };
+
+        ensure!(
+            root_node.hash() == self.expected_root_hash,
+            "Restored tree has root hash {}, expecting {}",
+            root_node.hash(),
+            self.expected_root_hash,
+        );
+        node_batch.insert(root_node_key, root_node);
+        self.store.write_node_batch(&node_batch)
+    }
+}
+
+impl<K> JellyfishMerkleRestore<K>
+where
+    K: crate::Key + CryptoHash + 'static,
+{
+    fn new_shard(store: Arc<dyn TreeWriter<K>>, version: Version, shard_id: usize) -> Self {
+        let shard_root_key =
+            NodeKey::new_empty_path(version).gen_child_node_key(version, (shard_id as u8).into());
+
+        Self {
+            store,
+            version,
+            root_depth: 1,
+            partial_nodes: vec![InternalInfo::new_empty(shard_root_key)],
+            frozen_nodes: HashMap::new(),
+            previous_leaf: None,
+            num_keys_received: 0,
+            // Chunks of a shard are not verified, the sharded restore checks the root hash.
+            expected_root_hash: HashValue::zero(),
+            finished: false,
+            async_commit: false,
+            async_commit_result: None,
+        }
+    }
+
+    /// Writes all remaining nodes of the shard, and returns what the root node needs to know
+    /// about the shard, if it is not empty.
+    ///
+    /// A shard with a single leaf doesn't write anything, since the leaf moves up to the root
+    /// if it's the only one in the tree.
+    fn finish_shard(mut self) -> Result<Option<ChildInfo<K>>> {
+        if self.partial_nodes.len() == 1 {
+            let children = self.partial_nodes[0]
+                .children
+                .iter()
+                .flatten()
+                .collect_vec();
+            match children.as_slice() {
+                [] => return Ok(None),
+                [ChildInfo::Leaf(leaf)] => return Ok(Some(ChildInfo::Leaf(leaf.clone()))),
+                _ => (),
+            }
+        }
+
+        // Freeze everything below the root of the shard, then the root itself.
+        self.freeze(1);
+        let (node_key, internal_node) = self
+            .partial_nodes
+            .pop()
+            .expect("The root of the shard must exist.")
+            .into_internal_node(self.version);
+        let child_info = ChildInfo::Internal {
+            hash: Some(internal_node.hash()),
+            leaf_count: Some(internal_node.leaf_count()),
+        };
+        self.frozen_nodes.insert(node_key, internal_node.into());
+        self.store.write_node_batch(&self.frozen_nodes)?;
+        self.frozen_nodes.clear();
+
+        Ok(Some(child_info))
+    }
+}
@@ -36,8 +36,8 @@ use batch::{IntoRawBatch, NativeBatch, WriteBatch};
 use iterator::{ScanDirection, SchemaIterator};
 /// Type alias to `rocksdb::ReadOptions`. See [`rocksdb doc`](https://github.com/pingcap/rust-rocksdb/blob/master/src/rocksdb_options.rs)
 pub use rocksdb::{
-    BlockBasedOptions, Cache, ColumnFamilyDescriptor, DBCompressionType, Options, ReadOptions,
-    SliceTransform, DEFAULT_COLUMN_FAMILY_NAME,
+    BlockBasedOptions, Cache, ColumnFamilyDescriptor, CompactionDecision, DBCompressionType,
+    Options, ReadOptions, SliceTransform, DEFAULT_COLUMN_FAMILY_NAME,
 };
 use rocksdb::{ErrorKind, WriteOptions};
 use std::{collections::HashSet, fmt::Debug, iter::Iterator, path::Path};
@@ -294,6 +294,15 @@ impl DB {
         self.write_schemas_inner(batch, &WriteOptions::default())
     }
 
+    /// Writes without the write-ahead log. Records that are not yet flushed to SST files are
+    /// lost if the process crashes, so this is only for data that can be recovered elsewhere or
+    /// recomputed, in exchange for skipping the WAL write.
+    pub fn write_schemas_ephemeral(&self, batch: impl IntoRawBatch) -> DbResult<()> {
+        let mut option = WriteOptions::default();
+        option.disable_wal(true);
+        self.write_schemas_inner(batch, &option)
+    }
+
     fn get_cf_handle(&self, cf_name: &str) -> DbResult<&rocksdb::ColumnFamily> {
         self.inner
             .cf_handle(cf_name)
@@ -9,6 +9,8 @@ mod sparse_merkle;
 #[cfg(any(test, feature = "bench", feature = "fuzzing"))]
 pub use crate::sparse_merkle::test_utils;
 pub use crate::sparse_merkle::{
-    dropper::SUBTREE_DROPPER, utils::get_state_shard_id, FrozenSparseMerkleTree, ProofRead,
-    SparseMerkleTree, StateStoreStatus,
+    dropper::SUBTREE_DROPPER,
+    memory::{is_under_memory_pressure, set_rss_budget_bytes},
+    utils::get_state_shard_id,
+    FrozenSparseMerkleTree, ProofRead, SparseMerkleTree, StateStoreStatus,
 };
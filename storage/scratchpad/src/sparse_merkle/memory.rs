@@ -0,0 +1,121 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Accounts for the memory held by the in-memory nodes of all the sparse Merkle trees in the
+//! process, and detects when the process approaches its RSS budget, so that the owner of the
+//! trees can drop older generations earlier (by persisting them sooner).
+
+use crate::sparse_merkle::{metrics::MEMORY, node::Node};
+use aptos_metrics_core::IntGaugeVecHelper;
+use std::sync::atomic::{AtomicI64, AtomicU64, Ordering};
+
+/// The process is considered under memory pressure once its RSS reaches this percentage of the
+/// budget, so that older generations can be dropped before the budget is actually exceeded.
+const PRESSURE_THRESHOLD_PERCENT: u64 = 90;
+
+static NUM_LIVE_LEAVES: AtomicI64 = AtomicI64::new(0);
+static NUM_LIVE_INTERNAL_NODES: AtomicI64 = AtomicI64::new(0);
+/// 0 means no budget.
+static RSS_BUDGET_BYTES: AtomicU64 = AtomicU64::new(0);
+
+pub(crate) fn on_node_created(is_leaf: bool) {
+    live_node_counter(is_leaf).fetch_add(1, Ordering::Relaxed);
+}
+
+pub(crate) fn on_node_dropped(is_leaf: bool) {
+    live_node_counter(is_leaf).fetch_sub(1, Ordering::Relaxed);
+}
+
+fn live_node_counter(is_leaf: bool) -> &'static AtomicI64 {
+    if is_leaf {
+        &NUM_LIVE_LEAVES
+    } else {
+        &NUM_LIVE_INTERNAL_NODES
+    }
+}
+
+/// The number of nodes held in memory by all the trees.
+pub fn num_live_nodes() -> usize {
+    (NUM_LIVE_LEAVES.load(Ordering::Relaxed) + NUM_LIVE_INTERNAL_NODES.load(Ordering::Relaxed))
+        .max(0) as usize
+}
+
+/// The bytes held by the nodes in memory, counting the `Arc` each node is allocated in.
+pub fn live_node_bytes() -> usize {
+    num_live_nodes() * (std::mem::size_of::<Node>() + 2 * std::mem::size_of::<usize>())
+}
+
+/// Reports the memory usage, given the number of generations between the oldest tree still
+/// referenced and the latest one.
+pub(crate) fn log_memory_usage(num_retained_generations: u64) {
+    let num_live_nodes = num_live_nodes();
+    MEMORY.set_with(&["live_leaves"], NUM_LIVE_LEAVES.load(Ordering::Relaxed));
+    MEMORY.set_with(
+        &["live_internal_nodes"],
+        NUM_LIVE_INTERNAL_NODES.load(Ordering::Relaxed),
+    );
+    MEMORY.set_with(&["live_node_bytes"], live_node_bytes() as i64);
+    MEMORY.set_with(&["retained_generations"], num_retained_generations as i64);
+    MEMORY.set_with(
+        &["nodes_per_retained_generation"],
+        (num_live_nodes as u64 / num_retained_generations.max(1)) as i64,
+    );
+}
+
+/// Sets the RSS budget of the process, see `is_under_memory_pressure()`. 0 disables the check.
+pub fn set_rss_budget_bytes(budget: u64) {
+    RSS_BUDGET_BYTES.store(budget, Ordering::Relaxed);
+    MEMORY.set_with(&["rss_budget_bytes"], budget as i64);
+}
+
+/// Whether the process RSS approaches the budget set by `set_rss_budget_bytes()`. Always false
+/// if no budget is set or the RSS can't be read (e.g., not on Linux).
+pub fn is_under_memory_pressure() -> bool {
+    let budget = RSS_BUDGET_BYTES.load(Ordering::Relaxed);
+    if budget == 0 {
+        return false;
+    }
+    let Some(rss) = current_rss_bytes() else {
+        return false;
+    };
+    MEMORY.set_with(&["rss_bytes"], rss as i64);
+
+    let under_pressure = rss as u128 * 100 >= budget as u128 * PRESSURE_THRESHOLD_PERCENT as u128;
+    MEMORY.set_with(&["under_pressure"], under_pressure as i64);
+    under_pressure
+}
+
+fn current_rss_bytes() -> Option<u64> {
+    let status = std::fs::read_to_string("/proc/self/status").ok()?;
+    parse_vm_rss_bytes(&status)
+}
+
+/// Parses the `VmRSS:    1234 kB` line of `/proc/<pid>/status`.
+fn parse_vm_rss_bytes(status: &str) -> Option<u64> {
+    let line = status.lines().find(|line| line.starts_with("VmRSS:"))?;
+    let mut fields = line["VmRSS:".len()..].split_whitespace();
+    let value: u64 = fields.next()?.parse().ok()?;
+    match fields.next()? {
+        "kB" => Some(value * 1024),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_vm_rss_bytes() {
+        let status = "Name:\taptos-node\nVmPeak:\t  4096 kB\nVmRSS:\t  2048 kB\nThreads:\t8\n";
+        assert_eq!(parse_vm_rss_bytes(status), Some(2048 * 1024));
+        assert_eq!(parse_vm_rss_bytes("Name:\taptos-node\n"), None);
+        assert_eq!(parse_vm_rss_bytes("VmRSS:\t2048 MB\n"), None);
+    }
+
+    #[test]
+    fn test_no_pressure_without_budget() {
+        set_rss_budget_bytes(0);
+        assert!(!is_under_memory_pressure());
+    }
+}
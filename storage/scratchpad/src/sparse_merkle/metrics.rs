@@ -27,3 +27,12 @@ pub static GENERATION: Lazy<IntGaugeVec> = Lazy::new(|| {
     )
     .unwrap()
 });
+
+pub static MEMORY: Lazy<IntGaugeVec> = Lazy::new(|| {
+    register_int_gauge_vec!(
+        "aptos_scratchpad_smt_memory",
+        "Memory held by the in-memory nodes, and the RSS budget they are held under.",
+        &["name"],
+    )
+    .unwrap()
+});
@@ -71,6 +71,7 @@
 #![allow(clippy::while_let_loop)]
 
 pub mod dropper;
+pub mod memory;
 mod metrics;
 mod node;
 #[cfg(test)]
@@ -216,6 +217,7 @@ impl SparseMerkleTree {
 
         self.log_generation("freeze");
         base_smt.log_generation("oldest");
+        memory::log_memory_usage(self.generation().saturating_sub(base_smt.generation()) + 1);
 
         FrozenSparseMerkleTree {
             base_smt: base_smt.clone(),
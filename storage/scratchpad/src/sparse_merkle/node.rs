@@ -22,6 +22,7 @@
 //! corresponding account content. The difference is that a `LeafNode` does not always have the
 //! value, in the case when the leaf was loaded into memory as part of a non-inclusion proof.
 
+use crate::sparse_merkle::memory;
 use aptos_crypto::{
     hash::{CryptoHash, SPARSE_MERKLE_PLACEHOLDER_HASH},
     HashValue,
@@ -65,31 +66,41 @@ impl Node {
 }
 
 impl Node {
+    fn new(inner: NodeInner, generation: u64) -> Self {
+        let node = Self { generation, inner };
+        memory::on_node_created(node.is_leaf());
+        node
+    }
+
     pub fn new_leaf(key: HashValue, value: HashValue, generation: u64) -> Self {
-        Self {
-            generation,
-            inner: NodeInner::Leaf(LeafNode::new(key, value)),
-        }
+        Self::new(NodeInner::Leaf(LeafNode::new(key, value)), generation)
     }
 
     #[cfg(test)]
     pub fn new_internal(left: SubTree, right: SubTree, generation: u64) -> Self {
-        Self {
+        Self::new(
+            NodeInner::Internal(InternalNode { left, right }),
             generation,
-            inner: NodeInner::Internal(InternalNode { left, right }),
-        }
+        )
     }
 
     pub fn new_internal_from_node(node: InternalNode, generation: u64) -> Self {
-        Self {
-            generation,
-            inner: NodeInner::Internal(node),
-        }
+        Self::new(NodeInner::Internal(node), generation)
     }
 
     pub fn inner(&self) -> &NodeInner {
         &self.inner
     }
+
+    fn is_leaf(&self) -> bool {
+        matches!(self.inner, NodeInner::Leaf(_))
+    }
+}
+
+impl Drop for Node {
+    fn drop(&mut self) {
+        memory::on_node_dropped(self.is_leaf());
+    }
 }
 
 #[derive(Debug)]
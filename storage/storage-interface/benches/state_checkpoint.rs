@@ -0,0 +1,131 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Benchmarks hashing the state updates of a block into the state summary (i.e., computing the
+//! state checkpoint), in isolation from the VM. Blocks are generated synthetically with varying
+//! sizes and key skews, and applied on top of a state summary that is fully in memory, so that
+//! the proof reader is never hit.
+
+use aptos_storage_interface::{
+    mock::MockDbReaderWriter,
+    state_store::{
+        state_summary::{LedgerStateSummary, ProvableStateSummary, StateSummary},
+        state_update_refs::StateUpdateRefs,
+    },
+};
+use aptos_types::{
+    state_store::state_key::StateKey,
+    transaction::Version,
+    write_set::{WriteOp, WriteSet, WriteSetMut},
+};
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+/// The number of distinct keys written by the blocks
+const NUM_KEYS: usize = 1_000_000;
+/// The number of keys written before the measured blocks, so they don't start from an empty tree
+const NUM_BASE_KEYS: usize = 200_000;
+const WRITES_PER_TXN: usize = 4;
+
+/// How the keys written by a block are distributed
+#[derive(Clone, Copy)]
+enum Skew {
+    Uniform,
+    /// 90% of the writes go to 1% of the keys
+    Hot,
+}
+
+impl Skew {
+    fn name(&self) -> &'static str {
+        match self {
+            Skew::Uniform => "uniform",
+            Skew::Hot => "hot",
+        }
+    }
+
+    fn gen_key_index(&self, num_keys: usize, rng: &mut StdRng) -> usize {
+        match self {
+            Skew::Uniform => rng.gen_range(0, num_keys),
+            Skew::Hot => {
+                if rng.gen_ratio(9, 10) {
+                    rng.gen_range(0, num_keys / 100)
+                } else {
+                    rng.gen_range(0, num_keys)
+                }
+            },
+        }
+    }
+}
+
+fn gen_write_sets(
+    keys: &[StateKey],
+    num_txns: usize,
+    skew: Skew,
+    rng: &mut StdRng,
+) -> Vec<WriteSet> {
+    (0..num_txns)
+        .map(|_| {
+            WriteSetMut::new((0..WRITES_PER_TXN).map(|_| {
+                let key = keys[skew.gen_key_index(keys.len(), rng)].clone();
+                let value = rng.gen::<[u8; 32]>().to_vec();
+                (key, WriteOp::legacy_modification(value.into()))
+            }))
+            .freeze()
+            .unwrap()
+        })
+        .collect()
+}
+
+fn index_block(first_version: Version, write_sets: &[WriteSet]) -> StateUpdateRefs {
+    // A regular block, ending with a checkpoint
+    StateUpdateRefs::index_write_sets(
+        first_version,
+        write_sets,
+        write_sets.len(),
+        Some(write_sets.len() - 1),
+    )
+}
+
+fn state_checkpoint(c: &mut Criterion) {
+    let mut rng = StdRng::seed_from_u64(0);
+    let keys: Vec<_> = (0..NUM_KEYS)
+        .map(|i| StateKey::raw(&i.to_le_bytes()))
+        .collect();
+
+    // Nothing is persisted, so the proof reader is never asked for proofs
+    let db = MockDbReaderWriter;
+    let persisted = ProvableStateSummary::new(StateSummary::new_empty(), &db);
+
+    let base_write_sets = gen_write_sets(
+        &keys[..NUM_BASE_KEYS],
+        NUM_BASE_KEYS / WRITES_PER_TXN,
+        Skew::Uniform,
+        &mut rng,
+    );
+    let base = LedgerStateSummary::new_empty()
+        .update(&persisted, &index_block(0, &base_write_sets))
+        .unwrap();
+
+    let mut group = c.benchmark_group("ledger_state_summary_update");
+    for skew in [Skew::Uniform, Skew::Hot] {
+        for num_txns in [1_000, 5_000, 20_000] {
+            let write_sets = gen_write_sets(&keys, num_txns, skew, &mut rng);
+            let updates = index_block(base.next_version(), &write_sets);
+
+            group.throughput(Throughput::Elements((num_txns * WRITES_PER_TXN) as u64));
+            group.bench_function(BenchmarkId::new(skew.name(), num_txns), |b| {
+                // the resulting summary is returned so the cost of dropping it is not counted
+                b.iter_with_large_drop(|| base.update(&persisted, &updates).unwrap())
+            });
+        }
+    }
+    group.finish();
+}
+
+criterion_group!(
+    name = state_checkpoint_benches;
+    config = Criterion::default().sample_size(10);
+    targets = state_checkpoint
+);
+
+criterion_main!(state_checkpoint_benches);
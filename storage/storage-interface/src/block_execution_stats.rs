@@ -0,0 +1,93 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use aptos_types::{
+    state_store::state_storage_usage::StateStorageUsage,
+    transaction::{
+        BlockConflictStats, ExecutionStatus, TransactionOutput, TransactionStatus, Version,
+    },
+};
+use serde::{Deserialize, Serialize};
+use std::ops::Deref;
+
+/// Statistics about the execution of a block, assembled by the executor so that readers don't
+/// need to recompute them from the transactions of the block.
+///
+/// Only recorded by nodes that execute blocks; nodes that apply or re-execute chunks during state
+/// sync don't see discarded transactions or block executor conflicts and don't record any.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[cfg_attr(any(test, feature = "fuzzing"), derive(proptest_derive::Arbitrary))]
+pub enum BlockExecutionStats {
+    V0(BlockExecutionStatsV0),
+}
+
+impl BlockExecutionStats {
+    /// `statuses_for_input_txns` is expected to cover the input transactions only, and
+    /// `committed_outputs` every transaction committed for the block, including the block
+    /// epilogue.
+    pub fn new(
+        first_version: Version,
+        statuses_for_input_txns: &[TransactionStatus],
+        committed_outputs: &[TransactionOutput],
+        conflict_stats: Option<BlockConflictStats>,
+        parent_usage: StateStorageUsage,
+        result_usage: StateStorageUsage,
+    ) -> Self {
+        let mut stats = BlockExecutionStatsV0 {
+            first_version,
+            num_txns: statuses_for_input_txns.len() as u64,
+            num_conflicting_txns: conflict_stats.map(|stats| stats.num_conflicting_txns),
+            num_speculative_aborts: conflict_stats.map(|stats| stats.num_speculative_aborts),
+            ..Default::default()
+        };
+        for status in statuses_for_input_txns {
+            match status {
+                TransactionStatus::Keep(ExecutionStatus::Success) => stats.num_succeeded += 1,
+                TransactionStatus::Keep(_) => stats.num_failed += 1,
+                TransactionStatus::Discard(_) => stats.num_discarded += 1,
+                TransactionStatus::Retry => stats.num_retried += 1,
+            }
+        }
+        stats.total_gas_used = committed_outputs.iter().map(|o| o.gas_used()).sum();
+        if !parent_usage.is_untracked() && !result_usage.is_untracked() {
+            stats.state_items_delta = result_usage.items() as i64 - parent_usage.items() as i64;
+            stats.state_bytes_delta = result_usage.bytes() as i64 - parent_usage.bytes() as i64;
+        }
+
+        Self::V0(stats)
+    }
+}
+
+impl Deref for BlockExecutionStats {
+    type Target = BlockExecutionStatsV0;
+
+    fn deref(&self) -> &Self::Target {
+        match self {
+            BlockExecutionStats::V0(v0) => v0,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[cfg_attr(any(test, feature = "fuzzing"), derive(proptest_derive::Arbitrary))]
+pub struct BlockExecutionStatsV0 {
+    /// Version of the first transaction of the block.
+    pub first_version: Version,
+    /// Number of input transactions, not counting the block epilogue added by the executor.
+    pub num_txns: u64,
+    pub num_succeeded: u64,
+    /// Kept, but aborted or otherwise failed.
+    pub num_failed: u64,
+    pub num_discarded: u64,
+    /// Not executed because the block hit a limit or an epoch change.
+    pub num_retried: u64,
+    /// Total gas units used by the committed transactions.
+    pub total_gas_used: u64,
+    /// Committed transactions executed more than once, if the block was executed in parallel.
+    pub num_conflicting_txns: Option<u64>,
+    /// Aborted speculative executions, if the block was executed in parallel.
+    pub num_speculative_aborts: Option<u64>,
+    /// Change of the state storage usage, zero if usage is not tracked.
+    pub state_items_delta: i64,
+    pub state_bytes_delta: i64,
+}
@@ -6,7 +6,7 @@ use aptos_types::{
     account_address::AccountAddress, account_config::NewBlockEvent, transaction::Version,
 };
 use serde::{Deserialize, Serialize};
-use std::ops::Deref;
+use std::{fmt, ops::Deref};
 
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
 #[cfg_attr(any(test, feature = "fuzzing"), derive(proptest_derive::Arbitrary))]
@@ -85,3 +85,22 @@ impl BlockInfoV0 {
         self.first_version
     }
 }
+
+/// Which block to pick when looking up blocks by timestamp.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum BlockTimestampSearch {
+    /// The latest block with a timestamp at or before the requested one, i.e. the block whose
+    /// state the ledger was in at that time.
+    ClosestBefore,
+    /// The earliest block with a timestamp at or after the requested one.
+    ClosestAfter,
+}
+
+impl fmt::Display for BlockTimestampSearch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BlockTimestampSearch::ClosestBefore => write!(f, "at or before"),
+            BlockTimestampSearch::ClosestAfter => write!(f, "at or after"),
+        }
+    }
+}
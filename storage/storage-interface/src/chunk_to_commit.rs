@@ -1,12 +1,15 @@
 // Copyright (c) Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::state_store::{
-    state::LedgerState,
-    state_summary::LedgerStateSummary,
-    state_update_refs::StateUpdateRefs,
-    state_view::cached_state_view::ShardedStateCache,
-    state_with_summary::{LedgerStateWithSummary, StateWithSummary},
+use crate::{
+    block_execution_stats::BlockExecutionStats,
+    state_store::{
+        state::LedgerState,
+        state_summary::LedgerStateSummary,
+        state_update_refs::StateUpdateRefs,
+        state_view::cached_state_view::ShardedStateCache,
+        state_with_summary::{LedgerStateWithSummary, StateWithSummary},
+    },
 };
 use aptos_types::transaction::{
     PersistedAuxiliaryInfo, Transaction, TransactionInfo, TransactionOutput, Version,
@@ -24,6 +27,8 @@ pub struct ChunkToCommit<'a> {
     pub state_update_refs: &'a StateUpdateRefs<'a>,
     pub state_reads: &'a ShardedStateCache,
     pub is_reconfig: bool,
+    /// Only set if the chunk is a block executed by this node.
+    pub block_execution_stats: Option<&'a BlockExecutionStats>,
 }
 
 impl ChunkToCommit<'_> {
@@ -69,8 +69,18 @@ impl From<AptosDbError> for StateViewError {
     fn from(error: AptosDbError) -> Self {
         match error {
             AptosDbError::NotFound(msg) => StateViewError::NotFound(msg),
+            AptosDbError::MissingRootError(_) => StateViewError::NotFound(format!("{}", error)),
+            AptosDbError::BcsError(_) | AptosDbError::ParseIntError(_) => {
+                StateViewError::Corrupted(format!("{}", error))
+            },
+            AptosDbError::RocksDbIncompleteResult(_)
+            | AptosDbError::OtherRocksDbError(_)
+            | AptosDbError::IoError(_)
+            | AptosDbError::RecvError(_) => {
+                StateViewError::BackendUnavailable(format!("{}", error))
+            },
             AptosDbError::Other(msg) => StateViewError::Other(msg),
-            _ => StateViewError::Other(format!("{}", error)),
+            AptosDbError::TooManyRequested(..) => StateViewError::Other(format!("{}", error)),
         }
     }
 }
@@ -81,6 +91,9 @@ impl From<StateViewError> for AptosDbError {
             StateViewError::NotFound(msg) => AptosDbError::NotFound(msg),
             StateViewError::Other(msg) => AptosDbError::Other(msg),
             StateViewError::BcsError(err) => AptosDbError::BcsError(err.to_string()),
+            StateViewError::Corrupted(_)
+            | StateViewError::BackendUnavailable(_)
+            | StateViewError::Timeout(_) => AptosDbError::Other(error.to_string()),
         }
     }
 }
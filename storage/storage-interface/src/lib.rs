@@ -2,7 +2,10 @@
 // Parts of the project are originally copyright © Meta Platforms, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::state_store::state_view::hot_state_view::HotStateView;
+use crate::{
+    block_execution_stats::BlockExecutionStats, block_info::BlockTimestampSearch,
+    state_store::state_view::hot_state_view::HotStateView,
+};
 use aptos_crypto::HashValue;
 pub use aptos_types::indexer::indexer_db_reader::Order;
 use aptos_types::{
@@ -32,9 +35,10 @@ use aptos_types::{
     write_set::WriteSet,
 };
 use serde::{Deserialize, Serialize};
-use std::sync::Arc;
+use std::{ops::Range, sync::Arc, time::Duration};
 use thiserror::Error;
 
+pub mod block_execution_stats;
 pub mod block_info;
 pub mod chunk_to_commit;
 pub mod errors;
@@ -264,6 +268,22 @@ pub trait DbReader: Send + Sync {
             height: u64,
         ) -> Result<(Version, Version, NewBlockEvent)>;
 
+        /// Returns the first version, last version and `NewBlockEvent` of the block closest to
+        /// the timestamp (in microseconds), in the direction given by `search`.
+        fn get_block_by_timestamp(
+            &self,
+            timestamp_usecs: u64,
+            search: BlockTimestampSearch,
+        ) -> Result<(Version, Version, NewBlockEvent)>;
+
+        /// Returns the execution statistics of the block containing the input transaction
+        /// version, or None if they were not recorded for that block.
+        fn get_block_stats(&self, version: Version) -> Result<Option<BlockExecutionStats>>;
+
+        /// Returns the value put by [DbWriter::put_scratch_value] under the key, unless it has
+        /// expired or was lost on a crash.
+        fn get_scratch_value(&self, key: &[u8]) -> Result<Option<Vec<u8>>>;
+
         /// Gets the version of the last transaction committed before timestamp,
         /// a committed block at or after the required timestamp must exist (otherwise it's possible
         /// the next block committed as a timestamp smaller than the one in the request).
@@ -482,6 +502,16 @@ pub trait DbReader: Send + Sync {
         /// Returns state storage usage at the end of an epoch.
         fn get_state_storage_usage(&self, version: Option<Version>) -> Result<StateStorageUsage>;
 
+        /// Returns the state storage usage sampled every `granularity` versions in `range`, as
+        /// of the latest version at or before each sample that has the usage persisted, i.e.
+        /// every checkpoint committed and the end of every chunk. Samples that hit the same
+        /// version are returned once, and samples before the pruned versions are skipped.
+        fn get_usage_history(
+            &self,
+            range: Range<Version>,
+            granularity: u64,
+        ) -> Result<Vec<(Version, StateStorageUsage)>>;
+
         fn get_event_by_version_and_index(
             &self,
             version: Version,
@@ -613,6 +643,30 @@ pub trait DbWriter: Send + Sync {
     ) -> Result<()> {
         unimplemented!()
     }
+
+    /// Persists the state that is buffered in memory (i.e., the state merkle tree up to the last
+    /// checkpoint), so that it doesn't have to be replayed from the ledger on restart.
+    fn flush_buffered_state(&self) -> Result<()> {
+        unimplemented!()
+    }
+
+    /// Puts a value in the scratch space, which is separate from the ledger and meant for data
+    /// that doesn't need crash durability, e.g. data certified and retrievable elsewhere. The
+    /// value bypasses the WAL, so it can be lost on a crash, and expires after `ttl`.
+    fn put_scratch_value(&self, key: &[u8], value: &[u8], ttl: Duration) -> Result<()> {
+        unimplemented!()
+    }
+
+    /// Puts several (key, value, ttl) entries in the scratch space in a single write, see
+    /// `put_scratch_value`.
+    fn put_scratch_values(&self, values: &[(Vec<u8>, Vec<u8>, Duration)]) -> Result<()> {
+        unimplemented!()
+    }
+
+    /// Deletes a value from the scratch space.
+    fn delete_scratch_value(&self, key: &[u8]) -> Result<()> {
+        unimplemented!()
+    }
 }
 
 #[derive(Clone)]
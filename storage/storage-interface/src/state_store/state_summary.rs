@@ -2,7 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    metrics::TIMER,
+    metrics::{COUNTER, TIMER},
     state_store::{
         state::LedgerState,
         state_update_refs::{BatchedStateUpdateRefs, StateUpdateRefs},
@@ -11,12 +11,14 @@ use crate::{
 };
 use anyhow::Result;
 use aptos_crypto::{hash::CORRUPTION_SENTINEL, HashValue};
-use aptos_metrics_core::TimerHelper;
+use aptos_logger::{error, warn};
+use aptos_metrics_core::{IntCounterVecHelper, TimerHelper};
 use aptos_scratchpad::{ProofRead, SparseMerkleTree};
 use aptos_types::{proof::SparseMerkleProofExt, transaction::Version};
 use derive_more::Deref;
 use itertools::Itertools;
 use rayon::prelude::*;
+use std::time::Duration;
 
 /// The data structure through which the entire state at a given
 /// version can be summarized to a concise digest (the root hash).
@@ -162,11 +164,35 @@ impl LedgerStateSummary {
     ) -> Result<Self> {
         let _timer = TIMER.timer_with(&["ledger_state_summary__update"]);
 
-        let last_checkpoint = if let Some(updates) = &updates.for_last_checkpoint {
-            self.latest.update(persisted, updates)?
+        let last_checkpoint = self.update_last_checkpoint(persisted, updates)?;
+        self.update_latest(last_checkpoint, persisted, updates)
+    }
+
+    /// First half of [`Self::update`], hashes the state up to the last checkpoint in `updates`,
+    /// which is all the ledger needs to commit to.
+    pub fn update_last_checkpoint(
+        &self,
+        persisted: &ProvableStateSummary,
+        updates: &StateUpdateRefs,
+    ) -> Result<StateSummary> {
+        let _timer = TIMER.timer_with(&["ledger_state_summary__update_last_checkpoint"]);
+
+        if let Some(updates) = &updates.for_last_checkpoint {
+            self.latest.update(persisted, updates)
         } else {
-            self.last_checkpoint.clone()
-        };
+            Ok(self.last_checkpoint.clone())
+        }
+    }
+
+    /// Second half of [`Self::update`], hashes the state after the last checkpoint on top of
+    /// `last_checkpoint` as returned by [`Self::update_last_checkpoint`].
+    pub fn update_latest(
+        &self,
+        last_checkpoint: StateSummary,
+        persisted: &ProvableStateSummary,
+        updates: &StateUpdateRefs,
+    ) -> Result<Self> {
+        let _timer = TIMER.timer_with(&["ledger_state_summary__update_latest"]);
 
         let base_of_latest = if updates.for_last_checkpoint.is_none() {
             self.latest()
@@ -183,6 +209,11 @@ impl LedgerStateSummary {
     }
 }
 
+/// The number of times fetching a proof from the DB is attempted before giving up on the key.
+const MAX_PROOF_FETCH_ATTEMPTS: u32 = 3;
+/// The wait before the first retry, doubled on every subsequent one.
+const PROOF_FETCH_RETRY_BACKOFF: Duration = Duration::from_millis(10);
+
 #[derive(Deref)]
 pub struct ProvableStateSummary<'db> {
     #[deref]
@@ -199,6 +230,37 @@ impl<'db> ProvableStateSummary<'db> {
         Self { state_summary, db }
     }
 
+    /// Fetches the proof from the DB, attempting up to `MAX_PROOF_FETCH_ATTEMPTS` times with an
+    /// exponential backoff in between.
+    fn fetch_proof(
+        &self,
+        key: &HashValue,
+        version: Version,
+        root_depth: usize,
+    ) -> Result<SparseMerkleProofExt> {
+        let _timer = TIMER.timer_with(&["provable_state_summary__fetch_proof"]);
+
+        let mut attempt = 1;
+        loop {
+            match self.get_proof(key, version, root_depth) {
+                Ok(proof) => return Ok(proof),
+                Err(err) if attempt < MAX_PROOF_FETCH_ATTEMPTS => {
+                    COUNTER.inc_with(&["provable_state_summary__fetch_proof_retry"]);
+                    warn!(
+                        key = *key,
+                        version = version,
+                        attempt = attempt,
+                        error = ?err,
+                        "Failed to fetch proof, retrying."
+                    );
+                    std::thread::sleep(PROOF_FETCH_RETRY_BACKOFF * 2u32.pow(attempt - 1));
+                    attempt += 1;
+                },
+                Err(err) => return Err(err),
+            }
+        }
+    }
+
     fn get_proof(
         &self,
         key: &HashValue,
@@ -226,13 +288,106 @@ impl<'db> ProvableStateSummary<'db> {
 }
 
 impl ProofRead for ProvableStateSummary<'_> {
-    // TODO(aldenhu): return error
+    /// Returns `None` if fetching the proof from the DB keeps failing, which fails the update
+    /// with `UpdateError::MissingProof` instead of bringing down the node.
     fn get_proof(&self, key: &HashValue, root_depth: usize) -> Option<SparseMerkleProofExt> {
-        self.version().map(|ver| {
-            let _timer = TIMER.timer_with(&["provable_state_summary__get_proof"]);
+        let ver = self.version()?;
+        let _timer = TIMER.timer_with(&["provable_state_summary__get_proof"]);
+
+        match self.fetch_proof(key, ver, root_depth) {
+            Ok(proof) => Some(proof),
+            Err(err) => {
+                COUNTER.inc_with(&["provable_state_summary__fetch_proof_failure"]);
+                error!(
+                    key = *key,
+                    version = ver,
+                    root_depth = root_depth,
+                    error = ?err,
+                    "Failed to get account state with proof by version."
+                );
+                None
+            },
+        }
+    }
+}
 
-            self.get_proof(key, ver, root_depth)
-                .expect("Failed to get account state with proof by version.")
-        })
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aptos_types::state_store::state_value::StateValue;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// Fails the first `num_failures` proof fetches.
+    struct FlakyDb {
+        num_failures: usize,
+        num_fetches: AtomicUsize,
+    }
+
+    impl FlakyDb {
+        fn new(num_failures: usize) -> Self {
+            Self {
+                num_failures,
+                num_fetches: AtomicUsize::new(0),
+            }
+        }
+
+        fn fetch(&self) -> Result<SparseMerkleProofExt> {
+            if self.num_fetches.fetch_add(1, Ordering::SeqCst) < self.num_failures {
+                anyhow::bail!("injected failure");
+            }
+            Ok(SparseMerkleProofExt::new(None, vec![]))
+        }
+    }
+
+    impl DbReader for FlakyDb {
+        fn get_state_proof_by_version_ext(
+            &self,
+            _key_hash: &HashValue,
+            _version: Version,
+            _root_depth: usize,
+        ) -> Result<SparseMerkleProofExt> {
+            self.fetch()
+        }
+
+        fn get_state_value_with_proof_by_version_ext(
+            &self,
+            _key_hash: &HashValue,
+            _version: Version,
+            _root_depth: usize,
+        ) -> Result<(Option<StateValue>, SparseMerkleProofExt)> {
+            self.fetch().map(|proof| (None, proof))
+        }
+    }
+
+    fn summary_at_version_0() -> StateSummary {
+        StateSummary::new_at_version(
+            Some(0),
+            SparseMerkleTree::new_empty(),
+            SparseMerkleTree::new_empty(),
+        )
+    }
+
+    #[test]
+    fn test_fetch_on_miss_retries() {
+        let db = FlakyDb::new(MAX_PROOF_FETCH_ATTEMPTS as usize - 1);
+        let summary = ProvableStateSummary::new(summary_at_version_0(), &db);
+
+        assert!(ProofRead::get_proof(&summary, &HashValue::random(), 0).is_some());
+        assert_eq!(
+            db.num_fetches.load(Ordering::SeqCst),
+            MAX_PROOF_FETCH_ATTEMPTS as usize
+        );
+    }
+
+    #[test]
+    fn test_fetch_on_miss_gives_up() {
+        let db = FlakyDb::new(MAX_PROOF_FETCH_ATTEMPTS as usize);
+        let summary = ProvableStateSummary::new(summary_at_version_0(), &db);
+
+        assert!(ProofRead::get_proof(&summary, &HashValue::random(), 0).is_none());
+        assert_eq!(
+            db.num_fetches.load(Ordering::SeqCst),
+            MAX_PROOF_FETCH_ATTEMPTS as usize
+        );
     }
 }
@@ -2,10 +2,11 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{metrics::TIMER, state_store::versioned_state_value::StateUpdateRef};
+use aptos_crypto::HashValue;
 use aptos_logger::{sample, sample::SampleRate, warn};
 use aptos_metrics_core::TimerHelper;
 use aptos_types::{
-    state_store::{state_key::StateKey, NUM_STATE_SHARDS},
+    state_store::{state_key::StateKey, state_value::StateValue, NUM_STATE_SHARDS},
     transaction::Version,
     write_set::{BaseStateOp, WriteSet},
 };
@@ -13,10 +14,56 @@ use arr_macro::arr;
 use itertools::Itertools;
 use rayon::iter::{IndexedParallelIterator, IntoParallelRefMutIterator, ParallelIterator};
 use std::{
-    collections::{hash_map::Entry, HashMap},
+    collections::{
+        hash_map::{DefaultHasher, Entry},
+        HashMap,
+    },
+    hash::{Hash, Hasher},
     time::Duration,
 };
 
+/// Checksum of the state values written to a shard of the state KV DB, so that the DB can check
+/// what it persisted against what execution produced. Only computed when verification is enabled,
+/// see `PerVersionStateUpdateRefs::checksum`.
+///
+/// The hashes of the individual writes are summed up, so the checksum doesn't depend on the order
+/// the writes are visited in.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct StateKvShardChecksum {
+    num_writes: usize,
+    sum: u64,
+}
+
+impl StateKvShardChecksum {
+    pub fn add(&mut self, key_hash: &HashValue, version: Version, value: Option<&StateValue>) {
+        let mut hasher = DefaultHasher::new();
+        key_hash.hash(&mut hasher);
+        version.hash(&mut hasher);
+        value.hash(&mut hasher);
+
+        self.num_writes += 1;
+        self.sum = self.sum.wrapping_add(hasher.finish());
+    }
+
+    pub fn num_writes(&self) -> usize {
+        self.num_writes
+    }
+
+    fn of_updates(updates: &[(&StateKey, StateUpdateRef)]) -> Self {
+        let mut checksum = Self::default();
+        for (key, update) in updates {
+            if let Some(write_op) = update.state_op.as_write_op_opt() {
+                checksum.add(
+                    key.crypto_hash_ref(),
+                    update.version,
+                    write_op.as_state_value_opt(),
+                );
+            }
+        }
+        checksum
+    }
+}
+
 #[derive(Debug)]
 pub struct PerVersionStateUpdateRefs<'kv> {
     pub first_version: Version,
@@ -53,13 +100,20 @@ impl<'kv> PerVersionStateUpdateRefs<'kv> {
             }
         }
         assert_eq!(versions_seen, num_versions);
+        let shards = shards.map(|shard| shard.into_boxed_slice());
 
         Self {
             first_version,
-            shards: shards.map(|shard| shard.into_boxed_slice()),
+            shards,
             num_versions,
         }
     }
+
+    /// Checksum of the state values written to the shard, see `StateKvShardChecksum`.
+    pub fn checksum(&self, shard_id: usize) -> StateKvShardChecksum {
+        let _timer = TIMER.timer_with(&["state_updates__checksum"]);
+        StateKvShardChecksum::of_updates(&self.shards[shard_id])
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -18,9 +18,13 @@ use anyhow::Result;
 use aptos_metrics_core::{IntCounterVecHelper, TimerHelper};
 use aptos_types::{
     state_store::{
-        hot_state::THotStateSlot, state_key::StateKey, state_slot::StateSlot,
-        state_storage_usage::StateStorageUsage, state_value::StateValue, StateViewId,
-        StateViewResult, TStateView, NUM_STATE_SHARDS,
+        hot_state::THotStateSlot,
+        in_memory_state_view::{too_many_items, AsInMemoryStateView},
+        state_key::{prefix::StateKeyPrefix, StateKey},
+        state_slot::StateSlot,
+        state_storage_usage::StateStorageUsage,
+        state_value::StateValue,
+        StateViewId, StateViewResult, TStateView, NUM_STATE_SHARDS,
     },
     transaction::Version,
 };
@@ -30,7 +34,7 @@ use once_cell::sync::Lazy;
 use parking_lot::RwLock;
 use rayon::prelude::*;
 use std::{
-    collections::HashMap,
+    collections::{BTreeMap, HashMap},
     fmt::{Debug, Formatter},
     sync::Arc,
 };
@@ -182,6 +186,24 @@ impl CachedStateView {
         })
     }
 
+    /// Warms the cache with the given keys on the IO pool while `f` runs on the current thread,
+    /// and returns once both are done. Failed reads are ignored, since they will be retried (and
+    /// reported) by whoever actually needs the value.
+    pub fn prefetch_while<R>(&self, keys: &[StateKey], f: impl FnOnce() -> R) -> R {
+        IO_POOL.in_place_scope(|s| {
+            s.spawn(|_| self.prefetch(keys));
+            f()
+        })
+    }
+
+    fn prefetch(&self, keys: &[StateKey]) {
+        let _timer = TIMER.timer_with(&["prefetch_state_cache"]);
+
+        keys.par_iter().for_each(|key| {
+            let _ = self.get_state_value(key);
+        });
+    }
+
     fn prime_cache_for_batched_updates(&self, updates: &BatchedStateUpdateRefs) -> Result<()> {
         updates
             .shards
@@ -328,6 +350,50 @@ impl TStateView for CachedStateView {
     }
 }
 
+impl AsInMemoryStateView for CachedStateView {
+    /// Scans the persisted state by prefix and applies the speculative updates on top, without
+    /// memorizing anything.
+    fn get_prefixed_state_values(
+        &self,
+        prefix: &StateKeyPrefix,
+        max_items: usize,
+    ) -> StateViewResult<Vec<(StateKey, StateValue)>> {
+        let mut updates = Vec::new();
+        for shard in self.speculative.shards.iter() {
+            for (key, slot) in shard.iter() {
+                if prefix.is_prefix(&key)? {
+                    updates.push((key, slot.into_state_value_opt()));
+                }
+            }
+        }
+
+        let mut values = BTreeMap::new();
+        if let Some(base_version) = self.base_version() {
+            // The updates might delete some of the persisted values, so read past the limit by as
+            // many as there are updates.
+            for res in self
+                .cold
+                .get_prefixed_state_value_iterator(prefix, None, base_version)?
+                .take(max_items + updates.len() + 1)
+            {
+                let (key, value) = res?;
+                values.insert(key, value);
+            }
+        }
+        for (key, value) in updates {
+            match value {
+                Some(value) => values.insert(key, value),
+                None => values.remove(&key),
+            };
+        }
+
+        if values.len() > max_items {
+            return Err(too_many_items(max_items));
+        }
+        Ok(values.into_iter().collect())
+    }
+}
+
 pub struct CachedDbStateView {
     db_state_view: DbStateView,
     state_cache: RwLock<HashMap<StateKey, Option<StateValue>>>,
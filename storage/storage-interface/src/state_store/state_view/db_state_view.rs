@@ -6,8 +6,12 @@ use aptos_crypto::HashValue;
 use aptos_types::{
     ledger_info::LedgerInfo,
     state_store::{
-        errors::StateViewError, state_key::StateKey, state_storage_usage::StateStorageUsage,
-        state_value::StateValue, StateViewResult, TStateView,
+        errors::StateViewError,
+        in_memory_state_view::{too_many_items, AsInMemoryStateView},
+        state_key::{prefix::StateKeyPrefix, StateKey},
+        state_storage_usage::StateStorageUsage,
+        state_value::StateValue,
+        StateViewResult, TStateView,
     },
     transaction::Version,
 };
@@ -33,7 +37,9 @@ impl DbStateView {
                 if let Ok((value, proof)) =
                     self.db.get_state_value_with_proof_by_version(key, version)
                 {
-                    proof.verify(root_hash, *key.crypto_hash_ref(), value.as_ref())?;
+                    proof
+                        .verify(root_hash, *key.crypto_hash_ref(), value.as_ref())
+                        .map_err(|err| StateViewError::Corrupted(format!("{}", err)))?;
                     return Ok(value);
                 }
             }
@@ -62,6 +68,29 @@ impl TStateView for DbStateView {
     }
 }
 
+impl AsInMemoryStateView for DbStateView {
+    fn get_prefixed_state_values(
+        &self,
+        prefix: &StateKeyPrefix,
+        max_items: usize,
+    ) -> StateViewResult<Vec<(StateKey, StateValue)>> {
+        let Some(version) = self.version else {
+            return Ok(vec![]);
+        };
+        // Values are not verified against the state root hash, since there are no range proofs
+        // by prefix.
+        let values = self
+            .db
+            .get_prefixed_state_value_iterator(prefix, None, version)?
+            .take(max_items + 1)
+            .collect::<crate::Result<Vec<_>>>()?;
+        if values.len() > max_items {
+            return Err(too_many_items(max_items));
+        }
+        Ok(values)
+    }
+}
+
 pub trait LatestDbStateCheckpointView {
     fn latest_state_checkpoint_view(&self) -> StateViewResult<DbStateView>;
 }
@@ -105,6 +105,6 @@ pub(crate) fn net_bench_two_region_inner(netbench_config: NetbenchConfig) -> For
         .add_network_test(wrap_with_two_region_env(Delay::new(180)))
         .with_initial_validator_count(NonZeroUsize::new(2).unwrap())
         .with_validator_override_node_config_fn(Arc::new(move |config, _| {
-            config.netbench = Some(netbench_config);
+            config.netbench = Some(netbench_config.clone());
         }))
 }
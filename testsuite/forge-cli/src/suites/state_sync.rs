@@ -10,12 +10,13 @@ use aptos_forge::{
 };
 use aptos_testcases::{
     consensus_reliability_tests::ChangingWorkingQuorumTest,
+    state_sync_catch_up_performance::StateSyncCatchUpPerformance,
     state_sync_performance::{
         StateSyncFullnodeFastSyncPerformance, StateSyncFullnodePerformance,
         StateSyncValidatorPerformance,
     },
 };
-use std::{num::NonZeroUsize, sync::Arc};
+use std::{num::NonZeroUsize, sync::Arc, time::Duration};
 
 /// Attempts to match the test name to a state sync test
 pub fn get_state_sync_test(test_name: &str) -> Option<ForgeConfig> {
@@ -26,6 +27,8 @@ pub fn get_state_sync_test(test_name: &str) -> Option<ForgeConfig> {
         },
         "state_sync_perf_fullnodes_fast_sync" => state_sync_perf_fullnodes_fast_sync(),
         "state_sync_perf_validators" => state_sync_perf_validators(),
+        "state_sync_catch_up_fast_sync" => state_sync_catch_up_fast_sync(),
+        "state_sync_catch_up_intelligent_sync" => state_sync_catch_up_intelligent_sync(),
         "state_sync_failures_catching_up" => state_sync_failures_catching_up(),
         "state_sync_slow_processing_catching_up" => state_sync_slow_processing_catching_up(),
 
@@ -48,6 +51,13 @@ pub fn state_sync_config_apply_transaction_outputs(state_sync_config: &mut State
         ContinuousSyncingMode::ApplyTransactionOutputs;
 }
 
+pub fn state_sync_config_intelligent_sync(state_sync_config: &mut StateSyncConfig) {
+    state_sync_config.state_sync_driver.bootstrapping_mode =
+        BootstrappingMode::ExecuteOrApplyFromGenesis;
+    state_sync_config.state_sync_driver.continuous_syncing_mode =
+        ContinuousSyncingMode::ExecuteTransactionsOrApplyOutputs;
+}
+
 pub fn state_sync_config_fast_sync(state_sync_config: &mut StateSyncConfig) {
     state_sync_config.state_sync_driver.bootstrapping_mode =
         BootstrappingMode::DownloadLatestStates;
@@ -125,6 +135,47 @@ fn state_sync_perf_validators() -> ForgeConfig {
         .with_success_criteria(SuccessCriteria::new(5000))
 }
 
+/// The config for running a state sync catch up test where a wiped fullnode
+/// fast syncs to the latest epoch (while the network is loaded).
+fn state_sync_catch_up_fast_sync() -> ForgeConfig {
+    state_sync_perf_fullnodes_config()
+        .add_network_test(StateSyncCatchUpPerformance {
+            wipe_storage: true,
+            lag_fraction: 0.25,
+            min_p50_sync_throughput: 3000,
+            max_catch_up_duration: Duration::from_secs(600),
+            max_p99_lag_secs: 10.0,
+        })
+        .with_genesis_helm_config_fn(Arc::new(|helm_values| {
+            helm_values["chain"]["epoch_duration_secs"] = 120.into(); // Frequent epochs
+        }))
+        .with_fullnode_override_node_config_fn(Arc::new(|config, _| {
+            state_sync_config_fast_sync(&mut config.state_sync);
+        }))
+        .with_success_criteria(SuccessCriteria::new(3000))
+}
+
+/// The config for running a state sync catch up test where a lagging fullnode
+/// executes transactions or applies outputs (whichever is faster) to catch up
+/// (while the network is loaded).
+fn state_sync_catch_up_intelligent_sync() -> ForgeConfig {
+    state_sync_perf_fullnodes_config()
+        .add_network_test(StateSyncCatchUpPerformance {
+            wipe_storage: false,
+            lag_fraction: 0.25,
+            min_p50_sync_throughput: 5000,
+            max_catch_up_duration: Duration::from_secs(600),
+            max_p99_lag_secs: 10.0,
+        })
+        .with_genesis_helm_config_fn(Arc::new(|helm_values| {
+            helm_values["chain"]["epoch_duration_secs"] = 600.into();
+        }))
+        .with_fullnode_override_node_config_fn(Arc::new(|config, _| {
+            state_sync_config_intelligent_sync(&mut config.state_sync);
+        }))
+        .with_success_criteria(SuccessCriteria::new(3000))
+}
+
 fn state_sync_failures_catching_up() -> ForgeConfig {
     changing_working_quorum_test_helper(
         7,
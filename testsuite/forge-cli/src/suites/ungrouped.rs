@@ -43,6 +43,7 @@ use aptos_testcases::{
     modifiers::CpuChaosTest,
     multi_region_network_test::MultiRegionNetworkEmulationTest,
     network_bandwidth_test::NetworkBandwidthTest,
+    network_chaos_schedule_test::NetworkChaosScheduleTest,
     network_loss_test::NetworkLossTest,
     network_partition_test::NetworkPartitionTest,
     performance_test::PerformanceBenchmark,
@@ -107,6 +108,7 @@ pub fn get_ungrouped_test(test_name: &str, duration: Duration) -> Option<ForgeCo
         // Network tests
         "network_bandwidth" => Some(network_bandwidth()),
         "network_partition" => Some(network_partition()),
+        "network_chaos_schedule" => Some(network_chaos_schedule()),
         "twin_validator_test" => Some(twin_validator_test()),
         "validator_reboot_stress_test" => Some(validator_reboot_stress_test()),
         "validators_join_and_leave" => Some(validators_join_and_leave()),
@@ -776,6 +778,17 @@ fn network_partition() -> ForgeConfig {
         }))
 }
 
+fn network_chaos_schedule() -> ForgeConfig {
+    ForgeConfig::default()
+        .with_initial_validator_count(NonZeroUsize::new(7).unwrap())
+        .add_network_test(NetworkChaosScheduleTest)
+        .with_success_criteria(
+            SuccessCriteria::new(2000)
+                .add_no_restarts()
+                .add_wait_for_catchup_s(240),
+        )
+}
+
 fn epoch_changer_performance() -> ForgeConfig {
     ForgeConfig::default()
         .with_initial_validator_count(NonZeroUsize::new(5).unwrap())
@@ -2,8 +2,9 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    dump_string_to_file, K8sSwarm, Result, Swarm, SwarmChaos, SwarmCpuStress, SwarmNetEm,
-    SwarmNetworkBandwidth, SwarmNetworkDelay, SwarmNetworkLoss, SwarmNetworkPartition, KUBECTL_BIN,
+    dump_string_to_file, K8sSwarm, Result, Swarm, SwarmChaos, SwarmCpuStress,
+    SwarmDirectedPartition, SwarmNetEm, SwarmNetworkBandwidth, SwarmNetworkDelay, SwarmNetworkLoss,
+    SwarmNetworkPartition, KUBECTL_BIN,
 };
 use anyhow::bail;
 use aptos_sdk::{move_types::account_address::AccountAddress, types::PeerId};
@@ -21,6 +22,11 @@ macro_rules! PARTITION_NETWORK_CHAOS_TEMPLATE {
         "chaos/network_partition.yaml"
     };
 }
+macro_rules! DIRECTED_PARTITION_NETWORK_CHAOS_TEMPLATE {
+    () => {
+        "chaos/network_directed_partition.yaml"
+    };
+}
 macro_rules! BANDWIDTH_NETWORK_CHAOS_TEMPLATE {
     () => {
         "chaos/network_bandwidth.yaml"
@@ -130,6 +136,31 @@ impl K8sSwarm {
         ))
     }
 
+    fn create_directed_partition_template(
+        &self,
+        swarm_directed_partition: &SwarmDirectedPartition,
+    ) -> Result<String> {
+        let mut network_chaos_specs = vec![];
+
+        for group_partition in &swarm_directed_partition.group_partitions {
+            let source_instance_labels = self.get_instance_labels(&group_partition.source_nodes);
+            let target_instance_labels = self.get_instance_labels(&group_partition.target_nodes);
+            let service_targets = self.get_service_targets(&group_partition.target_nodes);
+
+            network_chaos_specs.push(format!(
+                include_str!(DIRECTED_PARTITION_NETWORK_CHAOS_TEMPLATE!()),
+                name = &group_partition.name,
+                namespace = self.kube_namespace,
+                direction = group_partition.direction,
+                instance_labels = &source_instance_labels,
+                target_instance_labels = &target_instance_labels,
+                service_targets = &service_targets,
+            ));
+        }
+
+        Ok(network_chaos_specs.join("\n---\n"))
+    }
+
     fn create_network_bandwidth_template(
         &self,
         swarm_network_bandwidth: &SwarmNetworkBandwidth,
@@ -215,6 +246,7 @@ impl K8sSwarm {
         match chaos {
             SwarmChaos::Delay(c) => self.create_network_delay_template(c),
             SwarmChaos::Partition(c) => self.create_network_partition_template(c),
+            SwarmChaos::DirectedPartition(c) => self.create_directed_partition_template(c),
             SwarmChaos::Bandwidth(c) => self.create_network_bandwidth_template(c),
             SwarmChaos::Loss(c) => self.create_network_loss_template(c),
             SwarmChaos::NetEm(c) => self.create_netem_template(c),
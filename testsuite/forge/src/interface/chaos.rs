@@ -8,6 +8,7 @@ use std::fmt::{Display, Formatter};
 pub enum SwarmChaos {
     Delay(SwarmNetworkDelay),
     Partition(SwarmNetworkPartition),
+    DirectedPartition(SwarmDirectedPartition),
     Bandwidth(SwarmNetworkBandwidth),
     Loss(SwarmNetworkLoss),
     NetEm(SwarmNetEm),
@@ -46,6 +47,49 @@ impl Display for SwarmNetworkPartition {
     }
 }
 
+/// Partitions specific groups of nodes from each other, optionally only in one
+/// direction (e.g., A can't reach B, but B can still reach A).
+#[derive(Eq, Hash, PartialEq, Debug, Clone)]
+pub struct SwarmDirectedPartition {
+    pub group_partitions: Vec<GroupNetworkPartition>,
+}
+
+impl Display for SwarmDirectedPartition {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(f, "Directed partition nodes {:?}", self.group_partitions)
+    }
+}
+
+#[derive(Eq, Hash, PartialEq, Debug, Clone)]
+pub struct GroupNetworkPartition {
+    pub name: String,
+    pub source_nodes: Vec<PeerId>,
+    pub target_nodes: Vec<PeerId>,
+    pub direction: PartitionDirection,
+}
+
+/// The direction of the traffic that is dropped, from the perspective of the source nodes
+#[derive(Eq, Hash, PartialEq, Debug, Clone, Copy)]
+pub enum PartitionDirection {
+    /// Traffic from the source nodes to the target nodes is dropped
+    To,
+    /// Traffic from the target nodes to the source nodes is dropped
+    From,
+    /// All traffic between the source and target nodes is dropped
+    Both,
+}
+
+impl Display for PartitionDirection {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        let direction = match self {
+            PartitionDirection::To => "to",
+            PartitionDirection::From => "from",
+            PartitionDirection::Both => "both",
+        };
+        write!(f, "{}", direction)
+    }
+}
+
 #[derive(Eq, Hash, PartialEq, Debug, Clone)]
 pub struct SwarmNetworkBandwidth {
     pub group_network_bandwidths: Vec<GroupNetworkBandwidth>,
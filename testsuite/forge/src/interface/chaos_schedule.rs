@@ -0,0 +1,157 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{Result, Swarm, SwarmChaos};
+use log::{info, warn};
+use std::{sync::Arc, time::Duration};
+use tokio::sync::RwLock;
+
+/// A single chaos injection that is active from `start` until `start + duration`
+/// (relative to the start of the schedule).
+#[derive(Eq, Hash, PartialEq, Debug, Clone)]
+pub struct ChaosEvent {
+    pub start: Duration,
+    pub duration: Duration,
+    pub chaos: SwarmChaos,
+}
+
+impl ChaosEvent {
+    pub fn end(&self) -> Duration {
+        self.start + self.duration
+    }
+}
+
+#[derive(Eq, PartialEq, Debug, Clone)]
+pub(crate) enum ChaosAction {
+    Inject(usize),
+    Heal(usize),
+}
+
+/// A deterministic schedule of chaos (e.g., partitions, latency, loss) to inject into
+/// a swarm during a test, with each injection automatically healed once its duration
+/// has elapsed. For example:
+///
+/// ```ignore
+/// ChaosSchedule::new()
+///     .inject(Duration::from_secs(30), Duration::from_secs(60), partition)
+///     .inject(Duration::from_secs(120), Duration::from_secs(60), latency)
+///     .run(swarm)
+///     .await?;
+/// ```
+#[derive(Default, Debug, Clone)]
+pub struct ChaosSchedule {
+    events: Vec<ChaosEvent>,
+}
+
+impl ChaosSchedule {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Injects `chaos` at `start` and heals it after `duration`
+    pub fn inject(mut self, start: Duration, duration: Duration, chaos: SwarmChaos) -> Self {
+        self.events.push(ChaosEvent {
+            start,
+            duration,
+            chaos,
+        });
+        self
+    }
+
+    pub fn events(&self) -> &[ChaosEvent] {
+        &self.events
+    }
+
+    /// The time it takes to run the whole schedule, i.e., until the last chaos is healed
+    pub fn total_duration(&self) -> Duration {
+        self.events
+            .iter()
+            .map(ChaosEvent::end)
+            .max()
+            .unwrap_or_default()
+    }
+
+    /// Returns all actions ordered by the time at which they happen. Actions that happen
+    /// at the same time are ordered by healing first, so that back-to-back events never
+    /// overlap, and then by the order in which the events were added.
+    pub(crate) fn actions(&self) -> Vec<(Duration, ChaosAction)> {
+        let mut actions = vec![];
+        for (index, event) in self.events.iter().enumerate() {
+            actions.push((event.start, ChaosAction::Inject(index)));
+            actions.push((event.end(), ChaosAction::Heal(index)));
+        }
+        actions.sort_by_key(|(time, action)| match action {
+            ChaosAction::Heal(index) => (*time, 0, *index),
+            ChaosAction::Inject(index) => (*time, 1, *index),
+        });
+        actions
+    }
+
+    /// Runs the schedule against the swarm, returning once all chaos has been healed.
+    /// If any injection fails, all chaos that is still active is healed before the
+    /// error is returned.
+    pub async fn run(&self, swarm: Arc<RwLock<Box<dyn Swarm>>>) -> Result<()> {
+        let schedule_start = tokio::time::Instant::now();
+        let mut active = vec![];
+
+        let result: Result<()> = async {
+            for (time, action) in self.actions() {
+                tokio::time::sleep_until(schedule_start + time).await;
+                match action {
+                    ChaosAction::Inject(index) => {
+                        let chaos = self.events[index].chaos.clone();
+                        info!("Injecting chaos at {:?}: {:?}", time, chaos);
+                        swarm.write().await.inject_chaos(chaos).await?;
+                        active.push(index);
+                    },
+                    ChaosAction::Heal(index) => {
+                        let chaos = self.events[index].chaos.clone();
+                        info!("Healing chaos at {:?}: {:?}", time, chaos);
+                        active.retain(|active_index| *active_index != index);
+                        swarm.write().await.remove_chaos(chaos).await?;
+                    },
+                }
+            }
+            Ok(())
+        }
+        .await;
+
+        if result.is_err() {
+            for index in active {
+                let chaos = self.events[index].chaos.clone();
+                if let Err(e) = swarm.write().await.remove_chaos(chaos.clone()).await {
+                    warn!("Failed to heal chaos {:?}: {:?}", chaos, e);
+                }
+            }
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{SwarmCpuStress, SwarmNetworkLoss};
+
+    #[test]
+    fn test_actions_order() {
+        let loss = SwarmChaos::Loss(SwarmNetworkLoss {
+            loss_percentage: 10,
+            correlation_percentage: 0,
+        });
+        let stress = SwarmChaos::CpuStress(SwarmCpuStress {
+            group_cpu_stresses: vec![],
+        });
+        let schedule = ChaosSchedule::new()
+            .inject(Duration::from_secs(10), Duration::from_secs(10), loss)
+            .inject(Duration::from_secs(20), Duration::from_secs(5), stress);
+
+        assert_eq!(schedule.total_duration(), Duration::from_secs(25));
+        assert_eq!(schedule.actions(), vec![
+            (Duration::from_secs(10), ChaosAction::Inject(0)),
+            (Duration::from_secs(20), ChaosAction::Heal(0)),
+            (Duration::from_secs(20), ChaosAction::Inject(1)),
+            (Duration::from_secs(25), ChaosAction::Heal(1)),
+        ]);
+    }
+}
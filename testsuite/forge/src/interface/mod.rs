@@ -16,6 +16,8 @@ mod swarm;
 pub use swarm::*;
 mod chaos;
 pub use chaos::*;
+mod chaos_schedule;
+pub use chaos_schedule::*;
 mod node;
 pub use node::*;
 mod chain_info;
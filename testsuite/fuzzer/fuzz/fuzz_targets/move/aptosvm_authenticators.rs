@@ -45,7 +45,7 @@ use utils::{
         FuzzerTransactionAuthenticator, Style, TransactionState,
     },
     helpers::base64url_encode_str,
-    vm::check_for_invariant_violation,
+    vm::{check_for_invariant_violation, is_storage_error},
 };
 
 // genesis write set generated once for each fuzzing session
@@ -459,7 +459,7 @@ fn run_case(input: TransactionState) -> Result<(), Corpus> {
     let status = match tdbg!(res.status()) {
         TransactionStatus::Keep(status) => status,
         TransactionStatus::Discard(e) => {
-            if e.status_type() == StatusType::InvariantViolation {
+            if e.status_type() == StatusType::InvariantViolation && !is_storage_error(*e) {
                 panic!("invariant violation {:?}", e);
             }
             return Err(Corpus::Keep);
@@ -472,7 +472,7 @@ fn run_case(input: TransactionState) -> Result<(), Corpus> {
             if let Some(e) = e {
                 if e.status_type() == StatusType::InvariantViolation
                     && *e != StatusCode::TYPE_RESOLUTION_FAILURE
-                    && *e != StatusCode::STORAGE_ERROR
+                    && !is_storage_error(*e)
                 {
                     panic!("invariant violation {:?}", e);
                 }
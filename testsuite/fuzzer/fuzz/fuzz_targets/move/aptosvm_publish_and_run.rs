@@ -32,7 +32,9 @@ use std::{
 };
 mod utils;
 use fuzzer::{Authenticator, ExecVariant, RunnableState};
-use utils::vm::{check_for_invariant_violation, publish_group, sort_by_deps, BYTECODE_VERSION};
+use utils::vm::{
+    check_for_invariant_violation, is_storage_error, publish_group, sort_by_deps, BYTECODE_VERSION,
+};
 
 // genesis write set generated once for each fuzzing session
 static VM_WRITE_SET: Lazy<WriteSet> = Lazy::new(|| GENESIS_CHANGE_SET_HEAD.write_set().clone());
@@ -369,8 +371,9 @@ fn run_case(mut input: RunnableState) -> Result<(), Corpus> {
     let status = match tdbg!(res.status()) {
         TransactionStatus::Keep(status) => status,
         TransactionStatus::Discard(e) => {
-            if e.status_type() == StatusType::InvariantViolation
-                || e.status_type() == StatusType::Unknown
+            if (e.status_type() == StatusType::InvariantViolation
+                || e.status_type() == StatusType::Unknown)
+                && !is_storage_error(*e)
             {
                 panic!("invariant violation {:?}", e);
             }
@@ -385,7 +388,7 @@ fn run_case(mut input: RunnableState) -> Result<(), Corpus> {
                 if (e.status_type() == StatusType::InvariantViolation
                     || e.status_type() == StatusType::Unknown)
                     && *e != StatusCode::TYPE_RESOLUTION_FAILURE
-                    && *e != StatusCode::STORAGE_ERROR
+                    && !is_storage_error(*e)
                 {
                     panic!("invariant violation {:?}, {:?}", e, res.auxiliary_data());
                 }
@@ -132,6 +132,19 @@ fn publish_transaction_payload(modules: &[CompiledModule]) -> TransactionPayload
 // If some invariant violation do not come with a message, we need to attach a message to it at throwing site.
 const KNOWN_FALSE_POSITIVES_VMSTATUS: &[&str] = &["moving container with dangling references"];
 
+// Storage failures are reported as invariant violations, but they are not caused by the fuzzed
+// input, so they are not flagged.
+pub(crate) fn is_storage_error(code: StatusCode) -> bool {
+    matches!(
+        code,
+        StatusCode::STORAGE_ERROR
+            | StatusCode::STORAGE_NOT_FOUND
+            | StatusCode::STORAGE_CORRUPTED
+            | StatusCode::STORAGE_UNAVAILABLE
+            | StatusCode::STORAGE_TIMEOUT
+    )
+}
+
 // panic to catch invariant violations
 pub(crate) fn check_for_invariant_violation(e: VMStatus) {
     let is_known_false_positive = e.message().map_or(false, |msg| {
@@ -0,0 +1,171 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::utils::{check_create_mint_transfer_node, MAX_CATCH_UP_WAIT_SECS};
+use aptos_forge::{
+    ActiveNodesGuard, Factory, LocalFactory, LocalSwarm, NodeExt, Swarm, SwarmExt, Version,
+};
+use aptos_infallible::Mutex;
+use aptos_logger::info;
+use aptos_rest_client::Client as RestClient;
+use aptos_types::PeerId;
+use rand::rngs::OsRng;
+use std::{num::NonZeroUsize, sync::Arc, time::Duration};
+
+/// Comma separated git revisions (e.g., release branches) to test the compatibility of the
+/// current workspace with. If unset, the merge base with upstream/main is used.
+const COMPAT_MATRIX_REVISIONS_ENV: &str = "APTOS_COMPAT_MATRIX_REVISIONS";
+
+const NUM_VALIDATORS: usize = 4;
+
+#[derive(Clone, Copy, Debug)]
+enum UpgradeDirection {
+    /// Start on the older revision and restart the nodes on the workspace
+    Upgrade,
+    /// Start on the workspace and restart the nodes on the older revision
+    Rollback,
+}
+
+/// The ledger state recorded before the nodes are restarted on the other version
+struct LedgerCheckpoint {
+    version: u64,
+    accumulator_root_hash: String,
+}
+
+impl LedgerCheckpoint {
+    async fn record(client: &RestClient) -> Self {
+        let version = client
+            .get_ledger_information()
+            .await
+            .unwrap()
+            .into_inner()
+            .version;
+        Self {
+            version,
+            accumulator_root_hash: get_accumulator_root_hash(client, version).await,
+        }
+    }
+
+    /// Verifies the node can still serve the ledger history written by the other version,
+    /// i.e., the DB was opened (and migrated, if needed) without losing or altering any data.
+    async fn verify(&self, client: &RestClient) {
+        let ledger_info = client.get_ledger_information().await.unwrap().into_inner();
+        assert!(
+            ledger_info.version >= self.version,
+            "The ledger went backwards after the restart: {} < {}",
+            ledger_info.version,
+            self.version
+        );
+        assert_eq!(
+            get_accumulator_root_hash(client, self.version).await,
+            self.accumulator_root_hash,
+            "The ledger history changed after the restart!"
+        );
+    }
+}
+
+async fn get_accumulator_root_hash(client: &RestClient, version: u64) -> String {
+    client
+        .get_transaction_by_version(version)
+        .await
+        .unwrap()
+        .into_inner()
+        .transaction_info()
+        .unwrap()
+        .accumulator_root_hash
+        .to_string()
+}
+
+/// Creates a local factory for every revision in the matrix, each also holding the workspace
+fn create_matrix_factories() -> Vec<LocalFactory> {
+    match std::env::var(COMPAT_MATRIX_REVISIONS_ENV) {
+        Ok(revisions) => revisions
+            .split(',')
+            .map(str::trim)
+            .filter(|revision| !revision.is_empty())
+            .map(|revision| LocalFactory::with_revision_and_workspace(revision).unwrap())
+            .collect(),
+        Err(_) => vec![LocalFactory::with_upstream_merge_base_and_workspace().unwrap()],
+    }
+}
+
+/// Brings up a network on one version, generates state, and then restarts the validators
+/// on the other version (first half of them, then the rest), verifying that the network
+/// keeps making progress with mixed versions and that no ledger history is lost.
+async fn run_compatibility_case(factory: &LocalFactory, direction: UpgradeDirection) {
+    let mut versions: Vec<Version> = factory.versions().collect();
+    versions.sort();
+    let (old_version, new_version) = (versions[0].clone(), versions[1].clone());
+    let (from, to) = match direction {
+        UpgradeDirection::Upgrade => (old_version, new_version),
+        UpgradeDirection::Rollback => (new_version, old_version),
+    };
+    info!(
+        "Running compatibility case {:?}: {} ==> {}",
+        direction, from, to
+    );
+
+    let guard = ActiveNodesGuard::grab(NUM_VALIDATORS, Arc::new(Mutex::new(0))).await;
+    let mut swarm = factory
+        .new_swarm_with_version(
+            OsRng,
+            NonZeroUsize::new(NUM_VALIDATORS).unwrap(),
+            0,
+            &from,
+            None,
+            None,
+            None,
+            None,
+            None,
+            guard,
+        )
+        .await
+        .unwrap();
+
+    // Generate some state on the starting version
+    check_create_mint_transfer_node(&mut swarm, 0).await;
+    let checkpoint =
+        LedgerCheckpoint::record(&swarm.validators().next().unwrap().rest_client()).await;
+
+    // Restart half of the validators, so both versions have to talk to each other
+    let validators: Vec<_> = swarm.validators().map(|v| v.peer_id()).collect();
+    let (first_batch, second_batch) = validators.split_at(NUM_VALIDATORS / 2);
+    restart_validators(&mut swarm, first_batch, &to).await;
+    check_create_mint_transfer_node(&mut swarm, 0).await;
+    check_create_mint_transfer_node(&mut swarm, NUM_VALIDATORS - 1).await;
+
+    // Restart the rest, and verify the network on the target version
+    restart_validators(&mut swarm, second_batch, &to).await;
+    check_create_mint_transfer_node(&mut swarm, NUM_VALIDATORS - 1).await;
+    swarm
+        .wait_for_all_nodes_to_catchup(Duration::from_secs(MAX_CATCH_UP_WAIT_SECS))
+        .await
+        .unwrap();
+    for validator in swarm.validators() {
+        checkpoint.verify(&validator.rest_client()).await;
+    }
+}
+
+async fn restart_validators(swarm: &mut LocalSwarm, validators: &[PeerId], version: &Version) {
+    for validator in validators {
+        swarm.upgrade_validator(*validator, version).await.unwrap();
+    }
+    swarm
+        .wait_all_alive(Duration::from_secs(MAX_CATCH_UP_WAIT_SECS))
+        .await
+        .unwrap();
+}
+
+// Ignored, as it has to build aptos-node at every revision in the matrix. Run it with, e.g.,
+// APTOS_COMPAT_MATRIX_REVISIONS=aptos-release-v1.20,aptos-release-v1.21 cargo test
+// -p smoke-test test_compatibility_matrix -- --ignored
+#[ignore]
+#[tokio::test]
+async fn test_compatibility_matrix() {
+    ::aptos_logger::Logger::new().init();
+    for factory in create_matrix_factories() {
+        for direction in [UpgradeDirection::Upgrade, UpgradeDirection::Rollback] {
+            run_compatibility_case(&factory, direction).await;
+        }
+    }
+}
@@ -73,6 +73,41 @@ script {{
         aptos_governance::reconfigure(&framework);
     }}
 }}
+"#
+            )
+        },
+        OnChainJWKConsensusConfig::V2(config_v2) => {
+            let provider_lines = config_v2
+                .oidc_providers
+                .iter()
+                .map(|provider| {
+                    format!(
+                        "jwk_consensus_config::new_oidc_provider_v2(utf8(b\"{}\"), utf8(b\"{}\"), {}, {}, utf8(b\"{}\")),",
+                        provider.name,
+                        provider.config_url,
+                        provider.config_url_kind,
+                        provider.fetch_interval_secs,
+                        provider.default_alg
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join("\n            ");
+            format!(
+                r#"
+script {{
+    use aptos_framework::aptos_governance;
+    use aptos_framework::jwk_consensus_config;
+    use std::string::utf8;
+
+    fun main(core_resources: &signer) {{
+        let framework = aptos_governance::get_signer_testnet_only(core_resources, @0x1);
+        let config = jwk_consensus_config::new_v2(vector[
+            {provider_lines}
+        ]);
+        jwk_consensus_config::set_for_next_epoch(&framework, config);
+        aptos_governance::reconfigure(&framework);
+    }}
+}}
 "#
             )
         },
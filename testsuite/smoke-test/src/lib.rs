@@ -13,6 +13,8 @@ mod aptos_cli;
 #[cfg(test)]
 mod client;
 #[cfg(test)]
+mod compatibility_matrix;
+#[cfg(test)]
 mod consensus;
 #[cfg(test)]
 mod consensus_key_rotation;
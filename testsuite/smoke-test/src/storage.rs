@@ -320,6 +320,84 @@ fn get_backup_storage_state(
 }
 
 #[allow(clippy::zombie_processes)]
+#[tokio::test]
+async fn test_db_bootstrap_from_local_backup() {
+    ::aptos_logger::Logger::new().init();
+    workspace_builder::get_bin("aptos-debugger");
+
+    let mut swarm = SwarmBuilder::new_local(4).with_aptos().build().await;
+    let validator_peer_ids = swarm.validators().map(|v| v.peer_id()).collect::<Vec<_>>();
+    let client_1 = swarm
+        .validator(validator_peer_ids[1])
+        .unwrap()
+        .rest_client();
+    let transaction_factory = swarm.chain_info().transaction_factory();
+    let mut account_0 = create_and_fund_account(&mut swarm, 1000000).await;
+    let account_1 = create_and_fund_account(&mut swarm, 1000000).await;
+    transfer_and_maybe_reconfig(
+        &client_1,
+        &transaction_factory,
+        swarm.chain_info().root_account,
+        &mut account_0,
+        &account_1,
+        5,
+    )
+    .await;
+    for _ in 0..2 {
+        reconfig(
+            &client_1,
+            &transaction_factory,
+            swarm.chain_info().root_account,
+        )
+        .await;
+    }
+
+    info!("---------- 1. making a backup from node 1.");
+    let node1_config = swarm.validator(validator_peer_ids[1]).unwrap().config();
+    let port = node1_config.storage.backup_service_address.port();
+    let (backup_path, _) = db_backup(port, 2, 100, 20, 1, &[]);
+
+    info!("---------- 2. nuking the DB of node 0, it's restored from the backup on startup.");
+    let node_to_restart = validator_peer_ids[0];
+    swarm.validator_mut(node_to_restart).unwrap().stop();
+    let node0_config_path = swarm.validator(node_to_restart).unwrap().config_path();
+    let mut node0_config = swarm.validator(node_to_restart).unwrap().config().clone();
+    node0_config.storage.bootstrap_snapshot_dir = Some(backup_path.path().to_path_buf());
+    node0_config.save_to_path(node0_config_path).unwrap();
+    fs::remove_dir_all(node0_config.storage.dir()).unwrap();
+
+    transfer_and_maybe_reconfig(
+        &client_1,
+        &transaction_factory,
+        swarm.chain_info().root_account,
+        &mut account_0,
+        &account_1,
+        3,
+    )
+    .await;
+
+    swarm
+        .validator_mut(node_to_restart)
+        .unwrap()
+        .start()
+        .unwrap();
+    swarm
+        .validator_mut(node_to_restart)
+        .unwrap()
+        .wait_until_healthy(Instant::now() + Duration::from_secs(MAX_HEALTHY_WAIT_SECS))
+        .await
+        .unwrap();
+
+    info!("---------- 3. node 0 is healthy, verifying that it caught up.");
+    swarm
+        .wait_for_all_nodes_to_catchup(Duration::from_secs(MAX_CATCH_UP_WAIT_SECS))
+        .await
+        .unwrap();
+    let client_0 = swarm.validator(node_to_restart).unwrap().rest_client();
+    assert_balance(&client_0, &account_0, 1000000 - 8).await;
+    assert_balance(&client_0, &account_1, 1000000 + 8).await;
+}
+
 pub(crate) fn db_backup(
     backup_service_port: u16,
     target_epoch: u64,
@@ -12,6 +12,7 @@ pub mod load_vs_perf_benchmark;
 pub mod modifiers;
 pub mod multi_region_network_test;
 pub mod network_bandwidth_test;
+pub mod network_chaos_schedule_test;
 pub mod network_loss_test;
 pub mod network_partition_test;
 pub mod partial_nodes_down_test;
@@ -19,6 +20,7 @@ pub mod performance_test;
 pub mod public_fullnode_performance;
 pub mod quorum_store_onchain_enable_test;
 pub mod reconfiguration_test;
+pub mod state_sync_catch_up_performance;
 pub mod state_sync_performance;
 pub mod three_region_simulation_test;
 pub mod twin_validator_test;
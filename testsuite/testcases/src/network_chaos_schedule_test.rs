@@ -0,0 +1,96 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::NetworkLoadTest;
+use anyhow::ensure;
+use aptos_forge::{
+    ChaosSchedule, GroupNetEm, GroupNetworkPartition, NetworkContextSynchronizer, NetworkTest,
+    PartitionDirection, Result, Swarm, SwarmChaos, SwarmDirectedPartition, SwarmNetEm, Test,
+    TestReport,
+};
+use async_trait::async_trait;
+use std::{sync::Arc, time::Duration};
+use tokio::sync::RwLock;
+
+/// Runs a deterministic chaos schedule while the load is running: first one validator
+/// can't send to the rest of the network (but can still receive), then the link between
+/// two other validators gets latency, jitter and packet loss. Each chaos is healed
+/// automatically, so the network has time to recover before the next one.
+pub struct NetworkChaosScheduleTest;
+
+impl Test for NetworkChaosScheduleTest {
+    fn name(&self) -> &'static str {
+        "network::chaos-schedule-test"
+    }
+}
+
+impl NetworkChaosScheduleTest {
+    fn create_schedule(&self, swarm: &dyn Swarm, duration: Duration) -> Result<ChaosSchedule> {
+        let mut validators: Vec<_> = swarm.validators().map(|v| v.peer_id()).collect();
+        validators.sort();
+        ensure!(
+            validators.len() >= 4,
+            "The chaos schedule test needs at least 4 validators, got {}",
+            validators.len()
+        );
+
+        let phase = duration / 4;
+        let partition = SwarmChaos::DirectedPartition(SwarmDirectedPartition {
+            group_partitions: vec![GroupNetworkPartition {
+                name: "isolate-outbound".to_string(),
+                source_nodes: vec![validators[0]],
+                target_nodes: validators[1..].to_vec(),
+                direction: PartitionDirection::To,
+            }],
+        });
+        let netem = SwarmChaos::NetEm(SwarmNetEm {
+            group_netems: vec![GroupNetEm {
+                name: "degraded-link".to_string(),
+                source_nodes: vec![validators[1]],
+                target_nodes: vec![validators[2]],
+                delay_latency_ms: 200,
+                delay_jitter_ms: 50,
+                delay_correlation_percentage: 50,
+                loss_percentage: 5,
+                loss_correlation_percentage: 50,
+                rate_in_mbps: 1000,
+            }],
+        });
+
+        Ok(ChaosSchedule::new()
+            .inject(phase / 2, phase, partition)
+            .inject(phase * 2, phase, netem))
+    }
+}
+
+#[async_trait]
+impl NetworkLoadTest for NetworkChaosScheduleTest {
+    async fn test(
+        &self,
+        swarm: Arc<RwLock<Box<dyn Swarm>>>,
+        report: &mut TestReport,
+        duration: Duration,
+    ) -> Result<()> {
+        let schedule = self.create_schedule(swarm.read().await.as_ref(), duration)?;
+        for event in schedule.events() {
+            let msg = format!(
+                "Scheduled chaos from {:?} for {:?}: {:?}",
+                event.start, event.duration, event.chaos
+            );
+            println!("{}", msg);
+            report.report_text(msg);
+        }
+
+        let start = tokio::time::Instant::now();
+        schedule.run(swarm).await?;
+        tokio::time::sleep_until(start + duration).await;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl NetworkTest for NetworkChaosScheduleTest {
+    async fn run<'a>(&self, ctx: NetworkContextSynchronizer<'a>) -> Result<()> {
+        <dyn NetworkLoadTest>::run(self, ctx).await
+    }
+}
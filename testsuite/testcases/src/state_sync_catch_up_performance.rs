@@ -0,0 +1,295 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{LoadDestination, NetworkLoadTest};
+use anyhow::{bail, format_err};
+use aptos_forge::{
+    get_highest_synced_version_and_epoch, NetworkContext, NetworkContextSynchronizer, NetworkTest,
+    NodeExt, Result, Swarm, SwarmExt, Test, TestReport,
+};
+use aptos_rest_client::Client as RestClient;
+use aptos_sdk::types::PeerId;
+use async_trait::async_trait;
+use log::info;
+use std::{sync::Arc, time::Duration};
+use tokio::{sync::RwLock, time::Instant};
+
+const SAMPLE_INTERVAL: Duration = Duration::from_secs(1); // The interval at which sync progress is sampled
+
+/// A state sync performance test that measures how quickly a lagging fullnode
+/// catches up to a loaded network. While the load is running, the last fullnode
+/// is stopped (and optionally wiped, to force it to bootstrap again), left behind
+/// for a while and then restarted. Its sync progress is sampled every second, and
+/// the throughput percentiles (while catching up) and lag percentiles (once caught
+/// up) are reported and checked against the given thresholds.
+///
+/// The sync path being measured is determined by the fullnode's state sync config,
+/// e.g., fast sync (wiped storage and downloading the latest states) or intelligent
+/// sync (executing transactions or applying outputs, whichever is faster).
+pub struct StateSyncCatchUpPerformance {
+    /// Whether to wipe the lagging fullnode's storage before restarting it
+    pub wipe_storage: bool,
+    /// How long the fullnode is kept offline (as a fraction of the test duration)
+    pub lag_fraction: f64,
+    /// The minimum median throughput (versions per second) while catching up
+    pub min_p50_sync_throughput: u64,
+    /// The maximum time allowed for the fullnode to catch up after restarting
+    pub max_catch_up_duration: Duration,
+    /// The maximum p99 lag (in seconds) behind the network once caught up
+    pub max_p99_lag_secs: f64,
+}
+
+impl Test for StateSyncCatchUpPerformance {
+    fn name(&self) -> &'static str {
+        "StateSyncCatchUpPerformance"
+    }
+}
+
+/// The sync progress of the lagging fullnode at a single point in time
+struct SyncSample {
+    time: Instant,
+    node_version: u64,
+    node_timestamp_usecs: u64,
+    network_version: u64,
+    network_timestamp_usecs: u64,
+}
+
+impl StateSyncCatchUpPerformance {
+    async fn sample_sync_progress(
+        lagging_client: &RestClient,
+        validator_clients: &[(String, RestClient)],
+    ) -> Result<SyncSample> {
+        let time = Instant::now();
+        let (network_version, _) = get_highest_synced_version_and_epoch(validator_clients).await?;
+        let mut network_timestamp_usecs = 0;
+        for (_, client) in validator_clients {
+            if let Ok(response) = client.get_ledger_information().await {
+                network_timestamp_usecs =
+                    network_timestamp_usecs.max(response.inner().timestamp_usecs);
+            }
+        }
+
+        // The node might not be serving requests yet, in which case it hasn't synced anything
+        let (node_version, node_timestamp_usecs) = lagging_client
+            .get_ledger_information()
+            .await
+            .map(|response| (response.inner().version, response.inner().timestamp_usecs))
+            .unwrap_or((0, 0));
+
+        Ok(SyncSample {
+            time,
+            node_version,
+            node_timestamp_usecs,
+            network_version,
+            network_timestamp_usecs,
+        })
+    }
+
+    fn report_percentiles(
+        &self,
+        report: &mut TestReport,
+        metric: &str,
+        unit: &str,
+        values: &mut [f64],
+    ) -> Vec<f64> {
+        values.sort_by(|a, b| a.total_cmp(b));
+        let percentiles: Vec<_> = [50.0, 90.0, 99.0]
+            .iter()
+            .map(|p| {
+                let value = percentile(values, *p);
+                report.report_metric(self.name(), format!("{}_p{}", metric, p), value);
+                value
+            })
+            .collect();
+        let msg = format!(
+            "{}: p50 {:.2} {}, p90 {:.2} {}, p99 {:.2} {}",
+            metric, percentiles[0], unit, percentiles[1], unit, percentiles[2], unit
+        );
+        info!("{}", msg);
+        report.report_text(msg);
+        percentiles
+    }
+}
+
+#[async_trait]
+impl NetworkLoadTest for StateSyncCatchUpPerformance {
+    async fn setup<'a>(&self, ctx: &mut NetworkContext<'a>) -> Result<LoadDestination> {
+        if ctx.swarm.read().await.full_nodes().next().is_none() {
+            bail!("{} requires at least 1 fullnode!", self.name());
+        }
+        // The lagging fullnode will be offline, so only send the load to the validators
+        Ok(LoadDestination::AllValidators)
+    }
+
+    async fn test(
+        &self,
+        swarm: Arc<RwLock<Box<dyn Swarm>>>,
+        report: &mut TestReport,
+        duration: Duration,
+    ) -> Result<()> {
+        let test_start = Instant::now();
+        let (lagging_fullnode, lagging_client, validator_clients) = {
+            let swarm = swarm.read().await;
+            let fullnode = swarm.full_nodes().last().unwrap();
+            (
+                fullnode.peer_id(),
+                fullnode.rest_client(),
+                swarm.get_validator_clients_with_names(),
+            )
+        };
+
+        // Take the fullnode offline, and let it fall behind the loaded network
+        let lag_duration = duration.mul_f64(self.lag_fraction);
+        info!(
+            "Stopping fullnode {} for {:?} (wipe storage: {}).",
+            lagging_fullnode, lag_duration, self.wipe_storage
+        );
+        stop_fullnode(&swarm, lagging_fullnode, self.wipe_storage).await?;
+        tokio::time::sleep(lag_duration).await;
+        swarm
+            .read()
+            .await
+            .full_node(lagging_fullnode)
+            .unwrap()
+            .start()
+            .await?;
+
+        // Sample the sync progress until the fullnode has caught up
+        let catch_up_start = Instant::now();
+        let mut previous_sample =
+            Self::sample_sync_progress(&lagging_client, &validator_clients).await?;
+        let versions_behind = previous_sample
+            .network_version
+            .saturating_sub(previous_sample.node_version);
+        let mut sync_throughputs = vec![];
+        loop {
+            if catch_up_start.elapsed() > self.max_catch_up_duration {
+                bail!(
+                    "Fullnode {} failed to catch up within {:?}! Synced version: {}, network version: {}",
+                    lagging_fullnode,
+                    self.max_catch_up_duration,
+                    previous_sample.node_version,
+                    previous_sample.network_version
+                );
+            }
+            tokio::time::sleep(SAMPLE_INTERVAL).await;
+
+            let sample = Self::sample_sync_progress(&lagging_client, &validator_clients).await?;
+            if sample.node_version > 0 && previous_sample.node_version > 0 {
+                let elapsed_secs = (sample.time - previous_sample.time).as_secs_f64();
+                let synced_versions = sample
+                    .node_version
+                    .saturating_sub(previous_sample.node_version);
+                sync_throughputs.push(synced_versions as f64 / elapsed_secs);
+            }
+
+            // The fullnode has caught up once it reaches the network version of the previous sample
+            let caught_up = sample.node_version >= previous_sample.network_version;
+            previous_sample = sample;
+            if caught_up {
+                break;
+            }
+        }
+        let catch_up_duration = catch_up_start.elapsed();
+        let msg = format!(
+            "Fullnode caught up {} versions in {:.2} secs",
+            versions_behind,
+            catch_up_duration.as_secs_f64()
+        );
+        info!("{}", msg);
+        report.report_text(msg);
+        report.report_metric(
+            self.name(),
+            "catch_up_duration_secs",
+            catch_up_duration.as_secs_f64(),
+        );
+        if sync_throughputs.is_empty() {
+            return Err(format_err!(
+                "No sync throughput samples were taken for fullnode {}! Something has gone wrong!",
+                lagging_fullnode
+            ));
+        }
+        let throughput_percentiles = self.report_percentiles(
+            report,
+            "sync_throughput",
+            "versions/sec",
+            &mut sync_throughputs,
+        );
+
+        // Once caught up, sample how far behind the network the fullnode stays
+        let mut lags_secs = vec![];
+        while test_start.elapsed() + SAMPLE_INTERVAL < duration || lags_secs.is_empty() {
+            tokio::time::sleep(SAMPLE_INTERVAL).await;
+            let sample = Self::sample_sync_progress(&lagging_client, &validator_clients).await?;
+            let lag_usecs = sample
+                .network_timestamp_usecs
+                .saturating_sub(sample.node_timestamp_usecs);
+            lags_secs.push(lag_usecs as f64 / 1_000_000.0);
+        }
+        let lag_percentiles = self.report_percentiles(report, "sync_lag", "secs", &mut lags_secs);
+
+        // Ensure we meet the success criteria
+        if throughput_percentiles[0] < self.min_p50_sync_throughput as f64 {
+            bail!(
+                "State sync catch up throughput requirement failed. p50 throughput: {:.2}, minimum required: {}",
+                throughput_percentiles[0],
+                self.min_p50_sync_throughput
+            );
+        }
+        if lag_percentiles[2] > self.max_p99_lag_secs {
+            bail!(
+                "State sync lag requirement failed. p99 lag: {:.2} secs, maximum allowed: {:.2} secs",
+                lag_percentiles[2],
+                self.max_p99_lag_secs
+            );
+        }
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl NetworkTest for StateSyncCatchUpPerformance {
+    async fn run<'a>(&self, ctx: NetworkContextSynchronizer<'a>) -> Result<()> {
+        <dyn NetworkLoadTest>::run(self, ctx).await
+    }
+}
+
+/// Stops the given fullnode, optionally wiping its storage
+async fn stop_fullnode(
+    swarm: &Arc<RwLock<Box<dyn Swarm>>>,
+    fullnode: PeerId,
+    wipe_storage: bool,
+) -> Result<()> {
+    let swarm = swarm.read().await;
+    let fullnode = swarm.full_node(fullnode).unwrap();
+    if wipe_storage {
+        fullnode.clear_storage().await
+    } else {
+        fullnode.stop().await
+    }
+}
+
+/// Returns the given percentile (0-100) of the sorted values, using the nearest rank
+fn percentile(sorted_values: &[f64], percentile: f64) -> f64 {
+    if sorted_values.is_empty() {
+        return 0.0;
+    }
+    let rank = (percentile / 100.0 * sorted_values.len() as f64).ceil() as usize;
+    sorted_values[rank.clamp(1, sorted_values.len()) - 1]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::percentile;
+
+    #[test]
+    fn test_percentile() {
+        let values: Vec<_> = (1..=100).map(|v| v as f64).collect();
+        assert_eq!(percentile(&values, 50.0), 50.0);
+        assert_eq!(percentile(&values, 99.0), 99.0);
+        assert_eq!(percentile(&values, 100.0), 100.0);
+        assert_eq!(percentile(&[7.0], 90.0), 7.0);
+        assert_eq!(percentile(&[], 50.0), 0.0);
+    }
+}
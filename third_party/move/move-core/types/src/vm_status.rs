@@ -614,9 +614,10 @@ pub enum StatusCode {
     NONCE_ALREADY_USED = 42,
     EMPTY_PAYLOAD_PROVIDED = 43,
     TRANSACTION_EXPIRATION_TOO_FAR_IN_FUTURE = 44,
+    // The fee payer's sponsorship quota for the sender or the called function is used up
+    SPONSORSHIP_QUOTA_EXCEEDED = 45,
 
     // Reserved error code for future use
-    RESERVED_VALIDATION_ERROR_10 = 45,
     RESERVED_VALIDATION_ERROR_11 = 46,
     RESERVED_VALIDATION_ERROR_12 = 47,
     RESERVED_VALIDATION_ERROR_13 = 48,
@@ -823,12 +824,14 @@ pub enum StatusCode {
     // Should never be committed on chain
     SPECULATIVE_EXECUTION_ABORT_ERROR = 2024,
     ACCESS_CONTROL_INVARIANT_VIOLATION = 2025,
+    // Storage errors, more specific than STORAGE_ERROR. Failures to reach the storage backend or
+    // timeouts are not about the transaction, and executing it again can succeed.
+    STORAGE_NOT_FOUND = 2026,
+    STORAGE_CORRUPTED = 2027,
+    STORAGE_UNAVAILABLE = 2028,
+    STORAGE_TIMEOUT = 2039,
 
     // Reserved error code for future use
-    RESERVED_INVARIANT_VIOLATION_ERROR_1 = 2026,
-    RESERVED_INVARIANT_VIOLATION_ERROR_2 = 2027,
-    RESERVED_INVARIANT_VIOLATION_ERROR_3 = 2028,
-    RESERVED_INVARIANT_VIOLATION_ERROR_4 = 2039,
     RESERVED_INVARIANT_VIOLATION_ERROR_5 = 2040,
 
     // Errors that can arise from binary decoding (deserialization)
@@ -4,14 +4,20 @@
 #[macro_export]
 macro_rules! module_storage_error {
     ($addr:expr, $name:expr, $err:ident) => {
-        move_binary_format::errors::PartialVMError::new(
-            move_core_types::vm_status::StatusCode::STORAGE_ERROR,
+        $crate::module_storage_error!(
+            $addr,
+            $name,
+            $err,
+            move_core_types::vm_status::StatusCode::STORAGE_ERROR
         )
-        .with_message(format!(
-            "Unexpected storage error for module {}::{}: {:?}",
-            $addr, $name, $err
-        ))
-        .finish(move_binary_format::errors::Location::Undefined)
+    };
+    ($addr:expr, $name:expr, $err:ident, $status:expr) => {
+        move_binary_format::errors::PartialVMError::new($status)
+            .with_message(format!(
+                "Unexpected storage error for module {}::{}: {:?}",
+                $addr, $name, $err
+            ))
+            .finish(move_binary_format::errors::Location::Undefined)
     };
 }
 
@@ -32,6 +32,28 @@ impl Default for BlockExecutorModuleCacheLocalConfig {
     }
 }
 
+/// Local, per-node configuration for detecting blocks dominated by a single hot sender. The
+/// transactions of the sender are executed one after another (in a sequential lane), while the
+/// rest of the block is executed in parallel, to avoid speculative abort storms. Only supported
+/// by BlockSTMv1.
+#[derive(Clone, Debug)]
+pub struct HotAccountDetectionConfig {
+    /// Percentage of the user transactions in a block that need to share the sender for it to be
+    /// considered hot.
+    pub min_hot_percentage: usize,
+    /// Blocks with fewer user transactions are always executed fully in parallel.
+    pub min_num_user_txns: usize,
+}
+
+impl Default for HotAccountDetectionConfig {
+    fn default() -> Self {
+        Self {
+            min_hot_percentage: 50,
+            min_num_user_txns: 32,
+        }
+    }
+}
+
 /// Local, per-node configuration.
 #[derive(Clone, Debug)]
 pub struct BlockExecutorLocalConfig {
@@ -45,6 +67,9 @@ pub struct BlockExecutorLocalConfig {
     // (allow_fallback needs to be set)
     pub discard_failed_blocks: bool,
     pub module_cache_config: BlockExecutorModuleCacheLocalConfig,
+    // If specified, transactions of a hot sender dominating the block are executed in a
+    // sequential lane during parallel execution. Must not be set with blockstm_v2.
+    pub hot_account_detection: Option<HotAccountDetectionConfig>,
 }
 
 impl BlockExecutorLocalConfig {
@@ -52,6 +77,7 @@ impl BlockExecutorLocalConfig {
     ///   - Allowed fallback to sequential execution from parallel.
     ///   - Not allowed discards of failed blocks.
     ///   - Default module cache configs.
+    ///   - No hot account detection.
     pub fn default_with_concurrency_level(concurrency_level: usize) -> Self {
         Self {
             blockstm_v2: false,
@@ -59,6 +85,7 @@ impl BlockExecutorLocalConfig {
             allow_fallback: true,
             discard_failed_blocks: false,
             module_cache_config: BlockExecutorModuleCacheLocalConfig::default(),
+            hot_account_detection: None,
         }
     }
 }
@@ -10,6 +10,7 @@ use crate::{
     dkg::DKGStartEvent,
     event::EventKey,
     jwks::ObservedJWKsUpdated,
+    stake_pool::UpdateNetworkAndFullnodeAddresses,
     transaction::Version,
 };
 use anyhow::{bail, Error, Result};
@@ -325,6 +326,26 @@ impl TryFrom<&ContractEvent> for DKGStartEvent {
     }
 }
 
+impl TryFrom<&ContractEvent> for UpdateNetworkAndFullnodeAddresses {
+    type Error = Error;
+
+    fn try_from(event: &ContractEvent) -> Result<Self> {
+        match event {
+            ContractEvent::V1(_) => {
+                bail!(
+                    "conversion to network address update failed with wrong contract event version"
+                );
+            },
+            ContractEvent::V2(event) => {
+                if event.type_tag != TypeTag::Struct(Box::new(Self::struct_tag())) {
+                    bail!("conversion to network address update failed with wrong type tag")
+                }
+                bcs::from_bytes(&event.event_data).map_err(Into::into)
+            },
+        }
+    }
+}
+
 impl TryFrom<&ContractEvent> for NewEpochEvent {
     type Error = Error;
 
@@ -4,12 +4,18 @@
 
 #![forbid(unsafe_code)]
 
-use crate::ledger_info::{LedgerInfo, LedgerInfoWithSignatures};
+use crate::{
+    epoch_state::EpochState,
+    ledger_info::{LedgerInfo, LedgerInfoWithSignatures},
+    validator_verifier::ValidatorVerifier,
+};
 use anyhow::{ensure, format_err, Result};
+use aptos_crypto::{hash::CryptoHash, HashValue};
+use aptos_infallible::RwLock;
 #[cfg(any(test, feature = "fuzzing"))]
 use proptest::{collection::vec, prelude::*};
 use serde::{Deserialize, Serialize};
-use std::fmt::Debug;
+use std::{collections::BTreeMap, fmt::Debug, sync::Arc};
 
 /// The verification of the epoch change proof starts with verifier that is trusted by the
 /// client: could be either a waypoint (upon startup) or a known epoch info.
@@ -17,6 +23,11 @@ pub trait Verifier: Debug + Send + Sync {
     /// Verify if the ledger_info is trust worthy.
     fn verify(&self, ledger_info: &LedgerInfoWithSignatures) -> Result<()>;
 
+    /// Verify that the ledger_info directly follows from this verifier (e.g. is in the epoch it
+    /// trusts), without verifying its signatures. Used for ledger infos whose signatures were
+    /// already verified.
+    fn verify_follows(&self, ledger_info: &LedgerInfo) -> Result<()>;
+
     /// Returns true in case the given epoch is larger than the existing verifier can support.
     /// In this case the EpochChangeProof should be verified and the verifier updated.
     fn epoch_change_verification_required(&self, epoch: u64) -> bool;
@@ -65,6 +76,25 @@ impl EpochChangeProof {
     ///
     /// We will also skip any stale ledger info's in the [`EpochChangeProof`].
     pub fn verify(&self, verifier: &dyn Verifier) -> Result<&LedgerInfoWithSignatures> {
+        self.verify_impl(verifier, None)
+    }
+
+    /// Same as [`EpochChangeProof::verify`], but the signatures of epoch ending ledger infos
+    /// found in `cache` are not verified again, and the ones verified are added to it.
+    pub fn verify_with_cache(
+        &self,
+        verifier: &dyn Verifier,
+        cache: &VerifiedEpochCache,
+    ) -> Result<&LedgerInfoWithSignatures> {
+        self.verify_impl(verifier, Some(cache))
+    }
+
+    fn verify_impl(
+        &self,
+        verifier: &dyn Verifier,
+        cache: Option<&VerifiedEpochCache>,
+    ) -> Result<&LedgerInfoWithSignatures> {
+        let is_cached = |ledger_info: &LedgerInfo| cache.is_some_and(|c| c.contains(ledger_info));
         ensure!(
             !self.ledger_info_with_sigs.is_empty(),
             "The EpochChangeProof is empty"
@@ -75,9 +105,7 @@ impl EpochChangeProof {
             "The EpochChangeProof is stale as our verifier is already ahead \
              of the entire EpochChangeProof"
         );
-        let mut verifier_ref = verifier;
-
-        for ledger_info_with_sigs in self
+        let mut ledger_infos_with_sigs = self
             .ledger_info_with_sigs
             .iter()
             // Skip any stale ledger infos in the proof prefix. Note that with
@@ -102,23 +130,108 @@ impl EpochChangeProof {
             // response as it's completely stale.
             .skip_while(|&ledger_info_with_sigs| {
                 verifier.is_ledger_info_stale(ledger_info_with_sigs.ledger_info())
-            })
-        {
-            // Try to verify each (epoch -> epoch + 1) jump in the EpochChangeProof.
-            verifier_ref.verify(ledger_info_with_sigs)?;
-            // While the original verification could've been via waypoints,
-            // all the next epoch changes are verified using the (already
-            // trusted) validator sets.
-            verifier_ref = ledger_info_with_sigs
-                .ledger_info()
-                .next_epoch_state()
-                .ok_or_else(|| format_err!("LedgerInfo doesn't carry a ValidatorSet"))?;
+            });
+
+        // The first (epoch -> epoch + 1) jump is verified with the original verifier, which
+        // could be a waypoint.
+        let first_ledger_info_with_sigs = ledger_infos_with_sigs
+            .next()
+            .expect("The EpochChangeProof has a non-stale ledger info");
+        // A cached ledger info only saves verifying the signatures, it still has to follow from
+        // the verifier.
+        if is_cached(first_ledger_info_with_sigs.ledger_info()) {
+            verifier.verify_follows(first_ledger_info_with_sigs.ledger_info())?;
+        } else {
+            verifier.verify(first_ledger_info_with_sigs)?;
+        }
+        let mut epoch_state = next_epoch_state(first_ledger_info_with_sigs)?;
+        let mut verified = vec![first_ledger_info_with_sigs.ledger_info()];
+
+        // All the next epoch changes are verified using the (already trusted) validator sets.
+        // Their signatures are verified in a single batch, which is much faster than one by one
+        // for long proofs. Since the batch is only accepted if every signature is valid, it is
+        // fine to read the next validator set before its ledger info is verified.
+        let mut batch = vec![];
+        for ledger_info_with_sigs in ledger_infos_with_sigs {
+            let ledger_info = ledger_info_with_sigs.ledger_info();
+            ensure!(
+                epoch_state.epoch == ledger_info.epoch(),
+                "LedgerInfo has unexpected epoch {}, expected {}",
+                ledger_info.epoch(),
+                epoch_state.epoch
+            );
+            if !is_cached(ledger_info) {
+                batch.push((
+                    ledger_info,
+                    ledger_info_with_sigs.signatures(),
+                    epoch_state.verifier.as_ref(),
+                ));
+            }
+            verified.push(ledger_info);
+            epoch_state = next_epoch_state(ledger_info_with_sigs)?;
+        }
+        ValidatorVerifier::verify_multi_signatures_batch(&batch)?;
+
+        if let Some(cache) = cache {
+            cache.insert_all(verified);
         }
 
         Ok(self.ledger_info_with_sigs.last().unwrap())
     }
 }
 
+/// Epoch ending ledger infos that have already been verified, so that repeatedly verifying
+/// epoch change proofs from the same trusted waypoint (e.g. the backup verifier, state sync or
+/// the CLI) doesn't verify the signatures of every epoch again.
+///
+/// Only ledger infos that were verified against a trusted verifier are added, and a cached
+/// ledger info is trusted as if it was verified again. So a cache must only be shared between
+/// verifiers trusting the same chain. Clones share the same underlying cache.
+#[derive(Clone, Debug, Default)]
+pub struct VerifiedEpochCache {
+    // Epoch -> hash of the ledger info ending that epoch.
+    verified: Arc<RwLock<BTreeMap<u64, HashValue>>>,
+}
+
+impl VerifiedEpochCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Whether `ledger_info` is a verified epoch ending ledger info.
+    pub fn contains(&self, ledger_info: &LedgerInfo) -> bool {
+        ledger_info.ends_epoch()
+            && self.verified.read().get(&ledger_info.epoch()) == Some(&ledger_info.hash())
+    }
+
+    /// The latest epoch with a verified epoch ending ledger info.
+    pub fn latest_epoch(&self) -> Option<u64> {
+        self.verified.read().keys().next_back().copied()
+    }
+
+    pub fn len(&self) -> usize {
+        self.verified.read().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.verified.read().is_empty()
+    }
+
+    fn insert_all<'a>(&self, ledger_infos: impl IntoIterator<Item = &'a LedgerInfo>) {
+        let mut verified = self.verified.write();
+        for ledger_info in ledger_infos {
+            verified.insert(ledger_info.epoch(), ledger_info.hash());
+        }
+    }
+}
+
+fn next_epoch_state(ledger_info_with_sigs: &LedgerInfoWithSignatures) -> Result<&EpochState> {
+    ledger_info_with_sigs
+        .ledger_info()
+        .next_epoch_state()
+        .ok_or_else(|| format_err!("LedgerInfo doesn't carry a ValidatorSet"))
+}
+
 #[cfg(any(test, feature = "fuzzing"))]
 impl Arbitrary for EpochChangeProof {
     type Parameters = ();
@@ -140,34 +253,30 @@ mod tests {
         epoch_state::EpochState,
         waypoint::Waypoint,
     };
-    use std::sync::Arc;
 
-    #[test]
-    fn verify_epoch_change_proof() {
-        use crate::{ledger_info::LedgerInfo, validator_verifier::random_validator_verifier};
-        use aptos_crypto::hash::HashValue;
+    /// Generates end-epoch ledger infos for epoch 1 to 10, each signed by the current validator
+    /// set and carrying the next epoch info, along with the validator set of each epoch.
+    fn epoch_ending_ledger_infos() -> (Vec<LedgerInfoWithSignatures>, Vec<Arc<ValidatorVerifier>>) {
+        use crate::validator_verifier::random_validator_verifier;
 
-        let all_epoch: Vec<u64> = (1..=10).collect();
         let mut valid_ledger_info = vec![];
         let mut validator_verifier = vec![];
 
-        // We generate end-epoch ledger info for epoch 1 to 10, each signed by the current
-        // validator set and carrying the next epoch info.
         let (mut current_signers, current_verifier) = random_validator_verifier(1, None, true);
         let mut current_verifier = Arc::new(current_verifier);
         let mut current_version = 123;
-        for epoch in &all_epoch {
+        for epoch in 1..=10u64 {
             validator_verifier.push(current_verifier.clone());
             let (next_signers, next_verifier) =
-                random_validator_verifier((*epoch + 1) as usize, None, true);
+                random_validator_verifier((epoch + 1) as usize, None, true);
             let next_verifier = Arc::new(next_verifier);
             let epoch_state = EpochState {
-                epoch: *epoch + 1,
+                epoch: epoch + 1,
                 verifier: next_verifier.clone(),
             };
             let ledger_info = LedgerInfo::new(
                 BlockInfo::new(
-                    *epoch,
+                    epoch,
                     0,
                     HashValue::zero(),
                     HashValue::zero(),
@@ -196,6 +305,13 @@ mod tests {
             current_verifier = next_verifier;
             current_version += 1;
         }
+        (valid_ledger_info, validator_verifier)
+    }
+
+    #[test]
+    fn verify_epoch_change_proof() {
+        let all_epoch: Vec<u64> = (1..=10).collect();
+        let (valid_ledger_info, validator_verifier) = epoch_ending_ledger_infos();
 
         // Test well-formed proof will succeed
         let proof_1 = EpochChangeProof::new(valid_ledger_info.clone(), /* more = */ false);
@@ -290,4 +406,41 @@ mod tests {
         let proof_8 = EpochChangeProof::new(valid_ledger_info[..1].to_vec(), /* more */ false);
         assert!(proof_8.verify(&waypoint_for_3_to_4).is_err());
     }
+
+    #[test]
+    fn verify_epoch_change_proof_with_cache() {
+        let (valid_ledger_info, validator_verifier) = epoch_ending_ledger_infos();
+        let cache = VerifiedEpochCache::new();
+        let proof = EpochChangeProof::new(valid_ledger_info.clone(), /* more = */ false);
+        let epoch_state = EpochState {
+            epoch: 1,
+            verifier: validator_verifier[0].clone(),
+        };
+        proof.verify_with_cache(&epoch_state, &cache).unwrap();
+        assert_eq!(cache.len(), 10);
+        assert_eq!(cache.latest_epoch(), Some(10));
+        // Verifying again only uses the cache
+        proof.verify_with_cache(&epoch_state, &cache).unwrap();
+
+        // The ledger infos of an unrelated chain are cached, but don't follow from the trusted
+        // epoch of this one.
+        let (other_ledger_info, other_validator_verifier) = epoch_ending_ledger_infos();
+        let other_proof =
+            EpochChangeProof::new(other_ledger_info[4..].to_vec(), /* more = */ false);
+        other_proof
+            .verify_with_cache(
+                &EpochState {
+                    epoch: 5,
+                    verifier: other_validator_verifier[4].clone(),
+                },
+                &cache,
+            )
+            .unwrap();
+        let trusted = EpochState {
+            epoch: 2,
+            verifier: validator_verifier[1].clone(),
+        };
+        assert!(other_proof.verify_with_cache(&trusted, &cache).is_err());
+        assert!(other_proof.verify(&trusted).is_err());
+    }
 }
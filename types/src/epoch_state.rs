@@ -50,6 +50,16 @@ impl Verifier for EpochState {
         Ok(())
     }
 
+    fn verify_follows(&self, ledger_info: &LedgerInfo) -> anyhow::Result<()> {
+        ensure!(
+            self.epoch == ledger_info.epoch(),
+            "LedgerInfo has unexpected epoch {}, expected {}",
+            ledger_info.epoch(),
+            self.epoch
+        );
+        Ok(())
+    }
+
     fn epoch_change_verification_required(&self, epoch: u64) -> bool {
         self.epoch < epoch
     }
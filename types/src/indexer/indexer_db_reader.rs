@@ -13,6 +13,8 @@ use crate::{
     transaction::{AccountOrderedTransactionsWithProof, Version},
 };
 use anyhow::Result;
+use move_core_types::language_storage::TypeTag;
+use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Copy, Eq, PartialEq)]
 pub enum Order {
@@ -20,6 +22,31 @@ pub enum Order {
     Descending,
 }
 
+/// Identifies the asset an indexed balance is tracked for.
+#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize, Deserialize)]
+#[cfg_attr(any(test, feature = "fuzzing"), derive(proptest_derive::Arbitrary))]
+pub enum BalanceAsset {
+    /// Balance held in `0x1::coin::CoinStore<CoinType>`, keyed by the coin type.
+    Coin(TypeTag),
+    /// Balance held in the owner's primary `0x1::fungible_asset::FungibleStore`, keyed by the
+    /// address of the asset's metadata object.
+    FungibleAsset(AccountAddress),
+}
+
+/// A package published at an address, as indexed from the address' `0x1::code::PackageRegistry`.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(any(test, feature = "fuzzing"), derive(proptest_derive::Arbitrary))]
+pub struct IndexedPackage {
+    pub address: AccountAddress,
+    pub name: String,
+    pub upgrade_number: u64,
+    /// The upgrade policy, as in `0x1::code::UpgradePolicy`.
+    pub upgrade_policy: u8,
+    pub modules: Vec<String>,
+    /// The (address, name) of the packages this package depends on.
+    pub deps: Vec<(AccountAddress, String)>,
+}
+
 pub trait IndexerReader: Send + Sync {
     fn is_internal_indexer_enabled(&self) -> bool;
 
@@ -59,6 +86,24 @@ pub trait IndexerReader: Send + Sync {
         version: Version,
     ) -> Result<Box<dyn Iterator<Item = Result<(StateKey, StateValue)>> + '_>>;
 
+    /// Returns the balances of up to `limit` assets `address` has ever held, as of
+    /// `ledger_version`, starting from `start_asset`, along with the asset the next page starts
+    /// from. Assets whose balance dropped to zero are included with a zero balance.
+    fn get_account_balances(
+        &self,
+        address: AccountAddress,
+        start_asset: Option<&BalanceAsset>,
+        limit: u64,
+        ledger_version: Version,
+    ) -> Result<(Vec<(BalanceAsset, u64)>, Option<BalanceAsset>)>;
+
+    /// Returns the packages named `package_name`, across all addresses, as of `ledger_version`.
+    fn get_packages_by_name(
+        &self,
+        package_name: &str,
+        ledger_version: Version,
+    ) -> Result<Vec<IndexedPackage>>;
+
     fn get_latest_internal_indexer_ledger_version(&self) -> Result<Option<Version>>;
     fn get_latest_table_info_ledger_version(&self) -> Result<Option<Version>>;
 
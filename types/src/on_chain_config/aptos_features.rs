@@ -2,11 +2,13 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::on_chain_config::OnChainConfig;
+use aptos_crypto::HashValue;
 use move_binary_format::{
     file_format_common,
     file_format_common::{IDENTIFIER_SIZE_MAX, LEGACY_IDENTIFIER_SIZE_MAX},
 };
 use move_core_types::{
+    account_address::AccountAddress,
     effects::{ChangeSet, Op},
     language_storage::CORE_CODE_ADDRESS,
 };
@@ -144,6 +146,16 @@ pub enum FeatureFlag {
     CALCULATE_TRANSACTION_FEE_FOR_DISTRIBUTION = 96,
     DISTRIBUTE_TRANSACTION_FEE = 97,
     MONOTONICALLY_INCREASING_COUNTER = 98,
+    /// Allows WebAuthn assertions signed by Ed25519 (COSE EdDSA) passkeys.
+    WEBAUTHN_ED25519_SIGNATURE = 99,
+    /// Enforces the gas sponsorship quotas fee payers configure in
+    /// `0x1::sponsorship_quota` during the fee payer prologue.
+    SPONSORSHIP_QUOTAS = 100,
+    /// Enables the precompiles registered in the framework natives, called through
+    /// `0x1::precompile`.
+    PRECOMPILES = 101,
+    /// Requires WebAuthn assertions to come from an assertion ceremony with the user present.
+    WEBAUTHN_ASSERTION_CEREMONY = 102,
 }
 
 impl FeatureFlag {
@@ -244,6 +256,9 @@ impl FeatureFlag {
             FeatureFlag::DISTRIBUTE_TRANSACTION_FEE,
             FeatureFlag::ENABLE_LAZY_LOADING,
             FeatureFlag::MONOTONICALLY_INCREASING_COUNTER,
+            FeatureFlag::WEBAUTHN_ED25519_SIGNATURE,
+            FeatureFlag::PRECOMPILES,
+            FeatureFlag::WEBAUTHN_ASSERTION_CEREMONY,
         ]
     }
 }
@@ -465,10 +480,117 @@ pub fn aptos_test_feature_flags_genesis() -> ChangeSet {
     change_set
 }
 
+/// A stage of the rollout of a feature flag, as defined in `feature_activation.move`.
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
+pub struct ScheduledFeature {
+    pub feature: u64,
+    pub activation_epoch: u64,
+    pub activation_time_microseconds: u64,
+    pub rollout_percentage: u64,
+}
+
+/// A feature flag that is enabled for a percentage of the accounts only.
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
+pub struct FeatureRollout {
+    pub feature: u64,
+    pub rollout_percentage: u64,
+}
+
+/// The stages of the feature flags scheduled for a staged rollout, and the partial rollouts in
+/// effect. The stages that are due are applied on reconfiguration, when fully rolled out flags are
+/// enabled in the on-chain [Features], so the VM only needs to look at the partial rollouts.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Eq, Serialize)]
+pub struct FeatureActivationSchedule {
+    pub scheduled: Vec<ScheduledFeature>,
+    pub rollouts: Vec<FeatureRollout>,
+}
+
+impl OnChainConfig for FeatureActivationSchedule {
+    const MODULE_IDENTIFIER: &'static str = "feature_activation";
+    const TYPE_IDENTIFIER: &'static str = "FeatureActivationSchedule";
+}
+
+impl FeatureActivationSchedule {
+    const FULL_ROLLOUT_PERCENTAGE: u64 = 100;
+
+    /// Returns the percentage of the accounts the flag is enabled for.
+    pub fn rollout_percentage(&self, features: &Features, flag: FeatureFlag) -> u64 {
+        if features.is_enabled(flag) {
+            return Self::FULL_ROLLOUT_PERCENTAGE;
+        }
+        self.rollouts
+            .iter()
+            .find(|rollout| rollout.feature == flag as u64)
+            .map_or(0, |rollout| rollout.rollout_percentage)
+    }
+
+    /// Returns true if the flag is enabled for the account, same as
+    /// `feature_activation::is_enabled_for` in Move.
+    pub fn is_enabled_for(
+        &self,
+        features: &Features,
+        flag: FeatureFlag,
+        account: &AccountAddress,
+    ) -> bool {
+        Self::rollout_bucket(account) < self.rollout_percentage(features, flag)
+    }
+
+    fn rollout_bucket(account: &AccountAddress) -> u64 {
+        // The BCS encoding of an address is its bytes.
+        let digest = HashValue::sha3_256_of(account.as_ref());
+        (((digest[0] as u64) << 8) | digest[1] as u64) % Self::FULL_ROLLOUT_PERCENTAGE
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
 
+    #[test]
+    fn test_feature_activation_schedule() {
+        let schedule = FeatureActivationSchedule {
+            scheduled: vec![ScheduledFeature {
+                feature: FeatureFlag::BN254_STRUCTURES as u64,
+                activation_epoch: 5,
+                activation_time_microseconds: 0,
+                rollout_percentage: 100,
+            }],
+            rollouts: vec![FeatureRollout {
+                feature: FeatureFlag::BLS12_381_STRUCTURES as u64,
+                rollout_percentage: 20,
+            }],
+        };
+        let accounts = (0..1000u64)
+            .map(|i| AccountAddress::new(*HashValue::sha3_256_of(&i.to_le_bytes())))
+            .collect::<Vec<_>>();
+        let num_enabled = |features: &Features, flag| {
+            accounts
+                .iter()
+                .filter(|account| schedule.is_enabled_for(features, flag, account))
+                .count()
+        };
+
+        // Scheduled stages only take effect once applied on reconfiguration.
+        let mut features = Features { features: vec![] };
+        assert_eq!(schedule.rollout_percentage(&features, FeatureFlag::BN254_STRUCTURES), 0);
+        assert_eq!(num_enabled(&features, FeatureFlag::BN254_STRUCTURES), 0);
+
+        // Partial rollouts cover roughly their percentage of the accounts.
+        assert_eq!(
+            schedule.rollout_percentage(&features, FeatureFlag::BLS12_381_STRUCTURES),
+            20
+        );
+        let num_bls_enabled = num_enabled(&features, FeatureFlag::BLS12_381_STRUCTURES);
+        assert!((100..300).contains(&num_bls_enabled));
+
+        // Flags enabled on-chain are enabled for everyone.
+        features.enable(FeatureFlag::BLS12_381_STRUCTURES);
+        assert_eq!(
+            num_enabled(&features, FeatureFlag::BLS12_381_STRUCTURES),
+            accounts.len()
+        );
+    }
+
     #[test]
     fn test_features_into_flag_vec() {
         let mut features = Features { features: vec![] };
@@ -2,7 +2,10 @@
 // Parts of the project are originally copyright © Meta Platforms, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{block_info::Round, on_chain_config::OnChainConfig};
+use crate::{
+    block_info::Round,
+    on_chain_config::{bcs_from_bytes_lenient, OnChainConfig},
+};
 use anyhow::{format_err, Result};
 use move_core_types::account_address::AccountAddress;
 use serde::{Deserialize, Serialize};
@@ -411,6 +414,11 @@ impl OnChainConfig for OnChainConsensusConfig {
         bcs::from_bytes(&raw_bytes)
             .map_err(|e| format_err!("[on-chain config] Failed to deserialize into config: {}", e))
     }
+
+    fn deserialize_into_config_lenient(bytes: &[u8]) -> Result<Self> {
+        let raw_bytes: Vec<u8> = bcs::from_bytes(bytes)?;
+        bcs_from_bytes_lenient(&raw_bytes)
+    }
 }
 
 #[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
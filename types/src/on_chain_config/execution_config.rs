@@ -2,7 +2,8 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    block_executor::config::BlockExecutorConfigFromOnchain, on_chain_config::OnChainConfig,
+    block_executor::config::BlockExecutorConfigFromOnchain,
+    on_chain_config::{bcs_from_bytes_lenient, OnChainConfig},
 };
 use anyhow::{format_err, Result};
 use serde::{Deserialize, Serialize};
@@ -21,6 +22,7 @@ pub enum OnChainExecutionConfig {
     V5(ExecutionConfigV5),
     V6(ExecutionConfigV6),
     V7(ExecutionConfigV7),
+    V8(ExecutionConfigV8),
 }
 
 /// The public interface that exposes all values with safe fallback.
@@ -36,6 +38,7 @@ impl OnChainExecutionConfig {
             OnChainExecutionConfig::V5(config) => config.transaction_shuffler_type.clone(),
             OnChainExecutionConfig::V6(config) => config.transaction_shuffler_type.clone(),
             OnChainExecutionConfig::V7(config) => config.transaction_shuffler_type.clone(),
+            OnChainExecutionConfig::V8(config) => config.transaction_shuffler_type.clone(),
         }
     }
 
@@ -54,6 +57,7 @@ impl OnChainExecutionConfig {
             OnChainExecutionConfig::V5(config) => config.block_gas_limit_type.clone(),
             OnChainExecutionConfig::V6(config) => config.block_gas_limit_type.clone(),
             OnChainExecutionConfig::V7(config) => config.block_gas_limit_type.clone(),
+            OnChainExecutionConfig::V8(config) => config.block_gas_limit_type.clone(),
         }
     }
 
@@ -67,6 +71,7 @@ impl OnChainExecutionConfig {
             OnChainExecutionConfig::V5(config) => config.enable_per_block_gas_limit,
             OnChainExecutionConfig::V6(config) => config.enable_per_block_gas_limit,
             OnChainExecutionConfig::V7(config) => config.enable_per_block_gas_limit,
+            OnChainExecutionConfig::V8(config) => config.enable_per_block_gas_limit,
         }
     }
 
@@ -80,6 +85,7 @@ impl OnChainExecutionConfig {
             | OnChainExecutionConfig::V5(_) => None,
             OnChainExecutionConfig::V6(config) => Some(config.gas_price_to_burn),
             OnChainExecutionConfig::V7(config) => Some(config.gas_price_to_burn),
+            OnChainExecutionConfig::V8(config) => Some(config.gas_price_to_burn),
         }
     }
 
@@ -93,6 +99,23 @@ impl OnChainExecutionConfig {
             | OnChainExecutionConfig::V5(_)
             | OnChainExecutionConfig::V6(_) => 0,
             OnChainExecutionConfig::V7(config) => config.persisted_auxiliary_info_version,
+            OnChainExecutionConfig::V8(config) => config.persisted_auxiliary_info_version,
+        }
+    }
+
+    /// Whether the transactions a block defers (i.e., doesn't execute, e.g., because its block gas
+    /// limit is reached) are carried over by proposers into the payload of their next proposal.
+    pub fn carry_over_deferred_txns(&self) -> bool {
+        match self {
+            OnChainExecutionConfig::Missing
+            | OnChainExecutionConfig::V1(_)
+            | OnChainExecutionConfig::V2(_)
+            | OnChainExecutionConfig::V3(_)
+            | OnChainExecutionConfig::V4(_)
+            | OnChainExecutionConfig::V5(_)
+            | OnChainExecutionConfig::V6(_)
+            | OnChainExecutionConfig::V7(_) => false,
+            OnChainExecutionConfig::V8(config) => config.carry_over_deferred_txns,
         }
     }
 
@@ -116,6 +139,7 @@ impl OnChainExecutionConfig {
             OnChainExecutionConfig::V5(config) => config.transaction_deduper_type.clone(),
             OnChainExecutionConfig::V6(config) => config.transaction_deduper_type.clone(),
             OnChainExecutionConfig::V7(config) => config.transaction_deduper_type.clone(),
+            OnChainExecutionConfig::V8(config) => config.transaction_deduper_type.clone(),
         }
     }
 
@@ -171,6 +195,11 @@ impl OnChainConfig for OnChainExecutionConfig {
         bcs::from_bytes(&raw_bytes)
             .map_err(|e| format_err!("[on-chain config] Failed to deserialize into config: {}", e))
     }
+
+    fn deserialize_into_config_lenient(bytes: &[u8]) -> Result<Self> {
+        let raw_bytes: Vec<u8> = bcs::from_bytes(bytes)?;
+        bcs_from_bytes_lenient(&raw_bytes)
+    }
 }
 
 #[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
@@ -225,6 +254,17 @@ pub struct ExecutionConfigV7 {
     pub persisted_auxiliary_info_version: u8,
 }
 
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
+pub struct ExecutionConfigV8 {
+    pub transaction_shuffler_type: TransactionShufflerType,
+    pub block_gas_limit_type: BlockGasLimitType,
+    pub enable_per_block_gas_limit: bool,
+    pub transaction_deduper_type: TransactionDeduperType,
+    pub gas_price_to_burn: u64,
+    pub persisted_auxiliary_info_version: u8,
+    pub carry_over_deferred_txns: bool,
+}
+
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
 #[serde(rename_all = "snake_case")] // cannot use tag = "type" as nested enums cannot work, and bcs doesn't support it
 pub enum TransactionShufflerType {
@@ -310,6 +350,26 @@ pub enum BlockGasLimitType {
         /// NOTE: Currently not supported.
         add_block_limit_outcome_onchain: bool,
     },
+    /// Same as ComplexLimitV1, but additionally provides separate budgets for the execution gas
+    /// and the IO gas used by the block (without multipliers or conflict penalties). The block is
+    /// ended as soon as any of its limits (effective gas, execution gas, IO gas or output size)
+    /// is reached.
+    ComplexLimitV2 {
+        effective_block_gas_limit: u64,
+        execution_gas_effective_multiplier: u64,
+        io_gas_effective_multiplier: u64,
+        conflict_penalty_window: u32,
+        use_granular_resource_group_conflicts: bool,
+        use_module_publishing_block_conflict: bool,
+        block_output_limit: Option<u64>,
+        include_user_txn_size_in_block_output: bool,
+        add_block_limit_outcome_onchain: bool,
+
+        /// Block limit on the total execution gas used by the committed txns.
+        block_execution_gas_limit: Option<u64>,
+        /// Block limit on the total IO gas used by the committed txns.
+        block_io_gas_limit: Option<u64>,
+    },
 }
 
 impl BlockGasLimitType {
@@ -320,6 +380,10 @@ impl BlockGasLimitType {
             BlockGasLimitType::ComplexLimitV1 {
                 effective_block_gas_limit,
                 ..
+            }
+            | BlockGasLimitType::ComplexLimitV2 {
+                effective_block_gas_limit,
+                ..
             } => Some(*effective_block_gas_limit),
         }
     }
@@ -331,6 +395,10 @@ impl BlockGasLimitType {
             BlockGasLimitType::ComplexLimitV1 {
                 execution_gas_effective_multiplier,
                 ..
+            }
+            | BlockGasLimitType::ComplexLimitV2 {
+                execution_gas_effective_multiplier,
+                ..
             } => *execution_gas_effective_multiplier,
         }
     }
@@ -342,6 +410,10 @@ impl BlockGasLimitType {
             BlockGasLimitType::ComplexLimitV1 {
                 io_gas_effective_multiplier,
                 ..
+            }
+            | BlockGasLimitType::ComplexLimitV2 {
+                io_gas_effective_multiplier,
+                ..
             } => *io_gas_effective_multiplier,
         }
     }
@@ -352,6 +424,9 @@ impl BlockGasLimitType {
             BlockGasLimitType::Limit(_) => None,
             BlockGasLimitType::ComplexLimitV1 {
                 block_output_limit, ..
+            }
+            | BlockGasLimitType::ComplexLimitV2 {
+                block_output_limit, ..
             } => *block_output_limit,
         }
     }
@@ -363,6 +438,10 @@ impl BlockGasLimitType {
             BlockGasLimitType::ComplexLimitV1 {
                 conflict_penalty_window,
                 ..
+            }
+            | BlockGasLimitType::ComplexLimitV2 {
+                conflict_penalty_window,
+                ..
             } => {
                 if *conflict_penalty_window > 1 {
                     Some(*conflict_penalty_window)
@@ -380,6 +459,10 @@ impl BlockGasLimitType {
             BlockGasLimitType::ComplexLimitV1 {
                 use_module_publishing_block_conflict,
                 ..
+            }
+            | BlockGasLimitType::ComplexLimitV2 {
+                use_module_publishing_block_conflict,
+                ..
             } => *use_module_publishing_block_conflict,
         }
     }
@@ -391,6 +474,10 @@ impl BlockGasLimitType {
             BlockGasLimitType::ComplexLimitV1 {
                 include_user_txn_size_in_block_output,
                 ..
+            }
+            | BlockGasLimitType::ComplexLimitV2 {
+                include_user_txn_size_in_block_output,
+                ..
             } => *include_user_txn_size_in_block_output,
         }
     }
@@ -402,6 +489,10 @@ impl BlockGasLimitType {
             BlockGasLimitType::ComplexLimitV1 {
                 add_block_limit_outcome_onchain,
                 ..
+            }
+            | BlockGasLimitType::ComplexLimitV2 {
+                add_block_limit_outcome_onchain,
+                ..
             } => *add_block_limit_outcome_onchain,
         }
     }
@@ -413,9 +504,38 @@ impl BlockGasLimitType {
             BlockGasLimitType::ComplexLimitV1 {
                 use_granular_resource_group_conflicts,
                 ..
+            }
+            | BlockGasLimitType::ComplexLimitV2 {
+                use_granular_resource_group_conflicts,
+                ..
             } => *use_granular_resource_group_conflicts,
         }
     }
+
+    /// The limit on the execution gas used by the block, if any.
+    pub fn block_execution_gas_limit(&self) -> Option<u64> {
+        match self {
+            BlockGasLimitType::NoLimit
+            | BlockGasLimitType::Limit(_)
+            | BlockGasLimitType::ComplexLimitV1 { .. } => None,
+            BlockGasLimitType::ComplexLimitV2 {
+                block_execution_gas_limit,
+                ..
+            } => *block_execution_gas_limit,
+        }
+    }
+
+    /// The limit on the IO gas used by the block, if any.
+    pub fn block_io_gas_limit(&self) -> Option<u64> {
+        match self {
+            BlockGasLimitType::NoLimit
+            | BlockGasLimitType::Limit(_)
+            | BlockGasLimitType::ComplexLimitV1 { .. } => None,
+            BlockGasLimitType::ComplexLimitV2 {
+                block_io_gas_limit, ..
+            } => *block_io_gas_limit,
+        }
+    }
 }
 
 #[cfg(test)]
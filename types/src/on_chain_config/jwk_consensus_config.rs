@@ -33,10 +33,53 @@ impl AsMoveAny for ConfigV1 {
     const MOVE_TYPE_NAME: &'static str = "0x1::jwk_consensus_config::ConfigV1";
 }
 
+/// Move type `0x1::jwk_consensus_config::OIDCProviderV2` in rust.
+/// See its doc in Move for more details.
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
+pub struct OIDCProviderV2 {
+    pub name: String,
+    pub config_url: String,
+    pub config_url_kind: u8,
+    pub fetch_interval_secs: u64,
+    pub default_alg: String,
+}
+
+impl OIDCProviderV2 {
+    pub const CONFIG_URL_KIND_JWKS: u8 = 1;
+    pub const CONFIG_URL_KIND_OPENID_CONFIGURATION: u8 = 0;
+    /// The fetch interval of providers configured without one, i.e. in `ConfigV1`.
+    pub const DEFAULT_FETCH_INTERVAL_SECS: u64 = 10;
+    /// Must match `MIN_FETCH_INTERVAL_SECS` in `jwk_consensus_config.move`.
+    pub const MIN_FETCH_INTERVAL_SECS: u64 = 5;
+}
+
+impl From<OIDCProvider> for OIDCProviderV2 {
+    fn from(provider: OIDCProvider) -> Self {
+        let OIDCProvider { name, config_url } = provider;
+        Self {
+            name,
+            config_url,
+            config_url_kind: Self::CONFIG_URL_KIND_OPENID_CONFIGURATION,
+            fetch_interval_secs: Self::DEFAULT_FETCH_INTERVAL_SECS,
+            default_alg: String::new(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
+pub struct ConfigV2 {
+    pub oidc_providers: Vec<OIDCProviderV2>,
+}
+
+impl AsMoveAny for ConfigV2 {
+    const MOVE_TYPE_NAME: &'static str = "0x1::jwk_consensus_config::ConfigV2";
+}
+
 #[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
 pub enum OnChainJWKConsensusConfig {
     Off,
     V1(ConfigV1),
+    V2(ConfigV2),
 }
 
 impl OnChainJWKConsensusConfig {
@@ -70,6 +113,7 @@ impl OnChainJWKConsensusConfig {
         match self {
             OnChainJWKConsensusConfig::Off => false,
             OnChainJWKConsensusConfig::V1 { .. } => true,
+            OnChainJWKConsensusConfig::V2 { .. } => true,
         }
     }
 
@@ -77,6 +121,29 @@ impl OnChainJWKConsensusConfig {
         match self {
             OnChainJWKConsensusConfig::Off => vec![],
             OnChainJWKConsensusConfig::V1(v1) => v1.oidc_providers.clone(),
+            OnChainJWKConsensusConfig::V2(v2) => v2
+                .oidc_providers
+                .iter()
+                .map(|provider| OIDCProvider {
+                    name: provider.name.clone(),
+                    config_url: provider.config_url.clone(),
+                })
+                .collect(),
+        }
+    }
+
+    /// The providers to watch along with how to fetch their JWKs, with `ConfigV1` providers
+    /// getting the defaults.
+    pub fn oidc_providers_v2_cloned(&self) -> Vec<OIDCProviderV2> {
+        match self {
+            OnChainJWKConsensusConfig::Off => vec![],
+            OnChainJWKConsensusConfig::V1(v1) => v1
+                .oidc_providers
+                .iter()
+                .cloned()
+                .map(OIDCProviderV2::from)
+                .collect(),
+            OnChainJWKConsensusConfig::V2(v2) => v2.oidc_providers.clone(),
         }
     }
 }
@@ -93,9 +160,18 @@ impl OnChainConfig for OnChainJWKConsensusConfig {
                 let config_v1 = Any::unpack::<ConfigV1>(ConfigV1::MOVE_TYPE_NAME, variant).map_err(|e|anyhow!("OnChainJWKConsensusConfig deserialization failed with ConfigV1 unpack error: {e}"))?;
                 Ok(OnChainJWKConsensusConfig::V1(config_v1))
             },
+            ConfigV2::MOVE_TYPE_NAME => {
+                let config_v2 = Any::unpack::<ConfigV2>(ConfigV2::MOVE_TYPE_NAME, variant).map_err(|e|anyhow!("OnChainJWKConsensusConfig deserialization failed with ConfigV2 unpack error: {e}"))?;
+                Ok(OnChainJWKConsensusConfig::V2(config_v2))
+            },
             _ => Err(anyhow!("unknown variant type")),
         }
     }
+
+    // Variants are identified by their Move type name, unknown ones can't be decoded leniently.
+    fn deserialize_into_config_lenient(bytes: &[u8]) -> anyhow::Result<Self> {
+        Self::deserialize_into_config(bytes)
+    }
 }
 
 impl AsMoveValue for OnChainJWKConsensusConfig {
@@ -103,6 +179,7 @@ impl AsMoveValue for OnChainJWKConsensusConfig {
         let packed_variant = match self {
             OnChainJWKConsensusConfig::Off => ConfigOff {}.as_move_any(),
             OnChainJWKConsensusConfig::V1(v1) => v1.as_move_any(),
+            OnChainJWKConsensusConfig::V2(v2) => v2.as_move_any(),
         };
         MoveValue::Struct(MoveStruct::Runtime(vec![packed_variant.as_move_value()]))
     }
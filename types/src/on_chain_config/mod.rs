@@ -31,6 +31,7 @@ mod gas_schedule;
 mod jwk_consensus_config;
 pub mod randomness_api_v0_config;
 mod randomness_config;
+mod registry;
 mod timed_features;
 mod timestamp;
 mod transaction_fee;
@@ -50,15 +51,18 @@ pub use self::{
     },
     execution_config::{
         BlockGasLimitType, ExecutionConfigV1, ExecutionConfigV2, ExecutionConfigV4,
-        ExecutionConfigV7, OnChainExecutionConfig, TransactionDeduperType, TransactionShufflerType,
+        ExecutionConfigV7, ExecutionConfigV8, OnChainExecutionConfig, TransactionDeduperType,
+        TransactionShufflerType,
     },
     gas_schedule::{DiffItem, GasSchedule, GasScheduleV2, StorageGasSchedule},
     jwk_consensus_config::{
-        ConfigV1 as JWKConsensusConfigV1, OIDCProvider, OnChainJWKConsensusConfig,
+        ConfigV1 as JWKConsensusConfigV1, ConfigV2 as JWKConsensusConfigV2, OIDCProvider,
+        OIDCProviderV2, OnChainJWKConsensusConfig,
     },
     randomness_config::{
         OnChainRandomnessConfig, RandomnessConfigMoveStruct, RandomnessConfigSeqNum,
     },
+    registry::{bcs_from_bytes_lenient, OnChainConfigRegistry, VersionedConfig},
     timed_features::{TimedFeatureFlag, TimedFeatureOverride, TimedFeatures, TimedFeaturesBuilder},
     timestamp::CurrentTimeMicroseconds,
     transaction_fee::TransactionFeeBurnCap,
@@ -67,7 +71,8 @@ pub use self::{
 
 /// To register an on-chain config in Rust:
 /// 1. Implement the `OnChainConfig` trait for the Rust representation of the config
-/// 2. Add the config's `ConfigID` to `ON_CHAIN_CONFIG_REGISTRY`
+/// 2. Register the type in `OnChainConfigRegistry::with_default_configs`, if it's read by the
+///    reconfiguration subscribers (e.g. consensus and mempool) on every epoch
 
 #[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
 pub struct ConfigID(&'static str, &'static str, &'static str);
@@ -173,6 +178,16 @@ pub trait OnChainConfig: Send + Sync + DeserializeOwned {
         Self::deserialize_default_impl(bytes)
     }
 
+    // Same as `deserialize_into_config`, but if that fails, falls back to ignoring trailing
+    // bytes, i.e. fields appended to the Move struct by a newer framework release. Only used by
+    // the `OnChainConfigRegistry`, everything else decodes strictly.
+    // Configs whose bytes are wrapped (e.g. in a `vector<u8>`) should override this to apply
+    // the fallback to the inner bytes only.
+    fn deserialize_into_config_lenient(bytes: &[u8]) -> Result<Self> {
+        Self::deserialize_into_config(bytes)
+            .or_else(|e| bcs_from_bytes_lenient::<Self>(bytes).map_err(|_| e))
+    }
+
     /// TODO: This does not work if `T`'s reflection on the Move side is using resource groups.
     fn fetch_config<T>(storage: &T) -> Option<Self>
     where
@@ -257,20 +272,24 @@ impl ConfigurationResource {
         &self.events
     }
 
-    #[cfg(feature = "fuzzing")]
-    pub fn bump_epoch_for_test(&self) -> Self {
-        let epoch = self.epoch + 1;
-        let last_reconfiguration_time = self.last_reconfiguration_time + 1;
+    /// Returns the configuration of the next epoch, for write sets that start a new epoch without
+    /// going through governance, e.g. the ones applied by the DB bootstrapper.
+    pub fn bump_epoch(&self, reconfiguration_time_micros: u64) -> Self {
         let mut events = self.events.clone();
         *events.count_mut() += 1;
 
         Self {
-            epoch,
-            last_reconfiguration_time,
+            epoch: self.epoch + 1,
+            last_reconfiguration_time: reconfiguration_time_micros,
             events,
         }
     }
 
+    #[cfg(feature = "fuzzing")]
+    pub fn bump_epoch_for_test(&self) -> Self {
+        self.bump_epoch(self.last_reconfiguration_time + 1)
+    }
+
     #[cfg(feature = "fuzzing")]
     pub fn set_last_reconfiguration_time_for_test(&mut self, last_reconfiguration_time: u64) {
         self.last_reconfiguration_time = last_reconfiguration_time;
@@ -0,0 +1,380 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    dkg::DKGState,
+    jwks::{ObservedJWKs, SupportedOIDCProviders},
+    on_chain_config::{
+        ApprovedExecutionHashes, AptosVersion, ConfigID, ConfigStorage, ConfigurationResource,
+        FeatureActivationSchedule, Features, GasScheduleV2, InMemoryOnChainConfig, OnChainConfig,
+        OnChainConfigProvider, OnChainConsensusConfig, OnChainExecutionConfig,
+        OnChainJWKConsensusConfig, RandomnessConfigMoveStruct, RandomnessConfigSeqNum,
+        StorageGasSchedule, ValidatorSet,
+    },
+    state_store::state_key::StateKey,
+    transaction::Version,
+};
+use anyhow::{format_err, Result};
+use aptos_infallible::{Mutex, RwLock};
+use bytes::Bytes;
+use serde::de::DeserializeOwned;
+use std::{
+    any::Any,
+    collections::HashMap,
+    fmt,
+    sync::{
+        mpsc::{channel, Receiver},
+        Arc,
+    },
+};
+
+/// BCS deserialization that tolerates trailing bytes, i.e. fields appended to the end of a
+/// Move struct by a newer framework release that this binary doesn't know about yet.
+///
+/// BCS is not self-describing, so we find the length of the known prefix by bisection: a prefix
+/// that is too short fails with `Eof`, one that is too long with `RemainingInput`.
+pub fn bcs_from_bytes_lenient<T: DeserializeOwned>(bytes: &[u8]) -> Result<T> {
+    let (mut low, mut high) = (0, bytes.len());
+    loop {
+        let len = low + (high - low) / 2;
+        match bcs::from_bytes::<T>(&bytes[..len]) {
+            Ok(value) => return Ok(value),
+            Err(bcs::Error::Eof) if len < high => low = len + 1,
+            Err(bcs::Error::RemainingInput) if len > low => high = len,
+            Err(e) => {
+                return Err(format_err!(
+                    "[on-chain config] Failed to deserialize into config: {}",
+                    e
+                ))
+            },
+        }
+    }
+}
+
+type AnyConfig = Arc<dyn Any + Send + Sync>;
+
+/// The latest value of a registered config, together with the version it was read at.
+pub struct VersionedConfig<T> {
+    pub version: Version,
+    pub bytes: Bytes,
+    pub config: Arc<T>,
+}
+
+impl<T> Clone for VersionedConfig<T> {
+    fn clone(&self) -> Self {
+        Self {
+            version: self.version,
+            bytes: self.bytes.clone(),
+            config: self.config.clone(),
+        }
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for VersionedConfig<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("VersionedConfig")
+            .field("version", &self.version)
+            .field("config", &self.config)
+            .finish()
+    }
+}
+
+/// Sends the (type erased) config to a subscriber, returning false once it has hung up.
+type Notifier = Box<dyn Fn(Version, &Bytes, &AnyConfig) -> bool + Send + Sync>;
+
+struct RegisteredConfig {
+    state_key: StateKey,
+    decode: fn(&[u8]) -> Result<AnyConfig>,
+    latest: RwLock<Option<(Version, Bytes, AnyConfig)>>,
+    subscribers: Mutex<Vec<Notifier>>,
+}
+
+impl RegisteredConfig {
+    fn new<T: OnChainConfig + 'static>() -> Result<Self> {
+        Ok(Self {
+            state_key: StateKey::on_chain_config::<T>()?,
+            decode: |bytes| Ok(Arc::new(T::deserialize_into_config_lenient(bytes)?)),
+            latest: RwLock::new(None),
+            subscribers: Mutex::new(vec![]),
+        })
+    }
+}
+
+/// Typed registry of the on-chain configs a component cares about.
+///
+/// Configs are registered once by type, which maps their state key to the Rust type used to
+/// decode them. Decoding is lenient (see [`OnChainConfig::deserialize_into_config_lenient`]), so
+/// that a node keeps working when a framework upgrade appends fields to a config. The registry
+/// keeps the latest decoded value of every config, and subscribers are notified whenever a new
+/// value with different bytes is observed.
+#[derive(Clone, Default)]
+pub struct OnChainConfigRegistry {
+    configs: Arc<HashMap<ConfigID, RegisteredConfig>>,
+    config_ids: Arc<HashMap<StateKey, ConfigID>>,
+}
+
+impl OnChainConfigRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A registry with the configs read by consensus, execution, mempool and the other
+    /// reconfiguration subscribers on every epoch.
+    pub fn with_default_configs() -> Self {
+        Self::new()
+            .register::<ValidatorSet>()
+            .register::<ConfigurationResource>()
+            .register::<AptosVersion>()
+            .register::<Features>()
+            .register::<FeatureActivationSchedule>()
+            .register::<GasScheduleV2>()
+            .register::<StorageGasSchedule>()
+            .register::<OnChainConsensusConfig>()
+            .register::<OnChainExecutionConfig>()
+            .register::<OnChainJWKConsensusConfig>()
+            .register::<RandomnessConfigMoveStruct>()
+            .register::<RandomnessConfigSeqNum>()
+            .register::<ApprovedExecutionHashes>()
+            .register::<DKGState>()
+            .register::<ObservedJWKs>()
+            .register::<SupportedOIDCProviders>()
+    }
+
+    /// Registers `T`. Must be called before the registry is cloned or shared.
+    pub fn register<T: OnChainConfig + 'static>(mut self) -> Self {
+        let config = RegisteredConfig::new::<T>()
+            .unwrap_or_else(|e| panic!("Invalid state key for {}: {}", T::CONFIG_ID, e));
+        Arc::get_mut(&mut self.config_ids)
+            .expect("Configs must be registered before the registry is shared")
+            .insert(config.state_key.clone(), T::CONFIG_ID);
+        Arc::get_mut(&mut self.configs)
+            .expect("Configs must be registered before the registry is shared")
+            .insert(T::CONFIG_ID, config);
+        self
+    }
+
+    pub fn is_registered<T: OnChainConfig>(&self) -> bool {
+        self.configs.contains_key(&T::CONFIG_ID)
+    }
+
+    /// Returns the config stored under `state_key`, if any, e.g. to filter a write set.
+    pub fn config_id(&self, state_key: &StateKey) -> Option<ConfigID> {
+        self.config_ids.get(state_key).copied()
+    }
+
+    pub fn state_keys(&self) -> impl Iterator<Item = &StateKey> {
+        self.config_ids.keys()
+    }
+
+    /// Returns the latest observed value of `T`, if it's registered and has been observed.
+    pub fn get<T: OnChainConfig + 'static>(&self) -> Option<VersionedConfig<T>> {
+        let (version, bytes, config) = self.configs.get(&T::CONFIG_ID)?.latest.read().clone()?;
+        Some(VersionedConfig {
+            version,
+            bytes,
+            config: config.downcast::<T>().ok()?,
+        })
+    }
+
+    /// Records the bytes stored under `state_key` at `version`. Returns true if they changed
+    /// the registered config, in which case its subscribers are notified. Returns false for
+    /// unregistered keys and stale versions.
+    pub fn update(&self, version: Version, state_key: &StateKey, bytes: Bytes) -> Result<bool> {
+        let Some(config) = self
+            .config_id(state_key)
+            .and_then(|config_id| self.configs.get(&config_id))
+        else {
+            return Ok(false);
+        };
+
+        let mut latest = config.latest.write();
+        if let Some((latest_version, latest_bytes, _)) = latest.as_ref() {
+            if *latest_version > version || *latest_bytes == bytes {
+                return Ok(false);
+            }
+        }
+        let decoded = (config.decode)(&bytes)?;
+        config
+            .subscribers
+            .lock()
+            .retain(|notify| notify(version, &bytes, &decoded));
+        *latest = Some((version, bytes, decoded));
+        Ok(true)
+    }
+
+    /// Reads all registered configs from `storage` (as of `version`), and returns the ones
+    /// that changed. Configs that are missing or fail to decode are skipped.
+    pub fn refresh<S: ConfigStorage + ?Sized>(
+        &self,
+        version: Version,
+        storage: &S,
+    ) -> Vec<ConfigID> {
+        self.configs
+            .iter()
+            .filter(|(_, config)| {
+                storage
+                    .fetch_config_bytes(&config.state_key)
+                    .is_some_and(|bytes| {
+                        matches!(self.update(version, &config.state_key, bytes), Ok(true))
+                    })
+            })
+            .map(|(config_id, _)| *config_id)
+            .collect()
+    }
+
+    /// Reads the bytes of all registered configs from `storage`, e.g. the configs of a new epoch.
+    /// Unlike [`refresh`](Self::refresh), this doesn't record them, and they are decoded strictly
+    /// by their readers.
+    pub fn read_configs<S: ConfigStorage + ?Sized>(&self, storage: &S) -> InMemoryOnChainConfig {
+        InMemoryOnChainConfig::new(
+            self.configs
+                .iter()
+                .filter_map(|(config_id, config)| {
+                    let bytes = storage.fetch_config_bytes(&config.state_key)?;
+                    Some((*config_id, bytes.to_vec()))
+                })
+                .collect(),
+        )
+    }
+
+    /// Subscribes to changes of `T`. The current value, if any, is sent right away.
+    pub fn subscribe<T: OnChainConfig + 'static>(&self) -> Result<Receiver<VersionedConfig<T>>> {
+        let config = self
+            .configs
+            .get(&T::CONFIG_ID)
+            .ok_or_else(|| format_err!("{} is not registered", T::CONFIG_ID))?;
+
+        let (sender, receiver) = channel();
+        let notify: Notifier = Box::new(move |version, bytes, decoded| {
+            let Ok(config) = decoded.clone().downcast::<T>() else {
+                return false;
+            };
+            sender
+                .send(VersionedConfig {
+                    version,
+                    bytes: bytes.clone(),
+                    config,
+                })
+                .is_ok()
+        });
+
+        // Hold the latest value while adding the subscriber, so no update is missed.
+        let latest = config.latest.read();
+        if let Some((version, bytes, decoded)) = latest.as_ref() {
+            notify(*version, bytes, decoded);
+        }
+        config.subscribers.lock().push(notify);
+        Ok(receiver)
+    }
+}
+
+impl fmt::Debug for OnChainConfigRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.configs.keys()).finish()
+    }
+}
+
+impl OnChainConfigProvider for OnChainConfigRegistry {
+    fn get<T: OnChainConfig>(&self) -> Result<T> {
+        let config = self
+            .configs
+            .get(&T::CONFIG_ID)
+            .ok_or_else(|| format_err!("{} is not registered", T::CONFIG_ID))?;
+        let latest = config.latest.read();
+        let (_, bytes, _) = latest
+            .as_ref()
+            .ok_or_else(|| format_err!("{} has not been observed yet", T::CONFIG_ID))?;
+        T::deserialize_into_config_lenient(bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        chain_id::ChainId,
+        state_store::{state_value::StateValue, MockStateView},
+    };
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, Deserialize, PartialEq, Serialize)]
+    struct ConfigV1 {
+        a: u64,
+        b: Vec<u8>,
+    }
+
+    #[derive(Debug, Deserialize, PartialEq, Serialize)]
+    struct ConfigV2 {
+        a: u64,
+        b: Vec<u8>,
+        c: String,
+    }
+
+    #[test]
+    fn test_lenient_decoding_ignores_appended_fields() {
+        let v2 = ConfigV2 {
+            a: 7,
+            b: vec![1, 2, 3],
+            c: "new field".to_string(),
+        };
+        let bytes = bcs::to_bytes(&v2).unwrap();
+
+        assert!(bcs::from_bytes::<ConfigV1>(&bytes).is_err());
+        assert_eq!(
+            bcs_from_bytes_lenient::<ConfigV1>(&bytes).unwrap(),
+            ConfigV1 {
+                a: 7,
+                b: vec![1, 2, 3],
+            }
+        );
+        assert_eq!(bcs_from_bytes_lenient::<ConfigV2>(&bytes).unwrap(), v2);
+
+        // Truncated bytes are still rejected.
+        assert!(bcs_from_bytes_lenient::<ConfigV1>(&bytes[..5]).is_err());
+    }
+
+    #[test]
+    fn test_update_and_subscribe() {
+        let registry = OnChainConfigRegistry::new().register::<ChainId>();
+        let state_key = StateKey::on_chain_config::<ChainId>().unwrap();
+        assert_eq!(registry.config_id(&state_key), Some(ChainId::CONFIG_ID));
+
+        let receiver = registry.subscribe::<ChainId>().unwrap();
+        let bytes = Bytes::from(bcs::to_bytes(&ChainId::test()).unwrap());
+        assert!(registry.update(10, &state_key, bytes.clone()).unwrap());
+        // Same bytes, or older versions, are not changes.
+        assert!(!registry.update(11, &state_key, bytes).unwrap());
+        let older = Bytes::from(bcs::to_bytes(&ChainId::mainnet()).unwrap());
+        assert!(!registry.update(5, &state_key, older).unwrap());
+
+        let update = receiver.try_recv().unwrap();
+        assert_eq!(update.version, 10);
+        assert_eq!(*update.config, ChainId::test());
+        assert!(receiver.try_recv().is_err());
+
+        assert_eq!(*registry.get::<ChainId>().unwrap().config, ChainId::test());
+        assert_eq!(
+            OnChainConfigProvider::get::<ChainId>(&registry).unwrap(),
+            ChainId::test()
+        );
+    }
+
+    #[test]
+    fn test_read_configs() {
+        let registry = OnChainConfigRegistry::new()
+            .register::<ChainId>()
+            .register::<Features>();
+        let state_view = MockStateView::new(HashMap::from([(
+            StateKey::on_chain_config::<ChainId>().unwrap(),
+            StateValue::new_legacy(bcs::to_bytes(&ChainId::test()).unwrap().into()),
+        )]));
+
+        let configs = registry.read_configs(&state_view);
+        assert_eq!(configs.get::<ChainId>().unwrap(), ChainId::test());
+        // Missing and unregistered configs are not in the payload.
+        assert!(configs.get::<Features>().is_err());
+        assert!(configs.get::<ValidatorSet>().is_err());
+        // Reading doesn't record the configs.
+        assert!(registry.get::<ChainId>().is_none());
+    }
+}
@@ -1,7 +1,11 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{account_address::AccountAddress, event::EventHandle};
+use crate::{account_address::AccountAddress, event::EventHandle, network_address::NetworkAddress};
+use move_core_types::{
+    ident_str, identifier::IdentStr, language_storage::TypeTag, move_resource::MoveStructType,
+};
+use once_cell::sync::Lazy;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -78,6 +82,34 @@ pub struct UpdateNetworkAndFullnodeAddressesEvent {
     pub new_fullnode_addresses: Vec<u8>,
 }
 
+/// The module event emitted in place of `UpdateNetworkAndFullnodeAddressesEvent`
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UpdateNetworkAndFullnodeAddresses {
+    pub pool_address: AccountAddress,
+    pub old_network_addresses: Vec<u8>,
+    pub new_network_addresses: Vec<u8>,
+    pub old_fullnode_addresses: Vec<u8>,
+    pub new_fullnode_addresses: Vec<u8>,
+}
+
+impl UpdateNetworkAndFullnodeAddresses {
+    pub fn new_fullnode_network_addresses(&self) -> Result<Vec<NetworkAddress>, bcs::Error> {
+        bcs::from_bytes(&self.new_fullnode_addresses)
+    }
+
+    pub fn new_validator_network_addresses(&self) -> Result<Vec<NetworkAddress>, bcs::Error> {
+        bcs::from_bytes(&self.new_network_addresses)
+    }
+}
+
+impl MoveStructType for UpdateNetworkAndFullnodeAddresses {
+    const MODULE_NAME: &'static IdentStr = ident_str!("stake");
+    const STRUCT_NAME: &'static IdentStr = ident_str!("UpdateNetworkAndFullnodeAddresses");
+}
+
+pub static UPDATE_NETWORK_AND_FULLNODE_ADDRESSES_MOVE_TYPE_TAG: Lazy<TypeTag> =
+    Lazy::new(|| TypeTag::Struct(Box::new(UpdateNetworkAndFullnodeAddresses::struct_tag())));
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct IncreaseLockupEvent {
     pub pool_address: AccountAddress,
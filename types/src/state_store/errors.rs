@@ -2,12 +2,23 @@
 // Parts of the project are originally copyright © Meta Platforms, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use move_core_types::vm_status::StatusCode;
 use thiserror::Error;
 
 #[derive(Debug, Error)]
 pub enum StateViewError {
+    /// The requested data is not in storage, e.g. because it has been pruned.
     #[error("{0} not found.")]
     NotFound(String),
+    /// The data in storage is inconsistent, can't be decoded or fails verification.
+    #[error("Corrupted data: {0}")]
+    Corrupted(String),
+    /// The storage backend failed to serve the request, e.g. due to an IO error.
+    #[error("Storage backend unavailable: {0}")]
+    BackendUnavailable(String),
+    /// The request didn't complete in time, e.g. when reading state from a remote service.
+    #[error("Timed out: {0}")]
+    Timeout(String),
     /// Other non-classified error.
     #[error("{0}")]
     Other(String),
@@ -15,8 +26,58 @@ pub enum StateViewError {
     BcsError(#[from] bcs::Error),
 }
 
+impl StateViewError {
+    /// Returns true if the same read may succeed when retried, i.e. the error is not caused by
+    /// the data itself.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            Self::BackendUnavailable(_) | Self::Timeout(_) => true,
+            Self::NotFound(_) | Self::Corrupted(_) | Self::Other(_) | Self::BcsError(_) => false,
+        }
+    }
+
+    /// The status to discard transactions with when their execution runs into this error.
+    pub fn status_code(&self) -> StatusCode {
+        match self {
+            Self::NotFound(_) => StatusCode::STORAGE_NOT_FOUND,
+            Self::Corrupted(_) | Self::BcsError(_) => StatusCode::STORAGE_CORRUPTED,
+            Self::BackendUnavailable(_) => StatusCode::STORAGE_UNAVAILABLE,
+            Self::Timeout(_) => StatusCode::STORAGE_TIMEOUT,
+            Self::Other(_) => StatusCode::STORAGE_ERROR,
+        }
+    }
+}
+
 impl From<anyhow::Error> for StateViewError {
     fn from(error: anyhow::Error) -> Self {
         Self::Other(format!("{}", error))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classification() {
+        let retryable = [
+            StateViewError::BackendUnavailable("io".to_string()),
+            StateViewError::Timeout("remote".to_string()),
+        ];
+        let non_retryable = [
+            StateViewError::NotFound("key".to_string()),
+            StateViewError::Corrupted("proof".to_string()),
+            StateViewError::Other("other".to_string()),
+        ];
+        assert!(retryable.iter().all(StateViewError::is_retryable));
+        assert!(!non_retryable.iter().any(StateViewError::is_retryable));
+
+        // Every class is discarded with its own status.
+        let status_codes = retryable
+            .iter()
+            .chain(non_retryable.iter())
+            .map(StateViewError::status_code)
+            .collect::<std::collections::HashSet<_>>();
+        assert_eq!(status_codes.len(), 5);
+    }
+}
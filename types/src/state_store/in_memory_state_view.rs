@@ -0,0 +1,188 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    state_store::{
+        errors::StateViewError,
+        state_key::{prefix::StateKeyPrefix, StateKey},
+        state_storage_usage::StateStorageUsage,
+        state_value::StateValue,
+        StateView, StateViewResult, TStateView,
+    },
+    transaction::Version,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// A state view holding all of its state in memory. It can be serialized, so that e.g. the inputs
+/// of a failing execution can be captured from a node and replayed elsewhere.
+///
+/// Keys that are not in the view are considered non-existent.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct InMemoryStateView {
+    state_data: BTreeMap<StateKey, StateValue>,
+    usage: StateStorageUsage,
+    next_version: Version,
+}
+
+impl InMemoryStateView {
+    pub fn new(
+        state_data: BTreeMap<StateKey, StateValue>,
+        usage: StateStorageUsage,
+        next_version: Version,
+    ) -> Self {
+        Self {
+            state_data,
+            usage,
+            next_version,
+        }
+    }
+
+    pub fn state_data(&self) -> &BTreeMap<StateKey, StateValue> {
+        &self.state_data
+    }
+
+    pub fn into_state_data(self) -> BTreeMap<StateKey, StateValue> {
+        self.state_data
+    }
+}
+
+impl TStateView for InMemoryStateView {
+    type Key = StateKey;
+
+    fn get_state_value(&self, state_key: &StateKey) -> StateViewResult<Option<StateValue>> {
+        Ok(self.state_data.get(state_key).cloned())
+    }
+
+    fn get_usage(&self) -> StateViewResult<StateStorageUsage> {
+        Ok(self.usage)
+    }
+
+    fn next_version(&self) -> Version {
+        self.next_version
+    }
+}
+
+/// The part of the state to materialize in an [`InMemoryStateView`].
+#[derive(Clone, Debug)]
+pub enum StateKeySelection {
+    /// Explicit list of keys, the ones that don't exist are left out.
+    Keys(Vec<StateKey>),
+    /// All keys with the prefix, e.g. all resources of an account.
+    Prefix(StateKeyPrefix),
+}
+
+/// State views that can materialize part of their state into an [`InMemoryStateView`].
+pub trait AsInMemoryStateView: StateView {
+    /// Returns all state values with keys under the prefix. Fails if there are more than
+    /// `max_items` of them, rather than returning a partial result.
+    fn get_prefixed_state_values(
+        &self,
+        prefix: &StateKeyPrefix,
+        max_items: usize,
+    ) -> StateViewResult<Vec<(StateKey, StateValue)>>;
+
+    /// Materializes the selected state, along with the usage and version of this view. Fails if
+    /// the selection has more than `max_items` items.
+    fn as_in_memory_state_view(
+        &self,
+        selection: &StateKeySelection,
+        max_items: usize,
+    ) -> StateViewResult<InMemoryStateView> {
+        let state_data = match selection {
+            StateKeySelection::Keys(keys) => {
+                if keys.len() > max_items {
+                    return Err(too_many_items(max_items));
+                }
+                keys.iter()
+                    .filter_map(|key| {
+                        self.get_state_value(key)
+                            .transpose()
+                            .map(|value| value.map(|value| (key.clone(), value)))
+                    })
+                    .collect::<StateViewResult<_>>()?
+            },
+            StateKeySelection::Prefix(prefix) => self
+                .get_prefixed_state_values(prefix, max_items)?
+                .into_iter()
+                .collect(),
+        };
+
+        Ok(InMemoryStateView::new(
+            state_data,
+            self.get_usage()?,
+            self.next_version(),
+        ))
+    }
+}
+
+impl AsInMemoryStateView for InMemoryStateView {
+    fn get_prefixed_state_values(
+        &self,
+        prefix: &StateKeyPrefix,
+        max_items: usize,
+    ) -> StateViewResult<Vec<(StateKey, StateValue)>> {
+        let mut values = Vec::new();
+        for (key, value) in &self.state_data {
+            if prefix.is_prefix(key)? {
+                if values.len() == max_items {
+                    return Err(too_many_items(max_items));
+                }
+                values.push((key.clone(), value.clone()));
+            }
+        }
+        Ok(values)
+    }
+}
+
+/// Returns the error for selections with more than `max_items` items.
+pub fn too_many_items(max_items: usize) -> StateViewError {
+    StateViewError::Other(format!(
+        "The selected state has more than {} items.",
+        max_items
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        account_config::{AccountResource, CoinStoreResource},
+        AptosCoinType,
+    };
+    use move_core_types::account_address::AccountAddress;
+    use std::collections::BTreeSet;
+
+    #[test]
+    fn test_as_in_memory_state_view() {
+        let alice = AccountAddress::random();
+        let bob = AccountAddress::random();
+        let keys = [
+            StateKey::raw(&[1]),
+            StateKey::resource_typed::<AccountResource>(&alice).unwrap(),
+            StateKey::resource_typed::<CoinStoreResource<AptosCoinType>>(&alice).unwrap(),
+            StateKey::resource_typed::<AccountResource>(&bob).unwrap(),
+        ];
+        let state_data = keys
+            .iter()
+            .map(|key| (key.clone(), StateValue::new_legacy(vec![1, 2, 3].into())))
+            .collect();
+        let view = InMemoryStateView::new(state_data, StateStorageUsage::new(4, 12), 10);
+
+        // Keys that don't exist are left out.
+        let selection = StateKeySelection::Keys(vec![keys[0].clone(), StateKey::raw(&[5])]);
+        let snapshot = view.as_in_memory_state_view(&selection, 2).unwrap();
+        assert!(snapshot.state_data().keys().eq([&keys[0]]));
+        assert_eq!(snapshot.get_usage().unwrap(), view.get_usage().unwrap());
+        assert_eq!(snapshot.next_version(), view.next_version());
+        assert!(view.as_in_memory_state_view(&selection, 1).is_err());
+
+        let selection = StateKeySelection::Prefix(StateKeyPrefix::from(alice));
+        let snapshot = view.as_in_memory_state_view(&selection, 2).unwrap();
+        assert_eq!(
+            snapshot.state_data().keys().collect::<BTreeSet<_>>(),
+            keys[1..3].iter().collect()
+        );
+        assert!(view.as_in_memory_state_view(&selection, 1).is_err());
+    }
+}
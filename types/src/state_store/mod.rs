@@ -18,6 +18,7 @@ use std::ops::Deref;
 
 pub mod errors;
 pub mod hot_state;
+pub mod in_memory_state_view;
 pub mod state_key;
 pub mod state_slot;
 pub mod state_storage_usage;
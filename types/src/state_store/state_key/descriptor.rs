@@ -0,0 +1,300 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Machine readable descriptions of state keys, and a canonical human readable string form,
+//! shared by explorers, debuggers and other tooling.
+//!
+//! The human readable form is `<kind>:<body>`:
+//! * `module:0x1::coin`
+//! * `resource:0x1/0x1::account::Account`
+//! * `resource_group:0xa11ce/0x1::object::ObjectGroup`
+//! * `table_item:0x1b2c/0x0102ff` (table handle / hex encoded key)
+//! * `raw:0x0102ff`
+
+use crate::{
+    access_path::Path,
+    state_store::{
+        state_key::{inner::StateKeyInner, StateKey},
+        table::TableHandle,
+    },
+};
+use anyhow::{anyhow, bail, Result};
+use move_core_types::{
+    account_address::AccountAddress,
+    identifier::Identifier,
+    language_storage::{ModuleId, StructTag},
+};
+use serde::{Deserialize, Serialize};
+use std::{fmt, str::FromStr};
+
+/// The different forms a state key can take.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StateKeyKind {
+    Module,
+    Resource,
+    ResourceGroup,
+    TableItem,
+    Raw,
+}
+
+impl StateKeyKind {
+    pub const ALL: [StateKeyKind; 5] = [
+        StateKeyKind::Module,
+        StateKeyKind::Resource,
+        StateKeyKind::ResourceGroup,
+        StateKeyKind::TableItem,
+        StateKeyKind::Raw,
+    ];
+
+    /// The prefix used in the human readable form.
+    pub fn name(&self) -> &'static str {
+        match self {
+            StateKeyKind::Module => "module",
+            StateKeyKind::Resource => "resource",
+            StateKeyKind::ResourceGroup => "resource_group",
+            StateKeyKind::TableItem => "table_item",
+            StateKeyKind::Raw => "raw",
+        }
+    }
+
+    /// Format of the human readable form, for help texts.
+    pub fn syntax(&self) -> &'static str {
+        match self {
+            StateKeyKind::Module => "module:<address>::<module_name>",
+            StateKeyKind::Resource => "resource:<address>/<struct_tag>",
+            StateKeyKind::ResourceGroup => "resource_group:<address>/<struct_tag>",
+            StateKeyKind::TableItem => "table_item:<table_handle>/<hex_key>",
+            StateKeyKind::Raw => "raw:<hex_bytes>",
+        }
+    }
+}
+
+impl fmt::Display for StateKeyKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl FromStr for StateKeyKind {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|kind| kind.name() == s)
+            .ok_or_else(|| anyhow!("Unknown state key kind: {}", s))
+    }
+}
+
+/// Structured description of a [`StateKey`], which serializes to self describing formats
+/// (e.g. JSON) in a way that doesn't depend on the storage encoding.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum StateKeyDescriptor {
+    Module {
+        address: AccountAddress,
+        name: Identifier,
+    },
+    Resource {
+        address: AccountAddress,
+        struct_tag: StructTag,
+    },
+    ResourceGroup {
+        address: AccountAddress,
+        struct_tag: StructTag,
+    },
+    TableItem {
+        handle: TableHandle,
+        #[serde(with = "hex_bytes")]
+        key: Vec<u8>,
+    },
+    Raw {
+        #[serde(with = "hex_bytes")]
+        bytes: Vec<u8>,
+    },
+}
+
+impl StateKeyDescriptor {
+    pub fn kind(&self) -> StateKeyKind {
+        match self {
+            StateKeyDescriptor::Module { .. } => StateKeyKind::Module,
+            StateKeyDescriptor::Resource { .. } => StateKeyKind::Resource,
+            StateKeyDescriptor::ResourceGroup { .. } => StateKeyKind::ResourceGroup,
+            StateKeyDescriptor::TableItem { .. } => StateKeyKind::TableItem,
+            StateKeyDescriptor::Raw { .. } => StateKeyKind::Raw,
+        }
+    }
+
+    pub fn to_state_key(&self) -> Result<StateKey> {
+        Ok(match self {
+            StateKeyDescriptor::Module { address, name } => StateKey::module(address, name),
+            StateKeyDescriptor::Resource {
+                address,
+                struct_tag,
+            } => StateKey::resource(address, struct_tag)?,
+            StateKeyDescriptor::ResourceGroup {
+                address,
+                struct_tag,
+            } => StateKey::resource_group(address, struct_tag),
+            StateKeyDescriptor::TableItem { handle, key } => StateKey::table_item(handle, key),
+            StateKeyDescriptor::Raw { bytes } => StateKey::raw(bytes),
+        })
+    }
+}
+
+impl TryFrom<&StateKey> for StateKeyDescriptor {
+    type Error = anyhow::Error;
+
+    fn try_from(state_key: &StateKey) -> Result<Self> {
+        Ok(match state_key.inner() {
+            StateKeyInner::AccessPath(access_path) => {
+                let address = access_path.address;
+                match bcs::from_bytes::<Path>(&access_path.path)? {
+                    Path::Code(module_id) => StateKeyDescriptor::Module {
+                        address,
+                        name: module_id.name,
+                    },
+                    Path::Resource(struct_tag) => StateKeyDescriptor::Resource {
+                        address,
+                        struct_tag,
+                    },
+                    Path::ResourceGroup(struct_tag) => StateKeyDescriptor::ResourceGroup {
+                        address,
+                        struct_tag,
+                    },
+                }
+            },
+            StateKeyInner::TableItem { handle, key } => StateKeyDescriptor::TableItem {
+                handle: *handle,
+                key: key.clone(),
+            },
+            StateKeyInner::Raw(bytes) => StateKeyDescriptor::Raw {
+                bytes: bytes.clone(),
+            },
+        })
+    }
+}
+
+impl fmt::Display for StateKeyDescriptor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:", self.kind())?;
+        match self {
+            StateKeyDescriptor::Module { address, name } => {
+                write!(f, "{}::{}", address.to_hex_literal(), name)
+            },
+            StateKeyDescriptor::Resource {
+                address,
+                struct_tag,
+            }
+            | StateKeyDescriptor::ResourceGroup {
+                address,
+                struct_tag,
+            } => write!(
+                f,
+                "{}/{}",
+                address.to_hex_literal(),
+                struct_tag.to_canonical_string()
+            ),
+            StateKeyDescriptor::TableItem { handle, key } => {
+                write!(f, "{}/0x{}", handle.0.to_hex_literal(), hex::encode(key))
+            },
+            StateKeyDescriptor::Raw { bytes } => write!(f, "0x{}", hex::encode(bytes)),
+        }
+    }
+}
+
+impl FromStr for StateKeyDescriptor {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let s = s.trim();
+        let (kind, body) = s
+            .split_once(':')
+            .ok_or_else(|| anyhow!("Expected <kind>:<body>, got: {}", s))?;
+        let kind = StateKeyKind::from_str(kind)?;
+        let split_address = |body: &str| -> Result<(AccountAddress, String)> {
+            let (address, rest) = body
+                .split_once('/')
+                .ok_or_else(|| anyhow!("Expected {}, got: {}", kind.syntax(), s))?;
+            Ok((AccountAddress::from_str(address)?, rest.to_string()))
+        };
+
+        Ok(match kind {
+            StateKeyKind::Module => {
+                let module_id = ModuleId::from_str(body)
+                    .map_err(|e| anyhow!("Invalid module id {}: {}", body, e))?;
+                StateKeyDescriptor::Module {
+                    address: module_id.address,
+                    name: module_id.name,
+                }
+            },
+            StateKeyKind::Resource => {
+                let (address, struct_tag) = split_address(body)?;
+                StateKeyDescriptor::Resource {
+                    address,
+                    struct_tag: StructTag::from_str(&struct_tag)?,
+                }
+            },
+            StateKeyKind::ResourceGroup => {
+                let (address, struct_tag) = split_address(body)?;
+                StateKeyDescriptor::ResourceGroup {
+                    address,
+                    struct_tag: StructTag::from_str(&struct_tag)?,
+                }
+            },
+            StateKeyKind::TableItem => {
+                let (handle, key) = split_address(body)?;
+                StateKeyDescriptor::TableItem {
+                    handle: TableHandle(handle),
+                    key: decode_hex(&key)?,
+                }
+            },
+            StateKeyKind::Raw => StateKeyDescriptor::Raw {
+                bytes: decode_hex(body)?,
+            },
+        })
+    }
+}
+
+fn decode_hex(s: &str) -> Result<Vec<u8>> {
+    let s = s.strip_prefix("0x").unwrap_or(s);
+    if s.len() % 2 != 0 {
+        bail!("Odd number of hex digits: {}", s);
+    }
+    Ok(hex::decode(s)?)
+}
+
+mod hex_bytes {
+    use serde::{de::Error, Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&format!("0x{}", hex::encode(bytes)))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<u8>, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        super::decode_hex(&s).map_err(D::Error::custom)
+    }
+}
+
+impl StateKey {
+    /// Parses the human readable form of a state key, e.g. `resource:0x1/0x1::account::Account`
+    /// (see [`StateKeyKind::syntax`] for all forms).
+    pub fn parse_human(s: &str) -> Result<Self> {
+        StateKeyDescriptor::from_str(s)?.to_state_key()
+    }
+
+    /// Returns the canonical human readable form, which [`StateKey::parse_human`] accepts.
+    pub fn to_human_string(&self) -> String {
+        match self.descriptor() {
+            Ok(descriptor) => descriptor.to_string(),
+            // Only possible for access paths that don't decode, which the constructors prevent.
+            Err(_) => format!("{:?}", self),
+        }
+    }
+
+    pub fn descriptor(&self) -> Result<StateKeyDescriptor> {
+        StateKeyDescriptor::try_from(self)
+    }
+}
@@ -3,7 +3,7 @@
 
 use crate::{
     account_config::{AccountResource, ObjectGroupResource},
-    state_store::state_key::StateKey,
+    state_store::state_key::{descriptor::StateKeyDescriptor, StateKey},
 };
 use aptos_crypto::hash::CryptoHash;
 use move_core_types::{account_address::AccountAddress, ident_str, move_resource::MoveStructType};
@@ -89,6 +89,55 @@ fn test_debug() {
     assert_eq!(&format!("{:?}", key), "StateKey::Raw(010203)",);
 }
 
+#[test]
+fn test_human_string() {
+    let cases = [
+        (
+            StateKey::module(&AccountAddress::ONE, ident_str!("account")),
+            "module:0x1::account",
+        ),
+        (
+            StateKey::resource_typed::<AccountResource>(&AccountAddress::FOUR).unwrap(),
+            "resource:0x4/0x1::account::Account",
+        ),
+        (
+            StateKey::resource_group(&AccountAddress::THREE, &ObjectGroupResource::struct_tag()),
+            "resource_group:0x3/0x1::object::ObjectGroup",
+        ),
+        (
+            StateKey::table_item(&"0x123".parse().unwrap(), &[1, 255]),
+            "table_item:0x123/0x01ff",
+        ),
+        (StateKey::raw(&[1, 2, 3]), "raw:0x010203"),
+    ];
+
+    for (key, human) in cases {
+        assert_eq!(key.to_human_string(), human);
+        assert_eq!(StateKey::parse_human(human).unwrap(), key);
+
+        let descriptor = key.descriptor().unwrap();
+        let json = serde_json::to_string(&descriptor).unwrap();
+        assert_eq!(
+            serde_json::from_str::<StateKeyDescriptor>(&json).unwrap(),
+            descriptor
+        );
+    }
+
+    // Generic resources and non-canonical addresses are accepted too.
+    let key = StateKey::parse_human(
+        "resource:0x0a/0x1::coin::CoinStore<0x0000000000000000000000000000000000000000000000000000000000000001::aptos_coin::AptosCoin>",
+    )
+    .unwrap();
+    assert_eq!(
+        key.to_human_string(),
+        "resource:0xa/0x1::coin::CoinStore<0x1::aptos_coin::AptosCoin>"
+    );
+
+    assert!(StateKey::parse_human("account:0x1").is_err());
+    assert!(StateKey::parse_human("resource:0x1::account::Account").is_err());
+    assert!(StateKey::parse_human("raw:0x123").is_err());
+}
+
 proptest! {
     #[test]
     fn test_shard_order(
@@ -2,9 +2,14 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    on_chain_config::CurrentTimeMicroseconds, proof::SparseMerkleRangeProof,
-    state_store::state_key::StateKey, transaction::Version,
+    on_chain_config::CurrentTimeMicroseconds,
+    proof::{SparseMerkleProof, SparseMerkleRangeProof, TransactionInfoWithProof},
+    state_proof::StateProof,
+    state_store::state_key::StateKey,
+    transaction::Version,
+    trusted_state::TrustedState,
 };
+use anyhow::{ensure, format_err};
 use aptos_crypto::{hash::SPARSE_MERKLE_PLACEHOLDER_HASH, HashValue};
 use aptos_crypto_derive::{BCSCryptoHash, CryptoHasher};
 use bytes::Bytes;
@@ -178,7 +183,7 @@ impl PersistedStateValue {
     }
 }
 
-#[derive(BCSCryptoHash, Clone, CryptoHasher, Debug, Eq, PartialEq)]
+#[derive(BCSCryptoHash, Clone, CryptoHasher, Debug, Eq, Hash, PartialEq)]
 pub struct StateValue {
     data: Bytes,
     metadata: StateValueMetadata,
@@ -322,6 +327,56 @@ impl StateValueChunkWithProof {
     }
 }
 
+/// A state value (or its absence) at a state checkpoint version, together with the proofs linking
+/// it to a ledger info that can be verified from a trusted waypoint.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct StateValueWithProof {
+    /// The state checkpoint version the value is read at
+    pub version: Version,
+    pub value: Option<StateValue>,
+    /// Proves `value` against the state checkpoint hash of the transaction at `version`
+    pub proof: SparseMerkleProof,
+    /// Proves the transaction info at `version` against the latest ledger info of `state_proof`
+    pub transaction_info_with_proof: TransactionInfoWithProof,
+    /// The latest ledger info and the epoch changes leading to it from the client's known version
+    pub state_proof: StateProof,
+}
+
+impl StateValueWithProof {
+    /// Verifies that `state_key` maps to `value` at `version` in the ledger history trusted by
+    /// `trusted_state`.
+    pub fn verify(&self, state_key: &StateKey, trusted_state: &TrustedState) -> anyhow::Result<()> {
+        let ledger_info = self.state_proof.latest_ledger_info();
+        let verified_version = trusted_state
+            .verify_and_ratchet(&self.state_proof)?
+            .new_state()
+            .map_or_else(|| trusted_state.version(), |state| state.version());
+        // The epoch change proof may stop short of the latest epoch if it's too long.
+        ensure!(
+            verified_version == ledger_info.version(),
+            "Ledger info at version {} could only be verified up to version {}, \
+             retry with a more recent waypoint",
+            ledger_info.version(),
+            verified_version,
+        );
+
+        self.transaction_info_with_proof
+            .verify(ledger_info, self.version)?;
+        let root_hash = self
+            .transaction_info_with_proof
+            .transaction_info()
+            .state_checkpoint_hash()
+            .ok_or_else(|| {
+                format_err!(
+                    "Transaction at version {} is not a state checkpoint",
+                    self.version
+                )
+            })?;
+        self.proof
+            .verify(root_hash, *state_key.crypto_hash_ref(), self.value.as_ref())
+    }
+}
+
 /// Indicates a state value becomes stale since `stale_since_version`.
 #[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
 #[cfg_attr(any(test, feature = "fuzzing"), derive(proptest_derive::Arbitrary))]
@@ -159,6 +159,16 @@ impl TransactionAuthenticator {
 
     /// Return Ok if all AccountAuthenticator's public keys match their signatures, Err otherwise
     pub fn verify(&self, raw_txn: &RawTransaction) -> Result<()> {
+        self.verify_with(raw_txn, AccountAuthenticator::verify_signing_message)
+    }
+
+    /// Same as `verify`, but the signatures of each `AccountAuthenticator` over the message it
+    /// signs are checked by `verify_signer`.
+    pub fn verify_with(
+        &self,
+        raw_txn: &RawTransaction,
+        verify_signer: impl Fn(&AccountAuthenticator, TransactionSigningMessage) -> Result<()>,
+    ) -> Result<()> {
         let num_sigs: usize = self.sender().number_of_signatures()
             + self
                 .secondary_signers()
@@ -169,10 +179,10 @@ impl TransactionAuthenticator {
             return Err(Error::new(AuthenticationError::MaxSignaturesExceeded));
         }
         match self {
-            Self::Ed25519 {
-                public_key,
-                signature,
-            } => signature.verify(raw_txn, public_key),
+            Self::Ed25519 { .. } | Self::MultiEd25519 { .. } => verify_signer(
+                &self.sender(),
+                TransactionSigningMessage::RawTransaction(raw_txn),
+            ),
             Self::FeePayer {
                 sender,
                 secondary_signer_addresses,
@@ -202,7 +212,15 @@ impl TransactionAuthenticator {
 
                 let mut remaining = to_verify
                     .iter()
-                    .filter(|verifier| verifier.verify(&no_fee_payer_address_message).is_err())
+                    .filter(|verifier| {
+                        verify_signer(
+                            verifier,
+                            TransactionSigningMessage::RawTransactionWithData(
+                                &no_fee_payer_address_message,
+                            ),
+                        )
+                        .is_err()
+                    })
                     .collect::<Vec<_>>();
 
                 remaining.push(&fee_payer_signer);
@@ -214,15 +232,16 @@ impl TransactionAuthenticator {
                 );
 
                 for verifier in remaining {
-                    verifier.verify(&fee_payer_address_message)?;
+                    verify_signer(
+                        verifier,
+                        TransactionSigningMessage::RawTransactionWithData(
+                            &fee_payer_address_message,
+                        ),
+                    )?;
                 }
 
                 Ok(())
             },
-            Self::MultiEd25519 {
-                public_key,
-                signature,
-            } => signature.verify(raw_txn, public_key),
             Self::MultiAgent {
                 sender,
                 secondary_signer_addresses,
@@ -232,13 +251,16 @@ impl TransactionAuthenticator {
                     raw_txn.clone(),
                     secondary_signer_addresses.clone(),
                 );
-                sender.verify(&message)?;
+                let message = TransactionSigningMessage::RawTransactionWithData(&message);
+                verify_signer(sender, message)?;
                 for signer in secondary_signers {
-                    signer.verify(&message)?;
+                    verify_signer(signer, message)?;
                 }
                 Ok(())
             },
-            Self::SingleSender { sender } => sender.verify(raw_txn),
+            Self::SingleSender { sender } => {
+                verify_signer(sender, TransactionSigningMessage::RawTransaction(raw_txn))
+            },
         }
     }
 
@@ -516,6 +538,14 @@ impl fmt::Display for Scheme {
     }
 }
 
+/// The message an `AccountAuthenticator` of a transaction signs: the raw transaction, or for
+/// multi-agent and fee payer transactions, the raw transaction with the other signers.
+#[derive(Clone, Copy, Debug)]
+pub enum TransactionSigningMessage<'a> {
+    RawTransaction(&'a RawTransaction),
+    RawTransactionWithData(&'a RawTransactionWithData),
+}
+
 /// An `AccountAuthenticator` is an abstraction of a signature scheme. It must know:
 /// (1) How to check its signature against a message and public key
 /// (2) How to convert its public key into an `AuthenticationKeyPreimage` structured as
@@ -680,6 +710,14 @@ impl AccountAuthenticator {
         }
     }
 
+    /// Same as `verify`, for the message signed by the authenticators of a transaction
+    pub fn verify_signing_message(&self, message: TransactionSigningMessage) -> Result<()> {
+        match message {
+            TransactionSigningMessage::RawTransaction(message) => self.verify(message),
+            TransactionSigningMessage::RawTransactionWithData(message) => self.verify(message),
+        }
+    }
+
     /// Return the raw bytes of `self.public_key`
     pub fn public_key_bytes(&self) -> Vec<u8> {
         match self {
@@ -1317,6 +1355,9 @@ impl EphemeralSignature {
             (Self::WebAuthn { signature }, EphemeralPublicKey::Secp256r1Ecdsa { public_key }) => {
                 signature.verify(message, &AnyPublicKey::secp256r1_ecdsa(public_key.clone()))
             },
+            (Self::WebAuthn { signature }, EphemeralPublicKey::Ed25519 { public_key }) => {
+                signature.verify(message, &AnyPublicKey::ed25519(public_key.clone()))
+            },
             _ => {
                 bail!("Unsupported ephemeral signature and public key combination");
             },
@@ -1338,6 +1379,9 @@ impl EphemeralSignature {
                     &AnyPublicKey::secp256r1_ecdsa(public_key.clone()),
                 )
             },
+            (Self::WebAuthn { signature }, EphemeralPublicKey::Ed25519 { public_key }) => {
+                signature.verify_arbitrary_msg(message, &AnyPublicKey::ed25519(public_key.clone()))
+            },
             _ => {
                 bail!("Unsupported ephemeral signature and public key combination");
             },
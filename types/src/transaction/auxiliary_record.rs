@@ -0,0 +1,191 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Typed, versioned records the VM can attach to a transaction's auxiliary data.
+//!
+//! Like the rest of [`TransactionAuxiliaryData`](super::TransactionAuxiliaryData), records are
+//! not part of the transaction hash, but they are persisted by the DB and served by the API. To
+//! keep what every node stores identical, only kinds in the registry below can be attached, each
+//! kind at most once, always encoded with its registered version, and records are kept sorted
+//! by kind.
+
+use anyhow::{bail, ensure, Result};
+#[cfg(any(test, feature = "fuzzing"))]
+use proptest_derive::Arbitrary;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::fmt;
+
+/// Registry of the known record kinds.
+///
+/// Values are persisted, so existing kinds must never be renumbered or removed. Changing the
+/// encoding of a kind requires bumping its version; newer versions may only append fields.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize, Deserialize)]
+#[cfg_attr(any(test, feature = "fuzzing"), derive(Arbitrary))]
+pub enum AuxiliaryRecordKind {
+    FeeBreakdown,
+    KeylessVerificationStats,
+    StateBytesDelta,
+}
+
+impl AuxiliaryRecordKind {
+    pub const ALL: [AuxiliaryRecordKind; 3] = [
+        AuxiliaryRecordKind::FeeBreakdown,
+        AuxiliaryRecordKind::KeylessVerificationStats,
+        AuxiliaryRecordKind::StateBytesDelta,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            AuxiliaryRecordKind::FeeBreakdown => "fee_breakdown",
+            AuxiliaryRecordKind::KeylessVerificationStats => "keyless_verification_stats",
+            AuxiliaryRecordKind::StateBytesDelta => "state_bytes_delta",
+        }
+    }
+
+    /// The version every node encodes records of this kind with.
+    pub fn registered_version(&self) -> u16 {
+        match self {
+            AuxiliaryRecordKind::FeeBreakdown => FeeBreakdownRecord::VERSION,
+            AuxiliaryRecordKind::KeylessVerificationStats => {
+                KeylessVerificationStatsRecord::VERSION
+            },
+            AuxiliaryRecordKind::StateBytesDelta => StateBytesDeltaRecord::VERSION,
+        }
+    }
+
+    /// Decodes a record of this kind into JSON, for the API.
+    fn decode_json(&self, data: &[u8]) -> Result<serde_json::Value> {
+        Ok(match self {
+            AuxiliaryRecordKind::FeeBreakdown => {
+                serde_json::to_value(decode::<FeeBreakdownRecord>(data)?)?
+            },
+            AuxiliaryRecordKind::KeylessVerificationStats => {
+                serde_json::to_value(decode::<KeylessVerificationStatsRecord>(data)?)?
+            },
+            AuxiliaryRecordKind::StateBytesDelta => {
+                serde_json::to_value(decode::<StateBytesDeltaRecord>(data)?)?
+            },
+        })
+    }
+}
+
+impl fmt::Display for AuxiliaryRecordKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// A Rust type that can be stored as an auxiliary record.
+pub trait TAuxiliaryRecord: Serialize + DeserializeOwned {
+    const KIND: AuxiliaryRecordKind;
+    const VERSION: u16;
+}
+
+/// A BCS encoded auxiliary record.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(any(test, feature = "fuzzing"), derive(Arbitrary))]
+pub struct AuxiliaryRecord {
+    kind: AuxiliaryRecordKind,
+    version: u16,
+    #[serde(with = "serde_bytes")]
+    data: Vec<u8>,
+}
+
+impl AuxiliaryRecord {
+    pub fn new<T: TAuxiliaryRecord>(record: &T) -> Result<Self> {
+        ensure!(
+            T::VERSION == T::KIND.registered_version(),
+            "Auxiliary record {} has version {}, but {} is registered",
+            T::KIND,
+            T::VERSION,
+            T::KIND.registered_version(),
+        );
+        Ok(Self {
+            kind: T::KIND,
+            version: T::VERSION,
+            data: bcs::to_bytes(record)?,
+        })
+    }
+
+    pub fn kind(&self) -> AuxiliaryRecordKind {
+        self.kind
+    }
+
+    pub fn version(&self) -> u16 {
+        self.version
+    }
+
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    /// Decodes the record as `T`. Records written by a newer version of the same kind decode
+    /// as long as that version only appended fields.
+    pub fn decode<T: TAuxiliaryRecord>(&self) -> Result<T> {
+        if self.kind != T::KIND {
+            bail!("Expected auxiliary record {}, got {}", T::KIND, self.kind);
+        }
+        decode(&self.data)
+    }
+
+    pub fn to_json(&self) -> Result<serde_json::Value> {
+        self.kind.decode_json(&self.data)
+    }
+}
+
+fn decode<T: DeserializeOwned>(data: &[u8]) -> Result<T> {
+    crate::on_chain_config::bcs_from_bytes_lenient(data)
+}
+
+/// Detailed breakdown of the fee charged, complementing the `FeeStatement` event with the
+/// price the fee was charged at.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct FeeBreakdownRecord {
+    pub gas_unit_price: u64,
+    pub execution_gas_units: u64,
+    pub io_gas_units: u64,
+    pub storage_fee_octas: u64,
+    pub storage_fee_refund_octas: u64,
+    pub total_charge_octas: u64,
+}
+
+impl TAuxiliaryRecord for FeeBreakdownRecord {
+    const KIND: AuxiliaryRecordKind = AuxiliaryRecordKind::FeeBreakdown;
+    const VERSION: u16 = 1;
+}
+
+/// Statistics about the keyless signatures verified for a transaction.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct KeylessVerificationStatsRecord {
+    pub num_zk_signatures: u64,
+    pub num_openid_signatures: u64,
+    pub num_federated_jwk_lookups: u64,
+}
+
+impl TAuxiliaryRecord for KeylessVerificationStatsRecord {
+    const KIND: AuxiliaryRecordKind = AuxiliaryRecordKind::KeylessVerificationStats;
+    const VERSION: u16 = 1;
+}
+
+/// How much the transaction grew (or shrank) the state by, counting the keys and values of the
+/// items it wrote against what they replaced. Attached by the executor rather than the VM, as
+/// it's only known once the writes of the earlier transactions in the block are applied.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct StateBytesDeltaRecord {
+    pub items_delta: i64,
+    pub bytes_delta: i64,
+}
+
+impl TAuxiliaryRecord for StateBytesDeltaRecord {
+    const KIND: AuxiliaryRecordKind = AuxiliaryRecordKind::StateBytesDelta;
+    const VERSION: u16 = 1;
+}
+
+/// Inserts `record` into `records`, replacing a previous record of the same kind and keeping
+/// the records sorted by kind.
+pub(super) fn insert_record(records: &mut Vec<AuxiliaryRecord>, record: AuxiliaryRecord) {
+    match records.binary_search_by_key(&record.kind, |r| r.kind) {
+        Ok(idx) => records[idx] = record,
+        Err(idx) => records.insert(idx, record),
+    }
+}
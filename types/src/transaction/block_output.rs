@@ -4,6 +4,15 @@
 use super::BlockExecutableTransaction;
 use std::fmt::Debug;
 
+/// Conflicts observed while executing a block in parallel.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct BlockConflictStats {
+    /// Committed transactions that were executed more than once.
+    pub num_conflicting_txns: u64,
+    /// Speculative executions of committed transactions that were aborted and redone.
+    pub num_speculative_aborts: u64,
+}
+
 #[derive(Debug)]
 pub struct BlockOutput<T, Output>
 where
@@ -14,6 +23,8 @@ where
     // A BlockEpilogueTxn might be appended to the block.
     // This field will be None iff the input is not a block, or an epoch change is triggered.
     block_epilogue_txn: Option<T>,
+    // Only set if the block was executed in parallel.
+    conflict_stats: Option<BlockConflictStats>,
 }
 
 impl<T, Output> BlockOutput<T, Output>
@@ -25,9 +36,19 @@ where
         Self {
             transaction_outputs,
             block_epilogue_txn,
+            conflict_stats: None,
         }
     }
 
+    pub fn with_conflict_stats(mut self, conflict_stats: Option<BlockConflictStats>) -> Self {
+        self.conflict_stats = conflict_stats;
+        self
+    }
+
+    pub fn conflict_stats(&self) -> Option<BlockConflictStats> {
+        self.conflict_stats
+    }
+
     pub fn into_transaction_outputs_forced(self) -> Vec<Output> {
         self.transaction_outputs
     }
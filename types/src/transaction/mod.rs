@@ -14,7 +14,7 @@ use crate::{
     proof::{TransactionInfoListWithProof, TransactionInfoWithProof},
     transaction::authenticator::{
         AccountAuthenticator, AnyPublicKey, AnySignature, SingleKeyAuthenticator,
-        TransactionAuthenticator,
+        TransactionAuthenticator, TransactionSigningMessage,
     },
     vm_status::{DiscardedVMStatus, KeptVMStatus, StatusCode, StatusType, VMStatus},
     write_set::{HotStateOp, WriteSet},
@@ -43,6 +43,7 @@ use std::{
 
 pub mod analyzed_transaction;
 pub mod authenticator;
+pub mod auxiliary_record;
 pub mod block_epilogue;
 mod block_output;
 mod change_set;
@@ -70,7 +71,11 @@ use crate::{
     validator_txn::ValidatorTransaction,
     write_set::TransactionWrite,
 };
-pub use block_output::BlockOutput;
+pub use auxiliary_record::{
+    AuxiliaryRecord, AuxiliaryRecordKind, FeeBreakdownRecord, KeylessVerificationStatsRecord,
+    StateBytesDeltaRecord, TAuxiliaryRecord,
+};
+pub use block_output::{BlockConflictStats, BlockOutput};
 pub use change_set::ChangeSet;
 pub use module::{Module, ModuleBundle};
 pub use move_core_types::transaction_argument::TransactionArgument;
@@ -571,6 +576,30 @@ impl RawTransaction {
         self.sender
     }
 
+    pub fn sequence_number(&self) -> u64 {
+        self.sequence_number
+    }
+
+    pub fn payload(&self) -> &TransactionPayload {
+        &self.payload
+    }
+
+    pub fn max_gas_amount(&self) -> u64 {
+        self.max_gas_amount
+    }
+
+    pub fn gas_unit_price(&self) -> u64 {
+        self.gas_unit_price
+    }
+
+    pub fn expiration_timestamp_secs(&self) -> u64 {
+        self.expiration_timestamp_secs
+    }
+
+    pub fn chain_id(&self) -> ChainId {
+        self.chain_id
+    }
+
     /// Return the signing message for creating transaction signature.
     pub fn signing_message(&self) -> Result<Vec<u8>, CryptoMaterialError> {
         signing_message(self)
@@ -1238,12 +1267,36 @@ impl SignedTransaction {
     /// Checks that the signature of given transaction. Returns `Ok(SignatureCheckedTransaction)` if
     /// the signature is valid.
     pub fn check_signature(self) -> Result<SignatureCheckedTransaction> {
-        self.authenticator.verify(&self.raw_txn)?;
+        self.check_signature_with(AccountAuthenticator::verify_signing_message)
+    }
+
+    /// Same as `check_signature`, but the signatures are verified by `verify_signer` (see
+    /// `TransactionAuthenticator::verify_with`).
+    pub fn check_signature_with(
+        self,
+        verify_signer: impl Fn(&AccountAuthenticator, TransactionSigningMessage) -> Result<()>,
+    ) -> Result<SignatureCheckedTransaction> {
+        self.verify_signature_with(verify_signer)?;
         Ok(SignatureCheckedTransaction(self))
     }
 
+    /// Verifies the signature of the transaction. If the same transaction (i.e., with the same
+    /// committed hash) was already verified on this node, the check is skipped.
     pub fn verify_signature(&self) -> Result<()> {
-        self.authenticator.verify(&self.raw_txn)?;
+        self.verify_signature_with(AccountAuthenticator::verify_signing_message)
+    }
+
+    /// Same as `verify_signature`, but the signatures are verified by `verify_signer`.
+    pub fn verify_signature_with(
+        &self,
+        verify_signer: impl Fn(&AccountAuthenticator, TransactionSigningMessage) -> Result<()>,
+    ) -> Result<()> {
+        let committed_hash = self.committed_hash();
+        if signature_verified_transaction::is_signature_verified(&committed_hash) {
+            return Ok(());
+        }
+        self.authenticator.verify_with(&self.raw_txn, verify_signer)?;
+        signature_verified_transaction::mark_signature_verified(committed_hash);
         Ok(())
     }
 
@@ -1647,11 +1700,20 @@ pub struct TransactionAuxiliaryDataV1 {
     pub detail_error_message: Option<VMErrorDetail>,
 }
 
+/// Adds typed records, see [`auxiliary_record`]. Records are sorted by kind and unique.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(any(test, feature = "fuzzing"), derive(Arbitrary))]
+pub struct TransactionAuxiliaryDataV2 {
+    pub detail_error_message: Option<VMErrorDetail>,
+    pub records: Vec<AuxiliaryRecord>,
+}
+
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 #[cfg_attr(any(test, feature = "fuzzing"), derive(Arbitrary))]
 pub enum TransactionAuxiliaryData {
     None,
     V1(TransactionAuxiliaryDataV1),
+    V2(TransactionAuxiliaryDataV2),
 }
 
 impl Default for TransactionAuxiliaryData {
@@ -1664,9 +1726,41 @@ impl TransactionAuxiliaryData {
     pub fn get_detail_error_message(&self) -> Option<&VMErrorDetail> {
         match self {
             Self::V1(data) => data.detail_error_message.as_ref(),
+            Self::V2(data) => data.detail_error_message.as_ref(),
             _ => None,
         }
     }
+
+    pub fn records(&self) -> &[AuxiliaryRecord] {
+        match self {
+            Self::V2(data) => &data.records,
+            _ => &[],
+        }
+    }
+
+    /// Returns the record of type `T`, if attached.
+    pub fn get_record<T: TAuxiliaryRecord>(&self) -> Result<Option<T>> {
+        self.records()
+            .iter()
+            .find(|record| record.kind() == T::KIND)
+            .map(|record| record.decode())
+            .transpose()
+    }
+
+    /// Attaches `record`, replacing a previously attached record of the same type.
+    pub fn add_record<T: TAuxiliaryRecord>(&mut self, record: &T) -> Result<()> {
+        let record = AuxiliaryRecord::new(record)?;
+        match self {
+            Self::V2(data) => auxiliary_record::insert_record(&mut data.records, record),
+            _ => {
+                *self = Self::V2(TransactionAuxiliaryDataV2 {
+                    detail_error_message: self.get_detail_error_message().cloned(),
+                    records: vec![record],
+                })
+            },
+        }
+        Ok(())
+    }
 }
 
 /// The output of executing a transaction.
@@ -1754,6 +1848,11 @@ impl TransactionOutput {
         &self.auxiliary_data
     }
 
+    /// Attaches an auxiliary record, which must be computed deterministically as it's persisted.
+    pub fn add_auxiliary_record<T: TAuxiliaryRecord>(&mut self, record: &T) -> Result<()> {
+        self.auxiliary_data.add_record(record)
+    }
+
     pub fn unpack(
         self,
     ) -> (
@@ -12,8 +12,31 @@ use crate::{
 };
 use aptos_crypto::{hash::CryptoHash, HashValue};
 use move_core_types::{account_address::AccountAddress, language_storage::StructTag};
+use once_cell::sync::Lazy;
+use quick_cache::sync::Cache;
+use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
-use std::fmt::Debug;
+use std::{collections::HashMap, fmt::Debug};
+
+/// The maximum number of transactions remembered as having valid signatures
+const VERIFIED_SIGNATURE_CACHE_SIZE: usize = 200_000;
+
+/// The committed hashes of the user transactions whose signatures were verified on this node.
+/// The same transaction is otherwise verified by the API, mempool (on every node it is broadcast
+/// to) and consensus. The committed hash covers the authenticator, so a hit implies that the
+/// exact same signatures were verified. Only valid signatures are cached.
+static VERIFIED_SIGNATURE_CACHE: Lazy<Cache<HashValue, ()>> =
+    Lazy::new(|| Cache::new(VERIFIED_SIGNATURE_CACHE_SIZE));
+
+/// Returns true iff the signature of the transaction with the given committed hash was verified
+pub fn is_signature_verified(committed_hash: &HashValue) -> bool {
+    VERIFIED_SIGNATURE_CACHE.get(committed_hash).is_some()
+}
+
+/// Marks the signature of the transaction with the given committed hash as verified
+pub(crate) fn mark_signature_verified(committed_hash: HashValue) {
+    VERIFIED_SIGNATURE_CACHE.insert(committed_hash, ());
+}
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub enum SignatureVerifiedTransaction {
@@ -142,6 +165,53 @@ pub fn into_signature_verified_block(txns: Vec<Transaction>) -> Vec<SignatureVer
     txns.into_iter().map(|t| t.into()).collect()
 }
 
+/// Verifies the signatures of the given user transactions as a batch on the current rayon pool.
+/// Transactions already verified on this node are served from the verified signature cache,
+/// and each distinct transaction in the batch is only verified once.
+///
+/// Note: the signatures are verified individually (and in parallel). Ed25519 batch verification
+/// is randomized and cofactorless, so it may disagree with strict verification on inputs with
+/// small order components, which would break determinism across validators.
+pub fn verify_user_txn_signatures(
+    txns: Vec<SignedTransaction>,
+    min_len: usize,
+) -> Vec<SignatureVerifiedTransaction> {
+    let committed_hashes: Vec<HashValue> = txns
+        .par_iter()
+        .with_min_len(min_len)
+        .map(|txn| txn.committed_hash())
+        .collect();
+
+    // Pick a single transaction to verify per distinct hash that isn't already cached
+    let mut to_verify = HashMap::new();
+    for (index, committed_hash) in committed_hashes.iter().enumerate() {
+        if !is_signature_verified(committed_hash) {
+            to_verify.entry(*committed_hash).or_insert(index);
+        }
+    }
+    let to_verify: Vec<_> = to_verify.into_iter().collect();
+    let verification_results: HashMap<HashValue, bool> = to_verify
+        .into_par_iter()
+        .with_min_len(min_len)
+        .map(|(committed_hash, index)| (committed_hash, txns[index].verify_signature().is_ok()))
+        .collect();
+
+    txns.into_iter()
+        .zip(committed_hashes)
+        .map(|(txn, committed_hash)| {
+            let is_valid = verification_results
+                .get(&committed_hash)
+                .copied()
+                .unwrap_or(true);
+            if is_valid {
+                SignatureVerifiedTransaction::Valid(Transaction::UserTransaction(txn))
+            } else {
+                SignatureVerifiedTransaction::Invalid(Transaction::UserTransaction(txn))
+            }
+        })
+        .collect()
+}
+
 pub trait TransactionProvider: Debug {
     fn get_transaction(&self) -> Option<&Transaction>;
 }
@@ -4,13 +4,21 @@
 use crate::transaction::authenticator::AnyPublicKey;
 use anyhow::{anyhow, Result};
 use aptos_crypto::{
-    hash::CryptoHash, secp256r1_ecdsa, signing_message, CryptoMaterialError, HashValue, Signature,
+    ed25519::Ed25519Signature, hash::CryptoHash, secp256r1_ecdsa, signing_message,
+    CryptoMaterialError, HashValue, Signature,
+};
+use passkey_types::{
+    crypto::sha256,
+    webauthn::{ClientDataType, CollectedClientData},
+    Bytes,
 };
-use passkey_types::{crypto::sha256, webauthn::CollectedClientData, Bytes};
 use serde::{Deserialize, Serialize};
 
 pub const MAX_WEBAUTHN_SIGNATURE_BYTES: usize = 1024;
 
+/// Length of the fixed prefix of authenticator data: rpIdHash (32) || flags (1) || signCount (4)
+const AUTHENTICATOR_DATA_PREFIX_LENGTH: usize = 37;
+
 /// Returns the binary concatenation of
 /// 1. [`authenticator_data_bytes`](PartialAuthenticatorAssertionResponse) and
 /// 2. SHA-256 hash of [`client_data_json`](PartialAuthenticatorAssertionResponse),
@@ -47,11 +55,78 @@ fn verify_expected_challenge_from_message_matches_actual<T: Serialize + CryptoHa
         .ok_or(CryptoMaterialError::ValidationError)
 }
 
+/// The fixed-length prefix of the authenticator data returned with an assertion. Attested
+/// credential data and extensions, if any, follow it and are not parsed.
+///
+/// See <https://www.w3.org/TR/webauthn-3/#sctn-authenticator-data>
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct AuthenticatorData {
+    /// SHA-256 hash of the RP ID the credential is scoped to
+    pub rp_id_hash: [u8; 32],
+    pub flags: u8,
+    pub sign_count: u32,
+}
+
+impl AuthenticatorData {
+    pub const FLAG_ATTESTED_CREDENTIAL_DATA: u8 = 0x40;
+    pub const FLAG_BACKED_UP: u8 = 0x10;
+    pub const FLAG_BACKUP_ELIGIBLE: u8 = 0x08;
+    pub const FLAG_EXTENSION_DATA: u8 = 0x80;
+    pub const FLAG_USER_PRESENT: u8 = 0x01;
+    pub const FLAG_USER_VERIFIED: u8 = 0x04;
+
+    pub fn parse(bytes: &[u8]) -> Result<Self> {
+        if bytes.len() < AUTHENTICATOR_DATA_PREFIX_LENGTH {
+            return Err(anyhow!(
+                "Authenticator data is {} bytes, expected at least {}",
+                bytes.len(),
+                AUTHENTICATOR_DATA_PREFIX_LENGTH
+            ));
+        }
+        let mut rp_id_hash = [0u8; 32];
+        rp_id_hash.copy_from_slice(&bytes[..32]);
+        let mut sign_count = [0u8; 4];
+        sign_count.copy_from_slice(&bytes[33..37]);
+        Ok(Self {
+            rp_id_hash,
+            flags: bytes[32],
+            // The signature counter is big-endian
+            sign_count: u32::from_be_bytes(sign_count),
+        })
+    }
+
+    pub fn user_present(&self) -> bool {
+        self.flags & Self::FLAG_USER_PRESENT != 0
+    }
+
+    pub fn user_verified(&self) -> bool {
+        self.flags & Self::FLAG_USER_VERIFIED != 0
+    }
+}
+
+/// A raw assertion signature, by the COSE algorithm of the credential it was produced with.
+/// Only ES256 and EdDSA credentials are supported, assertions made with any other algorithm
+/// (e.g., RS256) can't be represented and are rejected on deserialization.
+///
+/// See <https://www.iana.org/assignments/cose/cose.xhtml#algorithms>
 #[derive(Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
 pub enum AssertionSignature {
+    /// ES256, COSE algorithm -7
     Secp256r1Ecdsa {
         signature: secp256r1_ecdsa::Signature,
     },
+    /// EdDSA over Ed25519, COSE algorithm -8
+    Ed25519 { signature: Ed25519Signature },
+}
+
+impl AssertionSignature {
+    /// The COSE algorithm identifier of the signature
+    pub fn cose_algorithm(&self) -> i64 {
+        match self {
+            Self::Secp256r1Ecdsa { .. } => -7,
+            Self::Ed25519 { .. } => -8,
+        }
+    }
 }
 
 /// Custom arbitrary implementation for fuzzing
@@ -60,6 +135,12 @@ pub enum AssertionSignature {
 #[cfg(feature = "fuzzing")]
 impl<'a> arbitrary::Arbitrary<'a> for AssertionSignature {
     fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        if u.arbitrary()? {
+            return Ok(AssertionSignature::Ed25519 {
+                signature: u.arbitrary()?,
+            });
+        }
+
         // Generate a fixed-length byte array for the signature
         let bytes: [u8; aptos_crypto::secp256r1_ecdsa::Signature::LENGTH] = u.arbitrary()?;
 
@@ -121,6 +202,16 @@ impl PartialAuthenticatorAssertionResponse {
         bcs::to_bytes(&self.signature).expect("Only unhandleable errors happen here.")
     }
 
+    /// Parses the fixed prefix of `authenticator_data`
+    pub fn authenticator_data(&self) -> Result<AuthenticatorData> {
+        AuthenticatorData::parse(&self.authenticator_data)
+    }
+
+    /// Parses `client_data_json`
+    pub fn collected_client_data(&self) -> Result<CollectedClientData> {
+        Ok(serde_json::from_slice(self.client_data_json.as_slice())?)
+    }
+
     /// In our adaptation of WebAuthn, the `challenge` provided to `authenticatorGetAssertion`
     /// is the SHA3-256 digest of the `RawTransaction`.
     ///
@@ -130,62 +221,60 @@ impl PartialAuthenticatorAssertionResponse {
     ///    authenticator_data and SHA-256(client_data_json)
     /// 3. Signature verification
     ///
+    /// The ceremony itself is checked separately by
+    /// [`verify_assertion_ceremony`](Self::verify_assertion_ceremony), as that is feature gated.
+    ///
     /// See WebAuthn §6.3.3 `authenticatorGetAssertion` for more info
     pub fn verify<T: Serialize + CryptoHash>(
         &self,
         message: &T,
         public_key: &AnyPublicKey,
     ) -> Result<()> {
-        let collected_client_data: CollectedClientData =
-            serde_json::from_slice(self.client_data_json.as_slice())?;
-        let challenge_bytes = Bytes::try_from(collected_client_data.challenge.as_str())
-            .map_err(|e| anyhow!("Failed to decode challenge bytes {:?}", e))?;
-
-        // Check if expected challenge and actual challenge match. If there's no match, throw error
-        verify_expected_challenge_from_message_matches_actual(message, challenge_bytes.as_slice())?;
+        self.verify_with_challenge(public_key, |actual_challenge| {
+            verify_expected_challenge_from_message_matches_actual(message, actual_challenge)
+        })
+    }
 
-        // Generates binary concatenation of authenticator_data and hash(client_data_json)
-        let verification_data = generate_verification_data(
-            self.authenticator_data.as_slice(),
-            self.client_data_json.as_slice(),
-        );
+    /// Same as [`verify`](Self::verify), but the expected `challenge` is `message` itself
+    pub fn verify_arbitrary_msg(&self, message: &[u8], public_key: &AnyPublicKey) -> Result<()> {
+        self.verify_with_challenge(public_key, |actual_challenge| {
+            actual_challenge
+                .eq(message)
+                .then_some(())
+                .ok_or(CryptoMaterialError::ValidationError)
+        })
+    }
 
-        // Note: We must call verify_arbitrary_msg instead of verify here. We do NOT want to
-        // use verify because it BCS serializes and prefixes the message with a hash
-        // via the signing_message function invocation
-        match (&public_key, &self.signature) {
-            (
-                AnyPublicKey::Secp256r1Ecdsa { public_key },
-                AssertionSignature::Secp256r1Ecdsa { signature },
-            ) => signature.verify_arbitrary_msg(&verification_data, public_key),
-            _ => Err(anyhow!(
-                "WebAuthn verification failure, invalid key, signature pairing"
-            )),
+    /// Verifies the client data and authenticator data describe an assertion made with the user
+    /// present. Only enforced once `WEBAUTHN_ASSERTION_CEREMONY` is enabled on chain, as
+    /// signatures accepted before must keep verifying.
+    pub fn verify_assertion_ceremony(&self) -> Result<()> {
+        // The client data of a registration ceremony must never be accepted as an assertion.
+        // Both `webauthn.get` and Secure Payment Confirmation's `payment.get` are allowed.
+        if matches!(self.collected_client_data()?.ty, ClientDataType::Create) {
+            return Err(anyhow!(
+                "WebAuthn verification failure, client data is not from an assertion"
+            ));
+        }
+        if !self.authenticator_data()?.user_present() {
+            return Err(anyhow!(
+                "WebAuthn verification failure, user presence flag is not set"
+            ));
         }
+        Ok(())
     }
 
-    /// In our adaptation of WebAuthn, the `challenge` provided to `authenticatorGetAssertion`
-    /// is the SHA3-256 digest of the `RawTransaction`.
-    ///
-    /// This function should do the following:
-    /// 1. Verify `actual_challenge` and expected challenge from message are equal
-    /// 2. Construct `verification_data` as the binary concatenation of
-    ///    authenticator_data and SHA-256(client_data_json)
-    /// 3. Signature verification
-    ///
-    /// See WebAuthn §6.3.3 `authenticatorGetAssertion` for more info
-    pub fn verify_arbitrary_msg(&self, message: &[u8], public_key: &AnyPublicKey) -> Result<()> {
-        let collected_client_data: CollectedClientData =
-            serde_json::from_slice(self.client_data_json.as_slice())?;
+    fn verify_with_challenge(
+        &self,
+        public_key: &AnyPublicKey,
+        check_challenge: impl FnOnce(&[u8]) -> std::result::Result<(), CryptoMaterialError>,
+    ) -> Result<()> {
+        let collected_client_data = self.collected_client_data()?;
         let challenge_bytes = Bytes::try_from(collected_client_data.challenge.as_str())
             .map_err(|e| anyhow!("Failed to decode challenge bytes {:?}", e))?;
 
         // Check if expected challenge and actual challenge match. If there's no match, throw error
-        challenge_bytes
-            .as_slice()
-            .eq(message)
-            .then_some(())
-            .ok_or(CryptoMaterialError::ValidationError)?;
+        check_challenge(challenge_bytes.as_slice())?;
 
         // Generates binary concatenation of authenticator_data and hash(client_data_json)
         let verification_data = generate_verification_data(
@@ -201,6 +290,9 @@ impl PartialAuthenticatorAssertionResponse {
                 AnyPublicKey::Secp256r1Ecdsa { public_key },
                 AssertionSignature::Secp256r1Ecdsa { signature },
             ) => signature.verify_arbitrary_msg(&verification_data, public_key),
+            (AnyPublicKey::Ed25519 { public_key }, AssertionSignature::Ed25519 { signature }) => {
+                signature.verify_arbitrary_msg(&verification_data, public_key)
+            },
             _ => Err(anyhow!(
                 "WebAuthn verification failure, invalid key, signature pairing"
             )),
@@ -208,6 +300,40 @@ impl PartialAuthenticatorAssertionResponse {
     }
 }
 
+#[cfg(any(test, feature = "fuzzing"))]
+impl PartialAuthenticatorAssertionResponse {
+    /// Builds an assertion over `raw_txn` signed by an Ed25519 (COSE EdDSA) credential, with
+    /// the client data of type `ty` and the authenticator data `flags`
+    pub fn ed25519_for_testing(
+        private_key: &aptos_crypto::ed25519::Ed25519PrivateKey,
+        raw_txn: &crate::transaction::RawTransaction,
+        ty: &str,
+        flags: u8,
+    ) -> Self {
+        use aptos_crypto::SigningKey;
+
+        let challenge = HashValue::sha3_256_of(&signing_message(raw_txn).unwrap());
+        let client_data_json = format!(
+            r#"{{"type":"{}","challenge":"{}","origin":"http://localhost:5173","crossOrigin":false}}"#,
+            ty,
+            base64::encode_config(challenge.to_vec(), base64::URL_SAFE_NO_PAD)
+        )
+        .into_bytes();
+        let mut authenticator_data = vec![0; AUTHENTICATOR_DATA_PREFIX_LENGTH];
+        authenticator_data[..32].copy_from_slice(&sha256(b"localhost"));
+        authenticator_data[32] = flags;
+
+        let verification_data = generate_verification_data(&authenticator_data, &client_data_json);
+        Self::new(
+            AssertionSignature::Ed25519 {
+                signature: private_key.sign_arbitrary_message(&verification_data),
+            },
+            authenticator_data,
+            client_data_json,
+        )
+    }
+}
+
 impl TryFrom<&[u8]> for PartialAuthenticatorAssertionResponse {
     type Error = CryptoMaterialError;
 
@@ -226,12 +352,15 @@ mod tests {
         test_helpers::transaction_test_helpers::get_test_raw_transaction,
         transaction::{
             authenticator::{AnyPublicKey, AuthenticationKey},
-            webauthn::{AssertionSignature, PartialAuthenticatorAssertionResponse},
+            webauthn::{
+                AssertionSignature, AuthenticatorData, PartialAuthenticatorAssertionResponse,
+            },
             RawTransaction,
         },
     };
     use anyhow::anyhow;
     use aptos_crypto::{
+        ed25519::Ed25519PrivateKey,
         secp256r1_ecdsa,
         secp256r1_ecdsa::{PrivateKey, PublicKey, Signature},
         signing_message, HashValue, PrivateKey as PrivateKeyTrait, Uniform,
@@ -941,4 +1070,72 @@ mod tests {
         let verification_result = paar.verify(&raw_txn, &any_public_key);
         assert!(verification_result.is_ok());
     }
+
+    #[test]
+    fn parse_authenticator_data() {
+        let authenticator_data = AuthenticatorData::parse(AUTHENTICATOR_DATA).unwrap();
+        assert_eq!(authenticator_data.rp_id_hash, AUTHENTICATOR_DATA[..32]);
+        assert_eq!(authenticator_data.sign_count, 0);
+        assert!(authenticator_data.user_present());
+        assert!(authenticator_data.user_verified());
+
+        assert!(AuthenticatorData::parse(&AUTHENTICATOR_DATA[..36]).is_err());
+    }
+
+    #[test]
+    fn verify_ed25519_partial_authenticator_assertion_response() {
+        let mut rng: StdRng = SeedableRng::from_seed([0; 32]);
+        let private_key: Ed25519PrivateKey = Uniform::generate(&mut rng);
+        let any_public_key = AnyPublicKey::Ed25519 {
+            public_key: private_key.public_key(),
+        };
+        let raw_txn: RawTransaction = bcs::from_bytes(RAW_TXN_BCS_BYTES).unwrap();
+
+        let paar = PartialAuthenticatorAssertionResponse::ed25519_for_testing(
+            &private_key,
+            &raw_txn,
+            "webauthn.get",
+            AuthenticatorData::FLAG_USER_PRESENT,
+        );
+        assert_eq!(paar.signature().cose_algorithm(), -8);
+        paar.verify(&raw_txn, &any_public_key).unwrap();
+
+        // An Ed25519 assertion must not verify against a Secp256r1 key
+        let cose_key = parse_cose_key_from_att_obj(ATTESTATION_OBJECT).unwrap();
+        let secp256r1_public_key = AnyPublicKey::Secp256r1Ecdsa {
+            public_key: generate_secp256r1_public_key_from_cose_key(&cose_key).unwrap(),
+        };
+        assert!(paar.verify(&raw_txn, &secp256r1_public_key).is_err());
+
+        paar.verify_assertion_ceremony().unwrap();
+    }
+
+    #[test]
+    fn verify_assertion_ceremony() {
+        let mut rng: StdRng = SeedableRng::from_seed([0; 32]);
+        let private_key: Ed25519PrivateKey = Uniform::generate(&mut rng);
+        let any_public_key = AnyPublicKey::Ed25519 {
+            public_key: private_key.public_key(),
+        };
+        let raw_txn: RawTransaction = bcs::from_bytes(RAW_TXN_BCS_BYTES).unwrap();
+
+        // The ceremony is not part of the signature check, so these still verify
+        let paar = PartialAuthenticatorAssertionResponse::ed25519_for_testing(
+            &private_key,
+            &raw_txn,
+            "webauthn.get",
+            0,
+        );
+        paar.verify(&raw_txn, &any_public_key).unwrap();
+        assert!(paar.verify_assertion_ceremony().is_err());
+
+        let paar = PartialAuthenticatorAssertionResponse::ed25519_for_testing(
+            &private_key,
+            &raw_txn,
+            "webauthn.create",
+            AuthenticatorData::FLAG_USER_PRESENT,
+        );
+        paar.verify(&raw_txn, &any_public_key).unwrap();
+        assert!(paar.verify_assertion_ceremony().is_err());
+    }
 }
@@ -5,7 +5,7 @@
 #![allow(clippy::arc_with_non_send_sync)]
 
 use crate::{
-    epoch_change::{EpochChangeProof, Verifier},
+    epoch_change::{EpochChangeProof, VerifiedEpochCache, Verifier},
     epoch_state::EpochState,
     ledger_info::{LedgerInfo, LedgerInfoWithSignatures},
     proof::TransactionAccumulatorSummary,
@@ -145,10 +145,33 @@ impl TrustedState {
         )
     }
 
+    /// Same as [`TrustedState::verify_and_ratchet`], but reuses (and extends) the epoch ending
+    /// ledger infos already verified in `cache`, see [`VerifiedEpochCache`].
+    pub fn verify_and_ratchet_with_cache<'a>(
+        &self,
+        state_proof: &'a StateProof,
+        cache: &VerifiedEpochCache,
+    ) -> Result<TrustedStateChange<'a>> {
+        self.verify_and_ratchet_impl(
+            state_proof.latest_ledger_info_w_sigs(),
+            state_proof.epoch_changes(),
+            Some(cache),
+        )
+    }
+
     pub fn verify_and_ratchet_inner<'a>(
         &self,
         latest_li: &'a LedgerInfoWithSignatures,
         epoch_change_proof: &'a EpochChangeProof,
+    ) -> Result<TrustedStateChange<'a>> {
+        self.verify_and_ratchet_impl(latest_li, epoch_change_proof, None)
+    }
+
+    fn verify_and_ratchet_impl<'a>(
+        &self,
+        latest_li: &'a LedgerInfoWithSignatures,
+        epoch_change_proof: &'a EpochChangeProof,
+        cache: Option<&VerifiedEpochCache>,
     ) -> Result<TrustedStateChange<'a>> {
         // Abort early if the response is stale.
         let curr_version = self.version();
@@ -161,7 +184,10 @@ impl TrustedState {
 
         if self.epoch_change_verification_required(latest_li.ledger_info().next_block_epoch()) {
             // Verify the EpochChangeProof to move us into the latest epoch.
-            let epoch_change_li = epoch_change_proof.verify(self)?;
+            let epoch_change_li = match cache {
+                Some(cache) => epoch_change_proof.verify_with_cache(self, cache)?,
+                None => epoch_change_proof.verify(self)?,
+            };
             let new_epoch_state = epoch_change_li
                 .ledger_info()
                 .next_epoch_state()
@@ -242,6 +268,15 @@ impl Verifier for TrustedState {
         }
     }
 
+    fn verify_follows(&self, ledger_info: &LedgerInfo) -> Result<()> {
+        match self {
+            Self::EpochWaypoint(waypoint) => Verifier::verify_follows(waypoint, ledger_info),
+            Self::EpochState { epoch_state, .. } => {
+                Verifier::verify_follows(epoch_state, ledger_info)
+            },
+        }
+    }
+
     fn epoch_change_verification_required(&self, epoch: u64) -> bool {
         match self {
             Self::EpochWaypoint(waypoint) => {
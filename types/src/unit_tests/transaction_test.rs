@@ -6,9 +6,12 @@ use crate::{
     account_address::AccountAddress,
     chain_id::ChainId,
     transaction::{
-        AccountOrderedTransactionsWithProof, RawTransaction, Script, SignedTransaction,
-        Transaction, TransactionInfo, TransactionListWithProof, TransactionPayload,
-        TransactionWithProof,
+        signature_verified_transaction::{is_signature_verified, verify_user_txn_signatures},
+        AccountOrderedTransactionsWithProof, AuxiliaryRecordKind, FeeBreakdownRecord,
+        KeylessVerificationStatsRecord, RawTransaction, Script, SignedTransaction,
+        StateBytesDeltaRecord, Transaction, TransactionAuxiliaryData, TransactionAuxiliaryDataV1,
+        TransactionInfo, TransactionListWithProof, TransactionPayload, TransactionWithProof,
+        VMErrorDetail,
     },
 };
 use aptos_crypto::{
@@ -16,6 +19,7 @@ use aptos_crypto::{
     PrivateKey, Uniform,
 };
 use bcs::test_helpers::assert_canonical_encode_decode;
+use move_core_types::vm_status::StatusCode;
 use proptest::prelude::*;
 use std::convert::TryFrom;
 
@@ -40,6 +44,44 @@ fn test_invalid_signature() {
     )
 }
 
+#[test]
+fn test_verify_user_txn_signatures() {
+    let raw_txn = RawTransaction::new_script(
+        AccountAddress::random(),
+        0,
+        Script::new(vec![], vec![], vec![]),
+        0,
+        0,
+        0,
+        ChainId::test(),
+    );
+    let private_key = Ed25519PrivateKey::generate_for_testing();
+    let valid_txn = raw_txn
+        .clone()
+        .sign(&private_key, private_key.public_key())
+        .unwrap()
+        .into_inner();
+    let invalid_txn = SignedTransaction::new(
+        raw_txn,
+        private_key.public_key(),
+        Ed25519Signature::try_from(&[1u8; 64][..]).unwrap(),
+    );
+
+    // Duplicates are verified once, and only valid signatures are cached
+    let verified_txns = verify_user_txn_signatures(
+        vec![valid_txn.clone(), invalid_txn.clone(), valid_txn.clone()],
+        1,
+    );
+    let validity: Vec<_> = verified_txns.iter().map(|txn| txn.is_valid()).collect();
+    assert_eq!(validity, vec![true, false, true]);
+    assert!(is_signature_verified(&valid_txn.committed_hash()));
+    assert!(!is_signature_verified(&invalid_txn.committed_hash()));
+
+    // Cached transactions are still reported as valid
+    let verified_txns = verify_user_txn_signatures(vec![valid_txn], 1);
+    assert!(verified_txns[0].is_valid());
+}
+
 proptest! {
     #[test]
     fn test_sign_raw_transaction(raw_txn in any::<RawTransaction>(), keypair in ed25519::keypair_strategy()) {
@@ -92,3 +134,65 @@ proptest! {
         assert_canonical_encode_decode(acct_txns_with_proof);
     }
 }
+
+#[test]
+fn test_auxiliary_records() {
+    let error = VMErrorDetail::new(StatusCode::OUT_OF_GAS, Some("out of gas".to_string()));
+    let mut aux_data = TransactionAuxiliaryData::V1(TransactionAuxiliaryDataV1 {
+        detail_error_message: Some(error.clone()),
+    });
+    assert!(aux_data.records().is_empty());
+
+    let stats = KeylessVerificationStatsRecord {
+        num_zk_signatures: 1,
+        ..Default::default()
+    };
+    let fees = FeeBreakdownRecord {
+        gas_unit_price: 100,
+        execution_gas_units: 3,
+        io_gas_units: 2,
+        storage_fee_octas: 500,
+        storage_fee_refund_octas: 0,
+        total_charge_octas: 1000,
+    };
+    let state_bytes_delta = StateBytesDeltaRecord {
+        items_delta: -1,
+        bytes_delta: -100,
+    };
+    aux_data.add_record(&state_bytes_delta).unwrap();
+    aux_data.add_record(&stats).unwrap();
+    aux_data.add_record(&fees).unwrap();
+    // Upgrading to V2 keeps the error detail.
+    assert_eq!(aux_data.get_detail_error_message(), Some(&error));
+
+    // Records are sorted by kind, regardless of the order they were added in.
+    let kinds: Vec<_> = aux_data.records().iter().map(|r| r.kind()).collect();
+    assert_eq!(kinds, AuxiliaryRecordKind::ALL.to_vec());
+
+    // Adding a record of the same kind replaces it.
+    let stats = KeylessVerificationStatsRecord {
+        num_openid_signatures: 1,
+        ..Default::default()
+    };
+    aux_data.add_record(&stats).unwrap();
+    assert_eq!(aux_data.records().len(), 3);
+    assert_eq!(
+        aux_data
+            .get_record::<KeylessVerificationStatsRecord>()
+            .unwrap(),
+        Some(stats)
+    );
+
+    let decoded: TransactionAuxiliaryData =
+        bcs::from_bytes(&bcs::to_bytes(&aux_data).unwrap()).unwrap();
+    assert_eq!(decoded, aux_data);
+    assert_eq!(
+        decoded.get_record::<FeeBreakdownRecord>().unwrap(),
+        Some(fees)
+    );
+    assert_eq!(
+        decoded.records()[0].to_json().unwrap()["gas_unit_price"],
+        100
+    );
+    assert_eq!(decoded.records()[2].to_json().unwrap()["bytes_delta"], -100);
+}
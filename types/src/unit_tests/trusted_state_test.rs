@@ -5,10 +5,11 @@
 use crate::{
     aggregate_signature::{AggregateSignature, PartialSignatures},
     block_info::BlockInfo,
-    epoch_change::EpochChangeProof,
+    epoch_change::{EpochChangeProof, VerifiedEpochCache},
     epoch_state::EpochState,
     ledger_info::{LedgerInfo, LedgerInfoWithSignatures},
     proof::accumulator::mock::MockTransactionAccumulator,
+    state_proof::StateProof,
     transaction::Version,
     trusted_state::{TrustedState, TrustedStateChange, TrustedStateHasher},
     validator_signer::ValidatorSigner,
@@ -443,6 +444,42 @@ proptest! {
             .expect_err("Should always return Err with an invalid change proof");
     }
 
+    #[test]
+    fn test_ratchet_with_verified_epoch_cache(
+        (_vsets, lis_with_sigs, latest_li, _) in arb_update_proof(
+            10,   /* start epoch */
+            123,  /* start version */
+            1..3, /* version delta */
+            2..5, /* epoch changes */
+            1..5, /* validators per epoch */
+        )
+    ) {
+        let waypoint = Waypoint::new_epoch_boundary(lis_with_sigs[0].ledger_info()).unwrap();
+        let trusted_state = TrustedState::from_epoch_waypoint(waypoint);
+        let cache = VerifiedEpochCache::new();
+
+        let change_proof = EpochChangeProof::new(lis_with_sigs.clone(), false /* more */);
+        let change = change_proof.verify_with_cache(&trusted_state, &cache).unwrap();
+        prop_assert_eq!(cache.len(), lis_with_sigs.len());
+        prop_assert_eq!(cache.latest_epoch(), Some(change.ledger_info().epoch()));
+
+        // Strip the signatures of the already verified ledger infos, which are then only
+        // accepted through the cache.
+        let unsigned_lis = lis_with_sigs
+            .iter()
+            .map(|li| LedgerInfoWithSignatures::new(li.ledger_info().clone(), AggregateSignature::empty()))
+            .collect::<Vec<_>>();
+        let unsigned_proof = EpochChangeProof::new(unsigned_lis, false /* more */);
+        trusted_state
+            .verify_and_ratchet_inner(&latest_li, &unsigned_proof)
+            .expect_err("Should fail without the cache");
+        let state_proof = StateProof::new(latest_li, unsigned_proof);
+        let trusted_state_change = trusted_state
+            .verify_and_ratchet_with_cache(&state_proof, &cache)
+            .expect("Cached epoch ending ledger infos should not be verified again");
+        prop_assert!(trusted_state_change.is_epoch_change());
+    }
+
     #[test]
     fn test_ratchet_fails_with_invalid_latest_li(
         (_vsets, mut lis_with_sigs, latest_li, accumulator) in arb_update_proof(
@@ -2,8 +2,13 @@
 // Parts of the project are originally copyright © Meta Platforms, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::write_set::WriteSet;
+use crate::{
+    state_store::state_key::{prefix::StateKeyPrefix, StateKey},
+    write_set::{WriteOp, WriteSet, WriteSetBuilder, WriteSetConflictPolicy},
+};
 use bcs::test_helpers::assert_canonical_encode_decode;
+use bytes::Bytes;
+use move_core_types::{account_address::AccountAddress, identifier::Identifier};
 use proptest::prelude::*;
 
 proptest! {
@@ -12,3 +17,123 @@ proptest! {
         assert_canonical_encode_decode(write_set);
     }
 }
+
+fn key(address: AccountAddress, name: &str) -> StateKey {
+    StateKey::module(&address, &Identifier::new(name).unwrap())
+}
+
+fn value(byte: u8) -> Bytes {
+    vec![byte].into()
+}
+
+#[test]
+fn test_write_set_builder_merge() {
+    let base = WriteSetBuilder::new()
+        .modification(key(AccountAddress::ONE, "a"), value(1))
+        .creation(key(AccountAddress::ONE, "b"), value(2));
+    let other = WriteSetBuilder::new()
+        .modification(key(AccountAddress::ONE, "b"), value(3))
+        .deletion(key(AccountAddress::TWO, "c"))
+        .build()
+        .unwrap();
+
+    assert!(base
+        .clone()
+        .merge(
+            other.clone().into_write_op_iter(),
+            WriteSetConflictPolicy::Reject
+        )
+        .is_err());
+
+    let kept = base
+        .clone()
+        .merge(
+            other.clone().into_write_op_iter(),
+            WriteSetConflictPolicy::KeepExisting,
+        )
+        .unwrap()
+        .build()
+        .unwrap();
+    assert_eq!(
+        kept.get_write_op(&key(AccountAddress::ONE, "b")),
+        Some(&WriteOp::legacy_creation(value(2)))
+    );
+    assert_eq!(kept.write_op_iter().count(), 3);
+
+    let overwritten = base
+        .clone()
+        .merge(
+            other.clone().into_write_op_iter(),
+            WriteSetConflictPolicy::Overwrite,
+        )
+        .unwrap()
+        .build()
+        .unwrap();
+    assert_eq!(
+        overwritten.get_write_op(&key(AccountAddress::ONE, "b")),
+        Some(&WriteOp::legacy_modification(value(3)))
+    );
+
+    // Creation followed by modification squashes into a creation of the new value.
+    let squashed = base
+        .merge(other.into_write_op_iter(), WriteSetConflictPolicy::Squash)
+        .unwrap()
+        .build()
+        .unwrap();
+    assert_eq!(
+        squashed.get_write_op(&key(AccountAddress::ONE, "b")),
+        Some(&WriteOp::legacy_creation(value(3)))
+    );
+}
+
+#[test]
+fn test_write_set_builder_prefix_filter() {
+    let builder = WriteSetBuilder::new()
+        .modification(key(AccountAddress::ONE, "a"), value(1))
+        .modification(key(AccountAddress::ONE, "b"), value(2))
+        .modification(key(AccountAddress::TWO, "a"), value(3))
+        .modification(StateKey::raw(&[1, 2, 3]), value(4));
+    let prefix = StateKeyPrefix::from(AccountAddress::ONE);
+
+    let retained = builder.clone().retain_prefix(&prefix).unwrap();
+    assert_eq!(retained.len(), 2);
+    let removed = builder.remove_prefix(&prefix).unwrap().build().unwrap();
+    assert_eq!(removed.write_op_iter().count(), 2);
+    assert!(removed
+        .get_write_op(&key(AccountAddress::TWO, "a"))
+        .is_some());
+}
+
+#[test]
+fn test_write_set_diff() {
+    let left = WriteSetBuilder::new()
+        .modification(key(AccountAddress::ONE, "same"), value(1))
+        .modification(key(AccountAddress::ONE, "changed"), value(2))
+        .deletion(key(AccountAddress::ONE, "removed"))
+        .build()
+        .unwrap();
+    let right = WriteSetBuilder::new()
+        .modification(key(AccountAddress::ONE, "same"), value(1))
+        .modification(key(AccountAddress::ONE, "changed"), value(3))
+        .modification(key(AccountAddress::ONE, "added"), value(4))
+        .build()
+        .unwrap();
+
+    let diff = left.diff(&right);
+    assert_eq!(diff.added.keys().collect::<Vec<_>>(), vec![&key(
+        AccountAddress::ONE,
+        "added"
+    )]);
+    assert_eq!(diff.removed.keys().collect::<Vec<_>>(), vec![&key(
+        AccountAddress::ONE,
+        "removed"
+    )]);
+    assert_eq!(
+        diff.changed.get(&key(AccountAddress::ONE, "changed")),
+        Some(&(
+            WriteOp::legacy_modification(value(2)),
+            WriteOp::legacy_modification(value(3))
+        ))
+    );
+    assert!(left.diff(&left).is_empty());
+}
@@ -29,6 +29,9 @@ use std::{
 };
 use thiserror::Error;
 
+/// The number of signatures verified in each parallel task, in one batch if they are all valid.
+const SIGNATURE_VERIFICATION_BATCH_SIZE: usize = 8;
+
 /// Errors possible during signature verification.
 #[derive(Debug, Error, PartialEq, Eq)]
 pub enum VerifyError {
@@ -290,27 +293,62 @@ impl ValidatorVerifier {
         message: &T,
         signatures: BTreeMap<AccountAddress, SignatureWithStatus>,
     ) -> BTreeMap<AccountAddress, SignatureWithStatus> {
+        let signing_message = aptos_crypto::signing_message(message).ok();
         signatures
             .into_iter()
             .collect_vec()
             .into_par_iter()
-            .with_min_len(4) // At least 4 signatures are verified in each task
-            .filter_map(|(account_address, signature)| {
-                if signature.is_verified()
-                    || self
-                        .verify(account_address, message, signature.signature())
-                        .is_ok()
-                {
-                    signature.set_verified();
-                    Some((account_address, signature))
-                } else {
-                    self.add_pessimistic_verify_set(account_address);
-                    None
-                }
+            // The signatures of each task are verified in a single batch first, and one by one
+            // only if the batch fails.
+            .chunks(SIGNATURE_VERIFICATION_BATCH_SIZE)
+            .flat_map_iter(|batch| {
+                let batch_verified = signing_message
+                    .as_ref()
+                    .is_some_and(|signing_message| self.verify_batch(signing_message, &batch));
+                batch
+                    .into_iter()
+                    .filter_map(move |(account_address, signature)| {
+                        if batch_verified
+                            || signature.is_verified()
+                            || self
+                                .verify(account_address, message, signature.signature())
+                                .is_ok()
+                        {
+                            signature.set_verified();
+                            Some((account_address, signature))
+                        } else {
+                            self.add_pessimistic_verify_set(account_address);
+                            None
+                        }
+                    })
             })
             .collect()
     }
 
+    /// Returns whether all the unverified signatures in `batch` are valid signatures on
+    /// `signing_message` by their authors.
+    fn verify_batch(
+        &self,
+        signing_message: &[u8],
+        batch: &[(AccountAddress, SignatureWithStatus)],
+    ) -> bool {
+        let mut public_keys = vec![];
+        let mut signatures = vec![];
+        for (author, signature) in batch.iter().filter(|(_, sig)| !sig.is_verified()) {
+            match self.get_public_key(author) {
+                Some(public_key) => public_keys.push(public_key),
+                None => return false,
+            }
+            signatures.push(signature.signature());
+        }
+        bls12381::verify_aggregate_signatures_batch(
+            &vec![signing_message; signatures.len()],
+            &public_keys.iter().collect_vec(),
+            &signatures,
+        )
+        .is_ok()
+    }
+
     // Generates a multi signature or aggregate signature
     // from partial signatures as well as returns the aggregated pub key along with
     // list of pub keys used in signature aggregation.
@@ -348,6 +386,56 @@ impl ValidatorVerifier {
         message: &T,
         multi_signature: &AggregateSignature,
     ) -> std::result::Result<(), VerifyError> {
+        let Some((aggregated_key, multi_sig)) =
+            self.multi_signature_verification_inputs(multi_signature)?
+        else {
+            return Ok(());
+        };
+        // Verify the optimistically aggregated signature.
+        multi_sig
+            .verify(message, &aggregated_key)
+            .map_err(|_| VerifyError::InvalidMultiSignature)?;
+        Ok(())
+    }
+
+    /// Verifies multisignatures from several validator sets, e.g. the ledger infos of an epoch
+    /// change proof, in a single randomized batch. Each element of `batch` is a message, its
+    /// multisignature, and the validator set that signed it.
+    ///
+    /// The batch is accepted iff `verify_multi_signatures` accepts every element.
+    pub fn verify_multi_signatures_batch<T: CryptoHash + Serialize>(
+        batch: &[(&T, &AggregateSignature, &ValidatorVerifier)],
+    ) -> std::result::Result<(), VerifyError> {
+        let mut messages = vec![];
+        let mut aggregated_keys = vec![];
+        let mut multi_sigs = vec![];
+        for (message, multi_signature, verifier) in batch {
+            if let Some((aggregated_key, multi_sig)) =
+                verifier.multi_signature_verification_inputs(multi_signature)?
+            {
+                messages.push(
+                    aptos_crypto::signing_message(*message)
+                        .map_err(|_| VerifyError::InvalidMultiSignature)?,
+                );
+                aggregated_keys.push(aggregated_key);
+                multi_sigs.push(multi_sig);
+            }
+        }
+
+        bls12381::verify_aggregate_signatures_batch(
+            &messages.iter().map(Vec::as_slice).collect::<Vec<_>>(),
+            &aggregated_keys.iter().collect::<Vec<_>>(),
+            &multi_sigs,
+        )
+        .map_err(|_| VerifyError::InvalidMultiSignature)
+    }
+
+    /// Checks the signers and their voting power of `multi_signature`, and returns the aggregated
+    /// public key and the signature to verify, if any.
+    fn multi_signature_verification_inputs<'a>(
+        &self,
+        multi_signature: &'a AggregateSignature,
+    ) -> std::result::Result<Option<(PublicKey, &'a bls12381::Signature)>, VerifyError> {
         // Verify the number of signature is not greater than expected.
         Self::check_num_of_voters(self.len() as u16, multi_signature.get_signers_bitvec())?;
         let mut pub_keys = vec![];
@@ -368,7 +456,7 @@ impl ValidatorVerifier {
                 // This should happen only in case of tests.
                 // TODO(skedia): Clean up the test behaviors to not rely on empty signature
                 // verification
-                return Ok(());
+                return Ok(None);
             }
         }
         // Verify empty multi signature
@@ -376,14 +464,9 @@ impl ValidatorVerifier {
             .sig()
             .as_ref()
             .ok_or(VerifyError::EmptySignature)?;
-        // Verify the optimistically aggregated signature.
         let aggregated_key =
             PublicKey::aggregate(pub_keys).map_err(|_| VerifyError::FailedToAggregatePubKey)?;
-
-        multi_sig
-            .verify(message, &aggregated_key)
-            .map_err(|_| VerifyError::InvalidMultiSignature)?;
-        Ok(())
+        Ok(Some((aggregated_key, multi_sig)))
     }
 
     pub fn verify_aggregate_signatures<T: CryptoHash + Serialize>(
@@ -85,6 +85,10 @@ impl Verifier for Waypoint {
         self.verify(ledger_info.ledger_info())
     }
 
+    fn verify_follows(&self, ledger_info: &LedgerInfo) -> Result<()> {
+        self.verify(ledger_info)
+    }
+
     fn epoch_change_verification_required(&self, _epoch: u64) -> bool {
         true
     }
@@ -6,7 +6,7 @@
 //! path it updates. For each access path, the VM can either give its new value or delete it.
 
 use crate::state_store::{
-    state_key::StateKey,
+    state_key::{prefix::StateKeyPrefix, StateKey},
     state_slot::StateSlot,
     state_value::{PersistedStateValueMetadata, StateValue, StateValueMetadata},
 };
@@ -845,3 +845,172 @@ impl FromIterator<(StateKey, WriteOp)> for WriteSetMut {
         ws
     }
 }
+
+/// What to do when both write sets being merged write the same key.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum WriteSetConflictPolicy {
+    /// Fail the merge.
+    Reject,
+    /// Keep the op already in the builder.
+    KeepExisting,
+    /// Replace the op in the builder with the incoming one.
+    Overwrite,
+    /// Apply the incoming op on top of the existing one, see [`WriteOp::squash`].
+    Squash,
+}
+
+/// Builder for composing write sets, e.g. for genesis and db-bootstrapper write sets, or in
+/// tests, instead of manipulating the underlying map by hand.
+#[derive(Clone, Debug, Default)]
+pub struct WriteSetBuilder {
+    write_set: WriteSetMut,
+}
+
+impl WriteSetBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn from_write_set(write_set: WriteSet) -> Self {
+        Self {
+            write_set: write_set.into_mut(),
+        }
+    }
+
+    /// Adds `write_op`, replacing any previous op on the same key.
+    pub fn write_op(mut self, state_key: StateKey, write_op: WriteOp) -> Self {
+        self.write_set.insert((state_key, write_op));
+        self
+    }
+
+    pub fn modification(self, state_key: StateKey, bytes: Bytes) -> Self {
+        self.write_op(state_key, WriteOp::legacy_modification(bytes))
+    }
+
+    pub fn creation(self, state_key: StateKey, bytes: Bytes) -> Self {
+        self.write_op(state_key, WriteOp::legacy_creation(bytes))
+    }
+
+    pub fn deletion(self, state_key: StateKey) -> Self {
+        self.write_op(state_key, WriteOp::legacy_deletion())
+    }
+
+    /// Adds a modification of `state_key` to the BCS encoding of `value`.
+    pub fn modify_value<T: Serialize>(self, state_key: StateKey, value: &T) -> Result<Self> {
+        Ok(self.modification(state_key, bcs::to_bytes(value)?.into()))
+    }
+
+    /// Merges `other` in, resolving keys written by both according to `policy`.
+    pub fn merge(
+        mut self,
+        other: impl IntoIterator<Item = (StateKey, WriteOp)>,
+        policy: WriteSetConflictPolicy,
+    ) -> Result<Self> {
+        use btree_map::Entry::*;
+
+        for (key, op) in other {
+            match self.write_set.as_inner_mut().entry(key) {
+                Vacant(entry) => {
+                    entry.insert(op);
+                },
+                Occupied(mut entry) => match policy {
+                    WriteSetConflictPolicy::Reject => {
+                        bail!("Conflicting writes to {:?}", entry.key())
+                    },
+                    WriteSetConflictPolicy::KeepExisting => {},
+                    WriteSetConflictPolicy::Overwrite => {
+                        entry.insert(op);
+                    },
+                    WriteSetConflictPolicy::Squash => {
+                        if !WriteOp::squash(entry.get_mut(), op)? {
+                            entry.remove();
+                        }
+                    },
+                },
+            }
+        }
+        Ok(self)
+    }
+
+    /// Keeps only the ops for which `predicate` returns true.
+    pub fn filter(mut self, mut predicate: impl FnMut(&StateKey, &WriteOp) -> bool) -> Self {
+        self.write_set
+            .as_inner_mut()
+            .retain(|key, op| predicate(key, op));
+        self
+    }
+
+    /// Keeps only the ops on keys starting with `prefix`, e.g. the ones of a single account.
+    pub fn retain_prefix(self, prefix: &StateKeyPrefix) -> Result<Self> {
+        self.filter_prefix(prefix, true)
+    }
+
+    /// Drops the ops on keys starting with `prefix`.
+    pub fn remove_prefix(self, prefix: &StateKeyPrefix) -> Result<Self> {
+        self.filter_prefix(prefix, false)
+    }
+
+    fn filter_prefix(mut self, prefix: &StateKeyPrefix, keep_matching: bool) -> Result<Self> {
+        let encoded_prefix = prefix.encode()?;
+        self.write_set
+            .as_inner_mut()
+            .retain(|key, _| key.encoded().starts_with(&encoded_prefix) == keep_matching);
+        Ok(self)
+    }
+
+    pub fn len(&self) -> usize {
+        self.write_set.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.write_set.is_empty()
+    }
+
+    pub fn build(self) -> Result<WriteSet> {
+        self.write_set.freeze()
+    }
+}
+
+/// Per key differences between two write sets, as returned by [`WriteSet::diff`].
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct WriteSetDiff {
+    /// Keys only written by the right hand side.
+    pub added: BTreeMap<StateKey, WriteOp>,
+    /// Keys only written by the left hand side.
+    pub removed: BTreeMap<StateKey, WriteOp>,
+    /// Keys written by both, with different ops, as (left, right).
+    pub changed: BTreeMap<StateKey, (WriteOp, WriteOp)>,
+}
+
+impl WriteSetDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+impl WriteSet {
+    /// Compares the value writes of the two write sets, key by key.
+    pub fn diff(&self, other: &WriteSet) -> WriteSetDiff {
+        let mut diff = WriteSetDiff::default();
+        for entry in self
+            .write_op_iter()
+            .merge_join_by(other.write_op_iter(), |a, b| a.0.cmp(b.0))
+        {
+            match entry {
+                EitherOrBoth::Left((key, op)) => {
+                    diff.removed.insert(key.clone(), op.clone());
+                },
+                EitherOrBoth::Right((key, op)) => {
+                    diff.added.insert(key.clone(), op.clone());
+                },
+                EitherOrBoth::Both((key, left), (_, right)) => {
+                    if left != right {
+                        diff.changed
+                            .insert(key.clone(), (left.clone(), right.clone()));
+                    }
+                },
+            }
+        }
+        diff
+    }
+}